@@ -0,0 +1,259 @@
+// The read-only interface of a running Citybound server, for companion apps
+// like map viewers, dashboards or bots that play test games. It doesn't depend
+// on the engine, so these apps only link this small crate.
+//
+// A server started with `--api <host:port>` accepts any number of TCP
+// connections there. Each line sent to it is one JSON encoded `Request`,
+// each line it sends back is the `Response` to one of them. Requests are
+// answered after the simulation turn they arrived in, usually in the order
+// they were sent, which is why responses carry the id of their request.
+
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Query {
+    // the statistics of the most recent timeline snapshot
+    Stats,
+    // a page of all households, ordered by their id
+    Households { offset: u32, limit: u32 },
+    // the newest simulation log entries, at most `max` of them and none
+    // before the one numbered `from`, oldest first
+    Events { from: u32, max: u32 },
+    // where trips of the last full day went, and how long they took
+    TripStats,
+    // the metrics of every complete day since the one numbered `from_day`
+    Metrics { from_day: u32 },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Request {
+    pub id: u64,
+    pub query: Query,
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CityStats {
+    // simulation ticks since the city was founded, 3 per simulated second
+    pub ticks: u64,
+    pub population: u32,
+    pub births: u32,
+    pub deaths: u32,
+    pub n_jobs: u32,
+    pub n_commuters: u32,
+    pub average_commute_minutes: f32,
+    pub n_buildings: u32,
+    pub built_area: f32,
+    pub network_length: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HouseholdSummary {
+    pub id: String,
+    pub name: String,
+    pub n_members: u32,
+    pub money: f32,
+    // averaged over members, from 0 (miserable) to 1
+    pub happiness: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventLevel {
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Event {
+    // pass the number of the newest one plus one as `from` to only get newer ones
+    pub number: u32,
+    pub level: EventLevel,
+    pub topic: String,
+    pub message: String,
+}
+
+// All trips of one mode between two zones that left in the same part of the day.
+// Zones are squares of 1km, counted from the center of the map
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OriginDestination {
+    pub origin: (i32, i32),
+    pub destination: (i32, i32),
+    // the part of the day the trips left in, counted in 3 hour slices from midnight
+    pub slice: u8,
+    pub mode: String,
+    pub n_trips: u32,
+    pub average_minutes: f32,
+}
+
+// How long trips of one mode were expected to take, compared to how long they took
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TravelTimes {
+    pub mode: String,
+    pub n_trips: u32,
+    pub average_planned_minutes: f32,
+    pub average_actual_minutes: f32,
+}
+
+// What the city looked like on one day, collected at its start
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DayMetrics {
+    // days since the city was founded
+    pub day: u32,
+    pub population: u32,
+    // of the inhabitants of working age, from 0 to 1
+    pub employment_rate: f32,
+    pub average_commute_minutes: f32,
+    pub treasury_balance: f32,
+    // average asking price per unit of each resource on sale
+    pub prices: Vec<(String, f32)>,
+    // how many trips of each mode were made the day before
+    pub trips: Vec<(String, u32)>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Answer {
+    Stats(CityStats),
+    Households {
+        total: u32,
+        households: Vec<HouseholdSummary>,
+    },
+    Events(Vec<Event>),
+    TripStats {
+        od_matrix: Vec<OriginDestination>,
+        travel_times: Vec<TravelTimes>,
+    },
+    Metrics(Vec<DayMetrics>),
+    Error(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Response {
+    pub request: u64,
+    pub answer: Answer,
+}
+
+pub fn encode<T: serde::Serialize>(message: &T) -> String {
+    let mut line = serde_json::to_string(message).expect("API messages are always encodable");
+    line.push('\n');
+    line
+}
+
+pub fn decode<'a, T: serde::Deserialize<'a>>(line: &'a str) -> Option<T> {
+    serde_json::from_str(line.trim()).ok()
+}
+
+// One row per day, with a column for the price of every resource
+// and the trips of every mode that show up on any of the days
+pub fn metrics_csv(days: &[DayMetrics]) -> String {
+    let mut resources = Vec::<&str>::new();
+    let mut modes = Vec::<&str>::new();
+    for day in days {
+        for (resource, _) in &day.prices {
+            if !resources.contains(&resource.as_str()) {
+                resources.push(resource);
+            }
+        }
+        for (mode, _) in &day.trips {
+            if !modes.contains(&mode.as_str()) {
+                modes.push(mode);
+            }
+        }
+    }
+
+    let mut csv = "day,population,employment_rate,average_commute_minutes,treasury_balance"
+        .to_owned();
+    for resource in &resources {
+        csv.push_str(&format!(",price_{}", resource));
+    }
+    for mode in &modes {
+        csv.push_str(&format!(",trips_{}", mode));
+    }
+    csv.push('\n');
+
+    for day in days {
+        csv.push_str(&format!(
+            "{},{},{},{},{}",
+            day.day,
+            day.population,
+            day.employment_rate,
+            day.average_commute_minutes,
+            day.treasury_balance
+        ));
+        for resource in &resources {
+            csv.push(',');
+            let maybe_price = day
+                .prices
+                .iter()
+                .find(|(other, _)| other.as_str() == *resource);
+            if let Some((_, price)) = maybe_price {
+                csv.push_str(&price.to_string());
+            }
+        }
+        for mode in &modes {
+            let n_trips = day
+                .trips
+                .iter()
+                .find(|(other, _)| other.as_str() == *mode)
+                .map(|&(_, n_trips)| n_trips)
+                .unwrap_or(0);
+            csv.push_str(&format!(",{}", n_trips));
+        }
+        csv.push('\n');
+    }
+
+    csv
+}
+
+// A blocking connection to a server, asking one query at a time
+pub struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    next_request: u64,
+}
+
+impl Client {
+    pub fn connect<A: ToSocketAddrs>(address: A) -> io::Result<Client> {
+        let writer = TcpStream::connect(address)?;
+        let reader = BufReader::new(writer.try_clone()?);
+        Ok(Client {
+            reader,
+            writer,
+            next_request: 0,
+        })
+    }
+
+    pub fn query(&mut self, query: Query) -> io::Result<Answer> {
+        let id = self.next_request;
+        self.next_request += 1;
+        self.writer
+            .write_all(encode(&Request { id, query }).as_bytes())?;
+
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::ConnectionAborted,
+                    "closed by the server",
+                ));
+            }
+            match decode::<Response>(&line) {
+                Some(ref response) if response.request == id => return Ok(response.answer.clone()),
+                // answers to earlier queries that were given up on
+                Some(_) => {}
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid response {:?}", line),
+                    ))
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+import React from 'react';
+
+export const settingsSpec = {
+    streetNames: { default: true, description: "Street Names" },
+    districtNames: { default: true, description: "District Names" },
+    buildingLabels: { default: true, description: "Building Labels" },
+    maxLabels: { default: 150, description: "Max. Labels on Screen", min: 20, max: 500, step: 10 },
+};
+
+// camera distances at which each kind of annotation is legible and useful.
+// Kinds with higher priority are placed first and win collisions,
+// labels that don't collide are never hidden by other labels
+const KINDS = {
+    selection: { setting: null, minDistance: 0, maxDistance: Infinity, priority: 5, collides: false, charWidth: 7, lineHeight: 13 },
+    incident: { setting: null, minDistance: 0, maxDistance: 3000, priority: 4, collides: false, charWidth: 7, lineHeight: 13 },
+    debug: { setting: null, minDistance: 0, maxDistance: Infinity, priority: 4, collides: false, charWidth: 7, lineHeight: 13 },
+    district: { setting: "districtNames", minDistance: 600, maxDistance: Infinity, priority: 3, collides: true, charWidth: 11, lineHeight: 22 },
+    street: { setting: "streetNames", minDistance: 0, maxDistance: 1500, priority: 2, collides: true, charWidth: 6, lineHeight: 12 },
+    building: { setting: "buildingLabels", minDistance: 0, maxDistance: 350, priority: 1, collides: true, charWidth: 6, lineHeight: 12 },
+};
+
+const SCREEN_MARGIN = 50;
+const COLLISION_CELL_SIZE = 100;
+// the same text shown closer than this (in pixels) is redundant,
+// like the name of a street on each of its lanes
+const DUPLICATE_DISTANCE = 250;
+
+export const AnnotationContext = React.createContext("annotations");
+
+// Adds a batch of annotations of one kind, each being
+// { key, position: [x, y], text, direction?: [dx, dy] }.
+// With a direction, the text is laid out along it, like a street name along a road
+export function AnnotationBatch(props) {
+    return <AnnotationContext.Consumer>
+        {batches => {
+            batches.push(props);
+            return null;
+        }}
+    </AnnotationContext.Consumer>
+}
+
+// Turns a state subtree into annotations only when the subtree actually changed
+export function memoizedAnnotations(compute) {
+    let lastSource = null;
+    let lastAnnotations = [];
+
+    return source => {
+        if (source !== lastSource) {
+            lastSource = source;
+            lastAnnotations = compute(source);
+        }
+        return lastAnnotations;
+    };
+}
+
+function readableAngle(angle) {
+    if (angle > Math.PI / 2) {
+        return angle - Math.PI;
+    } else if (angle < -Math.PI / 2) {
+        return angle + Math.PI;
+    } else {
+        return angle;
+    }
+}
+
+class CollisionGrid {
+    constructor() {
+        this.cells = new Map();
+    }
+
+    cellKeys(box) {
+        const keys = [];
+        for (let cx = Math.floor(box.minX / COLLISION_CELL_SIZE); cx <= Math.floor(box.maxX / COLLISION_CELL_SIZE); cx++) {
+            for (let cy = Math.floor(box.minY / COLLISION_CELL_SIZE); cy <= Math.floor(box.maxY / COLLISION_CELL_SIZE); cy++) {
+                keys.push(cx + ":" + cy);
+            }
+        }
+        return keys;
+    }
+
+    collides(box) {
+        return this.cellKeys(box).some(key => (this.cells.get(key) || []).some(other =>
+            box.minX < other.maxX && box.maxX > other.minX && box.minY < other.maxY && box.maxY > other.minY
+        ));
+    }
+
+    insert(box) {
+        for (let key of this.cellKeys(box)) {
+            if (!this.cells.has(key)) {
+                this.cells.set(key, []);
+            }
+            this.cells.get(key).push(box);
+        }
+    }
+}
+
+function placeAnnotations(batches, settings, cameraDistance, project3dTo2d, width, height) {
+    const candidates = [];
+
+    for (let batch of batches) {
+        const kind = KINDS[batch.kind];
+        const enabled = !kind.setting || settings[kind.setting];
+        if (!enabled || cameraDistance < kind.minDistance || cameraDistance > kind.maxDistance) {
+            continue;
+        }
+
+        for (let annotation of batch.annotations) {
+            const [x, y] = project3dTo2d([...annotation.position, 0.0]);
+            if (x < -SCREEN_MARGIN || y < -SCREEN_MARGIN || x > width + SCREEN_MARGIN || y > height + SCREEN_MARGIN) {
+                continue;
+            }
+
+            let angle = 0;
+            if (annotation.direction) {
+                const [ax, ay] = project3dTo2d([
+                    annotation.position[0] + annotation.direction[0],
+                    annotation.position[1] + annotation.direction[1],
+                    0.0
+                ]);
+                angle = readableAngle(Math.atan2(ay - y, ax - x));
+            }
+
+            candidates.push({ annotation, kindName: batch.kind, kind, x, y, angle });
+        }
+    }
+
+    candidates.sort((a, b) => b.kind.priority - a.kind.priority);
+
+    const grid = new CollisionGrid();
+    const shownTexts = new Map();
+    const placed = [];
+    let nCollidingPlaced = 0;
+
+    for (let candidate of candidates) {
+        const { annotation, kind, x, y, angle } = candidate;
+
+        if (kind.collides) {
+            if (nCollidingPlaced >= settings.maxLabels) {
+                continue;
+            }
+
+            const sameText = shownTexts.get(annotation.text) || [];
+            if (sameText.some(([ox, oy]) => Math.hypot(ox - x, oy - y) < DUPLICATE_DISTANCE)) {
+                continue;
+            }
+
+            // rotated labels are approximated by their axis-aligned bounding box
+            const lines = annotation.text.split("\n");
+            const halfWidth = 0.5 * kind.charWidth * Math.max(...lines.map(line => line.length));
+            const halfHeight = 0.5 * kind.lineHeight * lines.length;
+            const cos = Math.abs(Math.cos(angle));
+            const sin = Math.abs(Math.sin(angle));
+            const extentX = cos * halfWidth + sin * halfHeight;
+            const extentY = sin * halfWidth + cos * halfHeight;
+            const box = { minX: x - extentX, maxX: x + extentX, minY: y - extentY, maxY: y + extentY };
+
+            if (grid.collides(box)) {
+                continue;
+            }
+
+            grid.insert(box);
+            shownTexts.set(annotation.text, [...sameText, [x, y]]);
+            nCollidingPlaced++;
+        }
+
+        placed.push(candidate);
+    }
+
+    return placed;
+}
+
+export function Overlay(props) {
+    const { batches, state, project3dTo2d, width, height } = props;
+
+    const placed = placeAnnotations(
+        batches,
+        state.settings.annotations,
+        state.camera.distance,
+        project3dTo2d,
+        width,
+        height
+    );
+
+    return <div className="annotations">
+        {placed.map(({ annotation, kindName, x, y, angle }) =>
+            <div key={kindName + annotation.key} className={"annotation annotation-" + kindName}
+                style={{ transform: `translate(${x}px, ${y}px) translate(-50%, -50%) rotate(${angle}rad)` }}>
+                {annotation.text}
+            </div>
+        )}
+    </div>;
+}
@@ -0,0 +1,37 @@
+const STREET_NAMES = [
+    "Oak", "Maple", "Cedar", "Elm", "Pine", "Birch", "Willow", "Chestnut", "Walnut", "Ash",
+    "Mill", "Church", "Market", "Bridge", "Station", "Harbor", "Garden", "Park", "Lake", "River",
+    "Baker", "Miller", "Hill", "Meadow", "Orchard", "Spring", "Sunset", "Union", "Liberty", "Forest"
+];
+
+const STREET_SUFFIXES = ["Street", "Avenue", "Road", "Lane", "Boulevard", "Way", "Drive"];
+
+const DISTRICT_PREFIXES = ["North", "South", "East", "West", "Old", "New", "Upper", "Lower"];
+
+const DISTRICT_NAMES = [
+    "Brookside", "Greenfield", "Ashford", "Riverton", "Fairview", "Highgate", "Oakwood",
+    "Millbrook", "Stonebridge", "Kingsley", "Westmoor", "Elmhurst", "Redcliff", "Larkspur"
+];
+
+// cheap, stable integer hash so that the same inputs always give the same name
+function hash(...numbers) {
+    let h = 2166136261;
+    for (let n of numbers) {
+        h = Math.imul(h ^ (n | 0), 16777619);
+    }
+    return h >>> 0;
+}
+
+export function streetName(key) {
+    const h = hash(...key);
+    return STREET_NAMES[h % STREET_NAMES.length] + " "
+        + STREET_SUFFIXES[(h >>> 8) % STREET_SUFFIXES.length];
+}
+
+export function districtName(x, y) {
+    const h = hash(x, y);
+    const name = DISTRICT_NAMES[h % DISTRICT_NAMES.length];
+    return (h >>> 8) % 3 === 0
+        ? DISTRICT_PREFIXES[(h >>> 12) % DISTRICT_PREFIXES.length] + " " + name
+        : name;
+}
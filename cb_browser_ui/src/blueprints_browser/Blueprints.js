@@ -0,0 +1,268 @@
+import React from 'react';
+import update from 'immutability-helper';
+import { Button } from 'antd';
+import { Toolbar } from '../toolbar';
+
+export const initialState = {
+    blueprint: null,
+    mode: null,
+    selectionStart: null,
+    stampAt: null,
+    rotation: 0,
+    mirrored: false,
+    lastLoad: null
+}
+
+// each click on "Rotate" turns the stamp by this much
+const ROTATION_STEP = Math.PI / 12;
+// stamped road ends this close to a control point of an existing road are moved onto it,
+// so they connect to that road instead of ending right next to it
+const CONNECTION_DISTANCE = 6.0;
+
+function isRoadGesture(gesture) {
+    return gesture.intent.Road || gesture.intent === "TurnBan" || gesture.intent === "Roundabout";
+}
+
+// the master plan with the current project on top, as it is shown while planning
+function currentGestures(state) {
+    const project = state.planning.projects[state.planning.currentProject];
+    const steps = [Object.keys(state.planning.master.gestures).reduce((gestures, gestureId) =>
+        Object.assign(gestures, { [gestureId]: state.planning.master.gestures[gestureId][0] }), {}
+    )].concat(project
+        ? project.undoable_history.concat([project.ongoing || { gestures: {} }]).map(step => step.gestures)
+        : []
+    );
+    return steps.reduce((coll, gestures) => Object.assign(coll, gestures), {});
+}
+
+// Takes all road gestures that lie completely within the rectangle between two corners,
+// with their points relative to the middle of the selection
+function selectBlueprint(state, cornerA, cornerB) {
+    const [minX, maxX] = [Math.min(cornerA[0], cornerB[0]), Math.max(cornerA[0], cornerB[0])];
+    const [minY, maxY] = [Math.min(cornerA[1], cornerB[1]), Math.max(cornerA[1], cornerB[1])];
+    const isInside = ([x, y]) => x >= minX && x <= maxX && y >= minY && y <= maxY;
+
+    const gestures = currentGestures(state);
+    const selected = Object.keys(gestures).map(gestureId => gestures[gestureId])
+        .filter(gesture => !gesture.deleted && isRoadGesture(gesture) && gesture.points.length && gesture.points.every(isInside));
+
+    if (!selected.length) {
+        return null;
+    }
+
+    const allPoints = [].concat(...selected.map(gesture => gesture.points));
+    const centerX = allPoints.reduce((sum, [x]) => sum + x, 0) / allPoints.length;
+    const centerY = allPoints.reduce((sum, [, y]) => sum + y, 0) / allPoints.length;
+
+    return {
+        name: "Blueprint " + new Date().toISOString().slice(0, 16).replace("T", " "),
+        gestures: selected.map(gesture => ({
+            intent: gesture.intent,
+            points: gesture.points.map(([x, y]) => [x - centerX, y - centerY])
+        }))
+    };
+}
+
+// Moves the blueprint to where it is stamped, mirrored first and then turned around its middle
+function stampedGestures(state) {
+    const { blueprint, stampAt, rotation, mirrored } = state.blueprints;
+    const [cos, sin] = [Math.cos(rotation), Math.sin(rotation)];
+
+    const existing = currentGestures(state);
+    const existingRoadPoints = [].concat(...Object.keys(existing).map(gestureId => existing[gestureId])
+        .filter(gesture => !gesture.deleted && gesture.intent.Road).map(gesture => gesture.points));
+
+    const connected = point => {
+        const closest = existingRoadPoints.reduce((best, existingPoint) => {
+            const distance = Math.hypot(existingPoint[0] - point[0], existingPoint[1] - point[1]);
+            return distance < best.distance ? { point: existingPoint, distance } : best;
+        }, { point, distance: CONNECTION_DISTANCE });
+        return [closest.point[0], closest.point[1]];
+    };
+
+    return blueprint.gestures.map(gesture => {
+        const points = gesture.points.map(([x, y]) => {
+            const mirroredX = mirrored ? -x : x;
+            return [
+                stampAt[0] + cos * mirroredX - sin * y,
+                stampAt[1] + sin * mirroredX + cos * y
+            ];
+        });
+        const lastIdx = points.length - 1;
+
+        return {
+            intent: gesture.intent,
+            points: gesture.intent.Road
+                ? points.map((point, idx) => (idx == 0 || idx == lastIdx) ? connected(point) : point)
+                : points
+        };
+    });
+}
+
+// STATE MUTATING ACTIONS
+
+function setMode(mode) {
+    return oldState => update(oldState, {
+        blueprints: {
+            mode: { $set: oldState.blueprints.mode == mode ? null : mode },
+            selectionStart: { $set: null },
+            stampAt: { $set: null }
+        }
+    });
+}
+
+function setBlueprint(blueprint, lastLoad) {
+    return oldState => update(oldState, {
+        blueprints: {
+            blueprint: { $set: blueprint || oldState.blueprints.blueprint },
+            lastLoad: { $set: lastLoad },
+            rotation: { $set: 0 },
+            mirrored: { $set: false }
+        }
+    });
+}
+
+// INTERACTION
+
+// Used by the planning canvas instead of drawing gestures while copying or stamping a blueprint
+export function onCanvasEvent(e, state, setState) {
+    const { mode, selectionStart } = state.blueprints;
+
+    if (mode == "stamping" && e.hover && e.hover.now) {
+        setState(oldState => update(oldState, {
+            blueprints: { stampAt: { $set: [e.hover.now[0], e.hover.now[1]] } }
+        }));
+    }
+
+    if (e.drag && e.drag.end) {
+        if (mode == "selecting") {
+            if (selectionStart) {
+                const blueprint = selectBlueprint(state, selectionStart, e.drag.end);
+                setState(oldState => update(setBlueprint(blueprint, null)(oldState), {
+                    blueprints: {
+                        mode: { $set: blueprint ? "stamping" : null },
+                        selectionStart: { $set: null }
+                    }
+                }));
+            } else {
+                setState(oldState => update(oldState, {
+                    blueprints: { selectionStart: { $set: [e.drag.end[0], e.drag.end[1]] } }
+                }));
+            }
+        } else if (mode == "stamping") {
+            const stateAtClick = update(state, {
+                blueprints: { stampAt: { $set: [e.drag.end[0], e.drag.end[1]] } }
+            });
+            cbRustBrowser.stamp_blueprint(state.planning.currentProject, stampedGestures(stateAtClick));
+        }
+    }
+}
+
+export function isActive(state) {
+    return state.uiMode == "planning" && state.planning.planningMode == "roads" && !!state.blueprints.mode;
+}
+
+// Control point dots showing the selection corner or where the blueprint would be stamped
+export function previewPoints(state) {
+    if (!isActive(state)) {
+        return [];
+    }
+
+    const { mode, selectionStart, blueprint, stampAt } = state.blueprints;
+
+    if (mode == "selecting") {
+        return selectionStart ? [selectionStart] : [];
+    } else if (mode == "stamping" && blueprint && stampAt) {
+        return [].concat(...stampedGestures(state).map(gesture => gesture.points));
+    } else {
+        return [];
+    }
+}
+
+export function BlueprintToolbar(props) {
+    const { state, setState } = props;
+
+    return <Toolbar id="road-blueprint-toolbar"
+        options={{
+            selecting: { description: "Copy Roads To Blueprint (click two corners)" },
+            stamping: { description: "Stamp Blueprint", disabled: !state.blueprints.blueprint },
+            rotate: { description: "Rotate Blueprint", disabled: !state.blueprints.blueprint },
+            mirror: { description: "Mirror Blueprint", disabled: !state.blueprints.blueprint }
+        }}
+        value={state.blueprints.mode}
+        onChange={value => {
+            if (value == "rotate") {
+                setState(oldState => update(oldState, {
+                    blueprints: { rotation: { $set: (oldState.blueprints.rotation + ROTATION_STEP) % (2 * Math.PI) } }
+                }));
+            } else if (value == "mirror") {
+                setState(oldState => update(oldState, {
+                    blueprints: { mirrored: { $set: !oldState.blueprints.mirrored } }
+                }));
+            } else {
+                setState(setMode(value));
+            }
+        }} />;
+}
+
+// SAVING AND LOADING
+
+function download(fileName, content, mimeType) {
+    const url = URL.createObjectURL(new Blob([content], { type: mimeType }));
+    const link = document.createElement("a");
+    link.href = url;
+    link.download = fileName;
+    document.body.appendChild(link);
+    link.click();
+    document.body.removeChild(link);
+    URL.revokeObjectURL(url);
+}
+
+function isPoint(point) {
+    return Array.isArray(point) && point.length >= 2 && point.every(coordinate => typeof coordinate === "number");
+}
+
+function parseBlueprint(text) {
+    const blueprint = JSON.parse(text);
+    if (!blueprint || !Array.isArray(blueprint.gestures) || !blueprint.gestures.every(gesture =>
+        gesture && gesture.intent && isRoadGesture(gesture) && Array.isArray(gesture.points) && gesture.points.every(isPoint)
+    )) {
+        throw new Error("Not a road blueprint");
+    }
+    return {
+        name: "" + (blueprint.name || "Blueprint"),
+        gestures: blueprint.gestures.map(({ intent, points }) => ({ intent, points: points.map(([x, y]) => [x, y]) }))
+    };
+}
+
+function loadFile(file, setState) {
+    const reader = new FileReader();
+    reader.onload = () => {
+        try {
+            setState(setBlueprint(parseBlueprint(reader.result), { fileName: file.name }));
+        } catch (e) {
+            setState(setBlueprint(null, { fileName: file.name, error: e.message }));
+        }
+    };
+    reader.readAsText(file);
+}
+
+export function Panel(props) {
+    const { state, setState } = props;
+    const { blueprint, lastLoad } = state.blueprints;
+
+    return <div>
+        <p>Blueprints are road layouts copied from a plan, to be stamped elsewhere, turned or mirrored.
+            Copy one with the blueprint tools while planning roads, then save it here to share it.</p>
+        {blueprint
+            ? <p>Current blueprint: <em>{blueprint.name}</em> ({blueprint.gestures.length} roads and intersection rules){" "}
+                <Button onClick={() => download(blueprint.name.replace(/[^\w-]+/g, "-") + ".json", JSON.stringify(blueprint), "application/json")}>Save</Button>
+            </p>
+            : <p><em>No blueprint copied or loaded yet.</em></p>}
+        <p>Load a blueprint: <input type="file" accept=".json" onChange={e => {
+            e.target.files[0] && loadFile(e.target.files[0], setState);
+            e.target.value = "";
+        }} /></p>
+        {lastLoad && lastLoad.error && <p>Couldn't load {lastLoad.fileName}: {lastLoad.error}</p>}
+    </div>;
+}
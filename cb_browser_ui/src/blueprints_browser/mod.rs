@@ -0,0 +1,57 @@
+use kay::TypedID;
+use stdweb::serde::Serde;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use stdweb::js_export;
+use {SYSTEM, local_origin};
+
+use descartes::P2;
+use cb_planning::GestureID;
+use cb_planning::plan_manager::ProjectID;
+use planning::{CBPlanManagerID, CBGestureIntent};
+
+// A gesture of a blueprint, already moved, turned and
+// mirrored to where it is stamped by the browser
+#[derive(Deserialize)]
+pub struct StampedGesture {
+    intent: CBGestureIntent,
+    points: Vec<P2>,
+}
+
+// Each gesture of the blueprint becomes a new gesture in the given project,
+// so it connects to existing roads just like a gesture drawn by hand
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn stamp_blueprint(project_id: Serde<ProjectID>, gestures: Serde<Vec<StampedGesture>>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+
+    let plan_manager = CBPlanManagerID::global_first(world);
+
+    for gesture in gestures.0 {
+        if gesture.points.is_empty() {
+            continue;
+        }
+
+        let id = GestureID::new();
+        plan_manager.start_new_gesture(
+            local_origin(),
+            project_id.0,
+            id,
+            gesture.intent,
+            gesture.points[0],
+            world,
+        );
+
+        let last_idx = gesture.points.len() - 1;
+        for (idx, point) in gesture.points.iter().enumerate().skip(1) {
+            plan_manager.add_control_point(
+                local_origin(),
+                project_id.0,
+                id,
+                *point,
+                true,
+                idx == last_idx,
+                world,
+            );
+        }
+    }
+}
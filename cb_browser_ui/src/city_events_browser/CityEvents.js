@@ -0,0 +1,31 @@
+import React from 'react';
+import update from 'immutability-helper';
+
+export const initialState = {
+    // by kind, there is only one of each at a time
+    active: {}
+}
+
+function focus(setState, position) {
+    setState(oldState => update(oldState, {
+        camera: { target: { $set: [position[0], position[1], 0] } }
+    }));
+}
+
+export function Windows(props) {
+    const { state, setState } = props;
+    const events = Object.entries(state.cityEvents.active);
+
+    if (events.length === 0) {
+        return null;
+    }
+
+    return <div className="city-events">
+        {events.map(([kind, event]) => event.position
+            ? <div key={kind} className={"city-event focusable city-event-" + kind.toLowerCase()}
+                title="Show on the map"
+                onClick={() => focus(setState, event.position)}>{event.description}</div>
+            : <div key={kind} className={"city-event city-event-" + kind.toLowerCase()}>{event.description}</div>
+        )}
+    </div>
+}
@@ -0,0 +1,92 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BrowserCityEventsUI {
+    type ID = BrowserCityEventsUIID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BrowserCityEventsUIID {
+    _raw_id: RawID
+}
+
+impl Copy for BrowserCityEventsUIID {}
+impl Clone for BrowserCityEventsUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BrowserCityEventsUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BrowserCityEventsUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BrowserCityEventsUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BrowserCityEventsUIID {
+    fn eq(&self, other: &BrowserCityEventsUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BrowserCityEventsUIID {}
+
+impl TypedID for BrowserCityEventsUIID {
+    type Target = BrowserCityEventsUI;
+
+    fn from_raw(id: RawID) -> Self {
+        BrowserCityEventsUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BrowserCityEventsUIID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = BrowserCityEventsUIID::from_raw(world.allocate_instance_id::<BrowserCityEventsUI>());
+        let swarm = world.local_broadcast::<BrowserCityEventsUI>();
+        world.send(swarm, MSG_BrowserCityEventsUI_spawn(id, ));
+        id
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserCityEventsUI_spawn(pub BrowserCityEventsUIID, );
+
+impl Into<FrameListenerID> for BrowserCityEventsUIID {
+    fn into(self) -> FrameListenerID {
+        FrameListenerID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<CityEventsUIID> for BrowserCityEventsUIID {
+    fn into(self) -> CityEventsUIID {
+        CityEventsUIID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    FrameListenerID::register_implementor::<BrowserCityEventsUI>(system);
+    CityEventsUIID::register_implementor::<BrowserCityEventsUI>(system);
+    system.add_spawner::<BrowserCityEventsUI, _, _>(
+        |&MSG_BrowserCityEventsUI_spawn(id, ), world| {
+            BrowserCityEventsUI::spawn(id, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,65 @@
+use kay::{World, ActorSystem, Actor, TypedID};
+use stdweb::serde::Serde;
+use browser_utils::{FrameListener, FrameListenerID};
+
+use environment::city_events::{CityEvent, CityEventsID, CityEventsUI, CityEventsUIID};
+
+#[derive(Compact, Clone)]
+pub struct BrowserCityEventsUI {
+    id: BrowserCityEventsUIID,
+    caught_up: bool,
+}
+
+impl BrowserCityEventsUI {
+    pub fn spawn(id: BrowserCityEventsUIID, _: &mut World) -> BrowserCityEventsUI {
+        BrowserCityEventsUI {
+            id,
+            caught_up: false,
+        }
+    }
+}
+
+// Events that were already going on when the browser connected
+impl FrameListener for BrowserCityEventsUI {
+    fn on_frame(&mut self, world: &mut World) {
+        if !self.caught_up {
+            CityEventsID::global_first(world).get_active_events(self.id_as(), world);
+            self.caught_up = true;
+        }
+    }
+}
+
+// there is only one event of each kind at a time
+impl CityEventsUI for BrowserCityEventsUI {
+    fn on_city_event(&mut self, event: CityEvent, started: bool, _: &mut World) {
+        let key = format!("{:?}", event.kind);
+        if started {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    cityEvents: {active: {[@{key}]: {"$set": {
+                        description: @{event.kind.description()},
+                        position: @{Serde(event.position)}
+                    }}}}
+                }));
+            }
+        } else {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    cityEvents: {active: {"$unset": [@{key}]}}
+                }));
+            }
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserCityEventsUI>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserCityEventsUIID::spawn(world);
+}
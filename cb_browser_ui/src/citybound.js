@@ -38,12 +38,27 @@ import * as Transport from './transport_browser/Transport';
 import * as LandUse from './land_use_browser/LandUse';
 import * as Households from './households_browser/Households';
 import * as Vegetation from './vegetation_browser/Vegetation';
+import * as Water from './water_browser/Water';
 import * as Time from './time_browser/Time';
+import * as CityEvents from './city_events_browser/CityEvents';
+import * as Scenario from './scenarios_browser/Scenario';
+import * as Territory from './territory_browser/Territory';
+import * as GridLayers from './grid_layers_browser/GridLayers';
+import * as Timeline from './timeline_browser/Timeline';
+import * as Districts from './districts_browser/Districts';
+import * as Underground from './underground_browser/Underground';
+import * as Export from './export_browser/Export';
+import * as OsmImport from './osm_import_browser/OsmImport';
+import * as Blueprints from './blueprints_browser/Blueprints';
+import * as Savegame from './savegame/Savegame';
 import * as Debug from './debug/Debug';
+import * as Annotations from './annotations/Annotations';
 import * as Settings from './settings';
 import * as Menu from './menu';
 import * as Utils from './browser_utils/Utils';
 import Stage from './stage/Stage';
+import { PostProcessing, settingsSpec as postProcessingSettingsSpec } from './postprocessing/PostProcessing';
+import { settingsSpec as shadowsSettingsSpec, SHADOW_CASTING_MATERIALS } from './postprocessing/Shadows';
 import colors from './colors';
 window.update = update;
 
@@ -53,10 +68,18 @@ require('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(cbRustBro
     const settingSpecs = {
         camera: Camera.settingSpec,
         debug: Debug.settingsSpec,
+        territory: Territory.settingsSpec,
+        gridLayers: GridLayers.settingsSpec,
+        households: Households.settingsSpec,
+        timeline: Timeline.settingsSpec,
+        underground: Underground.settingsSpec,
         planning: Planning.settingsSpec,
         rendering: {
             retinaFactor: { default: 2, description: "Oversampling/Retina Factor", min: 0.5, max: 4.0, step: 0.1 }
-        }
+        },
+        postProcessing: postProcessingSettingsSpec,
+        shadows: shadowsSettingsSpec,
+        annotations: Annotations.settingsSpec
     };
 
     class CityboundReactApp extends React.Component {
@@ -69,7 +92,17 @@ require('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(cbRustBro
                 landUse: LandUse.initialState,
                 households: Households.initialState,
                 vegetation: Vegetation.initialState,
+                water: Water.initialState,
                 debug: Debug.initialState,
+                territory: Territory.initialState,
+                gridLayers: GridLayers.initialState,
+                timeline: Timeline.initialState,
+                districts: Districts.initialState,
+                underground: Underground.initialState,
+                export: Export.initialState,
+                osmImport: OsmImport.initialState,
+                blueprints: Blueprints.initialState,
+                savegame: Savegame.initialState,
                 uiMode: null,
                 system: {
                     networkingTurns: ""
@@ -78,6 +111,8 @@ require('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(cbRustBro
                     enabled: true
                 },
                 time: Time.initialState,
+                cityEvents: CityEvents.initialState,
+                scenario: Scenario.initialState,
                 camera: Camera.initialState,
 
                 menu: Menu.initalState,
@@ -85,6 +120,7 @@ require('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(cbRustBro
             }
 
             this.renderer = React.createRef();
+            this.postProcessing = React.createRef();
             this.boundSetState = this.setState.bind(this);
             // this.pendingUpdaters = [];
             // this.boundSetState = (updater) => this.pendingUpdaters.push(updater);
@@ -93,7 +129,13 @@ require('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(cbRustBro
         componentDidMount() {
             Camera.bindInputs(this.state, this.boundSetState);
             Debug.bindInputs(this.state, this.boundSetState);
+            Territory.bindInputs(this.state, this.boundSetState);
+            GridLayers.bindInputs(this.state, this.boundSetState);
+            Households.bindInputs(this.state, this.boundSetState);
+            Timeline.bindInputs(this.state, this.boundSetState);
+            Underground.bindInputs(this.state, this.boundSetState);
             Planning.bindInputs(this.state, this.boundSetState);
+            Savegame.loadReport(this.boundSetState);
         }
 
         onFrame() {
@@ -106,12 +148,14 @@ require('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(cbRustBro
             if (this.state.rendering.enabled) {
                 Camera.onFrame(this.state, this.boundSetState);
                 this.renderer.current.renderFrame();
+                this.postProcessing.current.renderFrame(ReactDOM.findDOMNode(this.renderer.current));
             }
         }
 
         render() {
             let layers = [];
             let interactive3Dshapes = [];
+            let annotationBatches = [];
 
             return <div style={{ width: "100%", height: "100%" }}>
                 <ContainerDimensions style={{ width: "100%", height: "100%", position: "relative" }}>{({ width, height }) =>
@@ -120,25 +164,50 @@ require('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(cbRustBro
                             <div style={{ width, height }}>
                                 <div key="ui2dTools" className="ui2dTools">
                                     <Planning.Tools state={this.state} setState={this.boundSetState} />
+                                    <Transport.Tools state={this.state} setState={this.boundSetState} />
+                                    <Districts.Tools state={this.state} setState={this.boundSetState} />
+                                    <Underground.Tools state={this.state} setState={this.boundSetState} />
                                     <Menu.Tools state={this.state} setState={this.boundSetState} />
                                 </div>
                                 <div key="ui2d" className="ui2d">
                                     <Time.Windows state={this.state} setState={this.boundSetState} />
+                                    <CityEvents.Windows state={this.state} setState={this.boundSetState} />
+                                    <Scenario.Windows state={this.state} setState={this.boundSetState} />
+                                    <Savegame.Windows state={this.state} setState={this.boundSetState} />
                                     <Debug.Windows state={this.state} setState={this.boundSetState} />
+                                    <Territory.Windows state={this.state} setState={this.boundSetState} />
+                                    <GridLayers.Windows state={this.state} setState={this.boundSetState} />
+                                    <Timeline.Windows state={this.state} setState={this.boundSetState} />
                                     <Households.Windows state={this.state} setState={this.boundSetState} project3dTo2d={project3dTo2d} />
                                     <Menu.Windows state={this.state} setState={this.boundSetState} settingSpecs={settingSpecs} />
                                 </div>
 
+                                <Annotations.AnnotationContext.Provider value={annotationBatches}>
+                                    <Transport.Annotations state={this.state} />
+                                    <LandUse.Annotations state={this.state} />
+                                    <Districts.Annotations state={this.state} />
+                                </Annotations.AnnotationContext.Provider>
+
+                                <Annotations.Overlay key="annotations" state={this.state} batches={annotationBatches}
+                                    {...{ project3dTo2d, width, height }} />
+
                                 <Utils.Interactive3DContext.Provider value={interactive3Dshapes}>
                                     <Utils.RenderContext.Provider value={layers}>
 
                                         <Households.Shapes state={this.state} setState={this.boundSetState} />
+                                        <Transport.Shapes state={this.state} setState={this.boundSetState} />
+                                        <Districts.Shapes state={this.state} setState={this.boundSetState} />
 
                                         <Planning.ShapesAndLayers state={this.state} setState={this.boundSetState} />
 
-                                        <LandUse.Layers state={this.state} />
-                                        <Vegetation.Layers state={this.state} />
+                                        {/* buildings and trees would hide what lies beneath them */}
+                                        {!Underground.isActive(this.state) && <LandUse.Layers state={this.state} />}
+                                        <GridLayers.Layers state={this.state} />
+                                        <Water.Layers state={this.state} />
+                                        {!Underground.isActive(this.state) && <Vegetation.Layers state={this.state} />}
                                         <Transport.Layers state={this.state} />
+                                        <Districts.Layers state={this.state} />
+                                        <Underground.Layers state={this.state} />
 
                                     </Utils.RenderContext.Provider>
                                 </Utils.Interactive3DContext.Provider>
@@ -148,6 +217,14 @@ require('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(cbRustBro
                                     clearColor={[...colors.grass, 1.0]}
                                     {... { layers, width, height, viewMatrix: view, perspectiveMatrix: perspective }} />
 
+                                <PostProcessing key="postProcessing" ref={this.postProcessing}
+                                    retinaFactor={this.state.settings.rendering.retinaFactor}
+                                    settings={this.state.settings.postProcessing}
+                                    shadowSettings={this.state.settings.shadows}
+                                    time={this.state.time.time}
+                                    shadowCasters={SHADOW_CASTING_MATERIALS.map(material => this.state.landUse.rendering.buildingMeshes[material])}
+                                    {... { width, height, viewMatrix: view, perspectiveMatrix: perspective }} />
+
                                 <Stage key="stage"
                                     requestedProjections={this.state.requestedProjections}
                                     style={{ width, height, position: "absolute", top: 0, left: 0 }}
@@ -54,10 +54,18 @@ export default {
     canopies: [0.3, 0.5, 0.2],
     asphalt: [0.6, 0.6, 0.6],
     roadMarker: [1.0, 1.0, 1.0],
+    sidewalk: [0.75, 0.74, 0.72],
+    crosswalk: [0.95, 0.95, 0.95],
+    bikeLane: [0.55, 0.75, 0.45],
+    pedestrian: [0.2, 0.25, 0.35],
+    truck: [0.85, 0.55, 0.15],
 
     WhiteWall: [0.95, 0.95, 0.95],
+    PlasterWall: [0.93, 0.85, 0.7],
+    BrickWall: [0.7, 0.35, 0.25],
     FlatRoof: [0.5, 0.5, 0.5],
     TiledRoof: [0.8, 0.5, 0.2],
+    SlateRoof: [0.35, 0.37, 0.42],
     FieldWheat: [0.7, 0.7, 0.2],
     FieldMeadow: [0.49, 0.68, 0.37],
     FieldRows: [0.62, 0.56, 0.5],
@@ -66,11 +74,29 @@ export default {
     MetalFence: [0.8, 0.8, 0.8],
     LotAsphalt: [0.65, 0.65, 0.65],
 
-    plannedAsphalt: [1.0, 1.0, 1.0],
-    plannedRoadMarker: [0.6, 0.6, 0.6],
+    plannedAsphalt: [0.55, 0.7, 1.0],
+    plannedRoadMarker: [0.9, 0.95, 1.0],
     destructedAsphalt: [1.0, 0.0, 0.0],
     buildingOutlines: [0.0, 0.0, 0.0],
 
+    debugLaneCenterline: [0.0, 0.8, 1.0],
+    debugIntersectionConnector: [1.0, 0.2, 0.8],
+    debugBuildingFootprint: [1.0, 0.6, 0.0],
+
+    routeRemaining: [0.0, 0.35, 1.0],
+    routeTraveled: [0.45, 0.6, 0.85],
+
+    transitLine: [0.85, 0.2, 0.35],
+    transitBus: [0.9, 0.25, 0.3],
+    railLine: [0.2, 0.45, 0.8],
+    railTrack: [0.35, 0.33, 0.32],
+    transitTrain: [0.25, 0.5, 0.85],
+
+    toll: [0.95, 0.65, 0.0],
+    district: [0.55, 0.3, 0.85],
+    conduitElectricity: [0.95, 0.8, 0.1],
+    conduitWater: [0.15, 0.5, 0.95],
+
     controlPointMaster: [0.3, 0.3, 1.0],
     controlPointCurrentProject: [0.0, 0.061, 1.0],//[0, 72, 255]
     controlPointHover: [0.3, 0.361, 1.0],
@@ -83,6 +109,13 @@ export default {
     Recreational: mix(toLinFloat([124, 192, 124]), grass, 0.9),
     Administrative: mix(toLinFloat([39, 150, 221]), grass, 0.9),
 
+    water: toLinFloat([60, 120, 160]),
+    shore: mix(toLinFloat([194, 178, 128]), grass, 0.5),
+
+    // map overlays fade from grass to these
+    gridLayerGood: toLinFloat([20, 110, 210]),
+    gridLayerBad: toLinFloat([210, 50, 30]),
+
     carColors: shuffle([
         [30.0, 45.0, 45.0],    // black
         [45.0, 30.0, 45.0],    // black
@@ -1,5 +1,5 @@
 import React from 'react';
-import { Button, InputNumber } from 'antd';
+import { Button, InputNumber, Switch } from 'antd';
 import { fmtId } from '../browser_utils/Utils';
 
 export const initialState = {
@@ -12,6 +12,12 @@ export const initialState = {
     spawnCarsSettings: {
         triesPerLane: 50
     },
+    geometryLayers: {
+        laneCenterlines: false,
+        intersectionConnectors: false,
+        buildingFootprints: false,
+        pathfindingNodes: false
+    },
     logLastEntry: 0,
     logTextStart: 0,
     logFirstEntry: 0,
@@ -25,6 +31,13 @@ export const settingsSpec = {
 
 let refreshInterval = null;
 
+const GEOMETRY_LAYER_NAMES = {
+    laneCenterlines: "Lane centerlines",
+    intersectionConnectors: "Intersection connectors",
+    buildingFootprints: "Building footprints",
+    pathfindingNodes: "Pathfinding nodes"
+};
+
 export function Windows(props) {
     const { state, setState } = props;
 
@@ -109,6 +122,29 @@ export function Windows(props) {
                         )}>{state.rendering.enabled ? "Disable rendering" : "Enable rendering"}</Button>
                 </div>
             </details>
+            <details>
+                <summary>Geometry Diagnostics</summary>
+                <div key="geometryLayers">
+                    {Object.keys(GEOMETRY_LAYER_NAMES).map(layer =>
+                        <div key={layer}>
+                            <Switch
+                                size="small"
+                                checked={state.debug.geometryLayers[layer]}
+                                onChange={enabled => {
+                                    if (enabled && layer === "pathfindingNodes") {
+                                        cbRustBrowser.get_pathfinding_debug_info();
+                                    }
+                                    setState(oldState => update(oldState, {
+                                        debug: { geometryLayers: { [layer]: { $set: enabled } } }
+                                    }));
+                                }} /> {GEOMETRY_LAYER_NAMES[layer]}
+                        </div>
+                    )}
+                    <Button
+                        disabled={!state.debug.geometryLayers.pathfindingNodes}
+                        onClick={() => cbRustBrowser.get_pathfinding_debug_info()}>Refresh pathfinding nodes</Button>
+                </div>
+            </details>
             <details>
                 <summary>Networking</summary>
                 <div>{Object.keys(state.system.networkingTurns).map(machine =>
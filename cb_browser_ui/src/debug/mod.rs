@@ -2,7 +2,7 @@ use kay::TypedID;
 use stdweb::serde::Serde;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use stdweb::js_export;
-use SYSTEM;
+use {SYSTEM, local_origin};
 
 use cb_planning::GestureID;
 use cb_planning::plan_manager::ProjectID;
@@ -15,21 +15,24 @@ pub fn plan_grid(project_id: Serde<ProjectID>, n: Serde<isize>, n_lanes: Serde<u
 
     let plan_manager = CBPlanManagerID::global_first(world);
 
-    use ::transport::transport_planning::RoadIntent;
+    use ::transport::transport_planning::{RoadIntent, RoadClass};
     use ::descartes::P2;
 
+    let road_intent = RoadIntent::new(n_lanes.0, n_lanes.0, RoadClass::for_lanes(n_lanes.0));
+
     for x in -n.0 / 2..n.0 / 2 {
         let id = GestureID::new();
         let p1 = P2::new(x as f32 * spacing.0, (-n.0 / 2) as f32 * spacing.0);
         let p2 = P2::new(x as f32 * spacing.0, (n.0 / 2) as f32 * spacing.0);
         plan_manager.start_new_gesture(
+            local_origin(),
             project_id.0,
             id,
-            CBGestureIntent::Road(RoadIntent::new(n_lanes.0, n_lanes.0)),
+            CBGestureIntent::Road(road_intent),
             p1,
             world,
         );
-        plan_manager.add_control_point(project_id.0, id, p2, true, true, world);
+        plan_manager.add_control_point(local_origin(), project_id.0, id, p2, true, true, world);
     }
 
     for y in -n.0 / 2..n.0 / 2 {
@@ -37,13 +40,14 @@ pub fn plan_grid(project_id: Serde<ProjectID>, n: Serde<isize>, n_lanes: Serde<u
         let p1 = P2::new((-n.0 / 2) as f32 * spacing.0, y as f32 * spacing.0);
         let p2 = P2::new((n.0 / 2) as f32 * spacing.0, y as f32 * spacing.0);
         plan_manager.start_new_gesture(
+            local_origin(),
             project_id.0,
             id,
-            CBGestureIntent::Road(RoadIntent::new(n_lanes.0, n_lanes.0)),
+            CBGestureIntent::Road(road_intent),
             p1,
             world,
         );
-        plan_manager.add_control_point(project_id.0, id, p2, true, true, world);
+        plan_manager.add_control_point(local_origin(), project_id.0, id, p2, true, true, world);
     }
 }
 
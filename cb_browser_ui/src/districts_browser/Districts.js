@@ -0,0 +1,190 @@
+import colors from '../colors';
+import renderOrder from '../renderOrder';
+import { Interactive3DShape } from '../browser_utils/Utils';
+import { AnnotationBatch, memoizedAnnotations } from '../annotations/Annotations';
+import { PolylineOverlay } from '../overlays/Polylines';
+import { Toolbar } from '../toolbar';
+import React from 'react';
+import update from 'immutability-helper';
+import { Button, Select, Input, InputNumber } from 'antd';
+const Option = Select.Option;
+
+export const initialState = {
+    districts: [],
+    drawnOutline: [],
+    drawnName: "",
+    // null means "designate a new district", otherwise the id of the one being edited
+    selectedDistrict: null,
+    policies: { tax_modifier: 0, speed_limit: null, heavy_traffic_ban: false }
+};
+
+const DISTRICT_WIDTH = 4.0;
+const DISTRICT_DASH = [8.0, 4.0];
+// further than this from where the mouse went down, it's a camera pan, not a click
+const MAX_CLICK_DISTANCE = 3.0;
+// speed limits are entered in km/h, but policies keep m/s
+const KMH_PER_MS = 3.6;
+
+export function districtDisplayName(districts, id) {
+    const district = districts.find(district => district.id === id);
+    return district ? district.name : `District #${id}`;
+}
+
+let lastDistricts = null;
+let lastDrawnOutline = null;
+let lastDistrictPolylines = [];
+
+function closed(outline) {
+    return outline.length > 2 ? [...outline, outline[0]] : outline;
+}
+
+function districtPolylines(districts, drawnOutline) {
+    if (districts !== lastDistricts || drawnOutline !== lastDrawnOutline) {
+        lastDistricts = districts;
+        lastDrawnOutline = drawnOutline;
+        lastDistrictPolylines = districts.map(district =>
+            ({ points: closed(district.outline), width: DISTRICT_WIDTH, color: colors.district })
+        );
+        if (drawnOutline.length > 1) {
+            lastDistrictPolylines.push({
+                points: closed(drawnOutline), width: DISTRICT_WIDTH, color: colors.district, dash: DISTRICT_DASH
+            });
+        }
+    }
+    return lastDistrictPolylines;
+}
+
+export function Layers(props) {
+    const { state } = props;
+
+    if (state.uiMode != "districts") {
+        return null;
+    }
+
+    return <PolylineOverlay
+        renderOrder={renderOrder.polylineOverlays}
+        polylines={districtPolylines(state.districts.districts, state.districts.drawnOutline)} />;
+}
+
+export function Shapes(props) {
+    const { state, setState } = props;
+
+    if (state.uiMode != "districts" || state.districts.selectedDistrict !== null) {
+        return null;
+    }
+
+    // clicks add corners to the outline of the district being drawn
+    return <Interactive3DShape
+        id="districtDrawing"
+        key="districtDrawing"
+        shape={{ type: "everywhere" }}
+        zIndex={1}
+        cursorHover="crosshair"
+        cursorActive="pointer"
+        onEvent={e => {
+            if (e.drag && e.drag.end && Math.hypot(e.drag.end[0] - e.drag.start[0], e.drag.end[1] - e.drag.start[1]) < MAX_CLICK_DISTANCE) {
+                const point = [e.drag.end[0], e.drag.end[1]];
+                setState(oldState => update(oldState, {
+                    districts: { drawnOutline: { $push: [point] } }
+                }));
+            }
+        }} />;
+}
+
+// labelled at the average of their corners
+const districtAnnotations = memoizedAnnotations(districts =>
+    districts.map(district => ({
+        key: "drawn" + district.id,
+        position: [
+            district.outline.reduce((sum, point) => sum + point[0], 0) / district.outline.length,
+            district.outline.reduce((sum, point) => sum + point[1], 0) / district.outline.length
+        ],
+        text: district.name
+    }))
+);
+
+export function Annotations(props) {
+    const { state } = props;
+
+    return <AnnotationBatch key="drawnDistricts" kind="district"
+        annotations={districtAnnotations(state.districts.districts)} />;
+}
+
+export function Tools(props) {
+    const { state, setState } = props;
+
+    if (state.uiMode != "districts") {
+        return null;
+    }
+
+    const { districts, drawnOutline, drawnName, selectedDistrict, policies } = state.districts;
+    const discard = () => setState(oldState => update(oldState, {
+        districts: { drawnOutline: { $set: [] }, drawnName: { $set: "" } }
+    }));
+    const setPolicy = (key, value) => setState(oldState => update(oldState, {
+        districts: { policies: { [key]: { $set: value } } }
+    }));
+    const select = id => setState(oldState => update(oldState, {
+        districts: {
+            selectedDistrict: { $set: id },
+            policies: {
+                $set: id === null
+                    ? initialState.policies
+                    : oldState.districts.districts.find(district => district.id === id).policies
+            }
+        }
+    }));
+
+    const policyInputs = [
+        "Tax",
+        <InputNumber value={Math.round(policies.tax_modifier * 100)} min={-50} max={100} step={5}
+            formatter={value => `${value}%`} parser={value => value.replace('%', '')}
+            onChange={percent => setPolicy("tax_modifier", (percent || 0) / 100)} />,
+        "Speed limit",
+        <InputNumber value={policies.speed_limit === null ? undefined : Math.round(policies.speed_limit * KMH_PER_MS)}
+            min={10} max={130} step={10} placeholder="none"
+            onChange={kmh => setPolicy("speed_limit", kmh ? kmh / KMH_PER_MS : null)} />,
+        <Toolbar id="heavy-traffic-toolbar"
+            options={{ allowed: { description: "Trucks allowed" }, banned: { description: "Trucks banned" } }}
+            value={policies.heavy_traffic_ban ? "banned" : "allowed"}
+            onChange={choice => setPolicy("heavy_traffic_ban", choice == "banned")} />
+    ];
+
+    const districtSelect = districts.length > 0 && <Select
+        style={{ width: 180 }}
+        value={selectedDistrict === null ? "new" : selectedDistrict}
+        onChange={value => select(value === "new" ? null : value)}
+    >
+        <Option value="new">New district</Option>
+        {districts.map(district => <Option value={district.id}>{district.name}</Option>)}
+    </Select>;
+
+    if (selectedDistrict !== null) {
+        return [
+            districtSelect,
+            ...policyInputs,
+            <Button type="primary"
+                onClick={() => cbRustBrowser.set_district_policies(selectedDistrict, policies)}>Apply policies</Button>,
+            <Button onClick={() => {
+                cbRustBrowser.dissolve_district(selectedDistrict);
+                select(null);
+            }}>Dissolve</Button>
+        ];
+    }
+
+    return [
+        districtSelect,
+        <Input style={{ width: 160 }} placeholder="District name" value={drawnName}
+            onChange={e => setState(oldState => update(oldState, {
+                districts: { drawnName: { $set: e.target.value } }
+            }))} />,
+        ...policyInputs,
+        <Button type="primary"
+            disabled={drawnOutline.length < 3 || drawnName.trim() == ""}
+            onClick={() => {
+                cbRustBrowser.designate_district(drawnName.trim(), drawnOutline, policies);
+                discard();
+            }}>Designate</Button>,
+        <Button disabled={drawnOutline.length == 0} onClick={discard}>Discard</Button>
+    ];
+}
@@ -0,0 +1,85 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BrowserDistrictUI {
+    type ID = BrowserDistrictUIID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BrowserDistrictUIID {
+    _raw_id: RawID
+}
+
+impl Copy for BrowserDistrictUIID {}
+impl Clone for BrowserDistrictUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BrowserDistrictUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BrowserDistrictUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BrowserDistrictUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BrowserDistrictUIID {
+    fn eq(&self, other: &BrowserDistrictUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BrowserDistrictUIID {}
+
+impl TypedID for BrowserDistrictUIID {
+    type Target = BrowserDistrictUI;
+
+    fn from_raw(id: RawID) -> Self {
+        BrowserDistrictUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BrowserDistrictUIID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = BrowserDistrictUIID::from_raw(world.allocate_instance_id::<BrowserDistrictUI>());
+        let swarm = world.local_broadcast::<BrowserDistrictUI>();
+        world.send(swarm, MSG_BrowserDistrictUI_spawn(id, ));
+        id
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserDistrictUI_spawn(pub BrowserDistrictUIID, );
+
+impl Into<DistrictListenerID> for BrowserDistrictUIID {
+    fn into(self) -> DistrictListenerID {
+        DistrictListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    DistrictListenerID::register_implementor::<BrowserDistrictUI>(system);
+    system.add_spawner::<BrowserDistrictUI, _, _>(
+        |&MSG_BrowserDistrictUI_spawn(id, ), world| {
+            BrowserDistrictUI::spawn(id, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,97 @@
+use kay::{World, ActorSystem, TypedID};
+use compact::CVec;
+use descartes::P2;
+use stdweb::serde::Serde;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use stdweb::js_export;
+use land_use::districts::{DistrictArea, DistrictListener, DistrictListenerID, DistrictPolicies,
+DistrictRegistryID};
+use {SYSTEM, local_origin};
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn designate_district(
+    name: String,
+    points: Serde<Vec<P2>>,
+    policies: Serde<DistrictPolicies>,
+) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    DistrictRegistryID::global_first(world).designate(
+        local_origin(),
+        name.into(),
+        points.0.into(),
+        policies.0,
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn set_district_policies(district: u16, policies: Serde<DistrictPolicies>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    DistrictRegistryID::global_first(world).set_policies(
+        local_origin(),
+        district,
+        policies.0,
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn dissolve_district(district: u16) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    DistrictRegistryID::global_first(world).dissolve(local_origin(), district, world);
+}
+
+#[derive(Serialize)]
+struct DistrictInfo {
+    id: u16,
+    name: String,
+    outline: Vec<P2>,
+    policies: DistrictPolicies,
+}
+
+#[derive(Compact, Clone)]
+pub struct BrowserDistrictUI {
+    id: BrowserDistrictUIID,
+}
+
+impl BrowserDistrictUI {
+    pub fn spawn(id: BrowserDistrictUIID, world: &mut World) -> BrowserDistrictUI {
+        DistrictRegistryID::global_first(world).get_districts(id.into(), world);
+        BrowserDistrictUI { id }
+    }
+}
+
+impl DistrictListener for BrowserDistrictUI {
+    fn on_districts_changed(&mut self, districts: &CVec<DistrictArea>, _: &mut World) {
+        let infos = districts
+            .iter()
+            .map(|district| DistrictInfo {
+                id: district.id,
+                name: district.name.to_string(),
+                outline: district.outline.iter().cloned().collect(),
+                policies: district.policies,
+            })
+            .collect::<Vec<_>>();
+
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                districts: {districts: {"$set": @{Serde(infos)}}}
+            }));
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserDistrictUI>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserDistrictUIID::spawn(world);
+}
@@ -0,0 +1,145 @@
+import React from 'react';
+import { Button } from 'antd';
+import update from 'immutability-helper';
+import { fmtId } from '../browser_utils/Utils';
+
+export const initialState = {
+    collecting: false
+}
+
+// roads and buildings report independently and there is no way to know
+// when the last one did, so we just give them plenty of time
+const COLLECTION_TIME = 3000;
+
+// Coordinates are exported as they are used in the simulation:
+// meters in a flat, local coordinate system, not longitude/latitude
+
+function closedRing(points) {
+    const first = points[0];
+    const last = points[points.length - 1];
+    return (first[0] === last[0] && first[1] === last[1]) ? points : [...points, first];
+}
+
+function featureCollection(features) {
+    return JSON.stringify({ type: "FeatureCollection", features }, null, 1);
+}
+
+function laneFeature(lane) {
+    return {
+        type: "Feature",
+        geometry: { type: "LineString", coordinates: lane.points },
+        properties: {
+            id: fmtId(lane.id),
+            length: lane.length,
+            onIntersection: lane.onIntersection,
+            nCars: lane.nCars
+        }
+    };
+}
+
+function buildingFeature(building) {
+    return {
+        type: "Feature",
+        geometry: { type: "Polygon", coordinates: [closedRing(building.points)] },
+        properties: {
+            id: fmtId(building.id),
+            style: building.style,
+            area: building.area,
+            nHouseholds: building.nHouseholds
+        }
+    };
+}
+
+// zones only exist as gestures of the master plan
+function zones(state) {
+    const gestures = state.planning.master.gestures;
+
+    return Object.keys(gestures)
+        .map(gestureId => [gestureId, gestures[gestureId][0]])
+        .filter(([, gesture]) => gesture.intent.Zone && gesture.points.length > 2)
+        .map(([gestureId, gesture]) => {
+            const [kind, value] = Object.entries(gesture.intent.Zone)[0];
+            return { id: gestureId, points: gesture.points, kind, value };
+        });
+}
+
+function zoneFeature(zone) {
+    return {
+        type: "Feature",
+        geometry: { type: "Polygon", coordinates: [closedRing(zone.points)] },
+        properties: { id: zone.id, [zone.kind]: zone.value }
+    };
+}
+
+function csvValue(value) {
+    const string = String(value);
+    return /[",\n]/.test(string) ? '"' + string.replace(/"/g, '""') + '"' : string;
+}
+
+function toCSV(columns, rows) {
+    return [
+        columns.join(","),
+        ...rows.map(row => columns.map(column => csvValue(row[column])).join(","))
+    ].join("\n");
+}
+
+function center(points) {
+    const sum = points.reduce(([sx, sy], [x, y]) => [sx + x, sy + y], [0, 0]);
+    return [sum[0] / points.length, sum[1] / points.length];
+}
+
+function download(fileName, content, mimeType) {
+    const url = URL.createObjectURL(new Blob([content], { type: mimeType }));
+    const link = document.createElement("a");
+    link.href = url;
+    link.download = fileName;
+    document.body.appendChild(link);
+    link.click();
+    document.body.removeChild(link);
+    URL.revokeObjectURL(url);
+}
+
+function finishExport(state) {
+    const { lanes, buildings } = window.cbExportBuffer;
+
+    download("city-roads.geojson", featureCollection(lanes.map(laneFeature)), "application/geo+json");
+    download("city-buildings.geojson", featureCollection(buildings.map(buildingFeature)), "application/geo+json");
+    download("city-zones.geojson", featureCollection(zones(state).map(zoneFeature)), "application/geo+json");
+
+    download("city-roads.csv", toCSV(
+        ["id", "length", "onIntersection", "nCars"],
+        lanes.map(lane => Object.assign({}, lane, { id: fmtId(lane.id) }))
+    ), "text/csv");
+    download("city-buildings.csv", toCSV(
+        ["id", "style", "x", "y", "area", "nHouseholds"],
+        buildings.map(building => {
+            const [x, y] = center(building.points);
+            return Object.assign({}, building, { id: fmtId(building.id), x, y });
+        })
+    ), "text/csv");
+
+    window.cbExportBuffer = null;
+}
+
+function startExport(setState) {
+    setState(oldState => update(oldState, { export: { collecting: { $set: true } } }));
+    cbRustBrowser.start_export();
+
+    setTimeout(() => {
+        finishExport(window.cbReactApp.state);
+        setState(oldState => update(oldState, { export: { collecting: { $set: false } } }));
+    }, COLLECTION_TIME);
+}
+
+export function Panel(props) {
+    const { state, setState } = props;
+
+    return <div>
+        <p>Exports roads, buildings and zones as GeoJSON, as well as roads and buildings with their key figures as CSV,
+            for analysis in GIS or statistics tools.</p>
+        <p>Coordinates are in meters, relative to the center of the map.</p>
+        <Button disabled={state.export.collecting} onClick={() => startExport(setState)}>
+            {state.export.collecting ? "Collecting..." : "Export City"}
+        </Button>
+    </div>;
+}
@@ -0,0 +1,85 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BrowserExporter {
+    type ID = BrowserExporterID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BrowserExporterID {
+    _raw_id: RawID
+}
+
+impl Copy for BrowserExporterID {}
+impl Clone for BrowserExporterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BrowserExporterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BrowserExporterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BrowserExporterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BrowserExporterID {
+    fn eq(&self, other: &BrowserExporterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BrowserExporterID {}
+
+impl TypedID for BrowserExporterID {
+    type Target = BrowserExporter;
+
+    fn from_raw(id: RawID) -> Self {
+        BrowserExporterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BrowserExporterID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = BrowserExporterID::from_raw(world.allocate_instance_id::<BrowserExporter>());
+        let swarm = world.local_broadcast::<BrowserExporter>();
+        world.send(swarm, MSG_BrowserExporter_spawn(id, ));
+        id
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserExporter_spawn(pub BrowserExporterID, );
+
+impl Into<ExporterID> for BrowserExporterID {
+    fn into(self) -> ExporterID {
+        ExporterID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    ExporterID::register_implementor::<BrowserExporter>(system);
+    system.add_spawner::<BrowserExporter, _, _>(
+        |&MSG_BrowserExporter_spawn(id, ), world| {
+            BrowserExporter::spawn(id, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+use kay::{World, ActorSystem, TypedID};
+use stdweb::serde::Serde;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use stdweb::js_export;
+use SYSTEM;
+
+use descartes::{N, LinePath};
+use transport::lane::LaneID;
+use land_use::buildings::{BuildingID, BuildingStyle};
+use export::{Exporter, ExporterID, ExportableID};
+
+// Exported features are collected outside of the React state,
+// since there can be a lot of them and they are only needed once
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn start_export() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    js! {
+        window.cbExportBuffer = { lanes: [], buildings: [] };
+    }
+    ExportableID::global_broadcast(world)
+        .export(BrowserExporterID::local_first(world).into(), world);
+}
+
+fn to_js_points(path: &LinePath) -> Vec<[N; 2]> {
+    path.points.iter().map(|point| [point.x, point.y]).collect()
+}
+
+#[derive(Compact, Clone)]
+pub struct BrowserExporter {
+    id: BrowserExporterID,
+}
+
+impl BrowserExporter {
+    pub fn spawn(id: BrowserExporterID, _: &mut World) -> BrowserExporter {
+        BrowserExporter { id }
+    }
+}
+
+impl Exporter for BrowserExporter {
+    fn on_exported_lane(
+        &mut self,
+        lane: LaneID,
+        path: &LinePath,
+        on_intersection: bool,
+        n_cars: u32,
+        _: &mut World,
+    ) {
+        js! {
+            window.cbExportBuffer.lanes.push({
+                id: @{Serde(lane)},
+                points: @{Serde(to_js_points(path))},
+                length: @{path.length()},
+                onIntersection: @{on_intersection},
+                nCars: @{n_cars}
+            });
+        }
+    }
+
+    fn on_exported_building(
+        &mut self,
+        building: BuildingID,
+        outline: &LinePath,
+        style: BuildingStyle,
+        area: N,
+        n_households: u32,
+        _: &mut World,
+    ) {
+        js! {
+            window.cbExportBuffer.buildings.push({
+                id: @{Serde(building)},
+                points: @{Serde(to_js_points(outline))},
+                style: @{Serde(style)},
+                area: @{area},
+                nHouseholds: @{n_households}
+            });
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserExporter>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserExporterID::spawn(world);
+}
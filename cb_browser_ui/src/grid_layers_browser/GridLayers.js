@@ -0,0 +1,119 @@
+import React from 'react';
+import update from 'immutability-helper';
+import { RenderLayer } from '../browser_utils/Utils';
+import colors from '../colors';
+import renderOrder from '../renderOrder';
+
+// cycled through in this order, starting and ending with no overlay
+const LAYERS = ["LandValue", "SchoolCoverage", "HealthcareCoverage", "Pollution", "Noise"];
+
+// values at which a layer is shown at full strength, and whether more is better
+const LAYER_DISPLAY = {
+    LandValue: { name: "Land Value", fullAt: 10, good: true },
+    SchoolCoverage: { name: "School Coverage", fullAt: 1, good: true },
+    HealthcareCoverage: { name: "Healthcare Coverage", fullAt: 1, good: true },
+    Pollution: { name: "Pollution", fullAt: 3, good: false },
+    Noise: { name: "Noise", fullAt: 2, good: false },
+};
+
+export const initialState = {
+    shown: null,
+    kind: null,
+    cellSize: 50,
+    values: []
+}
+
+export const settingsSpec = {
+    cycleOverlayKey: { default: { key: 'o' }, description: "Cycle Map Overlays" }
+}
+
+function mix(a, b, alpha) {
+    return [0, 1, 2].map(i => a[i] * alpha + b[i] * (1 - alpha));
+}
+
+function cellColor(kind, value) {
+    const { fullAt, good } = LAYER_DISPLAY[kind];
+    const strength = Math.max(-1, Math.min(1, value / fullAt));
+    const isGood = (strength > 0) == good;
+    return mix(isGood ? colors.gridLayerGood : colors.gridLayerBad, colors.grass, Math.abs(strength));
+}
+
+let cellMesh = null;
+let cellMeshSize = null;
+
+function squareMesh(size) {
+    if (cellMeshSize !== size) {
+        cellMesh = {
+            vertices: new Float32Array([0, 0, 0, size, 0, 0, size, size, 0, 0, size, 0]),
+            indices: new Uint16Array([0, 1, 2, 0, 2, 3])
+        };
+        cellMeshSize = size;
+    }
+    return cellMesh;
+}
+
+// instances are only rebuilt when new values arrived
+let cachedValues = null;
+let cachedInstances = null;
+
+function cellInstances(kind, cellSize, values) {
+    if (cachedValues !== values) {
+        cachedInstances = new Float32Array(values.length * 8);
+        values.forEach(([[x, y], value], i) => {
+            cachedInstances.set([x * cellSize, y * cellSize, 0.0, 1.0, 0.0, ...cellColor(kind, value)], i * 8);
+        });
+        cachedValues = values;
+    }
+    return cachedInstances;
+}
+
+let refreshInterval = null;
+
+export function Windows(props) {
+    const { state } = props;
+    const { shown } = state.gridLayers;
+
+    if (refreshInterval && refreshInterval.kind !== shown) {
+        clearInterval(refreshInterval.id);
+        refreshInterval = null;
+    }
+    if (shown && !refreshInterval) {
+        cbRustBrowser.get_grid_layer(shown);
+        refreshInterval = { kind: shown, id: setInterval(() => cbRustBrowser.get_grid_layer(shown), 2000) };
+    }
+
+    return shown && <div key="gridLayers" className="window grid-layers">
+        <h2>{LAYER_DISPLAY[shown].name}</h2>
+        <p>Press {state.settings.gridLayers.cycleOverlayKey.key.toUpperCase()} for the next overlay</p>
+    </div>
+}
+
+export function Layers(props) {
+    const { state } = props;
+    const { shown, kind, cellSize, values } = state.gridLayers;
+
+    return shown && shown === kind && <RenderLayer
+        key="gridLayerOverlay"
+        decal={true}
+        renderOrder={renderOrder.gridLayerOverlay}
+        batches={[{
+            mesh: squareMesh(cellSize),
+            instances: cellInstances(kind, cellSize, values)
+        }]} />;
+}
+
+export function bindInputs(state, setState) {
+    const inputActions = {
+        "cycleOverlay": () => setState(oldState => update(oldState, {
+            gridLayers: {
+                shown: {
+                    $apply: shown => shown === LAYERS[LAYERS.length - 1]
+                        ? null
+                        : LAYERS[shown ? LAYERS.indexOf(shown) + 1 : 0]
+                }
+            }
+        })),
+    }
+
+    Mousetrap.bind(state.settings.gridLayers.cycleOverlayKey.key, inputActions["cycleOverlay"]);
+}
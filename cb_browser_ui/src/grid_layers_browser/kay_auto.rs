@@ -0,0 +1,85 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BrowserGridLayersUI {
+    type ID = BrowserGridLayersUIID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BrowserGridLayersUIID {
+    _raw_id: RawID
+}
+
+impl Copy for BrowserGridLayersUIID {}
+impl Clone for BrowserGridLayersUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BrowserGridLayersUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BrowserGridLayersUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BrowserGridLayersUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BrowserGridLayersUIID {
+    fn eq(&self, other: &BrowserGridLayersUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BrowserGridLayersUIID {}
+
+impl TypedID for BrowserGridLayersUIID {
+    type Target = BrowserGridLayersUI;
+
+    fn from_raw(id: RawID) -> Self {
+        BrowserGridLayersUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BrowserGridLayersUIID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = BrowserGridLayersUIID::from_raw(world.allocate_instance_id::<BrowserGridLayersUI>());
+        let swarm = world.local_broadcast::<BrowserGridLayersUI>();
+        world.send(swarm, MSG_BrowserGridLayersUI_spawn(id, ));
+        id
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserGridLayersUI_spawn(pub BrowserGridLayersUIID, );
+
+impl Into<GridLayerUIID> for BrowserGridLayersUIID {
+    fn into(self) -> GridLayerUIID {
+        GridLayerUIID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    GridLayerUIID::register_implementor::<BrowserGridLayersUI>(system);
+    system.add_spawner::<BrowserGridLayersUI, _, _>(
+        |&MSG_BrowserGridLayersUI_spawn(id, ), world| {
+            BrowserGridLayersUI::spawn(id, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,64 @@
+use kay::{World, ActorSystem, TypedID};
+use compact::CVec;
+use stdweb::serde::Serde;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use stdweb::js_export;
+use SYSTEM;
+
+use descartes::N;
+use land_use::grid_layers::{GridLayersID, GridLayerUI, GridLayerUIID, LayerKind};
+use land_use::grid_layers::sliced_grid::Cell;
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_grid_layer(kind: Serde<LayerKind>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    GridLayersID::global_first(world).get_layer(
+        kind.0,
+        BrowserGridLayersUIID::local_first(world).into(),
+        world,
+    );
+}
+
+#[derive(Compact, Clone)]
+pub struct BrowserGridLayersUI {
+    id: BrowserGridLayersUIID,
+}
+
+impl BrowserGridLayersUI {
+    pub fn spawn(id: BrowserGridLayersUIID, _: &mut World) -> BrowserGridLayersUI {
+        BrowserGridLayersUI { id }
+    }
+}
+
+impl GridLayerUI for BrowserGridLayersUI {
+    fn on_grid_layer(
+        &mut self,
+        kind: LayerKind,
+        cell_size: N,
+        values: &CVec<(Cell, f32)>,
+        _world: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                gridLayers: {
+                    kind: {"$set": @{Serde(kind)}},
+                    cellSize: {"$set": @{cell_size}},
+                    values: {"$set": @{Serde(values)}}
+                }
+            }));
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserGridLayersUI>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserGridLayersUIID::spawn(world);
+}
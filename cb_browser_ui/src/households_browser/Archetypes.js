@@ -0,0 +1,45 @@
+import React from 'react';
+import { Button, Input } from 'antd';
+
+// Archetypes are edited as plain JSON, changes apply to families immigrating from then on
+class ArchetypeEditor extends React.Component {
+    constructor(props) {
+        super(props);
+        this.state = { text: JSON.stringify(props.archetype, null, 2), error: null };
+    }
+
+    apply() {
+        try {
+            cbRustBrowser.set_household_archetype(this.props.name, JSON.parse(this.state.text));
+            this.setState({ error: null });
+        } catch (e) {
+            this.setState({ error: e.message });
+        }
+    }
+
+    render() {
+        const { name } = this.props;
+        const { text, error } = this.state;
+
+        return <div className="archetype">
+            <h3>{name}</h3>
+            <Input.TextArea rows={8} value={text} onChange={e => this.setState({ text: e.target.value })} />
+            {error && <p className="archetype-error">{error}</p>}
+            <Button onClick={() => this.apply()}>Apply</Button>
+        </div>;
+    }
+}
+
+export function Panel(props) {
+    const { state } = props;
+    const archetypes = state.households.archetypes;
+
+    return <div>
+        <p>The kinds of households that move into the city: how often they come, who they are made up of,
+            how likely they bring a car, how fast they use up resources (relative to the usual rate)
+            and when their adults sleep.</p>
+        {Object.keys(archetypes).sort().map(name =>
+            <ArchetypeEditor key={name} name={name} archetype={archetypes[name]} />
+        )}
+    </div>;
+}
@@ -10,8 +10,21 @@ export const initialState = {
     inspectedBuildingPinned: false,
     inspectedBuildingState: null,
     householdInfo: {},
+    activityLogs: {},
+    accounts: {},
+    archetypes: {},
+    showVignettes: false,
+    vignette: null,
 };
 
+export const settingsSpec = {
+    toggleVignetteWindowKey: { default: { key: 'c' }, description: "Toggle Humans of the City Window" }
+}
+
+// how often somebody else from the city is picked
+const VIGNETTE_INTERVAL_MS = 15000;
+let vignetteInterval = null;
+
 export function render(state, setState) {
     return {};
 }
@@ -58,7 +71,49 @@ export function Shapes(props) {
 }
 
 export function Windows(props) {
-    const { inspectedBuilding, inspectedBuildingState, inspectedBuildingPinned, householdInfo, buildingPositions } = props.state.households;
+    return [
+        <VignetteWindow key="vignette" {...props} />,
+        <InspectionWindow key="inspection" {...props} />
+    ];
+}
+
+function VignetteWindow(props) {
+    const { showVignettes, vignette } = props.state.households;
+
+    if (showVignettes) {
+        if (!vignetteInterval) {
+            // the first call only finds out who there is to pick from
+            cbRustBrowser.sample_citizen();
+            setTimeout(() => cbRustBrowser.sample_citizen(), 500);
+            vignetteInterval = setInterval(() => cbRustBrowser.sample_citizen(), VIGNETTE_INTERVAL_MS);
+        }
+    } else {
+        if (vignetteInterval) {
+            clearInterval(vignetteInterval);
+            vignetteInterval = null;
+        }
+    }
+
+    return showVignettes && <div className="window vignette">
+        <h1>Humans of the City</h1>
+        {vignette
+            ? [<h3>{vignette.title}</h3>, <p>{vignette.text}</p>, <p>{fmtId(vignette.household)}</p>]
+            : <p>Looking for someone to tell you about...</p>}
+    </div>;
+}
+
+export function bindInputs(state, setState) {
+    const inputActions = {
+        "toggleVignetteWindow": () => setState(oldState => update(oldState, {
+            households: { showVignettes: { $apply: b => !b } }
+        })),
+    }
+
+    Mousetrap.bind(state.settings.households.toggleVignetteWindowKey.key, inputActions["toggleVignetteWindow"]);
+}
+
+function InspectionWindow(props) {
+    const { inspectedBuilding, inspectedBuildingState, inspectedBuildingPinned, householdInfo, activityLogs, accounts, buildingPositions } = props.state.households;
     if (props.state.uiMode == "inspection" && inspectedBuilding && buildingPositions[inspectedBuilding]) {
         const buildingPosition3d = [...buildingPositions[inspectedBuilding], 0.0];
 
@@ -73,7 +128,7 @@ export function Windows(props) {
         return <BuildingInfo
             buildingPosition2d={props.project3dTo2d(buildingPosition3d)}
             pinned={inspectedBuildingPinned}
-            {...{ inspectedBuilding, inspectedBuildingState, householdInfo, closeWindow }}
+            {...{ inspectedBuilding, inspectedBuildingState, householdInfo, activityLogs, accounts, closeWindow }}
         />
     } else {
         return null;
@@ -118,7 +173,7 @@ class BuildingInfo extends React.Component {
                 <div className="household-list">
                     {this.props.inspectedBuildingState.households.map(id => <div className="household">{[
                         <h3>{fmtId(id)}</h3>,
-                        this.props.householdInfo[id] && <HouseholdInfo core={this.props.householdInfo[id].core} id={id} here={this.props.inspectedBuilding} />
+                        this.props.householdInfo[id] && <HouseholdInfo core={this.props.householdInfo[id].core} account={this.props.accounts[id]} activities={this.props.activityLogs[id]} id={id} here={this.props.inspectedBuilding} />
                     ]}</div>)}
                 </div>
             ]}
@@ -126,17 +181,29 @@ class BuildingInfo extends React.Component {
     }
 }
 
+// keep in sync with cb_simulation/src/economy/households/health/mod.rs
+const SICKNESS_THRESHOLD = 0.5;
+
 function HouseholdInfo(props) {
-    const { resources, member_resources, member_tasks } = props.core;
+    const { resources, member_resources, member_tasks, member_education, member_health, member_plans, happiness } = props.core;
 
     return [
+        <p>Happiness: {(happiness * 100).toFixed(0)}%</p>,
         resources.entries.map(([resource, amount]) =>
             <p>{resource}: {amount.toFixed(2)}</p>
         ),
+        props.account && [
+            <p>Savings: {props.account.savings.toFixed(2)}</p>,
+            <p>Loan: {props.account.loan.toFixed(2)}{props.account.missed_repayments > 0 && ` (${props.account.missed_repayments} missed repayments)`}</p>
+        ],
         member_resources.map((memberResources, memberI) =>
             [
                 <h4>Member {memberI}</h4>,
                 <p><StateAndGoal here={props.here} state={member_tasks[memberI].state} goal={member_tasks[memberI].goal} /></p>,
+                <p>Education: {member_education[memberI].toFixed(1)} years</p>,
+                <p>Health: {(member_health[memberI].condition * 100).toFixed(0)}%{member_health[memberI].condition < SICKNESS_THRESHOLD && " (sick)"}</p>,
+                member_plans[memberI].slots.length > 0 && <DayPlan plan={member_plans[memberI]} />,
+                props.activities && props.activities[memberI] && <ActivityLog entries={props.activities[memberI]} />,
                 memberResources.entries.map(([resource, amount]) =>
                     <p>{resource}: {amount.toFixed(2)}</p>
                 ),
@@ -145,6 +212,54 @@ function HouseholdInfo(props) {
     ]
 }
 
+function fmtTimeOfDay(time) {
+    const hours = Math.floor(time.minutes_of_day / 60);
+    const minutes = time.minutes_of_day % 60;
+    return hours + ":" + (minutes < 10 ? "0" : "") + minutes;
+}
+
+// keep in sync with cb_time/src/units.rs
+const TICKS_PER_SIM_MINUTE = 180;
+const BEGINNING_TIME_OF_DAY = 7;
+
+function fmtActivity(activity) {
+    if (activity == "FoundNothing") {
+        return "found nothing";
+    } else if (activity.Searched) {
+        return "searched for " + activity.Searched.toLowerCase();
+    } else if (activity.FollowedPlan) {
+        return "followed plan for " + activity.FollowedPlan.toLowerCase();
+    } else if (activity.ContinuedChainedTrip) {
+        return "went on for " + activity.ContinuedChainedTrip.toLowerCase();
+    } else if (activity.Chose) {
+        return "chose " + activity.Chose[0].toLowerCase() + " at " + fmtId(activity.Chose[1].household);
+    } else if (activity.Traveled) {
+        return "trip for " + activity.Traveled[0].toLowerCase() + " took " + Math.round(activity.Traveled[1] / 60) + " min";
+    } else if (activity.TripFailed) {
+        return "trip for " + activity.TripFailed.toLowerCase() + " failed";
+    } else if (activity.TurnedAway) {
+        return "turned away when asking for " + activity.TurnedAway.toLowerCase();
+    } else {
+        return JSON.stringify(activity);
+    }
+}
+
+function ActivityLog(props) {
+    return <details>
+        <summary>Recent activity</summary>
+        {props.entries.slice().reverse().map(entry => {
+            const minutesOfDay = (BEGINNING_TIME_OF_DAY * 60 + Math.floor(entry.instant / TICKS_PER_SIM_MINUTE)) % (24 * 60);
+            return <p>{fmtTimeOfDay({ minutes_of_day: minutesOfDay })} {fmtActivity(entry.activity)}</p>;
+        })}
+    </details>;
+}
+
+function DayPlan(props) {
+    return <p>Plan: {props.plan.slots.map(slot =>
+        slot.activity + " " + fmtTimeOfDay(slot.time.start) + "-" + fmtTimeOfDay(slot.time.end) + (slot.started ? " (started)" : "")
+    ).join(", ")}</p>;
+}
+
 function StateAndGoal(props) {
     let statePart;
     let goalGerund = false;
@@ -161,7 +276,10 @@ function StateAndGoal(props) {
             statePart = "Currently";
             goalGerund = true;
         } else if (props.state.InTrip) {
-            statePart = "On the way to"
+            statePart = [
+                <a onClick={() => cbRustBrowser.select_trip(props.state.InTrip)}>On the way</a>,
+                " to"
+            ];
         } else {
             statePart = JSON.stringify(props)
         }
@@ -173,12 +291,26 @@ function StateAndGoal(props) {
                 goalPart = (goalGerund ? "working at " : "work at ") + fmtId(props.goal[1].household)
             } else if (props.goal[0] == "Wakefulness") {
                 goalPart = (goalGerund ? "sleeping at " : "sleep at ") + fmtId(props.goal[1].household)
+            } else if (props.goal[0] == "Companionship") {
+                goalPart = (goalGerund ? "spending time with family at " : "spend time with family at ") + fmtId(props.goal[1].household)
+            } else if (props.goal[0] == "Healthcare") {
+                goalPart = (goalGerund ? "getting treated at " : "get treated at ") + fmtId(props.goal[1].household)
+            } else if (props.goal[0] == "Education") {
+                goalPart = (goalGerund ? "attending school at " : "attend school at ") + fmtId(props.goal[1].household)
+            } else if (props.goal[0] == "Car") {
+                goalPart = (goalGerund ? "buying a car at " : "buy a car at ") + fmtId(props.goal[1].household)
+            } else if (props.goal[0] == "Bicycle") {
+                goalPart = (goalGerund ? "buying a bike at " : "buy a bike at ") + fmtId(props.goal[1].household)
+            } else if (props.goal[0] == "Fuel") {
+                goalPart = (goalGerund ? "refueling at " : "refuel at ") + fmtId(props.goal[1].household)
+            } else if (props.goal[0] == "Entertainment") {
+                goalPart = (goalGerund ? "relaxing at " : "relax at ") + fmtId(props.goal[1].household)
             } else {
                 goalPart = (goalGerund ? "getting " : "get ") + props.goal[0].toLowerCase() + " at " + fmtId(props.goal[1].household)
             }
-            return statePart + " " + goalPart + ".";
+            return [statePart, " " + goalPart + "."];
         } else {
-            return statePart + ".";
+            return [statePart, "."];
         }
     } else {
         return "Gone missing?"
@@ -61,10 +61,22 @@ impl BrowserHouseholdUIID {
         world.send(swarm, MSG_BrowserHouseholdUI_spawn(id, ));
         id
     }
+    
+    pub fn sample_citizen(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_BrowserHouseholdUI_sample_citizen());
+    }
 }
 
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_BrowserHouseholdUI_spawn(pub BrowserHouseholdUIID, );
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserHouseholdUI_sample_citizen();
+
+impl Into<ConfigUserID<HouseholdArchetype>> for BrowserHouseholdUIID {
+    fn into(self) -> ConfigUserID<HouseholdArchetype> {
+        ConfigUserID::from_raw(self.as_raw())
+    }
+}
 
 impl Into<HouseholdUIID> for BrowserHouseholdUIID {
     fn into(self) -> HouseholdUIID {
@@ -72,14 +84,28 @@ impl Into<HouseholdUIID> for BrowserHouseholdUIID {
     }
 }
 
+impl Into<AccountUIID> for BrowserHouseholdUIID {
+    fn into(self) -> AccountUIID {
+        AccountUIID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
+    ConfigUserID::<HouseholdArchetype>::register_implementor::<BrowserHouseholdUI>(system);
     HouseholdUIID::register_implementor::<BrowserHouseholdUI>(system);
+    AccountUIID::register_implementor::<BrowserHouseholdUI>(system);
     system.add_spawner::<BrowserHouseholdUI, _, _>(
         |&MSG_BrowserHouseholdUI_spawn(id, ), world| {
             BrowserHouseholdUI::spawn(id, world)
         }, false
     );
+    
+    system.add_handler::<BrowserHouseholdUI, _, _>(
+        |&MSG_BrowserHouseholdUI_sample_citizen(), instance, world| {
+            instance.sample_citizen(world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
@@ -1,8 +1,13 @@
-use kay::{World, ActorSystem, TypedID};
+use kay::{World, ActorSystem, TypedID, Actor};
 use stdweb::serde::Serde;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use stdweb::js_export;
-use SYSTEM;
+use {SYSTEM, local_origin};
+use compact::{CVec, CHashMap, COption, CString};
+use cb_util::config_manager::{Name, ConfigUser, ConfigUserID, ConfigManagerID};
+use cb_util::random::{seed, Rng};
+use economy::households::{HouseholdID, MemberIdx};
+use economy::households::archetypes::HouseholdArchetype;
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn get_household_info(household_id: Serde<::economy::households::HouseholdID>) {
@@ -11,20 +16,109 @@ pub fn get_household_info(household_id: Serde<::economy::households::HouseholdID
     household_id
         .0
         .get_ui_info(BrowserHouseholdUIID::local_first(world).into(), world);
+    household_id
+        .0
+        .get_activity_log(BrowserHouseholdUIID::local_first(world).into(), world);
+    ::economy::finance::BankID::global_first(world).get_account(
+        household_id.0,
+        BrowserHouseholdUIID::local_first(world).into(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn set_household_archetype(name: String, archetype: Serde<HouseholdArchetype>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    if let Ok(name) = Name::from(&name) {
+        ConfigManagerID::<HouseholdArchetype>::global_first(world).update_entry(
+            local_origin(),
+            name,
+            COption(Some(archetype.0)),
+            world,
+        );
+    }
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn sample_citizen() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    BrowserHouseholdUIID::local_first(world).sample_citizen(world);
 }
 
 #[derive(Compact, Clone)]
 pub struct BrowserHouseholdUI {
     id: BrowserHouseholdUIID,
+    archetypes: CHashMap<Name, HouseholdArchetype>,
+    // households that answered the last call for someone to tell about
+    vignette_candidates: CVec<HouseholdID>,
+    n_samples: u32,
 }
 
 impl BrowserHouseholdUI {
-    pub fn spawn(id: BrowserHouseholdUIID, _: &mut World) -> BrowserHouseholdUI {
-        BrowserHouseholdUI { id }
+    pub fn spawn(id: BrowserHouseholdUIID, world: &mut World) -> BrowserHouseholdUI {
+        let ui = BrowserHouseholdUI {
+            id,
+            archetypes: CHashMap::new(),
+            vignette_candidates: CVec::new(),
+            n_samples: 0,
+        };
+        ui.get_initial_config(world);
+        ui
+    }
+
+    // Tells about someone who answered the previous call and calls again,
+    // so the very first sample only collects candidates
+    pub fn sample_citizen(&mut self, world: &mut World) {
+        let mut rng = seed((self.id, self.n_samples));
+        if let Some(&household) = rng.choose(&self.vignette_candidates) {
+            household.get_vignette(rng.gen(), self.id_as(), world);
+        }
+
+        self.n_samples += 1;
+        self.vignette_candidates.clear();
+        HouseholdID::global_broadcast(world).offer_for_vignette(self.id_as(), world);
+    }
+}
+
+impl ConfigUser<HouseholdArchetype> for BrowserHouseholdUI {
+    fn local_cache(&mut self) -> &mut CHashMap<Name, HouseholdArchetype> {
+        &mut self.archetypes
+    }
+
+    fn on_config_change(
+        &mut self,
+        name: Name,
+        maybe_value: &COption<HouseholdArchetype>,
+        world: &mut World,
+    ) {
+        if let COption(Some(ref archetype)) = *maybe_value {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    households: {
+                        archetypes: {
+                            [@{name.as_str()}]: {"$set": @{Serde(archetype)}}
+                        }
+                    }
+                }));
+            }
+        } else {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    households: {
+                        archetypes: {"$unset": [@{name.as_str()}]}
+                    }
+                }));
+            }
+        }
+
+        self.apply_config_change(name, maybe_value, world);
     }
 }
 
 use economy::households::ui::{HouseholdUI, HouseholdUIID};
+use economy::households::activity_log::ActivityLogEntry;
 
 impl HouseholdUI for BrowserHouseholdUI {
     fn on_household_ui_info(
@@ -72,6 +166,10 @@ impl HouseholdUI for BrowserHouseholdUI {
                                 used_offers: @{Serde(&core.used_offers)},
                                 member_used_offers: @{Serde(&core.member_used_offers)},
                                 provided_offers: @{Serde(&core.provided_offers)},
+                                happiness: @{core.happiness},
+                                member_education: @{Serde(&core.member_education)},
+                                member_health: @{Serde(&core.member_health)},
+                                member_plans: @{Serde(&core.member_plans)},
                             }
                         }}
                     }
@@ -79,6 +177,70 @@ impl HouseholdUI for BrowserHouseholdUI {
             }));
         }
     }
+
+    fn on_household_activity_log(
+        &mut self,
+        id: ::economy::households::HouseholdID,
+        member_activities: &CVec<CVec<ActivityLogEntry>>,
+        _world: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                households: {
+                    activityLogs: {
+                        [@{Serde(id)}]: {"$set": @{Serde(member_activities)}}
+                    }
+                }
+            }));
+        }
+    }
+
+    fn on_vignette_candidate(&mut self, id: HouseholdID, _world: &mut World) {
+        self.vignette_candidates.push(id);
+    }
+
+    fn on_member_vignette(
+        &mut self,
+        id: HouseholdID,
+        member: MemberIdx,
+        title: &CString,
+        text: &CString,
+        _world: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                households: {
+                    vignette: {"$set": {
+                        household: @{Serde(id)},
+                        member: @{member.as_idx() as u32},
+                        title: @{Serde(title)},
+                        text: @{Serde(text)}
+                    }}
+                }
+            }));
+        }
+    }
+}
+
+use economy::finance::{Account, AccountUI, AccountUIID};
+
+impl AccountUI for BrowserHouseholdUI {
+    fn on_account_info(
+        &mut self,
+        household: ::economy::households::HouseholdID,
+        account: Account,
+        _world: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                households: {
+                    accounts: {
+                        [@{Serde(household)}]: {"$set": @{Serde(account)}}
+                    }
+                }
+            }));
+        }
+    }
 }
 
 mod kay_auto;
@@ -1,14 +1,17 @@
 import colors from '../colors';
 import renderOrder from '../renderOrder';
 import { RenderLayer } from "../browser_utils/Utils";
+import { AnnotationBatch, memoizedAnnotations } from '../annotations/Annotations';
+import { districtName } from '../annotations/names';
 import * as propMeshes from './propMeshes';
 
-const MATERIALS = ["WhiteWall", "TiledRoof", "FlatRoof", "FieldWheat", "FieldRows", "FieldPlant", "FieldMeadow", "WoodenFence", "MetalFence", "LotAsphalt"];
+const MATERIALS = ["WhiteWall", "PlasterWall", "BrickWall", "TiledRoof", "SlateRoof", "FlatRoof", "FieldWheat", "FieldRows", "FieldPlant", "FieldMeadow", "WoodenFence", "MetalFence", "LotAsphalt"];
 const PROP_TYPES = ["SmallWindow", "ShopWindowGlass", "ShopWindowBanner", "NarrowDoor", "WideDoor"];
 
 const initialRenderingState = {
     buildingMeshes: {},
     buildingProps: {},
+    debugFootprints: {},
     propMeshes: {
         SmallWindow: propMeshes.smallWindow,
         NarrowDoor: propMeshes.narrowDoor,
@@ -18,6 +21,7 @@ const initialRenderingState = {
     }
 };
 const materialInstances = {};
+const footprintInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.debugBuildingFootprint]);
 
 for (let material of MATERIALS) {
     initialRenderingState.buildingMeshes[material] = {};
@@ -29,7 +33,8 @@ for (let propType of PROP_TYPES) {
 }
 
 export const initialState = {
-    rendering: initialRenderingState
+    rendering: initialRenderingState,
+    buildingLabels: {}
 }
 
 import React from 'react';
@@ -56,5 +61,79 @@ export function Layers(props) {
                 instances: new Float32Array(Object.values(state.landUse.rendering.buildingProps[propType])
                     .reduce((allPropInstances, buildingPropInstances) => allPropInstances.concat(buildingPropInstances), []))
             }]} />
-    ));
+    )).concat(state.debug.geometryLayers.buildingFootprints ? [
+        <RenderLayer
+            key="debugFootprints"
+            decal={true}
+            renderOrder={renderOrder.debugGeometry}
+            batches={Object.values(state.landUse.rendering.debugFootprints).map(footprint => ({
+                mesh: footprint,
+                instances: footprintInstance
+            }))} />
+    ] : []);
+}
+
+// family houses are too numerous to be worth labelling
+const BUILDING_LABEL_TEXTS = {
+    GroceryShop: "Grocery Shop",
+    Field: "Farm",
+    Mill: "Mill",
+    Bakery: "Bakery",
+    School: "School",
+    Hospital: "Hospital",
+    CarDealer: "Car Dealer",
+    Restaurant: "Restaurant",
+    Park: "Park",
+    Cinema: "Cinema",
+    PowerPlant: "Power Plant",
+    Waterworks: "Waterworks",
+    TaxiDepot: "Taxi Depot",
+    DepartmentStore: "Department Store",
+    NeighboringTownConnection: "Neighboring Town",
+};
+
+// same grid as the simulation uses to aggregate statistics by district
+export const DISTRICT_SIZE = 1000;
+
+const buildingAnnotations = memoizedAnnotations(buildingLabels =>
+    Object.keys(buildingLabels)
+        .filter(id => BUILDING_LABEL_TEXTS[buildingLabels[id].style])
+        .map(id => ({
+            key: id,
+            position: buildingLabels[id].position,
+            text: BUILDING_LABEL_TEXTS[buildingLabels[id].style]
+        }))
+);
+
+// districts are labelled at the center of their buildings
+const districtAnnotations = memoizedAnnotations(buildingLabels => {
+    const districts = new Map();
+
+    for (let { position } of Object.values(buildingLabels)) {
+        const x = Math.floor(position[0] / DISTRICT_SIZE);
+        const y = Math.floor(position[1] / DISTRICT_SIZE);
+        const key = x + ":" + y;
+        const district = districts.get(key) || { x, y, sum: [0, 0], n: 0 };
+        district.sum = [district.sum[0] + position[0], district.sum[1] + position[1]];
+        district.n++;
+        districts.set(key, district);
+    }
+
+    return [...districts.entries()].map(([key, { x, y, sum, n }]) => ({
+        key,
+        position: [sum[0] / n, sum[1] / n],
+        text: districtName(x, y)
+    }));
+});
+
+export function Annotations(props) {
+    const { state } = props;
+
+    return [
+        <AnnotationBatch key="buildings" kind="building"
+            annotations={buildingAnnotations(state.landUse.buildingLabels)} />,
+        // once the player draws districts, their names replace the made up ones
+        state.districts.districts.length == 0 && <AnnotationBatch key="districts" kind="district"
+            annotations={districtAnnotations(state.landUse.buildingLabels)} />
+    ];
 }
\ No newline at end of file
@@ -4,15 +4,18 @@ use stdweb::serde::Serde;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use stdweb::js_export;
 use browser_utils::{to_js_mesh, flatten_instances};
+use michelangelo::Mesh;
 use SYSTEM;
 use cb_util::config_manager::{Name, ConfigUser, ConfigUserID};
-use ::std::collections::HashMap;
-use ::land_use::buildings::{BuildingID, BuildingStyle};
-use ::land_use::buildings::architecture::{build_building};
-use ::land_use::buildings::architecture::language::ArchitectureRule;
-use ::land_use::buildings::architecture::materials_and_props::{ALL_MATERIALS, ALL_PROP_TYPES};
-use ::land_use::zone_planning::Lot;
-use ::economy::households::HouseholdID;
+use std::collections::HashMap;
+use land_use::buildings::{BuildingID, BuildingStyle};
+use land_use::buildings::architecture::{build_building};
+use land_use::buildings::architecture::language::ArchitectureRule;
+use land_use::buildings::architecture::materials_and_props::{ALL_MATERIALS, ALL_PROP_TYPES};
+use land_use::zone_planning::Lot;
+use economy::households::HouseholdID;
+
+const DEBUG_LINE_WIDTH: f32 = 0.3;
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn get_building_info(building_id: Serde<BuildingID>) {
@@ -71,9 +74,17 @@ impl LandUseUI for BrowserLandUseUI {
         lot: &Lot,
         households: &CVec<HouseholdID>,
         style: BuildingStyle,
+        construction_progress: Option<f32>,
         world: &mut World,
     ) {
-        let result = build_building(lot, style, &self.architecture_rules, households, world);
+        let result = build_building(
+            lot,
+            style,
+            construction_progress,
+            &self.architecture_rules,
+            households,
+            world,
+        );
         match result {
             Ok(building_mesh) => {
                 let material_updates: ::stdweb::Object = building_mesh
@@ -111,14 +122,29 @@ impl LandUseUI for BrowserLandUseUI {
                         (prop_type.to_string(), material_update)
                     })
                     .collect::<HashMap<_, _>>()
-                    .into();;
+                    .into();
+
+                let footprint_mesh = Mesh::from_path_as_band(
+                    lot.area.primitives[0].boundary.path(),
+                    DEBUG_LINE_WIDTH,
+                    0.2,
+                );
 
                 js! {
                     window.cbReactApp.boundSetState(oldState => update(oldState, {
-                        landUse: {rendering: {
-                            buildingMeshes: @{material_updates},
-                            buildingProps: @{prop_updates}
-                        }},
+                        landUse: {
+                            rendering: {
+                                buildingMeshes: @{material_updates},
+                                buildingProps: @{prop_updates},
+                                debugFootprints: {[@{id.as_raw_string()}]: {
+                                    "$set": @{to_js_mesh(&footprint_mesh)}
+                                }}
+                            },
+                            buildingLabels: {[@{Serde(id)}]: {"$set": {
+                                position: @{Serde(lot.center_point())},
+                                style: @{Serde(style)}
+                            }}}
+                        },
                         households: {
                             buildingPositions: {[@{Serde(id)}]: {
                                 "$set": @{Serde(lot.center_point())}
@@ -153,10 +179,14 @@ impl LandUseUI for BrowserLandUseUI {
             .into();
         js! {
             window.cbReactApp.boundSetState(oldState => update(oldState, {
-                landUse: {rendering: {
-                    buildingMeshes: @{material_unsets},
-                    buildingProps: @{prop_unsets}
-                }},
+                landUse: {
+                    rendering: {
+                        buildingMeshes: @{material_unsets},
+                        buildingProps: @{prop_unsets},
+                        debugFootprints: {"$unset": [@{id.as_raw_string()}]}
+                    },
+                    buildingLabels: {"$unset": [@{Serde(id)}]}
+                },
                 households: {buildingPositions: {"$unset": [@{Serde(id)}]}}
             }));
         }
@@ -11,7 +11,8 @@ use stdweb::js_export;
 extern crate serde_derive;
 
 extern crate kay;
-use kay::{ActorSystem, TypedID};
+use kay::{ActorSystem, TypedID, MachineID};
+use cb_util::session::Origin;
 
 #[macro_use]
 extern crate compact_macros;
@@ -28,14 +29,36 @@ use std::panic;
 pub mod planning_browser;
 pub mod debug;
 pub mod time_browser;
+pub mod city_events_browser;
+pub mod scenarios_browser;
 pub mod households_browser;
 pub mod transport_browser;
 pub mod land_use_browser;
 pub mod vegetation_browser;
+pub mod water_browser;
+pub mod territory_browser;
+pub mod grid_layers_browser;
+pub mod timeline_browser;
+pub mod districts_browser;
+pub mod underground_browser;
+pub mod export_browser;
+pub mod osm_import_browser;
+pub mod blueprints_browser;
 pub mod browser_utils;
 
 // TODO: not thread safe for now
 static mut SYSTEM: *mut ActorSystem = 0 as *mut ActorSystem;
+static mut MACHINE_ID: u8 = 1;
+static mut SESSION_KEY: u64 = 0;
+
+// Sent along with changes to the simulation, so the server can keep us to our role,
+// see `cb_util::session`
+pub fn local_origin() -> Origin {
+    Origin {
+        machine: MachineID(unsafe { MACHINE_ID }),
+        key: unsafe { SESSION_KEY },
+    }
+}
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn start() {
@@ -63,9 +86,24 @@ pub fn start() {
 
     use stdweb::unstable::TryFrom;
 
+    let machine_id = u32::try_from(network_settings.remove("machineId").unwrap()).unwrap() as u8;
+    let n_machines = u32::try_from(network_settings.remove("nMachines").unwrap()).unwrap() as u8;
+    // too big for a JS number, so it comes as a string
+    let session_key = String::try_from(network_settings.remove("sessionKey").unwrap())
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+    unsafe {
+        MACHINE_ID = machine_id;
+        SESSION_KEY = session_key;
+    };
+
     let mut system = kay::ActorSystem::new(kay::Networking::new(
-        1,
-        vec![format!("{}:{}", server_host, 9999), "ws-client".to_owned()],
+        machine_id,
+        Some(format!("{}:{}", server_host, 9999))
+            .into_iter()
+            .chain((1..n_machines).map(|_| "ws-client".to_owned()))
+            .collect(),
         u32::try_from(network_settings.remove("batchMessageBytes").unwrap()).unwrap() as usize,
         u32::try_from(network_settings.remove("acceptableTurnDistance").unwrap()).unwrap() as usize,
         u32::try_from(network_settings.remove("skipTurnsPerTurnAhead").unwrap()).unwrap() as usize,
@@ -77,9 +115,18 @@ pub fn start() {
     planning_browser::setup(&mut system);
     transport_browser::setup(&mut system);
     time_browser::setup(&mut system);
+    city_events_browser::setup(&mut system);
+    scenarios_browser::setup(&mut system);
     land_use_browser::setup(&mut system);
     households_browser::setup(&mut system);
     vegetation_browser::setup(&mut system);
+    water_browser::setup(&mut system);
+    territory_browser::setup(&mut system);
+    grid_layers_browser::setup(&mut system);
+    timeline_browser::setup(&mut system);
+    districts_browser::setup(&mut system);
+    underground_browser::setup(&mut system);
+    export_browser::setup(&mut system);
 
     js! {
         window.cbTypeIdMapping = @{Serde(system.get_actor_type_id_to_name_mapping())}
@@ -91,9 +138,18 @@ pub fn start() {
     planning_browser::spawn(&mut system.world());
     transport_browser::spawn(&mut system.world());
     time_browser::spawn(&mut system.world());
+    city_events_browser::spawn(&mut system.world());
+    scenarios_browser::spawn(&mut system.world());
     land_use_browser::spawn(&mut system.world());
     households_browser::spawn(&mut system.world());
     vegetation_browser::spawn(&mut system.world());
+    water_browser::spawn(&mut system.world());
+    territory_browser::spawn(&mut system.world());
+    grid_layers_browser::spawn(&mut system.world());
+    timeline_browser::spawn(&mut system.world());
+    districts_browser::spawn(&mut system.world());
+    underground_browser::spawn(&mut system.world());
+    export_browser::spawn(&mut system.world());
 
     system.process_all_messages();
 
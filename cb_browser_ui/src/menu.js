@@ -1,6 +1,10 @@
 import React from 'react';
 import { Toolbar } from './toolbar';
 import { Settings } from './settings';
+import * as Export from './export_browser/Export';
+import * as OsmImport from './osm_import_browser/OsmImport';
+import * as Blueprints from './blueprints_browser/Blueprints';
+import * as Archetypes from './households_browser/Archetypes';
 import { Collapse, Checkbox, Tabs, Progress } from 'antd';
 import aePlayLogo from '../assets/ae_play.png';
 
@@ -111,6 +115,18 @@ export function Windows(props) {
             <TabPane tab="Settings &amp; Controls" key="settings">
                 <Settings currentSettings={state.settings} specs={settingSpecs} {...{ setState }} />
             </TabPane>
+            <TabPane tab="Export" key="export">
+                <Export.Panel state={state} setState={setState} />
+            </TabPane>
+            <TabPane tab="Import" key="import">
+                <OsmImport.Panel state={state} setState={setState} />
+            </TabPane>
+            <TabPane tab="Blueprints" key="blueprints">
+                <Blueprints.Panel state={state} setState={setState} />
+            </TabPane>
+            <TabPane tab="Households" key="households">
+                <Archetypes.Panel state={state} setState={setState} />
+            </TabPane>
         </Tabs>
     </div>;
 }
@@ -0,0 +1,270 @@
+import React from 'react';
+import update from 'immutability-helper';
+
+export const initialState = {
+    lastImport: null
+}
+
+// lanes in each direction for the road classes we import,
+// everything else (paths, service roads, tracks...) is left out
+const ROAD_CLASSES = {
+    motorway: 2, trunk: 2, primary: 2, secondary: 1, tertiary: 1,
+    unclassified: 1, residential: 1, living_street: 1,
+    motorway_link: 1, trunk_link: 1, primary_link: 1, secondary_link: 1, tertiary_link: 1
+};
+const MAX_LANES_PER_DIRECTION = 3;
+// everything not listed becomes a residential street
+const ROAD_CLASS_OF_TAG = {
+    motorway: "Highway", trunk: "Highway", primary: "Avenue", secondary: "Avenue"
+};
+
+const EARTH_RADIUS = 6371000;
+// the planner smooths roads within a few meters of their control points anyway
+const SIMPLIFICATION_TOLERANCE = 3.0;
+// control points closer than this, or roads shorter than this,
+// can't be turned into sensible road pieces by the planner
+const MIN_POINT_DISTANCE = 10.0;
+const MIN_ROAD_LENGTH = 20.0;
+// turns sharper than this can't be smoothed into arcs and split the road instead
+const MAX_TURN_ANGLE = 0.75 * Math.PI;
+// only small extracts make sense, the rest of the map is left out
+const MAX_DISTANCE_FROM_CENTER = 3000.0;
+
+function parseOsm(text) {
+    const doc = new DOMParser().parseFromString(text, "application/xml");
+    if (doc.getElementsByTagName("parsererror").length) {
+        throw new Error("Not a valid OpenStreetMap XML file");
+    }
+
+    const nodes = new Map();
+    for (let node of doc.getElementsByTagName("node")) {
+        nodes.set(node.getAttribute("id"), [parseFloat(node.getAttribute("lat")), parseFloat(node.getAttribute("lon"))]);
+    }
+
+    const ways = [];
+    for (let way of doc.getElementsByTagName("way")) {
+        const tags = {};
+        for (let tag of way.getElementsByTagName("tag")) {
+            tags[tag.getAttribute("k")] = tag.getAttribute("v");
+        }
+        const nodeIds = Array.from(way.getElementsByTagName("nd")).map(nd => nd.getAttribute("ref"));
+        ways.push({ tags, nodeIds });
+    }
+
+    const bounds = doc.getElementsByTagName("bounds")[0];
+    return { nodes, ways, bounds };
+}
+
+// Equirectangular projection around the center of the extract,
+// which is precise enough at the scale of a city
+function projection(nodes, bounds) {
+    let latCenter, lonCenter;
+    if (bounds) {
+        latCenter = (parseFloat(bounds.getAttribute("minlat")) + parseFloat(bounds.getAttribute("maxlat"))) / 2;
+        lonCenter = (parseFloat(bounds.getAttribute("minlon")) + parseFloat(bounds.getAttribute("maxlon"))) / 2;
+    } else {
+        const latLons = Array.from(nodes.values());
+        latCenter = latLons.reduce((sum, [lat]) => sum + lat, 0) / latLons.length;
+        lonCenter = latLons.reduce((sum, [, lon]) => sum + lon, 0) / latLons.length;
+    }
+
+    const toRadians = Math.PI / 180;
+    const cosLatCenter = Math.cos(latCenter * toRadians);
+
+    return ([lat, lon]) => [
+        EARTH_RADIUS * (lon - lonCenter) * toRadians * cosLatCenter,
+        EARTH_RADIUS * (lat - latCenter) * toRadians
+    ];
+}
+
+function lanesFor(tags) {
+    const lanesPerDirection = ROAD_CLASSES[tags.highway];
+    const oneway = tags.oneway === "yes" || tags.oneway === "1" || tags.oneway === "-1"
+        || (tags.highway === "motorway" && tags.oneway !== "no")
+        || tags.junction === "roundabout";
+    const taggedLanes = parseInt(tags.lanes, 10);
+
+    let forward = lanesPerDirection;
+    if (taggedLanes > 0) {
+        forward = oneway ? taggedLanes : Math.ceil(taggedLanes / 2);
+    }
+    forward = Math.min(forward, MAX_LANES_PER_DIRECTION);
+
+    return {
+        forward,
+        backward: oneway ? 0 : forward,
+        reversed: tags.oneway === "-1",
+        roadClass: ROAD_CLASS_OF_TAG[tags.highway] || "Residential"
+    };
+}
+
+function distance(a, b) {
+    return Math.hypot(b[0] - a[0], b[1] - a[1]);
+}
+
+function distanceToSegment(point, start, end) {
+    const length = distance(start, end);
+    if (length === 0) {
+        return distance(point, start);
+    }
+    const t = Math.max(0, Math.min(1,
+        ((point[0] - start[0]) * (end[0] - start[0]) + (point[1] - start[1]) * (end[1] - start[1])) / (length * length)
+    ));
+    return distance(point, [start[0] + t * (end[0] - start[0]), start[1] + t * (end[1] - start[1])]);
+}
+
+// Douglas-Peucker
+function simplify(points, tolerance) {
+    if (points.length < 3) {
+        return points;
+    }
+
+    let maxDistance = 0;
+    let maxIdx = 0;
+    for (let i = 1; i < points.length - 1; i++) {
+        const d = distanceToSegment(points[i], points[0], points[points.length - 1]);
+        if (d > maxDistance) {
+            maxDistance = d;
+            maxIdx = i;
+        }
+    }
+
+    if (maxDistance <= tolerance) {
+        return [points[0], points[points.length - 1]];
+    }
+
+    return [
+        ...simplify(points.slice(0, maxIdx + 1), tolerance).slice(0, -1),
+        ...simplify(points.slice(maxIdx), tolerance)
+    ];
+}
+
+function withoutClosePoints(points) {
+    const kept = [points[0]];
+    for (let point of points.slice(1)) {
+        if (distance(kept[kept.length - 1], point) >= MIN_POINT_DISTANCE) {
+            kept.push(point);
+        }
+    }
+    // always keep the actual end, so connected roads still meet
+    const end = points[points.length - 1];
+    if (kept.length > 1 && kept[kept.length - 1] !== end) {
+        kept[kept.length - 1] = end;
+    }
+    return kept;
+}
+
+function turnAngle(before, at, after) {
+    const angleIn = Math.atan2(at[1] - before[1], at[0] - before[0]);
+    const angleOut = Math.atan2(after[1] - at[1], after[0] - at[0]);
+    const turn = Math.abs(angleOut - angleIn) % (2 * Math.PI);
+    return turn > Math.PI ? 2 * Math.PI - turn : turn;
+}
+
+function splitAtSharpTurns(points) {
+    const pieces = [[points[0]]];
+    for (let i = 1; i < points.length; i++) {
+        pieces[pieces.length - 1].push(points[i]);
+        if (i < points.length - 1 && turnAngle(points[i - 1], points[i], points[i + 1]) > MAX_TURN_ANGLE) {
+            pieces.push([points[i]]);
+        }
+    }
+    return pieces;
+}
+
+// closed ways (like roundabouts) would start and end in the same spot,
+// which the planner can't tell apart from a road that doubles back
+function splitLoop(points) {
+    if (points.length > 3 && distance(points[0], points[points.length - 1]) < MIN_POINT_DISTANCE) {
+        const middle = Math.floor(points.length / 2);
+        return [points.slice(0, middle + 1), [...points.slice(middle, -1), points[0]]];
+    }
+    return [points];
+}
+
+function roadLength(points) {
+    return points.slice(1).reduce((sum, point, i) => sum + distance(points[i], point), 0);
+}
+
+export function convertOsm(text) {
+    const { nodes, ways, bounds } = parseOsm(text);
+    const project = projection(nodes, bounds);
+
+    const roads = [];
+    let nSkipped = 0;
+
+    for (let way of ways) {
+        if (!ROAD_CLASSES[way.tags.highway] || way.tags.area === "yes") {
+            continue;
+        }
+
+        const latLons = way.nodeIds.map(id => nodes.get(id));
+        const points = latLons.filter(latLon => latLon).map(project);
+
+        if (latLons.length !== points.length
+            || points.some(([x, y]) => Math.hypot(x, y) > MAX_DISTANCE_FROM_CENTER)) {
+            nSkipped++;
+            continue;
+        }
+
+        const { forward, backward, reversed, roadClass } = lanesFor(way.tags);
+        const directedPoints = reversed ? points.reverse() : points;
+
+        for (let loopPiece of splitLoop(directedPoints)) {
+            const cleaned = withoutClosePoints(simplify(loopPiece, SIMPLIFICATION_TOLERANCE));
+            for (let piece of splitAtSharpTurns(cleaned)) {
+                if (piece.length < 2 || roadLength(piece) < MIN_ROAD_LENGTH) {
+                    nSkipped++;
+                    continue;
+                }
+                roads.push({
+                    points: piece,
+                    n_lanes_forward: forward,
+                    n_lanes_backward: backward,
+                    class: roadClass
+                });
+            }
+        }
+    }
+
+    return { roads, nSkipped };
+}
+
+function importFile(file, projectId, setState) {
+    const reader = new FileReader();
+    reader.onload = () => {
+        let lastImport;
+        try {
+            const { roads, nSkipped } = convertOsm(reader.result);
+            cbRustBrowser.import_roads(projectId, roads);
+            lastImport = { fileName: file.name, nRoads: roads.length, nSkipped };
+        } catch (e) {
+            lastImport = { fileName: file.name, error: e.message };
+        }
+        setState(oldState => update(oldState, { osmImport: { lastImport: { $set: lastImport } } }));
+    };
+    reader.readAsText(file);
+}
+
+export function Panel(props) {
+    const { state, setState } = props;
+    const { lastImport } = state.osmImport;
+    const projectId = state.planning.currentProject;
+
+    return <div>
+        <p>Imports the streets of a small OpenStreetMap extract (.osm) into the current project,
+            to start from a real street layout. The extract is centered on the middle of the map.</p>
+        <p>Paths, service roads and anything further than {MAX_DISTANCE_FROM_CENTER / 1000}km from the center are left out,
+            as are pieces of road that are too short or too winding to be planned.</p>
+        {projectId
+            ? <input type="file" accept=".osm,.xml" onChange={e => {
+                e.target.files[0] && importFile(e.target.files[0], projectId, setState);
+                e.target.value = "";
+            }} />
+            : <p><em>Open or start a project in planning mode to import into it.</em></p>}
+        {lastImport && (lastImport.error
+            ? <p>Couldn't import {lastImport.fileName}: {lastImport.error}</p>
+            : <p>Imported {lastImport.nRoads} roads from {lastImport.fileName}
+                {lastImport.nSkipped ? ` (skipped ${lastImport.nSkipped})` : ""}.</p>)}
+    </div>;
+}
@@ -0,0 +1,64 @@
+use kay::TypedID;
+use stdweb::serde::Serde;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use stdweb::js_export;
+use {SYSTEM, local_origin};
+
+use descartes::P2;
+use cb_planning::GestureID;
+use cb_planning::plan_manager::ProjectID;
+use planning::{CBPlanManagerID, CBGestureIntent};
+use transport::transport_planning::{RoadIntent, RoadClass};
+
+// A street as it comes out of the importer: already projected into
+// local meters, simplified and checked against what the planner accepts
+#[derive(Deserialize)]
+pub struct ImportedRoad {
+    points: Vec<P2>,
+    n_lanes_forward: u8,
+    n_lanes_backward: u8,
+    class: RoadClass,
+}
+
+// Each road becomes one road gesture in the given project,
+// added the same way as if it had been drawn by hand
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn import_roads(project_id: Serde<ProjectID>, roads: Serde<Vec<ImportedRoad>>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+
+    let plan_manager = CBPlanManagerID::global_first(world);
+
+    for road in roads.0 {
+        if road.points.len() < 2 {
+            continue;
+        }
+
+        let id = GestureID::new();
+        plan_manager.start_new_gesture(
+            local_origin(),
+            project_id.0,
+            id,
+            CBGestureIntent::Road(RoadIntent::new(
+                road.n_lanes_forward,
+                road.n_lanes_backward,
+                road.class,
+            )),
+            road.points[0],
+            world,
+        );
+
+        let last_idx = road.points.len() - 1;
+        for (idx, point) in road.points.iter().enumerate().skip(1) {
+            plan_manager.add_control_point(
+                local_origin(),
+                project_id.0,
+                id,
+                *point,
+                true,
+                idx == last_idx,
+                world,
+            );
+        }
+    }
+}
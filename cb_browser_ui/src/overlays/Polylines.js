@@ -0,0 +1,142 @@
+import React from 'react';
+import { RenderLayer } from '../browser_utils/Utils';
+
+// Flat lines drawn onto the ground, for highlighting routes, measurements
+// and everything else that is best shown as a path over the world
+
+// sharp corners would make mitered joins shoot out far beyond the line
+const MAX_MITER_SCALE = 3.0;
+
+function normal(from, to) {
+    const dx = to[0] - from[0];
+    const dy = to[1] - from[1];
+    const length = Math.hypot(dx, dy) || 1.0;
+    return [-dy / length, dx / length];
+}
+
+// Splits a polyline into the pieces of a dashed line
+export function dashPolyline(points, dashLength, gapLength) {
+    const dashes = [];
+    let currentDash = null;
+    let distanceInPattern = 0;
+
+    for (let i = 1; i < points.length; i++) {
+        const from = points[i - 1];
+        const to = points[i];
+        const segmentLength = Math.hypot(to[0] - from[0], to[1] - from[1]);
+        let distanceOnSegment = 0;
+
+        while (distanceOnSegment < segmentLength) {
+            const inDash = distanceInPattern < dashLength;
+            const untilSwitch = inDash ? dashLength - distanceInPattern : dashLength + gapLength - distanceInPattern;
+            const step = Math.min(untilSwitch, segmentLength - distanceOnSegment);
+            const t = (distanceOnSegment + step) / segmentLength;
+            const point = [from[0] + t * (to[0] - from[0]), from[1] + t * (to[1] - from[1])];
+
+            if (inDash) {
+                if (!currentDash) {
+                    const tStart = distanceOnSegment / segmentLength;
+                    currentDash = [[from[0] + tStart * (to[0] - from[0]), from[1] + tStart * (to[1] - from[1])]];
+                }
+                currentDash.push(point);
+            } else if (currentDash) {
+                dashes.push(currentDash);
+                currentDash = null;
+            }
+
+            distanceOnSegment += step;
+            distanceInPattern = (distanceInPattern + step) % (dashLength + gapLength);
+        }
+    }
+
+    if (currentDash) {
+        dashes.push(currentDash);
+    }
+
+    return dashes;
+}
+
+// Builds a flat band mesh of the given width along a polyline
+export function polylineMesh(points, width) {
+    const vertices = new Float32Array(points.length * 2 * 3);
+    const indices = new Uint16Array(Math.max(points.length - 1, 0) * 6);
+    const halfWidth = width / 2;
+
+    for (let i = 0; i < points.length; i++) {
+        const before = i > 0 ? normal(points[i - 1], points[i]) : null;
+        const after = i < points.length - 1 ? normal(points[i], points[i + 1]) : null;
+        let offset = before && after
+            ? [before[0] + after[0], before[1] + after[1]]
+            : (before || after);
+        const offsetLength = Math.hypot(offset[0], offset[1]) || 1.0;
+        offset = [offset[0] / offsetLength, offset[1] / offsetLength];
+        const miterScale = before && after
+            ? Math.min(1.0 / Math.max(offset[0] * after[0] + offset[1] * after[1], 1e-3), MAX_MITER_SCALE)
+            : 1.0;
+
+        const [x, y] = points[i];
+        const dx = offset[0] * halfWidth * miterScale;
+        const dy = offset[1] * halfWidth * miterScale;
+        vertices.set([x + dx, y + dy, 0.0, x - dx, y - dy, 0.0], i * 6);
+
+        if (i > 0) {
+            const left = 2 * i;
+            indices.set([left - 2, left - 1, left, left - 1, left + 1, left], (i - 1) * 6);
+        }
+    }
+
+    return { vertices, indices };
+}
+
+function concatMeshes(meshes) {
+    const nVertexFloats = meshes.reduce((sum, mesh) => sum + mesh.vertices.length, 0);
+    const nIndices = meshes.reduce((sum, mesh) => sum + mesh.indices.length, 0);
+    const vertices = new Float32Array(nVertexFloats);
+    const indices = new Uint16Array(nIndices);
+    let vertexOffset = 0;
+    let indexOffset = 0;
+
+    for (let mesh of meshes) {
+        vertices.set(mesh.vertices, vertexOffset);
+        indices.set(mesh.indices.map(index => index + vertexOffset / 3), indexOffset);
+        vertexOffset += mesh.vertices.length;
+        indexOffset += mesh.indices.length;
+    }
+
+    return { vertices, indices };
+}
+
+// meshes are only rebuilt when a polyline's points actually changed
+const meshCache = new WeakMap();
+
+function cachedMesh(polyline) {
+    const cacheKey = polyline.width + ":" + (polyline.dash ? polyline.dash.join(",") : "");
+    let cached = meshCache.get(polyline.points);
+
+    if (!cached || cached.key !== cacheKey) {
+        const pieces = polyline.dash
+            ? dashPolyline(polyline.points, polyline.dash[0], polyline.dash[1])
+            : [polyline.points];
+        cached = {
+            key: cacheKey,
+            mesh: concatMeshes(pieces.filter(piece => piece.length > 1).map(piece => polylineMesh(piece, polyline.width)))
+        };
+        meshCache.set(polyline.points, cached);
+    }
+
+    return cached.mesh;
+}
+
+// Renders polylines of the shape { points: [[x, y], ...], width, color, dash?: [dashLength, gapLength] }
+// as one decal layer. Keep the points arrays stable to avoid rebuilding meshes each frame
+export function PolylineOverlay(props) {
+    const { polylines, renderOrder } = props;
+
+    return <RenderLayer
+        renderOrder={renderOrder}
+        decal={true}
+        batches={polylines.filter(polyline => polyline.points.length > 1).map(polyline => ({
+            mesh: cachedMesh(polyline),
+            instances: new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...polyline.color])
+        }))} />;
+}
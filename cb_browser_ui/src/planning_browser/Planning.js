@@ -4,6 +4,8 @@ import { vec3, mat4 } from 'gl-matrix';
 import uuid from '../uuid';
 import { solidColorShader } from 'monet';
 import * as PlanningMenu from './PlanningMenu';
+import * as Blueprints from '../blueprints_browser/Blueprints';
+import { snapRoadPoint, guidePoints } from './snapping';
 export const Tools = PlanningMenu.Tools;
 import React from 'react'
 import { RenderLayer, Interactive3DShape } from '../browser_utils/Utils';
@@ -48,6 +50,12 @@ export const initialState = {
         addToEnd: true,
         previousClick: null,
     },
+    zoneDemand: null,
+    snapGuides: [],
+    implementationHistory: {
+        nUndoable: 0,
+        nRedoable: 0,
+    },
 };
 
 export const settingsSpec = {
@@ -66,7 +74,17 @@ export const settingsSpec = {
             key: /Mac|iPod|iPhone|iPad/.test(navigator.platform) ? 'command+shift+z' : 'ctrl+shift+z'
         }, description: "Redo Plan Step"
     },
-    finishGestureDistance: { default: 3.0, description: "Finish Gesture Double-Click Distance", min: 0.5, max: 10.0, step: 0.1 }
+    deleteGestureKey: {
+        default: {
+            key: /Mac|iPod|iPhone|iPad/.test(navigator.platform) ? 'backspace' : 'del'
+        }, description: "Delete Hovered Road Or Zone"
+    },
+    finishGestureDistance: { default: 3.0, description: "Finish Gesture Double-Click Distance", min: 0.5, max: 10.0, step: 0.1 },
+    snapToGrid: { default: false, description: "Snap Road Points To Grid" },
+    snapGridSize: { default: 10, description: "Road Grid Size (m)", min: 1, max: 50, step: 1 },
+    showGuides: { default: true, description: "Parallel/Perpendicular Road Guides" },
+    snapCurveRadii: { default: true, description: "Snap Road Curves To Round Radii" },
+    constrainedAngleStep: { default: 15, description: "Road Angle Step While Holding Shift (°)", min: 15, max: 90, step: 15 }
 }
 
 // STATE MUTATING ACTIONS
@@ -169,8 +187,8 @@ function splitGesture(projectId, gestureId, splitAt, doneSplitting) {
     });
 }
 
-function setNLanes(projectId, gestureId, nLanesForward, nLanesBackward, doneChanging) {
-    cbRustBrowser.set_n_lanes(projectId, gestureId, nLanesForward, nLanesBackward, doneChanging);
+function setNLanes(projectId, gestureId, nLanesForward, nLanesBackward, roadClass, level, bikeLanes, doneChanging) {
+    cbRustBrowser.set_n_lanes(projectId, gestureId, nLanesForward, nLanesBackward, roadClass, level, bikeLanes, doneChanging);
 
     return oldState => update(oldState, {
         planning: {
@@ -179,6 +197,37 @@ function setNLanes(projectId, gestureId, nLanesForward, nLanesBackward, doneChan
     });
 }
 
+function setSnapGuides(guides) {
+    return oldState => oldState.planning.snapGuides.length || guides.length
+        ? update(oldState, { planning: { snapGuides: { $set: guides } } })
+        : oldState;
+}
+
+// Snaps a point of a road gesture that is being drawn or moved,
+// other gestures are drawn freely
+function snapped(state, gestures, point, modifiers, gestureId, previousPoints) {
+    const gesture = gestureId && gestures[gestureId];
+    const isRoad = gesture ? gesture.intent.Road : (state.planning.canvasMode.intent && state.planning.canvasMode.intent.Road);
+
+    if (!isRoad) {
+        return { point, guides: [] };
+    }
+
+    const snap = snapRoadPoint(point, {
+        gestures,
+        gestureId,
+        previousPoints,
+        constrained: modifiers && modifiers.shift,
+        settings: state.settings.planning
+    });
+    return { point: [snap.point[0], snap.point[1], 0.0], guides: snap.guides };
+}
+
+function previousPointsOf(state, gestureId, addToEnd) {
+    const gesture = getGestureAsOf(state, state.planning.currentProject, gestureId);
+    return gesture ? (addToEnd ? gesture.points : [...gesture.points].reverse()) : [];
+}
+
 function finishGesture(projectId, gestureId) {
     return oldState => update(oldState, {
         planning: {
@@ -215,6 +264,22 @@ void main() {
     }
 }`}));
 
+// Planned but unbuilt geometry is drawn like a ghost: every other pixel is left out,
+// so whatever is actually built there right now still shows through
+const ghostShader = {
+    vertex: solidColorShader.vertex,
+    fragment: `
+precision mediump float;
+varying vec3 p;
+varying vec3 color;
+void main() {
+    if (mod(floor(gl_FragCoord.x) + floor(gl_FragCoord.y), 2.0) < 1.0) {
+        discard;
+    } else {
+        gl_FragColor = vec4(pow(color, vec3(1.0/2.2)), 1.0);
+    }
+}`};
+
 const shadersForLandUses = {
     Residential: stripedShaders[0],
     Commercial: stripedShaders[1],
@@ -224,20 +289,91 @@ const shadersForLandUses = {
     Administrative: stripedShaders[2]
 };
 
+function previewLayers(state) {
+    const { lanesToConstructGroups,
+        lanesToConstructMarkerGroups,
+        lanesToConstructMarkerGapsGroups,
+        zoneGroups, zoneOutlineGroups,
+        buildingOutlinesGroup } = state.planning.rendering.currentPreview;
+
+    return [
+        <RenderLayer renderOrder={renderOrder.addedGesturesAsphalt}
+            decal={true}
+            shader={ghostShader}
+            batches={[...lanesToConstructGroups.values()].map(groupMesh => ({
+                mesh: groupMesh,
+                instances: plannedAsphaltInstance
+            }))} />,
+        <RenderLayer renderOrder={renderOrder.addedGesturesMarker}
+            decal={true}
+            shader={ghostShader}
+            batches={[...lanesToConstructMarkerGroups.values()].map(groupMesh => ({
+                mesh: groupMesh,
+                instances: plannedRoadMarkerInstance
+            }))} />,
+        <RenderLayer renderOrder={renderOrder.addedGesturesMarkerGap}
+            decal={true}
+            shader={ghostShader}
+            batches={[...lanesToConstructMarkerGapsGroups.values()].map(groupMesh => ({
+                mesh: groupMesh,
+                instances: plannedAsphaltInstance
+            }))} />,
+        [...zoneGroups.entries()].map(([landUse, groups]) => <RenderLayer renderOrder={renderOrder.addedGesturesZones}
+            decal={true}
+            shader={ghostShader}
+            batches={[...groups.values()].map(groupMesh => ({
+                mesh: groupMesh,
+                instances: landUseInstances.get(landUse)
+            }))} />
+        ),
+        [...zoneGroups.entries()].reverse().map(([landUse, groups]) => <RenderLayer renderOrder={renderOrder.addedGesturesZonesStipple}
+            decal={true}
+            shader={shadersForLandUses[landUse]}
+            batches={[...groups.values()].map(groupMesh => ({
+                mesh: groupMesh,
+                instances: landUseInstances.get(landUse)
+            }))} />
+        ),
+        [...zoneOutlineGroups.entries()].map(([landUse, groups]) => <RenderLayer renderOrder={renderOrder.addedGesturesZonesOutlines}
+            decal={true}
+            batches={[...groups.values()].map(groupMesh => ({
+                mesh: groupMesh,
+                instances: landUseInstances.get(landUse)
+            }))} />
+        ),
+        <RenderLayer renderOrder={renderOrder.buildingOutlines}
+            decal={true}
+            batches={[...buildingOutlinesGroup.values()].map(groupMesh => ({
+                mesh: groupMesh,
+                instances: buildingOutlinesInstance
+            }))} />
+    ];
+}
+
 // TODO: share constants with Rust somehow
 const LANE_DISTANCE = 0.8 * 3.9;
+// how far guides are drawn from the point they go through, as dots this far apart
+const GUIDE_LENGTH = 100.0;
+const GUIDE_DOT_SPACING = 4.0;
 
 export function ShapesAndLayers(props) {
     const { state, setState } = props;
     const controlPointsInstances = [];
     const controlPointsInteractables = [];
 
-    if (state.uiMode != "planning" || !state.planning.currentProject) {
+    if (!state.planning.currentProject) {
         return null;
     }
 
+    // the plan stays visible outside of planning mode, just not editable
+    if (state.uiMode != "planning") {
+        return previewLayers(state);
+    }
+
+    let gestures = {};
+
     if (state.planning) {
-        let gestures = Object.keys(state.planning.master.gestures).map(gestureId =>
+        gestures = Object.keys(state.planning.master.gestures).map(gestureId =>
             ({ [gestureId]: Object.assign(state.planning.master.gestures[gestureId][0], { fromMaster: true }) })
         ).concat((state.planning.currentProject && state.planning.projects[state.planning.currentProject])
             ? state.planning.projects[state.planning.currentProject].undoable_history
@@ -250,10 +386,16 @@ export function ShapesAndLayers(props) {
         for (let gestureId of Object.keys(gestures)) {
             const gesture = gestures[gestureId];
 
+            if (gesture.deleted) {
+                continue;
+            }
+
             for (let [pointIdx, point] of gesture.points.entries()) {
 
-                let isRelevant = (gesture.intent.Road && state.planning.planningMode === "roads")
-                    || (gesture.intent.Zone && state.planning.planningMode === "zoning");
+                let isRelevant = ((gesture.intent.Road || gesture.intent === "TurnBan" || gesture.intent === "Roundabout") && state.planning.planningMode === "roads")
+                    || (gesture.intent.Zone && state.planning.planningMode === "zoning")
+                    || (gesture.intent === "Park" && state.planning.planningMode === "parks")
+                    || (gesture.intent.Conduit && state.planning.planningMode === "conduits");
 
                 if (isRelevant) {
                     let isHovered = gestureId == hoveredGestureId && pointIdx == hoveredPointIdx;
@@ -313,10 +455,12 @@ export function ShapesAndLayers(props) {
                                             }
                                         }))
                                     } else {
-                                        setState(moveControlPoint(state.planning.currentProject, gestureId, pointIdx, e.drag.end, true));
+                                        const { point } = snapped(state, gestures, e.drag.end, e.modifiers, gestureId, []);
+                                        setState(moveControlPoint(state.planning.currentProject, gestureId, pointIdx, point, true));
                                     }
                                 } else if (e.drag.now) {
-                                    setState(moveControlPoint(state.planning.currentProject, gestureId, pointIdx, e.drag.now, false));
+                                    const { point } = snapped(state, gestures, e.drag.now, e.modifiers, gestureId, []);
+                                    setState(moveControlPoint(state.planning.currentProject, gestureId, pointIdx, point, false));
                                 }
                             }
                         }
@@ -330,7 +474,7 @@ export function ShapesAndLayers(props) {
 
     if (state.planning.planningMode === "roads") {
         for (let gestureId of Object.keys(state.planning.rendering.roadInfos)) {
-            let { centerLine, outline, nLanesForward, nLanesBackward } = state.planning.rendering.roadInfos[gestureId];
+            let { centerLine, outline, nLanesForward, nLanesBackward, roadClass, level, bikeLanes } = state.planning.rendering.roadInfos[gestureId];
 
             roadCenterInteractables.push(<Interactive3DShape
                 id={gestureId + "insert"}
@@ -438,7 +582,7 @@ export function ShapesAndLayers(props) {
                                 newNLanesBackward = Math.max(0.0, Math.round(-orthogonalDistance / 3.0));
                             }
 
-                            setState(setNLanes(state.planning.currentProject, gestureId, newNLanesForward, newNLanesBackward, e.drag.end ? true : false));
+                            setState(setNLanes(state.planning.currentProject, gestureId, newNLanesForward, newNLanesBackward, roadClass, level, bikeLanes, e.drag.end ? true : false));
                         }
                     }
                     if (e.hover) {
@@ -477,59 +621,8 @@ export function ShapesAndLayers(props) {
         }
     }
 
-    const { lanesToConstructGroups,
-        lanesToConstructMarkerGroups,
-        lanesToConstructMarkerGapsGroups,
-        zoneGroups, zoneOutlineGroups,
-        buildingOutlinesGroup } = state.planning.rendering.currentPreview;
-
     const layers = [
-        <RenderLayer renderOrder={renderOrder.addedGesturesAsphalt}
-            decal={true}
-            batches={[...lanesToConstructGroups.values()].map(groupMesh => ({
-                mesh: groupMesh,
-                instances: plannedAsphaltInstance
-            }))} />,
-        <RenderLayer renderOrder={renderOrder.addedGesturesMarker}
-            decal={true}
-            batches={[...lanesToConstructMarkerGroups.values()].map(groupMesh => ({
-                mesh: groupMesh,
-                instances: plannedRoadMarkerInstance
-            }))} />,
-        <RenderLayer renderOrder={renderOrder.addedGesturesMarkerGap}
-            decal={true}
-            batches={[...lanesToConstructMarkerGapsGroups.values()].map(groupMesh => ({
-                mesh: groupMesh,
-                instances: plannedAsphaltInstance
-            }))} />,
-        [...zoneGroups.entries()].map(([landUse, groups]) => <RenderLayer renderOrder={renderOrder.addedGesturesZones}
-            decal={true}
-            batches={[...groups.values()].map(groupMesh => ({
-                mesh: groupMesh,
-                instances: landUseInstances.get(landUse)
-            }))} />
-        ),
-        [...zoneGroups.entries()].reverse().map(([landUse, groups]) => <RenderLayer renderOrder={renderOrder.addedGesturesZonesStipple}
-            decal={true}
-            shader={shadersForLandUses[landUse]}
-            batches={[...groups.values()].map(groupMesh => ({
-                mesh: groupMesh,
-                instances: landUseInstances.get(landUse)
-            }))} />
-        ),
-        [...zoneOutlineGroups.entries()].map(([landUse, groups]) => <RenderLayer renderOrder={renderOrder.addedGesturesZonesOutlines}
-            decal={true}
-            batches={[...groups.values()].map(groupMesh => ({
-                mesh: groupMesh,
-                instances: landUseInstances.get(landUse)
-            }))} />
-        ),
-        <RenderLayer renderOrder={renderOrder.buildingOutlines}
-            decal={true}
-            batches={[...buildingOutlinesGroup.values()].map(groupMesh => ({
-                mesh: groupMesh,
-                instances: buildingOutlinesInstance
-            }))} />,
+        previewLayers(state),
         <RenderLayer renderOrder={renderOrder.gestureInteractables}
             decal={true}
             batches={[{
@@ -548,7 +641,27 @@ export function ShapesAndLayers(props) {
                         ...colors.controlPointCurrentProject
                     ])
                 }
-            ] : [])]} />,
+            ] : []),
+            {
+                mesh: state.planning.rendering.staticMeshes.GestureDot,
+                instances: new Float32Array([].concat(...(state.planning.canvasMode.currentGesture
+                    ? guidePoints(state.planning.snapGuides, GUIDE_LENGTH, GUIDE_DOT_SPACING)
+                    : []
+                ).map(point => [
+                    point[0], point[1], 0.0,
+                    0.3, // scaled down
+                    0.0,
+                    ...colors.controlPointMaster
+                ])))
+            },
+            {
+                mesh: state.planning.rendering.staticMeshes.GestureDot,
+                instances: new Float32Array([].concat(...Blueprints.previewPoints(state).map(point => [
+                    point[0], point[1], 0.0,
+                    1.0, 0.0,
+                    ...colors.controlPointHover
+                ])))
+            }]} />,
         <RenderLayer renderOrder={renderOrder.gestureInteractables}
             decal={true}
             batches={[
@@ -583,11 +696,65 @@ export function ShapesAndLayers(props) {
                 ] : [])]} />
     ];
 
+    const bulldozerInteractables = [];
+
+    // The bulldozer takes out the piece of road between the two control points
+    // around where it is used, or a whole building. Residents are evicted
+    // and part of the materials refunded once the project is implemented
+    if (state.planning.planningMode === "bulldozer") {
+        for (let gestureId of Object.keys(state.planning.rendering.roadInfos)) {
+            let { centerLine, nLanesForward, nLanesBackward } = state.planning.rendering.roadInfos[gestureId];
+
+            bulldozerInteractables.push(<Interactive3DShape
+                id={gestureId + "bulldoze"}
+                shape={{
+                    type: "path",
+                    path: centerLine,
+                    maxDistanceLeft: LANE_DISTANCE * nLanesBackward + 2,
+                    maxDistanceRight: LANE_DISTANCE * nLanesForward + 2,
+                }}
+                zIndex={4}
+                cursorHover="pointer"
+                cursorActive="pointer"
+                onEvent={e => {
+                    if (e.drag && e.drag.end) {
+                        cbRustBrowser.remove_gesture_segment(state.planning.currentProject, gestureId, [e.drag.end[0], e.drag.end[1]]);
+                    }
+                }} />);
+        }
+
+        for (let gestureId of Object.keys(gestures)) {
+            const gesture = gestures[gestureId];
+
+            if (gesture.deleted || !gesture.intent.Building) {
+                continue;
+            }
+
+            bulldozerInteractables.push(<Interactive3DShape
+                id={gestureId + "bulldoze"}
+                shape={{
+                    type: "polygon",
+                    area: gesture.intent.Building.lot.area,
+                }}
+                zIndex={3}
+                cursorHover="pointer"
+                cursorActive="pointer"
+                onEvent={e => {
+                    if (e.drag && e.drag.end) {
+                        cbRustBrowser.delete_gesture(state.planning.currentProject, gestureId);
+                    }
+                }} />);
+        }
+    }
+
     // TODO: invent a better way to preserve identity
 
+    const drawingElsewhere = state.planning.canvasMode.currentGesture || Blueprints.isActive(state);
+
     const interactables = [
-        state.planning.canvasMode.currentGesture ? null : controlPointsInteractables,
-        state.planning.canvasMode.currentGesture ? null : roadCenterInteractables,
+        drawingElsewhere ? null : controlPointsInteractables,
+        drawingElsewhere ? null : roadCenterInteractables,
+        drawingElsewhere ? null : bulldozerInteractables,
         <Interactive3DShape
             id="planningCanvas"
             key="planningCanvas"
@@ -598,29 +765,42 @@ export function ShapesAndLayers(props) {
             cursorHover={state.uiMode == "planning" ? "crosshair" : "normal"}
             cursorActive="pointer"
             onEvent={e => {
+                if (Blueprints.isActive(state)) {
+                    Blueprints.onCanvasEvent(e, state, setState);
+                    return;
+                }
+
                 const canvasMode = state.planning.canvasMode;
+                const previousPoints = canvasMode.currentGesture
+                    ? previousPointsOf(state, canvasMode.currentGesture, canvasMode.addToEnd)
+                    : [];
                 if (e.hover && e.hover.now) {
                     if (canvasMode.currentGesture) {
+                        const { point, guides } = snapped(state, gestures, e.hover.now, e.modifiers, canvasMode.currentGesture, previousPoints);
                         setState(addControlPoint(
                             state.planning.currentProject, canvasMode.currentGesture,
-                            e.hover.now, canvasMode.addToEnd, false
-                        ))
+                            point, canvasMode.addToEnd, false
+                        ));
+                        setState(setSnapGuides(guides));
                     }
                 }
                 if (e.drag && e.drag.end) {
+                    const { point } = snapped(state, gestures, e.drag.end, e.modifiers, canvasMode.currentGesture, previousPoints);
                     if (canvasMode.currentGesture) {
                         if (canvasMode.previousClick
-                            && vec3.dist(e.drag.end, canvasMode.previousClick) < state.settings.planning.finishGestureDistance) {
+                            && (vec3.dist(e.drag.end, canvasMode.previousClick) < state.settings.planning.finishGestureDistance
+                                || vec3.dist(point, canvasMode.previousClick) < state.settings.planning.finishGestureDistance)) {
                             setState(finishGesture(state.planning.currentProject, canvasMode.currentGesture));
+                            setState(setSnapGuides([]));
                         } else {
                             setState(addControlPoint(
                                 state.planning.currentProject, canvasMode.currentGesture,
-                                e.drag.end, canvasMode.addToEnd, true
+                                point, canvasMode.addToEnd, true
                             ))
                         }
                     } else if (canvasMode.intent) {
                         setState(startNewGesture(
-                            state.planning.currentProject, canvasMode.intent, e.drag.end
+                            state.planning.currentProject, canvasMode.intent, point
                         ));
                     }
                 }
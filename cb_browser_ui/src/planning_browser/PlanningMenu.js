@@ -5,6 +5,8 @@ const Option = Select.Option;
 import uuid from '../uuid';
 
 import { Toolbar } from '../toolbar';
+import { BlueprintToolbar } from '../blueprints_browser/Blueprints';
+import * as Underground from '../underground_browser/Underground';
 
 // STATE MUTATING ACTIONS
 
@@ -63,11 +65,77 @@ function redo(oldState) {
     return oldState
 }
 
+function deleteHoveredGesture(oldState) {
+    const { gestureId } = oldState.planning.hoveredControlPoint;
+    if (oldState.planning.currentProject && gestureId && !oldState.planning.canvasMode.currentGesture) {
+        cbRustBrowser.delete_gesture(oldState.planning.currentProject, gestureId);
+        return update(oldState, {
+            planning: { hoveredControlPoint: { $set: {} } }
+        });
+    }
+    return oldState
+}
+
+// implemented projects are undone as a whole, by building what was there before
+function undoImplementation(oldState) {
+    cbRustBrowser.undo_implementation();
+    return oldState
+}
+
+function redoImplementation(oldState) {
+    cbRustBrowser.redo_implementation();
+    return oldState
+}
+
+// new roads start out as wide as is typical for their class
+const DEFAULT_LANES_PER_DIRECTION = { Residential: 1, Avenue: 2, Highway: 3 };
+
+function roadIntent(roadClass, oneWay, level, bikeLanes) {
+    const nLanes = DEFAULT_LANES_PER_DIRECTION[roadClass];
+    return { Road: { n_lanes_forward: nLanes, n_lanes_backward: oneWay ? 0 : nLanes, class: roadClass, level: level || 0, bike_lanes: bikeLanes || false } };
+}
+
+function currentRoad(intent) {
+    return (intent && intent.Road) || { class: "Residential", level: 0, bike_lanes: false };
+}
+
+function roadDrawingMode(intent) {
+    if (intent === "TurnBan") return "turnBan";
+    if (intent === "Roundabout") return "roundabout";
+    return intent && intent.Road && intent.Road.n_lanes_backward === 0 ? "oneWay" : "twoWay";
+}
+
+function zoneIntent(landUse, alongRoad) {
+    return { Zone: alongRoad ? { LandUseAlongRoad: landUse } : { LandUse: landUse } };
+}
+
+function currentZone(intent) {
+    const zone = (intent && intent.Zone) || {};
+    return { landUse: zone.LandUse || zone.LandUseAlongRoad, alongRoad: !!zone.LandUseAlongRoad };
+}
+
+// demand goes from -1 (plenty) to 1 (urgently needed)
+function ZoneDemand(props) {
+    const { demand } = props;
+    return <div className="zone-demand" title="Zone demand">
+        {[["Residential", "R"], ["Commercial", "C"], ["Industrial", "I"]].map(([landUse, letter]) => {
+            const value = demand ? demand[landUse.toLowerCase()] : 0;
+            return <div className="zone-demand-bar" key={landUse}>
+                <div className="zone-demand-fill" style={{
+                    height: (Math.max(0, value) * 50) + "%",
+                    backgroundColor: toCSS(fromLinFloat(colors[landUse]))
+                }} />
+                <span>{letter}</span>
+            </div>
+        })}
+    </div>;
+}
+
 export function Tools(props) {
     const { state, setState } = props;
     return [
         <Toolbar id="main-toolbar"
-            options={{ inspection: { description: "Inspection" }, planning: { description: "Planning" } }}
+            options={{ inspection: { description: "Inspection" }, planning: { description: "Planning" }, transit: { description: "Transit Lines" }, tolls: { description: "Tolls" }, districts: { description: "Districts" } }}
             value={state.uiMode}
             onChange={newMode => setState({ uiMode: newMode })} />,
         state.uiMode == 'planning' && [
@@ -93,6 +161,13 @@ export function Tools(props) {
                     <Option value={projectId}>Project '{projectId.slice(0, 3).toUpperCase()}'</Option>
                 )}</Select>
                 : <Button type="primary" onClick={() => setState(startNewProject)}>Start new project</Button>,
+            <Toolbar id="implementation-history-toolbar"
+                options={{
+                    undoImplementation: { description: "Undo Last Implemented Project", disabled: !state.planning.implementationHistory.nUndoable },
+                    redoImplementation: { description: "Redo Implemented Project", disabled: !state.planning.implementationHistory.nRedoable },
+                }}
+                onChange={value => value == "undoImplementation" ? setState(undoImplementation) : setState(redoImplementation)}
+            />,
             state.planning.currentProject && [
                 <Button type="primary"
                     onClick={() => setState(implementProject)}
@@ -106,14 +181,100 @@ export function Tools(props) {
                 />,
                 state.planning.currentProject &&
                 <Toolbar id="planning-toolbar"
-                    options={{ roads: { description: "Roads" }, zoning: { description: "Zoning" } }}
+                    options={{ roads: { description: "Roads" }, zoning: { description: "Zoning" }, parks: { description: "Parks" }, conduits: { description: "Underground Conduits" }, bulldozer: { description: "Bulldozer (click a piece of road or a building)" } }}
                     value={state.planning.planningMode}
-                    onChange={(value) => setState(oldState => update(oldState, {
+                    onChange={(value) => {
+                        if (value == "zoning") cbRustBrowser.get_zone_demand();
+                        setState(oldState => update(oldState, {
+                            planning: {
+                                planningMode: { $set: value },
+                                canvasMode: { intent: { $set: value == "roads" ? roadIntent("Residential") : value == "parks" ? "Park" : value == "conduits" ? { Conduit: "Electricity" } : null } }
+                            }
+                        }));
+                        Underground.setView(setState, value == "conduits");
+                    }} />,
+                state.planning.currentProject && state.planning.planningMode == "roads" &&
+                <Toolbar id="road-class-toolbar"
+                    options={{
+                        Residential: { description: "Residential Street" },
+                        Avenue: { description: "Avenue" },
+                        Highway: { description: "Highway" }
+                    }}
+                    value={state.planning.canvasMode.intent && state.planning.canvasMode.intent.Road && state.planning.canvasMode.intent.Road.class}
+                    onChange={roadClass => setState(oldState => update(oldState, {
                         planning: {
-                            planningMode: { $set: value },
-                            canvasMode: { intent: { $set: value == "roads" ? { Road: { n_lanes_forward: 1, n_lanes_backward: 1 } } : null } }
+                            canvasMode: {
+                                intent: { $set: roadIntent(roadClass, roadDrawingMode(oldState.planning.canvasMode.intent) == "oneWay", currentRoad(oldState.planning.canvasMode.intent).level, currentRoad(oldState.planning.canvasMode.intent).bike_lanes) }
+                            }
                         }
                     }))} />,
+                state.planning.currentProject && state.planning.planningMode == "roads" &&
+                <Toolbar id="road-drawing-toolbar"
+                    options={{
+                        twoWay: { description: "Two-way" },
+                        oneWay: { description: "One-way" },
+                        turnBan: { description: "Ban Turn" },
+                        roundabout: { description: "Roundabout" }
+                    }}
+                    value={roadDrawingMode(state.planning.canvasMode.intent)}
+                    onChange={mode => setState(oldState => update(oldState, {
+                        planning: {
+                            canvasMode: {
+                                intent: {
+                                    $set: mode == "turnBan"
+                                        ? "TurnBan"
+                                        : mode == "roundabout"
+                                        ? "Roundabout"
+                                        : roadIntent(currentRoad(oldState.planning.canvasMode.intent).class, mode == "oneWay", currentRoad(oldState.planning.canvasMode.intent).level, currentRoad(oldState.planning.canvasMode.intent).bike_lanes)
+                                }
+                            }
+                        }
+                    }))} />,
+                state.planning.currentProject && state.planning.planningMode == "roads" &&
+                <Toolbar id="road-level-toolbar"
+                    options={{
+                        "-1": { description: "Tunnel" },
+                        "0": { description: "Ground" },
+                        "1": { description: "Bridge" }
+                    }}
+                    value={"" + currentRoad(state.planning.canvasMode.intent).level}
+                    onChange={level => setState(oldState => update(oldState, {
+                        planning: {
+                            canvasMode: {
+                                intent: {
+                                    $set: roadIntent(
+                                        currentRoad(oldState.planning.canvasMode.intent).class,
+                                        roadDrawingMode(oldState.planning.canvasMode.intent) == "oneWay",
+                                        parseInt(level),
+                                        currentRoad(oldState.planning.canvasMode.intent).bike_lanes
+                                    )
+                                }
+                            }
+                        }
+                    }))} />,
+                state.planning.currentProject && state.planning.planningMode == "roads" &&
+                <Toolbar id="road-bike-lanes-toolbar"
+                    options={{
+                        withoutBikeLanes: { description: "No Bike Lanes" },
+                        withBikeLanes: { description: "Bike Lanes" }
+                    }}
+                    value={currentRoad(state.planning.canvasMode.intent).bike_lanes ? "withBikeLanes" : "withoutBikeLanes"}
+                    onChange={value => setState(oldState => update(oldState, {
+                        planning: {
+                            canvasMode: {
+                                intent: {
+                                    $set: roadIntent(
+                                        currentRoad(oldState.planning.canvasMode.intent).class,
+                                        roadDrawingMode(oldState.planning.canvasMode.intent) == "oneWay",
+                                        currentRoad(oldState.planning.canvasMode.intent).level,
+                                        value == "withBikeLanes"
+                                    )
+                                }
+                            }
+                        }
+                    }))} />,
+                state.planning.currentProject && state.planning.planningMode == "roads" &&
+                <BlueprintToolbar state={state} setState={setState} />,
                 state.planning.currentProject && state.planning.planningMode == "zoning" &&
                 <Toolbar id="zoning-toolbar"
                     options={{
@@ -124,14 +285,30 @@ export function Tools(props) {
                         Recreational: { description: "Recreational", color: toCSS(fromLinFloat(colors["Recreational"])) },
                         Administrative: { description: "Administrative", color: toCSS(fromLinFloat(colors["Administrative"])) }
                     }}
-                    value={state.planning.canvasMode.intent && state.planning.canvasMode.intent.Zone && state.planning.canvasMode.intent.Zone.LandUse}
+                    value={currentZone(state.planning.canvasMode.intent).landUse}
                     onChange={newLandUse => setState(oldState => update(oldState, {
                         planning: {
                             canvasMode: {
-                                intent: { $set: { Zone: { LandUse: newLandUse } } }
+                                intent: { $set: zoneIntent(newLandUse, currentZone(oldState.planning.canvasMode.intent).alongRoad) }
                             }
                         }
-                    }))} />
+                    }))} />,
+                state.planning.currentProject && state.planning.planningMode == "zoning" &&
+                <Toolbar id="zoning-drawing-toolbar"
+                    options={{
+                        area: { description: "Outline Area" },
+                        alongRoad: { description: "Paint Along Roads" }
+                    }}
+                    value={currentZone(state.planning.canvasMode.intent).alongRoad ? "alongRoad" : "area"}
+                    onChange={mode => setState(oldState => update(oldState, {
+                        planning: {
+                            canvasMode: {
+                                intent: { $set: zoneIntent(currentZone(oldState.planning.canvasMode.intent).landUse || "Residential", mode == "alongRoad") }
+                            }
+                        }
+                    }))} />,
+                state.planning.currentProject && state.planning.planningMode == "zoning" &&
+                <ZoneDemand demand={state.planning.zoneDemand} />
             ]
         ]
     ];
@@ -141,10 +318,12 @@ export function bindInputs(state, setState) {
     const inputActions = {
         "implementProject": () => setState(implementProject),
         "undo": () => setState(undo),
-        "redo": () => setState(redo)
+        "redo": () => setState(redo),
+        "deleteGesture": () => setState(deleteHoveredGesture)
     }
 
     Mousetrap.bind(state.settings.planning.implementProjectKey.key, inputActions["implementProject"]);
     Mousetrap.bind(state.settings.planning.undoKey.key, inputActions["undo"]);
     Mousetrap.bind(state.settings.planning.redoKey.key, inputActions["redo"]);
+    Mousetrap.bind(state.settings.planning.deleteGestureKey.key, inputActions["deleteGesture"]);
 }
\ No newline at end of file
@@ -10,11 +10,13 @@ use cb_planning::plan_manager::ProjectID;
 use cb_planning::plan_manager::ui::{PlanningUI, PlanningUIID};
 use planning::{CBPlanningLogic, CBPlanManagerID, CBGestureIntent, CBPrototypeKind};
 use ::land_use::zone_planning::{LandUse, LAND_USES};
+use economy::immigration_and_development::{DevelopmentManagerID, ZoneDemand, ZoneDemandUI,
+ZoneDemandUIID};
 use browser_utils::{updated_groups_to_js, to_js_mesh, FrameListener, FrameListenerID};
 
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use stdweb::js_export;
-use SYSTEM;
+use {SYSTEM, local_origin};
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn move_gesture_point(
@@ -27,6 +29,7 @@ pub fn move_gesture_point(
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
     CBPlanManagerID::global_first(world).move_control_point(
+        local_origin(),
         project_id.0,
         gesture_id.0,
         point_idx,
@@ -46,6 +49,7 @@ pub fn start_new_gesture(
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
     CBPlanManagerID::global_first(world).start_new_gesture(
+        local_origin(),
         project_id.0,
         gesture_id.0,
         intent.0,
@@ -65,6 +69,7 @@ pub fn add_control_point(
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
     CBPlanManagerID::global_first(world).add_control_point(
+        local_origin(),
         project_id.0,
         gesture_id.0,
         new_point.0,
@@ -84,6 +89,7 @@ pub fn insert_control_point(
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
     CBPlanManagerID::global_first(world).insert_control_point(
+        local_origin(),
         project_id.0,
         gesture_id.0,
         new_point.0,
@@ -102,6 +108,7 @@ pub fn split_gesture(
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
     CBPlanManagerID::global_first(world).split_gesture(
+        local_origin(),
         project_id.0,
         gesture_id.0,
         split_at.0,
@@ -116,48 +123,106 @@ pub fn set_n_lanes(
     gesture_id: Serde<GestureID>,
     n_lanes_forward: usize,
     n_lanes_backward: usize,
+    road_class: Serde<::transport::transport_planning::RoadClass>,
+    level: i32,
+    bike_lanes: bool,
     done_changing: bool,
 ) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
     CBPlanManagerID::global_first(world).set_intent(
+        local_origin(),
         project_id.0,
         gesture_id.0,
-        CBGestureIntent::Road(::transport::transport_planning::RoadIntent {
-            n_lanes_forward: n_lanes_forward as u8,
-            n_lanes_backward: n_lanes_backward as u8,
-        }),
+        CBGestureIntent::Road(::transport::transport_planning::RoadIntent::new(
+            n_lanes_forward as u8,
+            n_lanes_backward as u8,
+            road_class.0,
+        )
+        .at_level(level as i8)
+        .with_bike_lanes(bike_lanes)),
         done_changing,
         world,
     )
 }
 
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn delete_gesture(project_id: Serde<ProjectID>, gesture_id: Serde<GestureID>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    CBPlanManagerID::global_first(world).delete_gesture(
+        local_origin(),
+        project_id.0,
+        gesture_id.0,
+        world,
+    )
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn remove_gesture_segment(
+    project_id: Serde<ProjectID>,
+    gesture_id: Serde<GestureID>,
+    remove_at: Serde<P2>,
+) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    CBPlanManagerID::global_first(world).remove_gesture_segment(
+        local_origin(),
+        project_id.0,
+        gesture_id.0,
+        remove_at.0,
+        world,
+    )
+}
+
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn undo(project_id: Serde<ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    CBPlanManagerID::global_first(world).undo(project_id.0, world)
+    CBPlanManagerID::global_first(world).undo(local_origin(), project_id.0, world)
 }
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn redo(project_id: Serde<ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    CBPlanManagerID::global_first(world).redo(project_id.0, world)
+    CBPlanManagerID::global_first(world).redo(local_origin(), project_id.0, world)
 }
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn implement_project(project_id: Serde<ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    CBPlanManagerID::global_first(world).implement(project_id.0, world);
+    CBPlanManagerID::global_first(world).implement(local_origin(), project_id.0, world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn undo_implementation() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    CBPlanManagerID::global_first(world).undo_implementation(local_origin(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn redo_implementation() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    CBPlanManagerID::global_first(world).redo_implementation(local_origin(), world);
 }
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn start_new_project(project_id: Serde<ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    CBPlanManagerID::global_first(world).start_new_project(project_id.0, world);
+    CBPlanManagerID::global_first(world).start_new_project(local_origin(), project_id.0, world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_zone_demand() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    DevelopmentManagerID::global_first(world)
+        .get_zone_demand(BrowserPlanningUIID::local_first(world).into(), world);
 }
 
 #[derive(Compact, Clone)]
@@ -187,6 +252,8 @@ pub struct BrowserPlanningUINonPersistedState {
     result_preview: PlanResult<CBPrototypeKind>,
     actions_preview: ActionGroups,
     awaiting_preview_update: bool,
+    // how many implemented projects can be undone and redone
+    implementation_history: (u32, u32),
 
     // planning geometry
     lanes_to_construct_grouper: MeshGrouper<PrototypeID>,
@@ -251,6 +318,7 @@ impl BrowserPlanningUI {
                 result_preview: PlanResult::new(),
                 actions_preview: ActionGroups::new(),
                 awaiting_preview_update: false,
+                implementation_history: (0, 0),
                 lanes_to_construct_grouper: MeshGrouper::new(2000),
                 lanes_to_construct_marker_grouper: MeshGrouper::new(2000),
                 lanes_to_construct_marker_gaps_grouper: MeshGrouper::new(2000),
@@ -281,6 +349,7 @@ impl FrameListener for BrowserPlanningUI {
                 .collect(),
             world,
         );
+        CBPlanManagerID::global_first(world).get_implementation_history(self.id_as(), world);
 
         let maybe_current_project_id: Result<Serde<ProjectID>, _> = js! {
             return (window.cbReactApp.state.uiMode == "planning" &&
@@ -380,7 +449,7 @@ impl PlanningUI<CBPlanningLogic> for BrowserPlanningUI {
     ) {
         use ::transport::transport_planning::{RoadPrototype, LanePrototype,
 SwitchLanePrototype, IntersectionPrototype};
-        use ::transport::ui::{lane_mesh, marker_mesh, switch_marker_gap_mesh};
+        use ::transport::ui::{lane_mesh, marker_mesh, switch_marker_gap_mesh, elevated};
         use ::land_use::zone_planning::{LotPrototype, LotOccupancy};
 
         let mut lanes_to_construct_add = Vec::new();
@@ -466,23 +535,30 @@ SwitchLanePrototype, IntersectionPrototype};
         for new_prototype in &result_update.new_prototypes {
             let corresponding_action = new_actions.corresponding_action(new_prototype.id);
             match new_prototype.kind {
-                CBPrototypeKind::Road(RoadPrototype::Lane(LanePrototype(ref lane_path, _))) => {
-                    match corresponding_action {
-                        Some(ref action) if action.is_construct() => {
-                            lanes_to_construct_add.push((new_prototype.id, lane_mesh(lane_path)));
-                            let marker = marker_mesh(lane_path);
-                            lanes_to_construct_marker_add
-                                .push((new_prototype.id, marker.0 + marker.1));
-                        }
-                        _ => {}
+                CBPrototypeKind::Road(RoadPrototype::Lane(LanePrototype(
+                    ref lane_path,
+                    _,
+                    _,
+                    ref elevation,
+                ))) => match corresponding_action {
+                    Some(ref action) if action.is_construct() => {
+                        lanes_to_construct_add
+                            .push((new_prototype.id, elevated(lane_mesh(lane_path), elevation)));
+                        let marker = marker_mesh(lane_path);
+                        lanes_to_construct_marker_add
+                            .push((new_prototype.id, elevated(marker.0 + marker.1, elevation)));
                     }
-                }
+                    _ => {}
+                },
                 CBPrototypeKind::Road(RoadPrototype::SwitchLane(SwitchLanePrototype(
                     ref lane_path,
+                    ref elevation,
                 ))) => match corresponding_action {
                     Some(ref action) if action.is_construct() => {
-                        lanes_to_construct_marker_gaps_add
-                            .push((new_prototype.id, switch_marker_gap_mesh(lane_path)));
+                        lanes_to_construct_marker_gaps_add.push((
+                            new_prototype.id,
+                            elevated(switch_marker_gap_mesh(lane_path), elevation),
+                        ));
                     }
                     _ => {}
                 },
@@ -492,10 +568,10 @@ SwitchLanePrototype, IntersectionPrototype};
                 })) => match corresponding_action {
                     Some(ref action) if action.is_construct() => {
                         let mut intersection_mesh = Mesh::empty();
-                        for &LanePrototype(ref lane_path, _) in
+                        for &LanePrototype(ref lane_path, _, _, ref elevation) in
                             connecting_lanes.values().flat_map(|lanes| lanes)
                         {
-                            intersection_mesh += lane_mesh(lane_path);
+                            intersection_mesh += elevated(lane_mesh(lane_path), elevation);
                         }
                         lanes_to_construct_add.push((new_prototype.id, intersection_mesh))
                     }
@@ -600,6 +676,9 @@ SwitchLanePrototype, IntersectionPrototype};
             outline: LinePath,
             n_lanes_forward: usize,
             n_lanes_backward: usize,
+            road_class: ::transport::transport_planning::RoadClass,
+            level: i8,
+            bike_lanes: bool,
         }
 
         let road_infos: HashMap<GestureID, RoadInfo> =
@@ -623,6 +702,9 @@ SwitchLanePrototype, IntersectionPrototype};
                             center_line: path,
                             n_lanes_forward: road_intent.n_lanes_forward as usize,
                             n_lanes_backward: road_intent.n_lanes_backward as usize,
+                            road_class: road_intent.class,
+                            level: road_intent.level,
+                            bike_lanes: road_intent.bike_lanes,
                         },
                     )
                 })
@@ -664,6 +746,35 @@ SwitchLanePrototype, IntersectionPrototype};
         self.actions_preview = new_actions.clone();
         self.awaiting_preview_update = false;
     }
+
+    fn on_implementation_history_update(
+        &mut self,
+        n_undoable: u32,
+        n_redoable: u32,
+        _world: &mut World,
+    ) {
+        if self.implementation_history != (n_undoable, n_redoable) {
+            self.implementation_history = (n_undoable, n_redoable);
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    planning: {implementationHistory: {"$set": {
+                        nUndoable: @{n_undoable},
+                        nRedoable: @{n_redoable}
+                    }}}
+                }));
+            }
+        }
+    }
+}
+
+impl ZoneDemandUI for BrowserPlanningUI {
+    fn on_zone_demand(&mut self, demand: ZoneDemand, _world: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                planning: {zoneDemand: {"$set": @{Serde(demand)}}}
+            }));
+        }
+    }
 }
 
 mod kay_auto;
@@ -0,0 +1,193 @@
+// Drawing aids for roads: points are pulled onto existing road ends, directions onto
+// fixed angles or parallel/perpendicular to nearby roads, curves onto round radii
+// and everything else onto a grid, in that order of preference
+
+// existing road ends closer than this are snapped onto, so the roads connect
+const ENDPOINT_SNAP_DISTANCE = 8.0;
+// directions this close to parallel or perpendicular to a nearby road are snapped to it
+const GUIDE_SNAP_ANGLE = 3 * Math.PI / 180;
+// only roads this close to the drawn point offer guides
+const GUIDE_SEARCH_DISTANCE = 150.0;
+// turns gentler than this are meant to be straight and are left alone
+const MIN_CURVE_TURN = 2 * Math.PI / 180;
+// curves through the last control points are snapped to the closest of these radii
+const SENSIBLE_CURVE_RADII = [25, 50, 75, 100, 150, 200, 300, 500];
+
+function sub(a, b) {
+    return [a[0] - b[0], a[1] - b[1]];
+}
+
+function length(v) {
+    return Math.hypot(v[0], v[1]);
+}
+
+function rotated(v, angle) {
+    const [cos, sin] = [Math.cos(angle), Math.sin(angle)];
+    return [cos * v[0] - sin * v[1], sin * v[0] + cos * v[1]];
+}
+
+function along(start, direction, distance) {
+    return [start[0] + distance * direction[0], start[1] + distance * direction[1]];
+}
+
+function signedAngle(from, to) {
+    return Math.atan2(from[0] * to[1] - from[1] * to[0], from[0] * to[0] + from[1] * to[1]);
+}
+
+function distanceToSegment(point, a, b) {
+    const ab = sub(b, a);
+    const t = Math.max(0, Math.min(1, ((point[0] - a[0]) * ab[0] + (point[1] - a[1]) * ab[1]) / (ab[0] * ab[0] + ab[1] * ab[1] || 1)));
+    return length(sub(point, along(a, ab, t)));
+}
+
+function otherRoads(gestures, gestureId) {
+    return Object.keys(gestures)
+        .filter(id => id !== gestureId && !gestures[id].deleted && gestures[id].intent.Road)
+        .map(id => gestures[id].points);
+}
+
+function snapToEndpoint(point, roads) {
+    let closest = null;
+    let closestDistance = ENDPOINT_SNAP_DISTANCE;
+
+    for (let points of roads) {
+        for (let end of [points[0], points[points.length - 1]]) {
+            const distance = length(sub(point, end));
+            if (distance < closestDistance) {
+                closest = [end[0], end[1]];
+                closestDistance = distance;
+            }
+        }
+    }
+
+    return closest;
+}
+
+// Rounds the direction from the last point to the next one to multiples of the angle step,
+// relative to the direction the road had so far, or to the x axis for its first piece
+function constrainAngle(point, previousPoints, angleStep) {
+    const last = previousPoints[previousPoints.length - 1];
+    const reference = previousPoints.length >= 2
+        ? sub(last, previousPoints[previousPoints.length - 2])
+        : [1, 0];
+    const offset = sub(point, last);
+    const step = angleStep * Math.PI / 180;
+    const angle = Math.round(signedAngle(reference, offset) / step) * step;
+    const direction = rotated(reference, angle);
+
+    return along(last, direction, length(offset) / length(direction));
+}
+
+function snapToGuide(point, previousPoints, roads) {
+    const last = previousPoints[previousPoints.length - 1];
+    const offset = sub(point, last);
+    let closest = null;
+    let closestDistance = GUIDE_SEARCH_DISTANCE;
+
+    for (let points of roads) {
+        for (let i = 0; i < points.length - 1; i++) {
+            const distance = distanceToSegment(point, points[i], points[i + 1]);
+            if (distance < closestDistance) {
+                closest = sub(points[i + 1], points[i]);
+                closestDistance = distance;
+            }
+        }
+    }
+
+    if (!closest || !length(closest)) {
+        return null;
+    }
+
+    const angle = signedAngle(closest, offset);
+    const quarterTurns = Math.round(angle / (Math.PI / 2));
+
+    if (Math.abs(angle - quarterTurns * Math.PI / 2) < GUIDE_SNAP_ANGLE) {
+        const roadDirection = rotated(closest, quarterTurns * Math.PI / 2);
+        const direction = [roadDirection[0] / length(roadDirection), roadDirection[1] / length(roadDirection)];
+        return {
+            point: along(last, direction, length(offset)),
+            guide: { start: last, direction, kind: quarterTurns % 2 ? "perpendicular" : "parallel" }
+        };
+    }
+
+    return null;
+}
+
+// For three points on a circle, the turn at the middle one is the sum
+// of the half angles that the two chords span as seen from the center
+function snapCurveRadius(point, previousPoints) {
+    const [a, b] = previousPoints.slice(-2);
+    const incoming = sub(b, a);
+    const outgoing = sub(point, b);
+    const turn = signedAngle(incoming, outgoing);
+    const chordA = length(incoming);
+    const chordB = length(outgoing);
+
+    if (Math.abs(turn) < MIN_CURVE_TURN || !chordA || !chordB) {
+        return null;
+    }
+
+    const radius = length(sub(point, a)) / (2 * Math.sin(Math.abs(turn)));
+    if (radius > 1.5 * SENSIBLE_CURVE_RADII[SENSIBLE_CURVE_RADII.length - 1]) {
+        return null;
+    }
+
+    const snappedRadius = SENSIBLE_CURVE_RADII.reduce((best, candidate) =>
+        Math.abs(candidate - radius) < Math.abs(best - radius) ? candidate : best
+    );
+    const snappedTurn = Math.asin(Math.min(1, chordA / (2 * snappedRadius)))
+        + Math.asin(Math.min(1, chordB / (2 * snappedRadius)));
+    const direction = rotated([incoming[0] / chordA, incoming[1] / chordA], Math.sign(turn) * snappedTurn);
+
+    return along(b, direction, chordB);
+}
+
+function snapToGrid(point, gridSize) {
+    return [Math.round(point[0] / gridSize) * gridSize, Math.round(point[1] / gridSize) * gridSize];
+}
+
+// previousPoints are the points the new one follows, ending with the one it connects to
+export function snapRoadPoint(point, { gestures, gestureId, previousPoints, constrained, settings }) {
+    const roads = otherRoads(gestures, gestureId);
+    const hasPrevious = previousPoints && previousPoints.length > 0;
+
+    const endpoint = snapToEndpoint(point, roads);
+    if (endpoint) {
+        return { point: endpoint, guides: [] };
+    }
+
+    if (hasPrevious && constrained) {
+        return { point: constrainAngle(point, previousPoints, settings.constrainedAngleStep), guides: [] };
+    }
+
+    if (hasPrevious && settings.showGuides) {
+        const guided = snapToGuide(point, previousPoints, roads);
+        if (guided) {
+            return { point: guided.point, guides: [guided.guide] };
+        }
+    }
+
+    if (hasPrevious && previousPoints.length >= 2 && settings.snapCurveRadii) {
+        const curved = snapCurveRadius(point, previousPoints);
+        if (curved) {
+            return { point: curved, guides: [] };
+        }
+    }
+
+    if (settings.snapToGrid) {
+        return { point: snapToGrid(point, settings.snapGridSize), guides: [] };
+    }
+
+    return { point: [point[0], point[1]], guides: [] };
+}
+
+// Points along guides, to be drawn as small dots
+export function guidePoints(guides, guideLength, spacing) {
+    const points = [];
+    for (let { start, direction } of guides) {
+        for (let distance = -guideLength; distance <= guideLength; distance += spacing) {
+            points.push(along(start, direction, distance));
+        }
+    }
+    return points;
+}
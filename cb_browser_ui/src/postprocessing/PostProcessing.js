@@ -0,0 +1,348 @@
+import React from 'react';
+import { compileProgram, createTarget, deleteTarget } from './glUtils';
+import { ShadowRenderer, viewPositionFunctions, setViewUniforms } from './Shadows';
+
+// Post-processing works on the finished frame that Monet rendered:
+// each frame, Monet's canvas is uploaded as a texture, gets building
+// shadows and ambient occlusion (SSAO) applied, and is run through a bloom
+// pass (bright-pass, separable blur, additive composite) and FXAA.
+// When frames take too long, bloom and ambient occlusion are turned off
+// until the settings are changed again.
+
+export const settingsSpec = {
+    enabled: { default: true, description: "Post-Processing" },
+    bloom: { default: true, description: "Bloom on Bright Lights" },
+    bloomThreshold: { default: 0.8, description: "Bloom Threshold", min: 0.0, max: 1.0, step: 0.05 },
+    bloomIntensity: { default: 0.6, description: "Bloom Intensity", min: 0.0, max: 2.0, step: 0.1 },
+    fxaa: { default: true, description: "Anti-Aliasing (FXAA)" },
+    ssao: { default: true, description: "Ambient Occlusion (SSAO)" },
+    ssaoRadius: { default: 4.0, description: "Ambient Occlusion Radius", min: 1.0, max: 10.0, step: 0.5 },
+    ssaoIntensity: { default: 0.7, description: "Ambient Occlusion Intensity", min: 0.0, max: 1.0, step: 0.05 },
+    turnOffWhenSlow: { default: true, description: "Turn Off Bloom and SSAO When Frames Are Slow" },
+}
+
+// frame times are averaged over roughly this many frames before judging them
+const FRAME_TIME_SMOOTHING = 1 / 60;
+const SLOW_FRAME_MS = 1000 / 25;
+// longer gaps mean rendering was paused (like in a background tab), not slow
+const MAX_FRAME_GAP_MS = 1000;
+
+const brightPassFragment = `
+precision mediump float;
+uniform sampler2D source;
+uniform float threshold;
+varying vec2 v_uv;
+void main() {
+    vec3 color = texture2D(source, v_uv).rgb;
+    float brightness = dot(color, vec3(0.2126, 0.7152, 0.0722));
+    gl_FragColor = vec4(color * smoothstep(threshold, 1.0, brightness), 1.0);
+}`;
+
+const blurFragment = `
+precision mediump float;
+uniform sampler2D source;
+uniform vec2 direction;
+varying vec2 v_uv;
+void main() {
+    vec3 sum = texture2D(source, v_uv).rgb * 0.227027;
+    sum += texture2D(source, v_uv + direction * 1.384615).rgb * 0.316216;
+    sum += texture2D(source, v_uv - direction * 1.384615).rgb * 0.316216;
+    sum += texture2D(source, v_uv + direction * 3.230769).rgb * 0.070270;
+    sum += texture2D(source, v_uv - direction * 3.230769).rgb * 0.070270;
+    gl_FragColor = vec4(sum, 1.0);
+}`;
+
+const compositeFragment = `
+precision mediump float;
+uniform sampler2D source;
+uniform sampler2D bloom;
+uniform float intensity;
+varying vec2 v_uv;
+void main() {
+    vec3 color = texture2D(source, v_uv).rgb + texture2D(bloom, v_uv).rgb * intensity;
+    gl_FragColor = vec4(color, 1.0);
+}`;
+
+// Samples a hemisphere around each visible point and darkens it by how many
+// of the samples lie behind what is seen in their direction
+const ssaoFragment = `
+precision highp float;
+uniform sampler2D source;
+uniform mat4 viewProjection;
+uniform vec2 texel;
+uniform float radius;
+uniform float intensity;
+varying vec2 v_uv;
+${viewPositionFunctions}
+const int N_SAMPLES = 12;
+
+void main() {
+    vec3 color = texture2D(source, v_uv).rgb;
+    vec3 position = viewPosition(v_uv);
+    vec3 normal = normalize(cross(
+        viewPosition(v_uv + vec2(texel.x, 0.0)) - position,
+        viewPosition(v_uv + vec2(0.0, texel.y)) - position));
+    if (dot(normal, eye - position) < 0.0) normal = -normal;
+    vec3 tangent = normalize(cross(normal, abs(normal.z) < 0.9 ? vec3(0.0, 0.0, 1.0) : vec3(1.0, 0.0, 0.0)));
+    vec3 bitangent = cross(normal, tangent);
+    // rotate the samples differently for each pixel, so the pattern becomes noise instead of bands
+    float rotation = fract(sin(dot(v_uv, vec2(12.9898, 78.233))) * 43758.5453) * 6.2831853;
+
+    float occlusion = 0.0;
+    for (int i = 0; i < N_SAMPLES; i++) {
+        float progress = (float(i) + 0.5) / float(N_SAMPLES);
+        float angle = rotation + float(i) * 2.3999632;
+        vec3 direction = (tangent * cos(angle) + bitangent * sin(angle)) * sqrt(progress)
+            + normal * sqrt(1.0 - progress);
+        vec3 samplePosition = position + direction * radius * mix(0.2, 1.0, progress);
+
+        vec4 projected = viewProjection * vec4(samplePosition, 1.0);
+        vec3 seen = viewPosition(projected.xy / projected.w * 0.5 + 0.5);
+        float sampleDistance = distance(eye, samplePosition);
+        float seenDistance = distance(eye, seen);
+        // what is much closer to the camera doesn't shade what lies behind it
+        float inRange = smoothstep(0.0, 1.0, radius / max(sampleDistance - seenDistance, 0.0001));
+        occlusion += step(seenDistance, sampleDistance - 0.02 * radius) * inRange;
+    }
+
+    gl_FragColor = vec4(color * (1.0 - intensity * occlusion / float(N_SAMPLES)), 1.0);
+}`;
+
+// condensed version of Timothy Lottes' FXAA 3.11 console variant
+const fxaaFragment = `
+precision mediump float;
+uniform sampler2D source;
+uniform vec2 texel;
+varying vec2 v_uv;
+const vec3 LUMA = vec3(0.299, 0.587, 0.114);
+void main() {
+    float lumaNW = dot(texture2D(source, v_uv + vec2(-1.0, -1.0) * texel).rgb, LUMA);
+    float lumaNE = dot(texture2D(source, v_uv + vec2(1.0, -1.0) * texel).rgb, LUMA);
+    float lumaSW = dot(texture2D(source, v_uv + vec2(-1.0, 1.0) * texel).rgb, LUMA);
+    float lumaSE = dot(texture2D(source, v_uv + vec2(1.0, 1.0) * texel).rgb, LUMA);
+    vec3 rgbM = texture2D(source, v_uv).rgb;
+    float lumaM = dot(rgbM, LUMA);
+    float lumaMin = min(lumaM, min(min(lumaNW, lumaNE), min(lumaSW, lumaSE)));
+    float lumaMax = max(lumaM, max(max(lumaNW, lumaNE), max(lumaSW, lumaSE)));
+
+    vec2 dir = vec2(
+        -((lumaNW + lumaNE) - (lumaSW + lumaSE)),
+        ((lumaNW + lumaSW) - (lumaNE + lumaSE))
+    );
+    float dirReduce = max((lumaNW + lumaNE + lumaSW + lumaSE) * 0.03125, 1.0 / 128.0);
+    float rcpDirMin = 1.0 / (min(abs(dir.x), abs(dir.y)) + dirReduce);
+    dir = clamp(dir * rcpDirMin, vec2(-8.0), vec2(8.0)) * texel;
+
+    vec3 rgbA = 0.5 * (
+        texture2D(source, v_uv + dir * (1.0 / 3.0 - 0.5)).rgb +
+        texture2D(source, v_uv + dir * (2.0 / 3.0 - 0.5)).rgb);
+    vec3 rgbB = rgbA * 0.5 + 0.25 * (
+        texture2D(source, v_uv - dir * 0.5).rgb +
+        texture2D(source, v_uv + dir * 0.5).rgb);
+    float lumaB = dot(rgbB, LUMA);
+
+    gl_FragColor = vec4((lumaB < lumaMin || lumaB > lumaMax) ? rgbA : rgbB, 1.0);
+}`;
+
+export class PostProcessing extends React.Component {
+    constructor(props) {
+        super(props);
+        this.canvas = React.createRef();
+        this.gl = null;
+        this.tooSlow = false;
+        this.lastFrameAt = null;
+        this.averageFrameTime = null;
+        this.nTimedFrames = 0;
+        this.targets = null;
+        this.shadows = null;
+        this.state = { supported: false };
+    }
+
+    componentDidMount() {
+        const gl = this.canvas.current.getContext("webgl", { alpha: false, antialias: false, depth: false });
+
+        if (!gl) {
+            console.warn("WebGL unavailable for post-processing, falling back to plain rendering");
+            return;
+        }
+
+        try {
+            this.programs = {
+                brightPass: compileProgram(gl, brightPassFragment),
+                blur: compileProgram(gl, blurFragment),
+                composite: compileProgram(gl, compositeFragment),
+                fxaa: compileProgram(gl, fxaaFragment),
+                ssao: compileProgram(gl, ssaoFragment),
+            };
+            this.shadows = new ShadowRenderer(gl);
+        } catch (e) {
+            console.warn(e.message);
+            return;
+        }
+
+        this.quad = gl.createBuffer();
+        gl.bindBuffer(gl.ARRAY_BUFFER, this.quad);
+        gl.bufferData(gl.ARRAY_BUFFER, new Float32Array([-1, -1, 1, -1, -1, 1, 1, 1]), gl.STATIC_DRAW);
+
+        this.sourceTexture = gl.createTexture();
+        gl.bindTexture(gl.TEXTURE_2D, this.sourceTexture);
+        gl.texParameteri(gl.TEXTURE_2D, gl.TEXTURE_MIN_FILTER, gl.LINEAR);
+        gl.texParameteri(gl.TEXTURE_2D, gl.TEXTURE_MAG_FILTER, gl.LINEAR);
+        gl.texParameteri(gl.TEXTURE_2D, gl.TEXTURE_WRAP_S, gl.CLAMP_TO_EDGE);
+        gl.texParameteri(gl.TEXTURE_2D, gl.TEXTURE_WRAP_T, gl.CLAMP_TO_EDGE);
+
+        this.gl = gl;
+        this.setState({ supported: true });
+    }
+
+    componentDidUpdate(prevProps) {
+        // give the expensive passes another chance with whatever the user changed
+        if (prevProps.settings !== this.props.settings) {
+            this.tooSlow = false;
+            this.averageFrameTime = null;
+        }
+    }
+
+    timeFrame() {
+        const now = performance.now();
+        const frameTime = this.lastFrameAt === null ? null : now - this.lastFrameAt;
+        this.lastFrameAt = now;
+        if (frameTime === null || frameTime > MAX_FRAME_GAP_MS) return;
+
+        if (this.averageFrameTime === null) {
+            this.averageFrameTime = frameTime;
+            this.nTimedFrames = 1;
+        } else {
+            this.averageFrameTime += (frameTime - this.averageFrameTime) * FRAME_TIME_SMOOTHING;
+            this.nTimedFrames += 1;
+        }
+
+        const judged = this.nTimedFrames >= 1 / FRAME_TIME_SMOOTHING;
+        if (this.props.settings.turnOffWhenSlow && !this.tooSlow && judged && this.averageFrameTime > SLOW_FRAME_MS) {
+            this.tooSlow = true;
+            console.warn(`Frames take ${this.averageFrameTime.toFixed(0)}ms, turning off bloom and ambient occlusion`);
+        }
+    }
+
+    isActive() {
+        return this.props.settings.enabled && this.gl !== null;
+    }
+
+    ensureTargets(width, height) {
+        const gl = this.gl;
+        if (!this.targets || this.targets.full.width !== width || this.targets.full.height !== height) {
+            if (this.targets) {
+                for (let target of Object.values(this.targets)) {
+                    deleteTarget(gl, target);
+                }
+            }
+
+            const halfWidth = Math.max(1, Math.floor(width / 2));
+            const halfHeight = Math.max(1, Math.floor(height / 2));
+
+            this.targets = {
+                full: createTarget(gl, width, height),
+                shadowed: createTarget(gl, width, height),
+                viewDepth: createTarget(gl, width, height, true),
+                occluded: createTarget(gl, width, height),
+                bloomA: createTarget(gl, halfWidth, halfHeight),
+                bloomB: createTarget(gl, halfWidth, halfHeight),
+            };
+        }
+    }
+
+    pass({ program, uniforms }, target, textures, setUniforms) {
+        const gl = this.gl;
+        gl.useProgram(program);
+        gl.bindFramebuffer(gl.FRAMEBUFFER, target ? target.framebuffer : null);
+        gl.viewport(0, 0, target ? target.width : gl.drawingBufferWidth, target ? target.height : gl.drawingBufferHeight);
+
+        Object.keys(textures).forEach((name, unit) => {
+            gl.activeTexture(gl.TEXTURE0 + unit);
+            gl.bindTexture(gl.TEXTURE_2D, textures[name]);
+            gl.uniform1i(uniforms[name], unit);
+        });
+
+        setUniforms && setUniforms(uniforms);
+
+        gl.bindBuffer(gl.ARRAY_BUFFER, this.quad);
+        gl.enableVertexAttribArray(0);
+        gl.vertexAttribPointer(0, 2, gl.FLOAT, false, 0, 0);
+        gl.drawArrays(gl.TRIANGLE_STRIP, 0, 4);
+    }
+
+    // has to be called right after Monet rendered, while its drawing buffer is still valid
+    renderFrame(sourceNode) {
+        const sourceCanvas = sourceNode && (sourceNode.tagName === "CANVAS" ? sourceNode : sourceNode.querySelector("canvas"));
+        if (!this.isActive() || !sourceCanvas) return;
+
+        const gl = this.gl;
+        const { settings } = this.props;
+        const width = gl.drawingBufferWidth;
+        const height = gl.drawingBufferHeight;
+        this.ensureTargets(width, height);
+        this.timeFrame();
+        const expensive = !(settings.turnOffWhenSlow && this.tooSlow);
+
+        gl.pixelStorei(gl.UNPACK_FLIP_Y_WEBGL, true);
+        gl.bindTexture(gl.TEXTURE_2D, this.sourceTexture);
+        gl.texImage2D(gl.TEXTURE_2D, 0, gl.RGBA, gl.RGBA, gl.UNSIGNED_BYTE, sourceCanvas);
+
+        const { shadowSettings, time, viewMatrix, perspectiveMatrix, shadowCasters } = this.props;
+        const ssao = settings.ssao && expensive;
+        const view = (shadowSettings.enabled || ssao) && viewMatrix && perspectiveMatrix
+            && this.shadows.renderViewDepth(this.targets.viewDepth, viewMatrix, perspectiveMatrix, shadowCasters);
+
+        const shadowed = view && shadowSettings.enabled && this.shadows.render(
+            this.pass.bind(this), this.sourceTexture, this.targets.shadowed,
+            { settings: shadowSettings, time, view });
+        let frame = shadowed ? this.targets.shadowed.texture : this.sourceTexture;
+
+        if (view && ssao) {
+            this.pass(this.programs.ssao, this.targets.occluded, { source: frame, viewDepth: view.texture }, u => {
+                setViewUniforms(gl, u, view);
+                gl.uniformMatrix4fv(u.viewProjection, false, view.viewProjection);
+                gl.uniform2f(u.texel, 1 / width, 1 / height);
+                gl.uniform1f(u.radius, settings.ssaoRadius);
+                gl.uniform1f(u.intensity, settings.ssaoIntensity);
+            });
+            frame = this.targets.occluded.texture;
+        }
+
+        const bloom = settings.bloom && expensive;
+
+        if (bloom) {
+            const { full, bloomA, bloomB } = this.targets;
+            this.pass(this.programs.brightPass, bloomA, { source: frame },
+                u => gl.uniform1f(u.threshold, settings.bloomThreshold));
+            this.pass(this.programs.blur, bloomB, { source: bloomA.texture },
+                u => gl.uniform2f(u.direction, 1 / bloomA.width, 0));
+            this.pass(this.programs.blur, bloomA, { source: bloomB.texture },
+                u => gl.uniform2f(u.direction, 0, 1 / bloomA.height));
+            this.pass(this.programs.composite, settings.fxaa ? full : null,
+                { source: frame, bloom: bloomA.texture },
+                u => gl.uniform1f(u.intensity, settings.bloomIntensity));
+        }
+
+        if (settings.fxaa) {
+            this.pass(this.programs.fxaa, null,
+                { source: bloom ? this.targets.full.texture : frame },
+                u => gl.uniform2f(u.texel, 1 / width, 1 / height));
+        } else if (!bloom) {
+            this.pass(this.programs.composite, null,
+                { source: frame, bloom: frame },
+                u => gl.uniform1f(u.intensity, 0.0));
+        }
+    }
+
+    render() {
+        const { width, height, retinaFactor, settings } = this.props;
+
+        return <canvas ref={this.canvas}
+            width={width * retinaFactor} height={height * retinaFactor}
+            style={{
+                width, height, position: "absolute", top: 0, left: 0, pointerEvents: "none",
+                display: settings.enabled && this.state.supported ? "block" : "none"
+            }} />
+    }
+}
@@ -0,0 +1,402 @@
+import { mat4, vec3, vec4 } from 'gl-matrix';
+import { compileProgram, createTarget, deleteTarget } from './glUtils';
+
+// Cascaded shadow maps for buildings, applied as part of post-processing.
+// Monet doesn't expose its depth buffer, so the buildings are rendered once more
+// from the camera into a depth map of their own (see `renderViewDepth`), which
+// ambient occlusion uses as well. Shadows are received by buildings and the
+// ground plane (streets and terrain): every pixel is looked up in the shadow maps
+// where the depth map says a building was seen, or where the view ray hits the ground.
+
+export const settingsSpec = {
+    enabled: { default: true, description: "Building Shadows (needs Post-Processing)" },
+    quality: { default: 1, description: "Shadow Quality", min: 0, max: 2, step: 1 },
+    darkness: { default: 0.4, description: "Shadow Darkness", min: 0.0, max: 1.0, step: 0.05 },
+}
+
+const QUALITY_LEVELS = [
+    { nCascades: 1, mapSize: 1024, pcf: false },
+    { nCascades: 2, mapSize: 2048, pcf: true },
+    { nCascades: 3, mapSize: 2048, pcf: true },
+];
+
+// flat materials like fields don't cast any noticeable shadows
+export const SHADOW_CASTING_MATERIALS = ["WhiteWall", "PlasterWall", "BrickWall", "TiledRoof", "SlateRoof", "FlatRoof"];
+
+const MAX_SHADOW_DISTANCE = 3000;
+// each cascade covers this many times the radius of the next finer one
+const CASCADE_SPLIT = 3;
+const MAX_CASTER_HEIGHT = 100;
+const DEPTH_BIAS = 0.002;
+// building walls and roofs are looked up this far towards the sun, so they don't
+// shadow themselves, while walls facing away from it end up in their own shadow
+const SURFACE_OFFSET = 0.5;
+// view depths are stored as a fraction of this, anything further counts as ground or sky
+const MAX_VIEW_DEPTH = 10000;
+
+const MIN_SUN_ELEVATION = 8 / 180 * Math.PI;
+const MAX_SUN_ELEVATION = 60 / 180 * Math.PI;
+// the sun only moves in coarse steps, so that cached shadow maps stay valid for a while
+const SUN_ANGLE_STEP = 0.5 / 180 * Math.PI;
+
+// Simplified sun path: rises in the east at 6:00, is highest in the south
+// at noon and sets in the west at 18:00. Returns the direction towards the sun,
+// or null at night.
+export function sunDirection([hours, minutes]) {
+    const dayProgress = (hours + minutes / 60 - 6) / 12;
+    if (dayProgress <= 0 || dayProgress >= 1) return null;
+
+    const quantize = angle => Math.round(angle / SUN_ANGLE_STEP) * SUN_ANGLE_STEP;
+    const azimuth = quantize(Math.PI * dayProgress);
+    const elevation = quantize(Math.max(MIN_SUN_ELEVATION, Math.sin(Math.PI * dayProgress) * MAX_SUN_ELEVATION));
+
+    return [
+        Math.cos(azimuth) * Math.cos(elevation),
+        -Math.sin(azimuth) * Math.cos(elevation),
+        Math.sin(elevation)
+    ];
+}
+
+const casterVertex = `
+attribute vec3 position;
+uniform mat4 matrix;
+varying float v_depth;
+void main() {
+    gl_Position = matrix * vec4(position, 1.0);
+    v_depth = gl_Position.z * 0.5 + 0.5;
+}`;
+
+// distance along the view direction, which unlike depth after the perspective
+// divide can be interpolated across triangles
+const viewDepthVertex = `
+attribute vec3 position;
+uniform mat4 matrix;
+uniform float maxDepth;
+varying float v_depth;
+void main() {
+    gl_Position = matrix * vec4(position, 1.0);
+    v_depth = gl_Position.w / maxDepth;
+}`;
+
+// depth is packed into all four channels, since depth textures aren't available everywhere
+const shadowMapFragment = `
+precision highp float;
+varying float v_depth;
+void main() {
+    vec4 packed = fract(v_depth * vec4(1.0, 255.0, 65025.0, 16581375.0));
+    gl_FragColor = packed - packed.yzww * vec4(1.0 / 255.0, 1.0 / 255.0, 1.0 / 255.0, 0.0);
+}`;
+
+// For passes that need to know what is seen where: buildings from the view depth map,
+// everything else from where the view ray hits the ground, or far away for the sky
+export const viewPositionFunctions = `
+uniform sampler2D viewDepth;
+uniform mat4 inverseViewProjection;
+uniform vec3 eye;
+uniform vec3 forward;
+uniform float maxViewDepth;
+
+float unpackDepth(vec4 packed) {
+    return dot(packed, vec4(1.0, 1.0 / 255.0, 1.0 / 65025.0, 1.0 / 16581375.0));
+}
+
+bool onBuilding(vec2 uv) {
+    return unpackDepth(texture2D(viewDepth, uv)) < 1.0;
+}
+
+vec3 viewPosition(vec2 uv) {
+    vec4 alongRay = inverseViewProjection * vec4(uv * 2.0 - 1.0, 0.999, 1.0);
+    vec3 ray = alongRay.xyz / alongRay.w - eye;
+    float depth = unpackDepth(texture2D(viewDepth, uv));
+
+    if (depth < 1.0) {
+        return eye + ray * (depth * maxViewDepth / dot(ray, forward));
+    } else if (ray.z < 0.0) {
+        return eye - ray * (eye.z / ray.z);
+    } else {
+        return eye + normalize(ray) * maxViewDepth;
+    }
+}`;
+
+const receiverFragment = `
+precision highp float;
+uniform sampler2D source;
+uniform sampler2D shadowMap0;
+uniform sampler2D shadowMap1;
+uniform sampler2D shadowMap2;
+uniform mat4 lightMatrix0;
+uniform mat4 lightMatrix1;
+uniform mat4 lightMatrix2;
+uniform int nCascades;
+uniform float texel;
+uniform float bias;
+uniform float darkness;
+uniform bool pcf;
+uniform vec3 sun;
+uniform float surfaceOffset;
+varying vec2 v_uv;
+${viewPositionFunctions}
+
+float litAmount(sampler2D shadowMap, vec3 coords) {
+    if (!pcf) {
+        return step(coords.z - bias, unpackDepth(texture2D(shadowMap, coords.xy)));
+    }
+
+    float sum = 0.0;
+    for (int x = -1; x <= 1; x += 2) {
+        for (int y = -1; y <= 1; y += 2) {
+            vec2 offset = vec2(float(x), float(y)) * 0.5 * texel;
+            sum += step(coords.z - bias, unpackDepth(texture2D(shadowMap, coords.xy + offset)));
+        }
+    }
+    return sum * 0.25;
+}
+
+vec3 toShadowMap(mat4 lightMatrix, vec3 position) {
+    return (lightMatrix * vec4(position, 1.0)).xyz * 0.5 + 0.5;
+}
+
+bool insideShadowMap(vec3 coords) {
+    return all(greaterThan(coords, vec3(0.0))) && all(lessThan(coords, vec3(1.0)));
+}
+
+void main() {
+    vec3 color = texture2D(source, v_uv).rgb;
+    float lit = 1.0;
+
+    vec4 alongRay = inverseViewProjection * vec4(v_uv * 2.0 - 1.0, 0.999, 1.0);
+    vec3 ray = alongRay.xyz / alongRay.w - eye;
+    bool building = onBuilding(v_uv);
+
+    if (building || ray.z < 0.0) {
+        vec3 position = viewPosition(v_uv);
+        if (building) {
+            position += sun * surfaceOffset;
+        }
+
+        vec3 coords0 = toShadowMap(lightMatrix0, position);
+        vec3 coords1 = toShadowMap(lightMatrix1, position);
+        vec3 coords2 = toShadowMap(lightMatrix2, position);
+
+        if (insideShadowMap(coords0)) {
+            lit = litAmount(shadowMap0, coords0);
+        } else if (nCascades > 1 && insideShadowMap(coords1)) {
+            lit = litAmount(shadowMap1, coords1);
+        } else if (nCascades > 2 && insideShadowMap(coords2)) {
+            lit = litAmount(shadowMap2, coords2);
+        }
+    }
+
+    gl_FragColor = vec4(color * (1.0 - darkness * (1.0 - lit)), 1.0);
+}`;
+
+function groundHit(inverseViewProjection, eye, x, y) {
+    const alongRay = vec4.transformMat4(vec4.create(), [x, y, 0.999, 1.0], inverseViewProjection);
+    const ray = vec3.sub(vec3.create(), vec3.scale(vec3.create(), alongRay, 1 / alongRay[3]), eye);
+    if (ray[2] >= 0) return null;
+    return vec3.scaleAndAdd(vec3.create(), eye, ray, -eye[2] / ray[2]);
+}
+
+export class ShadowRenderer {
+    constructor(gl) {
+        this.gl = gl;
+        this.programs = {
+            shadowMap: compileProgram(gl, shadowMapFragment, casterVertex),
+            viewDepth: compileProgram(gl, shadowMapFragment, viewDepthVertex),
+            receiver: compileProgram(gl, receiverFragment),
+        };
+        this.meshBuffers = new Map();
+        this.cascades = [];
+        this.mapSize = 0;
+        this.casterMaterials = [];
+        this.meshes = [];
+    }
+
+    meshBuffersFor(mesh) {
+        const gl = this.gl;
+        let buffers = this.meshBuffers.get(mesh);
+
+        if (!buffers) {
+            const vertices = gl.createBuffer();
+            gl.bindBuffer(gl.ARRAY_BUFFER, vertices);
+            gl.bufferData(gl.ARRAY_BUFFER, mesh.vertices, gl.STATIC_DRAW);
+            const indices = gl.createBuffer();
+            gl.bindBuffer(gl.ELEMENT_ARRAY_BUFFER, indices);
+            gl.bufferData(gl.ELEMENT_ARRAY_BUFFER, mesh.indices, gl.STATIC_DRAW);
+
+            buffers = { vertices, indices, count: mesh.indices.length };
+            this.meshBuffers.set(mesh, buffers);
+        }
+
+        return buffers;
+    }
+
+    // Buildings only change when they are built or replaced, so their buffers and the
+    // shadow maps rendered from them are kept until the set of caster meshes changes
+    updateCasters(casterMaterials) {
+        const changed = casterMaterials.length !== this.casterMaterials.length
+            || casterMaterials.some((meshes, i) => meshes !== this.casterMaterials[i]);
+
+        if (changed) {
+            const meshes = new Set(casterMaterials.reduce((all, meshesById) => all.concat(Object.values(meshesById)), []));
+
+            for (let [mesh, buffers] of this.meshBuffers.entries()) {
+                if (!meshes.has(mesh)) {
+                    this.gl.deleteBuffer(buffers.vertices);
+                    this.gl.deleteBuffer(buffers.indices);
+                    this.meshBuffers.delete(mesh);
+                }
+            }
+
+            for (let cascade of this.cascades) {
+                cascade.key = null;
+            }
+
+            this.casterMaterials = casterMaterials;
+            this.meshes = [...meshes];
+        }
+    }
+
+    ensureTargets(quality) {
+        const gl = this.gl;
+
+        if (this.mapSize !== quality.mapSize || this.cascades.length !== quality.nCascades) {
+            for (let cascade of this.cascades) {
+                deleteTarget(gl, cascade.target);
+            }
+
+            this.cascades = [];
+            for (let i = 0; i < quality.nCascades; i++) {
+                this.cascades.push({ target: createTarget(gl, quality.mapSize, quality.mapSize, true), key: null, lightMatrix: mat4.create() });
+            }
+            this.mapSize = quality.mapSize;
+        }
+    }
+
+    drawCasters(program, target, matrix, setUniforms) {
+        const gl = this.gl;
+        gl.useProgram(program.program);
+        gl.bindFramebuffer(gl.FRAMEBUFFER, target.framebuffer);
+        gl.viewport(0, 0, target.width, target.height);
+        gl.uniformMatrix4fv(program.uniforms.matrix, false, matrix);
+        setUniforms && setUniforms(program.uniforms);
+        gl.enableVertexAttribArray(0);
+
+        for (let mesh of this.meshes) {
+            const { vertices, indices, count } = this.meshBuffersFor(mesh);
+            gl.bindBuffer(gl.ARRAY_BUFFER, vertices);
+            gl.vertexAttribPointer(0, 3, gl.FLOAT, false, 0, 0);
+            gl.bindBuffer(gl.ELEMENT_ARRAY_BUFFER, indices);
+            gl.drawElements(gl.TRIANGLES, count, gl.UNSIGNED_SHORT, 0);
+        }
+    }
+
+    // Cascades are concentric squares (in light space) around the point the camera looks at,
+    // snapped to a coarse grid so that small camera movements reuse the cached shadow maps
+    updateCascades(sun, focus, outerRadius) {
+        const gl = this.gl;
+        const up = Math.abs(sun[2]) > 0.99 ? [0, 1, 0] : [0, 0, 1];
+        const lightView = mat4.lookAt(mat4.create(), sun, [0, 0, 0], up);
+        const focusInLight = vec3.transformMat4(vec3.create(), focus, lightView);
+
+        this.cascades.forEach((cascade, i) => {
+            const radius = outerRadius / Math.pow(CASCADE_SPLIT, this.cascades.length - 1 - i);
+            const grid = radius / 8;
+            const [x, y, z] = focusInLight.map(coordinate => Math.round(coordinate / grid) * grid);
+            const key = [x, y, z, radius, ...sun].join(",");
+
+            if (cascade.key !== key) {
+                const depthRange = radius + MAX_CASTER_HEIGHT;
+                const projection = mat4.ortho(mat4.create(), x - radius, x + radius, y - radius, y + radius, -z - depthRange, -z + depthRange);
+                mat4.multiply(cascade.lightMatrix, projection, lightView);
+
+                gl.bindFramebuffer(gl.FRAMEBUFFER, cascade.target.framebuffer);
+                gl.clearColor(1.0, 1.0, 1.0, 1.0);
+                gl.clear(gl.COLOR_BUFFER_BIT | gl.DEPTH_BUFFER_BIT);
+                gl.enable(gl.DEPTH_TEST);
+                this.drawCasters(this.programs.shadowMap, cascade.target, cascade.lightMatrix);
+                gl.disable(gl.DEPTH_TEST);
+
+                cascade.key = key;
+            }
+        });
+    }
+
+    // Renders the buildings as seen from the camera into a target with a depth buffer,
+    // returns the uniforms that `viewPositionFunctions` need to make sense of it
+    renderViewDepth(target, viewMatrix, perspectiveMatrix, casterMaterials) {
+        const gl = this.gl;
+        const viewProjection = mat4.multiply(mat4.create(), perspectiveMatrix, viewMatrix);
+        const inverseView = mat4.invert(mat4.create(), viewMatrix);
+        const eye = vec3.transformMat4(vec3.create(), [0, 0, 0], inverseView);
+        const ahead = vec3.transformMat4(vec3.create(), [0, 0, -1], inverseView);
+        const forward = vec3.normalize(vec3.create(), vec3.sub(vec3.create(), ahead, eye));
+
+        this.updateCasters(casterMaterials);
+
+        gl.bindFramebuffer(gl.FRAMEBUFFER, target.framebuffer);
+        gl.clearColor(1.0, 1.0, 1.0, 1.0);
+        gl.clear(gl.COLOR_BUFFER_BIT | gl.DEPTH_BUFFER_BIT);
+        gl.enable(gl.DEPTH_TEST);
+        this.drawCasters(this.programs.viewDepth, target, viewProjection,
+            u => gl.uniform1f(u.maxDepth, MAX_VIEW_DEPTH));
+        gl.disable(gl.DEPTH_TEST);
+
+        const inverseViewProjection = mat4.invert(mat4.create(), viewProjection);
+        return { viewProjection, inverseViewProjection, eye, forward, texture: target.texture };
+    }
+
+    // Renders the shadowed frame into target and returns whether shadows were applied at all
+    render(pass, sourceTexture, target, { settings, time, view }) {
+        const gl = this.gl;
+        const sun = sunDirection(time);
+        if (!sun) return false;
+
+        const { inverseViewProjection, eye } = view;
+
+        const focus = groundHit(inverseViewProjection, eye, 0, 0);
+        if (!focus) return false;
+
+        const outerRadius = Math.min(MAX_SHADOW_DISTANCE, Math.max(...[[-1, -1], [1, -1], [-1, 1], [1, 1]].map(([x, y]) => {
+            const hit = groundHit(inverseViewProjection, eye, x, y);
+            return hit ? vec3.distance(hit, focus) : MAX_SHADOW_DISTANCE;
+        })));
+        // only grow and shrink the cascades in coarse steps, to keep them cacheable while zooming
+        const quantizedRadius = Math.pow(1.25, Math.ceil(Math.log(Math.max(outerRadius, 1)) / Math.log(1.25)));
+
+        const quality = QUALITY_LEVELS[settings.quality] || QUALITY_LEVELS[0];
+        this.ensureTargets(quality);
+        this.updateCascades(sun, focus, quantizedRadius);
+
+        const cascade = i => this.cascades[Math.min(i, this.cascades.length - 1)];
+
+        pass(this.programs.receiver, target, {
+            source: sourceTexture,
+            viewDepth: view.texture,
+            shadowMap0: cascade(0).target.texture,
+            shadowMap1: cascade(1).target.texture,
+            shadowMap2: cascade(2).target.texture,
+        }, u => {
+            gl.uniformMatrix4fv(u.lightMatrix0, false, cascade(0).lightMatrix);
+            gl.uniformMatrix4fv(u.lightMatrix1, false, cascade(1).lightMatrix);
+            gl.uniformMatrix4fv(u.lightMatrix2, false, cascade(2).lightMatrix);
+            setViewUniforms(gl, u, view);
+            gl.uniform1i(u.nCascades, this.cascades.length);
+            gl.uniform1f(u.texel, 1 / quality.mapSize);
+            gl.uniform1f(u.bias, DEPTH_BIAS);
+            gl.uniform1f(u.darkness, settings.darkness);
+            gl.uniform1i(u.pcf, quality.pcf);
+            gl.uniform3fv(u.sun, sun);
+            gl.uniform1f(u.surfaceOffset, SURFACE_OFFSET);
+        });
+
+        return true;
+    }
+}
+
+export function setViewUniforms(gl, uniforms, view) {
+    gl.uniformMatrix4fv(uniforms.inverseViewProjection, false, view.inverseViewProjection);
+    gl.uniform3fv(uniforms.eye, view.eye);
+    gl.uniform3fv(uniforms.forward, view.forward);
+    gl.uniform1f(uniforms.maxViewDepth, MAX_VIEW_DEPTH);
+}
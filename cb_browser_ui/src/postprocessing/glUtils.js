@@ -0,0 +1,73 @@
+export const fullscreenVertex = `
+attribute vec2 position;
+varying vec2 v_uv;
+void main() {
+    v_uv = position * 0.5 + 0.5;
+    gl_Position = vec4(position, 0.0, 1.0);
+}`;
+
+export function compileProgram(gl, fragmentSource, vertexSource = fullscreenVertex) {
+    const program = gl.createProgram();
+
+    for (let [type, source] of [[gl.VERTEX_SHADER, vertexSource], [gl.FRAGMENT_SHADER, fragmentSource]]) {
+        const shader = gl.createShader(type);
+        gl.shaderSource(shader, source);
+        gl.compileShader(shader);
+        if (!gl.getShaderParameter(shader, gl.COMPILE_STATUS)) {
+            throw new Error("Post-processing shader failed to compile: " + gl.getShaderInfoLog(shader));
+        }
+        gl.attachShader(program, shader);
+    }
+
+    gl.bindAttribLocation(program, 0, "position");
+    gl.linkProgram(program);
+    if (!gl.getProgramParameter(program, gl.LINK_STATUS)) {
+        throw new Error("Post-processing program failed to link: " + gl.getProgramInfoLog(program));
+    }
+
+    const uniforms = {};
+    const nUniforms = gl.getProgramParameter(program, gl.ACTIVE_UNIFORMS);
+    for (let i = 0; i < nUniforms; i++) {
+        const name = gl.getActiveUniform(program, i).name;
+        uniforms[name] = gl.getUniformLocation(program, name);
+    }
+
+    return { program, uniforms };
+}
+
+// depth-tested targets are used for rendering actual geometry (like shadow maps),
+// which must not be filtered since their texels encode depth, not color
+export function createTarget(gl, width, height, withDepth = false) {
+    const filter = withDepth ? gl.NEAREST : gl.LINEAR;
+    const texture = gl.createTexture();
+    gl.bindTexture(gl.TEXTURE_2D, texture);
+    gl.texImage2D(gl.TEXTURE_2D, 0, gl.RGBA, width, height, 0, gl.RGBA, gl.UNSIGNED_BYTE, null);
+    gl.texParameteri(gl.TEXTURE_2D, gl.TEXTURE_MIN_FILTER, filter);
+    gl.texParameteri(gl.TEXTURE_2D, gl.TEXTURE_MAG_FILTER, filter);
+    gl.texParameteri(gl.TEXTURE_2D, gl.TEXTURE_WRAP_S, gl.CLAMP_TO_EDGE);
+    gl.texParameteri(gl.TEXTURE_2D, gl.TEXTURE_WRAP_T, gl.CLAMP_TO_EDGE);
+
+    const framebuffer = gl.createFramebuffer();
+    gl.bindFramebuffer(gl.FRAMEBUFFER, framebuffer);
+    gl.framebufferTexture2D(gl.FRAMEBUFFER, gl.COLOR_ATTACHMENT0, gl.TEXTURE_2D, texture, 0);
+
+    let depthBuffer = null;
+    if (withDepth) {
+        depthBuffer = gl.createRenderbuffer();
+        gl.bindRenderbuffer(gl.RENDERBUFFER, depthBuffer);
+        gl.renderbufferStorage(gl.RENDERBUFFER, gl.DEPTH_COMPONENT16, width, height);
+        gl.framebufferRenderbuffer(gl.FRAMEBUFFER, gl.DEPTH_ATTACHMENT, gl.RENDERBUFFER, depthBuffer);
+    }
+
+    gl.bindFramebuffer(gl.FRAMEBUFFER, null);
+
+    return { texture, framebuffer, depthBuffer, width, height };
+}
+
+export function deleteTarget(gl, target) {
+    gl.deleteTexture(target.texture);
+    gl.deleteFramebuffer(target.framebuffer);
+    if (target.depthBuffer) {
+        gl.deleteRenderbuffer(target.depthBuffer);
+    }
+}
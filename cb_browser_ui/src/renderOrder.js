@@ -2,19 +2,29 @@ let i = 0;
 
 export default {
     deletedGestures: i++,
+    water: i++,
+    shore: i++,
     buildingGround: i++,
+    gridLayerOverlay: i++,
     addedGesturesZones: i++,
     addedGesturesZonesStipple: i++,
     addedGesturesZonesOutlines: i++,
+    sidewalks: i++,
     asphalt: i++,
     asphaltMarker: i++,
     asphaltMarkerGap: i++,
+    crosswalks: i++,
+    bikeLanes: i++,
     addedGesturesAsphalt: i++,
     addedGesturesMarker: i++,
     addedGesturesMarkerGap: i++,
+    polylineOverlays: i++,
     buildingOutlines: i++,
     building3D: i++,
     vegetation: i++,
     gestureInteractables: i++,
-    cars: i++
+    cars: i++,
+    trucks: i++,
+    pedestrians: i++,
+    debugGeometry: i++
 };
\ No newline at end of file
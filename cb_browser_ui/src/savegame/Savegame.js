@@ -0,0 +1,50 @@
+import React from 'react';
+import { Button } from 'antd';
+import update from 'immutability-helper';
+
+export const initialState = {
+    report: null,
+    dismissed: false
+}
+
+// The server reports what survived of the savegame when it was loaded,
+// which only needs attention if parts of it had to be dropped
+export function loadReport(setState) {
+    fetch("/savegame_report.json").then(response => response.json()).then(report =>
+        setState(oldState => update(oldState, { savegame: { report: { $set: report } } }))
+    ).catch(e => console.error("Couldn't load savegame report", e));
+}
+
+function isPartial(report) {
+    return report.subsystems.some(({ status }) => status === "Dropped");
+}
+
+export function Windows(props) {
+    const { state, setState } = props;
+    const { report, dismissed } = state.savegame;
+
+    if (!report || dismissed || !isPartial(report)) {
+        return null;
+    }
+
+    const dismiss = () => setState(oldState => update(oldState, { savegame: { dismissed: { $set: true } } }));
+
+    return <div key="savegame" className="window savegame">
+        <a className="close-window" onClick={dismiss}>×</a>
+        <h1>Savegame partially loaded</h1>
+        <p>This city was saved with version {report.saved_version} and couldn't be fully loaded
+            with version {report.current_version}.</p>
+        <table className="savegame-subsystems">
+            <tbody>
+                {report.subsystems.map(({ subsystem, status, consequence }) =>
+                    <tr key={subsystem} className={"savegame-" + status.toLowerCase()}>
+                        <th>{subsystem}</th>
+                        <td>{status}</td>
+                        <td>{consequence}</td>
+                    </tr>
+                )}
+            </tbody>
+        </table>
+        <Button onClick={dismiss}>Continue</Button>
+    </div>;
+}
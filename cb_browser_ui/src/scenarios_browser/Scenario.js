@@ -0,0 +1,43 @@
+import React from 'react';
+import { Progress } from 'antd';
+
+export const initialState = {
+    // null until the city turns out to be playing a scenario
+    name: null,
+    status: "Playing",
+    // [isWinCondition, description, progress]
+    goals: []
+}
+
+const STATUS_TEXT = {
+    Playing: null,
+    Won: "Scenario completed!",
+    Lost: "Scenario failed."
+};
+
+export function Windows(props) {
+    const { state } = props;
+    const { name, status, goals } = state.scenario;
+
+    if (!name) {
+        return null;
+    }
+
+    const goalList = isWin => goals.filter(([win]) => win === isWin).map(([, description, progress], i) =>
+        <div key={i} className="scenario-goal">
+            {description}
+            <Progress size="small" percent={Math.floor(progress * 100)}
+                status={isWin ? (progress >= 1 ? "success" : "active") : (progress >= 1 ? "exception" : "normal")}
+                showInfo={false} />
+        </div>
+    );
+    const loseGoals = goalList(false);
+
+    return <div key="scenario" className={"window scenario scenario-" + status.toLowerCase()}>
+        <h2>{name}</h2>
+        {STATUS_TEXT[status] && <p className="scenario-status">{STATUS_TEXT[status]}</p>}
+        <h3>Goals</h3>
+        {goalList(true)}
+        {loseGoals.length > 0 && [<h3 key="lose">Avoid</h3>, ...loseGoals]}
+    </div>
+}
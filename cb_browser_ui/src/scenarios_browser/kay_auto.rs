@@ -0,0 +1,92 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BrowserScenarioUI {
+    type ID = BrowserScenarioUIID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BrowserScenarioUIID {
+    _raw_id: RawID
+}
+
+impl Copy for BrowserScenarioUIID {}
+impl Clone for BrowserScenarioUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BrowserScenarioUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BrowserScenarioUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BrowserScenarioUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BrowserScenarioUIID {
+    fn eq(&self, other: &BrowserScenarioUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BrowserScenarioUIID {}
+
+impl TypedID for BrowserScenarioUIID {
+    type Target = BrowserScenarioUI;
+
+    fn from_raw(id: RawID) -> Self {
+        BrowserScenarioUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BrowserScenarioUIID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = BrowserScenarioUIID::from_raw(world.allocate_instance_id::<BrowserScenarioUI>());
+        let swarm = world.local_broadcast::<BrowserScenarioUI>();
+        world.send(swarm, MSG_BrowserScenarioUI_spawn(id, ));
+        id
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserScenarioUI_spawn(pub BrowserScenarioUIID, );
+
+impl Into<FrameListenerID> for BrowserScenarioUIID {
+    fn into(self) -> FrameListenerID {
+        FrameListenerID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<ScenarioUIID> for BrowserScenarioUIID {
+    fn into(self) -> ScenarioUIID {
+        ScenarioUIID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    FrameListenerID::register_implementor::<BrowserScenarioUI>(system);
+    ScenarioUIID::register_implementor::<BrowserScenarioUI>(system);
+    system.add_spawner::<BrowserScenarioUI, _, _>(
+        |&MSG_BrowserScenarioUI_spawn(id, ), world| {
+            BrowserScenarioUI::spawn(id, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,70 @@
+use kay::{World, ActorSystem, Actor, TypedID};
+use compact::{CVec, CString};
+use stdweb::serde::Serde;
+use browser_utils::{FrameListener, FrameListenerID};
+
+use scenarios::{ScenarioTrackerID, ScenarioUI, ScenarioUIID, ScenarioStatus, GoalProgress, Outcome};
+
+#[derive(Compact, Clone)]
+pub struct BrowserScenarioUI {
+    id: BrowserScenarioUIID,
+    asked: bool,
+}
+
+impl BrowserScenarioUI {
+    pub fn spawn(id: BrowserScenarioUIID, _: &mut World) -> BrowserScenarioUI {
+        BrowserScenarioUI { id, asked: false }
+    }
+}
+
+// Cities without a scenario don't have a tracker, so nobody answers
+impl FrameListener for BrowserScenarioUI {
+    fn on_frame(&mut self, world: &mut World) {
+        if !self.asked {
+            ScenarioTrackerID::global_broadcast(world).get_progress(self.id_as(), world);
+            self.asked = true;
+        }
+    }
+}
+
+impl ScenarioUI for BrowserScenarioUI {
+    fn on_scenario_progress(
+        &mut self,
+        name: &CString,
+        status: ScenarioStatus,
+        goals: &CVec<GoalProgress>,
+        _: &mut World,
+    ) {
+        let goals = goals
+            .iter()
+            .map(|goal| {
+                (
+                    goal.outcome == Outcome::Win,
+                    goal.description.to_string(),
+                    goal.progress,
+                )
+            })
+            .collect::<Vec<_>>();
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                scenario: {
+                    name: {"$set": @{name.to_string()}},
+                    status: {"$set": @{Serde(status)}},
+                    goals: {"$set": @{Serde(goals)}}
+                }
+            }));
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserScenarioUI>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserScenarioUIID::spawn(world);
+}
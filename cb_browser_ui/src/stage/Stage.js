@@ -1,6 +1,11 @@
 import React from 'react';
 import { vec3, vec4, mat4 } from 'gl-matrix';
 
+// touches don't come with modifier keys, so they are all off for them
+function modifiersOf(e) {
+    return { shift: !!e.shiftKey, alt: !!e.altKey };
+}
+
 export default class Stage extends React.Component {
     constructor(props) {
         super(props);
@@ -25,7 +30,7 @@ export default class Stage extends React.Component {
             if (maybeInteractableInfo) {
                 const [newActiveInteractable, projectedPosition, direction] = maybeInteractableInfo;
                 this.activeInteractable = newActiveInteractable;
-                this.activeInteractable.onEvent({ drag: { start: projectedPosition }, projectedPosition, direction });
+                this.activeInteractable.onEvent({ drag: { start: projectedPosition }, projectedPosition, direction, modifiers: modifiersOf(e) });
                 this.dragStart = cursorPosition3d;
                 this.dragStartProjectedPosition = projectedPosition;
                 this.dragStartDirection = direction;
@@ -44,7 +49,8 @@ export default class Stage extends React.Component {
                         projectedPosition: this.dragStartProjectedPosition,
                         direction: this.dragStartDirection,
                         now: cursorPosition3d,
-                    }
+                    },
+                    modifiers: modifiersOf(e)
                 })
             } else {
                 const oldHoveredInteractable = this.hoveredInteractable;
@@ -69,7 +75,8 @@ export default class Stage extends React.Component {
                                 now: cursorPosition3d,
                                 direction: hoveredDirection,
                                 projectedPosition
-                            }
+                            },
+                            modifiers: modifiersOf(e)
                         });
                     }
                 } else {
@@ -94,7 +101,8 @@ export default class Stage extends React.Component {
                         projectedPosition: this.dragStartProjectedPosition,
                         direction: this.dragStartDirection,
                         end: cursorPosition3d,
-                    }
+                    },
+                    modifiers: modifiersOf(e)
                 });
                 this.activeInteractable = null;
                 this.dragStart = null;
@@ -0,0 +1,54 @@
+import React from 'react';
+import { Button } from 'antd';
+import update from 'immutability-helper';
+
+export const initialState = {
+    show: false,
+    tileSize: 0,
+    owned: [],
+    purchasable: []
+}
+
+export const settingsSpec = {
+    toggleTerritoryWindowKey: { default: { key: 't' }, description: "Toggle Territory Window" }
+}
+
+let refreshInterval = null;
+
+export function Windows(props) {
+    const { state } = props;
+
+    if (state.territory.show) {
+        if (!refreshInterval) {
+            cbRustBrowser.get_territory_info();
+            refreshInterval = setInterval(() => cbRustBrowser.get_territory_info(), 1000);
+        }
+    } else {
+        if (refreshInterval) {
+            clearInterval(refreshInterval);
+            refreshInterval = null;
+        }
+    }
+
+    return state.territory.show && <div key="territory" className="window territory">
+        <h1>Territory</h1>
+        <p>{state.territory.owned.length} tiles of {state.territory.tileSize}m owned</p>
+        <h2>Purchasable Tiles</h2>
+        {state.territory.purchasable.map(([[x, y], price]) =>
+            <div key={x + "," + y}>
+                ({x}, {y}): {price.toFixed(0)}$
+                <Button onClick={() => cbRustBrowser.purchase_tile(x, y)}>Buy</Button>
+            </div>
+        )}
+    </div>
+}
+
+export function bindInputs(state, setState) {
+    const inputActions = {
+        "toggleTerritoryView": () => setState(oldState => update(oldState, {
+            territory: { show: { $apply: b => !b } }
+        })),
+    }
+
+    Mousetrap.bind(state.settings.territory.toggleTerritoryWindowKey.key, inputActions["toggleTerritoryView"]);
+}
@@ -0,0 +1,85 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BrowserTerritoryUI {
+    type ID = BrowserTerritoryUIID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BrowserTerritoryUIID {
+    _raw_id: RawID
+}
+
+impl Copy for BrowserTerritoryUIID {}
+impl Clone for BrowserTerritoryUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BrowserTerritoryUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BrowserTerritoryUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BrowserTerritoryUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BrowserTerritoryUIID {
+    fn eq(&self, other: &BrowserTerritoryUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BrowserTerritoryUIID {}
+
+impl TypedID for BrowserTerritoryUIID {
+    type Target = BrowserTerritoryUI;
+
+    fn from_raw(id: RawID) -> Self {
+        BrowserTerritoryUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BrowserTerritoryUIID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = BrowserTerritoryUIID::from_raw(world.allocate_instance_id::<BrowserTerritoryUI>());
+        let swarm = world.local_broadcast::<BrowserTerritoryUI>();
+        world.send(swarm, MSG_BrowserTerritoryUI_spawn(id, ));
+        id
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserTerritoryUI_spawn(pub BrowserTerritoryUIID, );
+
+impl Into<TerritoryUIID> for BrowserTerritoryUIID {
+    fn into(self) -> TerritoryUIID {
+        TerritoryUIID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    TerritoryUIID::register_implementor::<BrowserTerritoryUI>(system);
+    system.add_spawner::<BrowserTerritoryUI, _, _>(
+        |&MSG_BrowserTerritoryUI_spawn(id, ), world| {
+            BrowserTerritoryUI::spawn(id, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,69 @@
+use kay::{World, ActorSystem, TypedID};
+use compact::CVec;
+use stdweb::serde::Serde;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use stdweb::js_export;
+use {SYSTEM, local_origin};
+
+use descartes::N;
+use cb_planning::Tile;
+use economy::resources::ResourceAmount;
+use land_use::territory::{TerritoryID, TerritoryUI, TerritoryUIID};
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_territory_info() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TerritoryID::global_first(world)
+        .get_territory_info(BrowserTerritoryUIID::local_first(world).into(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn purchase_tile(x: i32, y: i32) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TerritoryID::global_first(world).purchase(local_origin(), (x, y), world);
+}
+
+#[derive(Compact, Clone)]
+pub struct BrowserTerritoryUI {
+    id: BrowserTerritoryUIID,
+}
+
+impl BrowserTerritoryUI {
+    pub fn spawn(id: BrowserTerritoryUIID, _: &mut World) -> BrowserTerritoryUI {
+        BrowserTerritoryUI { id }
+    }
+}
+
+impl TerritoryUI for BrowserTerritoryUI {
+    fn on_territory_info(
+        &mut self,
+        tile_size: N,
+        owned: &CVec<Tile>,
+        purchasable: &CVec<(Tile, ResourceAmount)>,
+        _world: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                territory: {
+                    tileSize: {"$set": @{tile_size}},
+                    owned: {"$set": @{Serde(owned)}},
+                    purchasable: {"$set": @{Serde(purchasable)}}
+                }
+            }));
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserTerritoryUI>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserTerritoryUIID::spawn(world);
+}
@@ -1,30 +1,47 @@
 import React from 'react';
-import { Slider } from 'antd';
+import { Button, Slider } from 'antd';
 import update from 'immutability-helper';
 
 export const initialState = {
     ticks: 0,
     time: [0, 0],
-    speed: 1
+    date: "",
+    weather: "Clear",
+    speed: 1,
+    paused: false
 }
 
 export function Windows(props) {
     const { state, setState } = props;
 
-    return <div className="sim-time">
+    return [<div key="time" className="sim-time" title={state.time.date + ", " + state.time.weather}>
         {(state.time.time[0] + "").padStart(2, "0")}
         <span className="sim-time-colon">:</span>
         {(state.time.time[1] + "").padStart(2, "0")}
         <Slider className="sim-speed"
-            value={state.time.speed == 0 ? 0 : Math.log2(state.time.speed) + 1}
-            min={0} max={6}
-            marks={{ 0: "||", 1: "1x", 3: "4x", 6: "32x" }}
+            value={Math.log2(state.time.speed)}
+            min={0} max={5}
+            marks={{ 0: "1x", 1: "2x", 2: "4x", 5: "32x" }}
             onChange={newSpeedLog => {
-                const newSpeed = newSpeedLog == 0 ? 0 : Math.pow(2, newSpeedLog - 1);
+                const newSpeed = Math.pow(2, newSpeedLog);
                 cbRustBrowser.set_sim_speed(newSpeed);
                 setState(oldState => update(oldState, { time: { speed: { $set: newSpeed } } }));
             }}
-            tipFormatter={speed => speed ? `Speed: ${Math.pow(2, speed - 1)}x` : "Pause"}
+            tipFormatter={speedLog => `Speed: ${Math.pow(2, speedLog)}x`}
         />
-    </div>
+        <div className="sim-controls">
+            <Button size="small"
+                type={state.time.paused ? "primary" : "default"}
+                onClick={() => {
+                    const paused = !state.time.paused;
+                    cbRustBrowser.set_sim_paused(paused);
+                    setState(oldState => update(oldState, { time: { paused: { $set: paused } } }));
+                }}>{state.time.paused ? "▶" : "||"}</Button>
+            <Button size="small" title="Single tick"
+                disabled={!state.time.paused}
+                onClick={() => cbRustBrowser.step_sim()}>+1</Button>
+        </div>
+    </div>,
+    state.time.weather != "Clear" &&
+        <div key="weather" className={"weather-effect weather-" + state.time.weather.toLowerCase()} />]
 }
\ No newline at end of file
@@ -72,6 +72,12 @@ impl Into<FrameListenerID> for BrowserTimeUIID {
     }
 }
 
+impl Into<WeatherListenerID> for BrowserTimeUIID {
+    fn into(self) -> WeatherListenerID {
+        WeatherListenerID::from_raw(self.as_raw())
+    }
+}
+
 impl Into<TimeUIID> for BrowserTimeUIID {
     fn into(self) -> TimeUIID {
         TimeUIID::from_raw(self.as_raw())
@@ -83,6 +89,7 @@ impl Into<TimeUIID> for BrowserTimeUIID {
 pub fn auto_setup(system: &mut ActorSystem) {
     
     FrameListenerID::register_implementor::<BrowserTimeUI>(system);
+    WeatherListenerID::register_implementor::<BrowserTimeUI>(system);
     TimeUIID::register_implementor::<BrowserTimeUI>(system);
     system.add_spawner::<BrowserTimeUI, _, _>(
         |&MSG_BrowserTimeUI_spawn(id, ), world| {
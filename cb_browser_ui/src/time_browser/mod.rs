@@ -2,39 +2,81 @@ use kay::{World, ActorSystem, Actor, TypedID};
 use stdweb::serde::Serde;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use stdweb::js_export;
-use SYSTEM;
+use {SYSTEM, local_origin};
 use browser_utils::{FrameListener, FrameListenerID};
 
 use cb_time::actors::TimeID;
 use cb_time::actors::ui::{TimeUI, TimeUIID};
 use cb_time::units::{TimeOfDay, Instant};
+use cb_time::calendar::Date;
+use environment::weather::{Weather, WeatherStationID, WeatherListener, WeatherListenerID};
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn set_sim_speed(new_speed: u16) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    TimeID::global_first(world).set_speed(new_speed, world);
+    TimeID::global_first(world).set_speed(local_origin(), new_speed, world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn set_sim_paused(paused: bool) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TimeID::global_first(world).set_paused(local_origin(), paused, world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn step_sim() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TimeID::global_first(world).step(local_origin(), world);
 }
 
 #[derive(Compact, Clone)]
 pub struct BrowserTimeUI {
     id: BrowserTimeUIID,
+    subscribed_to_weather: bool,
 }
 
 impl BrowserTimeUI {
     pub fn spawn(id: BrowserTimeUIID, _: &mut World) -> BrowserTimeUI {
-        BrowserTimeUI { id }
+        BrowserTimeUI {
+            id,
+            subscribed_to_weather: false,
+        }
     }
 }
 
 impl FrameListener for BrowserTimeUI {
     fn on_frame(&mut self, world: &mut World) {
         TimeID::global_first(world).get_info(self.id_as(), world);
+        if !self.subscribed_to_weather {
+            WeatherStationID::global_first(world).subscribe(self.id_as(), world);
+            self.subscribed_to_weather = true;
+        }
+    }
+}
+
+impl WeatherListener for BrowserTimeUI {
+    fn on_weather(&mut self, weather: Weather, _world: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                time: {
+                    weather: {"$set": @{format!("{:?}", weather)}}
+                }
+            }))
+        }
     }
 }
 
 impl TimeUI for BrowserTimeUI {
-    fn on_time_info(&mut self, current_instant: Instant, speed: u16, _world: &mut World) {
+    fn on_time_info(
+        &mut self,
+        current_instant: Instant,
+        speed: u16,
+        paused: bool,
+        _world: &mut World,
+    ) {
         js! {
             window.cbReactApp.boundSetState(oldState => update(oldState, {
                 time: {
@@ -42,7 +84,9 @@ impl TimeUI for BrowserTimeUI {
                     time: {"$set": @{
                         Serde(TimeOfDay::from(current_instant).hours_minutes())
                     }},
-                    speed: {"$set": @{speed}}
+                    date: {"$set": @{Date::from(current_instant).to_string()}},
+                    speed: {"$set": @{speed}},
+                    paused: {"$set": @{paused}}
                 }
             }))
         }
@@ -0,0 +1,202 @@
+import React from 'react';
+import { Button, Slider } from 'antd';
+import update from 'immutability-helper';
+import { districtName } from '../annotations/names';
+import { DISTRICT_SIZE } from '../land_use_browser/LandUse';
+import { districtDisplayName } from '../districts_browser/Districts';
+
+export const initialState = {
+    show: false,
+    snapshotTicks: [],
+    snapshots: {},
+    currentIdx: 0,
+    playing: false,
+    // [x, y] of the grid district or id of the drawn district drilled down into,
+    // or null for the whole city
+    selectedDistrict: null
+}
+
+export const settingsSpec = {
+    toggleTimelineWindowKey: { default: { key: 'h' }, description: "Toggle Timeline Window" }
+}
+
+const MAP_SIZE = 300;
+const PLAYBACK_INTERVAL = 200;
+
+let playbackInterval = null;
+
+function showSnapshot(state, setState, idx) {
+    const ticks = state.timeline.snapshotTicks[idx];
+    if (ticks !== undefined && !state.timeline.snapshots[ticks]) {
+        cbRustBrowser.get_timeline_snapshot(ticks);
+    }
+    setState(oldState => update(oldState, { timeline: { currentIdx: { $set: idx } } }));
+}
+
+function sameDistrict(a, b) {
+    return Array.isArray(a) && Array.isArray(b)
+        ? a[0] === b[0] && a[1] === b[1]
+        : a !== null && a === b;
+}
+
+function nameOf(district, drawnDistricts) {
+    return Array.isArray(district) ? districtName(...district) : districtDisplayName(drawnDistricts, district);
+}
+
+function selectDistrict(setState, district) {
+    setState(oldState => update(oldState, { timeline: { selectedDistrict: { $set: district } } }));
+}
+
+function SnapshotMap({ snapshot, selectedDistrict, setState }) {
+    const points = [
+        ...snapshot.building_positions,
+        ...snapshot.network_segments.flat()
+    ];
+
+    if (points.length === 0) {
+        return <svg width={MAP_SIZE} height={MAP_SIZE} />
+    }
+
+    const xs = points.map(p => p[0]);
+    const ys = points.map(p => p[1]);
+    const minX = Math.min(...xs), maxX = Math.max(...xs);
+    const minY = Math.min(...ys), maxY = Math.max(...ys);
+    const extent = Math.max(maxX - minX, maxY - minY, 1);
+    const project = ([x, y]) => [
+        (x - minX) / extent * (MAP_SIZE - 10) + 5,
+        MAP_SIZE - ((y - minY) / extent * (MAP_SIZE - 10) + 5)
+    ];
+
+    return <svg width={MAP_SIZE} height={MAP_SIZE} className="timeline-map">
+        {snapshot.districts.map(([district]) => {
+            const [x1, y1] = project([district[0] * DISTRICT_SIZE, (district[1] + 1) * DISTRICT_SIZE]);
+            const [x2, y2] = project([(district[0] + 1) * DISTRICT_SIZE, district[1] * DISTRICT_SIZE]);
+            return <rect key={"d" + district.join(",")} x={x1} y={y1} width={x2 - x1} height={y2 - y1}
+                className={"timeline-district" + (sameDistrict(district, selectedDistrict) ? " selected" : "")}
+                onClick={() => selectDistrict(setState, district)} />
+        })}
+        {snapshot.network_segments.map(([start, end], i) => {
+            const [x1, y1] = project(start);
+            const [x2, y2] = project(end);
+            return <line key={"n" + i} {...{ x1, y1, x2, y2 }} stroke="#888" strokeWidth={1} />
+        })}
+        {snapshot.building_positions.map((position, i) => {
+            const [cx, cy] = project(position);
+            return <circle key={"b" + i} {...{ cx, cy }} r={1.5} fill="#c95" />
+        })}
+    </svg>
+}
+
+function averageCommute(stats) {
+    return stats.n_commuters > 0
+        ? (stats.total_commute_minutes / stats.n_commuters).toFixed(0) + "min"
+        : "-";
+}
+
+// statistics that both the city as a whole and each district have
+function Stats({ stats }) {
+    return [
+        <p>Population: {stats.population}</p>,
+        <p>Jobs: {stats.n_jobs}</p>,
+        <p>Average Commute: {averageCommute(stats)}</p>,
+        <p>Buildings: {stats.n_buildings} ({(stats.built_area / 10000).toFixed(1)}ha)</p>,
+        <p>Network: {(stats.network_length / 1000).toFixed(1)}km</p>
+    ];
+}
+
+// once the player draws districts, the table lists them instead of the grid
+function DistrictTable({ snapshot, drawnDistricts, setState }) {
+    const entries = snapshot.drawn_districts.length > 0 ? snapshot.drawn_districts : snapshot.districts;
+    const byPopulation = [...entries].sort(([, a], [, b]) => b.population - a.population);
+
+    return <table className="timeline-districts">
+        <thead>
+            <tr><th>District</th><th>Population</th><th>Jobs</th><th>Commute</th></tr>
+        </thead>
+        <tbody>
+            {byPopulation.map(([district, stats]) =>
+                <tr key={String(district)} onClick={() => selectDistrict(setState, district)}>
+                    <td><a>{nameOf(district, drawnDistricts)}</a></td>
+                    <td>{stats.population}</td>
+                    <td>{stats.n_jobs}</td>
+                    <td>{averageCommute(stats)}</td>
+                </tr>
+            )}
+        </tbody>
+    </table>
+}
+
+export function Windows(props) {
+    const { state, setState } = props;
+    const { show, snapshotTicks, snapshots, currentIdx, playing, selectedDistrict } = state.timeline;
+
+    if (show && playing) {
+        if (!playbackInterval) {
+            playbackInterval = setInterval(() => {
+                const { snapshotTicks, currentIdx } = window.cbReactApp.state.timeline;
+                if (currentIdx + 1 < snapshotTicks.length) {
+                    showSnapshot(window.cbReactApp.state, setState, currentIdx + 1);
+                } else {
+                    setState(oldState => update(oldState, { timeline: { playing: { $set: false } } }));
+                }
+            }, PLAYBACK_INTERVAL);
+        }
+    } else if (playbackInterval) {
+        clearInterval(playbackInterval);
+        playbackInterval = null;
+    }
+
+    if (!show) {
+        return null;
+    }
+
+    const snapshot = snapshots[snapshotTicks[currentIdx]];
+    const drawnDistricts = state.districts.districts;
+    const selectedDistrictEntry = snapshot && [...snapshot.districts, ...snapshot.drawn_districts].find(([district]) => sameDistrict(district, selectedDistrict));
+    const selectedDistrictStats = selectedDistrictEntry && selectedDistrictEntry[1];
+
+    return <div key="timeline" className="window timeline">
+        <h1>Timeline</h1>
+        <Button onClick={() => cbRustBrowser.get_timeline()}>Refresh</Button>
+        <Button
+            disabled={snapshotTicks.length === 0}
+            onClick={() => setState(oldState => update(oldState, {
+                timeline: { playing: { $apply: p => !p } }
+            }))}>
+            {playing ? "Pause" : "Play"}
+        </Button>
+        <Slider
+            value={currentIdx}
+            min={0} max={Math.max(snapshotTicks.length - 1, 0)}
+            onChange={idx => showSnapshot(state, setState, idx)}
+        />
+        {snapshot
+            ? <div>
+                <SnapshotMap snapshot={snapshot} selectedDistrict={selectedDistrict} setState={setState} />
+                {selectedDistrictStats
+                    ? [
+                        <h2>{nameOf(selectedDistrict, drawnDistricts)} <a onClick={() => selectDistrict(setState, null)}>(back to city)</a></h2>,
+                        <Stats stats={selectedDistrictStats} />
+                    ]
+                    : [
+                        <Stats stats={snapshot} />,
+                        <p>Births and Deaths: +{snapshot.births} / -{snapshot.deaths}</p>,
+                        <DistrictTable snapshot={snapshot} drawnDistricts={drawnDistricts} setState={setState} />
+                    ]}
+            </div>
+            : <p>No snapshot selected</p>}
+    </div>
+}
+
+export function bindInputs(state, setState) {
+    const inputActions = {
+        "toggleTimelineView": () => {
+            cbRustBrowser.get_timeline();
+            setState(oldState => update(oldState, {
+                timeline: { show: { $apply: b => !b } }
+            }));
+        },
+    }
+
+    Mousetrap.bind(state.settings.timeline.toggleTimelineWindowKey.key, inputActions["toggleTimelineView"]);
+}
@@ -0,0 +1,85 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BrowserTimelineUI {
+    type ID = BrowserTimelineUIID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BrowserTimelineUIID {
+    _raw_id: RawID
+}
+
+impl Copy for BrowserTimelineUIID {}
+impl Clone for BrowserTimelineUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BrowserTimelineUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BrowserTimelineUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BrowserTimelineUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BrowserTimelineUIID {
+    fn eq(&self, other: &BrowserTimelineUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BrowserTimelineUIID {}
+
+impl TypedID for BrowserTimelineUIID {
+    type Target = BrowserTimelineUI;
+
+    fn from_raw(id: RawID) -> Self {
+        BrowserTimelineUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BrowserTimelineUIID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = BrowserTimelineUIID::from_raw(world.allocate_instance_id::<BrowserTimelineUI>());
+        let swarm = world.local_broadcast::<BrowserTimelineUI>();
+        world.send(swarm, MSG_BrowserTimelineUI_spawn(id, ));
+        id
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserTimelineUI_spawn(pub BrowserTimelineUIID, );
+
+impl Into<TimelineUIID> for BrowserTimelineUIID {
+    fn into(self) -> TimelineUIID {
+        TimelineUIID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    TimelineUIID::register_implementor::<BrowserTimelineUI>(system);
+    system.add_spawner::<BrowserTimelineUI, _, _>(
+        |&MSG_BrowserTimelineUI_spawn(id, ), world| {
+            BrowserTimelineUI::spawn(id, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,80 @@
+use kay::{World, ActorSystem, TypedID};
+use compact::CVec;
+use stdweb::serde::Serde;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use stdweb::js_export;
+use SYSTEM;
+
+use cb_time::units::Instant;
+use timeline::{TimelineID, TimelineUI, TimelineUIID, Snapshot};
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_timeline() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TimelineID::global_first(world)
+        .get_snapshot_instants(BrowserTimelineUIID::local_first(world).into(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_timeline_snapshot(ticks: u32) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TimelineID::global_first(world).get_snapshot(
+        Instant::new(ticks as usize),
+        BrowserTimelineUIID::local_first(world).into(),
+        world,
+    );
+}
+
+#[derive(Compact, Clone)]
+pub struct BrowserTimelineUI {
+    id: BrowserTimelineUIID,
+}
+
+impl BrowserTimelineUI {
+    pub fn spawn(id: BrowserTimelineUIID, _: &mut World) -> BrowserTimelineUI {
+        BrowserTimelineUI { id }
+    }
+}
+
+impl TimelineUI for BrowserTimelineUI {
+    fn on_snapshot_instants(&mut self, instants: &CVec<Instant>, _world: &mut World) {
+        let ticks: Vec<u32> = instants
+            .iter()
+            .map(|instant| instant.ticks() as u32)
+            .collect();
+
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                timeline: {
+                    snapshotTicks: {"$set": @{ticks}}
+                }
+            }));
+        }
+    }
+
+    fn on_snapshot(&mut self, snapshot: &Snapshot, _world: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                timeline: {
+                    snapshots: {
+                        [@{snapshot.instant.ticks() as u32}]: {"$set": @{Serde(snapshot)}}
+                    }
+                }
+            }));
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserTimelineUI>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserTimelineUIID::spawn(world);
+}
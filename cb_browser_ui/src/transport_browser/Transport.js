@@ -1,28 +1,94 @@
 import colors from '../colors';
 import renderOrder from '../renderOrder';
 import carMesh from './carMesh';
-import { RenderLayer } from '../browser_utils/Utils';
+import pedestrianMesh from './pedestrianMesh';
+import truckMesh from './truckMesh';
+import { RenderLayer, Interactive3DShape, fmtId } from '../browser_utils/Utils';
+import { AnnotationBatch, memoizedAnnotations } from '../annotations/Annotations';
+import { streetName } from '../annotations/names';
+import { PolylineOverlay } from '../overlays/Polylines';
+import { Toolbar } from '../toolbar';
 import React from 'react';
+import update from 'immutability-helper';
+import { Button, Select, InputNumber } from 'antd';
+const Option = Select.Option;
 
 export const initialState = {
     rendering: {
         staticMeshes: {
-            car: carMesh
+            car: carMesh,
+            truck: truckMesh,
+            pedestrian: pedestrianMesh
         },
         laneAsphaltGroups: new Map(),
         laneMarkerGroups: new Map(),
         laneMarkerGapGroups: new Map(),
-        carInstances: []
-    }
+        sidewalkGroups: new Map(),
+        crosswalkGroups: new Map(),
+        bikeLaneGroups: new Map(),
+        debugCenterlineGroups: new Map(),
+        debugConnectorGroups: new Map(),
+        carInstances: [],
+        truckInstances: [],
+        pedestrianInstances: []
+    },
+    debugPathfindingNodes: {},
+    streetLabels: {},
+    selectedCar: null,
+    transitLines: {},
+    railTracks: [],
+    trafficSignals: {},
+    incidents: {},
+    inspectionTool: "selectCar",
+    drawnTransitLine: [],
+    drawnTransitMode: "Bus",
+    tollSchemes: {},
+    drawnToll: [],
+    drawnTollKind: "Road",
+    tollPrices: { base: 1, peak: 3, peakStart: 7, peakEnd: 19 }
 };
 
+const ROUTE_WIDTH = 2.5;
+const TRAIL_WIDTH = 1.5;
+const TRAIL_DASH = [4.0, 3.0];
+const TRANSIT_LINE_WIDTH = 2.0;
+const RAIL_TRACK_WIDTH = 5.0;
+const DRAWN_TRANSIT_LINE_DASH = [6.0, 4.0];
+const TOLL_WIDTH = 3.0;
+const TOLL_DASH = [3.0, 3.0];
+// further than this from where the mouse went down, it's a camera pan, not a click
+const MAX_CLICK_DISTANCE = 3.0;
+
 const asphaltInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.asphalt]);
 const roadMarkerInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.roadMarker]);
+const sidewalkInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.sidewalk]);
+const crosswalkInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.crosswalk]);
+const bikeLaneInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.bikeLane]);
+const centerlineInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.debugLaneCenterline]);
+const connectorInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.debugIntersectionConnector]);
 
 export function Layers(props) {
     const { state } = props
+    const { geometryLayers } = state.debug;
+    const selectedCar = state.transport.selectedCar;
 
     return [
+        selectedCar && <PolylineOverlay
+            renderOrder={renderOrder.polylineOverlays}
+            polylines={routePolylines(selectedCar)} />,
+        <PolylineOverlay
+            renderOrder={renderOrder.polylineOverlays}
+            polylines={transitPolylines(state.transport.transitLines, state.transport.railTracks, state.transport.drawnTransitLine, state.transport.drawnTransitMode)} />,
+        <PolylineOverlay
+            renderOrder={renderOrder.polylineOverlays}
+            polylines={tollPolylines(state.transport.tollSchemes, state.transport.drawnToll, state.transport.drawnTollKind)} />,
+        <RenderLayer
+            renderOrder={renderOrder.sidewalks}
+            decal={true}
+            batches={[...state.transport.rendering.sidewalkGroups.values()].map(groupMesh => ({
+                mesh: groupMesh,
+                instances: sidewalkInstance
+            }))} />,
         <RenderLayer
             renderOrder={renderOrder.asphalt}
             decal={true}
@@ -44,12 +110,365 @@ export function Layers(props) {
                 mesh: groupMesh,
                 instances: asphaltInstance
             }))} />,
+        <RenderLayer
+            renderOrder={renderOrder.crosswalks}
+            decal={true}
+            batches={[...state.transport.rendering.crosswalkGroups.values()].map(groupMesh => ({
+                mesh: groupMesh,
+                instances: crosswalkInstance
+            }))} />,
+        <RenderLayer
+            renderOrder={renderOrder.bikeLanes}
+            decal={true}
+            batches={[...state.transport.rendering.bikeLaneGroups.values()].map(groupMesh => ({
+                mesh: groupMesh,
+                instances: bikeLaneInstance
+            }))} />,
         <RenderLayer
             renderOrder={renderOrder.cars}
             decal={false}
             batches={[{
                 mesh: state.transport.rendering.staticMeshes.car,
                 instances: state.transport.rendering.carInstances
-            }]} />
+            }]} />,
+        <RenderLayer
+            renderOrder={renderOrder.trucks}
+            decal={false}
+            batches={[{
+                mesh: state.transport.rendering.staticMeshes.truck,
+                instances: state.transport.rendering.truckInstances
+            }]} />,
+        <RenderLayer
+            renderOrder={renderOrder.pedestrians}
+            decal={false}
+            batches={[{
+                mesh: state.transport.rendering.staticMeshes.pedestrian,
+                instances: state.transport.rendering.pedestrianInstances
+            }]} />,
+        geometryLayers.laneCenterlines && <RenderLayer
+            renderOrder={renderOrder.debugGeometry}
+            decal={true}
+            batches={[...state.transport.rendering.debugCenterlineGroups.values()].map(groupMesh => ({
+                mesh: groupMesh,
+                instances: centerlineInstance
+            }))} />,
+        geometryLayers.intersectionConnectors && <RenderLayer
+            renderOrder={renderOrder.debugGeometry}
+            decal={true}
+            batches={[...state.transport.rendering.debugConnectorGroups.values()].map(groupMesh => ({
+                mesh: groupMesh,
+                instances: connectorInstance
+            }))} />
+    ];
+}
+
+// Lanes don't know which road they belong to, but lanes of the same straight road
+// are parallel and lie on roughly the same line, so they end up with the same name
+const ROAD_ANGLE_TOLERANCE = Math.PI / 36;
+const ROAD_OFFSET_TOLERANCE = 30;
+
+function roadKey(position, direction) {
+    const angle = (Math.atan2(direction[1], direction[0]) + Math.PI) % Math.PI;
+    const offset = -position[0] * Math.sin(angle) + position[1] * Math.cos(angle);
+    const nAngleSteps = Math.round(Math.PI / ROAD_ANGLE_TOLERANCE);
+    return [Math.round(angle / ROAD_ANGLE_TOLERANCE) % nAngleSteps, Math.round(offset / ROAD_OFFSET_TOLERANCE)];
+}
+
+const streetAnnotations = memoizedAnnotations(streetLabels =>
+    Object.keys(streetLabels).map(id => {
+        const { position, direction } = streetLabels[id];
+        return { key: id, position, direction, text: streetName(roadKey(position, direction)) };
+    })
+);
+
+const pathfindingAnnotations = memoizedAnnotations(debugPathfindingNodes =>
+    Object.keys(debugPathfindingNodes).map(id => {
+        const { position, landmark, hopsFromLandmark } = debugPathfindingNodes[id];
+        const landmarkInfo = landmark
+            ? (landmark === id ? "landmark" : `${fmtId(landmark)} +${hopsFromLandmark}`)
+            : "no landmark";
+        return { key: id, position, text: fmtId(id) + "\n" + landmarkInfo };
+    })
+);
+
+let lastSelectedCar = null;
+let lastRoutePolylines = [];
+
+// the route arrives as one path per lane, but is drawn as one continuous line
+function routePolylines(selectedCar) {
+    if (selectedCar !== lastSelectedCar) {
+        lastSelectedCar = selectedCar;
+        lastRoutePolylines = [
+            { points: selectedCar.trail, width: TRAIL_WIDTH, color: colors.routeTraveled, dash: TRAIL_DASH },
+            { points: [].concat(...selectedCar.route), width: ROUTE_WIDTH, color: colors.routeRemaining }
+        ];
+    }
+    return lastRoutePolylines;
+}
+
+let lastTransitLines = null;
+let lastRailTracks = null;
+let lastDrawnTransitLine = null;
+let lastDrawnTransitMode = null;
+let lastTransitPolylines = [];
+
+function transitLineColor(mode) {
+    return mode == "Rail" ? colors.railLine : colors.transitLine;
+}
+
+function transitPolylines(transitLines, railTracks, drawnTransitLine, drawnTransitMode) {
+    if (transitLines !== lastTransitLines || railTracks !== lastRailTracks
+        || drawnTransitLine !== lastDrawnTransitLine || drawnTransitMode !== lastDrawnTransitMode) {
+        lastTransitLines = transitLines;
+        lastRailTracks = railTracks;
+        lastDrawnTransitLine = drawnTransitLine;
+        lastDrawnTransitMode = drawnTransitMode;
+        // tracks go underneath the rail lines that run on them
+        lastTransitPolylines = railTracks.map(segment =>
+            ({ points: segment, width: RAIL_TRACK_WIDTH, color: colors.railTrack })
+        ).concat(Object.values(transitLines).map(line =>
+            ({ points: line.stops, width: TRANSIT_LINE_WIDTH, color: transitLineColor(line.mode) })
+        ));
+        if (drawnTransitLine.length > 1) {
+            lastTransitPolylines.push({
+                points: drawnTransitLine, width: TRANSIT_LINE_WIDTH, color: transitLineColor(drawnTransitMode), dash: DRAWN_TRANSIT_LINE_DASH
+            });
+        }
+    }
+    return lastTransitPolylines;
+}
+
+let lastTollSchemes = null;
+let lastDrawnToll = null;
+let lastDrawnTollKind = null;
+let lastTollPolylines = [];
+
+// cordons are drawn as the closed boundary of their zone
+function tollOutline(kind, outline) {
+    return kind == "Cordon" && outline.length > 2 ? [...outline, outline[0]] : outline;
+}
+
+function tollPolylines(tollSchemes, drawnToll, drawnTollKind) {
+    if (tollSchemes !== lastTollSchemes || drawnToll !== lastDrawnToll || drawnTollKind !== lastDrawnTollKind) {
+        lastTollSchemes = tollSchemes;
+        lastDrawnToll = drawnToll;
+        lastDrawnTollKind = drawnTollKind;
+        lastTollPolylines = Object.values(tollSchemes).map(scheme =>
+            ({ points: tollOutline(scheme.kind, scheme.outline), width: TOLL_WIDTH, color: colors.toll })
+        );
+        if (drawnToll.length > 1) {
+            lastTollPolylines.push({
+                points: tollOutline(drawnTollKind, drawnToll), width: TOLL_WIDTH, color: colors.toll, dash: TOLL_DASH
+            });
+        }
+    }
+    return lastTollPolylines;
+}
+
+function formatDistance(meters) {
+    return meters >= 1000 ? (meters / 1000).toFixed(1) + " km" : Math.round(meters) + " m";
+}
+
+const incidentAnnotations = memoizedAnnotations(incidents =>
+    Object.keys(incidents).map(id => {
+        const { position, kind } = incidents[id];
+        return { key: id, position, text: kind == "Accident" ? "Accident" : "Closed" };
+    })
+);
+
+const selectedCarAnnotations = memoizedAnnotations(selectedCar => {
+    if (!selectedCar.position) {
+        return [];
+    }
+
+    const progress = selectedCar.progress !== null
+        ? ` (${Math.round(selectedCar.progress * 100)}%)`
+        : "";
+    return [{
+        key: selectedCar.trip,
+        position: selectedCar.position,
+        text: fmtId(selectedCar.trip) + "\n" + formatDistance(selectedCar.remainingDistance) + " to go" + progress
+    }];
+});
+
+export function Shapes(props) {
+    const { state, setState } = props;
+
+    // every click on the ground adds the next stop of the line being drawn
+    if (state.uiMode == "transit") {
+        return <Interactive3DShape
+            id="transitLineDrawing"
+            key="transitLineDrawing"
+            shape={{ type: "everywhere" }}
+            zIndex={1}
+            cursorHover="crosshair"
+            cursorActive="pointer"
+            onEvent={e => {
+                if (e.drag && e.drag.end && Math.hypot(e.drag.end[0] - e.drag.start[0], e.drag.end[1] - e.drag.start[1]) < MAX_CLICK_DISTANCE) {
+                    const stop = [e.drag.end[0], e.drag.end[1]];
+                    setState(oldState => update(oldState, {
+                        transport: { drawnTransitLine: { $push: [stop] } }
+                    }));
+                }
+            }} />;
+    }
+
+    // the same for the road or zone boundary of a toll
+    if (state.uiMode == "tolls") {
+        return <Interactive3DShape
+            id="tollDrawing"
+            key="tollDrawing"
+            shape={{ type: "everywhere" }}
+            zIndex={1}
+            cursorHover="crosshair"
+            cursorActive="pointer"
+            onEvent={e => {
+                if (e.drag && e.drag.end && Math.hypot(e.drag.end[0] - e.drag.start[0], e.drag.end[1] - e.drag.start[1]) < MAX_CLICK_DISTANCE) {
+                    const point = [e.drag.end[0], e.drag.end[1]];
+                    setState(oldState => update(oldState, {
+                        transport: { drawnToll: { $push: [point] } }
+                    }));
+                }
+            }} />;
+    }
+
+    if (state.uiMode != "inspection") {
+        return null;
+    }
+
+    // clicking a lane closes it for construction, clicking it again opens it
+    if (state.transport.inspectionTool == "closeLane") {
+        return <Interactive3DShape
+            id="laneClosing"
+            key="laneClosing"
+            shape={{ type: "everywhere" }}
+            zIndex={1}
+            cursorHover="crosshair"
+            cursorActive="pointer"
+            onEvent={e => {
+                if (e.drag && e.drag.end && Math.hypot(e.drag.end[0] - e.drag.start[0], e.drag.end[1] - e.drag.start[1]) < MAX_CLICK_DISTANCE) {
+                    cbRustBrowser.toggle_lane_closure_near([e.drag.end[0], e.drag.end[1]]);
+                }
+            }} />;
+    }
+
+    return <Interactive3DShape
+        id="carSelection"
+        key="carSelection"
+        shape={{ type: "everywhere" }}
+        zIndex={1}
+        cursorHover="default"
+        cursorActive="default"
+        onEvent={e => {
+            if (e.drag && e.drag.end && Math.hypot(e.drag.end[0] - e.drag.start[0], e.drag.end[1] - e.drag.start[1]) < MAX_CLICK_DISTANCE) {
+                cbRustBrowser.select_car_near([e.drag.end[0], e.drag.end[1]]);
+            }
+        }} />;
+}
+
+export function Tools(props) {
+    const { state, setState } = props;
+
+    if (state.uiMode == "inspection") {
+        return <Toolbar id="inspection-tool-toolbar"
+            options={{ selectCar: { description: "Select cars" }, closeLane: { description: "Close lanes" } }}
+            value={state.transport.inspectionTool}
+            onChange={newTool => setState(oldState => update(oldState, {
+                transport: { inspectionTool: { $set: newTool } }
+            }))} />;
+    }
+
+    if (state.uiMode == "tolls") {
+        return tollTools(state, setState);
+    }
+
+    if (state.uiMode != "transit") {
+        return null;
+    }
+
+    const { drawnTransitLine, drawnTransitMode } = state.transport;
+    const discard = () => setState(oldState => update(oldState, {
+        transport: { drawnTransitLine: { $set: [] } }
+    }));
+
+    return [
+        <Toolbar id="transit-mode-toolbar"
+            options={{ Bus: { description: "Bus" }, Rail: { description: "Rail" } }}
+            value={drawnTransitMode}
+            onChange={newMode => setState(oldState => update(oldState, {
+                transport: { drawnTransitMode: { $set: newMode } }
+            }))} />,
+        <Button type="primary"
+            disabled={drawnTransitLine.length < 2}
+            onClick={() => {
+                cbRustBrowser.add_transit_line(drawnTransitMode, drawnTransitLine);
+                discard();
+            }}>Open line</Button>,
+        <Button disabled={drawnTransitLine.length == 0} onClick={discard}>Discard</Button>,
+        Object.keys(state.transport.transitLines).length > 0 && <Select
+            style={{ width: 180 }}
+            placeholder="Close a line"
+            value={undefined}
+            onChange={lineKey => cbRustBrowser.remove_transit_line(state.transport.transitLines[lineKey].id)}
+        >{Object.keys(state.transport.transitLines).map(lineKey =>
+            <Option value={lineKey}>{state.transport.transitLines[lineKey].mode == "Rail" ? "Rail line" : "Bus line"} {fmtId(state.transport.transitLines[lineKey].id)} ({state.transport.transitLines[lineKey].stops.length} stops)</Option>
+        )}</Select>
+    ];
+}
+
+function tollTools(state, setState) {
+    const { drawnToll, drawnTollKind, tollPrices, tollSchemes } = state.transport;
+    const discard = () => setState(oldState => update(oldState, {
+        transport: { drawnToll: { $set: [] } }
+    }));
+    const setPrice = (key, value) => setState(oldState => update(oldState, {
+        transport: { tollPrices: { [key]: { $set: value } } }
+    }));
+
+    return [
+        <Toolbar id="toll-kind-toolbar"
+            options={{ Road: { description: "Toll road" }, Cordon: { description: "Toll zone" } }}
+            value={drawnTollKind}
+            onChange={newKind => setState(oldState => update(oldState, {
+                transport: { drawnTollKind: { $set: newKind } }
+            }))} />,
+        "Price",
+        <InputNumber value={tollPrices.base} min={0} step={0.5} onChange={price => setPrice("base", price)} />,
+        "Peak price",
+        <InputNumber value={tollPrices.peak} min={0} step={0.5} onChange={price => setPrice("peak", price)} />,
+        "Peak hours",
+        <InputNumber value={tollPrices.peakStart} min={0} max={23} onChange={hour => setPrice("peakStart", hour)} />,
+        <InputNumber value={tollPrices.peakEnd} min={0} max={23} onChange={hour => setPrice("peakEnd", hour)} />,
+        <Button type="primary"
+            disabled={drawnToll.length < (drawnTollKind == "Cordon" ? 3 : 2)}
+            onClick={() => {
+                cbRustBrowser.designate_toll(drawnTollKind, drawnToll, tollPrices.base, tollPrices.peak, tollPrices.peakStart, tollPrices.peakEnd);
+                discard();
+            }}>Charge toll</Button>,
+        <Button disabled={drawnToll.length == 0} onClick={discard}>Discard</Button>,
+        Object.keys(tollSchemes).length > 0 && <Select
+            style={{ width: 220 }}
+            placeholder="Lift a toll"
+            value={undefined}
+            onDropdownVisibleChange={open => open && cbRustBrowser.get_toll_info()}
+            onChange={schemeKey => cbRustBrowser.lift_toll(tollSchemes[schemeKey].id)}
+        >{Object.keys(tollSchemes).map(schemeKey =>
+            <Option value={schemeKey}>{tollSchemes[schemeKey].kind == "Cordon" ? "Toll zone" : "Toll road"} #{tollSchemes[schemeKey].id} ({Math.round(tollSchemes[schemeKey].collected)} collected)</Option>
+        )}</Select>
+    ];
+}
+
+export function Annotations(props) {
+    const { state } = props;
+
+    return [
+        state.transport.selectedCar && <AnnotationBatch key="selectedCar" kind="selection"
+            annotations={selectedCarAnnotations(state.transport.selectedCar)} />,
+        <AnnotationBatch key="incidents" kind="incident"
+            annotations={incidentAnnotations(state.transport.incidents)} />,
+        <AnnotationBatch key="streets" kind="street"
+            annotations={streetAnnotations(state.transport.streetLabels)} />,
+        state.debug.geometryLayers.pathfindingNodes && <AnnotationBatch key="pathfinding" kind="debug"
+            annotations={pathfindingAnnotations(state.transport.debugPathfindingNodes)} />
     ];
 }
\ No newline at end of file
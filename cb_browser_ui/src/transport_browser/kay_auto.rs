@@ -61,10 +61,22 @@ impl BrowserTransportUIID {
         world.send(swarm, MSG_BrowserTransportUI_spawn(id, ));
         id
     }
+    
+    pub fn select_car_near(self, position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_BrowserTransportUI_select_car_near(position));
+    }
+    
+    pub fn select_trip(self, trip: TripID, world: &mut World) {
+        world.send(self.as_raw(), MSG_BrowserTransportUI_select_trip(trip));
+    }
 }
 
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_BrowserTransportUI_spawn(pub BrowserTransportUIID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserTransportUI_select_car_near(pub P2);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserTransportUI_select_trip(pub TripID);
 
 impl Into<FrameListenerID> for BrowserTransportUIID {
     fn into(self) -> FrameListenerID {
@@ -89,4 +101,16 @@ pub fn auto_setup(system: &mut ActorSystem) {
             BrowserTransportUI::spawn(id, world)
         }, false
     );
+    
+    system.add_handler::<BrowserTransportUI, _, _>(
+        |&MSG_BrowserTransportUI_select_car_near(position), instance, world| {
+            instance.select_car_near(position, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<BrowserTransportUI, _, _>(
+        |&MSG_BrowserTransportUI_select_trip(trip), instance, world| {
+            instance.select_trip(trip, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
@@ -1,9 +1,124 @@
 use kay::{World, ActorSystem, Actor, RawID, External, TypedID};
 use compact::CVec;
 use std::collections::HashMap;
-use descartes::LinePath;
-use michelangelo::{MeshGrouper, Instance};
+use descartes::{LinePath, P2, V2};
+use michelangelo::{Mesh, MeshGrouper, Instance};
+use stdweb::serde::Serde;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use stdweb::js_export;
 use browser_utils::{FrameListener, FrameListenerID, flatten_instances, updated_groups_to_js};
+use {SYSTEM, local_origin};
+
+const DEBUG_LINE_WIDTH: f32 = 0.3;
+const CAR_SELECTION_RADIUS: f32 = 6.0;
+// cars briefly disappear while they are handed over between lanes
+const MAX_FRAMES_SELECTED_CAR_UNSEEN: usize = 120;
+const TRAIL_RESOLUTION: f32 = 2.0;
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_pathfinding_debug_info() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::transport::lane::LaneID::global_broadcast(world)
+        .get_pathfinding_info(BrowserTransportUIID::local_first(world).into(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_traffic_signal_info() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TrafficSignalID::global_broadcast(world)
+        .get_signal_info(BrowserTransportUIID::local_first(world).into(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn set_signal_phase_duration(signal: Serde<TrafficSignalID>, phase: u8, seconds: u32) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    signal
+        .0
+        .set_phase_duration(local_origin(), phase, Duration(seconds), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn select_car_near(position: Serde<P2>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    BrowserTransportUIID::local_first(world).select_car_near(position.0, world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn toggle_lane_closure_near(position: Serde<P2>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::transport::lane::LaneID::global_broadcast(world).toggle_closure_near(
+        local_origin(),
+        position.0,
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn select_trip(trip: Serde<TripID>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    BrowserTransportUIID::local_first(world).select_trip(trip.0, world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn add_transit_line(mode: Serde<TransitMode>, points: Serde<Vec<P2>>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TransitNetworkID::global_first(world).add_line(local_origin(), mode.0, points.0.into(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn remove_transit_line(line: Serde<TransitLineID>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TransitNetworkID::global_first(world).remove_line(local_origin(), line.0, world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn designate_toll(
+    kind: Serde<TollKind>,
+    points: Serde<Vec<P2>>,
+    base_price: f32,
+    peak_price: f32,
+    peak_start_hour: u32,
+    peak_end_hour: u32,
+) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    let prices = TollPrices {
+        base: base_price,
+        peak: peak_price,
+        peak_hours: TimeOfDayRange::new(peak_start_hour as usize, 0, peak_end_hour as usize, 0),
+    };
+    TollRegistryID::global_first(world).designate(
+        local_origin(),
+        kind.0,
+        points.0.into(),
+        prices,
+        world,
+    );
+}
+
+// also tells how much each toll collected so far
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_toll_info() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TollRegistryID::global_first(world)
+        .get_toll_info(BrowserTransportUIID::local_first(world).into(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn lift_toll(scheme: u16) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TollRegistryID::global_first(world).lift(local_origin(), scheme, world);
+}
 
 #[derive(Compact, Clone)]
 pub struct BrowserTransportUI {
@@ -27,12 +142,92 @@ impl ::std::ops::DerefMut for BrowserTransportUI {
 
 pub struct BrowserTransportUINonPersistedState {
     car_instance_buffers: HashMap<RawID, Vec<::michelangelo::Instance>>,
+    truck_instance_buffers: HashMap<RawID, Vec<::michelangelo::Instance>>,
+    car_trips: HashMap<RawID, Vec<(TripID, P2)>>,
     car_colors: Vec<[f32; 3]>,
+    // buses and trains report where they are every frame, so they are collected anew every frame
+    transit_vehicle_instances: Vec<::michelangelo::Instance>,
+    bus_color: [f32; 3],
+    train_color: [f32; 3],
+    pedestrian_color: [f32; 3],
+    truck_color: [f32; 3],
+    selected_car: Option<SelectedCar>,
 
     // transport geometry
     asphalt_grouper: MeshGrouper<RawID>,
     lane_marker_grouper: MeshGrouper<RawID>,
     lane_marker_gaps_grouper: MeshGrouper<RawID>,
+    sidewalk_grouper: MeshGrouper<RawID>,
+    crosswalk_grouper: MeshGrouper<RawID>,
+    bike_lane_grouper: MeshGrouper<RawID>,
+
+    // debug geometry
+    lane_centerline_grouper: MeshGrouper<RawID>,
+    intersection_connector_grouper: MeshGrouper<RawID>,
+}
+
+struct SelectedCar {
+    trip: TripID,
+    lane: Option<RawID>,
+    frames_unseen: usize,
+    trail: Vec<P2>,
+    trail_length: f32,
+    trail_length_when_traced: f32,
+    route: Vec<Option<LinePath>>,
+    route_complete: bool,
+    changed: bool,
+}
+
+impl SelectedCar {
+    fn new(trip: TripID) -> SelectedCar {
+        SelectedCar {
+            trip,
+            lane: None,
+            frames_unseen: 0,
+            trail: Vec::new(),
+            trail_length: 0.0,
+            trail_length_when_traced: 0.0,
+            route: Vec::new(),
+            route_complete: false,
+            changed: true,
+        }
+    }
+
+    fn remaining_distance(&self) -> f32 {
+        let route_length: f32 = self
+            .route
+            .iter()
+            .filter_map(|maybe_hop| maybe_hop.as_ref().map(|hop| hop.length()))
+            .sum();
+        (route_length - (self.trail_length - self.trail_length_when_traced)).max(0.0)
+    }
+
+    fn to_js(&self) -> ::stdweb::Value {
+        let points = |path: &LinePath| path.points.iter().map(|p| [p.x, p.y]).collect::<Vec<_>>();
+        let trail = self.trail.iter().map(|p| [p.x, p.y]).collect::<Vec<_>>();
+        let route = self
+            .route
+            .iter()
+            .filter_map(|maybe_hop| maybe_hop.as_ref().map(points))
+            .collect::<Vec<_>>();
+        let remaining_distance = self.remaining_distance();
+        let progress = if self.route_complete {
+            Some(self.trail_length / (self.trail_length + remaining_distance).max(1.0))
+        } else {
+            None
+        };
+
+        js! {
+            return {
+                trip: @{Serde(self.trip)},
+                position: @{Serde(self.trail.last().cloned())},
+                trail: @{Serde(trail)},
+                route: @{Serde(route)},
+                remainingDistance: @{remaining_distance},
+                progress: @{Serde(progress)}
+            };
+        }
+    }
 }
 
 impl BrowserTransportUI {
@@ -41,16 +236,36 @@ impl BrowserTransportUI {
             ::transport::lane::LaneID::global_broadcast(world).get_render_info(id.into(), world);
             ::transport::lane::SwitchLaneID::global_broadcast(world)
                 .get_render_info(id.into(), world);
+            TransitLineID::global_broadcast(world).get_render_info(id.into(), world);
+            TransitNetworkID::global_first(world).get_track_render_info(id.into(), world);
+            FootwayID::global_broadcast(world).get_render_info(id.into(), world);
+            BikewayID::global_broadcast(world).get_render_info(id.into(), world);
+            ::transport::lane::LaneID::global_broadcast(world)
+                .get_incident_info(id.into(), world);
+            TollRegistryID::global_first(world).get_toll_info(id.into(), world);
         }
 
         BrowserTransportUI {
             id,
             state: External::new(BrowserTransportUINonPersistedState {
                 car_instance_buffers: HashMap::new(),
+                truck_instance_buffers: HashMap::new(),
+                car_trips: HashMap::new(),
                 car_colors: vec![[0.0, 0.0, 0.0]],
+                transit_vehicle_instances: Vec::new(),
+                bus_color: [0.0, 0.0, 0.0],
+                train_color: [0.0, 0.0, 0.0],
+                pedestrian_color: [0.0, 0.0, 0.0],
+                truck_color: [0.0, 0.0, 0.0],
+                selected_car: None,
                 asphalt_grouper: MeshGrouper::new(2000),
                 lane_marker_grouper: MeshGrouper::new(2000),
                 lane_marker_gaps_grouper: MeshGrouper::new(2000),
+                sidewalk_grouper: MeshGrouper::new(2000),
+                crosswalk_grouper: MeshGrouper::new(2000),
+                bike_lane_grouper: MeshGrouper::new(2000),
+                lane_centerline_grouper: MeshGrouper::new(2000),
+                intersection_connector_grouper: MeshGrouper::new(2000),
             }),
         }
     }
@@ -60,6 +275,8 @@ impl FrameListener for BrowserTransportUI {
     fn on_frame(&mut self, world: &mut World) {
         ::transport::lane::LaneID::global_broadcast(world).get_car_info(self.id_as(), world);
         ::transport::lane::SwitchLaneID::global_broadcast(world).get_car_info(self.id_as(), world);
+        TransitVehicleID::global_broadcast(world).get_vehicle_info(self.id_as(), world);
+        PedestrianNetworkID::global_first(world).get_pedestrian_info(self.id_as(), world);
 
         let mut car_instances = Vec::with_capacity(600_000);
 
@@ -67,18 +284,32 @@ impl FrameListener for BrowserTransportUI {
             car_instances.extend_from_slice(lane_instances);
         }
 
+        car_instances.extend_from_slice(&self.transit_vehicle_instances);
+        self.transit_vehicle_instances.clear();
+
+        let mut truck_instances = Vec::new();
+
+        for lane_instances in self.truck_instance_buffers.values() {
+            truck_instances.extend_from_slice(lane_instances);
+        }
+
         let car_instances_js: ::stdweb::web::TypedArray<f32> =
             flatten_instances(&car_instances).into();
+        let truck_instances_js: ::stdweb::web::TypedArray<f32> =
+            flatten_instances(&truck_instances).into();
 
         js! {
             window.cbReactApp.boundSetState(oldState => update(oldState, {
                 transport: {rendering: {
-                    carInstances: {"$set": @{car_instances_js}}
+                    carInstances: {"$set": @{car_instances_js}},
+                    truckInstances: {"$set": @{truck_instances_js}}
                 }}
             }))
         }
 
-        use ::stdweb::unstable::TryInto;
+        self.update_selected_car(world);
+
+        use stdweb::unstable::TryInto;
 
         let car_color_vals: Vec<::stdweb::Value> = js! {
             return require("../../../src/colors").default.carColors;
@@ -93,25 +324,50 @@ impl FrameListener for BrowserTransportUI {
                 [color[0] as f32, color[1] as f32, color[2] as f32]
             })
             .collect();
+
+        let agent_colors: Vec<Vec<f64>> = js! {
+            const colors = require("../../../src/colors").default;
+            return [colors.transitBus, colors.transitTrain, colors.pedestrian, colors.truck];
+        }
+        .try_into()
+        .unwrap();
+        let to_color = |color: &Vec<f64>| [color[0] as f32, color[1] as f32, color[2] as f32];
+        self.bus_color = to_color(&agent_colors[0]);
+        self.train_color = to_color(&agent_colors[1]);
+        self.pedestrian_color = to_color(&agent_colors[2]);
+        self.truck_color = to_color(&agent_colors[3]);
     }
 }
 
-use transport::ui::{TransportUI, TransportUIID, CarRenderInfo};
+use transport::ui::{TransportUI, TransportUIID, CarRenderInfo, PedestrianRenderInfo,
+RouteTraceableID};
+use transport::transport_planning::Elevation;
+use transport::pathfinding::trip::TripID;
+use transport::transit::{TransitNetworkID, TransitLineID, TransitVehicleID, TransitMode};
+use transport::signals::TrafficSignalID;
+use transport::incidents::IncidentKind;
+use transport::tolls::{TollRegistryID, TollKind, TollPrices};
+use transport::microtraffic::VehicleClass;
+use transport::pedestrians::{FootwayID, PedestrianNetworkID};
+use transport::cycling::BikewayID;
+use cb_time::units::{Duration, TimeOfDayRange};
 
 impl TransportUI for BrowserTransportUI {
     fn on_lane_constructed(
         &mut self,
         id: RawID,
         lane_path: &LinePath,
+        elevation: Elevation,
         is_switch: bool,
         on_intersection: bool,
         _world: &mut World,
     ) {
-        use ::transport::ui::{lane_mesh, marker_mesh, switch_marker_gap_mesh};
+        use transport::ui::{lane_mesh, marker_mesh, switch_marker_gap_mesh, elevated};
         if is_switch {
-            let updated_lane_marker_gaps_groups = self
-                .lane_marker_gaps_grouper
-                .update(None, Some((id, switch_marker_gap_mesh(lane_path))));
+            let updated_lane_marker_gaps_groups = self.lane_marker_gaps_grouper.update(
+                None,
+                Some((id, elevated(switch_marker_gap_mesh(lane_path), &elevation))),
+            );
 
             js! {
                 window.cbReactApp.boundSetState(oldState => update(oldState, {
@@ -125,8 +381,23 @@ impl TransportUI for BrowserTransportUI {
                 }));
             }
         } else {
-            let mesh = lane_mesh(lane_path);
+            let mesh = elevated(lane_mesh(lane_path), &elevation);
             let updated_asphalt_groups = self.asphalt_grouper.update(None, Some((id, mesh)));
+            if !on_intersection {
+                let half_length = lane_path.length() / 2.0;
+                update_street_label(
+                    id,
+                    Some((
+                        lane_path.along(half_length),
+                        lane_path.direction_along(half_length),
+                    )),
+                );
+            }
+            self.update_debug_geometry(
+                id,
+                Some(Mesh::from_path_as_band(lane_path, DEBUG_LINE_WIDTH, 0.2)),
+                on_intersection,
+            );
 
             if on_intersection {
                 js! {
@@ -142,9 +413,10 @@ impl TransportUI for BrowserTransportUI {
                 }
             } else {
                 let marker_meshes = marker_mesh(lane_path);
-                let updated_lane_marker_groups = self
-                    .lane_marker_grouper
-                    .update(None, Some((id, marker_meshes.0 + marker_meshes.1)));
+                let updated_lane_marker_groups = self.lane_marker_grouper.update(
+                    None,
+                    Some((id, elevated(marker_meshes.0 + marker_meshes.1, &elevation))),
+                );
                 js! {
                     window.cbReactApp.boundSetState(oldState => update(oldState, {
                         transport: {rendering: {
@@ -189,6 +461,16 @@ impl TransportUI for BrowserTransportUI {
             }
         } else {
             let updated_asphalt_groups = self.asphalt_grouper.update(Some(id), None);
+            self.update_debug_geometry(id, None, on_intersection);
+            if !on_intersection {
+                update_street_label(id, None);
+            }
+
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    transport: {incidents: {"$unset": [@{Serde(id)}]}}
+                }));
+            }
 
             if on_intersection {
                 js! {
@@ -225,16 +507,475 @@ impl TransportUI for BrowserTransportUI {
     }
 
     fn on_car_info(&mut self, from_lane: RawID, infos: &CVec<CarRenderInfo>, _: &mut World) {
-        let colored = infos
+        let (trucks, cars): (Vec<_>, Vec<_>) = infos
             .iter()
-            .map(|render_info| Instance {
-                instance_position: [render_info.position[0], render_info.position[1], 0.0],
-                instance_direction: render_info.direction,
-                instance_color: self.car_colors
-                    [render_info.trip.as_raw().instance_id as usize % self.car_colors.len()],
+            .map(|render_info| {
+                let is_truck = render_info.vehicle == VehicleClass::Truck;
+                let instance = Instance {
+                    instance_position: render_info.position,
+                    instance_direction: render_info.direction,
+                    instance_color: if is_truck {
+                        self.truck_color
+                    } else {
+                        self.car_colors
+                            [render_info.trip.as_raw().instance_id as usize % self.car_colors.len()]
+                    },
+                };
+                (is_truck, instance)
             })
-            .collect();
-        self.car_instance_buffers.insert(from_lane, colored);
+            .partition(|&(is_truck, _)| is_truck);
+        self.car_instance_buffers.insert(
+            from_lane,
+            cars.into_iter().map(|(_, instance)| instance).collect(),
+        );
+        self.truck_instance_buffers.insert(
+            from_lane,
+            trucks.into_iter().map(|(_, instance)| instance).collect(),
+        );
+        self.car_trips.insert(
+            from_lane,
+            infos
+                .iter()
+                .map(|info| (info.trip, P2::new(info.position[0], info.position[1])))
+                .collect(),
+        );
+    }
+
+    fn on_transit_line(
+        &mut self,
+        id: RawID,
+        mode: TransitMode,
+        stop_positions: &CVec<P2>,
+        _: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {transitLines: {[@{Serde(id)}]: {"$set": {
+                    id: @{Serde(id)},
+                    mode: @{Serde(mode)},
+                    stops: @{Serde(stop_positions)}
+                }}}}
+            }));
+        }
+    }
+
+    fn on_transit_line_removed(&mut self, id: RawID, _: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {transitLines: {"$unset": [@{Serde(id)}]}}
+            }));
+        }
+    }
+
+    fn on_toll_scheme(
+        &mut self,
+        id: u16,
+        kind: TollKind,
+        outline: &CVec<P2>,
+        prices: TollPrices,
+        collected: f32,
+        _: &mut World,
+    ) {
+        let (peak_start, _) = prices.peak_hours.start.hours_minutes();
+        let (peak_end, _) = prices.peak_hours.end.hours_minutes();
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {tollSchemes: {[@{id}]: {"$set": {
+                    id: @{id},
+                    kind: @{Serde(kind)},
+                    outline: @{Serde(outline)},
+                    basePrice: @{prices.base},
+                    peakPrice: @{prices.peak},
+                    peakHours: [@{peak_start as u32}, @{peak_end as u32}],
+                    collected: @{collected}
+                }}}}
+            }));
+        }
+    }
+
+    fn on_toll_scheme_removed(&mut self, id: u16, _: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {tollSchemes: {"$unset": [@{id}]}}
+            }));
+        }
+    }
+
+    fn on_rail_tracks(&mut self, segments: &CVec<[P2; 2]>, _: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {railTracks: {"$set": @{Serde(segments)}}}
+            }));
+        }
+    }
+
+    fn on_transit_vehicle_info(
+        &mut self,
+        _vehicle: RawID,
+        mode: TransitMode,
+        position: [f32; 2],
+        direction: [f32; 2],
+        _: &mut World,
+    ) {
+        self.transit_vehicle_instances.push(Instance {
+            instance_position: [position[0], position[1], 0.0],
+            instance_direction: direction,
+            instance_color: match mode {
+                TransitMode::Bus => self.bus_color,
+                TransitMode::Rail => self.train_color,
+            },
+        });
+    }
+
+    fn on_traffic_signal_info(
+        &mut self,
+        signal: RawID,
+        position: P2,
+        phase_durations: &CVec<Duration>,
+        approach_delays: &CVec<f32>,
+        _: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {trafficSignals: {[@{Serde(signal)}]: {"$set": {
+                    position: @{Serde(position)},
+                    phaseDurations: @{Serde(phase_durations)},
+                    approachDelays: @{Serde(approach_delays)}
+                }}}}
+            }));
+        }
+    }
+
+    fn on_lane_incident(
+        &mut self,
+        lane: RawID,
+        position: P2,
+        kind: Option<IncidentKind>,
+        _: &mut World,
+    ) {
+        if let Some(kind) = kind {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    transport: {incidents: {[@{Serde(lane)}]: {"$set": {
+                        position: @{Serde(position)},
+                        kind: @{Serde(kind)}
+                    }}}}
+                }));
+            }
+        } else {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    transport: {incidents: {"$unset": [@{Serde(lane)}]}}
+                }));
+            }
+        }
+    }
+
+    fn on_footway_constructed(
+        &mut self,
+        id: RawID,
+        path: &LinePath,
+        crossing: bool,
+        _: &mut World,
+    ) {
+        use transport::ui::{sidewalk_mesh, crosswalk_mesh};
+        if crossing {
+            let updated_crosswalk_groups = self
+                .crosswalk_grouper
+                .update(None, Some((id, crosswalk_mesh(path))));
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    transport: {rendering: {
+                        crosswalkGroups: {
+                            "$add": @{updated_groups_to_js(updated_crosswalk_groups)}
+                        }
+                    }}
+                }));
+            }
+        } else {
+            let updated_sidewalk_groups = self
+                .sidewalk_grouper
+                .update(None, Some((id, sidewalk_mesh(path))));
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    transport: {rendering: {
+                        sidewalkGroups: {
+                            "$add": @{updated_groups_to_js(updated_sidewalk_groups)}
+                        }
+                    }}
+                }));
+            }
+        }
+    }
+
+    fn on_footway_destructed(&mut self, id: RawID, crossing: bool, _: &mut World) {
+        if crossing {
+            let updated_crosswalk_groups = self.crosswalk_grouper.update(Some(id), None);
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    transport: {rendering: {
+                        crosswalkGroups: {
+                            "$add": @{updated_groups_to_js(updated_crosswalk_groups)}
+                        }
+                    }}
+                }));
+            }
+        } else {
+            let updated_sidewalk_groups = self.sidewalk_grouper.update(Some(id), None);
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    transport: {rendering: {
+                        sidewalkGroups: {
+                            "$add": @{updated_groups_to_js(updated_sidewalk_groups)}
+                        }
+                    }}
+                }));
+            }
+        }
+    }
+
+    fn on_bikeway_constructed(&mut self, id: RawID, path: &LinePath, _: &mut World) {
+        use transport::ui::bike_lane_mesh;
+        let updated_bike_lane_groups = self
+            .bike_lane_grouper
+            .update(None, Some((id, bike_lane_mesh(path))));
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {rendering: {
+                    bikeLaneGroups: {
+                        "$add": @{updated_groups_to_js(updated_bike_lane_groups)}
+                    }
+                }}
+            }));
+        }
+    }
+
+    fn on_bikeway_destructed(&mut self, id: RawID, _: &mut World) {
+        let updated_bike_lane_groups = self.bike_lane_grouper.update(Some(id), None);
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {rendering: {
+                    bikeLaneGroups: {
+                        "$add": @{updated_groups_to_js(updated_bike_lane_groups)}
+                    }
+                }}
+            }));
+        }
+    }
+
+    // all pedestrians are reported at once, so they are sent on right away
+    fn on_pedestrian_info(&mut self, infos: &CVec<PedestrianRenderInfo>, _: &mut World) {
+        let pedestrian_instances = infos
+            .iter()
+            .map(|info| Instance {
+                instance_position: [info.position[0], info.position[1], 0.0],
+                instance_direction: info.direction,
+                instance_color: self.pedestrian_color,
+            })
+            .collect::<Vec<_>>();
+        let pedestrian_instances_js: ::stdweb::web::TypedArray<f32> =
+            flatten_instances(&pedestrian_instances).into();
+
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {rendering: {
+                    pedestrianInstances: {"$set": @{pedestrian_instances_js}}
+                }}
+            }))
+        }
+    }
+
+    fn on_lane_pathfinding_info(
+        &mut self,
+        id: RawID,
+        label_position: P2,
+        landmark: Option<RawID>,
+        hops_from_landmark: u8,
+        _: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {debugPathfindingNodes: {[@{Serde(id)}]: {"$set": {
+                    position: @{Serde(label_position)},
+                    landmark: @{Serde(landmark)},
+                    hopsFromLandmark: @{hops_from_landmark as u32}
+                }}}}
+            }));
+        }
+    }
+
+    fn on_route_hop(
+        &mut self,
+        trip: TripID,
+        hop: u16,
+        hop_path: &LinePath,
+        is_last: bool,
+        _: &mut World,
+    ) {
+        if let Some(ref mut selected_car) = self.selected_car {
+            if selected_car.trip != trip {
+                return;
+            }
+
+            // a new trace was started from the lane the car is on now
+            if hop == 0 {
+                selected_car.route.clear();
+                selected_car.route_complete = false;
+                selected_car.trail_length_when_traced = selected_car.trail_length;
+            }
+
+            let hop = hop as usize;
+            if selected_car.route.len() <= hop {
+                selected_car.route.resize(hop + 1, None);
+            }
+            selected_car.route[hop] = Some(hop_path.clone());
+            selected_car.route_complete = selected_car.route_complete || is_last;
+            selected_car.changed = true;
+        }
+    }
+}
+
+impl BrowserTransportUI {
+    pub fn select_car_near(&mut self, position: P2, _: &mut World) {
+        let maybe_nearest = self
+            .car_trips
+            .values()
+            .flat_map(|trips| trips.iter())
+            .map(|&(trip, car_position)| (trip, (car_position - position).norm()))
+            .filter(|&(_, distance)| distance < CAR_SELECTION_RADIUS)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+        self.selected_car = maybe_nearest.map(|(trip, _)| SelectedCar::new(trip));
+
+        if self.selected_car.is_none() {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    transport: {selectedCar: {"$set": null}}
+                }));
+            }
+        }
+    }
+
+    pub fn select_trip(&mut self, trip: TripID, _: &mut World) {
+        self.selected_car = Some(SelectedCar::new(trip));
+    }
+
+    fn update_selected_car(&mut self, world: &mut World) {
+        let id = self.id;
+        let state = &mut *self.state;
+
+        let deselect = if let Some(ref mut selected_car) = state.selected_car {
+            let maybe_seen = state
+                .car_trips
+                .iter()
+                .filter_map(|(&lane, trips)| {
+                    trips
+                        .iter()
+                        .find(|&&(trip, _)| trip == selected_car.trip)
+                        .map(|&(_, position)| (lane, position))
+                })
+                .next();
+
+            if let Some((lane, position)) = maybe_seen {
+                selected_car.frames_unseen = 0;
+
+                let moved = selected_car
+                    .trail
+                    .last()
+                    .map(|&last| (position - last).norm())
+                    .unwrap_or(::std::f32::INFINITY);
+                if moved > TRAIL_RESOLUTION {
+                    if !selected_car.trail.is_empty() {
+                        selected_car.trail_length += moved;
+                    }
+                    selected_car.trail.push(position);
+                    selected_car.changed = true;
+                }
+
+                if selected_car.lane != Some(lane) {
+                    selected_car.lane = Some(lane);
+                    RouteTraceableID::from_raw(lane).trace_route(
+                        selected_car.trip,
+                        id.into(),
+                        world,
+                    );
+                }
+
+                false
+            } else {
+                selected_car.frames_unseen += 1;
+                selected_car.frames_unseen > MAX_FRAMES_SELECTED_CAR_UNSEEN
+            }
+        } else {
+            false
+        };
+
+        if deselect {
+            state.selected_car = None;
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    transport: {selectedCar: {"$set": null}}
+                }));
+            }
+        } else if let Some(ref mut selected_car) = state.selected_car {
+            if selected_car.changed {
+                selected_car.changed = false;
+                js! {
+                    window.cbReactApp.boundSetState(oldState => update(oldState, {
+                        transport: {selectedCar: {"$set": @{selected_car.to_js()}}}
+                    }));
+                }
+            }
+        }
+    }
+
+    fn update_debug_geometry(
+        &mut self,
+        id: RawID,
+        maybe_mesh: Option<Mesh>,
+        on_intersection: bool,
+    ) {
+        let (grouper, groups_key) = if on_intersection {
+            (
+                &mut self.intersection_connector_grouper,
+                "debugConnectorGroups",
+            )
+        } else {
+            (&mut self.lane_centerline_grouper, "debugCenterlineGroups")
+        };
+        let updated_groups = match maybe_mesh {
+            Some(mesh) => grouper.update(None, Some((id, mesh))),
+            None => grouper.update(Some(id), None),
+        };
+
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {rendering: {
+                    [@{groups_key}]: {
+                        "$add": @{updated_groups_to_js(updated_groups)}
+                    }
+                }}
+            }));
+        }
+    }
+}
+
+fn update_street_label(id: RawID, maybe_anchor: Option<(P2, V2)>) {
+    if let Some((position, direction)) = maybe_anchor {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {streetLabels: {[@{Serde(id)}]: {"$set": {
+                    position: @{Serde(position)},
+                    direction: @{Serde(direction)}
+                }}}}
+            }));
+        }
+    } else {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {streetLabels: {"$unset": [@{Serde(id)}]}}
+            }));
+        }
     }
 }
 
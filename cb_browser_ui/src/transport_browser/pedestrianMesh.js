@@ -0,0 +1,40 @@
+//          a simple pedestrian, a box
+//
+//       7--------6
+//      /|       /|       1.8
+//     4--------5 |       |
+//     | 3------|-2       Z
+//     |/       |/        |    Y   .  0.2
+//     0--------1         0    -0.2
+//
+//    -0.15----X----0.15
+
+export default {
+    vertices: new Float32Array([
+        -0.15, -0.2, 0.00, // 0
+        0.15, -0.2, 0.00,  // 1
+        0.15, 0.2, 0.00,   // 2
+        -0.15, 0.2, 0.00,  // 3
+        -0.15, -0.2, 1.80, // 4
+        0.15, -0.2, 1.80,  // 5
+        0.15, 0.2, 1.80,   // 6
+        -0.15, 0.2, 1.80,  // 7
+    ]),
+    indices: new Uint16Array([
+        // right side
+        0, 1, 5,
+        0, 5, 4,
+        // left side
+        3, 7, 6,
+        3, 6, 2,
+        // back
+        0, 4, 7,
+        0, 7, 3,
+        // front
+        1, 2, 6,
+        1, 6, 5,
+        // top
+        4, 5, 6,
+        4, 6, 7,
+    ])
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+//          a simple truck, a cargo box with a lower cab in front
+//
+//   +---------------+
+//   |     cargo     |  +----+      3.5
+//   |               |  |cab |      2.6
+//   +---------------+  +----+      0
+//
+// -6.0             3.0 3.2  6.0   X,  1.25 wide to each side
+
+function box(x0, x1, y, z, offset) {
+    return {
+        vertices: [
+            x0, -y, 0.0, x1, -y, 0.0, x1, y, 0.0, x0, y, 0.0,
+            x0, -y, z, x1, -y, z, x1, y, z, x0, y, z,
+        ],
+        indices: [
+            // right side
+            0, 1, 5, 0, 5, 4,
+            // left side
+            3, 7, 6, 3, 6, 2,
+            // back
+            0, 4, 7, 0, 7, 3,
+            // front
+            1, 2, 6, 1, 6, 5,
+            // top
+            4, 5, 6, 4, 6, 7,
+        ].map(i => i + offset)
+    };
+}
+
+const cargo = box(-6.0, 3.0, 1.25, 3.5, 0);
+const cab = box(3.2, 6.0, 1.25, 2.6, 8);
+
+export default {
+    vertices: new Float32Array([...cargo.vertices, ...cab.vertices]),
+    indices: new Uint16Array([...cargo.indices, ...cab.indices])
+}
\ No newline at end of file
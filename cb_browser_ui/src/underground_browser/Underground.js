@@ -0,0 +1,128 @@
+import colors from '../colors';
+import renderOrder from '../renderOrder';
+import { PolylineOverlay } from '../overlays/Polylines';
+import { Toolbar } from '../toolbar';
+import React from 'react';
+import update from 'immutability-helper';
+
+export const initialState = {
+    conduits: {},
+    view: false,
+    // the underground view keeps its own camera pitch, looking almost straight down
+    undergroundPitch: 0.45 * Math.PI,
+    surfacePitch: null
+};
+
+export const settingsSpec = {
+    toggleUndergroundViewKey: { default: { key: 'u' }, description: "Toggle Underground View" }
+};
+
+const CONDUIT_WIDTH = 2.0;
+const PLANNED_CONDUIT_DASH = [4.0, 3.0];
+// see `Utility::conduit_cost_per_meter`
+const COST_PER_METER = { Electricity: 2.0, Water: 4.0 };
+
+export function isActive(state) {
+    return state.underground.view;
+}
+
+export function setView(setState, view) {
+    setState(oldState => oldState.underground.view == view ? oldState : update(oldState, {
+        underground: {
+            view: { $set: view },
+            undergroundPitch: { $set: view ? oldState.underground.undergroundPitch : oldState.camera.pitch },
+            surfacePitch: { $set: view ? oldState.camera.pitch : oldState.underground.surfacePitch }
+        },
+        camera: {
+            pitch: { $set: view ? oldState.underground.undergroundPitch : oldState.underground.surfacePitch }
+        }
+    }));
+}
+
+// conduits drawn in the current project, not yet laid
+function plannedConduits(state) {
+    const project = state.planning.currentProject && state.planning.projects[state.planning.currentProject];
+    if (!project) {
+        return [];
+    }
+
+    const gestures = project.undoable_history
+        .concat([project.ongoing || { gestures: {} }])
+        .reduce((coll, step) => Object.assign(coll, step.gestures), {});
+
+    return Object.values(gestures)
+        .filter(gesture => !gesture.deleted && gesture.intent.Conduit && gesture.points.length > 1);
+}
+
+function pathLength(points) {
+    let length = 0;
+    for (let i = 1; i < points.length; i++) {
+        length += Math.hypot(points[i][0] - points[i - 1][0], points[i][1] - points[i - 1][1]);
+    }
+    return length;
+}
+
+let lastConduits = null;
+let lastConduitPolylines = [];
+
+function conduitPolylines(conduits) {
+    if (conduits !== lastConduits) {
+        lastConduits = conduits;
+        lastConduitPolylines = Object.values(conduits).map(conduit =>
+            ({ points: conduit.points, width: CONDUIT_WIDTH, color: colors["conduit" + conduit.utility] })
+        );
+    }
+    return lastConduitPolylines;
+}
+
+export function Layers(props) {
+    const { state } = props;
+
+    if (!isActive(state)) {
+        return null;
+    }
+
+    return [
+        <PolylineOverlay
+            renderOrder={renderOrder.polylineOverlays}
+            polylines={conduitPolylines(state.underground.conduits)} />,
+        <PolylineOverlay
+            renderOrder={renderOrder.polylineOverlays}
+            polylines={plannedConduits(state).map(gesture => ({
+                points: gesture.points,
+                width: CONDUIT_WIDTH,
+                color: colors["conduit" + gesture.intent.Conduit],
+                dash: PLANNED_CONDUIT_DASH
+            }))} />
+    ];
+}
+
+export function Tools(props) {
+    const { state, setState } = props;
+
+    if (state.uiMode != "planning" || !state.planning.currentProject || state.planning.planningMode != "conduits") {
+        return null;
+    }
+
+    const intent = state.planning.canvasMode.intent;
+    const cost = plannedConduits(state).reduce((sum, gesture) =>
+        sum + pathLength(gesture.points) * COST_PER_METER[gesture.intent.Conduit], 0);
+
+    return [
+        <Toolbar id="conduit-utility-toolbar"
+            options={{ Electricity: { description: "Power Line" }, Water: { description: "Water Pipe" } }}
+            value={intent && intent.Conduit}
+            onChange={utility => setState(oldState => update(oldState, {
+                planning: { canvasMode: { intent: { $set: { Conduit: utility } } } }
+            }))} />,
+        `Cost: ${Math.round(cost)}`
+    ];
+}
+
+export function bindInputs(state, setState) {
+    const inputActions = {
+        "toggleUndergroundView": () => setView(setState, !window.cbReactApp.state.underground.view)
+    }
+
+    Mousetrap.bind(state.settings.underground.toggleUndergroundViewKey.key, inputActions["toggleUndergroundView"]);
+}
@@ -0,0 +1,85 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BrowserConduitUI {
+    type ID = BrowserConduitUIID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BrowserConduitUIID {
+    _raw_id: RawID
+}
+
+impl Copy for BrowserConduitUIID {}
+impl Clone for BrowserConduitUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BrowserConduitUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BrowserConduitUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BrowserConduitUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BrowserConduitUIID {
+    fn eq(&self, other: &BrowserConduitUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BrowserConduitUIID {}
+
+impl TypedID for BrowserConduitUIID {
+    type Target = BrowserConduitUI;
+
+    fn from_raw(id: RawID) -> Self {
+        BrowserConduitUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BrowserConduitUIID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = BrowserConduitUIID::from_raw(world.allocate_instance_id::<BrowserConduitUI>());
+        let swarm = world.local_broadcast::<BrowserConduitUI>();
+        world.send(swarm, MSG_BrowserConduitUI_spawn(id, ));
+        id
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserConduitUI_spawn(pub BrowserConduitUIID, );
+
+impl Into<ConduitUIID> for BrowserConduitUIID {
+    fn into(self) -> ConduitUIID {
+        ConduitUIID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    ConduitUIID::register_implementor::<BrowserConduitUI>(system);
+    system.add_spawner::<BrowserConduitUI, _, _>(
+        |&MSG_BrowserConduitUI_spawn(id, ), world| {
+            BrowserConduitUI::spawn(id, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+use kay::{World, ActorSystem, TypedID};
+use stdweb::serde::Serde;
+use land_use::underground::{ConduitID, ConduitPrototype, ConduitUI, ConduitUIID};
+
+#[derive(Compact, Clone)]
+pub struct BrowserConduitUI {
+    id: BrowserConduitUIID,
+}
+
+impl BrowserConduitUI {
+    pub fn spawn(id: BrowserConduitUIID, world: &mut World) -> BrowserConduitUI {
+        ConduitID::global_broadcast(world).get_render_info(id.into(), world);
+        BrowserConduitUI { id }
+    }
+}
+
+impl ConduitUI for BrowserConduitUI {
+    fn on_conduit_added(&mut self, id: ConduitID, proto: &ConduitPrototype, _: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                underground: {conduits: {[@{id.as_raw_string()}]: {"$set": {
+                    utility: @{Serde(proto.utility)},
+                    points: @{Serde(&proto.path.points)}
+                }}}}
+            }));
+        }
+    }
+
+    fn on_conduit_removed(&mut self, id: ConduitID, _: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                underground: {conduits: {"$unset": [@{id.as_raw_string()}]}}
+            }));
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserConduitUI>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserConduitUIID::spawn(world);
+}
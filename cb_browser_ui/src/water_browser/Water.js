@@ -0,0 +1,31 @@
+import colors from '../colors';
+import renderOrder from '../renderOrder';
+import { RenderLayer } from "../browser_utils/Utils";
+import React from 'react';
+
+export const initialState = {
+    rendering: {
+        waterMeshes: {},
+        shoreMeshes: {}
+    }
+};
+
+const waterInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.water]);
+const shoreInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.shore]);
+
+export function Layers(props) {
+    const { waterMeshes, shoreMeshes } = props.state.water.rendering;
+
+    return [
+        <RenderLayer
+            key="water"
+            decal={true}
+            renderOrder={renderOrder.water}
+            batches={Object.values(waterMeshes).map(mesh => ({ mesh, instances: waterInstance }))} />,
+        <RenderLayer
+            key="shore"
+            decal={true}
+            renderOrder={renderOrder.shore}
+            batches={Object.values(shoreMeshes).map(mesh => ({ mesh, instances: shoreInstance }))} />
+    ];
+}
@@ -0,0 +1,85 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BrowserWaterUI {
+    type ID = BrowserWaterUIID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BrowserWaterUIID {
+    _raw_id: RawID
+}
+
+impl Copy for BrowserWaterUIID {}
+impl Clone for BrowserWaterUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BrowserWaterUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BrowserWaterUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BrowserWaterUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BrowserWaterUIID {
+    fn eq(&self, other: &BrowserWaterUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BrowserWaterUIID {}
+
+impl TypedID for BrowserWaterUIID {
+    type Target = BrowserWaterUI;
+
+    fn from_raw(id: RawID) -> Self {
+        BrowserWaterUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BrowserWaterUIID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = BrowserWaterUIID::from_raw(world.allocate_instance_id::<BrowserWaterUI>());
+        let swarm = world.local_broadcast::<BrowserWaterUI>();
+        world.send(swarm, MSG_BrowserWaterUI_spawn(id, ));
+        id
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserWaterUI_spawn(pub BrowserWaterUIID, );
+
+impl Into<WaterUIID> for BrowserWaterUIID {
+    fn into(self) -> WaterUIID {
+        WaterUIID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    WaterUIID::register_implementor::<BrowserWaterUI>(system);
+    system.add_spawner::<BrowserWaterUI, _, _>(
+        |&MSG_BrowserWaterUI_spawn(id, ), world| {
+            BrowserWaterUI::spawn(id, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,69 @@
+use kay::{World, ActorSystem, TypedID};
+use environment::water::{WaterBodyID, WaterPrototype, WaterUI, WaterUIID};
+use browser_utils::to_js_mesh;
+use michelangelo::Mesh;
+
+// wide enough to read as a beach or embankment from afar
+const SHORE_WIDTH: f32 = 6.0;
+
+#[derive(Compact, Clone)]
+pub struct BrowserWaterUI {
+    id: BrowserWaterUIID,
+}
+
+impl BrowserWaterUI {
+    pub fn spawn(id: BrowserWaterUIID, world: &mut World) -> BrowserWaterUI {
+        WaterBodyID::global_broadcast(world).get_render_info(id.into(), world);
+        BrowserWaterUI { id }
+    }
+}
+
+impl WaterUI for BrowserWaterUI {
+    fn on_water_added(&mut self, id: WaterBodyID, proto: &WaterPrototype, _: &mut World) {
+        let water_mesh = Mesh::from_area(&proto.area);
+        let mut shore_mesh = Mesh::empty();
+        for shoreline in proto.shorelines() {
+            shore_mesh += Mesh::from_path_as_band(shoreline, SHORE_WIDTH, 0.0);
+        }
+
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                water: {
+                    rendering: {
+                        waterMeshes: {[@{id.as_raw_string()}]: {
+                            "$set": @{to_js_mesh(&water_mesh)}
+                        }},
+                        shoreMeshes: {[@{id.as_raw_string()}]: {
+                            "$set": @{to_js_mesh(&shore_mesh)}
+                        }}
+                    }
+                }
+            }));
+        }
+    }
+
+    fn on_water_removed(&mut self, id: WaterBodyID, _: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                water: {
+                    rendering: {
+                        waterMeshes: {"$unset": [@{id.as_raw_string()}]},
+                        shoreMeshes: {"$unset": [@{id.as_raw_string()}]}
+                    }
+                }
+            }));
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserWaterUI>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserWaterUIID::spawn(world);
+}
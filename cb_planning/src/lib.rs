@@ -39,6 +39,19 @@ impl<GI: GestureIntent> Gesture<GI> {
             deleted: false,
         }
     }
+
+    // Deleting a gesture is a change like any other: a new version of it that is
+    // left out of the result, so that it can be undone by going back a version
+    pub fn as_deleted(&self) -> Self {
+        Gesture {
+            deleted: true,
+            ..self.clone()
+        }
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted
+    }
 }
 
 #[derive(Copy, Clone, Hash, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -109,6 +122,18 @@ impl<GI: GestureIntent> PlanHistory<GI> {
         history
     }
 
+    pub fn without_deleted(&self) -> PlanHistory<GI> {
+        PlanHistory {
+            gestures: self
+                .gestures
+                .pairs()
+                .filter(|(_, VersionedGesture(gesture, _))| !gesture.deleted)
+                .map(|(gesture_id, versioned_gesture)| (*gesture_id, versioned_gesture.clone()))
+                .collect(),
+            steps: self.steps.clone(),
+        }
+    }
+
     pub fn latest_step_id(&self) -> StepID {
         *self.steps.last().expect("should always have a step")
     }
@@ -749,6 +774,28 @@ impl ActionGroups {
     }
 }
 
+pub type Tile = (i32, i32);
+
+// The square tiles in which player gestures are allowed
+#[derive(Compact, Clone, Debug, Serialize, Deserialize)]
+pub struct BuildableArea {
+    pub tile_size: N,
+    pub tiles: CVec<Tile>,
+}
+
+impl BuildableArea {
+    pub fn tile_containing(&self, point: P2) -> Tile {
+        (
+            (point.x / self.tile_size).floor() as i32,
+            (point.y / self.tile_size).floor() as i32,
+        )
+    }
+
+    pub fn contains(&self, point: P2) -> bool {
+        self.tiles.contains(&self.tile_containing(point))
+    }
+}
+
 #[allow(type_alias_bounds)]
 pub type PlanningStepFn<PL: PlanningLogic> =
     fn(
@@ -765,6 +812,7 @@ pub trait PlanningLogic: Compact + 'static {
         history: &PlanHistory<Self::GestureIntent>,
     ) -> Result<PlanResult<Self::PrototypeKind>, AreaError> {
         let mut result = PlanResult::new();
+        let history = &history.without_deleted();
 
         for prototype_fn in Self::planning_step_functions() {
             let new_prototypes = prototype_fn(history, &result)?;
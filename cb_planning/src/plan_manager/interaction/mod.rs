@@ -1,4 +1,5 @@
-use kay::{World};
+use kay::World;
+use cb_util::session::{permits, Permission, Origin};
 use compact::{CHashMap, COption};
 use descartes::{P2, AreaError, LinePath};
 use ::{PlanHistory, PlanResult, ActionGroups, KnownHistoryState, KnownProjectState, ProjectUpdate,
@@ -155,12 +156,17 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
 
     pub fn start_new_gesture(
         &mut self,
+        origin: Origin,
         project_id: ProjectID,
         new_gesture_id: GestureID,
         intent: &Logic::GestureIntent,
         start: P2,
-        _: &mut World,
+        world: &mut World,
     ) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
         let new_gesture = Gesture::new(vec![start].into(), intent.clone());
 
         let new_step = Plan::from_gestures(Some((new_gesture_id, new_gesture)));
@@ -176,13 +182,18 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
 
     pub fn add_control_point(
         &mut self,
+        origin: Origin,
         project_id: ProjectID,
         gesture_id: GestureID,
         new_point: P2,
         add_to_end: bool,
         commit: bool,
-        _: &mut World,
+        world: &mut World,
     ) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
         let new_step = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -223,12 +234,17 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
 
     pub fn insert_control_point(
         &mut self,
+        origin: Origin,
         project_id: ProjectID,
         gesture_id: GestureID,
         new_point: P2,
         commit: bool,
-        _: &mut World,
+        world: &mut World,
     ) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
         let new_step = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -270,13 +286,18 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
 
     pub fn move_control_point(
         &mut self,
+        origin: Origin,
         project_id: ProjectID,
         gesture_id: GestureID,
         point_index: u32,
         new_position: P2,
         is_move_finished: bool,
-        _: &mut World,
+        world: &mut World,
     ) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
         let current_change = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -311,12 +332,17 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
 
     pub fn split_gesture(
         &mut self,
+        origin: Origin,
         project_id: ProjectID,
         gesture_id: GestureID,
         split_at: P2,
         commit: bool,
-        _: &mut World,
+        world: &mut World,
     ) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
         let maybe_new_step = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -377,12 +403,17 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
 
     pub fn set_intent(
         &mut self,
+        origin: Origin,
         project_id: ProjectID,
         gesture_id: GestureID,
         new_intent: &Logic::GestureIntent,
         is_move_finished: bool,
-        _: &mut World,
+        world: &mut World,
     ) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
         let current_change = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -408,12 +439,106 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
         }
     }
 
-    pub fn undo(&mut self, project_id: ProjectID, _: &mut World) {
+    pub fn delete_gesture(
+        &mut self,
+        origin: Origin,
+        project_id: ProjectID,
+        gesture_id: GestureID,
+        world: &mut World,
+    ) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
+        let new_step = {
+            let current_gesture = self.get_current_version_of(gesture_id, project_id);
+            Plan::from_gestures(Some((gesture_id, current_gesture.as_deleted())))
+        };
+
+        self.projects
+            .get_mut(project_id)
+            .unwrap()
+            .set_ongoing_step(new_step);
+        self.projects.get_mut(project_id).unwrap().start_new_step();
+
+        self.ui_state.invalidate(project_id);
+    }
+
+    // Takes out the piece of a gesture between the two points around the given
+    // one, what is left on either side stays as a gesture of its own
+    pub fn remove_gesture_segment(
+        &mut self,
+        origin: Origin,
+        project_id: ProjectID,
+        gesture_id: GestureID,
+        remove_at: P2,
+        world: &mut World,
+    ) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
+        let maybe_new_step = {
+            let current_gesture = self.get_current_version_of(gesture_id, project_id);
+
+            if let Some(idx) = LinePath::new(current_gesture.points.clone()).and_then(|path| {
+                path.project(remove_at).and_then(|(remove_along, _projected)| {
+                    path.distances
+                        .iter()
+                        .position(|point_i_along| *point_i_along >= remove_along)
+                        .map(|idx_after| idx_after.max(1) - 1)
+                })
+            }) {
+                let piece = |points: &[P2]| Gesture {
+                    points: points.iter().cloned().collect(),
+                    ..current_gesture.clone()
+                };
+                let before = &current_gesture.points[..=idx];
+                let after = &current_gesture.points[idx + 1..];
+
+                let mut gestures = vec![(
+                    gesture_id,
+                    if before.len() >= 2 {
+                        piece(before)
+                    } else {
+                        current_gesture.as_deleted()
+                    },
+                )];
+                if after.len() >= 2 {
+                    gestures.push((GestureID::new(), piece(after)));
+                }
+
+                Some(Plan::from_gestures(gestures))
+            } else {
+                None
+            }
+        };
+
+        if let Some(new_step) = maybe_new_step {
+            self.projects
+                .get_mut(project_id)
+                .unwrap()
+                .set_ongoing_step(new_step);
+            self.projects.get_mut(project_id).unwrap().start_new_step();
+
+            self.ui_state.invalidate(project_id);
+        }
+    }
+
+    pub fn undo(&mut self, origin: Origin, project_id: ProjectID, world: &mut World) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
         self.projects.get_mut(project_id).unwrap().undo();
         self.ui_state.invalidate(project_id);
     }
 
-    pub fn redo(&mut self, project_id: ProjectID, _: &mut World) {
+    pub fn redo(&mut self, origin: Origin, project_id: ProjectID, world: &mut World) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
         self.projects.get_mut(project_id).unwrap().redo();
         self.ui_state.invalidate(project_id);
     }
@@ -62,12 +62,28 @@ impl<Logic: PlanningLogic + 'static> PlanManagerID<Logic> {
         id
     }
     
-    pub fn start_new_project(self, project_id: ProjectID, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_start_new_project(project_id));
+    pub fn start_new_project(self, origin: Origin, project_id: ProjectID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_start_new_project(origin, project_id));
     }
     
-    pub fn implement(self, project_id: ProjectID, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_implement(project_id));
+    pub fn set_buildable_area(self, area: BuildableArea, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_set_buildable_area(area));
+    }
+    
+    pub fn implement(self, origin: Origin, project_id: ProjectID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_implement(origin, project_id));
+    }
+    
+    pub fn undo_implementation(self, origin: Origin, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_undo_implementation(origin));
+    }
+    
+    pub fn redo_implementation(self, origin: Origin, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_redo_implementation(origin));
+    }
+    
+    pub fn get_implementation_history(self, ui: PlanningUIID < Logic >, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_get_implementation_history::<Logic>(ui));
     }
     
     pub fn implement_artificial_project(self, project: Project < Logic :: GestureIntent >, based_on: CVec < PrototypeID >, world: &mut World) {
@@ -78,9 +94,17 @@ impl<Logic: PlanningLogic + 'static> PlanManagerID<Logic> {
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_PlanManager_spawn<Logic: PlanningLogic + 'static>(pub PlanManagerID<Logic>, );
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_start_new_project(pub ProjectID);
+struct MSG_PlanManager_start_new_project(pub Origin, pub ProjectID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_set_buildable_area(pub BuildableArea);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_implement(pub Origin, pub ProjectID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_undo_implementation(pub Origin);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_implement(pub ProjectID);
+struct MSG_PlanManager_redo_implementation(pub Origin);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_get_implementation_history<Logic: PlanningLogic + 'static>(pub PlanningUIID < Logic >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_PlanManager_implement_artificial_project<Logic: PlanningLogic + 'static>(pub Project < Logic :: GestureIntent >, pub CVec < PrototypeID >);
 
@@ -97,14 +121,38 @@ pub fn auto_setup<Logic: PlanningLogic + 'static>(system: &mut ActorSystem) {
     );
     
     system.add_handler::<PlanManager<Logic>, _, _>(
-        |&MSG_PlanManager_start_new_project(project_id), instance, world| {
-            instance.start_new_project(project_id, world); Fate::Live
+        |&MSG_PlanManager_start_new_project(origin, project_id), instance, world| {
+            instance.start_new_project(origin, project_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager<Logic>, _, _>(
+        |&MSG_PlanManager_set_buildable_area(ref area), instance, world| {
+            instance.set_buildable_area(area, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager<Logic>, _, _>(
+        |&MSG_PlanManager_implement(origin, project_id), instance, world| {
+            instance.implement(origin, project_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager<Logic>, _, _>(
+        |&MSG_PlanManager_undo_implementation(origin), instance, world| {
+            instance.undo_implementation(origin, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager<Logic>, _, _>(
+        |&MSG_PlanManager_redo_implementation(origin), instance, world| {
+            instance.redo_implementation(origin, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager<Logic>, _, _>(
-        |&MSG_PlanManager_implement(project_id), instance, world| {
-            instance.implement(project_id, world); Fate::Live
+        |&MSG_PlanManager_get_implementation_history::<Logic>(ui), instance, world| {
+            instance.get_implementation_history(ui, world); Fate::Live
         }, false
     );
     
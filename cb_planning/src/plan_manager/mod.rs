@@ -1,15 +1,17 @@
 use kay::{World, ActorSystem, TypedID};
 use ::construction::ConstructionID;
-use ::{PlanHistory, PlanResult, Gesture, Project, GestureID, PrototypeID, VersionedGesture,
-PlanningLogic};
-use compact::{CVec, CHashMap};
+use ::{PlanHistory, PlanResult, Gesture, Plan, Project, GestureID, StepID, PrototypeID,
+VersionedGesture, PlanningLogic, BuildableArea};
+use compact::{CVec, CHashMap, COption};
 use cb_util::random::{Uuid, uuid};
 use cb_util::log::{error, info};
+use cb_util::session::{permits, Permission, Origin};
 const LOG_T: &str = "Planning";
 
 pub mod interaction;
 use self::interaction::PlanManagerUIState;
 pub mod ui;
+use self::ui::PlanningUIID;
 
 #[derive(Copy, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub struct ProjectID(pub Uuid);
@@ -20,6 +22,19 @@ impl ProjectID {
     }
 }
 
+// What implementing a project changed in the master plan, kept so that
+// the change can still be taken back (and redone) after it was built
+#[derive(Compact, Clone)]
+pub struct ImplementedChange<Logic: PlanningLogic> {
+    // the changed gestures as they were before, new ones as deleted
+    before: Plan<Logic::GestureIntent>,
+    // the changed gestures as they were implemented
+    after: Plan<Logic::GestureIntent>,
+    // the versions of the changed gestures in the master plan right after
+    // the change, if any of them is changed again it can't be taken back
+    versions: CHashMap<GestureID, StepID>,
+}
+
 #[derive(Compact, Clone)]
 //#[derive(Clone)]
 pub struct PlanManager<Logic: PlanningLogic + 'static> {
@@ -28,7 +43,10 @@ pub struct PlanManager<Logic: PlanningLogic + 'static> {
     master_result: PlanResult<Logic::PrototypeKind>,
     projects: CHashMap<ProjectID, Project<Logic::GestureIntent>>,
     implemented_projects: CHashMap<ProjectID, Project<Logic::GestureIntent>>,
+    undoable_implementations: CVec<ImplementedChange<Logic>>,
+    redoable_implementations: CVec<ImplementedChange<Logic>>,
     ui_state: PlanManagerUIState<Logic>,
+    buildable_area: COption<BuildableArea>,
 }
 
 //mod compact_workaround;
@@ -41,7 +59,10 @@ impl<Logic: PlanningLogic + 'static> PlanManager<Logic> {
             master_result: PlanResult::new(),
             projects: CHashMap::new(),
             implemented_projects: CHashMap::new(),
+            undoable_implementations: CVec::new(),
+            redoable_implementations: CVec::new(),
             ui_state: PlanManagerUIState::new(),
+            buildable_area: COption(None),
         }
     }
 
@@ -69,19 +90,118 @@ impl<Logic: PlanningLogic + 'static> PlanManager<Logic> {
             .expect("Expected gesture (that point should be added to) to exist!")
     }
 
-    pub fn start_new_project(&mut self, project_id: ProjectID, _: &mut World) {
+    pub fn start_new_project(
+        &mut self,
+        origin: Origin,
+        project_id: ProjectID,
+        world: &mut World,
+    ) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
         self.projects.insert(project_id, Project::new());
     }
 
-    pub fn implement(&mut self, project_id: ProjectID, world: &mut World) {
+    pub fn set_buildable_area(&mut self, area: &BuildableArea, _: &mut World) {
+        self.buildable_area = COption(Some(area.clone()));
+    }
+
+    pub fn implement(&mut self, origin: Origin, project_id: ProjectID, world: &mut World) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
+        if let COption(Some(ref area)) = self.buildable_area {
+            let outside_of_area = self
+                .projects
+                .get(project_id)
+                .expect("Project should exist")
+                .current_history()
+                .iter()
+                .flat_map(|plan| plan.gestures.values())
+                .filter(|gesture| !gesture.deleted)
+                .any(|gesture| gesture.points.iter().any(|point| !area.contains(*point)));
+
+            if outside_of_area {
+                error(
+                    LOG_T,
+                    "Implement Plan Error: Project reaches outside of the buildable area",
+                    self.id,
+                    world,
+                );
+                return;
+            }
+        }
+
+        let changed_gesture_ids = self
+            .projects
+            .get(project_id)
+            .expect("Project should exist")
+            .current_history()
+            .iter()
+            .flat_map(|plan| plan.gestures.keys().cloned())
+            .collect::<::std::collections::HashSet<_>>();
+
+        let before = Plan::from_gestures(
+            changed_gesture_ids
+                .iter()
+                .map(|gesture_id| {
+                    let gesture_before = self
+                        .master_plan
+                        .gestures
+                        .get(*gesture_id)
+                        .map(|VersionedGesture(gesture, _)| gesture.clone())
+                        .unwrap_or_else(|| {
+                            self.get_current_version_of(*gesture_id, project_id)
+                                .as_deleted()
+                        });
+                    (*gesture_id, gesture_before)
+                }),
+        );
+
+        if self.implement_unchecked(project_id, world) {
+            let after = Plan::from_gestures(changed_gesture_ids.iter().filter_map(|gesture_id| {
+                self.master_plan
+                    .gestures
+                    .get(*gesture_id)
+                    .map(|VersionedGesture(gesture, _)| (*gesture_id, gesture.clone()))
+            }));
+            let versions = self.versions_of(&after);
+
+            self.undoable_implementations.push(ImplementedChange {
+                before,
+                after,
+                versions,
+            });
+            self.redoable_implementations.clear();
+        }
+    }
+
+    fn implement_unchecked(&mut self, project_id: ProjectID, world: &mut World) -> bool {
         let project = self
             .projects
             .remove(project_id)
             .expect("Project should exist");
 
-        self.master_plan = project.apply_to(&self.master_plan);
+        let new_master_plan = project.apply_to(&self.master_plan);
 
-        match Logic::calculate_result(&self.master_plan) {
+        if self.materialize(new_master_plan, world) {
+            self.implemented_projects.insert(project_id, project);
+            true
+        } else {
+            // keep it around, so it can be fixed and implemented again
+            self.projects.insert(project_id, project);
+            false
+        }
+    }
+
+    fn materialize(
+        &mut self,
+        new_master_plan: PlanHistory<Logic::GestureIntent>,
+        world: &mut World,
+    ) -> bool {
+        match Logic::calculate_result(&new_master_plan) {
             Ok(result) => {
                 let (actions, new_prototypes) = self.master_result.actions_to(&result);
                 ConstructionID::<Logic::PrototypeKind>::global_first(world).implement(
@@ -89,10 +209,11 @@ impl<Logic: PlanningLogic + 'static> PlanManager<Logic> {
                     new_prototypes,
                     world,
                 );
-                self.implemented_projects.insert(project_id, project);
+                self.master_plan = new_master_plan;
                 self.master_result = result;
 
                 self.ui_state.invalidate_all();
+                true
             }
             Err(err) => {
                 let err_str = match err {
@@ -102,10 +223,111 @@ impl<Logic: PlanningLogic + 'static> PlanManager<Logic> {
                     _ => format!("Implement Plan Error: {:?}", err),
                 };
                 error(LOG_T, err_str, self.id, world);
+                false
             }
         }
     }
 
+    fn versions_of(&self, plan: &Plan<Logic::GestureIntent>) -> CHashMap<GestureID, StepID> {
+        plan.gestures
+            .keys()
+            .filter_map(|gesture_id| {
+                self.master_plan
+                    .gestures
+                    .get(*gesture_id)
+                    .map(|VersionedGesture(_, step_id)| (*gesture_id, *step_id))
+            })
+            .collect()
+    }
+
+    // Applies one side of an implemented change to the master plan and builds
+    // (or demolishes) what is needed for it, unless any of the gestures it
+    // changed has been changed again in the meantime
+    fn reapply(
+        &mut self,
+        change: &ImplementedChange<Logic>,
+        undoing: bool,
+        world: &mut World,
+    ) -> Option<ImplementedChange<Logic>> {
+        let still_as_left = change.versions.pairs().all(|(gesture_id, step_id)| {
+            self.master_plan
+                .gestures
+                .get(*gesture_id)
+                .map(|VersionedGesture(_, current_step_id)| current_step_id == step_id)
+                .unwrap_or(false)
+        });
+
+        if !still_as_left {
+            error(
+                LOG_T,
+                "Implement Plan Error: A later project changed the same things since",
+                self.id,
+                world,
+            );
+            return None;
+        }
+
+        // a fresh step, so the same gestures can go back and forth several times
+        let gestures = if undoing {
+            &change.before
+        } else {
+            &change.after
+        };
+        let step = Plan::from_gestures(
+            gestures
+                .gestures
+                .pairs()
+                .map(|(gesture_id, gesture)| (*gesture_id, gesture.clone())),
+        );
+
+        let new_master_plan = self.master_plan.and_then(Some(&step));
+
+        if self.materialize(new_master_plan, world) {
+            Some(ImplementedChange {
+                versions: self.versions_of(&step),
+                ..change.clone()
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn undo_implementation(&mut self, origin: Origin, world: &mut World) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
+        if let Some(change) = self.undoable_implementations.pop() {
+            if let Some(undone_change) = self.reapply(&change, true, world) {
+                self.redoable_implementations.push(undone_change);
+            } else {
+                self.undoable_implementations.push(change);
+            }
+        }
+    }
+
+    pub fn redo_implementation(&mut self, origin: Origin, world: &mut World) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
+        if let Some(change) = self.redoable_implementations.pop() {
+            if let Some(redone_change) = self.reapply(&change, false, world) {
+                self.undoable_implementations.push(redone_change);
+            } else {
+                self.redoable_implementations.push(change);
+            }
+        }
+    }
+
+    pub fn get_implementation_history(&mut self, ui: PlanningUIID<Logic>, world: &mut World) {
+        ui.on_implementation_history_update(
+            self.undoable_implementations.len() as u32,
+            self.redoable_implementations.len() as u32,
+            world,
+        );
+    }
+
     pub fn implement_artificial_project(
         &mut self,
         project: &Project<Logic::GestureIntent>,
@@ -118,7 +340,7 @@ impl<Logic: PlanningLogic + 'static> PlanManager<Logic> {
         {
             let project_id = ProjectID::new();
             self.projects.insert(project_id, project.clone());
-            self.implement(project_id, world);
+            self.implement_unchecked(project_id, world);
         } else {
             info(
                 LOG_T,
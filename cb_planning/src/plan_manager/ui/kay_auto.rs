@@ -57,11 +57,16 @@ impl<Logic: PlanningLogic> PlanningUIID<Logic> {
     pub fn on_project_preview_update(self, project_id: ProjectID, effective_history: PlanHistory < Logic :: GestureIntent >, result_update: PlanResultUpdate < Logic :: PrototypeKind >, new_actions: ActionGroups, world: &mut World) {
         world.send(self.as_raw(), MSG_PlanningUI_on_project_preview_update::<Logic>(project_id, effective_history, result_update, new_actions));
     }
+    
+    pub fn on_implementation_history_update(self, n_undoable: u32, n_redoable: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanningUI_on_implementation_history_update(n_undoable, n_redoable));
+    }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<PlanningUIRepresentative<Logic>>();
         system.register_trait_message::<MSG_PlanningUI_on_plans_update<Logic>>();
         system.register_trait_message::<MSG_PlanningUI_on_project_preview_update<Logic>>();
+        system.register_trait_message::<MSG_PlanningUI_on_implementation_history_update>();
     }
 
     pub fn register_implementor<Act: Actor + PlanningUI<Logic>>(system: &mut ActorSystem) {
@@ -77,6 +82,12 @@ impl<Logic: PlanningLogic> PlanningUIID<Logic> {
                 instance.on_project_preview_update(project_id, effective_history, result_update, new_actions, world); Fate::Live
             }, false
         );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_PlanningUI_on_implementation_history_update(n_undoable, n_redoable), instance, world| {
+                instance.on_implementation_history_update(n_undoable, n_redoable, world); Fate::Live
+            }, false
+        );
     }
 }
 
@@ -84,6 +95,8 @@ impl<Logic: PlanningLogic> PlanningUIID<Logic> {
 struct MSG_PlanningUI_on_plans_update<Logic: PlanningLogic>(pub PlanHistoryUpdate < Logic :: GestureIntent >, pub CHashMap < ProjectID , ProjectUpdate < Logic :: GestureIntent > >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_PlanningUI_on_project_preview_update<Logic: PlanningLogic>(pub ProjectID, pub PlanHistory < Logic :: GestureIntent >, pub PlanResultUpdate < Logic :: PrototypeKind >, pub ActionGroups);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanningUI_on_implementation_history_update(pub u32, pub u32);
 
 
 
@@ -20,6 +20,13 @@ pub trait PlanningUI<Logic: PlanningLogic> {
         new_actions: &ActionGroups,
         _world: &mut World,
     );
+
+    fn on_implementation_history_update(
+        &mut self,
+        n_undoable: u32,
+        n_redoable: u32,
+        _world: &mut World,
+    );
 }
 
 pub mod kay_auto;
@@ -0,0 +1,156 @@
+// Serves the read-only API for external tools, see the `citybound_api` crate.
+//
+// Like the region link, connections are polled once per turn from the
+// simulation thread and never block it. Requests of all connections are
+// renumbered, so the answers collected by the simulation can be sent back
+// to whoever asked, with the request id they chose.
+
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+use cb_simulation::citybound_api::{self, Answer, Query, Request, Response};
+
+struct Connection {
+    stream: TcpStream,
+    received: Vec<u8>,
+    open: bool,
+}
+
+pub struct ApiServer {
+    listener: TcpListener,
+    connections: HashMap<usize, Connection>,
+    next_connection: usize,
+    // by the number the simulation knows them under
+    requests: HashMap<u64, (usize, u64)>,
+    next_request: u64,
+}
+
+impl ApiServer {
+    pub fn new(address: &str) -> io::Result<ApiServer> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        println!("Serving the API for external tools at {}", address);
+
+        Ok(ApiServer {
+            listener,
+            connections: HashMap::new(),
+            next_connection: 0,
+            requests: HashMap::new(),
+            next_request: 0,
+        })
+    }
+
+    // Accepts new connections and collects the queries that arrived on all of them
+    pub fn receive(&mut self) -> Vec<(u64, Query)> {
+        loop {
+            match self
+                .listener
+                .accept()
+                .and_then(|(stream, _)| stream.set_nonblocking(true).map(|_| stream))
+            {
+                Ok(stream) => {
+                    println!("API tool connected from {:?}", stream.peer_addr());
+                    self.connections.insert(
+                        self.next_connection,
+                        Connection {
+                            stream,
+                            received: Vec::new(),
+                            open: true,
+                        },
+                    );
+                    self.next_connection += 1;
+                }
+                Err(ref error) if error.kind() == io::ErrorKind::WouldBlock => break,
+                Err(error) => {
+                    println!("Couldn't accept API connection: {}", error);
+                    break;
+                }
+            }
+        }
+
+        let mut queries = Vec::new();
+
+        for (&connection_idx, connection) in &mut self.connections {
+            if let Err(error) = connection.read() {
+                println!("Lost API connection: {}", error);
+                connection.open = false;
+                continue;
+            }
+
+            while let Some(newline) = connection.received.iter().position(|&byte| byte == b'\n') {
+                let line = connection.received.drain(..=newline).collect::<Vec<_>>();
+                let line = String::from_utf8_lossy(&line);
+                match citybound_api::decode::<Request>(&line) {
+                    Some(request) => {
+                        self.requests
+                            .insert(self.next_request, (connection_idx, request.id));
+                        queries.push((self.next_request, request.query));
+                        self.next_request += 1;
+                    }
+                    None => println!("Ignoring invalid API request: {:?}", line),
+                }
+            }
+        }
+
+        self.forget_closed();
+        queries
+    }
+
+    pub fn answer(&mut self, answers: Vec<(u64, Answer)>) {
+        for (request, answer) in answers {
+            if let Some((connection_idx, their_request)) = self.requests.remove(&request) {
+                if let Some(connection) = self.connections.get_mut(&connection_idx) {
+                    let response = Response {
+                        request: their_request,
+                        answer,
+                    };
+                    if let Err(error) = connection.write(&citybound_api::encode(&response)) {
+                        println!("Lost API connection: {}", error);
+                        connection.open = false;
+                    }
+                }
+            }
+        }
+
+        self.forget_closed();
+    }
+
+    fn forget_closed(&mut self) {
+        self.connections.retain(|_, connection| connection.open);
+        let connections = &self.connections;
+        self.requests
+            .retain(|_, &mut (connection_idx, _)| connections.contains_key(&connection_idx));
+    }
+}
+
+impl Connection {
+    fn read(&mut self) -> io::Result<()> {
+        let mut buffer = [0u8; 4096];
+
+        loop {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::ConnectionAborted,
+                        "closed by the tool",
+                    ))
+                }
+                Ok(n) => self.received.extend_from_slice(&buffer[..n]),
+                Err(ref error) if error.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    // answers can be big, a tool that stops reading gets disconnected
+    // instead of holding up the simulation
+    fn write(&mut self, text: &str) -> io::Result<()> {
+        self.stream.set_nonblocking(false)?;
+        self.stream
+            .set_write_timeout(Some(Duration::from_secs(1)))?;
+        let result = self.stream.write_all(text.as_bytes());
+        self.stream.set_nonblocking(true)?;
+        result
+    }
+}
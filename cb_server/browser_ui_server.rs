@@ -1,14 +1,30 @@
 extern crate rouille;
 use self::rouille::{Response, extension_to_mime};
+use cb_simulation::savegame::CompatibilityReport;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[derive(RustEmbed)]
 #[folder = "cb_browser_ui/dist/"]
 struct Asset;
 
-pub fn start_browser_ui_server(version: &'static str, network_config: ::init::NetworkConfig) {
+pub fn start_browser_ui_server(
+    version: &'static str,
+    network_config: ::init::NetworkConfig,
+    savegame_report: Option<CompatibilityReport>,
+) {
+    let n_page_loads = AtomicUsize::new(0);
+
     rouille::start_server(network_config.serve_host_port.clone(), move |request| {
         if request.raw_url() == "/" {
-            println!("{:?} loaded page", request.remote_addr());
+            let machine =
+                network_config.client_machine(n_page_loads.fetch_add(1, Ordering::SeqCst));
+            let role = network_config.role_of(machine);
+            println!(
+                "{:?} loaded page, joining as client {} ({})",
+                request.remote_addr(),
+                machine,
+                role.name()
+            );
 
             let template = ::std::str::from_utf8(
                 &Asset::get("index.html").expect("index.html should exist as asset"),
@@ -29,9 +45,19 @@ pub fn start_browser_ui_server(version: &'static str, network_config: ::init::Ne
                 .replace(
                     "CB_SKIP_TURNS_PER_TURN_AHEAD",
                     &format!("{}", network_config.skip_ratio),
-                );
+                )
+                .replace("CB_MACHINE_ID", &format!("{}", machine))
+                .replace(
+                    "CB_N_MACHINES",
+                    &format!("{}", network_config.n_clients as usize + 1),
+                )
+                .replace("CB_ROLE", role.name())
+                .replace("CB_SESSION_KEY", &format!("{}", network_config.key_of(machine)));
 
             Response::html(rendered)
+        } else if request.raw_url() == "/savegame_report.json" {
+            // null if the city was just created
+            Response::json(&savegame_report)
         } else if let Some(asset) = Asset::get(&request.url()[1..]) {
             Response::from_data(
                 if request.url().ends_with(".wasm") {
@@ -0,0 +1,269 @@
+// Runs two instances of the simulation side by side in one process, both
+// starting from the same city, and compares their state after every turn.
+// Each instance runs on a thread of its own, since the simulation keeps some
+// state per thread, like the messages for the neighboring city, answers to
+// API queries and the session, which the instances mustn't share.
+//
+// Everything the simulation does should only depend on the state of its
+// actors and the messages they get, which is what keeps clients in sync with
+// the server and would make replays possible. Wall clock time, unseeded
+// randomness or the iteration order of a std HashMap break that, and the
+// first turn where the two instances differ shows which subsystem did it.
+//
+// Both instances persist themselves into memory mapped files of their own,
+// named after the actor types, so their state is compared file by file,
+// together with the messages they would send to a neighboring city.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use cb_simulation::kay::{ActorSystem, Networking, Tuning, TypedID};
+use cb_simulation::cb_time::actors::TimeID;
+use cb_simulation::cb_util::session;
+use cb_simulation::economy::region::{self, RegionMessage};
+use cb_simulation::observation;
+use cb_simulation::savegame::Subsystem;
+use cb_simulation::economy::difficulty::DifficultyProfile;
+use snapshots::{self, SnapshotStore};
+
+const AUDIT_FOLDER: &str = "cb_determinism_audit";
+// the instances never connect to anything, but each needs an address of its own
+const INSTANCE_ADDRESSES: [&str; 2] = ["localhost:9997", "localhost:9998"];
+// compared like a file, but not one
+const REGION_OUTBOX: &str = "(messages for the neighboring city)";
+
+type State = BTreeMap<String, u64>;
+
+struct Instance {
+    system: Box<ActorSystem>,
+    time: TimeID,
+    folder: PathBuf,
+    outgoing: String,
+}
+
+impl Instance {
+    fn new(nth: usize, maybe_city_folder: Option<&Path>) -> io::Result<Instance> {
+        let folder = ::std::env::temp_dir()
+            .join(AUDIT_FOLDER)
+            .join(nth.to_string());
+        if folder.exists() {
+            fs::remove_dir_all(&folder)?;
+        }
+        fs::create_dir_all(&folder)?;
+
+        if let Some(city_folder) = maybe_city_folder {
+            let store = SnapshotStore::new(city_folder);
+            for path in store.city_files()? {
+                let target = folder.join(&path);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(city_folder.join(&path), target)?;
+            }
+        }
+
+        let mut system = Box::new(ActorSystem::new_mmap_persisted(
+            Networking::new(0, vec![INSTANCE_ADDRESSES[nth].to_owned()], 5000, 2, 5),
+            &folder.to_string_lossy(),
+            Tuning::default(),
+        ));
+        ::cb_simulation::setup_common(&mut system);
+        system.networking_connect();
+
+        let time = {
+            let world = &mut system.world();
+            if maybe_city_folder.is_some() {
+                TimeID::global_first(world)
+            } else {
+                ::cb_simulation::spawn_for_server(world, DifficultyProfile::normal())
+            }
+        };
+        system.process_all_messages();
+
+        Ok(Instance {
+            system,
+            time,
+            folder,
+            outgoing: String::new(),
+        })
+    }
+
+    fn turn(&mut self) {
+        let world = &mut self.system.world();
+        self.time.progress(world);
+        self.system.process_all_messages();
+
+        // nobody is listening on the other side, but both should have said the same
+        self.outgoing = region::take_outgoing()
+            .iter()
+            .map(RegionMessage::encode)
+            .collect::<Vec<_>>()
+            .join("\n");
+        observation::take_answers();
+    }
+
+    fn state_hashes(&self) -> io::Result<State> {
+        let mut state = SnapshotStore::new(&self.folder)
+            .city_files()?
+            .into_iter()
+            .map(|path| {
+                let contents = fs::read(self.folder.join(&path))?;
+                Ok((path, snapshots::hash(&contents)))
+            })
+            .collect::<io::Result<State>>()?;
+        state.insert(
+            REGION_OUTBOX.to_owned(),
+            snapshots::hash(self.outgoing.as_bytes()),
+        );
+        Ok(state)
+    }
+}
+
+fn instance_gone() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "Instance stopped unexpectedly")
+}
+
+// Runs an instance on its own thread, one turn whenever asked to
+struct InstanceThread {
+    turns: Sender<()>,
+    states: Receiver<io::Result<State>>,
+}
+
+impl InstanceThread {
+    fn spawn(nth: usize, maybe_city_folder: Option<PathBuf>, server_key: u64) -> InstanceThread {
+        let (turns, turns_to_take) = mpsc::channel();
+        let (report_state, states) = mpsc::channel();
+
+        thread::spawn(move || {
+            session::set_server_key(server_key);
+            let maybe_city_folder = maybe_city_folder.as_ref().map(PathBuf::as_path);
+            let mut instance = match Instance::new(nth, maybe_city_folder) {
+                Ok(instance) => instance,
+                Err(error) => {
+                    let _ = report_state.send(Err(error));
+                    return;
+                }
+            };
+            if report_state.send(instance.state_hashes()).is_err() {
+                return;
+            }
+
+            for () in turns_to_take {
+                instance.turn();
+                if report_state.send(instance.state_hashes()).is_err() {
+                    return;
+                }
+            }
+        });
+
+        InstanceThread { turns, states }
+    }
+
+    fn take_turn(&self) -> io::Result<()> {
+        self.turns.send(()).map_err(|_| instance_gone())
+    }
+
+    // Once after the instance was set up and once after each turn
+    fn next_state(&self) -> io::Result<State> {
+        self.states.recv().map_err(|_| instance_gone())?
+    }
+}
+
+// Files that only exist in one of the instances differ as well
+fn differing_files(a: &State, b: &State) -> Vec<String> {
+    a.keys()
+        .chain(b.keys().filter(|path| !a.contains_key(*path)))
+        .filter(|path| a.get(*path) != b.get(*path))
+        .cloned()
+        .collect()
+}
+
+pub fn subsystem_name(path: &str) -> &'static str {
+    if path == REGION_OUTBOX {
+        return Subsystem::Economy.name();
+    }
+    let actor_type = path.split('/').next().unwrap_or(path);
+    Subsystem::of_actor_type(actor_type)
+        .map(Subsystem::name)
+        .unwrap_or("actor system internals")
+}
+
+// Audits a copy of the city if it was saved with this version, a new city otherwise.
+// Returns whether the instances stayed the same for all turns
+pub fn run(city_folder: &str, n_turns: usize, version: &str) -> bool {
+    let city_path = Path::new(city_folder);
+    let saved_version = fs::read_to_string(city_path.join("__cb_version.txt")).ok();
+    let maybe_city_folder =
+        if saved_version.as_ref().map(|saved| saved.trim()) == Some(version.trim()) {
+            println!(
+                "Auditing determinism of {} for {} turns...",
+                city_folder, n_turns
+            );
+            Some(city_path)
+        } else {
+            println!(
+                "No savegame of this version in {}, auditing a new city for {} turns...",
+                city_folder, n_turns
+            );
+            None
+        };
+
+    let server_key = session::new_key();
+    let instances = (0..2)
+        .map(|nth| {
+            InstanceThread::spawn(nth, maybe_city_folder.map(Path::to_owned), server_key)
+        })
+        .collect::<Vec<_>>();
+
+    for instance in &instances {
+        if let Err(error) = instance.next_state() {
+            println!("Couldn't set up the audit: {}", error);
+            return false;
+        }
+    }
+
+    for turn in 0..n_turns {
+        // both take their turn at the same time
+        for instance in &instances {
+            if let Err(error) = instance.take_turn() {
+                println!("Couldn't run the instances: {}", error);
+                return false;
+            }
+        }
+
+        let differing = match (instances[0].next_state(), instances[1].next_state()) {
+            (Ok(a), Ok(b)) => differing_files(&a, &b),
+            (Err(error), _) | (_, Err(error)) => {
+            (Ok(a), Ok(b)) => differing_files(&a, &b),
+            (Err(error), _) | (_, Err(error)) => {
+                println!("Couldn't read the state of the instances: {}", error);
+                return false;
+            }
+        };
+
+        if !differing.is_empty() {
+            let mut subsystems = Vec::new();
+            for path in &differing {
+                if !subsystems.contains(&subsystem_name(path)) {
+                    subsystems.push(subsystem_name(path));
+                }
+            }
+
+            println!(
+                "Instances diverged in turn {}, in {}:",
+                turn,
+                subsystems.join(", ")
+            );
+            for path in differing {
+                println!("  {:<24} {}", subsystem_name(&path), path);
+            }
+            return false;
+        }
+    }
+
+    println!("Instances stayed identical for {} turns.", n_turns);
+    true
+}
@@ -2,6 +2,12 @@ extern crate backtrace;
 extern crate clap;
 
 use std::time::{Instant, Duration};
+use std::collections::HashMap;
+use cb_simulation::cb_util::session::{self, Role};
+use cb_simulation::bots::Strategy;
+use cb_simulation::economy::difficulty::DifficultyProfile;
+use cb_simulation::scenarios::Scenario;
+use region_link::RegionConfig;
 
 pub fn print_start_message(version: &str, network_config: &NetworkConfig) {
     let my_host = format!(
@@ -37,9 +43,60 @@ pub struct NetworkConfig {
     pub batch_msg_bytes: usize,
     pub ok_turn_dist: usize,
     pub skip_ratio: usize,
+    pub n_clients: u8,
+    pub roles: HashMap<u8, Role>,
+    // handed to each client with its page, see `cb_util::session`
+    pub keys: HashMap<u8, u64>,
+    pub region: Option<RegionConfig>,
+    pub api: Option<String>,
 }
 
-pub fn match_cmd_line_args(version: &str) -> (NetworkConfig, String) {
+impl NetworkConfig {
+    // Clients are machines 1 to n_clients, page loads are handed out to them in turn
+    pub fn client_machine(&self, nth_page_load: usize) -> u8 {
+        1 + (nth_page_load % self.n_clients as usize) as u8
+    }
+
+    pub fn role_of(&self, machine: u8) -> Role {
+        self.roles.get(&machine).cloned().unwrap_or(Role::Observer)
+    }
+
+    pub fn key_of(&self, machine: u8) -> u64 {
+        self.keys[&machine]
+    }
+}
+
+#[derive(Clone)]
+pub struct AutosaveConfig {
+    pub interval: Duration,
+    pub n_to_keep: usize,
+    // go back to the most recent autosave before starting
+    pub recover: bool,
+    // record the inputs of this run, starting from an autosave, see `replay`
+    pub record_replay: bool,
+    // dump the daily metrics of the city to this CSV file with every autosave
+    pub metrics_csv: Option<String>,
+}
+
+// What to do instead of serving the city
+pub enum HeadlessRun {
+    AuditDeterminism(usize),
+    Soak(usize),
+    // from the autosave with this number
+    Replay(usize),
+}
+
+pub fn match_cmd_line_args(
+    version: &str,
+) -> (
+    NetworkConfig,
+    String,
+    DifficultyProfile,
+    Option<Scenario>,
+    Option<Strategy>,
+    Option<HeadlessRun>,
+    AutosaveConfig,
+) {
     use self::clap::{Arg, App};
     let matches = App::new("citybound")
         .version(version.trim())
@@ -107,8 +164,190 @@ pub fn match_cmd_line_args(version: &str) -> (NetworkConfig, String) {
                 .default_value("5")
                 .help("How many network turns to skip if server/client are ahead"),
         )
+        .arg(
+            Arg::with_name("clients")
+                .long("clients")
+                .value_name("n-clients")
+                .default_value("1")
+                .validator(|value| match value.parse::<u8>() {
+                    Ok(n_clients) if n_clients >= 1 => Ok(()),
+                    _ => Err(format!("{:?} is not a number of clients from 1 to 255", value)),
+                })
+                .help("How many browsers can be connected to the simulation at the same time"),
+        )
+        .arg(
+            Arg::with_name("role")
+                .long("role")
+                .value_name("client=mayor/planner/observer")
+                .multiple(true)
+                .number_of_values(1)
+                .validator(|value| match parse_role_arg(&value) {
+                    Some(_) => Ok(()),
+                    None => Err(format!("{:?} is not a role, expected e.g. 2=planner", value)),
+                })
+                .help(
+                    "What the stock browser UI of a connected client lets it change, \
+                     clients are numbered from 1. Client 1 is mayor and all others are \
+                     observers unless set otherwise",
+                ),
+        )
+        .arg(
+            Arg::with_name("region-listen")
+                .long("region-listen")
+                .value_name("host:port")
+                .conflicts_with("region-connect")
+                .help("Wait for the server of a neighboring city to connect, for region play"),
+        )
+        .arg(
+            Arg::with_name("region-connect")
+                .long("region-connect")
+                .value_name("host:port")
+                .help("Connect to the server of a neighboring city, for region play"),
+        )
+        .arg(
+            Arg::with_name("api")
+                .long("api")
+                .value_name("host:port")
+                .help("Let external tools like map viewers or dashboards query the city here"),
+        )
+        .arg(
+            Arg::with_name("audit-determinism")
+                .long("audit-determinism")
+                .value_name("n-turns")
+                .validator(|value| match value.parse::<usize>() {
+                    Ok(_) => Ok(()),
+                    Err(_) => Err(format!("{:?} is not a number of turns", value)),
+                })
+                .help(
+                    "Instead of serving, run two instances of the city side by side \
+                     and check that they stay identical",
+                ),
+        )
+        .arg(
+            Arg::with_name("difficulty")
+                .long("difficulty")
+                .value_name("profile")
+                .default_value("normal")
+                .help(
+                    "How forgiving the economy of a new city is and how often disasters \
+                     strike: easy, normal, hard or custom:<starting money>,<import price \
+                     factor>,<demand growth factor>[,<disaster frequency factor>]",
+                ),
+        )
+        .arg(
+            Arg::with_name("scenario")
+                .long("scenario")
+                .value_name("file")
+                .help(
+                    "Found a new city as a scenario, with the starting map, funds \
+                     and goals described in this file",
+                ),
+        )
+        .arg(
+            Arg::with_name("autosave-minutes")
+                .long("autosave-minutes")
+                .value_name("n-minutes")
+                .default_value("5")
+                .help("How often to autosave the city while it runs, in real minutes"),
+        )
+        .arg(
+            Arg::with_name("autosaves")
+                .long("autosaves")
+                .value_name("n-autosaves")
+                .default_value("5")
+                .help("How many of the most recent autosaves to keep"),
+        )
+        .arg(
+            Arg::with_name("recover-autosave")
+                .long("recover-autosave")
+                .help(
+                    "Go back to the most recent intact autosave before starting, \
+                     for example after a crash",
+                ),
+        )
+        .arg(
+            Arg::with_name("record-replay")
+                .long("record-replay")
+                .help(
+                    "Record what comes into the simulation from outside, starting from \
+                     an autosave, so that the run can be replayed exactly",
+                ),
+        )
+        .arg(
+            Arg::with_name("metrics-csv")
+                .long("metrics-csv")
+                .value_name("file")
+                .help(
+                    "Write the daily metrics of the city to this CSV file with every autosave, \
+                     for analyzing long runs in other tools",
+                ),
+        )
+        .arg(
+            Arg::with_name("replay")
+                .long("replay")
+                .value_name("snapshot")
+                .help(
+                    "Instead of serving, replay the run recorded from this autosave in a copy \
+                     of the city and check that it ends up the same",
+                ),
+        )
+        .arg(
+            Arg::with_name("bot")
+                .long("bot")
+                .value_name("strategy")
+                .possible_values(&["grid", "corridor"])
+                .help("Let a scripted bot play a new city, for automated playtests"),
+        )
+        .arg(
+            Arg::with_name("soak")
+                .long("soak")
+                .value_name("n-days")
+                .requires("bot")
+                .help(
+                    "Instead of serving, let the bot play a new city for this many \
+                     simulated days as fast as possible and report how it went",
+                ),
+        )
         .get_matches();
 
+    // checked by the validator
+    let n_clients: u8 = matches.value_of("clients").unwrap().parse().unwrap();
+
+    let mut roles = HashMap::new();
+    roles.insert(1, Role::Mayor);
+
+    for role_arg in matches.values_of("role").into_iter().flatten() {
+        let (machine, role) = parse_role_arg(role_arg).expect("checked by the validator");
+
+        if machine > n_clients {
+            clap::Error::with_description(
+                &format!(
+                    "Invalid value for '--role': client {} doesn't exist with --clients {}",
+                    machine, n_clients
+                ),
+                clap::ErrorKind::InvalidValue,
+            )
+            .exit();
+        }
+
+        roles.insert(machine, role);
+    }
+
+    let difficulty_arg = matches.value_of("difficulty").unwrap();
+    let difficulty = DifficultyProfile::from_name(difficulty_arg).unwrap_or_else(|| {
+        panic!(
+            "Invalid difficulty {:?}, expected easy, normal, hard or e.g. custom:150000,1.2,0.9",
+            difficulty_arg
+        )
+    });
+
+    let maybe_scenario = matches.value_of("scenario").map(|path| {
+        let text = ::std::fs::read_to_string(path)
+            .unwrap_or_else(|error| panic!("Couldn't read scenario {:?}: {}", path, error));
+        Scenario::parse(&text)
+            .unwrap_or_else(|error| panic!("Invalid scenario {:?}, {}", path, error))
+    });
+
     (
         NetworkConfig {
             serve_host_port: matches.value_of("bind").unwrap().to_owned(),
@@ -117,11 +356,67 @@ pub fn match_cmd_line_args(version: &str) -> (NetworkConfig, String) {
             batch_msg_bytes: matches.value_of("batch-msg-b").unwrap().parse().unwrap(),
             ok_turn_dist: matches.value_of("ok-turn-dist").unwrap().parse().unwrap(),
             skip_ratio: matches.value_of("skip-ratio").unwrap().parse().unwrap(),
+            n_clients,
+            roles,
+            keys: (1..=n_clients)
+                .map(|machine| (machine, session::new_key()))
+                .collect(),
+            region: matches
+                .value_of("region-listen")
+                .map(|address| RegionConfig::Listen(address.to_owned()))
+                .or_else(|| {
+                    matches
+                        .value_of("region-connect")
+                        .map(|address| RegionConfig::Connect(address.to_owned()))
+                }),
+            api: matches.value_of("api").map(|address| address.to_owned()),
         },
         matches.value_of("CITY_FOLDER").unwrap().to_owned(),
+        difficulty,
+        maybe_scenario,
+        matches.value_of("bot").and_then(Strategy::from_name),
+        matches
+            .value_of("audit-determinism")
+            // checked by the validator
+            .map(|n_turns| HeadlessRun::AuditDeterminism(n_turns.parse().unwrap()))
+            .or_else(|| {
+                matches
+                    .value_of("soak")
+                    .map(|n_days| HeadlessRun::Soak(n_days.parse().unwrap()))
+            })
+            .or_else(|| {
+                matches
+                    .value_of("replay")
+                    .map(|snapshot| HeadlessRun::Replay(snapshot.parse().unwrap()))
+            }),
+        AutosaveConfig {
+            interval: Duration::from_secs(
+                60 * matches
+                    .value_of("autosave-minutes")
+                    .unwrap()
+                    .parse::<u64>()
+                    .unwrap()
+                    .max(1),
+            ),
+            n_to_keep: matches.value_of("autosaves").unwrap().parse().unwrap(),
+            recover: matches.is_present("recover-autosave"),
+            record_replay: matches.is_present("record-replay"),
+            metrics_csv: matches.value_of("metrics-csv").map(|path| path.to_owned()),
+        },
     )
 }
 
+// client=role, clients are numbered from 1
+fn parse_role_arg(role_arg: &str) -> Option<(u8, Role)> {
+    let mut parts = role_arg.splitn(2, '=');
+    let machine = parts
+        .next()
+        .and_then(|machine| machine.trim().parse::<u8>().ok())
+        .filter(|&machine| machine >= 1)?;
+    let role = parts.next().and_then(Role::from_name)?;
+    Some((machine, role))
+}
+
 pub fn ensure_crossplatform_proper_thread<F: Fn() -> () + Send + 'static>(callback: F) {
     // Makes sure that:
     // a) on Windows we use a dummy thread with manually set stack size
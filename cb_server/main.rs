@@ -1,5 +1,10 @@
 extern crate cb_simulation;
 use cb_simulation::kay::TypedID;
+use cb_simulation::savegame::{self, CompatibilityReport};
+use cb_simulation::economy::region::{self, RegionLinkID};
+use cb_simulation::observation::{self, ApiObserverID};
+use cb_simulation::planning::CBPlanManagerID;
+use init::HeadlessRun;
 
 #[macro_use]
 extern crate rust_embed_flag;
@@ -10,12 +15,46 @@ const VERSION: &str = include_str!("../.version");
 
 mod init;
 mod browser_ui_server;
+mod snapshots;
+mod region_link;
+mod api_server;
+mod determinism_audit;
+mod soak;
+mod replay;
+mod metrics_export;
+use region_link::RegionEvent;
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 fn main() {
-    let (network_config, city_folder) = init::match_cmd_line_args(VERSION);
+    let (
+        network_config,
+        city_folder,
+        difficulty,
+        maybe_scenario,
+        maybe_bot,
+        maybe_headless_run,
+        autosave_config,
+    ) = init::match_cmd_line_args(VERSION);
+
+    if let Some(headless_run) = maybe_headless_run {
+        init::ensure_crossplatform_proper_thread(move || {
+            let succeeded = match headless_run {
+                HeadlessRun::AuditDeterminism(n_turns) => {
+                    determinism_audit::run(&city_folder, n_turns, VERSION)
+                }
+                HeadlessRun::Soak(n_days) => {
+                    soak::run(n_days, maybe_bot.expect("Soak runs need a bot"), difficulty)
+                }
+                HeadlessRun::Replay(checkpoint) => replay::run(&city_folder, checkpoint, VERSION),
+            };
+            if !succeeded {
+                ::std::process::exit(1);
+            }
+        });
+        return;
+    }
 
     init::print_start_message(VERSION, &network_config);
 
@@ -28,30 +67,82 @@ fn main() {
     })
     .expect("Error setting Ctrl-C handler");
 
+    let city_path = ::std::path::PathBuf::from(&city_folder);
+    let version_file_path = city_path.join("__cb_version.txt");
+
+    // scenarios only apply to the city they found
+    let maybe_new_scenario = maybe_scenario.filter(|_| !version_file_path.exists());
+    if let Some(map) = maybe_new_scenario
+        .as_ref()
+        .and_then(|scenario| scenario.map.as_ref())
+    {
+        match snapshots::SnapshotStore::new(::std::path::Path::new(map)).copy_city_into(&city_path)
+        {
+            Ok(n_files) => println!("Starting from map {} ({} files).", map, n_files),
+            Err(error) => {
+                println!("Couldn't copy the starting map {}: {}", map, error);
+                ::std::process::exit(1);
+            }
+        }
+    }
+    let snapshot_store =
+        snapshots::SnapshotStore::new(&city_path).keeping(autosave_config.n_to_keep);
+    let recovery = if autosave_config.recover {
+        snapshot_store.recover_latest()
+    } else {
+        snapshot_store.recover_if_truncated()
+    };
+    match recovery {
+        Ok(Some(number)) => println!("Recovered savegame from snapshot {}.", number),
+        Ok(None) if autosave_config.recover => println!("There is no autosave to recover yet."),
+        Ok(None) => {
+            if snapshot_store.was_interrupted() {
+                snapshots::offer_recovery(&snapshot_store);
+            }
+        }
+        Err(error) => println!("Couldn't recover savegame: {}", error),
+    }
+    let maybe_savegame_report = if let Ok(saved_version) =
+        std::fs::read_to_string(&version_file_path)
+    {
+        println!("Loading from savegame {}...", &city_folder);
+        let report = CompatibilityReport::check(&city_path, &saved_version, VERSION);
+        if saved_version != VERSION {
+            print!("{}", report.summary());
+        }
+        if !report.can_load() {
+            println!("Essential parts of the city can't be loaded with this version, stopping.");
+            ::std::process::exit(1);
+        }
+        if report.is_partial() {
+            let removed = savegame::drop_persisted_state(&city_path, &report)
+                .expect("Couldn't drop incompatible parts of the savegame");
+            println!(
+                "Loading partially, dropped {} incompatible savegame files.",
+                removed.len()
+            );
+        }
+        Some(report)
+    } else {
+        println!("Savegame folder {} not found, creating...", city_folder);
+        std::fs::create_dir_all(&city_folder).expect("Couldn't create savegame folder.");
+        None
+    };
+
     let network_config_2 = network_config.clone();
+    let savegame_report_2 = maybe_savegame_report.clone();
     ::std::thread::spawn(move || {
-        browser_ui_server::start_browser_ui_server(VERSION, network_config_2);
+        browser_ui_server::start_browser_ui_server(VERSION, network_config_2, savegame_report_2);
     });
 
     init::ensure_crossplatform_proper_thread(move || {
-        let version_file_path = ::std::path::PathBuf::from(&city_folder).join("__cb_version.txt");
-        let savegame_exists = if let Ok(version) = std::fs::read_to_string(&version_file_path) {
-            println!("Loading from savegame {}...", &city_folder);
-            if version != VERSION {
-                println!("POTENTIALLY INCOMPATIBLE SAVEGAME!")
-            }
-            true
-        } else {
-            println!("Savegame folder {} not found, creating...", city_folder);
-            std::fs::create_dir_all(&city_folder).expect("Couldn't create savegame folder.");
-            ::std::fs::write(version_file_path, VERSION).expect("Could not write savegame version");
-            false
-        };
-
         let mut system = Box::new(cb_simulation::kay::ActorSystem::new_mmap_persisted(
             cb_simulation::kay::Networking::new(
                 0,
-                vec![network_config.bind_sim.clone(), "ws-client".to_owned()],
+                Some(network_config.bind_sim.clone())
+                    .into_iter()
+                    .chain((0..network_config.n_clients).map(|_| "ws-client".to_owned()))
+                    .collect(),
                 network_config.batch_msg_bytes,
                 network_config.ok_turn_dist,
                 network_config.skip_ratio,
@@ -60,17 +151,51 @@ fn main() {
             cb_simulation::kay::Tuning::default()
         ));
         init::set_error_hook();
+        cb_simulation::cb_util::session::set_session(
+            network_config.roles.clone(),
+            network_config.keys.clone(),
+        );
 
         cb_simulation::setup_common(&mut system);
         system.networking_connect();
 
         let world = &mut system.world();
 
-        let time = if savegame_exists {
-            cb_simulation::cb_time::actors::TimeID::global_first(world)
+        let time = if let Some(ref report) = maybe_savegame_report {
+            let time = cb_simulation::cb_time::actors::TimeID::global_first(world);
+            if report.is_partial() {
+                let dropped_actor_types = savegame::dropped_actor_types(
+                    report,
+                    &system.get_actor_type_id_to_name_mapping(),
+                );
+                savegame::restore_dropped(report, &dropped_actor_types, time, world);
+            }
+            time
         } else {
-            cb_simulation::spawn_for_server(world)
+            let time = cb_simulation::spawn_for_server(world, difficulty);
+            if let Some(strategy) = maybe_bot {
+                println!("A {:?} bot plays the new city.", strategy);
+                cb_simulation::bots::spawn(
+                    world,
+                    time,
+                    CBPlanManagerID::global_first(world),
+                    strategy,
+                );
+            }
+            time
         };
+
+        if let Some(ref scenario) = maybe_new_scenario {
+            println!("Playing the scenario {}.", scenario.name);
+            cb_simulation::scenarios::start(world, scenario, time);
+        }
+
+        ::std::fs::write(&version_file_path, VERSION).expect("Could not write savegame version");
+        savegame::write_manifest(&city_path).expect("Could not write savegame manifest");
+        snapshots::autosave(&snapshot_store);
+        if let Err(error) = snapshot_store.mark_running() {
+            println!("Couldn't mark the city as running: {}", error);
+        }
         println!(
             "Simulation running.\n(You can stop this process at any point and the savegame should \
              be fine)"
@@ -80,6 +205,24 @@ fn main() {
 
         let mut frame_counter = init::FrameCounter::new();
         let mut skip_turns = 0;
+        let mut last_autosave = ::std::time::Instant::now();
+        let mut background_autosave = snapshots::BackgroundAutosave::new(snapshot_store.clone());
+        let mut maybe_region_link = network_config
+            .region
+            .clone()
+            .map(region_link::RegionLink::new);
+        let mut maybe_api_server = network_config.api.as_ref().and_then(|address| {
+            api_server::ApiServer::new(address)
+                .map_err(|error| println!("Couldn't serve the API at {}: {}", address, error))
+                .ok()
+        });
+        let mut maybe_recorder = if autosave_config.record_replay {
+            replay::ReplayRecorder::start(&snapshot_store, VERSION)
+                .map_err(|error| println!("Couldn't start recording a replay: {}", error))
+                .ok()
+        } else {
+            None
+        };
 
         while running.load(Ordering::SeqCst) {
             frame_counter.start_frame();
@@ -89,6 +232,33 @@ fn main() {
             if skip_turns == 0 {
                 time.progress(world);
                 system.process_all_messages();
+            } else if let Some(ref mut recorder) = maybe_recorder {
+                recorder.skipped();
+            }
+
+            if let Some(ref mut region_link) = maybe_region_link {
+                let link_actor = RegionLinkID::global_first(world);
+                for event in region_link.exchange(region::take_outgoing()) {
+                    if let Some(ref mut recorder) = maybe_recorder {
+                        recorder.region_event(&event);
+                    }
+                    match event {
+                        RegionEvent::Connected => link_actor.set_connected(true, world),
+                        RegionEvent::Disconnected => link_actor.set_connected(false, world),
+                        RegionEvent::Received(message) => link_actor.receive(message, world),
+                    }
+                }
+                system.process_all_messages();
+            }
+
+            if let Some(ref mut api_server) = maybe_api_server {
+                for (request, query) in api_server.receive() {
+                    observation::ask(request, query, world);
+                }
+                system.process_all_messages();
+                ApiObserverID::global_first(world).flush(world);
+                system.process_all_messages();
+                api_server.answer(observation::take_answers());
             }
 
             system.networking_send_and_receive();
@@ -103,7 +273,36 @@ fn main() {
                 }
             }
 
+            // checkpoint barrier: the city is only captured once all messages of the
+            // turn are handled and no skipped turns are still being caught up on,
+            // so that the files of all actors are from the same moment
+            if last_autosave.elapsed() > autosave_config.interval && skip_turns == 0 {
+                system.process_all_messages();
+                background_autosave.start();
+                last_autosave = ::std::time::Instant::now();
+                if let Some(ref path) = autosave_config.metrics_csv {
+                    metrics_export::export(&mut system, path);
+                }
+            }
+
+            if let Some(ref mut recorder) = maybe_recorder {
+                recorder.end_turn();
+            }
+
             frame_counter.sleep_if_faster_than(120);
         }
+
+        background_autosave.finish();
+        if let Some(ref path) = autosave_config.metrics_csv {
+            metrics_export::export(&mut system, path);
+        }
+        if let Some(recorder) = maybe_recorder {
+            recorder.finish(&snapshot_store);
+        } else {
+            snapshots::autosave(&snapshot_store);
+        }
+        if let Err(error) = snapshot_store.mark_stopped() {
+            println!("Couldn't mark the city as stopped: {}", error);
+        }
     });
 }
@@ -0,0 +1,44 @@
+// Dumps the daily metrics of the city into a CSV file, for analyzing long
+// runs in spreadsheets or notebooks. The metrics are asked for like an API
+// tool would, and the whole history is written every time, so the file is
+// always complete on its own.
+
+use std::fs;
+use std::io;
+use cb_simulation::kay::{ActorSystem, TypedID};
+use cb_simulation::observation::{self, ApiObserverID};
+use cb_simulation::citybound_api::{self, Answer, Query};
+
+// never handed out to API tools, their requests are numbered from 0
+const EXPORT_REQUEST: u64 = ::std::u64::MAX;
+
+fn write_csv(system: &mut ActorSystem, path: &str) -> io::Result<()> {
+    let world = &mut system.world();
+    observation::ask(EXPORT_REQUEST, Query::Metrics { from_day: 0 }, world);
+    system.process_all_messages();
+    ApiObserverID::global_first(world).flush(world);
+    system.process_all_messages();
+
+    let maybe_days = observation::take_answers()
+        .into_iter()
+        .filter(|&(request, _)| request == EXPORT_REQUEST)
+        .filter_map(|(_, answer)| match answer {
+            Answer::Metrics(days) => Some(days),
+            _ => None,
+        })
+        .next();
+
+    match maybe_days {
+        Some(days) => fs::write(path, citybound_api::metrics_csv(&days)),
+        None => Err(io::Error::new(
+            io::ErrorKind::Other,
+            "the city didn't answer with its metrics",
+        )),
+    }
+}
+
+pub fn export(system: &mut ActorSystem, path: &str) {
+    if let Err(error) = write_csv(system, path) {
+        println!("Couldn't write the metrics to {}: {}", path, error);
+    }
+}
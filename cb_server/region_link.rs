@@ -0,0 +1,181 @@
+// The connection to the server of a neighboring city, for cooperative region play.
+//
+// One of the two servers listens, the other one connects to it, after that
+// both sides are the same: region messages go back and forth as lines of
+// text. The connection is polled once per turn from the simulation thread
+// and never blocks it. A lost connection is picked up again automatically.
+
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+use cb_simulation::economy::region::RegionMessage;
+
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
+pub enum RegionConfig {
+    Listen(String),
+    Connect(String),
+}
+
+pub struct RegionLink {
+    config: RegionConfig,
+    listener: Option<TcpListener>,
+    stream: Option<TcpStream>,
+    received: Vec<u8>,
+    last_attempt: Option<Instant>,
+}
+
+pub enum RegionEvent {
+    Connected,
+    Disconnected,
+    Received(RegionMessage),
+}
+
+impl RegionLink {
+    pub fn new(config: RegionConfig) -> RegionLink {
+        RegionLink {
+            config,
+            listener: None,
+            stream: None,
+            received: Vec::new(),
+            last_attempt: None,
+        }
+    }
+
+    fn try_connect(&mut self) -> io::Result<Option<TcpStream>> {
+        match self.config {
+            RegionConfig::Listen(ref address) => {
+                if self.listener.is_none() {
+                    let listener = TcpListener::bind(address)?;
+                    listener.set_nonblocking(true)?;
+                    println!("Waiting for a neighboring city to connect at {}", address);
+                    self.listener = Some(listener);
+                }
+
+                match self.listener.as_ref().unwrap().accept() {
+                    Ok((stream, _)) => Ok(Some(stream)),
+                    Err(ref error) if error.kind() == io::ErrorKind::WouldBlock => Ok(None),
+                    Err(error) => Err(error),
+                }
+            }
+            RegionConfig::Connect(ref address) => TcpStream::connect(address).map(Some),
+        }
+    }
+
+    // Sends what the simulation has for the neighbor and collects what arrived from it
+    pub fn exchange(&mut self, outgoing: Vec<RegionMessage>) -> Vec<RegionEvent> {
+        let mut events = Vec::new();
+
+        if self.stream.is_none() {
+            let should_attempt = self
+                .last_attempt
+                .map(|last_attempt| last_attempt.elapsed() > RECONNECT_INTERVAL)
+                .unwrap_or(true);
+
+            // listening is cheap, so a listener checks for the neighbor every turn
+            if let RegionConfig::Connect(_) = self.config {
+                if !should_attempt {
+                    return events;
+                }
+            }
+            self.last_attempt = Some(Instant::now());
+
+            match self
+                .try_connect()
+                .and_then(|maybe_stream| match maybe_stream {
+                    Some(stream) => stream.set_nonblocking(true).map(|_| Some(stream)),
+                    None => Ok(None),
+                }) {
+                Ok(Some(stream)) => {
+                    println!("Connected to neighboring city at {:?}", stream.peer_addr());
+                    self.stream = Some(stream);
+                    self.received.clear();
+                    events.push(RegionEvent::Connected);
+                }
+                Ok(None) => return events,
+                Err(error) => {
+                    if should_attempt {
+                        println!("Couldn't connect to neighboring city: {}", error);
+                    }
+                    return events;
+                }
+            }
+        }
+
+        // messages from before the connection was up are about old deals, drop them
+        if events.is_empty() {
+            if let Err(error) = self.write(&outgoing) {
+                return self.disconnected(events, &error);
+            }
+        }
+
+        if let Err(error) = self.read() {
+            return self.disconnected(events, &error);
+        }
+
+        while let Some(newline) = self.received.iter().position(|&byte| byte == b'\n') {
+            let line = self.received.drain(..=newline).collect::<Vec<_>>();
+            let line = String::from_utf8_lossy(&line);
+            match RegionMessage::decode(line.trim()) {
+                Some(message) => events.push(RegionEvent::Received(message)),
+                None => println!("Ignoring invalid message from neighboring city: {:?}", line),
+            }
+        }
+
+        events
+    }
+
+    fn write(&mut self, outgoing: &[RegionMessage]) -> io::Result<()> {
+        if outgoing.is_empty() {
+            return Ok(());
+        }
+
+        let mut text = String::new();
+        for message in outgoing {
+            text.push_str(&message.encode());
+            text.push('\n');
+        }
+
+        let stream = self.stream.as_mut().unwrap();
+        // messages are small and rare, so the socket buffer only fills up
+        // if the neighbor stopped reading, which a short block can wait out
+        stream.set_nonblocking(false)?;
+        stream.set_write_timeout(Some(Duration::from_secs(1)))?;
+        let result = stream.write_all(text.as_bytes());
+        stream.set_nonblocking(true)?;
+        result
+    }
+
+    fn read(&mut self) -> io::Result<()> {
+        let stream = self.stream.as_mut().unwrap();
+        let mut buffer = [0u8; 4096];
+
+        loop {
+            match stream.read(&mut buffer) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::ConnectionAborted,
+                        "closed by the neighboring city",
+                    ))
+                }
+                Ok(n) => self.received.extend_from_slice(&buffer[..n]),
+                Err(ref error) if error.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    fn disconnected(
+        &mut self,
+        mut events: Vec<RegionEvent>,
+        error: &io::Error,
+    ) -> Vec<RegionEvent> {
+        println!("Lost connection to neighboring city: {}", error);
+        self.stream = None;
+        self.received.clear();
+        self.last_attempt = Some(Instant::now());
+        events.push(RegionEvent::Disconnected);
+        events
+    }
+}
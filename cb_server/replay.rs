@@ -0,0 +1,300 @@
+// Records what the server feeds into the simulation from outside, turn by
+// turn, starting from an autosave, so that a run can be repeated exactly
+// later on, for example to find out how a desync or a strange state of the
+// economy came about after hours of play.
+//
+// Everything the simulation does only depends on the state of its actors and
+// the messages they get (see `determinism_audit`), and its randomness is
+// seeded from that state as well. So apart from the autosave it starts from,
+// a replay only needs which turns were skipped to let clients catch up and
+// what neighboring cities sent. When the recording stops, another autosave
+// is taken, which the replayed city is compared with at the end.
+//
+// Messages from connected clients are delivered inside the actor system's
+// networking, out of reach of the server loop, so they aren't recorded yet.
+// If players changed the city while recording, the replay will diverge, and
+// the comparison shows where. Queries of the API are read-only and left out.
+//
+// A replay is a text file, one line per input:
+//   version <version>
+//   <turn> skip                 the simulation time didn't progress
+//   <turn> connected            the neighboring city connected
+//   <turn> disconnected         or the connection was lost
+//   <turn> region <message>     a message from the neighboring city
+//   <turn> end <snapshot>       the recording stopped, before this turn
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use cb_simulation::kay::{ActorSystem, Networking, Tuning, TypedID};
+use cb_simulation::cb_time::actors::TimeID;
+use cb_simulation::economy::region::{self, RegionLinkID, RegionMessage};
+use region_link::RegionEvent;
+use snapshots::{Snapshot, SnapshotStore};
+use determinism_audit::subsystem_name;
+
+const REPLAY_FOLDER: &str = "cb_replay";
+// the replay never connects to anything, but needs an address of its own
+const REPLAY_ADDRESS: &str = "localhost:9995";
+
+pub struct ReplayRecorder {
+    file: BufWriter<File>,
+    turn: usize,
+    unflushed: bool,
+    failed: bool,
+}
+
+impl ReplayRecorder {
+    // Takes the autosave the replay starts from. Has to be called between turns
+    pub fn start(store: &SnapshotStore, version: &str) -> io::Result<ReplayRecorder> {
+        let checkpoint = store.take()?.number;
+        let path = store.replay_path(checkpoint);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = BufWriter::new(File::create(&path)?);
+        writeln!(file, "version {}", version.trim())?;
+        println!("Recording a replay from snapshot {}.", checkpoint);
+
+        Ok(ReplayRecorder {
+            file,
+            turn: 0,
+            unflushed: true,
+            failed: false,
+        })
+    }
+
+    fn record(&mut self, input: &str) {
+        if self.failed {
+            return;
+        }
+        if let Err(error) = writeln!(self.file, "{} {}", self.turn, input) {
+            println!("Stopped recording the replay: {}", error);
+            self.failed = true;
+        }
+        self.unflushed = true;
+    }
+
+    pub fn skipped(&mut self) {
+        self.record("skip");
+    }
+
+    pub fn region_event(&mut self, event: &RegionEvent) {
+        match *event {
+            RegionEvent::Connected => self.record("connected"),
+            RegionEvent::Disconnected => self.record("disconnected"),
+            RegionEvent::Received(message) => {
+                self.record(&format!("region {}", message.encode()))
+            }
+        }
+    }
+
+    // Whatever was recorded makes it to disk, even if the simulation crashes in the next turn
+    pub fn end_turn(&mut self) {
+        if self.unflushed && !self.failed {
+            if let Err(error) = self.file.flush() {
+                println!("Stopped recording the replay: {}", error);
+                self.failed = true;
+            }
+            self.unflushed = false;
+        }
+        self.turn += 1;
+    }
+
+    // Takes the autosave the replayed city is compared with. Has to be called between turns
+    pub fn finish(mut self, store: &SnapshotStore) {
+        match store.take() {
+            Ok(stats) => {
+                self.record(&format!("end {}", stats.number));
+                self.end_turn();
+                println!(
+                    "Recorded a replay of {} turns, up to snapshot {}.",
+                    self.turn - 1,
+                    stats.number
+                );
+            }
+            Err(error) => println!("Couldn't take the last snapshot of the replay: {}", error),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+enum ReplayInput {
+    Skip,
+    Connected,
+    Disconnected,
+    Region(RegionMessage),
+    End(usize),
+}
+
+fn parse_input(line: &str) -> Option<(usize, ReplayInput)> {
+    let mut parts = line.splitn(3, ' ');
+    let turn = parts.next()?.parse().ok()?;
+    let input = match parts.next()? {
+        "skip" => ReplayInput::Skip,
+        "connected" => ReplayInput::Connected,
+        "disconnected" => ReplayInput::Disconnected,
+        "region" => ReplayInput::Region(RegionMessage::decode(parts.next()?)?),
+        "end" => ReplayInput::End(parts.next()?.parse().ok()?),
+        _ => return None,
+    };
+    Some((turn, input))
+}
+
+// Returns the recorded inputs, in the order of their turns
+fn read_replay(path: &Path, version: &str) -> Result<Vec<(usize, ReplayInput)>, String> {
+    let text = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let mut lines = text.lines();
+
+    match lines.next() {
+        Some(first) if first == format!("version {}", version.trim()) => {}
+        Some(first) => return Err(format!("It was recorded with {}", first)),
+        None => return Err("It is empty".to_owned()),
+    }
+
+    lines
+        .enumerate()
+        .map(|(l, line)| {
+            parse_input(line).ok_or_else(|| format!("Line {} is invalid: {:?}", l + 2, line))
+        })
+        .collect()
+}
+
+fn restore_copy(store: &SnapshotStore, snapshot: &Snapshot, folder: &Path) -> io::Result<()> {
+    if folder.exists() {
+        fs::remove_dir_all(folder)?;
+    }
+    fs::create_dir_all(folder)?;
+    store.restore_into(snapshot, folder)
+}
+
+// Repeats the run recorded from the snapshot in a copy of the city.
+// Returns whether the replayed city ended up like the recorded one
+pub fn run(city_folder: &str, checkpoint: usize, version: &str) -> bool {
+    let store = SnapshotStore::new(Path::new(city_folder));
+
+    let inputs = match read_replay(&store.replay_path(checkpoint), version) {
+        Ok(inputs) => inputs,
+        Err(error) => {
+            println!("Couldn't read the replay from snapshot {}: {}", checkpoint, error);
+            return false;
+        }
+    };
+    let snapshots = store.snapshots();
+    let find_snapshot = |number| snapshots.iter().find(|snapshot| snapshot.number == number);
+
+    let start = match find_snapshot(checkpoint) {
+        Some(start) => start,
+        None => {
+            println!("Snapshot {} the replay starts from is gone.", checkpoint);
+            return false;
+        }
+    };
+
+    let folder = ::std::env::temp_dir().join(REPLAY_FOLDER);
+    if let Err(error) = restore_copy(&store, start, &folder) {
+        println!("Couldn't set up the replay: {}", error);
+        return false;
+    }
+
+    let mut system = Box::new(ActorSystem::new_mmap_persisted(
+        Networking::new(0, vec![REPLAY_ADDRESS.to_owned()], 5000, 2, 5),
+        &folder.to_string_lossy(),
+        Tuning::default(),
+    ));
+    ::cb_simulation::setup_common(&mut system);
+    system.networking_connect();
+    let time = TimeID::global_first(&mut system.world());
+    system.process_all_messages();
+
+    println!("Replaying the run recorded from snapshot {}...", checkpoint);
+
+    let mut remaining = inputs.iter().peekable();
+    let mut maybe_end = None;
+    let mut turn = 0;
+
+    while maybe_end.is_none() && remaining.peek().is_some() {
+        let world = &mut system.world();
+        let mut skipped = false;
+        let mut region_events = Vec::new();
+
+        while let Some(&&(input_turn, input)) = remaining.peek() {
+            if input_turn != turn {
+                break;
+            }
+            remaining.next();
+            match input {
+                ReplayInput::Skip => skipped = true,
+                ReplayInput::End(final_snapshot) => maybe_end = Some(final_snapshot),
+                other => region_events.push(other),
+            }
+        }
+
+        if maybe_end.is_some() {
+            break;
+        }
+
+        // in the same order as the server loop
+        system.process_all_messages();
+
+        if !skipped {
+            time.progress(world);
+            system.process_all_messages();
+        }
+
+        if !region_events.is_empty() {
+            let link_actor = RegionLinkID::global_first(world);
+            for input in region_events {
+                match input {
+                    ReplayInput::Connected => link_actor.set_connected(true, world),
+                    ReplayInput::Disconnected => link_actor.set_connected(false, world),
+                    ReplayInput::Region(message) => link_actor.receive(message, world),
+                    ReplayInput::Skip | ReplayInput::End(_) => unreachable!(),
+                }
+            }
+            system.process_all_messages();
+        }
+        // nobody is listening on the other side
+        region::take_outgoing();
+
+        turn += 1;
+    }
+
+    let final_snapshot = match maybe_end.and_then(find_snapshot) {
+        Some(final_snapshot) => final_snapshot,
+        None => {
+            println!(
+                "Replayed {} turns, but the recording didn't stop properly, \
+                 so there is nothing to compare the city with. It's in {:?}",
+                turn, folder
+            );
+            return false;
+        }
+    };
+
+    match store.differing_files(final_snapshot, &folder) {
+        Ok(ref differing) if differing.is_empty() => {
+            println!(
+                "Replayed {} turns, the city ended up exactly like snapshot {}.",
+                turn, final_snapshot.number
+            );
+            true
+        }
+        Ok(differing) => {
+            println!(
+                "Replayed {} turns, but the city ended up different from snapshot {}, in:",
+                turn, final_snapshot.number
+            );
+            for path in differing {
+                println!("  {:<24} {}", subsystem_name(&path), path);
+            }
+            println!("The replayed city is in {:?}", folder);
+            false
+        }
+        Err(error) => {
+            println!("Couldn't compare the replayed city: {}", error);
+            false
+        }
+    }
+}
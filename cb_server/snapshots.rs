@@ -0,0 +1,783 @@
+// Autosaves of the city folder as chunked, content-addressed snapshots.
+//
+// The simulation persists itself continuously into memory mapped files,
+// which are rewritten all over the place and can end up truncated if the
+// process or machine dies at the wrong moment. Snapshots copy them into a
+// store where nothing is ever rewritten: files are split into chunks named
+// after a hash of their contents, so a new snapshot only adds the chunks
+// that changed since the last one, which keeps autosaves of huge cities
+// fast and lets file sync services upload just the difference.
+//
+// While the simulation runs, only a checkpoint of the city files is taken
+// between two of its turns: copy-on-write clones of them where the file system
+// can make those, which is about as cheap as listing them. Reading, chunking
+// and writing them into the store happens in the background, so autosaving a
+// huge city doesn't freeze the simulation. Where files can't be cloned, they
+// have to be read into memory between the two turns instead.
+//
+// A marker file exists for as long as the simulation runs. If it's still there
+// when the city is opened again, the simulation crashed or was killed, and the
+// player is offered to go back to the most recent autosave, even if no file
+// was left truncated.
+//
+// __cb_snapshots/
+//   chunks/<hash>        immutable chunk contents
+//   manifests/<n>.txt    for snapshot n: each file with its length and chunks
+//   journal.txt          one line per complete snapshot, only ever appended to
+//   running              exists while the simulation runs
+//   replays/<n>.txt      inputs recorded from snapshot n on, see `replay`
+//   checkpoint/          clones of the city files, until they're stored
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SNAPSHOT_FOLDER: &str = "__cb_snapshots";
+const CHUNK_SIZE: usize = 1024 * 1024;
+pub const DEFAULT_SNAPSHOTS_TO_KEEP: usize = 5;
+const RUNNING_MARKER: &str = "running";
+const CHECKPOINT_FOLDER: &str = "checkpoint";
+
+// FNV-1a, good enough to tell chunks apart and to notice damaged ones
+pub fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// Writes to a temporary file first, so a file in the store is either complete or missing
+fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let temp_path = path.with_extension("tmp");
+    {
+        let mut file = File::create(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+    }
+    fs::rename(temp_path, path)
+}
+
+// Makes a copy-on-write clone, which shares all contents with the original
+// until one of them is written to, so it only costs a bit of file metadata
+#[cfg(target_os = "linux")]
+fn clone_file(from: &Path, to: &Path) -> io::Result<()> {
+    use std::os::raw::{c_int, c_ulong};
+    use std::os::unix::io::AsRawFd;
+
+    extern "C" {
+        fn ioctl(fd: c_int, request: c_ulong, ...) -> c_int;
+    }
+    const FICLONE: c_ulong = 0x4004_9409;
+
+    let original = File::open(from)?;
+    let clone = File::create(to)?;
+    if unsafe { ioctl(clone.as_raw_fd(), FICLONE, original.as_raw_fd()) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn clone_file(from: &Path, to: &Path) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::raw::{c_char, c_int};
+    use std::os::unix::ffi::OsStrExt;
+
+    extern "C" {
+        fn clonefile(from: *const c_char, to: *const c_char, flags: u32) -> c_int;
+    }
+
+    let from = CString::new(from.as_os_str().as_bytes())?;
+    let to = CString::new(to.as_os_str().as_bytes())?;
+    if unsafe { clonefile(from.as_ptr(), to.as_ptr(), 0) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn clone_file(_from: &Path, _to: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        "Copy-on-write clones aren't supported here",
+    ))
+}
+
+pub struct SnapshotFile {
+    pub path: String,
+    pub len: u64,
+    pub chunks: Vec<u64>,
+}
+
+pub struct Snapshot {
+    pub number: usize,
+    pub taken_at: u64,
+    pub files: Vec<SnapshotFile>,
+}
+
+impl Snapshot {
+    fn to_manifest(&self) -> String {
+        self.files
+            .iter()
+            .map(|file| {
+                let chunks = file
+                    .chunks
+                    .iter()
+                    .map(|chunk| format!("{:016x}", chunk))
+                    .collect::<Vec<_>>();
+                format!("{} {} {}\n", file.len, chunks.join(","), file.path)
+            })
+            .collect()
+    }
+
+    fn from_manifest(number: usize, taken_at: u64, manifest: &str) -> Option<Snapshot> {
+        let files = manifest
+            .lines()
+            .map(|line| {
+                let mut parts = line.splitn(3, ' ');
+                let len = parts.next()?.parse().ok()?;
+                let chunks = parts
+                    .next()?
+                    .split(',')
+                    .filter(|chunk| !chunk.is_empty())
+                    .map(|chunk| u64::from_str_radix(chunk, 16).ok())
+                    .collect::<Option<Vec<_>>>()?;
+                let path = parts.next()?.to_owned();
+                Some(SnapshotFile { path, len, chunks })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Snapshot {
+            number,
+            taken_at,
+            files,
+        })
+    }
+}
+
+pub struct SnapshotStats {
+    pub number: usize,
+    pub n_chunks: usize,
+    pub n_new_chunks: usize,
+    pub new_bytes: usize,
+}
+
+// The contents of all city files at one moment, relative to the city folder
+#[derive(Default)]
+pub struct Capture {
+    files: Vec<(String, Vec<u8>)>,
+}
+
+impl Capture {
+    // Reuses the buffers of an earlier capture, which are about the right size already
+    fn read(&mut self, folder: &Path, paths: Vec<String>) -> io::Result<()> {
+        let mut buffers = ::std::mem::replace(&mut self.files, Vec::new())
+            .into_iter()
+            .map(|(_, buffer)| buffer);
+
+        for path in paths {
+            let mut buffer = buffers.next().unwrap_or_default();
+            buffer.clear();
+            File::open(folder.join(&path))?.read_to_end(&mut buffer)?;
+            self.files.push((path, buffer));
+        }
+
+        Ok(())
+    }
+}
+
+// All city files at one moment, not necessarily read yet
+pub enum Checkpoint {
+    Cloned { folder: PathBuf, paths: Vec<String> },
+    Captured,
+}
+
+impl Checkpoint {
+    // Can happen long after the checkpoint was taken, the clones stay as they were
+    pub fn read_into(self, capture: &mut Capture) -> io::Result<()> {
+        match self {
+            Checkpoint::Cloned { folder, paths } => {
+                let result = capture.read(&folder, paths);
+                fs::remove_dir_all(&folder)?;
+                result
+            }
+            Checkpoint::Captured => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SnapshotStore {
+    city_folder: PathBuf,
+    folder: PathBuf,
+    n_to_keep: usize,
+}
+
+impl SnapshotStore {
+    pub fn new(city_folder: &Path) -> SnapshotStore {
+        SnapshotStore {
+            city_folder: city_folder.to_owned(),
+            folder: city_folder.join(SNAPSHOT_FOLDER),
+            n_to_keep: DEFAULT_SNAPSHOTS_TO_KEEP,
+        }
+    }
+
+    pub fn keeping(self, n_to_keep: usize) -> SnapshotStore {
+        SnapshotStore {
+            n_to_keep: n_to_keep.max(1),
+            ..self
+        }
+    }
+
+    fn chunk_path(&self, chunk: u64) -> PathBuf {
+        self.folder.join("chunks").join(format!("{:016x}", chunk))
+    }
+
+    fn manifest_path(&self, number: usize) -> PathBuf {
+        self.folder
+            .join("manifests")
+            .join(format!("{}.txt", number))
+    }
+
+    fn journal_path(&self) -> PathBuf {
+        self.folder.join("journal.txt")
+    }
+
+    pub fn replay_path(&self, number: usize) -> PathBuf {
+        self.folder.join("replays").join(format!("{}.txt", number))
+    }
+
+    fn running_marker_path(&self) -> PathBuf {
+        self.folder.join(RUNNING_MARKER)
+    }
+
+    pub fn mark_running(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.folder)?;
+        write_atomically(&self.running_marker_path(), b"")
+    }
+
+    pub fn mark_stopped(&self) -> io::Result<()> {
+        fs::remove_file(self.running_marker_path())
+    }
+
+    // Whether the simulation didn't get to stop properly the last time it ran
+    pub fn was_interrupted(&self) -> bool {
+        self.running_marker_path().exists()
+    }
+
+    // All files of the city, relative to the city folder, except the snapshots themselves
+    pub fn city_files(&self) -> io::Result<Vec<String>> {
+        let mut files = Vec::new();
+        let mut folders_to_visit = vec![self.city_folder.clone()];
+
+        while let Some(folder) = folders_to_visit.pop() {
+            for entry in fs::read_dir(&folder)? {
+                let path = entry?.path();
+                if path == self.folder {
+                    continue;
+                } else if path.is_dir() {
+                    folders_to_visit.push(path);
+                } else if let Ok(relative) = path.strip_prefix(&self.city_folder) {
+                    let components = relative
+                        .components()
+                        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                        .collect::<Vec<_>>();
+                    files.push(components.join("/"));
+                }
+            }
+        }
+
+        files.sort();
+        Ok(files)
+    }
+
+    // Copies the city, without its autosaves, into a folder that doesn't
+    // contain a city yet, like a scenario's starting map into a new city
+    pub fn copy_city_into(&self, folder: &Path) -> io::Result<usize> {
+        let files = self.city_files()?;
+        for file in &files {
+            let path = folder.join(file);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(self.city_folder.join(file), path)?;
+        }
+        Ok(files.len())
+    }
+
+    // Entries of the journal that are complete and whose manifests are intact,
+    // oldest first. A line cut short by a crash is simply ignored
+    pub fn snapshots(&self) -> Vec<Snapshot> {
+        let journal = fs::read_to_string(self.journal_path()).unwrap_or_default();
+
+        journal
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split(' ');
+                let number = parts.next()?.parse().ok()?;
+                let taken_at = parts.next()?.parse().ok()?;
+                let manifest_hash = u64::from_str_radix(parts.next()?, 16).ok()?;
+                let manifest = fs::read_to_string(self.manifest_path(number)).ok()?;
+                if hash(manifest.as_bytes()) != manifest_hash {
+                    return None;
+                }
+                Snapshot::from_manifest(number, taken_at, &manifest)
+            })
+            .collect()
+    }
+
+    pub fn capture(&self, capture: &mut Capture) -> io::Result<()> {
+        capture.read(&self.city_folder, self.city_files()?)
+    }
+
+    // Clones all city files if the file system allows it, only reads
+    // them into the capture right away if it doesn't
+    pub fn checkpoint(&self, capture: &mut Capture) -> io::Result<Checkpoint> {
+        let folder = self.folder.join(CHECKPOINT_FOLDER);
+        if folder.exists() {
+            fs::remove_dir_all(&folder)?;
+        }
+
+        let paths = self.city_files()?;
+        let cloned = paths.iter().try_for_each(|path| {
+            let clone_path = folder.join(path);
+            if let Some(parent) = clone_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            clone_file(&self.city_folder.join(path), &clone_path)
+        });
+
+        if cloned.is_ok() {
+            Ok(Checkpoint::Cloned { folder, paths })
+        } else {
+            if folder.exists() {
+                fs::remove_dir_all(&folder)?;
+            }
+            self.capture(capture)?;
+            Ok(Checkpoint::Captured)
+        }
+    }
+
+    pub fn take(&self) -> io::Result<SnapshotStats> {
+        let mut capture = Capture::default();
+        self.capture(&mut capture)?;
+        self.store(&capture)
+    }
+
+    pub fn store(&self, capture: &Capture) -> io::Result<SnapshotStats> {
+        fs::create_dir_all(self.folder.join("chunks"))?;
+        fs::create_dir_all(self.folder.join("manifests"))?;
+
+        let number = self
+            .snapshots()
+            .last()
+            .map(|snapshot| snapshot.number + 1)
+            .unwrap_or(0);
+        let taken_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or(0);
+
+        let mut snapshot = Snapshot {
+            number,
+            taken_at,
+            files: Vec::new(),
+        };
+        let mut stats = SnapshotStats {
+            number,
+            n_chunks: 0,
+            n_new_chunks: 0,
+            new_bytes: 0,
+        };
+
+        for &(ref path, ref contents) in &capture.files {
+            let mut snapshot_file = SnapshotFile {
+                path: path.clone(),
+                len: 0,
+                chunks: Vec::new(),
+            };
+
+            for chunk_bytes in contents.chunks(CHUNK_SIZE) {
+                let chunk = hash(chunk_bytes);
+                let chunk_path = self.chunk_path(chunk);
+                if !chunk_path.exists() {
+                    write_atomically(&chunk_path, chunk_bytes)?;
+                    stats.n_new_chunks += 1;
+                    stats.new_bytes += chunk_bytes.len();
+                }
+
+                snapshot_file.len += chunk_bytes.len() as u64;
+                snapshot_file.chunks.push(chunk);
+                stats.n_chunks += 1;
+            }
+
+            snapshot.files.push(snapshot_file);
+        }
+
+        let manifest = snapshot.to_manifest();
+        write_atomically(&self.manifest_path(number), manifest.as_bytes())?;
+
+        // only now the snapshot counts as taken
+        let mut journal = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.journal_path())?;
+        journal.write_all(
+            format!(
+                "{} {} {:016x}\n",
+                number,
+                taken_at,
+                hash(manifest.as_bytes())
+            )
+            .as_bytes(),
+        )?;
+        journal.sync_all()?;
+
+        self.prune()?;
+
+        Ok(stats)
+    }
+
+    // Forgets all but the most recent snapshots and the chunks only they used,
+    // except for snapshots that replays start from.
+    // The journal keeps its old lines, they just don't lead anywhere anymore
+    fn prune(&self) -> io::Result<()> {
+        let snapshots = self.snapshots();
+        if snapshots.len() <= self.n_to_keep {
+            return Ok(());
+        }
+
+        let (older, most_recent) = snapshots.split_at(snapshots.len() - self.n_to_keep);
+        let (replayed, to_forget): (Vec<&Snapshot>, Vec<&Snapshot>) = older
+            .iter()
+            .partition(|snapshot| self.replay_path(snapshot.number).exists());
+        for snapshot in to_forget {
+            fs::remove_file(self.manifest_path(snapshot.number))?;
+        }
+
+        let used_chunks = most_recent
+            .iter()
+            .chain(replayed)
+            .flat_map(|snapshot| snapshot.files.iter())
+            .flat_map(|file| file.chunks.iter().cloned())
+            .collect::<::std::collections::HashSet<_>>();
+
+        for entry in fs::read_dir(self.folder.join("chunks"))? {
+            let path = entry?.path();
+            let is_used = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| u64::from_str_radix(name, 16).ok())
+                .map(|chunk| used_chunks.contains(&chunk))
+                .unwrap_or(false);
+            if !is_used {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Files of the city that are missing or shorter than in the snapshot,
+    // which happens when the simulation was stopped in the middle of writing them
+    pub fn truncated_files(&self, snapshot: &Snapshot) -> Vec<String> {
+        snapshot
+            .files
+            .iter()
+            .filter(|file| {
+                fs::metadata(self.city_folder.join(&file.path))
+                    .map(|metadata| metadata.len() < file.len)
+                    .unwrap_or(true)
+            })
+            .map(|file| file.path.clone())
+            .collect()
+    }
+
+    fn read_chunk(&self, chunk: u64) -> io::Result<Vec<u8>> {
+        let bytes = fs::read(self.chunk_path(chunk))?;
+        if hash(&bytes) == chunk {
+            Ok(bytes)
+        } else {
+            Err(invalid_data(format!("Chunk {:016x} is damaged", chunk)))
+        }
+    }
+
+    // Checks all chunks first, so a damaged snapshot leaves the city untouched
+    pub fn restore(&self, snapshot: &Snapshot) -> io::Result<()> {
+        self.restore_into(snapshot, &self.city_folder)
+    }
+
+    pub fn restore_into(&self, snapshot: &Snapshot, folder: &Path) -> io::Result<()> {
+        for file in &snapshot.files {
+            for &chunk in &file.chunks {
+                self.read_chunk(chunk)?;
+            }
+        }
+
+        for file in &snapshot.files {
+            let path = folder.join(&file.path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            let mut city_file = File::create(path)?;
+            for &chunk in &file.chunks {
+                city_file.write_all(&self.read_chunk(chunk)?)?;
+            }
+            city_file.sync_all()?;
+        }
+
+        Ok(())
+    }
+
+    // Files in the folder that aren't exactly like in the snapshot, or aren't in it at all
+    pub fn differing_files(&self, snapshot: &Snapshot, folder: &Path) -> io::Result<Vec<String>> {
+        let mut differing = Vec::new();
+
+        for file in &snapshot.files {
+            let same = match fs::read(folder.join(&file.path)) {
+                Ok(contents) => {
+                    contents.len() as u64 == file.len
+                        && contents
+                            .chunks(CHUNK_SIZE)
+                            .map(hash)
+                            .eq(file.chunks.iter().cloned())
+                }
+                Err(ref error) if error.kind() == io::ErrorKind::NotFound => false,
+                Err(error) => return Err(error),
+            };
+            if !same {
+                differing.push(file.path.clone());
+            }
+        }
+
+        let folder_store = SnapshotStore::new(folder);
+        for path in folder_store.city_files()? {
+            if !snapshot.files.iter().any(|file| file.path == path) {
+                differing.push(path);
+            }
+        }
+
+        Ok(differing)
+    }
+
+    // If the city was left truncated, puts back the most recent snapshot
+    // that is still intact. Returns the number of the restored snapshot
+    pub fn recover_if_truncated(&self) -> io::Result<Option<usize>> {
+        let snapshots = self.snapshots();
+        let latest = match snapshots.last() {
+            Some(latest) => latest,
+            None => return Ok(None),
+        };
+
+        let truncated = self.truncated_files(latest);
+        if truncated.is_empty() {
+            return Ok(None);
+        }
+        println!(
+            "Savegame is incomplete, {} files are missing or truncated.",
+            truncated.len()
+        );
+
+        self.restore_latest_intact(&snapshots).map(Some)
+    }
+
+    // Puts back the most recent snapshot that is still intact, no matter the state of the city.
+    // Returns the number of the restored snapshot
+    pub fn recover_latest(&self) -> io::Result<Option<usize>> {
+        let snapshots = self.snapshots();
+        if snapshots.is_empty() {
+            return Ok(None);
+        }
+
+        self.restore_latest_intact(&snapshots).map(Some)
+    }
+
+    fn restore_latest_intact(&self, snapshots: &[Snapshot]) -> io::Result<usize> {
+        for snapshot in snapshots.iter().rev() {
+            match self.restore(snapshot) {
+                Ok(()) => return Ok(snapshot.number),
+                Err(error) => println!(
+                    "Couldn't restore snapshot {}: {}, trying an older one.",
+                    snapshot.number, error
+                ),
+            }
+        }
+
+        Err(invalid_data(
+            "No intact snapshot left to recover from".to_owned(),
+        ))
+    }
+}
+
+fn report(result: io::Result<SnapshotStats>) {
+    match result {
+        Ok(stats) => println!(
+            "Autosaved snapshot {}, {} of {} chunks changed ({} KB).",
+            stats.number,
+            stats.n_new_chunks,
+            stats.n_chunks,
+            stats.new_bytes / 1024
+        ),
+        Err(error) => println!("Autosave failed: {}", error),
+    }
+}
+
+pub fn autosave(store: &SnapshotStore) {
+    report(store.take());
+}
+
+// Nothing is restored without asking, the city might be fine after all
+pub fn offer_recovery(store: &SnapshotStore) {
+    if let Some(latest) = store.snapshots().last() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or(0);
+        println!(
+            "Citybound wasn't stopped properly last time. If the city seems broken, \
+             restart with --recover-autosave to go back to the autosave from {} minutes ago.",
+            now.saturating_sub(latest.taken_at) / 60
+        );
+    }
+}
+
+// Autosaves while the simulation keeps going. Only one autosave is read and written
+// at a time, the capture comes back from it to be filled again by the next one
+pub struct BackgroundAutosave {
+    store: SnapshotStore,
+    spare_capture: Capture,
+    in_progress: Option<Receiver<Capture>>,
+}
+
+impl BackgroundAutosave {
+    pub fn new(store: SnapshotStore) -> BackgroundAutosave {
+        BackgroundAutosave {
+            store,
+            spare_capture: Capture::default(),
+            in_progress: None,
+        }
+    }
+
+    // Has to be called between turns of the simulation, when its files are consistent
+    pub fn start(&mut self) {
+        if let Some(in_progress) = self.in_progress.take() {
+            match in_progress.try_recv() {
+                Ok(capture) => self.spare_capture = capture,
+                Err(TryRecvError::Empty) => {
+                    println!("Previous autosave is still being written, skipping this one.");
+                    self.in_progress = Some(in_progress);
+                    return;
+                }
+                // the autosave thread panicked, its error was printed already
+                Err(TryRecvError::Disconnected) => {}
+            }
+        }
+
+        let mut capture = ::std::mem::replace(&mut self.spare_capture, Capture::default());
+        match self.store.checkpoint(&mut capture) {
+            Ok(checkpoint) => self.write_in_background(capture, move |capture: &mut Capture| {
+                checkpoint.read_into(capture)
+            }),
+            Err(error) => {
+                println!("Autosave failed: {}", error);
+                self.spare_capture = capture;
+            }
+        }
+    }
+
+    fn write_in_background<R>(&mut self, mut capture: Capture, read: R)
+    where
+        R: FnOnce(&mut Capture) -> io::Result<()> + Send + 'static,
+    {
+        let store = self.store.clone();
+        let (done, in_progress) = mpsc::channel();
+        thread::spawn(move || {
+            report(read(&mut capture).and_then(|()| store.store(&capture)));
+            // nobody might be waiting anymore, which is fine
+            let _ = done.send(capture);
+        });
+        self.in_progress = Some(in_progress);
+    }
+
+    // Waits for the autosave that is being written, if any
+    pub fn finish(&mut self) {
+        if let Some(in_progress) = self.in_progress.take() {
+            if let Ok(capture) = in_progress.recv() {
+                self.spare_capture = capture;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::RecvTimeoutError;
+    use std::time::Duration;
+
+    fn city_folder(name: &str) -> PathBuf {
+        let folder = ::std::env::temp_dir().join(format!(
+            "cb_snapshots_{}_{}",
+            name,
+            ::std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&folder);
+        fs::create_dir_all(folder.join("actors")).unwrap();
+        fs::write(folder.join("actors/households"), b"before").unwrap();
+        folder
+    }
+
+    #[test]
+    fn checkpoint_keeps_files_as_they_were_when_taken() {
+        let folder = city_folder("checkpoint");
+        let store = SnapshotStore::new(&folder);
+
+        let mut capture = Capture::default();
+        let checkpoint = store.checkpoint(&mut capture).unwrap();
+        fs::write(folder.join("actors/households"), b"after").unwrap();
+        checkpoint.read_into(&mut capture).unwrap();
+
+        assert_eq!(
+            capture.files,
+            vec![("actors/households".to_owned(), b"before".to_vec())]
+        );
+        assert!(!folder.join(SNAPSHOT_FOLDER).join(CHECKPOINT_FOLDER).exists());
+        fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn simulation_keeps_going_while_the_checkpoint_is_read() {
+        let folder = city_folder("background");
+        let store = SnapshotStore::new(&folder);
+        let mut autosave = BackgroundAutosave::new(store.clone());
+
+        let mut capture = Capture::default();
+        let checkpoint = store.checkpoint(&mut capture).unwrap();
+        let (release, released) = mpsc::channel::<()>();
+        autosave.write_in_background(capture, move |capture: &mut Capture| {
+            released.recv().unwrap();
+            checkpoint.read_into(capture)
+        });
+
+        // back on the simulation thread before anything was read
+        match autosave.in_progress.as_ref().unwrap().recv_timeout(Duration::from_millis(50)) {
+            Err(RecvTimeoutError::Timeout) => {}
+            _ => panic!("Autosave finished before its checkpoint was read"),
+        }
+        assert!(store.snapshots().is_empty());
+
+        release.send(()).unwrap();
+        autosave.finish();
+        assert_eq!(store.snapshots().len(), 1);
+        fs::remove_dir_all(&folder).unwrap();
+    }
+}
@@ -0,0 +1,129 @@
+// Lets a scripted bot play a new city for a number of simulated days, as fast
+// as the machine allows and without any clients, for automated long-run
+// playtests. The city's statistics are reported after every simulated day.
+//
+// The run fails if the simulation logged any errors, or if nobody lives in
+// the city at the end, which means that planning, the economy or traffic
+// broke somewhere along the way.
+
+use std::fs;
+use std::io;
+use cb_simulation::kay::{ActorSystem, Networking, Tuning, TypedID};
+use cb_simulation::cb_time::units::TICKS_PER_SIM_SECOND;
+use cb_simulation::bots::{self, Strategy};
+use cb_simulation::economy::difficulty::DifficultyProfile;
+use cb_simulation::planning::CBPlanManagerID;
+use cb_simulation::observation::{self, ApiObserverID};
+use cb_simulation::citybound_api::{Answer, CityStats, EventLevel, Query};
+
+const SOAK_FOLDER: &str = "cb_soak";
+// the run never connects to anything, but needs an address of its own
+const SOAK_ADDRESS: &str = "localhost:9996";
+const TURNS_PER_DAY: usize = 24 * 60 * 60 * TICKS_PER_SIM_SECOND as usize;
+const MAX_EVENTS_PER_DAY: u32 = 10_000;
+
+fn ask(system: &mut ActorSystem, query: Query) -> Option<Answer> {
+    let world = &mut system.world();
+    observation::ask(0, query, world);
+    system.process_all_messages();
+    ApiObserverID::global_first(world).flush(world);
+    system.process_all_messages();
+    observation::take_answers()
+        .into_iter()
+        .next()
+        .map(|(_, answer)| answer)
+}
+
+fn new_system() -> io::Result<Box<ActorSystem>> {
+    let folder = ::std::env::temp_dir().join(SOAK_FOLDER);
+    if folder.exists() {
+        fs::remove_dir_all(&folder)?;
+    }
+    fs::create_dir_all(&folder)?;
+
+    Ok(Box::new(ActorSystem::new_mmap_persisted(
+        Networking::new(0, vec![SOAK_ADDRESS.to_owned()], 5000, 2, 5),
+        &folder.to_string_lossy(),
+        Tuning::default(),
+    )))
+}
+
+// Returns whether the city made it through all days
+pub fn run(n_days: usize, strategy: Strategy, difficulty: DifficultyProfile) -> bool {
+    println!(
+        "Letting a {:?} bot play a new city for {} days...",
+        strategy, n_days
+    );
+
+    let mut system = match new_system() {
+        Ok(system) => system,
+        Err(error) => {
+            println!("Couldn't set up the soak run: {}", error);
+            return false;
+        }
+    };
+    ::cb_simulation::setup_common(&mut system);
+    system.networking_connect();
+
+    let time = {
+        let world = &mut system.world();
+        let time = ::cb_simulation::spawn_for_server(world, difficulty);
+        bots::spawn(world, time, CBPlanManagerID::global_first(world), strategy);
+        time
+    };
+    system.process_all_messages();
+
+    let mut n_errors = 0;
+    let mut next_event = 0;
+    let mut latest_stats = CityStats::default();
+
+    for day in 1..=n_days {
+        for _ in 0..TURNS_PER_DAY {
+            let world = &mut system.world();
+            time.progress(world);
+            system.process_all_messages();
+        }
+
+        if let Some(Answer::Events(events)) = ask(
+            &mut system,
+            Query::Events {
+                from: next_event,
+                max: MAX_EVENTS_PER_DAY,
+            },
+        ) {
+            for event in events {
+                if event.level == EventLevel::Error {
+                    println!("  Error in {}: {}", event.topic, event.message);
+                    n_errors += 1;
+                }
+                next_event = event.number + 1;
+            }
+        }
+
+        if let Some(Answer::Stats(stats)) = ask(&mut system, Query::Stats) {
+            latest_stats = stats;
+        }
+
+        println!(
+            "Day {}: {} inhabitants, {} jobs, {} buildings, {:.0}m of roads, \
+             commutes of {:.1} minutes",
+            day,
+            latest_stats.population,
+            latest_stats.n_jobs,
+            latest_stats.n_buildings,
+            latest_stats.network_length,
+            latest_stats.average_commute_minutes
+        );
+    }
+
+    if n_errors > 0 {
+        println!("The simulation logged {} errors.", n_errors);
+        false
+    } else if latest_stats.population == 0 {
+        println!("Nobody lives in the city after {} days.", n_days);
+        false
+    } else {
+        println!("The city made it through {} days.", n_days);
+        true
+    }
+}
@@ -0,0 +1,85 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for Bot {
+    type ID = BotID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BotID {
+    _raw_id: RawID
+}
+
+impl Copy for BotID {}
+impl Clone for BotID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BotID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BotID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BotID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BotID {
+    fn eq(&self, other: &BotID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BotID {}
+
+impl TypedID for BotID {
+    type Target = Bot;
+
+    fn from_raw(id: RawID) -> Self {
+        BotID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BotID {
+    pub fn spawn(strategy: Strategy, plan_manager: CBPlanManagerID, time: TimeID, world: &mut World) -> Self {
+        let id = BotID::from_raw(world.allocate_instance_id::<Bot>());
+        let swarm = world.local_broadcast::<Bot>();
+        world.send(swarm, MSG_Bot_spawn(id, strategy, plan_manager, time));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Bot_spawn(pub BotID, pub Strategy, pub CBPlanManagerID, pub TimeID);
+
+impl Into<SleeperID> for BotID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    SleeperID::register_implementor::<Bot>(system);
+    system.add_spawner::<Bot, _, _>(
+        |&MSG_Bot_spawn(id, strategy, plan_manager, time), world| {
+            Bot::spawn(id, strategy, plan_manager, time, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,326 @@
+// Bots play the city like a mayor would, for automated playtests. They follow
+// a scripted strategy and only use the messages the planning UI of a player
+// sends, so a long run with them exercises planning, the economy and traffic
+// together. The server can start one in a new city, or run a headless soak
+// test with one, see `--bot` and `--soak`
+use kay::{ActorSystem, World, TypedID};
+use compact::CVec;
+use descartes::P2;
+use cb_time::actors::{TimeID, Sleeper, SleeperID};
+use cb_time::units::{Instant, Duration, Ticks};
+use cb_util::log::info;
+use cb_util::session::server_origin;
+use cb_planning::GestureID;
+use cb_planning::plan_manager::ProjectID;
+use planning::{CBPlanManagerID, CBGestureIntent};
+use transport::transport_planning::{RoadIntent, RoadClass};
+use land_use::zone_planning::{ZoneIntent, LandUse};
+use economy::resources::{Resource, ResourceAmount};
+use economy::policies::{SubsidyID, TreasuryID};
+const LOG_T: &str = "Bots";
+
+// between the middle of a road and the zones next to it
+const ROAD_CLEARANCE: f32 = 10.0;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Strategy {
+    // A growing grid of small streets with mixed blocks
+    Grid,
+    // One long arterial road, with dense zones along both sides
+    Corridor,
+}
+
+impl Strategy {
+    pub fn from_name(name: &str) -> Option<Strategy> {
+        match name {
+            "grid" => Some(Strategy::Grid),
+            "corridor" => Some(Strategy::Corridor),
+            _ => None,
+        }
+    }
+
+    pub fn script(self) -> Vec<ScriptedAction> {
+        match self {
+            Strategy::Grid => grid_script(),
+            Strategy::Corridor => corridor_script(),
+        }
+    }
+}
+
+#[derive(Compact, Clone)]
+pub enum BotAction {
+    Road {
+        points: CVec<P2>,
+        n_lanes: u8,
+    },
+    Zone {
+        corners: CVec<P2>,
+        land_use: LandUse,
+    },
+    Subsidize {
+        resource: Resource,
+        per_unit: ResourceAmount,
+    },
+}
+
+#[derive(Compact, Clone)]
+pub struct ScriptedAction {
+    // since the bot started playing
+    after: Duration,
+    action: BotAction,
+}
+
+#[derive(Compact, Clone)]
+pub struct Bot {
+    id: BotID,
+    strategy: Strategy,
+    plan_manager: CBPlanManagerID,
+    time: TimeID,
+    script: CVec<ScriptedAction>,
+    n_done: u32,
+    started: Option<Instant>,
+}
+
+impl Bot {
+    pub fn spawn(
+        id: BotID,
+        strategy: Strategy,
+        plan_manager: CBPlanManagerID,
+        time: TimeID,
+        world: &mut World,
+    ) -> Bot {
+        time.wake_up_in(Ticks(0), id.into(), world);
+
+        Bot {
+            id,
+            strategy,
+            plan_manager,
+            time,
+            script: strategy.script().into(),
+            n_done: 0,
+            started: None,
+        }
+    }
+
+    // Plans and implements it as one project, like a player would draw it.
+    // Bots play as the server, which is always allowed to plan
+    fn perform(&self, action: &BotAction, world: &mut World) {
+        let origin = server_origin();
+
+        let (intent, points) = match *action {
+            BotAction::Road {
+                ref points,
+                n_lanes,
+            } => (
+                CBGestureIntent::Road(RoadIntent::new(
+                    n_lanes,
+                    n_lanes,
+                    RoadClass::for_lanes(n_lanes),
+                )),
+                points,
+            ),
+            BotAction::Zone {
+                ref corners,
+                land_use,
+            } => (
+                CBGestureIntent::Zone(ZoneIntent::LandUse(land_use)),
+                corners,
+            ),
+            BotAction::Subsidize { resource, per_unit } => {
+                SubsidyID::spawn(resource, per_unit, TreasuryID::global_first(world), world);
+                info(
+                    LOG_T,
+                    format!("Subsidizing {} with {} per unit", resource, per_unit),
+                    self.id,
+                    world,
+                );
+                return;
+            }
+        };
+
+        let project = ProjectID::new();
+        let gesture = GestureID::new();
+        self.plan_manager.start_new_project(origin, project, world);
+        self.plan_manager
+            .start_new_gesture(origin, project, gesture, intent, points[0], world);
+        for &point in points.iter().skip(1) {
+            self.plan_manager
+                .add_control_point(origin, project, gesture, point, true, true, world);
+        }
+        self.plan_manager.implement(origin, project, world);
+    }
+}
+
+impl Sleeper for Bot {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        let started = *self.started.get_or_insert(current_instant);
+
+        while let Some(next) = self.script.get(self.n_done as usize).cloned() {
+            let due = started + next.after;
+            if due > current_instant {
+                self.time.wake_up_in(
+                    Ticks(due.ticks() as u32 - current_instant.ticks() as u32),
+                    self.id_as(),
+                    world,
+                );
+                return;
+            }
+
+            self.perform(&next.action, world);
+            self.n_done += 1;
+        }
+
+        info(
+            LOG_T,
+            format!("Done playing the {:?} strategy", self.strategy),
+            self.id,
+            world,
+        );
+    }
+}
+
+fn road(from: (f32, f32), to: (f32, f32), n_lanes: u8) -> BotAction {
+    BotAction::Road {
+        points: vec![P2::new(from.0, from.1), P2::new(to.0, to.1)].into(),
+        n_lanes,
+    }
+}
+
+// The area between the given road axes, leaving room for the roads
+fn block(min: (f32, f32), max: (f32, f32), land_use: LandUse) -> BotAction {
+    let (min_x, min_y) = (min.0 + ROAD_CLEARANCE, min.1 + ROAD_CLEARANCE);
+    let (max_x, max_y) = (max.0 - ROAD_CLEARANCE, max.1 - ROAD_CLEARANCE);
+
+    BotAction::Zone {
+        corners: vec![
+            P2::new(min_x, min_y),
+            P2::new(max_x, min_y),
+            P2::new(max_x, max_y),
+            P2::new(min_x, max_y),
+        ]
+        .into(),
+        land_use,
+    }
+}
+
+fn at(after: Duration, action: BotAction) -> ScriptedAction {
+    ScriptedAction { after, action }
+}
+
+const GRID_SPACING: f32 = 150.0;
+const GRID_N_ROWS: isize = 6;
+
+// Adds a column of blocks every day, growing the grid eastwards. Most blocks
+// are homes, with shops, parks, and later industry and farms mixed in
+fn grid_script() -> Vec<ScriptedAction> {
+    let mut script = Vec::new();
+    let half_height = GRID_N_ROWS as f32 / 2.0 * GRID_SPACING;
+
+    script.push(at(
+        Duration(0),
+        road((0.0, -half_height), (0.0, half_height), 1),
+    ));
+
+    for column in 0..8 {
+        let after = Duration::from_hours(24 * column);
+        let (west, east) = (
+            column as f32 * GRID_SPACING,
+            (column + 1) as f32 * GRID_SPACING,
+        );
+
+        script.push(at(
+            after,
+            road((east, -half_height), (east, half_height), 1),
+        ));
+
+        for row in 0..=GRID_N_ROWS {
+            let y = -half_height + row as f32 * GRID_SPACING;
+            script.push(at(after, road((west, y), (east, y), 1)));
+
+            if row < GRID_N_ROWS {
+                let land_use = match (column as isize + row) % 7 {
+                    0 => LandUse::Commercial,
+                    3 if column > 1 => LandUse::Industrial,
+                    5 if column > 3 => LandUse::Agricultural,
+                    6 => LandUse::Recreational,
+                    _ => LandUse::Residential,
+                };
+                // zones follow their roads, which have to be planned first
+                script.push(at(
+                    after + Duration::from_hours(1),
+                    block((west, y), (east, y + GRID_SPACING), land_use),
+                ));
+            }
+        }
+    }
+
+    script.push(at(
+        Duration::from_hours(24),
+        BotAction::Subsidize {
+            resource: Resource::Groceries,
+            per_unit: amount!(0.5),
+        },
+    ));
+
+    script.sort_by_key(|scripted| scripted.after);
+    script
+}
+
+const CORRIDOR_SECTION: f32 = 400.0;
+const CORRIDOR_DEPTH: f32 = 120.0;
+
+// Extends a wide road by one section in both directions every day and
+// zones along it, homes on one side, work on the other
+fn corridor_script() -> Vec<ScriptedAction> {
+    let mut script = Vec::new();
+
+    for day in 0..8 {
+        let after = Duration::from_hours(24 * day);
+        let (near, far) = (
+            day as f32 * CORRIDOR_SECTION,
+            (day + 1) as f32 * CORRIDOR_SECTION,
+        );
+
+        for &(from, to) in &[(near, far), (-near, -far)] {
+            script.push(at(after, road((from, 0.0), (to, 0.0), 2)));
+
+            let (min_x, max_x) = (from.min(to), from.max(to));
+            let work = if day % 3 == 2 {
+                LandUse::Industrial
+            } else {
+                LandUse::Commercial
+            };
+            script.push(at(
+                after + Duration::from_hours(1),
+                block((min_x, 0.0), (max_x, CORRIDOR_DEPTH), LandUse::Residential),
+            ));
+            script.push(at(
+                after + Duration::from_hours(1),
+                block((min_x, -CORRIDOR_DEPTH), (max_x, 0.0), work),
+            ));
+        }
+    }
+
+    script.push(at(
+        Duration::from_hours(72),
+        BotAction::Subsidize {
+            resource: Resource::Fuel,
+            per_unit: amount!(0.2),
+        },
+    ));
+
+    script.sort_by_key(|scripted| scripted.after);
+    script
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Bot>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID, plan_manager: CBPlanManagerID, strategy: Strategy) {
+    BotID::spawn(strategy, plan_manager, time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
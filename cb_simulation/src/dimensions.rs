@@ -15,3 +15,18 @@ pub const LANE_CONNECTION_TOLERANCE: N = 0.1;
 pub const MAX_SWITCHING_LANE_DISTANCE: N = 0.6 * LANE_DISTANCE;
 pub const MIN_SWITCHING_LANE_LENGTH: N = 6.0;
 pub const SWITCHING_LANE_OVERLAP_TOLERANCE: N = 0.3;
+
+pub const SIDEWALK_WIDTH: N = 2.0;
+// from the outer edge of the outermost lane's share of the road
+pub const SIDEWALK_OFFSET: N = 0.75 * LANE_DISTANCE;
+pub const BIKE_LANE_WIDTH: N = 1.5;
+// from the outer edge of the outermost lane's share of the road,
+// where there is a bike lane, the sidewalk is further out by its width
+pub const BIKE_LANE_OFFSET: N = 0.3 * LANE_DISTANCE;
+pub const CROSSWALK_WIDTH: N = 3.0;
+pub const CROSSWALK_STRIPE_WIDTH: N = 0.6;
+
+// between the levels roads can be built on
+pub const LEVEL_HEIGHT: N = 6.0;
+// bridges and tunnels reach their full height this far from the ends of their road
+pub const RAMP_LENGTH: N = 60.0;
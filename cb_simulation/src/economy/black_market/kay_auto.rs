@@ -0,0 +1,99 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BlackMarket {
+    type ID = BlackMarketID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BlackMarketID {
+    _raw_id: RawID
+}
+
+impl Copy for BlackMarketID {}
+impl Clone for BlackMarketID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BlackMarketID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BlackMarketID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BlackMarketID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BlackMarketID {
+    fn eq(&self, other: &BlackMarketID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BlackMarketID {}
+
+impl TypedID for BlackMarketID {
+    type Target = BlackMarket;
+
+    fn from_raw(id: RawID) -> Self {
+        BlackMarketID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BlackMarketID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = BlackMarketID::from_raw(world.allocate_instance_id::<BlackMarket>());
+        let swarm = world.local_broadcast::<BlackMarket>();
+        world.send(swarm, MSG_BlackMarket_spawn(id, time));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BlackMarket_spawn(pub BlackMarketID, pub TimeID);
+
+impl Into<HotspotRequesterID> for BlackMarketID {
+    fn into(self) -> HotspotRequesterID {
+        HotspotRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SupplyReportRequesterID> for BlackMarketID {
+    fn into(self) -> SupplyReportRequesterID {
+        SupplyReportRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for BlackMarketID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HotspotRequesterID::register_implementor::<BlackMarket>(system);
+    SupplyReportRequesterID::register_implementor::<BlackMarket>(system);
+    SleeperID::register_implementor::<BlackMarket>(system);
+    system.add_spawner::<BlackMarket, _, _>(
+        |&MSG_BlackMarket_spawn(id, time), world| {
+            BlackMarket::spawn(id, time, world)
+        }, false
+    );
+}
\ No newline at end of file
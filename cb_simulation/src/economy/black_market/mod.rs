@@ -0,0 +1,169 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use compact::CVec;
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Duration};
+use cb_util::log::{info, warn};
+use transport::pathfinding::RoughLocationID;
+use super::resources::{Resource, ResourceAmount, ResourceMap};
+use super::resources::Resource::*;
+use super::market::{MarketID, SupplyReportRequester, SupplyReportRequesterID};
+use super::households::HouseholdID;
+use super::households::household_kinds::informal_dealer::InformalDealerID;
+use super::unmet_demand::{UnmetDemandID, HotspotRequester, HotspotRequesterID};
+const LOG_T: &str = "Black Market";
+
+const CHECK_INTERVAL: Duration = Duration(60 * 60);
+// how long a resource needs to be completely unavailable before informal
+// dealers start filling the gap
+const SUSTAINED_SHORTAGE: Duration = Duration(24 * 60 * 60);
+
+// the resources households can't do without, with the amount and price
+// of a typical formal retail deal
+const ESSENTIALS: &[(Resource, ResourceAmount, f32)] = &[(Groceries, amount!(30.0), 2.7)];
+
+#[derive(Compact, Clone)]
+pub struct BlackMarket {
+    id: BlackMarketID,
+    time: TimeID,
+    current_instant: Instant,
+    awaited_reports: u32,
+    n_offers: ResourceMap<u32>,
+    unavailable_since: ResourceMap<Instant>,
+    dealers: CVec<(Resource, InformalDealerID)>,
+}
+
+impl BlackMarket {
+    pub fn spawn(id: BlackMarketID, time: TimeID, world: &mut World) -> BlackMarket {
+        time.wake_up_every(CHECK_INTERVAL, id.into(), world);
+
+        BlackMarket {
+            id,
+            time,
+            current_instant: Instant::new(0),
+            awaited_reports: 0,
+            n_offers: ResourceMap::new(),
+            unavailable_since: ResourceMap::new(),
+            dealers: CVec::new(),
+        }
+    }
+
+    fn dealer_for(&self, resource: Resource) -> Option<InformalDealerID> {
+        self.dealers
+            .iter()
+            .find(|&&(dealer_resource, _)| dealer_resource == resource)
+            .map(|&(_, dealer)| dealer)
+    }
+
+    fn update_shortages(&mut self, world: &mut World) {
+        for &(resource, _, _) in ESSENTIALS {
+            let maybe_dealer = self.dealer_for(resource);
+            let n_informal_offers = if maybe_dealer.is_some() { 1 } else { 0 };
+            let n_formal_offers = self
+                .n_offers
+                .get(resource)
+                .cloned()
+                .unwrap_or(0)
+                .saturating_sub(n_informal_offers);
+
+            if n_formal_offers > 0 {
+                self.unavailable_since.remove(resource);
+
+                if let Some(dealer) = maybe_dealer {
+                    info(
+                        LOG_T,
+                        format!(
+                            "Formal supply of {} is back, informal dealer retires",
+                            resource
+                        ),
+                        self.id,
+                        world,
+                    );
+                    let dealer_as_household: HouseholdID = dealer.into();
+                    dealer_as_household.destroy(world);
+                    self.dealers.retain(|&(_, d)| d != dealer);
+                    UnmetDemandID::global_first(world).shortage_resolved(resource, world);
+                }
+            } else if maybe_dealer.is_none() {
+                let since = *self
+                    .unavailable_since
+                    .mut_entry_or(resource, self.current_instant);
+
+                if self.current_instant >= since + SUSTAINED_SHORTAGE {
+                    UnmetDemandID::global_first(world).find_hotspot(resource, self.id_as(), world);
+                }
+            }
+        }
+    }
+}
+
+impl HotspotRequester for BlackMarket {
+    fn on_hotspot(&mut self, resource: Resource, location: RoughLocationID, world: &mut World) {
+        if self.dealer_for(resource).is_some() || self.unavailable_since.get(resource).is_none() {
+            return;
+        }
+
+        if let Some(&(_, formal_amount, formal_price)) = ESSENTIALS
+            .iter()
+            .find(|&&(essential, _, _)| essential == resource)
+        {
+            warn(
+                LOG_T,
+                format!(
+                    "Nobody has been offering {} for a while, an informal dealer steps in",
+                    resource
+                ),
+                self.id,
+                world,
+            );
+            let dealer = InformalDealerID::spawn(
+                location,
+                resource,
+                formal_amount,
+                formal_price,
+                self.time,
+                world,
+            );
+            self.dealers.push((resource, dealer));
+            UnmetDemandID::global_first(world).record_shortage(resource, world);
+        }
+    }
+}
+
+impl SupplyReportRequester for BlackMarket {
+    fn expect_supply_reports(&mut self, n: u32, _: &mut World) {
+        self.awaited_reports = n;
+        self.n_offers = ResourceMap::new();
+    }
+
+    fn on_supply_report(&mut self, n_offers: &ResourceMap<u32>, world: &mut World) {
+        for &(resource, _, _) in ESSENTIALS {
+            if let Some(&n) = n_offers.get(resource) {
+                *self.n_offers.mut_entry_or(resource, 0) += n;
+            }
+        }
+
+        self.awaited_reports = self.awaited_reports.saturating_sub(1);
+        if self.awaited_reports == 0 {
+            self.update_shortages(world);
+        }
+    }
+}
+
+impl Sleeper for BlackMarket {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        self.current_instant = current_instant;
+        MarketID::global_first(world).report_supply(self.id.into(), world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BlackMarket>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    BlackMarketID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
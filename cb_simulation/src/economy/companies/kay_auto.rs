@@ -0,0 +1,154 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for Company {
+    type ID = CompanyID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct CompanyID {
+    _raw_id: RawID
+}
+
+impl Copy for CompanyID {}
+impl Clone for CompanyID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for CompanyID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "CompanyID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for CompanyID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for CompanyID {
+    fn eq(&self, other: &CompanyID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for CompanyID {}
+
+impl TypedID for CompanyID {
+    type Target = Company;
+
+    fn from_raw(id: RawID) -> Self {
+        CompanyID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl CompanyID {
+    pub fn found(first_branch: GroceryShopID, time: TimeID, world: &mut World) -> Self {
+        let id = CompanyID::from_raw(world.allocate_instance_id::<Company>());
+        let swarm = world.local_broadcast::<Company>();
+        world.send(swarm, MSG_Company_found(id, first_branch, time));
+        id
+    }
+    
+    pub fn deposit(self, amount: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_Company_deposit(amount));
+    }
+    
+    pub fn request_funds(self, branch: HouseholdID, amount: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_Company_request_funds(branch, amount));
+    }
+    
+    pub fn on_stock_report(self, branch: GroceryShopID, stock: Inventory, world: &mut World) {
+        world.send(self.as_raw(), MSG_Company_on_stock_report(branch, stock));
+    }
+    
+    pub fn branch_closed(self, branch: GroceryShopID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Company_branch_closed(branch));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Company_found(pub CompanyID, pub GroceryShopID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Company_deposit(pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Company_request_funds(pub HouseholdID, pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Company_on_stock_report(pub GroceryShopID, pub Inventory);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Company_branch_closed(pub GroceryShopID);
+
+impl Into<HotspotRequesterID> for CompanyID {
+    fn into(self) -> HotspotRequesterID {
+        HotspotRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<UnitRequesterID> for CompanyID {
+    fn into(self) -> UnitRequesterID {
+        UnitRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<PositionRequesterID> for CompanyID {
+    fn into(self) -> PositionRequesterID {
+        PositionRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for CompanyID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HotspotRequesterID::register_implementor::<Company>(system);
+    UnitRequesterID::register_implementor::<Company>(system);
+    PositionRequesterID::register_implementor::<Company>(system);
+    SleeperID::register_implementor::<Company>(system);
+    system.add_spawner::<Company, _, _>(
+        |&MSG_Company_found(id, first_branch, time), world| {
+            Company::found(id, first_branch, time, world)
+        }, false
+    );
+    
+    system.add_handler::<Company, _, _>(
+        |&MSG_Company_deposit(amount), instance, world| {
+            instance.deposit(amount, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Company, _, _>(
+        |&MSG_Company_request_funds(branch, amount), instance, world| {
+            instance.request_funds(branch, amount, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Company, _, _>(
+        |&MSG_Company_on_stock_report(branch, ref stock), instance, world| {
+            instance.on_stock_report(branch, stock, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Company, _, _>(
+        |&MSG_Company_branch_closed(branch), instance, world| {
+            instance.branch_closed(branch, world)
+        }, false
+    );
+}
\ No newline at end of file
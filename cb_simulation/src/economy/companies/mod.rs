@@ -0,0 +1,246 @@
+use kay::{ActorSystem, World, Actor, TypedID, Fate};
+use compact::{CVec, CHashMap};
+use descartes::P2;
+use ordered_float::OrderedFloat;
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Duration};
+use cb_util::log::info;
+use land_use::buildings::{BuildingID, BuildingStyle, UnitType, UnitIdx, UnitRequester,
+UnitRequesterID, business_site_cost};
+use transport::pathfinding::{RoughLocationID, PositionRequester, PositionRequesterID};
+use super::resources::{Resource, ResourceAmount, Inventory};
+use super::resources::Resource::*;
+use super::finance::BankID;
+use super::households::HouseholdID;
+use super::households::household_kinds::grocery_shop::GroceryShopID;
+use super::unmet_demand::{UnmetDemandID, HotspotRequester, HotspotRequesterID};
+use super::immigration_and_development::DevelopmentManagerID;
+const LOG_T: &str = "Companies";
+
+const STOCK_SHARING_INTERVAL: Duration = Duration(2 * 60 * 60);
+const EXPANSION_CHECK_INTERVAL: Duration = Duration(24 * 60 * 60);
+const BRANCH_OPENING_COST: ResourceAmount = amount!(2_000.0);
+// kept in the treasury to cover branches that run short, even after opening a branch
+const TREASURY_RESERVE: ResourceAmount = amount!(500.0);
+
+// wholesale goods that the purchasing branch buys for all branches
+pub const SHARED_STOCK: &[Resource] = &[Produce, Grain, Flour, BakedGoods, Meat, DairyGoods];
+
+// Runs several grocery shops as one business. Branches pay what they earn
+// into a shared treasury and get covered from it when they run short.
+// Only one of them buys wholesale goods, which are then shared out evenly
+// among all branches. Once the treasury is big enough, a new branch is
+// opened in the vacant shop closest to where groceries are lacking the most
+#[derive(Compact, Clone)]
+pub struct Company {
+    id: CompanyID,
+    time: TimeID,
+    // the first one is the purchasing branch
+    branches: CVec<GroceryShopID>,
+    treasury: ResourceAmount,
+    stock_reports: CVec<(GroceryShopID, Inventory)>,
+    next_expansion_check: Instant,
+    expanding_towards: Option<RoughLocationID>,
+    // with their land value
+    vacant_shops: CVec<(BuildingID, UnitIdx, f32)>,
+    positions: CHashMap<RoughLocationID, P2>,
+}
+
+impl Company {
+    pub fn found(
+        id: CompanyID,
+        first_branch: GroceryShopID,
+        time: TimeID,
+        world: &mut World,
+    ) -> Company {
+        time.wake_up_every(STOCK_SHARING_INTERVAL, id.into(), world);
+
+        Company {
+            id,
+            time,
+            branches: vec![first_branch].into(),
+            treasury: amount!(0.0),
+            stock_reports: CVec::new(),
+            next_expansion_check: Instant::new(0),
+            expanding_towards: None,
+            vacant_shops: CVec::new(),
+            positions: CHashMap::new(),
+        }
+    }
+
+    pub fn deposit(&mut self, amount: ResourceAmount, _: &mut World) {
+        self.treasury += amount;
+    }
+
+    // Pays out of the treasury first, the bank lends the branch what's missing
+    pub fn request_funds(
+        &mut self,
+        branch: HouseholdID,
+        amount: ResourceAmount,
+        world: &mut World,
+    ) {
+        let from_treasury = amount.min(self.treasury.max(0.0));
+        self.treasury -= from_treasury;
+
+        if from_treasury > 0.0 {
+            branch.receive_funds(from_treasury, world);
+        }
+        if from_treasury < amount {
+            BankID::global_first(world).request_funds(branch, amount - from_treasury, world);
+        }
+    }
+
+    pub fn on_stock_report(&mut self, branch: GroceryShopID, stock: &Inventory, world: &mut World) {
+        self.stock_reports
+            .retain(|&(reported, _)| reported != branch);
+        self.stock_reports.push((branch, stock.clone()));
+
+        if self.stock_reports.len() == self.branches.len() {
+            self.share_out_stock(world);
+        }
+    }
+
+    fn share_out_stock(&mut self, world: &mut World) {
+        let n_branches = self.stock_reports.len() as f32;
+        let mut transfers = vec![Inventory::new(); self.stock_reports.len()];
+
+        for &resource in SHARED_STOCK {
+            let amounts = self
+                .stock_reports
+                .iter()
+                .map(|&(_, ref stock)| stock.get(resource).cloned().unwrap_or(amount!(0.0)))
+                .collect::<Vec<_>>();
+            let even_share = amounts.iter().sum::<ResourceAmount>() / n_branches;
+
+            for (transfer, amount) in transfers.iter_mut().zip(amounts) {
+                transfer.insert(resource, even_share - amount);
+            }
+        }
+
+        for (&(branch, _), transfer) in self.stock_reports.iter().zip(transfers) {
+            branch.transfer_stock(transfer, world);
+        }
+
+        self.stock_reports.clear();
+    }
+
+    pub fn branch_closed(&mut self, branch: GroceryShopID, world: &mut World) -> Fate {
+        let was_purchasing = self.branches.first() == Some(&branch);
+        self.branches.retain(|&existing| existing != branch);
+        self.stock_reports
+            .retain(|&(reported, _)| reported != branch);
+
+        if let Some(&new_purchasing) = self.branches.first() {
+            if was_purchasing {
+                new_purchasing.set_purchasing(true, world);
+            }
+            Fate::Live
+        } else {
+            info(
+                LOG_T,
+                "Closed its last branch and went out of business",
+                self.id,
+                world,
+            );
+            self.time.stop_waking_up(self.id.into(), world);
+            Fate::Die
+        }
+    }
+
+    fn open_branch_near(&mut self, hotspot: RoughLocationID, world: &mut World) {
+        let maybe_site = self.positions.get(hotspot).and_then(|&hotspot_position| {
+            self.vacant_shops
+                .iter()
+                .filter_map(|&(building, unit, land_value)| {
+                    let building_location: RoughLocationID = building.into();
+                    self.positions.get(building_location).map(|&position| {
+                        let distance = (position - hotspot_position).norm();
+                        (building, unit, business_site_cost(distance, land_value))
+                    })
+                })
+                .min_by_key(|&(_, _, cost)| OrderedFloat(cost))
+                .map(|(building, unit, _)| (building, unit))
+        });
+
+        if let Some((building, unit)) = maybe_site {
+            info(
+                LOG_T,
+                format!("Opening branch #{}", self.branches.len() + 1),
+                self.id,
+                world,
+            );
+            self.treasury -= BRANCH_OPENING_COST;
+            let branch = GroceryShopID::open_branch(building, self.id, self.time, world);
+            building.add_household(branch.into(), unit, world);
+            self.branches.push(branch);
+        } else {
+            DevelopmentManagerID::global_first(world)
+                .try_develop(BuildingStyle::GroceryShop, world);
+        }
+
+        self.expanding_towards = None;
+        self.vacant_shops.clear();
+        self.positions = CHashMap::new();
+    }
+}
+
+impl HotspotRequester for Company {
+    fn on_hotspot(&mut self, _resource: Resource, location: RoughLocationID, world: &mut World) {
+        if self.expanding_towards.is_some() {
+            return;
+        }
+
+        self.expanding_towards = Some(location);
+        location.resolve_as_position(self.id_as(), location, world);
+        BuildingID::global_broadcast(world).try_offer_unit(UnitType::Retail, self.id_as(), world);
+    }
+}
+
+impl UnitRequester for Company {
+    fn on_unit_offer(
+        &mut self,
+        building: BuildingID,
+        unit: UnitIdx,
+        land_value: f32,
+        world: &mut World,
+    ) {
+        if self.expanding_towards.is_some() {
+            self.vacant_shops.push((building, unit, land_value));
+            let building_location: RoughLocationID = building.into();
+            building_location.resolve_as_position(self.id_as(), building_location, world);
+        }
+    }
+}
+
+impl PositionRequester for Company {
+    fn position_resolved(&mut self, rough_location: RoughLocationID, position: P2, _: &mut World) {
+        self.positions.insert(rough_location, position);
+    }
+}
+
+impl Sleeper for Company {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        // vacant shops had until now to answer
+        if let Some(hotspot) = self.expanding_towards {
+            self.open_branch_near(hotspot, world);
+        } else if current_instant >= self.next_expansion_check
+            && self.treasury >= BRANCH_OPENING_COST + TREASURY_RESERVE
+        {
+            self.next_expansion_check = current_instant + EXPANSION_CHECK_INTERVAL;
+            UnmetDemandID::global_first(world).find_hotspot(Groceries, self.id_as(), world);
+        }
+
+        self.stock_reports.clear();
+        for &branch in self.branches.iter() {
+            branch.report_stock(world);
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Company>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,117 @@
+use kay::{ActorSystem, World};
+use compact::CHashMap;
+use cb_util::config_manager::{Config, ConfigManager, ConfigManagerID, Name};
+use economy::resources::{ResourceAmount, to_amount};
+
+// How forgiving the economy and the city's mishaps are. Chosen when a new city is founded and kept
+// as a config entry from then on, so that it is saved with the city and
+// can still be adjusted while the simulation is running
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct DifficultyProfile {
+    // what the treasury holds when the city is founded
+    pub starting_money: ResourceAmount,
+    // how much more or less the neighboring towns charge for their goods
+    pub import_price_factor: f32,
+    // how much more or less readily families move into the city
+    pub demand_growth_factor: f32,
+    // how much more or less often buildings catch fire or water mains break
+    pub disaster_frequency_factor: f32,
+}
+
+impl Config for DifficultyProfile {}
+
+// the single entry all config users look up
+pub const CURRENT: &str = "Current";
+
+impl DifficultyProfile {
+    pub fn easy() -> DifficultyProfile {
+        DifficultyProfile {
+            starting_money: amount!(400_000.0),
+            import_price_factor: 0.8,
+            demand_growth_factor: 1.3,
+            disaster_frequency_factor: 0.5,
+        }
+    }
+
+    pub fn normal() -> DifficultyProfile {
+        DifficultyProfile {
+            starting_money: amount!(200_000.0),
+            import_price_factor: 1.0,
+            demand_growth_factor: 1.0,
+            disaster_frequency_factor: 1.0,
+        }
+    }
+
+    pub fn hard() -> DifficultyProfile {
+        DifficultyProfile {
+            starting_money: amount!(80_000.0),
+            import_price_factor: 1.3,
+            demand_growth_factor: 0.7,
+            disaster_frequency_factor: 1.5,
+        }
+    }
+
+    // "easy", "normal", "hard" or "custom:<starting money>,<import price factor>,
+    // <demand growth factor>[,<disaster frequency factor>]", disasters being as
+    // frequent as on normal if left out
+    pub fn from_name(name: &str) -> Option<DifficultyProfile> {
+        match name {
+            "easy" => Some(Self::easy()),
+            "normal" => Some(Self::normal()),
+            "hard" => Some(Self::hard()),
+            _ if name.starts_with("custom:") => {
+                let mut values = name["custom:".len()..]
+                    .split(',')
+                    .map(|value| value.trim().parse::<f32>());
+                match (
+                    values.next(),
+                    values.next(),
+                    values.next(),
+                    values.next().unwrap_or(Ok(1.0)),
+                    values.next(),
+                ) {
+                    (
+                        Some(Ok(starting_money)),
+                        Some(Ok(import)),
+                        Some(Ok(growth)),
+                        Ok(disasters),
+                        None,
+                    )
+                        if starting_money >= 0.0
+                            && import > 0.0
+                            && growth > 0.0
+                            && disasters >= 0.0 =>
+                    {
+                        Some(DifficultyProfile {
+                            starting_money: to_amount(starting_money),
+                            import_price_factor: import,
+                            demand_growth_factor: growth,
+                            disaster_frequency_factor: disasters,
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+// The profile in a config user's local cache, normal until it has arrived
+pub fn current(cache: &CHashMap<Name, DifficultyProfile>) -> DifficultyProfile {
+    cache
+        .get(Name::from(CURRENT).unwrap())
+        .cloned()
+        .unwrap_or_else(DifficultyProfile::normal)
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<ConfigManager<DifficultyProfile>>();
+    ::cb_util::config_manager::auto_setup::<DifficultyProfile>(system);
+}
+
+pub fn spawn(world: &mut World, profile: DifficultyProfile) {
+    let mut entries = CHashMap::new();
+    entries.insert(Name::from(CURRENT).unwrap(), profile);
+    ConfigManagerID::<DifficultyProfile>::spawn(entries, world);
+}
@@ -0,0 +1,113 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for Entrepreneur {
+    type ID = EntrepreneurID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct EntrepreneurID {
+    _raw_id: RawID
+}
+
+impl Copy for EntrepreneurID {}
+impl Clone for EntrepreneurID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for EntrepreneurID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "EntrepreneurID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for EntrepreneurID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for EntrepreneurID {
+    fn eq(&self, other: &EntrepreneurID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for EntrepreneurID {}
+
+impl TypedID for EntrepreneurID {
+    type Target = Entrepreneur;
+
+    fn from_raw(id: RawID) -> Self {
+        EntrepreneurID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl EntrepreneurID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = EntrepreneurID::from_raw(world.allocate_instance_id::<Entrepreneur>());
+        let swarm = world.local_broadcast::<Entrepreneur>();
+        world.send(swarm, MSG_Entrepreneur_spawn(id, time));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Entrepreneur_spawn(pub EntrepreneurID, pub TimeID);
+
+impl Into<HotspotRequesterID> for EntrepreneurID {
+    fn into(self) -> HotspotRequesterID {
+        HotspotRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TaxiDemandRequesterID> for EntrepreneurID {
+    fn into(self) -> TaxiDemandRequesterID {
+        TaxiDemandRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<UnitRequesterID> for EntrepreneurID {
+    fn into(self) -> UnitRequesterID {
+        UnitRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<PositionRequesterID> for EntrepreneurID {
+    fn into(self) -> PositionRequesterID {
+        PositionRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for EntrepreneurID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HotspotRequesterID::register_implementor::<Entrepreneur>(system);
+    TaxiDemandRequesterID::register_implementor::<Entrepreneur>(system);
+    UnitRequesterID::register_implementor::<Entrepreneur>(system);
+    PositionRequesterID::register_implementor::<Entrepreneur>(system);
+    SleeperID::register_implementor::<Entrepreneur>(system);
+    system.add_spawner::<Entrepreneur, _, _>(
+        |&MSG_Entrepreneur_spawn(id, time), world| {
+            Entrepreneur::spawn(id, time, world)
+        }, false
+    );
+}
\ No newline at end of file
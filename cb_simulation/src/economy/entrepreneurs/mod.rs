@@ -0,0 +1,272 @@
+use kay::{ActorSystem, World, Actor, TypedID};
+use compact::{CVec, CHashMap};
+use descartes::P2;
+use ordered_float::OrderedFloat;
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Duration};
+use cb_util::log::info;
+use land_use::buildings::{BuildingID, UnitIdx, UnitRequester, UnitRequesterID,
+business_site_cost};
+use transport::pathfinding::{RoughLocationID, PositionRequester, PositionRequesterID};
+use transport::taxis::{TaxiDispatcherID, TaxiDemandRequester, TaxiDemandRequesterID};
+use super::resources::{Resource, ResourceMap};
+use super::resources::Resource::*;
+use super::households::household_kinds::leisure_venue::LeisureKind;
+use super::utilities::Utility;
+use super::unmet_demand::{UnmetDemandID, HotspotRequester, HotspotRequesterID};
+use super::immigration_and_development::{self, HouseholdTypeToSpawn, DevelopmentManagerID,
+unit_type_for, building_style_for};
+const LOG_T: &str = "Entrepreneurs";
+
+const CHECK_INTERVAL: Duration = Duration(60 * 60);
+// how often a resource needs to have been lacking since the last check
+const MIN_RECENT_UNMET_DEMAND: u32 = 5;
+// how often nobody could be sent a taxi since the last check
+const MIN_UNSERVED_TAXI_CALLS: u32 = 10;
+// a new business needs a while until its customers find it
+const FOUNDING_COOLDOWN: Duration = Duration(24 * 60 * 60);
+
+// which kind of business fills a gap in which resource
+const BUSINESSES: &[(Resource, HouseholdTypeToSpawn)] = &[
+    (Groceries, HouseholdTypeToSpawn::GroceryShop),
+    (
+        Satiety,
+        HouseholdTypeToSpawn::LeisureVenue(LeisureKind::Restaurant),
+    ),
+    (
+        Entertainment,
+        HouseholdTypeToSpawn::LeisureVenue(LeisureKind::Cinema),
+    ),
+    (Education, HouseholdTypeToSpawn::School),
+    (Healthcare, HouseholdTypeToSpawn::Hospital),
+    (Car, HouseholdTypeToSpawn::CarDealer),
+    (Fuel, HouseholdTypeToSpawn::CarDealer),
+    (Bicycle, HouseholdTypeToSpawn::CarDealer),
+    (Produce, HouseholdTypeToSpawn::VegetableFarm),
+    (Grain, HouseholdTypeToSpawn::GrainFarm),
+    (Meat, HouseholdTypeToSpawn::CowFarm),
+    (DairyGoods, HouseholdTypeToSpawn::CowFarm),
+    (Flour, HouseholdTypeToSpawn::Mill),
+    (BakedGoods, HouseholdTypeToSpawn::Bakery),
+    (Construction, HouseholdTypeToSpawn::ConstructionFirm),
+    (
+        Electricity,
+        HouseholdTypeToSpawn::UtilityCompany(Utility::Electricity),
+    ),
+    (Water, HouseholdTypeToSpawn::UtilityCompany(Utility::Water)),
+];
+
+fn business_for(resource: Resource) -> Option<HouseholdTypeToSpawn> {
+    BUSINESSES
+        .iter()
+        .find(|&&(business_resource, _)| business_resource == resource)
+        .map(|&(_, household_type)| household_type)
+}
+
+// Besides resources that households can't find, taxi calls that the
+// dispatcher had no taxi for are a gap a taxi company fills
+#[derive(Copy, Clone, Debug)]
+enum Gap {
+    Lacking(Resource),
+    UnservedTaxiCalls,
+}
+
+impl Gap {
+    fn business(self) -> HouseholdTypeToSpawn {
+        match self {
+            Gap::Lacking(resource) => {
+                business_for(resource).expect("Only looks for gaps it can fill")
+            }
+            Gap::UnservedTaxiCalls => HouseholdTypeToSpawn::TaxiCompany,
+        }
+    }
+}
+
+// Watches what households couldn't find and founds the business that provides it,
+// in the vacant building closest to where it is lacking the most, or gets one built.
+// New businesses list their jobs on the market like any other, where they are
+// taken by members looking for work, and their suppliers are founded the same way
+// once they can't find what they need themselves
+#[derive(Compact, Clone)]
+pub struct Entrepreneur {
+    id: EntrepreneurID,
+    time: TimeID,
+    current_instant: Instant,
+    // the gap to found a business for and where it is lacking the most,
+    // while vacant buildings answer
+    founding: Option<(Gap, RoughLocationID)>,
+    // with their land value
+    vacant_units: CVec<(BuildingID, UnitIdx, f32)>,
+    positions: CHashMap<RoughLocationID, P2>,
+    last_founded: ResourceMap<Instant>,
+    last_taxi_company_founded: Option<Instant>,
+}
+
+impl Entrepreneur {
+    pub fn spawn(id: EntrepreneurID, time: TimeID, world: &mut World) -> Entrepreneur {
+        time.wake_up_in(CHECK_INTERVAL.into(), id.into(), world);
+
+        Entrepreneur {
+            id,
+            time,
+            current_instant: Instant::new(0),
+            founding: None,
+            vacant_units: CVec::new(),
+            positions: CHashMap::new(),
+            last_founded: ResourceMap::new(),
+            last_taxi_company_founded: None,
+        }
+    }
+
+    fn start_founding(&mut self, gap: Gap, location: RoughLocationID, world: &mut World) {
+        self.founding = Some((gap, location));
+        location.resolve_as_position(self.id_as(), location, world);
+        BuildingID::global_broadcast(world).try_offer_unit(
+            unit_type_for(gap.business()),
+            self.id_as(),
+            world,
+        );
+    }
+
+    fn found_business(&mut self, gap: Gap, hotspot: RoughLocationID, world: &mut World) {
+        let household_type = gap.business();
+
+        let maybe_site = self.positions.get(hotspot).and_then(|&hotspot_position| {
+            self.vacant_units
+                .iter()
+                .filter_map(|&(building, unit, land_value)| {
+                    let building_location: RoughLocationID = building.into();
+                    self.positions.get(building_location).map(|&position| {
+                        let distance = (position - hotspot_position).norm();
+                        (building, unit, business_site_cost(distance, land_value))
+                    })
+                })
+                .min_by_key(|&(_, _, cost)| OrderedFloat(cost))
+                .map(|(building, unit, _)| (building, unit))
+        });
+
+        if let Some((building, unit)) = maybe_site {
+            let purpose = match gap {
+                Gap::Lacking(resource) => format!("to provide {}", resource),
+                Gap::UnservedTaxiCalls => "to serve taxi calls".to_owned(),
+            };
+            info(
+                LOG_T,
+                format!("Founding {:?} {}", household_type, purpose),
+                self.id,
+                world,
+            );
+            let business =
+                immigration_and_development::move_into(household_type, building, self.time, world);
+            building.add_household(business, unit, world);
+        } else {
+            DevelopmentManagerID::global_first(world)
+                .try_develop(building_style_for(household_type), world);
+        }
+
+        match gap {
+            Gap::Lacking(resource) => {
+                self.last_founded.insert(resource, self.current_instant);
+            }
+            Gap::UnservedTaxiCalls => self.last_taxi_company_founded = Some(self.current_instant),
+        }
+        self.vacant_units.clear();
+        self.positions = CHashMap::new();
+    }
+}
+
+impl HotspotRequester for Entrepreneur {
+    fn on_hotspot(&mut self, resource: Resource, location: RoughLocationID, world: &mut World) {
+        if self.founding.is_some() {
+            return;
+        }
+
+        if business_for(resource).is_some() {
+            self.start_founding(Gap::Lacking(resource), location, world);
+        }
+    }
+}
+
+impl TaxiDemandRequester for Entrepreneur {
+    fn on_unserved_calls(&mut self, location: RoughLocationID, world: &mut World) {
+        if self.founding.is_none() {
+            self.start_founding(Gap::UnservedTaxiCalls, location, world);
+        }
+    }
+}
+
+impl UnitRequester for Entrepreneur {
+    fn on_unit_offer(
+        &mut self,
+        building: BuildingID,
+        unit: UnitIdx,
+        land_value: f32,
+        world: &mut World,
+    ) {
+        if self.founding.is_some() {
+            self.vacant_units.push((building, unit, land_value));
+            let building_location: RoughLocationID = building.into();
+            building_location.resolve_as_position(self.id_as(), building_location, world);
+        }
+    }
+}
+
+impl PositionRequester for Entrepreneur {
+    fn position_resolved(&mut self, rough_location: RoughLocationID, position: P2, _: &mut World) {
+        self.positions.insert(rough_location, position);
+    }
+}
+
+impl Sleeper for Entrepreneur {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        self.current_instant = current_instant;
+
+        // vacant buildings had until now to answer
+        if let Some((gap, hotspot)) = self.founding.take() {
+            self.found_business(gap, hotspot, world);
+        } else {
+            let candidates = BUSINESSES
+                .iter()
+                .map(|&(resource, _)| resource)
+                .filter(|&resource| {
+                    self.last_founded.get(resource).map_or(true, |&founded| {
+                        current_instant >= founded + FOUNDING_COOLDOWN
+                    })
+                })
+                .collect::<CVec<_>>();
+
+            UnmetDemandID::global_first(world).find_biggest_gap(
+                candidates,
+                MIN_RECENT_UNMET_DEMAND,
+                self.id_as(),
+                world,
+            );
+
+            let taxis_cooled_down = self
+                .last_taxi_company_founded
+                .map_or(true, |founded| current_instant >= founded + FOUNDING_COOLDOWN);
+            if taxis_cooled_down {
+                TaxiDispatcherID::global_first(world).find_unserved_hotspot(
+                    MIN_UNSERVED_TAXI_CALLS,
+                    self.id_as(),
+                    world,
+                );
+            }
+        }
+
+        self.time
+            .wake_up_in(CHECK_INTERVAL.into(), self.id.into(), world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Entrepreneur>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    EntrepreneurID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,210 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct AccountUIID {
+    _raw_id: RawID
+}
+
+impl Copy for AccountUIID {}
+impl Clone for AccountUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for AccountUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "AccountUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for AccountUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for AccountUIID {
+    fn eq(&self, other: &AccountUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for AccountUIID {}
+
+pub struct AccountUIRepresentative;
+
+impl ActorOrActorTrait for AccountUIRepresentative {
+    type ID = AccountUIID;
+}
+
+impl TypedID for AccountUIID {
+    type Target = AccountUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        AccountUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + AccountUI> TraitIDFrom<Act> for AccountUIID {}
+
+impl AccountUIID {
+    pub fn on_account_info(self, household: HouseholdID, account: Account, world: &mut World) {
+        world.send(self.as_raw(), MSG_AccountUI_on_account_info(household, account));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<AccountUIRepresentative>();
+        system.register_trait_message::<MSG_AccountUI_on_account_info>();
+    }
+
+    pub fn register_implementor<Act: Actor + AccountUI>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, AccountUIRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_AccountUI_on_account_info(household, account), instance, world| {
+                instance.on_account_info(household, account, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_AccountUI_on_account_info(pub HouseholdID, pub Account);
+
+impl Actor for Bank {
+    type ID = BankID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BankID {
+    _raw_id: RawID
+}
+
+impl Copy for BankID {}
+impl Clone for BankID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BankID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BankID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BankID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BankID {
+    fn eq(&self, other: &BankID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BankID {}
+
+impl TypedID for BankID {
+    type Target = Bank;
+
+    fn from_raw(id: RawID) -> Self {
+        BankID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BankID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = BankID::from_raw(world.allocate_instance_id::<Bank>());
+        let swarm = world.local_broadcast::<Bank>();
+        world.send(swarm, MSG_Bank_spawn(id, time));
+        id
+    }
+    
+    pub fn deposit(self, household: HouseholdID, amount: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_Bank_deposit(household, amount));
+    }
+    
+    pub fn request_funds(self, household: HouseholdID, amount: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_Bank_request_funds(household, amount));
+    }
+    
+    pub fn repayment_received(self, household: HouseholdID, paid: ResourceAmount, due: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_Bank_repayment_received(household, paid, due));
+    }
+    
+    pub fn close_account(self, household: HouseholdID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Bank_close_account(household));
+    }
+    
+    pub fn get_account(self, household: HouseholdID, requester: AccountUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Bank_get_account(household, requester));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Bank_spawn(pub BankID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Bank_deposit(pub HouseholdID, pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Bank_request_funds(pub HouseholdID, pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Bank_repayment_received(pub HouseholdID, pub ResourceAmount, pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Bank_close_account(pub HouseholdID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Bank_get_account(pub HouseholdID, pub AccountUIID);
+
+impl Into<SleeperID> for BankID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    AccountUIID::register_trait(system);
+    SleeperID::register_implementor::<Bank>(system);
+    system.add_spawner::<Bank, _, _>(
+        |&MSG_Bank_spawn(id, time), world| {
+            Bank::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<Bank, _, _>(
+        |&MSG_Bank_deposit(household, amount), instance, world| {
+            instance.deposit(household, amount, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Bank, _, _>(
+        |&MSG_Bank_request_funds(household, amount), instance, world| {
+            instance.request_funds(household, amount, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Bank, _, _>(
+        |&MSG_Bank_repayment_received(household, paid, due), instance, world| {
+            instance.repayment_received(household, paid, due, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Bank, _, _>(
+        |&MSG_Bank_close_account(household), instance, world| {
+            instance.close_account(household, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Bank, _, _>(
+        |&MSG_Bank_get_account(household, requester), instance, world| {
+            instance.get_account(household, requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
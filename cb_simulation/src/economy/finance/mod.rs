@@ -0,0 +1,183 @@
+use kay::{ActorSystem, World, TypedID};
+use compact::CHashMap;
+use cb_time::actors::{Sleeper, TimeID};
+use cb_time::units::{Instant, Duration};
+use cb_util::log::{info, warn};
+use super::resources::ResourceAmount;
+use super::households::HouseholdID;
+const LOG_T: &str = "Finance";
+
+// money a household keeps at hand, anything above gets put into savings
+pub const CASH_BUFFER: ResourceAmount = amount!(200.0);
+const LOAN_LIMIT: ResourceAmount = amount!(5_000.0);
+const REPAYMENT_INTERVAL: Duration = Duration(24 * 60 * 60);
+// share of the outstanding loan that is due every repayment interval
+const REPAYMENT_RATE: f32 = 0.1;
+const MIN_REPAYMENT: ResourceAmount = amount!(10.0);
+const INTEREST_RATE: f32 = 0.01;
+// households that missed this many repayments don't get any new loans
+const MAX_MISSED_REPAYMENTS_FOR_CREDIT: u32 = 3;
+const MISSED_REPAYMENTS_BEFORE_EVICTION: u32 = 10;
+
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct Account {
+    pub savings: ResourceAmount,
+    pub loan: ResourceAmount,
+    pub missed_repayments: u32,
+}
+
+impl Account {
+    pub fn is_creditworthy(&self) -> bool {
+        self.missed_repayments < MAX_MISSED_REPAYMENTS_FOR_CREDIT
+    }
+}
+
+pub trait AccountUI {
+    fn on_account_info(&mut self, household: HouseholdID, account: Account, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct Bank {
+    id: BankID,
+    accounts: CHashMap<HouseholdID, Account>,
+}
+
+impl Bank {
+    pub fn spawn(id: BankID, time: TimeID, world: &mut World) -> Bank {
+        time.wake_up_every(REPAYMENT_INTERVAL, id.into(), world);
+
+        Bank {
+            id,
+            accounts: CHashMap::new(),
+        }
+    }
+
+    fn account_mut(&mut self, household: HouseholdID) -> &mut Account {
+        if self.accounts.get(household).is_none() {
+            self.accounts.insert(household, Account::default());
+        }
+        self.accounts
+            .get_mut(household)
+            .expect("Just ensured the account exists")
+    }
+
+    pub fn deposit(&mut self, household: HouseholdID, amount: ResourceAmount, _: &mut World) {
+        self.account_mut(household).savings += amount;
+    }
+
+    // Pays out of savings first and only lends what savings can't cover
+    pub fn request_funds(
+        &mut self,
+        household: HouseholdID,
+        amount: ResourceAmount,
+        world: &mut World,
+    ) {
+        let granted = {
+            let account = self.account_mut(household);
+            let from_savings = amount.min(account.savings);
+            account.savings -= from_savings;
+
+            let shortfall = amount - from_savings;
+            let lent = if account.is_creditworthy() {
+                shortfall.min(LOAN_LIMIT - account.loan).max(0.0)
+            } else {
+                amount!(0.0)
+            };
+            account.loan += lent;
+
+            from_savings + lent
+        };
+
+        if granted < amount {
+            info(
+                LOG_T,
+                format!(
+                    "Could only grant {} of {} to {:?}",
+                    granted, amount, household
+                ),
+                self.id,
+                world,
+            );
+        }
+
+        household.receive_funds(granted, world);
+    }
+
+    pub fn repayment_received(
+        &mut self,
+        household: HouseholdID,
+        paid: ResourceAmount,
+        due: ResourceAmount,
+        world: &mut World,
+    ) {
+        let evict = {
+            let account = self.account_mut(household);
+            account.loan -= paid;
+
+            if paid < due {
+                account.missed_repayments += 1;
+            } else if account.missed_repayments > 0 {
+                account.missed_repayments -= 1;
+            }
+
+            account.missed_repayments >= MISSED_REPAYMENTS_BEFORE_EVICTION
+        };
+
+        if evict {
+            warn(
+                LOG_T,
+                format!(
+                    "Evicting {:?} after repeatedly missed repayments",
+                    household
+                ),
+                self.id,
+                world,
+            );
+            self.accounts.remove(household);
+            household.destroy(world);
+        }
+    }
+
+    pub fn close_account(&mut self, household: HouseholdID, _: &mut World) {
+        self.accounts.remove(household);
+    }
+
+    pub fn get_account(
+        &mut self,
+        household: HouseholdID,
+        requester: AccountUIID,
+        world: &mut World,
+    ) {
+        let account = self.accounts.get(household).cloned().unwrap_or_default();
+        requester.on_account_info(household, account, world);
+    }
+}
+
+impl Sleeper for Bank {
+    fn wake(&mut self, _current_instant: Instant, world: &mut World) {
+        let households = self.accounts.keys().cloned().collect::<Vec<_>>();
+
+        for household in households {
+            let account = self.account_mut(household);
+            if account.loan > 0.0 {
+                account.loan *= 1.0 + INTEREST_RATE;
+                let due = (account.loan * REPAYMENT_RATE)
+                    .max(MIN_REPAYMENT)
+                    .min(account.loan);
+                household.collect_repayment(due, self.id, world);
+            }
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Bank>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    BankID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
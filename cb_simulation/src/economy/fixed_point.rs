@@ -0,0 +1,438 @@
+// Resource amounts as integers in units of 1/2^20, see `resources::ResourceAmount`.
+// Adding and subtracting is exact, so what one side of a deal gives is exactly
+// what the other side gets and money totals don't drift over long games.
+// Multiplying and dividing round to the nearest unit, in the same way on every
+// platform. Results that don't fit saturate instead of wrapping around.
+// It behaves like the f32 it replaces wherever it can, so that the rest of the
+// economy reads the same with either representation
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Sub, Mul, Div, Neg, AddAssign, SubAssign, MulAssign, DivAssign};
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+
+const FRACTION_BITS: u32 = 20;
+pub const FIXED_POINT_SCALE: i64 = 1 << FRACTION_BITS;
+
+// Not `Ord` on purpose: `min` and `max` on a `&mut FixedAmount` would then
+// resolve to the ones for references instead of the ones below
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Hash)]
+pub struct FixedAmount(pub i64);
+
+// floats that are out of range saturate, NaN becomes zero
+fn saturating_from_f64(value: f64) -> i64 {
+    if value.is_nan() {
+        0
+    } else if value >= i64::max_value() as f64 {
+        i64::max_value()
+    } else if value <= i64::min_value() as f64 {
+        i64::min_value()
+    } else {
+        value.round() as i64
+    }
+}
+
+fn saturating_from_i128(value: i128) -> i64 {
+    if value > i128::from(i64::max_value()) {
+        i64::max_value()
+    } else if value < i128::from(i64::min_value()) {
+        i64::min_value()
+    } else {
+        value as i64
+    }
+}
+
+// rounds half away from zero, like `f64::round`
+fn rounded_div(numerator: i128, denominator: i128) -> i128 {
+    let quotient = (numerator.abs() + denominator.abs() / 2) / denominator.abs();
+    if (numerator < 0) == (denominator < 0) {
+        quotient
+    } else {
+        -quotient
+    }
+}
+
+impl FixedAmount {
+    pub const ZERO: FixedAmount = FixedAmount(0);
+    pub const MAX: FixedAmount = FixedAmount(::std::i64::MAX);
+    pub const MIN: FixedAmount = FixedAmount(::std::i64::MIN);
+
+    pub fn from_f64(value: f64) -> FixedAmount {
+        FixedAmount(saturating_from_f64(value * FIXED_POINT_SCALE as f64))
+    }
+
+    pub fn from_f32(value: f32) -> FixedAmount {
+        FixedAmount::from_f64(f64::from(value))
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / FIXED_POINT_SCALE as f64
+    }
+
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    pub fn abs(self) -> FixedAmount {
+        FixedAmount(self.0.checked_abs().unwrap_or_else(i64::max_value))
+    }
+
+    pub fn max<T: Into<FixedAmount>>(self, other: T) -> FixedAmount {
+        FixedAmount(::std::cmp::max(self.0, other.into().0))
+    }
+
+    pub fn min<T: Into<FixedAmount>>(self, other: T) -> FixedAmount {
+        FixedAmount(::std::cmp::min(self.0, other.into().0))
+    }
+
+    pub fn floor(self) -> FixedAmount {
+        let remainder = self.0 % FIXED_POINT_SCALE;
+        if remainder < 0 {
+            FixedAmount((self.0 - remainder).saturating_sub(FIXED_POINT_SCALE))
+        } else {
+            FixedAmount(self.0 - remainder)
+        }
+    }
+
+    pub fn ceil(self) -> FixedAmount {
+        -(-self).floor()
+    }
+
+    pub fn round(self) -> FixedAmount {
+        FixedAmount(saturating_from_i128(
+            rounded_div(i128::from(self.0), i128::from(FIXED_POINT_SCALE))
+                * i128::from(FIXED_POINT_SCALE),
+        ))
+    }
+
+    pub fn signum(self) -> f32 {
+        self.0.signum() as f32
+    }
+
+    pub fn is_sign_negative(self) -> bool {
+        self.0 < 0
+    }
+}
+
+impl From<f32> for FixedAmount {
+    fn from(value: f32) -> FixedAmount {
+        FixedAmount::from_f32(value)
+    }
+}
+
+impl From<FixedAmount> for f32 {
+    fn from(amount: FixedAmount) -> f32 {
+        amount.to_f32()
+    }
+}
+
+impl From<FixedAmount> for f64 {
+    fn from(amount: FixedAmount) -> f64 {
+        amount.to_f64()
+    }
+}
+
+impl Add for FixedAmount {
+    type Output = FixedAmount;
+
+    fn add(self, other: FixedAmount) -> FixedAmount {
+        FixedAmount(self.0.saturating_add(other.0))
+    }
+}
+
+impl Sub for FixedAmount {
+    type Output = FixedAmount;
+
+    fn sub(self, other: FixedAmount) -> FixedAmount {
+        FixedAmount(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Mul for FixedAmount {
+    type Output = FixedAmount;
+
+    fn mul(self, other: FixedAmount) -> FixedAmount {
+        FixedAmount(saturating_from_i128(rounded_div(
+            i128::from(self.0) * i128::from(other.0),
+            i128::from(FIXED_POINT_SCALE),
+        )))
+    }
+}
+
+// Dividing by zero saturates like a float would become infinite
+impl Div for FixedAmount {
+    type Output = FixedAmount;
+
+    fn div(self, other: FixedAmount) -> FixedAmount {
+        if other.0 == 0 {
+            match self.0.cmp(&0) {
+                Ordering::Less => FixedAmount::MIN,
+                Ordering::Equal => FixedAmount::ZERO,
+                Ordering::Greater => FixedAmount::MAX,
+            }
+        } else {
+            FixedAmount(saturating_from_i128(rounded_div(
+                i128::from(self.0) * i128::from(FIXED_POINT_SCALE),
+                i128::from(other.0),
+            )))
+        }
+    }
+}
+
+impl Neg for FixedAmount {
+    type Output = FixedAmount;
+
+    fn neg(self) -> FixedAmount {
+        FixedAmount(self.0.checked_neg().unwrap_or_else(i64::max_value))
+    }
+}
+
+// With factors and amounts written as floats
+impl Add<f32> for FixedAmount {
+    type Output = FixedAmount;
+
+    fn add(self, other: f32) -> FixedAmount {
+        self + FixedAmount::from(other)
+    }
+}
+
+impl Sub<f32> for FixedAmount {
+    type Output = FixedAmount;
+
+    fn sub(self, other: f32) -> FixedAmount {
+        self - FixedAmount::from(other)
+    }
+}
+
+impl Mul<f32> for FixedAmount {
+    type Output = FixedAmount;
+
+    fn mul(self, factor: f32) -> FixedAmount {
+        FixedAmount(saturating_from_f64(self.0 as f64 * f64::from(factor)))
+    }
+}
+
+impl Div<f32> for FixedAmount {
+    type Output = FixedAmount;
+
+    fn div(self, divisor: f32) -> FixedAmount {
+        FixedAmount(saturating_from_f64(self.0 as f64 / f64::from(divisor)))
+    }
+}
+
+impl Add<FixedAmount> for f32 {
+    type Output = FixedAmount;
+
+    fn add(self, other: FixedAmount) -> FixedAmount {
+        FixedAmount::from(self) + other
+    }
+}
+
+impl Sub<FixedAmount> for f32 {
+    type Output = FixedAmount;
+
+    fn sub(self, other: FixedAmount) -> FixedAmount {
+        FixedAmount::from(self) - other
+    }
+}
+
+impl Mul<FixedAmount> for f32 {
+    type Output = FixedAmount;
+
+    fn mul(self, amount: FixedAmount) -> FixedAmount {
+        amount * self
+    }
+}
+
+impl Div<FixedAmount> for f32 {
+    type Output = FixedAmount;
+
+    fn div(self, amount: FixedAmount) -> FixedAmount {
+        FixedAmount::from(self) / amount
+    }
+}
+
+impl<T> AddAssign<T> for FixedAmount
+where
+    FixedAmount: Add<T, Output = FixedAmount>,
+{
+    fn add_assign(&mut self, other: T) {
+        *self = *self + other;
+    }
+}
+
+impl<T> SubAssign<T> for FixedAmount
+where
+    FixedAmount: Sub<T, Output = FixedAmount>,
+{
+    fn sub_assign(&mut self, other: T) {
+        *self = *self - other;
+    }
+}
+
+impl<T> MulAssign<T> for FixedAmount
+where
+    FixedAmount: Mul<T, Output = FixedAmount>,
+{
+    fn mul_assign(&mut self, other: T) {
+        *self = *self * other;
+    }
+}
+
+impl<T> DivAssign<T> for FixedAmount
+where
+    FixedAmount: Div<T, Output = FixedAmount>,
+{
+    fn div_assign(&mut self, other: T) {
+        *self = *self / other;
+    }
+}
+
+impl PartialEq<f32> for FixedAmount {
+    fn eq(&self, other: &f32) -> bool {
+        *self == FixedAmount::from(*other)
+    }
+}
+
+impl PartialOrd<f32> for FixedAmount {
+    fn partial_cmp(&self, other: &f32) -> Option<Ordering> {
+        Some(self.0.cmp(&FixedAmount::from(*other).0))
+    }
+}
+
+impl PartialEq<FixedAmount> for f32 {
+    fn eq(&self, other: &FixedAmount) -> bool {
+        FixedAmount::from(*self) == *other
+    }
+}
+
+impl PartialOrd<FixedAmount> for f32 {
+    fn partial_cmp(&self, other: &FixedAmount) -> Option<Ordering> {
+        Some(FixedAmount::from(*self).0.cmp(&other.0))
+    }
+}
+
+impl Sum for FixedAmount {
+    fn sum<I: Iterator<Item = FixedAmount>>(iter: I) -> FixedAmount {
+        iter.fold(FixedAmount::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a FixedAmount> for FixedAmount {
+    fn sum<I: Iterator<Item = &'a FixedAmount>>(iter: I) -> FixedAmount {
+        iter.cloned().sum()
+    }
+}
+
+// Shown, logged and sent to the UI like the float it stands for
+impl fmt::Display for FixedAmount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.to_f64(), f)
+    }
+}
+
+impl fmt::Debug for FixedAmount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_f64(), f)
+    }
+}
+
+impl Serialize for FixedAmount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.to_f64())
+    }
+}
+
+impl<'de> Deserialize<'de> for FixedAmount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<FixedAmount, D::Error> {
+        f64::deserialize(deserializer).map(FixedAmount::from_f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cb_util::random::{seed, Rng};
+
+    const N_ACCOUNTS: usize = 50;
+    const N_TRANSFERS: usize = 1_000_000;
+
+    // Transfers of odd amounts between accounts, many of which would each be
+    // rounded if they were floats, with large balances like a treasury's
+    fn run_transfers<F: FnMut(&mut [FixedAmount], usize, usize, FixedAmount)>(mut transfer: F) {
+        let mut rng = seed(548u32);
+        let mut accounts = vec![FixedAmount::from(200_000.0); N_ACCOUNTS];
+        let total_before: FixedAmount = accounts.iter().sum();
+
+        for _ in 0..N_TRANSFERS {
+            let from = rng.gen_range(0, N_ACCOUNTS);
+            let to = rng.gen_range(0, N_ACCOUNTS);
+            let amount = FixedAmount::from(rng.gen_range(0.01f32, 1_000.0)) * 1.07;
+            transfer(&mut accounts, from, to, amount);
+        }
+
+        let total_after: FixedAmount = accounts.iter().sum();
+        assert_eq!(total_before, total_after);
+    }
+
+    #[test]
+    fn transfers_conserve_totals() {
+        run_transfers(|accounts, from, to, amount| {
+            accounts[from] -= amount;
+            accounts[to] += amount;
+        });
+    }
+
+    #[test]
+    fn fractional_transfers_conserve_totals() {
+        // like a share of a price that is passed on, computed on one side only
+        run_transfers(|accounts, from, to, amount| {
+            let share = amount * 0.3 / 7.0;
+            accounts[from] -= share;
+            accounts[to] += share;
+        });
+    }
+
+    #[test]
+    fn round_trips_through_floats() {
+        for &value in &[0.0f32, 1.0, -1.0, 0.5, 200_000.0, -3.25, 1e-3] {
+            let amount = FixedAmount::from(value);
+            assert!((amount.to_f32() - value).abs() <= 1.0 / FIXED_POINT_SCALE as f32);
+        }
+    }
+
+    #[test]
+    fn arithmetic_matches_floats() {
+        let a = FixedAmount::from(12.5);
+        let b = FixedAmount::from(-4.0);
+        assert_eq!(a + b, FixedAmount::from(8.5));
+        assert_eq!(a - b, FixedAmount::from(16.5));
+        assert_eq!(a * b, FixedAmount::from(-50.0));
+        assert_eq!(a / b, FixedAmount::from(-3.125));
+        assert_eq!(a * 2.0, FixedAmount::from(25.0));
+        assert_eq!(FixedAmount::from(-2.5).floor(), FixedAmount::from(-3.0));
+        assert_eq!(FixedAmount::from(-2.5).ceil(), FixedAmount::from(-2.0));
+        assert_eq!(FixedAmount::from(2.5).round(), FixedAmount::from(3.0));
+        assert_eq!(FixedAmount::from(-2.5).round(), FixedAmount::from(-3.0));
+        assert!(a > 12.0 && a < 13.0);
+    }
+
+    #[test]
+    fn literals_round_like_conversions() {
+        for &value in &[0.0, 0.75, 1.0015, -1.0015, 0.1 + 0.2, 2.9999, -2.9999, 1e-4, -5e-4] {
+            assert_eq!(amount!(value), FixedAmount::from_f64(value));
+        }
+        const PRICE: FixedAmount = amount!(0.0045);
+        assert_eq!(PRICE, FixedAmount::from_f64(0.0045));
+    }
+
+    #[test]
+    fn saturates_instead_of_overflowing() {
+        assert_eq!(FixedAmount::MAX + FixedAmount::from(1.0), FixedAmount::MAX);
+        assert_eq!(FixedAmount::MIN - FixedAmount::from(1.0), FixedAmount::MIN);
+        assert_eq!(FixedAmount::MAX * 2.0, FixedAmount::MAX);
+        assert_eq!(-FixedAmount::MIN, FixedAmount::MAX);
+        assert_eq!(FixedAmount::from(1.0) / FixedAmount::ZERO, FixedAmount::MAX);
+        assert_eq!(FixedAmount::from(::std::f32::NAN), FixedAmount::ZERO);
+    }
+}
@@ -0,0 +1,34 @@
+use compact::CVec;
+use cb_time::units::{Duration, Instant};
+use economy::resources::Resource;
+use super::OfferID;
+
+// per member, older entries are dropped
+const ACTIVITY_LOG_LENGTH: usize = 20;
+
+// A decision a member made or something that happened to them on the way,
+// kept to inspect members and to spot decisions that go around in circles
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum LoggedActivity {
+    Searched(Resource),
+    FollowedPlan(Resource),
+    ContinuedChainedTrip(Resource),
+    Chose(Resource, OfferID),
+    FoundNothing,
+    Traveled(Resource, Duration),
+    TripFailed(Resource),
+    TurnedAway(Resource),
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct ActivityLogEntry {
+    pub instant: Instant,
+    pub activity: LoggedActivity,
+}
+
+pub fn record(log: &mut CVec<ActivityLogEntry>, instant: Instant, activity: LoggedActivity) {
+    if log.len() >= ACTIVITY_LOG_LENGTH {
+        log.remove(0);
+    }
+    log.push(ActivityLogEntry { instant, activity });
+}
@@ -0,0 +1,133 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct LifecycleListenerID {
+    _raw_id: RawID
+}
+
+impl Copy for LifecycleListenerID {}
+impl Clone for LifecycleListenerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for LifecycleListenerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "LifecycleListenerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for LifecycleListenerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for LifecycleListenerID {
+    fn eq(&self, other: &LifecycleListenerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for LifecycleListenerID {}
+
+pub struct LifecycleListenerRepresentative;
+
+impl ActorOrActorTrait for LifecycleListenerRepresentative {
+    type ID = LifecycleListenerID;
+}
+
+impl TypedID for LifecycleListenerID {
+    type Target = LifecycleListenerRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        LifecycleListenerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + LifecycleListener> TraitIDFrom<Act> for LifecycleListenerID {}
+
+impl LifecycleListenerID {
+    pub fn on_member_born(self, household: HouseholdID, member: MemberIdx, world: &mut World) {
+        world.send(self.as_raw(), MSG_LifecycleListener_on_member_born(household, member));
+    }
+    
+    pub fn on_member_stage_changed(self, household: HouseholdID, member: MemberIdx, stage: LifeStage, world: &mut World) {
+        world.send(self.as_raw(), MSG_LifecycleListener_on_member_stage_changed(household, member, stage));
+    }
+    
+    pub fn on_member_died(self, household: HouseholdID, member: MemberIdx, age: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_LifecycleListener_on_member_died(household, member, age));
+    }
+    
+    pub fn on_household_dissolved(self, household: HouseholdID, world: &mut World) {
+        world.send(self.as_raw(), MSG_LifecycleListener_on_household_dissolved(household));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<LifecycleListenerRepresentative>();
+        system.register_trait_message::<MSG_LifecycleListener_on_member_born>();
+        system.register_trait_message::<MSG_LifecycleListener_on_member_stage_changed>();
+        system.register_trait_message::<MSG_LifecycleListener_on_member_died>();
+        system.register_trait_message::<MSG_LifecycleListener_on_household_dissolved>();
+    }
+
+    pub fn register_implementor<Act: Actor + LifecycleListener>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, LifecycleListenerRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_LifecycleListener_on_member_born(household, member), instance, world| {
+                instance.on_member_born(household, member, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_LifecycleListener_on_member_stage_changed(household, member, stage), instance, world| {
+                instance.on_member_stage_changed(household, member, stage, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_LifecycleListener_on_member_died(household, member, age), instance, world| {
+                instance.on_member_died(household, member, age, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_LifecycleListener_on_household_dissolved(household), instance, world| {
+                instance.on_household_dissolved(household, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_LifecycleListener_on_member_born(pub HouseholdID, pub MemberIdx);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_LifecycleListener_on_member_stage_changed(pub HouseholdID, pub MemberIdx, pub LifeStage);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_LifecycleListener_on_member_died(pub HouseholdID, pub MemberIdx, pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_LifecycleListener_on_household_dissolved(pub HouseholdID);
+
+
+
+impl LifeStageID {
+    
+}
+
+
+
+impl Into<std::fmt::DisplayID> for LifeStageID {
+    fn into(self) -> std::fmt::DisplayID {
+        std::fmt::DisplayID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    LifecycleListenerID::register_trait(system);
+    std::fmt::DisplayID::register_implementor::<LifeStage>(system);
+}
\ No newline at end of file
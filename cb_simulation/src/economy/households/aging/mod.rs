@@ -0,0 +1,146 @@
+use kay::World;
+use economy::resources::Resource;
+use economy::resources::Resource::*;
+use super::{HouseholdID, MemberIdx};
+
+// simulated years are heavily compressed, see `cb_time::calendar`
+pub use cb_time::calendar::DAYS_PER_YEAR;
+
+pub const STUDENT_AGE: u32 = 6;
+pub const WORKER_AGE: u32 = 18;
+pub const RETIREMENT_AGE: u32 = 65;
+const MORTALITY_ONSET_AGE: u32 = 60;
+const MAX_AGE: u32 = 105;
+
+pub const MIN_PARENT_AGE: u32 = 20;
+pub const MAX_PARENT_AGE: u32 = 45;
+pub const BIRTH_CHANCE_PER_YEAR: f32 = 0.15;
+pub const MAX_FAMILY_SIZE: usize = 6;
+// grown children still living with their parents
+pub const MOVING_OUT_CHANCE_PER_YEAR: f32 = 0.2;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum LifeStage {
+    Child,
+    Student,
+    Worker,
+    Retiree,
+    Deceased,
+    // left to live in a household of their own
+    MovedOut,
+}
+
+impl LifeStage {
+    pub fn at_age(years: u32) -> LifeStage {
+        if years < STUDENT_AGE {
+            LifeStage::Child
+        } else if years < WORKER_AGE {
+            LifeStage::Student
+        } else if years < RETIREMENT_AGE {
+            LifeStage::Worker
+        } else {
+            LifeStage::Retiree
+        }
+    }
+
+    // how strongly a member in this stage of life cares about a resource,
+    // relative to a working adult
+    pub fn demand_factor(self, resource: Resource) -> f32 {
+        match (self, resource) {
+            (LifeStage::Deceased, _) | (LifeStage::MovedOut, _) => 0.0,
+            (LifeStage::Child, Money) => 0.0,
+            (LifeStage::Student, Money) => 0.3,
+            (LifeStage::Retiree, Money) => 0.2,
+            (LifeStage::Child, Wakefulness) => 1.3,
+            (LifeStage::Retiree, Wakefulness) => 1.2,
+            (LifeStage::Student, Education) => 1.0,
+            (_, Education) => 0.0,
+            // only adults buy and take care of cars, students of bikes as well
+            (LifeStage::Child, Car) | (LifeStage::Student, Car) => 0.0,
+            (LifeStage::Child, Fuel) | (LifeStage::Student, Fuel) => 0.0,
+            (LifeStage::Child, Bicycle) => 0.0,
+            _ => 1.0,
+        }
+    }
+}
+
+impl ::std::fmt::Display for LifeStage {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct MemberLife {
+    pub age_in_days: u32,
+    pub stage: LifeStage,
+}
+
+impl MemberLife {
+    pub fn aged(years: u32) -> MemberLife {
+        MemberLife {
+            age_in_days: years * DAYS_PER_YEAR,
+            stage: LifeStage::at_age(years),
+        }
+    }
+
+    pub fn newborn() -> MemberLife {
+        MemberLife::aged(0)
+    }
+
+    pub fn years(&self) -> u32 {
+        self.age_in_days / DAYS_PER_YEAR
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.stage != LifeStage::Deceased
+    }
+
+    // still alive and still living in the household
+    pub fn is_present(&self) -> bool {
+        self.is_alive() && self.stage != LifeStage::MovedOut
+    }
+
+    pub fn has_birthday(&self) -> bool {
+        self.age_in_days % DAYS_PER_YEAR == 0
+    }
+
+    pub fn could_be_parent(&self) -> bool {
+        self.is_present() && self.years() >= MIN_PARENT_AGE && self.years() <= MAX_PARENT_AGE
+    }
+}
+
+// chance of dying within the year after reaching the given age
+pub fn mortality(years: u32) -> f32 {
+    if years >= MAX_AGE {
+        1.0
+    } else if years < MORTALITY_ONSET_AGE {
+        0.001
+    } else {
+        let progress =
+            (years - MORTALITY_ONSET_AGE) as f32 / (MAX_AGE - MORTALITY_ONSET_AGE) as f32;
+        0.01 + 0.5 * progress * progress
+    }
+}
+
+pub trait LifecycleListener {
+    fn on_member_born(&mut self, household: HouseholdID, member: MemberIdx, world: &mut World);
+    fn on_member_stage_changed(
+        &mut self,
+        household: HouseholdID,
+        member: MemberIdx,
+        stage: LifeStage,
+        world: &mut World,
+    );
+    fn on_member_died(
+        &mut self,
+        household: HouseholdID,
+        member: MemberIdx,
+        age: u32,
+        world: &mut World,
+    );
+    fn on_household_dissolved(&mut self, household: HouseholdID, world: &mut World);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,224 @@
+use kay::{ActorSystem, World};
+use compact::{CVec, CHashMap};
+use cb_util::config_manager::{Config, ConfigManager, ConfigManagerID, Name};
+use cb_util::random::Rng;
+use cb_time::units::TimeOfDayRange;
+use economy::resources::ResourceMap;
+use economy::resources::Resource::*;
+use super::aging::{MemberLife, DAYS_PER_YEAR, STUDENT_AGE, WORKER_AGE, MAX_FAMILY_SIZE};
+
+// A group of similar members of an immigrating household, like "two to four children"
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct MemberTemplate {
+    pub min_count: u32,
+    pub max_count: u32,
+    pub min_age: u32,
+    pub max_age: u32,
+}
+
+// The kinds of families that move into the city. They are config entries,
+// so they can be rebalanced while the simulation is running
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct HouseholdArchetype {
+    // how often this kind of family immigrates, relative to the others
+    pub weight: f32,
+    pub members: CVec<MemberTemplate>,
+    pub car_ownership: f32,
+    pub bike_ownership: f32,
+    // how fast members use up each resource, relative to the usual rate
+    pub consumption: ResourceMap<f32>,
+    // when adults sleep, if they deviate from the usual
+    pub sleep: Option<TimeOfDayRange>,
+}
+
+impl Config for HouseholdArchetype {}
+
+impl HouseholdArchetype {
+    // two parents with a child, as every family used to be
+    pub fn fallback() -> HouseholdArchetype {
+        HouseholdArchetype {
+            weight: 1.0,
+            members: vec![
+                MemberTemplate {
+                    min_count: 2,
+                    max_count: 2,
+                    min_age: 25,
+                    max_age: 45,
+                },
+                MemberTemplate {
+                    min_count: 1,
+                    max_count: 1,
+                    min_age: 0,
+                    max_age: 15,
+                },
+            ]
+            .into(),
+            car_ownership: 0.4,
+            bike_ownership: 0.5,
+            consumption: ResourceMap::new(),
+            sleep: None,
+        }
+    }
+
+    // Ages and schooling of the members, adults first.
+    // Adults went to school elsewhere, children so far as their age allows
+    pub fn generate_members<R: Rng>(&self, rng: &mut R) -> Vec<(MemberLife, f32)> {
+        let mut members = Vec::new();
+
+        for template in self.members.iter() {
+            let count = rng.gen_range(template.min_count, template.max_count + 1);
+            for _ in 0..count {
+                let years = rng.gen_range(template.min_age, template.max_age + 1);
+                let mut life = MemberLife::aged(years);
+                life.age_in_days += rng.gen_range(0, DAYS_PER_YEAR);
+                let education = if years >= WORKER_AGE {
+                    rng.gen_range(8.0, 17.0)
+                } else {
+                    years.saturating_sub(STUDENT_AGE) as f32
+                };
+                members.push((life, education));
+            }
+        }
+
+        if members.is_empty() {
+            return HouseholdArchetype::fallback().generate_members(rng);
+        }
+
+        members.sort_by_key(|&(life, _)| ::std::cmp::Reverse(life.age_in_days));
+        members.truncate(MAX_FAMILY_SIZE);
+        members
+    }
+}
+
+pub fn choose<R: Rng>(
+    archetypes: &CHashMap<Name, HouseholdArchetype>,
+    rng: &mut R,
+) -> (Name, HouseholdArchetype) {
+    let total_weight: f32 = archetypes
+        .values()
+        .map(|archetype| archetype.weight.max(0.0))
+        .sum();
+
+    if total_weight > 0.0 {
+        let mut dot = rng.gen::<f32>() * total_weight;
+        for (name, archetype) in archetypes.pairs() {
+            let weight = archetype.weight.max(0.0);
+            if dot < weight {
+                return (*name, archetype.clone());
+            }
+            dot -= weight;
+        }
+    }
+
+    (
+        Name::from("Family").unwrap(),
+        HouseholdArchetype::fallback(),
+    )
+}
+
+fn default_archetypes() -> CHashMap<Name, HouseholdArchetype> {
+    vec![
+        (
+            "Family",
+            HouseholdArchetype {
+                weight: 2.0,
+                ..HouseholdArchetype::fallback()
+            },
+        ),
+        (
+            "SingleStudent",
+            HouseholdArchetype {
+                weight: 1.0,
+                members: vec![MemberTemplate {
+                    min_count: 1,
+                    max_count: 1,
+                    min_age: 18,
+                    max_age: 24,
+                }]
+                .into(),
+                car_ownership: 0.1,
+                bike_ownership: 0.7,
+                consumption: vec![(Satiety, 0.8), (Companionship, 1.4), (Entertainment, 1.5)]
+                    .into_iter()
+                    .collect(),
+                sleep: Some(TimeOfDayRange::new(1, 0, 9, 0)),
+            },
+        ),
+        (
+            "RetireeCouple",
+            HouseholdArchetype {
+                weight: 0.8,
+                members: vec![MemberTemplate {
+                    min_count: 2,
+                    max_count: 2,
+                    min_age: 65,
+                    max_age: 80,
+                }]
+                .into(),
+                car_ownership: 0.3,
+                bike_ownership: 0.2,
+                consumption: vec![(Satiety, 0.9), (Companionship, 1.2), (Entertainment, 0.7)]
+                    .into_iter()
+                    .collect(),
+                sleep: Some(TimeOfDayRange::new(22, 0, 6, 30)),
+            },
+        ),
+        (
+            "LargeFamily",
+            HouseholdArchetype {
+                weight: 1.0,
+                members: vec![
+                    MemberTemplate {
+                        min_count: 2,
+                        max_count: 2,
+                        min_age: 28,
+                        max_age: 45,
+                    },
+                    MemberTemplate {
+                        min_count: 3,
+                        max_count: 4,
+                        min_age: 0,
+                        max_age: 15,
+                    },
+                ]
+                .into(),
+                car_ownership: 0.5,
+                bike_ownership: 0.6,
+                consumption: vec![(Satiety, 1.1), (Companionship, 0.8)]
+                    .into_iter()
+                    .collect(),
+                sleep: None,
+            },
+        ),
+        (
+            // double income, no kids
+            "Dink",
+            HouseholdArchetype {
+                weight: 1.0,
+                members: vec![MemberTemplate {
+                    min_count: 2,
+                    max_count: 2,
+                    min_age: 25,
+                    max_age: 40,
+                }]
+                .into(),
+                car_ownership: 0.8,
+                bike_ownership: 0.4,
+                consumption: vec![(Entertainment, 1.3)].into_iter().collect(),
+                sleep: Some(TimeOfDayRange::new(0, 0, 7, 0)),
+            },
+        ),
+    ]
+    .into_iter()
+    .map(|(name, archetype)| (Name::from(name).unwrap(), archetype))
+    .collect()
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<ConfigManager<HouseholdArchetype>>();
+    ::cb_util::config_manager::auto_setup::<HouseholdArchetype>(system);
+}
+
+pub fn spawn(world: &mut World) {
+    ConfigManagerID::<HouseholdArchetype>::spawn(default_archetypes(), world);
+}
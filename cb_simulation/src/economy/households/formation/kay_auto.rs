@@ -0,0 +1,198 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for HousingSearch {
+    type ID = HousingSearchID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct HousingSearchID {
+    _raw_id: RawID
+}
+
+impl Copy for HousingSearchID {}
+impl Clone for HousingSearchID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for HousingSearchID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "HousingSearchID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for HousingSearchID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for HousingSearchID {
+    fn eq(&self, other: &HousingSearchID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for HousingSearchID {}
+
+impl TypedID for HousingSearchID {
+    type Target = HousingSearch;
+
+    fn from_raw(id: RawID) -> Self {
+        HousingSearchID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl HousingSearchID {
+    pub fn spawn(n_expected: u32, world: &mut World) -> Self {
+        let id = HousingSearchID::from_raw(world.allocate_instance_id::<HousingSearch>());
+        let swarm = world.local_broadcast::<HousingSearch>();
+        world.send(swarm, MSG_HousingSearch_spawn(id, n_expected));
+        id
+    }
+    
+    pub fn join(self, maybe_mover: COption < MovingMember >, world: &mut World) {
+        world.send(self.as_raw(), MSG_HousingSearch_join(maybe_mover));
+    }
+    
+    pub fn done(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_HousingSearch_done());
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HousingSearch_spawn(pub HousingSearchID, pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HousingSearch_join(pub COption < MovingMember >);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HousingSearch_done();
+
+impl Into<HousingSeekerID> for HousingSearchID {
+    fn into(self) -> HousingSeekerID {
+        HousingSeekerID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for HousingSearchID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+impl Actor for Matchmaker {
+    type ID = MatchmakerID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct MatchmakerID {
+    _raw_id: RawID
+}
+
+impl Copy for MatchmakerID {}
+impl Clone for MatchmakerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for MatchmakerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "MatchmakerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for MatchmakerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for MatchmakerID {
+    fn eq(&self, other: &MatchmakerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for MatchmakerID {}
+
+impl TypedID for MatchmakerID {
+    type Target = Matchmaker;
+
+    fn from_raw(id: RawID) -> Self {
+        MatchmakerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl MatchmakerID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = MatchmakerID::from_raw(world.allocate_instance_id::<Matchmaker>());
+        let swarm = world.local_broadcast::<Matchmaker>();
+        world.send(swarm, MSG_Matchmaker_spawn(id, time));
+        id
+    }
+    
+    pub fn looking_for_partner(self, household: HouseholdID, member: MemberIdx, years: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Matchmaker_looking_for_partner(household, member, years));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Matchmaker_spawn(pub MatchmakerID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Matchmaker_looking_for_partner(pub HouseholdID, pub MemberIdx, pub u32);
+
+impl Into<SleeperID> for MatchmakerID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HousingSeekerID::register_implementor::<HousingSearch>(system);
+    SleeperID::register_implementor::<HousingSearch>(system);
+    system.add_spawner::<HousingSearch, _, _>(
+        |&MSG_HousingSearch_spawn(id, n_expected), world| {
+            HousingSearch::spawn(id, n_expected, world)
+        }, false
+    );
+    
+    system.add_handler::<HousingSearch, _, _>(
+        |&MSG_HousingSearch_join(ref maybe_mover), instance, world| {
+            instance.join(maybe_mover, world)
+        }, false
+    );
+    
+    system.add_handler::<HousingSearch, _, _>(
+        |&MSG_HousingSearch_done(), instance, world| {
+            instance.done(world)
+        }, false
+    );
+    SleeperID::register_implementor::<Matchmaker>(system);
+    system.add_spawner::<Matchmaker, _, _>(
+        |&MSG_Matchmaker_spawn(id, time), world| {
+            Matchmaker::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<Matchmaker, _, _>(
+        |&MSG_Matchmaker_looking_for_partner(household, member, years), instance, world| {
+            instance.looking_for_partner(household, member, years, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,289 @@
+use kay::{ActorSystem, World, Actor, Fate};
+use compact::{CVec, COption};
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Duration};
+use cb_util::random::{seed, Rng};
+use cb_util::log::{info, debug};
+use economy::resources::{Resource, Inventory, ResourceMap, Entry};
+use economy::resources::Resource::*;
+use economy::immigration_and_development::DevelopmentManagerID;
+use land_use::buildings::BuildingStyle;
+use transport::pathfinding::RoughLocationID;
+use super::{HouseholdID, MemberIdx, OfferID};
+use super::aging::{MemberLife, DAYS_PER_YEAR};
+use super::needs::Needs;
+use super::health::Health;
+use super::household_kinds::family::FamilyID;
+use economy::housing::{self, Dwelling, DwellingRequest, HousingMarketID, HousingSeeker,
+HousingSeekerID};
+const LOG_T: &str = "Family Formation";
+
+const MATCHMAKING_INTERVAL: Duration = Duration(24 * 60 * 60);
+// chance of a single finding a partner among the other singles within a year
+const PAIRING_CHANCE_PER_YEAR: f32 = 0.3;
+const MAX_PARTNER_AGE_GAP: u32 = 10;
+// how long to wait for new dwellings to be built after finding none,
+// and how often to try before giving up
+const HOUSING_SEARCH_PATIENCE: Duration = Duration(60 * 60);
+const MAX_HOUSING_SEARCH_ATTEMPTS: u32 = 12;
+
+// cars and bikes can't be split up, they stay with whoever remains in the household
+pub fn is_divisible(resource: Resource) -> bool {
+    match resource {
+        Car | Fuel | Bicycle => false,
+        _ => true,
+    }
+}
+
+// Everything a member takes along when leaving their household: who they are,
+// their share of the household's belongings and their personal contracts,
+// like jobs or school places, which are handed over to their new household
+#[derive(Compact, Clone)]
+pub struct MovingMember {
+    pub origin: HouseholdID,
+    pub origin_member: MemberIdx,
+    pub location: RoughLocationID,
+    pub life: MemberLife,
+    pub education: f32,
+    pub health: Health,
+    pub needs: Needs,
+    pub resources: Inventory,
+    pub shared_resources: Inventory,
+    pub used_offers: ResourceMap<OfferID>,
+}
+
+#[derive(Copy, Clone)]
+pub enum HousingSearchState {
+    Gathering,
+    Searching(u32),
+    WaitingForDevelopment(u32),
+    Settled,
+}
+
+// Collects the members that will form a new household from their old
+// households and finds them a dwelling on the housing market. Members that didn't make it
+// (for example because they died in the meantime) are simply left out
+#[derive(Compact, Clone)]
+pub struct HousingSearch {
+    id: HousingSearchID,
+    n_expected: u32,
+    n_answered: u32,
+    movers: CVec<MovingMember>,
+    state: HousingSearchState,
+}
+
+impl HousingSearch {
+    pub fn spawn(id: HousingSearchID, n_expected: u32, _: &mut World) -> HousingSearch {
+        HousingSearch {
+            id,
+            n_expected,
+            n_answered: 0,
+            movers: CVec::new(),
+            state: HousingSearchState::Gathering,
+        }
+    }
+
+    pub fn join(&mut self, maybe_mover: &COption<MovingMember>, world: &mut World) -> Fate {
+        self.n_answered += 1;
+        if let Some(mover) = maybe_mover.as_ref() {
+            self.movers.push(mover.clone());
+        }
+
+        if self.n_answered < self.n_expected {
+            Fate::Live
+        } else if self.movers.is_empty() {
+            Fate::Die
+        } else {
+            self.search(1, world);
+            Fate::Live
+        }
+    }
+
+    // they don't know yet what they'll earn together, so they start out modestly
+    fn search(&mut self, attempt: u32, world: &mut World) {
+        HousingMarketID::global_first(world).search(
+            DwellingRequest {
+                n_members: self.movers.len() as u32,
+                max_rent: housing::rent_budget(self.movers.len() as u32, amount!(0.0)),
+                workplace: None,
+            },
+            self.id_as(),
+            world,
+        );
+        self.state = HousingSearchState::Searching(attempt);
+    }
+
+    pub fn done(&mut self, _: &mut World) -> Fate {
+        Fate::Die
+    }
+}
+
+impl HousingSeeker for HousingSearch {
+    fn on_dwelling_offer(&mut self, maybe_dwelling: Option<Dwelling>, world: &mut World) {
+        match (self.state, maybe_dwelling) {
+            (HousingSearchState::Searching(_), Some(dwelling)) => {
+                FamilyID::found(
+                    self.movers.clone(),
+                    dwelling,
+                    TimeID::local_first(world),
+                    world,
+                );
+                self.state = HousingSearchState::Settled;
+                self.id.done(world);
+            }
+            (HousingSearchState::Searching(attempt), None) => {
+                DevelopmentManagerID::global_first(world)
+                    .try_develop(BuildingStyle::FamilyHouse, world);
+                TimeID::local_first(world).wake_up_in(
+                    HOUSING_SEARCH_PATIENCE.into(),
+                    self.id.into(),
+                    world,
+                );
+                self.state = HousingSearchState::WaitingForDevelopment(attempt);
+            }
+            (_, Some(dwelling)) => {
+                HousingMarketID::global_first(world).list(dwelling, world);
+            }
+            (_, None) => {}
+        }
+    }
+}
+
+impl Sleeper for HousingSearch {
+    fn wake(&mut self, _: Instant, world: &mut World) {
+        match self.state {
+            HousingSearchState::WaitingForDevelopment(attempt)
+                if attempt < MAX_HOUSING_SEARCH_ATTEMPTS =>
+            {
+                self.search(attempt + 1, world);
+            }
+            HousingSearchState::WaitingForDevelopment(_) => {
+                // they take their contracts with them when leaving the city
+                for mover in self.movers.iter() {
+                    for &Entry(_, offer) in mover.used_offers.iter() {
+                        offer.household.stopped_using(
+                            offer.idx,
+                            mover.origin,
+                            Some(mover.origin_member),
+                            world,
+                        );
+                    }
+                }
+                info(
+                    LOG_T,
+                    format!(
+                        "{} member(s) found no home and leave the city",
+                        self.movers.len()
+                    ),
+                    self.id,
+                    world,
+                );
+                self.id.done(world);
+            }
+            HousingSearchState::Searching(_)
+            | HousingSearchState::Settled
+            | HousingSearchState::Gathering => {}
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct PartnerCandidate {
+    household: HouseholdID,
+    member: MemberIdx,
+    years: u32,
+}
+
+// Pairs up singles of different households, who then
+// move out together to start a new household
+#[derive(Compact, Clone)]
+pub struct Matchmaker {
+    id: MatchmakerID,
+    time: TimeID,
+    // reported anew before every round
+    candidates: CVec<PartnerCandidate>,
+}
+
+impl Matchmaker {
+    pub fn spawn(id: MatchmakerID, time: TimeID, world: &mut World) -> Matchmaker {
+        time.wake_up_in(MATCHMAKING_INTERVAL.into(), id.into(), world);
+
+        Matchmaker {
+            id,
+            time,
+            candidates: CVec::new(),
+        }
+    }
+
+    pub fn looking_for_partner(
+        &mut self,
+        household: HouseholdID,
+        member: MemberIdx,
+        years: u32,
+        _: &mut World,
+    ) {
+        self.candidates.push(PartnerCandidate {
+            household,
+            member,
+            years,
+        });
+    }
+}
+
+impl Sleeper for Matchmaker {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        let mut rng = seed((current_instant.ticks(), self.id));
+        let mut candidates = self.candidates.iter().cloned().collect::<Vec<_>>();
+        rng.shuffle(&mut candidates);
+
+        let mut paired = vec![false; candidates.len()];
+
+        for i in 0..candidates.len() {
+            if paired[i] || rng.gen::<f32>() > PAIRING_CHANCE_PER_YEAR / DAYS_PER_YEAR as f32 {
+                continue;
+            }
+
+            let a = candidates[i];
+            let maybe_partner_idx = (i + 1..candidates.len()).find(|&j| {
+                let b = candidates[j];
+                !paired[j]
+                    && b.household != a.household
+                    && (a.years as i32 - b.years as i32).abs() <= MAX_PARTNER_AGE_GAP as i32
+            });
+
+            if let Some(j) = maybe_partner_idx {
+                paired[i] = true;
+                paired[j] = true;
+                let b = candidates[j];
+
+                debug(
+                    LOG_T,
+                    format!("Pairing up singles aged {} and {}", a.years, b.years),
+                    self.id,
+                    world,
+                );
+
+                let search = HousingSearchID::spawn(2, world);
+                a.household.move_out(a.member, search, world);
+                b.household.move_out(b.member, search, world);
+            }
+        }
+
+        self.candidates = CVec::new();
+        self.time
+            .wake_up_in(MATCHMAKING_INTERVAL.into(), self.id.into(), world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<HousingSearch>();
+    system.register::<Matchmaker>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    MatchmakerID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
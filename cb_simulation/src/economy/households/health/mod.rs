@@ -0,0 +1,75 @@
+use economy::resources::Resource;
+use economy::resources::Resource::*;
+
+// going hungry for longer than this in a day starts to hurt
+const MALNUTRITION_ONSET_HOURS: f32 = 12.0;
+const MALNUTRITION_DAMAGE_PER_DAY: f32 = 0.1;
+const RECOVERY_PER_DAY: f32 = 0.05;
+// untreated sickness gets worse every day, until it is fatal
+const ESCALATION_PER_DAY: f32 = 0.08;
+const SICKNESS_THRESHOLD: f32 = 0.5;
+// this sick, nobody waits for the next doctor's appointment
+const CRITICAL_THRESHOLD: f32 = 0.2;
+const TREATMENT_EFFECT: f32 = 0.3;
+// right next to a mill, breathing its air makes somebody fall sick about once a year
+const SICKNESS_CHANCE_PER_DAY_AND_POLLUTION: f32 = 0.003;
+const FALLING_SICK_CONDITION: f32 = 0.45;
+
+pub fn pollution_sickness_chance(pollution: f32) -> f32 {
+    SICKNESS_CHANCE_PER_DAY_AND_POLLUTION * pollution.max(0.0)
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct Health {
+    // 1.0 is perfectly healthy, 0.0 is dead
+    pub condition: f32,
+}
+
+impl Health {
+    pub fn healthy() -> Health {
+        Health { condition: 1.0 }
+    }
+
+    pub fn is_sick(self) -> bool {
+        self.condition < SICKNESS_THRESHOLD
+    }
+
+    pub fn is_critical(self) -> bool {
+        self.condition < CRITICAL_THRESHOLD
+    }
+
+    pub fn is_fatal(self) -> bool {
+        self.condition <= 0.0
+    }
+
+    pub fn live_through_day(&mut self, hours_without_food: f32) {
+        let malnourished = hours_without_food > MALNUTRITION_ONSET_HOURS;
+
+        if malnourished {
+            self.condition -= MALNUTRITION_DAMAGE_PER_DAY;
+        }
+
+        if self.is_sick() {
+            self.condition -= ESCALATION_PER_DAY;
+        } else if !malnourished {
+            self.condition = (self.condition + RECOVERY_PER_DAY).min(1.0);
+        }
+    }
+
+    pub fn fall_sick(&mut self) {
+        self.condition = self.condition.min(FALLING_SICK_CONDITION);
+    }
+
+    pub fn treat(&mut self) {
+        self.condition = (self.condition + TREATMENT_EFFECT).min(1.0);
+    }
+
+    // sick members stay away from work and look for treatment instead
+    pub fn demand_factor(self, resource: Resource) -> f32 {
+        match (self.is_sick(), resource) {
+            (true, Money) => 0.0,
+            (false, Healthcare) => 0.0,
+            _ => 1.0,
+        }
+    }
+}
@@ -3,7 +3,8 @@ use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks};
 use cb_time::actors::TimeID;
 use economy::resources::Resource;
 use economy::resources::Resource::*;
-use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use economy::market::{Deal, MarketTier, EvaluationRequester, EvaluationRequesterID,
+EvaluatedSearchResult};
 use land_use::buildings::BuildingID;
 
 use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
@@ -28,23 +29,22 @@ impl Bakery {
                 1,
                 site.into(),
                 vec![
-                    Offer::new(
+                    Offer::wholesale(
                         MemberIdx(0),
                         TimeOfDayRange::new(7, 0, 20, 0),
                         Deal::new(
                             vec![
-                                (Resource::BakedGoods, 100.0),
-                                (Resource::Money, -100.0 * 2.5),
+                                (Resource::BakedGoods, amount!(100.0)),
+                                (Resource::Money, amount!(-100.0 * 2.5)),
                             ],
                             Duration::from_minutes(10),
                         ),
                         30,
-                        false,
                     ),
                     Offer::new(
                         MemberIdx(0),
                         TimeOfDayRange::new(5, 0, 15, 0),
-                        Deal::new(Some((Resource::Money, 50.0)), Duration::from_hours(5)),
+                        Deal::new(Some((Resource::Money, amount!(50.0))), Duration::from_hours(5)),
                         3,
                         false,
                     ),
@@ -98,19 +98,23 @@ impl Household for Bakery {
         ]
     }
 
+    fn market_tier(_resource: Resource) -> MarketTier {
+        MarketTier::Wholesale
+    }
+
     fn decay(&mut self, dt: Duration, _: &mut World) {
         {
-            let baked = self.core.resources.mut_entry_or(BakedGoods, 0.0);
+            let baked = self.core.resources.mut_entry_or(BakedGoods, amount!(0.0));
             *baked += 300.0 * dt.as_days();
         }
 
         {
-            let flour = self.core.resources.mut_entry_or(Flour, 0.0);
+            let flour = self.core.resources.mut_entry_or(Flour, amount!(0.0));
             *flour += 300.0 * 0.1 * dt.as_days();
         }
 
         {
-            let dairy = self.core.resources.mut_entry_or(DairyGoods, 0.0);
+            let dairy = self.core.resources.mut_entry_or(DairyGoods, amount!(0.0));
             *dairy += 300.0 * 0.05 * dt.as_days();
         }
     }
@@ -130,14 +134,13 @@ impl Household for Bakery {
 
 use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
 use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+use cb_time::load_spreading;
 const UPDATE_EVERY_N_SECS: u32 = 4;
 
 impl Temporal for Bakery {
     fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id.as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
+        let update_interval = Ticks(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+        if load_spreading::is_turn(self.id.as_raw(), current_instant, update_interval) {
             self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
         }
     }
@@ -163,6 +166,7 @@ impl RoughLocation for Bakery {
 }
 
 use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+use transport::taxis::TaxiOperatorID;
 
 impl TripListener for Bakery {
     fn trip_created(&mut self, trip: TripID, world: &mut World) {
@@ -179,6 +183,14 @@ impl TripListener for Bakery {
     ) {
         self.on_trip_result(trip, result, rough_source, rough_destination, world);
     }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
 }
 
 pub fn setup(system: &mut ActorSystem) {
@@ -0,0 +1,120 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for CarDealer {
+    type ID = CarDealerID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct CarDealerID {
+    _raw_id: RawID
+}
+
+impl Copy for CarDealerID {}
+impl Clone for CarDealerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for CarDealerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "CarDealerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for CarDealerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for CarDealerID {
+    fn eq(&self, other: &CarDealerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for CarDealerID {}
+
+impl TypedID for CarDealerID {
+    type Target = CarDealer;
+
+    fn from_raw(id: RawID) -> Self {
+        CarDealerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl CarDealerID {
+    pub fn move_into(site: BuildingID, time: TimeID, world: &mut World) -> Self {
+        let id = CarDealerID::from_raw(world.allocate_instance_id::<CarDealer>());
+        let swarm = world.local_broadcast::<CarDealer>();
+        world.send(swarm, MSG_CarDealer_move_into(id, site, time));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CarDealer_move_into(pub CarDealerID, pub BuildingID, pub TimeID);
+
+impl Into<HouseholdID> for CarDealerID {
+    fn into(self) -> HouseholdID {
+        HouseholdID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<EvaluationRequesterID> for CarDealerID {
+    fn into(self) -> EvaluationRequesterID {
+        EvaluationRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TemporalID> for CarDealerID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for CarDealerID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<RoughLocationID> for CarDealerID {
+    fn into(self) -> RoughLocationID {
+        RoughLocationID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TripListenerID> for CarDealerID {
+    fn into(self) -> TripListenerID {
+        TripListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HouseholdID::register_implementor::<CarDealer>(system);
+    EvaluationRequesterID::register_implementor::<CarDealer>(system);
+    TemporalID::register_implementor::<CarDealer>(system);
+    SleeperID::register_implementor::<CarDealer>(system);
+    RoughLocationID::register_implementor::<CarDealer>(system);
+    TripListenerID::register_implementor::<CarDealer>(system);
+    system.add_spawner::<CarDealer, _, _>(
+        |&MSG_CarDealer_move_into(id, site, time), world| {
+            CarDealer::move_into(id, site, time, world)
+        }, false
+    );
+}
\ No newline at end of file
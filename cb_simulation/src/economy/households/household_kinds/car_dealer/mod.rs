@@ -0,0 +1,241 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks};
+use cb_time::actors::TimeID;
+use economy::resources::Resource;
+use economy::resources::Resource::*;
+use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use land_use::buildings::BuildingID;
+
+use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
+
+#[derive(Compact, Clone)]
+pub struct CarDealer {
+    id: CarDealerID,
+    site: BuildingID,
+    core: HouseholdCore,
+}
+
+impl CarDealer {
+    pub fn move_into(
+        id: CarDealerID,
+        site: BuildingID,
+        time: TimeID,
+        world: &mut World,
+    ) -> CarDealer {
+        time.wake_up_in(Ticks(0), id.into(), world);
+
+        CarDealer {
+            id,
+            site,
+            core: HouseholdCore::new(
+                id.into(),
+                world,
+                1,
+                site.into(),
+                vec![
+                    // buying a new car, or getting a worn one serviced,
+                    // comes with a full tank
+                    Offer::new(
+                        MemberIdx(0),
+                        TimeOfDayRange::new(9, 0, 17, 0),
+                        Deal::new(
+                            vec![
+                                (Car, amount!(1.0)),
+                                (Fuel, amount!(40.0)),
+                                (Money, amount!(-600.0)),
+                            ],
+                            Duration::from_hours(1),
+                        ),
+                        3,
+                        false,
+                    )
+                    .staffed(1),
+                    Offer::new(
+                        MemberIdx(0),
+                        TimeOfDayRange::new(6, 0, 22, 0),
+                        Deal::new(
+                            vec![(Fuel, amount!(40.0)), (Money, amount!(-40.0 * 1.5))],
+                            Duration::from_minutes(15),
+                        ),
+                        8,
+                        false,
+                    )
+                    .staffed(4),
+                    // bikes are sold and repaired on the side
+                    Offer::new(
+                        MemberIdx(0),
+                        TimeOfDayRange::new(9, 0, 18, 0),
+                        Deal::new(
+                            vec![(Bicycle, amount!(1.0)), (Money, amount!(-80.0))],
+                            Duration::from_minutes(30),
+                        ),
+                        5,
+                        false,
+                    )
+                    .staffed(1),
+                    Offer::new(
+                        MemberIdx(0),
+                        TimeOfDayRange::new(8, 0, 12, 0),
+                        Deal::new(Some((Money, amount!(60.0))), Duration::from_hours(6)),
+                        3,
+                        false,
+                    ),
+                    Offer::new(
+                        MemberIdx(0),
+                        TimeOfDayRange::new(7, 0, 11, 0),
+                        Deal::new(Some((Money, amount!(75.0))), Duration::from_hours(7)),
+                        2,
+                        false,
+                    )
+                    .requiring_education(11.0),
+                ]
+                .into(),
+            ),
+        }
+    }
+}
+
+impl Household for CarDealer {
+    fn core(&self) -> &HouseholdCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut HouseholdCore {
+        &mut self.core
+    }
+
+    fn site(&self) -> RoughLocationID {
+        self.site.into()
+    }
+
+    fn is_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn supplier_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn importance(_: Resource, _: TimeOfDay) -> f32 {
+        0.0
+    }
+
+    fn interesting_resources() -> &'static [Resource] {
+        &[Money, Car, Fuel, Bicycle]
+    }
+
+    // cars, fuel and bikes are delivered from elsewhere
+    fn decay(&mut self, dt: Duration, _: &mut World) {
+        {
+            let cars = self.core.resources.mut_entry_or(Car, amount!(0.0));
+            *cars += 5.0 * dt.as_days();
+        }
+
+        {
+            let fuel = self.core.resources.mut_entry_or(Fuel, amount!(0.0));
+            *fuel += 2000.0 * dt.as_days();
+        }
+
+        {
+            let bicycles = self.core.resources.mut_entry_or(Bicycle, amount!(0.0));
+            *bicycles += 10.0 * dt.as_days();
+        }
+    }
+
+    fn household_name(&self) -> String {
+        "Car Dealer".to_owned()
+    }
+
+    fn member_name(&self, member: MemberIdx) -> String {
+        format!("Car Dealer Worker {}", member.0 + 1)
+    }
+
+    fn on_destroy(&mut self, world: &mut World) {
+        self.site.remove_household(self.id_as(), world);
+    }
+}
+
+use economy::households::ResultAspect;
+
+impl EvaluationRequester for CarDealer {
+    fn expect_n_results(&mut self, resource: Resource, n: u32, world: &mut World) {
+        self.update_results(resource, &ResultAspect::SetTarget(n), world);
+    }
+
+    fn on_result(&mut self, result: &EvaluatedSearchResult, world: &mut World) {
+        let &EvaluatedSearchResult {
+            resource,
+            ref evaluated_deals,
+            ..
+        } = result;
+        self.update_results(
+            resource,
+            &ResultAspect::AddDeals(evaluated_deals.clone()),
+            world,
+        );
+    }
+}
+
+use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
+use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+use cb_time::load_spreading;
+const UPDATE_EVERY_N_SECS: u32 = 4;
+
+impl Temporal for CarDealer {
+    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+        let update_interval = Ticks(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+        if load_spreading::is_turn(self.id.as_raw(), current_instant, update_interval) {
+            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+        }
+    }
+}
+
+impl Sleeper for CarDealer {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        self.update_core(current_instant, world);
+    }
+}
+
+use transport::pathfinding::{RoughLocationID, RoughLocation, RoughLocationResolve};
+
+impl RoughLocation for CarDealer {
+    fn resolve(&self) -> RoughLocationResolve {
+        RoughLocationResolve::SameAs(self.site())
+    }
+}
+
+use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+use transport::taxis::TaxiOperatorID;
+
+impl TripListener for CarDealer {
+    fn trip_created(&mut self, trip: TripID, world: &mut World) {
+        self.on_trip_created(trip, world);
+    }
+
+    fn trip_result(
+        &mut self,
+        trip: TripID,
+        result: TripResult,
+        rough_source: RoughLocationID,
+        rough_destination: RoughLocationID,
+        world: &mut World,
+    ) {
+        self.on_trip_result(trip, result, rough_source, rough_destination, world);
+    }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<CarDealer>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
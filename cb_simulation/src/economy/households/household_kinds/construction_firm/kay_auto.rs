@@ -0,0 +1,168 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for ConstructionFirm {
+    type ID = ConstructionFirmID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ConstructionFirmID {
+    _raw_id: RawID
+}
+
+impl Copy for ConstructionFirmID {}
+impl Clone for ConstructionFirmID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ConstructionFirmID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ConstructionFirmID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ConstructionFirmID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ConstructionFirmID {
+    fn eq(&self, other: &ConstructionFirmID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ConstructionFirmID {}
+
+impl TypedID for ConstructionFirmID {
+    type Target = ConstructionFirm;
+
+    fn from_raw(id: RawID) -> Self {
+        ConstructionFirmID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl ConstructionFirmID {
+    pub fn move_into(site: BuildingID, time: TimeID, world: &mut World) -> Self {
+        let id = ConstructionFirmID::from_raw(world.allocate_instance_id::<ConstructionFirm>());
+        let swarm = world.local_broadcast::<ConstructionFirm>();
+        world.send(swarm, MSG_ConstructionFirm_move_into(id, site, time));
+        id
+    }
+    
+    pub fn tender(self, building: BuildingID, world: &mut World) {
+        world.send(self.as_raw(), MSG_ConstructionFirm_tender(building));
+    }
+    
+    pub fn award(self, building: BuildingID, materials: ResourceAmount, labor: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_ConstructionFirm_award(building, materials, labor));
+    }
+    
+    pub fn project_completed(self, building: BuildingID, world: &mut World) {
+        world.send(self.as_raw(), MSG_ConstructionFirm_project_completed(building));
+    }
+    
+    pub fn project_cancelled(self, building: BuildingID, world: &mut World) {
+        world.send(self.as_raw(), MSG_ConstructionFirm_project_cancelled(building));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ConstructionFirm_move_into(pub ConstructionFirmID, pub BuildingID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ConstructionFirm_tender(pub BuildingID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ConstructionFirm_award(pub BuildingID, pub ResourceAmount, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ConstructionFirm_project_completed(pub BuildingID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ConstructionFirm_project_cancelled(pub BuildingID);
+
+impl Into<HouseholdID> for ConstructionFirmID {
+    fn into(self) -> HouseholdID {
+        HouseholdID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TemporalID> for ConstructionFirmID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for ConstructionFirmID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<EvaluationRequesterID> for ConstructionFirmID {
+    fn into(self) -> EvaluationRequesterID {
+        EvaluationRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<RoughLocationID> for ConstructionFirmID {
+    fn into(self) -> RoughLocationID {
+        RoughLocationID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TripListenerID> for ConstructionFirmID {
+    fn into(self) -> TripListenerID {
+        TripListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HouseholdID::register_implementor::<ConstructionFirm>(system);
+    TemporalID::register_implementor::<ConstructionFirm>(system);
+    SleeperID::register_implementor::<ConstructionFirm>(system);
+    EvaluationRequesterID::register_implementor::<ConstructionFirm>(system);
+    RoughLocationID::register_implementor::<ConstructionFirm>(system);
+    TripListenerID::register_implementor::<ConstructionFirm>(system);
+    system.add_spawner::<ConstructionFirm, _, _>(
+        |&MSG_ConstructionFirm_move_into(id, site, time), world| {
+            ConstructionFirm::move_into(id, site, time, world)
+        }, false
+    );
+    
+    system.add_handler::<ConstructionFirm, _, _>(
+        |&MSG_ConstructionFirm_tender(building), instance, world| {
+            instance.tender(building, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ConstructionFirm, _, _>(
+        |&MSG_ConstructionFirm_award(building, materials, labor), instance, world| {
+            instance.award(building, materials, labor, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ConstructionFirm, _, _>(
+        |&MSG_ConstructionFirm_project_completed(building), instance, world| {
+            instance.project_completed(building, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ConstructionFirm, _, _>(
+        |&MSG_ConstructionFirm_project_cancelled(building), instance, world| {
+            instance.project_cancelled(building, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
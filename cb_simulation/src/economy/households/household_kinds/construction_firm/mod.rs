@@ -0,0 +1,305 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use compact::CVec;
+use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks};
+use cb_time::actors::TimeID;
+use economy::resources::{Resource, ResourceAmount};
+use economy::resources::Resource::*;
+use economy::market::{Deal, MarketTier, EvaluationRequester, EvaluationRequesterID,
+EvaluatedSearchResult};
+use land_use::buildings::BuildingID;
+use super::neighboring_town_trade::trade_price;
+
+use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
+
+const N_WORKERS: u32 = 8;
+const WAGE_PER_SHIFT: ResourceAmount = amount!(60.0);
+const SHIFT_HOURS: usize = 6;
+// what the developers who commissioned a building pay on top of the firm's costs
+const CONTRACT_MARKUP: f32 = 1.2;
+
+#[derive(Compact, Clone)]
+pub struct Project {
+    building: BuildingID,
+    materials_missing: ResourceAmount,
+    labor_missing: f32,
+    price: ResourceAmount,
+}
+
+// Puts up buildings for the city. It bids for new building sites, buys the
+// materials they need wholesale, which its foreman fetches on freight trips,
+// and employs builders. Whatever arrives goes to its oldest project first,
+// so the building there grows as materials and worked hours come in
+#[derive(Compact, Clone)]
+pub struct ConstructionFirm {
+    id: ConstructionFirmID,
+    site: BuildingID,
+    core: HouseholdCore,
+    projects: CVec<Project>,
+}
+
+impl ConstructionFirm {
+    pub fn move_into(
+        id: ConstructionFirmID,
+        site: BuildingID,
+        time: TimeID,
+        world: &mut World,
+    ) -> ConstructionFirm {
+        time.wake_up_in(Ticks(0), id.into(), world);
+
+        ConstructionFirm {
+            id,
+            site,
+            core: HouseholdCore::new(
+                id.into(),
+                world,
+                1,
+                site.into(),
+                vec![Offer::new(
+                    MemberIdx(0),
+                    TimeOfDayRange::new(6, 0, 16, 0),
+                    Deal::new(
+                        Some((Resource::Money, WAGE_PER_SHIFT)),
+                        Duration::from_hours(SHIFT_HOURS),
+                    ),
+                    N_WORKERS,
+                    false,
+                )]
+                .into(),
+            ),
+            projects: CVec::new(),
+        }
+    }
+
+    pub fn tender(&mut self, building: BuildingID, world: &mut World) {
+        building.on_bid(self.id, self.projects.len() as u32, world);
+    }
+
+    pub fn award(
+        &mut self,
+        building: BuildingID,
+        materials: ResourceAmount,
+        labor: f32,
+        _: &mut World,
+    ) {
+        let costs = materials * trade_price(BuildingMaterials).unwrap_or(amount!(0.0))
+            + labor * WAGE_PER_SHIFT / SHIFT_HOURS as f32;
+
+        // lacking the materials makes the foreman go buy them
+        *self.core.resources.mut_entry_or(BuildingMaterials, amount!(0.0)) -= materials;
+
+        self.projects.push(Project {
+            building,
+            materials_missing: materials,
+            labor_missing: labor,
+            price: costs * CONTRACT_MARKUP,
+        });
+    }
+
+    pub fn project_completed(&mut self, building: BuildingID, _: &mut World) {
+        if let Some(project) = self.remove_project(building) {
+            *self.core.resources.mut_entry_or(Money, amount!(0.0)) += project.price;
+        }
+    }
+
+    // The building was torn down before it was finished
+    pub fn project_cancelled(&mut self, building: BuildingID, _: &mut World) {
+        self.remove_project(building);
+    }
+
+    fn remove_project(&mut self, building: BuildingID) -> Option<Project> {
+        let position = self
+            .projects
+            .iter()
+            .position(|project| project.building == building)?;
+        let project = self.projects.remove(position);
+
+        // what was still to be bought for it isn't needed anymore
+        *self.core.resources.mut_entry_or(BuildingMaterials, amount!(0.0)) +=
+            project.materials_missing;
+        Some(project)
+    }
+
+    fn deliver_materials(&mut self, mut materials: ResourceAmount, world: &mut World) {
+        for project in self.projects.iter_mut() {
+            let delivered = materials.min(project.materials_missing);
+            if delivered > 0.0 {
+                project.materials_missing -= delivered;
+                materials -= delivered;
+                project.building.advance_construction(delivered, 0.0, world);
+            }
+        }
+    }
+
+    fn put_in_labor(&mut self, mut labor: f32, world: &mut World) {
+        for project in self.projects.iter_mut() {
+            let worked = labor.min(project.labor_missing);
+            if worked > 0.0 {
+                project.labor_missing -= worked;
+                labor -= worked;
+                project.building.advance_construction(amount!(0.0), worked, world);
+            }
+        }
+    }
+}
+
+impl Household for ConstructionFirm {
+    fn core(&self) -> &HouseholdCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut HouseholdCore {
+        &mut self.core
+    }
+
+    fn site(&self) -> RoughLocationID {
+        self.site.into()
+    }
+
+    fn is_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn supplier_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn importance(resource: Resource, time: TimeOfDay) -> f32 {
+        let hour = time.hours_minutes().0;
+
+        let bihourly_importance = match resource {
+            BuildingMaterials => Some([0, 0, 0, 1, 1, 1, 1, 1, 1, 0, 0, 0]),
+            _ => None,
+        };
+
+        bihourly_importance
+            .map(|lookup| lookup[hour / 2] as f32)
+            .unwrap_or(0.0)
+    }
+
+    fn interesting_resources() -> &'static [Resource] {
+        &[Resource::Money, Resource::BuildingMaterials]
+    }
+
+    fn market_tier(_resource: Resource) -> MarketTier {
+        MarketTier::Wholesale
+    }
+
+    fn decay(&mut self, _dt: Duration, _: &mut World) {}
+
+    fn receive_deal(&mut self, deal: &Deal, member: MemberIdx, world: &mut World) {
+        {
+            let core = &mut self.core;
+            deal.delta.give_to_shared_private(
+                &mut core.resources,
+                &mut core.member_resources[member.as_idx()],
+                Self::is_shared,
+            );
+        }
+
+        if let Some(&materials) = deal.delta.get(BuildingMaterials) {
+            if materials > 0.0 {
+                self.deliver_materials(materials, world);
+            }
+        }
+    }
+
+    // the only deals it provides are the wages of builders after their shift
+    fn provide_deal(&mut self, deal: &Deal, member: MemberIdx, world: &mut World) {
+        {
+            let core = &mut self.core;
+            deal.delta.take_from_shared_private(
+                &mut core.resources,
+                &mut core.member_resources[member.as_idx()],
+                Self::is_shared,
+            );
+        }
+
+        if deal.delta.get(Money).map_or(false, |&wage| wage > 0.0) {
+            self.put_in_labor(deal.duration.as_hours(), world);
+        }
+    }
+
+    fn on_destroy(&mut self, world: &mut World) {
+        for project in self.projects.iter() {
+            project.building.contractor_gone(self.id, world);
+        }
+        self.site.remove_household(self.id_as(), world);
+    }
+
+    fn household_name(&self) -> String {
+        "Construction Firm".to_owned()
+    }
+
+    fn member_name(&self, member: MemberIdx) -> String {
+        format!("Foreman {}", member.0 + 1)
+    }
+}
+
+use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
+use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+use cb_time::load_spreading;
+const UPDATE_EVERY_N_SECS: u32 = 4;
+
+impl Temporal for ConstructionFirm {
+    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+        let update_interval = Ticks(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+        if load_spreading::is_turn(self.id.as_raw(), current_instant, update_interval) {
+            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+        }
+    }
+}
+
+impl Sleeper for ConstructionFirm {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        self.update_core(current_instant, world);
+    }
+}
+
+impl EvaluationRequester for ConstructionFirm {
+    fn expect_n_results(&mut self, _r: Resource, _n: u32, _: &mut World) {}
+    fn on_result(&mut self, _e: &EvaluatedSearchResult, _: &mut World) {}
+}
+
+use transport::pathfinding::{RoughLocationID, RoughLocation, RoughLocationResolve};
+
+impl RoughLocation for ConstructionFirm {
+    fn resolve(&self) -> RoughLocationResolve {
+        RoughLocationResolve::SameAs(self.site())
+    }
+}
+
+use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+use transport::taxis::TaxiOperatorID;
+
+impl TripListener for ConstructionFirm {
+    fn trip_created(&mut self, trip: TripID, world: &mut World) {
+        self.on_trip_created(trip, world);
+    }
+
+    fn trip_result(
+        &mut self,
+        trip: TripID,
+        result: TripResult,
+        rough_source: RoughLocationID,
+        rough_destination: RoughLocationID,
+        world: &mut World,
+    ) {
+        self.on_trip_result(trip, result, rough_source, rough_destination, world);
+    }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<ConstructionFirm>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -3,7 +3,8 @@ use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks};
 use cb_time::actors::TimeID;
 use economy::resources::Resource;
 use economy::resources::Resource::*;
-use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use economy::market::{Deal, MarketTier, EvaluationRequester, EvaluationRequesterID,
+EvaluatedSearchResult};
 use land_use::buildings::BuildingID;
 
 use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
@@ -28,33 +29,34 @@ impl CowFarm {
                 1,
                 site.into(),
                 vec![
-                    Offer::new(
+                    Offer::wholesale(
                         MemberIdx(0),
                         TimeOfDayRange::new(7, 0, 20, 0),
                         Deal::new(
-                            vec![(Resource::Meat, 5.0), (Resource::Money, -5.0 * 3.0)],
+                            vec![
+                                (Resource::Meat, amount!(5.0)),
+                                (Resource::Money, amount!(-5.0 * 3.0)),
+                            ],
                             Duration::from_minutes(10),
                         ),
                         4,
-                        false,
                     ),
-                    Offer::new(
+                    Offer::wholesale(
                         MemberIdx(0),
                         TimeOfDayRange::new(7, 0, 20, 0),
                         Deal::new(
                             vec![
-                                (Resource::DairyGoods, 10.0),
-                                (Resource::Money, -10.0 * 1.31),
+                                (Resource::DairyGoods, amount!(10.0)),
+                                (Resource::Money, amount!(-10.0 * 1.31)),
                             ],
                             Duration::from_minutes(10),
                         ),
                         4,
-                        false,
                     ),
                     Offer::new(
                         MemberIdx(0),
                         TimeOfDayRange::new(5, 0, 15, 0),
-                        Deal::new(Some((Resource::Money, 40.0)), Duration::from_hours(4)),
+                        Deal::new(Some((Resource::Money, amount!(40.0))), Duration::from_hours(4)),
                         2,
                         false,
                     ),
@@ -108,19 +110,23 @@ impl Household for CowFarm {
         ]
     }
 
+    fn market_tier(_resource: Resource) -> MarketTier {
+        MarketTier::Wholesale
+    }
+
     fn decay(&mut self, dt: Duration, _: &mut World) {
         {
-            let meat = self.core.resources.mut_entry_or(Meat, 0.0);
+            let meat = self.core.resources.mut_entry_or(Meat, amount!(0.0));
             *meat += 20.0 * dt.as_days();
         }
 
         {
-            let dairy = self.core.resources.mut_entry_or(DairyGoods, 0.0);
+            let dairy = self.core.resources.mut_entry_or(DairyGoods, amount!(0.0));
             *dairy += 40.0 * dt.as_days();
         }
 
         {
-            let grain = self.core.resources.mut_entry_or(Grain, 0.0);
+            let grain = self.core.resources.mut_entry_or(Grain, amount!(0.0));
             *grain -= (20.0 * 0.2 + 40.0 * 0.1) * dt.as_days();
         }
     }
@@ -140,14 +146,13 @@ impl Household for CowFarm {
 
 use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
 use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+use cb_time::load_spreading;
 const UPDATE_EVERY_N_SECS: u32 = 4;
 
 impl Temporal for CowFarm {
     fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id.as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
+        let update_interval = Ticks(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+        if load_spreading::is_turn(self.id.as_raw(), current_instant, update_interval) {
             self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
         }
     }
@@ -173,6 +178,7 @@ impl RoughLocation for CowFarm {
 }
 
 use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+use transport::taxis::TaxiOperatorID;
 
 impl TripListener for CowFarm {
     fn trip_created(&mut self, trip: TripID, world: &mut World) {
@@ -189,6 +195,14 @@ impl TripListener for CowFarm {
     ) {
         self.on_trip_result(trip, result, rough_source, rough_destination, world);
     }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
 }
 
 pub fn setup(system: &mut ActorSystem) {
@@ -55,16 +55,37 @@ impl TypedID for FamilyID {
 }
 
 impl FamilyID {
-    pub fn move_into(n_members: u32, home: BuildingID, time: TimeID, world: &mut World) -> Self {
+    pub fn move_into(archetype: HouseholdArchetype, members: CVec < ( MemberLife , f32 ) >, home: Dwelling, arrival: RoughLocationID, time: TimeID, world: &mut World) -> Self {
         let id = FamilyID::from_raw(world.allocate_instance_id::<Family>());
         let swarm = world.local_broadcast::<Family>();
-        world.send(swarm, MSG_Family_move_into(id, n_members, home, time));
+        world.send(swarm, MSG_Family_move_into(id, archetype, members, home, arrival, time));
         id
     }
+    
+    pub fn found(movers: CVec < MovingMember >, home: Dwelling, time: TimeID, world: &mut World) -> Self {
+        let id = FamilyID::from_raw(world.allocate_instance_id::<Family>());
+        let swarm = world.local_broadcast::<Family>();
+        world.send(swarm, MSG_Family_found(id, movers, home, time));
+        id
+    }
+    
+    pub fn hear_of_festival(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Family_hear_of_festival());
+    }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Family_move_into(pub FamilyID, pub u32, pub BuildingID, pub TimeID);
+struct MSG_Family_move_into(pub FamilyID, pub HouseholdArchetype, pub CVec < ( MemberLife , f32 ) >, pub Dwelling, pub RoughLocationID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Family_found(pub FamilyID, pub CVec < MovingMember >, pub Dwelling, pub TimeID);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Family_hear_of_festival();
+
+impl Into<HousingSeekerID> for FamilyID {
+    fn into(self) -> HousingSeekerID {
+        HousingSeekerID::from_raw(self.as_raw())
+    }
+}
 
 impl Into<SleeperID> for FamilyID {
     fn into(self) -> SleeperID {
@@ -96,25 +117,52 @@ impl Into<TemporalID> for FamilyID {
     }
 }
 
+impl Into<EmergencyCallerID> for FamilyID {
+    fn into(self) -> EmergencyCallerID {
+        EmergencyCallerID::from_raw(self.as_raw())
+    }
+}
+
 impl Into<RoughLocationID> for FamilyID {
     fn into(self) -> RoughLocationID {
         RoughLocationID::from_raw(self.as_raw())
     }
 }
 
+impl Into<ResidentID> for FamilyID {
+    fn into(self) -> ResidentID {
+        ResidentID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
+    HousingSeekerID::register_implementor::<Family>(system);
     SleeperID::register_implementor::<Family>(system);
     EvaluationRequesterID::register_implementor::<Family>(system);
     TripListenerID::register_implementor::<Family>(system);
     HouseholdID::register_implementor::<Family>(system);
     TemporalID::register_implementor::<Family>(system);
+    EmergencyCallerID::register_implementor::<Family>(system);
     RoughLocationID::register_implementor::<Family>(system);
+    ResidentID::register_implementor::<Family>(system);
+    system.add_spawner::<Family, _, _>(
+        |&MSG_Family_move_into(id, ref archetype, ref members, home, arrival, time), world| {
+            Family::move_into(id, archetype, members, home, arrival, time, world)
+        }, false
+    );
+    
     system.add_spawner::<Family, _, _>(
-        |&MSG_Family_move_into(id, n_members, home, time), world| {
-            Family::move_into(id, n_members, home, time, world)
+        |&MSG_Family_found(id, ref movers, home, time), world| {
+            Family::found(id, movers, home, time, world)
+        }, false
+    );
+    
+    system.add_handler::<Family, _, _>(
+        |&MSG_Family_hear_of_festival(), instance, world| {
+            instance.hear_of_festival(world); Fate::Live
         }, false
     );
 }
\ No newline at end of file
@@ -1,9 +1,12 @@
-use kay::{ActorSystem, World, Actor};
+use kay::{ActorSystem, World, TypedID, Actor};
+use compact::CVec;
 use cb_util::random::{seed, Rng};
+use cb_util::log::info;
+const LOG_T: &str = "Family";
 
 use cb_time::actors::{Temporal, TemporalID, TimeID};
 use cb_time::units::{TimeOfDay, TimeOfDayRange, Instant, Duration, Ticks};
-use economy::resources::Resource;
+use economy::resources::{Resource, ResourceAmount, ResourceMap, Entry, amount_to_f32};
 use economy::resources::Resource::*;
 use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
 use land_use::buildings::BuildingID;
@@ -13,50 +16,311 @@ use transport::pathfinding::RoughLocationID;
 pub mod names;
 use self::names::{family_name, member_name};
 
-use economy::households::{Household, HouseholdID, HouseholdCore,
-MemberIdx, Offer, OfferID, OfferIdx};
+use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
+use economy::households::aging::{LifeStage, MemberLife};
+use economy::households::formation::MovingMember;
+use economy::households::archetypes::HouseholdArchetype;
+use economy::households::schedule::sleep_window;
+use economy::housing::{self, Dwelling, DwellingRequest, HousingMarketID, HousingSeeker,
+HousingSeekerID};
+
+// a car lasts for a couple of (compressed) years without servicing
+const CAR_WEAR_PER_DAY: f32 = 0.02;
+// bikes need a repair less often
+const BIKE_WEAR_PER_DAY: f32 = 0.005;
+// families with a longer average commute start looking for a home closer to work
+const MAX_ACCEPTABLE_COMMUTE_MINUTES: f32 = 60.0;
+// chance of an unsatisfied family to start looking at each wellbeing check,
+// so that they don't all move at once
+const RELOCATION_CHANCE: f32 = 0.1;
+// evicted families that find nothing they can afford in time leave the city
+const MAX_HOMELESS_DURATION: Duration = Duration(3 * 24 * 60 * 60);
+// sleeping rough is less restful and lonelier than being at home
+const HOMELESS_HARDSHIP: f32 = 1.5;
+// a home without power or water is less restful, and meals are cold
+const OUTAGE_HARDSHIP: f32 = 1.2;
+// how much more everyone wants to go out when there's a festival in town
+const FESTIVAL_CRAVING: f32 = 1.0;
 
 #[derive(Compact, Clone)]
 pub struct Family {
     id: FamilyID,
     home: BuildingID,
+    rent: ResourceAmount,
+    relocating: bool,
+    // evicted families live on the street in front of their former home
+    homeless_since: Option<Instant>,
     core: HouseholdCore,
+    // taken over from the archetype the family immigrated as
+    consumption: ResourceMap<f32>,
+    sleep: Option<TimeOfDayRange>,
+}
+
+fn home_offers() -> CVec<Offer> {
+    vec![
+        Offer::private(
+            MemberIdx(0),
+            TimeOfDayRange::new(16, 0, 11, 0),
+            Deal::new(Some((Wakefulness, amount!(3.0))), Duration::from_hours(1)),
+            1,
+        ),
+        Offer::private(
+            MemberIdx(0),
+            TimeOfDayRange::new(17, 0, 22, 0),
+            Deal::new(Some((Companionship, amount!(1.5))), Duration::from_hours(1)),
+            1,
+        ),
+        Offer::private(
+            MemberIdx(0),
+            TimeOfDayRange::new(18, 0, 23, 0),
+            Deal::new(Some((Entertainment, amount!(1.0))), Duration::from_hours(2)),
+            1,
+        ),
+        // friends coming over are better company than the family alone
+        Offer::social(
+            MemberIdx(0),
+            TimeOfDayRange::new(15, 0, 21, 0),
+            Deal::new(
+                vec![(Companionship, amount!(2.5)), (Entertainment, amount!(1.0))],
+                Duration::from_hours(2),
+            ),
+            4,
+        ),
+    ]
+    .into()
 }
 
 impl Family {
+    // the members are generated beforehand, to find a home with enough room for them
     pub fn move_into(
         id: FamilyID,
-        n_members: u32,
-        home: BuildingID,
+        archetype: &HouseholdArchetype,
+        members: &CVec<(MemberLife, f32)>,
+        home: Dwelling,
+        arrival: RoughLocationID,
         time: TimeID,
         world: &mut World,
     ) -> Family {
         time.wake_up_in(Ticks(0), id.into(), world);
+        housing::move_in(id.into(), home, world);
+
+        let mut rng = seed(id);
+
+        let mut core = HouseholdCore::new(id.into(), world, members.len(), arrival, home_offers());
+
+        for (i, &(life, education)) in members.iter().enumerate() {
+            core.member_lives[i] = life;
+            core.member_education[i] = education;
+        }
+
+        if rng.gen::<f32>() < archetype.car_ownership {
+            *core.resources.mut_entry_or(Car, amount!(0.0)) = amount!(1.0);
+            *core.resources.mut_entry_or(Fuel, amount!(0.0)) = amount!(20.0);
+        }
+        if rng.gen::<f32>() < archetype.bike_ownership {
+            *core.resources.mut_entry_or(Bicycle, amount!(0.0)) = amount!(1.0);
+        }
+
+        Family {
+            id,
+            home: home.building,
+            rent: home.rent,
+            relocating: false,
+            homeless_since: None,
+            core,
+            consumption: archetype.consumption.clone(),
+            sleep: archetype.sleep,
+        }
+    }
+
+    // a new family formed by members leaving their old households,
+    // who bring along their belongings and keep their jobs and school places
+    pub fn found(
+        id: FamilyID,
+        movers: &CVec<MovingMember>,
+        home: Dwelling,
+        time: TimeID,
+        world: &mut World,
+    ) -> Family {
+        time.wake_up_in(Ticks(0), id.into(), world);
+        housing::move_in(id.into(), home, world);
 
         let mut core = HouseholdCore::new(
             id.into(),
             world,
-            n_members as usize,
-            home.into(),
-            vec![Offer::new(
-                MemberIdx(0),
-                TimeOfDayRange::new(16, 0, 11, 0),
-                Deal::new(Some((Wakefulness, 3.0)), Duration::from_hours(1)),
-                1,
-                true,
-            )]
-            .into(),
+            movers.len(),
+            movers[0].location,
+            home_offers(),
         );
 
-        core.used_offers.insert(
-            Wakefulness,
-            OfferID {
-                household: id.into(),
-                idx: OfferIdx(0),
+        for (i, mover) in movers.iter().enumerate() {
+            let member = MemberIdx::new(i);
+            core.take_in(member, mover);
+
+            for &Entry(_, offer) in mover.used_offers.iter() {
+                offer.household.transfer_use(
+                    offer.idx,
+                    mover.origin,
+                    Some(mover.origin_member),
+                    id.into(),
+                    Some(member),
+                    world,
+                );
+            }
+        }
+
+        let family = Family {
+            id,
+            home: home.building,
+            rent: home.rent,
+            relocating: false,
+            homeless_since: None,
+            core,
+            consumption: ResourceMap::new(),
+            sleep: None,
+        };
+
+        info(
+            LOG_T,
+            format!("{} moves into their new home", family.household_name()),
+            id,
+            world,
+        );
+
+        family
+    }
+
+    // Everybody wants to go and have a look, so parks, restaurants
+    // and cinemas get a lot more visitors for a while
+    pub fn hear_of_festival(&mut self, _: &mut World) {
+        for member_resources in self.core.member_resources.iter_mut() {
+            *member_resources.mut_entry_or(Entertainment, amount!(0.0)) -= FESTIVAL_CRAVING;
+        }
+    }
+
+    fn daily_wages(&self) -> ResourceAmount {
+        self.core
+            .member_used_offers
+            .iter()
+            .filter_map(|used_offers| used_offers.get(Money))
+            .filter_map(|&offer| self.core.known_deal(offer))
+            .filter_map(|known| known.deal.delta.get(Money).cloned())
+            .sum()
+    }
+
+    fn workplace(&self) -> Option<RoughLocationID> {
+        self.core
+            .member_used_offers
+            .iter()
+            .filter_map(|used_offers| used_offers.get(Money))
+            .map(|offer| offer.household.into())
+            .next()
+    }
+
+    // Families whose rent has become more than they can afford
+    // or whose commute takes too long look for a better home.
+    // Homeless families keep looking for anything cheaper than before
+    fn consider_relocating(&mut self, instant: Instant, world: &mut World) {
+        if self.relocating || self.core.being_destroyed || !self.core.departures.is_empty() {
+            return;
+        }
+
+        let n_members = self.core.n_present_members() as u32;
+        let budget = housing::rent_budget(n_members, self.daily_wages());
+
+        if let Some(homeless_since) = self.homeless_since {
+            if instant >= homeless_since + MAX_HOMELESS_DURATION {
+                info(
+                    LOG_T,
+                    format!("{} found no home they can afford", self.household_name()),
+                    self.id,
+                    world,
+                );
+                self.emigrate(world);
+            } else {
+                self.relocating = true;
+                HousingMarketID::global_first(world).search(
+                    DwellingRequest {
+                        n_members,
+                        max_rent: budget.min(self.rent),
+                        workplace: None,
+                    },
+                    self.id_as(),
+                    world,
+                );
+            }
+            return;
+        }
+
+        let reason = if self.rent > budget {
+            "can't afford their rent anymore"
+        } else if self
+            .core
+            .average_commute_minutes
+            .map_or(false, |minutes| minutes > MAX_ACCEPTABLE_COMMUTE_MINUTES)
+        {
+            "want to live closer to work"
+        } else {
+            return;
+        };
+
+        if seed((instant.ticks(), self.id)).gen::<f32>() > RELOCATION_CHANCE {
+            return;
+        }
+
+        info(
+            LOG_T,
+            format!(
+                "{} {} and look for a new home",
+                self.household_name(),
+                reason
+            ),
+            self.id,
+            world,
+        );
+        self.relocating = true;
+        HousingMarketID::global_first(world).search(
+            DwellingRequest {
+                n_members,
+                max_rent: budget,
+                workplace: self.workplace(),
             },
+            self.id_as(),
+            world,
         );
+    }
+}
+
+impl HousingSeeker for Family {
+    fn on_dwelling_offer(&mut self, maybe_dwelling: Option<Dwelling>, world: &mut World) {
+        self.relocating = false;
+
+        if let Some(dwelling) = maybe_dwelling {
+            // the family might have grown while looking
+            let still_fits = dwelling.capacity as usize >= self.core.n_present_members();
+
+            if self.core.being_destroyed || !still_fits {
+                HousingMarketID::global_first(world).list(dwelling, world);
+                return;
+            }
 
-        Family { id, home, core }
+            let old_site = self.site();
+            if self.homeless_since.is_none() {
+                self.home.remove_household(self.id_as(), world);
+            }
+            housing::move_in(self.id_as(), dwelling, world);
+            self.home = dwelling.building;
+            self.rent = dwelling.rent;
+            self.homeless_since = None;
+            self.on_relocated(old_site, world);
+
+            info(
+                LOG_T,
+                format!("{} moved to a new home", self.household_name()),
+                self.id,
+                world,
+            );
+        }
     }
 }
 
@@ -90,6 +354,7 @@ impl EvaluationRequester for Family {
 }
 
 use transport::pathfinding::trip::{TripListener, TripID};
+use transport::taxis::TaxiOperatorID;
 
 impl TripListener for Family {
     fn trip_created(&mut self, trip: TripID, world: &mut World) {
@@ -106,6 +371,14 @@ impl TripListener for Family {
     ) {
         self.on_trip_result(trip, result, rough_source, rough_destination, world);
     }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
 }
 
 impl Household for Family {
@@ -123,28 +396,45 @@ impl Household for Family {
 
     fn is_shared(resource: Resource) -> bool {
         match resource {
-            Wakefulness | Satiety => false,
-            Money | Groceries => true,
+            Wakefulness | Satiety | Companionship | Entertainment | Education | Healthcare => false,
+            Money | Groceries | Car | Fuel | Bicycle => true,
             _ => unimplemented!(),
         }
     }
 
     fn supplier_shared(resource: Resource) -> bool {
         match resource {
-            Money => false,
-            Wakefulness | Satiety | Groceries => true,
+            // each student or patient takes up a place of their own
+            Money | Education | Healthcare => false,
+            Wakefulness | Satiety | Companionship | Entertainment | Groceries | Car | Fuel
+            | Bicycle => true,
             _ => unimplemented!(),
         }
     }
 
+    fn has_lifecycle() -> bool {
+        true
+    }
+
+    fn can_open_without_utilities() -> bool {
+        true
+    }
+
     fn importance(resource: Resource, time: TimeOfDay) -> f32 {
         let hour = time.hours_minutes().0;
 
         let bihourly_importance = match resource {
             Wakefulness => Some([7, 7, 7, 7, 5, 5, 5, 5, 5, 5, 7, 7]),
             Satiety => Some([0, 0, 5, 5, 1, 5, 5, 1, 5, 5, 1, 1]),
+            Companionship => Some([0, 0, 1, 1, 1, 1, 1, 1, 3, 4, 4, 1]),
+            Entertainment => Some([0, 0, 0, 0, 1, 1, 1, 1, 2, 3, 3, 2]),
+            Education => Some([0, 0, 0, 5, 5, 3, 0, 0, 0, 0, 0, 0]),
+            Healthcare => Some([2, 2, 2, 8, 8, 8, 8, 8, 8, 8, 2, 2]),
             Money => Some([0, 0, 3, 3, 5, 5, 5, 3, 3, 1, 1, 1]),
             Groceries => Some([0, 0, 4, 4, 1, 4, 4, 4, 4, 4, 0, 0]),
+            Car => Some([0, 0, 0, 0, 1, 1, 1, 1, 1, 0, 0, 0]),
+            Fuel => Some([0, 0, 0, 2, 2, 2, 2, 2, 2, 2, 2, 0]),
+            Bicycle => Some([0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0]),
             _ => None,
         };
 
@@ -153,12 +443,28 @@ impl Household for Family {
             .unwrap_or(0.0)
     }
 
+    fn graveness(resource: Resource, amount: ResourceAmount, time: TimeOfDay) -> f32 {
+        let amount = amount_to_f32(amount);
+        match resource {
+            // a car or bike isn't used up, not having a working one is the problem
+            Car | Bicycle => (1.0 - amount).max(0.0) * Self::importance(resource, time),
+            _ => -amount * Self::importance(resource, time),
+        }
+    }
+
     fn interesting_resources() -> &'static [Resource] {
         &[
             Wakefulness,
-            Satiety, //Entertainment,
+            Satiety,
+            Companionship,
+            Entertainment,
+            Education,
+            Healthcare,
             Money,
             Groceries,
+            Car,
+            Fuel,
+            Bicycle,
             /* Furniture,
              *Clothes,
              *Devices,
@@ -167,27 +473,76 @@ impl Household for Family {
     }
 
     fn decay(&mut self, dt: Duration, _: &mut World) {
+        let consumption = &self.consumption;
+        let hardship = if self.homeless_since.is_some() {
+            HOMELESS_HARDSHIP
+        } else {
+            1.0
+        };
+        let outage_hardship = if self.core.cut_off_from.is_empty() {
+            1.0
+        } else {
+            OUTAGE_HARDSHIP
+        };
+        let rate = |resource| {
+            let rate = consumption.get(resource).cloned().unwrap_or(1.0);
+            match resource {
+                Wakefulness => rate * hardship * outage_hardship,
+                Companionship => rate * hardship,
+                Satiety => rate * outage_hardship,
+                _ => rate,
+            }
+        };
+        let member_lives = &self.core.member_lives;
         for (i, member_resources) in self.core.member_resources.iter_mut().enumerate() {
+            if !member_lives[i].is_present() {
+                continue;
+            }
             {
                 let individuality = seed((self.id, i)).gen_range(0.8, 1.2);
-                let wakefulness = member_resources.mut_entry_or(Wakefulness, 0.0);
-                *wakefulness -= 1.0 * individuality * dt.as_hours();
+                let wakefulness = member_resources.mut_entry_or(Wakefulness, amount!(0.0));
+                *wakefulness -= 1.0 * rate(Wakefulness) * individuality * dt.as_hours();
             }
             {
                 let individuality = seed((self.id, i, 1u8)).gen_range(0.8, 1.2);
-                let satiety = member_resources.mut_entry_or(Satiety, 0.0);
+                let satiety = member_resources.mut_entry_or(Satiety, amount!(0.0));
                 if *satiety < 0.0 {
-                    let groceries = self.core.resources.mut_entry_or(Groceries, 0.0);
+                    let groceries = self.core.resources.mut_entry_or(Groceries, amount!(0.0));
                     *groceries -= 1.0;
                     *satiety += 1.0;
                 }
-                *satiety -= 3.0 * individuality * dt.as_days();
+                *satiety -= 3.0 * rate(Satiety) * individuality * dt.as_days();
+            }
+            {
+                let individuality = seed((self.id, i, 2u8)).gen_range(0.8, 1.2);
+                let companionship = member_resources.mut_entry_or(Companionship, amount!(0.0));
+                *companionship -= 2.0 * rate(Companionship) * individuality * dt.as_days();
+            }
+            {
+                let individuality = seed((self.id, i, 3u8)).gen_range(0.8, 1.2);
+                let entertainment = member_resources.mut_entry_or(Entertainment, amount!(0.0));
+                *entertainment -= 1.5 * rate(Entertainment) * individuality * dt.as_days();
+            }
+            {
+                // only students care about this, see `LifeStage::demand_factor`
+                let education = member_resources.mut_entry_or(Education, amount!(0.0));
+                *education -= 1.0 * dt.as_days();
+            }
+            {
+                // only the sick care about this, see `Health::demand_factor`.
+                // Capped, so that falling sick after years of health isn't an emergency
+                let healthcare = member_resources.mut_entry_or(Healthcare, amount!(0.0));
+                *healthcare = (*healthcare - 1.0 * dt.as_days()).max(-1.0);
             }
-            // {
-            //     let individuality = seed((self.id, i)).gen_range(0.8, 1.2);
-            //     let entertainment = member_resources.mut_entry_or(Entertainment, 0.0);
-            //     *entertainment -= 0.2 * individuality * dt.as_hours();
-            // }
+        }
+        {
+            // servicing or replacing a car can't make it better than new
+            let car = self.core.resources.mut_entry_or(Car, amount!(0.0));
+            *car = (car.min(1.0) - CAR_WEAR_PER_DAY * dt.as_days()).max(0.0);
+        }
+        {
+            let bicycle = self.core.resources.mut_entry_or(Bicycle, amount!(0.0));
+            *bicycle = (bicycle.min(1.0) - BIKE_WEAR_PER_DAY * dt.as_days()).max(0.0);
         }
         // {
         //     let individuality = seed(self.id).gen_range(0.8, 1.2);
@@ -206,8 +561,36 @@ impl Household for Family {
         // }
     }
 
+    // night owls and early birds only differ once they're grown up
+    fn sleep_window(&self, stage: LifeStage) -> TimeOfDayRange {
+        match (stage, self.sleep) {
+            (LifeStage::Worker, Some(window)) | (LifeStage::Retiree, Some(window)) => window,
+            _ => sleep_window(stage),
+        }
+    }
+
     fn on_destroy(&mut self, world: &mut World) {
+        if self.homeless_since.is_none() {
+            self.home.remove_household(self.id_as(), world);
+            HousingMarketID::global_first(world).end_lease(self.id_as(), world);
+        }
+    }
+
+    // The dwelling is vacated right away and they start looking for a cheaper one
+    fn evicted(&mut self, instant: Instant, world: &mut World) {
+        if self.core.being_destroyed || self.homeless_since.is_some() {
+            return;
+        }
+
+        info(
+            LOG_T,
+            format!("{} got evicted and is homeless now", self.household_name()),
+            self.id,
+            world,
+        );
         self.home.remove_household(self.id_as(), world);
+        self.homeless_since = Some(instant);
+        self.consider_relocating(instant, world);
     }
 
     fn household_name(&self) -> String {
@@ -217,6 +600,20 @@ impl Household for Family {
     fn member_name(&self, member: MemberIdx) -> String {
         member_name(self.id, member)
     }
+
+    fn call_ambulance(&mut self, member: MemberIdx, instant: Instant, world: &mut World) {
+        EmergencyDispatchID::global_first(world).call(
+            self.site(),
+            self.id_as(),
+            member.as_idx() as u8,
+            instant,
+            world,
+        );
+    }
+
+    fn is_homeless(&self) -> bool {
+        self.homeless_since.is_some()
+    }
 }
 
 impl Temporal for Family {
@@ -225,6 +622,34 @@ impl Temporal for Family {
     }
 }
 
+use transport::emergency::{EmergencyCaller, EmergencyCallerID, EmergencyDispatchID};
+
+// paramedics treat the member right where they are
+impl EmergencyCaller for Family {
+    fn responders_arrived(
+        &mut self,
+        tag: u8,
+        response_time: Duration,
+        _instant: Instant,
+        world: &mut World,
+    ) {
+        let member = MemberIdx::new(tag as usize);
+        if self.core.member_lives[member.as_idx()].is_present() {
+            self.core.member_health[member.as_idx()].treat();
+            info(
+                LOG_T,
+                format!(
+                    "Paramedics treated {} after {:.0} min",
+                    self.member_name(member),
+                    response_time.as_minutes()
+                ),
+                self.id,
+                world,
+            );
+        }
+    }
+}
+
 use transport::pathfinding::{RoughLocation, RoughLocationResolve};
 
 impl RoughLocation for Family {
@@ -233,6 +658,39 @@ impl RoughLocation for Family {
     }
 }
 
+use economy::migration::{Resident, ResidentID, MigrationID};
+
+impl Resident for Family {
+    fn report_wellbeing(&mut self, migration: MigrationID, instant: Instant, world: &mut World) {
+        if self.core.n_present_members() == 0 {
+            return;
+        }
+
+        migration.on_wellbeing(
+            self.id_as(),
+            self.core.average_commute_minutes,
+            self.core.happiness,
+            world,
+        );
+
+        self.consider_relocating(instant, world);
+    }
+
+    fn emigrate(&mut self, world: &mut World) {
+        if self.core.being_destroyed {
+            return;
+        }
+
+        info(
+            LOG_T,
+            format!("{} leaves the city", self.household_name()),
+            self.id,
+            world,
+        );
+        self.destroy(world);
+    }
+}
+
 pub fn setup(system: &mut ActorSystem) {
     system.register::<Family>();
     auto_setup(system);
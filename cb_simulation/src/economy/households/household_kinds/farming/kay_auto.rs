@@ -0,0 +1,82 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct FarmID {
+    _raw_id: RawID
+}
+
+impl Copy for FarmID {}
+impl Clone for FarmID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for FarmID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "FarmID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for FarmID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for FarmID {
+    fn eq(&self, other: &FarmID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for FarmID {}
+
+pub struct FarmRepresentative;
+
+impl ActorOrActorTrait for FarmRepresentative {
+    type ID = FarmID;
+}
+
+impl TypedID for FarmID {
+    type Target = FarmRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        FarmID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + Farm> TraitIDFrom<Act> for FarmID {}
+
+impl FarmID {
+    pub fn on_field_measured(self, area: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Farm_on_field_measured(area));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<FarmRepresentative>();
+        system.register_trait_message::<MSG_Farm_on_field_measured>();
+    }
+
+    pub fn register_implementor<Act: Actor + Farm>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, FarmRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_Farm_on_field_measured(area), instance, world| {
+                instance.on_field_measured(area, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Farm_on_field_measured(pub f32);
+
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    FarmID::register_trait(system);
+    
+}
\ No newline at end of file
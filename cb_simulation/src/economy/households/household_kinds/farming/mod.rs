@@ -0,0 +1,154 @@
+// What grain and vegetable farms have in common: a field that is sown in
+// spring, grows with the seasons and the weather, and is harvested all at
+// once. The harvest is what the farm sells to mills and shops until the next one
+use kay::World;
+use cb_time::units::{Instant, Duration, TimeOfDay};
+use economy::resources::{Inventory, Resource, ResourceAmount, to_amount};
+use economy::resources::Resource::*;
+use environment::seasons::{Season, growth_rate, growing_weather};
+
+pub trait Farm {
+    fn on_field_measured(&mut self, area: f32, world: &mut World);
+}
+
+// How much seeds a square meter of field takes, what it yields under ideal
+// weather, and how many days of summer it takes to ripen
+fn cultivation(crop: Resource) -> (f32, f32, f32) {
+    match crop {
+        Grain => (0.1, 2.0, 4.0),
+        Produce => (0.02, 0.1, 2.0),
+        _ => unreachable!("Only grain and produce are grown on fields"),
+    }
+}
+
+// the last season a crop can still be sown in to ripen before winter
+fn can_be_sown_in(crop: Resource, season: Season) -> bool {
+    match (crop, season) {
+        (_, Season::Spring) | (Produce, Season::Summer) => true,
+        _ => false,
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FieldState {
+    Fallow,
+    // waiting for the seeds the farmer went to buy
+    Sowing,
+    Growing,
+}
+
+#[derive(Compact, Clone)]
+pub struct Field {
+    crop: Resource,
+    // unknown until the building measured it
+    area: f32,
+    state: FieldState,
+    growth: f32,
+    // of the weather while growing, weighted by how much the crop grew
+    weather_sum: f32,
+    weather_weight: f32,
+}
+
+impl Field {
+    pub fn new(crop: Resource) -> Field {
+        Field {
+            crop,
+            area: 0.0,
+            state: FieldState::Fallow,
+            growth: 0.0,
+            weather_sum: 0.0,
+            weather_weight: 0.0,
+        }
+    }
+
+    pub fn set_area(&mut self, area: f32) {
+        self.area = area;
+    }
+
+    fn seeds_needed(&self) -> ResourceAmount {
+        let (seeds_per_area, _, _) = cultivation(self.crop);
+        to_amount(seeds_per_area * self.area)
+    }
+
+    // Works the field for a while, harvesting straight into the farm's
+    // stock. Seeds that are lacking make the farmer go buy them.
+    // Returns the size of the harvest, if there was one
+    pub fn tend(
+        &mut self,
+        dt: Duration,
+        instant: Instant,
+        resources: &mut Inventory,
+    ) -> Option<ResourceAmount> {
+        let season = Season::of(instant);
+
+        match self.state {
+            FieldState::Fallow => {
+                if self.area > 0.0 && can_be_sown_in(self.crop, season) {
+                    *resources.mut_entry_or(Seeds, amount!(0.0)) -= self.seeds_needed();
+                    self.state = FieldState::Sowing;
+                }
+                None
+            }
+            FieldState::Sowing => {
+                if resources.get(Seeds).map_or(true, |&seeds| seeds >= 0.0) {
+                    self.state = FieldState::Growing;
+                    self.growth = 0.0;
+                    self.weather_sum = 0.0;
+                    self.weather_weight = 0.0;
+                } else if !can_be_sown_in(self.crop, season) {
+                    // too late this year, the seeds still missing aren't needed anymore
+                    *resources.mut_entry_or(Seeds, amount!(0.0)) += self.seeds_needed();
+                    self.state = FieldState::Fallow;
+                }
+                None
+            }
+            FieldState::Growing => {
+                if season == Season::Winter {
+                    // frost got what wasn't ripe yet
+                    self.state = FieldState::Fallow;
+                    return None;
+                }
+
+                let (_, yield_per_area, growing_days) = cultivation(self.crop);
+                let grown = dt.as_days() * growth_rate(season) / growing_days;
+                self.growth += grown;
+                self.weather_sum += grown * growing_weather(instant);
+                self.weather_weight += grown;
+
+                if self.growth >= 1.0 {
+                    let weather = self.weather_sum / self.weather_weight;
+                    let harvest = to_amount(yield_per_area * self.area * weather);
+                    *resources.mut_entry_or(self.crop, amount!(0.0)) += harvest;
+                    self.state = FieldState::Fallow;
+                    Some(harvest)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    // only what was harvested can be sold, in the bulk the offers are made for
+    pub fn has_in_store(&self, amount: ResourceAmount, resources: &Inventory) -> bool {
+        resources
+            .get(self.crop)
+            .map_or(false, |&stored| stored >= amount)
+    }
+}
+
+// Farmers buy seeds in the morning
+pub fn importance(resource: Resource, time: TimeOfDay) -> f32 {
+    let hour = time.hours_minutes().0;
+
+    let bihourly_importance = match resource {
+        Seeds => Some([0, 0, 0, 1, 1, 1, 0, 0, 0, 0, 0, 0]),
+        _ => None,
+    };
+
+    bihourly_importance
+        .map(|lookup| lookup[hour / 2] as f32)
+        .unwrap_or(0.0)
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -66,6 +66,12 @@ impl GrainFarmID {
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_GrainFarm_move_into(pub GrainFarmID, pub BuildingID, pub TimeID);
 
+impl Into<FarmID> for GrainFarmID {
+    fn into(self) -> FarmID {
+        FarmID::from_raw(self.as_raw())
+    }
+}
+
 impl Into<HouseholdID> for GrainFarmID {
     fn into(self) -> HouseholdID {
         HouseholdID::from_raw(self.as_raw())
@@ -106,6 +112,7 @@ impl Into<TripListenerID> for GrainFarmID {
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
+    FarmID::register_implementor::<GrainFarm>(system);
     HouseholdID::register_implementor::<GrainFarm>(system);
     TemporalID::register_implementor::<GrainFarm>(system);
     SleeperID::register_implementor::<GrainFarm>(system);
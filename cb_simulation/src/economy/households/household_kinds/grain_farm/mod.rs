@@ -3,8 +3,12 @@ use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks};
 use cb_time::actors::TimeID;
 use economy::resources::Resource;
 use economy::resources::Resource::*;
-use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use economy::market::{Deal, MarketTier, EvaluationRequester, EvaluationRequesterID,
+EvaluatedSearchResult};
 use land_use::buildings::BuildingID;
+use cb_util::log::info;
+use super::farming::{self, Farm, FarmID, Field};
+const LOG_T: &str = "Grain Farm";
 
 use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
 
@@ -13,6 +17,7 @@ pub struct GrainFarm {
     id: GrainFarmID,
     site: BuildingID,
     core: HouseholdCore,
+    field: Field,
 }
 
 impl GrainFarm {
@@ -23,6 +28,7 @@ impl GrainFarm {
         world: &mut World,
     ) -> GrainFarm {
         time.wake_up_in(Ticks(0), id.into(), world);
+        site.measure_field(id.into(), world);
 
         GrainFarm {
             id,
@@ -33,30 +39,39 @@ impl GrainFarm {
                 1,
                 site.into(),
                 vec![
-                    Offer::new(
+                    Offer::wholesale(
                         MemberIdx(0),
                         TimeOfDayRange::new(7, 0, 20, 0),
                         Deal::new(
-                            vec![(Resource::Grain, 200.0), (Resource::Money, -200.0 * 0.13)],
+                            vec![
+                                (Resource::Grain, amount!(200.0)),
+                                (Resource::Money, amount!(-200.0 * 0.13)),
+                            ],
                             Duration::from_minutes(10),
                         ),
                         4,
-                        false,
                     ),
                     Offer::new(
                         MemberIdx(0),
                         TimeOfDayRange::new(5, 0, 15, 0),
-                        Deal::new(Some((Resource::Money, 40.0)), Duration::from_hours(4)),
+                        Deal::new(Some((Resource::Money, amount!(40.0))), Duration::from_hours(4)),
                         2,
                         false,
                     ),
                 ]
                 .into(),
             ),
+            field: Field::new(Grain),
         }
     }
 }
 
+impl Farm for GrainFarm {
+    fn on_field_measured(&mut self, area: f32, _: &mut World) {
+        self.field.set_area(area);
+    }
+}
+
 impl Household for GrainFarm {
     fn core(&self) -> &HouseholdCore {
         &self.core
@@ -78,19 +93,24 @@ impl Household for GrainFarm {
         true
     }
 
-    fn importance(_: Resource, _: TimeOfDay) -> f32 {
-        0.0
+    fn importance(resource: Resource, time: TimeOfDay) -> f32 {
+        farming::importance(resource, time)
     }
 
     fn interesting_resources() -> &'static [Resource] {
-        &[Resource::Money, Resource::Grain]
+        &[Resource::Money, Resource::Grain, Resource::Seeds]
     }
 
-    fn decay(&mut self, dt: Duration, _: &mut World) {
-        {
-            let grain = self.core.resources.mut_entry_or(Grain, 0.0);
-            *grain += 800.0 * dt.as_days();
-        }
+    fn market_tier(_resource: Resource) -> MarketTier {
+        MarketTier::Wholesale
+    }
+
+    fn decay(&mut self, _dt: Duration, _: &mut World) {}
+
+    fn can_provide(&self, deal: &Deal) -> bool {
+        deal.delta.get(Grain).map_or(true, |&amount| {
+            self.field.has_in_store(amount, &self.core.resources)
+        })
     }
 
     fn on_destroy(&mut self, world: &mut World) {
@@ -108,15 +128,27 @@ impl Household for GrainFarm {
 
 use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
 use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+use cb_time::load_spreading;
 const UPDATE_EVERY_N_SECS: u32 = 4;
 
 impl Temporal for GrainFarm {
     fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id.as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
+        let update_interval = Ticks(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+        if load_spreading::is_turn(self.id.as_raw(), current_instant, update_interval) {
             self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+
+            let dt = Duration(UPDATE_EVERY_N_SECS);
+            if let Some(harvest) = self
+                .field
+                .tend(dt, current_instant, &mut self.core.resources)
+            {
+                info(
+                    LOG_T,
+                    format!("Harvested {:.0} {}", harvest, Grain),
+                    self.id,
+                    world,
+                );
+            }
         }
     }
 }
@@ -141,6 +173,7 @@ impl RoughLocation for GrainFarm {
 }
 
 use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+use transport::taxis::TaxiOperatorID;
 
 impl TripListener for GrainFarm {
     fn trip_created(&mut self, trip: TripID, world: &mut World) {
@@ -157,6 +190,14 @@ impl TripListener for GrainFarm {
     ) {
         self.on_trip_result(trip, result, rough_source, rough_destination, world);
     }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
 }
 
 pub fn setup(system: &mut ActorSystem) {
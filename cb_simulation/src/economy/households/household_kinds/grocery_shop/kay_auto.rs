@@ -61,10 +61,37 @@ impl GroceryShopID {
         world.send(swarm, MSG_GroceryShop_move_into(id, site, time));
         id
     }
+    
+    pub fn open_branch(site: BuildingID, company: CompanyID, time: TimeID, world: &mut World) -> Self {
+        let id = GroceryShopID::from_raw(world.allocate_instance_id::<GroceryShop>());
+        let swarm = world.local_broadcast::<GroceryShop>();
+        world.send(swarm, MSG_GroceryShop_open_branch(id, site, company, time));
+        id
+    }
+    
+    pub fn report_stock(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_GroceryShop_report_stock());
+    }
+    
+    pub fn transfer_stock(self, transfer: Inventory, world: &mut World) {
+        world.send(self.as_raw(), MSG_GroceryShop_transfer_stock(transfer));
+    }
+    
+    pub fn set_purchasing(self, purchasing: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_GroceryShop_set_purchasing(purchasing));
+    }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_GroceryShop_move_into(pub GroceryShopID, pub BuildingID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GroceryShop_open_branch(pub GroceryShopID, pub BuildingID, pub CompanyID, pub TimeID);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GroceryShop_report_stock();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GroceryShop_transfer_stock(pub Inventory);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GroceryShop_set_purchasing(pub bool);
 
 impl Into<HouseholdID> for GroceryShopID {
     fn into(self) -> HouseholdID {
@@ -117,4 +144,28 @@ pub fn auto_setup(system: &mut ActorSystem) {
             GroceryShop::move_into(id, site, time, world)
         }, false
     );
+    
+    system.add_spawner::<GroceryShop, _, _>(
+        |&MSG_GroceryShop_open_branch(id, site, company, time), world| {
+            GroceryShop::open_branch(id, site, company, time, world)
+        }, false
+    );
+    
+    system.add_handler::<GroceryShop, _, _>(
+        |&MSG_GroceryShop_report_stock(), instance, world| {
+            instance.report_stock(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<GroceryShop, _, _>(
+        |&MSG_GroceryShop_transfer_stock(ref transfer), instance, world| {
+            instance.transfer_stock(transfer, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<GroceryShop, _, _>(
+        |&MSG_GroceryShop_set_purchasing(purchasing), instance, world| {
+            instance.set_purchasing(purchasing, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
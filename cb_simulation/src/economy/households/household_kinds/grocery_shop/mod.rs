@@ -1,9 +1,11 @@
 use kay::{ActorSystem, World, TypedID, Actor};
 use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks};
 use cb_time::actors::TimeID;
-use economy::resources::Resource;
+use economy::resources::{Resource, Inventory};
 use economy::resources::Resource::*;
-use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use economy::companies::{CompanyID, SHARED_STOCK};
+use economy::market::{Deal, MarketTier, EvaluationRequester, EvaluationRequesterID,
+EvaluatedSearchResult};
 use land_use::buildings::BuildingID;
 
 use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
@@ -12,21 +14,49 @@ use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offe
 pub struct GroceryShop {
     id: GroceryShopID,
     site: BuildingID,
+    company: CompanyID,
+    // buys the wholesale goods for all branches of the company
+    purchasing: bool,
     core: HouseholdCore,
 }
 
 impl GroceryShop {
+    // an independent shop, which is a company with a single branch
     pub fn move_into(
         id: GroceryShopID,
         site: BuildingID,
         time: TimeID,
         world: &mut World,
+    ) -> GroceryShop {
+        let company = CompanyID::found(id, time, world);
+        GroceryShop::new(id, site, company, true, time, world)
+    }
+
+    pub fn open_branch(
+        id: GroceryShopID,
+        site: BuildingID,
+        company: CompanyID,
+        time: TimeID,
+        world: &mut World,
+    ) -> GroceryShop {
+        GroceryShop::new(id, site, company, false, time, world)
+    }
+
+    fn new(
+        id: GroceryShopID,
+        site: BuildingID,
+        company: CompanyID,
+        purchasing: bool,
+        time: TimeID,
+        world: &mut World,
     ) -> GroceryShop {
         time.wake_up_in(Ticks(0), id.into(), world);
 
         GroceryShop {
             id,
             site,
+            company,
+            purchasing,
             core: HouseholdCore::new(
                 id.into(),
                 world,
@@ -37,24 +67,53 @@ impl GroceryShop {
                         MemberIdx(0),
                         TimeOfDayRange::new(7, 0, 20, 0),
                         Deal::new(
-                            vec![(Groceries, 30.0), (Money, -30.0 * 2.7)],
+                            vec![(Groceries, amount!(30.0)), (Money, amount!(-30.0 * 2.7))],
                             Duration::from_minutes(30),
                         ),
                         16,
                         false,
-                    ),
+                    )
+                    .staffed(4),
                     Offer::new(
                         MemberIdx(0),
                         TimeOfDayRange::new(7, 0, 15, 0),
-                        Deal::new(Some((Money, 50.0)), Duration::from_hours(5)),
+                        Deal::new(Some((Money, amount!(50.0))), Duration::from_hours(5)),
                         5,
                         false,
                     ),
+                    Offer::new(
+                        MemberIdx(0),
+                        TimeOfDayRange::new(7, 0, 12, 0),
+                        Deal::new(Some((Money, amount!(80.0))), Duration::from_hours(6)),
+                        1,
+                        false,
+                    )
+                    .requiring_education(12.0),
                 ]
                 .into(),
             ),
         }
     }
+
+    pub fn report_stock(&mut self, world: &mut World) {
+        let stock = SHARED_STOCK
+            .iter()
+            .map(|&resource| {
+                let amount = self.core.resources.get(resource).cloned().unwrap_or(amount!(0.0));
+                (resource, amount)
+            })
+            .collect::<Inventory>();
+        self.company.on_stock_report(self.id, stock, world);
+    }
+
+    // can take stock away as well as add to it
+    pub fn transfer_stock(&mut self, transfer: &Inventory, _: &mut World) {
+        transfer.give_to(&mut self.core.resources);
+    }
+
+    pub fn set_purchasing(&mut self, purchasing: bool, _: &mut World) {
+        self.purchasing = purchasing;
+    }
 }
 
 impl Household for GroceryShop {
@@ -99,39 +158,51 @@ impl Household for GroceryShop {
         ]
     }
 
+    fn market_tier(_resource: Resource) -> MarketTier {
+        MarketTier::Wholesale
+    }
+
+    fn company(&self) -> Option<CompanyID> {
+        Some(self.company)
+    }
+
+    fn purchases(&self, _resource: Resource) -> bool {
+        self.purchasing
+    }
+
     fn decay(&mut self, dt: Duration, _: &mut World) {
         {
-            let groceries = self.core.resources.mut_entry_or(Groceries, 0.0);
+            let groceries = self.core.resources.mut_entry_or(Groceries, amount!(0.0));
             *groceries += 500.0 * dt.as_days();
         }
 
         {
-            let meat = self.core.resources.mut_entry_or(Meat, 0.0);
+            let meat = self.core.resources.mut_entry_or(Meat, amount!(0.0));
             *meat -= 500.0 * 0.2 * dt.as_days();
         }
 
         {
-            let dairy = self.core.resources.mut_entry_or(DairyGoods, 0.0);
+            let dairy = self.core.resources.mut_entry_or(DairyGoods, amount!(0.0));
             *dairy -= 500.0 * 0.1 * dt.as_days();
         }
 
         {
-            let produce = self.core.resources.mut_entry_or(Produce, 0.0);
+            let produce = self.core.resources.mut_entry_or(Produce, amount!(0.0));
             *produce -= 500.0 * 0.1 * dt.as_days();
         }
 
         {
-            let grain = self.core.resources.mut_entry_or(Grain, 0.0);
+            let grain = self.core.resources.mut_entry_or(Grain, amount!(0.0));
             *grain -= 500.0 * 0.05 * dt.as_days();
         }
 
         {
-            let flour = self.core.resources.mut_entry_or(Flour, 0.0);
+            let flour = self.core.resources.mut_entry_or(Flour, amount!(0.0));
             *flour -= 500.0 * 0.01 * dt.as_days();
         }
 
         {
-            let baked = self.core.resources.mut_entry_or(BakedGoods, 0.0);
+            let baked = self.core.resources.mut_entry_or(BakedGoods, amount!(0.0));
             *baked -= 500.0 * 0.3 * dt.as_days();
         }
     }
@@ -146,6 +217,7 @@ impl Household for GroceryShop {
 
     fn on_destroy(&mut self, world: &mut World) {
         self.site.remove_household(self.id_as(), world);
+        self.company.branch_closed(self.id, world);
     }
 }
 
@@ -172,14 +244,13 @@ impl EvaluationRequester for GroceryShop {
 
 use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
 use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+use cb_time::load_spreading;
 const UPDATE_EVERY_N_SECS: u32 = 4;
 
 impl Temporal for GroceryShop {
     fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id.as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
+        let update_interval = Ticks(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+        if load_spreading::is_turn(self.id.as_raw(), current_instant, update_interval) {
             self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
         }
     }
@@ -200,6 +271,7 @@ impl RoughLocation for GroceryShop {
 }
 
 use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+use transport::taxis::TaxiOperatorID;
 
 impl TripListener for GroceryShop {
     fn trip_created(&mut self, trip: TripID, world: &mut World) {
@@ -216,6 +288,14 @@ impl TripListener for GroceryShop {
     ) {
         self.on_trip_result(trip, result, rough_source, rough_destination, world);
     }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
 }
 
 pub fn setup(system: &mut ActorSystem) {
@@ -0,0 +1,120 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for Hospital {
+    type ID = HospitalID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct HospitalID {
+    _raw_id: RawID
+}
+
+impl Copy for HospitalID {}
+impl Clone for HospitalID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for HospitalID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "HospitalID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for HospitalID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for HospitalID {
+    fn eq(&self, other: &HospitalID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for HospitalID {}
+
+impl TypedID for HospitalID {
+    type Target = Hospital;
+
+    fn from_raw(id: RawID) -> Self {
+        HospitalID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl HospitalID {
+    pub fn move_into(site: BuildingID, time: TimeID, world: &mut World) -> Self {
+        let id = HospitalID::from_raw(world.allocate_instance_id::<Hospital>());
+        let swarm = world.local_broadcast::<Hospital>();
+        world.send(swarm, MSG_Hospital_move_into(id, site, time));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Hospital_move_into(pub HospitalID, pub BuildingID, pub TimeID);
+
+impl Into<HouseholdID> for HospitalID {
+    fn into(self) -> HouseholdID {
+        HouseholdID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<EvaluationRequesterID> for HospitalID {
+    fn into(self) -> EvaluationRequesterID {
+        EvaluationRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TemporalID> for HospitalID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for HospitalID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<RoughLocationID> for HospitalID {
+    fn into(self) -> RoughLocationID {
+        RoughLocationID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TripListenerID> for HospitalID {
+    fn into(self) -> TripListenerID {
+        TripListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HouseholdID::register_implementor::<Hospital>(system);
+    EvaluationRequesterID::register_implementor::<Hospital>(system);
+    TemporalID::register_implementor::<Hospital>(system);
+    SleeperID::register_implementor::<Hospital>(system);
+    RoughLocationID::register_implementor::<Hospital>(system);
+    TripListenerID::register_implementor::<Hospital>(system);
+    system.add_spawner::<Hospital, _, _>(
+        |&MSG_Hospital_move_into(id, site, time), world| {
+            Hospital::move_into(id, site, time, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,203 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks};
+use cb_time::actors::TimeID;
+use economy::resources::Resource;
+use economy::resources::Resource::*;
+use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use land_use::buildings::BuildingID;
+use transport::emergency::EmergencyDispatchID;
+
+use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
+
+#[derive(Compact, Clone)]
+pub struct Hospital {
+    id: HospitalID,
+    site: BuildingID,
+    core: HouseholdCore,
+}
+
+impl Hospital {
+    pub fn move_into(
+        id: HospitalID,
+        site: BuildingID,
+        time: TimeID,
+        world: &mut World,
+    ) -> Hospital {
+        time.wake_up_in(Ticks(0), id.into(), world);
+        EmergencyDispatchID::global_first(world).add_station(site.into(), world);
+
+        Hospital {
+            id,
+            site,
+            core: HouseholdCore::new(
+                id.into(),
+                world,
+                1,
+                site.into(),
+                vec![
+                    Offer::new(
+                        MemberIdx(0),
+                        TimeOfDayRange::new(0, 0, 23, 59),
+                        Deal::new(
+                            vec![(Healthcare, amount!(1.0)), (Money, amount!(-20.0))],
+                            Duration::from_hours(4),
+                        ),
+                        10,
+                        false,
+                    ),
+                    Offer::new(
+                        MemberIdx(0),
+                        TimeOfDayRange::new(6, 0, 10, 0),
+                        Deal::new(Some((Money, amount!(60.0))), Duration::from_hours(8)),
+                        4,
+                        false,
+                    )
+                    .requiring_education(12.0),
+                    Offer::new(
+                        MemberIdx(0),
+                        TimeOfDayRange::new(6, 0, 10, 0),
+                        Deal::new(Some((Money, amount!(90.0))), Duration::from_hours(8)),
+                        2,
+                        false,
+                    )
+                    .requiring_education(16.0),
+                ]
+                .into(),
+            ),
+        }
+    }
+}
+
+impl Household for Hospital {
+    fn core(&self) -> &HouseholdCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut HouseholdCore {
+        &mut self.core
+    }
+
+    fn site(&self) -> RoughLocationID {
+        self.site.into()
+    }
+
+    fn is_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn supplier_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn importance(_resource: Resource, _time: TimeOfDay) -> f32 {
+        0.0
+    }
+
+    fn interesting_resources() -> &'static [Resource] {
+        &[Money, Healthcare]
+    }
+
+    fn decay(&mut self, dt: Duration, _: &mut World) {
+        // enough beds to treat every patient a few times a day
+        let healthcare = self.core.resources.mut_entry_or(Healthcare, amount!(0.0));
+        *healthcare += 40.0 * dt.as_days();
+    }
+
+    fn household_name(&self) -> String {
+        "Hospital".to_owned()
+    }
+
+    fn member_name(&self, member: MemberIdx) -> String {
+        format!("Medical Worker {}", member.0 + 1)
+    }
+
+    fn on_destroy(&mut self, world: &mut World) {
+        EmergencyDispatchID::global_first(world).remove_station(self.site.into(), world);
+        self.site.remove_household(self.id_as(), world);
+    }
+}
+
+use economy::households::ResultAspect;
+
+impl EvaluationRequester for Hospital {
+    fn expect_n_results(&mut self, resource: Resource, n: u32, world: &mut World) {
+        self.update_results(resource, &ResultAspect::SetTarget(n), world);
+    }
+
+    fn on_result(&mut self, result: &EvaluatedSearchResult, world: &mut World) {
+        let &EvaluatedSearchResult {
+            resource,
+            ref evaluated_deals,
+            ..
+        } = result;
+        self.update_results(
+            resource,
+            &ResultAspect::AddDeals(evaluated_deals.clone()),
+            world,
+        );
+    }
+}
+
+use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
+use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+use cb_time::load_spreading;
+const UPDATE_EVERY_N_SECS: u32 = 4;
+
+impl Temporal for Hospital {
+    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+        let update_interval = Ticks(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+        if load_spreading::is_turn(self.id.as_raw(), current_instant, update_interval) {
+            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+        }
+    }
+}
+
+impl Sleeper for Hospital {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        self.update_core(current_instant, world);
+    }
+}
+
+use transport::pathfinding::{RoughLocationID, RoughLocation, RoughLocationResolve};
+
+impl RoughLocation for Hospital {
+    fn resolve(&self) -> RoughLocationResolve {
+        RoughLocationResolve::SameAs(self.site())
+    }
+}
+
+use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+use transport::taxis::TaxiOperatorID;
+
+impl TripListener for Hospital {
+    fn trip_created(&mut self, trip: TripID, world: &mut World) {
+        self.on_trip_created(trip, world);
+    }
+
+    fn trip_result(
+        &mut self,
+        trip: TripID,
+        result: TripResult,
+        rough_source: RoughLocationID,
+        rough_destination: RoughLocationID,
+        world: &mut World,
+    ) {
+        self.on_trip_result(trip, result, rough_source, rough_destination, world);
+    }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Hospital>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,120 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for InformalDealer {
+    type ID = InformalDealerID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct InformalDealerID {
+    _raw_id: RawID
+}
+
+impl Copy for InformalDealerID {}
+impl Clone for InformalDealerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for InformalDealerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "InformalDealerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for InformalDealerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for InformalDealerID {
+    fn eq(&self, other: &InformalDealerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for InformalDealerID {}
+
+impl TypedID for InformalDealerID {
+    type Target = InformalDealer;
+
+    fn from_raw(id: RawID) -> Self {
+        InformalDealerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl InformalDealerID {
+    pub fn spawn(site: RoughLocationID, resource: Resource, formal_amount: ResourceAmount, formal_price: f32, time: TimeID, world: &mut World) -> Self {
+        let id = InformalDealerID::from_raw(world.allocate_instance_id::<InformalDealer>());
+        let swarm = world.local_broadcast::<InformalDealer>();
+        world.send(swarm, MSG_InformalDealer_spawn(id, site, resource, formal_amount, formal_price, time));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_InformalDealer_spawn(pub InformalDealerID, pub RoughLocationID, pub Resource, pub ResourceAmount, pub f32, pub TimeID);
+
+impl Into<HouseholdID> for InformalDealerID {
+    fn into(self) -> HouseholdID {
+        HouseholdID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for InformalDealerID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<EvaluationRequesterID> for InformalDealerID {
+    fn into(self) -> EvaluationRequesterID {
+        EvaluationRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TripListenerID> for InformalDealerID {
+    fn into(self) -> TripListenerID {
+        TripListenerID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TemporalID> for InformalDealerID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<RoughLocationID> for InformalDealerID {
+    fn into(self) -> RoughLocationID {
+        RoughLocationID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HouseholdID::register_implementor::<InformalDealer>(system);
+    SleeperID::register_implementor::<InformalDealer>(system);
+    EvaluationRequesterID::register_implementor::<InformalDealer>(system);
+    TripListenerID::register_implementor::<InformalDealer>(system);
+    TemporalID::register_implementor::<InformalDealer>(system);
+    RoughLocationID::register_implementor::<InformalDealer>(system);
+    system.add_spawner::<InformalDealer, _, _>(
+        |&MSG_InformalDealer_spawn(id, site, resource, formal_amount, formal_price, time), world| {
+            InformalDealer::spawn(id, site, resource, formal_amount, formal_price, time, world)
+        }, false
+    );
+}
\ No newline at end of file
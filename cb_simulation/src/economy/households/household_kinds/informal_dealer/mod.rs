@@ -0,0 +1,190 @@
+use kay::{ActorSystem, World};
+use cb_time::actors::{Temporal, TemporalID, TimeID};
+use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Instant, Ticks};
+use economy::resources::{Resource, ResourceAmount};
+use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use transport::pathfinding::RoughLocationID;
+use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+use transport::taxis::TaxiOperatorID;
+
+use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
+
+// Informal dealers only hand out a fraction of what a proper shop would
+// for the same kind of deal, at a steep markup
+const QUALITY_FACTOR: f32 = 0.5;
+const PRICE_MARKUP: f32 = 2.5;
+const STOCK_PER_DAY: ResourceAmount = amount!(200.0);
+
+#[derive(Compact, Clone)]
+pub struct InformalDealer {
+    id: InformalDealerID,
+    site: RoughLocationID,
+    resource: Resource,
+    core: HouseholdCore,
+}
+
+impl InformalDealer {
+    pub fn spawn(
+        id: InformalDealerID,
+        site: RoughLocationID,
+        resource: Resource,
+        formal_amount: ResourceAmount,
+        formal_price: f32,
+        time: TimeID,
+        world: &mut World,
+    ) -> Self {
+        time.wake_up_in(Ticks(0), id.into(), world);
+
+        let amount = formal_amount * QUALITY_FACTOR;
+
+        InformalDealer {
+            id,
+            site,
+            resource,
+            core: HouseholdCore::new(
+                id.into(),
+                world,
+                1,
+                site,
+                vec![Offer::new(
+                    MemberIdx(0),
+                    TimeOfDayRange::new(16, 0, 23, 0),
+                    Deal::new(
+                        vec![
+                            (resource, amount),
+                            (
+                                Resource::Money,
+                                -formal_amount * formal_price * PRICE_MARKUP,
+                            ),
+                        ],
+                        Duration::from_minutes(20),
+                    ),
+                    8,
+                    false,
+                )]
+                .into(),
+            ),
+        }
+    }
+}
+
+impl Household for InformalDealer {
+    fn core(&self) -> &HouseholdCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut HouseholdCore {
+        &mut self.core
+    }
+
+    fn site(&self) -> RoughLocationID {
+        self.site
+    }
+
+    fn is_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn supplier_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn importance(_: Resource, _: TimeOfDay) -> f32 {
+        0.0
+    }
+
+    fn interesting_resources() -> &'static [Resource] {
+        &[Resource::Money]
+    }
+
+    fn decay(&mut self, dt: Duration, _: &mut World) {
+        let stock = self.core.resources.mut_entry_or(self.resource, amount!(0.0));
+        *stock += STOCK_PER_DAY * dt.as_days();
+    }
+
+    fn household_name(&self) -> String {
+        format!("Informal {} Dealer", self.resource)
+    }
+
+    fn member_name(&self, member: MemberIdx) -> String {
+        format!("Dealer {}", member.0 + 1)
+    }
+
+    fn on_destroy(&mut self, _: &mut World) {}
+}
+
+use cb_time::actors::{Sleeper, SleeperID};
+
+impl Sleeper for InformalDealer {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        self.update_core(current_instant, world);
+    }
+}
+
+use economy::households::ResultAspect;
+
+impl EvaluationRequester for InformalDealer {
+    fn expect_n_results(&mut self, resource: Resource, n: u32, world: &mut World) {
+        self.update_results(resource, &ResultAspect::SetTarget(n), world);
+    }
+
+    fn on_result(&mut self, result: &EvaluatedSearchResult, world: &mut World) {
+        let &EvaluatedSearchResult {
+            resource,
+            ref evaluated_deals,
+            ..
+        } = result;
+        self.update_results(
+            resource,
+            &ResultAspect::AddDeals(evaluated_deals.clone()),
+            world,
+        );
+    }
+}
+
+impl TripListener for InformalDealer {
+    fn trip_created(&mut self, trip: TripID, world: &mut World) {
+        self.on_trip_created(trip, world);
+    }
+
+    fn trip_result(
+        &mut self,
+        trip: TripID,
+        result: TripResult,
+        rough_source: RoughLocationID,
+        rough_destination: RoughLocationID,
+        world: &mut World,
+    ) {
+        self.on_trip_result(trip, result, rough_source, rough_destination, world);
+    }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
+}
+
+impl Temporal for InformalDealer {
+    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+        self.on_tick(current_instant, world);
+    }
+}
+
+use transport::pathfinding::{RoughLocation, RoughLocationResolve};
+
+impl RoughLocation for InformalDealer {
+    fn resolve(&self) -> RoughLocationResolve {
+        RoughLocationResolve::SameAs(self.site())
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<InformalDealer>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
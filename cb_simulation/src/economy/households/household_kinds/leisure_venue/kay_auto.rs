@@ -0,0 +1,135 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for LeisureVenue {
+    type ID = LeisureVenueID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct LeisureVenueID {
+    _raw_id: RawID
+}
+
+impl Copy for LeisureVenueID {}
+impl Clone for LeisureVenueID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for LeisureVenueID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "LeisureVenueID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for LeisureVenueID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for LeisureVenueID {
+    fn eq(&self, other: &LeisureVenueID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for LeisureVenueID {}
+
+impl TypedID for LeisureVenueID {
+    type Target = LeisureVenue;
+
+    fn from_raw(id: RawID) -> Self {
+        LeisureVenueID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl LeisureVenueID {
+    pub fn move_into(kind: LeisureKind, site: BuildingID, time: TimeID, world: &mut World) -> Self {
+        let id = LeisureVenueID::from_raw(world.allocate_instance_id::<LeisureVenue>());
+        let swarm = world.local_broadcast::<LeisureVenue>();
+        world.send(swarm, MSG_LeisureVenue_move_into(id, kind, site, time));
+        id
+    }
+    
+    pub fn open_park(site: BuildingID, capacity: usize, time: TimeID, world: &mut World) -> Self {
+        let id = LeisureVenueID::from_raw(world.allocate_instance_id::<LeisureVenue>());
+        let swarm = world.local_broadcast::<LeisureVenue>();
+        world.send(swarm, MSG_LeisureVenue_open_park(id, site, capacity, time));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_LeisureVenue_move_into(pub LeisureVenueID, pub LeisureKind, pub BuildingID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_LeisureVenue_open_park(pub LeisureVenueID, pub BuildingID, pub usize, pub TimeID);
+
+impl Into<HouseholdID> for LeisureVenueID {
+    fn into(self) -> HouseholdID {
+        HouseholdID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<EvaluationRequesterID> for LeisureVenueID {
+    fn into(self) -> EvaluationRequesterID {
+        EvaluationRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TemporalID> for LeisureVenueID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for LeisureVenueID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<RoughLocationID> for LeisureVenueID {
+    fn into(self) -> RoughLocationID {
+        RoughLocationID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TripListenerID> for LeisureVenueID {
+    fn into(self) -> TripListenerID {
+        TripListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HouseholdID::register_implementor::<LeisureVenue>(system);
+    EvaluationRequesterID::register_implementor::<LeisureVenue>(system);
+    TemporalID::register_implementor::<LeisureVenue>(system);
+    SleeperID::register_implementor::<LeisureVenue>(system);
+    RoughLocationID::register_implementor::<LeisureVenue>(system);
+    TripListenerID::register_implementor::<LeisureVenue>(system);
+    system.add_spawner::<LeisureVenue, _, _>(
+        |&MSG_LeisureVenue_move_into(id, kind, site, time), world| {
+            LeisureVenue::move_into(id, kind, site, time, world)
+        }, false
+    );
+    
+    system.add_spawner::<LeisureVenue, _, _>(
+        |&MSG_LeisureVenue_open_park(id, site, capacity, time), world| {
+            LeisureVenue::open_park(id, site, capacity, time, world)
+        }, false
+    );
+}
\ No newline at end of file
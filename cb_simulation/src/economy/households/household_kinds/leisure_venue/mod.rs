@@ -0,0 +1,288 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use compact::CVec;
+use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks};
+use cb_time::actors::TimeID;
+use economy::resources::{Resource, Entry};
+use economy::resources::Resource::*;
+use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use land_use::buildings::BuildingID;
+
+use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum LeisureKind {
+    Restaurant,
+    Park,
+    Cinema,
+}
+
+impl LeisureKind {
+    fn name(self) -> &'static str {
+        match self {
+            LeisureKind::Restaurant => "Restaurant",
+            LeisureKind::Park => "Park",
+            LeisureKind::Cinema => "Cinema",
+        }
+    }
+
+    fn offers(self) -> CVec<Offer> {
+        match self {
+            LeisureKind::Restaurant => vec![
+                Offer::new(
+                    MemberIdx(0),
+                    TimeOfDayRange::new(11, 0, 22, 0),
+                    Deal::new(
+                        vec![
+                            (Satiety, amount!(1.0)),
+                            (Entertainment, amount!(0.5)),
+                            (Money, amount!(-4.0)),
+                        ],
+                        Duration::from_hours(1),
+                    ),
+                    20,
+                    false,
+                )
+                .staffed(8),
+                Offer::new(
+                    MemberIdx(0),
+                    TimeOfDayRange::new(9, 0, 12, 0),
+                    Deal::new(Some((Money, amount!(50.0))), Duration::from_hours(8)),
+                    3,
+                    false,
+                ),
+            ],
+            LeisureKind::Park => park_offers(50),
+            LeisureKind::Cinema => vec![
+                Offer::new(
+                    MemberIdx(0),
+                    TimeOfDayRange::new(14, 0, 22, 0),
+                    Deal::new(
+                        vec![(Entertainment, amount!(2.5)), (Money, amount!(-3.0))],
+                        Duration::from_hours(3),
+                    ),
+                    40,
+                    false,
+                )
+                .staffed(20),
+                Offer::new(
+                    MemberIdx(0),
+                    TimeOfDayRange::new(12, 0, 15, 0),
+                    Deal::new(Some((Money, amount!(50.0))), Duration::from_hours(8)),
+                    2,
+                    false,
+                ),
+            ],
+        }
+        .into()
+    }
+}
+
+// public and free, looked after by a single keeper
+fn park_offers(max_visitors: usize) -> Vec<Offer> {
+    vec![Offer::new(
+        MemberIdx(0),
+        TimeOfDayRange::new(6, 0, 21, 0),
+        Deal::new(Some((Entertainment, amount!(1.0))), Duration::from_hours(2)),
+        max_visitors,
+        false,
+    )]
+}
+
+// planned parks have room for more visitors the larger they are
+const PARK_AREA_PER_VISITOR: f32 = 200.0;
+const MIN_PARK_VISITORS: usize = 10;
+
+pub fn park_capacity(area: f32) -> usize {
+    ((area / PARK_AREA_PER_VISITOR) as usize).max(MIN_PARK_VISITORS)
+}
+
+// Restaurants, parks and cinemas, where people go to be entertained
+#[derive(Compact, Clone)]
+pub struct LeisureVenue {
+    id: LeisureVenueID,
+    site: BuildingID,
+    kind: LeisureKind,
+    core: HouseholdCore,
+}
+
+impl LeisureVenue {
+    pub fn move_into(
+        id: LeisureVenueID,
+        kind: LeisureKind,
+        site: BuildingID,
+        time: TimeID,
+        world: &mut World,
+    ) -> LeisureVenue {
+        time.wake_up_in(Ticks(0), id.into(), world);
+
+        LeisureVenue {
+            id,
+            site,
+            kind,
+            core: HouseholdCore::new(id.into(), world, 1, site.into(), kind.offers()),
+        }
+    }
+
+    // Nobody has to found a park, it opens once the city has laid it out
+    pub fn open_park(
+        id: LeisureVenueID,
+        site: BuildingID,
+        capacity: usize,
+        time: TimeID,
+        world: &mut World,
+    ) -> LeisureVenue {
+        time.wake_up_in(Ticks(0), id.into(), world);
+
+        LeisureVenue {
+            id,
+            site,
+            kind: LeisureKind::Park,
+            core: HouseholdCore::new(
+                id.into(),
+                world,
+                1,
+                site.into(),
+                park_offers(capacity).into(),
+            ),
+        }
+    }
+}
+
+impl Household for LeisureVenue {
+    fn core(&self) -> &HouseholdCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut HouseholdCore {
+        &mut self.core
+    }
+
+    fn site(&self) -> RoughLocationID {
+        self.site.into()
+    }
+
+    fn is_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn supplier_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn importance(_resource: Resource, _time: TimeOfDay) -> f32 {
+        0.0
+    }
+
+    fn interesting_resources() -> &'static [Resource] {
+        &[Money, Satiety, Entertainment]
+    }
+
+    fn decay(&mut self, dt: Duration, _: &mut World) {
+        // enough capacity to serve every guest a day
+        for offer in self.core.provided_offers.iter() {
+            for &Entry(resource, amount) in offer.deal.delta.iter() {
+                if resource != Money && amount > 0.0 {
+                    let capacity = self.core.resources.mut_entry_or(resource, amount!(0.0));
+                    *capacity += amount * offer.max_users as f32 * dt.as_days();
+                }
+            }
+        }
+    }
+
+    fn household_name(&self) -> String {
+        self.kind.name().to_owned()
+    }
+
+    fn member_name(&self, member: MemberIdx) -> String {
+        format!("{} Staff {}", self.kind.name(), member.0 + 1)
+    }
+
+    fn on_destroy(&mut self, world: &mut World) {
+        self.site.remove_household(self.id_as(), world);
+    }
+}
+
+use economy::households::ResultAspect;
+
+impl EvaluationRequester for LeisureVenue {
+    fn expect_n_results(&mut self, resource: Resource, n: u32, world: &mut World) {
+        self.update_results(resource, &ResultAspect::SetTarget(n), world);
+    }
+
+    fn on_result(&mut self, result: &EvaluatedSearchResult, world: &mut World) {
+        let &EvaluatedSearchResult {
+            resource,
+            ref evaluated_deals,
+            ..
+        } = result;
+        self.update_results(
+            resource,
+            &ResultAspect::AddDeals(evaluated_deals.clone()),
+            world,
+        );
+    }
+}
+
+use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
+use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+use cb_time::load_spreading;
+const UPDATE_EVERY_N_SECS: u32 = 4;
+
+impl Temporal for LeisureVenue {
+    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+        let update_interval = Ticks(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+        if load_spreading::is_turn(self.id.as_raw(), current_instant, update_interval) {
+            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+        }
+    }
+}
+
+impl Sleeper for LeisureVenue {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        self.update_core(current_instant, world);
+    }
+}
+
+use transport::pathfinding::{RoughLocationID, RoughLocation, RoughLocationResolve};
+
+impl RoughLocation for LeisureVenue {
+    fn resolve(&self) -> RoughLocationResolve {
+        RoughLocationResolve::SameAs(self.site())
+    }
+}
+
+use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+use transport::taxis::TaxiOperatorID;
+
+impl TripListener for LeisureVenue {
+    fn trip_created(&mut self, trip: TripID, world: &mut World) {
+        self.on_trip_created(trip, world);
+    }
+
+    fn trip_result(
+        &mut self,
+        trip: TripID,
+        result: TripResult,
+        rough_source: RoughLocationID,
+        rough_destination: RoughLocationID,
+        world: &mut World,
+    ) {
+        self.on_trip_result(trip, result, rough_source, rough_destination, world);
+    }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<LeisureVenue>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
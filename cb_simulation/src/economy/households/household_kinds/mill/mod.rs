@@ -3,7 +3,8 @@ use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks};
 use cb_time::actors::TimeID;
 use economy::resources::Resource;
 use economy::resources::Resource::*;
-use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use economy::market::{Deal, MarketTier, EvaluationRequester, EvaluationRequesterID,
+EvaluatedSearchResult};
 use land_use::buildings::BuildingID;
 
 use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
@@ -28,20 +29,22 @@ impl Mill {
                 1,
                 site.into(),
                 vec![
-                    Offer::new(
+                    Offer::wholesale(
                         MemberIdx(0),
                         TimeOfDayRange::new(7, 0, 20, 0),
                         Deal::new(
-                            vec![(Resource::Flour, 200.0), (Resource::Money, -200.0 * 0.3)],
+                            vec![
+                                (Resource::Flour, amount!(200.0)),
+                                (Resource::Money, amount!(-200.0 * 0.3)),
+                            ],
                             Duration::from_minutes(10),
                         ),
                         4,
-                        false,
                     ),
                     Offer::new(
                         MemberIdx(0),
                         TimeOfDayRange::new(5, 0, 15, 0),
-                        Deal::new(Some((Resource::Money, 40.0)), Duration::from_hours(4)),
+                        Deal::new(Some((Resource::Money, amount!(40.0))), Duration::from_hours(4)),
                         3,
                         false,
                     ),
@@ -90,14 +93,18 @@ impl Household for Mill {
         &[Resource::Money, Resource::Grain, Resource::Flour]
     }
 
+    fn market_tier(_resource: Resource) -> MarketTier {
+        MarketTier::Wholesale
+    }
+
     fn decay(&mut self, dt: Duration, _: &mut World) {
         {
-            let flour = self.core.resources.mut_entry_or(Flour, 0.0);
+            let flour = self.core.resources.mut_entry_or(Flour, amount!(0.0));
             *flour += 800.0 * dt.as_days();
         }
 
         {
-            let grain = self.core.resources.mut_entry_or(Grain, 0.0);
+            let grain = self.core.resources.mut_entry_or(Grain, amount!(0.0));
             *grain -= 800.0 * 1.0 * dt.as_days();
         }
     }
@@ -117,14 +124,13 @@ impl Household for Mill {
 
 use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
 use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+use cb_time::load_spreading;
 const UPDATE_EVERY_N_SECS: u32 = 4;
 
 impl Temporal for Mill {
     fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id.as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
+        let update_interval = Ticks(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+        if load_spreading::is_turn(self.id.as_raw(), current_instant, update_interval) {
             self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
         }
     }
@@ -150,6 +156,7 @@ impl RoughLocation for Mill {
 }
 
 use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+use transport::taxis::TaxiOperatorID;
 
 impl TripListener for Mill {
     fn trip_created(&mut self, trip: TripID, world: &mut World) {
@@ -166,6 +173,14 @@ impl TripListener for Mill {
     ) {
         self.on_trip_result(trip, result, rough_source, rough_destination, world);
     }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
 }
 
 pub fn setup(system: &mut ActorSystem) {
@@ -1,8 +1,17 @@
 pub mod family;
 pub mod grocery_shop;
+pub mod school;
+pub mod hospital;
+pub mod car_dealer;
 pub mod grain_farm;
 pub mod cow_farm;
 pub mod vegetable_farm;
 pub mod mill;
 pub mod bakery;
 pub mod neighboring_town_trade;
+pub mod informal_dealer;
+pub mod leisure_venue;
+pub mod construction_firm;
+pub mod utility_company;
+pub mod taxi_company;
+pub mod farming;
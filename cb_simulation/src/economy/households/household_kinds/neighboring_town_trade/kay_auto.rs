@@ -61,10 +61,52 @@ impl NeighboringTownTradeID {
         world.send(swarm, MSG_NeighboringTownTrade_move_into(id, town, time));
         id
     }
+    
+    pub fn report_stock(self, link: RegionLinkID, world: &mut World) {
+        world.send(self.as_raw(), MSG_NeighboringTownTrade_report_stock(link));
+    }
+    
+    pub fn ship_freight(self, resource: Resource, amount: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_NeighboringTownTrade_ship_freight(resource, amount));
+    }
+    
+    pub fn receive_freight(self, resource: Resource, amount: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_NeighboringTownTrade_receive_freight(resource, amount));
+    }
+    
+    pub fn set_regional_jobs(self, jobs: Option < ( u32 , ResourceAmount ) >, world: &mut World) {
+        world.send(self.as_raw(), MSG_NeighboringTownTrade_set_regional_jobs(jobs));
+    }
+    
+    pub fn host_commuters(self, n_commuters: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_NeighboringTownTrade_host_commuters(n_commuters));
+    }
+    
+    pub fn set_utility_import(self, utility: Utility, capacity: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_NeighboringTownTrade_set_utility_import(utility, capacity));
+    }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_NeighboringTownTrade_move_into(pub NeighboringTownTradeID, pub BuildingID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NeighboringTownTrade_report_stock(pub RegionLinkID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NeighboringTownTrade_ship_freight(pub Resource, pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NeighboringTownTrade_receive_freight(pub Resource, pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NeighboringTownTrade_set_regional_jobs(pub Option < ( u32 , ResourceAmount ) >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NeighboringTownTrade_host_commuters(pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NeighboringTownTrade_set_utility_import(pub Utility, pub f32);
+
+impl Into<ConfigUserID<DifficultyProfile>> for NeighboringTownTradeID {
+    fn into(self) -> ConfigUserID<DifficultyProfile> {
+        ConfigUserID::from_raw(self.as_raw())
+    }
+}
 
 impl Into<HouseholdID> for NeighboringTownTradeID {
     fn into(self) -> HouseholdID {
@@ -106,6 +148,7 @@ impl Into<RoughLocationID> for NeighboringTownTradeID {
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
+    ConfigUserID::<DifficultyProfile>::register_implementor::<NeighboringTownTrade>(system);
     HouseholdID::register_implementor::<NeighboringTownTrade>(system);
     SleeperID::register_implementor::<NeighboringTownTrade>(system);
     EvaluationRequesterID::register_implementor::<NeighboringTownTrade>(system);
@@ -117,4 +160,40 @@ pub fn auto_setup(system: &mut ActorSystem) {
             NeighboringTownTrade::move_into(id, town, time, world)
         }, false
     );
+    
+    system.add_handler::<NeighboringTownTrade, _, _>(
+        |&MSG_NeighboringTownTrade_report_stock(link), instance, world| {
+            instance.report_stock(link, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<NeighboringTownTrade, _, _>(
+        |&MSG_NeighboringTownTrade_ship_freight(resource, amount), instance, world| {
+            instance.ship_freight(resource, amount, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<NeighboringTownTrade, _, _>(
+        |&MSG_NeighboringTownTrade_receive_freight(resource, amount), instance, world| {
+            instance.receive_freight(resource, amount, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<NeighboringTownTrade, _, _>(
+        |&MSG_NeighboringTownTrade_set_regional_jobs(jobs), instance, world| {
+            instance.set_regional_jobs(jobs, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<NeighboringTownTrade, _, _>(
+        |&MSG_NeighboringTownTrade_host_commuters(n_commuters), instance, world| {
+            instance.host_commuters(n_commuters, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<NeighboringTownTrade, _, _>(
+        |&MSG_NeighboringTownTrade_set_utility_import(utility, capacity), instance, world| {
+            instance.set_utility_import(utility, capacity, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
@@ -1,20 +1,59 @@
 use kay::{ActorSystem, World, Actor};
+use compact::{CHashMap, COption, CVec};
 use cb_time::actors::{Temporal, TemporalID, TimeID};
 use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Instant, Ticks};
-use economy::resources::Resource;
+use economy::resources::{Resource, ResourceAmount, Entry};
 use economy::resources::Resource::*;
 use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use economy::households::aging::{self, MemberLife};
 use land_use::buildings::BuildingID;
 use transport::pathfinding::RoughLocationID;
 use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+use transport::taxis::TaxiOperatorID;
 
 use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
+use economy::region::RegionLinkID;
+use economy::utilities::{UtilityNetworkID, Utility, ALL_UTILITIES};
+use economy::difficulty::{self, DifficultyProfile};
+use cb_util::config_manager::{Name, ConfigUser, ConfigUserID};
+
+// what the neighboring town charges per unit of the goods it sells to the city
+pub const TRADED_GOODS: &[(Resource, ResourceAmount)] = &[
+    (Groceries, amount!(2.7)),
+    (Produce, amount!(1.3)),
+    (Grain, amount!(0.13)),
+    (Flour, amount!(0.3)),
+    (BakedGoods, amount!(2.5)),
+    (Meat, amount!(3.0)),
+    (DairyGoods, amount!(1.31)),
+    (BuildingMaterials, amount!(1.5)),
+    (Seeds, amount!(0.3)),
+];
+
+pub fn trade_price(resource: Resource) -> Option<ResourceAmount> {
+    TRADED_GOODS
+        .iter()
+        .find(|&&(traded, _)| traded == resource)
+        .map(|&(_, price)| price)
+}
+
+// jobs in the neighboring town, taken by residents who commute there
+const JOBS_OFFER_IDX: usize = 0;
+const N_JOBS: u32 = 300;
+pub const WAGE_PER_SHIFT: ResourceAmount = amount!(50.0);
+const SHIFT_HOURS: usize = 5;
+// power and water the city can draw from the regional grid through each
+// connection, enough for a village. Bigger cities need their own plants
+const UTILITY_IMPORT_CAPACITY: f32 = 40.0;
 
 #[derive(Compact, Clone)]
 pub struct NeighboringTownTrade {
     id: NeighboringTownTradeID,
     town: BuildingID,
     core: HouseholdCore,
+    // members that come from a connected neighboring city to work here
+    commuters: CVec<MemberIdx>,
+    difficulty: CHashMap<Name, DifficultyProfile>,
 }
 
 impl NeighboringTownTrade {
@@ -25,13 +64,27 @@ impl NeighboringTownTrade {
         world: &mut World,
     ) -> Self {
         time.wake_up_in(Ticks(0), id.into(), world);
+        for &utility in &ALL_UTILITIES {
+            UtilityNetworkID::global_first(world).add_supply(
+                id.into(),
+                town.into(),
+                utility,
+                UTILITY_IMPORT_CAPACITY,
+                world,
+            );
+        }
+
+        let price = |resource| trade_price(resource).expect("Should be a traded good");
 
         let offers = vec![
             Offer::new(
                 MemberIdx(0),
                 TimeOfDayRange::new(5, 0, 15, 0),
-                Deal::new(Some((Resource::Money, 50.0)), Duration::from_hours(5)),
-                300,
+                Deal::new(
+                    Some((Resource::Money, WAGE_PER_SHIFT)),
+                    Duration::from_hours(SHIFT_HOURS),
+                ),
+                N_JOBS,
                 false,
             ),
             // Offer::new(
@@ -58,77 +111,104 @@ impl NeighboringTownTrade {
                 MemberIdx(0),
                 TimeOfDayRange::new(7, 0, 20, 0),
                 Deal::new(
-                    vec![(Groceries, 30.0), (Money, -30.0 * 2.7)],
+                    vec![(Groceries, amount!(30.0)), (Money, -30.0 * price(Groceries))],
                     Duration::from_minutes(30),
                 ),
                 32,
                 false,
             ),
-            Offer::new(
+            Offer::wholesale(
                 MemberIdx(0),
                 TimeOfDayRange::new(7, 0, 20, 0),
                 Deal::new(
-                    vec![(Resource::Produce, 20.0), (Resource::Money, -20.0 * 1.3)],
+                    vec![
+                        (Resource::Produce, amount!(20.0)),
+                        (Resource::Money, -20.0 * price(Produce)),
+                    ],
                     Duration::from_minutes(10),
                 ),
                 8,
-                false,
             ),
-            Offer::new(
+            Offer::wholesale(
                 MemberIdx(0),
                 TimeOfDayRange::new(7, 0, 20, 0),
                 Deal::new(
-                    vec![(Resource::Grain, 200.0), (Resource::Money, -200.0 * 0.13)],
+                    vec![
+                        (Resource::Grain, amount!(200.0)),
+                        (Resource::Money, -200.0 * price(Grain)),
+                    ],
                     Duration::from_minutes(10),
                 ),
                 8,
-                false,
             ),
-            Offer::new(
+            Offer::wholesale(
                 MemberIdx(0),
                 TimeOfDayRange::new(7, 0, 20, 0),
                 Deal::new(
-                    vec![(Resource::Flour, 200.0), (Resource::Money, -200.0 * 0.3)],
+                    vec![
+                        (Resource::Flour, amount!(200.0)),
+                        (Resource::Money, -200.0 * price(Flour)),
+                    ],
                     Duration::from_minutes(10),
                 ),
                 8,
-                false,
             ),
-            Offer::new(
+            Offer::wholesale(
                 MemberIdx(0),
                 TimeOfDayRange::new(7, 0, 20, 0),
                 Deal::new(
                     vec![
-                        (Resource::BakedGoods, 100.0),
-                        (Resource::Money, -100.0 * 2.5),
+                        (Resource::BakedGoods, amount!(100.0)),
+                        (Resource::Money, -100.0 * price(BakedGoods)),
                     ],
                     Duration::from_minutes(10),
                 ),
                 60,
-                false,
             ),
-            Offer::new(
+            Offer::wholesale(
                 MemberIdx(0),
                 TimeOfDayRange::new(7, 0, 20, 0),
                 Deal::new(
-                    vec![(Resource::Meat, 5.0), (Resource::Money, -5.0 * 3.0)],
+                    vec![(Resource::Meat, amount!(5.0)), (Resource::Money, -5.0 * price(Meat))],
                     Duration::from_minutes(10),
                 ),
                 8,
-                false,
             ),
-            Offer::new(
+            Offer::wholesale(
                 MemberIdx(0),
                 TimeOfDayRange::new(7, 0, 20, 0),
                 Deal::new(
                     vec![
-                        (Resource::DairyGoods, 10.0),
-                        (Resource::Money, -10.0 * 1.31),
+                        (Resource::DairyGoods, amount!(10.0)),
+                        (Resource::Money, -10.0 * price(DairyGoods)),
+                    ],
+                    Duration::from_minutes(10),
+                ),
+                8,
+            ),
+            Offer::wholesale(
+                MemberIdx(0),
+                TimeOfDayRange::new(7, 0, 20, 0),
+                Deal::new(
+                    vec![
+                        (Resource::BuildingMaterials, amount!(50.0)),
+                        (Resource::Money, -50.0 * price(BuildingMaterials)),
+                    ],
+                    Duration::from_minutes(20),
+                ),
+                8,
+            ),
+            Offer::wholesale(
+                MemberIdx(0),
+                TimeOfDayRange::new(7, 0, 20, 0),
+                Deal::new(
+                    vec![
+                        (Resource::Seeds, amount!(100.0)),
+                        (Resource::Money, -100.0 * price(Seeds)),
                     ],
                     Duration::from_minutes(10),
                 ),
                 8,
-                false,
             ),
             /* Offer::new(
              *     MemberIdx(0),
@@ -182,11 +262,119 @@ impl NeighboringTownTrade {
              * ), */
         ];
 
-        NeighboringTownTrade {
+        let trade = NeighboringTownTrade {
             id,
             town,
             core: HouseholdCore::new(id.into(), world, 10, town.into(), offers.into()),
+            commuters: CVec::new(),
+            difficulty: CHashMap::new(),
+        };
+        trade.get_initial_config(world);
+        trade
+    }
+
+    // Offers are created at base prices, the difficulty scales them once it's known
+    fn reprice_imports(&mut self) {
+        let factor = difficulty::current(&self.difficulty).import_price_factor;
+        for offer in self.core.provided_offers.iter_mut() {
+            let resource = offer.deal.main_given();
+            if let Some(price) = trade_price(resource) {
+                let amount = offer.deal.delta.get(resource).cloned().unwrap_or(amount!(0.0));
+                *offer.deal.delta.mut_entry_or(Money, amount!(0.0)) = -amount * price * factor;
+            }
+        }
+    }
+
+    // Goods bought from the city count up, goods sold to it count down. What's
+    // left over can be shipped to a connected neighboring city, shortfalls
+    // can be covered by it
+    pub fn report_stock(&mut self, link: RegionLinkID, world: &mut World) {
+        let stock = self
+            .core
+            .resources
+            .iter()
+            .filter(|&&Entry(resource, _)| trade_price(resource).is_some())
+            .map(|&Entry(resource, amount)| (resource, amount))
+            .collect();
+        link.on_gateway_stock(self.id, stock, world);
+    }
+
+    pub fn ship_freight(&mut self, resource: Resource, amount: ResourceAmount, _: &mut World) {
+        *self.core.resources.mut_entry_or(resource, amount!(0.0)) -= amount;
+    }
+
+    pub fn receive_freight(&mut self, resource: Resource, amount: ResourceAmount, _: &mut World) {
+        *self.core.resources.mut_entry_or(resource, amount!(0.0)) += amount;
+    }
+
+    // While connected to a neighboring city, only the jobs it agreed to
+    // give to commuters are offered, at the wage it pays
+    pub fn set_regional_jobs(&mut self, jobs: Option<(u32, ResourceAmount)>, _: &mut World) {
+        let (n_jobs, wage) = jobs.unwrap_or((N_JOBS, WAGE_PER_SHIFT));
+        let offer = &mut self.core.provided_offers[JOBS_OFFER_IDX];
+        offer.max_users = n_jobs;
+        offer.deal = Deal::new(
+            Some((Resource::Money, wage)),
+            Duration::from_hours(SHIFT_HOURS),
+        );
+    }
+
+    // Each of the given number of commuters owes a shift until they worked it
+    // at one of the city's businesses, they only ever look for work.
+    // Commuters beyond that stay at the gateway
+    pub fn host_commuters(&mut self, n_commuters: u32, _: &mut World) {
+        while self.commuters.len() < n_commuters as usize {
+            let member = self.core.add_member(self.town.into());
+            self.core.member_lives[member.as_idx()] = MemberLife::aged(aging::WORKER_AGE);
+            self.commuters.push(member);
+        }
+
+        for (i, member) in self.commuters.iter().enumerate() {
+            let money = self.core.member_resources[member.as_idx()]
+                .mut_entry_or(Money, amount!(0.0));
+            if i < n_commuters as usize {
+                if *money >= 0.0 {
+                    *money = -WAGE_PER_SHIFT;
+                }
+            } else {
+                *money = amount!(0.0);
+            }
+        }
+    }
+
+    // Power and water from a connected neighboring city, on top of
+    // what the regional grid provides anyway
+    pub fn set_utility_import(&mut self, utility: Utility, capacity: f32, world: &mut World) {
+        UtilityNetworkID::global_first(world).set_supply_capacity(
+            self.id_as(),
+            utility,
+            UTILITY_IMPORT_CAPACITY + capacity,
+            world,
+        );
+    }
+
+    fn is_commuter(&self, member: MemberIdx) -> bool {
+        self.commuters.contains(&member)
+    }
+}
+
+impl ConfigUser<DifficultyProfile> for NeighboringTownTrade {
+    fn local_cache(&mut self) -> &mut CHashMap<Name, DifficultyProfile> {
+        &mut self.difficulty
+    }
+
+    fn apply_config_change(
+        &mut self,
+        name: Name,
+        maybe_value: &COption<DifficultyProfile>,
+        _: &mut World,
+    ) {
+        if let COption(Some(value)) = *maybe_value {
+            self.difficulty.insert(name, value);
+        } else {
+            self.difficulty.remove(name);
         }
+        self.reprice_imports();
     }
 }
 
@@ -215,12 +403,38 @@ impl Household for NeighboringTownTrade {
         1.0
     }
 
+    fn member_purchases(&self, member: MemberIdx, resource: Resource) -> bool {
+        if self.is_commuter(member) {
+            resource == Money
+        } else {
+            self.purchases(resource)
+        }
+    }
+
+    // what commuters earn they keep for themselves
+    fn receive_deal(&mut self, deal: &Deal, member: MemberIdx, _: &mut World) {
+        let is_commuter = self.is_commuter(member);
+        let core = &mut self.core;
+        deal.delta.give_to_shared_private(
+            &mut core.resources,
+            &mut core.member_resources[member.as_idx()],
+            |_| !is_commuter,
+        );
+    }
+
     fn interesting_resources() -> &'static [Resource] {
         &[
             //Entertainment,
             //Services,
-            Groceries, Produce, Grain, Flour, BakedGoods, Meat,
+            Groceries,
+            Produce,
+            Grain,
+            Flour,
+            BakedGoods,
+            Meat,
             DairyGoods,
+            BuildingMaterials,
+            Seeds,
             /* Wood,
              *Furniture,
              *TextileGoods,
@@ -236,10 +450,15 @@ impl Household for NeighboringTownTrade {
     }
 
     fn member_name(&self, member: MemberIdx) -> String {
-        format!("Neighboring Town Worker {}", member.0 + 1)
+        if self.is_commuter(member) {
+            format!("Commuter from the Neighboring City {}", member.0 + 1)
+        } else {
+            format!("Neighboring Town Worker {}", member.0 + 1)
+        }
     }
 
     fn on_destroy(&mut self, world: &mut World) {
+        UtilityNetworkID::global_first(world).remove_supply(self.id_as(), world);
         self.town.remove_household(self.id_as(), world);
     }
 }
@@ -259,15 +478,25 @@ impl EvaluationRequester for NeighboringTownTrade {
         self.update_results(resource, &ResultAspect::SetTarget(n), world);
     }
 
+    // commuters come for the city's jobs, not the ones at the gateway
     fn on_result(&mut self, result: &EvaluatedSearchResult, world: &mut World) {
         let &EvaluatedSearchResult {
             resource,
             ref evaluated_deals,
             ..
         } = result;
+        let id_as_household = self.id_as();
         self.update_results(
             resource,
-            &ResultAspect::AddDeals(evaluated_deals.clone()),
+            &ResultAspect::AddDeals(
+                evaluated_deals
+                    .iter()
+                    .filter(|evaluated| {
+                        resource != Money || evaluated.offer.household != id_as_household
+                    })
+                    .cloned()
+                    .collect(),
+            ),
             world,
         );
     }
@@ -288,6 +517,14 @@ impl TripListener for NeighboringTownTrade {
     ) {
         self.on_trip_result(trip, result, rough_source, rough_destination, world);
     }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
 }
 
 impl Temporal for NeighboringTownTrade {
@@ -0,0 +1,120 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for School {
+    type ID = SchoolID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct SchoolID {
+    _raw_id: RawID
+}
+
+impl Copy for SchoolID {}
+impl Clone for SchoolID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for SchoolID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "SchoolID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for SchoolID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for SchoolID {
+    fn eq(&self, other: &SchoolID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for SchoolID {}
+
+impl TypedID for SchoolID {
+    type Target = School;
+
+    fn from_raw(id: RawID) -> Self {
+        SchoolID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl SchoolID {
+    pub fn move_into(site: BuildingID, time: TimeID, world: &mut World) -> Self {
+        let id = SchoolID::from_raw(world.allocate_instance_id::<School>());
+        let swarm = world.local_broadcast::<School>();
+        world.send(swarm, MSG_School_move_into(id, site, time));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_School_move_into(pub SchoolID, pub BuildingID, pub TimeID);
+
+impl Into<HouseholdID> for SchoolID {
+    fn into(self) -> HouseholdID {
+        HouseholdID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<EvaluationRequesterID> for SchoolID {
+    fn into(self) -> EvaluationRequesterID {
+        EvaluationRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TemporalID> for SchoolID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for SchoolID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<RoughLocationID> for SchoolID {
+    fn into(self) -> RoughLocationID {
+        RoughLocationID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TripListenerID> for SchoolID {
+    fn into(self) -> TripListenerID {
+        TripListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HouseholdID::register_implementor::<School>(system);
+    EvaluationRequesterID::register_implementor::<School>(system);
+    TemporalID::register_implementor::<School>(system);
+    SleeperID::register_implementor::<School>(system);
+    RoughLocationID::register_implementor::<School>(system);
+    TripListenerID::register_implementor::<School>(system);
+    system.add_spawner::<School, _, _>(
+        |&MSG_School_move_into(id, site, time), world| {
+            School::move_into(id, site, time, world)
+        }, false
+    );
+}
\ No newline at end of file
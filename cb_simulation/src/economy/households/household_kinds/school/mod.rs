@@ -0,0 +1,187 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks};
+use cb_time::actors::TimeID;
+use economy::resources::Resource;
+use economy::resources::Resource::*;
+use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use land_use::buildings::BuildingID;
+
+use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
+
+#[derive(Compact, Clone)]
+pub struct School {
+    id: SchoolID,
+    site: BuildingID,
+    core: HouseholdCore,
+}
+
+impl School {
+    pub fn move_into(id: SchoolID, site: BuildingID, time: TimeID, world: &mut World) -> School {
+        time.wake_up_in(Ticks(0), id.into(), world);
+
+        School {
+            id,
+            site,
+            core: HouseholdCore::new(
+                id.into(),
+                world,
+                1,
+                site.into(),
+                vec![
+                    Offer::new(
+                        MemberIdx(0),
+                        TimeOfDayRange::new(7, 0, 10, 0),
+                        Deal::new(
+                            vec![(Education, amount!(1.0)), (Money, amount!(-2.0))],
+                            Duration::from_hours(6),
+                        ),
+                        30,
+                        false,
+                    ),
+                    Offer::new(
+                        MemberIdx(0),
+                        TimeOfDayRange::new(7, 0, 10, 0),
+                        Deal::new(Some((Money, amount!(70.0))), Duration::from_hours(7)),
+                        3,
+                        false,
+                    )
+                    .requiring_education(14.0),
+                ]
+                .into(),
+            ),
+        }
+    }
+}
+
+impl Household for School {
+    fn core(&self) -> &HouseholdCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut HouseholdCore {
+        &mut self.core
+    }
+
+    fn site(&self) -> RoughLocationID {
+        self.site.into()
+    }
+
+    fn is_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn supplier_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn importance(_resource: Resource, _time: TimeOfDay) -> f32 {
+        0.0
+    }
+
+    fn interesting_resources() -> &'static [Resource] {
+        &[Money, Education]
+    }
+
+    fn decay(&mut self, dt: Duration, _: &mut World) {
+        // teaching capacity for a full class each day
+        let education = self.core.resources.mut_entry_or(Education, amount!(0.0));
+        *education += 30.0 * dt.as_days();
+    }
+
+    fn household_name(&self) -> String {
+        "School".to_owned()
+    }
+
+    fn member_name(&self, member: MemberIdx) -> String {
+        format!("Teacher {}", member.0 + 1)
+    }
+
+    fn on_destroy(&mut self, world: &mut World) {
+        self.site.remove_household(self.id_as(), world);
+    }
+}
+
+use economy::households::ResultAspect;
+
+impl EvaluationRequester for School {
+    fn expect_n_results(&mut self, resource: Resource, n: u32, world: &mut World) {
+        self.update_results(resource, &ResultAspect::SetTarget(n), world);
+    }
+
+    fn on_result(&mut self, result: &EvaluatedSearchResult, world: &mut World) {
+        let &EvaluatedSearchResult {
+            resource,
+            ref evaluated_deals,
+            ..
+        } = result;
+        self.update_results(
+            resource,
+            &ResultAspect::AddDeals(evaluated_deals.clone()),
+            world,
+        );
+    }
+}
+
+use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
+use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+use cb_time::load_spreading;
+const UPDATE_EVERY_N_SECS: u32 = 4;
+
+impl Temporal for School {
+    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+        let update_interval = Ticks(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+        if load_spreading::is_turn(self.id.as_raw(), current_instant, update_interval) {
+            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+        }
+    }
+}
+
+impl Sleeper for School {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        self.update_core(current_instant, world);
+    }
+}
+
+use transport::pathfinding::{RoughLocationID, RoughLocation, RoughLocationResolve};
+
+impl RoughLocation for School {
+    fn resolve(&self) -> RoughLocationResolve {
+        RoughLocationResolve::SameAs(self.site())
+    }
+}
+
+use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+use transport::taxis::TaxiOperatorID;
+
+impl TripListener for School {
+    fn trip_created(&mut self, trip: TripID, world: &mut World) {
+        self.on_trip_created(trip, world);
+    }
+
+    fn trip_result(
+        &mut self,
+        trip: TripID,
+        result: TripResult,
+        rough_source: RoughLocationID,
+        rough_destination: RoughLocationID,
+        world: &mut World,
+    ) {
+        self.on_trip_result(trip, result, rough_source, rough_destination, world);
+    }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<School>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,127 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for TaxiCompany {
+    type ID = TaxiCompanyID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TaxiCompanyID {
+    _raw_id: RawID
+}
+
+impl Copy for TaxiCompanyID {}
+impl Clone for TaxiCompanyID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TaxiCompanyID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TaxiCompanyID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TaxiCompanyID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TaxiCompanyID {
+    fn eq(&self, other: &TaxiCompanyID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TaxiCompanyID {}
+
+impl TypedID for TaxiCompanyID {
+    type Target = TaxiCompany;
+
+    fn from_raw(id: RawID) -> Self {
+        TaxiCompanyID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TaxiCompanyID {
+    pub fn move_into(site: BuildingID, time: TimeID, world: &mut World) -> Self {
+        let id = TaxiCompanyID::from_raw(world.allocate_instance_id::<TaxiCompany>());
+        let swarm = world.local_broadcast::<TaxiCompany>();
+        world.send(swarm, MSG_TaxiCompany_move_into(id, site, time));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TaxiCompany_move_into(pub TaxiCompanyID, pub BuildingID, pub TimeID);
+
+impl Into<HouseholdID> for TaxiCompanyID {
+    fn into(self) -> HouseholdID {
+        HouseholdID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TaxiOperatorID> for TaxiCompanyID {
+    fn into(self) -> TaxiOperatorID {
+        TaxiOperatorID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TemporalID> for TaxiCompanyID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for TaxiCompanyID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<EvaluationRequesterID> for TaxiCompanyID {
+    fn into(self) -> EvaluationRequesterID {
+        EvaluationRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<RoughLocationID> for TaxiCompanyID {
+    fn into(self) -> RoughLocationID {
+        RoughLocationID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TripListenerID> for TaxiCompanyID {
+    fn into(self) -> TripListenerID {
+        TripListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HouseholdID::register_implementor::<TaxiCompany>(system);
+    TaxiOperatorID::register_implementor::<TaxiCompany>(system);
+    TemporalID::register_implementor::<TaxiCompany>(system);
+    SleeperID::register_implementor::<TaxiCompany>(system);
+    EvaluationRequesterID::register_implementor::<TaxiCompany>(system);
+    RoughLocationID::register_implementor::<TaxiCompany>(system);
+    TripListenerID::register_implementor::<TaxiCompany>(system);
+    system.add_spawner::<TaxiCompany, _, _>(
+        |&MSG_TaxiCompany_move_into(id, site, time), world| {
+            TaxiCompany::move_into(id, site, time, world)
+        }, false
+    );
+}
\ No newline at end of file
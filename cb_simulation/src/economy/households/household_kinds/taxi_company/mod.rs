@@ -0,0 +1,179 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks};
+use cb_time::actors::TimeID;
+use economy::resources::{Resource, to_amount};
+use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use land_use::buildings::BuildingID;
+use transport::taxis::{TaxiOperator, TaxiOperatorID, TaxiDispatcherID};
+
+use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
+
+const FLEET_SIZE: u8 = 6;
+
+// Runs a fleet of taxis from its depot, which the taxi dispatcher
+// sends to whoever calls, and lives off the fares
+#[derive(Compact, Clone)]
+pub struct TaxiCompany {
+    id: TaxiCompanyID,
+    site: BuildingID,
+    core: HouseholdCore,
+}
+
+impl TaxiCompany {
+    pub fn move_into(
+        id: TaxiCompanyID,
+        site: BuildingID,
+        time: TimeID,
+        world: &mut World,
+    ) -> TaxiCompany {
+        time.wake_up_in(Ticks(0), id.into(), world);
+        TaxiDispatcherID::global_first(world).add_fleet(id.into(), site.into(), FLEET_SIZE, world);
+
+        TaxiCompany {
+            id,
+            site,
+            core: HouseholdCore::new(
+                id.into(),
+                world,
+                1,
+                site.into(),
+                vec![
+                    Offer::new(
+                        MemberIdx(0),
+                        TimeOfDayRange::new(6, 0, 14, 0),
+                        Deal::new(Some((Resource::Money, amount!(55.0))), Duration::from_hours(8)),
+                        3,
+                        false,
+                    ),
+                    Offer::new(
+                        MemberIdx(0),
+                        TimeOfDayRange::new(14, 0, 22, 0),
+                        Deal::new(Some((Resource::Money, amount!(55.0))), Duration::from_hours(8)),
+                        3,
+                        false,
+                    ),
+                ]
+                .into(),
+            ),
+        }
+    }
+}
+
+impl Household for TaxiCompany {
+    fn core(&self) -> &HouseholdCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut HouseholdCore {
+        &mut self.core
+    }
+
+    fn site(&self) -> RoughLocationID {
+        self.site.into()
+    }
+
+    fn is_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn supplier_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn importance(_resource: Resource, _time: TimeOfDay) -> f32 {
+        0.0
+    }
+
+    fn interesting_resources() -> &'static [Resource] {
+        &[Resource::Money]
+    }
+
+    fn decay(&mut self, _dt: Duration, _: &mut World) {}
+
+    fn on_destroy(&mut self, world: &mut World) {
+        TaxiDispatcherID::global_first(world).remove_fleet(self.id_as(), world);
+        self.site.remove_household(self.id_as(), world);
+    }
+
+    fn household_name(&self) -> String {
+        "Taxi Company".to_owned()
+    }
+
+    fn member_name(&self, member: MemberIdx) -> String {
+        format!("Taxi Driver {}", member.0 + 1)
+    }
+}
+
+impl TaxiOperator for TaxiCompany {
+    fn fare_received(&mut self, amount: f32, world: &mut World) {
+        self.receive_funds(to_amount(amount), world);
+    }
+}
+
+use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
+use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+use cb_time::load_spreading;
+const UPDATE_EVERY_N_SECS: u32 = 4;
+
+impl Temporal for TaxiCompany {
+    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+        let update_interval = Ticks(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+        if load_spreading::is_turn(self.id.as_raw(), current_instant, update_interval) {
+            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+        }
+    }
+}
+
+impl Sleeper for TaxiCompany {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        self.update_core(current_instant, world);
+    }
+}
+
+impl EvaluationRequester for TaxiCompany {
+    fn expect_n_results(&mut self, _r: Resource, _n: u32, _: &mut World) {}
+    fn on_result(&mut self, _e: &EvaluatedSearchResult, _: &mut World) {}
+}
+
+use transport::pathfinding::{RoughLocationID, RoughLocation, RoughLocationResolve};
+
+impl RoughLocation for TaxiCompany {
+    fn resolve(&self) -> RoughLocationResolve {
+        RoughLocationResolve::SameAs(self.site())
+    }
+}
+
+use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+
+impl TripListener for TaxiCompany {
+    fn trip_created(&mut self, trip: TripID, world: &mut World) {
+        self.on_trip_created(trip, world);
+    }
+
+    fn trip_result(
+        &mut self,
+        trip: TripID,
+        result: TripResult,
+        rough_source: RoughLocationID,
+        rough_destination: RoughLocationID,
+        world: &mut World,
+    ) {
+        self.on_trip_result(trip, result, rough_source, rough_destination, world);
+    }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<TaxiCompany>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,120 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for UtilityCompany {
+    type ID = UtilityCompanyID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct UtilityCompanyID {
+    _raw_id: RawID
+}
+
+impl Copy for UtilityCompanyID {}
+impl Clone for UtilityCompanyID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for UtilityCompanyID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "UtilityCompanyID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for UtilityCompanyID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for UtilityCompanyID {
+    fn eq(&self, other: &UtilityCompanyID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for UtilityCompanyID {}
+
+impl TypedID for UtilityCompanyID {
+    type Target = UtilityCompany;
+
+    fn from_raw(id: RawID) -> Self {
+        UtilityCompanyID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl UtilityCompanyID {
+    pub fn move_into(utility: Utility, site: BuildingID, time: TimeID, world: &mut World) -> Self {
+        let id = UtilityCompanyID::from_raw(world.allocate_instance_id::<UtilityCompany>());
+        let swarm = world.local_broadcast::<UtilityCompany>();
+        world.send(swarm, MSG_UtilityCompany_move_into(id, utility, site, time));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityCompany_move_into(pub UtilityCompanyID, pub Utility, pub BuildingID, pub TimeID);
+
+impl Into<HouseholdID> for UtilityCompanyID {
+    fn into(self) -> HouseholdID {
+        HouseholdID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TemporalID> for UtilityCompanyID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for UtilityCompanyID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<EvaluationRequesterID> for UtilityCompanyID {
+    fn into(self) -> EvaluationRequesterID {
+        EvaluationRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<RoughLocationID> for UtilityCompanyID {
+    fn into(self) -> RoughLocationID {
+        RoughLocationID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TripListenerID> for UtilityCompanyID {
+    fn into(self) -> TripListenerID {
+        TripListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HouseholdID::register_implementor::<UtilityCompany>(system);
+    TemporalID::register_implementor::<UtilityCompany>(system);
+    SleeperID::register_implementor::<UtilityCompany>(system);
+    EvaluationRequesterID::register_implementor::<UtilityCompany>(system);
+    RoughLocationID::register_implementor::<UtilityCompany>(system);
+    TripListenerID::register_implementor::<UtilityCompany>(system);
+    system.add_spawner::<UtilityCompany, _, _>(
+        |&MSG_UtilityCompany_move_into(id, utility, site, time), world| {
+            UtilityCompany::move_into(id, utility, site, time, world)
+        }, false
+    );
+}
\ No newline at end of file
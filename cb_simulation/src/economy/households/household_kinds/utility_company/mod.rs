@@ -0,0 +1,183 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks};
+use cb_time::actors::TimeID;
+use economy::resources::Resource;
+use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use economy::utilities::{Utility, UtilityNetworkID};
+use land_use::buildings::BuildingID;
+
+use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
+
+// enough for a neighborhood of a couple of hundred homes and the shops around them
+const CAPACITY: f32 = 300.0;
+
+// Power plants and waterworks, which feed the utility network
+// for as long as they are in the city
+#[derive(Compact, Clone)]
+pub struct UtilityCompany {
+    id: UtilityCompanyID,
+    site: BuildingID,
+    utility: Utility,
+    core: HouseholdCore,
+}
+
+impl UtilityCompany {
+    pub fn move_into(
+        id: UtilityCompanyID,
+        utility: Utility,
+        site: BuildingID,
+        time: TimeID,
+        world: &mut World,
+    ) -> UtilityCompany {
+        time.wake_up_in(Ticks(0), id.into(), world);
+        UtilityNetworkID::global_first(world).add_supply(
+            id.into(),
+            site.into(),
+            utility,
+            CAPACITY,
+            world,
+        );
+
+        UtilityCompany {
+            id,
+            site,
+            utility,
+            core: HouseholdCore::new(
+                id.into(),
+                world,
+                1,
+                site.into(),
+                vec![Offer::new(
+                    MemberIdx(0),
+                    TimeOfDayRange::new(6, 0, 14, 0),
+                    Deal::new(Some((Resource::Money, amount!(60.0))), Duration::from_hours(8)),
+                    4,
+                    false,
+                )
+                .requiring_education(11.0)]
+                .into(),
+            ),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self.utility {
+            Utility::Electricity => "Power Plant",
+            Utility::Water => "Waterworks",
+        }
+    }
+}
+
+impl Household for UtilityCompany {
+    fn core(&self) -> &HouseholdCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut HouseholdCore {
+        &mut self.core
+    }
+
+    fn site(&self) -> RoughLocationID {
+        self.site.into()
+    }
+
+    fn is_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn supplier_shared(_: Resource) -> bool {
+        true
+    }
+
+    fn importance(_resource: Resource, _time: TimeOfDay) -> f32 {
+        0.0
+    }
+
+    fn interesting_resources() -> &'static [Resource] {
+        &[Resource::Money]
+    }
+
+    fn decay(&mut self, _dt: Duration, _: &mut World) {}
+
+    fn on_destroy(&mut self, world: &mut World) {
+        UtilityNetworkID::global_first(world).remove_supply(self.id_as(), world);
+        self.site.remove_household(self.id_as(), world);
+    }
+
+    fn household_name(&self) -> String {
+        self.name().to_owned()
+    }
+
+    fn member_name(&self, member: MemberIdx) -> String {
+        format!("{} Operator {}", self.name(), member.0 + 1)
+    }
+}
+
+use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
+use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+use cb_time::load_spreading;
+const UPDATE_EVERY_N_SECS: u32 = 4;
+
+impl Temporal for UtilityCompany {
+    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+        let update_interval = Ticks(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+        if load_spreading::is_turn(self.id.as_raw(), current_instant, update_interval) {
+            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+        }
+    }
+}
+
+impl Sleeper for UtilityCompany {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        self.update_core(current_instant, world);
+    }
+}
+
+impl EvaluationRequester for UtilityCompany {
+    fn expect_n_results(&mut self, _r: Resource, _n: u32, _: &mut World) {}
+    fn on_result(&mut self, _e: &EvaluatedSearchResult, _: &mut World) {}
+}
+
+use transport::pathfinding::{RoughLocationID, RoughLocation, RoughLocationResolve};
+
+impl RoughLocation for UtilityCompany {
+    fn resolve(&self) -> RoughLocationResolve {
+        RoughLocationResolve::SameAs(self.site())
+    }
+}
+
+use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+use transport::taxis::TaxiOperatorID;
+
+impl TripListener for UtilityCompany {
+    fn trip_created(&mut self, trip: TripID, world: &mut World) {
+        self.on_trip_created(trip, world);
+    }
+
+    fn trip_result(
+        &mut self,
+        trip: TripID,
+        result: TripResult,
+        rough_source: RoughLocationID,
+        rough_destination: RoughLocationID,
+        world: &mut World,
+    ) {
+        self.on_trip_result(trip, result, rough_source, rough_destination, world);
+    }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<UtilityCompany>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -66,6 +66,12 @@ impl VegetableFarmID {
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_VegetableFarm_move_into(pub VegetableFarmID, pub BuildingID, pub TimeID);
 
+impl Into<FarmID> for VegetableFarmID {
+    fn into(self) -> FarmID {
+        FarmID::from_raw(self.as_raw())
+    }
+}
+
 impl Into<HouseholdID> for VegetableFarmID {
     fn into(self) -> HouseholdID {
         HouseholdID::from_raw(self.as_raw())
@@ -106,6 +112,7 @@ impl Into<TripListenerID> for VegetableFarmID {
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
+    FarmID::register_implementor::<VegetableFarm>(system);
     HouseholdID::register_implementor::<VegetableFarm>(system);
     TemporalID::register_implementor::<VegetableFarm>(system);
     SleeperID::register_implementor::<VegetableFarm>(system);
@@ -3,8 +3,12 @@ use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks};
 use cb_time::actors::TimeID;
 use economy::resources::Resource;
 use economy::resources::Resource::*;
-use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use economy::market::{Deal, MarketTier, EvaluationRequester, EvaluationRequesterID,
+EvaluatedSearchResult};
 use land_use::buildings::BuildingID;
+use cb_util::log::info;
+use super::farming::{self, Farm, FarmID, Field};
+const LOG_T: &str = "Vegetable Farm";
 
 use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
 
@@ -13,6 +17,7 @@ pub struct VegetableFarm {
     id: VegetableFarmID,
     site: BuildingID,
     core: HouseholdCore,
+    field: Field,
 }
 
 impl VegetableFarm {
@@ -23,6 +28,7 @@ impl VegetableFarm {
         world: &mut World,
     ) -> VegetableFarm {
         time.wake_up_in(Ticks(0), id.into(), world);
+        site.measure_field(id.into(), world);
 
         VegetableFarm {
             id,
@@ -33,30 +39,39 @@ impl VegetableFarm {
                 1,
                 site.into(),
                 vec![
-                    Offer::new(
+                    Offer::wholesale(
                         MemberIdx(0),
                         TimeOfDayRange::new(7, 0, 20, 0),
                         Deal::new(
-                            vec![(Resource::Produce, 20.0), (Resource::Money, -20.0 * 1.3)],
+                            vec![
+                                (Resource::Produce, amount!(20.0)),
+                                (Resource::Money, amount!(-20.0 * 1.3)),
+                            ],
                             Duration::from_minutes(10),
                         ),
                         4,
-                        false,
                     ),
                     Offer::new(
                         MemberIdx(0),
                         TimeOfDayRange::new(5, 0, 15, 0),
-                        Deal::new(Some((Resource::Money, 40.0)), Duration::from_hours(4)),
+                        Deal::new(Some((Resource::Money, amount!(40.0))), Duration::from_hours(4)),
                         2,
                         false,
                     ),
                 ]
                 .into(),
             ),
+            field: Field::new(Produce),
         }
     }
 }
 
+impl Farm for VegetableFarm {
+    fn on_field_measured(&mut self, area: f32, _: &mut World) {
+        self.field.set_area(area);
+    }
+}
+
 impl Household for VegetableFarm {
     fn core(&self) -> &HouseholdCore {
         &self.core
@@ -78,19 +93,24 @@ impl Household for VegetableFarm {
         true
     }
 
-    fn importance(_: Resource, _: TimeOfDay) -> f32 {
-        0.0
+    fn importance(resource: Resource, time: TimeOfDay) -> f32 {
+        farming::importance(resource, time)
     }
 
     fn interesting_resources() -> &'static [Resource] {
-        &[Resource::Money, Resource::Produce]
+        &[Resource::Money, Resource::Produce, Resource::Seeds]
     }
 
-    fn decay(&mut self, dt: Duration, _: &mut World) {
-        {
-            let produce = self.core.resources.mut_entry_or(Produce, 0.0);
-            *produce += 80.0 * dt.as_days();
-        }
+    fn market_tier(_resource: Resource) -> MarketTier {
+        MarketTier::Wholesale
+    }
+
+    fn decay(&mut self, _dt: Duration, _: &mut World) {}
+
+    fn can_provide(&self, deal: &Deal) -> bool {
+        deal.delta.get(Produce).map_or(true, |&amount| {
+            self.field.has_in_store(amount, &self.core.resources)
+        })
     }
 
     fn on_destroy(&mut self, world: &mut World) {
@@ -108,15 +128,27 @@ impl Household for VegetableFarm {
 
 use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
 use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+use cb_time::load_spreading;
 const UPDATE_EVERY_N_SECS: u32 = 4;
 
 impl Temporal for VegetableFarm {
     fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id.as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
+        let update_interval = Ticks(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+        if load_spreading::is_turn(self.id.as_raw(), current_instant, update_interval) {
             self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+
+            let dt = Duration(UPDATE_EVERY_N_SECS);
+            if let Some(harvest) = self
+                .field
+                .tend(dt, current_instant, &mut self.core.resources)
+            {
+                info(
+                    LOG_T,
+                    format!("Harvested {:.0} {}", harvest, Produce),
+                    self.id,
+                    world,
+                );
+            }
         }
     }
 }
@@ -141,6 +173,7 @@ impl RoughLocation for VegetableFarm {
 }
 
 use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+use transport::taxis::TaxiOperatorID;
 
 impl TripListener for VegetableFarm {
     fn trip_created(&mut self, trip: TripID, world: &mut World) {
@@ -157,6 +190,14 @@ impl TripListener for VegetableFarm {
     ) {
         self.on_trip_result(trip, result, rough_source, rough_destination, world);
     }
+
+    fn toll_paid(&mut self, amount: f32, world: &mut World) {
+        self.pay_toll(amount, world);
+    }
+
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        self.pay_fare(amount, operator, world);
+    }
 }
 
 pub fn setup(system: &mut ActorSystem) {
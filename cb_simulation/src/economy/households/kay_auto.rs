@@ -86,10 +86,74 @@ impl HouseholdID {
         world.send(self.as_raw(), MSG_Household_on_destroy());
     }
     
+    pub fn search_started(self, resource: Resource, search: NearestOffersSearchID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_search_started(resource, search));
+    }
+    
+    pub fn receive_funds(self, amount: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_receive_funds(amount));
+    }
+    
+    pub fn pay_toll(self, toll: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_pay_toll(toll));
+    }
+    
+    pub fn pay_fare(self, fare: f32, operator: TaxiOperatorID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_pay_fare(fare, operator));
+    }
+    
+    pub fn call_ambulance(self, member: MemberIdx, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_call_ambulance(member, instant));
+    }
+    
+    pub fn collect_repayment(self, due: ResourceAmount, bank: BankID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_collect_repayment(due, bank));
+    }
+    
+    pub fn collect_rent(self, due: ResourceAmount, market: HousingMarketID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_collect_rent(due, market));
+    }
+    
+    pub fn evicted(self, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_evicted(instant));
+    }
+    
+    pub fn on_relocated(self, old_site: RoughLocationID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_on_relocated(old_site));
+    }
+    
+    pub fn counterpart_relocated(self, offer: OfferID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_counterpart_relocated(offer));
+    }
+    
     pub fn update_core(self, current_instant: Instant, world: &mut World) {
         world.send(self.as_raw(), MSG_Household_update_core(current_instant));
     }
     
+    pub fn age_members(self, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_age_members(instant));
+    }
+    
+    pub fn dissolve(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_dissolve());
+    }
+    
+    pub fn move_out(self, member: MemberIdx, search: HousingSearchID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_move_out(member, search));
+    }
+    
+    pub fn let_members_depart(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_let_members_depart());
+    }
+    
+    pub fn plan_days(self, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_plan_days(instant));
+    }
+    
+    pub fn release_member_offers(self, member: MemberIdx, only_resource: Option < Resource >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_release_member_offers(member, only_resource));
+    }
+    
     pub fn find_new_task_for(self, member: MemberIdx, instant: Instant, location: RoughLocationID, world: &mut World) {
         world.send(self.as_raw(), MSG_Household_find_new_task_for(member, instant, location));
     }
@@ -102,6 +166,14 @@ impl HouseholdID {
         world.send(self.as_raw(), MSG_Household_choose_deal());
     }
     
+    pub fn commit_to_deal(self, member: MemberIdx, instant: Instant, location: RoughLocationID, best: EvaluatedDeal, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_commit_to_deal(member, instant, location, best));
+    }
+    
+    pub fn on_chained_deals(self, source: RoughLocationID, chained: CVec < EvaluatedDeal >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_on_chained_deals(source, chained));
+    }
+    
     pub fn start_trip(self, member: MemberIdx, instant: Instant, world: &mut World) {
         world.send(self.as_raw(), MSG_Household_start_trip(member, instant));
     }
@@ -126,14 +198,18 @@ impl HouseholdID {
         world.send(self.as_raw(), MSG_Household_on_tick(current_instant));
     }
     
-    pub fn evaluate(self, offer_idx: OfferIdx, instant: Instant, location: RoughLocationID, requester: EvaluationRequesterID, world: &mut World) {
-        world.send(self.as_raw(), MSG_Household_evaluate(offer_idx, instant, location, requester));
+    pub fn evaluate(self, offer_idx: OfferIdx, instant: Instant, location: RoughLocationID, requester: EvaluationRequesterID, modes: ModeSet, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_evaluate(offer_idx, instant, location, requester, modes));
     }
     
     pub fn request_receive_deal(self, offer_idx: OfferIdx, requester: HouseholdID, requester_member: MemberIdx, world: &mut World) {
         world.send(self.as_raw(), MSG_Household_request_receive_deal(offer_idx, requester, requester_member));
     }
     
+    pub fn settle_deal(self, offer_idx: OfferIdx, requester: HouseholdID, requester_member: MemberIdx, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_settle_deal(offer_idx, requester, requester_member));
+    }
+    
     pub fn request_receive_undo_deal(self, offer_idx: OfferIdx, requester: HouseholdID, requester_member: MemberIdx, world: &mut World) {
         world.send(self.as_raw(), MSG_Household_request_receive_undo_deal(offer_idx, requester, requester_member));
     }
@@ -142,6 +218,10 @@ impl HouseholdID {
         world.send(self.as_raw(), MSG_Household_started_using(offer_idx, user, using_member));
     }
     
+    pub fn transfer_use(self, offer_idx: OfferIdx, from: HouseholdID, from_member: Option < MemberIdx >, to: HouseholdID, to_member: Option < MemberIdx >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_transfer_use(offer_idx, from, from_member, to, to_member));
+    }
+    
     pub fn stopped_using(self, offer_idx: OfferIdx, user: HouseholdID, using_member: Option < MemberIdx >, world: &mut World) {
         world.send(self.as_raw(), MSG_Household_stopped_using(offer_idx, user, using_member));
     }
@@ -154,6 +234,42 @@ impl HouseholdID {
         world.send(self.as_raw(), MSG_Household_stopped_actively_using(offer_idx, user, using_member));
     }
     
+    pub fn serve_waiting(self, offer_idx: OfferIdx, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_serve_waiting(offer_idx));
+    }
+    
+    pub fn on_staff_change(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_on_staff_change());
+    }
+    
+    pub fn deal_rejected(self, offer: OfferID, member: MemberIdx, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_deal_rejected(offer, member));
+    }
+    
+    pub fn evaluate_visit(self, resource: Resource, instant: Instant, location: RoughLocationID, requester: EvaluationRequesterID, modes: ModeSet, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_evaluate_visit(resource, instant, location, requester, modes));
+    }
+    
+    pub fn on_utility_supply(self, utility: Utility, supplied: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_on_utility_supply(utility, supplied));
+    }
+    
+    pub fn on_air_pollution(self, pollution: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_on_air_pollution(pollution));
+    }
+    
+    pub fn invest_in_upgrade(self, building: BuildingID, upgrade: EfficiencyUpgrade, cost: ResourceAmount, subsidy: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_invest_in_upgrade(building, upgrade, cost, subsidy));
+    }
+    
+    pub fn befriended(self, friend: HouseholdID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_befriended(friend));
+    }
+    
+    pub fn unfriended(self, former_friend: HouseholdID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_unfriended(former_friend));
+    }
+    
     pub fn withdrawal_confirmed(self, offer_idx: OfferIdx, world: &mut World) {
         world.send(self.as_raw(), MSG_Household_withdrawal_confirmed(offer_idx));
     }
@@ -161,6 +277,30 @@ impl HouseholdID {
     pub fn get_ui_info(self, requester: ui :: HouseholdUIID, world: &mut World) {
         world.send(self.as_raw(), MSG_Household_get_ui_info(requester));
     }
+    
+    pub fn get_activity_log(self, requester: ui :: HouseholdUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_get_activity_log(requester));
+    }
+    
+    pub fn offer_for_vignette(self, requester: ui :: HouseholdUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_offer_for_vignette(requester));
+    }
+    
+    pub fn get_vignette(self, draw: u32, requester: ui :: HouseholdUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_get_vignette(draw, requester));
+    }
+    
+    pub fn observe(self, observer: ApiObserverID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_observe(observer));
+    }
+    
+    pub fn contribute_to_metrics(self, metrics: CityMetricsID, day: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_contribute_to_metrics(metrics, day));
+    }
+    
+    pub fn contribute_to_district_snapshot(self, timeline: TimelineID, instant: Instant, position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_contribute_to_district_snapshot(timeline, instant, position));
+    }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<HouseholdRepresentative>();
@@ -173,10 +313,28 @@ impl HouseholdID {
         system.register_trait_message::<MSG_Household_stop_using>();
         system.register_trait_message::<MSG_Household_destroy>();
         system.register_trait_message::<MSG_Household_on_destroy>();
+        system.register_trait_message::<MSG_Household_search_started>();
+        system.register_trait_message::<MSG_Household_receive_funds>();
+        system.register_trait_message::<MSG_Household_pay_toll>();
+        system.register_trait_message::<MSG_Household_pay_fare>();
+        system.register_trait_message::<MSG_Household_call_ambulance>();
+        system.register_trait_message::<MSG_Household_collect_repayment>();
+        system.register_trait_message::<MSG_Household_collect_rent>();
+        system.register_trait_message::<MSG_Household_evicted>();
+        system.register_trait_message::<MSG_Household_on_relocated>();
+        system.register_trait_message::<MSG_Household_counterpart_relocated>();
         system.register_trait_message::<MSG_Household_update_core>();
+        system.register_trait_message::<MSG_Household_age_members>();
+        system.register_trait_message::<MSG_Household_dissolve>();
+        system.register_trait_message::<MSG_Household_move_out>();
+        system.register_trait_message::<MSG_Household_let_members_depart>();
+        system.register_trait_message::<MSG_Household_plan_days>();
+        system.register_trait_message::<MSG_Household_release_member_offers>();
         system.register_trait_message::<MSG_Household_find_new_task_for>();
         system.register_trait_message::<MSG_Household_update_results>();
         system.register_trait_message::<MSG_Household_choose_deal>();
+        system.register_trait_message::<MSG_Household_commit_to_deal>();
+        system.register_trait_message::<MSG_Household_on_chained_deals>();
         system.register_trait_message::<MSG_Household_start_trip>();
         system.register_trait_message::<MSG_Household_on_trip_created>();
         system.register_trait_message::<MSG_Household_on_trip_result>();
@@ -185,13 +343,30 @@ impl HouseholdID {
         system.register_trait_message::<MSG_Household_on_tick>();
         system.register_trait_message::<MSG_Household_evaluate>();
         system.register_trait_message::<MSG_Household_request_receive_deal>();
+        system.register_trait_message::<MSG_Household_settle_deal>();
         system.register_trait_message::<MSG_Household_request_receive_undo_deal>();
         system.register_trait_message::<MSG_Household_started_using>();
+        system.register_trait_message::<MSG_Household_transfer_use>();
         system.register_trait_message::<MSG_Household_stopped_using>();
         system.register_trait_message::<MSG_Household_started_actively_using>();
         system.register_trait_message::<MSG_Household_stopped_actively_using>();
+        system.register_trait_message::<MSG_Household_serve_waiting>();
+        system.register_trait_message::<MSG_Household_on_staff_change>();
+        system.register_trait_message::<MSG_Household_deal_rejected>();
+        system.register_trait_message::<MSG_Household_evaluate_visit>();
+        system.register_trait_message::<MSG_Household_on_utility_supply>();
+        system.register_trait_message::<MSG_Household_on_air_pollution>();
+        system.register_trait_message::<MSG_Household_invest_in_upgrade>();
+        system.register_trait_message::<MSG_Household_befriended>();
+        system.register_trait_message::<MSG_Household_unfriended>();
         system.register_trait_message::<MSG_Household_withdrawal_confirmed>();
         system.register_trait_message::<MSG_Household_get_ui_info>();
+        system.register_trait_message::<MSG_Household_get_activity_log>();
+        system.register_trait_message::<MSG_Household_offer_for_vignette>();
+        system.register_trait_message::<MSG_Household_get_vignette>();
+        system.register_trait_message::<MSG_Household_observe>();
+        system.register_trait_message::<MSG_Household_contribute_to_metrics>();
+        system.register_trait_message::<MSG_Household_contribute_to_district_snapshot>();
     }
 
     pub fn register_implementor<Act: Actor + Household>(system: &mut ActorSystem) {
@@ -250,12 +425,108 @@ impl HouseholdID {
             }, false
         );
         
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_search_started(resource, search), instance, world| {
+                instance.search_started(resource, search, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_receive_funds(amount), instance, world| {
+                instance.receive_funds(amount, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_pay_toll(toll), instance, world| {
+                instance.pay_toll(toll, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_pay_fare(fare, operator), instance, world| {
+                instance.pay_fare(fare, operator, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_call_ambulance(member, instant), instance, world| {
+                instance.call_ambulance(member, instant, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_collect_repayment(due, bank), instance, world| {
+                instance.collect_repayment(due, bank, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_collect_rent(due, market), instance, world| {
+                instance.collect_rent(due, market, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_evicted(instant), instance, world| {
+                instance.evicted(instant, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_on_relocated(old_site), instance, world| {
+                instance.on_relocated(old_site, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_counterpart_relocated(offer), instance, world| {
+                instance.counterpart_relocated(offer, world); Fate::Live
+            }, false
+        );
+        
         system.add_handler::<Act, _, _>(
             |&MSG_Household_update_core(current_instant), instance, world| {
                 instance.update_core(current_instant, world); Fate::Live
             }, false
         );
         
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_age_members(instant), instance, world| {
+                instance.age_members(instant, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_dissolve(), instance, world| {
+                instance.dissolve(world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_move_out(member, search), instance, world| {
+                instance.move_out(member, search, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_let_members_depart(), instance, world| {
+                instance.let_members_depart(world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_plan_days(instant), instance, world| {
+                instance.plan_days(instant, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_release_member_offers(member, only_resource), instance, world| {
+                instance.release_member_offers(member, only_resource, world); Fate::Live
+            }, false
+        );
+        
         system.add_handler::<Act, _, _>(
             |&MSG_Household_find_new_task_for(member, instant, location), instance, world| {
                 instance.find_new_task_for(member, instant, location, world); Fate::Live
@@ -274,6 +545,18 @@ impl HouseholdID {
             }, false
         );
         
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_commit_to_deal(member, instant, location, ref best), instance, world| {
+                instance.commit_to_deal(member, instant, location, best, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_on_chained_deals(source, ref chained), instance, world| {
+                instance.on_chained_deals(source, chained, world); Fate::Live
+            }, false
+        );
+        
         system.add_handler::<Act, _, _>(
             |&MSG_Household_start_trip(member, instant), instance, world| {
                 instance.start_trip(member, instant, world); Fate::Live
@@ -311,8 +594,8 @@ impl HouseholdID {
         );
         
         system.add_handler::<Act, _, _>(
-            |&MSG_Household_evaluate(offer_idx, instant, location, requester), instance, world| {
-                instance.evaluate(offer_idx, instant, location, requester, world); Fate::Live
+            |&MSG_Household_evaluate(offer_idx, instant, location, requester, modes), instance, world| {
+                instance.evaluate(offer_idx, instant, location, requester, modes, world); Fate::Live
             }, false
         );
         
@@ -322,6 +605,12 @@ impl HouseholdID {
             }, false
         );
         
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_settle_deal(offer_idx, requester, requester_member), instance, world| {
+                instance.settle_deal(offer_idx, requester, requester_member, world); Fate::Live
+            }, false
+        );
+        
         system.add_handler::<Act, _, _>(
             |&MSG_Household_request_receive_undo_deal(offer_idx, requester, requester_member), instance, world| {
                 instance.request_receive_undo_deal(offer_idx, requester, requester_member, world); Fate::Live
@@ -334,6 +623,12 @@ impl HouseholdID {
             }, false
         );
         
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_transfer_use(offer_idx, from, from_member, to, to_member), instance, world| {
+                instance.transfer_use(offer_idx, from, from_member, to, to_member, world); Fate::Live
+            }, false
+        );
+        
         system.add_handler::<Act, _, _>(
             |&MSG_Household_stopped_using(offer_idx, user, using_member), instance, world| {
                 instance.stopped_using(offer_idx, user, using_member, world)
@@ -352,6 +647,60 @@ impl HouseholdID {
             }, false
         );
         
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_serve_waiting(offer_idx), instance, world| {
+                instance.serve_waiting(offer_idx, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_on_staff_change(), instance, world| {
+                instance.on_staff_change(world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_deal_rejected(offer, member), instance, world| {
+                instance.deal_rejected(offer, member, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_evaluate_visit(resource, instant, location, requester, modes), instance, world| {
+                instance.evaluate_visit(resource, instant, location, requester, modes, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_on_utility_supply(utility, supplied), instance, world| {
+                instance.on_utility_supply(utility, supplied, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_on_air_pollution(pollution), instance, world| {
+                instance.on_air_pollution(pollution, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_invest_in_upgrade(building, upgrade, cost, subsidy), instance, world| {
+                instance.invest_in_upgrade(building, upgrade, cost, subsidy, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_befriended(friend), instance, world| {
+                instance.befriended(friend, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_unfriended(former_friend), instance, world| {
+                instance.unfriended(former_friend, world); Fate::Live
+            }, false
+        );
+        
         system.add_handler::<Act, _, _>(
             |&MSG_Household_withdrawal_confirmed(offer_idx), instance, world| {
                 instance.withdrawal_confirmed(offer_idx, world)
@@ -363,6 +712,42 @@ impl HouseholdID {
                 instance.get_ui_info(requester, world); Fate::Live
             }, false
         );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_get_activity_log(requester), instance, world| {
+                instance.get_activity_log(requester, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_offer_for_vignette(requester), instance, world| {
+                instance.offer_for_vignette(requester, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_get_vignette(draw, requester), instance, world| {
+                instance.get_vignette(draw, requester, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_observe(observer), instance, world| {
+                instance.observe(observer, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_contribute_to_metrics(metrics, day), instance, world| {
+                instance.contribute_to_metrics(metrics, day, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_contribute_to_district_snapshot(timeline, instant, position), instance, world| {
+                instance.contribute_to_district_snapshot(timeline, instant, position, world); Fate::Live
+            }, false
+        );
     }
 }
 
@@ -385,14 +770,50 @@ struct MSG_Household_destroy();
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_on_destroy();
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_search_started(pub Resource, pub NearestOffersSearchID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_receive_funds(pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_pay_toll(pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_pay_fare(pub f32, pub TaxiOperatorID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_call_ambulance(pub MemberIdx, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_collect_repayment(pub ResourceAmount, pub BankID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_collect_rent(pub ResourceAmount, pub HousingMarketID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_evicted(pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_on_relocated(pub RoughLocationID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_counterpart_relocated(pub OfferID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_update_core(pub Instant);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_age_members(pub Instant);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_dissolve();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_move_out(pub MemberIdx, pub HousingSearchID);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_let_members_depart();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_plan_days(pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_release_member_offers(pub MemberIdx, pub Option < Resource >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_find_new_task_for(pub MemberIdx, pub Instant, pub RoughLocationID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_update_results(pub Resource, pub ResultAspect);
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_choose_deal();
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_commit_to_deal(pub MemberIdx, pub Instant, pub RoughLocationID, pub EvaluatedDeal);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_on_chained_deals(pub RoughLocationID, pub CVec < EvaluatedDeal >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_start_trip(pub MemberIdx, pub Instant);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_on_trip_created(pub TripID);
@@ -405,23 +826,57 @@ struct MSG_Household_stop_task(pub MemberIdx, pub Option < RoughLocationID >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_on_tick(pub Instant);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Household_evaluate(pub OfferIdx, pub Instant, pub RoughLocationID, pub EvaluationRequesterID);
+struct MSG_Household_evaluate(pub OfferIdx, pub Instant, pub RoughLocationID, pub EvaluationRequesterID, pub ModeSet);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_request_receive_deal(pub OfferIdx, pub HouseholdID, pub MemberIdx);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_settle_deal(pub OfferIdx, pub HouseholdID, pub MemberIdx);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_request_receive_undo_deal(pub OfferIdx, pub HouseholdID, pub MemberIdx);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_started_using(pub OfferIdx, pub HouseholdID, pub Option < MemberIdx >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_transfer_use(pub OfferIdx, pub HouseholdID, pub Option < MemberIdx >, pub HouseholdID, pub Option < MemberIdx >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_stopped_using(pub OfferIdx, pub HouseholdID, pub Option < MemberIdx >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_started_actively_using(pub OfferIdx, pub HouseholdID, pub MemberIdx);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_stopped_actively_using(pub OfferIdx, pub HouseholdID, pub MemberIdx);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_serve_waiting(pub OfferIdx);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_on_staff_change();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_deal_rejected(pub OfferID, pub MemberIdx);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_evaluate_visit(pub Resource, pub Instant, pub RoughLocationID, pub EvaluationRequesterID, pub ModeSet);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_on_utility_supply(pub Utility, pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_on_air_pollution(pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_invest_in_upgrade(pub BuildingID, pub EfficiencyUpgrade, pub ResourceAmount, pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_befriended(pub HouseholdID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_unfriended(pub HouseholdID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_withdrawal_confirmed(pub OfferIdx);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_get_ui_info(pub ui :: HouseholdUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_get_activity_log(pub ui :: HouseholdUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_offer_for_vignette(pub ui :: HouseholdUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_get_vignette(pub u32, pub ui :: HouseholdUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_observe(pub ApiObserverID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_contribute_to_metrics(pub CityMetricsID, pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_contribute_to_district_snapshot(pub TimelineID, pub Instant, pub P2);
 
 
 
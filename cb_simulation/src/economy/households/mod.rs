@@ -1,16 +1,29 @@
 use kay::{ActorSystem, World, Actor, TypedID, Fate};
 use compact::{CVec, CDict, COption};
-use cb_time::actors::{TimeID, Sleeper, Temporal};
-use cb_time::units::{Duration, TimeOfDay, Instant, Ticks, TICKS_PER_SIM_SECOND};
+use cb_time::actors::{TimeID, Sleeper, SleeperID, Temporal};
+use cb_time::load_spreading::{self, TickBudget};
+use cb_time::units::{Duration, TimeOfDay, Instant, Ticks, TICKS_PER_SIM_SECOND,
+TICKS_PER_SIM_MINUTE, TimeOfDayRange};
 use cb_util::async_counter::AsyncCounter;
+use descartes::P2;
 use cb_util::random::{seed, Rng};
 use ordered_float::OrderedFloat;
+use std::cell::RefCell;
 use cb_util::log::{debug, info, warn};
 const LOG_T: &str = "Households";
 
 pub mod tasks;
 pub mod offers;
 pub mod ui;
+pub mod aging;
+pub mod needs;
+pub mod health;
+pub mod schedule;
+pub mod formation;
+pub mod archetypes;
+pub mod social;
+pub mod activity_log;
+pub mod vignette;
 
 pub mod household_kinds;
 use self::household_kinds::*;
@@ -29,16 +42,79 @@ impl MemberIdx {
 }
 
 use super::market::{MarketID, Deal, EvaluatedDeal, EvaluationRequester, EvaluationRequesterID,
-TripCostEstimatorID, EvaluatedSearchResult};
-use super::resources::{Resource, ResourceAmount, ResourceMap, Entry, Inventory};
+TripCostEstimatorID, EvaluatedSearchResult, MarketTier, ChainedTripEstimatorID,
+NearestOffersSearchID};
+use super::resources::{Resource, ResourceAmount, ResourceMap, Entry, Inventory, to_amount,
+amount_to_f32};
+use super::finance::{BankID, CASH_BUFFER};
+use super::companies::CompanyID;
+use super::housing::HousingMarketID;
+use super::policies::{SettledDeal, TreasuryID};
+use super::unmet_demand::UnmetDemandID;
+use super::utilities::{Utility, EfficiencyUpgrade};
+use land_use::buildings::BuildingID;
 use transport::pathfinding::{RoughLocationID, RoughLocation};
-use transport::pathfinding::trip::{TripListener, TripID, TripResult, TripFate};
+use transport::pathfinding::trip::{TripListener, TripID, TripResult, TripFate, TravelMode};
+use transport::taxis::TaxiOperatorID;
+use transport::pathfinding::itinerary::ModeSet;
 use self::tasks::{Task, TaskState, TaskEndSchedulerID};
 pub use self::offers::{Offer, OfferIdx, OfferID};
+use self::aging::{LifeStage, MemberLife, LifecycleListenerID, mortality, BIRTH_CHANCE_PER_YEAR,
+DAYS_PER_YEAR, MAX_FAMILY_SIZE, MIN_PARENT_AGE, MOVING_OUT_CHANCE_PER_YEAR};
+use self::needs::{Need, Needs, ALL_NEEDS, happiness};
+use self::health::{Health, pollution_sickness_chance};
+use self::schedule::{DayPlan, Activity, PLANNING_TIME_OF_DAY, until_next};
+use self::formation::{MovingMember, HousingSearchID, MatchmakerID, is_divisible};
+use self::social::{SocialGraphID, visits_provide};
+use self::activity_log::{ActivityLogEntry, LoggedActivity};
+use timeline::TimelineID;
+use observation::ApiObserverID;
+use metrics::CityMetricsID;
 
 const N_TOP_PROBLEMS: usize = 5;
 const DECISION_PAUSE: Ticks = Ticks(200);
+// decisions after the pause are spread over this window, so that households
+// that were spawned together don't keep deciding in the same tick
+const DECISION_SPREAD: Ticks = Ticks(60);
 const UPDATE_EVERY_N_SECS: u32 = 4;
+const N_ACCEPTABLE_DEALS_PER_SEARCH: u32 = 5;
+// shared by all households deciding in the same tick, those that find it used up
+// try again in one of their next turns, see `load_spreading::TickBudget`
+const EVALUATIONS_PER_TICK: u32 = 200;
+// only found deals are cached, failed searches are simply tried again
+const DECISION_CACHE_VALIDITY: Duration = Duration(2 * 24 * 60 * 60);
+const FINANCES_CHECK_INTERVAL: Duration = Duration(60 * 60);
+const MAX_CHAINED_STOPS: usize = 2;
+const AGING_CHECK_INTERVAL: Duration = Duration(24 * 60 * 60);
+// how much a single commute changes the remembered average commute
+const COMMUTE_SMOOTHING: f32 = 0.2;
+// a car below this condition has to be serviced or replaced before it can be driven again
+const USABLE_CAR_CONDITION: f32 = 0.5;
+// the same goes for bikes
+const USABLE_BIKE_CONDITION: f32 = 0.5;
+// how far into the reserve a tank can be driven, enough to make it to a pump
+const FUEL_RESERVE: f32 = 10.0;
+const FUEL_PER_DRIVING_MINUTE: f32 = 0.1;
+// attending school every day of a (compressed) year adds up to one year of schooling
+const SCHOOL_DAY_EDUCATION: f32 = 1.0 / DAYS_PER_YEAR as f32;
+
+fn pause_before_deciding(sleeper: SleeperID, world: &mut World) {
+    TimeID::local_first(world).wake_up_in_turn(DECISION_PAUSE, DECISION_SPREAD, sleeper, world);
+}
+
+fn defer_deciding(sleeper: SleeperID, world: &mut World) {
+    TimeID::local_first(world).wake_up_in_turn(Ticks(1), DECISION_SPREAD, sleeper, world);
+}
+
+thread_local! {
+    // households are processed on the thread of their actor system, this is its budget
+    static EVALUATION_BUDGET: RefCell<TickBudget> =
+        RefCell::new(TickBudget::new(EVALUATIONS_PER_TICK));
+}
+
+fn try_spend_evaluation(instant: Instant) -> bool {
+    EVALUATION_BUDGET.with(|budget| budget.borrow_mut().try_spend(instant))
+}
 
 // TODO: make kay_codegen figure this out on it's own
 impl Into<RoughLocationID> for HouseholdID {
@@ -47,6 +123,12 @@ impl Into<RoughLocationID> for HouseholdID {
     }
 }
 
+impl Into<EvaluationRequesterID> for HouseholdID {
+    fn into(self) -> EvaluationRequesterID {
+        EvaluationRequesterID::from_raw(self.as_raw())
+    }
+}
+
 pub trait Household:
     Actor + EvaluationRequester + Sleeper + Temporal + TripListener + RoughLocation
 {
@@ -58,13 +140,83 @@ pub trait Household:
     fn supplier_shared(resource: Resource) -> bool;
     fn importance(resource: Resource, time: TimeOfDay) -> f32;
     fn graveness(resource: Resource, amount: ResourceAmount, time: TimeOfDay) -> f32 {
-        -amount * Self::importance(resource, time)
+        -amount_to_f32(amount) * Self::importance(resource, time)
     }
     fn interesting_resources() -> &'static [Resource];
+    fn market_tier(_resource: Resource) -> MarketTier {
+        MarketTier::Retail
+    }
+    // only households made up of actual people age, have children and die
+    fn has_lifecycle() -> bool {
+        false
+    }
     fn decay(&mut self, dt: Duration, world: &mut World);
+    fn sleep_window(&self, stage: LifeStage) -> TimeOfDayRange {
+        schedule::sleep_window(stage)
+    }
+
+    // People can always walk or take the bus or train where there is one,
+    // but only ride a bike while they own a working one
+    // and only drive while they own a working car and have fuel for it,
+    // all the way or to a station to ride on from, otherwise they might call a taxi.
+    // Businesses are assumed to always have vehicles of their own,
+    // trucks for what they buy wholesale
+    fn available_modes(&self) -> ModeSet {
+        if !Self::has_lifecycle() {
+            return ModeSet::only(TravelMode::Car);
+        }
+
+        let resources = &self.core().resources;
+        let has_usable_car = resources
+            .get(Resource::Car)
+            .map_or(false, |&condition| condition >= USABLE_CAR_CONDITION);
+        let has_fuel = resources
+            .get(Resource::Fuel)
+            .map_or(true, |&fuel| fuel > -FUEL_RESERVE);
+
+        let has_usable_bike = resources
+            .get(Resource::Bicycle)
+            .map_or(false, |&condition| condition >= USABLE_BIKE_CONDITION);
+
+        let mut modes = ModeSet::only(TravelMode::Walking).with(TravelMode::Transit);
+        if has_usable_bike {
+            modes = modes.with(TravelMode::Bike);
+        }
+        if has_usable_car && has_fuel {
+            modes.with(TravelMode::Car).with(TravelMode::ParkAndRide)
+        } else {
+            modes.with(TravelMode::Taxi)
+        }
+    }
+
+    // branches of a company keep their money in its treasury instead of the bank
+    fn company(&self) -> Option<CompanyID> {
+        None
+    }
+    // whether the household gets the resource for itself or somebody else does it
+    fn purchases(&self, _resource: Resource) -> bool {
+        true
+    }
+    // some members only go after some resources
+    fn member_purchases(&self, _member: MemberIdx, resource: Resource) -> bool {
+        self.purchases(resource)
+    }
+    // whether an offer can be taken up right now, businesses that only
+    // sell what they have in store run out
+    fn can_provide(&self, _deal: &Deal) -> bool {
+        true
+    }
+    // families get by at home without power and water, just less comfortably,
+    // businesses have to close until they're back
+    fn can_open_without_utilities() -> bool {
+        false
+    }
 
     fn household_name(&self) -> String;
     fn member_name(&self, member: MemberIdx) -> String;
+    fn is_homeless(&self) -> bool {
+        false
+    }
 
     fn receive_deal(&mut self, deal: &Deal, member: MemberIdx, _: &mut World) {
         let core = self.core_mut();
@@ -73,13 +225,29 @@ pub trait Household:
             &mut core.member_resources[member.as_idx()],
             Self::is_shared,
         );
+        if deal
+            .delta
+            .get(Resource::Education)
+            .map_or(false, |&amount| amount > 0.0)
+        {
+            core.member_education[member.as_idx()] += SCHOOL_DAY_EDUCATION;
+        }
+        if deal
+            .delta
+            .get(Resource::Healthcare)
+            .map_or(false, |&amount| amount > 0.0)
+        {
+            core.member_health[member.as_idx()].treat();
+        }
     }
 
     fn provide_deal(&mut self, deal: &Deal, member: MemberIdx, _: &mut World) {
         let core = self.core_mut();
-        let provide_wakefulness =
-            deal.delta.len() == 1 && deal.delta.get(Resource::Wakefulness).is_some();
-        if !provide_wakefulness {
+        let provided_for_free = !deal.delta.is_empty()
+            && deal.delta.iter().all(|&Entry(resource, _)| {
+                Need::of(resource).map_or(false, |need| need.is_self_provided())
+            });
+        if !provided_for_free {
             deal.delta.take_from_shared_private(
                 &mut core.resources,
                 &mut core.member_resources[member.as_idx()],
@@ -118,11 +286,14 @@ pub trait Household:
             world,
         );
         TaskEndSchedulerID::local_first(world).deschedule(self.id_as(), member, world);
+        self.core_mut().member_chained_deals[member.as_idx()].clear();
 
         self.stop_task(member, None, world);
     }
 
     fn stop_using(&mut self, offer: OfferID, world: &mut World) {
+        self.core_mut().forget_cached_decisions_for(offer);
+
         if let Some(Entry(associated_resource, _)) = self
             .core()
             .used_offers
@@ -184,6 +355,10 @@ pub trait Household:
     fn destroy(&mut self, world: &mut World) {
         self.core_mut().being_destroyed = true;
 
+        for &(_, search) in self.core().departures.iter() {
+            search.join(COption(None), world);
+        }
+
         for &Entry(_, offer) in self.core().used_offers.iter() {
             offer
                 .household
@@ -201,9 +376,47 @@ pub trait Household:
             }
         }
 
+        // searches it started don't need to ask any more offerers
+        if let DecisionState::Choosing(_, _, _, ref entries) = self.core().decision_state {
+            for entry in entries.values() {
+                if let COption(Some(search)) = entry.search {
+                    search.requester_gone(world);
+                }
+            }
+        }
+
+        // contracts end right away, so employees and customers look for
+        // others instead of waiting for the offers to be withdrawn
+        let id_as_household = self.id_as();
+        for (idx, offer) in self.core().provided_offers.iter().enumerate() {
+            let mut notified = Vec::<HouseholdID>::new();
+            for &(user, _) in offer.users.iter() {
+                if !notified.contains(&user) {
+                    notified.push(user);
+                    user.stop_using(
+                        OfferID {
+                            household: id_as_household,
+                            idx: OfferIdx(idx as u16),
+                        },
+                        world,
+                    );
+                }
+            }
+        }
+
+        // offers stay alive until the market confirmed they're gone,
+        // so searches that still list them get an answer
+        for offer in self.core_mut().provided_offers.iter_mut() {
+            offer.being_withdrawn = true;
+            offer.users = CVec::new();
+            offer.active_users = CVec::new();
+            offer.waiting = CVec::new();
+        }
+
         for (idx, offer) in self.core().provided_offers.iter().enumerate() {
             MarketID::local_first(world).withdraw(
                 offer.deal.main_given(),
+                offer.tier,
                 OfferID {
                     household: self.id_as(),
                     idx: OfferIdx(idx as u16),
@@ -212,22 +425,196 @@ pub trait Household:
             )
         }
 
+        BankID::global_first(world).close_account(self.id_as(), world);
+        SocialGraphID::global_first(world).forget(self.id_as(), world);
+
         self.on_destroy(world);
     }
     fn on_destroy(&mut self, world: &mut World);
 
+    // Remembered so the search can be stopped if the household goes away before it's over
+    fn search_started(
+        &mut self,
+        resource: Resource,
+        search: NearestOffersSearchID,
+        world: &mut World,
+    ) {
+        if self.core().being_destroyed {
+            search.requester_gone(world);
+        } else if let DecisionState::Choosing(_, _, _, ref mut entries) =
+            self.core_mut().decision_state
+        {
+            if let Some(entry) = entries.get_mut(resource) {
+                entry.search = COption(Some(search));
+            }
+        }
+    }
+
+    fn receive_funds(&mut self, amount: ResourceAmount, _: &mut World) {
+        *self.core_mut().resources.mut_entry_or(Resource::Money, amount!(0.0)) += amount;
+    }
+
+    // drivers pay at the toll, all of it goes to the city
+    fn pay_toll(&mut self, toll: f32, world: &mut World) {
+        let amount = to_amount(toll);
+        *self.core_mut().resources.mut_entry_or(Resource::Money, amount!(0.0)) -= amount;
+        TreasuryID::global_first(world).deposit(amount, world);
+    }
+
+    // taxi passengers pay the company that drove them
+    fn pay_fare(&mut self, fare: f32, operator: TaxiOperatorID, world: &mut World) {
+        *self.core_mut().resources.mut_entry_or(Resource::Money, amount!(0.0)) -= to_amount(fare);
+        operator.fare_received(fare, world);
+    }
+
+    // members in critical condition get an ambulance, where someone can call one
+    fn call_ambulance(&mut self, _member: MemberIdx, _instant: Instant, _: &mut World) {}
+
+    fn collect_repayment(&mut self, due: ResourceAmount, bank: BankID, world: &mut World) {
+        let paid = {
+            let money = self.core_mut().resources.mut_entry_or(Resource::Money, amount!(0.0));
+            let paid = due.min(money.max(0.0));
+            *money -= paid;
+            paid
+        };
+        bank.repayment_received(self.id_as(), paid, due, world);
+    }
+
+    // Rent is paid in full as long as the household isn't in the red already,
+    // savings or a loan from the bank then cover for it
+    fn collect_rent(&mut self, due: ResourceAmount, market: HousingMarketID, world: &mut World) {
+        let paid = {
+            let money = self.core_mut().resources.mut_entry_or(Resource::Money, amount!(0.0));
+            let paid = if *money >= 0.0 { due } else { amount!(0.0) };
+            *money -= paid;
+            paid
+        };
+        market.rent_received(self.id_as(), paid, due, world);
+    }
+
+    // The landlord ended the lease. Only families rent their homes and know
+    // how to get by without one, anyone else gives up and leaves the city
+    fn evicted(&mut self, _instant: Instant, world: &mut World) {
+        if !self.core().being_destroyed {
+            self.destroy(world);
+        }
+    }
+
+    // After a household moved, everything it and the users of its offers knew
+    // about getting from one to the other is outdated. Members waiting at the
+    // old site now wait at the new one, those who are out find their way
+    // from wherever they end up
+    fn on_relocated(&mut self, old_site: RoughLocationID, world: &mut World) {
+        let new_site = self.site();
+        let id_as_household: HouseholdID = self.id_as();
+
+        for task in self.core_mut().member_tasks.iter_mut() {
+            if let TaskState::IdleAt(location) = task.state {
+                if location == old_site {
+                    *task = Task::idle_at(new_site);
+                }
+            }
+        }
+
+        let n_members = self.core().member_lives.len();
+        self.core_mut().decision_cache = vec![ResourceMap::new(); n_members].into();
+        for chained_deals in self.core_mut().member_chained_deals.iter_mut() {
+            chained_deals.clear();
+        }
+
+        let mut has_listed_offers = false;
+        for (idx, offer) in self.core().provided_offers.iter().enumerate() {
+            has_listed_offers |= !offer.is_private;
+            for &(user, _) in offer.users.iter() {
+                if user != id_as_household {
+                    user.counterpart_relocated(
+                        OfferID {
+                            household: id_as_household,
+                            idx: OfferIdx(idx as u16),
+                        },
+                        world,
+                    );
+                }
+            }
+        }
+
+        if has_listed_offers {
+            MarketID::global_first(world).relocated(id_as_household.into(), world);
+        }
+    }
+
+    // the other side of a contract moved, so the way there changed
+    fn counterpart_relocated(&mut self, offer: OfferID, _: &mut World) {
+        self.core_mut().forget_cached_decisions_for(offer);
+    }
+
     fn update_core(&mut self, current_instant: Instant, world: &mut World) {
-        if let DecisionState::None = self.core().decision_state {
-            let idle_members_idx_loc = self
+        self.update_needs(current_instant);
+
+        // put surplus money into savings, cover debts from savings or loans
+        if current_instant >= self.core().next_finances_check {
+            self.core_mut().next_finances_check = current_instant + FINANCES_CHECK_INTERVAL;
+            let money = self
                 .core()
-                .member_tasks
-                .iter()
-                .enumerate()
-                .filter_map(|(idx, m)| match m.state {
-                    TaskState::IdleAt(loc) => Some((idx, loc)),
-                    _ => None,
-                })
-                .collect::<Vec<_>>();
+                .resources
+                .get(Resource::Money)
+                .cloned()
+                .unwrap_or(amount!(0.0));
+
+            if money > CASH_BUFFER {
+                *self
+                    .core_mut()
+                    .resources
+                    .mut_entry_or(Resource::Money, amount!(0.0)) = CASH_BUFFER;
+                if let Some(company) = self.company() {
+                    company.deposit(money - CASH_BUFFER, world);
+                } else {
+                    BankID::global_first(world).deposit(self.id_as(), money - CASH_BUFFER, world);
+                }
+            } else if money < 0.0 {
+                if let Some(company) = self.company() {
+                    company.request_funds(self.id_as(), -money, world);
+                } else {
+                    BankID::global_first(world).request_funds(self.id_as(), -money, world);
+                }
+            }
+        }
+
+        if Self::has_lifecycle()
+            && !self.core().being_destroyed
+            && current_instant >= self.core().next_aging_check
+        {
+            self.core_mut().next_aging_check = current_instant + AGING_CHECK_INTERVAL;
+            self.age_members(current_instant, world);
+        }
+
+        if Self::has_lifecycle()
+            && !self.core().being_destroyed
+            && current_instant >= self.core().next_planning
+        {
+            self.plan_days(current_instant, world);
+        }
+
+        if let DecisionState::None = self.core().decision_state {
+            if !self.core().departures.is_empty() {
+                self.let_members_depart(world);
+                if self.core().being_destroyed {
+                    return;
+                }
+            }
+
+            let idle_members_idx_loc = {
+                let core = self.core();
+                core.member_tasks
+                    .iter()
+                    .zip(core.member_lives.iter())
+                    .enumerate()
+                    .filter_map(|(idx, (m, life))| match m.state {
+                        TaskState::IdleAt(loc) if life.is_present() => Some((idx, loc)),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            };
             let mut rng = seed((current_instant.ticks(), self.id()));
             let maybe_idle_idx_loc = rng.choose(&idle_members_idx_loc);
             if let Some(&(idle_member_idx, location)) = maybe_idle_idx_loc {
@@ -241,568 +628,1776 @@ pub trait Household:
         };
     }
 
-    fn top_problems(&self, member: MemberIdx, time: TimeOfDay) -> Vec<(Resource, f32)> {
-        let mut resource_graveness = self
-            .core()
-            .resources
-            .iter()
-            .chain(self.core().member_resources[member.as_idx()].iter())
-            .filter_map(|&Entry(resource, amount)| {
-                let graveness = Self::graveness(resource, amount, time);
-                if graveness > 0.1 {
-                    Some((resource, graveness))
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<_>>();
-        resource_graveness.sort_by_key(|&(_r, i)| OrderedFloat(i));
+    fn age_members(&mut self, instant: Instant, world: &mut World) {
+        let mut rng = seed((instant.ticks(), self.id(), 2u8));
+        let id_as_household: HouseholdID = self.id_as();
 
-        resource_graveness.truncate(N_TOP_PROBLEMS);
-        resource_graveness
-    }
+        for idx in 0..self.core().member_lives.len() {
+            let member = MemberIdx::new(idx);
+            let mut life = self.core().member_lives[idx];
 
-    fn find_new_task_for(
-        &mut self,
-        member: MemberIdx,
-        instant: Instant,
-        location: RoughLocationID,
-        world: &mut World,
-    ) {
-        debug(LOG_T, "Top N Problems", self.id(), world);
+            if !life.is_present() {
+                continue;
+            }
 
-        let time = TimeOfDay::from(instant);
-        let top_problems = self.top_problems(member, time);
+            life.age_in_days += 1;
 
-        if top_problems.is_empty() {
-            TimeID::local_first(world).wake_up_in(DECISION_PAUSE, self.id_as(), world);
-        } else {
-            let mut decision_entries = CDict::<Resource, DecisionResourceEntry>::new();
-            let id_as_eval_requester = self.id_as();
-            let log_as = self.id();
-            let core = self.core_mut();
+            let was_sick = self.core().member_health[idx].is_sick();
+            let was_critical = self.core().member_health[idx].is_critical();
+            let pollution_chance = pollution_sickness_chance(self.core().air_pollution);
+            if !was_sick && rng.gen::<f32>() < pollution_chance {
+                self.core_mut().member_health[idx].fall_sick();
+            }
+            let hours_without_food = self.core().member_needs[idx].hours_unmet(Need::Food, instant);
+            self.core_mut().member_health[idx].live_through_day(hours_without_food);
+            let health = self.core().member_health[idx];
 
-            for &(resource, graveness) in &top_problems {
-                debug(
+            if health.is_sick() && !was_sick {
+                info(
                     LOG_T,
-                    format!("Member #{}: {} = {}", member.as_idx(), resource, graveness),
-                    log_as,
+                    format!("{} fell sick", self.member_name(member)),
+                    self.id(),
                     world,
                 );
-                let maybe_offer = if Self::supplier_shared(resource) {
-                    core.used_offers.get(resource)
-                } else {
-                    core.member_used_offers[member.as_idx()].get(resource)
-                };
+            }
 
-                let initial_counter = if let Some(&offer) = maybe_offer {
-                    debug(
-                        LOG_T,
-                        format!(" -> Using favorite offer {:?} for {}\n", offer, resource),
-                        log_as,
-                        world,
-                    );
-                    offer.household.evaluate(
-                        offer.idx,
-                        instant,
-                        location,
-                        id_as_eval_requester,
-                        world,
-                    );
+            if health.is_critical() && !was_critical && !health.is_fatal() {
+                warn(
+                    LOG_T,
+                    format!("{} is in critical condition", self.member_name(member)),
+                    self.id(),
+                    world,
+                );
+                self.call_ambulance(member, instant, world);
+            }
 
-                    AsyncCounter::with_target(1)
-                } else {
-                    debug(
+            if health.is_fatal()
+                || (life.has_birthday() && rng.gen::<f32>() < mortality(life.years()))
+            {
+                let years = life.years();
+                life.stage = LifeStage::Deceased;
+                self.core_mut().member_lives[idx] = life;
+                info(
+                    LOG_T,
+                    format!(
+                        "{} died aged {}{}",
+                        self.member_name(member),
+                        years,
+                        if health.is_fatal() {
+                            " of sickness"
+                        } else {
+                            ""
+                        }
+                    ),
+                    self.id(),
+                    world,
+                );
+                self.release_member_offers(member, None, world);
+                LifecycleListenerID::global_broadcast(world).on_member_died(
+                    id_as_household,
+                    member,
+                    years,
+                    world,
+                );
+                continue;
+            }
+
+            if life.has_birthday() {
+                let years = life.years();
+                let stage = LifeStage::at_age(years);
+
+                if stage != life.stage {
+                    life.stage = stage;
+                    info(
                         LOG_T,
-                        format!(" -> Doing market query for {}\n", resource),
-                        log_as,
+                        format!("{} is now a {}", self.member_name(member), stage),
+                        self.id(),
                         world,
                     );
-                    MarketID::global_first(world).search(
-                        instant,
-                        location,
-                        resource,
-                        id_as_eval_requester,
+                    if stage == LifeStage::Retiree {
+                        self.release_member_offers(member, Some(Resource::Money), world);
+                    }
+                    LifecycleListenerID::global_broadcast(world).on_member_stage_changed(
+                        id_as_household,
+                        member,
+                        stage,
                         world,
                     );
+                }
+            }
 
-                    AsyncCounter::new()
-                };
+            self.core_mut().member_lives[idx] = life;
+        }
 
-                decision_entries.insert(
-                    resource,
-                    DecisionResourceEntry {
-                        results_counter: initial_counter,
-                        best_deal: COption(None),
-                        best_deal_usefulness: 0.0,
-                    },
-                );
-            }
+        let n_alive = self.core().n_present_members();
 
-            core.decision_state =
-                DecisionState::Choosing(member, instant, top_problems.into(), decision_entries);
+        if n_alive == 0 {
+            self.dissolve(world);
+            return;
         }
-    }
 
-    fn update_results(&mut self, resource: Resource, update: &ResultAspect, world: &mut World) {
-        let done = {
-            let log_as = self.id();
-            let core = self.core_mut();
+        let n_potential_parents = self
+            .core()
+            .member_lives
+            .iter()
+            .filter(|life| life.could_be_parent())
+            .count();
 
-            if let DecisionState::Choosing(_, instant, ref top_problems, ref mut entries) =
-                core.decision_state
+        if n_potential_parents >= 2
+            && n_alive < MAX_FAMILY_SIZE
+            && rng.gen::<f32>() < BIRTH_CHANCE_PER_YEAR / DAYS_PER_YEAR as f32
+        {
+            let site = self.site();
+            let member = self.core_mut().add_member(site);
+            info(
+                LOG_T,
+                format!("{} was born", self.member_name(member)),
+                self.id(),
+                world,
+            );
+            LifecycleListenerID::global_broadcast(world).on_member_born(
+                id_as_household,
+                member,
+                world,
+            );
+        }
+
+        // grown children sometimes move out on their own,
+        // other singles look for a partner to move in with
+        for idx in 0..self.core().member_lives.len() {
+            let life = self.core().member_lives[idx];
+            let member = MemberIdx::new(idx);
+
+            if life.stage != LifeStage::Worker
+                || !self.core().could_leave(member)
+                || self.core().is_departing(member)
             {
-                {
-                    let entry = entries
-                        .get_mut(resource)
-                        .expect("Should have an entry for queried resource");
+                continue;
+            }
 
-                    match *update {
-                        ResultAspect::AddDeals(ref evaluated_deals) => {
-                            for evaluated_deal in evaluated_deals {
-                                debug(
-                                    LOG_T,
-                                    format!(
-                                        "Got eval'd deal for {}, {:?} -> {:?}\n",
-                                        evaluated_deal.deal.main_given(),
-                                        evaluated_deal.opening_hours.start.hours_minutes(),
-                                        evaluated_deal.opening_hours.end.hours_minutes(),
-                                    ),
-                                    log_as,
-                                    world,
-                                );
-                                if evaluated_deal.opening_hours.contains(instant) {
-                                    let new_deal_usefulness = Self::deal_usefulness(
-                                        top_problems,
-                                        evaluated_deal,
-                                        log_as,
-                                        world,
-                                    );
-                                    if new_deal_usefulness > entry.best_deal_usefulness {
-                                        entry.best_deal = COption(Some(evaluated_deal.clone()));
-                                        entry.best_deal_usefulness = new_deal_usefulness;
-                                    } else {
-                                        debug(
-                                            LOG_T,
-                                            format!(
-                                                "Deal rejected, not more useful: {} vs {}\n",
-                                                new_deal_usefulness, entry.best_deal_usefulness
-                                            ),
-                                            log_as,
-                                            world,
-                                        );
-                                    }
-                                } else {
-                                    debug(LOG_T, "Deal rejected: not open", log_as, world);
-                                }
-                            }
-
-                            entry.results_counter.increment();
-                        }
-                        ResultAspect::SetTarget(n) => {
-                            entry.results_counter.set_target(n as usize);
-                        }
-                    }
-                }
-
-                entries
-                    .values()
-                    .all(|entry| entry.results_counter.is_done())
-            } else {
-                warn(
-                    LOG_T,
-                    "Received unexpected deal / should be choosing",
-                    log_as,
+            if self.core().lives_with_parent(member)
+                && rng.gen::<f32>() < MOVING_OUT_CHANCE_PER_YEAR / DAYS_PER_YEAR as f32
+            {
+                let search = HousingSearchID::spawn(1, world);
+                self.core_mut().departures.push((member, search));
+            } else if self.core().is_single(member) {
+                MatchmakerID::global_first(world).looking_for_partner(
+                    id_as_household,
+                    member,
+                    life.years(),
                     world,
                 );
-                false
             }
-        };
+        }
+    }
 
-        if done {
-            self.choose_deal(world);
+    fn dissolve(&mut self, world: &mut World) {
+        info(
+            LOG_T,
+            format!("{} dissolves, nobody is left", self.household_name()),
+            self.id(),
+            world,
+        );
+        LifecycleListenerID::global_broadcast(world).on_household_dissolved(self.id_as(), world);
+        self.destroy(world);
+    }
+
+    // Asks a member to leave for a new household. They only actually leave
+    // once they're done with what they're currently doing, see `let_members_depart`.
+    // The housing search always hears back, even if the member can't come
+    fn move_out(&mut self, member: MemberIdx, search: HousingSearchID, world: &mut World) {
+        let can_come = !self.core().being_destroyed
+            && self
+                .core()
+                .member_lives
+                .get(member.as_idx())
+                .map_or(false, |life| life.is_present())
+            && !self.core().is_departing(member);
+
+        if can_come {
+            self.core_mut().departures.push((member, search));
+        } else {
+            search.join(COption(None), world);
         }
     }
 
-    fn deal_usefulness(
-        top_problems: &[(Resource, f32)],
-        evaluated: &EvaluatedDeal,
-        logging_from: <Self as Actor>::ID,
-        world: &mut World,
-    ) -> f32 {
-        let resource_graveness_improvement: f32 = top_problems
+    fn let_members_depart(&mut self, world: &mut World) {
+        let departing_now = self
+            .core()
+            .departures
             .iter()
-            .map(|&(resource, graveness)| {
-                let delta = evaluated.deal.delta.get(resource).cloned().unwrap_or(0.0);
-                let improvement_strength = delta * graveness;
-                debug(
+            .filter_map(
+                |&(member, search)| match self.core().member_tasks[member.as_idx()].state {
+                    TaskState::IdleAt(location) => Some((member, search, location)),
+                    _ => None,
+                },
+            )
+            .collect::<Vec<_>>();
+
+        if departing_now.is_empty() {
+            return;
+        }
+
+        let id_as_household = self.id_as();
+
+        for (member, search, location) in departing_now {
+            self.core_mut()
+                .departures
+                .retain(|&(departing, _)| departing != member);
+
+            if self.core().member_lives[member.as_idx()].is_present() {
+                info(
                     LOG_T,
                     format!(
-                        "{} improves by {} (graveness {}, delta: {:?})\n",
-                        resource,
-                        improvement_strength,
-                        graveness,
-                        evaluated.deal.delta.get(resource)
-                    )
-                    .as_str(),
-                    logging_from,
+                        "{} moves out to start a household of their own",
+                        self.member_name(member)
+                    ),
+                    self.id(),
                     world,
                 );
-                improvement_strength
-            })
-            .sum();
+                let mover = self.core_mut().part_with(id_as_household, member, location);
+                search.join(COption(Some(mover)), world);
+            } else {
+                search.join(COption(None), world);
+            }
+        }
 
-        resource_graveness_improvement / evaluated.deal.duration.as_seconds()
+        if self.core().n_present_members() == 0 {
+            self.dissolve(world);
+        }
     }
 
-    fn choose_deal(&mut self, world: &mut World) {
-        let log_as = self.id();
-        let id_as_household = self.id_as();
-        let id_as_sleeper = self.id_as();
-        debug(LOG_T, "Choosing deal!", self.id(), world);
-
-        let maybe_best_info = {
-            let core = self.core_mut();
-
-            if let DecisionState::Choosing(member, instant, _, ref entries) = core.decision_state {
-                let maybe_best = most_useful_evaluated_deal(entries);
+    // Gives every member a plan for the coming day: commitments like work
+    // and school are fitted around sleep, errands are given to whoever
+    // has time while they can be done, leisure gets what's left.
+    // Only known favorite offers are planned for, everything else
+    // is still found on the go
+    fn plan_days(&mut self, instant: Instant, world: &mut World) {
+        let (planning_h, planning_m) = PLANNING_TIME_OF_DAY;
+        self.core_mut().next_planning =
+            instant + until_next(TimeOfDay::new(planning_h, planning_m), instant.into());
+
+        let mut plans = self
+            .core()
+            .member_lives
+            .iter()
+            .map(|life| {
+                if life.is_present() {
+                    DayPlan::with_sleep(self.sleep_window(life.stage))
+                } else {
+                    DayPlan::new()
+                }
+            })
+            .collect::<Vec<_>>();
 
-                if let Some(best) = maybe_best {
-                    let task = &mut core.member_tasks[member.as_idx()];
+        let mut planned_resources = Self::interesting_resources()
+            .iter()
+            .filter_map(|&resource| {
+                Activity::of(resource)
+                    .filter(|&activity| activity != Activity::Sleep)
+                    .map(|activity| (activity, resource))
+            })
+            .collect::<Vec<_>>();
+        planned_resources.sort_by_key(|&(activity, _)| activity);
+
+        let mut unfitting_errands = Vec::new();
+
+        for (activity, resource) in planned_resources {
+            // a single trip takes care of this for the whole household
+            let is_errand = Self::is_shared(resource) && Self::supplier_shared(resource);
+
+            if is_errand {
+                let maybe_offer_deal = self
+                    .core()
+                    .used_offers
+                    .get(resource)
+                    .and_then(|&offer| self.core().known_deal(offer).map(|deal| (offer, deal)));
+
+                if let Some((offer, deal)) = maybe_offer_deal {
+                    let lives = &self.core().member_lives;
+                    // children aren't sent out on errands
+                    let fitted = plans.iter_mut().enumerate().any(|(idx, plan)| {
+                        lives[idx].is_present()
+                            && lives[idx].stage != LifeStage::Child
+                            && plan.try_fit(activity, &deal)
+                    });
+
+                    if !fitted {
+                        unfitting_errands.push((resource, offer));
+                    }
+                }
+            } else {
+                for (idx, plan) in plans.iter_mut().enumerate() {
+                    let life = self.core().member_lives[idx];
+                    if !life.is_present() || life.stage.demand_factor(resource) == 0.0 {
+                        continue;
+                    }
 
-                    *task = if let TaskState::IdleAt(location) = task.state {
-                        Task {
-                            goal: Some((best.deal.main_given(), best.offer)),
-                            duration: best.deal.duration,
-                            state: TaskState::GettingReadyAt(location),
-                        }
+                    let maybe_offer = if Self::supplier_shared(resource) {
+                        self.core().used_offers.get(resource)
                     } else {
-                        panic!("Member who gets new task should be idle");
+                        self.core().member_used_offers[idx].get(resource)
                     };
 
-                    debug(
-                        LOG_T,
-                        format!("Found best offer for {}\n", best.deal.main_given()),
-                        log_as,
-                        world,
-                    );
-
-                    Some((member, instant, best))
-                } else {
-                    None
-                }
-            } else {
-                panic!("Tried to choose deal while not deciding");
-            }
-        };
-
-        if let Some((member, instant, best)) = maybe_best_info {
-            {
-                let (used_offers, maybe_member) = if Self::supplier_shared(best.deal.main_given()) {
-                    (&mut self.core_mut().used_offers, None)
-                } else {
-                    (
-                        &mut self.core_mut().member_used_offers[member.as_idx()],
-                        Some(member),
-                    )
-                };
-                if let Some(previous_offer) = used_offers.insert(best.deal.main_given(), best.offer)
-                {
-                    if previous_offer != best.offer {
-                        previous_offer.household.stopped_using(
-                            previous_offer.idx,
-                            id_as_household,
-                            maybe_member,
-                            world,
-                        );
+                    if let Some(deal) = maybe_offer.and_then(|&offer| self.core().known_deal(offer))
+                    {
+                        if !plan.try_fit(activity, &deal) {
+                            debug(
+                                LOG_T,
+                                format!("No time for {} in the plan of member #{}", resource, idx),
+                                self.id(),
+                                world,
+                            );
+                        }
                     }
                 }
-                best.offer.household.started_using(
-                    best.offer.idx,
-                    id_as_household,
-                    maybe_member,
-                    world,
-                );
             }
+        }
 
-            self.core_mut().decision_state = DecisionState::WaitingForTrip(member);
-            best.offer.household.request_receive_deal(
-                best.offer.idx,
-                id_as_household,
-                member,
-                world,
-            );
-            self.start_trip(member, instant, world);
-        } else {
+        self.core_mut().member_plans = plans.into();
+
+        // rather than failing to get there in time again and again,
+        // look for somewhere that fits into the day
+        for (resource, offer) in unfitting_errands {
             debug(
                 LOG_T,
-                "Didn't find any suitable offers at all",
+                format!(
+                    "Nobody has time for {} while it's offered, looking elsewhere",
+                    resource
+                ),
                 self.id(),
                 world,
             );
-            self.core_mut().decision_state = DecisionState::None;
-            TimeID::local_first(world).wake_up_in(DECISION_PAUSE, id_as_sleeper, world);
+            self.stop_using(offer, world);
         }
+    }
 
-        fn most_useful_evaluated_deal(
-            entries: &CDict<Resource, DecisionResourceEntry>,
-        ) -> Option<EvaluatedDeal> {
-            entries
-                .values()
-                .max_by_key(|decision_entry| OrderedFloat(decision_entry.best_deal_usefulness))
-                .and_then(|best_entry| best_entry.best_deal.as_ref().cloned())
+    // stops using the offers only this member relied on,
+    // either all of them or just the one for a given resource
+    fn release_member_offers(
+        &mut self,
+        member: MemberIdx,
+        only_resource: Option<Resource>,
+        world: &mut World,
+    ) {
+        let id_as_household = self.id_as();
+        let released = self.core().member_used_offers[member.as_idx()]
+            .iter()
+            .filter(|&&Entry(resource, _)| only_resource.map_or(true, |only| only == resource))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for Entry(resource, offer) in released {
+            self.core_mut().member_used_offers[member.as_idx()].remove(resource);
+            self.core_mut().forget_cached_decisions_for(offer);
+            offer
+                .household
+                .stopped_using(offer.idx, id_as_household, Some(member), world);
         }
     }
 
-    fn start_trip(&mut self, member: MemberIdx, instant: Instant, world: &mut World) {
-        if let Task {
-            goal: Some((_, offer)),
-            state: TaskState::GettingReadyAt(source),
-            ..
-        } = self.core().member_tasks[member.as_idx()]
-        {
-            TripID::spawn(
-                source,
-                offer.household.into(),
-                Some(self.id_as()),
-                instant,
-                world,
-            );
-        } else {
-            panic!("Member should be getting ready before starting trip");
+    fn update_needs(&mut self, instant: Instant) {
+        let time = TimeOfDay::from(instant);
+        let mut total_happiness = 0.0;
+        let mut n_living = 0;
+
+        for idx in 0..self.core().member_needs.len() {
+            let life = self.core().member_lives[idx];
+            if !life.is_present() {
+                continue;
+            }
+
+            let mut total_urgency = 0.0;
+
+            for &need in &ALL_NEEDS {
+                let resource = need.resource();
+                let amount = self.core().member_resources[idx]
+                    .get(resource)
+                    .cloned()
+                    .unwrap_or(amount!(0.0));
+                let needs = &mut self.core_mut().member_needs[idx];
+                needs.update(need, amount, instant);
+                let graveness =
+                    Self::graveness(resource, amount, time) * life.stage.demand_factor(resource);
+                total_urgency += needs.urgency(need, graveness, instant).max(0.0);
+            }
+
+            total_happiness += happiness(total_urgency);
+            n_living += 1;
         }
+
+        self.core_mut().happiness = if n_living > 0 {
+            total_happiness / n_living as f32
+        } else {
+            1.0
+        };
     }
 
-    fn on_trip_created(&mut self, trip: TripID, world: &mut World) {
-        self.core_mut().decision_state =
-            if let DecisionState::WaitingForTrip(member) = self.core().decision_state {
-                self.core_mut().member_tasks[member.as_idx()].state = TaskState::InTrip(trip);
-                TimeID::local_first(world).wake_up_in(DECISION_PAUSE, self.id_as(), world);
-                DecisionState::None
+    // the problems of a member, most urgent first. Unmet needs become
+    // more urgent the longer they stay unmet
+    fn top_problems(&self, member: MemberIdx, instant: Instant) -> Vec<(Resource, f32)> {
+        let time = TimeOfDay::from(instant);
+        let stage = self.core().member_lives[member.as_idx()].stage;
+        let health = self.core().member_health[member.as_idx()];
+        let needs = &self.core().member_needs[member.as_idx()];
+        let mut resource_urgency = self
+            .core()
+            .resources
+            .iter()
+            .chain(self.core().member_resources[member.as_idx()].iter())
+            .filter(|&&Entry(resource, _)| self.member_purchases(member, resource))
+            .filter_map(|&Entry(resource, amount)| {
+                let graveness = Self::graveness(resource, amount, time)
+                    * stage.demand_factor(resource)
+                    * health.demand_factor(resource);
+                let urgency = match Need::of(resource) {
+                    Some(need) => needs.urgency(need, graveness, instant),
+                    None => graveness,
+                };
+                if urgency > 0.1 {
+                    Some((resource, urgency))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        resource_urgency.sort_by_key(|&(_r, urgency)| OrderedFloat(-urgency));
+
+        resource_urgency.truncate(N_TOP_PROBLEMS);
+        resource_urgency
+    }
+
+    fn find_new_task_for(
+        &mut self,
+        member: MemberIdx,
+        instant: Instant,
+        location: RoughLocationID,
+        world: &mut World,
+    ) {
+        let maybe_chained = {
+            let chained_deals = &mut self.core_mut().member_chained_deals[member.as_idx()];
+            if chained_deals.is_empty() {
+                None
             } else {
-                panic!("Should be in waiting for trip state")
-            };
+                Some(chained_deals.remove(0))
+            }
+        };
+
+        if let Some(chained) = maybe_chained {
+            if chained.opening_hours.contains(instant) {
+                debug(
+                    LOG_T,
+                    format!("Continuing chained trip for {}", chained.deal.main_given()),
+                    self.id(),
+                    world,
+                );
+                self.core_mut().log_activity(
+                    member,
+                    instant,
+                    LoggedActivity::ContinuedChainedTrip(chained.deal.main_given()),
+                );
+                self.commit_to_deal(member, instant, location, &chained, world);
+                return;
+            } else {
+                self.core_mut().member_chained_deals[member.as_idx()].clear();
+            }
+        }
+
+        let time = TimeOfDay::from(instant);
+        let maybe_planned = self.core_mut().member_plans[member.as_idx()].start_due(time);
+
+        if let Some(planned) = maybe_planned {
+            if planned.opening_hours.contains(instant) {
+                debug(
+                    LOG_T,
+                    format!("Following day plan for {}", planned.deal.main_given()),
+                    self.id(),
+                    world,
+                );
+                self.core_mut().log_activity(
+                    member,
+                    instant,
+                    LoggedActivity::FollowedPlan(planned.deal.main_given()),
+                );
+                self.commit_to_deal(member, instant, location, &planned, world);
+                return;
+            }
+        }
+
+        debug(LOG_T, "Top N Problems", self.id(), world);
+
+        let top_problems = {
+            let plan = &self.core().member_plans[member.as_idx()];
+            let asleep = plan.is_asleep(time);
+            self.top_problems(member, instant)
+                .into_iter()
+                .filter(|&(resource, _)| {
+                    if asleep {
+                        // nothing but rest is worth getting up for
+                        Activity::of(resource) == Some(Activity::Sleep)
+                    } else {
+                        !plan.defers(resource)
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        if top_problems.is_empty() {
+            pause_before_deciding(self.id_as(), world);
+        } else {
+            let mut decision_entries = CDict::<Resource, DecisionResourceEntry>::new();
+            let mut cached_results = Vec::new();
+            let id_as_eval_requester = self.id_as();
+            let id_as_household: HouseholdID = self.id_as();
+            let id_as_sleeper: SleeperID = self.id_as();
+            let log_as = self.id();
+            let modes = self.available_modes();
+            let core = self.core_mut();
+
+            for &(resource, graveness) in &top_problems {
+                // goods bought wholesale are fetched by truck
+                let modes = if Self::market_tier(resource) == MarketTier::Wholesale {
+                    ModeSet::only(TravelMode::Freight)
+                } else {
+                    modes
+                };
+                debug(
+                    LOG_T,
+                    format!("Member #{}: {} = {}", member.as_idx(), resource, graveness),
+                    log_as,
+                    world,
+                );
+                let maybe_offer = if Self::supplier_shared(resource) {
+                    core.used_offers.get(resource)
+                } else {
+                    core.member_used_offers[member.as_idx()].get(resource)
+                };
+                let maybe_cached = core.decision_cache[member.as_idx()]
+                    .get(resource)
+                    .filter(|cached| cached.valid_until > instant)
+                    .map(|cached| cached.best_deal.clone());
+                let from_cache = maybe_cached.is_some();
+                let mut n_private_results = 0;
+
+                let initial_counter = if let Some(cached_deal) = maybe_cached {
+                    debug(
+                        LOG_T,
+                        format!(" -> Using cached decision for {}\n", resource),
+                        log_as,
+                        world,
+                    );
+                    cached_results.push((resource, vec![cached_deal]));
+
+                    AsyncCounter::with_target(1)
+                } else if !try_spend_evaluation(instant) {
+                    debug(
+                        LOG_T,
+                        format!(" -> Out of evaluation budget for {}\n", resource),
+                        log_as,
+                        world,
+                    );
+                    continue;
+                } else if let Some(&offer) = maybe_offer {
+                    debug(
+                        LOG_T,
+                        format!(" -> Using favorite offer {:?} for {}\n", offer, resource),
+                        log_as,
+                        world,
+                    );
+                    offer.household.evaluate(
+                        offer.idx,
+                        instant,
+                        location,
+                        id_as_eval_requester,
+                        modes,
+                        world,
+                    );
+
+                    AsyncCounter::with_target(1)
+                } else {
+                    if let Some(private_offers) = core.private_offer_directory.get(resource) {
+                        for &offer_idx in private_offers.iter() {
+                            id_as_household.evaluate(
+                                offer_idx,
+                                instant,
+                                location,
+                                id_as_eval_requester,
+                                modes,
+                                world,
+                            );
+                        }
+                        n_private_results = private_offers.len() as u32;
+                    }
+                    if visits_provide(resource) {
+                        for &friend in core.friends.iter() {
+                            friend.evaluate_visit(
+                                resource,
+                                instant,
+                                location,
+                                id_as_eval_requester,
+                                modes,
+                                world,
+                            );
+                        }
+                        n_private_results += core.friends.len() as u32;
+                    }
+                    debug(
+                        LOG_T,
+                        format!(" -> Doing market query for {}\n", resource),
+                        log_as,
+                        world,
+                    );
+                    core.log_activity(member, instant, LoggedActivity::Searched(resource));
+                    MarketID::global_first(world).search_nearest(
+                        instant,
+                        location,
+                        resource,
+                        Self::market_tier(resource),
+                        id_as_household,
+                        N_ACCEPTABLE_DEALS_PER_SEARCH,
+                        modes,
+                        world,
+                    );
+
+                    AsyncCounter::new()
+                };
+
+                decision_entries.insert(
+                    resource,
+                    DecisionResourceEntry {
+                        results_counter: initial_counter,
+                        best_deal: COption(None),
+                        best_deal_usefulness: 0.0,
+                        from_cache,
+                        n_private_results,
+                        search: COption(None),
+                    },
+                );
+            }
+
+            // only when the evaluation budget ran out before any problem was looked into
+            if decision_entries.is_empty() {
+                defer_deciding(id_as_sleeper, world);
+                return;
+            }
+
+            core.decision_state =
+                DecisionState::Choosing(member, instant, top_problems.into(), decision_entries);
+
+            for (resource, cached_deals) in cached_results {
+                self.update_results(resource, &ResultAspect::AddDeals(cached_deals), world);
+            }
+        }
+    }
+
+    fn update_results(&mut self, resource: Resource, update: &ResultAspect, world: &mut World) {
+        if self.core().being_destroyed {
+            // results of a search that was under way, nobody acts on them anymore
+            self.core_mut().decision_state = DecisionState::None;
+            return;
+        }
+
+        let done = {
+            let log_as = self.id();
+            let core = self.core_mut();
+
+            if let DecisionState::Choosing(member, instant, ref top_problems, ref mut entries) =
+                core.decision_state
+            {
+                let education = core.member_education[member.as_idx()];
+
+                {
+                    let entry = entries
+                        .get_mut(resource)
+                        .expect("Should have an entry for queried resource");
+
+                    match *update {
+                        ResultAspect::AddDeals(ref evaluated_deals) => {
+                            for evaluated_deal in evaluated_deals {
+                                debug(
+                                    LOG_T,
+                                    format!(
+                                        "Got eval'd deal for {}, {:?} -> {:?}\n",
+                                        evaluated_deal.deal.main_given(),
+                                        evaluated_deal.opening_hours.start.hours_minutes(),
+                                        evaluated_deal.opening_hours.end.hours_minutes(),
+                                    ),
+                                    log_as,
+                                    world,
+                                );
+                                if evaluated_deal.min_education > education {
+                                    debug(
+                                        LOG_T,
+                                        "Deal rejected: not educated enough",
+                                        log_as,
+                                        world,
+                                    );
+                                } else if core.member_plans[member.as_idx()].conflicts_with(
+                                    resource,
+                                    instant.into(),
+                                    evaluated_deal.deal.duration,
+                                ) {
+                                    debug(
+                                        LOG_T,
+                                        "Deal rejected: conflicts with day plan",
+                                        log_as,
+                                        world,
+                                    );
+                                } else if evaluated_deal.opening_hours.contains(instant) {
+                                    let new_deal_usefulness = Self::deal_usefulness(
+                                        top_problems,
+                                        evaluated_deal,
+                                        log_as,
+                                        world,
+                                    );
+                                    if new_deal_usefulness > entry.best_deal_usefulness {
+                                        entry.best_deal = COption(Some(evaluated_deal.clone()));
+                                        entry.best_deal_usefulness = new_deal_usefulness;
+                                    } else {
+                                        debug(
+                                            LOG_T,
+                                            format!(
+                                                "Deal rejected, not more useful: {} vs {}\n",
+                                                new_deal_usefulness, entry.best_deal_usefulness
+                                            ),
+                                            log_as,
+                                            world,
+                                        );
+                                    }
+                                } else {
+                                    debug(LOG_T, "Deal rejected: not open", log_as, world);
+                                }
+                            }
+
+                            entry.results_counter.increment();
+                        }
+                        ResultAspect::SetTarget(n) => {
+                            entry
+                                .results_counter
+                                .set_target((n + entry.n_private_results) as usize);
+                            // the search is over, if there was one
+                            entry.search = COption(None);
+                        }
+                    }
+                }
+
+                entries
+                    .values()
+                    .all(|entry| entry.results_counter.is_done())
+            } else {
+                warn(
+                    LOG_T,
+                    "Received unexpected deal / should be choosing",
+                    log_as,
+                    world,
+                );
+                false
+            }
+        };
+
+        if done {
+            self.choose_deal(world);
+        }
+    }
+
+    fn deal_usefulness(
+        top_problems: &[(Resource, f32)],
+        evaluated: &EvaluatedDeal,
+        logging_from: <Self as Actor>::ID,
+        world: &mut World,
+    ) -> f32 {
+        let resource_graveness_improvement: f32 = top_problems
+            .iter()
+            .map(|&(resource, graveness)| {
+                let delta = evaluated
+                    .deal
+                    .delta
+                    .get(resource)
+                    .map_or(0.0, |&delta| amount_to_f32(delta));
+                let improvement_strength = delta * graveness;
+                debug(
+                    LOG_T,
+                    format!(
+                        "{} improves by {} (graveness {}, delta: {:?})\n",
+                        resource,
+                        improvement_strength,
+                        graveness,
+                        evaluated.deal.delta.get(resource)
+                    )
+                    .as_str(),
+                    logging_from,
+                    world,
+                );
+                improvement_strength
+            })
+            .sum();
+
+        resource_graveness_improvement / evaluated.deal.duration.as_seconds()
+    }
+
+    fn choose_deal(&mut self, world: &mut World) {
+        let log_as = self.id();
+        let id_as_household = self.id_as();
+        let id_as_sleeper = self.id_as();
+        debug(LOG_T, "Choosing deal!", self.id(), world);
+
+        let maybe_best_info = {
+            let core = self.core_mut();
+
+            if let DecisionState::Choosing(member, instant, _, ref entries) = core.decision_state {
+                for (&resource, entry) in entries.pairs() {
+                    if entry.from_cache {
+                        continue;
+                    }
+                    if let Some(ref best_deal) = *entry.best_deal {
+                        core.decision_cache[member.as_idx()].insert(
+                            resource,
+                            CachedDecision {
+                                best_deal: best_deal.clone(),
+                                valid_until: instant + DECISION_CACHE_VALIDITY,
+                            },
+                        );
+                    }
+                }
+
+                if let TaskState::IdleAt(location) = core.member_tasks[member.as_idx()].state {
+                    for (&resource, entry) in entries.pairs() {
+                        if entry.best_deal.is_none() {
+                            UnmetDemandID::global_first(world).record(resource, location, world);
+                        }
+                    }
+                }
+
+                let maybe_best = most_useful_evaluated_deal(entries);
+
+                if let Some(best) = maybe_best {
+                    debug(
+                        LOG_T,
+                        format!("Found best offer for {}\n", best.deal.main_given()),
+                        log_as,
+                        world,
+                    );
+
+                    // other needs that might be satisfied on the way
+                    let mut chain_candidates = entries
+                        .values()
+                        .filter_map(|entry| {
+                            entry.best_deal.as_ref().and_then(|deal| {
+                                if deal.offer.household != best.offer.household {
+                                    Some((entry.best_deal_usefulness, deal.clone()))
+                                } else {
+                                    None
+                                }
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    chain_candidates.sort_by_key(|&(usefulness, _)| OrderedFloat(-usefulness));
+                    let chain_candidates = chain_candidates
+                        .into_iter()
+                        .take(MAX_CHAINED_STOPS)
+                        .map(|(_, deal)| deal)
+                        .collect::<CVec<_>>();
+
+                    if let TaskState::IdleAt(location) = core.member_tasks[member.as_idx()].state {
+                        Some((member, instant, location, best, chain_candidates))
+                    } else {
+                        panic!("Member who gets new task should be idle");
+                    }
+                } else {
+                    None
+                }
+            } else {
+                panic!("Tried to choose deal while not deciding");
+            }
+        };
+
+        if let Some((member, instant, location, best, chain_candidates)) = maybe_best_info {
+            self.core_mut().member_chained_deals[member.as_idx()].clear();
+            if !chain_candidates.is_empty() {
+                ChainedTripEstimatorID::spawn(
+                    id_as_household,
+                    best.offer.household.into(),
+                    chain_candidates,
+                    best.mode,
+                    instant,
+                    world,
+                );
+            }
+
+            self.core_mut().log_activity(
+                member,
+                instant,
+                LoggedActivity::Chose(best.deal.main_given(), best.offer),
+            );
+            self.commit_to_deal(member, instant, location, &best, world);
+        } else {
+            debug(
+                LOG_T,
+                "Didn't find any suitable offers at all",
+                self.id(),
+                world,
+            );
+            if let DecisionState::Choosing(member, instant, ..) = self.core().decision_state {
+                self.core_mut()
+                    .log_activity(member, instant, LoggedActivity::FoundNothing);
+            }
+            self.core_mut().decision_state = DecisionState::None;
+            pause_before_deciding(id_as_sleeper, world);
+        }
+
+        fn most_useful_evaluated_deal(
+            entries: &CDict<Resource, DecisionResourceEntry>,
+        ) -> Option<EvaluatedDeal> {
+            entries
+                .values()
+                .max_by_key(|decision_entry| OrderedFloat(decision_entry.best_deal_usefulness))
+                .and_then(|best_entry| best_entry.best_deal.as_ref().cloned())
+        }
+    }
+
+    fn commit_to_deal(
+        &mut self,
+        member: MemberIdx,
+        instant: Instant,
+        location: RoughLocationID,
+        best: &EvaluatedDeal,
+        world: &mut World,
+    ) {
+        let id_as_household = self.id_as();
+
+        self.core_mut().member_tasks[member.as_idx()] = Task {
+            goal: Some((best.deal.main_given(), best.offer)),
+            duration: best.deal.duration,
+            state: TaskState::GettingReadyAt(location),
+            departure: instant,
+            mode: best.mode,
+            travel_time: best.travel_time,
+        };
+
+        {
+            let (used_offers, maybe_member) = if Self::supplier_shared(best.deal.main_given()) {
+                (&mut self.core_mut().used_offers, None)
+            } else {
+                (
+                    &mut self.core_mut().member_used_offers[member.as_idx()],
+                    Some(member),
+                )
+            };
+            if let Some(previous_offer) = used_offers.insert(best.deal.main_given(), best.offer) {
+                if previous_offer != best.offer {
+                    previous_offer.household.stopped_using(
+                        previous_offer.idx,
+                        id_as_household,
+                        maybe_member,
+                        world,
+                    );
+                }
+            }
+            best.offer.household.started_using(
+                best.offer.idx,
+                id_as_household,
+                maybe_member,
+                world,
+            );
+        }
+
+        self.core_mut().decision_state = DecisionState::WaitingForTrip(member);
+        best.offer
+            .household
+            .request_receive_deal(best.offer.idx, id_as_household, member, world);
+        self.start_trip(member, instant, world);
+    }
+
+    fn on_chained_deals(
+        &mut self,
+        source: RoughLocationID,
+        chained: &CVec<EvaluatedDeal>,
+        _: &mut World,
+    ) {
+        let maybe_member = self
+            .core()
+            .member_tasks
+            .iter()
+            .position(|task| match *task {
+                Task {
+                    goal: Some((_, offer)),
+                    state: TaskState::InTrip(_),
+                    ..
+                }
+                | Task {
+                    goal: Some((_, offer)),
+                    state: TaskState::StartedAt(..),
+                    ..
+                } => {
+                    let site: RoughLocationID = offer.household.into();
+                    site == source
+                }
+                _ => false,
+            });
+
+        if let Some(member) = maybe_member {
+            self.core_mut().member_chained_deals[member] = chained.clone();
+        }
+    }
+
+    fn start_trip(&mut self, member: MemberIdx, instant: Instant, world: &mut World) {
+        if let Task {
+            goal: Some((_, offer)),
+            state: TaskState::GettingReadyAt(source),
+            mode,
+            travel_time,
+            ..
+        } = self.core().member_tasks[member.as_idx()]
+        {
+            self.core_mut().member_tasks[member.as_idx()].departure = instant;
+            TripID::spawn(
+                source,
+                offer.household.into(),
+                Some(self.id_as()),
+                mode,
+                Some(travel_time),
+                instant,
+                world,
+            );
+        } else {
+            panic!("Member should be getting ready before starting trip");
+        }
+    }
+
+    fn on_trip_created(&mut self, trip: TripID, world: &mut World) {
+        self.core_mut().decision_state =
+            if let DecisionState::WaitingForTrip(member) = self.core().decision_state {
+                self.core_mut().member_tasks[member.as_idx()].state = TaskState::InTrip(trip);
+                pause_before_deciding(self.id_as(), world);
+                DecisionState::None
+            } else {
+                panic!("Should be in waiting for trip state")
+            };
+    }
+
+    fn on_trip_result(
+        &mut self,
+        trip: TripID,
+        result: TripResult,
+        rough_source: RoughLocationID,
+        rough_destination: RoughLocationID,
+        world: &mut World,
+    ) {
+        let (matching_task_member, matching_resource, matching_offer) = self
+            .core()
+            .member_tasks
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, task)| {
+                if let TaskState::InTrip(task_trip_id) = task.state {
+                    if task_trip_id == trip {
+                        if let Some((goal, offer)) = task.goal {
+                            Some((MemberIdx::new(idx), goal, offer))
+                        } else {
+                            None
+                        }
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            })
+            .next()
+            .expect("Should have a matching task");
+        {
+            let id_as_household = self.id_as();
+
+            let (used_offers, maybe_member) = if Self::supplier_shared(matching_resource) {
+                (&mut self.core_mut().used_offers, None)
+            } else {
+                (
+                    &mut self.core_mut().member_used_offers[matching_task_member.as_idx()],
+                    Some(matching_task_member),
+                )
+            };
+
+            match result.fate {
+                TripFate::Success(_) => {}
+                _ => {
+                    used_offers.remove(matching_resource);
+                    matching_offer.household.stopped_using(
+                        matching_offer.idx,
+                        id_as_household,
+                        maybe_member,
+                        world,
+                    );
+                }
+            }
+        }
+
+        match result.fate {
+            TripFate::Success(instant) => {
+                let Task {
+                    departure, mode, ..
+                } = self.core().member_tasks[matching_task_member.as_idx()];
+                self.core_mut().log_activity(
+                    matching_task_member,
+                    instant,
+                    LoggedActivity::Traveled(
+                        matching_resource,
+                        Duration::from_seconds(
+                            instant.ticks().saturating_sub(departure.ticks())
+                                / TICKS_PER_SIM_SECOND as usize,
+                        ),
+                    ),
+                );
+                // trips to work are what people consider their commute
+                if matching_resource == Resource::Money {
+                    self.core_mut().record_commute(departure, instant);
+                }
+                if Self::has_lifecycle() && mode == TravelMode::Car {
+                    let driving_minutes = instant.ticks().saturating_sub(departure.ticks()) as f32
+                        / TICKS_PER_SIM_MINUTE as f32;
+                    *self.core_mut().resources.mut_entry_or(Resource::Fuel, amount!(0.0)) -=
+                        FUEL_PER_DRIVING_MINUTE * driving_minutes;
+                }
+                self.start_task(matching_task_member, instant, rough_destination, world);
+            }
+            fate => {
+                // failed trips don't tell when, so they are logged as of the departure
+                let departure = self.core().member_tasks[matching_task_member.as_idx()].departure;
+                self.core_mut().log_activity(
+                    matching_task_member,
+                    departure,
+                    LoggedActivity::TripFailed(matching_resource),
+                );
+                self.core_mut().forget_cached_decisions_for(matching_offer);
+                self.core_mut().member_chained_deals[matching_task_member.as_idx()].clear();
+                info(
+                    LOG_T,
+                    format!(
+                        "Trip of member #{} from {:?} to {:?} failed ({:?})!\n",
+                        matching_task_member.as_idx(),
+                        rough_source,
+                        rough_destination,
+                        fate
+                    ),
+                    self.id(),
+                    world,
+                );
+
+                if let Some((_, offer)) =
+                    self.core().member_tasks[matching_task_member.as_idx()].goal
+                {
+                    offer.household.request_receive_undo_deal(
+                        offer.idx,
+                        self.id_as(),
+                        matching_task_member,
+                        world,
+                    );
+                }
+                self.stop_task(matching_task_member, result.location_now, world);
+            }
+        }
+    }
+
+    fn start_task(
+        &mut self,
+        member: MemberIdx,
+        start: Instant,
+        location: RoughLocationID,
+        world: &mut World,
+    ) {
+        debug(
+            LOG_T,
+            format!("Member #{} started task", member.0),
+            self.id(),
+            world,
+        );
+        TaskEndSchedulerID::local_first(world).schedule(
+            start + self.core().member_tasks[member.as_idx()].duration,
+            self.id_as(),
+            member,
+            world,
+        );
+        if let Some((_, offer)) = self.core().member_tasks[member.as_idx()].goal {
+            offer
+                .household
+                .started_actively_using(offer.idx, self.id_as(), member, world);
+        }
+        self.core_mut().member_tasks[member.as_idx()].state = TaskState::StartedAt(start, location);
+    }
+
+    fn stop_task(
+        &mut self,
+        member: MemberIdx,
+        location: Option<RoughLocationID>,
+        world: &mut World,
+    ) {
+        if let TaskState::InTrip(trip) = self.core().member_tasks[member.as_idx()].state {
+            debug(LOG_T, "Force stopping trip", self.id(), world);
+            // reuse normal trip failed behaviour
+            trip.finish(
+                TripResult {
+                    location_now: None,
+                    fate: TripFate::ForceStopped,
+                },
+                world,
+            )
+        } else {
+            let old_state = self.core().member_tasks[member.as_idx()].state;
+            debug(
+                LOG_T,
+                format!(
+                    "Task of member {} stopped (was in state {:?})\n",
+                    member.as_idx(),
+                    old_state,
+                ),
+                self.id(),
+                world,
+            );
+
+            self.core_mut().member_tasks[member.as_idx()].state =
+                TaskState::IdleAt(location.unwrap_or_else(|| self.site()));
+
+            if let Some((_, offer)) = self.core().member_tasks[member.as_idx()].goal {
+                offer
+                    .household
+                    .stopped_actively_using(offer.idx, self.id_as(), member, world);
+            }
+
+            TimeID::local_first(world).wake_up_in(Ticks(0), self.id_as(), world);
+        }
+    }
+
+    fn on_tick(&mut self, current_instant: Instant, world: &mut World) {
+        let update_interval = Ticks(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+        if load_spreading::is_turn(self.id().as_raw(), current_instant, update_interval) {
+            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+        }
+    }
+
+    fn get_offer(&self, idx: OfferIdx) -> &Offer {
+        &self.core().provided_offers[idx.0 as usize]
+    }
+
+    fn get_offer_mut(&mut self, idx: OfferIdx) -> &mut Offer {
+        &mut self.core_mut().provided_offers[idx.0 as usize]
+    }
+
+    fn evaluate(
+        &mut self,
+        offer_idx: OfferIdx,
+        instant: Instant,
+        location: RoughLocationID,
+        requester: EvaluationRequesterID,
+        modes: ModeSet,
+        world: &mut World,
+    ) {
+        let offer = self.get_offer(offer_idx);
+
+        if !self.core().being_destroyed
+            && offer
+                .opening_hours
+                .end_after_on_same_day(TimeOfDay::from(instant))
+            && self.can_provide(&offer.deal)
+            && (Self::can_open_without_utilities() || self.core().cut_off_from.is_empty())
+            && (offer.customers_per_staff.is_none() || self.staff_on_duty() > 0)
+        {
+            let search_result = EvaluatedSearchResult {
+                resource: offer.deal.main_given(),
+                evaluated_deals: vec![EvaluatedDeal {
+                    offer: OfferID {
+                        household: self.id_as(),
+                        idx: offer_idx,
+                    },
+                    deal: offer.deal.clone(),
+                    opening_hours: offer.opening_hours,
+                    min_education: offer.min_education,
+                    // until the trip cost estimator found the best way there
+                    mode: TravelMode::Walking,
+                    travel_time: Duration(0),
+                }]
+                .into(),
+            };
+            TripCostEstimatorID::spawn(
+                requester,
+                location,
+                self.site(),
+                search_result,
+                modes,
+                instant,
+                world,
+            );
+        } else {
+            debug(
+                LOG_T,
+                format!(
+                    "Not in opening hours, sold out, unstaffed or going away for {}",
+                    offer.deal.main_given()
+                ),
+                self.id(),
+                world,
+            );
+            requester.on_result(
+                EvaluatedSearchResult {
+                    resource: offer.deal.main_given(),
+                    evaluated_deals: CVec::new(),
+                },
+                world,
+            );
+        }
+    }
+
+    // Staffed offers only settle deals once the customer arrives and
+    // someone on shift gets to them, without staff they are closed
+    fn request_receive_deal(
+        &mut self,
+        offer_idx: OfferIdx,
+        requester: HouseholdID,
+        requester_member: MemberIdx,
+        world: &mut World,
+    ) {
+        if self.get_offer(offer_idx).customers_per_staff.is_some() {
+            if self.staff_on_duty() > 0 {
+                self.get_offer_mut(offer_idx)
+                    .waiting
+                    .push((requester, requester_member));
+                self.serve_waiting(offer_idx, world);
+            } else {
+                self.get_offer_mut(offer_idx)
+                    .turned_away
+                    .push((requester, requester_member));
+                requester.deal_rejected(
+                    OfferID {
+                        household: self.id_as(),
+                        idx: offer_idx,
+                    },
+                    requester_member,
+                    world,
+                );
+            }
+        } else {
+            self.settle_deal(offer_idx, requester, requester_member, world);
+        }
+    }
+
+    fn settle_deal(
+        &mut self,
+        offer_idx: OfferIdx,
+        requester: HouseholdID,
+        requester_member: MemberIdx,
+        world: &mut World,
+    ) {
+        let offer = self.get_offer(offer_idx).clone(); // borrow checker too dumb
+        self.provide_deal(&offer.deal, offer.offering_member, world);
+        requester.receive_deal(offer.deal.clone(), requester_member, world);
+        TreasuryID::global_first(world).record_settled_deal(
+            SettledDeal {
+                deal: offer.deal,
+                provider: self.id_as(),
+                provider_member: offer.offering_member,
+                receiver: requester,
+                receiver_member: requester_member,
+                undo: false,
+            },
+            world,
+        );
+    }
+
+    fn request_receive_undo_deal(
+        &mut self,
+        offer_idx: OfferIdx,
+        requester: HouseholdID,
+        requester_member: MemberIdx,
+        world: &mut World,
+    ) {
+        {
+            // deals of customers still waiting or turned away were never settled
+            let offer = self.get_offer_mut(offer_idx);
+            let customer = (requester, requester_member);
+            if offer.waiting.contains(&customer) || offer.turned_away.contains(&customer) {
+                offer.waiting.retain(|&waiting| waiting != customer);
+                offer
+                    .turned_away
+                    .retain(|&turned_away| turned_away != customer);
+                return;
+            }
+        }
+        let offer = self.get_offer(offer_idx).clone(); // borrow checker too dumb
+        self.receive_deal(&offer.deal, offer.offering_member, world);
+        requester.provide_deal(offer.deal.clone(), requester_member, world);
+        TreasuryID::global_first(world).record_settled_deal(
+            SettledDeal {
+                deal: offer.deal,
+                provider: self.id_as(),
+                provider_member: offer.offering_member,
+                receiver: requester,
+                receiver_member: requester_member,
+                undo: true,
+            },
+            world,
+        );
+    }
+
+    fn started_using(
+        &mut self,
+        offer_idx: OfferIdx,
+        user: HouseholdID,
+        using_member: Option<MemberIdx>,
+        world: &mut World,
+    ) {
+        let id_as_household = self.id_as();
+        if self.core().being_destroyed {
+            // found the offer just before it was withdrawn
+            user.stop_using(
+                OfferID {
+                    household: id_as_household,
+                    idx: offer_idx,
+                },
+                world,
+            );
+            return;
+        }
+
+        let offer = self.get_offer_mut(offer_idx);
+        if !offer.users.contains(&(user, using_member)) {
+            offer.users.push((user, using_member));
+            if !offer.is_internal
+                && !offer.is_private
+                && offer.users.len() >= offer.max_users as usize
+            {
+                MarketID::global_first(world).withdraw(
+                    offer.deal.main_given(),
+                    offer.tier,
+                    OfferID {
+                        household: id_as_household,
+                        idx: offer_idx,
+                    },
+                    world,
+                );
+                // already too much!
+                // TODO: this is a super hacky way to undo the overuse that happens when a lot
+                // households spawn at the same time. Replace this with a proper contract where the
+                // households waits for confirmation that it can indeed start using this offer
+                if offer.users.len() > offer.max_users as usize {
+                    user.stop_using(
+                        OfferID {
+                            household: id_as_household,
+                            idx: offer_idx,
+                        },
+                        world,
+                    )
+                }
+            }
+        }
+    }
+
+    // A user moving to another household keeps their place: the entry
+    // is handed over as it is, so the offer never looks free in between
+    fn transfer_use(
+        &mut self,
+        offer_idx: OfferIdx,
+        from: HouseholdID,
+        from_member: Option<MemberIdx>,
+        to: HouseholdID,
+        to_member: Option<MemberIdx>,
+        world: &mut World,
+    ) {
+        let transferred = {
+            let offer = self.get_offer_mut(offer_idx);
+            if let Some(user) = offer
+                .users
+                .iter_mut()
+                .find(|&&mut user| user == (from, from_member))
+            {
+                *user = (to, to_member);
+                true
+            } else {
+                false
+            }
+        };
+
+        if !transferred {
+            self.started_using(offer_idx, to, to_member, world);
+        }
     }
 
-    fn on_trip_result(
+    fn stopped_using(
         &mut self,
-        trip: TripID,
-        result: TripResult,
-        rough_source: RoughLocationID,
-        rough_destination: RoughLocationID,
+        offer_idx: OfferIdx,
+        user: HouseholdID,
+        using_member: Option<MemberIdx>,
         world: &mut World,
-    ) {
-        let (matching_task_member, matching_resource, matching_offer) = self
-            .core()
-            .member_tasks
-            .iter()
-            .enumerate()
-            .filter_map(|(idx, task)| {
-                if let TaskState::InTrip(task_trip_id) = task.state {
-                    if task_trip_id == trip {
-                        if let Some((goal, offer)) = task.goal {
-                            Some((MemberIdx::new(idx), goal, offer))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            })
-            .next()
-            .expect("Should have a matching task");
-        {
+    ) -> Fate {
+        let was_active = {
             let id_as_household = self.id_as();
+            let being_destroyed = self.core().being_destroyed;
+            let offer = self.get_offer_mut(offer_idx);
+            let users_before = offer.users.len();
 
-            let (used_offers, maybe_member) = if Self::supplier_shared(matching_resource) {
-                (&mut self.core_mut().used_offers, None)
-            } else {
-                (
-                    &mut self.core_mut().member_used_offers[matching_task_member.as_idx()],
-                    Some(matching_task_member),
-                )
-            };
+            offer.users.retain(|&(o_user, o_using_member)| {
+                o_user != user || o_using_member != using_member
+            });
 
-            match result.fate {
-                TripFate::Success(_) => {}
-                _ => {
-                    used_offers.remove(matching_resource);
-                    matching_offer.household.stopped_using(
-                        matching_offer.idx,
-                        id_as_household,
-                        maybe_member,
-                        world,
-                    );
-                }
-            }
-        }
+            // households that went away in the middle of using the offer,
+            // like employees that die at work, aren't there anymore either
+            let is_stopping = |&(o_user, o_using_member): &(HouseholdID, MemberIdx)| {
+                o_user == user && using_member.map_or(true, |member| member == o_using_member)
+            };
+            let active_before = offer.active_users.len();
+            offer.active_users.retain(|active| !is_stopping(active));
+            offer.waiting.retain(|waiting| !is_stopping(waiting));
 
-        match result.fate {
-            TripFate::Success(instant) => {
-                self.start_task(matching_task_member, instant, rough_destination, world);
-            }
-            fate => {
-                info(
-                    LOG_T,
-                    format!(
-                        "Trip of member #{} from {:?} to {:?} failed ({:?})!\n",
-                        matching_task_member.as_idx(),
-                        rough_source,
-                        rough_destination,
-                        fate
-                    ),
-                    self.id(),
+            if offer.is_internal
+                && !offer.is_private
+                && !being_destroyed
+                && users_before >= offer.max_users as usize
+                && offer.users.len() < offer.max_users as usize
+            {
+                MarketID::global_first(world).register(
+                    offer.deal.main_given(),
+                    offer.tier,
+                    OfferID {
+                        household: id_as_household,
+                        idx: offer_idx,
+                    },
                     world,
                 );
+            }
 
-                if let Some((_, offer)) =
-                    self.core().member_tasks[matching_task_member.as_idx()].goal
-                {
-                    offer.household.request_receive_undo_deal(
-                        offer.idx,
-                        self.id_as(),
-                        matching_task_member,
-                        world,
-                    );
-                }
-                self.stop_task(matching_task_member, result.location_now, world);
+            offer.active_users.len() < active_before
+        };
+
+        if was_active && !self.core().being_destroyed {
+            if self.get_offer(offer_idx).is_job() {
+                self.on_staff_change(world);
+            } else {
+                self.serve_waiting(offer_idx, world);
             }
         }
+
+        self.fate_while_destroyed()
     }
 
-    fn start_task(
+    // A household that is being destroyed only dies once none of its offers
+    // can be found or used anymore, so nobody is left waiting for an answer
+    fn fate_while_destroyed(&self) -> Fate {
+        let released = |offer: &Offer| offer.users.is_empty() && !offer.being_withdrawn;
+        if self.core().being_destroyed && self.core().provided_offers.iter().all(released) {
+            Fate::Die
+        } else {
+            Fate::Live // for now
+        }
+    }
+
+    fn started_actively_using(
         &mut self,
-        member: MemberIdx,
-        start: Instant,
-        location: RoughLocationID,
+        offer_idx: OfferIdx,
+        user: HouseholdID,
+        using_member: MemberIdx,
         world: &mut World,
     ) {
-        debug(
-            LOG_T,
-            format!("Member #{} started task", member.0),
-            self.id(),
-            world,
-        );
-        TaskEndSchedulerID::local_first(world).schedule(
-            start + self.core().member_tasks[member.as_idx()].duration,
-            self.id_as(),
-            member,
-            world,
-        );
-        if let Some((_, offer)) = self.core().member_tasks[member.as_idx()].goal {
+        let offer = self.get_offer_mut(offer_idx);
+        if offer.turned_away.contains(&(user, using_member)) {
+            // they only find the door closed
             offer
-                .household
-                .started_actively_using(offer.idx, self.id_as(), member, world);
+                .turned_away
+                .retain(|&turned_away| turned_away != (user, using_member));
+            return;
+        }
+        if !offer.active_users.contains(&(user, using_member)) {
+            // people spending their free time at the same place might get to know
+            // whoever arrived just before them
+            let is_leisure = !offer.is_private
+                && offer.deal.delta.iter().any(|&Entry(resource, amount)| {
+                    amount > 0.0
+                        && (resource == Resource::Entertainment
+                            || resource == Resource::Companionship)
+                });
+            if is_leisure {
+                if let Some(&(other, _)) = offer
+                    .active_users
+                    .iter()
+                    .rev()
+                    .find(|&&(other, _)| other != user)
+                {
+                    SocialGraphID::global_first(world).met(user, other, world);
+                }
+            }
+
+            offer.active_users.push((user, using_member));
+        }
+
+        if self.get_offer(offer_idx).is_job() {
+            self.on_staff_change(world);
+        } else {
+            self.serve_waiting(offer_idx, world);
         }
-        self.core_mut().member_tasks[member.as_idx()].state = TaskState::StartedAt(start, location);
     }
 
-    fn stop_task(
+    fn stopped_actively_using(
         &mut self,
-        member: MemberIdx,
-        location: Option<RoughLocationID>,
+        offer_idx: OfferIdx,
+        user: HouseholdID,
+        using_member: MemberIdx,
         world: &mut World,
     ) {
-        if let TaskState::InTrip(trip) = self.core().member_tasks[member.as_idx()].state {
-            debug(LOG_T, "Force stopping trip", self.id(), world);
-            // reuse normal trip failed behaviour
-            trip.finish(
-                TripResult {
-                    location_now: None,
-                    fate: TripFate::ForceStopped,
+        let gave_up_waiting = {
+            let offer = self.get_offer_mut(offer_idx);
+            offer.active_users.retain(|&(o_user, o_using_member)| {
+                o_user != user || o_using_member != using_member
+            });
+            let was_waiting = offer.waiting.contains(&(user, using_member));
+            offer
+                .waiting
+                .retain(|&waiting| waiting != (user, using_member));
+            was_waiting
+        };
+
+        if gave_up_waiting {
+            user.deal_rejected(
+                OfferID {
+                    household: self.id_as(),
+                    idx: offer_idx,
                 },
-                world,
-            )
-        } else {
-            let old_state = self.core().member_tasks[member.as_idx()].state;
-            debug(
-                LOG_T,
-                format!(
-                    "Task of member {} stopped (was in state {:?})\n",
-                    member.as_idx(),
-                    old_state,
-                ),
-                self.id(),
+                using_member,
                 world,
             );
+        }
 
-            self.core_mut().member_tasks[member.as_idx()].state =
-                TaskState::IdleAt(location.unwrap_or_else(|| self.site()));
-
-            if let Some((_, offer)) = self.core().member_tasks[member.as_idx()].goal {
-                offer
-                    .household
-                    .stopped_actively_using(offer.idx, self.id_as(), member, world);
-            }
-
-            TimeID::local_first(world).wake_up_in(Ticks(0), self.id_as(), world);
+        if self.get_offer(offer_idx).is_job() {
+            self.on_staff_change(world);
+        } else {
+            self.serve_waiting(offer_idx, world);
         }
     }
 
-    fn on_tick(&mut self, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id().as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
-            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+    fn staff_on_duty(&self) -> usize {
+        self.core()
+            .provided_offers
+            .iter()
+            .filter(|offer| offer.is_job())
+            .map(|offer| offer.active_users.len())
+            .sum()
+    }
+
+    // Serves customers that are already there in the order they asked,
+    // for as long as the staff on shift can handle more at a time
+    fn serve_waiting(&mut self, offer_idx: OfferIdx, world: &mut World) {
+        let capacity = match self.get_offer(offer_idx).customers_per_staff {
+            Some(customers_per_staff) => self.staff_on_duty() * customers_per_staff as usize,
+            None => return,
+        };
+
+        loop {
+            let maybe_next = {
+                let offer = self.get_offer(offer_idx);
+                let n_being_served = offer
+                    .active_users
+                    .iter()
+                    .filter(|&active_user| !offer.waiting.contains(active_user))
+                    .count();
+                if n_being_served < capacity {
+                    offer
+                        .waiting
+                        .iter()
+                        .find(|&waiting| offer.active_users.contains(waiting))
+                        .cloned()
+                } else {
+                    None
+                }
+            };
+
+            if let Some((customer, customer_member)) = maybe_next {
+                self.get_offer_mut(offer_idx)
+                    .waiting
+                    .retain(|&waiting| waiting != (customer, customer_member));
+                self.settle_deal(offer_idx, customer, customer_member, world);
+            } else {
+                break;
+            }
         }
     }
 
-    fn get_offer(&self, idx: OfferIdx) -> &Offer {
-        &self.core().provided_offers[idx.0 as usize]
+    // When the last member of staff leaves, everyone still waiting is sent away
+    fn on_staff_change(&mut self, world: &mut World) {
+        let id_as_household = self.id_as();
+        let on_duty = self.staff_on_duty();
+
+        for idx in 0..self.core().provided_offers.len() {
+            let offer_idx = OfferIdx(idx as u16);
+            if self.get_offer(offer_idx).customers_per_staff.is_none() {
+                continue;
+            }
+
+            if on_duty > 0 {
+                self.serve_waiting(offer_idx, world);
+            } else {
+                let offer = self.get_offer_mut(offer_idx);
+                for &(customer, customer_member) in offer.waiting.clone().iter() {
+                    if offer.active_users.contains(&(customer, customer_member)) {
+                        offer
+                            .active_users
+                            .retain(|&active_user| active_user != (customer, customer_member));
+                    } else {
+                        offer.turned_away.push((customer, customer_member));
+                    }
+                    customer.deal_rejected(
+                        OfferID {
+                            household: id_as_household,
+                            idx: offer_idx,
+                        },
+                        customer_member,
+                        world,
+                    );
+                }
+                offer.waiting.clear();
+            }
+        }
     }
 
-    fn get_offer_mut(&mut self, idx: OfferIdx) -> &mut Offer {
-        &mut self.core_mut().provided_offers[idx.0 as usize]
+    // Logged as of the departure, since the closed offer doesn't tell when
+    fn deal_rejected(&mut self, offer: OfferID, member: MemberIdx, _: &mut World) {
+        self.core_mut().forget_cached_decisions_for(offer);
+        let task = self.core().member_tasks[member.as_idx()];
+        if let Some((resource, goal_offer)) = task.goal {
+            if goal_offer == offer {
+                self.core_mut().log_activity(
+                    member,
+                    task.departure,
+                    LoggedActivity::TurnedAway(resource),
+                );
+            }
+        }
     }
 
-    fn evaluate(
+    // Friends ask for a visit like they would ask for any other offer,
+    // only that households without a fitting social offer answer with nothing
+    fn evaluate_visit(
         &mut self,
-        offer_idx: OfferIdx,
+        resource: Resource,
         instant: Instant,
         location: RoughLocationID,
         requester: EvaluationRequesterID,
+        modes: ModeSet,
         world: &mut World,
     ) {
-        let offer = self.get_offer(offer_idx);
+        let maybe_visit_idx = self
+            .core()
+            .provided_offers
+            .iter()
+            .position(|offer| offer.is_social && offer.deal.main_given() == resource);
 
-        if offer
-            .opening_hours
-            .end_after_on_same_day(TimeOfDay::from(instant))
-        {
-            let search_result = EvaluatedSearchResult {
-                resource: offer.deal.main_given(),
-                evaluated_deals: vec![EvaluatedDeal {
-                    offer: OfferID {
-                        household: self.id_as(),
-                        idx: offer_idx,
-                    },
-                    deal: offer.deal.clone(),
-                    opening_hours: offer.opening_hours,
-                }]
-                .into(),
-            };
-            TripCostEstimatorID::spawn(
-                requester,
-                location,
-                self.site(),
-                search_result,
+        if let Some(visit_idx) = maybe_visit_idx {
+            self.evaluate(
+                OfferIdx(visit_idx as u16),
                 instant,
+                location,
+                requester,
+                modes,
                 world,
             );
         } else {
-            debug(
-                LOG_T,
-                format!("Not in opening hours for {}", offer.deal.main_given()),
-                self.id(),
-                world,
-            );
             requester.on_result(
                 EvaluatedSearchResult {
-                    resource: offer.deal.main_given(),
+                    resource,
                     evaluated_deals: CVec::new(),
                 },
                 world,
@@ -810,146 +2405,79 @@ pub trait Household:
         }
     }
 
-    fn request_receive_deal(
-        &mut self,
-        offer_idx: OfferIdx,
-        requester: HouseholdID,
-        requester_member: MemberIdx,
-        world: &mut World,
-    ) {
-        let offer = self.get_offer(offer_idx).clone(); // borrow checker too dumb
-        self.provide_deal(&offer.deal, offer.offering_member, world);
-        requester.receive_deal(offer.deal, requester_member, world);
+    fn on_utility_supply(&mut self, utility: Utility, supplied: bool, _: &mut World) {
+        let cut_off_from = &mut self.core_mut().cut_off_from;
+        cut_off_from.retain(|&cut_off| cut_off != utility);
+        if !supplied {
+            cut_off_from.push(utility);
+        }
     }
 
-    fn request_receive_undo_deal(
-        &mut self,
-        offer_idx: OfferIdx,
-        requester: HouseholdID,
-        requester_member: MemberIdx,
-        world: &mut World,
-    ) {
-        let offer = self.get_offer(offer_idx).clone(); // borrow checker too dumb
-        self.receive_deal(&offer.deal, offer.offering_member, world);
-        requester.provide_deal(offer.deal, requester_member, world);
+    fn on_air_pollution(&mut self, pollution: f32, _: &mut World) {
+        self.core_mut().air_pollution = pollution;
     }
 
-    fn started_using(
+    // Paid like any other expense, so savings or loans cover it at the next
+    // finances check. Households that are already in debt don't invest
+    fn invest_in_upgrade(
         &mut self,
-        offer_idx: OfferIdx,
-        user: HouseholdID,
-        using_member: Option<MemberIdx>,
+        building: BuildingID,
+        upgrade: EfficiencyUpgrade,
+        cost: ResourceAmount,
+        subsidy: ResourceAmount,
         world: &mut World,
     ) {
-        let id_as_household = self.id_as();
-        let offer = self.get_offer_mut(offer_idx);
-        if !offer.users.contains(&(user, using_member)) {
-            offer.users.push((user, using_member));
-            if !offer.is_internal && offer.users.len() >= offer.max_users as usize {
-                MarketID::global_first(world).withdraw(
-                    offer.deal.main_given(),
-                    OfferID {
-                        household: id_as_household,
-                        idx: offer_idx,
-                    },
-                    world,
-                );
-                // already too much!
-                // TODO: this is a super hacky way to undo the overuse that happens when a lot
-                // households spawn at the same time. Replace this with a proper contract where the
-                // households waits for confirmation that it can indeed start using this offer
-                if offer.users.len() > offer.max_users as usize {
-                    user.stop_using(
-                        OfferID {
-                            household: id_as_household,
-                            idx: offer_idx,
-                        },
-                        world,
-                    )
-                }
-            }
+        let money = self
+            .core()
+            .resources
+            .get(Resource::Money)
+            .cloned()
+            .unwrap_or(amount!(0.0));
+        if self.core().being_destroyed || money < 0.0 {
+            return;
         }
-    }
-
-    fn stopped_using(
-        &mut self,
-        offer_idx: OfferIdx,
-        user: HouseholdID,
-        using_member: Option<MemberIdx>,
-        world: &mut World,
-    ) -> Fate {
-        {
-            let id_as_household = self.id_as();
-            let offer = self.get_offer_mut(offer_idx);
-            let users_before = offer.users.len();
 
-            offer.users.retain(|&(o_user, o_using_member)| {
-                o_user != user || o_using_member != using_member
-            });
+        *self.core_mut().resources.mut_entry_or(Resource::Money, amount!(0.0)) -= cost;
+        if subsidy > 0.0 {
+            TreasuryID::global_first(world).pay_out(self.id_as(), MemberIdx(0), subsidy, world);
+        }
+        building.install_upgrade(upgrade, world);
+    }
 
-            if offer.is_internal
-                && users_before >= offer.max_users as usize
-                && offer.users.len() < offer.max_users as usize
-            {
-                MarketID::global_first(world).register(
-                    offer.deal.main_given(),
-                    OfferID {
-                        household: id_as_household,
-                        idx: offer_idx,
-                    },
-                    world,
-                );
-            }
+    fn befriended(&mut self, friend: HouseholdID, _: &mut World) {
+        if !self.core().friends.contains(&friend) {
+            self.core_mut().friends.push(friend);
         }
+    }
 
-        if self.core().being_destroyed {
-            // maybe already all users are gone
-            let no_users = |offer: &Offer| offer.users.is_empty();
-            if self.core().provided_offers.iter().all(no_users) {
-                Fate::Die
-            } else {
-                Fate::Live // for now
+    fn unfriended(&mut self, former_friend: HouseholdID, world: &mut World) {
+        self.core_mut()
+            .friends
+            .retain(|&friend| friend != former_friend);
+
+        let mut visits = Vec::new();
+        let core = self.core();
+        for used_offers in Some(&core.used_offers)
+            .into_iter()
+            .chain(core.member_used_offers.iter())
+        {
+            for &Entry(_, offer) in used_offers.iter() {
+                if offer.household == former_friend && !visits.contains(&offer) {
+                    visits.push(offer);
+                }
             }
-        } else {
-            Fate::Live
         }
-    }
 
-    fn started_actively_using(
-        &mut self,
-        offer_idx: OfferIdx,
-        user: HouseholdID,
-        using_member: MemberIdx,
-        _: &mut World,
-    ) {
-        let offer = self.get_offer_mut(offer_idx);
-        if !offer.active_users.contains(&(user, using_member)) {
-            offer.active_users.push((user, using_member));
+        for visit in visits {
+            self.stop_using(visit, world);
         }
     }
 
-    fn stopped_actively_using(
-        &mut self,
-        offer_idx: OfferIdx,
-        user: HouseholdID,
-        using_member: MemberIdx,
-        _: &mut World,
-    ) {
-        let offer = self.get_offer_mut(offer_idx);
-        offer
-            .active_users
-            .retain(|&(o_user, o_using_member)| o_user != user || o_using_member != using_member);
-    }
-
-    // TODO: there is still a tiny potential race condition here:
-    //       1) household finds offer in market -> household
-    //       2) offer withdrawn from market
-    //       3) withdrawal confirmed
-    //       ... starting to notify existing users
-    //       4) household starts using offer
-    //       => dangling single user keeping the offer half-dead
+    // Households that find an offer just before it is withdrawn and only
+    // start using it afterwards are sent away again by `started_using`
     fn withdrawal_confirmed(&mut self, offer_idx: OfferIdx, world: &mut World) -> Fate {
         if self.core().being_destroyed {
+            self.get_offer_mut(offer_idx).being_withdrawn = false;
             let offer = self.get_offer(offer_idx);
 
             for user in &offer.users {
@@ -961,22 +2489,170 @@ pub trait Household:
                     world,
                 )
             }
-
-            // maybe already all users are gone
-            let no_users = |offer: &Offer| offer.users.is_empty();
-            if self.core().provided_offers.iter().all(no_users) {
-                Fate::Die
-            } else {
-                Fate::Live // for now
-            }
-        } else {
-            Fate::Live
         }
+
+        self.fate_while_destroyed()
     }
 
     fn get_ui_info(&mut self, requester: ui::HouseholdUIID, world: &mut World) {
         requester.on_household_ui_info(self.id_as(), self.core().clone(), world);
     }
+
+    fn get_activity_log(&mut self, requester: ui::HouseholdUIID, world: &mut World) {
+        requester.on_household_activity_log(
+            self.id_as(),
+            self.core().member_activities.clone(),
+            world,
+        );
+    }
+
+    // only people have stories worth telling
+    fn offer_for_vignette(&mut self, requester: ui::HouseholdUIID, world: &mut World) {
+        if Self::has_lifecycle()
+            && !self.core().being_destroyed
+            && self.core().n_present_members() > 0
+        {
+            requester.on_vignette_candidate(self.id_as(), world);
+        }
+    }
+
+    // about one of the members, picked by the draw of whoever asks
+    fn get_vignette(&mut self, draw: u32, requester: ui::HouseholdUIID, world: &mut World) {
+        let present = (0..self.core().member_lives.len())
+            .filter(|&idx| self.core().member_lives[idx].is_present())
+            .collect::<Vec<_>>();
+        if present.is_empty() {
+            return;
+        }
+
+        let member = MemberIdx::new(present[draw as usize % present.len()]);
+        let member_name = self.member_name(member);
+        requester.on_member_vignette(
+            self.id_as(),
+            member,
+            vignette::title(&member_name, self.core(), member).into(),
+            vignette::compose(
+                &self.household_name(),
+                &member_name,
+                self.is_homeless(),
+                self.core(),
+                member,
+            )
+            .into(),
+            world,
+        );
+    }
+
+    fn observe(&mut self, observer: ApiObserverID, world: &mut World) {
+        let money = self
+            .core()
+            .resources
+            .get(Resource::Money)
+            .cloned()
+            .unwrap_or(amount!(0.0));
+        observer.on_observed_household(
+            self.id_as(),
+            self.household_name().into(),
+            self.core().n_present_members() as u32,
+            amount_to_f32(money),
+            self.core().happiness,
+            world,
+        );
+    }
+
+    // Members of working age count as employed if they hold a job,
+    // and everything sold to others is counted at its asking price
+    fn contribute_to_metrics(&mut self, metrics: CityMetricsID, day: u32, world: &mut World) {
+        let (population, n_working_age, n_employed, commute_minutes) = if Self::has_lifecycle() {
+            let core = self.core();
+            let working_age = core
+                .member_lives
+                .iter()
+                .enumerate()
+                .filter(|&(_, life)| life.is_present() && life.stage == LifeStage::Worker)
+                .map(|(idx, _)| idx)
+                .collect::<Vec<_>>();
+            let n_employed = working_age
+                .iter()
+                .filter(|&&idx| core.member_used_offers[idx].get(Resource::Money).is_some())
+                .count();
+            (
+                core.n_present_members() as u32,
+                working_age.len() as u32,
+                n_employed as u32,
+                core.average_commute_minutes,
+            )
+        } else {
+            (0, 0, 0, None)
+        };
+
+        let prices = self
+            .core()
+            .provided_offers
+            .iter()
+            .filter(|offer| !offer.is_private && !offer.is_internal && !offer.is_job())
+            .filter_map(|offer| {
+                let resource = offer.deal.main_given();
+                let amount = offer.deal.delta.get(resource).cloned().unwrap_or(amount!(0.0));
+                let paid = -offer
+                    .deal
+                    .delta
+                    .get(Resource::Money)
+                    .cloned()
+                    .unwrap_or(amount!(0.0));
+                if amount > 0.0 && paid > 0.0 {
+                    Some((resource, amount_to_f32(paid / amount)))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        metrics.add_household(
+            day,
+            population,
+            n_working_age,
+            n_employed,
+            commute_minutes,
+            prices,
+            world,
+        );
+    }
+
+    // called by the building the household lives or works in,
+    // which knows where it is
+    fn contribute_to_district_snapshot(
+        &mut self,
+        timeline: TimelineID,
+        instant: Instant,
+        position: P2,
+        world: &mut World,
+    ) {
+        let (population, commute_minutes) = if Self::has_lifecycle() {
+            (
+                self.core().n_present_members() as u32,
+                self.core().average_commute_minutes,
+            )
+        } else {
+            (0, None)
+        };
+        let n_jobs = self
+            .core()
+            .provided_offers
+            .iter()
+            .filter(|offer| offer.is_job())
+            .map(|offer| offer.max_users)
+            .sum();
+
+        timeline.add_household(
+            instant,
+            position,
+            population,
+            n_jobs,
+            commute_minutes,
+            world,
+        );
+    }
 }
 
 #[derive(Compact, Clone)]
@@ -990,6 +2666,16 @@ pub struct DecisionResourceEntry {
     results_counter: AsyncCounter,
     best_deal: COption<EvaluatedDeal>,
     best_deal_usefulness: f32,
+    from_cache: bool,
+    n_private_results: u32,
+    // so the search can be stopped if the household goes away
+    search: COption<NearestOffersSearchID>,
+}
+
+#[derive(Compact, Clone, Debug, Serialize)]
+pub struct CachedDecision {
+    best_deal: EvaluatedDeal,
+    valid_until: Instant,
 }
 
 #[derive(Compact, Clone, Debug, Serialize)]
@@ -1013,7 +2699,30 @@ pub struct HouseholdCore {
     pub used_offers: ResourceMap<OfferID>,
     pub member_used_offers: CVec<ResourceMap<OfferID>>,
     pub provided_offers: CVec<Offer>,
+    pub private_offer_directory: ResourceMap<CVec<OfferIdx>>,
     pub being_destroyed: bool,
+    pub decision_cache: CVec<ResourceMap<CachedDecision>>,
+    pub member_chained_deals: CVec<CVec<EvaluatedDeal>>,
+    pub member_lives: CVec<MemberLife>,
+    pub average_commute_minutes: Option<f32>,
+    pub member_needs: CVec<Needs>,
+    pub happiness: f32,
+    // years of schooling of each member
+    pub member_education: CVec<f32>,
+    pub member_health: CVec<Health>,
+    pub member_plans: CVec<DayPlan>,
+    // what each member recently decided and did, newest last
+    pub member_activities: CVec<CVec<ActivityLogEntry>>,
+    // members about to leave for a new household, with the search they're joining
+    pub departures: CVec<(MemberIdx, HousingSearchID)>,
+    pub friends: CVec<HouseholdID>,
+    // utilities the household's building is currently cut off from
+    pub cut_off_from: CVec<Utility>,
+    // at the household's building, makes members more likely to fall sick
+    pub air_pollution: f32,
+    next_finances_check: Instant,
+    next_aging_check: Instant,
+    next_planning: Instant,
 }
 
 impl HouseholdCore {
@@ -1026,15 +2735,26 @@ impl HouseholdCore {
     ) -> Self {
         assert!(n_members > 0);
 
+        let mut private_offer_directory = ResourceMap::<CVec<OfferIdx>>::new();
+
         for (idx, offer) in provided_offers.iter().enumerate() {
-            MarketID::local_first(world).register(
-                offer.deal.main_given(),
-                OfferID {
-                    household: owner,
-                    idx: OfferIdx(idx as u16),
-                },
-                world,
-            )
+            if offer.is_social {
+                continue;
+            } else if offer.is_private {
+                private_offer_directory
+                    .mut_entry_or(offer.deal.main_given(), CVec::new())
+                    .push(OfferIdx(idx as u16));
+            } else {
+                MarketID::local_first(world).register(
+                    offer.deal.main_given(),
+                    offer.tier,
+                    OfferID {
+                        household: owner,
+                        idx: OfferIdx(idx as u16),
+                    },
+                    world,
+                )
+            }
         }
 
         HouseholdCore {
@@ -1045,7 +2765,226 @@ impl HouseholdCore {
             used_offers: ResourceMap::new(),
             member_used_offers: vec![ResourceMap::new(); n_members].into(),
             provided_offers,
+            private_offer_directory,
             being_destroyed: false,
+            decision_cache: vec![ResourceMap::new(); n_members].into(),
+            member_chained_deals: vec![CVec::new(); n_members].into(),
+            member_lives: vec![MemberLife::aged(aging::WORKER_AGE); n_members].into(),
+            average_commute_minutes: None,
+            member_needs: vec![Needs::new(); n_members].into(),
+            happiness: 1.0,
+            member_education: vec![0.0; n_members].into(),
+            member_health: vec![Health::healthy(); n_members].into(),
+            member_plans: vec![DayPlan::new(); n_members].into(),
+            member_activities: vec![CVec::new(); n_members].into(),
+            departures: CVec::new(),
+            friends: CVec::new(),
+            cut_off_from: CVec::new(),
+            air_pollution: 0.0,
+            next_finances_check: Instant::new(0),
+            next_aging_check: Instant::new(0),
+            next_planning: Instant::new(0),
+        }
+    }
+
+    pub fn record_commute(&mut self, departure: Instant, arrival: Instant) {
+        let minutes =
+            arrival.ticks().saturating_sub(departure.ticks()) as f32 / TICKS_PER_SIM_MINUTE as f32;
+        self.average_commute_minutes =
+            Some(self.average_commute_minutes.map_or(minutes, |average| {
+                average + COMMUTE_SMOOTHING * (minutes - average)
+            }));
+    }
+
+    pub fn log_activity(&mut self, member: MemberIdx, instant: Instant, activity: LoggedActivity) {
+        activity_log::record(
+            &mut self.member_activities[member.as_idx()],
+            instant,
+            activity,
+        );
+    }
+
+    pub fn n_present_members(&self) -> usize {
+        self.member_lives
+            .iter()
+            .filter(|life| life.is_present())
+            .count()
+    }
+
+    // newborns take over the slot of a deceased member if possible,
+    // so member indices used in offers and tasks stay stable
+    pub fn add_member(&mut self, location: RoughLocationID) -> MemberIdx {
+        let maybe_free_slot = self
+            .member_lives
+            .iter()
+            .zip(self.member_tasks.iter())
+            .position(|(life, task)| match task.state {
+                TaskState::IdleAt(_) => !life.is_present(),
+                _ => false,
+            });
+
+        if let Some(idx) = maybe_free_slot {
+            self.member_resources[idx] = Inventory::new();
+            self.member_tasks[idx] = Task::idle_at(location);
+            self.member_used_offers[idx] = ResourceMap::new();
+            self.decision_cache[idx] = ResourceMap::new();
+            self.member_chained_deals[idx] = CVec::new();
+            self.member_lives[idx] = MemberLife::newborn();
+            self.member_needs[idx] = Needs::new();
+            self.member_education[idx] = 0.0;
+            self.member_health[idx] = Health::healthy();
+            self.member_plans[idx] = DayPlan::new();
+            self.member_activities[idx] = CVec::new();
+            MemberIdx::new(idx)
+        } else {
+            self.member_resources.push(Inventory::new());
+            self.member_tasks.push(Task::idle_at(location));
+            self.member_used_offers.push(ResourceMap::new());
+            self.decision_cache.push(ResourceMap::new());
+            self.member_chained_deals.push(CVec::new());
+            self.member_lives.push(MemberLife::newborn());
+            self.member_needs.push(Needs::new());
+            self.member_education.push(0.0);
+            self.member_health.push(Health::healthy());
+            self.member_plans.push(DayPlan::new());
+            self.member_activities.push(CVec::new());
+            MemberIdx::new(self.member_lives.len() - 1)
+        }
+    }
+
+    pub fn is_departing(&self, member: MemberIdx) -> bool {
+        self.departures
+            .iter()
+            .any(|&(departing, _)| departing == member)
+    }
+
+    fn other_present_members(&self, member: MemberIdx) -> impl Iterator<Item = &MemberLife> {
+        self.member_lives
+            .iter()
+            .enumerate()
+            .filter(move |&(idx, life)| idx != member.as_idx() && life.is_present())
+            .map(|(_, life)| life)
+    }
+
+    // someone a generation older lives in the household
+    pub fn lives_with_parent(&self, member: MemberIdx) -> bool {
+        let years = self.member_lives[member.as_idx()].years();
+        self.other_present_members(member)
+            .any(|other| other.years() >= years + MIN_PARENT_AGE)
+    }
+
+    // an adult without a partner or children of their own in the household
+    pub fn is_single(&self, member: MemberIdx) -> bool {
+        let life = self.member_lives[member.as_idx()];
+        let years = life.years();
+        life.could_be_parent()
+            && self.other_present_members(member).all(|other| {
+                let is_partner = other.could_be_parent()
+                    && (other.years() as i32 - years as i32).abs() < MIN_PARENT_AGE as i32;
+                let is_own_child = other.years() + MIN_PARENT_AGE <= years;
+                !is_partner && !is_own_child
+            })
+    }
+
+    // nobody leaves only children behind
+    pub fn could_leave(&self, member: MemberIdx) -> bool {
+        let mut others = self.other_present_members(member).peekable();
+        others.peek().is_none()
+            || others.any(|other| match other.stage {
+                LifeStage::Worker | LifeStage::Retiree => true,
+                _ => false,
+            })
+    }
+
+    // Takes a member out of the household, together with their share of what
+    // the household owns. The last one to leave takes everything along
+    pub fn part_with(
+        &mut self,
+        owner: HouseholdID,
+        member: MemberIdx,
+        location: RoughLocationID,
+    ) -> MovingMember {
+        let idx = member.as_idx();
+        let n_present = self.n_present_members();
+
+        let mut shared_resources = Inventory::new();
+        let owned = self.resources.iter().cloned().collect::<Vec<_>>();
+        for Entry(resource, amount) in owned {
+            if amount > 0.0 && (n_present == 1 || is_divisible(resource)) {
+                let share = amount / n_present as f32;
+                *shared_resources.mut_entry_or(resource, amount!(0.0)) += share;
+                *self.resources.mut_entry_or(resource, amount!(0.0)) -= share;
+            }
+        }
+
+        let mover = MovingMember {
+            origin: owner,
+            origin_member: member,
+            location,
+            life: self.member_lives[idx],
+            education: self.member_education[idx],
+            health: self.member_health[idx],
+            needs: self.member_needs[idx],
+            resources: self.member_resources[idx].clone(),
+            shared_resources,
+            used_offers: self.member_used_offers[idx].clone(),
+        };
+
+        // the member's contracts go along with them, so they aren't stopped here
+        self.member_lives[idx].stage = LifeStage::MovedOut;
+        self.member_resources[idx] = Inventory::new();
+        self.member_used_offers[idx] = ResourceMap::new();
+        self.decision_cache[idx] = ResourceMap::new();
+        self.member_chained_deals[idx] = CVec::new();
+        self.member_needs[idx] = Needs::new();
+        self.member_plans[idx] = DayPlan::new();
+        self.member_activities[idx] = CVec::new();
+
+        mover
+    }
+
+    // Gives a member that moved here from another household their place.
+    // Their contracts still have to be handed over by the household itself
+    pub fn take_in(&mut self, member: MemberIdx, mover: &MovingMember) {
+        let idx = member.as_idx();
+        self.member_lives[idx] = mover.life;
+        self.member_education[idx] = mover.education;
+        self.member_health[idx] = mover.health;
+        self.member_needs[idx] = mover.needs;
+        self.member_resources[idx] = mover.resources.clone();
+        self.member_used_offers[idx] = mover.used_offers.clone();
+        self.member_tasks[idx] = Task::idle_at(mover.location);
+
+        for &Entry(resource, amount) in mover.shared_resources.iter() {
+            *self.resources.mut_entry_or(resource, amount!(0.0)) += amount;
+        }
+    }
+
+    // what the household last found out about an offer, from any member
+    pub fn known_deal(&self, offer: OfferID) -> Option<EvaluatedDeal> {
+        self.decision_cache
+            .iter()
+            .filter_map(|member_cache| {
+                member_cache
+                    .iter()
+                    .map(|&Entry(_, ref cached)| &cached.best_deal)
+                    .find(|cached_deal| cached_deal.offer == offer)
+            })
+            .next()
+            .cloned()
+    }
+
+    pub fn forget_cached_decisions_for(&mut self, offer: OfferID) {
+        for member_cache in self.decision_cache.iter_mut() {
+            let stale_resources = member_cache
+                .iter()
+                .filter(|&&Entry(_, ref cached)| cached.best_deal.offer == offer)
+                .map(|&Entry(resource, _)| resource)
+                .collect::<Vec<_>>();
+
+            for resource in stale_resources {
+                member_cache.remove(resource);
+            }
         }
     }
 }
@@ -1055,17 +2994,33 @@ pub fn setup(system: &mut ActorSystem) {
     tasks::setup(system);
     family::setup(system);
     grocery_shop::setup(system);
+    school::setup(system);
+    hospital::setup(system);
+    car_dealer::setup(system);
+    farming::auto_setup(system);
     grain_farm::setup(system);
     cow_farm::setup(system);
     vegetable_farm::setup(system);
     mill::setup(system);
     bakery::setup(system);
     neighboring_town_trade::setup(system);
+    informal_dealer::setup(system);
+    leisure_venue::setup(system);
+    construction_firm::setup(system);
+    utility_company::setup(system);
+    taxi_company::setup(system);
     ui::auto_setup(system);
+    aging::auto_setup(system);
+    formation::setup(system);
+    archetypes::setup(system);
+    social::setup(system);
 }
 
-pub fn spawn(world: &mut World) {
+pub fn spawn(world: &mut World, time: TimeID) {
     tasks::spawn(world);
+    formation::spawn(world, time);
+    archetypes::spawn(world);
+    social::spawn(world);
 }
 
 mod kay_auto;
@@ -0,0 +1,86 @@
+use cb_time::units::{Instant, TICKS_PER_SIM_MINUTE};
+use economy::resources::{Resource, ResourceAmount};
+use economy::resources::Resource::*;
+
+// how much more urgent an unmet need becomes for each hour it stays unmet,
+// capped so that long neglected needs don't drown out everything else
+const URGENCY_GROWTH_PER_HOUR: f32 = 0.1;
+const MAX_URGENCY_GROWTH: f32 = 2.0;
+// summed urgency of all needs at which a person is completely unhappy
+const UNHAPPINESS_URGENCY: f32 = 60.0;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Need {
+    Food,
+    Rest,
+    Social,
+    Leisure,
+}
+
+pub const ALL_NEEDS: [Need; 4] = [Need::Food, Need::Rest, Need::Social, Need::Leisure];
+
+impl Need {
+    pub fn resource(self) -> Resource {
+        match self {
+            Need::Food => Satiety,
+            Need::Rest => Wakefulness,
+            Need::Social => Companionship,
+            Need::Leisure => Entertainment,
+        }
+    }
+
+    pub fn of(resource: Resource) -> Option<Need> {
+        ALL_NEEDS
+            .iter()
+            .find(|need| need.resource() == resource)
+            .cloned()
+    }
+
+    // everything but food can be taken care of at home without using anything up
+    pub fn is_self_provided(self) -> bool {
+        self != Need::Food
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct Needs {
+    // since when each need has been unmet, in the order of `ALL_NEEDS`
+    unmet_since: [Option<Instant>; 4],
+}
+
+impl Needs {
+    pub fn new() -> Needs {
+        Needs {
+            unmet_since: [None; 4],
+        }
+    }
+
+    pub fn update(&mut self, need: Need, amount: ResourceAmount, instant: Instant) {
+        let unmet_since = &mut self.unmet_since[need as usize];
+        if amount >= 0.0 {
+            *unmet_since = None;
+        } else if unmet_since.is_none() {
+            *unmet_since = Some(instant);
+        }
+    }
+
+    pub fn hours_unmet(&self, need: Need, instant: Instant) -> f32 {
+        self.unmet_since[need as usize]
+            .map(|since| {
+                instant.ticks().saturating_sub(since.ticks()) as f32
+                    / (60 * TICKS_PER_SIM_MINUTE) as f32
+            })
+            .unwrap_or(0.0)
+    }
+
+    pub fn urgency(&self, need: Need, graveness: f32, instant: Instant) -> f32 {
+        let hours_unmet = self.hours_unmet(need, instant);
+
+        graveness * (1.0 + (URGENCY_GROWTH_PER_HOUR * hours_unmet).min(MAX_URGENCY_GROWTH))
+    }
+}
+
+// 1.0 means perfectly content, 0.0 means completely miserable
+pub fn happiness(total_urgency: f32) -> f32 {
+    1.0 - (total_urgency / UNHAPPINESS_URGENCY).max(0.0).min(1.0)
+}
@@ -1,5 +1,6 @@
 use compact::CVec;
-use economy::market::Deal;
+use economy::market::{Deal, MarketTier};
+use economy::resources::Resource;
 use super::{HouseholdID, MemberIdx};
 use cb_time::units::TimeOfDayRange;
 
@@ -19,9 +20,22 @@ pub struct Offer {
     pub deal: Deal,
     pub max_users: u32,
     pub is_internal: bool,
+    pub is_private: bool,
+    // only open to friends of the offering household, who come over to visit
+    pub is_social: bool,
+    pub tier: MarketTier,
+    // years of schooling a member needs to be able to use this offer
+    pub min_education: f32,
     pub users: CVec<(HouseholdID, Option<MemberIdx>)>,
     pub active_users: CVec<(HouseholdID, MemberIdx)>,
     pub being_withdrawn: bool,
+    // how many customers each member of staff on shift can serve at a time,
+    // if the offer needs staff at all
+    pub customers_per_staff: Option<u32>,
+    // customers whose deal is only settled once they are served, in order
+    pub waiting: CVec<(HouseholdID, MemberIdx)>,
+    // customers who were told the offer is closed but are still on their way
+    pub turned_away: CVec<(HouseholdID, MemberIdx)>,
 }
 
 impl Offer {
@@ -39,10 +53,79 @@ impl Offer {
             users: CVec::new(),
             active_users: CVec::new(),
             is_internal,
+            is_private: false,
+            is_social: false,
+            tier: MarketTier::Retail,
+            min_education: 0.0,
             max_users: max_users as u32,
             being_withdrawn: false,
+            customers_per_staff: None,
+            waiting: CVec::new(),
+            turned_away: CVec::new(),
         }
     }
+
+    // Private offers are never registered with the market and can only be found
+    // by members of the offering household, through its private offer directory
+    pub fn private(
+        offering_member: MemberIdx,
+        opening_hours: TimeOfDayRange,
+        deal: Deal,
+        max_users: usize,
+    ) -> Offer {
+        Offer {
+            is_private: true,
+            ..Offer::new(offering_member, opening_hours, deal, max_users, true)
+        }
+    }
+
+    // Social offers are never registered with the market either, friends of the
+    // offering household find them through the friend lists they keep
+    pub fn social(
+        offering_member: MemberIdx,
+        opening_hours: TimeOfDayRange,
+        deal: Deal,
+        max_users: usize,
+    ) -> Offer {
+        Offer {
+            is_social: true,
+            ..Offer::private(offering_member, opening_hours, deal, max_users)
+        }
+    }
+
+    // Bulk offers meant for other businesses, invisible to consumers
+    pub fn wholesale(
+        offering_member: MemberIdx,
+        opening_hours: TimeOfDayRange,
+        deal: Deal,
+        max_users: usize,
+    ) -> Offer {
+        Offer {
+            tier: MarketTier::Wholesale,
+            ..Offer::new(offering_member, opening_hours, deal, max_users, false)
+        }
+    }
+
+    // Skilled jobs, only open to members with enough years of schooling
+    pub fn requiring_education(self, years: f32) -> Offer {
+        Offer {
+            min_education: years,
+            ..self
+        }
+    }
+
+    // Service offers that can only be used while somebody is working a shift
+    pub fn staffed(self, customers_per_staff: u32) -> Offer {
+        Offer {
+            customers_per_staff: Some(customers_per_staff),
+            ..self
+        }
+    }
+
+    // only job offers are listed on the market with money as their main resource
+    pub fn is_job(&self) -> bool {
+        !self.is_private && self.deal.main_given() == Resource::Money
+    }
 }
 
 //     // The offer stays alive until the withdrawal is confirmed
@@ -0,0 +1,176 @@
+use compact::{CVec, COption};
+use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration};
+use economy::resources::Resource;
+use economy::resources::Resource::*;
+use economy::market::EvaluatedDeal;
+use super::aging::LifeStage;
+
+// plans for the next day are made in the evening,
+// after work and before anyone goes to bed
+pub const PLANNING_TIME_OF_DAY: (usize, usize) = (20, 0);
+
+// Parts of the day a member plans for, in the order they get to claim time
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum Activity {
+    Sleep,
+    Work,
+    School,
+    Shopping,
+    Leisure,
+}
+
+impl Activity {
+    // resources that aren't planned for are dealt with whenever they come up
+    pub fn of(resource: Resource) -> Option<Activity> {
+        match resource {
+            Wakefulness => Some(Activity::Sleep),
+            Money => Some(Activity::Work),
+            Education => Some(Activity::School),
+            Groceries => Some(Activity::Shopping),
+            Entertainment => Some(Activity::Leisure),
+            _ => None,
+        }
+    }
+}
+
+pub fn sleep_window(stage: LifeStage) -> TimeOfDayRange {
+    match stage {
+        LifeStage::Child => TimeOfDayRange::new(19, 0, 7, 0),
+        LifeStage::Student => TimeOfDayRange::new(21, 30, 6, 30),
+        _ => TimeOfDayRange::new(23, 0, 6, 0),
+    }
+}
+
+// a whole day if it is that time right now
+pub fn until_next(target: TimeOfDay, now: TimeOfDay) -> Duration {
+    let until = now.until(target);
+    if until == Duration(0) {
+        Duration::from_hours(24)
+    } else {
+        until
+    }
+}
+
+#[derive(Compact, Clone, Debug, Serialize)]
+pub struct PlannedSlot {
+    pub activity: Activity,
+    pub time: TimeOfDayRange,
+    // the deal this slot was planned around, none for sleep
+    pub deal: COption<EvaluatedDeal>,
+    pub started: bool,
+}
+
+// A member's intended day, rebuilt every evening. Time is handed out
+// to fixed commitments first, errands get fitted around them
+#[derive(Compact, Clone, Debug, Serialize)]
+pub struct DayPlan {
+    pub slots: CVec<PlannedSlot>,
+}
+
+impl DayPlan {
+    pub fn new() -> DayPlan {
+        DayPlan { slots: CVec::new() }
+    }
+
+    pub fn with_sleep(window: TimeOfDayRange) -> DayPlan {
+        DayPlan {
+            slots: vec![PlannedSlot {
+                activity: Activity::Sleep,
+                time: window,
+                deal: COption(None),
+                started: false,
+            }]
+            .into(),
+        }
+    }
+
+    fn is_free(&self, time: TimeOfDayRange) -> bool {
+        self.slots.iter().all(|slot| !slot.time.overlaps(time))
+    }
+
+    // Claims the earliest free time in which the deal can be started
+    // and done with. Gives up if the deal is only offered while busy
+    pub fn try_fit(&mut self, activity: Activity, deal: &EvaluatedDeal) -> bool {
+        let mut candidate_starts = self
+            .slots
+            .iter()
+            .map(|slot| slot.time.end)
+            .chain(Some(deal.opening_hours.start))
+            .filter(|&start| deal.opening_hours.contains(start))
+            .collect::<Vec<_>>();
+        candidate_starts.sort_by_key(|&start| deal.opening_hours.start.until(start));
+
+        let maybe_time = candidate_starts
+            .into_iter()
+            .map(|start| TimeOfDayRange {
+                start,
+                end: start.later_by(deal.deal.duration),
+            })
+            .find(|&time| self.is_free(time));
+
+        if let Some(time) = maybe_time {
+            let insert_idx = self
+                .slots
+                .iter()
+                .position(|slot| slot.time.start.minutes_of_day() > time.start.minutes_of_day())
+                .unwrap_or(self.slots.len());
+            self.slots.insert(
+                insert_idx,
+                PlannedSlot {
+                    activity,
+                    time,
+                    deal: COption(Some(deal.clone())),
+                    started: false,
+                },
+            );
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn is_asleep(&self, time: TimeOfDay) -> bool {
+        self.slots
+            .iter()
+            .any(|slot| slot.activity == Activity::Sleep && slot.time.contains(time))
+    }
+
+    // The planned deal whose time has come, marked as started
+    // so it isn't taken up twice before the next plan is made
+    pub fn start_due(&mut self, time: TimeOfDay) -> Option<EvaluatedDeal> {
+        self.slots
+            .iter_mut()
+            .find(|slot| !slot.started && slot.deal.as_ref().is_some() && slot.time.contains(time))
+            .and_then(|slot| {
+                slot.started = true;
+                slot.deal.as_ref().cloned()
+            })
+    }
+
+    // whether something that comes up now is better left for its planned slot
+    pub fn defers(&self, resource: Resource) -> bool {
+        Activity::of(resource)
+            .map(|activity| {
+                self.slots.iter().any(|slot| {
+                    slot.activity == activity && !slot.started && slot.deal.as_ref().is_some()
+                })
+            })
+            .unwrap_or(false)
+    }
+
+    // Unplanned deals may not eat into upcoming planned slots,
+    // which includes staying out past bedtime
+    pub fn conflicts_with(&self, resource: Resource, time: TimeOfDay, duration: Duration) -> bool {
+        let activity = Activity::of(resource);
+        let time = TimeOfDayRange {
+            start: time,
+            end: time.later_by(duration),
+        };
+        self.slots.iter().any(|slot| {
+            Some(slot.activity) != activity
+                && !slot.started
+                && !slot.time.contains(time.start)
+                && slot.time.overlaps(time)
+        })
+    }
+}
@@ -0,0 +1,104 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for SocialGraph {
+    type ID = SocialGraphID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct SocialGraphID {
+    _raw_id: RawID
+}
+
+impl Copy for SocialGraphID {}
+impl Clone for SocialGraphID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for SocialGraphID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "SocialGraphID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for SocialGraphID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for SocialGraphID {
+    fn eq(&self, other: &SocialGraphID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for SocialGraphID {}
+
+impl TypedID for SocialGraphID {
+    type Target = SocialGraph;
+
+    fn from_raw(id: RawID) -> Self {
+        SocialGraphID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl SocialGraphID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = SocialGraphID::from_raw(world.allocate_instance_id::<SocialGraph>());
+        let swarm = world.local_broadcast::<SocialGraph>();
+        world.send(swarm, MSG_SocialGraph_spawn(id, ));
+        id
+    }
+    
+    pub fn met(self, a: HouseholdID, b: HouseholdID, world: &mut World) {
+        world.send(self.as_raw(), MSG_SocialGraph_met(a, b));
+    }
+    
+    pub fn forget(self, household: HouseholdID, world: &mut World) {
+        world.send(self.as_raw(), MSG_SocialGraph_forget(household));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SocialGraph_spawn(pub SocialGraphID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SocialGraph_met(pub HouseholdID, pub HouseholdID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SocialGraph_forget(pub HouseholdID);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    
+    system.add_spawner::<SocialGraph, _, _>(
+        |&MSG_SocialGraph_spawn(id, ), world| {
+            SocialGraph::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<SocialGraph, _, _>(
+        |&MSG_SocialGraph_met(a, b), instance, world| {
+            instance.met(a, b, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<SocialGraph, _, _>(
+        |&MSG_SocialGraph_forget(household), instance, world| {
+            instance.forget(household, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
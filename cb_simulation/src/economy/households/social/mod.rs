@@ -0,0 +1,95 @@
+use kay::{ActorSystem, World};
+use compact::{CVec, CHashMap};
+use cb_util::random::{seed, Rng};
+use economy::resources::Resource;
+use super::HouseholdID;
+
+// how likely two households that spent leisure time at the same place become friends
+const FRIENDSHIP_CHANCE_PER_MEETING: f32 = 0.2;
+const MAX_FRIENDS: usize = 5;
+
+// Social visits are what friends are for, visiting them only ever helps with these
+pub fn visits_provide(resource: Resource) -> bool {
+    resource == Resource::Companionship
+}
+
+// Who is friends with whom, always symmetric. Households keep a copy of
+// their own friends, this is where friendships are made and forgotten
+#[derive(Compact, Clone)]
+pub struct SocialGraph {
+    id: SocialGraphID,
+    friends: CHashMap<HouseholdID, CVec<HouseholdID>>,
+    n_meetings: u32,
+}
+
+impl SocialGraph {
+    pub fn spawn(id: SocialGraphID, _: &mut World) -> SocialGraph {
+        SocialGraph {
+            id,
+            friends: CHashMap::new(),
+            n_meetings: 0,
+        }
+    }
+
+    fn n_friends(&self, household: HouseholdID) -> usize {
+        self.friends
+            .get(household)
+            .map(|friends| friends.len())
+            .unwrap_or(0)
+    }
+
+    fn are_friends(&self, a: HouseholdID, b: HouseholdID) -> bool {
+        self.friends
+            .get(a)
+            .map(|friends| friends.contains(&b))
+            .unwrap_or(false)
+    }
+
+    pub fn met(&mut self, a: HouseholdID, b: HouseholdID, world: &mut World) {
+        self.n_meetings += 1;
+
+        if a == b
+            || self.are_friends(a, b)
+            || self.n_friends(a) >= MAX_FRIENDS
+            || self.n_friends(b) >= MAX_FRIENDS
+            || seed((self.id, self.n_meetings)).gen::<f32>() > FRIENDSHIP_CHANCE_PER_MEETING
+        {
+            return;
+        }
+
+        for &(household, friend) in &[(a, b), (b, a)] {
+            let mut friends = self
+                .friends
+                .get(household)
+                .cloned()
+                .unwrap_or_else(CVec::new);
+            friends.push(friend);
+            self.friends.insert(household, friends);
+            household.befriended(friend, world);
+        }
+    }
+
+    // Called when a household leaves the city or dissolves
+    pub fn forget(&mut self, household: HouseholdID, world: &mut World) {
+        if let Some(friends) = self.friends.remove(household) {
+            for &friend in friends.iter() {
+                if let Some(friends_of_friend) = self.friends.get_mut(friend) {
+                    friends_of_friend.retain(|&other| other != household);
+                }
+                friend.unfriended(household, world);
+            }
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<SocialGraph>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    SocialGraphID::spawn(world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -78,9 +78,9 @@ struct MSG_TaskEndScheduler_schedule(pub Instant, pub HouseholdID, pub MemberIdx
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_TaskEndScheduler_deschedule(pub HouseholdID, pub MemberIdx);
 
-impl Into<TemporalID> for TaskEndSchedulerID {
-    fn into(self) -> TemporalID {
-        TemporalID::from_raw(self.as_raw())
+impl Into<SleeperID> for TaskEndSchedulerID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
     }
 }
 
@@ -88,7 +88,7 @@ impl Into<TemporalID> for TaskEndSchedulerID {
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
-    TemporalID::register_implementor::<TaskEndScheduler>(system);
+    SleeperID::register_implementor::<TaskEndScheduler>(system);
     system.add_spawner::<TaskEndScheduler, _, _>(
         |&MSG_TaskEndScheduler_spawn(id, ), world| {
             TaskEndScheduler::spawn(id, world)
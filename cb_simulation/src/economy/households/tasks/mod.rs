@@ -1,9 +1,9 @@
 use kay::{ActorSystem, World};
 use compact::CVec;
-use cb_time::actors::{Temporal, TemporalID};
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
 use cb_time::units::{Instant, Duration};
 use transport::pathfinding::RoughLocationID;
-use transport::pathfinding::trip::TripID;
+use transport::pathfinding::trip::{TripID, TravelMode};
 use super::super::resources::Resource;
 use super::OfferID;
 
@@ -22,6 +22,10 @@ pub struct Task {
     pub goal: Option<(Resource, OfferID)>,
     pub duration: Duration,
     pub state: TaskState,
+    pub departure: Instant,
+    pub mode: TravelMode,
+    // how long getting there was expected to take
+    pub travel_time: Duration,
 }
 
 impl Task {
@@ -30,6 +34,9 @@ impl Task {
             goal: None,
             duration: Duration(0),
             state: TaskState::IdleAt(location),
+            departure: Instant::new(0),
+            mode: TravelMode::Walking,
+            travel_time: Duration(0),
         }
     }
 }
@@ -53,8 +60,10 @@ impl TaskEndScheduler {
         end: Instant,
         household: HouseholdID,
         member: MemberIdx,
-        _: &mut World,
+        world: &mut World,
     ) {
+        // ends that were descheduled in the meantime still wake us up, but find nothing due
+        TimeID::local_first(world).wake_up_at(end, self.id.into(), world);
         let maybe_idx = self
             .task_ends
             .binary_search_by_key(&(-end.iticks()), |&(e, ..)| -(e.iticks()));
@@ -71,8 +80,8 @@ impl TaskEndScheduler {
     }
 }
 
-impl Temporal for TaskEndScheduler {
-    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+impl Sleeper for TaskEndScheduler {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
         while self
             .task_ends
             .last()
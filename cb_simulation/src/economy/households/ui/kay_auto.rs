@@ -53,10 +53,25 @@ impl HouseholdUIID {
     pub fn on_household_ui_info(self, id: HouseholdID, core: HouseholdCore, world: &mut World) {
         world.send(self.as_raw(), MSG_HouseholdUI_on_household_ui_info(id, core));
     }
+    
+    pub fn on_household_activity_log(self, id: HouseholdID, member_activities: CVec < CVec < ActivityLogEntry > >, world: &mut World) {
+        world.send(self.as_raw(), MSG_HouseholdUI_on_household_activity_log(id, member_activities));
+    }
+    
+    pub fn on_vignette_candidate(self, id: HouseholdID, world: &mut World) {
+        world.send(self.as_raw(), MSG_HouseholdUI_on_vignette_candidate(id));
+    }
+    
+    pub fn on_member_vignette(self, id: HouseholdID, member: MemberIdx, title: CString, text: CString, world: &mut World) {
+        world.send(self.as_raw(), MSG_HouseholdUI_on_member_vignette(id, member, title, text));
+    }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<HouseholdUIRepresentative>();
         system.register_trait_message::<MSG_HouseholdUI_on_household_ui_info>();
+        system.register_trait_message::<MSG_HouseholdUI_on_household_activity_log>();
+        system.register_trait_message::<MSG_HouseholdUI_on_vignette_candidate>();
+        system.register_trait_message::<MSG_HouseholdUI_on_member_vignette>();
     }
 
     pub fn register_implementor<Act: Actor + HouseholdUI>(system: &mut ActorSystem) {
@@ -66,11 +81,35 @@ impl HouseholdUIID {
                 instance.on_household_ui_info(id, core, world); Fate::Live
             }, false
         );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_HouseholdUI_on_household_activity_log(id, ref member_activities), instance, world| {
+                instance.on_household_activity_log(id, member_activities, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_HouseholdUI_on_vignette_candidate(id), instance, world| {
+                instance.on_vignette_candidate(id, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_HouseholdUI_on_member_vignette(id, member, ref title, ref text), instance, world| {
+                instance.on_member_vignette(id, member, title, text, world); Fate::Live
+            }, false
+        );
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_HouseholdUI_on_household_ui_info(pub HouseholdID, pub HouseholdCore);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HouseholdUI_on_household_activity_log(pub HouseholdID, pub CVec < CVec < ActivityLogEntry > >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HouseholdUI_on_vignette_candidate(pub HouseholdID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HouseholdUI_on_member_vignette(pub HouseholdID, pub MemberIdx, pub CString, pub CString);
 
 
 
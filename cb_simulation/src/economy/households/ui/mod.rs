@@ -1,8 +1,28 @@
 use kay::World;
-use super::{HouseholdID, HouseholdCore};
+use compact::{CVec, CString};
+use super::{HouseholdID, HouseholdCore, MemberIdx};
+use super::activity_log::ActivityLogEntry;
 
 pub trait HouseholdUI {
     fn on_household_ui_info(&mut self, id: HouseholdID, core: &HouseholdCore, _world: &mut World);
+
+    // one log per member, by member index
+    fn on_household_activity_log(
+        &mut self,
+        id: HouseholdID,
+        member_activities: &CVec<CVec<ActivityLogEntry>>,
+        _world: &mut World,
+    );
+
+    fn on_vignette_candidate(&mut self, id: HouseholdID, _world: &mut World);
+    fn on_member_vignette(
+        &mut self,
+        id: HouseholdID,
+        member: MemberIdx,
+        title: &CString,
+        text: &CString,
+        _world: &mut World,
+    );
 }
 
 mod kay_auto;
@@ -0,0 +1,153 @@
+// "Humans of the city": a few sentences about the day of a single member,
+// put together from what the simulation actually knows about them
+use economy::resources::Resource;
+use super::{HouseholdCore, MemberIdx};
+use super::aging::{LifeStage, DAYS_PER_YEAR};
+use super::activity_log::LoggedActivity;
+use super::needs::ALL_NEEDS;
+
+// how many of the latest things a member got are mentioned
+const N_MENTIONED_PURCHASES: usize = 3;
+
+fn resource_phrase(resource: Resource) -> String {
+    match resource {
+        Resource::Wakefulness => "some sleep".to_owned(),
+        Resource::Satiety => "something to eat".to_owned(),
+        Resource::Companionship => "time with friends".to_owned(),
+        Resource::Entertainment => "a night out".to_owned(),
+        Resource::Education => "school".to_owned(),
+        Resource::Healthcare => "a visit to the doctor".to_owned(),
+        Resource::Money => "work".to_owned(),
+        Resource::Car => "a car".to_owned(),
+        Resource::Bicycle => "a bike".to_owned(),
+        Resource::BakedGoods => "baked goods".to_owned(),
+        Resource::DairyGoods => "dairy goods".to_owned(),
+        other => other.to_string().to_lowercase(),
+    }
+}
+
+fn list(phrases: &[String]) -> String {
+    match phrases.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        Some((last, _)) => last.clone(),
+        None => String::new(),
+    }
+}
+
+pub fn title(member_name: &str, core: &HouseholdCore, member: MemberIdx) -> String {
+    let years = core.member_lives[member.as_idx()].age_in_days / DAYS_PER_YEAR;
+    format!("{}, {}", member_name, years)
+}
+
+pub fn compose(
+    household_name: &str,
+    member_name: &str,
+    homeless: bool,
+    core: &HouseholdCore,
+    member: MemberIdx,
+) -> String {
+    let idx = member.as_idx();
+    let name = member_name.split(' ').next().unwrap_or(member_name);
+    let mut sentences = Vec::new();
+
+    let n_others = core.n_present_members().saturating_sub(1);
+    sentences.push(if homeless {
+        format!("{} and their household have no home right now.", name)
+    } else if n_others == 0 {
+        format!("{} lives alone.", name)
+    } else {
+        format!(
+            "{} lives with {} other{} as part of {}.",
+            name,
+            n_others,
+            if n_others == 1 { "" } else { "s" },
+            household_name
+        )
+    });
+
+    let has_job = core.member_used_offers[idx].get(Resource::Money).is_some();
+    sentences.push(match (core.member_lives[idx].stage, has_job) {
+        (LifeStage::Child, _) => "They are too young for school yet.".to_owned(),
+        (LifeStage::Student, _) => "They go to school.".to_owned(),
+        (LifeStage::Retiree, _) => "They are retired.".to_owned(),
+        (_, true) => match core.average_commute_minutes {
+            Some(minutes) => format!(
+                "They have a job, getting there takes about {:.0} minutes.",
+                minutes
+            ),
+            None => "They have a job.".to_owned(),
+        },
+        (_, false) => "They are looking for work.".to_owned(),
+    });
+
+    let activities = &core.member_activities[idx];
+    let mut purchases: Vec<String> = Vec::new();
+    for entry in activities.iter().rev() {
+        if let LoggedActivity::Chose(resource, _) = entry.activity {
+            let phrase = resource_phrase(resource);
+            if resource != Resource::Money && !purchases.contains(&phrase) {
+                purchases.push(phrase);
+            }
+        }
+        if purchases.len() >= N_MENTIONED_PURCHASES {
+            break;
+        }
+    }
+    if !purchases.is_empty() {
+        sentences.push(format!("Lately they went for {}.", list(&purchases)));
+    }
+
+    let latest_setback = activities
+        .iter()
+        .rev()
+        .filter_map(|entry| match entry.activity {
+            LoggedActivity::TripFailed(resource) => Some(format!(
+                "Earlier, they couldn't get through to {}.",
+                resource_phrase(resource)
+            )),
+            LoggedActivity::TurnedAway(resource) => Some(format!(
+                "Earlier, they were turned away when they wanted {}.",
+                resource_phrase(resource)
+            )),
+            _ => None,
+        })
+        .next();
+    if let Some(setback) = latest_setback {
+        sentences.push(setback);
+    }
+
+    if core.member_health[idx].is_sick() {
+        sentences.push("They are sick and hope to get better soon.".to_owned());
+    }
+
+    sentences.push(
+        if core.happiness > 0.7 {
+            "All in all, they are in a good mood."
+        } else if core.happiness > 0.4 {
+            "All in all, they are doing alright."
+        } else {
+            "All in all, they are having a rough time."
+        }
+        .to_owned(),
+    );
+
+    let most_lacking = ALL_NEEDS
+        .iter()
+        .map(|need| {
+            let amount = core.member_resources[idx]
+                .get(need.resource())
+                .cloned()
+                .unwrap_or(amount!(0.0));
+            (need.resource(), amount)
+        })
+        .filter(|&(_, amount)| amount < 0.0)
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    if let Some((resource, _)) = most_lacking {
+        sentences.push(format!(
+            "What they'd like most right now is {}.",
+            resource_phrase(resource)
+        ));
+    }
+
+    sentences.join(" ")
+}
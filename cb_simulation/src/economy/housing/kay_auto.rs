@@ -0,0 +1,236 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct HousingSeekerID {
+    _raw_id: RawID
+}
+
+impl Copy for HousingSeekerID {}
+impl Clone for HousingSeekerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for HousingSeekerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "HousingSeekerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for HousingSeekerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for HousingSeekerID {
+    fn eq(&self, other: &HousingSeekerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for HousingSeekerID {}
+
+pub struct HousingSeekerRepresentative;
+
+impl ActorOrActorTrait for HousingSeekerRepresentative {
+    type ID = HousingSeekerID;
+}
+
+impl TypedID for HousingSeekerID {
+    type Target = HousingSeekerRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        HousingSeekerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + HousingSeeker> TraitIDFrom<Act> for HousingSeekerID {}
+
+impl HousingSeekerID {
+    pub fn on_dwelling_offer(self, maybe_dwelling: Option < Dwelling >, world: &mut World) {
+        world.send(self.as_raw(), MSG_HousingSeeker_on_dwelling_offer(maybe_dwelling));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<HousingSeekerRepresentative>();
+        system.register_trait_message::<MSG_HousingSeeker_on_dwelling_offer>();
+    }
+
+    pub fn register_implementor<Act: Actor + HousingSeeker>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, HousingSeekerRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_HousingSeeker_on_dwelling_offer(maybe_dwelling), instance, world| {
+                instance.on_dwelling_offer(maybe_dwelling, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HousingSeeker_on_dwelling_offer(pub Option < Dwelling >);
+
+impl Actor for HousingMarket {
+    type ID = HousingMarketID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct HousingMarketID {
+    _raw_id: RawID
+}
+
+impl Copy for HousingMarketID {}
+impl Clone for HousingMarketID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for HousingMarketID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "HousingMarketID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for HousingMarketID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for HousingMarketID {
+    fn eq(&self, other: &HousingMarketID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for HousingMarketID {}
+
+impl TypedID for HousingMarketID {
+    type Target = HousingMarket;
+
+    fn from_raw(id: RawID) -> Self {
+        HousingMarketID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl HousingMarketID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = HousingMarketID::from_raw(world.allocate_instance_id::<HousingMarket>());
+        let swarm = world.local_broadcast::<HousingMarket>();
+        world.send(swarm, MSG_HousingMarket_spawn(id, time));
+        id
+    }
+    
+    pub fn list(self, dwelling: Dwelling, world: &mut World) {
+        world.send(self.as_raw(), MSG_HousingMarket_list(dwelling));
+    }
+    
+    pub fn delist_building(self, building: BuildingID, world: &mut World) {
+        world.send(self.as_raw(), MSG_HousingMarket_delist_building(building));
+    }
+    
+    pub fn search(self, request: DwellingRequest, seeker: HousingSeekerID, world: &mut World) {
+        world.send(self.as_raw(), MSG_HousingMarket_search(request, seeker));
+    }
+    
+    pub fn sign_lease(self, household: HouseholdID, dwelling: Dwelling, world: &mut World) {
+        world.send(self.as_raw(), MSG_HousingMarket_sign_lease(household, dwelling));
+    }
+    
+    pub fn end_lease(self, household: HouseholdID, world: &mut World) {
+        world.send(self.as_raw(), MSG_HousingMarket_end_lease(household));
+    }
+    
+    pub fn rent_received(self, household: HouseholdID, paid: ResourceAmount, due: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_HousingMarket_rent_received(household, paid, due));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HousingMarket_spawn(pub HousingMarketID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HousingMarket_list(pub Dwelling);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HousingMarket_delist_building(pub BuildingID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HousingMarket_search(pub DwellingRequest, pub HousingSeekerID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HousingMarket_sign_lease(pub HouseholdID, pub Dwelling);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HousingMarket_end_lease(pub HouseholdID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HousingMarket_rent_received(pub HouseholdID, pub ResourceAmount, pub ResourceAmount);
+
+impl Into<PositionRequesterID> for HousingMarketID {
+    fn into(self) -> PositionRequesterID {
+        PositionRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<DistrictListenerID> for HousingMarketID {
+    fn into(self) -> DistrictListenerID {
+        DistrictListenerID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for HousingMarketID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    HousingSeekerID::register_trait(system);
+    PositionRequesterID::register_implementor::<HousingMarket>(system);
+    DistrictListenerID::register_implementor::<HousingMarket>(system);
+    SleeperID::register_implementor::<HousingMarket>(system);
+    system.add_spawner::<HousingMarket, _, _>(
+        |&MSG_HousingMarket_spawn(id, time), world| {
+            HousingMarket::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<HousingMarket, _, _>(
+        |&MSG_HousingMarket_list(dwelling), instance, world| {
+            instance.list(dwelling, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<HousingMarket, _, _>(
+        |&MSG_HousingMarket_delist_building(building), instance, world| {
+            instance.delist_building(building, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<HousingMarket, _, _>(
+        |&MSG_HousingMarket_search(request, seeker), instance, world| {
+            instance.search(request, seeker, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<HousingMarket, _, _>(
+        |&MSG_HousingMarket_sign_lease(household, dwelling), instance, world| {
+            instance.sign_lease(household, dwelling, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<HousingMarket, _, _>(
+        |&MSG_HousingMarket_end_lease(household), instance, world| {
+            instance.end_lease(household, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<HousingMarket, _, _>(
+        |&MSG_HousingMarket_rent_received(household, paid, due), instance, world| {
+            instance.rent_received(household, paid, due, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,306 @@
+use kay::{ActorSystem, World, Actor, TypedID};
+use compact::{CVec, CHashMap};
+use descartes::P2;
+use ordered_float::OrderedFloat;
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Duration};
+use cb_util::log::{info, warn};
+use land_use::buildings::{BuildingID, BuildingStyle, UnitIdx};
+use land_use::districts::{DistrictArea, DistrictListener, DistrictListenerID, DistrictRegistryID,
+district_at};
+use transport::pathfinding::{RoughLocationID, PositionRequester, PositionRequesterID};
+use transport::pathfinding::trip::TravelMode;
+use super::resources::{ResourceAmount, amount_to_f32};
+use super::households::HouseholdID;
+use super::policies::TreasuryID;
+const LOG_T: &str = "Housing";
+
+// how often households looking for a home are matched with vacant dwellings
+const MATCHING_INTERVAL: Duration = Duration(10 * 60);
+const RENT_INTERVAL: Duration = Duration(24 * 60 * 60);
+// tenants that missed paying this many rents more than they paid get evicted
+const MISSED_RENTS_BEFORE_EVICTION: u32 = 3;
+// daily rent for each member a dwelling has room for
+const RENT_PER_MEMBER: ResourceAmount = amount!(8.0);
+// dwellings next to neighboring towns are makeshift places to arrive at
+const GATEWAY_RENT_FACTOR: f32 = 0.6;
+// how much more or less rent each unit of land value around a dwelling makes,
+// within bounds so neither the best nor the worst places get absurd
+const RENT_FACTOR_PER_LAND_VALUE: f32 = 0.05;
+const MIN_LAND_VALUE_RENT_FACTOR: f32 = 0.5;
+const MAX_LAND_VALUE_RENT_FACTOR: f32 = 2.0;
+// what households are willing to pay for each member, on top of a share of what they earn
+const BASE_RENT_BUDGET_PER_MEMBER: ResourceAmount = amount!(10.0);
+const RENT_SHARE_OF_WAGES: f32 = 0.3;
+// what a minute of commuting, there and back every day, is worth to a household
+const COMMUTE_COST_PER_MINUTE: ResourceAmount = amount!(0.5);
+
+// A vacant dwelling as listed by its building, rented out by the day
+#[derive(Copy, Clone)]
+pub struct Dwelling {
+    pub building: BuildingID,
+    pub unit: UnitIdx,
+    // how many members a household living here can have at most
+    pub capacity: u32,
+    pub rent: ResourceAmount,
+    pub position: P2,
+}
+
+impl Dwelling {
+    pub fn new(
+        building: BuildingID,
+        unit: UnitIdx,
+        capacity: u32,
+        style: BuildingStyle,
+        land_value: f32,
+        position: P2,
+    ) -> Dwelling {
+        let rent_factor = if style == BuildingStyle::NeighboringTownConnection {
+            GATEWAY_RENT_FACTOR
+        } else {
+            (1.0 + RENT_FACTOR_PER_LAND_VALUE * land_value)
+                .max(MIN_LAND_VALUE_RENT_FACTOR)
+                .min(MAX_LAND_VALUE_RENT_FACTOR)
+        };
+
+        Dwelling {
+            building,
+            unit,
+            capacity,
+            rent: capacity as f32 * RENT_PER_MEMBER * rent_factor,
+            position,
+        }
+    }
+
+    fn is(&self, other: &Dwelling) -> bool {
+        self.building == other.building && self.unit == other.unit
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Lease {
+    pub dwelling: Dwelling,
+    pub missed_rents: u32,
+}
+
+pub fn rent_budget(n_members: u32, daily_wages: ResourceAmount) -> ResourceAmount {
+    n_members as f32 * BASE_RENT_BUDGET_PER_MEMBER + RENT_SHARE_OF_WAGES * daily_wages
+}
+
+#[derive(Copy, Clone)]
+pub struct DwellingRequest {
+    pub n_members: u32,
+    pub max_rent: ResourceAmount,
+    // where the household's members work, if anywhere, to keep commutes short
+    pub workplace: Option<RoughLocationID>,
+}
+
+// Anyone looking for a home for a household. Every search is answered
+// exactly once, with a dwelling that is reserved for the seeker, or with none.
+// A seeker that doesn't want the dwelling anymore has to list it again
+pub trait HousingSeeker {
+    fn on_dwelling_offer(&mut self, maybe_dwelling: Option<Dwelling>, world: &mut World);
+}
+
+// Moves a household into a dwelling it got offered and starts its lease,
+// replacing any lease it had before
+pub fn move_in(household: HouseholdID, dwelling: Dwelling, world: &mut World) {
+    dwelling
+        .building
+        .add_household(household, dwelling.unit, world);
+    HousingMarketID::global_first(world).sign_lease(household, dwelling, world);
+}
+
+// Dwellings are rented out on a market of their own: buildings list their
+// vacant dwellings, households looking for a home get the one that fits them
+// with the lowest rent and commute. Rents are collected from every lease daily,
+// acting as the landlord of all dwellings, who evicts tenants that don't pay.
+// Districts add their tax to the rents, which is passed on to the treasury
+#[derive(Compact, Clone)]
+pub struct HousingMarket {
+    id: HousingMarketID,
+    vacant: CVec<Dwelling>,
+    leases: CHashMap<HouseholdID, Lease>,
+    // answered at the next matching round, in the order they came in
+    pending: CVec<(DwellingRequest, HousingSeekerID)>,
+    workplace_positions: CHashMap<RoughLocationID, P2>,
+    last_rent_collection: Instant,
+    districts: CVec<DistrictArea>,
+}
+
+impl HousingMarket {
+    pub fn spawn(id: HousingMarketID, time: TimeID, world: &mut World) -> HousingMarket {
+        time.wake_up_every(MATCHING_INTERVAL, id.into(), world);
+        DistrictRegistryID::global_first(world).get_districts(id.into(), world);
+
+        HousingMarket {
+            id,
+            vacant: CVec::new(),
+            leases: CHashMap::new(),
+            pending: CVec::new(),
+            workplace_positions: CHashMap::new(),
+            last_rent_collection: Instant::new(0),
+            districts: CVec::new(),
+        }
+    }
+
+    pub fn list(&mut self, dwelling: Dwelling, _: &mut World) {
+        self.vacant.retain(|vacant| !vacant.is(&dwelling));
+        self.vacant.push(dwelling);
+    }
+
+    pub fn delist_building(&mut self, building: BuildingID, _: &mut World) {
+        self.vacant.retain(|vacant| vacant.building != building);
+    }
+
+    pub fn search(&mut self, request: DwellingRequest, seeker: HousingSeekerID, world: &mut World) {
+        if let Some(workplace) = request.workplace {
+            if self.workplace_positions.get(workplace).is_none() {
+                workplace.resolve_as_position(self.id_as(), workplace, world);
+            }
+        }
+        self.pending.push((request, seeker));
+    }
+
+    pub fn sign_lease(&mut self, household: HouseholdID, dwelling: Dwelling, _: &mut World) {
+        self.leases.insert(
+            household,
+            Lease {
+                dwelling,
+                missed_rents: 0,
+            },
+        );
+    }
+
+    pub fn end_lease(&mut self, household: HouseholdID, _: &mut World) {
+        self.leases.remove(household);
+    }
+
+    pub fn rent_received(
+        &mut self,
+        household: HouseholdID,
+        paid: ResourceAmount,
+        due: ResourceAmount,
+        world: &mut World,
+    ) {
+        if let Some(lease) = self.leases.get(household) {
+            let tax_modifier = self.tax_modifier_at(lease.dwelling.position);
+            if tax_modifier != 0.0 {
+                let tax = paid * tax_modifier / (1.0 + tax_modifier);
+                TreasuryID::global_first(world).deposit(tax, world);
+            }
+        }
+
+        let evict = if let Some(lease) = self.leases.get_mut(household) {
+            if paid < due {
+                lease.missed_rents += 1;
+            } else if lease.missed_rents > 0 {
+                lease.missed_rents -= 1;
+            }
+            lease.missed_rents >= MISSED_RENTS_BEFORE_EVICTION
+        } else {
+            // moved out in the meantime
+            return;
+        };
+
+        if paid < due {
+            info(
+                LOG_T,
+                format!("{:?} could only pay {} of {} rent", household, paid, due),
+                self.id,
+                world,
+            );
+        }
+
+        if evict {
+            warn(
+                LOG_T,
+                format!("Evicting {:?} after repeatedly missed rents", household),
+                self.id,
+                world,
+            );
+            self.leases.remove(household);
+            household.evicted(self.last_rent_collection, world);
+        }
+    }
+
+    // Rent plus what commuting from there would be worth, both per day.
+    // Workplaces that couldn't be located (yet) don't count
+    fn cost(&self, dwelling: &Dwelling, request: &DwellingRequest) -> ResourceAmount {
+        let commute_minutes = request
+            .workplace
+            .and_then(|workplace| self.workplace_positions.get(workplace))
+            .map(|&workplace_position| {
+                (dwelling.position - workplace_position).norm()
+                    / TravelMode::Car.assumed_avg_speed()
+                    / 60.0
+            })
+            .unwrap_or(0.0);
+
+        self.taxed_rent(dwelling) + 2.0 * commute_minutes * COMMUTE_COST_PER_MINUTE
+    }
+
+    fn tax_modifier_at(&self, position: P2) -> f32 {
+        district_at(&self.districts, position)
+            .map_or(0.0, |district| district.policies.tax_modifier)
+    }
+
+    // what tenants pay, including the tax of the district
+    fn taxed_rent(&self, dwelling: &Dwelling) -> ResourceAmount {
+        dwelling.rent * (1.0 + self.tax_modifier_at(dwelling.position))
+    }
+
+    fn best_match(&self, request: &DwellingRequest) -> Option<usize> {
+        self.vacant
+            .iter()
+            .enumerate()
+            .filter(|&(_, dwelling)| {
+                dwelling.capacity >= request.n_members
+                    && self.taxed_rent(dwelling) <= request.max_rent
+            })
+            .min_by_key(|&(_, dwelling)| OrderedFloat(amount_to_f32(self.cost(dwelling, request))))
+            .map(|(idx, _)| idx)
+    }
+}
+
+impl PositionRequester for HousingMarket {
+    fn position_resolved(&mut self, rough_location: RoughLocationID, position: P2, _: &mut World) {
+        self.workplace_positions.insert(rough_location, position);
+    }
+}
+
+impl DistrictListener for HousingMarket {
+    fn on_districts_changed(&mut self, districts: &CVec<DistrictArea>, _: &mut World) {
+        self.districts = districts.clone();
+    }
+}
+
+impl Sleeper for HousingMarket {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        let pending = ::std::mem::replace(&mut self.pending, CVec::new());
+
+        for &(ref request, seeker) in pending.iter() {
+            let maybe_dwelling = self.best_match(request).map(|idx| self.vacant.remove(idx));
+            seeker.on_dwelling_offer(maybe_dwelling, world);
+        }
+
+        if current_instant >= self.last_rent_collection + RENT_INTERVAL {
+            self.last_rent_collection = current_instant;
+            for (&household, lease) in self.leases.pairs() {
+                household.collect_rent(self.taxed_rent(&lease.dwelling), self.id, world);
+            }
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<HousingMarket>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    HousingMarketID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -5,7 +5,72 @@ use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTra
 #[allow(unused_imports)]
 use super::*;
 
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ZoneDemandUIID {
+    _raw_id: RawID
+}
+
+impl Copy for ZoneDemandUIID {}
+impl Clone for ZoneDemandUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ZoneDemandUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ZoneDemandUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ZoneDemandUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ZoneDemandUIID {
+    fn eq(&self, other: &ZoneDemandUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ZoneDemandUIID {}
+
+pub struct ZoneDemandUIRepresentative;
 
+impl ActorOrActorTrait for ZoneDemandUIRepresentative {
+    type ID = ZoneDemandUIID;
+}
+
+impl TypedID for ZoneDemandUIID {
+    type Target = ZoneDemandUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        ZoneDemandUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + ZoneDemandUI> TraitIDFrom<Act> for ZoneDemandUIID {}
+
+impl ZoneDemandUIID {
+    pub fn on_zone_demand(self, demand: ZoneDemand, world: &mut World) {
+        world.send(self.as_raw(), MSG_ZoneDemandUI_on_zone_demand(demand));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<ZoneDemandUIRepresentative>();
+        system.register_trait_message::<MSG_ZoneDemandUI_on_zone_demand>();
+    }
+
+    pub fn register_implementor<Act: Actor + ZoneDemandUI>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, ZoneDemandUIRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_ZoneDemandUI_on_zone_demand(demand), instance, world| {
+                instance.on_zone_demand(demand, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ZoneDemandUI_on_zone_demand(pub ZoneDemand);
 
 impl Actor for ImmigrationManager {
     type ID = ImmigrationManagerID;
@@ -62,21 +127,39 @@ impl ImmigrationManagerID {
         id
     }
     
-    pub fn on_unit_offer(self, building_id: BuildingID, unit_idx: UnitIdx, world: &mut World) {
-        world.send(self.as_raw(), MSG_ImmigrationManager_on_unit_offer(building_id, unit_idx));
+    pub fn update_migration(self, family_share: f32, gateways: CVec < RoughLocationID >, world: &mut World) {
+        world.send(self.as_raw(), MSG_ImmigrationManager_update_migration(family_share, gateways));
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_ImmigrationManager_spawn(pub ImmigrationManagerID, pub TimeID, pub DevelopmentManagerID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_ImmigrationManager_on_unit_offer(pub BuildingID, pub UnitIdx);
+struct MSG_ImmigrationManager_update_migration(pub f32, pub CVec < RoughLocationID >);
 
 impl Into<SleeperID> for ImmigrationManagerID {
     fn into(self) -> SleeperID {
         SleeperID::from_raw(self.as_raw())
     }
 }
+
+impl Into<HousingSeekerID> for ImmigrationManagerID {
+    fn into(self) -> HousingSeekerID {
+        HousingSeekerID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<ConfigUserID<HouseholdArchetype>> for ImmigrationManagerID {
+    fn into(self) -> ConfigUserID<HouseholdArchetype> {
+        ConfigUserID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<ConfigUserID<DifficultyProfile>> for ImmigrationManagerID {
+    fn into(self) -> ConfigUserID<DifficultyProfile> {
+        ConfigUserID::from_raw(self.as_raw())
+    }
+}
 impl Actor for DevelopmentManager {
     type ID = DevelopmentManagerID;
 
@@ -132,6 +215,14 @@ impl DevelopmentManagerID {
         id
     }
     
+    pub fn update_demand(self, demand: ZoneDemand, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_DevelopmentManager_update_demand(demand, instant));
+    }
+    
+    pub fn get_zone_demand(self, ui: ZoneDemandUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_DevelopmentManager_get_zone_demand(ui));
+    }
+    
     pub fn try_develop(self, building_style: BuildingStyle, world: &mut World) {
         world.send(self.as_raw(), MSG_DevelopmentManager_try_develop(building_style));
     }
@@ -144,6 +235,10 @@ impl DevelopmentManagerID {
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_DevelopmentManager_spawn(pub DevelopmentManagerID, pub TimeID, pub CBPlanManagerID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_DevelopmentManager_update_demand(pub ZoneDemand, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_DevelopmentManager_get_zone_demand(pub ZoneDemandUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_DevelopmentManager_try_develop(pub BuildingStyle);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_DevelopmentManager_on_suggested_lot(pub BuildingIntent, pub PrototypeID);
@@ -157,8 +252,11 @@ impl Into<SleeperID> for DevelopmentManagerID {
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
-    
+    ZoneDemandUIID::register_trait(system);
     SleeperID::register_implementor::<ImmigrationManager>(system);
+    HousingSeekerID::register_implementor::<ImmigrationManager>(system);
+    ConfigUserID::<HouseholdArchetype>::register_implementor::<ImmigrationManager>(system);
+    ConfigUserID::<DifficultyProfile>::register_implementor::<ImmigrationManager>(system);
     system.add_spawner::<ImmigrationManager, _, _>(
         |&MSG_ImmigrationManager_spawn(id, time, development_manager), world| {
             ImmigrationManager::spawn(id, time, development_manager, world)
@@ -166,8 +264,8 @@ pub fn auto_setup(system: &mut ActorSystem) {
     );
     
     system.add_handler::<ImmigrationManager, _, _>(
-        |&MSG_ImmigrationManager_on_unit_offer(building_id, unit_idx), instance, world| {
-            instance.on_unit_offer(building_id, unit_idx, world); Fate::Live
+        |&MSG_ImmigrationManager_update_migration(family_share, ref gateways), instance, world| {
+            instance.update_migration(family_share, gateways, world); Fate::Live
         }, false
     );
     SleeperID::register_implementor::<DevelopmentManager>(system);
@@ -177,6 +275,18 @@ pub fn auto_setup(system: &mut ActorSystem) {
         }, false
     );
     
+    system.add_handler::<DevelopmentManager, _, _>(
+        |&MSG_DevelopmentManager_update_demand(demand, instant), instance, world| {
+            instance.update_demand(demand, instant, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<DevelopmentManager, _, _>(
+        |&MSG_DevelopmentManager_get_zone_demand(ui), instance, world| {
+            instance.get_zone_demand(ui, world); Fate::Live
+        }, false
+    );
+    
     system.add_handler::<DevelopmentManager, _, _>(
         |&MSG_DevelopmentManager_try_develop(building_style), instance, world| {
             instance.try_develop(building_style, world); Fate::Live
@@ -1,22 +1,37 @@
-use kay::{World, ActorSystem, TypedID};
-use compact::COption;
-use land_use::buildings::{UnitType, BuildingID, UnitIdx};
+use kay::{World, ActorSystem, Actor, TypedID};
+use compact::{COption, CVec, CHashMap};
+use land_use::buildings::{UnitType, BuildingID};
 use cb_time::actors::{Sleeper, SleeperID, TimeID};
 use cb_time::units::{Instant, Duration};
 use cb_util::random::{seed, Rng};
 use cb_util::log::{debug};
 const LOG_T: &str = "Immigration/Development";
 
-use economy::households::household_kinds;
+use economy::households::{household_kinds, HouseholdID};
+use economy::households::archetypes::{self, HouseholdArchetype};
+use economy::households::aging::MemberLife;
+use economy::housing::{self, Dwelling, DwellingRequest, HousingMarketID, HousingSeeker,
+HousingSeekerID};
+use cb_util::config_manager::{Name, ConfigUser, ConfigUserID};
 use self::household_kinds::family::FamilyID;
 use self::household_kinds::grocery_shop::GroceryShopID;
+use self::household_kinds::school::SchoolID;
+use self::household_kinds::hospital::HospitalID;
+use self::household_kinds::car_dealer::CarDealerID;
 use self::household_kinds::cow_farm::CowFarmID;
 use self::household_kinds::grain_farm::GrainFarmID;
 use self::household_kinds::vegetable_farm::VegetableFarmID;
 use self::household_kinds::mill::MillID;
 use self::household_kinds::bakery::BakeryID;
 use self::household_kinds::neighboring_town_trade::NeighboringTownTradeID;
+use self::household_kinds::leisure_venue::{LeisureVenueID, LeisureKind};
+use self::household_kinds::construction_firm::ConstructionFirmID;
+use self::household_kinds::utility_company::UtilityCompanyID;
+use self::household_kinds::taxi_company::TaxiCompanyID;
+use economy::utilities::Utility;
+use economy::difficulty::{self, DifficultyProfile};
 use land_use::buildings::BuildingStyle;
+use transport::pathfinding::RoughLocationID;
 use land_use::vacant_lots::VacantLotID;
 use land_use::zone_planning::BuildingIntent;
 use cb_planning::{Project, PrototypeID, Plan, GestureID, Gesture};
@@ -34,7 +49,14 @@ pub enum HouseholdTypeToSpawn {
     VegetableFarm,
     Mill,
     Bakery,
+    School,
+    Hospital,
+    CarDealer,
+    LeisureVenue(LeisureKind),
     NeighboringTownTrade,
+    ConstructionFirm,
+    UtilityCompany(Utility),
+    TaxiCompany,
 }
 
 pub fn unit_type_for(household_type: HouseholdTypeToSpawn) -> UnitType {
@@ -46,7 +68,17 @@ pub fn unit_type_for(household_type: HouseholdTypeToSpawn) -> UnitType {
         | HouseholdTypeToSpawn::VegetableFarm => UnitType::Agriculture,
         HouseholdTypeToSpawn::Mill => UnitType::Mill,
         HouseholdTypeToSpawn::Bakery => UnitType::Bakery,
+        HouseholdTypeToSpawn::School => UnitType::School,
+        HouseholdTypeToSpawn::Hospital => UnitType::Hospital,
+        HouseholdTypeToSpawn::CarDealer => UnitType::CarDealer,
+        HouseholdTypeToSpawn::LeisureVenue(LeisureKind::Restaurant) => UnitType::Restaurant,
+        HouseholdTypeToSpawn::LeisureVenue(LeisureKind::Park) => UnitType::Park,
+        HouseholdTypeToSpawn::LeisureVenue(LeisureKind::Cinema) => UnitType::Cinema,
         HouseholdTypeToSpawn::NeighboringTownTrade => UnitType::NeighboringTownTrade,
+        HouseholdTypeToSpawn::ConstructionFirm => UnitType::ConstructionFirm,
+        HouseholdTypeToSpawn::UtilityCompany(Utility::Electricity) => UnitType::PowerPlant,
+        HouseholdTypeToSpawn::UtilityCompany(Utility::Water) => UnitType::Waterworks,
+        HouseholdTypeToSpawn::TaxiCompany => UnitType::TaxiCompany,
     }
 }
 
@@ -59,7 +91,59 @@ pub fn building_style_for(household_type: HouseholdTypeToSpawn) -> BuildingStyle
         | HouseholdTypeToSpawn::VegetableFarm => BuildingStyle::Field,
         HouseholdTypeToSpawn::Mill => BuildingStyle::Mill,
         HouseholdTypeToSpawn::Bakery => BuildingStyle::Bakery,
+        HouseholdTypeToSpawn::School => BuildingStyle::School,
+        HouseholdTypeToSpawn::Hospital => BuildingStyle::Hospital,
+        HouseholdTypeToSpawn::CarDealer => BuildingStyle::CarDealer,
+        HouseholdTypeToSpawn::LeisureVenue(LeisureKind::Restaurant) => BuildingStyle::Restaurant,
+        HouseholdTypeToSpawn::LeisureVenue(LeisureKind::Park) => BuildingStyle::Park,
+        HouseholdTypeToSpawn::LeisureVenue(LeisureKind::Cinema) => BuildingStyle::Cinema,
         HouseholdTypeToSpawn::NeighboringTownTrade => BuildingStyle::NeighboringTownConnection,
+        HouseholdTypeToSpawn::ConstructionFirm => BuildingStyle::ConstructionYard,
+        HouseholdTypeToSpawn::UtilityCompany(Utility::Electricity) => BuildingStyle::PowerPlant,
+        HouseholdTypeToSpawn::UtilityCompany(Utility::Water) => BuildingStyle::Waterworks,
+        HouseholdTypeToSpawn::TaxiCompany => BuildingStyle::TaxiDepot,
+    }
+}
+
+// Starts a business of the given type, the building still has to add it to a unit
+pub fn move_into(
+    household_type: HouseholdTypeToSpawn,
+    building_id: BuildingID,
+    time: TimeID,
+    world: &mut World,
+) -> HouseholdID {
+    match household_type {
+        HouseholdTypeToSpawn::Family => {
+            unreachable!("Families find their homes on the housing market")
+        }
+        HouseholdTypeToSpawn::GroceryShop => {
+            GroceryShopID::move_into(building_id, time, world).into()
+        }
+        HouseholdTypeToSpawn::GrainFarm => GrainFarmID::move_into(building_id, time, world).into(),
+        HouseholdTypeToSpawn::CowFarm => CowFarmID::move_into(building_id, time, world).into(),
+        HouseholdTypeToSpawn::VegetableFarm => {
+            VegetableFarmID::move_into(building_id, time, world).into()
+        }
+        HouseholdTypeToSpawn::Mill => MillID::move_into(building_id, time, world).into(),
+        HouseholdTypeToSpawn::Bakery => BakeryID::move_into(building_id, time, world).into(),
+        HouseholdTypeToSpawn::School => SchoolID::move_into(building_id, time, world).into(),
+        HouseholdTypeToSpawn::Hospital => HospitalID::move_into(building_id, time, world).into(),
+        HouseholdTypeToSpawn::CarDealer => CarDealerID::move_into(building_id, time, world).into(),
+        HouseholdTypeToSpawn::LeisureVenue(kind) => {
+            LeisureVenueID::move_into(kind, building_id, time, world).into()
+        }
+        HouseholdTypeToSpawn::NeighboringTownTrade => {
+            NeighboringTownTradeID::move_into(building_id, time, world).into()
+        }
+        HouseholdTypeToSpawn::ConstructionFirm => {
+            ConstructionFirmID::move_into(building_id, time, world).into()
+        }
+        HouseholdTypeToSpawn::UtilityCompany(utility) => {
+            UtilityCompanyID::move_into(utility, building_id, time, world).into()
+        }
+        HouseholdTypeToSpawn::TaxiCompany => {
+            TaxiCompanyID::move_into(building_id, time, world).into()
+        }
     }
 }
 
@@ -69,6 +153,21 @@ pub struct ImmigrationManager {
     time: TimeID,
     development_manager: DevelopmentManagerID,
     state: ImmigrationManagerState,
+    // set by migration, depending on how attractive the city currently is
+    family_share: f32,
+    // where immigrants enter the city, usually connections to neighboring towns
+    gateways: CVec<RoughLocationID>,
+    archetypes: CHashMap<Name, HouseholdArchetype>,
+    difficulty: CHashMap<Name, DifficultyProfile>,
+    // the family waiting for a home while in `FindingHome`
+    immigrants: COption<Immigrants>,
+}
+
+#[derive(Compact, Clone)]
+pub struct Immigrants {
+    name: Name,
+    archetype: HouseholdArchetype,
+    members: CVec<(MemberLife, f32)>,
 }
 
 impl ImmigrationManager {
@@ -80,154 +179,164 @@ impl ImmigrationManager {
     ) -> ImmigrationManager {
         time.wake_up_in(IMMIGRATION_PACE.into(), id.into(), world);
 
-        ImmigrationManager {
+        let manager = ImmigrationManager {
             id,
             time,
             development_manager,
             state: ImmigrationManagerState::Idle,
-        }
+            family_share: 1.0,
+            gateways: CVec::new(),
+            archetypes: CHashMap::new(),
+            difficulty: CHashMap::new(),
+            immigrants: COption(None),
+        };
+        ConfigUser::<HouseholdArchetype>::get_initial_config(&manager, world);
+        ConfigUser::<DifficultyProfile>::get_initial_config(&manager, world);
+        manager
+    }
+
+    pub fn update_migration(
+        &mut self,
+        family_share: f32,
+        gateways: &CVec<RoughLocationID>,
+        _: &mut World,
+    ) {
+        self.family_share = family_share;
+        self.gateways = gateways.clone();
     }
 }
 
 #[derive(Copy, Clone)]
 pub enum ImmigrationManagerState {
     Idle,
-    FindingBuilding(HouseholdTypeToSpawn),
+    FindingHome,
 }
 
 const IMMIGRATION_PACE: Duration = Duration(10);
+// Businesses are founded by entrepreneurs where they are lacking, only families
+// immigrate, in between idle turns. The more attractive the city, the fewer of those
+const IDLE_SHARE: f32 = 1.7;
 
 impl Sleeper for ImmigrationManager {
     fn wake(&mut self, current_instant: Instant, world: &mut World) {
         self.state = match self.state {
             ImmigrationManagerState::Idle => {
-                let family_share = 1.0;
-                let grocery_share = 0.2;
-                let cow_farm_share = 0.3;
-                let veg_farm_share = 0.26;
-                let grain_farm_share = 0.2; //0.0016;
-                let mill_share = 0.2; //0.001;
-                let bakery_share = 0.2; //0.01;
-
-                let total_share = family_share
-                    + grocery_share
-                    + cow_farm_share
-                    + veg_farm_share
-                    + grain_farm_share
-                    + mill_share
-                    + bakery_share;
-
-                let dot = seed(current_instant).gen_range(0.0, total_share);
-
-                let household_type_to_spawn = if dot < family_share {
-                    HouseholdTypeToSpawn::Family
-                } else if dot < family_share + grocery_share {
-                    HouseholdTypeToSpawn::GroceryShop
-                } else if dot < family_share + grocery_share + cow_farm_share {
-                    HouseholdTypeToSpawn::CowFarm
-                } else if dot < family_share + grocery_share + cow_farm_share + veg_farm_share {
-                    HouseholdTypeToSpawn::VegetableFarm
-                } else if dot
-                    < family_share
-                        + grocery_share
-                        + cow_farm_share
-                        + veg_farm_share
-                        + grain_farm_share
-                {
-                    HouseholdTypeToSpawn::GrainFarm
-                } else if dot
-                    < family_share
-                        + grocery_share
-                        + cow_farm_share
-                        + veg_farm_share
-                        + grain_farm_share
-                        + mill_share
-                {
-                    HouseholdTypeToSpawn::Mill
+                let family_share =
+                    self.family_share * difficulty::current(&self.difficulty).demand_growth_factor;
+                let family_chance = family_share / (family_share + IDLE_SHARE);
+
+                if seed(current_instant).gen_range(0.0, 1.0) < family_chance {
+                    // families look for a home with enough room on the housing market,
+                    // they can't know yet where they'll work
+                    let mut rng = seed((current_instant.ticks(), self.id));
+                    let (name, archetype) = archetypes::choose(&self.archetypes, &mut rng);
+                    let members = archetype.generate_members(&mut rng);
+
+                    HousingMarketID::global_first(world).search(
+                        DwellingRequest {
+                            n_members: members.len() as u32,
+                            max_rent: housing::rent_budget(members.len() as u32, amount!(0.0)),
+                            workplace: None,
+                        },
+                        self.id_as(),
+                        world,
+                    );
+
+                    self.immigrants = COption(Some(Immigrants {
+                        name,
+                        archetype,
+                        members: members.into(),
+                    }));
+                    ImmigrationManagerState::FindingHome
                 } else {
-                    HouseholdTypeToSpawn::Bakery
-                };
+                    ImmigrationManagerState::Idle
+                }
+            }
+            // the housing market always answers
+            ImmigrationManagerState::FindingHome => ImmigrationManagerState::FindingHome,
+        };
+
+        self.time
+            .wake_up_in(IMMIGRATION_PACE.into(), self.id.into(), world);
+    }
+}
+
+impl HousingSeeker for ImmigrationManager {
+    fn on_dwelling_offer(&mut self, maybe_dwelling: Option<Dwelling>, world: &mut World) {
+        let maybe_immigrants = ::std::mem::replace(&mut self.immigrants, COption(None)).0;
 
+        match (maybe_dwelling, maybe_immigrants) {
+            (Some(dwelling), Some(immigrants)) => {
                 debug(
                     LOG_T,
-                    format!("Trying to spawn {:?}", household_type_to_spawn),
+                    format!("Moving in a {} household", immigrants.name),
                     self.id,
                     world,
                 );
-
-                let required_unit_type = unit_type_for(household_type_to_spawn);
-
-                BuildingID::global_broadcast(world).try_offer_unit(
-                    required_unit_type,
-                    self.id,
+                let arrival = seed(dwelling.building)
+                    .choose(&self.gateways)
+                    .cloned()
+                    .unwrap_or_else(|| dwelling.building.into());
+                FamilyID::move_into(
+                    immigrants.archetype,
+                    immigrants.members,
+                    dwelling,
+                    arrival,
+                    self.time,
                     world,
                 );
-
-                ImmigrationManagerState::FindingBuilding(household_type_to_spawn)
             }
-            ImmigrationManagerState::FindingBuilding(household_type_to_spawn) => {
-                // didn't find a building in time
+            (Some(dwelling), None) => {
+                HousingMarketID::global_first(world).list(dwelling, world);
+            }
+            (None, _) => {
+                // no vacant dwelling fits them
                 self.development_manager
-                    .try_develop(building_style_for(household_type_to_spawn), world);
-
-                ImmigrationManagerState::Idle
+                    .try_develop(BuildingStyle::FamilyHouse, world);
             }
-        };
+        }
 
-        self.time
-            .wake_up_in(IMMIGRATION_PACE.into(), self.id.into(), world);
+        self.state = ImmigrationManagerState::Idle;
     }
 }
 
-impl ImmigrationManager {
-    pub fn on_unit_offer(&mut self, building_id: BuildingID, unit_idx: UnitIdx, world: &mut World) {
-        debug(LOG_T, "Got offer", self.id, world);
-        self.state = match self.state {
-            ImmigrationManagerState::FindingBuilding(household_type_to_spawn) => {
-                debug(LOG_T, "Moving in", self.id, world);
-
-                let household_id = match household_type_to_spawn {
-                    HouseholdTypeToSpawn::Family => {
-                        FamilyID::move_into(3, building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::GroceryShop => {
-                        GroceryShopID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::GrainFarm => {
-                        GrainFarmID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::CowFarm => {
-                        CowFarmID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::VegetableFarm => {
-                        VegetableFarmID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::Mill => {
-                        MillID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::Bakery => {
-                        BakeryID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::NeighboringTownTrade => {
-                        NeighboringTownTradeID::move_into(building_id, self.time, world).into()
-                    }
-                };
-
-                building_id.add_household(household_id, unit_idx, world);
-
-                ImmigrationManagerState::Idle
-            }
-            ImmigrationManagerState::Idle => ImmigrationManagerState::Idle,
-        }
+impl ConfigUser<HouseholdArchetype> for ImmigrationManager {
+    fn local_cache(&mut self) -> &mut CHashMap<Name, HouseholdArchetype> {
+        &mut self.archetypes
     }
 }
 
+impl ConfigUser<DifficultyProfile> for ImmigrationManager {
+    fn local_cache(&mut self) -> &mut CHashMap<Name, DifficultyProfile> {
+        &mut self.difficulty
+    }
+}
+
+// How much the city currently asks for more of each kind of zone,
+// from -1.0 (plenty left) to 1.0 (urgently needed), estimated by migration
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct ZoneDemand {
+    pub residential: f32,
+    pub commercial: f32,
+    pub industrial: f32,
+}
+
+pub trait ZoneDemandUI {
+    fn on_zone_demand(&mut self, demand: ZoneDemand, world: &mut World);
+}
+
+// above this, homes are built ahead of immigrants instead of only
+// when one of them finds no vacant dwelling. Businesses are still
+// founded by entrepreneurs, in whatever zoned lots are left for them
+const RESIDENTIAL_GROWTH_THRESHOLD: f32 = 0.3;
+
 #[derive(Compact, Clone)]
 pub struct DevelopmentManager {
     id: DevelopmentManagerID,
     time: TimeID,
     plan_manager: CBPlanManagerID,
     building_to_develop: COption<BuildingStyle>,
+    demand: ZoneDemand,
 }
 
 impl DevelopmentManager {
@@ -242,9 +351,26 @@ impl DevelopmentManager {
             time,
             plan_manager,
             building_to_develop: COption(None),
+            demand: ZoneDemand::default(),
         }
     }
 
+    pub fn update_demand(&mut self, demand: ZoneDemand, instant: Instant, world: &mut World) {
+        self.demand = demand;
+
+        if demand.residential > RESIDENTIAL_GROWTH_THRESHOLD {
+            self.try_develop(BuildingStyle::FamilyHouse, world);
+        }
+        // besides new buildings on vacant lots, valuable ones can grow denser
+        BuildingID::global_broadcast(world).consider_densifying(demand, instant, world);
+
+        ZoneDemandUIID::global_broadcast(world).on_zone_demand(demand, world);
+    }
+
+    pub fn get_zone_demand(&mut self, ui: ZoneDemandUIID, world: &mut World) {
+        ui.on_zone_demand(self.demand, world);
+    }
+
     pub fn try_develop(&mut self, building_style: BuildingStyle, world: &mut World) {
         if self.building_to_develop.is_none() {
             debug(
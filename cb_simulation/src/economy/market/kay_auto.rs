@@ -5,6 +5,85 @@ use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTra
 #[allow(unused_imports)]
 use super::*;
 
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct SupplyReportRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for SupplyReportRequesterID {}
+impl Clone for SupplyReportRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for SupplyReportRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "SupplyReportRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for SupplyReportRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for SupplyReportRequesterID {
+    fn eq(&self, other: &SupplyReportRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for SupplyReportRequesterID {}
+
+pub struct SupplyReportRequesterRepresentative;
+
+impl ActorOrActorTrait for SupplyReportRequesterRepresentative {
+    type ID = SupplyReportRequesterID;
+}
+
+impl TypedID for SupplyReportRequesterID {
+    type Target = SupplyReportRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        SupplyReportRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + SupplyReportRequester> TraitIDFrom<Act> for SupplyReportRequesterID {}
+
+impl SupplyReportRequesterID {
+    pub fn expect_supply_reports(self, n: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_SupplyReportRequester_expect_supply_reports(n));
+    }
+    
+    pub fn on_supply_report(self, n_offers: ResourceMap < u32 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_SupplyReportRequester_on_supply_report(n_offers));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<SupplyReportRequesterRepresentative>();
+        system.register_trait_message::<MSG_SupplyReportRequester_expect_supply_reports>();
+        system.register_trait_message::<MSG_SupplyReportRequester_on_supply_report>();
+    }
+
+    pub fn register_implementor<Act: Actor + SupplyReportRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, SupplyReportRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_SupplyReportRequester_expect_supply_reports(n), instance, world| {
+                instance.expect_supply_reports(n, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_SupplyReportRequester_on_supply_report(ref n_offers), instance, world| {
+                instance.on_supply_report(n_offers, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SupplyReportRequester_expect_supply_reports(pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SupplyReportRequester_on_supply_report(pub ResourceMap < u32 >);
 #[derive(Serialize, Deserialize)] #[serde(transparent)]
 pub struct EvaluationRequesterID {
     _raw_id: RawID
@@ -140,29 +219,223 @@ impl MarketID {
         id
     }
     
-    pub fn search(self, instant: Instant, location: RoughLocationID, resource: Resource, requester: EvaluationRequesterID, world: &mut World) {
-        world.send(self.as_raw(), MSG_Market_search(instant, location, resource, requester));
+    pub fn search_nearest(self, instant: Instant, location: RoughLocationID, resource: Resource, tier: MarketTier, household: HouseholdID, n_acceptable: u32, modes: ModeSet, world: &mut World) {
+        world.send(self.as_raw(), MSG_Market_search_nearest(instant, location, resource, tier, household, n_acceptable, modes));
     }
     
-    pub fn register(self, resource: Resource, offer: OfferID, world: &mut World) {
-        world.send(self.as_raw(), MSG_Market_register(resource, offer));
+    pub fn register(self, resource: Resource, tier: MarketTier, offer: OfferID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Market_register(resource, tier, offer));
     }
     
-    pub fn withdraw(self, resource: Resource, offer: OfferID, world: &mut World) {
-        world.send(self.as_raw(), MSG_Market_withdraw(resource, offer));
+    pub fn withdraw(self, resource: Resource, tier: MarketTier, offer: OfferID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Market_withdraw(resource, tier, offer));
+    }
+    
+    pub fn report_supply(self, requester: SupplyReportRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Market_report_supply(requester));
+    }
+    
+    pub fn relocated(self, site: RoughLocationID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Market_relocated(site));
     }
 }
 
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Market_spawn(pub MarketID, );
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Market_search(pub Instant, pub RoughLocationID, pub Resource, pub EvaluationRequesterID);
+struct MSG_Market_search_nearest(pub Instant, pub RoughLocationID, pub Resource, pub MarketTier, pub HouseholdID, pub u32, pub ModeSet);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Market_register(pub Resource, pub MarketTier, pub OfferID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Market_register(pub Resource, pub OfferID);
+struct MSG_Market_withdraw(pub Resource, pub MarketTier, pub OfferID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Market_withdraw(pub Resource, pub OfferID);
+struct MSG_Market_report_supply(pub SupplyReportRequesterID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Market_relocated(pub RoughLocationID);
+
 
+impl Actor for MarketShard {
+    type ID = MarketShardID;
 
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct MarketShardID {
+    _raw_id: RawID
+}
+
+impl Copy for MarketShardID {}
+impl Clone for MarketShardID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for MarketShardID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "MarketShardID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for MarketShardID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for MarketShardID {
+    fn eq(&self, other: &MarketShardID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for MarketShardID {}
+
+impl TypedID for MarketShardID {
+    type Target = MarketShard;
+
+    fn from_raw(id: RawID) -> Self {
+        MarketShardID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl MarketShardID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = MarketShardID::from_raw(world.allocate_instance_id::<MarketShard>());
+        let swarm = world.local_broadcast::<MarketShard>();
+        world.send(swarm, MSG_MarketShard_spawn(id, ));
+        id
+    }
+    
+    pub fn search_nearest(self, instant: Instant, location: RoughLocationID, resource: Resource, tier: MarketTier, household: HouseholdID, n_acceptable: u32, modes: ModeSet, world: &mut World) {
+        world.send(self.as_raw(), MSG_MarketShard_search_nearest(instant, location, resource, tier, household, n_acceptable, modes));
+    }
+    
+    pub fn register(self, resource: Resource, tier: MarketTier, offer: OfferID, world: &mut World) {
+        world.send(self.as_raw(), MSG_MarketShard_register(resource, tier, offer));
+    }
+    
+    pub fn withdraw(self, resource: Resource, tier: MarketTier, offer: OfferID, world: &mut World) {
+        world.send(self.as_raw(), MSG_MarketShard_withdraw(resource, tier, offer));
+    }
+    
+    pub fn report_supply(self, requester: SupplyReportRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_MarketShard_report_supply(requester));
+    }
+    
+    pub fn relocated(self, site: RoughLocationID, world: &mut World) {
+        world.send(self.as_raw(), MSG_MarketShard_relocated(site));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_MarketShard_spawn(pub MarketShardID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_MarketShard_search_nearest(pub Instant, pub RoughLocationID, pub Resource, pub MarketTier, pub HouseholdID, pub u32, pub ModeSet);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_MarketShard_register(pub Resource, pub MarketTier, pub OfferID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_MarketShard_withdraw(pub Resource, pub MarketTier, pub OfferID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_MarketShard_report_supply(pub SupplyReportRequesterID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_MarketShard_relocated(pub RoughLocationID);
+
+impl Into<PositionRequesterID> for MarketShardID {
+    fn into(self) -> PositionRequesterID {
+        PositionRequesterID::from_raw(self.as_raw())
+    }
+}
+impl Actor for NearestOffersSearch {
+    type ID = NearestOffersSearchID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct NearestOffersSearchID {
+    _raw_id: RawID
+}
+
+impl Copy for NearestOffersSearchID {}
+impl Clone for NearestOffersSearchID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for NearestOffersSearchID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "NearestOffersSearchID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for NearestOffersSearchID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for NearestOffersSearchID {
+    fn eq(&self, other: &NearestOffersSearchID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for NearestOffersSearchID {}
+
+impl TypedID for NearestOffersSearchID {
+    type Target = NearestOffersSearch;
+
+    fn from_raw(id: RawID) -> Self {
+        NearestOffersSearchID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl NearestOffersSearchID {
+    pub fn spawn(instant: Instant, location: RoughLocationID, resource: Resource, household: HouseholdID, n_acceptable: u32, candidates: CVec < ( OfferID , Option < P2 > ) >, modes: ModeSet, world: &mut World) -> Self {
+        let id = NearestOffersSearchID::from_raw(world.allocate_instance_id::<NearestOffersSearch>());
+        let swarm = world.local_broadcast::<NearestOffersSearch>();
+        world.send(swarm, MSG_NearestOffersSearch_spawn(id, instant, location, resource, household, n_acceptable, candidates, modes));
+        id
+    }
+    
+    pub fn requester_gone(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_NearestOffersSearch_requester_gone());
+    }
+    
+    pub fn done(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_NearestOffersSearch_done());
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NearestOffersSearch_spawn(pub NearestOffersSearchID, pub Instant, pub RoughLocationID, pub Resource, pub HouseholdID, pub u32, pub CVec < ( OfferID , Option < P2 > ) >, pub ModeSet);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NearestOffersSearch_requester_gone();
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NearestOffersSearch_done();
+
+impl Into<PositionRequesterID> for NearestOffersSearchID {
+    fn into(self) -> PositionRequesterID {
+        PositionRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<EvaluationRequesterID> for NearestOffersSearchID {
+    fn into(self) -> EvaluationRequesterID {
+        EvaluationRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for NearestOffersSearchID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
 impl Actor for TripCostEstimator {
     type ID = TripCostEstimatorID;
 
@@ -211,10 +484,10 @@ impl TypedID for TripCostEstimatorID {
 }
 
 impl TripCostEstimatorID {
-    pub fn spawn(requester: EvaluationRequesterID, rough_source: RoughLocationID, rough_destination: RoughLocationID, base_result: EvaluatedSearchResult, instant: Instant, world: &mut World) -> Self {
+    pub fn spawn(requester: EvaluationRequesterID, rough_source: RoughLocationID, rough_destination: RoughLocationID, base_result: EvaluatedSearchResult, modes: ModeSet, instant: Instant, world: &mut World) -> Self {
         let id = TripCostEstimatorID::from_raw(world.allocate_instance_id::<TripCostEstimator>());
         let swarm = world.local_broadcast::<TripCostEstimator>();
-        world.send(swarm, MSG_TripCostEstimator_spawn(id, requester, rough_source, rough_destination, base_result, instant));
+        world.send(swarm, MSG_TripCostEstimator_spawn(id, requester, rough_source, rough_destination, base_result, modes, instant));
         id
     }
     
@@ -224,17 +497,87 @@ impl TripCostEstimatorID {
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_TripCostEstimator_spawn(pub TripCostEstimatorID, pub EvaluationRequesterID, pub RoughLocationID, pub RoughLocationID, pub EvaluatedSearchResult, pub Instant);
+struct MSG_TripCostEstimator_spawn(pub TripCostEstimatorID, pub EvaluationRequesterID, pub RoughLocationID, pub RoughLocationID, pub EvaluatedSearchResult, pub ModeSet, pub Instant);
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_TripCostEstimator_done();
 
-impl Into<LocationRequesterID> for TripCostEstimatorID {
+impl Into<ItineraryRequesterID> for TripCostEstimatorID {
+    fn into(self) -> ItineraryRequesterID {
+        ItineraryRequesterID::from_raw(self.as_raw())
+    }
+}
+impl Actor for ChainedTripEstimator {
+    type ID = ChainedTripEstimatorID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ChainedTripEstimatorID {
+    _raw_id: RawID
+}
+
+impl Copy for ChainedTripEstimatorID {}
+impl Clone for ChainedTripEstimatorID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ChainedTripEstimatorID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ChainedTripEstimatorID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ChainedTripEstimatorID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ChainedTripEstimatorID {
+    fn eq(&self, other: &ChainedTripEstimatorID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ChainedTripEstimatorID {}
+
+impl TypedID for ChainedTripEstimatorID {
+    type Target = ChainedTripEstimator;
+
+    fn from_raw(id: RawID) -> Self {
+        ChainedTripEstimatorID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl ChainedTripEstimatorID {
+    pub fn spawn(requester: HouseholdID, rough_start: RoughLocationID, candidates: CVec < EvaluatedDeal >, mode: TravelMode, instant: Instant, world: &mut World) -> Self {
+        let id = ChainedTripEstimatorID::from_raw(world.allocate_instance_id::<ChainedTripEstimator>());
+        let swarm = world.local_broadcast::<ChainedTripEstimator>();
+        world.send(swarm, MSG_ChainedTripEstimator_spawn(id, requester, rough_start, candidates, mode, instant));
+        id
+    }
+    
+    pub fn done(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_ChainedTripEstimator_done());
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ChainedTripEstimator_spawn(pub ChainedTripEstimatorID, pub HouseholdID, pub RoughLocationID, pub CVec < EvaluatedDeal >, pub TravelMode, pub Instant);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ChainedTripEstimator_done();
+
+impl Into<LocationRequesterID> for ChainedTripEstimatorID {
     fn into(self) -> LocationRequesterID {
         LocationRequesterID::from_raw(self.as_raw())
     }
 }
 
-impl Into<DistanceRequesterID> for TripCostEstimatorID {
+impl Into<DistanceRequesterID> for ChainedTripEstimatorID {
     fn into(self) -> DistanceRequesterID {
         DistanceRequesterID::from_raw(self.as_raw())
     }
@@ -243,6 +586,7 @@ impl Into<DistanceRequesterID> for TripCostEstimatorID {
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
+    SupplyReportRequesterID::register_trait(system);
     EvaluationRequesterID::register_trait(system);
     
     system.add_spawner::<Market, _, _>(
@@ -252,27 +596,94 @@ pub fn auto_setup(system: &mut ActorSystem) {
     );
     
     system.add_handler::<Market, _, _>(
-        |&MSG_Market_search(instant, location, resource, requester), instance, world| {
-            instance.search(instant, location, resource, requester, world); Fate::Live
+        |&MSG_Market_search_nearest(instant, location, resource, tier, household, n_acceptable, modes), instance, world| {
+            instance.search_nearest(instant, location, resource, tier, household, n_acceptable, modes, world); Fate::Live
         }, false
     );
     
     system.add_handler::<Market, _, _>(
-        |&MSG_Market_register(resource, offer), instance, world| {
-            instance.register(resource, offer, world); Fate::Live
+        |&MSG_Market_register(resource, tier, offer), instance, world| {
+            instance.register(resource, tier, offer, world); Fate::Live
         }, false
     );
     
     system.add_handler::<Market, _, _>(
-        |&MSG_Market_withdraw(resource, offer), instance, world| {
-            instance.withdraw(resource, offer, world); Fate::Live
+        |&MSG_Market_withdraw(resource, tier, offer), instance, world| {
+            instance.withdraw(resource, tier, offer, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Market, _, _>(
+        |&MSG_Market_report_supply(requester), instance, world| {
+            instance.report_supply(requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Market, _, _>(
+        |&MSG_Market_relocated(site), instance, world| {
+            instance.relocated(site, world); Fate::Live
+        }, false
+    );
+    PositionRequesterID::register_implementor::<MarketShard>(system);
+    system.add_spawner::<MarketShard, _, _>(
+        |&MSG_MarketShard_spawn(id, ), world| {
+            MarketShard::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<MarketShard, _, _>(
+        |&MSG_MarketShard_search_nearest(instant, location, resource, tier, household, n_acceptable, modes), instance, world| {
+            instance.search_nearest(instant, location, resource, tier, household, n_acceptable, modes, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<MarketShard, _, _>(
+        |&MSG_MarketShard_register(resource, tier, offer), instance, world| {
+            instance.register(resource, tier, offer, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<MarketShard, _, _>(
+        |&MSG_MarketShard_withdraw(resource, tier, offer), instance, world| {
+            instance.withdraw(resource, tier, offer, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<MarketShard, _, _>(
+        |&MSG_MarketShard_report_supply(requester), instance, world| {
+            instance.report_supply(requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<MarketShard, _, _>(
+        |&MSG_MarketShard_relocated(site), instance, world| {
+            instance.relocated(site, world); Fate::Live
         }, false
     );
-    LocationRequesterID::register_implementor::<TripCostEstimator>(system);
-    DistanceRequesterID::register_implementor::<TripCostEstimator>(system);
+    PositionRequesterID::register_implementor::<NearestOffersSearch>(system);
+    EvaluationRequesterID::register_implementor::<NearestOffersSearch>(system);
+    SleeperID::register_implementor::<NearestOffersSearch>(system);
+    system.add_spawner::<NearestOffersSearch, _, _>(
+        |&MSG_NearestOffersSearch_spawn(id, instant, location, resource, household, n_acceptable, ref candidates, modes), world| {
+            NearestOffersSearch::spawn(id, instant, location, resource, household, n_acceptable, candidates, modes, world)
+        }, false
+    );
+    
+    system.add_handler::<NearestOffersSearch, _, _>(
+        |&MSG_NearestOffersSearch_requester_gone(), instance, world| {
+            instance.requester_gone(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<NearestOffersSearch, _, _>(
+        |&MSG_NearestOffersSearch_done(), instance, world| {
+            instance.done(world)
+        }, false
+    );
+    ItineraryRequesterID::register_implementor::<TripCostEstimator>(system);
     system.add_spawner::<TripCostEstimator, _, _>(
-        |&MSG_TripCostEstimator_spawn(id, requester, rough_source, rough_destination, ref base_result, instant), world| {
-            TripCostEstimator::spawn(id, requester, rough_source, rough_destination, base_result, instant, world)
+        |&MSG_TripCostEstimator_spawn(id, requester, rough_source, rough_destination, ref base_result, modes, instant), world| {
+            TripCostEstimator::spawn(id, requester, rough_source, rough_destination, base_result, modes, instant, world)
         }, false
     );
     
@@ -281,4 +692,17 @@ pub fn auto_setup(system: &mut ActorSystem) {
             instance.done(world)
         }, false
     );
+    LocationRequesterID::register_implementor::<ChainedTripEstimator>(system);
+    DistanceRequesterID::register_implementor::<ChainedTripEstimator>(system);
+    system.add_spawner::<ChainedTripEstimator, _, _>(
+        |&MSG_ChainedTripEstimator_spawn(id, requester, rough_start, ref candidates, mode, instant), world| {
+            ChainedTripEstimator::spawn(id, requester, rough_start, candidates, mode, instant, world)
+        }, false
+    );
+    
+    system.add_handler::<ChainedTripEstimator, _, _>(
+        |&MSG_ChainedTripEstimator_done(), instance, world| {
+            instance.done(world)
+        }, false
+    );
 }
\ No newline at end of file
@@ -1,12 +1,24 @@
 use kay::{ActorSystem, Fate, World, Actor};
-use compact::{CVec, CDict};
-use super::resources::{Inventory, Entry, Resource, ResourceAmount};
-use super::households::OfferID;
+use cb_time::actors::{Sleeper, TimeID};
+use compact::{CVec, CDict, CHashMap, COption};
+use super::resources::{Inventory, Entry, Resource, ResourceAmount, ResourceMap};
+use super::households::{HouseholdID, OfferID};
 use cb_time::units::{TimeOfDayRange, Duration, Instant};
-use transport::pathfinding::{RoughLocationID, LocationRequesterID};
+use transport::pathfinding::{RoughLocationID, LocationRequesterID, PositionRequester,
+PositionRequesterID};
+use transport::pathfinding::trip::TravelMode;
+use transport::transit;
+use transport::pathfinding::itinerary::{ModeSet, Itinerary, ItineraryRequester,
+ItineraryRequesterID, ItineraryQueryID};
+use descartes::P2;
+use ordered_float::OrderedFloat;
 use cb_util::log::warn;
 const LOG_T: &str = "Market";
 
+// Households that went away after a search listed their offers never answer.
+// A search doesn't wait for them any longer than this
+const MAX_EVALUATION_WAIT: Duration = Duration(30 * 60);
+
 #[derive(Compact, Clone, Debug, Serialize, Deserialize)]
 pub struct Deal {
     pub duration: Duration,
@@ -33,58 +45,464 @@ impl Deal {
     }
 }
 
+// Businesses trade in bulk on the wholesale tier, while consumers only ever
+// see the retail tier, so that families don't end up buying a ton of flour
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum MarketTier {
+    Retail,
+    Wholesale,
+}
+
+// Anyone who wants an overview of which offers are currently available,
+// reported in one part per market shard
+pub trait SupplyReportRequester {
+    fn expect_supply_reports(&mut self, n: u32, world: &mut World);
+    fn on_supply_report(&mut self, n_offers: &ResourceMap<u32>, world: &mut World);
+}
+
 pub trait EvaluationRequester {
     fn expect_n_results(&mut self, resource: Resource, n: u32, world: &mut World);
     fn on_result(&mut self, result: &EvaluatedSearchResult, world: &mut World);
 }
 
+const N_MARKET_SHARDS: usize = 4;
+
 #[derive(Compact, Clone)]
 pub struct Market {
     id: MarketID,
-    offers_by_resource: CDict<Resource, CVec<OfferID>>,
+    shards: CVec<MarketShardID>,
 }
 
 impl Market {
-    pub fn spawn(id: MarketID, _: &mut World) -> Market {
+    pub fn spawn(id: MarketID, world: &mut World) -> Market {
         Market {
+            id,
+            shards: (0..N_MARKET_SHARDS)
+                .map(|_| MarketShardID::spawn(world))
+                .collect(),
+        }
+    }
+
+    // Has to stay the same across runs and builds, offers are persisted in their shards
+    fn shard_for(&self, resource: Resource) -> MarketShardID {
+        self.shards[resource as usize % self.shards.len()]
+    }
+
+    pub fn search_nearest(
+        &mut self,
+        instant: Instant,
+        location: RoughLocationID,
+        resource: Resource,
+        tier: MarketTier,
+        household: HouseholdID,
+        n_acceptable: u32,
+        modes: ModeSet,
+        world: &mut World,
+    ) {
+        self.shard_for(resource).search_nearest(
+            instant,
+            location,
+            resource,
+            tier,
+            household,
+            n_acceptable,
+            modes,
+            world,
+        );
+    }
+
+    pub fn register(
+        &mut self,
+        resource: Resource,
+        tier: MarketTier,
+        offer: OfferID,
+        world: &mut World,
+    ) {
+        self.shard_for(resource)
+            .register(resource, tier, offer, world);
+    }
+
+    pub fn withdraw(
+        &mut self,
+        resource: Resource,
+        tier: MarketTier,
+        offer: OfferID,
+        world: &mut World,
+    ) {
+        self.shard_for(resource)
+            .withdraw(resource, tier, offer, world);
+    }
+
+    pub fn report_supply(&mut self, requester: SupplyReportRequesterID, world: &mut World) {
+        requester.expect_supply_reports(self.shards.len() as u32, world);
+        for shard in self.shards.iter() {
+            shard.report_supply(requester, world);
+        }
+    }
+
+    // the offers of a household that moved are somewhere else now
+    pub fn relocated(&mut self, site: RoughLocationID, world: &mut World) {
+        for shard in self.shards.iter() {
+            shard.relocated(site, world);
+        }
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct MarketShard {
+    id: MarketShardID,
+    offers_by_resource: CDict<(MarketTier, Resource), CVec<OfferID>>,
+    offer_positions: CHashMap<RoughLocationID, P2>,
+}
+
+impl MarketShard {
+    pub fn spawn(id: MarketShardID, _: &mut World) -> MarketShard {
+        MarketShard {
             id,
             offers_by_resource: CDict::new(),
+            offer_positions: CHashMap::new(),
         }
     }
 
-    pub fn search(
+    pub fn search_nearest(
         &mut self,
         instant: Instant,
         location: RoughLocationID,
         resource: Resource,
-        requester: EvaluationRequesterID,
+        tier: MarketTier,
+        household: HouseholdID,
+        n_acceptable: u32,
+        modes: ModeSet,
         world: &mut World,
     ) {
-        let n_to_expect = if let Some(offers) = self.offers_by_resource.get(resource) {
-            for offer in offers.iter() {
-                offer
-                    .household
-                    .evaluate(offer.idx, instant, location, requester, world);
+        let candidates: CVec<(OfferID, Option<P2>)> =
+            if let Some(offers) = self.offers_by_resource.get((tier, resource)) {
+                offers
+                    .iter()
+                    .map(|offer| {
+                        let site: RoughLocationID = offer.household.into();
+                        (*offer, self.offer_positions.get(site).cloned())
+                    })
+                    .collect()
+            } else {
+                CVec::new()
+            };
+
+        // sites that weren't resolvable on registration get another chance
+        for &(offer, maybe_position) in &candidates {
+            if maybe_position.is_none() {
+                let site: RoughLocationID = offer.household.into();
+                site.resolve_as_position(self.id_as(), site, world);
             }
+        }
 
-            offers.len()
+        if candidates.is_empty() {
+            let requester: EvaluationRequesterID = household.into();
+            requester.expect_n_results(resource, 0, world);
         } else {
-            0
-        };
-
-        requester.expect_n_results(resource, n_to_expect as u32, world);
+            NearestOffersSearchID::spawn(
+                instant,
+                location,
+                resource,
+                household,
+                n_acceptable,
+                candidates,
+                modes,
+                world,
+            );
+        }
     }
 
-    pub fn register(&mut self, resource: Resource, offer: OfferID, _: &mut World) {
-        self.offers_by_resource.push_at(resource, offer);
+    pub fn register(
+        &mut self,
+        resource: Resource,
+        tier: MarketTier,
+        offer: OfferID,
+        world: &mut World,
+    ) {
+        self.offers_by_resource.push_at((tier, resource), offer);
+
+        let site: RoughLocationID = offer.household.into();
+        if self.offer_positions.get(site).is_none() {
+            site.resolve_as_position(self.id_as(), site, world);
+        }
     }
 
-    pub fn withdraw(&mut self, resource: Resource, offer: OfferID, world: &mut World) {
-        if let Some(offers) = self.offers_by_resource.get_mut(resource) {
+    pub fn withdraw(
+        &mut self,
+        resource: Resource,
+        tier: MarketTier,
+        offer: OfferID,
+        world: &mut World,
+    ) {
+        if let Some(offers) = self.offers_by_resource.get_mut((tier, resource)) {
             offers.retain(|o| *o != offer);
         }
         offer.household.withdrawal_confirmed(offer.idx, world);
     }
+
+    pub fn report_supply(&mut self, requester: SupplyReportRequesterID, world: &mut World) {
+        let n_retail_offers: ResourceMap<u32> = self
+            .offers_by_resource
+            .pairs()
+            .filter(|&(&(tier, _), _)| tier == MarketTier::Retail)
+            .map(|(&(_, resource), offers)| (resource, offers.len() as u32))
+            .collect();
+        requester.on_supply_report(n_retail_offers, world);
+    }
+
+    pub fn relocated(&mut self, site: RoughLocationID, world: &mut World) {
+        if self.offer_positions.get(site).is_some() {
+            self.offer_positions.remove(site);
+            site.resolve_as_position(self.id_as(), site, world);
+        }
+    }
+}
+
+impl PositionRequester for MarketShard {
+    fn position_resolved(&mut self, rough_location: RoughLocationID, position: P2, _: &mut World) {
+        self.offer_positions.insert(rough_location, position);
+    }
+}
+
+// How far a search got through its candidates, nearest first: how many were
+// asked to evaluate their offer, how many answered and whether it is over
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SearchProgress {
+    n_candidates: u32,
+    n_acceptable: u32,
+    next_candidate: u32,
+    n_pending: u32,
+    n_forwarded: u32,
+    n_acceptable_found: u32,
+    finished: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SearchStep {
+    // ask the candidates from the first up to the second index
+    Evaluate(u32, u32),
+    WaitForAnswers,
+    // tell the requester how many results it got
+    Finish(u32),
+}
+
+impl SearchProgress {
+    pub fn new(n_candidates: u32, n_acceptable: u32) -> SearchProgress {
+        SearchProgress {
+            n_candidates,
+            n_acceptable,
+            next_candidate: 0,
+            n_pending: 0,
+            n_forwarded: 0,
+            n_acceptable_found: 0,
+            finished: false,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn n_pending(&self) -> u32 {
+        self.n_pending
+    }
+
+    // fewer candidates at once the more acceptable deals were already found
+    pub fn next_batch(&mut self) -> SearchStep {
+        let batch_size = self
+            .n_acceptable
+            .saturating_sub(self.n_acceptable_found)
+            .max(1);
+        let batch_start = self.next_candidate;
+        let batch_end = (batch_start + batch_size).min(self.n_candidates);
+        self.n_pending += batch_end - batch_start;
+        self.next_candidate = batch_end;
+
+        if self.n_pending == 0 {
+            self.finish()
+        } else {
+            SearchStep::Evaluate(batch_start, batch_end)
+        }
+    }
+
+    // None if the search is already over and the result is dropped
+    pub fn answered(&mut self, acceptable: bool) -> Option<SearchStep> {
+        if self.finished {
+            return None;
+        }
+        self.n_pending = self.n_pending.saturating_sub(1);
+        self.n_forwarded += 1;
+        if acceptable {
+            self.n_acceptable_found += 1;
+        }
+
+        Some(if self.n_pending > 0 {
+            SearchStep::WaitForAnswers
+        } else if self.n_acceptable_found < self.n_acceptable
+            && self.next_candidate < self.n_candidates
+        {
+            self.next_batch()
+        } else {
+            self.finish()
+        })
+    }
+
+    // Households that went away never answer, so the search gives up on them
+    // at some point. None if it was already over by then
+    pub fn timed_out(&mut self) -> Option<SearchStep> {
+        if self.finished {
+            None
+        } else {
+            Some(self.finish())
+        }
+    }
+
+    // The requester went away, nobody is told about the rest. Whether the
+    // search was still under way and so has to stop waiting for a timeout
+    pub fn abandon(&mut self) -> bool {
+        let was_under_way = !self.finished;
+        self.finished = true;
+        was_under_way
+    }
+
+    fn finish(&mut self) -> SearchStep {
+        self.finished = true;
+        SearchStep::Finish(self.n_forwarded)
+    }
+}
+
+// Evaluates offers in increasing rough distance from the requester and stops
+// as soon as enough acceptable deals have been found
+#[derive(Compact, Clone)]
+pub struct NearestOffersSearch {
+    id: NearestOffersSearchID,
+    instant: Instant,
+    location: RoughLocationID,
+    resource: Resource,
+    requester: EvaluationRequesterID,
+    candidates: CVec<(OfferID, Option<P2>)>,
+    modes: ModeSet,
+    progress: SearchProgress,
+}
+
+impl NearestOffersSearch {
+    pub fn spawn(
+        id: NearestOffersSearchID,
+        instant: Instant,
+        location: RoughLocationID,
+        resource: Resource,
+        household: HouseholdID,
+        n_acceptable: u32,
+        candidates: &CVec<(OfferID, Option<P2>)>,
+        modes: ModeSet,
+        world: &mut World,
+    ) -> NearestOffersSearch {
+        location.resolve_as_position(id.into(), location, world);
+        TimeID::local_first(world).wake_up_in(MAX_EVALUATION_WAIT.into(), id.into(), world);
+        household.search_started(resource, id, world);
+
+        NearestOffersSearch {
+            id,
+            instant,
+            location,
+            resource,
+            requester: household.into(),
+            candidates: candidates.clone(),
+            modes,
+            progress: SearchProgress::new(candidates.len() as u32, n_acceptable),
+        }
+    }
+
+    fn take_step(&mut self, step: SearchStep, world: &mut World) {
+        match step {
+            SearchStep::Evaluate(batch_start, batch_end) => {
+                for &(offer, _) in &self.candidates[batch_start as usize..batch_end as usize] {
+                    offer.household.evaluate(
+                        offer.idx,
+                        self.instant,
+                        self.location,
+                        self.id_as(),
+                        self.modes,
+                        world,
+                    );
+                }
+            }
+            SearchStep::WaitForAnswers => {}
+            SearchStep::Finish(n_forwarded) => {
+                self.requester
+                    .expect_n_results(self.resource, n_forwarded, world);
+                self.stop(world);
+            }
+        }
+    }
+
+    fn stop(&mut self, world: &mut World) {
+        TimeID::local_first(world).stop_waking_up(self.id.into(), world);
+        self.id.done(world);
+    }
+
+    // Told by the household that started the search when it is destroyed,
+    // no more offers are evaluated and the timeout doesn't fire anymore
+    pub fn requester_gone(&mut self, world: &mut World) {
+        if self.progress.abandon() {
+            self.stop(world);
+        }
+    }
+
+    pub fn done(&mut self, _: &mut World) -> Fate {
+        Fate::Die
+    }
+}
+
+impl PositionRequester for NearestOffersSearch {
+    fn position_resolved(
+        &mut self,
+        _rough_location: RoughLocationID,
+        position: P2,
+        world: &mut World,
+    ) {
+        self.candidates.sort_by_key(|&(_, maybe_offer_position)| {
+            OrderedFloat(
+                maybe_offer_position
+                    .map(|offer_position| (offer_position - position).norm())
+                    .unwrap_or(::std::f32::INFINITY),
+            )
+        });
+
+        let step = self.progress.next_batch();
+        self.take_step(step, world);
+    }
+}
+
+impl EvaluationRequester for NearestOffersSearch {
+    fn expect_n_results(&mut self, _resource: Resource, _n: u32, _: &mut World) {}
+
+    fn on_result(&mut self, result: &EvaluatedSearchResult, world: &mut World) {
+        // results after the search is over are too late, the requester doesn't expect them
+        if let Some(step) = self.progress.answered(!result.evaluated_deals.is_empty()) {
+            self.requester.on_result(result.clone(), world);
+            self.take_step(step, world);
+        }
+    }
+}
+
+impl Sleeper for NearestOffersSearch {
+    fn wake(&mut self, _current_instant: Instant, world: &mut World) {
+        let n_pending = self.progress.n_pending();
+        if let Some(step) = self.progress.timed_out() {
+            warn(
+                LOG_T,
+                format!(
+                    "Gave up on {} evaluations of {} offers",
+                    n_pending, self.resource
+                ),
+                self.id,
+                world,
+            );
+            self.take_step(step, world);
+        }
+    }
 }
 
 #[derive(Compact, Clone, Debug, Serialize, Deserialize)]
@@ -92,6 +510,33 @@ pub struct EvaluatedDeal {
     pub offer: OfferID,
     pub deal: Deal,
     pub opening_hours: TimeOfDayRange,
+    pub min_education: f32,
+    // how the evaluating household would get there
+    pub mode: TravelMode,
+    // and how long it expects to be on the way
+    pub travel_time: Duration,
+}
+
+// The times of day at which to set off to get there while the offer is open.
+// A ride on transit has to be over while the lines still run, none at all
+// fit if it takes longer than they run in a day
+pub fn departure_hours(
+    opening_hours: TimeOfDayRange,
+    travel_time: Duration,
+    rides_transit: bool,
+) -> Option<TimeOfDayRange> {
+    let departure_hours = opening_hours.earlier_by(travel_time);
+    if rides_transit {
+        let service_hours = transit::service_hours();
+        if travel_time >= service_hours.duration() {
+            return None;
+        }
+        departure_hours
+            .intersection(service_hours)
+            .and_then(|hours| hours.intersection(service_hours.earlier_by(travel_time)))
+    } else {
+        Some(departure_hours)
+    }
 }
 
 #[derive(Compact, Clone)]
@@ -103,15 +548,12 @@ pub struct EvaluatedSearchResult {
 use transport::pathfinding::{PreciseLocation, LocationRequester, DistanceRequester,
 DistanceRequesterID};
 
+// Estimates how long it takes the requester to get to the offers with the best
+// way of getting there that they have, which is also how they will travel there
 #[derive(Compact, Clone)]
 pub struct TripCostEstimator {
     id: TripCostEstimatorID,
     requester: EvaluationRequesterID,
-    rough_source: RoughLocationID,
-    source: Option<PreciseLocation>,
-    rough_destination: RoughLocationID,
-    destination: Option<PreciseLocation>,
-    n_resolved: u8,
     base_result: EvaluatedSearchResult,
 }
 
@@ -122,21 +564,23 @@ impl TripCostEstimator {
         rough_source: RoughLocationID,
         rough_destination: RoughLocationID,
         base_result: &EvaluatedSearchResult,
+        modes: ModeSet,
         instant: Instant,
         world: &mut World,
     ) -> TripCostEstimator {
-        rough_source.resolve_as_location(id.into(), rough_source, instant, world);
-        rough_destination.resolve_as_location(id.into(), rough_destination, instant, world);
+        ItineraryQueryID::spawn(
+            id.into(),
+            rough_source,
+            rough_destination,
+            modes,
+            instant,
+            world,
+        );
 
         TripCostEstimator {
             id,
             requester,
-            rough_source,
-            rough_destination,
             base_result: base_result.clone(),
-            source: None,
-            n_resolved: 0,
-            destination: None,
         }
     }
 
@@ -145,69 +589,33 @@ impl TripCostEstimator {
     }
 }
 
-impl LocationRequester for TripCostEstimator {
-    fn location_resolved(
-        &mut self,
-        rough_location: RoughLocationID,
-        location: Option<PreciseLocation>,
-        _tick: Instant,
-        world: &mut World,
-    ) {
-        if self.rough_source == rough_location {
-            self.source = location;
-        } else if self.rough_destination == rough_location {
-            self.destination = location;
-        } else {
-            panic!("Should have this rough source/destination")
-        }
-
-        self.n_resolved += 1;
-
-        if let (Some(source), Some(destination)) = (self.source, self.destination) {
-            source
-                .link
-                .get_distance_to(destination.location, self.id_as(), world);
-        } else if self.n_resolved == 2 {
-            warn(
-                LOG_T,
-                format!(
-                    "Either source or dest not resolvable for {}",
-                    self.base_result.resource
-                ),
-                self.id(),
-                world,
-            );
-
-            self.requester.on_result(
-                EvaluatedSearchResult {
-                    resource: self.base_result.resource,
-                    evaluated_deals: CVec::new(),
-                },
-                world,
-            );
-            self.id.done(world);
-        }
-    }
-}
-
-impl DistanceRequester for TripCostEstimator {
-    fn on_distance(&mut self, maybe_distance: Option<f32>, world: &mut World) {
-        const ASSUMED_AVG_SPEED: f32 = 10.0; // m/s
-
-        let result = if let Some(distance) = maybe_distance {
+impl ItineraryRequester for TripCostEstimator {
+    fn on_itinerary(&mut self, maybe_itinerary: &COption<Itinerary>, world: &mut World) {
+        let result = if let Some(ref itinerary) = maybe_itinerary.0 {
+            let estimated_travel_time = itinerary.duration();
+            // park and ride starts by car, but depends on the lines all the same
+            let rides_transit = itinerary
+                .legs
+                .iter()
+                .any(|leg| leg.mode == TravelMode::Transit);
             EvaluatedSearchResult {
                 evaluated_deals: self
                     .base_result
                     .evaluated_deals
                     .iter()
-                    .map(|evaluated_deal| {
-                        let estimated_travel_time = Duration((distance / ASSUMED_AVG_SPEED) as u32);
+                    .filter_map(|evaluated_deal| {
+                        let departures = departure_hours(
+                            evaluated_deal.opening_hours,
+                            estimated_travel_time,
+                            rides_transit,
+                        )?;
                         let mut new_deal = evaluated_deal.clone();
                         new_deal.deal.duration += estimated_travel_time;
-                        new_deal.opening_hours =
-                            new_deal.opening_hours.earlier_by(estimated_travel_time);
+                        new_deal.opening_hours = departures;
+                        new_deal.mode = itinerary.mode;
+                        new_deal.travel_time = estimated_travel_time;
                         // TODO: adjust resources to incorporate travel costs
-                        new_deal
+                        Some(new_deal)
                     })
                     .collect(),
                 ..self.base_result
@@ -215,10 +623,7 @@ impl DistanceRequester for TripCostEstimator {
         } else {
             warn(
                 LOG_T,
-                format!(
-                    "No distance for {}, from {:?} to {:?}",
-                    self.base_result.resource, self.source, self.destination
-                ),
+                format!("No way to get there for {}", self.base_result.resource),
                 self.id(),
                 world,
             );
@@ -232,9 +637,141 @@ impl DistanceRequester for TripCostEstimator {
     }
 }
 
+// Maximum detour from one stop of a chained trip to the next
+const MAX_CHAINED_LEG_DURATION: Duration = Duration(10 * 60);
+
+// Multi-destination mode of trip cost estimation: starting at the destination
+// of a household's main trip, checks one candidate stop after the other and keeps
+// those that are only a short hop away from the previously kept stop
+#[derive(Compact, Clone)]
+pub struct ChainedTripEstimator {
+    id: ChainedTripEstimatorID,
+    requester: HouseholdID,
+    rough_start: RoughLocationID,
+    candidates: CVec<EvaluatedDeal>,
+    locations: CHashMap<RoughLocationID, PreciseLocation>,
+    n_resolved: u32,
+    current: Option<PreciseLocation>,
+    next_candidate: u32,
+    chained: CVec<EvaluatedDeal>,
+    mode: TravelMode,
+}
+
+impl ChainedTripEstimator {
+    pub fn spawn(
+        id: ChainedTripEstimatorID,
+        requester: HouseholdID,
+        rough_start: RoughLocationID,
+        candidates: &CVec<EvaluatedDeal>,
+        mode: TravelMode,
+        instant: Instant,
+        world: &mut World,
+    ) -> ChainedTripEstimator {
+        rough_start.resolve_as_location(id.into(), rough_start, instant, world);
+        for candidate in candidates.iter() {
+            let site: RoughLocationID = candidate.offer.household.into();
+            site.resolve_as_location(id.into(), site, instant, world);
+        }
+
+        ChainedTripEstimator {
+            id,
+            requester,
+            rough_start,
+            candidates: candidates.clone(),
+            locations: CHashMap::new(),
+            n_resolved: 0,
+            current: None,
+            next_candidate: 0,
+            chained: CVec::new(),
+            mode,
+        }
+    }
+
+    fn estimate_next_leg(&mut self, world: &mut World) {
+        while (self.next_candidate as usize) < self.candidates.len() {
+            let site: RoughLocationID = self.candidates[self.next_candidate as usize]
+                .offer
+                .household
+                .into();
+
+            if let (Some(current), Some(next)) = (self.current, self.locations.get(site).cloned()) {
+                current
+                    .link
+                    .get_distance_to(next.location, self.id_as(), world);
+                return;
+            } else {
+                self.next_candidate += 1;
+            }
+        }
+
+        self.requester
+            .on_chained_deals(self.rough_start, self.chained.clone(), world);
+        self.id.done(world);
+    }
+
+    pub fn done(&mut self, _: &mut World) -> Fate {
+        Fate::Die
+    }
+}
+
+impl LocationRequester for ChainedTripEstimator {
+    fn location_resolved(
+        &mut self,
+        rough_location: RoughLocationID,
+        location: Option<PreciseLocation>,
+        _tick: Instant,
+        world: &mut World,
+    ) {
+        if let Some(location) = location {
+            self.locations.insert(rough_location, location);
+        }
+        self.n_resolved += 1;
+
+        if self.n_resolved as usize == self.candidates.len() + 1 {
+            self.current = self.locations.get(self.rough_start).cloned();
+            self.estimate_next_leg(world);
+        }
+    }
+}
+
+impl DistanceRequester for ChainedTripEstimator {
+    fn on_distance(&mut self, maybe_distance: Option<f32>, world: &mut World) {
+        let candidate = self.candidates[self.next_candidate as usize].clone();
+        self.next_candidate += 1;
+
+        if let Some(distance) = maybe_distance {
+            let estimated_travel_time = Duration((distance / self.mode.assumed_avg_speed()) as u32);
+
+            let maybe_departures = departure_hours(
+                candidate.opening_hours,
+                estimated_travel_time,
+                self.mode == TravelMode::Transit,
+            );
+
+            if estimated_travel_time <= MAX_CHAINED_LEG_DURATION {
+                if let Some(departures) = maybe_departures {
+                    let site: RoughLocationID = candidate.offer.household.into();
+                    self.current = self.locations.get(site).cloned();
+
+                    let mut chained_deal = candidate;
+                    chained_deal.deal.duration += estimated_travel_time;
+                    chained_deal.travel_time = estimated_travel_time;
+                    chained_deal.opening_hours = departures;
+                    self.chained.push(chained_deal);
+                }
+            }
+        }
+
+        self.estimate_next_leg(world);
+    }
+}
+
 pub fn setup(system: &mut ActorSystem) {
     system.register::<Market>();
+    system.register::<MarketShard>();
+    system.register::<NearestOffersSearch>();
     system.register::<TripCostEstimator>();
+    system.register::<ChainedTripEstimator>();
     kay_auto::auto_setup(system);
 }
 
@@ -244,3 +781,99 @@ pub fn spawn(world: &mut World) {
 
 mod kay_auto;
 pub use self::kay_auto::*;
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchProgress, SearchStep, departure_hours};
+    use cb_time::units::{Duration, TimeOfDayRange};
+
+    #[test]
+    fn finishes_right_away_without_candidates() {
+        let mut progress = SearchProgress::new(0, 3);
+        assert_eq!(progress.next_batch(), SearchStep::Finish(0));
+        assert_eq!(progress.timed_out(), None);
+    }
+
+    #[test]
+    fn asks_further_candidates_until_enough_are_acceptable() {
+        let mut progress = SearchProgress::new(4, 2);
+        assert_eq!(progress.next_batch(), SearchStep::Evaluate(0, 2));
+        assert_eq!(progress.answered(false), Some(SearchStep::WaitForAnswers));
+        assert_eq!(progress.answered(true), Some(SearchStep::Evaluate(2, 3)));
+        assert_eq!(progress.answered(true), Some(SearchStep::Finish(3)));
+        // the wake up that was scheduled in case of a timeout changes nothing
+        assert_eq!(progress.timed_out(), None);
+    }
+
+    #[test]
+    fn gives_up_on_offerer_despawned_mid_search() {
+        let mut progress = SearchProgress::new(3, 2);
+        assert_eq!(progress.next_batch(), SearchStep::Evaluate(0, 2));
+        assert_eq!(progress.answered(true), Some(SearchStep::WaitForAnswers));
+
+        // the household of the second candidate went away and never answers
+        assert_eq!(progress.n_pending(), 1);
+        assert_eq!(progress.timed_out(), Some(SearchStep::Finish(1)));
+        assert!(progress.is_finished());
+
+        // an answer that was under way anyway is dropped
+        assert_eq!(progress.answered(true), None);
+        assert_eq!(progress.timed_out(), None);
+    }
+
+    #[test]
+    fn stops_for_searcher_despawned_mid_search() {
+        let mut progress = SearchProgress::new(5, 1);
+        assert_eq!(progress.next_batch(), SearchStep::Evaluate(0, 1));
+
+        // the search was under way, so its timeout is cancelled
+        assert!(progress.abandon());
+        assert!(progress.is_finished());
+
+        // no further candidates are asked and nothing is forwarded
+        assert_eq!(progress.answered(false), None);
+        assert_eq!(progress.timed_out(), None);
+    }
+
+    #[test]
+    fn leaves_finished_search_alone_when_searcher_despawns() {
+        let mut progress = SearchProgress::new(2, 1);
+        assert_eq!(progress.next_batch(), SearchStep::Evaluate(0, 1));
+        assert_eq!(progress.answered(true), Some(SearchStep::Finish(1)));
+
+        // the search stopped and cancelled its timeout by itself already
+        assert!(!progress.abandon());
+        assert_eq!(progress.timed_out(), None);
+    }
+
+    fn hours(range: TimeOfDayRange) -> ((usize, usize), (usize, usize)) {
+        (range.start.hours_minutes(), range.end.hours_minutes())
+    }
+
+    #[test]
+    fn sets_off_early_enough_to_get_there_while_open() {
+        let departures =
+            departure_hours(TimeOfDayRange::new(7, 0, 20, 0), Duration::from_minutes(30), false);
+        assert_eq!(departures.map(hours), Some(((6, 30), (19, 30))));
+
+        // setting off the evening before for something that opens right after midnight
+        let departures =
+            departure_hours(TimeOfDayRange::new(0, 30, 6, 0), Duration::from_hours(1), false);
+        assert_eq!(departures.map(hours), Some(((23, 30), (5, 0))));
+    }
+
+    #[test]
+    fn rides_transit_only_while_the_lines_run() {
+        let departures =
+            departure_hours(TimeOfDayRange::new(22, 0, 2, 0), Duration::from_hours(1), true);
+        assert_eq!(departures.map(hours), Some(((21, 0), (22, 0))));
+
+        let departures =
+            departure_hours(TimeOfDayRange::new(1, 0, 4, 0), Duration::from_minutes(30), true);
+        assert_eq!(departures.map(hours), None);
+
+        let departures =
+            departure_hours(TimeOfDayRange::new(7, 0, 20, 0), Duration::from_hours(20), true);
+        assert_eq!(departures.map(hours), None);
+    }
+}
@@ -0,0 +1,206 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ResidentID {
+    _raw_id: RawID
+}
+
+impl Copy for ResidentID {}
+impl Clone for ResidentID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ResidentID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ResidentID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ResidentID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ResidentID {
+    fn eq(&self, other: &ResidentID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ResidentID {}
+
+pub struct ResidentRepresentative;
+
+impl ActorOrActorTrait for ResidentRepresentative {
+    type ID = ResidentID;
+}
+
+impl TypedID for ResidentID {
+    type Target = ResidentRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        ResidentID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + Resident> TraitIDFrom<Act> for ResidentID {}
+
+impl ResidentID {
+    pub fn report_wellbeing(self, migration: MigrationID, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Resident_report_wellbeing(migration, instant));
+    }
+    
+    pub fn emigrate(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Resident_emigrate());
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<ResidentRepresentative>();
+        system.register_trait_message::<MSG_Resident_report_wellbeing>();
+        system.register_trait_message::<MSG_Resident_emigrate>();
+    }
+
+    pub fn register_implementor<Act: Actor + Resident>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, ResidentRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_Resident_report_wellbeing(migration, instant), instance, world| {
+                instance.report_wellbeing(migration, instant, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Resident_emigrate(), instance, world| {
+                instance.emigrate(world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Resident_report_wellbeing(pub MigrationID, pub Instant);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Resident_emigrate();
+
+impl Actor for Migration {
+    type ID = MigrationID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct MigrationID {
+    _raw_id: RawID
+}
+
+impl Copy for MigrationID {}
+impl Clone for MigrationID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for MigrationID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "MigrationID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for MigrationID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for MigrationID {
+    fn eq(&self, other: &MigrationID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for MigrationID {}
+
+impl TypedID for MigrationID {
+    type Target = Migration;
+
+    fn from_raw(id: RawID) -> Self {
+        MigrationID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl MigrationID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = MigrationID::from_raw(world.allocate_instance_id::<Migration>());
+        let swarm = world.local_broadcast::<Migration>();
+        world.send(swarm, MSG_Migration_spawn(id, time));
+        id
+    }
+    
+    pub fn on_vacancy_report(self, building: BuildingID, is_gateway: bool, n_dwellings: u32, n_vacant: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Migration_on_vacancy_report(building, is_gateway, n_dwellings, n_vacant));
+    }
+    
+    pub fn on_recent_unmet_demand(self, n_recorded: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Migration_on_recent_unmet_demand(n_recorded));
+    }
+    
+    pub fn on_wellbeing(self, resident: ResidentID, commute_minutes: Option < f32 >, happiness: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Migration_on_wellbeing(resident, commute_minutes, happiness));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Migration_spawn(pub MigrationID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Migration_on_vacancy_report(pub BuildingID, pub bool, pub u32, pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Migration_on_recent_unmet_demand(pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Migration_on_wellbeing(pub ResidentID, pub Option < f32 >, pub f32);
+
+impl Into<SupplyReportRequesterID> for MigrationID {
+    fn into(self) -> SupplyReportRequesterID {
+        SupplyReportRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for MigrationID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    ResidentID::register_trait(system);
+    SupplyReportRequesterID::register_implementor::<Migration>(system);
+    SleeperID::register_implementor::<Migration>(system);
+    system.add_spawner::<Migration, _, _>(
+        |&MSG_Migration_spawn(id, time), world| {
+            Migration::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<Migration, _, _>(
+        |&MSG_Migration_on_vacancy_report(building, is_gateway, n_dwellings, n_vacant), instance, world| {
+            instance.on_vacancy_report(building, is_gateway, n_dwellings, n_vacant, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Migration, _, _>(
+        |&MSG_Migration_on_recent_unmet_demand(n_recorded), instance, world| {
+            instance.on_recent_unmet_demand(n_recorded, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Migration, _, _>(
+        |&MSG_Migration_on_wellbeing(resident, commute_minutes, happiness), instance, world| {
+            instance.on_wellbeing(resident, commute_minutes, happiness, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
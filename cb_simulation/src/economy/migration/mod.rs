@@ -0,0 +1,291 @@
+use kay::{ActorSystem, World, TypedID};
+use compact::CVec;
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Duration};
+use cb_util::log::{info, debug};
+use ordered_float::OrderedFloat;
+use transport::pathfinding::RoughLocationID;
+use land_use::buildings::BuildingID;
+use super::resources::{Resource, ResourceMap};
+use super::market::{MarketID, SupplyReportRequester, SupplyReportRequesterID};
+use super::unmet_demand::UnmetDemandID;
+use super::immigration_and_development::{ImmigrationManagerID, DevelopmentManagerID, ZoneDemand};
+const LOG_T: &str = "Migration";
+
+const CHECK_INTERVAL: Duration = Duration(6 * 60 * 60);
+
+// what a typical city offers, each aspect of attractiveness
+// is measured relative to these
+const TYPICAL_OPEN_JOBS_PER_HOUSEHOLD: f32 = 0.2;
+const TYPICAL_VACANCY_RATE: f32 = 0.1;
+const TYPICAL_UNMET_DEMAND_PER_HOUSEHOLD: f32 = 1.0;
+const TYPICAL_COMMUTE_MINUTES: f32 = 30.0;
+const TYPICAL_HAPPINESS: f32 = 0.6;
+
+// the share of immigrants among all newly moving in households
+// in a city of typical attractiveness
+const BASE_FAMILY_SHARE: f32 = 1.0;
+const EMIGRATION_THRESHOLD: f32 = -0.2;
+const MAX_EMIGRANTS_PER_CHECK: usize = 3;
+// households happier than this stay, no matter how bad the city is
+const HAPPINESS_TO_LEAVE: f32 = 0.3;
+
+pub trait Resident {
+    fn report_wellbeing(&mut self, migration: MigrationID, instant: Instant, world: &mut World);
+    fn emigrate(&mut self, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+struct Survey {
+    n_open_jobs: u32,
+    n_dwellings: u32,
+    n_vacant_dwellings: u32,
+    gateways: CVec<RoughLocationID>,
+    n_households: u32,
+    n_commuters: u32,
+    total_commute_minutes: f32,
+    total_happiness: f32,
+    unmet_demand: u32,
+    most_unhappy: CVec<(ResidentID, f32)>,
+}
+
+impl Survey {
+    fn new() -> Survey {
+        Survey {
+            n_open_jobs: 0,
+            n_dwellings: 0,
+            n_vacant_dwellings: 0,
+            gateways: CVec::new(),
+            n_households: 0,
+            n_commuters: 0,
+            total_commute_minutes: 0.0,
+            total_happiness: 0.0,
+            unmet_demand: 0,
+            most_unhappy: CVec::new(),
+        }
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct Migration {
+    id: MigrationID,
+    time: TimeID,
+    attractiveness: f32,
+    // filled by asynchronous reports and evaluated at the next check
+    survey: Survey,
+}
+
+// maps a ratio around 1.0 (typical) to -1.0 (terrible) .. 1.0 (great)
+fn aspect_score(actual: f32, typical: f32) -> f32 {
+    (actual / typical - 1.0).max(-1.0).min(1.0)
+}
+
+impl Migration {
+    pub fn spawn(id: MigrationID, time: TimeID, world: &mut World) -> Migration {
+        time.wake_up_in(CHECK_INTERVAL.into(), id.into(), world);
+
+        Migration {
+            id,
+            time,
+            attractiveness: 0.0,
+            survey: Survey::new(),
+        }
+    }
+
+    pub fn on_vacancy_report(
+        &mut self,
+        building: BuildingID,
+        is_gateway: bool,
+        n_dwellings: u32,
+        n_vacant: u32,
+        _: &mut World,
+    ) {
+        self.survey.n_dwellings += n_dwellings;
+        self.survey.n_vacant_dwellings += n_vacant;
+        if is_gateway {
+            self.survey.gateways.push(building.into());
+        }
+    }
+
+    pub fn on_recent_unmet_demand(&mut self, n_recorded: u32, _: &mut World) {
+        self.survey.unmet_demand += n_recorded;
+    }
+
+    pub fn on_wellbeing(
+        &mut self,
+        resident: ResidentID,
+        commute_minutes: Option<f32>,
+        happiness: f32,
+        _: &mut World,
+    ) {
+        self.survey.n_households += 1;
+        self.survey.total_happiness += happiness;
+
+        if let Some(minutes) = commute_minutes {
+            self.survey.n_commuters += 1;
+            self.survey.total_commute_minutes += minutes;
+        }
+
+        if happiness < HAPPINESS_TO_LEAVE {
+            let mut most_unhappy = self.survey.most_unhappy.iter().cloned().collect::<Vec<_>>();
+            most_unhappy.push((resident, happiness));
+            most_unhappy.sort_by_key(|&(_, happiness)| OrderedFloat(happiness));
+            most_unhappy.truncate(MAX_EMIGRANTS_PER_CHECK);
+            self.survey.most_unhappy = most_unhappy.into();
+        }
+    }
+
+    fn evaluate_survey(&self) -> f32 {
+        let survey = &self.survey;
+
+        if survey.n_households == 0 {
+            // an empty city is neither attractive nor repulsive yet
+            return 0.0;
+        }
+
+        let n_households = survey.n_households as f32;
+
+        let jobs = aspect_score(
+            survey.n_open_jobs as f32 / n_households,
+            TYPICAL_OPEN_JOBS_PER_HOUSEHOLD,
+        );
+        let housing = if survey.n_dwellings == 0 {
+            0.0
+        } else {
+            aspect_score(
+                survey.n_vacant_dwellings as f32 / survey.n_dwellings as f32,
+                TYPICAL_VACANCY_RATE,
+            )
+        };
+        let supply = -aspect_score(
+            survey.unmet_demand as f32 / n_households,
+            TYPICAL_UNMET_DEMAND_PER_HOUSEHOLD,
+        );
+        let commute = if survey.n_commuters == 0 {
+            0.0
+        } else {
+            -aspect_score(
+                survey.total_commute_minutes / survey.n_commuters as f32,
+                TYPICAL_COMMUTE_MINUTES,
+            )
+        };
+
+        let happiness = aspect_score(survey.total_happiness / n_households, TYPICAL_HAPPINESS);
+
+        (jobs + housing + supply + commute + happiness) / 5.0
+    }
+
+    // homes are wanted when the city is attractive but has few vacant ones,
+    // shops when many needs go unmet and workplaces when there are few open jobs
+    fn zone_demand(&self) -> ZoneDemand {
+        let survey = &self.survey;
+
+        if survey.n_households == 0 {
+            return ZoneDemand::default();
+        }
+
+        let n_households = survey.n_households as f32;
+
+        let vacancy = if survey.n_dwellings == 0 {
+            -1.0
+        } else {
+            aspect_score(
+                survey.n_vacant_dwellings as f32 / survey.n_dwellings as f32,
+                TYPICAL_VACANCY_RATE,
+            )
+        };
+
+        ZoneDemand {
+            residential: (self.attractiveness - vacancy) / 2.0,
+            commercial: aspect_score(
+                survey.unmet_demand as f32 / n_households,
+                TYPICAL_UNMET_DEMAND_PER_HOUSEHOLD,
+            ),
+            industrial: -aspect_score(
+                survey.n_open_jobs as f32 / n_households,
+                TYPICAL_OPEN_JOBS_PER_HOUSEHOLD,
+            ),
+        }
+    }
+}
+
+impl SupplyReportRequester for Migration {
+    // the survey is only evaluated at the next check,
+    // by then all shards will have reported
+    fn expect_supply_reports(&mut self, _: u32, _: &mut World) {}
+
+    fn on_supply_report(&mut self, n_offers: &ResourceMap<u32>, _: &mut World) {
+        // only employers with open positions are listed on the market
+        if let Some(&n) = n_offers.get(Resource::Money) {
+            self.survey.n_open_jobs += n;
+        }
+    }
+}
+
+impl Sleeper for Migration {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        self.attractiveness = self.evaluate_survey();
+
+        info(
+            LOG_T,
+            format!(
+                "City attractiveness is {:.2} ({} households, {} open jobs, {}/{} vacant)",
+                self.attractiveness,
+                self.survey.n_households,
+                self.survey.n_open_jobs,
+                self.survey.n_vacant_dwellings,
+                self.survey.n_dwellings
+            ),
+            self.id,
+            world,
+        );
+
+        ImmigrationManagerID::global_first(world).update_migration(
+            BASE_FAMILY_SHARE * (1.0 + self.attractiveness),
+            self.survey.gateways.clone(),
+            world,
+        );
+        DevelopmentManagerID::global_first(world).update_demand(
+            self.zone_demand(),
+            current_instant,
+            world,
+        );
+
+        if self.attractiveness < EMIGRATION_THRESHOLD {
+            let n_emigrants = ((-self.attractiveness * MAX_EMIGRANTS_PER_CHECK as f32).ceil()
+                as usize)
+                .min(MAX_EMIGRANTS_PER_CHECK);
+
+            for &(resident, happiness) in self.survey.most_unhappy.iter().take(n_emigrants) {
+                debug(
+                    LOG_T,
+                    format!("Household with happiness {:.2} leaves", happiness),
+                    self.id,
+                    world,
+                );
+                resident.emigrate(world);
+            }
+        }
+
+        self.survey = Survey::new();
+        MarketID::global_first(world).report_supply(self.id.into(), world);
+        BuildingID::global_broadcast(world).report_vacancy(self.id, world);
+        UnmetDemandID::global_first(world).report_recent(self.id, world);
+        ResidentID::global_broadcast(world).report_wellbeing(self.id, current_instant, world);
+
+        self.time
+            .wake_up_in(CHECK_INTERVAL.into(), self.id.into(), world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Migration>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    MigrationID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
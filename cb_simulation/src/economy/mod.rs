@@ -2,20 +2,61 @@ use kay::{ActorSystem, World};
 
 use cb_time::actors::TimeID;
 use planning::CBPlanManagerID;
+use self::difficulty::DifficultyProfile;
 
+#[macro_use]
 pub mod resources;
+pub mod fixed_point;
 pub mod market;
+pub mod policies;
+pub mod finance;
+pub mod housing;
 pub mod households;
+pub mod companies;
+pub mod unmet_demand;
+pub mod black_market;
+pub mod entrepreneurs;
+pub mod migration;
 pub mod immigration_and_development;
+pub mod region;
+pub mod utilities;
+pub mod difficulty;
 
 pub fn setup(system: &mut ActorSystem) {
     market::setup(system);
+    policies::setup(system);
+    finance::setup(system);
+    housing::setup(system);
     households::setup(system);
+    companies::setup(system);
+    unmet_demand::setup(system);
+    black_market::setup(system);
+    entrepreneurs::setup(system);
+    migration::setup(system);
     immigration_and_development::setup(system);
+    region::setup(system);
+    utilities::setup(system);
+    difficulty::setup(system);
 }
 
-pub fn spawn(world: &mut World, time: TimeID, plan_manager: CBPlanManagerID) {
+pub fn spawn(
+    world: &mut World,
+    time: TimeID,
+    plan_manager: CBPlanManagerID,
+    difficulty: DifficultyProfile,
+) {
+    // before anyone asks for the profile
+    difficulty::spawn(world, difficulty);
     market::spawn(world);
-    households::spawn(world);
+    policies::spawn(world, time, difficulty.starting_money);
+    finance::spawn(world, time);
+    housing::spawn(world, time);
+    households::spawn(world, time);
+    unmet_demand::spawn(world, time);
+    black_market::spawn(world, time);
+    entrepreneurs::spawn(world, time);
+    migration::spawn(world, time);
     immigration_and_development::spawn(world, time, plan_manager);
+    region::spawn(world, time);
+    utilities::spawn(world, time);
 }
@@ -0,0 +1,509 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct PolicyID {
+    _raw_id: RawID
+}
+
+impl Copy for PolicyID {}
+impl Clone for PolicyID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for PolicyID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "PolicyID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for PolicyID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for PolicyID {
+    fn eq(&self, other: &PolicyID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for PolicyID {}
+
+pub struct PolicyRepresentative;
+
+impl ActorOrActorTrait for PolicyRepresentative {
+    type ID = PolicyID;
+}
+
+impl TypedID for PolicyID {
+    type Target = PolicyRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        PolicyID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + Policy> TraitIDFrom<Act> for PolicyID {}
+
+impl PolicyID {
+    pub fn on_deals_settled(self, settled: CVec < SettledDeal >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Policy_on_deals_settled(settled));
+    }
+    
+    pub fn set_active(self, active: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_Policy_set_active(active));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<PolicyRepresentative>();
+        system.register_trait_message::<MSG_Policy_on_deals_settled>();
+        system.register_trait_message::<MSG_Policy_set_active>();
+    }
+
+    pub fn register_implementor<Act: Actor + Policy>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, PolicyRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_Policy_on_deals_settled(ref settled), instance, world| {
+                instance.on_deals_settled(settled, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Policy_set_active(active), instance, world| {
+                instance.set_active(active, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Policy_on_deals_settled(pub CVec < SettledDeal >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Policy_set_active(pub bool);
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct FundsRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for FundsRequesterID {}
+impl Clone for FundsRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for FundsRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "FundsRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for FundsRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for FundsRequesterID {
+    fn eq(&self, other: &FundsRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for FundsRequesterID {}
+
+pub struct FundsRequesterRepresentative;
+
+impl ActorOrActorTrait for FundsRequesterRepresentative {
+    type ID = FundsRequesterID;
+}
+
+impl TypedID for FundsRequesterID {
+    type Target = FundsRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        FundsRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + FundsRequester> TraitIDFrom<Act> for FundsRequesterID {}
+
+impl FundsRequesterID {
+    pub fn on_funds(self, granted: bool, amount: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_FundsRequester_on_funds(granted, amount));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<FundsRequesterRepresentative>();
+        system.register_trait_message::<MSG_FundsRequester_on_funds>();
+    }
+
+    pub fn register_implementor<Act: Actor + FundsRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, FundsRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_FundsRequester_on_funds(granted, amount), instance, world| {
+                instance.on_funds(granted, amount, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_FundsRequester_on_funds(pub bool, pub ResourceAmount);
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BalanceRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for BalanceRequesterID {}
+impl Clone for BalanceRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BalanceRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BalanceRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BalanceRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BalanceRequesterID {
+    fn eq(&self, other: &BalanceRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BalanceRequesterID {}
+
+pub struct BalanceRequesterRepresentative;
+
+impl ActorOrActorTrait for BalanceRequesterRepresentative {
+    type ID = BalanceRequesterID;
+}
+
+impl TypedID for BalanceRequesterID {
+    type Target = BalanceRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        BalanceRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + BalanceRequester> TraitIDFrom<Act> for BalanceRequesterID {}
+
+impl BalanceRequesterID {
+    pub fn on_balance(self, balance: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_BalanceRequester_on_balance(balance));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<BalanceRequesterRepresentative>();
+        system.register_trait_message::<MSG_BalanceRequester_on_balance>();
+    }
+
+    pub fn register_implementor<Act: Actor + BalanceRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, BalanceRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_BalanceRequester_on_balance(balance), instance, world| {
+                instance.on_balance(balance, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BalanceRequester_on_balance(pub ResourceAmount);
+
+impl Actor for Treasury {
+    type ID = TreasuryID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TreasuryID {
+    _raw_id: RawID
+}
+
+impl Copy for TreasuryID {}
+impl Clone for TreasuryID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TreasuryID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TreasuryID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TreasuryID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TreasuryID {
+    fn eq(&self, other: &TreasuryID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TreasuryID {}
+
+impl TypedID for TreasuryID {
+    type Target = Treasury;
+
+    fn from_raw(id: RawID) -> Self {
+        TreasuryID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TreasuryID {
+    pub fn spawn(time: TimeID, starting_money: ResourceAmount, world: &mut World) -> Self {
+        let id = TreasuryID::from_raw(world.allocate_instance_id::<Treasury>());
+        let swarm = world.local_broadcast::<Treasury>();
+        world.send(swarm, MSG_Treasury_spawn(id, time, starting_money));
+        id
+    }
+    
+    pub fn record_settled_deal(self, settled: SettledDeal, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_record_settled_deal(settled));
+    }
+    
+    pub fn request_funds(self, amount: ResourceAmount, requester: FundsRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_request_funds(amount, requester));
+    }
+    
+    pub fn deposit(self, amount: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_deposit(amount));
+    }
+    
+    pub fn get_balance(self, requester: BalanceRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_get_balance(requester));
+    }
+    
+    pub fn set_balance(self, balance: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_set_balance(balance));
+    }
+    
+    pub fn pay_out(self, recipient: HouseholdID, member: MemberIdx, amount: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_pay_out(recipient, member, amount));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_spawn(pub TreasuryID, pub TimeID, pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_record_settled_deal(pub SettledDeal);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_request_funds(pub ResourceAmount, pub FundsRequesterID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_deposit(pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_get_balance(pub BalanceRequesterID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_set_balance(pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_pay_out(pub HouseholdID, pub MemberIdx, pub ResourceAmount);
+
+impl Into<SleeperID> for TreasuryID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+impl Actor for Subsidy {
+    type ID = SubsidyID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct SubsidyID {
+    _raw_id: RawID
+}
+
+impl Copy for SubsidyID {}
+impl Clone for SubsidyID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for SubsidyID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "SubsidyID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for SubsidyID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for SubsidyID {
+    fn eq(&self, other: &SubsidyID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for SubsidyID {}
+
+impl TypedID for SubsidyID {
+    type Target = Subsidy;
+
+    fn from_raw(id: RawID) -> Self {
+        SubsidyID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl SubsidyID {
+    pub fn spawn(resource: Resource, per_unit: ResourceAmount, treasury: TreasuryID, world: &mut World) -> Self {
+        let id = SubsidyID::from_raw(world.allocate_instance_id::<Subsidy>());
+        let swarm = world.local_broadcast::<Subsidy>();
+        world.send(swarm, MSG_Subsidy_spawn(id, resource, per_unit, treasury));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Subsidy_spawn(pub SubsidyID, pub Resource, pub ResourceAmount, pub TreasuryID);
+
+impl Into<PolicyID> for SubsidyID {
+    fn into(self) -> PolicyID {
+        PolicyID::from_raw(self.as_raw())
+    }
+}
+impl Actor for PriceCap {
+    type ID = PriceCapID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct PriceCapID {
+    _raw_id: RawID
+}
+
+impl Copy for PriceCapID {}
+impl Clone for PriceCapID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for PriceCapID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "PriceCapID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for PriceCapID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for PriceCapID {
+    fn eq(&self, other: &PriceCapID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for PriceCapID {}
+
+impl TypedID for PriceCapID {
+    type Target = PriceCap;
+
+    fn from_raw(id: RawID) -> Self {
+        PriceCapID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl PriceCapID {
+    pub fn spawn(resource: Resource, max_price_per_unit: ResourceAmount, world: &mut World) -> Self {
+        let id = PriceCapID::from_raw(world.allocate_instance_id::<PriceCap>());
+        let swarm = world.local_broadcast::<PriceCap>();
+        world.send(swarm, MSG_PriceCap_spawn(id, resource, max_price_per_unit));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PriceCap_spawn(pub PriceCapID, pub Resource, pub ResourceAmount);
+
+impl Into<PolicyID> for PriceCapID {
+    fn into(self) -> PolicyID {
+        PolicyID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    PolicyID::register_trait(system);
+    FundsRequesterID::register_trait(system);
+    BalanceRequesterID::register_trait(system);
+    SleeperID::register_implementor::<Treasury>(system);
+    system.add_spawner::<Treasury, _, _>(
+        |&MSG_Treasury_spawn(id, time, starting_money), world| {
+            Treasury::spawn(id, time, starting_money, world)
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_record_settled_deal(ref settled), instance, world| {
+            instance.record_settled_deal(settled, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_request_funds(amount, requester), instance, world| {
+            instance.request_funds(amount, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_deposit(amount), instance, world| {
+            instance.deposit(amount, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_get_balance(requester), instance, world| {
+            instance.get_balance(requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_set_balance(balance), instance, world| {
+            instance.set_balance(balance, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_pay_out(recipient, member, amount), instance, world| {
+            instance.pay_out(recipient, member, amount, world); Fate::Live
+        }, false
+    );
+    PolicyID::register_implementor::<Subsidy>(system);
+    system.add_spawner::<Subsidy, _, _>(
+        |&MSG_Subsidy_spawn(id, resource, per_unit, treasury), world| {
+            Subsidy::spawn(id, resource, per_unit, treasury, world)
+        }, false
+    );
+    PolicyID::register_implementor::<PriceCap>(system);
+    system.add_spawner::<PriceCap, _, _>(
+        |&MSG_PriceCap_spawn(id, resource, max_price_per_unit), world| {
+            PriceCap::spawn(id, resource, max_price_per_unit, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,257 @@
+use kay::{ActorSystem, World, Actor};
+use compact::CVec;
+use super::resources::{Resource, ResourceAmount};
+use super::resources::Resource::Money;
+use super::market::Deal;
+use super::households::{HouseholdID, MemberIdx};
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Duration, Instant};
+use cb_util::log::debug;
+const LOG_T: &str = "Policies";
+
+// Settled deals are collected by the treasury and handed to all policies at once this often
+const SETTLEMENT_INTERVAL: Duration = Duration(60);
+
+#[derive(Compact, Clone)]
+pub struct SettledDeal {
+    pub deal: Deal,
+    pub provider: HouseholdID,
+    pub provider_member: MemberIdx,
+    pub receiver: HouseholdID,
+    pub receiver_member: MemberIdx,
+    pub undo: bool,
+}
+
+pub trait Policy {
+    // In the order they were settled
+    fn on_deals_settled(&mut self, settled: &CVec<SettledDeal>, world: &mut World);
+    fn set_active(&mut self, active: bool, world: &mut World);
+}
+
+pub trait FundsRequester {
+    fn on_funds(&mut self, granted: bool, amount: ResourceAmount, world: &mut World);
+}
+
+pub trait BalanceRequester {
+    fn on_balance(&mut self, balance: ResourceAmount, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct Treasury {
+    id: TreasuryID,
+    balance: ResourceAmount,
+    settled_since_wake: CVec<SettledDeal>,
+}
+
+impl Treasury {
+    pub fn spawn(
+        id: TreasuryID,
+        time: TimeID,
+        starting_money: ResourceAmount,
+        world: &mut World,
+    ) -> Treasury {
+        time.wake_up_every(SETTLEMENT_INTERVAL, id.into(), world);
+
+        Treasury {
+            id,
+            balance: starting_money,
+            settled_since_wake: CVec::new(),
+        }
+    }
+
+    pub fn record_settled_deal(&mut self, settled: &SettledDeal, _: &mut World) {
+        self.settled_since_wake.push(settled.clone());
+    }
+
+    pub fn request_funds(
+        &mut self,
+        amount: ResourceAmount,
+        requester: FundsRequesterID,
+        world: &mut World,
+    ) {
+        let granted = self.balance >= amount;
+        if granted {
+            self.balance -= amount;
+        }
+        requester.on_funds(granted, amount, world);
+    }
+
+    pub fn deposit(&mut self, amount: ResourceAmount, _: &mut World) {
+        self.balance += amount;
+    }
+
+    pub fn get_balance(&mut self, requester: BalanceRequesterID, world: &mut World) {
+        requester.on_balance(self.balance, world);
+    }
+
+    // Scenarios start their city with their own funds
+    pub fn set_balance(&mut self, balance: ResourceAmount, _: &mut World) {
+        self.balance = balance;
+    }
+
+    pub fn pay_out(
+        &mut self,
+        recipient: HouseholdID,
+        member: MemberIdx,
+        amount: ResourceAmount,
+        world: &mut World,
+    ) {
+        self.balance -= amount;
+        recipient.receive_deal(Deal::new(Some((Money, amount)), Duration(0)), member, world);
+        debug(
+            LOG_T,
+            format!(
+                "Paid out {} to {:?}, balance {}",
+                amount, recipient, self.balance
+            ),
+            self.id(),
+            world,
+        );
+    }
+}
+
+impl Sleeper for Treasury {
+    fn wake(&mut self, _current_instant: Instant, world: &mut World) {
+        if !self.settled_since_wake.is_empty() {
+            let settled = ::std::mem::replace(&mut self.settled_since_wake, CVec::new());
+            PolicyID::global_broadcast(world).on_deals_settled(settled, world);
+        }
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct Subsidy {
+    id: SubsidyID,
+    resource: Resource,
+    per_unit: ResourceAmount,
+    treasury: TreasuryID,
+    active: bool,
+}
+
+impl Subsidy {
+    pub fn spawn(
+        id: SubsidyID,
+        resource: Resource,
+        per_unit: ResourceAmount,
+        treasury: TreasuryID,
+        _: &mut World,
+    ) -> Subsidy {
+        Subsidy {
+            id,
+            resource,
+            per_unit,
+            treasury,
+            active: true,
+        }
+    }
+}
+
+impl Policy for Subsidy {
+    fn on_deals_settled(&mut self, settled: &CVec<SettledDeal>, world: &mut World) {
+        if !self.active {
+            return;
+        }
+
+        for settled_deal in settled.iter() {
+            let deal = &settled_deal.deal;
+            if deal.main_given() != self.resource {
+                continue;
+            }
+
+            if let Some(&given) = deal.delta.get(self.resource) {
+                let top_up = given * self.per_unit;
+                self.treasury.pay_out(
+                    settled_deal.provider,
+                    settled_deal.provider_member,
+                    if settled_deal.undo { -top_up } else { top_up },
+                    world,
+                );
+            }
+        }
+    }
+
+    fn set_active(&mut self, active: bool, _: &mut World) {
+        self.active = active;
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct PriceCap {
+    id: PriceCapID,
+    resource: Resource,
+    max_price_per_unit: ResourceAmount,
+    active: bool,
+}
+
+impl PriceCap {
+    pub fn spawn(
+        id: PriceCapID,
+        resource: Resource,
+        max_price_per_unit: ResourceAmount,
+        _: &mut World,
+    ) -> PriceCap {
+        PriceCap {
+            id,
+            resource,
+            max_price_per_unit,
+            active: true,
+        }
+    }
+}
+
+impl Policy for PriceCap {
+    fn on_deals_settled(&mut self, settled: &CVec<SettledDeal>, world: &mut World) {
+        if !self.active {
+            return;
+        }
+
+        for &SettledDeal {
+            ref deal,
+            provider,
+            provider_member,
+            receiver,
+            receiver_member,
+            undo,
+        } in settled.iter()
+        {
+            if deal.main_given() != self.resource {
+                continue;
+            }
+
+            if let (Some(&given), Some(&paid)) =
+                (deal.delta.get(self.resource), deal.delta.get(Money))
+            {
+                let excess = -paid - given * self.max_price_per_unit;
+                if excess > 0.0 {
+                    // the provider hands back everything above the cap
+                    let refund = Deal::new(Some((Money, excess)), Duration(0));
+                    if undo {
+                        provider.receive_deal(refund.clone(), provider_member, world);
+                        receiver.provide_deal(refund, receiver_member, world);
+                    } else {
+                        provider.provide_deal(refund.clone(), provider_member, world);
+                        receiver.receive_deal(refund, receiver_member, world);
+                    }
+                }
+            }
+        }
+    }
+
+    fn set_active(&mut self, active: bool, _: &mut World) {
+        self.active = active;
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Treasury>();
+    system.register::<Subsidy>();
+    system.register::<PriceCap>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID, starting_money: ResourceAmount) {
+    TreasuryID::spawn(time, starting_money, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
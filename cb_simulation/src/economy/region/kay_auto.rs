@@ -0,0 +1,147 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for RegionLink {
+    type ID = RegionLinkID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct RegionLinkID {
+    _raw_id: RawID
+}
+
+impl Copy for RegionLinkID {}
+impl Clone for RegionLinkID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for RegionLinkID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "RegionLinkID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for RegionLinkID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for RegionLinkID {
+    fn eq(&self, other: &RegionLinkID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for RegionLinkID {}
+
+impl TypedID for RegionLinkID {
+    type Target = RegionLink;
+
+    fn from_raw(id: RawID) -> Self {
+        RegionLinkID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl RegionLinkID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = RegionLinkID::from_raw(world.allocate_instance_id::<RegionLink>());
+        let swarm = world.local_broadcast::<RegionLink>();
+        world.send(swarm, MSG_RegionLink_spawn(id, time));
+        id
+    }
+    
+    pub fn set_connected(self, connected: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_RegionLink_set_connected(connected));
+    }
+    
+    pub fn on_gateway_stock(self, gateway: NeighboringTownTradeID, stock: Inventory, world: &mut World) {
+        world.send(self.as_raw(), MSG_RegionLink_on_gateway_stock(gateway, stock));
+    }
+    
+    pub fn on_utility_balance(self, utility: Utility, balance: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_RegionLink_on_utility_balance(utility, balance));
+    }
+    
+    pub fn receive(self, message: RegionMessage, world: &mut World) {
+        world.send(self.as_raw(), MSG_RegionLink_receive(message));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RegionLink_spawn(pub RegionLinkID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RegionLink_set_connected(pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RegionLink_on_gateway_stock(pub NeighboringTownTradeID, pub Inventory);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RegionLink_on_utility_balance(pub Utility, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RegionLink_receive(pub RegionMessage);
+
+impl Into<FundsRequesterID> for RegionLinkID {
+    fn into(self) -> FundsRequesterID {
+        FundsRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SupplyReportRequesterID> for RegionLinkID {
+    fn into(self) -> SupplyReportRequesterID {
+        SupplyReportRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for RegionLinkID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    FundsRequesterID::register_implementor::<RegionLink>(system);
+    SupplyReportRequesterID::register_implementor::<RegionLink>(system);
+    SleeperID::register_implementor::<RegionLink>(system);
+    system.add_spawner::<RegionLink, _, _>(
+        |&MSG_RegionLink_spawn(id, time), world| {
+            RegionLink::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<RegionLink, _, _>(
+        |&MSG_RegionLink_set_connected(connected), instance, world| {
+            instance.set_connected(connected, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<RegionLink, _, _>(
+        |&MSG_RegionLink_on_gateway_stock(gateway, ref stock), instance, world| {
+            instance.on_gateway_stock(gateway, stock, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<RegionLink, _, _>(
+        |&MSG_RegionLink_on_utility_balance(utility, balance), instance, world| {
+            instance.on_utility_balance(utility, balance, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<RegionLink, _, _>(
+        |&MSG_RegionLink_receive(message), instance, world| {
+            instance.receive(message, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,779 @@
+// Cooperative region play: two cities, each run by its own server, whose
+// gateways are connected to each other. Instead of trading with a made-up
+// neighboring town, the gateways then exchange freight, commuters and spare
+// power and water with the other city, on terms the two cities negotiate
+// deal by deal. Commuters come in as members of the gateways who look for
+// work in the city like anyone else, utilities as extra gateway capacity
+// that the supplying city holds back from its own grid.
+// Agreed commuters and utilities only last until the next negotiation.
+//
+// A deal is always started by the city that needs something and numbered by it:
+//   Request  -> "send me up to this much of a flow, I pay at most this much"
+//   Offer    <- "I can send this much at this price", or Decline <- "I can't"
+//   Accept   -> "agreed, the money is set aside", or Withdraw -> "not anymore"
+//   Deliver  <- the supplier hands over the flow and keeps the money
+//
+// The server carries these messages to and from the other city's server,
+// see `take_outgoing` and `RegionLink::receive`
+
+use kay::{ActorSystem, World, TypedID};
+use compact::CVec;
+use std::cell::RefCell;
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Duration};
+use cb_util::log::{info, warn};
+use super::resources::{Resource, ResourceAmount, ResourceMap, Inventory, Entry, to_amount,
+amount_to_f32};
+use super::market::{MarketID, SupplyReportRequester, SupplyReportRequesterID};
+use super::policies::{TreasuryID, FundsRequester, FundsRequesterID};
+use super::households::household_kinds::neighboring_town_trade::{NeighboringTownTradeID,
+TRADED_GOODS, WAGE_PER_SHIFT, trade_price};
+use super::utilities::{UtilityNetworkID, Utility, ALL_UTILITIES};
+
+const LOG_T: &str = "Region";
+
+pub const PROTOCOL_VERSION: u32 = 2;
+
+const NEGOTIATION_INTERVAL: Duration = Duration(6 * 60 * 60);
+// smaller amounts aren't worth a truck
+const MIN_FREIGHT: ResourceAmount = amount!(10.0);
+// goods are sold to the neighbor for a bit less than the outside world charges
+const REGIONAL_DISCOUNT: f32 = 0.9;
+const MAX_COMMUTERS_PER_DEAL: u32 = 100;
+// smaller amounts of spare capacity aren't worth switching lines for
+const MIN_UTILITY: f32 = 5.0;
+// per unit of capacity, for as long as a negotiation round lasts:
+// a quarter of what buildings are billed per day for it
+const UTILITY_PRICE: ResourceAmount = amount!(0.75);
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Flow {
+    Commuters,
+    Freight(Resource),
+    Utility(Utility),
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum RegionMessage {
+    Hello {
+        protocol: u32,
+    },
+    Request {
+        deal: u32,
+        flow: Flow,
+        amount: f32,
+        max_price: ResourceAmount,
+    },
+    Offer {
+        deal: u32,
+        amount: f32,
+        price: ResourceAmount,
+    },
+    Decline {
+        deal: u32,
+    },
+    Accept {
+        deal: u32,
+    },
+    Withdraw {
+        deal: u32,
+    },
+    Deliver {
+        deal: u32,
+        amount: f32,
+    },
+}
+
+impl Flow {
+    fn encode(self) -> String {
+        match self {
+            Flow::Commuters => "Commuters".to_owned(),
+            Flow::Freight(resource) => format!("Freight:{:?}", resource),
+            Flow::Utility(utility) => format!("Utility:{:?}", utility),
+        }
+    }
+
+    // only goods that gateways trade in can be shipped as freight
+    fn decode(text: &str) -> Option<Flow> {
+        if text == "Commuters" {
+            Some(Flow::Commuters)
+        } else if text.starts_with("Freight:") {
+            TRADED_GOODS
+                .iter()
+                .find(|&&(resource, _)| format!("{:?}", resource) == text[8..])
+                .map(|&(resource, _)| Flow::Freight(resource))
+        } else if text.starts_with("Utility:") {
+            ALL_UTILITIES
+                .iter()
+                .find(|&&utility| format!("{:?}", utility) == text[8..])
+                .map(|&utility| Flow::Utility(utility))
+        } else {
+            None
+        }
+    }
+}
+
+// One message per line of text, so the link can be debugged with telnet
+impl RegionMessage {
+    pub fn encode(&self) -> String {
+        match *self {
+            RegionMessage::Hello { protocol } => format!("HELLO {}", protocol),
+            RegionMessage::Request {
+                deal,
+                flow,
+                amount,
+                max_price,
+            } => format!(
+                "REQUEST {} {} {} {}",
+                deal,
+                flow.encode(),
+                amount,
+                max_price
+            ),
+            RegionMessage::Offer {
+                deal,
+                amount,
+                price,
+            } => format!("OFFER {} {} {}", deal, amount, price),
+            RegionMessage::Decline { deal } => format!("DECLINE {}", deal),
+            RegionMessage::Accept { deal } => format!("ACCEPT {}", deal),
+            RegionMessage::Withdraw { deal } => format!("WITHDRAW {}", deal),
+            RegionMessage::Deliver { deal, amount } => format!("DELIVER {} {}", deal, amount),
+        }
+    }
+
+    pub fn decode(line: &str) -> Option<RegionMessage> {
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+        let number = |idx: usize| parts.get(idx).and_then(|part| part.parse::<u32>().ok());
+        let amount = |idx: usize| {
+            parts
+                .get(idx)
+                .and_then(|part| part.parse::<f32>().ok())
+                .filter(|amount| amount.is_finite() && *amount >= 0.0)
+        };
+
+        match (parts.get(0).cloned(), parts.len()) {
+            (Some("HELLO"), 2) => Some(RegionMessage::Hello {
+                protocol: number(1)?,
+            }),
+            (Some("REQUEST"), 5) => Some(RegionMessage::Request {
+                deal: number(1)?,
+                flow: Flow::decode(parts[2])?,
+                amount: amount(3)?,
+                max_price: to_amount(amount(4)?),
+            }),
+            (Some("OFFER"), 4) => Some(RegionMessage::Offer {
+                deal: number(1)?,
+                amount: amount(2)?,
+                price: to_amount(amount(3)?),
+            }),
+            (Some("DECLINE"), 2) => Some(RegionMessage::Decline { deal: number(1)? }),
+            (Some("ACCEPT"), 2) => Some(RegionMessage::Accept { deal: number(1)? }),
+            (Some("WITHDRAW"), 2) => Some(RegionMessage::Withdraw { deal: number(1)? }),
+            (Some("DELIVER"), 3) => Some(RegionMessage::Deliver {
+                deal: number(1)?,
+                amount: amount(2)?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+thread_local! {
+    // messages for the neighboring city, picked up by the server after each turn
+    static OUTBOX: RefCell<Vec<RegionMessage>> = RefCell::new(Vec::new());
+}
+
+// Has to be called on the thread that processes the actor system's messages
+pub fn take_outgoing() -> Vec<RegionMessage> {
+    OUTBOX.with(|outbox| ::std::mem::replace(&mut *outbox.borrow_mut(), Vec::new()))
+}
+
+fn send(message: RegionMessage) {
+    OUTBOX.with(|outbox| outbox.borrow_mut().push(message));
+}
+
+#[derive(Copy, Clone, Debug)]
+struct RegionDeal {
+    id: u32,
+    flow: Flow,
+    amount: f32,
+    price: ResourceAmount,
+}
+
+#[derive(Compact, Clone)]
+struct RegionSurvey {
+    gateways: CVec<NeighboringTownTradeID>,
+    gateway_stock: CVec<(NeighboringTownTradeID, Resource, ResourceAmount)>,
+    n_open_jobs: u32,
+    // spare capacity, or unmet demand if negative
+    utility_balance: CVec<(Utility, f32)>,
+}
+
+impl RegionSurvey {
+    fn new() -> RegionSurvey {
+        RegionSurvey {
+            gateways: CVec::new(),
+            gateway_stock: CVec::new(),
+            n_open_jobs: 0,
+            utility_balance: CVec::new(),
+        }
+    }
+
+    fn utility_balance(&self, utility: Utility) -> f32 {
+        self.utility_balance
+            .iter()
+            .filter(|&&(balanced, _)| balanced == utility)
+            .map(|&(_, balance)| balance)
+            .sum()
+    }
+
+    fn total_stock(&self, resource: Resource) -> ResourceAmount {
+        self.gateway_stock
+            .iter()
+            .filter(|&&(_, stocked, _)| stocked == resource)
+            .map(|&(_, _, amount)| amount)
+            .sum()
+    }
+
+    // Spreads a change in stock over the gateways: amounts taken come from those
+    // that have the most, amounts added first cover those that are short
+    fn distribute(
+        &mut self,
+        resource: Resource,
+        amount: ResourceAmount,
+    ) -> Vec<(NeighboringTownTradeID, ResourceAmount)> {
+        let taking = amount > 0.0;
+        let mut stocks = self
+            .gateway_stock
+            .iter_mut()
+            .filter(|&&mut (_, stocked, _)| stocked == resource)
+            .collect::<Vec<_>>();
+        stocks.sort_by(|a, b| {
+            let (more, less) = if taking { (b, a) } else { (a, b) };
+            more.2
+                .partial_cmp(&less.2)
+                .unwrap_or(::std::cmp::Ordering::Equal)
+        });
+
+        let mut changes = Vec::new();
+        let mut remaining = amount;
+
+        for stock in stocks.iter_mut() {
+            let change = if taking {
+                remaining.min(stock.2.max(0.0))
+            } else {
+                remaining.max(stock.2.min(0.0))
+            };
+            if change != 0.0 {
+                stock.2 -= change;
+                remaining -= change;
+                changes.push((stock.0, change));
+            }
+        }
+
+        // whatever is added beyond the shortfalls goes to the first gateway
+        if !taking && remaining < 0.0 {
+            if let Some(stock) = stocks.first_mut() {
+                stock.2 -= remaining;
+                changes.push((stock.0, remaining));
+            } else if let Some(&gateway) = self.gateways.first() {
+                changes.push((gateway, remaining));
+            }
+        }
+
+        changes
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct RegionLink {
+    id: RegionLinkID,
+    connected: bool,
+    neighbor_compatible: bool,
+    next_deal: u32,
+    // what we asked the neighbor for, until it is delivered or called off
+    requested: CVec<RegionDeal>,
+    // what we offered the neighbor, until it is accepted or called off
+    offered: CVec<RegionDeal>,
+    // accepted offers, waiting for the treasury to set the money aside in this order
+    awaiting_funds: CVec<RegionDeal>,
+    n_agreed_commuters: u32,
+    commuter_wage: ResourceAmount,
+    // in the order of `ALL_UTILITIES`
+    utility_imports: CVec<f32>,
+    utility_exports: CVec<f32>,
+    // filled by asynchronous reports and used at the next negotiation
+    survey: RegionSurvey,
+}
+
+impl RegionLink {
+    pub fn spawn(id: RegionLinkID, time: TimeID, world: &mut World) -> RegionLink {
+        time.wake_up_every(NEGOTIATION_INTERVAL, id.into(), world);
+
+        RegionLink {
+            id,
+            connected: false,
+            neighbor_compatible: false,
+            next_deal: 0,
+            requested: CVec::new(),
+            offered: CVec::new(),
+            awaiting_funds: CVec::new(),
+            n_agreed_commuters: 0,
+            commuter_wage: WAGE_PER_SHIFT,
+            utility_imports: ALL_UTILITIES.iter().map(|_| 0.0).collect(),
+            utility_exports: ALL_UTILITIES.iter().map(|_| 0.0).collect(),
+            survey: RegionSurvey::new(),
+        }
+    }
+
+    pub fn set_connected(&mut self, connected: bool, world: &mut World) {
+        self.connected = connected;
+        self.neighbor_compatible = false;
+        self.requested = CVec::new();
+        self.offered = CVec::new();
+        self.n_agreed_commuters = 0;
+        self.update_regional_jobs(world);
+        self.host_commuters(0, world);
+        self.clear_utilities(world);
+
+        if connected {
+            info(LOG_T, "Connected to the neighboring city", self.id, world);
+            send(RegionMessage::Hello {
+                protocol: PROTOCOL_VERSION,
+            });
+        } else {
+            info(
+                LOG_T,
+                "Disconnected from the neighboring city, trading with the outside world again",
+                self.id,
+                world,
+            );
+        }
+    }
+
+    pub fn on_gateway_stock(
+        &mut self,
+        gateway: NeighboringTownTradeID,
+        stock: &Inventory,
+        _: &mut World,
+    ) {
+        self.survey.gateways.push(gateway);
+        for &Entry(resource, amount) in stock.iter() {
+            self.survey.gateway_stock.push((gateway, resource, amount));
+        }
+    }
+
+    fn take_deal(deals: &mut CVec<RegionDeal>, id: u32) -> Option<RegionDeal> {
+        let maybe_position = deals.iter().position(|deal| deal.id == id);
+        maybe_position.map(|position| deals.remove(position))
+    }
+
+    fn update_regional_jobs(&mut self, world: &mut World) {
+        for (gateway, n_jobs) in self.split_over_gateways(self.n_agreed_commuters) {
+            let jobs = if self.connected && self.neighbor_compatible {
+                Some((n_jobs, self.commuter_wage))
+            } else {
+                None
+            };
+            gateway.set_regional_jobs(jobs, world);
+        }
+    }
+
+    pub fn on_utility_balance(&mut self, utility: Utility, balance: f32, _: &mut World) {
+        self.survey.utility_balance.push((utility, balance));
+    }
+
+    // Spread evenly over the gateways, the first ones get the remainder
+    fn split_over_gateways(&self, n: u32) -> Vec<(NeighboringTownTradeID, u32)> {
+        let gateways = &self.survey.gateways;
+        let n_gateways = gateways.len() as u32;
+        gateways
+            .iter()
+            .enumerate()
+            .map(|(i, &gateway)| {
+                let share = n / n_gateways + if (i as u32) < n % n_gateways { 1 } else { 0 };
+                (gateway, share)
+            })
+            .collect()
+    }
+
+    fn host_commuters(&mut self, n_commuters: u32, world: &mut World) {
+        for (gateway, n) in self.split_over_gateways(n_commuters) {
+            gateway.host_commuters(n, world);
+        }
+    }
+
+    fn update_regional_utilities(&mut self, world: &mut World) {
+        UtilityNetworkID::global_first(world).set_exports(self.utility_exports.clone(), world);
+        let n_gateways = self.survey.gateways.len() as f32;
+        for &gateway in self.survey.gateways.iter() {
+            for (&utility, &imported) in ALL_UTILITIES.iter().zip(self.utility_imports.iter()) {
+                gateway.set_utility_import(utility, imported / n_gateways, world);
+            }
+        }
+    }
+
+    fn clear_utilities(&mut self, world: &mut World) {
+        self.utility_imports = ALL_UTILITIES.iter().map(|_| 0.0).collect();
+        self.utility_exports = ALL_UTILITIES.iter().map(|_| 0.0).collect();
+        self.update_regional_utilities(world);
+    }
+
+    fn negotiate(&mut self, world: &mut World) {
+        // whatever wasn't settled during the last round won't be anymore
+        self.requested = CVec::new();
+        self.offered = CVec::new();
+        self.n_agreed_commuters = 0;
+        self.update_regional_jobs(world);
+        self.host_commuters(0, world);
+        self.clear_utilities(world);
+
+        for &(resource, price) in TRADED_GOODS {
+            let shortfall = -self.survey.total_stock(resource);
+            if shortfall >= MIN_FREIGHT {
+                self.request(Flow::Freight(resource), amount_to_f32(shortfall), price);
+            }
+        }
+
+        let n_gateway_jobs = self.survey.gateways.len() as u32;
+        let n_open_jobs = self.survey.n_open_jobs.saturating_sub(n_gateway_jobs);
+        if n_open_jobs > 0 {
+            self.request(
+                Flow::Commuters,
+                n_open_jobs.min(MAX_COMMUTERS_PER_DEAL) as f32,
+                WAGE_PER_SHIFT,
+            );
+        }
+
+        for &utility in &ALL_UTILITIES {
+            let shortfall = -self.survey.utility_balance(utility);
+            if shortfall >= MIN_UTILITY {
+                self.request(Flow::Utility(utility), shortfall, UTILITY_PRICE);
+            }
+        }
+    }
+
+    fn request(&mut self, flow: Flow, amount: f32, max_price: ResourceAmount) {
+        let deal = RegionDeal {
+            id: self.next_deal,
+            flow,
+            amount,
+            price: max_price,
+        };
+        self.next_deal += 1;
+        self.requested.push(deal);
+        send(RegionMessage::Request {
+            deal: deal.id,
+            flow,
+            amount,
+            max_price,
+        });
+    }
+
+    pub fn receive(&mut self, message: RegionMessage, world: &mut World) {
+        if !self.connected {
+            return;
+        }
+
+        if let RegionMessage::Hello { protocol } = message {
+            self.neighbor_compatible = protocol == PROTOCOL_VERSION;
+            if !self.neighbor_compatible {
+                warn(
+                    LOG_T,
+                    format!(
+                        "The neighboring city speaks protocol version {}, we speak {}. \
+                         Not trading with it",
+                        protocol, PROTOCOL_VERSION
+                    ),
+                    self.id,
+                    world,
+                );
+            }
+            return;
+        }
+
+        if !self.neighbor_compatible {
+            return;
+        }
+
+        match message {
+            RegionMessage::Hello { .. } => unreachable!(),
+            RegionMessage::Request {
+                deal,
+                flow,
+                amount,
+                max_price,
+            } => self.on_request(deal, flow, amount, max_price, world),
+            RegionMessage::Offer {
+                deal,
+                amount,
+                price,
+            } => {
+                if let Some(requested) = Self::take_deal(&mut self.requested, deal) {
+                    let agreed = RegionDeal {
+                        amount,
+                        price,
+                        ..requested
+                    };
+                    if price > requested.price || amount > requested.amount {
+                        send(RegionMessage::Withdraw { deal });
+                    } else if let Flow::Commuters = requested.flow {
+                        // the businesses they work for pay them
+                        self.requested.push(agreed);
+                        send(RegionMessage::Accept { deal });
+                    } else {
+                        self.awaiting_funds.push(agreed);
+                        TreasuryID::global_first(world).request_funds(
+                            amount * price,
+                            self.id_as(),
+                            world,
+                        );
+                    }
+                }
+            }
+            RegionMessage::Decline { deal } => {
+                Self::take_deal(&mut self.requested, deal);
+            }
+            RegionMessage::Accept { deal } => {
+                if let Some(offered) = Self::take_deal(&mut self.offered, deal) {
+                    self.on_accepted(offered, world);
+                }
+            }
+            RegionMessage::Withdraw { deal } => {
+                Self::take_deal(&mut self.offered, deal);
+            }
+            RegionMessage::Deliver { deal, amount } => {
+                if let Some(requested) = Self::take_deal(&mut self.requested, deal) {
+                    self.on_delivered(requested, amount.min(requested.amount), world);
+                }
+            }
+        }
+    }
+
+    fn on_request(
+        &mut self,
+        deal: u32,
+        flow: Flow,
+        amount: f32,
+        max_price: ResourceAmount,
+        _: &mut World,
+    ) {
+        let maybe_offer = match flow {
+            Flow::Freight(resource) => {
+                let already_offered: f32 = self
+                    .offered
+                    .iter()
+                    .filter(|offered| offered.flow == flow)
+                    .map(|offered| offered.amount)
+                    .sum();
+                let available = amount_to_f32(self.survey.total_stock(resource)) - already_offered;
+                let price = trade_price(resource).expect("Freight should be a traded good")
+                    * REGIONAL_DISCOUNT;
+
+                if available >= MIN_FREIGHT && price <= max_price {
+                    Some((available.min(amount), price))
+                } else {
+                    None
+                }
+            }
+            // our residents only commute for at least what the outside world pays
+            Flow::Commuters => {
+                if max_price >= WAGE_PER_SHIFT && amount >= 1.0 {
+                    Some((amount.floor().min(MAX_COMMUTERS_PER_DEAL as f32), max_price))
+                } else {
+                    None
+                }
+            }
+            Flow::Utility(utility) => {
+                let already_offered: f32 = self
+                    .offered
+                    .iter()
+                    .filter(|offered| offered.flow == flow)
+                    .map(|offered| offered.amount)
+                    .sum();
+                let available = self.survey.utility_balance(utility)
+                    - self.utility_exports[utility as usize]
+                    - already_offered;
+
+                if available >= MIN_UTILITY && UTILITY_PRICE <= max_price {
+                    Some((available.min(amount), UTILITY_PRICE))
+                } else {
+                    None
+                }
+            }
+        };
+
+        if let Some((amount, price)) = maybe_offer {
+            self.offered.push(RegionDeal {
+                id: deal,
+                flow,
+                amount,
+                price,
+            });
+            send(RegionMessage::Offer {
+                deal,
+                amount,
+                price,
+            });
+        } else {
+            send(RegionMessage::Decline { deal });
+        }
+    }
+
+    fn on_accepted(&mut self, offered: RegionDeal, world: &mut World) {
+        match offered.flow {
+            Flow::Freight(resource) => {
+                let shipped = to_amount(offered.amount);
+                for (gateway, amount) in self.survey.distribute(resource, shipped) {
+                    gateway.ship_freight(resource, amount, world);
+                }
+                TreasuryID::global_first(world).deposit(offered.amount * offered.price, world);
+                info(
+                    LOG_T,
+                    format!(
+                        "Shipped {:.0} {} to the neighboring city for {:.0}",
+                        offered.amount,
+                        resource,
+                        offered.amount * offered.price
+                    ),
+                    self.id,
+                    world,
+                );
+            }
+            Flow::Commuters => {
+                self.n_agreed_commuters += offered.amount as u32;
+                self.commuter_wage = offered.price;
+                self.update_regional_jobs(world);
+            }
+            Flow::Utility(utility) => {
+                self.utility_exports[utility as usize] += offered.amount;
+                self.update_regional_utilities(world);
+                TreasuryID::global_first(world).deposit(offered.amount * offered.price, world);
+                info(
+                    LOG_T,
+                    format!(
+                        "Supplying {:.0} units of {:?} to the neighboring city for {:.0}",
+                        offered.amount,
+                        utility,
+                        offered.amount * offered.price
+                    ),
+                    self.id,
+                    world,
+                );
+            }
+        }
+
+        send(RegionMessage::Deliver {
+            deal: offered.id,
+            amount: offered.amount,
+        });
+    }
+
+    fn on_delivered(&mut self, requested: RegionDeal, amount: f32, world: &mut World) {
+        match requested.flow {
+            Flow::Freight(resource) => {
+                for (gateway, amount) in self.survey.distribute(resource, to_amount(-amount)) {
+                    gateway.receive_freight(resource, -amount, world);
+                }
+                info(
+                    LOG_T,
+                    format!(
+                        "Received {:.0} {} from the neighboring city",
+                        amount, resource
+                    ),
+                    self.id,
+                    world,
+                );
+            }
+            Flow::Commuters => {
+                self.host_commuters(amount as u32, world);
+                info(
+                    LOG_T,
+                    format!(
+                        "{:.0} commuters from the neighboring city come to work here",
+                        amount
+                    ),
+                    self.id,
+                    world,
+                );
+            }
+            Flow::Utility(utility) => {
+                self.utility_imports[utility as usize] += amount;
+                self.update_regional_utilities(world);
+                info(
+                    LOG_T,
+                    format!(
+                        "Drawing {:.0} units of {:?} from the neighboring city",
+                        amount, utility
+                    ),
+                    self.id,
+                    world,
+                );
+            }
+        }
+    }
+}
+
+impl FundsRequester for RegionLink {
+    fn on_funds(&mut self, granted: bool, _amount: ResourceAmount, world: &mut World) {
+        if self.awaiting_funds.is_empty() {
+            return;
+        }
+        let agreed = self.awaiting_funds.remove(0);
+
+        if granted {
+            self.requested.push(agreed);
+            send(RegionMessage::Accept { deal: agreed.id });
+        } else {
+            info(
+                LOG_T,
+                format!(
+                    "Not enough funds to buy {:.0} units of {:?} from the neighboring city",
+                    agreed.amount, agreed.flow
+                ),
+                self.id,
+                world,
+            );
+            send(RegionMessage::Withdraw { deal: agreed.id });
+        }
+    }
+}
+
+impl SupplyReportRequester for RegionLink {
+    // the survey is only used at the next negotiation,
+    // by then all shards will have reported
+    fn expect_supply_reports(&mut self, _: u32, _: &mut World) {}
+
+    fn on_supply_report(&mut self, n_offers: &ResourceMap<u32>, _: &mut World) {
+        // only employers with open positions are listed on the market
+        if let Some(&n) = n_offers.get(Resource::Money) {
+            self.survey.n_open_jobs += n;
+        }
+    }
+}
+
+impl Sleeper for RegionLink {
+    fn wake(&mut self, _current_instant: Instant, world: &mut World) {
+        if self.connected && self.neighbor_compatible {
+            self.negotiate(world);
+        }
+
+        self.survey = RegionSurvey::new();
+        MarketID::global_first(world).report_supply(self.id.into(), world);
+        NeighboringTownTradeID::global_broadcast(world).report_stock(self.id, world);
+        UtilityNetworkID::global_first(world).report_balance(self.id, world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<RegionLink>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    RegionLinkID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
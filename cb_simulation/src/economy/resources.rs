@@ -2,7 +2,6 @@
 pub enum Resource {
     Wakefulness,
     Satiety,
-    //Entertainment,
     //Services,
     Money,
     Groceries,
@@ -12,6 +11,19 @@ pub enum Resource {
     BakedGoods,
     Meat,
     DairyGoods,
+    // added later, after the ones above so existing savegames stay readable
+    BuildingMaterials,
+    Construction,
+    Seeds,
+    Electricity,
+    Water,
+    Bicycle,
+    Companionship,
+    Entertainment,
+    Education,
+    Healthcare,
+    Car,
+    Fuel,
     /* Wood,
      *Furniture,
      *TextileGoods,
@@ -32,7 +44,6 @@ impl Resource {
         match self {
             Wakefulness => "How much energy a person has.",
             Satiety => "How little hungry a person is.",
-            // Entertainment => "How entertained a person is.",
             // Services => "How many services a person or business needs.",
             Money => "Money.",
             Groceries => "Mixed food for daily consumption.",
@@ -42,6 +53,18 @@ impl Resource {
             BakedGoods => "Baked Goods",
             Meat => "Meat",
             DairyGoods => "Dairy Goods",
+            BuildingMaterials => "Bricks, timber and concrete for building sites",
+            Construction => "How much work a building site needs until the building stands.",
+            Seeds => "Seed grain and seedlings, sown on fields in spring",
+            Electricity => "Power from the utility network, without it businesses close.",
+            Water => "Running water from the utility network, without it businesses close.",
+            Bicycle => "How well a household is provided with working bicycles.",
+            Companionship => "How much time a person spent with others.",
+            Entertainment => "How entertained a person is.",
+            Education => "How much a person has recently learned.",
+            Healthcare => "How recently a sick person was treated.",
+            Car => "How well a household is provided with a working car.",
+            Fuel => "Fuel for driving, used up on every trip by car.",
             /* Wood => "Wood",
              * Furniture => "Furniture",
              * TextileGoods => "Textile Goods",
@@ -49,12 +72,77 @@ impl Resource {
              * Devices => "Devices", */
         }
     }
+
+    // Money and goods that change hands, as opposed to needs that are continuous.
+    // Totals of these are conserved exactly with the `fixed_point_resources` feature
+    pub fn is_exact(self) -> bool {
+        match self {
+            Money | Groceries | Car | Fuel | Produce | Grain | Flour | BakedGoods | Meat
+            | DairyGoods | BuildingMaterials | Seeds | Bicycle => true,
+            Wakefulness | Satiety | Companionship | Entertainment | Education | Healthcare
+            | Construction | Electricity | Water => false,
+        }
+    }
 }
 
 use compact::{CVec, Compact};
 
+// With the `fixed_point_resources` feature, amounts are integers in fixed point,
+// see `FixedAmount`. What one side of a deal gives is then exactly what the other
+// side gets, money totals don't drift over long games and the result doesn't
+// depend on how a platform rounds floats. Server and browser need to be built
+// with the same choice
+#[cfg(not(feature = "fixed_point_resources"))]
 pub type ResourceAmount = f32;
 
+#[cfg(feature = "fixed_point_resources")]
+pub type ResourceAmount = FixedAmount;
+
+pub use super::fixed_point::{FixedAmount, FIXED_POINT_SCALE};
+
+// For amounts in constants and literals, which can't call `to_amount`
+#[cfg(not(feature = "fixed_point_resources"))]
+macro_rules! amount {
+    ($value:expr) => {
+        $value
+    };
+}
+
+// Rounds half away from zero like `FixedAmount::from`, but without `f64::round`,
+// which can't be used in constants: the truncated double of the scaled value is
+// odd exactly when the fraction is at least a half
+#[cfg(feature = "fixed_point_resources")]
+macro_rules! amount {
+    ($value:expr) => {
+        ::economy::fixed_point::FixedAmount({
+            let doubled =
+                ($value * 2.0 * ::economy::fixed_point::FIXED_POINT_SCALE as f64) as i64;
+            (doubled + doubled.signum()) / 2
+        })
+    };
+}
+
+// For amounts computed from floats, like prices from valuations, and back
+#[cfg(not(feature = "fixed_point_resources"))]
+pub fn to_amount(value: f32) -> ResourceAmount {
+    value
+}
+
+#[cfg(not(feature = "fixed_point_resources"))]
+pub fn amount_to_f32(amount: ResourceAmount) -> f32 {
+    amount
+}
+
+#[cfg(feature = "fixed_point_resources")]
+pub fn to_amount(value: f32) -> ResourceAmount {
+    FixedAmount::from_f32(value)
+}
+
+#[cfg(feature = "fixed_point_resources")]
+pub fn amount_to_f32(amount: ResourceAmount) -> f32 {
+    amount.to_f32()
+}
+
 #[derive(Compact, Clone, Debug, Serialize, Deserialize)]
 pub struct Entry<AssociatedValue: Compact>(pub Resource, pub AssociatedValue);
 
@@ -152,13 +240,13 @@ pub type Inventory = ResourceMap<ResourceAmount>;
 impl Inventory {
     pub fn give_to(&self, target: &mut Inventory) {
         for &Entry(resource, delta) in self.iter() {
-            *(target.mut_entry_or(resource, 0.0)) += delta;
+            *(target.mut_entry_or(resource, amount!(0.0))) += delta;
         }
     }
 
     pub fn take_from(&self, target: &mut Inventory) {
         for &Entry(resource, delta) in self.iter() {
-            *(target.mut_entry_or(resource, 0.0)) -= delta;
+            *(target.mut_entry_or(resource, amount!(0.0))) -= delta;
         }
     }
 
@@ -170,9 +258,9 @@ impl Inventory {
     ) {
         for &Entry(resource, delta) in self.iter() {
             if is_shared(resource) {
-                *(shared.mut_entry_or(resource, 0.0)) += delta;
+                *(shared.mut_entry_or(resource, amount!(0.0))) += delta;
             } else {
-                *(private.mut_entry_or(resource, 0.0)) += delta;
+                *(private.mut_entry_or(resource, amount!(0.0))) += delta;
             }
         }
     }
@@ -185,9 +273,9 @@ impl Inventory {
     ) {
         for &Entry(resource, delta) in self.iter() {
             if is_shared(resource) {
-                *(shared.mut_entry_or(resource, 0.0)) -= delta;
+                *(shared.mut_entry_or(resource, amount!(0.0))) -= delta;
             } else {
-                *(private.mut_entry_or(resource, 0.0)) -= delta;
+                *(private.mut_entry_or(resource, amount!(0.0))) -= delta;
             }
         }
     }
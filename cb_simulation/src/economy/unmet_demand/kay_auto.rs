@@ -0,0 +1,308 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct HotspotRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for HotspotRequesterID {}
+impl Clone for HotspotRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for HotspotRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "HotspotRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for HotspotRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for HotspotRequesterID {
+    fn eq(&self, other: &HotspotRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for HotspotRequesterID {}
+
+pub struct HotspotRequesterRepresentative;
+
+impl ActorOrActorTrait for HotspotRequesterRepresentative {
+    type ID = HotspotRequesterID;
+}
+
+impl TypedID for HotspotRequesterID {
+    type Target = HotspotRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        HotspotRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + HotspotRequester> TraitIDFrom<Act> for HotspotRequesterID {}
+
+impl HotspotRequesterID {
+    pub fn on_hotspot(self, resource: Resource, location: RoughLocationID, world: &mut World) {
+        world.send(self.as_raw(), MSG_HotspotRequester_on_hotspot(resource, location));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<HotspotRequesterRepresentative>();
+        system.register_trait_message::<MSG_HotspotRequester_on_hotspot>();
+    }
+
+    pub fn register_implementor<Act: Actor + HotspotRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, HotspotRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_HotspotRequester_on_hotspot(resource, location), instance, world| {
+                instance.on_hotspot(resource, location, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HotspotRequester_on_hotspot(pub Resource, pub RoughLocationID);
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct UnmetDemandUIID {
+    _raw_id: RawID
+}
+
+impl Copy for UnmetDemandUIID {}
+impl Clone for UnmetDemandUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for UnmetDemandUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "UnmetDemandUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for UnmetDemandUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for UnmetDemandUIID {
+    fn eq(&self, other: &UnmetDemandUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for UnmetDemandUIID {}
+
+pub struct UnmetDemandUIRepresentative;
+
+impl ActorOrActorTrait for UnmetDemandUIRepresentative {
+    type ID = UnmetDemandUIID;
+}
+
+impl TypedID for UnmetDemandUIID {
+    type Target = UnmetDemandUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        UnmetDemandUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + UnmetDemandUI> TraitIDFrom<Act> for UnmetDemandUIID {}
+
+impl UnmetDemandUIID {
+    pub fn on_unmet_demand(self, by_district: CDict < District , ResourceMap < u32 > >, shortages: CVec < Resource >, world: &mut World) {
+        world.send(self.as_raw(), MSG_UnmetDemandUI_on_unmet_demand(by_district, shortages));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<UnmetDemandUIRepresentative>();
+        system.register_trait_message::<MSG_UnmetDemandUI_on_unmet_demand>();
+    }
+
+    pub fn register_implementor<Act: Actor + UnmetDemandUI>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, UnmetDemandUIRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_UnmetDemandUI_on_unmet_demand(ref by_district, ref shortages), instance, world| {
+                instance.on_unmet_demand(by_district, shortages, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UnmetDemandUI_on_unmet_demand(pub CDict < District , ResourceMap < u32 > >, pub CVec < Resource >);
+
+impl Actor for UnmetDemand {
+    type ID = UnmetDemandID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct UnmetDemandID {
+    _raw_id: RawID
+}
+
+impl Copy for UnmetDemandID {}
+impl Clone for UnmetDemandID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for UnmetDemandID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "UnmetDemandID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for UnmetDemandID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for UnmetDemandID {
+    fn eq(&self, other: &UnmetDemandID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for UnmetDemandID {}
+
+impl TypedID for UnmetDemandID {
+    type Target = UnmetDemand;
+
+    fn from_raw(id: RawID) -> Self {
+        UnmetDemandID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl UnmetDemandID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = UnmetDemandID::from_raw(world.allocate_instance_id::<UnmetDemand>());
+        let swarm = world.local_broadcast::<UnmetDemand>();
+        world.send(swarm, MSG_UnmetDemand_spawn(id, time));
+        id
+    }
+    
+    pub fn record(self, resource: Resource, location: RoughLocationID, world: &mut World) {
+        world.send(self.as_raw(), MSG_UnmetDemand_record(resource, location));
+    }
+    
+    pub fn report_recent(self, requester: MigrationID, world: &mut World) {
+        world.send(self.as_raw(), MSG_UnmetDemand_report_recent(requester));
+    }
+    
+    pub fn get_unmet_demand(self, requester: UnmetDemandUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_UnmetDemand_get_unmet_demand(requester));
+    }
+    
+    pub fn find_hotspot(self, resource: Resource, requester: HotspotRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_UnmetDemand_find_hotspot(resource, requester));
+    }
+    
+    pub fn find_biggest_gap(self, candidates: CVec < Resource >, min_count: u32, requester: HotspotRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_UnmetDemand_find_biggest_gap(candidates, min_count, requester));
+    }
+    
+    pub fn record_shortage(self, resource: Resource, world: &mut World) {
+        world.send(self.as_raw(), MSG_UnmetDemand_record_shortage(resource));
+    }
+    
+    pub fn shortage_resolved(self, resource: Resource, world: &mut World) {
+        world.send(self.as_raw(), MSG_UnmetDemand_shortage_resolved(resource));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UnmetDemand_spawn(pub UnmetDemandID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UnmetDemand_record(pub Resource, pub RoughLocationID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UnmetDemand_report_recent(pub MigrationID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UnmetDemand_get_unmet_demand(pub UnmetDemandUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UnmetDemand_find_hotspot(pub Resource, pub HotspotRequesterID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UnmetDemand_find_biggest_gap(pub CVec < Resource >, pub u32, pub HotspotRequesterID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UnmetDemand_record_shortage(pub Resource);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UnmetDemand_shortage_resolved(pub Resource);
+
+impl Into<SleeperID> for UnmetDemandID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<PositionRequesterID> for UnmetDemandID {
+    fn into(self) -> PositionRequesterID {
+        PositionRequesterID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    HotspotRequesterID::register_trait(system);
+    UnmetDemandUIID::register_trait(system);
+    SleeperID::register_implementor::<UnmetDemand>(system);
+    PositionRequesterID::register_implementor::<UnmetDemand>(system);
+    system.add_spawner::<UnmetDemand, _, _>(
+        |&MSG_UnmetDemand_spawn(id, time), world| {
+            UnmetDemand::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<UnmetDemand, _, _>(
+        |&MSG_UnmetDemand_record(resource, location), instance, world| {
+            instance.record(resource, location, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UnmetDemand, _, _>(
+        |&MSG_UnmetDemand_report_recent(requester), instance, world| {
+            instance.report_recent(requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UnmetDemand, _, _>(
+        |&MSG_UnmetDemand_get_unmet_demand(requester), instance, world| {
+            instance.get_unmet_demand(requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UnmetDemand, _, _>(
+        |&MSG_UnmetDemand_find_hotspot(resource, requester), instance, world| {
+            instance.find_hotspot(resource, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UnmetDemand, _, _>(
+        |&MSG_UnmetDemand_find_biggest_gap(ref candidates, min_count, requester), instance, world| {
+            instance.find_biggest_gap(candidates, min_count, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UnmetDemand, _, _>(
+        |&MSG_UnmetDemand_record_shortage(resource), instance, world| {
+            instance.record_shortage(resource, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UnmetDemand, _, _>(
+        |&MSG_UnmetDemand_shortage_resolved(resource), instance, world| {
+            instance.shortage_resolved(resource, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
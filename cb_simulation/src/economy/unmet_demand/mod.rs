@@ -0,0 +1,203 @@
+use kay::{ActorSystem, World, Actor};
+use compact::{CVec, CDict};
+use descartes::P2;
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Duration, Instant};
+use super::resources::{Resource, ResourceMap, Entry};
+use transport::pathfinding::{RoughLocationID, PositionRequester, PositionRequesterID};
+use super::migration::MigrationID;
+
+// side length of the square grid cells that statistics are aggregated in
+const DISTRICT_SIZE: f32 = 1000.0;
+// counts per district are halved this often, so that districts that are
+// supplied again are seen to recover
+const DECAY_INTERVAL: Duration = Duration(24 * 60 * 60);
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub struct District(pub i32, pub i32);
+
+impl District {
+    pub fn containing(position: P2) -> District {
+        District(
+            (position.x / DISTRICT_SIZE).floor() as i32,
+            (position.y / DISTRICT_SIZE).floor() as i32,
+        )
+    }
+}
+
+// Anyone who wants to know where a resource is lacking the most
+pub trait HotspotRequester {
+    fn on_hotspot(&mut self, resource: Resource, location: RoughLocationID, world: &mut World);
+}
+
+pub trait UnmetDemandUI {
+    fn on_unmet_demand(
+        &mut self,
+        by_district: &CDict<District, ResourceMap<u32>>,
+        shortages: &CVec<Resource>,
+        world: &mut World,
+    );
+}
+
+#[derive(Compact, Clone)]
+pub struct UnmetDemand {
+    id: UnmetDemandID,
+    by_district: CDict<District, ResourceMap<u32>>,
+    // the most recent location in each district that lacked something,
+    // usually somebody's home
+    latest_location_by_district: CDict<District, RoughLocationID>,
+    awaiting_position: CVec<(RoughLocationID, Resource)>,
+    // resources that currently have no formal supply at all
+    shortages: CVec<Resource>,
+    n_recorded_since_report: u32,
+    // since entrepreneurs last looked for gaps in the market
+    recent_by_resource: ResourceMap<u32>,
+}
+
+impl UnmetDemand {
+    pub fn spawn(id: UnmetDemandID, time: TimeID, world: &mut World) -> UnmetDemand {
+        time.wake_up_every(DECAY_INTERVAL, id.into(), world);
+
+        UnmetDemand {
+            id,
+            by_district: CDict::new(),
+            latest_location_by_district: CDict::new(),
+            awaiting_position: CVec::new(),
+            shortages: CVec::new(),
+            n_recorded_since_report: 0,
+            recent_by_resource: ResourceMap::new(),
+        }
+    }
+
+    pub fn record(&mut self, resource: Resource, location: RoughLocationID, world: &mut World) {
+        if !self
+            .awaiting_position
+            .iter()
+            .any(|&(pending_location, _)| pending_location == location)
+        {
+            location.resolve_as_position(self.id_as(), location, world);
+        }
+        self.awaiting_position.push((location, resource));
+        self.n_recorded_since_report += 1;
+        *self.recent_by_resource.mut_entry_or(resource, 0) += 1;
+    }
+
+    pub fn report_recent(&mut self, requester: MigrationID, world: &mut World) {
+        requester.on_recent_unmet_demand(self.n_recorded_since_report, world);
+        self.n_recorded_since_report = 0;
+    }
+
+    pub fn get_unmet_demand(&mut self, requester: UnmetDemandUIID, world: &mut World) {
+        requester.on_unmet_demand(self.by_district.clone(), self.shortages.clone(), world);
+    }
+
+    pub fn find_hotspot(
+        &mut self,
+        resource: Resource,
+        requester: HotspotRequesterID,
+        world: &mut World,
+    ) {
+        let maybe_hotspot = self
+            .by_district
+            .pairs()
+            .filter_map(|(district, counts)| counts.get(resource).map(|&count| (count, *district)))
+            .max_by_key(|&(count, _)| count)
+            .and_then(|(_, district)| self.latest_location_by_district.get(district).cloned());
+
+        if let Some(hotspot) = maybe_hotspot {
+            requester.on_hotspot(resource, hotspot, world);
+        }
+    }
+
+    // Finds where the candidate that was lacking the most recently is lacking
+    // the most, if it was lacking often enough. Starts counting anew
+    pub fn find_biggest_gap(
+        &mut self,
+        candidates: &CVec<Resource>,
+        min_count: u32,
+        requester: HotspotRequesterID,
+        world: &mut World,
+    ) {
+        let maybe_gap = candidates
+            .iter()
+            .filter_map(|&resource| {
+                self.recent_by_resource
+                    .get(resource)
+                    .map(|&count| (count, resource))
+            })
+            .filter(|&(count, _)| count >= min_count)
+            .max_by_key(|&(count, _)| count)
+            .map(|(_, resource)| resource);
+
+        self.recent_by_resource = ResourceMap::new();
+
+        if let Some(resource) = maybe_gap {
+            self.find_hotspot(resource, requester, world);
+        }
+    }
+
+    pub fn record_shortage(&mut self, resource: Resource, _: &mut World) {
+        if !self.shortages.contains(&resource) {
+            self.shortages.push(resource);
+        }
+    }
+
+    pub fn shortage_resolved(&mut self, resource: Resource, _: &mut World) {
+        self.shortages.retain(|&r| r != resource);
+    }
+}
+
+impl Sleeper for UnmetDemand {
+    fn wake(&mut self, _current_instant: Instant, _: &mut World) {
+        let mut decayed = CDict::new();
+
+        for (district, counts) in self.by_district.pairs() {
+            let decayed_counts: ResourceMap<u32> = counts
+                .iter()
+                .filter(|&&Entry(_, count)| count > 1)
+                .map(|&Entry(resource, count)| (resource, count / 2))
+                .collect();
+
+            if decayed_counts.is_empty() {
+                self.latest_location_by_district.remove(*district);
+            } else {
+                decayed.insert(*district, decayed_counts);
+            }
+        }
+
+        self.by_district = decayed;
+    }
+}
+
+impl PositionRequester for UnmetDemand {
+    fn position_resolved(&mut self, rough_location: RoughLocationID, position: P2, _: &mut World) {
+        let district = District::containing(position);
+        let mut counts = self.by_district.get(district).cloned().unwrap_or_default();
+
+        for &(_, resource) in self
+            .awaiting_position
+            .iter()
+            .filter(|&&(pending_location, _)| pending_location == rough_location)
+        {
+            *counts.mut_entry_or(resource, 0) += 1;
+        }
+
+        self.by_district.insert(district, counts);
+        self.latest_location_by_district
+            .insert(district, rough_location);
+        self.awaiting_position
+            .retain(|&(pending_location, _)| pending_location != rough_location);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<UnmetDemand>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    UnmetDemandID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,248 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for UtilityNetwork {
+    type ID = UtilityNetworkID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct UtilityNetworkID {
+    _raw_id: RawID
+}
+
+impl Copy for UtilityNetworkID {}
+impl Clone for UtilityNetworkID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for UtilityNetworkID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "UtilityNetworkID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for UtilityNetworkID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for UtilityNetworkID {
+    fn eq(&self, other: &UtilityNetworkID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for UtilityNetworkID {}
+
+impl TypedID for UtilityNetworkID {
+    type Target = UtilityNetwork;
+
+    fn from_raw(id: RawID) -> Self {
+        UtilityNetworkID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl UtilityNetworkID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = UtilityNetworkID::from_raw(world.allocate_instance_id::<UtilityNetwork>());
+        let swarm = world.local_broadcast::<UtilityNetwork>();
+        world.send(swarm, MSG_UtilityNetwork_spawn(id, time));
+        id
+    }
+    
+    pub fn connect(self, building: BuildingID, utility: Utility, position: P2, demand: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_UtilityNetwork_connect(building, utility, position, demand));
+    }
+    
+    pub fn set_demand(self, building: BuildingID, utility: Utility, demand: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_UtilityNetwork_set_demand(building, utility, demand));
+    }
+    
+    pub fn disconnect(self, building: BuildingID, world: &mut World) {
+        world.send(self.as_raw(), MSG_UtilityNetwork_disconnect(building));
+    }
+    
+    pub fn add_supply(self, supplier: HouseholdID, site: RoughLocationID, utility: Utility, capacity: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_UtilityNetwork_add_supply(supplier, site, utility, capacity));
+    }
+    
+    pub fn set_supply_capacity(self, supplier: HouseholdID, utility: Utility, capacity: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_UtilityNetwork_set_supply_capacity(supplier, utility, capacity));
+    }
+    
+    pub fn remove_supply(self, supplier: HouseholdID, world: &mut World) {
+        world.send(self.as_raw(), MSG_UtilityNetwork_remove_supply(supplier));
+    }
+    
+    pub fn break_main(self, utility: Utility, position: P2, until: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_UtilityNetwork_break_main(utility, position, until));
+    }
+    
+    pub fn add_conduit(self, conduit: ConduitID, utility: Utility, path: LinePath, world: &mut World) {
+        world.send(self.as_raw(), MSG_UtilityNetwork_add_conduit(conduit, utility, path));
+    }
+    
+    pub fn remove_conduit(self, conduit: ConduitID, world: &mut World) {
+        world.send(self.as_raw(), MSG_UtilityNetwork_remove_conduit(conduit));
+    }
+    
+    pub fn set_exports(self, exported: CVec < f32 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_UtilityNetwork_set_exports(exported));
+    }
+    
+    pub fn report_balance(self, link: RegionLinkID, world: &mut World) {
+        world.send(self.as_raw(), MSG_UtilityNetwork_report_balance(link));
+    }
+    
+    pub fn utility_bill_paid(self, utility: Utility, amount: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_UtilityNetwork_utility_bill_paid(utility, amount));
+    }
+    
+    pub fn set_upgrade_subsidy(self, origin: Origin, share: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_UtilityNetwork_set_upgrade_subsidy(origin, share));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityNetwork_spawn(pub UtilityNetworkID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityNetwork_connect(pub BuildingID, pub Utility, pub P2, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityNetwork_set_demand(pub BuildingID, pub Utility, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityNetwork_disconnect(pub BuildingID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityNetwork_add_supply(pub HouseholdID, pub RoughLocationID, pub Utility, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityNetwork_set_supply_capacity(pub HouseholdID, pub Utility, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityNetwork_remove_supply(pub HouseholdID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityNetwork_break_main(pub Utility, pub P2, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityNetwork_add_conduit(pub ConduitID, pub Utility, pub LinePath);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityNetwork_remove_conduit(pub ConduitID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityNetwork_set_exports(pub CVec < f32 >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityNetwork_report_balance(pub RegionLinkID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityNetwork_utility_bill_paid(pub Utility, pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UtilityNetwork_set_upgrade_subsidy(pub Origin, pub f32);
+
+impl Into<PositionRequesterID> for UtilityNetworkID {
+    fn into(self) -> PositionRequesterID {
+        PositionRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for UtilityNetworkID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    PositionRequesterID::register_implementor::<UtilityNetwork>(system);
+    SleeperID::register_implementor::<UtilityNetwork>(system);
+    system.add_spawner::<UtilityNetwork, _, _>(
+        |&MSG_UtilityNetwork_spawn(id, time), world| {
+            UtilityNetwork::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<UtilityNetwork, _, _>(
+        |&MSG_UtilityNetwork_connect(building, utility, position, demand), instance, world| {
+            instance.connect(building, utility, position, demand, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UtilityNetwork, _, _>(
+        |&MSG_UtilityNetwork_set_demand(building, utility, demand), instance, world| {
+            instance.set_demand(building, utility, demand, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UtilityNetwork, _, _>(
+        |&MSG_UtilityNetwork_disconnect(building), instance, world| {
+            instance.disconnect(building, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UtilityNetwork, _, _>(
+        |&MSG_UtilityNetwork_add_supply(supplier, site, utility, capacity), instance, world| {
+            instance.add_supply(supplier, site, utility, capacity, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UtilityNetwork, _, _>(
+        |&MSG_UtilityNetwork_set_supply_capacity(supplier, utility, capacity), instance, world| {
+            instance.set_supply_capacity(supplier, utility, capacity, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UtilityNetwork, _, _>(
+        |&MSG_UtilityNetwork_remove_supply(supplier), instance, world| {
+            instance.remove_supply(supplier, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UtilityNetwork, _, _>(
+        |&MSG_UtilityNetwork_break_main(utility, position, until), instance, world| {
+            instance.break_main(utility, position, until, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UtilityNetwork, _, _>(
+        |&MSG_UtilityNetwork_add_conduit(conduit, utility, ref path), instance, world| {
+            instance.add_conduit(conduit, utility, path, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UtilityNetwork, _, _>(
+        |&MSG_UtilityNetwork_remove_conduit(conduit), instance, world| {
+            instance.remove_conduit(conduit, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UtilityNetwork, _, _>(
+        |&MSG_UtilityNetwork_set_exports(ref exported), instance, world| {
+            instance.set_exports(exported, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UtilityNetwork, _, _>(
+        |&MSG_UtilityNetwork_report_balance(link), instance, world| {
+            instance.report_balance(link, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UtilityNetwork, _, _>(
+        |&MSG_UtilityNetwork_utility_bill_paid(utility, amount), instance, world| {
+            instance.utility_bill_paid(utility, amount, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<UtilityNetwork, _, _>(
+        |&MSG_UtilityNetwork_set_upgrade_subsidy(origin, share), instance, world| {
+            instance.set_upgrade_subsidy(origin, share, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
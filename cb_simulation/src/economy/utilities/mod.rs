@@ -0,0 +1,551 @@
+// Electricity and water don't travel on trips like other resources, they are
+// delivered through lines and pipes laid along the roads that every lot faces.
+// Utility companies and the neighboring towns feed capacity into the network,
+// standing buildings draw from it. When there is less capacity than demand,
+// supply reaches outward from the plants until it runs out and everything
+// further away is cut off. What the cut off buildings lack is recorded as
+// unmet demand, so that new utility companies are founded like any other business.
+// Supplied buildings are billed daily for what they draw, which is passed on
+// to the suppliers and makes efficiency upgrades worth it for building owners.
+// Heating and cooling make buildings draw more in snow and heat, see `weather`.
+// A broken main cuts off the buildings around it until it is repaired,
+// without that counting as demand that new suppliers could meet.
+// Conduits laid underground (see `land_use::underground`) carry supply from
+// the plants they reach to wherever they go, and keep buildings near them
+// supplied when a main breaks.
+// Capacity left over after balancing can be promised to a neighboring city
+// connected through the region link (see `region`), which takes it out of
+// what the next balancings can hand out here
+use kay::{ActorSystem, World, TypedID, Actor};
+use compact::{CVec, CHashMap};
+use descartes::{N, P2, LinePath};
+use ordered_float::OrderedFloat;
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Duration};
+use cb_util::log::info;
+use cb_util::session::{permits, Permission, Origin};
+use land_use::buildings::BuildingID;
+use land_use::underground::ConduitID;
+use transport::pathfinding::{RoughLocationID, PositionRequester, PositionRequesterID};
+use super::resources::{Resource, ResourceAmount};
+use super::market::Deal;
+use super::households::{HouseholdID, MemberIdx};
+use super::unmet_demand::UnmetDemandID;
+use super::region::RegionLinkID;
+use environment::weather::Weather;
+const LOG_T: &str = "Utilities";
+
+const BALANCE_INTERVAL: Duration = Duration(10 * 60);
+const BILLING_INTERVAL: Duration = Duration(24 * 60 * 60);
+// how far from a broken main buildings lose supply
+const MAIN_BREAK_RADIUS: f32 = 80.0;
+// how far from a conduit buildings and plants are connected to it
+const CONDUIT_REACH: N = 40.0;
+// conduits of the same utility join where one ends or bends this close to another
+const CONDUIT_JOIN_DISTANCE: N = 3.0;
+// what a building pays each day for every unit it draws
+pub const PRICE_PER_UNIT: ResourceAmount = amount!(3.0);
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Utility {
+    Electricity,
+    Water,
+}
+
+pub const ALL_UTILITIES: [Utility; 2] = [Utility::Electricity, Utility::Water];
+
+impl Utility {
+    pub fn resource(self) -> Resource {
+        match self {
+            Utility::Electricity => Resource::Electricity,
+            Utility::Water => Resource::Water,
+        }
+    }
+}
+
+// Investments of building owners that lower what their building draws for good
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum EfficiencyUpgrade {
+    Insulation,
+    Solar,
+}
+
+pub const ALL_UPGRADES: [EfficiencyUpgrade; 2] =
+    [EfficiencyUpgrade::Insulation, EfficiencyUpgrade::Solar];
+
+impl EfficiencyUpgrade {
+    // share of the demand for the utility that the upgrade saves
+    pub fn saving(self, utility: Utility) -> f32 {
+        match (self, utility) {
+            (EfficiencyUpgrade::Insulation, Utility::Electricity) => 0.3,
+            (EfficiencyUpgrade::Solar, Utility::Electricity) => 0.4,
+            (_, Utility::Water) => 0.0,
+        }
+    }
+
+    // for every unit of demand a building has without any upgrades
+    pub fn cost_per_unit(self) -> ResourceAmount {
+        match self {
+            EfficiencyUpgrade::Insulation => amount!(30.0),
+            EfficiencyUpgrade::Solar => amount!(60.0),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Connection {
+    building: BuildingID,
+    position: P2,
+    demand: f32,
+    supplied: bool,
+}
+
+#[derive(Copy, Clone)]
+pub struct Supply {
+    supplier: HouseholdID,
+    site: RoughLocationID,
+    capacity: f32,
+}
+
+#[derive(Copy, Clone)]
+pub struct MainBreak {
+    utility: Utility,
+    position: P2,
+    until: Instant,
+}
+
+#[derive(Compact, Clone)]
+pub struct ConduitLine {
+    conduit: ConduitID,
+    utility: Utility,
+    path: LinePath,
+}
+
+// Conduits that reach a plant, and all conduits joined to those
+fn fed_conduits<'a>(
+    conduits: &'a CVec<ConduitLine>,
+    utility: Utility,
+    supply_positions: &[P2],
+) -> Vec<&'a LinePath> {
+    let paths = conduits
+        .iter()
+        .filter(|line| line.utility == utility)
+        .map(|line| &line.path)
+        .collect::<Vec<_>>();
+    let joined = |a: &LinePath, b: &LinePath| {
+        a.points
+            .iter()
+            .any(|&point| b.distance_to(point) < CONDUIT_JOIN_DISTANCE)
+            || b.points
+                .iter()
+                .any(|&point| a.distance_to(point) < CONDUIT_JOIN_DISTANCE)
+    };
+
+    let mut fed = paths
+        .iter()
+        .map(|path| {
+            supply_positions
+                .iter()
+                .any(|&position| path.distance_to(position) < CONDUIT_REACH)
+        })
+        .collect::<Vec<_>>();
+
+    loop {
+        let mut newly_fed = false;
+        for i in 0..paths.len() {
+            if !fed[i] && (0..paths.len()).any(|j| fed[j] && joined(paths[i], paths[j])) {
+                fed[i] = true;
+                newly_fed = true;
+            }
+        }
+        if !newly_fed {
+            break;
+        }
+    }
+
+    paths
+        .into_iter()
+        .zip(fed)
+        .filter_map(|(path, fed)| if fed { Some(path) } else { None })
+        .collect()
+}
+
+#[derive(Compact, Clone)]
+pub struct UtilityGrid {
+    connections: CVec<Connection>,
+    supplies: CVec<Supply>,
+    // bills paid since the suppliers were last paid
+    revenue: ResourceAmount,
+    // promised to the neighboring city
+    exported: f32,
+    // as of the last balancing: capacity nobody here drew,
+    // or demand that couldn't be supplied
+    surplus: f32,
+    shortfall: f32,
+}
+
+impl UtilityGrid {
+    fn new() -> UtilityGrid {
+        UtilityGrid {
+            connections: CVec::new(),
+            supplies: CVec::new(),
+            revenue: amount!(0.0),
+            exported: 0.0,
+            surplus: 0.0,
+            shortfall: 0.0,
+        }
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct UtilityNetwork {
+    id: UtilityNetworkID,
+    // in the order of `ALL_UTILITIES`
+    grids: CVec<UtilityGrid>,
+    supply_positions: CHashMap<RoughLocationID, P2>,
+    // share of the cost of efficiency upgrades paid by the treasury
+    upgrade_subsidy: f32,
+    next_billing: Instant,
+    main_breaks: CVec<MainBreak>,
+    conduits: CVec<ConduitLine>,
+}
+
+impl UtilityNetwork {
+    pub fn spawn(id: UtilityNetworkID, time: TimeID, world: &mut World) -> UtilityNetwork {
+        time.wake_up_every(BALANCE_INTERVAL, id.into(), world);
+        // conduits outlive a network that starts over
+        ConduitID::global_broadcast(world).reconnect(id, world);
+
+        UtilityNetwork {
+            id,
+            grids: ALL_UTILITIES.iter().map(|_| UtilityGrid::new()).collect(),
+            supply_positions: CHashMap::new(),
+            upgrade_subsidy: 0.0,
+            next_billing: Instant::new(0) + BILLING_INTERVAL,
+            main_breaks: CVec::new(),
+            conduits: CVec::new(),
+        }
+    }
+
+    fn grid_mut(&mut self, utility: Utility) -> &mut UtilityGrid {
+        &mut self.grids[utility as usize]
+    }
+
+    // New connections count as supplied until the next balancing says otherwise
+    pub fn connect(
+        &mut self,
+        building: BuildingID,
+        utility: Utility,
+        position: P2,
+        demand: f32,
+        _: &mut World,
+    ) {
+        self.grid_mut(utility).connections.push(Connection {
+            building,
+            position,
+            demand,
+            supplied: true,
+        });
+    }
+
+    pub fn set_demand(
+        &mut self,
+        building: BuildingID,
+        utility: Utility,
+        demand: f32,
+        _: &mut World,
+    ) {
+        for connection in self.grid_mut(utility).connections.iter_mut() {
+            if connection.building == building {
+                connection.demand = demand;
+            }
+        }
+    }
+
+    pub fn disconnect(&mut self, building: BuildingID, _: &mut World) {
+        for grid in self.grids.iter_mut() {
+            grid.connections
+                .retain(|connection| connection.building != building);
+        }
+    }
+
+    pub fn add_supply(
+        &mut self,
+        supplier: HouseholdID,
+        site: RoughLocationID,
+        utility: Utility,
+        capacity: f32,
+        world: &mut World,
+    ) {
+        self.grid_mut(utility).supplies.push(Supply {
+            supplier,
+            site,
+            capacity,
+        });
+
+        if self.supply_positions.get(site).is_none() {
+            site.resolve_as_position(self.id_as(), site, world);
+        }
+    }
+
+    pub fn set_supply_capacity(
+        &mut self,
+        supplier: HouseholdID,
+        utility: Utility,
+        capacity: f32,
+        _: &mut World,
+    ) {
+        for supply in self.grid_mut(utility).supplies.iter_mut() {
+            if supply.supplier == supplier {
+                supply.capacity = capacity;
+            }
+        }
+    }
+
+    pub fn remove_supply(&mut self, supplier: HouseholdID, _: &mut World) {
+        for grid in self.grids.iter_mut() {
+            grid.supplies.retain(|supply| supply.supplier != supplier);
+        }
+    }
+
+    // Takes effect with the next balancing
+    pub fn break_main(
+        &mut self,
+        utility: Utility,
+        position: P2,
+        until: Instant,
+        world: &mut World,
+    ) {
+        self.main_breaks.push(MainBreak {
+            utility,
+            position,
+            until,
+        });
+        info(
+            LOG_T,
+            format!(
+                "{:?} main broke at ({:.0}, {:.0})",
+                utility, position.x, position.y
+            ),
+            self.id,
+            world,
+        );
+    }
+
+    pub fn add_conduit(
+        &mut self,
+        conduit: ConduitID,
+        utility: Utility,
+        path: &LinePath,
+        _: &mut World,
+    ) {
+        self.conduits.retain(|line| line.conduit != conduit);
+        self.conduits.push(ConduitLine {
+            conduit,
+            utility,
+            path: path.clone(),
+        });
+    }
+
+    pub fn remove_conduit(&mut self, conduit: ConduitID, _: &mut World) {
+        self.conduits.retain(|line| line.conduit != conduit);
+    }
+
+    // Takes effect with the next balancing, in the order of `ALL_UTILITIES`
+    pub fn set_exports(&mut self, exported: &CVec<f32>, _: &mut World) {
+        for (grid, &capacity) in self.grids.iter_mut().zip(exported.iter()) {
+            grid.exported = capacity;
+        }
+    }
+
+    pub fn report_balance(&mut self, link: RegionLinkID, world: &mut World) {
+        for (&utility, grid) in ALL_UTILITIES.iter().zip(self.grids.iter()) {
+            link.on_utility_balance(utility, grid.surplus - grid.shortfall, world);
+        }
+    }
+
+    pub fn utility_bill_paid(&mut self, utility: Utility, amount: ResourceAmount, _: &mut World) {
+        self.grid_mut(utility).revenue += amount;
+    }
+
+    // An ordinance of the city, so only whoever may set policies can change it
+    pub fn set_upgrade_subsidy(&mut self, origin: Origin, share: f32, world: &mut World) {
+        if !permits(origin, Permission::Policies, self.id, world) {
+            return;
+        }
+        self.upgrade_subsidy = share.max(0.0).min(1.0);
+        info(
+            LOG_T,
+            format!(
+                "Efficiency upgrades are now subsidized with {:.0}%",
+                self.upgrade_subsidy * 100.0
+            ),
+            self.id,
+            world,
+        );
+    }
+
+    // Suppliers get what was paid in proportion to their capacity,
+    // then supplied buildings get their next bill
+    fn bill(&mut self, weather: Weather, world: &mut World) {
+        for (&utility, grid) in ALL_UTILITIES.iter().zip(self.grids.iter_mut()) {
+            let total_capacity: f32 = grid.supplies.iter().map(|supply| supply.capacity).sum();
+            if total_capacity > 0.0 && grid.revenue > 0.0 {
+                for supply in grid.supplies.iter() {
+                    supply.supplier.receive_deal(
+                        Deal::new(
+                            Some((
+                                Resource::Money,
+                                grid.revenue * supply.capacity / total_capacity,
+                            )),
+                            Duration(0),
+                        ),
+                        MemberIdx(0),
+                        world,
+                    );
+                }
+                grid.revenue = amount!(0.0);
+            }
+
+            for connection in grid
+                .connections
+                .iter()
+                .filter(|connection| connection.supplied)
+            {
+                connection.building.bill_utility(
+                    utility,
+                    connection.demand * weather.demand_factor(utility) * PRICE_PER_UNIT,
+                    self.upgrade_subsidy,
+                    world,
+                );
+            }
+        }
+    }
+
+    fn balance(&mut self, weather: Weather, world: &mut World) {
+        for (&utility, grid) in ALL_UTILITIES.iter().zip(self.grids.iter_mut()) {
+            let demand_factor = weather.demand_factor(utility);
+            let supply_positions = grid
+                .supplies
+                .iter()
+                .filter_map(|supply| self.supply_positions.get(supply.site).cloned())
+                .collect::<Vec<_>>();
+            let fed_conduits = fed_conduits(&self.conduits, utility, &supply_positions);
+            let near_fed_conduit = |position: P2| {
+                fed_conduits
+                    .iter()
+                    .any(|path| path.distance_to(position) < CONDUIT_REACH)
+            };
+            let breaks = &self.main_breaks;
+            let behind_break = |position: P2| {
+                breaks.iter().any(|main_break| {
+                    main_break.utility == utility
+                        && (main_break.position - position).norm() < MAIN_BREAK_RADIUS
+                }) && !near_fed_conduit(position)
+            };
+            // supply reaches along fed conduits as if they were plants themselves
+            let distance_to_supply = |position: P2| {
+                supply_positions
+                    .iter()
+                    .map(|&supply_position| OrderedFloat((supply_position - position).norm()))
+                    .chain(
+                        fed_conduits
+                            .iter()
+                            .map(|path| OrderedFloat(path.distance_to(position))),
+                    )
+                    .min()
+                    .unwrap_or(OrderedFloat(::std::f32::INFINITY))
+            };
+
+            let mut by_distance = (0..grid.connections.len()).collect::<Vec<_>>();
+            by_distance.sort_by_key(|&idx| distance_to_supply(grid.connections[idx].position));
+
+            let mut capacity_left: f32 = (grid
+                .supplies
+                .iter()
+                .map(|supply| supply.capacity)
+                .sum::<f32>()
+                - grid.exported)
+                .max(0.0);
+            let mut shortfall = 0.0;
+            let mut n_newly_cut_off = 0;
+
+            for idx in by_distance {
+                let connection = &mut grid.connections[idx];
+                let demand = connection.demand * demand_factor;
+                let supplied = if behind_break(connection.position) {
+                    false
+                } else if capacity_left >= demand {
+                    capacity_left -= demand;
+                    true
+                } else {
+                    // nothing reaches past the first building that can't be supplied
+                    capacity_left = 0.0;
+                    shortfall += demand;
+                    UnmetDemandID::global_first(world).record(
+                        utility.resource(),
+                        connection.building.into(),
+                        world,
+                    );
+                    false
+                };
+
+                if supplied != connection.supplied {
+                    connection.supplied = supplied;
+                    connection
+                        .building
+                        .on_utility_supply(utility, supplied, world);
+                    if !supplied {
+                        n_newly_cut_off += 1;
+                    }
+                }
+            }
+
+            grid.surplus = capacity_left;
+            grid.shortfall = shortfall;
+
+            if n_newly_cut_off > 0 {
+                info(
+                    LOG_T,
+                    format!(
+                        "Outage cut off {} more buildings from {:?}",
+                        n_newly_cut_off, utility
+                    ),
+                    self.id,
+                    world,
+                );
+            }
+        }
+    }
+}
+
+impl PositionRequester for UtilityNetwork {
+    fn position_resolved(&mut self, rough_location: RoughLocationID, position: P2, _: &mut World) {
+        self.supply_positions.insert(rough_location, position);
+    }
+}
+
+impl Sleeper for UtilityNetwork {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        let weather = Weather::on(current_instant);
+        self.main_breaks
+            .retain(|main_break| main_break.until > current_instant);
+        self.balance(weather, world);
+        if current_instant >= self.next_billing {
+            self.bill(weather, world);
+            self.next_billing = current_instant + BILLING_INTERVAL;
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<UtilityNetwork>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    UtilityNetworkID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
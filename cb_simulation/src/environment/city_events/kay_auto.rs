@@ -0,0 +1,200 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct CityEventsUIID {
+    _raw_id: RawID
+}
+
+impl Copy for CityEventsUIID {}
+impl Clone for CityEventsUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for CityEventsUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "CityEventsUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for CityEventsUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for CityEventsUIID {
+    fn eq(&self, other: &CityEventsUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for CityEventsUIID {}
+
+pub struct CityEventsUIRepresentative;
+
+impl ActorOrActorTrait for CityEventsUIRepresentative {
+    type ID = CityEventsUIID;
+}
+
+impl TypedID for CityEventsUIID {
+    type Target = CityEventsUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        CityEventsUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + CityEventsUI> TraitIDFrom<Act> for CityEventsUIID {}
+
+impl CityEventsUIID {
+    pub fn on_city_event(self, event: CityEvent, started: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityEventsUI_on_city_event(event, started));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<CityEventsUIRepresentative>();
+        system.register_trait_message::<MSG_CityEventsUI_on_city_event>();
+    }
+
+    pub fn register_implementor<Act: Actor + CityEventsUI>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, CityEventsUIRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_CityEventsUI_on_city_event(event, started), instance, world| {
+                instance.on_city_event(event, started, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityEventsUI_on_city_event(pub CityEvent, pub bool);
+
+impl Actor for CityEvents {
+    type ID = CityEventsID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct CityEventsID {
+    _raw_id: RawID
+}
+
+impl Copy for CityEventsID {}
+impl Clone for CityEventsID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for CityEventsID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "CityEventsID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for CityEventsID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for CityEventsID {
+    fn eq(&self, other: &CityEventsID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for CityEventsID {}
+
+impl TypedID for CityEventsID {
+    type Target = CityEvents;
+
+    fn from_raw(id: RawID) -> Self {
+        CityEventsID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl CityEventsID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = CityEventsID::from_raw(world.allocate_instance_id::<CityEvents>());
+        let swarm = world.local_broadcast::<CityEvents>();
+        world.send(swarm, MSG_CityEvents_spawn(id, time));
+        id
+    }
+    
+    pub fn propose(self, kind: CityEventKind, building: BuildingID, position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityEvents_propose(kind, building, position));
+    }
+    
+    pub fn fire_put_out(self, building: BuildingID, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityEvents_fire_put_out(building));
+    }
+    
+    pub fn get_active_events(self, ui: CityEventsUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityEvents_get_active_events(ui));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityEvents_spawn(pub CityEventsID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityEvents_propose(pub CityEventKind, pub BuildingID, pub P2);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityEvents_fire_put_out(pub BuildingID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityEvents_get_active_events(pub CityEventsUIID);
+
+impl Into<SleeperID> for CityEventsID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<ConfigUserID<DifficultyProfile>> for CityEventsID {
+    fn into(self) -> ConfigUserID<DifficultyProfile> {
+        ConfigUserID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<CalendarListenerID> for CityEventsID {
+    fn into(self) -> CalendarListenerID {
+        CalendarListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    CityEventsUIID::register_trait(system);
+    SleeperID::register_implementor::<CityEvents>(system);
+    ConfigUserID::<DifficultyProfile>::register_implementor::<CityEvents>(system);
+    CalendarListenerID::register_implementor::<CityEvents>(system);
+    system.add_spawner::<CityEvents, _, _>(
+        |&MSG_CityEvents_spawn(id, time), world| {
+            CityEvents::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<CityEvents, _, _>(
+        |&MSG_CityEvents_propose(kind, building, position), instance, world| {
+            instance.propose(kind, building, position, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<CityEvents, _, _>(
+        |&MSG_CityEvents_fire_put_out(building), instance, world| {
+            instance.fire_put_out(building, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<CityEvents, _, _>(
+        |&MSG_CityEvents_get_active_events(ui), instance, world| {
+            instance.get_active_events(ui, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,314 @@
+// Every now and then something happens in the city: a park holds a festival,
+// a building catches fire or a water main breaks, and hot summer days turn
+// into heat waves. Like accidents, every building rolls for itself whether
+// something happens to it, so bigger cities see more. Events don't have
+// effects of their own, they use what is already there: festivals close the
+// roads around them and make everybody want to go out, fires close the roads
+// for the fire engines and are put out by emergency services, broken mains
+// cut off the water around them, and heat waves are the hot weather itself.
+// UIs hear about events as they start and end, so that they can show them
+use kay::{ActorSystem, World, TypedID};
+use compact::{CVec, CHashMap};
+use descartes::P2;
+use cb_time::actors::{CalendarListener, CalendarListenerID, Sleeper, SleeperID, TimeID};
+use cb_time::calendar::{CalendarPeriod, Date};
+use cb_time::units::{Instant, Duration};
+use cb_util::config_manager::{Name, ConfigUser, ConfigUserID};
+use cb_util::log::{info, warn};
+use cb_util::random::{seed, Rng};
+use economy::difficulty::{self, DifficultyProfile};
+use economy::households::household_kinds::family::FamilyID;
+use economy::utilities::{Utility, UtilityNetworkID};
+use land_use::buildings::{BuildingID, BuildingStyle};
+use transport::emergency::EmergencyDispatchID;
+use transport::lane::LaneID;
+use environment::weather::Weather;
+const LOG_T: &str = "City Events";
+
+const CHECK_INTERVAL: Duration = Duration(60 * 60);
+// per building and check, on normal difficulty
+const FIRE_CHANCE: f32 = 0.000_05;
+const MAIN_BREAK_CHANCE: f32 = 0.000_03;
+// per park and check
+const FESTIVAL_CHANCE: f32 = 0.002;
+const FESTIVAL_DURATION: Duration = Duration(6 * 60 * 60);
+const MAIN_REPAIR_DURATION: Duration = Duration(8 * 60 * 60);
+// in case no fire engine ever gets there
+const FIRE_DURATION: Duration = Duration(3 * 60 * 60);
+// how long the roads stay closed for the fire engines
+const FIRE_CLOSURE_DURATION: Duration = Duration(60 * 60);
+const CLOSURE_RADIUS: f32 = 60.0;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum CityEventKind {
+    Festival,
+    Fire,
+    WaterMainBreak,
+    HeatWave,
+}
+
+impl CityEventKind {
+    pub fn description(self) -> &'static str {
+        match self {
+            CityEventKind::Festival => "Festival",
+            CityEventKind::Fire => "Building on fire",
+            CityEventKind::WaterMainBreak => "Water main break",
+            CityEventKind::HeatWave => "Heat wave",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct CityEvent {
+    pub kind: CityEventKind,
+    // heat waves are everywhere
+    pub position: Option<P2>,
+    // heat waves last as long as the heat, fires until they are put out
+    pub until: Option<Instant>,
+    pub building: Option<BuildingID>,
+}
+
+// What, if anything, happens to a building of the given style in this check.
+// Fires and broken mains are more or less frequent depending on the difficulty
+pub fn roll(
+    building: BuildingID,
+    style: BuildingStyle,
+    instant: Instant,
+    disaster_frequency_factor: f32,
+) -> Option<CityEventKind> {
+    if style == BuildingStyle::NeighboringTownConnection {
+        return None;
+    }
+
+    let roll: f32 = seed((instant.ticks(), building, 3u8)).gen();
+    if style == BuildingStyle::Park {
+        if roll < FESTIVAL_CHANCE {
+            Some(CityEventKind::Festival)
+        } else {
+            None
+        }
+    } else if style == BuildingStyle::Field {
+        None
+    } else if roll < FIRE_CHANCE * disaster_frequency_factor {
+        Some(CityEventKind::Fire)
+    } else if roll < (FIRE_CHANCE + MAIN_BREAK_CHANCE) * disaster_frequency_factor
+        && style.utility_demand(Utility::Water) > 0.0
+    {
+        Some(CityEventKind::WaterMainBreak)
+    } else {
+        None
+    }
+}
+
+pub trait CityEventsUI {
+    fn on_city_event(&mut self, event: CityEvent, started: bool, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct CityEvents {
+    id: CityEventsID,
+    current_instant: Instant,
+    active: CVec<CityEvent>,
+    difficulty: CHashMap<Name, DifficultyProfile>,
+}
+
+impl CityEvents {
+    pub fn spawn(id: CityEventsID, time: TimeID, world: &mut World) -> CityEvents {
+        time.wake_up_every(CHECK_INTERVAL, id.into(), world);
+        time.notify_at_start_of(CalendarPeriod::Day, id.into(), world);
+
+        let events = CityEvents {
+            id,
+            current_instant: Instant::new(0),
+            active: CVec::new(),
+            difficulty: CHashMap::new(),
+        };
+        ConfigUser::<DifficultyProfile>::get_initial_config(&events, world);
+        events
+    }
+
+    // Only one event of each kind happens at a time, so that the city
+    // doesn't drown in them
+    pub fn propose(
+        &mut self,
+        kind: CityEventKind,
+        building: BuildingID,
+        position: P2,
+        world: &mut World,
+    ) {
+        if self.active.iter().any(|event| event.kind == kind) {
+            return;
+        }
+
+        let instant = self.current_instant;
+        let until = match kind {
+            CityEventKind::Festival => Some(instant + FESTIVAL_DURATION),
+            CityEventKind::Fire => Some(instant + FIRE_DURATION),
+            CityEventKind::WaterMainBreak => Some(instant + MAIN_REPAIR_DURATION),
+            CityEventKind::HeatWave => None,
+        };
+        self.start(
+            CityEvent {
+                kind,
+                position: Some(position),
+                until,
+                building: Some(building),
+            },
+            world,
+        );
+    }
+
+    fn start(&mut self, event: CityEvent, world: &mut World) {
+        let instant = self.current_instant;
+        match (event.kind, event.position, event.building) {
+            (CityEventKind::Festival, Some(position), _) => {
+                LaneID::global_broadcast(world).close_around(
+                    position,
+                    CLOSURE_RADIUS,
+                    instant + FESTIVAL_DURATION,
+                    world,
+                );
+                FamilyID::global_broadcast(world).hear_of_festival(world);
+            }
+            (CityEventKind::Fire, Some(position), Some(building)) => {
+                LaneID::global_broadcast(world).close_around(
+                    position,
+                    CLOSURE_RADIUS,
+                    instant + FIRE_CLOSURE_DURATION,
+                    world,
+                );
+                EmergencyDispatchID::global_first(world).call(
+                    building.into(),
+                    building.into(),
+                    0,
+                    instant,
+                    world,
+                );
+            }
+            (CityEventKind::WaterMainBreak, Some(position), _) => {
+                UtilityNetworkID::global_first(world).break_main(
+                    Utility::Water,
+                    position,
+                    instant + MAIN_REPAIR_DURATION,
+                    world,
+                );
+            }
+            _ => {}
+        }
+
+        let message = match event.position {
+            Some(position) => format!(
+                "{} at ({:.0}, {:.0})",
+                event.kind.description(),
+                position.x,
+                position.y
+            ),
+            None => format!("{} in the city", event.kind.description()),
+        };
+        if event.kind == CityEventKind::Festival {
+            info(LOG_T, message, self.id, world);
+        } else {
+            warn(LOG_T, message, self.id, world);
+        }
+
+        self.active.push(event);
+        CityEventsUIID::global_broadcast(world).on_city_event(event, true, world);
+    }
+
+    fn end(&mut self, idx: usize, world: &mut World) {
+        let event = self.active.remove(idx);
+        info(
+            LOG_T,
+            format!("{} is over", event.kind.description()),
+            self.id,
+            world,
+        );
+        CityEventsUIID::global_broadcast(world).on_city_event(event, false, world);
+    }
+
+    fn end_kind(&mut self, kind: CityEventKind, world: &mut World) {
+        if let Some(idx) = self.active.iter().position(|event| event.kind == kind) {
+            self.end(idx, world);
+        }
+    }
+
+    pub fn fire_put_out(&mut self, building: BuildingID, world: &mut World) {
+        if let Some(idx) = self.active.iter().position(|event| {
+            event.kind == CityEventKind::Fire && event.building == Some(building)
+        }) {
+            self.end(idx, world);
+        }
+    }
+
+    pub fn get_active_events(&mut self, ui: CityEventsUIID, world: &mut World) {
+        for &event in self.active.iter() {
+            ui.on_city_event(event, true, world);
+        }
+    }
+}
+
+impl Sleeper for CityEvents {
+    fn wake(&mut self, instant: Instant, world: &mut World) {
+        self.current_instant = instant;
+
+        while let Some(idx) = self
+            .active
+            .iter()
+            .position(|event| event.until.map_or(false, |until| instant >= until))
+        {
+            self.end(idx, world);
+        }
+
+        let disaster_frequency_factor =
+            difficulty::current(&self.difficulty).disaster_frequency_factor;
+        BuildingID::global_broadcast(world).roll_city_events(
+            self.id,
+            instant,
+            disaster_frequency_factor,
+            world,
+        );
+    }
+}
+
+impl ConfigUser<DifficultyProfile> for CityEvents {
+    fn local_cache(&mut self) -> &mut CHashMap<Name, DifficultyProfile> {
+        &mut self.difficulty
+    }
+}
+
+// A hot day is a heat wave, until the weather changes
+impl CalendarListener for CityEvents {
+    fn on_new_period(&mut self, _: CalendarPeriod, date: Date, world: &mut World) {
+        let hot = Weather::of(date) == Weather::Heat;
+        let ongoing = self
+            .active
+            .iter()
+            .any(|event| event.kind == CityEventKind::HeatWave);
+
+        if hot && !ongoing {
+            self.start(
+                CityEvent {
+                    kind: CityEventKind::HeatWave,
+                    position: None,
+                    until: None,
+                    building: None,
+                },
+                world,
+            );
+        } else if !hot && ongoing {
+            self.end_kind(CityEventKind::HeatWave, world);
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<CityEvents>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    CityEventsID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
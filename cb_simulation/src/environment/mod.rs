@@ -1,6 +1,13 @@
 use kay::ActorSystem;
 pub mod vegetation;
+pub mod seasons;
+pub mod weather;
+pub mod city_events;
+pub mod water;
 
 pub fn setup(system: &mut ActorSystem) {
     vegetation::setup(system);
+    weather::setup(system);
+    city_events::setup(system);
+    water::setup(system);
 }
@@ -0,0 +1,27 @@
+// The weather, which together with the season decides how well crops grow.
+// Years are as compressed as the lives of people (see `cb_time::calendar`),
+// so a farm sees a couple of harvests while a family sees its children grow up
+use cb_time::units::Instant;
+use cb_time::calendar::{day, years_since_founding};
+pub use cb_time::calendar::Season;
+use cb_util::random::{seed, Rng};
+
+// how fast crops grow, compared to the height of summer
+pub fn growth_rate(season: Season) -> f32 {
+    match season {
+        Season::Spring => 0.7,
+        Season::Summer => 1.0,
+        Season::Autumn => 0.4,
+        Season::Winter => 0.0,
+    }
+}
+
+// How good the weather of a day is for crops, from ruinous at 0.2 to ideal
+// at 1.2. It only depends on the day, so it is the same on every farm of the
+// city and in every run of it. Each year has a character of its own, from
+// droughts to perfect summers, and the days vary around that
+pub fn growing_weather(instant: Instant) -> f32 {
+    let year_quality = seed((years_since_founding(instant), 0u8)).gen_range(0.6, 1.1);
+    let day_variation = seed((day(instant), 1u8)).gen_range(-0.3, 0.2);
+    (year_quality + day_variation).max(0.2).min(1.2)
+}
@@ -9,6 +9,7 @@ use transport::transport_planning::RoadPrototype;
 use land_use::zone_planning::{LotPrototype, LotOccupancy};
 use land_use::buildings::BuildingStyle;
 use land_use::buildings::architecture::footprint_area;
+use environment::water::WaterPrototype;
 use cb_util::random::{seed, Rng};
 use noise::{NoiseFn, BasicMulti, Seedable, MultiFractal};
 
@@ -115,6 +116,10 @@ pub fn calculate_prototypes(
                 kind: CBPrototypeKind::Road(RoadPrototype::PavedArea(ref area)),
                 ..
             } => constructed_areas.push(area.clone()),
+            Prototype {
+                kind: CBPrototypeKind::Water(WaterPrototype { ref area, .. }),
+                ..
+            } => constructed_areas.push(area.clone()),
             Prototype {
                 kind:
                     CBPrototypeKind::Lot(LotPrototype {
@@ -125,7 +130,8 @@ pub fn calculate_prototypes(
                 ..
             } => {
                 constructed_areas.push(footprint_area(lot, style, 5.0));
-                if style == BuildingStyle::Field {
+                // trees line fields and parks
+                if style == BuildingStyle::Field || style == BuildingStyle::Park {
                     let boundary = lot.original_area.primitives[0].boundary.path();
                     let mut pos_along = 0.0;
                     let mut i = 0;
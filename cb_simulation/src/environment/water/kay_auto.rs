@@ -0,0 +1,175 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct WaterUIID {
+    _raw_id: RawID
+}
+
+impl Copy for WaterUIID {}
+impl Clone for WaterUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for WaterUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "WaterUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for WaterUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for WaterUIID {
+    fn eq(&self, other: &WaterUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for WaterUIID {}
+
+pub struct WaterUIRepresentative;
+
+impl ActorOrActorTrait for WaterUIRepresentative {
+    type ID = WaterUIID;
+}
+
+impl TypedID for WaterUIID {
+    type Target = WaterUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        WaterUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + WaterUI> TraitIDFrom<Act> for WaterUIID {}
+
+impl WaterUIID {
+    pub fn on_water_added(self, id: WaterBodyID, proto: WaterPrototype, world: &mut World) {
+        world.send(self.as_raw(), MSG_WaterUI_on_water_added(id, proto));
+    }
+    
+    pub fn on_water_removed(self, id: WaterBodyID, world: &mut World) {
+        world.send(self.as_raw(), MSG_WaterUI_on_water_removed(id));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<WaterUIRepresentative>();
+        system.register_trait_message::<MSG_WaterUI_on_water_added>();
+        system.register_trait_message::<MSG_WaterUI_on_water_removed>();
+    }
+
+    pub fn register_implementor<Act: Actor + WaterUI>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, WaterUIRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_WaterUI_on_water_added(id, ref proto), instance, world| {
+                instance.on_water_added(id, proto, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_WaterUI_on_water_removed(id), instance, world| {
+                instance.on_water_removed(id, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_WaterUI_on_water_added(pub WaterBodyID, pub WaterPrototype);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_WaterUI_on_water_removed(pub WaterBodyID);
+
+impl Actor for WaterBody {
+    type ID = WaterBodyID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct WaterBodyID {
+    _raw_id: RawID
+}
+
+impl Copy for WaterBodyID {}
+impl Clone for WaterBodyID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for WaterBodyID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "WaterBodyID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for WaterBodyID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for WaterBodyID {
+    fn eq(&self, other: &WaterBodyID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for WaterBodyID {}
+
+impl TypedID for WaterBodyID {
+    type Target = WaterBody;
+
+    fn from_raw(id: RawID) -> Self {
+        WaterBodyID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl WaterBodyID {
+    pub fn spawn(proto: WaterPrototype, world: &mut World) -> Self {
+        let id = WaterBodyID::from_raw(world.allocate_instance_id::<WaterBody>());
+        let swarm = world.local_broadcast::<WaterBody>();
+        world.send(swarm, MSG_WaterBody_spawn(id, proto));
+        id
+    }
+    
+    pub fn get_render_info(self, requester: WaterUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_WaterBody_get_render_info(requester));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_WaterBody_spawn(pub WaterBodyID, pub WaterPrototype);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_WaterBody_get_render_info(pub WaterUIID);
+
+impl Into<ConstructableID<CBPrototypeKind>> for WaterBodyID {
+    fn into(self) -> ConstructableID<CBPrototypeKind> {
+        ConstructableID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    WaterUIID::register_trait(system);
+    ConstructableID::<CBPrototypeKind>::register_implementor::<WaterBody>(system);
+    system.add_spawner::<WaterBody, _, _>(
+        |&MSG_WaterBody_spawn(id, ref proto), world| {
+            WaterBody::spawn(id, proto, world)
+        }, false
+    );
+    
+    system.add_handler::<WaterBody, _, _>(
+        |&MSG_WaterBody_get_render_info(requester), instance, world| {
+            instance.get_render_info(requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
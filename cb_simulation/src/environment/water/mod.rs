@@ -0,0 +1,312 @@
+// Lakes, rivers and the coast are part of the plan like everything else, as
+// gestures that are only added when a city is founded or a map is prepared.
+// They are planned first: roads can only cross them as bridges or tunnels,
+// zones and plants leave them out, and living by the water adds to land value
+use kay::{World, Fate, ActorSystem, TypedID};
+use compact::CVec;
+use descartes::{N, P2, V2, Area, Band, LinePath, ClosedLinePath, AreaError, PointContainer,
+RoughEq};
+use planning::{CBPlanManagerID, CBConstructionID, CBPrototypeKind, CBGestureIntent};
+use cb_planning::{Prototype, PrototypeID, PlanHistory, PlanResult, Project, Plan, Gesture,
+GestureID};
+use cb_planning::construction::{Constructable, ConstructableID};
+use land_use::grid_layers::GridLayersID;
+use cb_util::random::{seed, Rng};
+
+// shorelines are smoothed this often, each time cutting every corner
+const SHORELINE_SMOOTHING_ITERATIONS: usize = 3;
+// how far apart the points are that count as waterfront for land value
+const WATERFRONT_SAMPLE_DISTANCE: N = 100.0;
+// roads are checked for touching water at points this far apart
+const ROAD_SAMPLE_DISTANCE: N = 5.0;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum WaterKind {
+    Lake,
+    River,
+    Coast,
+}
+
+#[derive(Compact, Clone, Debug, Serialize, Deserialize)]
+pub enum WaterIntent {
+    // outlined by the gesture points
+    Lake,
+    // of the given width, the gesture points go down its middle
+    River(N),
+    // outlined like a lake, but usually reaching far beyond the map
+    Coast,
+    // a lake and a river where the seed of the gesture puts them, for a new city
+    Natural,
+}
+
+#[derive(Compact, Clone, Debug, Serialize, Deserialize)]
+pub struct WaterPrototype {
+    pub kind: WaterKind,
+    pub area: Area,
+}
+
+impl WaterPrototype {
+    pub fn shorelines<'a>(&'a self) -> impl Iterator<Item = &'a LinePath> + 'a {
+        self.area
+            .primitives
+            .iter()
+            .map(|primitive| primitive.boundary.path())
+    }
+
+    fn waterfront_points(&self) -> CVec<P2> {
+        self.shorelines()
+            .flat_map(|shoreline| {
+                let n_samples = (shoreline.length() / WATERFRONT_SAMPLE_DISTANCE).ceil() as usize;
+                (0..n_samples).map(move |i| shoreline.along(i as N * WATERFRONT_SAMPLE_DISTANCE))
+            })
+            .collect()
+    }
+
+    pub fn construct(
+        &self,
+        _self_id: PrototypeID,
+        report_to: CBConstructionID,
+        world: &mut World,
+    ) -> CVec<ConstructableID<CBPrototypeKind>> {
+        let id = WaterBodyID::spawn(self.clone(), world).into();
+        report_to.action_done(id, world);
+        vec![id].into()
+    }
+
+    pub fn morphable_from(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.area.primitives.len() == other.area.primitives.len()
+            && self
+                .shorelines()
+                .zip(other.shorelines())
+                .all(|(shoreline, other_shoreline)| {
+                    shoreline.points.len() == other_shoreline.points.len()
+                        && shoreline.start().rough_eq_by(other_shoreline.start(), 0.5)
+                })
+    }
+}
+
+pub trait WaterUI {
+    fn on_water_added(&mut self, id: WaterBodyID, proto: &WaterPrototype, world: &mut World);
+    fn on_water_removed(&mut self, id: WaterBodyID, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct WaterBody {
+    id: WaterBodyID,
+    proto: WaterPrototype,
+}
+
+impl WaterBody {
+    pub fn spawn(id: WaterBodyID, proto: &WaterPrototype, world: &mut World) -> WaterBody {
+        let water_body = WaterBody {
+            id,
+            proto: proto.clone(),
+        };
+        water_body.appear(world);
+        water_body
+    }
+
+    fn appear(&self, world: &mut World) {
+        WaterUIID::global_broadcast(world).on_water_added(self.id, self.proto.clone(), world);
+        GridLayersID::global_first(world).add_waterfront(
+            self.id,
+            self.proto.waterfront_points(),
+            world,
+        );
+    }
+
+    fn disappear(&self, world: &mut World) {
+        WaterUIID::global_broadcast(world).on_water_removed(self.id, world);
+        GridLayersID::global_first(world).remove_waterfront(
+            self.id,
+            self.proto.waterfront_points(),
+            world,
+        );
+    }
+
+    pub fn get_render_info(&mut self, requester: WaterUIID, world: &mut World) {
+        requester.on_water_added(self.id, self.proto.clone(), world);
+    }
+}
+
+impl Constructable<CBPrototypeKind> for WaterBody {
+    fn morph(
+        &mut self,
+        new_prototype: &Prototype<CBPrototypeKind>,
+        report_to: CBConstructionID,
+        world: &mut World,
+    ) {
+        if let CBPrototypeKind::Water(ref proto) = new_prototype.kind {
+            self.disappear(world);
+            self.proto = proto.clone();
+            self.appear(world);
+            report_to.action_done(self.id.into(), world);
+        } else {
+            unreachable!();
+        }
+    }
+
+    fn destruct(&mut self, report_to: CBConstructionID, world: &mut World) -> Fate {
+        self.disappear(world);
+        report_to.action_done(self.id.into(), world);
+        Fate::Die
+    }
+}
+
+// Cuts every corner of a shoreline, so outlines drawn with few points
+// still look like they were shaped by water. Closed outlines stay closed
+fn smoothed(points: &[P2], closed: bool) -> Vec<P2> {
+    let mut points = points.to_vec();
+
+    for _ in 0..SHORELINE_SMOOTHING_ITERATIONS {
+        let n_edges = if closed {
+            points.len()
+        } else {
+            points.len() - 1
+        };
+        let mut cut = Vec::with_capacity(2 * n_edges + 2);
+
+        if !closed {
+            cut.push(points[0]);
+        }
+        for i in 0..n_edges {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            cut.push(a + 0.25 * (b - a));
+            cut.push(a + 0.75 * (b - a));
+        }
+        if !closed {
+            cut.push(points[points.len() - 1]);
+        }
+
+        points = cut;
+    }
+
+    points
+}
+
+fn outlined_area(outline: &[P2]) -> Option<Area> {
+    if outline.len() < 3 {
+        return None;
+    }
+
+    let mut smooth_outline = smoothed(outline, true);
+    smooth_outline.push(smooth_outline[0]);
+
+    LinePath::new(smooth_outline.into())
+        .and_then(ClosedLinePath::new)
+        .map(|closed_line_path| Area::new_simple(closed_line_path.to_clockwise()))
+}
+
+fn river_area(middle: &[P2], width: N) -> Option<Area> {
+    if middle.len() < 2 {
+        return None;
+    }
+
+    LinePath::new(smoothed(middle, false).into()).map(|path| Band::new(path, width).as_area())
+}
+
+// A lake some way outside of the center and a river passing
+// nearer by, so the first bridge is needed before long
+fn natural_water(gesture_id: GestureID) -> Vec<(WaterKind, Option<Area>)> {
+    let mut rng = seed(gesture_id);
+
+    let lake_direction = rng.gen_range(0.0, 2.0 * ::std::f32::consts::PI);
+    let lake_center = P2::new(0.0, 0.0)
+        + rng.gen_range(1800.0, 2500.0) * V2::new(lake_direction.cos(), lake_direction.sin());
+    let lake_radius = rng.gen_range(150.0, 300.0);
+    let lake_outline = (0..16)
+        .map(|i| {
+            let angle = i as N / 16.0 * 2.0 * ::std::f32::consts::PI;
+            let radius = lake_radius * rng.gen_range(0.75, 1.25);
+            lake_center + radius * V2::new(angle.cos(), angle.sin())
+        })
+        .collect::<Vec<_>>();
+
+    let river_offset = rng.gen_range(700.0, 1200.0) * if rng.gen() { 1.0 } else { -1.0 };
+    let river_slope = rng.gen_range(-0.2, 0.2);
+    let river_middle = (-14..=14)
+        .map(|i| {
+            let x = i as N * 300.0;
+            P2::new(x, river_offset + river_slope * x + rng.gen_range(-120.0, 120.0))
+        })
+        .collect::<Vec<_>>();
+
+    vec![
+        (WaterKind::Lake, outlined_area(&lake_outline)),
+        (
+            WaterKind::River,
+            river_area(&river_middle, rng.gen_range(40.0, 80.0)),
+        ),
+    ]
+}
+
+pub fn calculate_prototypes(
+    history: &PlanHistory<CBGestureIntent>,
+    _current_result: &PlanResult<CBPrototypeKind>,
+) -> Result<Vec<Prototype<CBPrototypeKind>>, AreaError> {
+    let mut prototypes = Vec::new();
+
+    for (gesture_id, versioned_gesture) in history.gestures.pairs() {
+        if let CBGestureIntent::Water(ref water_intent) = versioned_gesture.0.intent {
+            let points = &versioned_gesture.0.points;
+            let water = match *water_intent {
+                WaterIntent::Lake => vec![(WaterKind::Lake, outlined_area(points))],
+                WaterIntent::Coast => vec![(WaterKind::Coast, outlined_area(points))],
+                WaterIntent::River(width) => vec![(WaterKind::River, river_area(points, width))],
+                WaterIntent::Natural => natural_water(*gesture_id),
+            };
+
+            for (i, (kind, maybe_area)) in water.into_iter().enumerate() {
+                if let Some(area) = maybe_area {
+                    let representative_position = area.primitives[0].boundary.path().start();
+                    prototypes.push(Prototype::new_with_influences(
+                        (gesture_id, i),
+                        CBPrototypeKind::Water(WaterPrototype { kind, area }),
+                        representative_position,
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(prototypes)
+}
+
+// the water planned before, for the planning steps that come after it
+pub fn water_areas<'a>(result: &'a PlanResult<CBPrototypeKind>) -> Vec<&'a Area> {
+    result
+        .prototypes
+        .values()
+        .filter_map(|prototype| match prototype.kind {
+            CBPrototypeKind::Water(WaterPrototype { ref area, .. }) => Some(area),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn touches_water(water_areas: &[&Area], path: &LinePath) -> bool {
+    let n_samples = (path.length() / ROAD_SAMPLE_DISTANCE).ceil() as usize;
+    (0..=n_samples).any(|i| {
+        let point = path.along((i as N * ROAD_SAMPLE_DISTANCE).min(path.length()));
+        water_areas.iter().any(|area| area.contains(point))
+    })
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<WaterBody>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, plan_manager: CBPlanManagerID) {
+    let gestures = Some((
+        GestureID::new(),
+        Gesture::new(CVec::new(), CBGestureIntent::Water(WaterIntent::Natural)),
+    ));
+    let project = Project::from_plan(Plan::from_gestures(gestures));
+
+    plan_manager.implement_artificial_project(project, CVec::new(), world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
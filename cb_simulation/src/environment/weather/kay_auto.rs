@@ -0,0 +1,174 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct WeatherListenerID {
+    _raw_id: RawID
+}
+
+impl Copy for WeatherListenerID {}
+impl Clone for WeatherListenerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for WeatherListenerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "WeatherListenerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for WeatherListenerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for WeatherListenerID {
+    fn eq(&self, other: &WeatherListenerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for WeatherListenerID {}
+
+pub struct WeatherListenerRepresentative;
+
+impl ActorOrActorTrait for WeatherListenerRepresentative {
+    type ID = WeatherListenerID;
+}
+
+impl TypedID for WeatherListenerID {
+    type Target = WeatherListenerRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        WeatherListenerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + WeatherListener> TraitIDFrom<Act> for WeatherListenerID {}
+
+impl WeatherListenerID {
+    pub fn on_weather(self, weather: Weather, world: &mut World) {
+        world.send(self.as_raw(), MSG_WeatherListener_on_weather(weather));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<WeatherListenerRepresentative>();
+        system.register_trait_message::<MSG_WeatherListener_on_weather>();
+    }
+
+    pub fn register_implementor<Act: Actor + WeatherListener>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, WeatherListenerRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_WeatherListener_on_weather(weather), instance, world| {
+                instance.on_weather(weather, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_WeatherListener_on_weather(pub Weather);
+
+impl Actor for WeatherStation {
+    type ID = WeatherStationID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct WeatherStationID {
+    _raw_id: RawID
+}
+
+impl Copy for WeatherStationID {}
+impl Clone for WeatherStationID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for WeatherStationID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "WeatherStationID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for WeatherStationID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for WeatherStationID {
+    fn eq(&self, other: &WeatherStationID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for WeatherStationID {}
+
+impl TypedID for WeatherStationID {
+    type Target = WeatherStation;
+
+    fn from_raw(id: RawID) -> Self {
+        WeatherStationID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl WeatherStationID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = WeatherStationID::from_raw(world.allocate_instance_id::<WeatherStation>());
+        let swarm = world.local_broadcast::<WeatherStation>();
+        world.send(swarm, MSG_WeatherStation_spawn(id, time));
+        id
+    }
+    
+    pub fn subscribe(self, listener: WeatherListenerID, world: &mut World) {
+        world.send(self.as_raw(), MSG_WeatherStation_subscribe(listener));
+    }
+    
+    pub fn unsubscribe(self, listener: WeatherListenerID, world: &mut World) {
+        world.send(self.as_raw(), MSG_WeatherStation_unsubscribe(listener));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_WeatherStation_spawn(pub WeatherStationID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_WeatherStation_subscribe(pub WeatherListenerID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_WeatherStation_unsubscribe(pub WeatherListenerID);
+
+impl Into<CalendarListenerID> for WeatherStationID {
+    fn into(self) -> CalendarListenerID {
+        CalendarListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    WeatherListenerID::register_trait(system);
+    CalendarListenerID::register_implementor::<WeatherStation>(system);
+    system.add_spawner::<WeatherStation, _, _>(
+        |&MSG_WeatherStation_spawn(id, time), world| {
+            WeatherStation::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<WeatherStation, _, _>(
+        |&MSG_WeatherStation_subscribe(listener), instance, world| {
+            instance.subscribe(listener, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<WeatherStation, _, _>(
+        |&MSG_WeatherStation_unsubscribe(listener), instance, world| {
+            instance.unsubscribe(listener, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
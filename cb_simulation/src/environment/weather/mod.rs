@@ -0,0 +1,131 @@
+// The weather changes every day and follows the seasons: snow only falls in
+// winter and heat waves only come in summer. Like the growing weather of
+// `seasons`, it only depends on the date, so every run of a city has the same
+// weather and everyone can look it up for themselves: cars drive slower on
+// wet and icy roads, rain, snow and heat make walking and cycling less
+// attractive, and buildings draw more electricity for heating and cooling.
+// The weather station announces the weather at the start of each day to
+// whoever subscribed, like the browser, which shows it
+use kay::{ActorSystem, World, TypedID};
+use compact::CVec;
+use cb_time::actors::{CalendarListener, CalendarListenerID, TimeID};
+use cb_time::calendar::{CalendarPeriod, Date, Season};
+use cb_time::units::Instant;
+use cb_util::random::{seed, Rng};
+use economy::utilities::Utility;
+use transport::pathfinding::trip::TravelMode;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Weather {
+    Clear,
+    Rain,
+    Snow,
+    Heat,
+}
+
+impl Weather {
+    pub fn of(date: Date) -> Weather {
+        let roll: f32 = seed((date.year, date.month as u8, date.day_of_month, 2u8)).gen();
+        match date.season() {
+            Season::Spring if roll < 0.35 => Weather::Rain,
+            Season::Summer if roll < 0.2 => Weather::Heat,
+            Season::Summer if roll < 0.4 => Weather::Rain,
+            Season::Autumn if roll < 0.45 => Weather::Rain,
+            Season::Winter if roll < 0.4 => Weather::Snow,
+            Season::Winter if roll < 0.55 => Weather::Rain,
+            _ => Weather::Clear,
+        }
+    }
+
+    pub fn on(instant: Instant) -> Weather {
+        Weather::of(Date::from(instant))
+    }
+
+    // share of the speed limit that cars can still drive safely
+    pub fn speed_factor(self) -> f32 {
+        match self {
+            Weather::Clear | Weather::Heat => 1.0,
+            Weather::Rain => 0.85,
+            Weather::Snow => 0.6,
+        }
+    }
+
+    // how much more unpleasant than usual it is to be out in it
+    pub fn exposure_penalty(self, mode: TravelMode) -> f32 {
+        match (self, mode) {
+            (Weather::Rain, TravelMode::Walking) => 0.3,
+            (Weather::Rain, TravelMode::Bike) => 0.6,
+            (Weather::Snow, TravelMode::Walking) => 0.6,
+            (Weather::Snow, TravelMode::Bike) => 1.5,
+            (Weather::Heat, TravelMode::Walking) => 0.2,
+            (Weather::Heat, TravelMode::Bike) => 0.4,
+            _ => 0.0,
+        }
+    }
+
+    // heating when it snows, cooling and watering in the heat
+    pub fn demand_factor(self, utility: Utility) -> f32 {
+        match (self, utility) {
+            (Weather::Snow, Utility::Electricity) => 1.4,
+            (Weather::Heat, Utility::Electricity) => 1.25,
+            (Weather::Heat, Utility::Water) => 1.3,
+            _ => 1.0,
+        }
+    }
+}
+
+pub trait WeatherListener {
+    fn on_weather(&mut self, weather: Weather, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct WeatherStation {
+    id: WeatherStationID,
+    current: Weather,
+    listeners: CVec<WeatherListenerID>,
+}
+
+impl WeatherStation {
+    pub fn spawn(id: WeatherStationID, time: TimeID, world: &mut World) -> WeatherStation {
+        time.notify_at_start_of(CalendarPeriod::Day, id.into(), world);
+
+        WeatherStation {
+            id,
+            current: Weather::on(Instant::new(0)),
+            listeners: CVec::new(),
+        }
+    }
+
+    // Listeners hear about the current weather right away
+    pub fn subscribe(&mut self, listener: WeatherListenerID, world: &mut World) {
+        if !self.listeners.contains(&listener) {
+            self.listeners.push(listener);
+        }
+        listener.on_weather(self.current, world);
+    }
+
+    pub fn unsubscribe(&mut self, listener: WeatherListenerID, _: &mut World) {
+        self.listeners.retain(|&other| other != listener);
+    }
+}
+
+impl CalendarListener for WeatherStation {
+    fn on_new_period(&mut self, _: CalendarPeriod, date: Date, world: &mut World) {
+        self.current = Weather::of(date);
+        for listener in self.listeners.iter() {
+            listener.on_weather(self.current, world);
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<WeatherStation>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    WeatherStationID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
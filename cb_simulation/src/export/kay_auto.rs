@@ -0,0 +1,162 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ExportableID {
+    _raw_id: RawID
+}
+
+impl Copy for ExportableID {}
+impl Clone for ExportableID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ExportableID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ExportableID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ExportableID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ExportableID {
+    fn eq(&self, other: &ExportableID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ExportableID {}
+
+pub struct ExportableRepresentative;
+
+impl ActorOrActorTrait for ExportableRepresentative {
+    type ID = ExportableID;
+}
+
+impl TypedID for ExportableID {
+    type Target = ExportableRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        ExportableID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + Exportable> TraitIDFrom<Act> for ExportableID {}
+
+impl ExportableID {
+    pub fn export(self, exporter: ExporterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Exportable_export(exporter));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<ExportableRepresentative>();
+        system.register_trait_message::<MSG_Exportable_export>();
+    }
+
+    pub fn register_implementor<Act: Actor + Exportable>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, ExportableRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_Exportable_export(exporter), instance, world| {
+                instance.export(exporter, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Exportable_export(pub ExporterID);
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ExporterID {
+    _raw_id: RawID
+}
+
+impl Copy for ExporterID {}
+impl Clone for ExporterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ExporterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ExporterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ExporterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ExporterID {
+    fn eq(&self, other: &ExporterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ExporterID {}
+
+pub struct ExporterRepresentative;
+
+impl ActorOrActorTrait for ExporterRepresentative {
+    type ID = ExporterID;
+}
+
+impl TypedID for ExporterID {
+    type Target = ExporterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        ExporterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + Exporter> TraitIDFrom<Act> for ExporterID {}
+
+impl ExporterID {
+    pub fn on_exported_lane(self, lane: LaneID, path: LinePath, on_intersection: bool, n_cars: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Exporter_on_exported_lane(lane, path, on_intersection, n_cars));
+    }
+    
+    pub fn on_exported_building(self, building: BuildingID, outline: LinePath, style: BuildingStyle, area: N, n_households: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Exporter_on_exported_building(building, outline, style, area, n_households));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<ExporterRepresentative>();
+        system.register_trait_message::<MSG_Exporter_on_exported_lane>();
+        system.register_trait_message::<MSG_Exporter_on_exported_building>();
+    }
+
+    pub fn register_implementor<Act: Actor + Exporter>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, ExporterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_Exporter_on_exported_lane(lane, ref path, on_intersection, n_cars), instance, world| {
+                instance.on_exported_lane(lane, path, on_intersection, n_cars, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Exporter_on_exported_building(building, ref outline, style, area, n_households), instance, world| {
+                instance.on_exported_building(building, outline, style, area, n_households, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Exporter_on_exported_lane(pub LaneID, pub LinePath, pub bool, pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Exporter_on_exported_building(pub BuildingID, pub LinePath, pub BuildingStyle, pub N, pub u32);
+
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    ExportableID::register_trait(system);
+    ExporterID::register_trait(system);
+    
+}
\ No newline at end of file
@@ -0,0 +1,38 @@
+use kay::{ActorSystem, World};
+use descartes::{N, LinePath};
+use transport::lane::LaneID;
+use land_use::buildings::{BuildingID, BuildingStyle};
+
+// Everything that can be part of an exported city, like for analysis in external tools.
+// Exportables report to the exporter independently, so it never knows
+// for sure when it has received everything
+pub trait Exportable {
+    fn export(&mut self, exporter: ExporterID, world: &mut World);
+}
+
+pub trait Exporter {
+    fn on_exported_lane(
+        &mut self,
+        lane: LaneID,
+        path: &LinePath,
+        on_intersection: bool,
+        n_cars: u32,
+        world: &mut World,
+    );
+    fn on_exported_building(
+        &mut self,
+        building: BuildingID,
+        outline: &LinePath,
+        style: BuildingStyle,
+        area: N,
+        n_households: u32,
+        world: &mut World,
+    );
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -49,7 +49,7 @@ impl<T: Clone + Compact> Choice<T> {
         )
     }
 
-    fn evaluate(&self, lot: &Lot) -> T {
+    pub fn evaluate(&self, lot: &Lot) -> T {
         match *self {
             Choice::Random(ref options, ref ident) => seed((lot.original_lot_id, ident))
                 .choose(options)
@@ -1,7 +1,10 @@
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 pub enum BuildingMaterial {
     WhiteWall,
+    PlasterWall,
+    BrickWall,
     TiledRoof,
+    SlateRoof,
     FlatRoof,
     FieldWheat,
     FieldRows,
@@ -18,9 +21,12 @@ impl ::std::fmt::Display for BuildingMaterial {
     }
 }
 
-pub const ALL_MATERIALS: [BuildingMaterial; 10] = [
+pub const ALL_MATERIALS: [BuildingMaterial; 13] = [
     BuildingMaterial::WhiteWall,
+    BuildingMaterial::PlasterWall,
+    BuildingMaterial::BrickWall,
     BuildingMaterial::TiledRoof,
+    BuildingMaterial::SlateRoof,
     BuildingMaterial::FlatRoof,
     BuildingMaterial::FieldWheat,
     BuildingMaterial::FieldRows,
@@ -9,6 +9,9 @@ use std::collections::HashMap;
 pub mod materials_and_props;
 use self::materials_and_props::{BuildingMaterial, BuildingProp};
 
+pub mod variation;
+use self::variation::{Variation, variation_for, fit_to_lot, WALL_MATERIALS, PITCHED_ROOF_MATERIALS};
+
 pub mod language;
 use self::language::{Choice, Variable, ArchitectureRule, BuildingRule, FacadeRule, RuleRef,
 FacadeDecorationRule, CorpusRule, FundamentRule, FloorRule, RoofRule, PavingRule, LotRule,
@@ -22,16 +25,36 @@ pub fn ideal_lot_shape(building_style: BuildingStyle) -> (N, N, N) {
         BuildingStyle::GroceryShop => (15.0, 20.0, 0.5),
         BuildingStyle::Bakery => (20.0, 30.0, 0.5),
         BuildingStyle::Mill => (20.0, 30.0, 0.5),
+        BuildingStyle::School => (30.0, 40.0, 0.5),
+        BuildingStyle::Hospital => (30.0, 40.0, 0.5),
+        BuildingStyle::CarDealer => (25.0, 35.0, 0.5),
+        BuildingStyle::Restaurant => (15.0, 20.0, 0.5),
+        BuildingStyle::Cinema => (30.0, 40.0, 0.5),
+        BuildingStyle::Park => (40.0, 60.0, 0.3),
         BuildingStyle::Field => (50.0, 100.0, 0.1),
+        BuildingStyle::ConstructionYard => (30.0, 40.0, 0.5),
+        BuildingStyle::TaxiDepot => (25.0, 35.0, 0.5),
+        BuildingStyle::ApartmentBlock => (20.0, 30.0, 0.5),
+        BuildingStyle::DepartmentStore => (15.0, 20.0, 0.5),
+        BuildingStyle::PowerPlant => (40.0, 60.0, 0.4),
+        BuildingStyle::Waterworks => (30.0, 50.0, 0.4),
         BuildingStyle::NeighboringTownConnection => (5.0, 5.0, 0.1),
     }
 }
 
-fn footprint_dimensions(building_style: BuildingStyle) -> (N, N) {
-    match building_style {
+// pitch of the gabled roofs of buildings that vary between flat and gabled
+const ROOF_ANGLE: N = 0.4;
+
+fn footprint_dimensions(lot: &Lot, building_style: BuildingStyle) -> (N, N) {
+    let (base_width, base_depth) = match building_style {
         BuildingStyle::FamilyHouse => (12.0, 8.0),
+        // a wide showroom, the rest of the lot is for parked cars
+        BuildingStyle::CarDealer => (20.0, 12.0),
+        BuildingStyle::Cinema => (20.0, 16.0),
+        BuildingStyle::PowerPlant => (24.0, 16.0),
         _ => (15.0, 10.0),
-    }
+    };
+    fit_to_lot(lot, base_width, base_depth)
 }
 
 #[derive(Clone)]
@@ -96,7 +119,7 @@ fn default_rules() -> CHashMap<Name, ArchitectureRule> {
         (
             "ResWindowedFac",
             ArchitectureRule::Facade(FacadeRule::Face(
-                Choice::Specific(BuildingMaterial::WhiteWall),
+                Choice::new_random(WALL_MATERIALS.to_vec(), "wallMaterial"),
                 vec![Choice::Specific(RuleRef::of("ResWindowedFacDc"))].into(),
             )),
         ),
@@ -128,8 +151,8 @@ fn default_rules() -> CHashMap<Name, ArchitectureRule> {
                 height: Variable::new_random(2.0, 5.0, "roofHeight"),
                 gable_depth_front: Variable::new_random(0.0, 3.0, "gableDepth"),
                 gable_depth_back: Variable::new_random(0.0, 3.0, "gableDepth"),
-                roof_material: Choice::Specific(BuildingMaterial::TiledRoof),
-                gable_material: Choice::Specific(BuildingMaterial::TiledRoof),
+                roof_material: Choice::new_random(PITCHED_ROOF_MATERIALS.to_vec(), "roofMaterial"),
+                gable_material: Choice::new_random(PITCHED_ROOF_MATERIALS.to_vec(), "roofMaterial"),
             }),
         ),
         (
@@ -183,13 +206,13 @@ fn default_rules() -> CHashMap<Name, ArchitectureRule> {
 }
 
 pub fn footprint_area(lot: &Lot, building_style: BuildingStyle, extra_padding: N) -> Area {
-    if let BuildingStyle::Field = building_style {
+    if let BuildingStyle::Field | BuildingStyle::Park = building_style {
         lot.area.clone()
     } else {
         // TODO keep original building if lot changes
         let mut rng = seed(lot.original_lot_id);
 
-        let (base_width, base_depth) = footprint_dimensions(building_style);
+        let (base_width, base_depth) = footprint_dimensions(lot, building_style);
 
         let (main_footprint, _entrance_footprint) =
             generate_house_footprint(lot, base_width, base_depth, extra_padding, &mut rng);
@@ -201,6 +224,7 @@ pub fn footprint_area(lot: &Lot, building_style: BuildingStyle, extra_padding: N
 pub fn build_building(
     lot: &Lot,
     building_style: BuildingStyle,
+    construction_progress: Option<N>,
     architecture_rules: &CHashMap<Name, ArchitectureRule>,
     household_ids: &[::economy::households::HouseholdID],
     world: &mut World,
@@ -208,11 +232,23 @@ pub fn build_building(
     // TODO keep original building if lot changes
     let mut rng = seed(lot.original_lot_id);
 
-    let (base_width, base_depth) = footprint_dimensions(building_style);
+    let (base_width, base_depth) = footprint_dimensions(lot, building_style);
 
     let (main_footprint, entrance_footprint) =
         generate_house_footprint(lot, base_width, base_depth, 0.0, &mut rng);
 
+    let variation = variation_for(lot, building_style);
+
+    if let Some(progress) = construction_progress {
+        return Ok(build_construction_site(
+            lot,
+            building_style,
+            &main_footprint,
+            &variation,
+            progress,
+        ));
+    }
+
     Ok(match building_style {
         BuildingStyle::FamilyHouse => {
             let building_rule =
@@ -221,8 +257,11 @@ pub fn build_building(
             building_rule.collect_geometry(&mut collector, lot, architecture_rules)?;
             collector.into_geometry()
         }
-        BuildingStyle::GroceryShop => {
-            let height = 3.0 + rng.gen::<f32>();
+        BuildingStyle::GroceryShop
+        | BuildingStyle::CarDealer
+        | BuildingStyle::Restaurant
+        | BuildingStyle::Cinema => {
+            let height = variation.height() + rng.gen::<f32>();
             let entrance_height = height - 0.7;
             let business_color = [
                 rng.gen_range(0.3, 0.6),
@@ -231,20 +270,12 @@ pub fn build_building(
             ];
 
             BuildingGeometry {
-                meshes: vec![
-                    (
-                        BuildingMaterial::WhiteWall,
-                        main_footprint.wall_mesh(height)
-                            + entrance_footprint.wall_mesh(entrance_height),
-                    ),
-                    (
-                        BuildingMaterial::FlatRoof,
-                        main_footprint.flat_roof_mesh(height)
-                            + entrance_footprint.flat_roof_mesh(entrance_height),
-                    ),
-                ]
-                .into_iter()
-                .collect(),
+                meshes: body_meshes(
+                    &main_footprint,
+                    height,
+                    Some((&entrance_footprint, entrance_height)),
+                    &variation,
+                ),
                 props: vec![
                     (
                         BuildingProp::ShopWindowGlass,
@@ -284,7 +315,7 @@ pub fn build_building(
                             Instance {
                                 instance_position: [position.x, position.y, 0.0],
                                 instance_direction: [direction.x, direction.y],
-                                instance_color: [0.6, 0.5, 0.5],
+                                instance_color: variation.trim_color,
                             }
                         }],
                     ),
@@ -326,6 +357,20 @@ pub fn build_building(
                 props: HashMap::new(),
             }
         }
+        BuildingStyle::Park => {
+            let lot_surface = FlatSurface::from_primitive_area(lot.area.primitives[0].clone(), 0.0);
+            let (_, shrunk_lot_surface) = lot_surface.extrude(0.0, 2.0).unwrap();
+
+            BuildingGeometry {
+                meshes: Some((
+                    BuildingMaterial::FieldMeadow,
+                    Sculpture::new(vec![shrunk_lot_surface.into()]).to_mesh(),
+                ))
+                .into_iter()
+                .collect(),
+                props: HashMap::new(),
+            }
+        }
         BuildingStyle::Mill => {
             let height = 3.0 + rng.gen::<f32>();
             let tower_height = 5.0 + rng.gen::<f32>();
@@ -338,7 +383,7 @@ pub fn build_building(
             BuildingGeometry {
                 meshes: vec![
                     (
-                        BuildingMaterial::WhiteWall,
+                        variation.wall_material,
                         main_footprint.wall_mesh(height)
                             + entrance_footprint.wall_mesh(tower_height)
                             + roof_wall_mesh
@@ -365,7 +410,7 @@ pub fn build_building(
                         Instance {
                             instance_position: [position.x, position.y, 0.0],
                             instance_direction: [direction.x, direction.y],
-                            instance_color: [0.6, 0.5, 0.5],
+                            instance_color: variation.trim_color,
                         }
                     }],
                 )]
@@ -388,12 +433,12 @@ pub fn build_building(
             BuildingGeometry {
                 meshes: vec![
                     (
-                        BuildingMaterial::WhiteWall,
+                        variation.wall_material,
                         main_footprint.wall_mesh(height)
                             + entrance_footprint.wall_mesh(entrance_height)
                             + entrance_roof_wall_mesh,
                     ),
-                    (BuildingMaterial::TiledRoof, entrance_roof_brick_mesh),
+                    (variation.roof_material, entrance_roof_brick_mesh),
                     (
                         BuildingMaterial::FlatRoof,
                         main_footprint.flat_roof_mesh(height),
@@ -440,7 +485,7 @@ pub fn build_building(
                             Instance {
                                 instance_position: [position.x, position.y, 0.0],
                                 instance_direction: [direction.x, direction.y],
-                                instance_color: [0.6, 0.5, 0.5],
+                                instance_color: variation.trim_color,
                             }
                         }],
                     ),
@@ -449,6 +494,358 @@ pub fn build_building(
                 .collect(),
             }
         }
+        BuildingStyle::School => {
+            // two stories with a window row each
+            let height = 6.0 + rng.gen::<f32>();
+            let entrance_height = 3.5;
+
+            let (roof_brick_mesh, roof_wall_mesh) =
+                main_footprint.open_gable_roof_mesh(height, 0.4);
+
+            BuildingGeometry {
+                meshes: vec![
+                    (
+                        variation.wall_material,
+                        main_footprint.wall_mesh(height)
+                            + entrance_footprint.wall_mesh(entrance_height)
+                            + roof_wall_mesh,
+                    ),
+                    (variation.roof_material, roof_brick_mesh),
+                    (
+                        BuildingMaterial::FlatRoof,
+                        entrance_footprint.flat_roof_mesh(entrance_height),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                props: vec![
+                    (
+                        BuildingProp::SmallWindow,
+                        main_footprint
+                            .distribute_along_walls(2.5)
+                            .into_iter()
+                            .flat_map(|(position, direction)| {
+                                vec![0.0, height / 2.0]
+                                    .into_iter()
+                                    .map(move |floor_height| Instance {
+                                        instance_position: [position.x, position.y, floor_height],
+                                        instance_direction: [direction.x, direction.y],
+                                        instance_color: [0.7, 0.6, 0.6],
+                                    })
+                            })
+                            .collect(),
+                    ),
+                    (
+                        BuildingProp::WideDoor,
+                        vec![{
+                            let position = P2::from_coordinates(
+                                (entrance_footprint.front_right.coords
+                                    + entrance_footprint.back_right.coords)
+                                    / 2.0,
+                            );
+                            let direction = (entrance_footprint.back_right
+                                - entrance_footprint.front_right)
+                                .normalize();
+                            Instance {
+                                instance_position: [position.x, position.y, 0.0],
+                                instance_direction: [direction.x, direction.y],
+                                instance_color: variation.trim_color,
+                            }
+                        }],
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            }
+        }
+        BuildingStyle::Hospital => {
+            // three stories with a window row each
+            let floor_height = 3.0;
+            let height = 3.0 * floor_height;
+            let entrance_height = 4.0;
+
+            BuildingGeometry {
+                meshes: vec![
+                    (
+                        BuildingMaterial::WhiteWall,
+                        main_footprint.wall_mesh(height)
+                            + entrance_footprint.wall_mesh(entrance_height),
+                    ),
+                    (
+                        BuildingMaterial::FlatRoof,
+                        main_footprint.flat_roof_mesh(height)
+                            + entrance_footprint.flat_roof_mesh(entrance_height),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                props: vec![
+                    (
+                        BuildingProp::SmallWindow,
+                        main_footprint
+                            .distribute_along_walls(2.0)
+                            .into_iter()
+                            .flat_map(|(position, direction)| {
+                                (0..3).map(move |floor| Instance {
+                                    instance_position: [
+                                        position.x,
+                                        position.y,
+                                        floor as N * floor_height,
+                                    ],
+                                    instance_direction: [direction.x, direction.y],
+                                    instance_color: [0.6, 0.7, 0.8],
+                                })
+                            })
+                            .collect(),
+                    ),
+                    (
+                        BuildingProp::WideDoor,
+                        vec![{
+                            let position = P2::from_coordinates(
+                                (entrance_footprint.front_right.coords
+                                    + entrance_footprint.back_right.coords)
+                                    / 2.0,
+                            );
+                            let direction = (entrance_footprint.back_right
+                                - entrance_footprint.front_right)
+                                .normalize();
+                            Instance {
+                                instance_position: [position.x, position.y, 0.0],
+                                instance_direction: [direction.x, direction.y],
+                                instance_color: [0.8, 0.3, 0.3],
+                            }
+                        }],
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            }
+        }
+        BuildingStyle::ApartmentBlock => {
+            // what a family house grows into, four to six stories of flats
+            let floor_height = variation.floor_height;
+            let n_floors = variation.n_floors;
+            let height = variation.height();
+            let entrance_height = 3.0;
+
+            BuildingGeometry {
+                meshes: body_meshes(
+                    &main_footprint,
+                    height,
+                    Some((&entrance_footprint, entrance_height)),
+                    &variation,
+                ),
+                props: vec![
+                    (
+                        BuildingProp::SmallWindow,
+                        main_footprint
+                            .distribute_along_walls(2.5)
+                            .into_iter()
+                            .flat_map(|(position, direction)| {
+                                (0..n_floors).map(move |floor| Instance {
+                                    instance_position: [
+                                        position.x,
+                                        position.y,
+                                        floor as N * floor_height,
+                                    ],
+                                    instance_direction: [direction.x, direction.y],
+                                    instance_color: variation.trim_color,
+                                })
+                            })
+                            .collect(),
+                    ),
+                    (
+                        BuildingProp::NarrowDoor,
+                        vec![{
+                            let position = P2::from_coordinates(
+                                (entrance_footprint.front_right.coords
+                                    + entrance_footprint.back_right.coords)
+                                    / 2.0,
+                            );
+                            let direction = (entrance_footprint.back_right
+                                - entrance_footprint.front_right)
+                                .normalize();
+                            Instance {
+                                instance_position: [position.x, position.y, 0.0],
+                                instance_direction: [direction.x, direction.y],
+                                instance_color: variation.trim_color,
+                            }
+                        }],
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            }
+        }
+        BuildingStyle::DepartmentStore => {
+            // what a grocery shop grows into, shop windows below and offices above
+            let floor_height = 3.5;
+            let n_floors = variation.n_floors;
+            let height = n_floors as N * floor_height + rng.gen::<f32>();
+            let entrance_height = floor_height;
+            let business_color = [
+                rng.gen_range(0.3, 0.6),
+                rng.gen_range(0.3, 0.6),
+                rng.gen_range(0.3, 0.6),
+            ];
+
+            BuildingGeometry {
+                meshes: body_meshes(
+                    &main_footprint,
+                    height,
+                    Some((&entrance_footprint, entrance_height)),
+                    &variation,
+                ),
+                props: vec![
+                    (
+                        BuildingProp::ShopWindowGlass,
+                        main_footprint
+                            .distribute_along_walls(3.0)
+                            .into_iter()
+                            .map(|(position, direction)| Instance {
+                                instance_position: [position.x, position.y, 0.0],
+                                instance_direction: [direction.x, direction.y],
+                                instance_color: [0.7, 0.6, 0.6],
+                            })
+                            .collect(),
+                    ),
+                    (
+                        BuildingProp::ShopWindowBanner,
+                        main_footprint
+                            .distribute_along_walls(3.0)
+                            .into_iter()
+                            .map(|(position, direction)| Instance {
+                                instance_position: [position.x, position.y, 0.0],
+                                instance_direction: [direction.x, direction.y],
+                                instance_color: business_color,
+                            })
+                            .collect(),
+                    ),
+                    (
+                        BuildingProp::SmallWindow,
+                        main_footprint
+                            .distribute_along_walls(2.5)
+                            .into_iter()
+                            .flat_map(|(position, direction)| {
+                                (1..n_floors).map(move |floor| Instance {
+                                    instance_position: [
+                                        position.x,
+                                        position.y,
+                                        floor as N * floor_height,
+                                    ],
+                                    instance_direction: [direction.x, direction.y],
+                                    instance_color: [0.6, 0.7, 0.8],
+                                })
+                            })
+                            .collect(),
+                    ),
+                    (
+                        BuildingProp::WideDoor,
+                        vec![{
+                            let position = P2::from_coordinates(
+                                (entrance_footprint.front_right.coords
+                                    + entrance_footprint.back_right.coords)
+                                    / 2.0,
+                            );
+                            let direction = (entrance_footprint.back_right
+                                - entrance_footprint.front_right)
+                                .normalize();
+                            Instance {
+                                instance_position: [position.x, position.y, 0.0],
+                                instance_direction: [direction.x, direction.y],
+                                instance_color: variation.trim_color,
+                            }
+                        }],
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            }
+        }
+        BuildingStyle::ConstructionYard | BuildingStyle::TaxiDepot => {
+            let height = 4.0 + rng.gen::<f32>();
+            let door_color = if building_style == BuildingStyle::TaxiDepot {
+                [0.95, 0.8, 0.1]
+            } else {
+                [0.8, 0.6, 0.2]
+            };
+
+            BuildingGeometry {
+                meshes: vec![
+                    (
+                        BuildingMaterial::LotAsphalt,
+                        Mesh::from_area(&lot.original_area),
+                    ),
+                    (
+                        BuildingMaterial::WhiteWall,
+                        main_footprint.wall_mesh(height),
+                    ),
+                    (
+                        BuildingMaterial::FlatRoof,
+                        main_footprint.flat_roof_mesh(height),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                props: vec![(
+                    BuildingProp::WideDoor,
+                    main_footprint
+                        .distribute_along_walls(6.0)
+                        .into_iter()
+                        .take(2)
+                        .map(|(position, direction)| Instance {
+                            instance_position: [position.x, position.y, 0.0],
+                            instance_direction: [direction.x, direction.y],
+                            instance_color: door_color,
+                        })
+                        .collect(),
+                )]
+                .into_iter()
+                .collect(),
+            }
+        }
+        BuildingStyle::PowerPlant | BuildingStyle::Waterworks => {
+            // a tall turbine hall or a low pump house
+            let (height, door_color) = if building_style == BuildingStyle::PowerPlant {
+                (12.0 + 2.0 * rng.gen::<f32>(), [0.9, 0.7, 0.1])
+            } else {
+                (5.0 + rng.gen::<f32>(), [0.2, 0.4, 0.8])
+            };
+
+            BuildingGeometry {
+                meshes: vec![
+                    (
+                        BuildingMaterial::LotAsphalt,
+                        Mesh::from_area(&lot.original_area),
+                    ),
+                    (
+                        BuildingMaterial::WhiteWall,
+                        main_footprint.wall_mesh(height),
+                    ),
+                    (
+                        BuildingMaterial::FlatRoof,
+                        main_footprint.flat_roof_mesh(height),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                props: vec![(
+                    BuildingProp::WideDoor,
+                    main_footprint
+                        .distribute_along_walls(8.0)
+                        .into_iter()
+                        .take(1)
+                        .map(|(position, direction)| Instance {
+                            instance_position: [position.x, position.y, 0.0],
+                            instance_direction: [direction.x, direction.y],
+                            instance_color: door_color,
+                        })
+                        .collect(),
+                )]
+                .into_iter()
+                .collect(),
+            }
+        }
         BuildingStyle::NeighboringTownConnection => BuildingGeometry {
             meshes: Some((
                 BuildingMaterial::WhiteWall,
@@ -461,6 +858,66 @@ pub fn build_building(
     })
 }
 
+// walls and roof of the main building and of an annex, which always
+// has a flat roof so it reads as an extension of the main building
+fn body_meshes(
+    main_footprint: &Footprint,
+    height: N,
+    annex: Option<(&Footprint, N)>,
+    variation: &Variation,
+) -> HashMap<BuildingMaterial, Mesh> {
+    let mut meshes = HashMap::new();
+    {
+        let mut add = |material, mesh| {
+            *meshes.entry(material).or_insert_with(Mesh::empty) += mesh;
+        };
+
+        add(variation.wall_material, main_footprint.wall_mesh(height));
+        if variation.gabled {
+            let (roof_mesh, gable_mesh) = main_footprint.open_gable_roof_mesh(height, ROOF_ANGLE);
+            add(variation.roof_material, roof_mesh);
+            add(variation.wall_material, gable_mesh);
+        } else {
+            add(BuildingMaterial::FlatRoof, main_footprint.flat_roof_mesh(height));
+        }
+
+        if let Some((annex_footprint, annex_height)) = annex {
+            add(variation.wall_material, annex_footprint.wall_mesh(annex_height));
+            add(BuildingMaterial::FlatRoof, annex_footprint.flat_roof_mesh(annex_height));
+        }
+    }
+    meshes
+}
+
+// Bare walls that grow with the construction progress up to
+// the height the building will have, fields and parks are only ploughed over
+fn build_construction_site(
+    lot: &Lot,
+    building_style: BuildingStyle,
+    main_footprint: &Footprint,
+    variation: &Variation,
+    progress: N,
+) -> BuildingGeometry {
+    let mesh = if let BuildingStyle::Field | BuildingStyle::Park = building_style {
+        let lot_surface = FlatSurface::from_primitive_area(lot.area.primitives[0].clone(), 0.0);
+        let (_, shrunk_lot_surface) = lot_surface.extrude(0.0, 2.0).unwrap();
+        (
+            BuildingMaterial::FieldRows,
+            Sculpture::new(vec![shrunk_lot_surface.into()]).to_mesh(),
+        )
+    } else {
+        (
+            variation.wall_material,
+            main_footprint.wall_mesh(0.3 + (variation.height() - 0.3) * progress),
+        )
+    };
+
+    BuildingGeometry {
+        meshes: Some(mesh).into_iter().collect(),
+        props: HashMap::new(),
+    }
+}
+
 pub struct Footprint {
     back_right: P2,
     back_left: P2,
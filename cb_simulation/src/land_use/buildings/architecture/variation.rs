@@ -0,0 +1,124 @@
+// Rows of buildings of the same style shouldn't look identical. What differs
+// between them follows from the shape of their lot, how dense their style is
+// and a seed for the lot and style, so a building always looks the same when
+// it is rendered again, but gets a new look when it grows into a denser style
+use descartes::N;
+use cb_util::random::{Rng, seed};
+use super::materials_and_props::BuildingMaterial;
+use super::language::Choice;
+use super::super::{Lot, BuildingStyle};
+
+pub const WALL_MATERIALS: [BuildingMaterial; 3] = [
+    BuildingMaterial::WhiteWall,
+    BuildingMaterial::PlasterWall,
+    BuildingMaterial::BrickWall,
+];
+
+pub const PITCHED_ROOF_MATERIALS: [BuildingMaterial; 2] =
+    [BuildingMaterial::TiledRoof, BuildingMaterial::SlateRoof];
+
+// footprints leave gaps to the neighbors and most of the depth as a yard
+const MAX_FRONTAGE_SHARE: N = 0.7;
+const MAX_DEPTH_SHARE: N = 0.5;
+const MIN_FOOTPRINT_SIDE: N = 4.0;
+// footprints are randomly widened by up to this much after fitting
+const MAX_FOOTPRINT_STRETCH: N = 1.3;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Density {
+    Low,
+    Medium,
+    High,
+}
+
+impl BuildingStyle {
+    pub fn density(self) -> Density {
+        match self {
+            BuildingStyle::ApartmentBlock | BuildingStyle::Hospital => Density::High,
+            BuildingStyle::DepartmentStore
+            | BuildingStyle::School
+            | BuildingStyle::Cinema
+            | BuildingStyle::PowerPlant => Density::Medium,
+            _ => Density::Low,
+        }
+    }
+}
+
+pub struct Variation {
+    pub wall_material: BuildingMaterial,
+    pub roof_material: BuildingMaterial,
+    // otherwise the roof is flat
+    pub gabled: bool,
+    pub floor_height: N,
+    pub n_floors: usize,
+    // of window frames and doors
+    pub trim_color: [f32; 3],
+}
+
+impl Variation {
+    pub fn height(&self) -> N {
+        self.n_floors as N * self.floor_height
+    }
+}
+
+pub fn variation_for(lot: &Lot, style: BuildingStyle) -> Variation {
+    let mut rng = seed((lot.original_lot_id, style as u8));
+
+    let floor_height = match style.density() {
+        Density::Low => rng.gen_range(2.6, 3.2),
+        Density::Medium => rng.gen_range(3.0, 3.5),
+        Density::High => rng.gen_range(2.8, 3.2),
+    };
+    let n_floors = match style.density() {
+        Density::Low => rng.gen_range(1, 3),
+        Density::Medium => rng.gen_range(2, 4),
+        Density::High => rng.gen_range(4, 7),
+    };
+    // tall buildings mostly end in flat roofs
+    let gable_chance = if style.density() == Density::High { 0.2 } else { 0.6 };
+    // zoning can cap the height, in meters
+    let n_floors = if lot.max_height > 0 {
+        n_floors.min(((lot.max_height as N / floor_height) as usize).max(1))
+    } else {
+        n_floors
+    };
+
+    Variation {
+        // chosen for the lot only, so that an extension keeps the facade
+        // of what it grew from, like the walls of family house rules do
+        wall_material: Choice::new_random(WALL_MATERIALS.to_vec(), "wallMaterial").evaluate(lot),
+        roof_material: *rng
+            .choose(&PITCHED_ROOF_MATERIALS)
+            .expect("Should have roof materials"),
+        gabled: rng.gen::<f32>() < gable_chance,
+        floor_height,
+        n_floors,
+        trim_color: [
+            rng.gen_range(0.5, 0.8),
+            rng.gen_range(0.4, 0.7),
+            rng.gen_range(0.4, 0.7),
+        ],
+    }
+}
+
+// Narrow or shallow lots get narrower or shallower buildings
+pub fn fit_to_lot(lot: &Lot, base_width: N, base_depth: N) -> (N, N) {
+    let (road_point, orientation) = lot.best_road_connection();
+    let frontage = lot.longest_road_boundary().length();
+    let depth = lot.original_area.primitives[0]
+        .boundary
+        .path()
+        .points
+        .iter()
+        .map(|&point| (point - road_point).dot(&orientation).abs())
+        .fold(0.0, N::max);
+
+    (
+        base_width
+            .min(frontage * MAX_FRONTAGE_SHARE / MAX_FOOTPRINT_STRETCH)
+            .max(MIN_FOOTPRINT_SIDE),
+        base_depth
+            .min(depth * MAX_DEPTH_SHARE / MAX_FOOTPRINT_STRETCH)
+            .max(MIN_FOOTPRINT_SIDE),
+    )
+}
@@ -0,0 +1,56 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+
+
+impl BuildingID {
+    pub fn on_bid(self, firm: ConstructionFirmID, n_projects: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_on_bid(firm, n_projects));
+    }
+    
+    pub fn advance_construction(self, materials: ResourceAmount, labor: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_advance_construction(materials, labor));
+    }
+    
+    pub fn contractor_gone(self, firm: ConstructionFirmID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_contractor_gone(firm));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_on_bid(pub ConstructionFirmID, pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_advance_construction(pub ResourceAmount, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_contractor_gone(pub ConstructionFirmID);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_on_bid(firm, n_projects), instance, world| {
+            instance.on_bid(firm, n_projects, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_advance_construction(materials, labor), instance, world| {
+            instance.advance_construction(materials, labor, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_contractor_gone(firm), instance, world| {
+            instance.contractor_gone(firm, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,278 @@
+use kay::{World, TypedID, Actor};
+use compact::{CVec, COption};
+use cb_time::actors::TimeID;
+use cb_time::units::{Instant, Duration, Ticks};
+use cb_util::log::{debug, info};
+use economy::resources::{Resource, ResourceAmount, amount_to_f32};
+use economy::unmet_demand::UnmetDemandID;
+use economy::households::household_kinds::construction_firm::ConstructionFirmID;
+use economy::households::household_kinds::neighboring_town_trade::trade_price;
+use super::{Building, BuildingID, BuildingStyle, rendering};
+const LOG_T: &str = "Construction Sites";
+
+// how often a site without a contractor asks construction firms again
+const TENDER_INTERVAL: Duration = Duration(60 * 60);
+// until then, nobody bidding means the city has no construction firm yet.
+// Builders from neighboring towns step in after that
+const MAX_UNANSWERED_TENDERS: u32 = 48;
+// the building is only shown anew once it grew this much
+const RENDERED_PROGRESS_STEP: f32 = 0.1;
+// the part of the materials in a torn down building that can be sold on
+const SALVAGED_SHARE: f32 = 0.25;
+
+// Materials and hours of work it takes to put up a building, buildings
+// that need none appear right away. Construction firms put up their own
+// yards, so that there is somebody to build everything else
+pub fn construction_inputs(style: BuildingStyle) -> Option<(ResourceAmount, f32)> {
+    match style {
+        BuildingStyle::FamilyHouse => Some((amount!(100.0), 40.0)),
+        BuildingStyle::GroceryShop
+        | BuildingStyle::Mill
+        | BuildingStyle::Bakery
+        | BuildingStyle::CarDealer
+        | BuildingStyle::TaxiDepot
+        | BuildingStyle::Restaurant => Some((amount!(150.0), 60.0)),
+        BuildingStyle::School | BuildingStyle::Hospital | BuildingStyle::Cinema => {
+            Some((amount!(400.0), 160.0))
+        }
+        // only the extension, the building it grows from still stands
+        BuildingStyle::ApartmentBlock | BuildingStyle::DepartmentStore => {
+            Some((amount!(300.0), 120.0))
+        }
+        BuildingStyle::PowerPlant | BuildingStyle::Waterworks => Some((amount!(600.0), 240.0)),
+        BuildingStyle::Field | BuildingStyle::Park => Some((amount!(20.0), 15.0)),
+        BuildingStyle::ConstructionYard | BuildingStyle::NeighboringTownConnection => None,
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct ConstructionSite {
+    contractor: Option<ConstructionFirmID>,
+    // construction firms that want the project, with how many they already have
+    bids: CVec<(ConstructionFirmID, u32)>,
+    next_tender: Option<Instant>,
+    n_unanswered_tenders: u32,
+    materials_needed: ResourceAmount,
+    materials_delivered: ResourceAmount,
+    labor_needed: f32,
+    labor_done: f32,
+    rendered_progress: f32,
+}
+
+impl ConstructionSite {
+    pub fn new(materials_needed: ResourceAmount, labor_needed: f32) -> ConstructionSite {
+        ConstructionSite {
+            contractor: None,
+            bids: CVec::new(),
+            next_tender: None,
+            n_unanswered_tenders: 0,
+            materials_needed,
+            materials_delivered: amount!(0.0),
+            labor_needed,
+            labor_done: 0.0,
+            rendered_progress: 0.0,
+        }
+    }
+
+    // materials nobody works with and work without materials don't get a
+    // building anywhere, whichever is lagging behind decides
+    pub fn progress(&self) -> f32 {
+        amount_to_f32(self.materials_delivered / self.materials_needed)
+            .min(self.labor_done / self.labor_needed)
+            .min(1.0)
+    }
+}
+
+impl Building {
+    pub fn construction_progress(&self) -> Option<f32> {
+        self.construction.0.as_ref().map(ConstructionSite::progress)
+    }
+
+    pub fn is_under_construction(&self) -> bool {
+        self.construction.is_some()
+    }
+
+    // What the city gets for the materials of a torn down building that can be
+    // reused, at the price neighboring towns sell them for. A site only
+    // has what was delivered to it so far
+    pub(super) fn salvage_value(&self) -> ResourceAmount {
+        let materials = match self.construction.0 {
+            Some(ref site) => site.materials_delivered,
+            None => construction_inputs(self.style)
+                .map(|(materials, _)| materials)
+                .unwrap_or(amount!(0.0)),
+        };
+        let price = trade_price(Resource::BuildingMaterials).unwrap_or(amount!(0.0));
+        SALVAGED_SHARE * materials * price
+    }
+
+    pub(super) fn tender(&self, world: &mut World) {
+        ConstructionFirmID::global_broadcast(world).tender(self.id, world);
+    }
+
+    pub fn on_bid(&mut self, firm: ConstructionFirmID, n_projects: u32, _: &mut World) {
+        if let Some(ref mut site) = self.construction.0 {
+            if site.contractor.is_none() {
+                site.bids.push((firm, n_projects));
+            }
+        }
+    }
+
+    // Awards the project to the least busy firm that bid since the last
+    // check, or asks again later if there was none
+    pub(super) fn award_contract(&mut self, instant: Instant, world: &mut World) {
+        let mut finished_by_outsiders = false;
+
+        if let Some(ref mut site) = self.construction.0 {
+            if site.contractor.is_some() {
+                return;
+            }
+
+            let best_bid = site
+                .bids
+                .iter()
+                .min_by_key(|&&(_, n_projects)| n_projects)
+                .cloned();
+
+            if let Some((firm, _)) = best_bid {
+                debug(LOG_T, format!("Awarded to {:?}", firm), self.id, world);
+                site.contractor = Some(firm);
+                site.bids.clear();
+                firm.award(
+                    self.id,
+                    site.materials_needed - site.materials_delivered,
+                    site.labor_needed - site.labor_done,
+                    world,
+                );
+            } else if site.next_tender.map_or(true, |due| instant >= due) {
+                site.n_unanswered_tenders += 1;
+
+                if site.n_unanswered_tenders >= MAX_UNANSWERED_TENDERS {
+                    finished_by_outsiders = true;
+                } else {
+                    UnmetDemandID::global_first(world).record(
+                        Resource::Construction,
+                        self.id.into(),
+                        world,
+                    );
+                    ConstructionFirmID::global_broadcast(world).tender(self.id, world);
+                    site.next_tender = Some(instant + TENDER_INTERVAL);
+                    TimeID::local_first(world).wake_up_in(
+                        Ticks::from(TENDER_INTERVAL),
+                        self.id.into(),
+                        world,
+                    );
+                }
+            }
+        }
+
+        if finished_by_outsiders {
+            info(
+                LOG_T,
+                format!("{:?} built by builders from a neighboring town", self.style),
+                self.id,
+                world,
+            );
+            self.finish_construction(world);
+        }
+    }
+
+    pub fn advance_construction(
+        &mut self,
+        materials: ResourceAmount,
+        labor: f32,
+        world: &mut World,
+    ) {
+        let (progress, rendered_progress) = match self.construction.0 {
+            Some(ref mut site) => {
+                site.materials_delivered += materials;
+                site.labor_done += labor;
+                (site.progress(), site.rendered_progress)
+            }
+            None => return,
+        };
+
+        if progress >= 1.0 {
+            self.finish_construction(world);
+        } else if progress - rendered_progress >= RENDERED_PROGRESS_STEP {
+            if let Some(ref mut site) = self.construction.0 {
+                site.rendered_progress = progress;
+            }
+            // Refresh appearance
+            rendering::on_destroy(self.id, world);
+            rendering::on_add(
+                self.id,
+                &self.lot,
+                self.all_households(),
+                self.style,
+                Some(progress),
+                world,
+            );
+        }
+    }
+
+    // The contractor went out of business, the project is up for grabs again
+    pub fn contractor_gone(&mut self, firm: ConstructionFirmID, world: &mut World) {
+        let was_contractor = match self.construction.0 {
+            Some(ref mut site) if site.contractor == Some(firm) => {
+                site.contractor = None;
+                site.next_tender = None;
+                site.n_unanswered_tenders = 0;
+                true
+            }
+            _ => false,
+        };
+
+        if was_contractor {
+            self.tender(world);
+            TimeID::local_first(world).wake_up_in(
+                Ticks::from(Duration::from_minutes(10)),
+                self.id_as(),
+                world,
+            );
+        }
+    }
+
+    fn finish_construction(&mut self, world: &mut World) {
+        if let Some(site) = self.construction.0.take() {
+            if let Some(contractor) = site.contractor {
+                contractor.project_completed(self.id, world);
+            }
+        }
+
+        debug(LOG_T, format!("Finished {:?}", self.style), self.id, world);
+        self.list_vacant_dwellings(world);
+        self.enter_grid_layers(world);
+        self.connect_utilities(world);
+        self.advertise_parking(world);
+        self.open_park(world);
+        // Refresh appearance
+        rendering::on_destroy(self.id, world);
+        rendering::on_add(
+            self.id,
+            &self.lot,
+            self.all_households(),
+            self.style,
+            None,
+            world,
+        );
+    }
+
+    pub(super) fn cancel_construction(&mut self, world: &mut World) {
+        if let Some(site) = self.construction.0.take() {
+            if let Some(contractor) = site.contractor {
+                contractor.project_cancelled(self.id, world);
+            }
+        }
+    }
+}
+
+pub fn construction_site_for(style: BuildingStyle) -> COption<ConstructionSite> {
+    COption(
+        construction_inputs(style)
+            .map(|(materials, labor)| ConstructionSite::new(materials, labor)),
+    )
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,32 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+
+
+impl BuildingID {
+    pub fn consider_densifying(self, demand: ZoneDemand, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_consider_densifying(demand, instant));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_consider_densifying(pub ZoneDemand, pub Instant);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_consider_densifying(demand, instant), instance, world| {
+            instance.consider_densifying(demand, instant, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
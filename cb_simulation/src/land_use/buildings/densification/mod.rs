@@ -0,0 +1,106 @@
+// Standing buildings grow denser in place once their land is valuable enough
+// and the city asks for more of what they offer: a family house becomes an
+// apartment block, a grocery shop a department store. Whoever lives or works
+// there stays while the extension is built, its new units only open once it stands
+use kay::{World, TypedID};
+use cb_time::actors::TimeID;
+use cb_time::units::{Instant, Ticks, Duration};
+use cb_util::log::info;
+use cb_util::random::{seed, Rng};
+use economy::immigration_and_development::ZoneDemand;
+use economy::utilities::UtilityNetworkID;
+use transport::parking::ParkingRegistryID;
+use land_use::grid_layers::GridLayersID;
+use super::{Building, BuildingID, BuildingStyle, Unit, units_for_style, rendering};
+use super::construction_site::construction_site_for;
+const LOG_T: &str = "Densification";
+
+const MIN_LAND_VALUE: f32 = 5.0;
+const MIN_DEMAND: f32 = 0.5;
+// so that not all buildings of a valuable neighborhood grow at once
+const CHANCE_PER_CHECK: f32 = 0.05;
+
+impl BuildingStyle {
+    pub fn denser(self) -> Option<BuildingStyle> {
+        match self {
+            BuildingStyle::FamilyHouse => Some(BuildingStyle::ApartmentBlock),
+            BuildingStyle::GroceryShop => Some(BuildingStyle::DepartmentStore),
+            _ => None,
+        }
+    }
+
+    fn demand_for_denser(self, demand: ZoneDemand) -> f32 {
+        match self {
+            BuildingStyle::FamilyHouse => demand.residential,
+            BuildingStyle::GroceryShop => demand.commercial,
+            _ => 0.0,
+        }
+    }
+}
+
+impl Building {
+    pub fn consider_densifying(&mut self, demand: ZoneDemand, instant: Instant, world: &mut World) {
+        if self.is_under_construction() || self.being_destroyed_for.is_some() {
+            return;
+        }
+
+        if let Some(denser_style) = self.style.denser() {
+            let fully_used = self.units.iter().all(|&Unit(household, _)| household.is_some());
+            let justified = fully_used
+                && self.land_value >= MIN_LAND_VALUE
+                && self.style.demand_for_denser(demand) >= MIN_DEMAND;
+
+            if justified && seed((instant.ticks(), self.id, 4u8)).gen::<f32>() < CHANCE_PER_CHECK {
+                self.densify(denser_style, world);
+            }
+        }
+    }
+
+    fn densify(&mut self, denser_style: BuildingStyle, world: &mut World) {
+        info(
+            LOG_T,
+            format!("{:?} grows into {:?}", self.style, denser_style),
+            self.id,
+            world,
+        );
+
+        // everything that depends on the style is entered
+        // again once the extension is finished
+        GridLayersID::global_first(world).remove_building(
+            self.id,
+            self.style,
+            self.lot.center_point(),
+            world,
+        );
+        UtilityNetworkID::global_first(world).disconnect(self.id, world);
+        ParkingRegistryID::global_first(world).remove_lot(self.id, world);
+
+        let n_existing_units = self.units.len();
+        for &unit in units_for_style(denser_style).iter().skip(n_existing_units) {
+            self.units.push(unit);
+        }
+        self.style = denser_style;
+        self.construction = construction_site_for(denser_style);
+
+        self.tender(world);
+        TimeID::local_first(world).wake_up_in(
+            Ticks::from(Duration::from_minutes(10)),
+            self.id_as(),
+            world,
+        );
+
+        // Refresh appearance
+        rendering::on_destroy(self.id, world);
+        rendering::on_add(
+            self.id,
+            &self.lot,
+            self.all_households(),
+            self.style,
+            self.construction_progress(),
+            world,
+        );
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
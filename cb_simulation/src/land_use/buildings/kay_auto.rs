@@ -5,7 +5,72 @@ use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTra
 #[allow(unused_imports)]
 use super::*;
 
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct UnitRequesterID {
+    _raw_id: RawID
+}
 
+impl Copy for UnitRequesterID {}
+impl Clone for UnitRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for UnitRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "UnitRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for UnitRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for UnitRequesterID {
+    fn eq(&self, other: &UnitRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for UnitRequesterID {}
+
+pub struct UnitRequesterRepresentative;
+
+impl ActorOrActorTrait for UnitRequesterRepresentative {
+    type ID = UnitRequesterID;
+}
+
+impl TypedID for UnitRequesterID {
+    type Target = UnitRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        UnitRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + UnitRequester> TraitIDFrom<Act> for UnitRequesterID {}
+
+impl UnitRequesterID {
+    pub fn on_unit_offer(self, building: BuildingID, unit_idx: UnitIdx, land_value: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_UnitRequester_on_unit_offer(building, unit_idx, land_value));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<UnitRequesterRepresentative>();
+        system.register_trait_message::<MSG_UnitRequester_on_unit_offer>();
+    }
+
+    pub fn register_implementor<Act: Actor + UnitRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, UnitRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_UnitRequester_on_unit_offer(building, unit_idx, land_value), instance, world| {
+                instance.on_unit_offer(building, unit_idx, land_value, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_UnitRequester_on_unit_offer(pub BuildingID, pub UnitIdx, pub f32);
 
 impl Actor for Building {
     type ID = BuildingID;
@@ -62,7 +127,7 @@ impl BuildingID {
         id
     }
     
-    pub fn try_offer_unit(self, required_unit_type: UnitType, requester: ImmigrationManagerID, world: &mut World) {
+    pub fn try_offer_unit(self, required_unit_type: UnitType, requester: UnitRequesterID, world: &mut World) {
         world.send(self.as_raw(), MSG_Building_try_offer_unit(required_unit_type, requester));
     }
     
@@ -74,33 +139,75 @@ impl BuildingID {
         world.send(self.as_raw(), MSG_Building_remove_household(household));
     }
     
+    pub fn forget_households(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_forget_households());
+    }
+    
+    pub fn on_utility_supply(self, utility: Utility, supplied: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_on_utility_supply(utility, supplied));
+    }
+    
+    pub fn bill_utility(self, utility: Utility, amount: ResourceAmount, upgrade_subsidy: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_bill_utility(utility, amount, upgrade_subsidy));
+    }
+    
+    pub fn install_upgrade(self, upgrade: EfficiencyUpgrade, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_install_upgrade(upgrade));
+    }
+    
     pub fn finally_destroy(self, world: &mut World) {
         world.send(self.as_raw(), MSG_Building_finally_destroy());
     }
     
+    pub fn measure_field(self, farm: FarmID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_measure_field(farm));
+    }
+    
     pub fn get_ui_info(self, requester: LandUseUIID, world: &mut World) {
         world.send(self.as_raw(), MSG_Building_get_ui_info(requester));
     }
     
+    pub fn report_vacancy(self, requester: MigrationID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_report_vacancy(requester));
+    }
+    
     pub fn reconnect(self, new_location: PreciseLocation, new_connection_point: P2, world: &mut World) {
         world.send(self.as_raw(), MSG_Building_reconnect(new_location, new_connection_point));
     }
+    
+    pub fn roll_city_events(self, events: CityEventsID, instant: Instant, disaster_frequency_factor: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_roll_city_events(events, instant, disaster_frequency_factor));
+    }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Building_spawn(pub BuildingID, pub BuildingStyle, pub Lot);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Building_try_offer_unit(pub UnitType, pub ImmigrationManagerID);
+struct MSG_Building_try_offer_unit(pub UnitType, pub UnitRequesterID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Building_add_household(pub HouseholdID, pub UnitIdx);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Building_remove_household(pub HouseholdID);
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_forget_households();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_on_utility_supply(pub Utility, pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_bill_utility(pub Utility, pub ResourceAmount, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_install_upgrade(pub EfficiencyUpgrade);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Building_finally_destroy();
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_measure_field(pub FarmID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Building_get_ui_info(pub LandUseUIID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_report_vacancy(pub MigrationID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Building_reconnect(pub PreciseLocation, pub P2);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_roll_city_events(pub CityEventsID, pub Instant, pub f32);
 
 impl Into<ConstructableID<CBPrototypeKind>> for BuildingID {
     fn into(self) -> ConstructableID<CBPrototypeKind> {
@@ -120,20 +227,48 @@ impl Into<SleeperID> for BuildingID {
     }
 }
 
+impl Into<GridLayerRequesterID> for BuildingID {
+    fn into(self) -> GridLayerRequesterID {
+        GridLayerRequesterID::from_raw(self.as_raw())
+    }
+}
+
 impl Into<RoughLocationID> for BuildingID {
     fn into(self) -> RoughLocationID {
         RoughLocationID::from_raw(self.as_raw())
     }
 }
 
+impl Into<EmergencyCallerID> for BuildingID {
+    fn into(self) -> EmergencyCallerID {
+        EmergencyCallerID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SnapshotContributorID> for BuildingID {
+    fn into(self) -> SnapshotContributorID {
+        SnapshotContributorID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<ExportableID> for BuildingID {
+    fn into(self) -> ExportableID {
+        ExportableID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
-    
+    UnitRequesterID::register_trait(system);
     ConstructableID::<CBPrototypeKind>::register_implementor::<Building>(system);
     AttacheeID::register_implementor::<Building>(system);
     SleeperID::register_implementor::<Building>(system);
+    GridLayerRequesterID::register_implementor::<Building>(system);
     RoughLocationID::register_implementor::<Building>(system);
+    EmergencyCallerID::register_implementor::<Building>(system);
+    SnapshotContributorID::register_implementor::<Building>(system);
+    ExportableID::register_implementor::<Building>(system);
     system.add_spawner::<Building, _, _>(
         |&MSG_Building_spawn(id, style, ref lot), world| {
             Building::spawn(id, style, lot, world)
@@ -158,21 +293,63 @@ pub fn auto_setup(system: &mut ActorSystem) {
         }, false
     );
     
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_forget_households(), instance, world| {
+            instance.forget_households(world)
+        }, false
+    );
+    
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_on_utility_supply(utility, supplied), instance, world| {
+            instance.on_utility_supply(utility, supplied, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_bill_utility(utility, amount, upgrade_subsidy), instance, world| {
+            instance.bill_utility(utility, amount, upgrade_subsidy, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_install_upgrade(upgrade), instance, world| {
+            instance.install_upgrade(upgrade, world); Fate::Live
+        }, false
+    );
+    
     system.add_handler::<Building, _, _>(
         |&MSG_Building_finally_destroy(), instance, world| {
             instance.finally_destroy(world)
         }, false
     );
     
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_measure_field(farm), instance, world| {
+            instance.measure_field(farm, world); Fate::Live
+        }, false
+    );
+    
     system.add_handler::<Building, _, _>(
         |&MSG_Building_get_ui_info(requester), instance, world| {
             instance.get_ui_info(requester, world); Fate::Live
         }, false
     );
     
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_report_vacancy(requester), instance, world| {
+            instance.report_vacancy(requester, world); Fate::Live
+        }, false
+    );
+    
     system.add_handler::<Building, _, _>(
         |&MSG_Building_reconnect(new_location, new_connection_point), instance, world| {
             instance.reconnect(new_location, new_connection_point, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_roll_city_events(events, instant, disaster_frequency_factor), instance, world| {
+            instance.roll_city_events(events, instant, disaster_frequency_factor, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
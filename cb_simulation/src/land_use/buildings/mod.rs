@@ -1,6 +1,6 @@
 use kay::{ActorSystem, World, Actor, Fate, TypedID};
 use compact::{CVec, COption};
-use descartes::P2;
+use descartes::{N, P2};
 
 use transport::lane::LaneID;
 use cb_time::actors::TimeID;
@@ -11,16 +11,57 @@ use planning::{CBConstructionID, CBPrototypeKind};
 
 pub mod rendering;
 pub mod architecture;
+pub mod construction_site;
+pub mod densification;
 
 use economy::households::HouseholdID;
+use economy::households::household_kinds::farming::FarmID;
 use transport::pathfinding::PreciseLocation;
-use economy::immigration_and_development::ImmigrationManagerID;
+use transport::parking::ParkingRegistryID;
+use economy::migration::MigrationID;
+use economy::housing::{Dwelling, HousingMarketID};
+use economy::households::aging::MAX_FAMILY_SIZE;
+use economy::utilities::{Utility, UtilityNetworkID, ALL_UTILITIES, EfficiencyUpgrade, ALL_UPGRADES};
+use economy::resources::{Resource, ResourceAmount};
+use economy::market::Deal;
+use economy::policies::TreasuryID;
+use economy::households::MemberIdx;
+use economy::households::household_kinds::leisure_venue::{LeisureVenueID, park_capacity};
+use economy::households::aging::DAYS_PER_YEAR;
 use land_use::zone_planning::{Lot, LandUse};
+use land_use::grid_layers::{GridLayersID, LayerKind, GridLayerRequester, GridLayerRequesterID};
 use super::ui::{LandUseUIID};
+use self::construction_site::{ConstructionSite, construction_site_for};
 
 use cb_util::log::debug;
+use cb_util::random::{seed, Rng};
 const LOG_T: &str = "Buildings";
 
+// dwellings differ in how many members of a household they have room for
+const MIN_DWELLING_CAPACITY: u32 = 2;
+// owners only invest in efficiency upgrades that pay for themselves within this time
+const MAX_UPGRADE_PAYBACK_YEARS: u32 = 3;
+
+// Anyone looking for a unit to move a household into
+pub trait UnitRequester {
+    fn on_unit_offer(
+        &mut self,
+        building: BuildingID,
+        unit_idx: UnitIdx,
+        land_value: f32,
+        world: &mut World,
+    );
+}
+
+// each unit of land value makes a site for a business as good as one this much
+// closer to where its customers are, since more and wealthier ones pass by
+const METERS_PER_LAND_VALUE: N = 50.0;
+
+// lower is better, for choosing among the units offered to a business
+pub fn business_site_cost(distance: N, land_value: f32) -> N {
+    distance - METERS_PER_LAND_VALUE * land_value
+}
+
 #[derive(Copy, Clone)]
 pub struct Unit(Option<HouseholdID>, UnitType);
 
@@ -31,10 +72,20 @@ pub enum UnitType {
     Agriculture,
     Mill,
     Bakery,
+    School,
+    Hospital,
+    CarDealer,
     NeighboringTownTrade,
+    Restaurant,
+    Park,
+    Cinema,
+    ConstructionFirm,
+    PowerPlant,
+    Waterworks,
+    TaxiCompany,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub struct UnitIdx(usize);
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -44,20 +95,93 @@ pub enum BuildingStyle {
     Field,
     Mill,
     Bakery,
+    School,
+    Hospital,
+    CarDealer,
     NeighboringTownConnection,
+    // added later, after the ones above so existing savegames stay readable
+    Restaurant,
+    Park,
+    Cinema,
+    ConstructionYard,
+    PowerPlant,
+    Waterworks,
+    TaxiDepot,
+    // only grown from the styles above, see `densification`
+    ApartmentBlock,
+    DepartmentStore,
 }
 
 impl BuildingStyle {
     pub fn can_appear_in(self, land_use: LandUse) -> bool {
         match (self, land_use) {
             (BuildingStyle::FamilyHouse, LandUse::Residential) => true,
+            (BuildingStyle::ApartmentBlock, LandUse::Residential) => true,
             (BuildingStyle::GroceryShop, LandUse::Commercial) => true,
             (BuildingStyle::Mill, LandUse::Commercial) => true,
             (BuildingStyle::Bakery, LandUse::Commercial) => true,
+            (BuildingStyle::CarDealer, LandUse::Commercial) => true,
+            (BuildingStyle::Restaurant, LandUse::Commercial) => true,
+            (BuildingStyle::Cinema, LandUse::Commercial) => true,
+            (BuildingStyle::DepartmentStore, LandUse::Commercial) => true,
+            (BuildingStyle::Park, LandUse::Recreational) => true,
             (BuildingStyle::Field, LandUse::Agricultural) => true,
+            (BuildingStyle::School, LandUse::Administrative) => true,
+            (BuildingStyle::Hospital, LandUse::Administrative) => true,
+            (BuildingStyle::ConstructionYard, LandUse::Industrial) => true,
+            (BuildingStyle::ConstructionYard, LandUse::Commercial) => true,
+            (BuildingStyle::PowerPlant, LandUse::Industrial) => true,
+            (BuildingStyle::Waterworks, LandUse::Industrial) => true,
+            (BuildingStyle::TaxiDepot, LandUse::Commercial) => true,
+            (BuildingStyle::TaxiDepot, LandUse::Industrial) => true,
             _ => false,
         }
     }
+
+    // What a standing building draws from the utility network,
+    // in homes' worth. Fields, parks and yards get by without
+    pub fn utility_demand(self, utility: Utility) -> f32 {
+        match (self, utility) {
+            (BuildingStyle::FamilyHouse, _) => 1.0,
+            (BuildingStyle::GroceryShop, _)
+            | (BuildingStyle::CarDealer, _)
+            | (BuildingStyle::TaxiDepot, _)
+            | (BuildingStyle::Cinema, Utility::Water) => 2.0,
+            (BuildingStyle::Bakery, _) | (BuildingStyle::Restaurant, _) => 3.0,
+            (BuildingStyle::Mill, Utility::Electricity)
+            | (BuildingStyle::Cinema, Utility::Electricity)
+            | (BuildingStyle::School, _) => 6.0,
+            (BuildingStyle::Mill, Utility::Water) => 2.0,
+            (BuildingStyle::Hospital, _) => 10.0,
+            (BuildingStyle::ApartmentBlock, _) => 6.0,
+            (BuildingStyle::DepartmentStore, _) => 5.0,
+            (BuildingStyle::Field, _)
+            | (BuildingStyle::Park, _)
+            | (BuildingStyle::ConstructionYard, _)
+            | (BuildingStyle::PowerPlant, _)
+            | (BuildingStyle::Waterworks, _)
+            | (BuildingStyle::NeighboringTownConnection, _) => 0.0,
+        }
+    }
+
+    // How many cars fit on the lot of a standing building,
+    // neighboring towns stand for all the parking beyond the city limits
+    pub fn parking_capacity(self) -> u16 {
+        match self {
+            BuildingStyle::FamilyHouse => 2,
+            BuildingStyle::Field | BuildingStyle::Waterworks => 2,
+            BuildingStyle::Mill | BuildingStyle::Bakery | BuildingStyle::ConstructionYard => 4,
+            BuildingStyle::Restaurant
+            | BuildingStyle::PowerPlant
+            | BuildingStyle::ApartmentBlock => 8,
+            BuildingStyle::GroceryShop | BuildingStyle::Park => 10,
+            BuildingStyle::CarDealer | BuildingStyle::TaxiDepot => 15,
+            BuildingStyle::School | BuildingStyle::DepartmentStore => 20,
+            BuildingStyle::Cinema => 30,
+            BuildingStyle::Hospital => 40,
+            BuildingStyle::NeighboringTownConnection => 500,
+        }
+    }
 }
 
 #[derive(Compact, Clone)]
@@ -69,6 +193,15 @@ pub struct Building {
     style: BuildingStyle,
     being_destroyed_for: COption<CBConstructionID>,
     started_reconnect: bool,
+    // until it stands, it has no room for anybody
+    construction: COption<ConstructionSite>,
+    // utilities the network currently can't supply it with
+    cut_off_from: CVec<Utility>,
+    upgrades: CVec<EfficiencyUpgrade>,
+    // as last looked up, sets the rent of its dwellings
+    land_value: f32,
+    // as last looked up, passed on to the households living or working here
+    pollution: f32,
 }
 
 //use stagemaster::geometry::add_debug_line;
@@ -77,7 +210,15 @@ impl Building {
     pub fn spawn(id: BuildingID, style: BuildingStyle, lot: &Lot, world: &mut World) -> Building {
         debug(LOG_T, format!("Spawned building {:?}", style), id, world);
 
-        rendering::on_add(id, lot, vec![], style, world);
+        let construction = construction_site_for(style);
+        rendering::on_add(
+            id,
+            lot,
+            vec![],
+            style,
+            construction.as_ref().map(ConstructionSite::progress),
+            world,
+        );
 
         TimeID::local_first(world).wake_up_in(
             Ticks::from(Duration::from_minutes(10)),
@@ -85,7 +226,7 @@ impl Building {
             world,
         );
 
-        Building {
+        let building = Building {
             id,
             units: units_for_style(style),
             lot: lot.clone(),
@@ -93,13 +234,53 @@ impl Building {
             style,
             being_destroyed_for: COption(None),
             started_reconnect: false,
+            construction,
+            cut_off_from: CVec::new(),
+            upgrades: CVec::new(),
+            land_value: 0.0,
+            pollution: 0.0,
+        };
+        if building.is_under_construction() {
+            building.tender(world);
+        } else {
+            building.list_vacant_dwellings(world);
+            building.enter_grid_layers(world);
+            building.connect_utilities(world);
+            building.advertise_parking(world);
+            building.open_park(world);
+        }
+        building
+    }
+
+    fn dwelling(&self, idx: usize) -> Dwelling {
+        let capacity =
+            seed((self.id, idx)).gen_range(MIN_DWELLING_CAPACITY, MAX_FAMILY_SIZE as u32 + 1);
+        Dwelling::new(
+            self.id,
+            UnitIdx(idx),
+            capacity,
+            self.style,
+            self.land_value,
+            self.lot.center_point(),
+        )
+    }
+
+    fn list_vacant_dwellings(&self, world: &mut World) {
+        if self.is_under_construction() {
+            return;
+        }
+
+        for (idx, &Unit(household, unit_type)) in self.units.iter().enumerate() {
+            if household.is_none() && unit_type == UnitType::Dwelling {
+                HousingMarketID::global_first(world).list(self.dwelling(idx), world);
+            }
         }
     }
 
     pub fn try_offer_unit(
         &mut self,
         required_unit_type: UnitType,
-        requester: ImmigrationManagerID,
+        requester: UnitRequesterID,
         world: &mut World,
     ) {
         debug(
@@ -111,11 +292,18 @@ impl Building {
             self.id(),
             world,
         );
-        if self.being_destroyed_for.is_none() {
+        if self.is_under_construction() {
+            debug(
+                LOG_T,
+                "...but is still under construction",
+                self.id(),
+                world,
+            );
+        } else if self.being_destroyed_for.is_none() {
             if let Some(idx) = self.units.iter().position(|&Unit(household, unit_type)| {
                 household.is_none() && unit_type == required_unit_type
             }) {
-                requester.on_unit_offer(self.id, UnitIdx(idx), world);
+                requester.on_unit_offer(self.id, UnitIdx(idx), self.land_value, world);
                 debug(LOG_T, "...and responded positively!", self.id(), world);
             } else {
                 debug(LOG_T, "...but doesn't have the unit type", self.id(), world);
@@ -127,9 +315,20 @@ impl Building {
 
     pub fn add_household(&mut self, household: HouseholdID, unit: UnitIdx, world: &mut World) {
         self.units[unit.0].0 = Some(household);
+        for &utility in &self.cut_off_from {
+            household.on_utility_supply(utility, false, world);
+        }
+        household.on_air_pollution(self.pollution, world);
         // Refresh appearance
         rendering::on_destroy(self.id, world);
-        rendering::on_add(self.id, &self.lot, self.all_households(), self.style, world);
+        rendering::on_add(
+            self.id,
+            &self.lot,
+            self.all_households(),
+            self.style,
+            self.construction_progress(),
+            world,
+        );
     }
 
     pub fn remove_household(&mut self, household: HouseholdID, world: &mut World) {
@@ -139,13 +338,48 @@ impl Building {
             .position(|&Unit(user, _)| user == Some(household))
             .expect("Tried to remove a household not in the building");
         self.units[position].0 = None;
+        self.appraise(world);
 
         if self.being_destroyed_for.is_some() && self.all_households().is_empty() {
             self.id.finally_destroy(world);
         } else {
+            if self.being_destroyed_for.is_none() && self.units[position].1 == UnitType::Dwelling {
+                HousingMarketID::global_first(world).list(self.dwelling(position), world);
+            }
+            // Refresh appearance
+            rendering::on_destroy(self.id, world);
+            rendering::on_add(
+                self.id,
+                &self.lot,
+                self.all_households(),
+                self.style,
+                self.construction_progress(),
+                world,
+            );
+        }
+    }
+
+    // after the households were dropped from a savegame that couldn't be fully loaded
+    pub fn forget_households(&mut self, world: &mut World) -> Fate {
+        for unit in self.units.iter_mut() {
+            unit.0 = None;
+        }
+
+        if self.being_destroyed_for.is_some() {
+            self.finally_destroy(world)
+        } else {
+            self.list_vacant_dwellings(world);
             // Refresh appearance
             rendering::on_destroy(self.id, world);
-            rendering::on_add(self.id, &self.lot, self.all_households(), self.style, world);
+            rendering::on_add(
+                self.id,
+                &self.lot,
+                vec![],
+                self.style,
+                self.construction_progress(),
+                world,
+            );
+            Fate::Live
         }
     }
 
@@ -156,7 +390,162 @@ impl Building {
             .collect()
     }
 
+    // only standing buildings count for land value and coverage
+    fn enter_grid_layers(&self, world: &mut World) {
+        GridLayersID::global_first(world).add_building(
+            self.id,
+            self.style,
+            self.lot.center_point(),
+            world,
+        );
+        self.appraise(world);
+    }
+
+    // land value and pollution change slowly, so it is enough to look them up
+    // again whenever a unit is vacated, for the next tenant's rent
+    fn appraise(&self, world: &mut World) {
+        for &kind in &[LayerKind::LandValue, LayerKind::Pollution] {
+            GridLayersID::global_first(world).get_value(
+                kind,
+                self.lot.center_point(),
+                self.id_as(),
+                world,
+            );
+        }
+    }
+
+    fn utility_demand(&self, utility: Utility) -> f32 {
+        self.upgrades
+            .iter()
+            .fold(self.style.utility_demand(utility), |demand, upgrade| {
+                demand * (1.0 - upgrade.saving(utility))
+            })
+    }
+
+    fn advertise_parking(&self, world: &mut World) {
+        ParkingRegistryID::global_first(world).add_lot(
+            self.id,
+            self.lot.center_point(),
+            self.style.parking_capacity(),
+            world,
+        );
+    }
+
+    // parks are public, so their keeper moves in as soon as they are laid out
+    fn open_park(&self, world: &mut World) {
+        if self.style != BuildingStyle::Park {
+            return;
+        }
+
+        for (idx, &Unit(household, unit_type)) in self.units.iter().enumerate() {
+            if household.is_none() && unit_type == UnitType::Park {
+                let venue = LeisureVenueID::open_park(
+                    self.id,
+                    park_capacity(self.lot_area()),
+                    TimeID::local_first(world),
+                    world,
+                );
+                self.id.add_household(venue.into(), UnitIdx(idx), world);
+            }
+        }
+    }
+
+    fn connect_utilities(&self, world: &mut World) {
+        for &utility in &ALL_UTILITIES {
+            let demand = self.utility_demand(utility);
+            if demand > 0.0 {
+                UtilityNetworkID::global_first(world).connect(
+                    self.id,
+                    utility,
+                    self.lot.center_point(),
+                    demand,
+                    world,
+                );
+            }
+        }
+    }
+
+    pub fn on_utility_supply(&mut self, utility: Utility, supplied: bool, world: &mut World) {
+        self.cut_off_from.retain(|&cut_off| cut_off != utility);
+        if !supplied {
+            self.cut_off_from.push(utility);
+        }
+
+        for household in self.all_households() {
+            household.on_utility_supply(utility, supplied, world);
+        }
+    }
+
+    // All households in the building share the bill. The first of them looks
+    // after the building and invests in an upgrade that would pay for itself
+    // soon enough, counting only what it has to pay after subsidies
+    pub fn bill_utility(
+        &mut self,
+        utility: Utility,
+        amount: ResourceAmount,
+        upgrade_subsidy: f32,
+        world: &mut World,
+    ) {
+        let households = self.all_households();
+        if households.is_empty() {
+            return;
+        }
+
+        let share = amount / households.len() as f32;
+        for &household in &households {
+            household.provide_deal(
+                Deal::new(Some((Resource::Money, share)), Duration(0)),
+                MemberIdx(0),
+                world,
+            );
+        }
+        UtilityNetworkID::global_first(world).utility_bill_paid(utility, amount, world);
+
+        let maybe_upgrade = ALL_UPGRADES.iter().find(|&&upgrade| {
+            let daily_saving = amount * upgrade.saving(utility);
+            let own_cost = upgrade.cost_per_unit()
+                * self.style.utility_demand(utility)
+                * (1.0 - upgrade_subsidy);
+            !self.upgrades.contains(&upgrade)
+                && daily_saving > 0.0
+                && own_cost <= daily_saving * (MAX_UPGRADE_PAYBACK_YEARS * DAYS_PER_YEAR) as f32
+        });
+
+        if let Some(&upgrade) = maybe_upgrade {
+            let cost = upgrade.cost_per_unit() * self.style.utility_demand(utility);
+            households[0].invest_in_upgrade(self.id, upgrade, cost, cost * upgrade_subsidy, world);
+        }
+    }
+
+    pub fn install_upgrade(&mut self, upgrade: EfficiencyUpgrade, world: &mut World) {
+        if self.upgrades.contains(&upgrade) {
+            return;
+        }
+        self.upgrades.push(upgrade);
+        for &utility in &ALL_UTILITIES {
+            let demand = self.utility_demand(utility);
+            if demand > 0.0 {
+                UtilityNetworkID::global_first(world).set_demand(self.id, utility, demand, world);
+            }
+        }
+    }
+
     pub fn finally_destroy(&mut self, world: &mut World) -> Fate {
+        let salvage_value = self.salvage_value();
+        if salvage_value > 0.0 {
+            TreasuryID::global_first(world).deposit(salvage_value, world);
+        }
+        UtilityNetworkID::global_first(world).disconnect(self.id, world);
+        ParkingRegistryID::global_first(world).remove_lot(self.id, world);
+        if !self.is_under_construction() {
+            GridLayersID::global_first(world).remove_building(
+                self.id,
+                self.style,
+                self.lot.center_point(),
+                world,
+            );
+        }
+        self.cancel_construction(world);
         rendering::on_destroy(self.id, world);
         if let Some(location) = self.location {
             location.link.remove_attachee(self.id_as(), world);
@@ -167,9 +556,40 @@ impl Building {
         Fate::Die
     }
 
+    fn lot_area(&self) -> f32 {
+        self.lot
+            .area
+            .primitives
+            .iter()
+            .map(|primitive| primitive.area().abs())
+            .sum()
+    }
+
+    pub fn measure_field(&mut self, farm: FarmID, world: &mut World) {
+        farm.on_field_measured(self.lot_area(), world);
+    }
+
     pub fn get_ui_info(&mut self, requester: LandUseUIID, world: &mut World) {
         requester.on_building_ui_info(self.id, self.style, self.all_households().into(), world);
     }
+
+    pub fn report_vacancy(&mut self, requester: MigrationID, world: &mut World) {
+        if self.being_destroyed_for.is_some() || self.is_under_construction() {
+            return;
+        }
+
+        let dwellings = self
+            .units
+            .iter()
+            .filter(|&&Unit(_, unit_type)| unit_type == UnitType::Dwelling);
+        let n_dwellings = dwellings.clone().count() as u32;
+        let n_vacant = dwellings
+            .filter(|&&Unit(household, _)| household.is_none())
+            .count() as u32;
+        let is_gateway = self.style == BuildingStyle::NeighboringTownConnection;
+
+        requester.on_vacancy_report(self.id, is_gateway, n_dwellings, n_vacant, world);
+    }
 }
 
 impl Constructable<CBPrototypeKind> for Building {
@@ -182,7 +602,14 @@ impl Constructable<CBPrototypeKind> for Building {
         if let CBPrototypeKind::Lot(ref lot_prototype) = new_prototype.kind {
             self.lot = lot_prototype.lot.clone();
             rendering::on_destroy(self.id, world);
-            rendering::on_add(self.id, &self.lot, self.all_households(), self.style, world);
+            rendering::on_add(
+                self.id,
+                &self.lot,
+                self.all_households(),
+                self.style,
+                self.construction_progress(),
+                world,
+            );
             report_to.action_done(self.id.into(), world);
         } else {
             unreachable!()
@@ -191,14 +618,13 @@ impl Constructable<CBPrototypeKind> for Building {
 
     fn destruct(&mut self, report_to: CBConstructionID, world: &mut World) -> Fate {
         self.being_destroyed_for = COption(Some(report_to));
+        HousingMarketID::global_first(world).delist_building(self.id, world);
 
         if self.all_households().is_empty() {
             self.finally_destroy(world)
         } else {
-            for household in &self.all_households() {
-                household.destroy(world);
-            }
-
+            // everybody is evicted once we know what time it is
+            TimeID::local_first(world).wake_up_in(Ticks::from(Duration(0)), self.id_as(), world);
             Fate::Live
         }
     }
@@ -232,7 +658,14 @@ impl Attachee for Building {
 }
 
 impl Sleeper for Building {
-    fn wake(&mut self, _instant: Instant, world: &mut World) {
+    fn wake(&mut self, instant: Instant, world: &mut World) {
+        if self.being_destroyed_for.is_some() {
+            self.evict_all(instant, world);
+            return;
+        }
+
+        self.award_contract(instant, world);
+
         if self.started_reconnect {
             if self.location.is_none() {
                 // TODO: do we still need to destroy here?
@@ -261,6 +694,38 @@ impl Sleeper for Building {
     }
 }
 
+impl Building {
+    // Families look for a new home, everyone else leaves the city. Each
+    // household removes itself, the last one to do so lets the building go
+    fn evict_all(&mut self, instant: Instant, world: &mut World) {
+        for (household, unit_type) in self
+            .units
+            .iter()
+            .filter_map(|&Unit(user, unit_type)| user.map(|household| (household, unit_type)))
+        {
+            if unit_type == UnitType::Dwelling {
+                HousingMarketID::global_first(world).end_lease(household, world);
+            }
+            household.evicted(instant, world);
+        }
+    }
+}
+
+impl GridLayerRequester for Building {
+    fn on_grid_layer_value(&mut self, kind: LayerKind, _: P2, value: f32, world: &mut World) {
+        match kind {
+            LayerKind::LandValue => self.land_value = value,
+            LayerKind::Pollution => {
+                self.pollution = value;
+                for household in self.all_households() {
+                    household.on_air_pollution(value, world);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 impl Building {
     pub fn reconnect(
         &mut self,
@@ -290,7 +755,87 @@ impl RoughLocation for Building {
     }
 }
 
+use environment::city_events::{self, CityEventsID};
+
+impl Building {
+    pub fn roll_city_events(
+        &mut self,
+        events: CityEventsID,
+        instant: Instant,
+        disaster_frequency_factor: f32,
+        world: &mut World,
+    ) {
+        if self.is_under_construction() || self.being_destroyed_for.is_some() {
+            return;
+        }
+
+        if let Some(kind) =
+            city_events::roll(self.id, self.style, instant, disaster_frequency_factor)
+        {
+            events.propose(kind, self.id, self.lot.center_point(), world);
+        }
+    }
+}
+
+use transport::emergency::{EmergencyCaller, EmergencyCallerID};
+
+// firefighters put out the fire
+impl EmergencyCaller for Building {
+    fn responders_arrived(
+        &mut self,
+        _tag: u8,
+        _response_time: Duration,
+        _instant: Instant,
+        world: &mut World,
+    ) {
+        CityEventsID::global_first(world).fire_put_out(self.id, world);
+    }
+}
+
+use timeline::{SnapshotContributor, TimelineID};
+
+impl SnapshotContributor for Building {
+    fn contribute_to_snapshot(
+        &mut self,
+        timeline: TimelineID,
+        instant: Instant,
+        world: &mut World,
+    ) {
+        let area = self.lot_area();
+        let position = self.lot.center_point();
+        timeline.add_building(instant, position, area, world);
+
+        for &Unit(maybe_household, _) in self.units.iter() {
+            if let Some(household) = maybe_household {
+                household.contribute_to_district_snapshot(timeline, instant, position, world);
+            }
+        }
+    }
+}
+
+use export::{Exportable, ExporterID};
+
+impl Exportable for Building {
+    fn export(&mut self, exporter: ExporterID, world: &mut World) {
+        let area = self.lot_area();
+        let n_households = self
+            .units
+            .iter()
+            .filter(|&&Unit(household, _)| household.is_some())
+            .count() as u32;
+        exporter.on_exported_building(
+            self.id,
+            &self.lot.area.primitives[0].boundary.path(),
+            self.style,
+            area,
+            n_households,
+            world,
+        );
+    }
+}
+
 const FAMILIES_PER_NEIGHBORING_TOWN: usize = 5;
+const FAMILIES_PER_APARTMENT_BLOCK: usize = 6;
 
 pub fn units_for_style(style: BuildingStyle) -> CVec<Unit> {
     match style {
@@ -299,6 +844,25 @@ pub fn units_for_style(style: BuildingStyle) -> CVec<Unit> {
         BuildingStyle::Bakery => vec![Unit(None, UnitType::Bakery)],
         BuildingStyle::Mill => vec![Unit(None, UnitType::Mill)],
         BuildingStyle::Field => vec![Unit(None, UnitType::Agriculture)],
+        BuildingStyle::School => vec![Unit(None, UnitType::School)],
+        BuildingStyle::Hospital => vec![Unit(None, UnitType::Hospital)],
+        BuildingStyle::CarDealer => vec![Unit(None, UnitType::CarDealer)],
+        BuildingStyle::Restaurant => vec![Unit(None, UnitType::Restaurant)],
+        BuildingStyle::Park => vec![Unit(None, UnitType::Park)],
+        BuildingStyle::Cinema => vec![Unit(None, UnitType::Cinema)],
+        BuildingStyle::ConstructionYard => vec![Unit(None, UnitType::ConstructionFirm)],
+        BuildingStyle::PowerPlant => vec![Unit(None, UnitType::PowerPlant)],
+        BuildingStyle::Waterworks => vec![Unit(None, UnitType::Waterworks)],
+        BuildingStyle::TaxiDepot => vec![Unit(None, UnitType::TaxiCompany)],
+        BuildingStyle::ApartmentBlock => {
+            vec![Unit(None, UnitType::Dwelling); FAMILIES_PER_APARTMENT_BLOCK]
+        }
+        // the grocery shop it grew from keeps the first unit
+        BuildingStyle::DepartmentStore => vec![
+            Unit(None, UnitType::Retail),
+            Unit(None, UnitType::Retail),
+            Unit(None, UnitType::Restaurant),
+        ],
         BuildingStyle::NeighboringTownConnection => {
             Some(Unit(None, UnitType::NeighboringTownTrade))
                 .into_iter()
@@ -321,6 +885,8 @@ pub fn setup(system: &mut ActorSystem) {
     system.register::<Building>();
     kay_auto::auto_setup(system);
     rendering::auto_setup(system);
+    construction_site::auto_setup(system);
+    densification::auto_setup(system);
     architecture::setup(system);
 }
 
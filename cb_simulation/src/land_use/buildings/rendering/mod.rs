@@ -1,4 +1,4 @@
-use kay::{ World, TypedID};
+use kay::{World, TypedID};
 use super::{Building, Lot, BuildingID, BuildingStyle};
 use super::super::ui::{LandUseUIID};
 use economy::households::HouseholdID;
@@ -10,6 +10,7 @@ impl Building {
             self.lot.clone(),
             self.all_households().into(),
             self.style,
+            self.construction_progress(),
             world,
         )
     }
@@ -20,6 +21,7 @@ pub fn on_add(
     lot: &Lot,
     households: Vec<HouseholdID>,
     building_type: BuildingStyle,
+    construction_progress: Option<f32>,
     world: &mut World,
 ) {
     LandUseUIID::global_broadcast(world).on_building_constructed(
@@ -27,6 +29,7 @@ pub fn on_add(
         lot.clone(),
         households.into(),
         building_type,
+        construction_progress,
         world,
     );
 }
@@ -0,0 +1,208 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct DistrictListenerID {
+    _raw_id: RawID
+}
+
+impl Copy for DistrictListenerID {}
+impl Clone for DistrictListenerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for DistrictListenerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "DistrictListenerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for DistrictListenerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for DistrictListenerID {
+    fn eq(&self, other: &DistrictListenerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for DistrictListenerID {}
+
+pub struct DistrictListenerRepresentative;
+
+impl ActorOrActorTrait for DistrictListenerRepresentative {
+    type ID = DistrictListenerID;
+}
+
+impl TypedID for DistrictListenerID {
+    type Target = DistrictListenerRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        DistrictListenerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + DistrictListener> TraitIDFrom<Act> for DistrictListenerID {}
+
+impl DistrictListenerID {
+    pub fn on_districts_changed(self, districts: CVec < DistrictArea >, world: &mut World) {
+        world.send(self.as_raw(), MSG_DistrictListener_on_districts_changed(districts));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<DistrictListenerRepresentative>();
+        system.register_trait_message::<MSG_DistrictListener_on_districts_changed>();
+    }
+
+    pub fn register_implementor<Act: Actor + DistrictListener>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, DistrictListenerRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_DistrictListener_on_districts_changed(ref districts), instance, world| {
+                instance.on_districts_changed(districts, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_DistrictListener_on_districts_changed(pub CVec < DistrictArea >);
+
+impl Actor for DistrictRegistry {
+    type ID = DistrictRegistryID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct DistrictRegistryID {
+    _raw_id: RawID
+}
+
+impl Copy for DistrictRegistryID {}
+impl Clone for DistrictRegistryID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for DistrictRegistryID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "DistrictRegistryID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for DistrictRegistryID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for DistrictRegistryID {
+    fn eq(&self, other: &DistrictRegistryID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for DistrictRegistryID {}
+
+impl TypedID for DistrictRegistryID {
+    type Target = DistrictRegistry;
+
+    fn from_raw(id: RawID) -> Self {
+        DistrictRegistryID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl DistrictRegistryID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = DistrictRegistryID::from_raw(world.allocate_instance_id::<DistrictRegistry>());
+        let swarm = world.local_broadcast::<DistrictRegistry>();
+        world.send(swarm, MSG_DistrictRegistry_spawn(id, ));
+        id
+    }
+    
+    pub fn designate(self, origin: Origin, name: CString, outline: CVec < P2 >, policies: DistrictPolicies, world: &mut World) {
+        world.send(self.as_raw(), MSG_DistrictRegistry_designate(origin, name, outline, policies));
+    }
+    
+    pub fn set_policies(self, origin: Origin, district: u16, policies: DistrictPolicies, world: &mut World) {
+        world.send(self.as_raw(), MSG_DistrictRegistry_set_policies(origin, district, policies));
+    }
+    
+    pub fn dissolve(self, origin: Origin, district: u16, world: &mut World) {
+        world.send(self.as_raw(), MSG_DistrictRegistry_dissolve(origin, district));
+    }
+    
+    pub fn get_districts(self, listener: DistrictListenerID, world: &mut World) {
+        world.send(self.as_raw(), MSG_DistrictRegistry_get_districts(listener));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_DistrictRegistry_spawn(pub DistrictRegistryID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_DistrictRegistry_designate(pub Origin, pub CString, pub CVec < P2 >, pub DistrictPolicies);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_DistrictRegistry_set_policies(pub Origin, pub u16, pub DistrictPolicies);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_DistrictRegistry_dissolve(pub Origin, pub u16);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_DistrictRegistry_get_districts(pub DistrictListenerID);
+
+
+
+
+impl LaneID {
+    
+}
+
+
+
+impl Into<DistrictListenerID> for LaneID {
+    fn into(self) -> DistrictListenerID {
+        DistrictListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    DistrictListenerID::register_trait(system);
+    
+    system.add_spawner::<DistrictRegistry, _, _>(
+        |&MSG_DistrictRegistry_spawn(id, ), world| {
+            DistrictRegistry::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<DistrictRegistry, _, _>(
+        |&MSG_DistrictRegistry_designate(origin, ref name, ref outline, policies), instance, world| {
+            instance.designate(origin, name, outline, policies, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<DistrictRegistry, _, _>(
+        |&MSG_DistrictRegistry_set_policies(origin, district, policies), instance, world| {
+            instance.set_policies(origin, district, policies, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<DistrictRegistry, _, _>(
+        |&MSG_DistrictRegistry_dissolve(origin, district), instance, world| {
+            instance.dissolve(origin, district, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<DistrictRegistry, _, _>(
+        |&MSG_DistrictRegistry_get_districts(listener), instance, world| {
+            instance.get_districts(listener, world); Fate::Live
+        }, false
+    );
+    DistrictListenerID::register_implementor::<Lane>(system);
+}
\ No newline at end of file
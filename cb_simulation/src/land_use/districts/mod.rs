@@ -0,0 +1,207 @@
+// Players draw named districts and attach policies to them. Everyone who
+// consults districts keeps their own copy, which the registry updates whenever
+// the player changes one: lanes inside a district keep to its speed limit and
+// fine the trucks it bans, the housing market adds its tax to the rents there
+// and the timeline sums up statistics by district
+use kay::{ActorSystem, World, TypedID};
+use compact::{CVec, CString};
+use descartes::{N, P2};
+use cb_util::log::info;
+use cb_util::session::{permits, Permission, Origin};
+use transport::lane::{Lane, LaneID};
+use transport::microtraffic::{LaneCar, VehicleClass};
+use transport::tolls::encloses;
+const LOG_T: &str = "Districts";
+
+// rent taxes and rebates stay within these shares of the rent
+const MIN_TAX_MODIFIER: f32 = -0.5;
+const MAX_TAX_MODIFIER: f32 = 1.0;
+// in m/s, no district can make its roads slower than walking pace
+const MIN_SPEED_LIMIT: N = 2.0;
+// what a truck pays for driving a lane of a district that bans heavy traffic
+const HEAVY_TRAFFIC_FINE: f32 = 5.0;
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DistrictPolicies {
+    // added to the rents in the district as a share of them, for the
+    // treasury. Negative values are a rebate the treasury pays for
+    pub tax_modifier: f32,
+    // no lane in the district can be driven faster than this, in m/s
+    pub speed_limit: Option<N>,
+    // trucks can still drive through, but are fined for each lane
+    pub heavy_traffic_ban: bool,
+}
+
+impl DistrictPolicies {
+    fn sanitized(self) -> DistrictPolicies {
+        DistrictPolicies {
+            tax_modifier: self.tax_modifier.max(MIN_TAX_MODIFIER).min(MAX_TAX_MODIFIER),
+            speed_limit: self.speed_limit.map(|limit| limit.max(MIN_SPEED_LIMIT)),
+            heavy_traffic_ban: self.heavy_traffic_ban,
+        }
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct DistrictArea {
+    pub id: u16,
+    pub name: CString,
+    pub outline: CVec<P2>,
+    pub policies: DistrictPolicies,
+}
+
+// districts drawn later lie on top of the ones drawn before
+pub fn district_at(districts: &CVec<DistrictArea>, point: P2) -> Option<&DistrictArea> {
+    districts
+        .iter()
+        .rev()
+        .find(|district| encloses(&district.outline, point))
+}
+
+// Anyone who consults the outlines or policies of districts
+pub trait DistrictListener {
+    fn on_districts_changed(&mut self, districts: &CVec<DistrictArea>, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct DistrictRegistry {
+    id: DistrictRegistryID,
+    districts: CVec<DistrictArea>,
+    next_district: u16,
+}
+
+impl DistrictRegistry {
+    pub fn spawn(id: DistrictRegistryID, _: &mut World) -> DistrictRegistry {
+        DistrictRegistry {
+            id,
+            districts: CVec::new(),
+            next_district: 0,
+        }
+    }
+
+    pub fn designate(
+        &mut self,
+        origin: Origin,
+        name: &CString,
+        outline: &CVec<P2>,
+        policies: DistrictPolicies,
+        world: &mut World,
+    ) {
+        if outline.len() < 3 || !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
+        let district = DistrictArea {
+            id: self.next_district,
+            name: name.clone(),
+            outline: outline.clone(),
+            policies: policies.sanitized(),
+        };
+        self.next_district = self.next_district.wrapping_add(1);
+
+        info(
+            LOG_T,
+            format!("District {} designated", district.name.to_string()),
+            self.id,
+            world,
+        );
+        self.districts.push(district);
+        self.announce(world);
+    }
+
+    pub fn set_policies(
+        &mut self,
+        origin: Origin,
+        district: u16,
+        policies: DistrictPolicies,
+        world: &mut World,
+    ) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
+        if let Some(district) = self.districts.iter_mut().find(|other| other.id == district) {
+            district.policies = policies.sanitized();
+        }
+        self.announce(world);
+    }
+
+    pub fn dissolve(&mut self, origin: Origin, district: u16, world: &mut World) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
+        if let Some(idx) = self.districts.iter().position(|other| other.id == district) {
+            let dissolved = self.districts.remove(idx);
+            info(
+                LOG_T,
+                format!("District {} dissolved", dissolved.name.to_string()),
+                self.id,
+                world,
+            );
+            self.announce(world);
+        }
+    }
+
+    // for listeners that appear after districts were designated
+    pub fn get_districts(&mut self, listener: DistrictListenerID, world: &mut World) {
+        listener.on_districts_changed(self.districts.clone(), world);
+    }
+
+    fn announce(&self, world: &mut World) {
+        DistrictListenerID::global_broadcast(world)
+            .on_districts_changed(self.districts.clone(), world);
+    }
+}
+
+impl DistrictListener for Lane {
+    fn on_districts_changed(&mut self, districts: &CVec<DistrictArea>, _: &mut World) {
+        let middle = self.construction.path.along(self.construction.length / 2.0);
+        let old_speed_limit = self.effective_speed_limit();
+
+        self.microtraffic.district_policies =
+            district_at(districts, middle).map(|district| district.policies);
+
+        if self.effective_speed_limit() != old_speed_limit {
+            self.pathfinding.routes_changed = true;
+        }
+    }
+}
+
+impl Lane {
+    // the speed limit of the road, unless its district sets a lower one
+    pub fn effective_speed_limit(&self) -> N {
+        self.microtraffic
+            .district_policies
+            .and_then(|policies| policies.speed_limit)
+            .map_or(self.speed_limit, |limit| limit.min(self.speed_limit))
+    }
+}
+
+// whoever the truck belongs to pays the fine, like a toll
+pub fn fine_heavy_traffic(lane: &Lane, car: &LaneCar, world: &mut World) {
+    let banned = lane
+        .microtraffic
+        .district_policies
+        .map_or(false, |policies| policies.heavy_traffic_ban);
+
+    if banned && car.vehicle == VehicleClass::Truck {
+        car.trip.pay_toll(HEAVY_TRAFFIC_FINE, world);
+    }
+}
+
+pub fn on_build(lane: &Lane, world: &mut World) {
+    DistrictRegistryID::global_first(world).get_districts(lane.id.into(), world);
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<DistrictRegistry>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    DistrictRegistryID::spawn(world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,313 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct GridLayerRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for GridLayerRequesterID {}
+impl Clone for GridLayerRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for GridLayerRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "GridLayerRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for GridLayerRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for GridLayerRequesterID {
+    fn eq(&self, other: &GridLayerRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for GridLayerRequesterID {}
+
+pub struct GridLayerRequesterRepresentative;
+
+impl ActorOrActorTrait for GridLayerRequesterRepresentative {
+    type ID = GridLayerRequesterID;
+}
+
+impl TypedID for GridLayerRequesterID {
+    type Target = GridLayerRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        GridLayerRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + GridLayerRequester> TraitIDFrom<Act> for GridLayerRequesterID {}
+
+impl GridLayerRequesterID {
+    pub fn on_grid_layer_value(self, kind: LayerKind, position: P2, value: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_GridLayerRequester_on_grid_layer_value(kind, position, value));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<GridLayerRequesterRepresentative>();
+        system.register_trait_message::<MSG_GridLayerRequester_on_grid_layer_value>();
+    }
+
+    pub fn register_implementor<Act: Actor + GridLayerRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, GridLayerRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_GridLayerRequester_on_grid_layer_value(kind, position, value), instance, world| {
+                instance.on_grid_layer_value(kind, position, value, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GridLayerRequester_on_grid_layer_value(pub LayerKind, pub P2, pub f32);
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct GridLayerUIID {
+    _raw_id: RawID
+}
+
+impl Copy for GridLayerUIID {}
+impl Clone for GridLayerUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for GridLayerUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "GridLayerUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for GridLayerUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for GridLayerUIID {
+    fn eq(&self, other: &GridLayerUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for GridLayerUIID {}
+
+pub struct GridLayerUIRepresentative;
+
+impl ActorOrActorTrait for GridLayerUIRepresentative {
+    type ID = GridLayerUIID;
+}
+
+impl TypedID for GridLayerUIID {
+    type Target = GridLayerUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        GridLayerUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + GridLayerUI> TraitIDFrom<Act> for GridLayerUIID {}
+
+impl GridLayerUIID {
+    pub fn on_grid_layer(self, kind: LayerKind, cell_size: N, values: CVec < ( Cell , f32 ) >, world: &mut World) {
+        world.send(self.as_raw(), MSG_GridLayerUI_on_grid_layer(kind, cell_size, values));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<GridLayerUIRepresentative>();
+        system.register_trait_message::<MSG_GridLayerUI_on_grid_layer>();
+    }
+
+    pub fn register_implementor<Act: Actor + GridLayerUI>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, GridLayerUIRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_GridLayerUI_on_grid_layer(kind, cell_size, ref values), instance, world| {
+                instance.on_grid_layer(kind, cell_size, values, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GridLayerUI_on_grid_layer(pub LayerKind, pub N, pub CVec < ( Cell , f32 ) >);
+
+impl Actor for GridLayers {
+    type ID = GridLayersID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct GridLayersID {
+    _raw_id: RawID
+}
+
+impl Copy for GridLayersID {}
+impl Clone for GridLayersID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for GridLayersID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "GridLayersID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for GridLayersID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for GridLayersID {
+    fn eq(&self, other: &GridLayersID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for GridLayersID {}
+
+impl TypedID for GridLayersID {
+    type Target = GridLayers;
+
+    fn from_raw(id: RawID) -> Self {
+        GridLayersID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl GridLayersID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = GridLayersID::from_raw(world.allocate_instance_id::<GridLayers>());
+        let swarm = world.local_broadcast::<GridLayers>();
+        world.send(swarm, MSG_GridLayers_spawn(id, ));
+        id
+    }
+    
+    pub fn add_building(self, building: BuildingID, style: BuildingStyle, position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_GridLayers_add_building(building, style, position));
+    }
+    
+    pub fn remove_building(self, building: BuildingID, style: BuildingStyle, position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_GridLayers_remove_building(building, style, position));
+    }
+    
+    pub fn add_lane(self, lane: LaneID, position: P2, speed_limit: N, world: &mut World) {
+        world.send(self.as_raw(), MSG_GridLayers_add_lane(lane, position, speed_limit));
+    }
+    
+    pub fn remove_lane(self, lane: LaneID, position: P2, speed_limit: N, world: &mut World) {
+        world.send(self.as_raw(), MSG_GridLayers_remove_lane(lane, position, speed_limit));
+    }
+    
+    pub fn add_waterfront(self, water: WaterBodyID, points: CVec < P2 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_GridLayers_add_waterfront(water, points));
+    }
+    
+    pub fn remove_waterfront(self, water: WaterBodyID, points: CVec < P2 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_GridLayers_remove_waterfront(water, points));
+    }
+    
+    pub fn get_value(self, kind: LayerKind, position: P2, requester: GridLayerRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_GridLayers_get_value(kind, position, requester));
+    }
+    
+    pub fn get_layer(self, kind: LayerKind, requester: GridLayerUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_GridLayers_get_layer(kind, requester));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GridLayers_spawn(pub GridLayersID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GridLayers_add_building(pub BuildingID, pub BuildingStyle, pub P2);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GridLayers_remove_building(pub BuildingID, pub BuildingStyle, pub P2);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GridLayers_add_lane(pub LaneID, pub P2, pub N);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GridLayers_remove_lane(pub LaneID, pub P2, pub N);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GridLayers_add_waterfront(pub WaterBodyID, pub CVec < P2 >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GridLayers_remove_waterfront(pub WaterBodyID, pub CVec < P2 >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GridLayers_get_value(pub LayerKind, pub P2, pub GridLayerRequesterID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GridLayers_get_layer(pub LayerKind, pub GridLayerUIID);
+
+impl Into<TemporalID> for GridLayersID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    GridLayerRequesterID::register_trait(system);
+    GridLayerUIID::register_trait(system);
+    TemporalID::register_implementor::<GridLayers>(system);
+    system.add_spawner::<GridLayers, _, _>(
+        |&MSG_GridLayers_spawn(id, ), world| {
+            GridLayers::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<GridLayers, _, _>(
+        |&MSG_GridLayers_add_building(building, style, position), instance, world| {
+            instance.add_building(building, style, position, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<GridLayers, _, _>(
+        |&MSG_GridLayers_remove_building(building, style, position), instance, world| {
+            instance.remove_building(building, style, position, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<GridLayers, _, _>(
+        |&MSG_GridLayers_add_lane(lane, position, speed_limit), instance, world| {
+            instance.add_lane(lane, position, speed_limit, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<GridLayers, _, _>(
+        |&MSG_GridLayers_remove_lane(lane, position, speed_limit), instance, world| {
+            instance.remove_lane(lane, position, speed_limit, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<GridLayers, _, _>(
+        |&MSG_GridLayers_add_waterfront(water, ref points), instance, world| {
+            instance.add_waterfront(water, points, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<GridLayers, _, _>(
+        |&MSG_GridLayers_remove_waterfront(water, ref points), instance, world| {
+            instance.remove_waterfront(water, points, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<GridLayers, _, _>(
+        |&MSG_GridLayers_get_value(kind, position, requester), instance, world| {
+            instance.get_value(kind, position, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<GridLayers, _, _>(
+        |&MSG_GridLayers_get_layer(kind, requester), instance, world| {
+            instance.get_layer(kind, requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,270 @@
+// Land value and service coverage, kept on grids that are only recomputed
+// where buildings or roads appeared or went away, a bounded number of cells per
+// tick, shared between all layers. Grid-based subsystems that come later add their
+// layer here instead of recomputing the whole map at once
+use kay::{ActorSystem, World, TypedID};
+use compact::CVec;
+use descartes::{N, P2};
+use std::collections::HashMap;
+use cb_time::units::Instant;
+use cb_time::actors::{Temporal, TemporalID};
+use transport::lane::LaneID;
+use super::buildings::{BuildingID, BuildingStyle};
+use environment::water::WaterBodyID;
+
+pub mod sliced_grid;
+use self::sliced_grid::{SlicedGrid, Cell};
+
+const CELL_SIZE: N = 50.0;
+// across all layers
+const CELLS_PER_TICK: usize = 20;
+
+// what being close to a road adds to land value, for its accessibility
+const ACCESSIBILITY_PER_LANE: f32 = 0.5;
+// lanes this fast are as loud as it gets, slower ones proportionally quieter
+const LOUDEST_SPEED_LIMIT: N = 25.0;
+// how much each unit of pollution and noise takes away from land value
+const POLLUTION_LAND_VALUE_LOSS: f32 = 1.5;
+const NOISE_LAND_VALUE_LOSS: f32 = 1.0;
+// what living by a lake, river or the coast adds to land value
+const WATERFRONT_LAND_VALUE: f32 = 1.0;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum LayerKind {
+    // what services and roads nearby add, pollution and noise are
+    // only subtracted when land value is looked up
+    LandValue,
+    SchoolCoverage,
+    HealthcareCoverage,
+    Pollution,
+    Noise,
+}
+
+pub const ALL_LAYERS: [LayerKind; 5] = [
+    LayerKind::LandValue,
+    LayerKind::SchoolCoverage,
+    LayerKind::HealthcareCoverage,
+    LayerKind::Pollution,
+    LayerKind::Noise,
+];
+
+impl LayerKind {
+    fn new_grid(self) -> SlicedGrid {
+        match self {
+            LayerKind::LandValue => SlicedGrid::new(CELL_SIZE, 300.0, ::std::f32::MAX),
+            // fully covered by one school or hospital close enough
+            LayerKind::SchoolCoverage => SlicedGrid::new(CELL_SIZE, 800.0, 1.0),
+            LayerKind::HealthcareCoverage => SlicedGrid::new(CELL_SIZE, 1500.0, 1.0),
+            LayerKind::Pollution => SlicedGrid::new(CELL_SIZE, 600.0, ::std::f32::MAX),
+            LayerKind::Noise => SlicedGrid::new(CELL_SIZE, 200.0, ::std::f32::MAX),
+        }
+    }
+
+    // How much a finished building adds to the layer around it, if anything.
+    // Parks take away from pollution and noise, but never below none
+    fn contribution(self, style: BuildingStyle) -> Option<f32> {
+        match (self, style) {
+            (LayerKind::LandValue, BuildingStyle::Park)
+            | (LayerKind::LandValue, BuildingStyle::School)
+            | (LayerKind::LandValue, BuildingStyle::Hospital) => Some(3.0),
+            (LayerKind::LandValue, BuildingStyle::GroceryShop)
+            | (LayerKind::LandValue, BuildingStyle::Bakery)
+            | (LayerKind::LandValue, BuildingStyle::Restaurant)
+            | (LayerKind::LandValue, BuildingStyle::Cinema) => Some(2.0),
+            (LayerKind::LandValue, BuildingStyle::DepartmentStore) => Some(2.5),
+            (LayerKind::LandValue, BuildingStyle::FamilyHouse)
+            | (LayerKind::LandValue, BuildingStyle::ApartmentBlock) => Some(1.0),
+            (LayerKind::SchoolCoverage, BuildingStyle::School)
+            | (LayerKind::HealthcareCoverage, BuildingStyle::Hospital) => Some(2.0),
+            (LayerKind::Pollution, BuildingStyle::Mill)
+            | (LayerKind::Pollution, BuildingStyle::ConstructionYard)
+            | (LayerKind::Pollution, BuildingStyle::Waterworks) => Some(1.0),
+            (LayerKind::Pollution, BuildingStyle::PowerPlant) => Some(2.0),
+            (LayerKind::Noise, BuildingStyle::Restaurant)
+            | (LayerKind::Noise, BuildingStyle::TaxiDepot)
+            | (LayerKind::Noise, BuildingStyle::DepartmentStore) => Some(0.5),
+            (LayerKind::Pollution, BuildingStyle::Park) => Some(-1.0),
+            (LayerKind::Noise, BuildingStyle::Park) => Some(-0.5),
+            (LayerKind::Noise, BuildingStyle::Cinema)
+            | (LayerKind::Noise, BuildingStyle::ConstructionYard)
+            | (LayerKind::Noise, BuildingStyle::PowerPlant) => Some(1.0),
+            _ => None,
+        }
+    }
+
+    // How much a road lane adds to the layer along it, if anything
+    fn lane_contribution(self, speed_limit: N) -> Option<f32> {
+        match self {
+            LayerKind::LandValue => Some(ACCESSIBILITY_PER_LANE),
+            LayerKind::Noise => Some((speed_limit / LOUDEST_SPEED_LIMIT).min(1.0)),
+            _ => None,
+        }
+    }
+}
+
+pub trait GridLayerRequester {
+    fn on_grid_layer_value(&mut self, kind: LayerKind, position: P2, value: f32, world: &mut World);
+}
+
+pub trait GridLayerUI {
+    fn on_grid_layer(
+        &mut self,
+        kind: LayerKind,
+        cell_size: N,
+        values: &CVec<(Cell, f32)>,
+        world: &mut World,
+    );
+}
+
+#[derive(Compact, Clone)]
+pub struct GridLayers {
+    id: GridLayersID,
+    // in the order of `ALL_LAYERS`
+    grids: CVec<SlicedGrid>,
+    // the layer that gets to recompute first next tick, so all get their turn
+    next_first: u32,
+}
+
+impl GridLayers {
+    pub fn spawn(id: GridLayersID, _: &mut World) -> GridLayers {
+        GridLayers {
+            id,
+            grids: ALL_LAYERS.iter().map(|kind| kind.new_grid()).collect(),
+            next_first: 0,
+        }
+    }
+
+    fn grid(&self, kind: LayerKind) -> &SlicedGrid {
+        &self.grids[kind as usize]
+    }
+
+    fn land_value_at(&self, position: P2) -> f32 {
+        self.grid(LayerKind::LandValue).value_at(position)
+            - POLLUTION_LAND_VALUE_LOSS * self.grid(LayerKind::Pollution).value_at(position)
+            - NOISE_LAND_VALUE_LOSS * self.grid(LayerKind::Noise).value_at(position)
+    }
+
+    fn land_values(&self) -> CVec<(Cell, f32)> {
+        let mut by_cell = HashMap::<Cell, f32>::new();
+        for &(kind, factor) in &[
+            (LayerKind::LandValue, 1.0),
+            (LayerKind::Pollution, -POLLUTION_LAND_VALUE_LOSS),
+            (LayerKind::Noise, -NOISE_LAND_VALUE_LOSS),
+        ] {
+            for (cell, value) in self.grid(kind).values() {
+                *by_cell.entry(cell).or_insert(0.0) += factor * value;
+            }
+        }
+        by_cell.into_iter().collect()
+    }
+
+    pub fn add_building(
+        &mut self,
+        building: BuildingID,
+        style: BuildingStyle,
+        position: P2,
+        _: &mut World,
+    ) {
+        for (&kind, grid) in ALL_LAYERS.iter().zip(self.grids.iter_mut()) {
+            if let Some(strength) = kind.contribution(style) {
+                grid.add_source(building.as_raw(), position, strength);
+            }
+        }
+    }
+
+    pub fn remove_building(
+        &mut self,
+        building: BuildingID,
+        style: BuildingStyle,
+        position: P2,
+        _: &mut World,
+    ) {
+        for (&kind, grid) in ALL_LAYERS.iter().zip(self.grids.iter_mut()) {
+            if kind.contribution(style).is_some() {
+                grid.remove_source(building.as_raw(), position);
+            }
+        }
+    }
+
+    // lanes are heard and reached from along their whole length,
+    // but their middle is a good enough approximation at this cell size
+    pub fn add_lane(&mut self, lane: LaneID, position: P2, speed_limit: N, _: &mut World) {
+        for (&kind, grid) in ALL_LAYERS.iter().zip(self.grids.iter_mut()) {
+            if let Some(strength) = kind.lane_contribution(speed_limit) {
+                grid.add_source(lane.as_raw(), position, strength);
+            }
+        }
+    }
+
+    pub fn remove_lane(&mut self, lane: LaneID, position: P2, speed_limit: N, _: &mut World) {
+        for (&kind, grid) in ALL_LAYERS.iter().zip(self.grids.iter_mut()) {
+            if kind.lane_contribution(speed_limit).is_some() {
+                grid.remove_source(lane.as_raw(), position);
+            }
+        }
+    }
+
+    pub fn add_waterfront(&mut self, water: WaterBodyID, points: &CVec<P2>, _: &mut World) {
+        let grid = &mut self.grids[LayerKind::LandValue as usize];
+        for &point in points.iter() {
+            grid.add_source(water.as_raw(), point, WATERFRONT_LAND_VALUE);
+        }
+    }
+
+    pub fn remove_waterfront(&mut self, water: WaterBodyID, points: &CVec<P2>, _: &mut World) {
+        let grid = &mut self.grids[LayerKind::LandValue as usize];
+        for &point in points.iter() {
+            grid.remove_source(water.as_raw(), point);
+        }
+    }
+
+    pub fn get_value(
+        &mut self,
+        kind: LayerKind,
+        position: P2,
+        requester: GridLayerRequesterID,
+        world: &mut World,
+    ) {
+        let value = if kind == LayerKind::LandValue {
+            self.land_value_at(position)
+        } else {
+            self.grid(kind).value_at(position)
+        };
+        requester.on_grid_layer_value(kind, position, value, world);
+    }
+
+    pub fn get_layer(&mut self, kind: LayerKind, requester: GridLayerUIID, world: &mut World) {
+        let values = if kind == LayerKind::LandValue {
+            self.land_values()
+        } else {
+            self.grid(kind).values()
+        };
+        requester.on_grid_layer(kind, CELL_SIZE, values, world);
+    }
+}
+
+impl Temporal for GridLayers {
+    fn tick(&mut self, _dt: f32, _current_instant: Instant, _: &mut World) {
+        let n_layers = self.grids.len();
+        let mut budget = CELLS_PER_TICK;
+
+        for offset in 0..n_layers {
+            let idx = (self.next_first as usize + offset) % n_layers;
+            budget -= self.grids[idx].recompute(budget);
+        }
+
+        self.next_first = ((self.next_first as usize + 1) % n_layers) as u32;
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<GridLayers>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    GridLayersID::spawn(world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
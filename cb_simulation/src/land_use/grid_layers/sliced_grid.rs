@@ -0,0 +1,138 @@
+// A grid of values computed from point sources that each influence the cells
+// within their reach. Adding or removing a source only marks those cells as
+// dirty, they are recomputed later in slices of bounded size, so the work per
+// tick stays the same no matter how big the city gets
+use compact::{CVec, CDict};
+use descartes::{N, P2};
+use kay::RawID;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub struct Cell(pub i32, pub i32);
+
+#[derive(Copy, Clone)]
+pub struct Source {
+    owner: RawID,
+    position: P2,
+    strength: f32,
+}
+
+#[derive(Compact, Clone)]
+pub struct SlicedGrid {
+    cell_size: N,
+    // sources further away than this don't influence a cell anymore
+    reach: N,
+    // values are capped, like a coverage that can't get better than full
+    max_value: f32,
+    values: CDict<Cell, f32>,
+    // by the cell they are in, to find the ones within reach quickly
+    sources: CDict<Cell, CVec<Source>>,
+    dirty: CVec<Cell>,
+}
+
+impl SlicedGrid {
+    pub fn new(cell_size: N, reach: N, max_value: f32) -> SlicedGrid {
+        SlicedGrid {
+            cell_size,
+            reach,
+            max_value,
+            values: CDict::new(),
+            sources: CDict::new(),
+            dirty: CVec::new(),
+        }
+    }
+
+    pub fn cell_containing(&self, position: P2) -> Cell {
+        Cell(
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    fn center_of(&self, cell: Cell) -> P2 {
+        P2::new(
+            (cell.0 as N + 0.5) * self.cell_size,
+            (cell.1 as N + 0.5) * self.cell_size,
+        )
+    }
+
+    fn cells_within_reach(&self, cell: Cell) -> impl Iterator<Item = Cell> {
+        let radius = (self.reach / self.cell_size).ceil() as i32;
+        (-radius..=radius)
+            .flat_map(move |dx| (-radius..=radius).map(move |dy| Cell(cell.0 + dx, cell.1 + dy)))
+    }
+
+    fn mark_dirty_around(&mut self, position: P2) {
+        let cell = self.cell_containing(position);
+        for affected in self.cells_within_reach(cell).collect::<Vec<_>>() {
+            if !self.dirty.contains(&affected) {
+                self.dirty.push(affected);
+            }
+        }
+    }
+
+    pub fn add_source(&mut self, owner: RawID, position: P2, strength: f32) {
+        let cell = self.cell_containing(position);
+        self.sources.push_at(
+            cell,
+            Source {
+                owner,
+                position,
+                strength,
+            },
+        );
+        self.mark_dirty_around(position);
+    }
+
+    pub fn remove_source(&mut self, owner: RawID, position: P2) {
+        let cell = self.cell_containing(position);
+        if let Some(in_cell) = self.sources.get_mut(cell) {
+            in_cell.retain(|source| source.owner != owner);
+        }
+        self.mark_dirty_around(position);
+    }
+
+    // Possibly outdated while the cell is still dirty
+    pub fn value_at(&self, position: P2) -> f32 {
+        self.values
+            .get(self.cell_containing(position))
+            .cloned()
+            .unwrap_or(0.0)
+    }
+
+    pub fn values(&self) -> CVec<(Cell, f32)> {
+        self.values
+            .pairs()
+            .map(|(&cell, &value)| (cell, value))
+            .collect()
+    }
+
+    pub fn n_dirty(&self) -> usize {
+        self.dirty.len()
+    }
+
+    // Recomputes at most `budget` of the dirty cells, oldest first.
+    // Returns how many it recomputed
+    pub fn recompute(&mut self, budget: usize) -> usize {
+        let cells = self.dirty.iter().take(budget).cloned().collect::<Vec<_>>();
+        self.dirty = self.dirty.iter().skip(cells.len()).cloned().collect();
+
+        for &cell in &cells {
+            let center = self.center_of(cell);
+            let value = self
+                .cells_within_reach(cell)
+                .filter_map(|nearby| self.sources.get(nearby))
+                .flat_map(|sources| sources.iter())
+                .map(|source| {
+                    let distance = (source.position - center).norm();
+                    source.strength * (1.0 - distance / self.reach).max(0.0)
+                })
+                .sum::<f32>()
+                // negative sources only ever cancel out positive ones
+                .max(0.0)
+                .min(self.max_value);
+            self.values.insert(cell, value);
+        }
+
+        cells.len()
+    }
+}
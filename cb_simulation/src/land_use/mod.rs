@@ -1,17 +1,29 @@
 use kay::{ActorSystem, World};
+use planning::CBPlanManagerID;
 
 pub mod buildings;
 pub mod vacant_lots;
 pub mod construction;
 pub mod zone_planning;
+pub mod territory;
+pub mod grid_layers;
+pub mod districts;
+pub mod underground;
 pub mod ui;
 
 pub fn setup(system: &mut ActorSystem) {
     buildings::setup(system);
     vacant_lots::setup(system);
+    territory::setup(system);
+    grid_layers::setup(system);
+    districts::setup(system);
+    underground::setup(system);
     ui::auto_setup(system);
 }
 
-pub fn spawn(world: &mut World) {
+pub fn spawn(world: &mut World, plan_manager: CBPlanManagerID) {
     buildings::spawn(world);
+    territory::spawn(world, plan_manager);
+    grid_layers::spawn(world);
+    districts::spawn(world);
 }
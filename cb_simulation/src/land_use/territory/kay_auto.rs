@@ -0,0 +1,267 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TerritoryUIID {
+    _raw_id: RawID
+}
+
+impl Copy for TerritoryUIID {}
+impl Clone for TerritoryUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TerritoryUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TerritoryUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TerritoryUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TerritoryUIID {
+    fn eq(&self, other: &TerritoryUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TerritoryUIID {}
+
+pub struct TerritoryUIRepresentative;
+
+impl ActorOrActorTrait for TerritoryUIRepresentative {
+    type ID = TerritoryUIID;
+}
+
+impl TypedID for TerritoryUIID {
+    type Target = TerritoryUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        TerritoryUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + TerritoryUI> TraitIDFrom<Act> for TerritoryUIID {}
+
+impl TerritoryUIID {
+    pub fn on_territory_info(self, tile_size: N, owned: CVec < Tile >, purchasable: CVec < ( Tile , ResourceAmount ) >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TerritoryUI_on_territory_info(tile_size, owned, purchasable));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<TerritoryUIRepresentative>();
+        system.register_trait_message::<MSG_TerritoryUI_on_territory_info>();
+    }
+
+    pub fn register_implementor<Act: Actor + TerritoryUI>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, TerritoryUIRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_TerritoryUI_on_territory_info(tile_size, ref owned, ref purchasable), instance, world| {
+                instance.on_territory_info(tile_size, owned, purchasable, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TerritoryUI_on_territory_info(pub N, pub CVec < Tile >, pub CVec < ( Tile , ResourceAmount ) >);
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TerritoryListenerID {
+    _raw_id: RawID
+}
+
+impl Copy for TerritoryListenerID {}
+impl Clone for TerritoryListenerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TerritoryListenerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TerritoryListenerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TerritoryListenerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TerritoryListenerID {
+    fn eq(&self, other: &TerritoryListenerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TerritoryListenerID {}
+
+pub struct TerritoryListenerRepresentative;
+
+impl ActorOrActorTrait for TerritoryListenerRepresentative {
+    type ID = TerritoryListenerID;
+}
+
+impl TypedID for TerritoryListenerID {
+    type Target = TerritoryListenerRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        TerritoryListenerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + TerritoryListener> TraitIDFrom<Act> for TerritoryListenerID {}
+
+impl TerritoryListenerID {
+    pub fn on_territory_changed(self, area: BuildableArea, world: &mut World) {
+        world.send(self.as_raw(), MSG_TerritoryListener_on_territory_changed(area));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<TerritoryListenerRepresentative>();
+        system.register_trait_message::<MSG_TerritoryListener_on_territory_changed>();
+    }
+
+    pub fn register_implementor<Act: Actor + TerritoryListener>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, TerritoryListenerRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_TerritoryListener_on_territory_changed(ref area), instance, world| {
+                instance.on_territory_changed(area, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TerritoryListener_on_territory_changed(pub BuildableArea);
+
+impl Actor for Territory {
+    type ID = TerritoryID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TerritoryID {
+    _raw_id: RawID
+}
+
+impl Copy for TerritoryID {}
+impl Clone for TerritoryID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TerritoryID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TerritoryID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TerritoryID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TerritoryID {
+    fn eq(&self, other: &TerritoryID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TerritoryID {}
+
+impl TypedID for TerritoryID {
+    type Target = Territory;
+
+    fn from_raw(id: RawID) -> Self {
+        TerritoryID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TerritoryID {
+    pub fn spawn(plan_manager: CBPlanManagerID, world: &mut World) -> Self {
+        let id = TerritoryID::from_raw(world.allocate_instance_id::<Territory>());
+        let swarm = world.local_broadcast::<Territory>();
+        world.send(swarm, MSG_Territory_spawn(id, plan_manager));
+        id
+    }
+    
+    pub fn purchase(self, origin: Origin, tile: Tile, world: &mut World) {
+        world.send(self.as_raw(), MSG_Territory_purchase(origin, tile));
+    }
+    
+    pub fn get_territory_info(self, ui: TerritoryUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Territory_get_territory_info(ui));
+    }
+    
+    pub fn get_territory(self, listener: TerritoryListenerID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Territory_get_territory(listener));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Territory_spawn(pub TerritoryID, pub CBPlanManagerID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Territory_purchase(pub Origin, pub Tile);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Territory_get_territory_info(pub TerritoryUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Territory_get_territory(pub TerritoryListenerID);
+
+impl Into<FundsRequesterID> for TerritoryID {
+    fn into(self) -> FundsRequesterID {
+        FundsRequesterID::from_raw(self.as_raw())
+    }
+}
+
+
+impl LaneID {
+    
+}
+
+
+
+impl Into<TerritoryListenerID> for LaneID {
+    fn into(self) -> TerritoryListenerID {
+        TerritoryListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    TerritoryUIID::register_trait(system);
+    TerritoryListenerID::register_trait(system);
+    FundsRequesterID::register_implementor::<Territory>(system);
+    system.add_spawner::<Territory, _, _>(
+        |&MSG_Territory_spawn(id, plan_manager), world| {
+            Territory::spawn(id, plan_manager, world)
+        }, false
+    );
+    
+    system.add_handler::<Territory, _, _>(
+        |&MSG_Territory_purchase(origin, tile), instance, world| {
+            instance.purchase(origin, tile, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Territory, _, _>(
+        |&MSG_Territory_get_territory_info(ui), instance, world| {
+            instance.get_territory_info(ui, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Territory, _, _>(
+        |&MSG_Territory_get_territory(listener), instance, world| {
+            instance.get_territory(listener, world); Fate::Live
+        }, false
+    );
+    TerritoryListenerID::register_implementor::<Lane>(system);
+}
\ No newline at end of file
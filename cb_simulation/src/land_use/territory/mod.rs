@@ -0,0 +1,173 @@
+use kay::{ActorSystem, World, Actor, TypedID};
+use compact::CVec;
+use descartes::N;
+use cb_planning::{BuildableArea, Tile};
+use planning::CBPlanManagerID;
+use economy::resources::ResourceAmount;
+use economy::policies::{TreasuryID, FundsRequester, FundsRequesterID};
+use cb_util::log::{info, warn};
+use cb_util::session::{permits, Permission, Origin};
+use transport::lane::{Lane, LaneID};
+use transport::pathfinding::Link;
+const LOG_T: &str = "Territory";
+
+const TILE_SIZE: N = 1000.0;
+// initially owned tiles are those within this many tiles of the origin
+const INITIAL_TILE_RADIUS: i32 = 1;
+const TILE_BASE_PRICE: ResourceAmount = amount!(20_000.0);
+
+pub trait TerritoryUI {
+    fn on_territory_info(
+        &mut self,
+        tile_size: N,
+        owned: &CVec<Tile>,
+        purchasable: &CVec<(Tile, ResourceAmount)>,
+        world: &mut World,
+    );
+}
+
+// Anything that only works within the city's territory
+pub trait TerritoryListener {
+    fn on_territory_changed(&mut self, area: &BuildableArea, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct Territory {
+    id: TerritoryID,
+    plan_manager: CBPlanManagerID,
+    owned: CVec<Tile>,
+    pending_purchase: Option<Tile>,
+}
+
+impl Territory {
+    pub fn spawn(id: TerritoryID, plan_manager: CBPlanManagerID, world: &mut World) -> Territory {
+        let owned = (-INITIAL_TILE_RADIUS..=INITIAL_TILE_RADIUS)
+            .flat_map(|x| (-INITIAL_TILE_RADIUS..=INITIAL_TILE_RADIUS).map(move |y| (x, y)))
+            .collect();
+
+        let territory = Territory {
+            id,
+            plan_manager,
+            owned,
+            pending_purchase: None,
+        };
+
+        territory.notify_changed(world);
+
+        territory
+    }
+
+    fn notify_changed(&self, world: &mut World) {
+        self.plan_manager
+            .set_buildable_area(self.buildable_area(), world);
+        TerritoryListenerID::global_broadcast(world)
+            .on_territory_changed(self.buildable_area(), world);
+    }
+
+    fn buildable_area(&self) -> BuildableArea {
+        BuildableArea {
+            tile_size: TILE_SIZE,
+            tiles: self.owned.clone(),
+        }
+    }
+
+    // tiles get more expensive the further out they are
+    fn price_of(tile: Tile) -> ResourceAmount {
+        let distance = tile.0.abs().max(tile.1.abs()) - INITIAL_TILE_RADIUS;
+        TILE_BASE_PRICE * distance.max(1) as f32
+    }
+
+    fn purchasable(&self) -> CVec<(Tile, ResourceAmount)> {
+        let mut purchasable = CVec::<(Tile, ResourceAmount)>::new();
+
+        for &(x, y) in &self.owned {
+            for &neighbor in &[(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                if !self.owned.contains(&neighbor)
+                    && !purchasable.iter().any(|&(tile, _)| tile == neighbor)
+                {
+                    purchasable.push((neighbor, Self::price_of(neighbor)));
+                }
+            }
+        }
+
+        purchasable
+    }
+
+    pub fn purchase(&mut self, origin: Origin, tile: Tile, world: &mut World) {
+        if !permits(origin, Permission::Budget, self.id, world) {
+            return;
+        }
+
+        if self.pending_purchase.is_some() {
+            info(LOG_T, "Already purchasing a tile", self.id, world);
+        } else if let Some(&(_, price)) = self
+            .purchasable()
+            .iter()
+            .find(|&&(purchasable_tile, _)| purchasable_tile == tile)
+        {
+            self.pending_purchase = Some(tile);
+            TreasuryID::global_first(world).request_funds(price, self.id_as(), world);
+        } else {
+            warn(
+                LOG_T,
+                format!("Tile {:?} is not adjacent to the city", tile),
+                self.id,
+                world,
+            );
+        }
+    }
+
+    pub fn get_territory_info(&mut self, ui: TerritoryUIID, world: &mut World) {
+        ui.on_territory_info(TILE_SIZE, self.owned.clone(), self.purchasable(), world);
+    }
+
+    pub fn get_territory(&mut self, listener: TerritoryListenerID, world: &mut World) {
+        listener.on_territory_changed(self.buildable_area(), world);
+    }
+}
+
+impl FundsRequester for Territory {
+    fn on_funds(&mut self, granted: bool, amount: ResourceAmount, world: &mut World) {
+        if let Some(tile) = self.pending_purchase.take() {
+            if granted {
+                self.owned.push(tile);
+                self.notify_changed(world);
+            } else {
+                info(
+                    LOG_T,
+                    format!("Not enough funds to purchase {:?} for {}", tile, amount),
+                    self.id,
+                    world,
+                );
+            }
+        }
+    }
+}
+
+// Roads can't be driven through where they reach into tiles that the city
+// doesn't own (yet), for example where they cut across the corner of one
+impl TerritoryListener for Lane {
+    fn on_territory_changed(&mut self, area: &BuildableArea, _: &mut World) {
+        let path = &self.construction.path;
+        let outside = [path.start(), path.along(path.length() / 2.0), path.end()]
+            .iter()
+            .any(|&point| !area.contains(point));
+        self.set_outside_territory(outside);
+    }
+}
+
+pub fn on_build(lane: &Lane, world: &mut World) {
+    TerritoryID::global_first(world).get_territory(lane.id.into(), world);
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Territory>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, plan_manager: CBPlanManagerID) {
+    TerritoryID::spawn(plan_manager, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
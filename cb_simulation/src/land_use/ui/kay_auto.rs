@@ -50,8 +50,8 @@ impl TypedID for LandUseUIID {
 impl<Act: Actor + LandUseUI> TraitIDFrom<Act> for LandUseUIID {}
 
 impl LandUseUIID {
-    pub fn on_building_constructed(self, id: BuildingID, lot: Lot, households: CVec < HouseholdID >, style: BuildingStyle, world: &mut World) {
-        world.send(self.as_raw(), MSG_LandUseUI_on_building_constructed(id, lot, households, style));
+    pub fn on_building_constructed(self, id: BuildingID, lot: Lot, households: CVec < HouseholdID >, style: BuildingStyle, construction_progress: Option < f32 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_LandUseUI_on_building_constructed(id, lot, households, style, construction_progress));
     }
     
     pub fn on_building_destructed(self, id: BuildingID, world: &mut World) {
@@ -72,8 +72,8 @@ impl LandUseUIID {
     pub fn register_implementor<Act: Actor + LandUseUI>(system: &mut ActorSystem) {
         system.register_implementor::<Act, LandUseUIRepresentative>();
         system.add_handler::<Act, _, _>(
-            |&MSG_LandUseUI_on_building_constructed(id, ref lot, ref households, style), instance, world| {
-                instance.on_building_constructed(id, lot, households, style, world); Fate::Live
+            |&MSG_LandUseUI_on_building_constructed(id, ref lot, ref households, style, construction_progress), instance, world| {
+                instance.on_building_constructed(id, lot, households, style, construction_progress, world); Fate::Live
             }, false
         );
         
@@ -92,7 +92,7 @@ impl LandUseUIID {
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_LandUseUI_on_building_constructed(pub BuildingID, pub Lot, pub CVec < HouseholdID >, pub BuildingStyle);
+struct MSG_LandUseUI_on_building_constructed(pub BuildingID, pub Lot, pub CVec < HouseholdID >, pub BuildingStyle, pub Option < f32 >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_LandUseUI_on_building_destructed(pub BuildingID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
@@ -11,6 +11,8 @@ pub trait LandUseUI {
         lot: &Lot,
         households: &CVec<HouseholdID>,
         style: BuildingStyle,
+        // while the building is still going up
+        construction_progress: Option<f32>,
         _world: &mut World,
     );
 
@@ -0,0 +1,187 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ConduitUIID {
+    _raw_id: RawID
+}
+
+impl Copy for ConduitUIID {}
+impl Clone for ConduitUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ConduitUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ConduitUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ConduitUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ConduitUIID {
+    fn eq(&self, other: &ConduitUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ConduitUIID {}
+
+pub struct ConduitUIRepresentative;
+
+impl ActorOrActorTrait for ConduitUIRepresentative {
+    type ID = ConduitUIID;
+}
+
+impl TypedID for ConduitUIID {
+    type Target = ConduitUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        ConduitUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + ConduitUI> TraitIDFrom<Act> for ConduitUIID {}
+
+impl ConduitUIID {
+    pub fn on_conduit_added(self, id: ConduitID, proto: ConduitPrototype, world: &mut World) {
+        world.send(self.as_raw(), MSG_ConduitUI_on_conduit_added(id, proto));
+    }
+    
+    pub fn on_conduit_removed(self, id: ConduitID, world: &mut World) {
+        world.send(self.as_raw(), MSG_ConduitUI_on_conduit_removed(id));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<ConduitUIRepresentative>();
+        system.register_trait_message::<MSG_ConduitUI_on_conduit_added>();
+        system.register_trait_message::<MSG_ConduitUI_on_conduit_removed>();
+    }
+
+    pub fn register_implementor<Act: Actor + ConduitUI>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, ConduitUIRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_ConduitUI_on_conduit_added(id, ref proto), instance, world| {
+                instance.on_conduit_added(id, proto, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_ConduitUI_on_conduit_removed(id), instance, world| {
+                instance.on_conduit_removed(id, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ConduitUI_on_conduit_added(pub ConduitID, pub ConduitPrototype);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ConduitUI_on_conduit_removed(pub ConduitID);
+
+impl Actor for Conduit {
+    type ID = ConduitID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ConduitID {
+    _raw_id: RawID
+}
+
+impl Copy for ConduitID {}
+impl Clone for ConduitID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ConduitID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ConduitID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ConduitID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ConduitID {
+    fn eq(&self, other: &ConduitID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ConduitID {}
+
+impl TypedID for ConduitID {
+    type Target = Conduit;
+
+    fn from_raw(id: RawID) -> Self {
+        ConduitID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl ConduitID {
+    pub fn spawn(proto: ConduitPrototype, world: &mut World) -> Self {
+        let id = ConduitID::from_raw(world.allocate_instance_id::<Conduit>());
+        let swarm = world.local_broadcast::<Conduit>();
+        world.send(swarm, MSG_Conduit_spawn(id, proto));
+        id
+    }
+    
+    pub fn get_render_info(self, requester: ConduitUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Conduit_get_render_info(requester));
+    }
+    
+    pub fn reconnect(self, network: UtilityNetworkID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Conduit_reconnect(network));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Conduit_spawn(pub ConduitID, pub ConduitPrototype);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Conduit_get_render_info(pub ConduitUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Conduit_reconnect(pub UtilityNetworkID);
+
+impl Into<ConstructableID<CBPrototypeKind>> for ConduitID {
+    fn into(self) -> ConstructableID<CBPrototypeKind> {
+        ConstructableID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    ConduitUIID::register_trait(system);
+    ConstructableID::<CBPrototypeKind>::register_implementor::<Conduit>(system);
+    system.add_spawner::<Conduit, _, _>(
+        |&MSG_Conduit_spawn(id, ref proto), world| {
+            Conduit::spawn(id, proto, world)
+        }, false
+    );
+    
+    system.add_handler::<Conduit, _, _>(
+        |&MSG_Conduit_get_render_info(requester), instance, world| {
+            instance.get_render_info(requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Conduit, _, _>(
+        |&MSG_Conduit_reconnect(network), instance, world| {
+            instance.reconnect(network, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,193 @@
+// The planning layer below the surface, for now only holding conduits:
+// power lines and water pipes laid as direct lines, in addition to the mains
+// along the roads. Each utility is laid at its own depth, so conduits of
+// different utilities cross without touching and nothing underground collides
+// with or makes room for what is on the surface above it. Conduits of the same
+// utility join where one ends on or bends at another. The treasury pays for
+// every meter that is laid, see `economy::utilities` for what conduits do
+use kay::{World, Fate, ActorSystem, TypedID};
+use compact::CVec;
+use descartes::{N, LinePath, AreaError, RoughEq};
+use planning::{CBConstructionID, CBPrototypeKind, CBGestureIntent};
+use cb_planning::{Prototype, PrototypeID, PlanHistory, PlanResult};
+use cb_planning::construction::{Constructable, ConstructableID};
+use cb_util::log::info;
+use economy::utilities::{Utility, UtilityNetworkID};
+use economy::policies::TreasuryID;
+use economy::resources::ResourceAmount;
+const LOG_T: &str = "Underground";
+
+impl Utility {
+    // below the surface, in meters
+    pub fn conduit_depth(self) -> N {
+        match self {
+            Utility::Electricity => 1.5,
+            // below the frost line
+            Utility::Water => 3.0,
+        }
+    }
+
+    // what the treasury pays for each meter of conduit, deeper is dearer
+    pub fn conduit_cost_per_meter(self) -> ResourceAmount {
+        match self {
+            Utility::Electricity => amount!(2.0),
+            Utility::Water => amount!(4.0),
+        }
+    }
+}
+
+#[derive(Compact, Clone, Debug, Serialize, Deserialize)]
+pub struct ConduitPrototype {
+    pub utility: Utility,
+    pub path: LinePath,
+}
+
+impl ConduitPrototype {
+    pub fn cost(&self) -> ResourceAmount {
+        self.path.length() * self.utility.conduit_cost_per_meter()
+    }
+
+    pub fn construct(
+        &self,
+        _self_id: PrototypeID,
+        report_to: CBConstructionID,
+        world: &mut World,
+    ) -> CVec<ConstructableID<CBPrototypeKind>> {
+        let id = ConduitID::spawn(self.clone(), world).into();
+        report_to.action_done(id, world);
+        vec![id].into()
+    }
+
+    pub fn morphable_from(&self, other: &Self) -> bool {
+        self.utility == other.utility && self.path.start().rough_eq_by(other.path.start(), 0.5)
+    }
+}
+
+pub trait ConduitUI {
+    fn on_conduit_added(&mut self, id: ConduitID, proto: &ConduitPrototype, world: &mut World);
+    fn on_conduit_removed(&mut self, id: ConduitID, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct Conduit {
+    id: ConduitID,
+    proto: ConduitPrototype,
+}
+
+impl Conduit {
+    pub fn spawn(id: ConduitID, proto: &ConduitPrototype, world: &mut World) -> Conduit {
+        let conduit = Conduit {
+            id,
+            proto: proto.clone(),
+        };
+        conduit.charge(proto.cost(), world);
+        conduit.appear(world);
+        conduit
+    }
+
+    fn charge(&self, cost: ResourceAmount, world: &mut World) {
+        if cost > 0.0 {
+            TreasuryID::global_first(world).deposit(-cost, world);
+            info(
+                LOG_T,
+                format!(
+                    "Laid {:.0}m of {:?} conduit for {:.0}",
+                    self.proto.path.length(),
+                    self.proto.utility,
+                    cost
+                ),
+                self.id,
+                world,
+            );
+        }
+    }
+
+    fn appear(&self, world: &mut World) {
+        ConduitUIID::global_broadcast(world).on_conduit_added(self.id, self.proto.clone(), world);
+        UtilityNetworkID::global_first(world).add_conduit(
+            self.id,
+            self.proto.utility,
+            self.proto.path.clone(),
+            world,
+        );
+    }
+
+    fn disappear(&self, world: &mut World) {
+        ConduitUIID::global_broadcast(world).on_conduit_removed(self.id, world);
+        UtilityNetworkID::global_first(world).remove_conduit(self.id, world);
+    }
+
+    pub fn get_render_info(&mut self, requester: ConduitUIID, world: &mut World) {
+        requester.on_conduit_added(self.id, self.proto.clone(), world);
+    }
+
+    // for a utility network that started over
+    pub fn reconnect(&mut self, network: UtilityNetworkID, world: &mut World) {
+        network.add_conduit(
+            self.id,
+            self.proto.utility,
+            self.proto.path.clone(),
+            world,
+        );
+    }
+}
+
+impl Constructable<CBPrototypeKind> for Conduit {
+    fn morph(
+        &mut self,
+        new_prototype: &Prototype<CBPrototypeKind>,
+        report_to: CBConstructionID,
+        world: &mut World,
+    ) {
+        if let CBPrototypeKind::Conduit(ref proto) = new_prototype.kind {
+            self.disappear(world);
+            // only what is laid in addition costs anything
+            let extra_cost = proto.cost() - self.proto.cost();
+            self.proto = proto.clone();
+            self.charge(extra_cost, world);
+            self.appear(world);
+            report_to.action_done(self.id.into(), world);
+        } else {
+            unreachable!();
+        }
+    }
+
+    fn destruct(&mut self, report_to: CBConstructionID, world: &mut World) -> Fate {
+        self.disappear(world);
+        report_to.action_done(self.id.into(), world);
+        Fate::Die
+    }
+}
+
+// Independent of everything on the surface, planned in any order with it
+pub fn calculate_prototypes(
+    history: &PlanHistory<CBGestureIntent>,
+    _current_result: &PlanResult<CBPrototypeKind>,
+) -> Result<Vec<Prototype<CBPrototypeKind>>, AreaError> {
+    Ok(history
+        .gestures
+        .pairs()
+        .filter_map(|(gesture_id, versioned_gesture)| {
+            if let CBGestureIntent::Conduit(utility) = versioned_gesture.0.intent {
+                LinePath::new(versioned_gesture.0.points.clone()).map(|path| {
+                    let representative_position = path.start();
+                    Prototype::new_with_influences(
+                        gesture_id,
+                        CBPrototypeKind::Conduit(ConduitPrototype { utility, path }),
+                        representative_position,
+                    )
+                })
+            } else {
+                None
+            }
+        })
+        .collect())
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Conduit>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
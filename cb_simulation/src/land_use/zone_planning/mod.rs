@@ -1,5 +1,5 @@
 use compact::CVec;
-use descartes::{P2, V2, Area, ClosedLinePath, LinePath, PointContainer,
+use descartes::{N, P2, V2, Area, Band, ClosedLinePath, LinePath, PointContainer,
 AreaError, WithUniqueOrthogonal, AreaEmbedding, AreaFilter};
 use land_use::buildings::BuildingStyle;
 use ordered_float::OrderedFloat;
@@ -7,6 +7,7 @@ use itertools::Itertools;
 use cb_util::random::{seed, RngCore};
 
 use transport::transport_planning::{RoadPrototype, LanePrototype};
+use environment::water::WaterPrototype;
 
 use cb_planning::{PlanHistory, VersionedGesture, PlanResult, Prototype, PrototypeID, GestureID,
 StepID};
@@ -15,10 +16,27 @@ use planning::{CBPrototypeKind, CBGestureIntent};
 #[derive(Compact, Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub enum ZoneIntent {
     LandUse(LandUse),
+    // painted along roads instead of outlined, covering a strip
+    // of `FRONTAGE_DEPTH` on both sides of the painted path
+    LandUseAlongRoad(LandUse),
     MaxHeight(u8),
     SetBack(u8),
 }
 
+// deep enough for the largest lots that face a road
+pub const FRONTAGE_DEPTH: N = 40.0;
+
+impl ZoneIntent {
+    pub fn land_use(&self) -> Option<LandUse> {
+        match *self {
+            ZoneIntent::LandUse(land_use) | ZoneIntent::LandUseAlongRoad(land_use) => {
+                Some(land_use)
+            }
+            _ => None,
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
 pub enum LandUse {
     Residential,
@@ -125,6 +143,12 @@ pub enum LotOccupancy {
     Occupied(BuildingStyle),
 }
 
+fn outlined_area(outline: &[P2]) -> Option<Area> {
+    LinePath::new(outline.iter().chain(outline.first()).cloned().collect())
+        .and_then(ClosedLinePath::new)
+        .map(|closed_line_path| Area::new_simple(closed_line_path.to_clockwise()))
+}
+
 pub fn calculate_prototypes(
     history: &PlanHistory<CBGestureIntent>,
     current_result: &PlanResult<CBPrototypeKind>,
@@ -132,8 +156,10 @@ pub fn calculate_prototypes(
     #[derive(Clone, PartialEq, Eq, Hash, Debug)]
     enum ZoneEmbeddingLabel {
         Paved(PrototypeID),
+        Water(PrototypeID),
         Building(GestureID, StepID),
         Zone(ZoneIntent, GestureID, StepID),
+        Park(GestureID, StepID),
     };
 
     let mut zone_embedding = AreaEmbedding::new(30.0);
@@ -149,6 +175,17 @@ pub fn calculate_prototypes(
         }
     }
 
+    for prototype in current_result.prototypes.values() {
+        if let Prototype {
+            kind: CBPrototypeKind::Water(WaterPrototype { ref area, .. }),
+            id,
+            ..
+        } = *prototype
+        {
+            zone_embedding.insert(area.clone(), ZoneEmbeddingLabel::Water(id))
+        }
+    }
+
     for (gesture_id, VersionedGesture(gesture, step_id)) in history.gestures.pairs() {
         if let CBGestureIntent::Building(BuildingIntent { ref lot, .. }) = gesture.intent {
             zone_embedding.insert(
@@ -176,6 +213,7 @@ pub fn calculate_prototypes(
                         .and(AreaFilter::Function(Box::new(|labels| {
                             labels.iter().all(|label| match label {
                                 ZoneEmbeddingLabel::Paved(_) => false,
+                                ZoneEmbeddingLabel::Water(_) => false,
                                 _ => true,
                             })
                         }))),
@@ -246,7 +284,7 @@ pub fn calculate_prototypes(
     ];
 
     for prototype in current_result.prototypes.values() {
-        if let CBPrototypeKind::Road(RoadPrototype::Lane(LanePrototype(ref path, _))) =
+        if let CBPrototypeKind::Road(RoadPrototype::Lane(LanePrototype(ref path, ..))) =
             prototype.kind
         {
             let distance = (path.start() - P2::new(0.0, 0.0)).norm();
@@ -344,26 +382,98 @@ pub fn calculate_prototypes(
 
     for (gesture_id, VersionedGesture(gesture, step_id)) in history.gestures.pairs() {
         if let CBGestureIntent::Zone(ref zone_intent) = gesture.intent {
-            if let Some(area) = LinePath::new(
-                gesture
-                    .points
-                    .iter()
-                    .chain(gesture.points.first())
-                    .cloned()
-                    .collect(),
-            )
-            .and_then(ClosedLinePath::new)
-            .map(|closed_line_path| Area::new_simple(closed_line_path.to_clockwise()))
-            {
+            let maybe_area = if let ZoneIntent::LandUseAlongRoad(_) = *zone_intent {
+                // the road itself is cut out again as a paved area
+                LinePath::new(gesture.points.clone())
+                    .map(|path| Band::new(path, 2.0 * FRONTAGE_DEPTH).as_area())
+            } else {
+                outlined_area(&gesture.points)
+            };
+
+            if let Some(area) = maybe_area {
                 zone_embedding.insert(
                     area,
                     ZoneEmbeddingLabel::Zone(zone_intent.clone(), *gesture_id, *step_id),
                 );
             }
+        } else if let CBGestureIntent::Park = gesture.intent {
+            if let Some(area) = outlined_area(&gesture.points) {
+                zone_embedding.insert(area, ZoneEmbeddingLabel::Park(*gesture_id, *step_id));
+            }
+        }
+    }
+
+    // parks take over whatever zoned land they cover, but go around roads,
+    // water and existing buildings, so one park can end up as several lots
+    let mut park_prototypes = vec![];
+
+    for (&gesture_id, &VersionedGesture(ref gesture, step_id)) in history.gestures.pairs() {
+        if let CBGestureIntent::Park = gesture.intent {
+            let areas_with_pieces = zone_embedding
+                .view(
+                    AreaFilter::Function(Box::new(move |labels| {
+                        labels.contains(&ZoneEmbeddingLabel::Park(gesture_id, step_id))
+                    }))
+                    .and(AreaFilter::Function(Box::new(|labels| {
+                        labels.iter().all(|label| match label {
+                            ZoneEmbeddingLabel::Building(..) => false,
+                            ZoneEmbeddingLabel::Paved(_) => false,
+                            ZoneEmbeddingLabel::Water(_) => false,
+                            _ => true,
+                        })
+                    }))),
+                )
+                .get_areas_with_pieces()?;
+
+            for (area, pieces) in areas_with_pieces {
+                let mut influenced_id = PrototypeID::from_influences(gesture_id);
+                influenced_id = influenced_id.add_influences(step_id);
+                influenced_id = influenced_id.add_influences(
+                    area.primitives[0]
+                        .boundary
+                        .path()
+                        .points
+                        .iter()
+                        .map(|p| (p.x.to_bits(), p.y.to_bits()))
+                        .collect::<Vec<_>>(),
+                );
+
+                let road_boundaries = pieces.into_iter().filter_map(|(piece, piece_area_label)| {
+                    if Some(&piece_area_label.own_right_label)
+                        .into_iter()
+                        .chain(piece_area_label.left_labels.iter())
+                        .any(|label| match label {
+                            ZoneEmbeddingLabel::Paved(_) => true,
+                            _ => false,
+                        })
+                    {
+                        Some(piece)
+                    } else {
+                        None
+                    }
+                });
+
+                park_prototypes.push(Prototype {
+                    representative_position: area.primitives[0].boundary.path().points[0],
+                    kind: CBPrototypeKind::Lot(LotPrototype {
+                        lot: Lot {
+                            land_uses: vec![LandUse::Recreational].into(),
+                            max_height: 0,
+                            set_back: 0,
+                            road_boundaries: road_boundaries.collect(),
+                            original_area: area.clone(),
+                            original_lot_id: seed(influenced_id).next_u32(),
+                            area,
+                        },
+                        occupancy: LotOccupancy::Occupied(BuildingStyle::Park),
+                    }),
+                    id: influenced_id,
+                })
+            }
         }
     }
 
-    // remove paved and existing buildings to get vacant lots
+    // remove paved, water, parks and existing buildings to get vacant lots
     let mut vacant_lot_prototypes = vec![];
 
     for &land_use in &LAND_USES {
@@ -371,10 +481,8 @@ pub fn calculate_prototypes(
             .view(
                 AreaFilter::Function(Box::new(move |labels| {
                     labels.iter().any(|label| match label {
-                        ZoneEmbeddingLabel::Zone(ZoneIntent::LandUse(label_land_use), ..)
-                            if *label_land_use == land_use =>
-                        {
-                            true
+                        ZoneEmbeddingLabel::Zone(zone_intent, ..) => {
+                            zone_intent.land_use() == Some(land_use)
                         }
                         _ => false,
                     })
@@ -383,6 +491,8 @@ pub fn calculate_prototypes(
                     labels.iter().all(|label| match label {
                         ZoneEmbeddingLabel::Building(..) => false,
                         ZoneEmbeddingLabel::Paved(_) => false,
+                        ZoneEmbeddingLabel::Water(_) => false,
+                        ZoneEmbeddingLabel::Park(..) => false,
                         _ => true,
                     })
                 }))),
@@ -448,6 +558,7 @@ pub fn calculate_prototypes(
     Ok(vacant_lot_prototypes
         .into_iter()
         .chain(building_prototypes)
+        .chain(park_prototypes)
         .chain(
             neighboring_town_distance_per_octant
                 .into_iter()
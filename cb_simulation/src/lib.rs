@@ -9,9 +9,10 @@ extern crate fnv;
 extern crate roaring;
 extern crate uuid;
 extern crate arrayvec;
-extern crate cb_util;
+pub extern crate cb_util;
 pub extern crate cb_time;
 extern crate cb_planning;
+pub extern crate citybound_api;
 
 pub extern crate compact;
 #[macro_use]
@@ -20,15 +21,24 @@ pub extern crate kay;
 pub extern crate michelangelo;
 pub extern crate descartes;
 
+extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 
 pub mod transport;
 pub mod planning;
+#[macro_use]
 pub mod economy;
 pub mod land_use;
 pub mod dimensions;
 pub mod environment;
+pub mod timeline;
+pub mod scenarios;
+pub mod metrics;
+pub mod export;
+pub mod observation;
+pub mod bots;
+pub mod savegame;
 
 pub fn setup_common(system: &mut kay::ActorSystem) {
     for setup_fn in &[
@@ -40,19 +50,34 @@ pub fn setup_common(system: &mut kay::ActorSystem) {
         economy::setup,
         land_use::setup,
         environment::setup,
+        timeline::setup,
+        scenarios::setup,
+        metrics::setup,
+        export::setup,
+        observation::setup,
+        bots::setup,
     ] {
         setup_fn(system)
     }
 }
 
-pub fn spawn_for_server(world: &mut kay::World) -> cb_time::actors::TimeID {
+pub fn spawn_for_server(
+    world: &mut kay::World,
+    difficulty: economy::difficulty::DifficultyProfile,
+) -> cb_time::actors::TimeID {
     cb_util::log::spawn(world);
     let time = cb_time::actors::spawn(world);
     let plan_manager = cb_planning::plan_manager::spawn::<planning::CBPlanningLogic>(world);
     cb_planning::construction::spawn::<planning::CBPrototypeKind>(world);
-    land_use::spawn(world);
+    land_use::spawn(world, plan_manager);
     transport::spawn(world, time);
-    economy::spawn(world, time, plan_manager);
+    economy::spawn(world, time, plan_manager, difficulty);
+    environment::water::spawn(world, plan_manager);
     environment::vegetation::spawn(world, plan_manager);
+    environment::weather::spawn(world, time);
+    environment::city_events::spawn(world, time);
+    timeline::spawn(world, time);
+    metrics::spawn(world, time);
+    observation::spawn(world);
     time
 }
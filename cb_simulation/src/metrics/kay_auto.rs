@@ -0,0 +1,188 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct MetricsRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for MetricsRequesterID {}
+impl Clone for MetricsRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for MetricsRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "MetricsRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for MetricsRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for MetricsRequesterID {
+    fn eq(&self, other: &MetricsRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for MetricsRequesterID {}
+
+pub struct MetricsRequesterRepresentative;
+
+impl ActorOrActorTrait for MetricsRequesterRepresentative {
+    type ID = MetricsRequesterID;
+}
+
+impl TypedID for MetricsRequesterID {
+    type Target = MetricsRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        MetricsRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + MetricsRequester> TraitIDFrom<Act> for MetricsRequesterID {}
+
+impl MetricsRequesterID {
+    pub fn on_metrics(self, days: CVec < DailyMetrics >, world: &mut World) {
+        world.send(self.as_raw(), MSG_MetricsRequester_on_metrics(days));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<MetricsRequesterRepresentative>();
+        system.register_trait_message::<MSG_MetricsRequester_on_metrics>();
+    }
+
+    pub fn register_implementor<Act: Actor + MetricsRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, MetricsRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_MetricsRequester_on_metrics(ref days), instance, world| {
+                instance.on_metrics(days, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_MetricsRequester_on_metrics(pub CVec < DailyMetrics >);
+
+impl Actor for CityMetrics {
+    type ID = CityMetricsID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct CityMetricsID {
+    _raw_id: RawID
+}
+
+impl Copy for CityMetricsID {}
+impl Clone for CityMetricsID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for CityMetricsID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "CityMetricsID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for CityMetricsID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for CityMetricsID {
+    fn eq(&self, other: &CityMetricsID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for CityMetricsID {}
+
+impl TypedID for CityMetricsID {
+    type Target = CityMetrics;
+
+    fn from_raw(id: RawID) -> Self {
+        CityMetricsID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl CityMetricsID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = CityMetricsID::from_raw(world.allocate_instance_id::<CityMetrics>());
+        let swarm = world.local_broadcast::<CityMetrics>();
+        world.send(swarm, MSG_CityMetrics_spawn(id, time));
+        id
+    }
+    
+    pub fn add_household(self, day: u32, population: u32, n_working_age: u32, n_employed: u32, commute_minutes: Option < f32 >, prices: CVec < ( Resource , f32 ) >, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityMetrics_add_household(day, population, n_working_age, n_employed, commute_minutes, prices));
+    }
+    
+    pub fn get_metrics(self, from_day: u32, requester: MetricsRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityMetrics_get_metrics(from_day, requester));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityMetrics_spawn(pub CityMetricsID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityMetrics_add_household(pub u32, pub u32, pub u32, pub u32, pub Option < f32 >, pub CVec < ( Resource , f32 ) >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityMetrics_get_metrics(pub u32, pub MetricsRequesterID);
+
+impl Into<SleeperID> for CityMetricsID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<BalanceRequesterID> for CityMetricsID {
+    fn into(self) -> BalanceRequesterID {
+        BalanceRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TripStatsRequesterID> for CityMetricsID {
+    fn into(self) -> TripStatsRequesterID {
+        TripStatsRequesterID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    MetricsRequesterID::register_trait(system);
+    SleeperID::register_implementor::<CityMetrics>(system);
+    BalanceRequesterID::register_implementor::<CityMetrics>(system);
+    TripStatsRequesterID::register_implementor::<CityMetrics>(system);
+    system.add_spawner::<CityMetrics, _, _>(
+        |&MSG_CityMetrics_spawn(id, time), world| {
+            CityMetrics::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<CityMetrics, _, _>(
+        |&MSG_CityMetrics_add_household(day, population, n_working_age, n_employed, commute_minutes, ref prices), instance, world| {
+            instance.add_household(day, population, n_working_age, n_employed, commute_minutes, prices, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<CityMetrics, _, _>(
+        |&MSG_CityMetrics_get_metrics(from_day, requester), instance, world| {
+            instance.get_metrics(from_day, requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
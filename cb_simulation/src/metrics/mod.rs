@@ -0,0 +1,205 @@
+// Once a day, the city takes stock of itself: how many people live here and
+// how many of them have a job, how long their commutes are, how much is in the
+// treasury, what things cost on the market and how many trips were made.
+// Unlike the timeline, which keeps snapshots to look back on in the game,
+// these are flat numbers for analyzing long runs outside of it, through the
+// `Metrics` query of the API or the server's CSV dump
+use kay::{ActorSystem, World, TypedID};
+use compact::CVec;
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::calendar::day;
+use cb_time::units::{Instant, Duration};
+use economy::households::HouseholdID;
+use economy::policies::{TreasuryID, BalanceRequester, BalanceRequesterID};
+use economy::resources::{Resource, ResourceAmount};
+use transport::pathfinding::trip::TravelMode;
+use transport::trip_stats::{TripStatsID, TripStatsRequester, TripStatsRequesterID, ODEntry,
+ModeTravelTimes};
+
+const COLLECTION_INTERVAL: Duration = Duration(24 * 60 * 60);
+// about ten years of play, the oldest days are forgotten after that
+const MAX_DAYS: usize = 120;
+
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct DailyMetrics {
+    // days since the city was founded
+    pub day: u32,
+    pub population: u32,
+    pub n_working_age: u32,
+    pub n_employed: u32,
+    pub n_commuters: u32,
+    pub total_commute_minutes: f32,
+    pub treasury_balance: ResourceAmount,
+    // asking prices per unit of everything on sale, summed up
+    // with the number of offers, sorted by resource
+    pub prices: CVec<(Resource, f32, u32)>,
+    // trips of the day before, by mode
+    pub trips: CVec<(TravelMode, u32)>,
+}
+
+impl DailyMetrics {
+    fn new(day: u32) -> DailyMetrics {
+        DailyMetrics {
+            day,
+            population: 0,
+            n_working_age: 0,
+            n_employed: 0,
+            n_commuters: 0,
+            total_commute_minutes: 0.0,
+            treasury_balance: amount!(0.0),
+            prices: CVec::new(),
+            trips: CVec::new(),
+        }
+    }
+
+    pub fn employment_rate(&self) -> f32 {
+        if self.n_working_age > 0 {
+            self.n_employed as f32 / self.n_working_age as f32
+        } else {
+            0.0
+        }
+    }
+
+    pub fn average_commute_minutes(&self) -> f32 {
+        if self.n_commuters > 0 {
+            self.total_commute_minutes / self.n_commuters as f32
+        } else {
+            0.0
+        }
+    }
+
+    pub fn average_prices<'a>(&'a self) -> impl Iterator<Item = (Resource, f32)> + 'a {
+        self.prices
+            .iter()
+            .map(|&(resource, total, n_offers)| (resource, total / n_offers as f32))
+    }
+
+    fn add_price(&mut self, resource: Resource, price: f32) {
+        match self
+            .prices
+            .binary_search_by_key(&resource, |&(other, _, _)| other)
+        {
+            Ok(idx) => {
+                self.prices[idx].1 += price;
+                self.prices[idx].2 += 1;
+            }
+            Err(idx) => self.prices.insert(idx, (resource, price, 1)),
+        }
+    }
+}
+
+pub trait MetricsRequester {
+    fn on_metrics(&mut self, days: &CVec<DailyMetrics>, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct CityMetrics {
+    id: CityMetricsID,
+    // the last day is still being collected
+    days: CVec<DailyMetrics>,
+}
+
+impl CityMetrics {
+    pub fn spawn(id: CityMetricsID, time: TimeID, world: &mut World) -> CityMetrics {
+        time.wake_up_every(COLLECTION_INTERVAL, id.into(), world);
+
+        CityMetrics {
+            id,
+            days: CVec::new(),
+        }
+    }
+
+    // contributions of a previous day that arrive late are dropped
+    fn day_mut(&mut self, day: u32) -> Option<&mut DailyMetrics> {
+        self.days.last_mut().filter(|metrics| metrics.day == day)
+    }
+
+    pub fn add_household(
+        &mut self,
+        day: u32,
+        population: u32,
+        n_working_age: u32,
+        n_employed: u32,
+        commute_minutes: Option<f32>,
+        prices: &CVec<(Resource, f32)>,
+        _: &mut World,
+    ) {
+        if let Some(metrics) = self.day_mut(day) {
+            metrics.population += population;
+            metrics.n_working_age += n_working_age;
+            metrics.n_employed += n_employed;
+            if let Some(minutes) = commute_minutes {
+                metrics.n_commuters += 1;
+                metrics.total_commute_minutes += minutes;
+            }
+            for &(resource, price) in prices.iter() {
+                metrics.add_price(resource, price);
+            }
+        }
+    }
+
+    // only complete days are handed out
+    pub fn get_metrics(&mut self, from_day: u32, requester: MetricsRequesterID, world: &mut World) {
+        let n_complete = self.days.len().saturating_sub(1);
+        requester.on_metrics(
+            self.days
+                .iter()
+                .take(n_complete)
+                .filter(|metrics| metrics.day >= from_day)
+                .cloned()
+                .collect(),
+            world,
+        );
+    }
+}
+
+impl Sleeper for CityMetrics {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        if self.days.len() >= MAX_DAYS {
+            self.days.remove(0);
+        }
+
+        let today = day(current_instant);
+        self.days.push(DailyMetrics::new(today));
+
+        HouseholdID::global_broadcast(world).contribute_to_metrics(self.id, today, world);
+        TreasuryID::global_first(world).get_balance(self.id_as(), world);
+        TripStatsID::global_first(world).get_trip_stats(self.id_as(), world);
+    }
+}
+
+impl BalanceRequester for CityMetrics {
+    fn on_balance(&mut self, balance: ResourceAmount, _: &mut World) {
+        if let Some(metrics) = self.days.last_mut() {
+            metrics.treasury_balance = balance;
+        }
+    }
+}
+
+impl TripStatsRequester for CityMetrics {
+    fn on_trip_stats(
+        &mut self,
+        _: &CVec<ODEntry>,
+        travel_times: &CVec<ModeTravelTimes>,
+        _: &mut World,
+    ) {
+        if let Some(metrics) = self.days.last_mut() {
+            metrics.trips = travel_times
+                .iter()
+                .map(|times| (times.mode, times.n_trips))
+                .collect();
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<CityMetrics>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    CityMetricsID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
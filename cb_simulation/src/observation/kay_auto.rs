@@ -0,0 +1,190 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for ApiObserver {
+    type ID = ApiObserverID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ApiObserverID {
+    _raw_id: RawID
+}
+
+impl Copy for ApiObserverID {}
+impl Clone for ApiObserverID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ApiObserverID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ApiObserverID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ApiObserverID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ApiObserverID {
+    fn eq(&self, other: &ApiObserverID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ApiObserverID {}
+
+impl TypedID for ApiObserverID {
+    type Target = ApiObserver;
+
+    fn from_raw(id: RawID) -> Self {
+        ApiObserverID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl ApiObserverID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = ApiObserverID::from_raw(world.allocate_instance_id::<ApiObserver>());
+        let swarm = world.local_broadcast::<ApiObserver>();
+        world.send(swarm, MSG_ApiObserver_spawn(id, ));
+        id
+    }
+    
+    pub fn get_stats(self, request: u64, world: &mut World) {
+        world.send(self.as_raw(), MSG_ApiObserver_get_stats(request));
+    }
+    
+    pub fn get_households(self, request: u64, offset: u32, limit: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_ApiObserver_get_households(request, offset, limit));
+    }
+    
+    pub fn get_events(self, request: u64, from: u32, max: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_ApiObserver_get_events(request, from, max));
+    }
+    
+    pub fn get_trip_stats(self, request: u64, world: &mut World) {
+        world.send(self.as_raw(), MSG_ApiObserver_get_trip_stats(request));
+    }
+    
+    pub fn get_metrics(self, request: u64, from_day: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_ApiObserver_get_metrics(request, from_day));
+    }
+    
+    pub fn on_observed_household(self, id: HouseholdID, name: CString, n_members: u32, money: f32, happiness: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_ApiObserver_on_observed_household(id, name, n_members, money, happiness));
+    }
+    
+    pub fn flush(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_ApiObserver_flush());
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ApiObserver_spawn(pub ApiObserverID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ApiObserver_get_stats(pub u64);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ApiObserver_get_households(pub u64, pub u32, pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ApiObserver_get_events(pub u64, pub u32, pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ApiObserver_get_trip_stats(pub u64);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ApiObserver_get_metrics(pub u64, pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ApiObserver_on_observed_household(pub HouseholdID, pub CString, pub u32, pub f32, pub f32);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ApiObserver_flush();
+
+impl Into<TimelineUIID> for ApiObserverID {
+    fn into(self) -> TimelineUIID {
+        TimelineUIID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TripStatsRequesterID> for ApiObserverID {
+    fn into(self) -> TripStatsRequesterID {
+        TripStatsRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<MetricsRequesterID> for ApiObserverID {
+    fn into(self) -> MetricsRequesterID {
+        MetricsRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<LogRecipientID> for ApiObserverID {
+    fn into(self) -> LogRecipientID {
+        LogRecipientID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    TimelineUIID::register_implementor::<ApiObserver>(system);
+    TripStatsRequesterID::register_implementor::<ApiObserver>(system);
+    MetricsRequesterID::register_implementor::<ApiObserver>(system);
+    LogRecipientID::register_implementor::<ApiObserver>(system);
+    system.add_spawner::<ApiObserver, _, _>(
+        |&MSG_ApiObserver_spawn(id, ), world| {
+            ApiObserver::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<ApiObserver, _, _>(
+        |&MSG_ApiObserver_get_stats(request), instance, world| {
+            instance.get_stats(request, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ApiObserver, _, _>(
+        |&MSG_ApiObserver_get_households(request, offset, limit), instance, world| {
+            instance.get_households(request, offset, limit, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ApiObserver, _, _>(
+        |&MSG_ApiObserver_get_events(request, from, max), instance, world| {
+            instance.get_events(request, from, max, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ApiObserver, _, _>(
+        |&MSG_ApiObserver_get_trip_stats(request), instance, world| {
+            instance.get_trip_stats(request, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ApiObserver, _, _>(
+        |&MSG_ApiObserver_get_metrics(request, from_day), instance, world| {
+            instance.get_metrics(request, from_day, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ApiObserver, _, _>(
+        |&MSG_ApiObserver_on_observed_household(id, ref name, n_members, money, happiness), instance, world| {
+            instance.on_observed_household(id, name, n_members, money, happiness, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ApiObserver, _, _>(
+        |&MSG_ApiObserver_flush(), instance, world| {
+            instance.flush(world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
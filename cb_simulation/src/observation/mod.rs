@@ -0,0 +1,372 @@
+// Answers the queries of external tools connected to the server's API, see the
+// `citybound_api` crate. Queries are sent in by the server between turns, the
+// actors that know the answers report back here, and once all their messages
+// are processed, the server flushes the collected answers and takes them out,
+// like the region link does with its messages
+use kay::{ActorSystem, World, Actor, TypedID};
+use compact::{CVec, CString};
+use std::cell::RefCell;
+use cb_time::units::Instant;
+use cb_util::log::{LogID, LogLevel, Entry, LogRecipient, LogRecipientID};
+use citybound_api::{Query, Answer, CityStats, HouseholdSummary, Event, EventLevel,
+OriginDestination, TravelTimes, DayMetrics};
+use economy::households::HouseholdID;
+use economy::resources::amount_to_f32;
+use timeline::{TimelineID, TimelineUI, TimelineUIID, Snapshot};
+use transport::trip_stats::{TripStatsID, TripStatsRequester, TripStatsRequesterID, ODEntry,
+ModeTravelTimes};
+use metrics::{CityMetricsID, MetricsRequester, MetricsRequesterID, DailyMetrics};
+
+#[derive(Compact, Clone)]
+pub struct ObservedHousehold {
+    id: HouseholdID,
+    name: CString,
+    n_members: u32,
+    money: f32,
+    happiness: f32,
+}
+
+#[derive(Compact, Clone)]
+pub struct ObservedEvent {
+    number: u32,
+    level: LogLevel,
+    topic: CString,
+    message: CString,
+}
+
+#[derive(Compact, Clone)]
+pub struct ApiObserver {
+    id: ApiObserverID,
+    stats_requests: CVec<u64>,
+    latest_stats: Option<CityStats>,
+    // requests with their offset and limit
+    household_requests: CVec<(u64, u32, u32)>,
+    households: CVec<ObservedHousehold>,
+    // requests with the first event number and how many events at most
+    event_requests: CVec<(u64, u32, u32)>,
+    events: CVec<ObservedEvent>,
+    trip_stats_requests: CVec<u64>,
+    od_matrix: CVec<ODEntry>,
+    travel_times: CVec<ModeTravelTimes>,
+    // requests with the first day they want
+    metrics_requests: CVec<(u64, u32)>,
+    metrics: CVec<DailyMetrics>,
+}
+
+impl ApiObserver {
+    pub fn spawn(id: ApiObserverID, _: &mut World) -> ApiObserver {
+        ApiObserver {
+            id,
+            stats_requests: CVec::new(),
+            latest_stats: None,
+            household_requests: CVec::new(),
+            households: CVec::new(),
+            event_requests: CVec::new(),
+            events: CVec::new(),
+            trip_stats_requests: CVec::new(),
+            od_matrix: CVec::new(),
+            travel_times: CVec::new(),
+            metrics_requests: CVec::new(),
+            metrics: CVec::new(),
+        }
+    }
+
+    pub fn get_stats(&mut self, request: u64, world: &mut World) {
+        if self.stats_requests.is_empty() {
+            TimelineID::global_first(world).get_snapshot_instants(self.id_as(), world);
+        }
+        self.stats_requests.push(request);
+    }
+
+    pub fn get_households(&mut self, request: u64, offset: u32, limit: u32, world: &mut World) {
+        if self.household_requests.is_empty() {
+            HouseholdID::global_broadcast(world).observe(self.id, world);
+        }
+        self.household_requests.push((request, offset, limit));
+    }
+
+    pub fn get_events(&mut self, request: u64, from: u32, max: u32, world: &mut World) {
+        LogID::global_first(world).get_after(from, max, self.id_as(), world);
+        self.event_requests.push((request, from, max));
+    }
+
+    pub fn get_trip_stats(&mut self, request: u64, world: &mut World) {
+        if self.trip_stats_requests.is_empty() {
+            TripStatsID::global_first(world).get_trip_stats(self.id_as(), world);
+        }
+        self.trip_stats_requests.push(request);
+    }
+
+    // all days are asked for once and each request gets the ones it wants
+    pub fn get_metrics(&mut self, request: u64, from_day: u32, world: &mut World) {
+        if self.metrics_requests.is_empty() {
+            CityMetricsID::global_first(world).get_metrics(0, self.id_as(), world);
+        }
+        self.metrics_requests.push((request, from_day));
+    }
+
+    pub fn on_observed_household(
+        &mut self,
+        id: HouseholdID,
+        name: &CString,
+        n_members: u32,
+        money: f32,
+        happiness: f32,
+        _: &mut World,
+    ) {
+        self.households.push(ObservedHousehold {
+            id,
+            name: name.clone(),
+            n_members,
+            money,
+            happiness,
+        });
+    }
+
+    // Answers all requests with what was collected for them, which is
+    // everything there is once all messages of the turn are processed
+    pub fn flush(&mut self, _: &mut World) {
+        for &request in self.stats_requests.iter() {
+            answer(
+                request,
+                match self.latest_stats {
+                    Some(stats) => Answer::Stats(stats),
+                    None => Answer::Error("No statistics recorded yet".to_owned()),
+                },
+            );
+        }
+
+        if !self.household_requests.is_empty() {
+            let mut households = self
+                .households
+                .iter()
+                .map(|household| HouseholdSummary {
+                    id: format!("{:?}", household.id.as_raw()),
+                    name: household.name.to_string(),
+                    n_members: household.n_members,
+                    money: household.money,
+                    happiness: household.happiness,
+                })
+                .collect::<Vec<_>>();
+            households.sort_by(|a, b| a.id.cmp(&b.id));
+
+            for &(request, offset, limit) in self.household_requests.iter() {
+                answer(
+                    request,
+                    Answer::Households {
+                        total: households.len() as u32,
+                        households: households
+                            .iter()
+                            .skip(offset as usize)
+                            .take(limit as usize)
+                            .cloned()
+                            .collect(),
+                    },
+                );
+            }
+        }
+
+        for &(request, from, max) in self.event_requests.iter() {
+            let mut events = self
+                .events
+                .iter()
+                .filter(|event| event.number >= from)
+                .map(|event| Event {
+                    number: event.number,
+                    level: match event.level {
+                        LogLevel::Debug => EventLevel::Debug,
+                        LogLevel::Info => EventLevel::Info,
+                        LogLevel::Warning => EventLevel::Warning,
+                        LogLevel::Error => EventLevel::Error,
+                    },
+                    topic: event.topic.to_string(),
+                    message: event.message.to_string(),
+                })
+                .collect::<Vec<_>>();
+            events.sort_by_key(|event| event.number);
+            let n_skipped = events.len().saturating_sub(max as usize);
+            answer(request, Answer::Events(events.split_off(n_skipped)));
+        }
+
+        if !self.trip_stats_requests.is_empty() {
+            let od_matrix = self
+                .od_matrix
+                .iter()
+                .map(|entry| OriginDestination {
+                    origin: (entry.origin.0, entry.origin.1),
+                    destination: (entry.destination.0, entry.destination.1),
+                    slice: entry.slice,
+                    mode: format!("{:?}", entry.mode),
+                    n_trips: entry.n_trips,
+                    average_minutes: entry.total_minutes / entry.n_trips as f32,
+                })
+                .collect::<Vec<_>>();
+            let travel_times = self
+                .travel_times
+                .iter()
+                .map(|times| TravelTimes {
+                    mode: format!("{:?}", times.mode),
+                    n_trips: times.n_trips,
+                    average_planned_minutes: times.planned_minutes / times.n_trips as f32,
+                    average_actual_minutes: times.actual_minutes / times.n_trips as f32,
+                })
+                .collect::<Vec<_>>();
+
+            for &request in self.trip_stats_requests.iter() {
+                answer(
+                    request,
+                    Answer::TripStats {
+                        od_matrix: od_matrix.clone(),
+                        travel_times: travel_times.clone(),
+                    },
+                );
+            }
+        }
+
+        for &(request, from_day) in self.metrics_requests.iter() {
+            answer(
+                request,
+                Answer::Metrics(
+                    self.metrics
+                        .iter()
+                        .filter(|metrics| metrics.day >= from_day)
+                        .map(|metrics| DayMetrics {
+                            day: metrics.day,
+                            population: metrics.population,
+                            employment_rate: metrics.employment_rate(),
+                            average_commute_minutes: metrics.average_commute_minutes(),
+                            treasury_balance: amount_to_f32(metrics.treasury_balance),
+                            prices: metrics
+                                .average_prices()
+                                .map(|(resource, price)| (format!("{:?}", resource), price))
+                                .collect(),
+                            trips: metrics
+                                .trips
+                                .iter()
+                                .map(|&(mode, n_trips)| (format!("{:?}", mode), n_trips))
+                                .collect(),
+                        })
+                        .collect(),
+                ),
+            );
+        }
+
+        self.stats_requests.clear();
+        self.latest_stats = None;
+        self.household_requests.clear();
+        self.households.clear();
+        self.event_requests.clear();
+        self.events.clear();
+        self.trip_stats_requests.clear();
+        self.od_matrix.clear();
+        self.travel_times.clear();
+        self.metrics_requests.clear();
+        self.metrics.clear();
+    }
+}
+
+impl TimelineUI for ApiObserver {
+    fn on_snapshot_instants(&mut self, instants: &CVec<Instant>, world: &mut World) {
+        if let Some(&latest) = instants.last() {
+            TimelineID::global_first(world).get_snapshot(latest, self.id_as(), world);
+        }
+    }
+
+    fn on_snapshot(&mut self, snapshot: &Snapshot, _: &mut World) {
+        self.latest_stats = Some(CityStats {
+            ticks: snapshot.instant.ticks() as u64,
+            population: snapshot.population,
+            births: snapshot.births,
+            deaths: snapshot.deaths,
+            n_jobs: snapshot.n_jobs,
+            n_commuters: snapshot.n_commuters,
+            average_commute_minutes: if snapshot.n_commuters > 0 {
+                snapshot.total_commute_minutes / snapshot.n_commuters as f32
+            } else {
+                0.0
+            },
+            n_buildings: snapshot.n_buildings,
+            built_area: snapshot.built_area,
+            network_length: snapshot.network_length,
+        });
+    }
+}
+
+impl TripStatsRequester for ApiObserver {
+    fn on_trip_stats(
+        &mut self,
+        od_matrix: &CVec<ODEntry>,
+        travel_times: &CVec<ModeTravelTimes>,
+        _: &mut World,
+    ) {
+        self.od_matrix = od_matrix.clone();
+        self.travel_times = travel_times.clone();
+    }
+}
+
+impl MetricsRequester for ApiObserver {
+    fn on_metrics(&mut self, days: &CVec<DailyMetrics>, _: &mut World) {
+        self.metrics = days.clone();
+    }
+}
+
+impl LogRecipient for ApiObserver {
+    fn receive_newest_logs(
+        &mut self,
+        entries: &CVec<Entry>,
+        text: &CString,
+        effective_last: u32,
+        effective_text_start: u32,
+        _: &mut World,
+    ) {
+        for (i, entry) in entries.iter().enumerate() {
+            let number = effective_last + i as u32;
+            if self.events.iter().all(|event| event.number != number) {
+                self.events.push(ObservedEvent {
+                    number,
+                    level: entry.level(),
+                    topic: entry.topic(text, effective_text_start).to_owned().into(),
+                    message: entry.message(text, effective_text_start).to_owned().into(),
+                });
+            }
+        }
+    }
+}
+
+thread_local! {
+    static ANSWERS: RefCell<Vec<(u64, Answer)>> = RefCell::new(Vec::new());
+}
+
+fn answer(request: u64, answer: Answer) {
+    ANSWERS.with(|answers| answers.borrow_mut().push((request, answer)));
+}
+
+// Has to be called on the thread that processes the actor system's messages
+pub fn take_answers() -> Vec<(u64, Answer)> {
+    ANSWERS.with(|answers| ::std::mem::replace(&mut *answers.borrow_mut(), Vec::new()))
+}
+
+pub fn ask(request: u64, query: Query, world: &mut World) {
+    let observer = ApiObserverID::global_first(world);
+    match query {
+        Query::Stats => observer.get_stats(request, world),
+        Query::Households { offset, limit } => {
+            observer.get_households(request, offset, limit, world)
+        }
+        Query::Events { from, max } => observer.get_events(request, from, max, world),
+        Query::TripStats => observer.get_trip_stats(request, world),
+        Query::Metrics { from_day } => observer.get_metrics(request, from_day, world),
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<ApiObserver>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    ApiObserverID::spawn(world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
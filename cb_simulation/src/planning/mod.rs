@@ -3,6 +3,9 @@ use compact::CVec;
 use transport::transport_planning::{RoadIntent, RoadPrototype};
 use land_use::zone_planning::{ZoneIntent, BuildingIntent, LotPrototype};
 use environment::vegetation::{PlantIntent, PlantPrototype};
+use environment::water::{WaterIntent, WaterPrototype};
+use land_use::underground::ConduitPrototype;
+use economy::utilities::Utility;
 use cb_planning::{PlanningLogic, PrototypeID, PlanningStepFn};
 use cb_planning::plan_manager::{PlanManager, PlanManagerID};
 use cb_planning::construction::{Construction, ConstructionID, PrototypeKind, GestureIntent,
@@ -17,9 +20,12 @@ impl PlanningLogic for CBPlanningLogic {
 
     fn planning_step_functions() -> &'static [PlanningStepFn<Self>] {
         &[
+            // everything else needs to know where the water is
+            ::environment::water::calculate_prototypes,
             ::transport::transport_planning::calculate_prototypes,
             ::land_use::zone_planning::calculate_prototypes,
             ::environment::vegetation::calculate_prototypes,
+            ::land_use::underground::calculate_prototypes,
         ]
     }
 }
@@ -35,6 +41,15 @@ pub enum CBGestureIntent {
     Zone(ZoneIntent),
     Building(BuildingIntent),
     Plant(PlantIntent),
+    Water(WaterIntent),
+    // outlines a park, which zone planning turns into park lots
+    Park,
+    // see `transport::transport_planning::TurnBan`
+    TurnBan,
+    // see `transport::transport_planning::Roundabout`
+    Roundabout,
+    // an underground line along the gesture points, see `land_use::underground`
+    Conduit(Utility),
 }
 
 impl GestureIntent for CBGestureIntent {}
@@ -44,6 +59,8 @@ pub enum CBPrototypeKind {
     Road(RoadPrototype),
     Lot(LotPrototype),
     Plant(PlantPrototype),
+    Water(WaterPrototype),
+    Conduit(ConduitPrototype),
 }
 
 impl PrototypeKind for CBPrototypeKind {
@@ -61,6 +78,12 @@ impl PrototypeKind for CBPrototypeKind {
             CBPrototypeKind::Plant(ref plant_prototype) => {
                 plant_prototype.construct(prototype_id, report_to, world)
             }
+            CBPrototypeKind::Water(ref water_prototype) => {
+                water_prototype.construct(prototype_id, report_to, world)
+            }
+            CBPrototypeKind::Conduit(ref conduit_prototype) => {
+                conduit_prototype.construct(prototype_id, report_to, world)
+            }
         }
     }
 
@@ -75,6 +98,13 @@ impl PrototypeKind for CBPrototypeKind {
             (&CBPrototypeKind::Plant(ref self_plant), &CBPrototypeKind::Plant(ref other_plant)) => {
                 self_plant.morphable_from(other_plant)
             }
+            (&CBPrototypeKind::Water(ref self_water), &CBPrototypeKind::Water(ref other_water)) => {
+                self_water.morphable_from(other_water)
+            }
+            (
+                &CBPrototypeKind::Conduit(ref self_conduit),
+                &CBPrototypeKind::Conduit(ref other_conduit),
+            ) => self_conduit.morphable_from(other_conduit),
             _ => false,
         }
     }
@@ -0,0 +1,414 @@
+use kay::World;
+use compact::CVec;
+use std::collections::HashMap;
+use std::path::Path;
+use std::io;
+use std::fs;
+
+use cb_time::actors::TimeID;
+use planning::CBPlanManagerID;
+use land_use::buildings::BuildingID;
+use transport::lane::{LaneID, SwitchLaneID};
+use transport::transit::{TransitNetworkID, TransitStopID, TransitVehicleID};
+use transport::parking::ParkingRegistryID;
+use transport::pedestrians::PedestrianNetworkID;
+use economy::difficulty::DifficultyProfile;
+
+const MANIFEST_FILE: &str = "__cb_subsystems.txt";
+
+// Groups of actors whose persisted state is versioned, dropped and restored together
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize)]
+pub enum Subsystem {
+    Time,
+    Log,
+    Planning,
+    Transport,
+    LandUse,
+    Environment,
+    Economy,
+    Timeline,
+    Observation,
+    Bots,
+    Scenarios,
+    Metrics,
+}
+
+pub const ALL_SUBSYSTEMS: [Subsystem; 12] = [
+    Subsystem::Time,
+    Subsystem::Log,
+    Subsystem::Planning,
+    Subsystem::Transport,
+    Subsystem::LandUse,
+    Subsystem::Environment,
+    Subsystem::Economy,
+    Subsystem::Timeline,
+    Subsystem::Observation,
+    Subsystem::Bots,
+    Subsystem::Scenarios,
+    Subsystem::Metrics,
+];
+
+impl Subsystem {
+    pub fn name(self) -> &'static str {
+        match self {
+            Subsystem::Time => "Time",
+            Subsystem::Log => "Log",
+            Subsystem::Planning => "Planning",
+            Subsystem::Transport => "Transport",
+            Subsystem::LandUse => "LandUse",
+            Subsystem::Environment => "Environment",
+            Subsystem::Economy => "Economy",
+            Subsystem::Timeline => "Timeline",
+            Subsystem::Observation => "Observation",
+            Subsystem::Bots => "Bots",
+            Subsystem::Scenarios => "Scenarios",
+            Subsystem::Metrics => "Metrics",
+        }
+    }
+
+    // Bump whenever the layout of any of the subsystem's persisted actors changes,
+    // so old savegames don't get misread as the new layout
+    pub fn schema_version(self) -> u32 {
+        match self {
+            Subsystem::Time => 4,
+            Subsystem::Log => 1,
+            Subsystem::Planning => 11,
+            Subsystem::Transport => 24,
+            Subsystem::LandUse => 9,
+            Subsystem::Environment => 5,
+            Subsystem::Economy => 38,
+            Subsystem::Timeline => 2,
+            Subsystem::Observation => 3,
+            Subsystem::Bots => 5,
+            Subsystem::Scenarios => 1,
+            Subsystem::Metrics => 5,
+        }
+    }
+
+    // Whether a city still makes sense when this subsystem starts over from scratch.
+    // Everything that was built by the player has to survive
+    pub fn can_be_dropped(self) -> bool {
+        match self {
+            Subsystem::Log
+            | Subsystem::Economy
+            | Subsystem::Timeline
+            | Subsystem::Observation
+            | Subsystem::Bots
+            | Subsystem::Scenarios
+            | Subsystem::Metrics => true,
+            _ => false,
+        }
+    }
+
+    pub fn consequence_of_dropping(self) -> &'static str {
+        match self {
+            Subsystem::Log => "The simulation log starts empty.",
+            Subsystem::Economy => {
+                "All households, their belongings and ongoing trips are gone. \
+                 Vacant homes fill up again with new immigrants, \
+                 but existing shops, farms and public buildings stay empty. \
+                 The difficulty goes back to normal."
+            }
+            Subsystem::Timeline => "The history of the city starts over.",
+            Subsystem::Observation => "Queries of connected API tools in progress go unanswered.",
+            Subsystem::Bots => "Scripted bots stop playing.",
+            Subsystem::Scenarios => "The goals of the scenario are no longer tracked.",
+            Subsystem::Metrics => "The daily metrics of the city start over.",
+            _ => "The city can't be loaded without it.",
+        }
+    }
+
+    // module paths of the actor types that belong to the subsystem
+    fn actor_paths(self) -> &'static [&'static str] {
+        match self {
+            Subsystem::Time => &["cb_time::"],
+            Subsystem::Log => &["cb_util::log::"],
+            Subsystem::Planning => &["cb_planning::", "cb_simulation::planning::"],
+            Subsystem::Transport => &["cb_simulation::transport::"],
+            Subsystem::LandUse => &["cb_simulation::land_use::"],
+            Subsystem::Environment => &["cb_simulation::environment::"],
+            // trips only exist for the households taking them
+            Subsystem::Economy => &[
+                "cb_simulation::economy::",
+                "cb_simulation::transport::pathfinding::trip::",
+            ],
+            Subsystem::Timeline => &["cb_simulation::timeline::"],
+            Subsystem::Observation => &["cb_simulation::observation::"],
+            Subsystem::Bots => &["cb_simulation::bots::"],
+            Subsystem::Scenarios => &["cb_simulation::scenarios::"],
+            Subsystem::Metrics => &["cb_simulation::metrics::"],
+        }
+    }
+
+    // Which subsystem an actor type (or a file named after it) belongs to.
+    // The outermost type decides for generic types, and within it the most
+    // specific module path, so trips belong to the economy and not to transport
+    pub fn of_actor_type(type_name: &str) -> Option<Subsystem> {
+        let normalized_name = normalize(type_name);
+
+        ALL_SUBSYSTEMS
+            .iter()
+            .flat_map(|&subsystem| {
+                let normalized_name = &normalized_name;
+                subsystem.actor_paths().iter().filter_map(move |path| {
+                    let normalized_path = normalize(path);
+                    normalized_name
+                        .find(&normalized_path)
+                        .map(|position| (position, normalized_path.len(), subsystem))
+                })
+            })
+            .min_by_key(|&(position, path_len, _)| (position, -(path_len as isize)))
+            .map(|(_, _, subsystem)| subsystem)
+    }
+}
+
+// Persisted actor files are named after their full type name, but separators
+// might have been replaced to make them valid file names, so we only compare
+// letters and digits, with any run of other characters standing for one separator
+fn normalize(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    let mut last_was_separator = false;
+
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            normalized.push(c.to_ascii_lowercase());
+            last_was_separator = false;
+        } else if !last_was_separator {
+            normalized.push('_');
+            last_was_separator = true;
+        }
+    }
+
+    normalized
+}
+
+pub fn write_manifest(city_folder: &Path) -> io::Result<()> {
+    let manifest = ALL_SUBSYSTEMS
+        .iter()
+        .map(|subsystem| format!("{} {}\n", subsystem.name(), subsystem.schema_version()))
+        .collect::<String>();
+    fs::write(city_folder.join(MANIFEST_FILE), manifest)
+}
+
+// None for savegames from before subsystems were versioned
+fn read_manifest(city_folder: &Path) -> Option<HashMap<String, u32>> {
+    let manifest = fs::read_to_string(city_folder.join(MANIFEST_FILE)).ok()?;
+
+    Some(
+        manifest
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let name = parts.next()?;
+                let schema_version = parts.next()?.parse().ok()?;
+                Some((name.to_owned(), schema_version))
+            })
+            .collect(),
+    )
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize)]
+pub enum SubsystemStatus {
+    Restored,
+    // saved before subsystems were versioned, loaded on the assumption that it didn't change
+    Unverified,
+    Dropped,
+    Incompatible,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SubsystemReport {
+    pub subsystem: Subsystem,
+    pub status: SubsystemStatus,
+    pub saved_schema_version: Option<u32>,
+    pub current_schema_version: u32,
+    pub consequence: Option<String>,
+}
+
+// What survives of a savegame when loading it with the current version,
+// shown to the player so nothing disappears silently
+#[derive(Clone, Debug, Serialize)]
+pub struct CompatibilityReport {
+    pub saved_version: String,
+    pub current_version: String,
+    pub subsystems: Vec<SubsystemReport>,
+}
+
+impl CompatibilityReport {
+    pub fn check(city_folder: &Path, saved_version: &str, current_version: &str) -> Self {
+        let maybe_manifest = read_manifest(city_folder);
+        let same_version = saved_version.trim() == current_version.trim();
+
+        let subsystems = ALL_SUBSYSTEMS
+            .iter()
+            .map(|&subsystem| {
+                let saved_schema_version = maybe_manifest
+                    .as_ref()
+                    .and_then(|manifest| manifest.get(subsystem.name()).cloned());
+
+                let compatible = if maybe_manifest.is_some() {
+                    saved_schema_version == Some(subsystem.schema_version())
+                } else {
+                    same_version
+                };
+
+                let status = if compatible {
+                    SubsystemStatus::Restored
+                } else if subsystem.can_be_dropped() {
+                    SubsystemStatus::Dropped
+                } else if maybe_manifest.is_none() {
+                    SubsystemStatus::Unverified
+                } else {
+                    SubsystemStatus::Incompatible
+                };
+
+                SubsystemReport {
+                    subsystem,
+                    status,
+                    saved_schema_version,
+                    current_schema_version: subsystem.schema_version(),
+                    consequence: match status {
+                        SubsystemStatus::Dropped | SubsystemStatus::Incompatible => {
+                            Some(subsystem.consequence_of_dropping().to_owned())
+                        }
+                        _ => None,
+                    },
+                }
+            })
+            .collect();
+
+        CompatibilityReport {
+            saved_version: saved_version.trim().to_owned(),
+            current_version: current_version.trim().to_owned(),
+            subsystems,
+        }
+    }
+
+    pub fn can_load(&self) -> bool {
+        self.with_status(SubsystemStatus::Incompatible).is_empty()
+    }
+
+    pub fn is_partial(&self) -> bool {
+        !self.dropped().is_empty()
+    }
+
+    pub fn dropped(&self) -> Vec<Subsystem> {
+        self.with_status(SubsystemStatus::Dropped)
+    }
+
+    fn with_status(&self, status: SubsystemStatus) -> Vec<Subsystem> {
+        self.subsystems
+            .iter()
+            .filter(|report| report.status == status)
+            .map(|report| report.subsystem)
+            .collect()
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Savegame from version {}, running version {}:\n",
+            self.saved_version, self.current_version
+        );
+
+        for report in &self.subsystems {
+            summary.push_str(&format!(
+                "  {:<12} {:?}{}\n",
+                report.subsystem.name(),
+                report.status,
+                report
+                    .consequence
+                    .as_ref()
+                    .map(|consequence| format!(" - {}", consequence))
+                    .unwrap_or_default()
+            ));
+        }
+
+        summary
+    }
+}
+
+// Removes the persisted actors of dropped subsystems before the actor system
+// loads the savegame, so they start over from scratch. Returns the removed files
+pub fn drop_persisted_state(
+    city_folder: &Path,
+    report: &CompatibilityReport,
+) -> io::Result<Vec<String>> {
+    let dropped = report.dropped();
+    let mut removed = Vec::new();
+
+    for entry in fs::read_dir(city_folder)? {
+        let path = entry?.path();
+        let file_name = match path.file_name().and_then(|name| name.to_str()) {
+            Some(file_name) if !file_name.starts_with("__cb_") => file_name.to_owned(),
+            _ => continue,
+        };
+
+        if let Some(subsystem) = Subsystem::of_actor_type(&file_name) {
+            if dropped.contains(&subsystem) {
+                if path.is_dir() {
+                    fs::remove_dir_all(&path)?;
+                } else {
+                    fs::remove_file(&path)?;
+                }
+                removed.push(file_name);
+            }
+        }
+    }
+
+    Ok(removed)
+}
+
+// Actor type ids of dropped subsystems, from the actor system's id to name mapping
+pub fn dropped_actor_types(
+    report: &CompatibilityReport,
+    type_names: &HashMap<u16, String>,
+) -> CVec<u16> {
+    let dropped = report.dropped();
+
+    type_names
+        .iter()
+        .filter(|&(_, type_name)| {
+            Subsystem::of_actor_type(type_name)
+                .map(|subsystem| dropped.contains(&subsystem))
+                .unwrap_or(false)
+        })
+        .map(|(&type_id, _)| type_id)
+        .collect()
+}
+
+// Spawns dropped subsystems anew and makes the surviving ones
+// forget everything that referred to the dropped actors
+pub fn restore_dropped(
+    report: &CompatibilityReport,
+    dropped_actor_types: &CVec<u16>,
+    time: TimeID,
+    world: &mut World,
+) {
+    time.forget_sleepers(dropped_actor_types.clone(), world);
+
+    for subsystem in report.dropped() {
+        match subsystem {
+            Subsystem::Log => ::cb_util::log::spawn(world),
+            Subsystem::Economy => {
+                BuildingID::global_broadcast(world).forget_households(world);
+                LaneID::global_broadcast(world).forget_cars(world);
+                SwitchLaneID::global_broadcast(world).forget_cars(world);
+                TransitStopID::global_broadcast(world).forget_passengers(world);
+                TransitVehicleID::global_broadcast(world).forget_passengers(world);
+                ParkingRegistryID::global_first(world).forget_parked_cars(world);
+                TransitNetworkID::global_first(world).forget_parked_cars(world);
+                PedestrianNetworkID::global_first(world).forget_walkers(world);
+
+                let plan_manager = CBPlanManagerID::global_first(world);
+                ::transport::pathfinding::spawn(world, time);
+                ::economy::spawn(world, time, plan_manager, DifficultyProfile::normal());
+            }
+            Subsystem::Timeline => ::timeline::spawn(world, time),
+            Subsystem::Observation => ::observation::spawn(world),
+            Subsystem::Metrics => ::metrics::spawn(world, time),
+            // they are only started for new cities
+            Subsystem::Bots | Subsystem::Scenarios => {}
+            _ => unreachable!("Only droppable subsystems can be dropped"),
+        }
+    }
+}
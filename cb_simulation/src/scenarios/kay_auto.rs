@@ -0,0 +1,176 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ScenarioUIID {
+    _raw_id: RawID
+}
+
+impl Copy for ScenarioUIID {}
+impl Clone for ScenarioUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ScenarioUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ScenarioUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ScenarioUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ScenarioUIID {
+    fn eq(&self, other: &ScenarioUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ScenarioUIID {}
+
+pub struct ScenarioUIRepresentative;
+
+impl ActorOrActorTrait for ScenarioUIRepresentative {
+    type ID = ScenarioUIID;
+}
+
+impl TypedID for ScenarioUIID {
+    type Target = ScenarioUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        ScenarioUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + ScenarioUI> TraitIDFrom<Act> for ScenarioUIID {}
+
+impl ScenarioUIID {
+    pub fn on_scenario_progress(self, name: CString, status: ScenarioStatus, goals: CVec < GoalProgress >, world: &mut World) {
+        world.send(self.as_raw(), MSG_ScenarioUI_on_scenario_progress(name, status, goals));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<ScenarioUIRepresentative>();
+        system.register_trait_message::<MSG_ScenarioUI_on_scenario_progress>();
+    }
+
+    pub fn register_implementor<Act: Actor + ScenarioUI>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, ScenarioUIRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_ScenarioUI_on_scenario_progress(ref name, status, ref goals), instance, world| {
+                instance.on_scenario_progress(name, status, goals, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ScenarioUI_on_scenario_progress(pub CString, pub ScenarioStatus, pub CVec < GoalProgress >);
+
+impl Actor for ScenarioTracker {
+    type ID = ScenarioTrackerID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ScenarioTrackerID {
+    _raw_id: RawID
+}
+
+impl Copy for ScenarioTrackerID {}
+impl Clone for ScenarioTrackerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ScenarioTrackerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ScenarioTrackerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ScenarioTrackerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ScenarioTrackerID {
+    fn eq(&self, other: &ScenarioTrackerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ScenarioTrackerID {}
+
+impl TypedID for ScenarioTrackerID {
+    type Target = ScenarioTracker;
+
+    fn from_raw(id: RawID) -> Self {
+        ScenarioTrackerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl ScenarioTrackerID {
+    pub fn spawn(name: CString, conditions: CVec < Condition >, time: TimeID, world: &mut World) -> Self {
+        let id = ScenarioTrackerID::from_raw(world.allocate_instance_id::<ScenarioTracker>());
+        let swarm = world.local_broadcast::<ScenarioTracker>();
+        world.send(swarm, MSG_ScenarioTracker_spawn(id, name, conditions, time));
+        id
+    }
+    
+    pub fn get_progress(self, ui: ScenarioUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_ScenarioTracker_get_progress(ui));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ScenarioTracker_spawn(pub ScenarioTrackerID, pub CString, pub CVec < Condition >, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ScenarioTracker_get_progress(pub ScenarioUIID);
+
+impl Into<SleeperID> for ScenarioTrackerID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<BalanceRequesterID> for ScenarioTrackerID {
+    fn into(self) -> BalanceRequesterID {
+        BalanceRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TimelineUIID> for ScenarioTrackerID {
+    fn into(self) -> TimelineUIID {
+        TimelineUIID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    ScenarioUIID::register_trait(system);
+    SleeperID::register_implementor::<ScenarioTracker>(system);
+    BalanceRequesterID::register_implementor::<ScenarioTracker>(system);
+    TimelineUIID::register_implementor::<ScenarioTracker>(system);
+    system.add_spawner::<ScenarioTracker, _, _>(
+        |&MSG_ScenarioTracker_spawn(id, ref name, ref conditions, time), world| {
+            ScenarioTracker::spawn(id, name, conditions, time, world)
+        }, false
+    );
+    
+    system.add_handler::<ScenarioTracker, _, _>(
+        |&MSG_ScenarioTracker_get_progress(ui), instance, world| {
+            instance.get_progress(ui, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
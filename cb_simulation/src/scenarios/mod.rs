@@ -0,0 +1,440 @@
+// Scenarios give a new city a goal. They are written as plain text files
+// and picked when the city is founded, see `--scenario`:
+//
+//     name: Commuter Town
+//     # a prepared city folder to start from, instead of an empty map
+//     map: maps/riverside
+//     funds: 150000
+//     win: population >= 10000 and average_commute < 20
+//     lose: funds < 0
+//     lose: days > 120
+//
+// The city wins once all of its win conditions hold at the same time and
+// loses as soon as any lose condition holds. The tracker evaluates them with
+// the latest statistics of the timeline and the balance of the treasury,
+// and tells UIs how close the city is to each of them
+use kay::{ActorSystem, World, TypedID};
+use compact::{CVec, CString};
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::calendar::TICKS_PER_DAY;
+use cb_time::units::{Instant, Duration};
+use cb_util::log::{info, warn};
+use economy::policies::{TreasuryID, BalanceRequester, BalanceRequesterID};
+use economy::resources::{ResourceAmount, to_amount, amount_to_f32};
+use timeline::{TimelineID, TimelineUI, TimelineUIID, Snapshot};
+const LOG_T: &str = "Scenario";
+
+const CHECK_INTERVAL: Duration = Duration(6 * 60 * 60);
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Metric {
+    Population,
+    AverageCommuteMinutes,
+    Jobs,
+    Buildings,
+    NetworkLength,
+    Funds,
+    // since the scenario started
+    Days,
+}
+
+impl Metric {
+    fn from_name(name: &str) -> Option<Metric> {
+        match name {
+            "population" => Some(Metric::Population),
+            "average_commute" => Some(Metric::AverageCommuteMinutes),
+            "jobs" => Some(Metric::Jobs),
+            "buildings" => Some(Metric::Buildings),
+            "network_length" => Some(Metric::NetworkLength),
+            "funds" => Some(Metric::Funds),
+            "days" => Some(Metric::Days),
+            _ => None,
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Metric::Population => "population",
+            Metric::AverageCommuteMinutes => "average commute (min)",
+            Metric::Jobs => "jobs",
+            Metric::Buildings => "buildings",
+            Metric::NetworkLength => "road network (m)",
+            Metric::Funds => "funds",
+            Metric::Days => "days",
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Comparator {
+    AtLeast,
+    AtMost,
+    Above,
+    Below,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Comparison {
+    pub metric: Metric,
+    pub comparator: Comparator,
+    pub target: f32,
+}
+
+impl Comparison {
+    fn parse(text: &str) -> Result<Comparison, String> {
+        let parts = text.split_whitespace().collect::<Vec<_>>();
+        if parts.len() != 3 {
+            return Err(format!("expected e.g. \"population >= 10000\", got {:?}", text));
+        }
+
+        let metric = Metric::from_name(parts[0])
+            .ok_or_else(|| format!("unknown statistic {:?}", parts[0]))?;
+        let comparator = match parts[1] {
+            ">=" => Comparator::AtLeast,
+            "<=" => Comparator::AtMost,
+            ">" => Comparator::Above,
+            "<" => Comparator::Below,
+            other => return Err(format!("unknown comparison {:?}", other)),
+        };
+        let target = parts[2]
+            .parse::<f32>()
+            .map_err(|_| format!("expected a number, got {:?}", parts[2]))?;
+
+        Ok(Comparison {
+            metric,
+            comparator,
+            target,
+        })
+    }
+
+    fn holds(&self, value: f32) -> bool {
+        match self.comparator {
+            Comparator::AtLeast => value >= self.target,
+            Comparator::AtMost => value <= self.target,
+            Comparator::Above => value > self.target,
+            Comparator::Below => value < self.target,
+        }
+    }
+
+    // How close the value is to the target, from 0 to 1
+    fn progress(&self, value: f32) -> f32 {
+        if self.holds(value) {
+            return 1.0;
+        }
+        let ratio = match self.comparator {
+            Comparator::AtLeast | Comparator::Above => value / self.target,
+            Comparator::AtMost | Comparator::Below => self.target / value,
+        };
+        if ratio.is_finite() {
+            ratio.max(0.0).min(1.0)
+        } else {
+            0.0
+        }
+    }
+
+    fn describe(&self) -> String {
+        let comparator = match self.comparator {
+            Comparator::AtLeast => "at least",
+            Comparator::AtMost => "at most",
+            Comparator::Above => "more than",
+            Comparator::Below => "less than",
+        };
+        format!(
+            "{} {} {}",
+            self.metric.description(),
+            comparator,
+            self.target
+        )
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Outcome {
+    Win,
+    Lose,
+}
+
+// All comparisons have to hold at the same time
+#[derive(Compact, Clone)]
+pub struct Condition {
+    pub outcome: Outcome,
+    pub comparisons: CVec<Comparison>,
+}
+
+fn parse_comparisons(text: &str) -> Result<Vec<Comparison>, String> {
+    text.split(" and ").map(Comparison::parse).collect()
+}
+
+impl Condition {
+    fn describe(&self) -> String {
+        self.comparisons
+            .iter()
+            .map(Comparison::describe)
+            .collect::<Vec<_>>()
+            .join(" and ")
+    }
+}
+
+#[derive(Clone)]
+pub struct Scenario {
+    pub name: String,
+    // a city folder to copy the new city from
+    pub map: Option<String>,
+    pub funds: Option<ResourceAmount>,
+    // the comparisons of each condition
+    pub conditions: Vec<(Outcome, Vec<Comparison>)>,
+}
+
+impl Scenario {
+    // One "key: value" per line, empty lines and lines starting with # are ignored
+    pub fn parse(text: &str) -> Result<Scenario, String> {
+        let mut scenario = Scenario {
+            name: "Scenario".to_owned(),
+            map: None,
+            funds: None,
+            conditions: Vec::new(),
+        };
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.splitn(2, ':');
+            let key = parts.next().unwrap_or("").trim();
+            let value = parts
+                .next()
+                .map(str::trim)
+                .ok_or_else(|| format!("line {}: expected \"key: value\"", i + 1))?;
+            let in_line = |error: String| format!("line {}: {}", i + 1, error);
+
+            match key {
+                "name" => scenario.name = value.to_owned(),
+                "map" => scenario.map = Some(value.to_owned()),
+                "funds" => {
+                    scenario.funds = Some(
+                        value
+                            .parse::<f32>()
+                            .map(to_amount)
+                            .map_err(|_| in_line(format!("expected an amount, got {:?}", value)))?,
+                    )
+                }
+                "win" => scenario
+                    .conditions
+                    .push((Outcome::Win, parse_comparisons(value).map_err(in_line)?)),
+                "lose" => scenario
+                    .conditions
+                    .push((Outcome::Lose, parse_comparisons(value).map_err(in_line)?)),
+                other => return Err(in_line(format!("unknown key {:?}", other))),
+            }
+        }
+
+        if !scenario
+            .conditions
+            .iter()
+            .any(|&(outcome, _)| outcome == Outcome::Win)
+        {
+            return Err("a scenario needs at least one win condition".to_owned());
+        }
+
+        Ok(scenario)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum ScenarioStatus {
+    Playing,
+    Won,
+    Lost,
+}
+
+#[derive(Compact, Clone)]
+pub struct GoalProgress {
+    pub outcome: Outcome,
+    pub description: CString,
+    // from 0 to 1, how close the city is to meeting the condition
+    pub progress: f32,
+}
+
+pub trait ScenarioUI {
+    fn on_scenario_progress(
+        &mut self,
+        name: &CString,
+        status: ScenarioStatus,
+        goals: &CVec<GoalProgress>,
+        world: &mut World,
+    );
+}
+
+#[derive(Compact, Clone)]
+pub struct ScenarioTracker {
+    id: ScenarioTrackerID,
+    name: CString,
+    conditions: CVec<Condition>,
+    status: ScenarioStatus,
+    started: Option<Instant>,
+    current_instant: Instant,
+    funds: ResourceAmount,
+    goals: CVec<GoalProgress>,
+}
+
+impl ScenarioTracker {
+    pub fn spawn(
+        id: ScenarioTrackerID,
+        name: &CString,
+        conditions: &CVec<Condition>,
+        time: TimeID,
+        world: &mut World,
+    ) -> ScenarioTracker {
+        time.wake_up_every(CHECK_INTERVAL, id.into(), world);
+
+        ScenarioTracker {
+            id,
+            name: name.clone(),
+            conditions: conditions.clone(),
+            status: ScenarioStatus::Playing,
+            started: None,
+            current_instant: Instant::new(0),
+            funds: amount!(0.0),
+            goals: CVec::new(),
+        }
+    }
+
+    fn value_of(&self, metric: Metric, snapshot: &Snapshot) -> f32 {
+        match metric {
+            Metric::Population => snapshot.population as f32,
+            Metric::AverageCommuteMinutes => {
+                if snapshot.n_commuters > 0 {
+                    snapshot.total_commute_minutes / snapshot.n_commuters as f32
+                } else {
+                    0.0
+                }
+            }
+            Metric::Jobs => snapshot.n_jobs as f32,
+            Metric::Buildings => snapshot.n_buildings as f32,
+            Metric::NetworkLength => snapshot.network_length,
+            Metric::Funds => amount_to_f32(self.funds),
+            Metric::Days => {
+                let started = self.started.unwrap_or(self.current_instant);
+                (self.current_instant.ticks() - started.ticks()) as f32 / TICKS_PER_DAY as f32
+            }
+        }
+    }
+
+    fn evaluate(&mut self, snapshot: &Snapshot, world: &mut World) {
+        self.goals = self
+            .conditions
+            .iter()
+            .map(|condition| GoalProgress {
+                outcome: condition.outcome,
+                description: condition.describe().into(),
+                progress: condition
+                    .comparisons
+                    .iter()
+                    .map(|comparison| {
+                        comparison.progress(self.value_of(comparison.metric, snapshot))
+                    })
+                    .fold(1.0, f32::min),
+            })
+            .collect();
+
+        if self.status == ScenarioStatus::Playing {
+            let met = |outcome| {
+                self.goals
+                    .iter()
+                    .filter(|goal| goal.outcome == outcome)
+                    .map(|goal| goal.progress >= 1.0)
+                    .collect::<Vec<_>>()
+            };
+            if met(Outcome::Lose).into_iter().any(|met| met) {
+                self.status = ScenarioStatus::Lost;
+                warn(
+                    LOG_T,
+                    format!("The city failed the scenario {}", self.name.to_string()),
+                    self.id,
+                    world,
+                );
+            } else if met(Outcome::Win).into_iter().all(|met| met) {
+                self.status = ScenarioStatus::Won;
+                info(
+                    LOG_T,
+                    format!("The city completed the scenario {}", self.name.to_string()),
+                    self.id,
+                    world,
+                );
+            }
+        }
+
+        ScenarioUIID::global_broadcast(world).on_scenario_progress(
+            self.name.clone(),
+            self.status,
+            self.goals.clone(),
+            world,
+        );
+    }
+
+    pub fn get_progress(&mut self, ui: ScenarioUIID, world: &mut World) {
+        ui.on_scenario_progress(self.name.clone(), self.status, self.goals.clone(), world);
+    }
+}
+
+// The balance is asked for first, so it has arrived by the time the snapshot does
+impl Sleeper for ScenarioTracker {
+    fn wake(&mut self, instant: Instant, world: &mut World) {
+        self.current_instant = instant;
+        if self.started.is_none() {
+            self.started = Some(instant);
+        }
+
+        TreasuryID::global_first(world).get_balance(self.id_as(), world);
+        TimelineID::global_first(world).get_snapshot_instants(self.id_as(), world);
+    }
+}
+
+impl BalanceRequester for ScenarioTracker {
+    fn on_balance(&mut self, balance: ResourceAmount, _: &mut World) {
+        self.funds = balance;
+    }
+}
+
+impl TimelineUI for ScenarioTracker {
+    // the newest snapshot might still be collecting contributions
+    fn on_snapshot_instants(&mut self, instants: &CVec<Instant>, world: &mut World) {
+        if instants.len() >= 2 {
+            TimelineID::global_first(world).get_snapshot(
+                instants[instants.len() - 2],
+                self.id_as(),
+                world,
+            );
+        }
+    }
+
+    fn on_snapshot(&mut self, snapshot: &Snapshot, world: &mut World) {
+        self.evaluate(snapshot, world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<ScenarioTracker>();
+    auto_setup(system);
+}
+
+// Only for new cities, after everything else was spawned
+pub fn start(world: &mut World, scenario: &Scenario, time: TimeID) {
+    if let Some(funds) = scenario.funds {
+        TreasuryID::global_first(world).set_balance(funds, world);
+    }
+    let conditions = scenario
+        .conditions
+        .iter()
+        .map(|(outcome, comparisons)| Condition {
+            outcome: *outcome,
+            comparisons: comparisons.clone().into(),
+        })
+        .collect();
+    ScenarioTrackerID::spawn(scenario.name.clone().into(), conditions, time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
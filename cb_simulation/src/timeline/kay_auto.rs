@@ -0,0 +1,304 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct SnapshotContributorID {
+    _raw_id: RawID
+}
+
+impl Copy for SnapshotContributorID {}
+impl Clone for SnapshotContributorID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for SnapshotContributorID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "SnapshotContributorID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for SnapshotContributorID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for SnapshotContributorID {
+    fn eq(&self, other: &SnapshotContributorID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for SnapshotContributorID {}
+
+pub struct SnapshotContributorRepresentative;
+
+impl ActorOrActorTrait for SnapshotContributorRepresentative {
+    type ID = SnapshotContributorID;
+}
+
+impl TypedID for SnapshotContributorID {
+    type Target = SnapshotContributorRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        SnapshotContributorID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + SnapshotContributor> TraitIDFrom<Act> for SnapshotContributorID {}
+
+impl SnapshotContributorID {
+    pub fn contribute_to_snapshot(self, timeline: TimelineID, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_SnapshotContributor_contribute_to_snapshot(timeline, instant));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<SnapshotContributorRepresentative>();
+        system.register_trait_message::<MSG_SnapshotContributor_contribute_to_snapshot>();
+    }
+
+    pub fn register_implementor<Act: Actor + SnapshotContributor>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, SnapshotContributorRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_SnapshotContributor_contribute_to_snapshot(timeline, instant), instance, world| {
+                instance.contribute_to_snapshot(timeline, instant, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SnapshotContributor_contribute_to_snapshot(pub TimelineID, pub Instant);
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TimelineUIID {
+    _raw_id: RawID
+}
+
+impl Copy for TimelineUIID {}
+impl Clone for TimelineUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TimelineUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TimelineUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TimelineUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TimelineUIID {
+    fn eq(&self, other: &TimelineUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TimelineUIID {}
+
+pub struct TimelineUIRepresentative;
+
+impl ActorOrActorTrait for TimelineUIRepresentative {
+    type ID = TimelineUIID;
+}
+
+impl TypedID for TimelineUIID {
+    type Target = TimelineUIRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        TimelineUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + TimelineUI> TraitIDFrom<Act> for TimelineUIID {}
+
+impl TimelineUIID {
+    pub fn on_snapshot_instants(self, instants: CVec < Instant >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TimelineUI_on_snapshot_instants(instants));
+    }
+    
+    pub fn on_snapshot(self, snapshot: Snapshot, world: &mut World) {
+        world.send(self.as_raw(), MSG_TimelineUI_on_snapshot(snapshot));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<TimelineUIRepresentative>();
+        system.register_trait_message::<MSG_TimelineUI_on_snapshot_instants>();
+        system.register_trait_message::<MSG_TimelineUI_on_snapshot>();
+    }
+
+    pub fn register_implementor<Act: Actor + TimelineUI>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, TimelineUIRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_TimelineUI_on_snapshot_instants(ref instants), instance, world| {
+                instance.on_snapshot_instants(instants, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TimelineUI_on_snapshot(ref snapshot), instance, world| {
+                instance.on_snapshot(snapshot, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TimelineUI_on_snapshot_instants(pub CVec < Instant >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TimelineUI_on_snapshot(pub Snapshot);
+
+impl Actor for Timeline {
+    type ID = TimelineID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TimelineID {
+    _raw_id: RawID
+}
+
+impl Copy for TimelineID {}
+impl Clone for TimelineID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TimelineID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TimelineID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TimelineID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TimelineID {
+    fn eq(&self, other: &TimelineID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TimelineID {}
+
+impl TypedID for TimelineID {
+    type Target = Timeline;
+
+    fn from_raw(id: RawID) -> Self {
+        TimelineID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TimelineID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = TimelineID::from_raw(world.allocate_instance_id::<Timeline>());
+        let swarm = world.local_broadcast::<Timeline>();
+        world.send(swarm, MSG_Timeline_spawn(id, time));
+        id
+    }
+    
+    pub fn add_household(self, instant: Instant, position: P2, population: u32, n_jobs: u32, commute_minutes: Option < f32 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Timeline_add_household(instant, position, population, n_jobs, commute_minutes));
+    }
+    
+    pub fn add_building(self, instant: Instant, position: P2, area: N, world: &mut World) {
+        world.send(self.as_raw(), MSG_Timeline_add_building(instant, position, area));
+    }
+    
+    pub fn add_network_segment(self, instant: Instant, start: P2, end: P2, length: N, world: &mut World) {
+        world.send(self.as_raw(), MSG_Timeline_add_network_segment(instant, start, end, length));
+    }
+    
+    pub fn get_snapshot_instants(self, ui: TimelineUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Timeline_get_snapshot_instants(ui));
+    }
+    
+    pub fn get_snapshot(self, instant: Instant, ui: TimelineUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Timeline_get_snapshot(instant, ui));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Timeline_spawn(pub TimelineID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Timeline_add_household(pub Instant, pub P2, pub u32, pub u32, pub Option < f32 >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Timeline_add_building(pub Instant, pub P2, pub N);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Timeline_add_network_segment(pub Instant, pub P2, pub P2, pub N);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Timeline_get_snapshot_instants(pub TimelineUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Timeline_get_snapshot(pub Instant, pub TimelineUIID);
+
+impl Into<SleeperID> for TimelineID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<DistrictListenerID> for TimelineID {
+    fn into(self) -> DistrictListenerID {
+        DistrictListenerID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<LifecycleListenerID> for TimelineID {
+    fn into(self) -> LifecycleListenerID {
+        LifecycleListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    SnapshotContributorID::register_trait(system);
+    TimelineUIID::register_trait(system);
+    SleeperID::register_implementor::<Timeline>(system);
+    DistrictListenerID::register_implementor::<Timeline>(system);
+    LifecycleListenerID::register_implementor::<Timeline>(system);
+    system.add_spawner::<Timeline, _, _>(
+        |&MSG_Timeline_spawn(id, time), world| {
+            Timeline::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<Timeline, _, _>(
+        |&MSG_Timeline_add_household(instant, position, population, n_jobs, commute_minutes), instance, world| {
+            instance.add_household(instant, position, population, n_jobs, commute_minutes, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Timeline, _, _>(
+        |&MSG_Timeline_add_building(instant, position, area), instance, world| {
+            instance.add_building(instant, position, area, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Timeline, _, _>(
+        |&MSG_Timeline_add_network_segment(instant, start, end, length), instance, world| {
+            instance.add_network_segment(instant, start, end, length, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Timeline, _, _>(
+        |&MSG_Timeline_get_snapshot_instants(ui), instance, world| {
+            instance.get_snapshot_instants(ui, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Timeline, _, _>(
+        |&MSG_Timeline_get_snapshot(instant, ui), instance, world| {
+            instance.get_snapshot(instant, ui, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
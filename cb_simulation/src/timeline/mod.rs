@@ -0,0 +1,308 @@
+use kay::{ActorSystem, World, TypedID};
+use compact::CVec;
+use descartes::{N, P2};
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Duration};
+use economy::households::{HouseholdID, MemberIdx};
+use economy::households::aging::{LifecycleListener, LifecycleListenerID, LifeStage};
+use economy::unmet_demand::District;
+use land_use::districts::{DistrictArea, DistrictListener, DistrictListenerID, DistrictRegistryID,
+district_at};
+
+const SNAPSHOT_INTERVAL: Duration = Duration(6 * 60 * 60);
+// once this many snapshots are stored, every other one is dropped,
+// so the timeline gets coarser the longer the city exists
+const MAX_SNAPSHOTS: usize = 512;
+
+#[derive(Copy, Clone, Default, Serialize, Deserialize)]
+pub struct DistrictStats {
+    pub population: u32,
+    pub n_jobs: u32,
+    pub n_commuters: u32,
+    pub total_commute_minutes: f32,
+    pub n_buildings: u32,
+    pub built_area: N,
+    pub network_length: N,
+}
+
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub instant: Instant,
+    pub population: u32,
+    pub births: u32,
+    pub deaths: u32,
+    pub n_jobs: u32,
+    pub n_commuters: u32,
+    pub total_commute_minutes: f32,
+    pub n_buildings: u32,
+    pub built_area: N,
+    pub network_length: N,
+    pub building_positions: CVec<P2>,
+    pub network_segments: CVec<(P2, P2)>,
+    // the same statistics, broken down by district and sorted by it
+    pub districts: CVec<(District, DistrictStats)>,
+    // and by the districts drawn by the player, sorted by their number
+    pub drawn_districts: CVec<(u16, DistrictStats)>,
+}
+
+impl Snapshot {
+    fn new(instant: Instant) -> Snapshot {
+        Snapshot {
+            instant,
+            population: 0,
+            births: 0,
+            deaths: 0,
+            n_jobs: 0,
+            n_commuters: 0,
+            total_commute_minutes: 0.0,
+            n_buildings: 0,
+            built_area: 0.0,
+            network_length: 0.0,
+            building_positions: CVec::new(),
+            network_segments: CVec::new(),
+            districts: CVec::new(),
+            drawn_districts: CVec::new(),
+        }
+    }
+
+    fn district_mut(&mut self, district: District) -> &mut DistrictStats {
+        let idx = match self
+            .districts
+            .binary_search_by_key(&district, |&(other, _)| other)
+        {
+            Ok(idx) => idx,
+            Err(idx) => {
+                self.districts
+                    .insert(idx, (district, DistrictStats::default()));
+                idx
+            }
+        };
+        &mut self.districts[idx].1
+    }
+
+    fn drawn_district_mut(&mut self, district: u16) -> &mut DistrictStats {
+        let idx = match self
+            .drawn_districts
+            .binary_search_by_key(&district, |&(other, _)| other)
+        {
+            Ok(idx) => idx,
+            Err(idx) => {
+                self.drawn_districts
+                    .insert(idx, (district, DistrictStats::default()));
+                idx
+            }
+        };
+        &mut self.drawn_districts[idx].1
+    }
+
+    // to the grid cell containing the position, and to the drawn district if there is one
+    fn add_to_districts<F: Fn(&mut DistrictStats)>(
+        &mut self,
+        position: P2,
+        drawn_districts: &CVec<DistrictArea>,
+        add: F,
+    ) {
+        add(self.district_mut(District::containing(position)));
+        if let Some(drawn) = district_at(drawn_districts, position) {
+            add(self.drawn_district_mut(drawn.id));
+        }
+    }
+}
+
+// contributions arrive asynchronously and are matched by instant,
+// since thinning out might have shifted the snapshot indices meanwhile
+fn snapshot_mut(snapshots: &mut CVec<Snapshot>, instant: Instant) -> Option<&mut Snapshot> {
+    snapshots
+        .iter_mut()
+        .rev()
+        .find(|snapshot| snapshot.instant == instant)
+}
+
+pub trait SnapshotContributor {
+    fn contribute_to_snapshot(&mut self, timeline: TimelineID, instant: Instant, world: &mut World);
+}
+
+pub trait TimelineUI {
+    fn on_snapshot_instants(&mut self, instants: &CVec<Instant>, world: &mut World);
+    fn on_snapshot(&mut self, snapshot: &Snapshot, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct Timeline {
+    id: TimelineID,
+    time: TimeID,
+    snapshots: CVec<Snapshot>,
+    births_since_snapshot: u32,
+    deaths_since_snapshot: u32,
+    // drawn by the player, as last announced
+    district_areas: CVec<DistrictArea>,
+}
+
+impl Timeline {
+    pub fn spawn(id: TimelineID, time: TimeID, world: &mut World) -> Timeline {
+        time.wake_up_in(SNAPSHOT_INTERVAL.into(), id.into(), world);
+        DistrictRegistryID::global_first(world).get_districts(id.into(), world);
+
+        Timeline {
+            id,
+            time,
+            snapshots: CVec::new(),
+            births_since_snapshot: 0,
+            deaths_since_snapshot: 0,
+            district_areas: CVec::new(),
+        }
+    }
+
+    pub fn add_household(
+        &mut self,
+        instant: Instant,
+        position: P2,
+        population: u32,
+        n_jobs: u32,
+        commute_minutes: Option<f32>,
+        _: &mut World,
+    ) {
+        if let Some(snapshot) = snapshot_mut(&mut self.snapshots, instant) {
+            snapshot.population += population;
+            snapshot.n_jobs += n_jobs;
+            if let Some(minutes) = commute_minutes {
+                snapshot.n_commuters += 1;
+                snapshot.total_commute_minutes += minutes;
+            }
+
+            snapshot.add_to_districts(position, &self.district_areas, |stats| {
+                stats.population += population;
+                stats.n_jobs += n_jobs;
+                if let Some(minutes) = commute_minutes {
+                    stats.n_commuters += 1;
+                    stats.total_commute_minutes += minutes;
+                }
+            });
+        }
+    }
+
+    pub fn add_building(&mut self, instant: Instant, position: P2, area: N, _: &mut World) {
+        if let Some(snapshot) = snapshot_mut(&mut self.snapshots, instant) {
+            snapshot.n_buildings += 1;
+            snapshot.built_area += area;
+            snapshot.building_positions.push(position);
+
+            snapshot.add_to_districts(position, &self.district_areas, |stats| {
+                stats.n_buildings += 1;
+                stats.built_area += area;
+            });
+        }
+    }
+
+    pub fn add_network_segment(
+        &mut self,
+        instant: Instant,
+        start: P2,
+        end: P2,
+        length: N,
+        _: &mut World,
+    ) {
+        if let Some(snapshot) = snapshot_mut(&mut self.snapshots, instant) {
+            snapshot.network_length += length;
+            snapshot.network_segments.push((start, end));
+
+            let middle = P2::from_coordinates((start.coords + end.coords) / 2.0);
+            snapshot.add_to_districts(middle, &self.district_areas, |stats| {
+                stats.network_length += length;
+            });
+        }
+    }
+
+    pub fn get_snapshot_instants(&mut self, ui: TimelineUIID, world: &mut World) {
+        ui.on_snapshot_instants(
+            self.snapshots
+                .iter()
+                .map(|snapshot| snapshot.instant)
+                .collect(),
+            world,
+        );
+    }
+
+    pub fn get_snapshot(&mut self, instant: Instant, ui: TimelineUIID, world: &mut World) {
+        if let Some(snapshot) = self
+            .snapshots
+            .iter()
+            .find(|snapshot| snapshot.instant == instant)
+        {
+            ui.on_snapshot(snapshot.clone(), world);
+        }
+    }
+}
+
+impl Sleeper for Timeline {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        if self.snapshots.len() >= MAX_SNAPSHOTS {
+            self.snapshots = self
+                .snapshots
+                .iter()
+                .enumerate()
+                .filter_map(|(i, snapshot)| {
+                    if i % 2 == 0 {
+                        Some(snapshot.clone())
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+        }
+
+        let mut snapshot = Snapshot::new(current_instant);
+        snapshot.births = self.births_since_snapshot;
+        snapshot.deaths = self.deaths_since_snapshot;
+        self.births_since_snapshot = 0;
+        self.deaths_since_snapshot = 0;
+        self.snapshots.push(snapshot);
+        SnapshotContributorID::global_broadcast(world).contribute_to_snapshot(
+            self.id,
+            current_instant,
+            world,
+        );
+
+        self.time
+            .wake_up_in(SNAPSHOT_INTERVAL.into(), self.id.into(), world);
+    }
+}
+
+impl DistrictListener for Timeline {
+    fn on_districts_changed(&mut self, districts: &CVec<DistrictArea>, _: &mut World) {
+        self.district_areas = districts.clone();
+    }
+}
+
+impl LifecycleListener for Timeline {
+    fn on_member_born(&mut self, _: HouseholdID, _: MemberIdx, _: &mut World) {
+        self.births_since_snapshot += 1;
+    }
+
+    fn on_member_stage_changed(
+        &mut self,
+        _: HouseholdID,
+        _: MemberIdx,
+        _: LifeStage,
+        _: &mut World,
+    ) {
+    }
+
+    fn on_member_died(&mut self, _: HouseholdID, _: MemberIdx, _: u32, _: &mut World) {
+        self.deaths_since_snapshot += 1;
+    }
+
+    fn on_household_dissolved(&mut self, _: HouseholdID, _: &mut World) {}
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Timeline>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    TimelineID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -10,10 +10,10 @@ use super::*;
 
 
 impl LaneID {
-    pub fn spawn_and_connect(path: LinePath, on_intersection: bool, timings: CVec < bool >, report_to: CBConstructionID, world: &mut World) -> Self {
+    pub fn spawn_and_connect(path: LinePath, on_intersection: bool, speed_limit: N, elevation: Elevation, yield_at: Option < N >, report_to: CBConstructionID, world: &mut World) -> Self {
         let id = LaneID::from_raw(world.allocate_instance_id::<Lane>());
         let swarm = world.local_broadcast::<Lane>();
-        world.send(swarm, MSG_Lane_spawn_and_connect(id, path, on_intersection, timings, report_to));
+        world.send(swarm, MSG_Lane_spawn_and_connect(id, path, on_intersection, speed_limit, elevation, yield_at, report_to));
         id
     }
     
@@ -21,8 +21,8 @@ impl LaneID {
         world.send(self.as_raw(), MSG_Lane_start_connecting_overlaps(lanes));
     }
     
-    pub fn connect(self, other_id: LaneID, other_start: P2, other_end: P2, other_length: N, reply_needed: bool, world: &mut World) {
-        world.send(self.as_raw(), MSG_Lane_connect(other_id, other_start, other_end, other_length, reply_needed));
+    pub fn connect(self, other_id: LaneID, other_start: P2, other_end: P2, other_length: N, other_elevation: Elevation, reply_needed: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_connect(other_id, other_start, other_end, other_length, other_elevation, reply_needed));
     }
     
     pub fn connect_overlaps(self, other_id: LaneID, other_path: LinePath, reply_needed: bool, world: &mut World) {
@@ -59,11 +59,11 @@ impl LaneID {
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Lane_spawn_and_connect(pub LaneID, pub LinePath, pub bool, pub CVec < bool >, pub CBConstructionID);
+struct MSG_Lane_spawn_and_connect(pub LaneID, pub LinePath, pub bool, pub N, pub Elevation, pub Option < N >, pub CBConstructionID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Lane_start_connecting_overlaps(pub CVec < LaneID >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Lane_connect(pub LaneID, pub P2, pub P2, pub N, pub bool);
+struct MSG_Lane_connect(pub LaneID, pub P2, pub P2, pub N, pub Elevation, pub bool);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Lane_connect_overlaps(pub LaneID, pub LinePath, pub bool);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
@@ -89,15 +89,15 @@ impl Into<ConstructableID<CBPrototypeKind>> for LaneID {
 
 
 impl SwitchLaneID {
-    pub fn spawn_and_connect(path: LinePath, report_to: CBConstructionID, world: &mut World) -> Self {
+    pub fn spawn_and_connect(path: LinePath, elevation: Elevation, report_to: CBConstructionID, world: &mut World) -> Self {
         let id = SwitchLaneID::from_raw(world.allocate_instance_id::<SwitchLane>());
         let swarm = world.local_broadcast::<SwitchLane>();
-        world.send(swarm, MSG_SwitchLane_spawn_and_connect(id, path, report_to));
+        world.send(swarm, MSG_SwitchLane_spawn_and_connect(id, path, elevation, report_to));
         id
     }
     
-    pub fn connect_switch_to_normal(self, other_id: LaneID, other_path: LinePath, world: &mut World) {
-        world.send(self.as_raw(), MSG_SwitchLane_connect_switch_to_normal(other_id, other_path));
+    pub fn connect_switch_to_normal(self, other_id: LaneID, other_path: LinePath, other_elevation: Elevation, world: &mut World) {
+        world.send(self.as_raw(), MSG_SwitchLane_connect_switch_to_normal(other_id, other_path, other_elevation));
     }
     
     pub fn disconnect(self, other: LaneID, world: &mut World) {
@@ -114,9 +114,9 @@ impl SwitchLaneID {
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_SwitchLane_spawn_and_connect(pub SwitchLaneID, pub LinePath, pub CBConstructionID);
+struct MSG_SwitchLane_spawn_and_connect(pub SwitchLaneID, pub LinePath, pub Elevation, pub CBConstructionID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_SwitchLane_connect_switch_to_normal(pub LaneID, pub LinePath);
+struct MSG_SwitchLane_connect_switch_to_normal(pub LaneID, pub LinePath, pub Elevation);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_SwitchLane_disconnect(pub LaneID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
@@ -136,8 +136,8 @@ pub fn auto_setup(system: &mut ActorSystem) {
     
     ConstructableID::<CBPrototypeKind>::register_implementor::<Lane>(system);
     system.add_spawner::<Lane, _, _>(
-        |&MSG_Lane_spawn_and_connect(id, ref path, on_intersection, ref timings, report_to), world| {
-            Lane::spawn_and_connect(id, path, on_intersection, timings, report_to, world)
+        |&MSG_Lane_spawn_and_connect(id, ref path, on_intersection, speed_limit, elevation, yield_at, report_to), world| {
+            Lane::spawn_and_connect(id, path, on_intersection, speed_limit, elevation, yield_at, report_to, world)
         }, false
     );
     
@@ -148,8 +148,8 @@ pub fn auto_setup(system: &mut ActorSystem) {
     );
     
     system.add_handler::<Lane, _, _>(
-        |&MSG_Lane_connect(other_id, other_start, other_end, other_length, reply_needed), instance, world| {
-            instance.connect(other_id, other_start, other_end, other_length, reply_needed, world); Fate::Live
+        |&MSG_Lane_connect(other_id, other_start, other_end, other_length, other_elevation, reply_needed), instance, world| {
+            instance.connect(other_id, other_start, other_end, other_length, other_elevation, reply_needed, world); Fate::Live
         }, false
     );
     
@@ -202,14 +202,14 @@ pub fn auto_setup(system: &mut ActorSystem) {
     );
     ConstructableID::<CBPrototypeKind>::register_implementor::<SwitchLane>(system);
     system.add_spawner::<SwitchLane, _, _>(
-        |&MSG_SwitchLane_spawn_and_connect(id, ref path, report_to), world| {
-            SwitchLane::spawn_and_connect(id, path, report_to, world)
+        |&MSG_SwitchLane_spawn_and_connect(id, ref path, elevation, report_to), world| {
+            SwitchLane::spawn_and_connect(id, path, elevation, report_to, world)
         }, false
     );
     
     system.add_handler::<SwitchLane, _, _>(
-        |&MSG_SwitchLane_connect_switch_to_normal(other_id, ref other_path), instance, world| {
-            instance.connect_switch_to_normal(other_id, other_path, world); Fate::Live
+        |&MSG_SwitchLane_connect_switch_to_normal(other_id, ref other_path, other_elevation), instance, world| {
+            instance.connect_switch_to_normal(other_id, other_path, other_elevation, world); Fate::Live
         }, false
     );
     
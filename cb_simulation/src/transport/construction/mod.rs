@@ -1,6 +1,6 @@
 use compact::CVec;
 use kay::{ActorSystem, World, Fate, Actor, TypedID};
-use descartes::{N, P2, Band, LinePath, ClosedLinePath, Segment,
+use descartes::{N, P2, V2, Band, LinePath, ClosedLinePath, Segment,
 RoughEq, Intersect, WithUniqueOrthogonal};
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
@@ -8,16 +8,27 @@ use ordered_float::OrderedFloat;
 use super::lane::{Lane, LaneID, SwitchLane, SwitchLaneID};
 use super::lane::connectivity::Interaction;
 use super::microtraffic::LaneLikeID;
+use super::signals::{TrafficSignalID, phases_from_timings};
+use super::pedestrians::FootwayID;
+use super::cycling::BikewayID;
 
 use cb_planning::Prototype;
 use cb_planning::construction::{Constructable, ConstructableID};
 use planning::{CBConstructionID, CBPrototypeKind};
 use super::transport_planning::{RoadPrototype, LanePrototype, SwitchLanePrototype,
-IntersectionPrototype};
+IntersectionPrototype, SidewalkPrototype, CrosswalkPrototype, BikeLanePrototype, Elevation};
 
 use cb_util::log::debug;
+use economy::resources::{Resource, ResourceAmount};
+use economy::households::household_kinds::neighboring_town_trade::trade_price;
+use economy::policies::TreasuryID;
 const LOG_T: &str = "Transport Construction";
 
+// building materials in every meter of lane, and the share of them that can
+// be reused once it is torn down, like for buildings
+const MATERIALS_PER_METER: f32 = 3.0;
+const SALVAGED_SHARE: f32 = 0.25;
+
 use dimensions::{LANE_CONNECTION_TOLERANCE, MAX_SWITCHING_LANE_DISTANCE,
 MIN_SWITCHING_LANE_LENGTH};
 
@@ -28,38 +39,65 @@ impl RoadPrototype {
         world: &mut World,
     ) -> CVec<ConstructableID<CBPrototypeKind>> {
         match *self {
-            RoadPrototype::Lane(LanePrototype(ref path, _)) => {
+            RoadPrototype::Lane(LanePrototype(ref path, _, class, elevation, yield_at)) => {
+                vec![LaneID::spawn_and_connect(
+                    path.clone(),
+                    false,
+                    class.speed_limit(),
+                    elevation,
+                    yield_at,
+                    report_to,
+                    world,
+                )
+                .into()]
+                .into()
+            }
+            RoadPrototype::SwitchLane(SwitchLanePrototype(ref path, elevation)) => {
                 vec![
-                    LaneID::spawn_and_connect(path.clone(), false, CVec::new(), report_to, world)
+                    SwitchLaneID::spawn_and_connect(path.clone(), elevation, report_to, world)
                         .into(),
                 ]
                 .into()
             }
-            RoadPrototype::SwitchLane(SwitchLanePrototype(ref path)) => {
-                vec![SwitchLaneID::spawn_and_connect(path.clone(), report_to, world).into()].into()
-            }
             RoadPrototype::Intersection(IntersectionPrototype {
                 ref connecting_lanes,
                 ..
             }) => {
-                let ids = connecting_lanes
+                let lane_prototypes = connecting_lanes
                     .values()
-                    .flat_map(|group| {
-                        group
-                            .iter()
-                            .map(|&LanePrototype(ref path, ref timings)| {
-                                LaneID::spawn_and_connect(
-                                    path.clone(),
-                                    true,
-                                    timings.clone(),
-                                    report_to,
-                                    world,
-                                )
-                            })
-                            .collect::<Vec<_>>()
+                    .flat_map(|group| group.iter())
+                    .collect::<Vec<_>>();
+                let ids = lane_prototypes
+                    .iter()
+                    .map(|&&LanePrototype(ref path, _, class, elevation, yield_at)| {
+                        LaneID::spawn_and_connect(
+                            path.clone(),
+                            true,
+                            class.speed_limit(),
+                            elevation,
+                            yield_at,
+                            report_to,
+                            world,
+                        )
                     })
                     .collect::<Vec<_>>();
 
+                let lane_timings = lane_prototypes
+                    .iter()
+                    .map(|&&LanePrototype(_, ref timings, ..)| timings)
+                    .collect::<Vec<_>>();
+                if let Some(phases) = phases_from_timings(&lane_timings) {
+                    let center = P2::from_coordinates(
+                        lane_prototypes
+                            .iter()
+                            .fold(V2::new(0.0, 0.0), |sum, &&LanePrototype(ref path, ..)| {
+                                sum + path.start().coords
+                            })
+                            / lane_prototypes.len() as N,
+                    );
+                    TrafficSignalID::spawn(center, ids.clone().into(), phases, world);
+                }
+
                 for id in &ids {
                     id.start_connecting_overlaps(
                         ids.iter().filter(|&other| other != id).cloned().collect(),
@@ -69,6 +107,17 @@ impl RoadPrototype {
 
                 ids.into_iter().map(std::convert::Into::into).collect()
             }
+            RoadPrototype::Sidewalk(SidewalkPrototype(ref path)) => {
+                vec![FootwayID::spawn_and_connect(path.clone(), false, report_to, world).into()]
+                    .into()
+            }
+            RoadPrototype::Crosswalk(CrosswalkPrototype(ref path)) => {
+                vec![FootwayID::spawn_and_connect(path.clone(), true, report_to, world).into()]
+                    .into()
+            }
+            RoadPrototype::BikeLane(BikeLanePrototype(ref path)) => {
+                vec![BikewayID::spawn_and_connect(path.clone(), report_to, world).into()].into()
+            }
             RoadPrototype::PavedArea(_) => CVec::new(),
         }
     }
@@ -109,16 +158,18 @@ pub struct ConstructionInfo {
     pub length: f32,
     pub path: LinePath,
     pub progress: f32,
+    pub elevation: Elevation,
     unbuilding_for: Option<CBConstructionID>,
     disconnects_remaining: u8,
 }
 
 impl ConstructionInfo {
-    pub fn from_path(path: LinePath) -> Self {
+    pub fn from_path(path: LinePath, elevation: Elevation) -> Self {
         ConstructionInfo {
             length: path.length(),
             path,
             progress: 0.0,
+            elevation,
             unbuilding_for: None,
             disconnects_remaining: 0,
         }
@@ -135,7 +186,9 @@ impl Lane {
         id: LaneID,
         path: &LinePath,
         on_intersection: bool,
-        timings: &CVec<bool>,
+        speed_limit: N,
+        elevation: Elevation,
+        yield_at: Option<N>,
         report_to: CBConstructionID,
         world: &mut World,
     ) -> Lane {
@@ -144,14 +197,28 @@ impl Lane {
             path.start(),
             path.end(),
             path.length(),
+            elevation,
             true,
             world,
         );
         if !on_intersection {
-            SwitchLaneID::global_broadcast(world).connect_switch_to_normal(id, path.clone(), world);
+            SwitchLaneID::global_broadcast(world).connect_switch_to_normal(
+                id,
+                path.clone(),
+                elevation,
+                world,
+            );
         }
         report_to.action_done(id.into(), world);
-        Lane::spawn(id, path, on_intersection, timings, world)
+        Lane::spawn(
+            id,
+            path,
+            on_intersection,
+            speed_limit,
+            elevation,
+            yield_at,
+            world,
+        )
     }
 
     pub fn start_connecting_overlaps(&mut self, lanes: &CVec<LaneID>, world: &mut World) {
@@ -166,6 +233,7 @@ impl Lane {
         other_start: P2,
         other_end: P2,
         other_length: N,
+        other_elevation: Elevation,
         reply_needed: bool,
         world: &mut World,
     ) {
@@ -174,8 +242,11 @@ impl Lane {
         };
 
         let mut connected = false;
+        let elevation = self.construction.elevation;
 
-        if other_start.rough_eq_by(self.construction.path.end(), LANE_CONNECTION_TOLERANCE) {
+        if other_start.rough_eq_by(self.construction.path.end(), LANE_CONNECTION_TOLERANCE)
+            && elevation.meets_at(&other_elevation, other_start)
+        {
             connected = true;
 
             let already_a_partner =
@@ -196,7 +267,9 @@ impl Lane {
             ::transport::pathfinding::Link::on_connect(self);
         }
 
-        if other_end.rough_eq_by(self.construction.path.start(), LANE_CONNECTION_TOLERANCE) {
+        if other_end.rough_eq_by(self.construction.path.start(), LANE_CONNECTION_TOLERANCE)
+            && elevation.meets_at(&other_elevation, other_end)
+        {
             connected = true;
 
             let already_a_partner =
@@ -224,6 +297,7 @@ impl Lane {
                 path.start(),
                 path.end(),
                 path.length(),
+                elevation,
                 false,
                 world,
             );
@@ -307,7 +381,12 @@ impl Lane {
     }
 
     pub fn connect_to_switch(&mut self, other_id: SwitchLaneID, world: &mut World) {
-        other_id.connect_switch_to_normal(self.id, self.construction.path.clone(), world);
+        other_id.connect_switch_to_normal(
+            self.id,
+            self.construction.path.clone(),
+            self.construction.elevation,
+            world,
+        );
     }
 
     pub fn add_switch_lane_interaction(&mut self, interaction: Interaction, _: &mut World) {
@@ -333,19 +412,8 @@ impl Lane {
 
         self.microtraffic.obstacles.drain();
 
-        let self_as_rough_location = self.id_as();
-
-        for car in self.microtraffic.cars.drain() {
-            car.trip.finish(
-                TripResult {
-                    location_now: Some(self_as_rough_location),
-                    fate: TripFate::HopDisconnected,
-                },
-                world,
-            );
-        }
-
         ::transport::pathfinding::Link::on_disconnect(self);
+        ::transport::microtraffic::reroute_stale_cars(self, TripFate::HopDisconnected, world);
         other_id.on_confirm_disconnect(world);
     }
 
@@ -356,19 +424,8 @@ impl Lane {
 
         self.microtraffic.obstacles.drain();
 
-        let self_as_rough_location = self.id_as();
-
-        for car in self.microtraffic.cars.drain() {
-            car.trip.finish(
-                TripResult {
-                    location_now: Some(self_as_rough_location),
-                    fate: TripFate::HopDisconnected,
-                },
-                world,
-            );
-        }
-
         ::transport::pathfinding::Link::on_disconnect(self);
+        ::transport::microtraffic::reroute_stale_cars(self, TripFate::HopDisconnected, world);
         other_id.on_confirm_disconnect(world);
     }
 
@@ -397,6 +454,10 @@ impl Lane {
             disconnects_remaining += 1;
         }
 
+        if let Some(signal) = self.microtraffic.signal {
+            signal.lane_unbuilt(self.id, world);
+        }
+
         super::ui::on_unbuild(self, world);
         unsafe {
             MEMOIZED_BANDS_OUTLINES
@@ -431,9 +492,25 @@ impl Lane {
 }
 
 impl Lane {
+    // What the city gets for the materials of a removed lane, at the price
+    // neighboring towns sell them for. Lanes within intersections are
+    // paved over by the lanes leading into them
+    fn salvage_value(&self) -> ResourceAmount {
+        if self.connectivity.on_intersection {
+            return amount!(0.0);
+        }
+        let price = trade_price(Resource::BuildingMaterials).unwrap_or(amount!(0.0));
+        SALVAGED_SHARE * self.construction.length * MATERIALS_PER_METER * price
+    }
+
     fn finalize(&self, report_to: CBConstructionID, world: &mut World) {
         report_to.action_done(self.id_as(), world);
 
+        let salvage_value = self.salvage_value();
+        if salvage_value > 0.0 {
+            TreasuryID::global_first(world).deposit(salvage_value, world);
+        }
+
         for car in &self.microtraffic.cars {
             car.trip.finish(
                 TripResult {
@@ -445,10 +522,20 @@ impl Lane {
         }
 
         ::transport::pathfinding::road_pathfinding::on_unbuild(self, world);
+
+        if !self.connectivity.on_intersection {
+            GridLayersID::global_first(world).remove_lane(
+                self.id,
+                self.construction.path.along(self.construction.length / 2.0),
+                self.speed_limit,
+                world,
+            );
+        }
     }
 }
 
 use land_use::buildings::{BuildingID};
+use land_use::grid_layers::GridLayersID;
 use dimensions::LANE_DISTANCE;
 use transport::pathfinding::PreciseLocation;
 
@@ -495,12 +582,13 @@ impl SwitchLane {
     pub fn spawn_and_connect(
         id: SwitchLaneID,
         path: &LinePath,
+        elevation: Elevation,
         report_to: CBConstructionID,
         world: &mut World,
     ) -> SwitchLane {
         LaneID::global_broadcast(world).connect_to_switch(id, world);
 
-        let lane = SwitchLane::spawn(id, path, world);
+        let lane = SwitchLane::spawn(id, path, elevation, world);
         super::ui::on_build_switch(&lane, world);
 
         report_to.action_done(id.into(), world);
@@ -512,8 +600,17 @@ impl SwitchLane {
         &mut self,
         other_id: LaneID,
         other_path: &LinePath,
+        other_elevation: Elevation,
         world: &mut World,
     ) {
+        // a lane right above or below is not one to switch to
+        let path = &self.construction.path;
+        if !self.construction.elevation.meets_at(&other_elevation, path.start())
+            || !self.construction.elevation.meets_at(&other_elevation, path.end())
+        {
+            return;
+        }
+
         let projections = (
             other_path.project_with_max_distance(
                 self.construction.path.start(),
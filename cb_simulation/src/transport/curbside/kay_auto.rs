@@ -0,0 +1,32 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+
+
+impl LaneID {
+    pub fn obstruct(self, obstruction: Obstruction, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_obstruct(obstruction));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_obstruct(pub Obstruction);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_obstruct(obstruction), instance, world| {
+            instance.obstruct(obstruction, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
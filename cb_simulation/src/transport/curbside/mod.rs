@@ -0,0 +1,105 @@
+// Buses stop right in the lane of their stop to let passengers on and off,
+// and cars pull over at the curb of the lane they were going to when they
+// park at the end of their trip. Either way, part of the lane is obstructed
+// for a while: cars behind a dwelling bus wait until it leaves, cars behind
+// someone parking squeeze past at a crawl. Emergency vehicles drive around both
+use kay::{ActorSystem, World, TypedID};
+use std::f32::INFINITY;
+use ordered_float::OrderedFloat;
+use cb_time::units::{Instant, Duration};
+use super::lane::{Lane, LaneID};
+use super::microtraffic::LaneCar;
+use super::pathfinding::PreciseLocation;
+
+// a bus and the space it needs to pull in and out
+const BUS_STOP_LENGTH: f32 = 15.0;
+const PARKING_LENGTH: f32 = 8.0;
+const PARKING_MANEUVER: Duration = Duration(20);
+// in m/s
+const SQUEEZING_VELOCITY: f32 = 2.0;
+const SQUEEZING_DECELERATION: f32 = 2.0;
+// closer than this to an obstruction, cars are squeezing past it
+pub const SQUEEZING_DISTANCE: f32 = 12.0;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ObstructionKind {
+    BusDwelling,
+    Parking,
+}
+
+#[derive(Copy, Clone)]
+pub struct Obstruction {
+    pub kind: ObstructionKind,
+    // how far along the lane it starts
+    pub at: f32,
+    pub length: f32,
+    pub until: Instant,
+}
+
+impl Obstruction {
+    // how fast cars get past it, if at all
+    pub fn passing_velocity(&self) -> Option<f32> {
+        match self.kind {
+            ObstructionKind::BusDwelling => None,
+            ObstructionKind::Parking => Some(SQUEEZING_VELOCITY),
+        }
+    }
+}
+
+impl Lane {
+    pub fn obstruct(&mut self, obstruction: Obstruction, _: &mut World) {
+        self.microtraffic.obstructions.push(obstruction);
+    }
+}
+
+// Buses take as long as passengers need to get on and off
+pub fn bus_dwelling(stop: PreciseLocation, until: Instant, world: &mut World) {
+    LaneID::from_raw(stop.link.as_raw()).obstruct(
+        Obstruction {
+            kind: ObstructionKind::BusDwelling,
+            at: stop.offset,
+            length: BUS_STOP_LENGTH,
+            until,
+        },
+        world,
+    );
+}
+
+pub fn start_parking(lane: &mut Lane, at: f32, now: Instant) {
+    lane.microtraffic.obstructions.push(Obstruction {
+        kind: ObstructionKind::Parking,
+        at,
+        length: PARKING_LENGTH,
+        until: now + PARKING_MANEUVER,
+    });
+}
+
+pub fn clear_expired_obstructions(lane: &mut Lane, now: Instant) {
+    lane.microtraffic
+        .obstructions
+        .retain(|obstruction| obstruction.until > now);
+}
+
+// the closest one the car isn't past yet
+pub fn next_obstruction(obstructions: &[Obstruction], car: &LaneCar) -> Option<Obstruction> {
+    obstructions
+        .iter()
+        .filter(|obstruction| *car.position < obstruction.at + obstruction.length)
+        .min_by_key(|obstruction| OrderedFloat(obstruction.at))
+        .cloned()
+}
+
+pub fn squeezing_acceleration(car: &LaneCar, passing_velocity: f32) -> f32 {
+    if car.velocity > passing_velocity {
+        -SQUEEZING_DECELERATION
+    } else {
+        INFINITY
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
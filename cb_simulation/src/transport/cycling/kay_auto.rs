@@ -0,0 +1,210 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for Bikeway {
+    type ID = BikewayID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BikewayID {
+    _raw_id: RawID
+}
+
+impl Copy for BikewayID {}
+impl Clone for BikewayID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BikewayID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BikewayID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BikewayID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BikewayID {
+    fn eq(&self, other: &BikewayID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BikewayID {}
+
+impl TypedID for BikewayID {
+    type Target = Bikeway;
+
+    fn from_raw(id: RawID) -> Self {
+        BikewayID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BikewayID {
+    pub fn spawn_and_connect(path: LinePath, report_to: CBConstructionID, world: &mut World) -> Self {
+        let id = BikewayID::from_raw(world.allocate_instance_id::<Bikeway>());
+        let swarm = world.local_broadcast::<Bikeway>();
+        world.send(swarm, MSG_Bikeway_spawn_and_connect(id, path, report_to));
+        id
+    }
+    
+    pub fn get_render_info(self, ui: TransportUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Bikeway_get_render_info(ui));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Bikeway_spawn_and_connect(pub BikewayID, pub LinePath, pub CBConstructionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Bikeway_get_render_info(pub TransportUIID);
+
+impl Into<ConstructableID<CBPrototypeKind>> for BikewayID {
+    fn into(self) -> ConstructableID<CBPrototypeKind> {
+        ConstructableID::from_raw(self.as_raw())
+    }
+}
+impl Actor for CyclingNetwork {
+    type ID = CyclingNetworkID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct CyclingNetworkID {
+    _raw_id: RawID
+}
+
+impl Copy for CyclingNetworkID {}
+impl Clone for CyclingNetworkID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for CyclingNetworkID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "CyclingNetworkID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for CyclingNetworkID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for CyclingNetworkID {
+    fn eq(&self, other: &CyclingNetworkID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for CyclingNetworkID {}
+
+impl TypedID for CyclingNetworkID {
+    type Target = CyclingNetwork;
+
+    fn from_raw(id: RawID) -> Self {
+        CyclingNetworkID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl CyclingNetworkID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = CyclingNetworkID::from_raw(world.allocate_instance_id::<CyclingNetwork>());
+        let swarm = world.local_broadcast::<CyclingNetwork>();
+        world.send(swarm, MSG_CyclingNetwork_spawn(id, ));
+        id
+    }
+    
+    pub fn add_bikeway(self, bikeway: BikewayID, points: CVec < P2 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_CyclingNetwork_add_bikeway(bikeway, points));
+    }
+    
+    pub fn remove_bikeway(self, bikeway: BikewayID, world: &mut World) {
+        world.send(self.as_raw(), MSG_CyclingNetwork_remove_bikeway(bikeway));
+    }
+    
+    pub fn estimate_bike_ride(self, from: P2, to: P2, requester: ItineraryQueryID, world: &mut World) {
+        world.send(self.as_raw(), MSG_CyclingNetwork_estimate_bike_ride(from, to, requester));
+    }
+    
+    pub fn cycle(self, from: P2, to: P2, trip: TripID, world: &mut World) {
+        world.send(self.as_raw(), MSG_CyclingNetwork_cycle(from, to, trip));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CyclingNetwork_spawn(pub CyclingNetworkID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CyclingNetwork_add_bikeway(pub BikewayID, pub CVec < P2 >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CyclingNetwork_remove_bikeway(pub BikewayID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CyclingNetwork_estimate_bike_ride(pub P2, pub P2, pub ItineraryQueryID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CyclingNetwork_cycle(pub P2, pub P2, pub TripID);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    ConstructableID::<CBPrototypeKind>::register_implementor::<Bikeway>(system);
+    system.add_spawner::<Bikeway, _, _>(
+        |&MSG_Bikeway_spawn_and_connect(id, ref path, report_to), world| {
+            Bikeway::spawn_and_connect(id, path, report_to, world)
+        }, false
+    );
+    
+    system.add_handler::<Bikeway, _, _>(
+        |&MSG_Bikeway_get_render_info(ui), instance, world| {
+            instance.get_render_info(ui, world); Fate::Live
+        }, false
+    );
+    
+    system.add_spawner::<CyclingNetwork, _, _>(
+        |&MSG_CyclingNetwork_spawn(id, ), world| {
+            CyclingNetwork::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<CyclingNetwork, _, _>(
+        |&MSG_CyclingNetwork_add_bikeway(bikeway, ref points), instance, world| {
+            instance.add_bikeway(bikeway, points, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<CyclingNetwork, _, _>(
+        |&MSG_CyclingNetwork_remove_bikeway(bikeway), instance, world| {
+            instance.remove_bikeway(bikeway, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<CyclingNetwork, _, _>(
+        |&MSG_CyclingNetwork_estimate_bike_ride(from, to, requester), instance, world| {
+            instance.estimate_bike_ride(from, to, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<CyclingNetwork, _, _>(
+        |&MSG_CyclingNetwork_cycle(from, to, trip), instance, world| {
+            instance.cycle(from, to, trip, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,179 @@
+// Cyclists ride on the bike lanes of roads that were planned with them.
+// Like footways, built bike lanes are recorded in a coarse grid, which tells
+// how much of a way can be ridden on them: elsewhere cyclists share the road
+// with cars, are slower there and exposed to traffic. Cyclists themselves
+// go in a straight line, just long enough for all of that to add up
+use kay::{ActorSystem, World, Fate, TypedID, Actor};
+use compact::{CVec, CHashMap};
+use descartes::{P2, LinePath};
+
+use cb_planning::Prototype;
+use cb_planning::construction::{Constructable, ConstructableID};
+use planning::{CBConstructionID, CBPrototypeKind};
+use cb_time::units::Duration;
+use super::pathfinding::trip::{TripID, TravelMode};
+use super::pathfinding::itinerary::ItineraryQueryID;
+use super::pedestrians::{Cell, cells_along};
+use super::ui::{TransportUI, TransportUIID};
+
+// roads are rarely straight
+const DETOUR_FACTOR: f32 = 1.3;
+// sharing the road with cars, cyclists ride this much slower
+const SHARED_ROAD_SPEED_FACTOR: f32 = 0.75;
+
+#[derive(Copy, Clone)]
+pub struct BikeEstimate {
+    pub duration: Duration,
+    // share of the way without a bike lane
+    pub unsafe_share: f32,
+}
+
+#[derive(Compact, Clone)]
+pub struct Bikeway {
+    id: BikewayID,
+    path: LinePath,
+}
+
+impl Bikeway {
+    pub fn spawn_and_connect(
+        id: BikewayID,
+        path: &LinePath,
+        report_to: CBConstructionID,
+        world: &mut World,
+    ) -> Bikeway {
+        CyclingNetworkID::global_first(world).add_bikeway(id, path.points.clone(), world);
+        TransportUIID::global_broadcast(world).on_bikeway_constructed(
+            id.as_raw(),
+            path.clone(),
+            world,
+        );
+        report_to.action_done(id.into(), world);
+
+        Bikeway {
+            id,
+            path: path.clone(),
+        }
+    }
+
+    pub fn get_render_info(&mut self, ui: TransportUIID, world: &mut World) {
+        ui.on_bikeway_constructed(self.id.as_raw(), self.path.clone(), world);
+    }
+}
+
+impl Constructable<CBPrototypeKind> for Bikeway {
+    fn morph(
+        &mut self,
+        _new_prototype: &Prototype<CBPrototypeKind>,
+        report_to: CBConstructionID,
+        world: &mut World,
+    ) {
+        report_to.action_done(self.id_as(), world);
+    }
+
+    fn destruct(&mut self, report_to: CBConstructionID, world: &mut World) -> Fate {
+        CyclingNetworkID::global_first(world).remove_bikeway(self.id, world);
+        TransportUIID::global_broadcast(world).on_bikeway_destructed(self.id.as_raw(), world);
+        report_to.action_done(self.id_as(), world);
+        Fate::Die
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct BikewayCells {
+    bikeway: BikewayID,
+    cells: CVec<Cell>,
+}
+
+#[derive(Compact, Clone)]
+pub struct CyclingNetwork {
+    id: CyclingNetworkID,
+    bikeways: CVec<BikewayCells>,
+    // how many bike lanes pass through each cell
+    bike_lane_cells: CHashMap<Cell, u16>,
+}
+
+impl CyclingNetwork {
+    pub fn spawn(id: CyclingNetworkID, _: &mut World) -> CyclingNetwork {
+        CyclingNetwork {
+            id,
+            bikeways: CVec::new(),
+            bike_lane_cells: CHashMap::new(),
+        }
+    }
+
+    pub fn add_bikeway(&mut self, bikeway: BikewayID, points: &CVec<P2>, _: &mut World) {
+        let cells = cells_along(points);
+        for &cell in &cells {
+            let count = self.bike_lane_cells.get(cell).cloned().unwrap_or(0);
+            self.bike_lane_cells.insert(cell, count + 1);
+        }
+        self.bikeways.push(BikewayCells {
+            bikeway,
+            cells: cells.into(),
+        });
+    }
+
+    pub fn remove_bikeway(&mut self, bikeway: BikewayID, _: &mut World) {
+        if let Some(idx) = self
+            .bikeways
+            .iter()
+            .position(|other| other.bikeway == bikeway)
+        {
+            let removed = self.bikeways.remove(idx);
+            for &cell in removed.cells.iter() {
+                match self.bike_lane_cells.get(cell).cloned() {
+                    Some(count) if count > 1 => {
+                        self.bike_lane_cells.insert(cell, count - 1);
+                    }
+                    _ => {
+                        self.bike_lane_cells.remove(cell);
+                    }
+                }
+            }
+        }
+    }
+
+    fn estimate(&self, from: P2, to: P2) -> BikeEstimate {
+        let cells = cells_along(&[from, to]);
+        let n_covered = cells
+            .iter()
+            .filter(|&&cell| self.bike_lane_cells.get(cell).is_some())
+            .count();
+
+        let unsafe_share = 1.0 - n_covered as f32 / cells.len() as f32;
+        let seconds = (to - from).norm() * DETOUR_FACTOR / TravelMode::Bike.assumed_avg_speed()
+            * ((1.0 - unsafe_share) + unsafe_share / SHARED_ROAD_SPEED_FACTOR);
+
+        BikeEstimate {
+            duration: Duration(seconds as u32),
+            unsafe_share,
+        }
+    }
+
+    pub fn estimate_bike_ride(
+        &mut self,
+        from: P2,
+        to: P2,
+        requester: ItineraryQueryID,
+        world: &mut World,
+    ) {
+        requester.on_bike_estimate(self.estimate(from, to), world);
+    }
+
+    pub fn cycle(&mut self, from: P2, to: P2, trip: TripID, world: &mut World) {
+        trip.on_cycling_started(self.estimate(from, to).duration, world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Bikeway>();
+    system.register::<CyclingNetwork>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    CyclingNetworkID::spawn(world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,193 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct EmergencyCallerID {
+    _raw_id: RawID
+}
+
+impl Copy for EmergencyCallerID {}
+impl Clone for EmergencyCallerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for EmergencyCallerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "EmergencyCallerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for EmergencyCallerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for EmergencyCallerID {
+    fn eq(&self, other: &EmergencyCallerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for EmergencyCallerID {}
+
+pub struct EmergencyCallerRepresentative;
+
+impl ActorOrActorTrait for EmergencyCallerRepresentative {
+    type ID = EmergencyCallerID;
+}
+
+impl TypedID for EmergencyCallerID {
+    type Target = EmergencyCallerRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        EmergencyCallerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + EmergencyCaller> TraitIDFrom<Act> for EmergencyCallerID {}
+
+impl EmergencyCallerID {
+    pub fn responders_arrived(self, tag: u8, response_time: Duration, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_EmergencyCaller_responders_arrived(tag, response_time, instant));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<EmergencyCallerRepresentative>();
+        system.register_trait_message::<MSG_EmergencyCaller_responders_arrived>();
+    }
+
+    pub fn register_implementor<Act: Actor + EmergencyCaller>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, EmergencyCallerRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_EmergencyCaller_responders_arrived(tag, response_time, instant), instance, world| {
+                instance.responders_arrived(tag, response_time, instant, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EmergencyCaller_responders_arrived(pub u8, pub Duration, pub Instant);
+
+impl Actor for EmergencyDispatch {
+    type ID = EmergencyDispatchID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct EmergencyDispatchID {
+    _raw_id: RawID
+}
+
+impl Copy for EmergencyDispatchID {}
+impl Clone for EmergencyDispatchID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for EmergencyDispatchID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "EmergencyDispatchID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for EmergencyDispatchID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for EmergencyDispatchID {
+    fn eq(&self, other: &EmergencyDispatchID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for EmergencyDispatchID {}
+
+impl TypedID for EmergencyDispatchID {
+    type Target = EmergencyDispatch;
+
+    fn from_raw(id: RawID) -> Self {
+        EmergencyDispatchID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl EmergencyDispatchID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = EmergencyDispatchID::from_raw(world.allocate_instance_id::<EmergencyDispatch>());
+        let swarm = world.local_broadcast::<EmergencyDispatch>();
+        world.send(swarm, MSG_EmergencyDispatch_spawn(id, ));
+        id
+    }
+    
+    pub fn add_station(self, location: RoughLocationID, world: &mut World) {
+        world.send(self.as_raw(), MSG_EmergencyDispatch_add_station(location));
+    }
+    
+    pub fn remove_station(self, location: RoughLocationID, world: &mut World) {
+        world.send(self.as_raw(), MSG_EmergencyDispatch_remove_station(location));
+    }
+    
+    pub fn call(self, scene: RoughLocationID, caller: EmergencyCallerID, tag: u8, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_EmergencyDispatch_call(scene, caller, tag, instant));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EmergencyDispatch_spawn(pub EmergencyDispatchID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EmergencyDispatch_add_station(pub RoughLocationID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EmergencyDispatch_remove_station(pub RoughLocationID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EmergencyDispatch_call(pub RoughLocationID, pub EmergencyCallerID, pub u8, pub Instant);
+
+impl Into<PositionRequesterID> for EmergencyDispatchID {
+    fn into(self) -> PositionRequesterID {
+        PositionRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TripListenerID> for EmergencyDispatchID {
+    fn into(self) -> TripListenerID {
+        TripListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    EmergencyCallerID::register_trait(system);
+    PositionRequesterID::register_implementor::<EmergencyDispatch>(system);
+    TripListenerID::register_implementor::<EmergencyDispatch>(system);
+    system.add_spawner::<EmergencyDispatch, _, _>(
+        |&MSG_EmergencyDispatch_spawn(id, ), world| {
+            EmergencyDispatch::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<EmergencyDispatch, _, _>(
+        |&MSG_EmergencyDispatch_add_station(location), instance, world| {
+            instance.add_station(location, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<EmergencyDispatch, _, _>(
+        |&MSG_EmergencyDispatch_remove_station(location), instance, world| {
+            instance.remove_station(location, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<EmergencyDispatch, _, _>(
+        |&MSG_EmergencyDispatch_call(scene, caller, tag, instant), instance, world| {
+            instance.call(scene, caller, tag, instant, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,250 @@
+// Ambulances drive from the closest station to wherever they are called,
+// through traffic like everyone else, except that cars close in front of them
+// pull over to the edge of the lane to let them pass, and that traffic signals
+// ahead of them turn green. How long they take to arrive is the response time,
+// which depends on how far the station is and how congested the way there
+use kay::{ActorSystem, World, TypedID};
+use compact::CVec;
+use descartes::P2;
+use ordered_float::OrderedFloat;
+use cb_time::units::{Instant, Duration, TICKS_PER_SIM_SECOND};
+use cb_util::log::{info, warn};
+use super::microtraffic::{LaneCar, VehicleClass};
+use super::pathfinding::{RoughLocationID, PositionRequester, PositionRequesterID};
+use super::pathfinding::trip::{TripID, TripListener, TripListenerID, TripResult, TripFate,
+TravelMode};
+use super::taxis::TaxiOperatorID;
+const LOG_T: &str = "Emergency Services";
+
+// how close behind a car an emergency vehicle has to be for it to pull over
+const YIELD_DISTANCE: f32 = 50.0;
+// how fast cars creep along the edge of the lane while they let it pass
+const YIELDING_VELOCITY: f32 = 2.0;
+const YIELDING_DECELERATION: f32 = 2.0;
+// how far before an intersection emergency vehicles ask its signal for green
+pub const PREEMPTION_DISTANCE: f32 = 100.0;
+
+// cars pull over for emergency vehicles close behind them
+pub fn is_yielding(cars: &[LaneCar], c: usize) -> bool {
+    cars[c].vehicle != VehicleClass::Emergency
+        && cars[..c].iter().any(|behind| {
+            behind.vehicle == VehicleClass::Emergency
+                && *cars[c].position - *behind.position < YIELD_DISTANCE
+        })
+}
+
+pub fn yielding_acceleration(car: &LaneCar) -> f32 {
+    if car.velocity > YIELDING_VELOCITY {
+        -YIELDING_DECELERATION
+    } else {
+        0.0
+    }
+}
+
+// emergency vehicles drive past the cars pulling over in front of them
+pub fn next_car_ahead(cars: &[LaneCar], c: usize) -> Option<&LaneCar> {
+    let car = &cars[c];
+    cars[c + 1..].iter().find(|ahead| {
+        car.vehicle != VehicleClass::Emergency
+            || *ahead.position - *car.position >= YIELD_DISTANCE
+    })
+}
+
+pub trait EmergencyCaller {
+    fn responders_arrived(
+        &mut self,
+        tag: u8,
+        response_time: Duration,
+        instant: Instant,
+        world: &mut World,
+    );
+}
+
+#[derive(Copy, Clone)]
+pub struct Station {
+    location: RoughLocationID,
+    position: Option<P2>,
+}
+
+// the tag tells the caller what the call was about
+#[derive(Copy, Clone)]
+pub struct EmergencyCall {
+    scene: RoughLocationID,
+    caller: EmergencyCallerID,
+    tag: u8,
+    called_at: Instant,
+    responding: Option<TripID>,
+}
+
+#[derive(Compact, Clone)]
+pub struct EmergencyDispatch {
+    id: EmergencyDispatchID,
+    stations: CVec<Station>,
+    calls: CVec<EmergencyCall>,
+    n_responses: u32,
+    total_response_time: Duration,
+}
+
+impl EmergencyDispatch {
+    pub fn spawn(id: EmergencyDispatchID, _: &mut World) -> EmergencyDispatch {
+        EmergencyDispatch {
+            id,
+            stations: CVec::new(),
+            calls: CVec::new(),
+            n_responses: 0,
+            total_response_time: Duration(0),
+        }
+    }
+
+    pub fn add_station(&mut self, location: RoughLocationID, world: &mut World) {
+        self.stations.push(Station {
+            location,
+            position: None,
+        });
+        location.resolve_as_position(self.id.into(), location, world);
+    }
+
+    pub fn remove_station(&mut self, location: RoughLocationID, _: &mut World) {
+        self.stations.retain(|station| station.location != location);
+    }
+
+    pub fn call(
+        &mut self,
+        scene: RoughLocationID,
+        caller: EmergencyCallerID,
+        tag: u8,
+        instant: Instant,
+        world: &mut World,
+    ) {
+        self.calls.push(EmergencyCall {
+            scene,
+            caller,
+            tag,
+            called_at: instant,
+            responding: None,
+        });
+        scene.resolve_as_position(self.id.into(), scene, world);
+    }
+
+    fn dispatch(&mut self, call_idx: usize, scene_position: P2, world: &mut World) {
+        let maybe_closest_station = self
+            .stations
+            .iter()
+            .filter_map(|station| station.position.map(|position| (station.location, position)))
+            .min_by_key(|&(_, position)| OrderedFloat((position - scene_position).norm()));
+
+        if let Some((station, _)) = maybe_closest_station {
+            let call = &mut self.calls[call_idx];
+            call.responding = Some(TripID::spawn(
+                station,
+                call.scene,
+                Some(self.id.into()),
+                TravelMode::Emergency,
+                None,
+                call.called_at,
+                world,
+            ));
+        } else {
+            self.calls.remove(call_idx);
+            warn(
+                LOG_T,
+                format!(
+                    "Nobody responded to the emergency at ({:.0}, {:.0}), there is no hospital",
+                    scene_position.x, scene_position.y
+                ),
+                self.id,
+                world,
+            );
+        }
+    }
+}
+
+impl PositionRequester for EmergencyDispatch {
+    fn position_resolved(
+        &mut self,
+        rough_location: RoughLocationID,
+        position: P2,
+        world: &mut World,
+    ) {
+        for station in self
+            .stations
+            .iter_mut()
+            .filter(|station| station.location == rough_location)
+        {
+            station.position = Some(position);
+        }
+
+        if let Some(call_idx) = self
+            .calls
+            .iter()
+            .position(|call| call.scene == rough_location && call.responding.is_none())
+        {
+            self.dispatch(call_idx, position, world);
+        }
+    }
+}
+
+impl TripListener for EmergencyDispatch {
+    fn trip_created(&mut self, _trip: TripID, _: &mut World) {}
+
+    fn trip_result(
+        &mut self,
+        trip: TripID,
+        result: TripResult,
+        _rough_source: RoughLocationID,
+        _rough_destination: RoughLocationID,
+        world: &mut World,
+    ) {
+        if let Some(call_idx) = self
+            .calls
+            .iter()
+            .position(|call| call.responding == Some(trip))
+        {
+            let call = self.calls.remove(call_idx);
+            if let TripFate::Success(instant) = result.fate {
+                let response_time = Duration(
+                    (instant.ticks().saturating_sub(call.called_at.ticks())
+                        / TICKS_PER_SIM_SECOND as usize) as u32,
+                );
+                self.n_responses += 1;
+                self.total_response_time += response_time;
+                info(
+                    LOG_T,
+                    format!(
+                        "Responders arrived after {:.1} min (on average {:.1} min)",
+                        response_time.as_minutes(),
+                        self.total_response_time.as_minutes() / self.n_responses as f32
+                    ),
+                    self.id,
+                    world,
+                );
+                call.caller
+                    .responders_arrived(call.tag, response_time, instant, world);
+            } else {
+                warn(
+                    LOG_T,
+                    format!("Responders didn't get through ({:?})", result.fate),
+                    self.id,
+                    world,
+                );
+            }
+        }
+    }
+
+    // emergency vehicles ride free and are never driven as taxis
+    fn toll_paid(&mut self, _amount: f32, _: &mut World) {}
+
+    fn fare_paid(&mut self, _amount: f32, _operator: TaxiOperatorID, _: &mut World) {}
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<EmergencyDispatch>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    EmergencyDispatchID::spawn(world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
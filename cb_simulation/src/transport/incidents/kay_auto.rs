@@ -0,0 +1,61 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+
+
+impl LaneID {
+    pub fn toggle_closure_near(self, origin: Origin, position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_toggle_closure_near(origin, position));
+    }
+    
+    pub fn close_around(self, position: P2, radius: f32, until: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_close_around(position, radius, until));
+    }
+    
+    pub fn get_incident_info(self, ui: TransportUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_get_incident_info(ui));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_toggle_closure_near(pub Origin, pub P2);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_close_around(pub P2, pub f32, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_get_incident_info(pub TransportUIID);
+
+impl Into<EmergencyCallerID> for LaneID {
+    fn into(self) -> EmergencyCallerID {
+        EmergencyCallerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    EmergencyCallerID::register_implementor::<Lane>(system);
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_toggle_closure_near(origin, position), instance, world| {
+            instance.toggle_closure_near(origin, position, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_close_around(position, radius, until), instance, world| {
+            instance.close_around(position, radius, until, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_get_incident_info(ui), instance, world| {
+            instance.get_incident_info(ui, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
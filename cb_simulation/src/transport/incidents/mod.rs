@@ -0,0 +1,258 @@
+// Incidents block a lane: accidents happen by chance where lanes of an
+// intersection conflict and are cleared shortly after an ambulance arrived
+// (or eventually without one), closures for construction
+// are made and lifted by the player, or last as long as a city event around them
+// (see `city_events`). Cars in front of the incident wait,
+// pathfinding treats the lane as closed so that everyone else drives around it
+use kay::{ActorSystem, World, TypedID};
+use descartes::P2;
+use cb_time::units::{Instant, Duration};
+use cb_util::log::{info, warn};
+use cb_util::random::{seed, Rng};
+use cb_util::session::{permits, Permission, Origin};
+use super::lane::Lane;
+use super::lane::connectivity::Interaction;
+use super::pathfinding::Link;
+use super::emergency::{EmergencyCaller, EmergencyCallerID, EmergencyDispatchID};
+use super::ui::{TransportUI, TransportUIID};
+use dimensions::LANE_WIDTH;
+const LOG_T: &str = "Incidents";
+
+// per car passing a lane, for each lane it conflicts with
+const ACCIDENT_CHANCE_PER_CONFLICT: f32 = 0.000_05;
+// in case no ambulance ever gets there
+const ACCIDENT_DURATION: Duration = Duration(45 * 60);
+// how long it takes to clear the scene once responders arrived
+const CLEARING_TIME: Duration = Duration(10 * 60);
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum IncidentKind {
+    Accident,
+    Closure,
+}
+
+#[derive(Copy, Clone)]
+pub struct Incident {
+    pub kind: IncidentKind,
+    // how far along the lane it blocks traffic
+    pub at: f32,
+    // closures last until they are lifted
+    pub until: Option<Instant>,
+}
+
+impl Lane {
+    fn incident_position(&self, incident: Incident) -> P2 {
+        let path = &self.construction.path;
+        match incident.kind {
+            IncidentKind::Accident => path.along(incident.at),
+            IncidentKind::Closure => path.along(path.length() / 2.0),
+        }
+    }
+
+    fn start_incident(&mut self, incident: Incident, world: &mut World) {
+        self.microtraffic.incident = Some(incident);
+        self.on_closed();
+
+        let position = self.incident_position(incident);
+        match incident.kind {
+            IncidentKind::Accident => warn(
+                LOG_T,
+                format!(
+                    "Accident at ({:.0}, {:.0}), the lane is blocked",
+                    position.x, position.y
+                ),
+                self.id,
+                world,
+            ),
+            IncidentKind::Closure => info(
+                LOG_T,
+                format!(
+                    "Lane at ({:.0}, {:.0}) closed {}",
+                    position.x,
+                    position.y,
+                    if incident.until.is_some() {
+                        "for the time being"
+                    } else {
+                        "for construction"
+                    }
+                ),
+                self.id,
+                world,
+            ),
+        }
+        TransportUIID::global_broadcast(world).on_lane_incident(
+            self.id.as_raw(),
+            position,
+            Some(incident.kind),
+            world,
+        );
+    }
+
+    fn clear_incident(&mut self, world: &mut World) {
+        if let Some(incident) = self.microtraffic.incident.take() {
+            self.on_reopened();
+
+            let position = self.incident_position(incident);
+            info(
+                LOG_T,
+                format!(
+                    "Lane at ({:.0}, {:.0}) is open again",
+                    position.x, position.y
+                ),
+                self.id,
+                world,
+            );
+            TransportUIID::global_broadcast(world).on_lane_incident(
+                self.id.as_raw(),
+                position,
+                None,
+                world,
+            );
+        }
+    }
+
+    // Every lane is asked, only the one that was clicked on reacts.
+    // Accidents are left for emergency services to clear
+    pub fn toggle_closure_near(&mut self, origin: Origin, position: P2, world: &mut World) {
+        if self.connectivity.on_intersection
+            || self.construction.path.distance_to(position) > LANE_WIDTH / 2.0
+            || !permits(origin, Permission::Planning, self.id, world)
+        {
+            return;
+        }
+
+        match self.microtraffic.incident.map(|incident| incident.kind) {
+            Some(IncidentKind::Closure) => self.clear_incident(world),
+            Some(IncidentKind::Accident) => {}
+            None => self.start_incident(
+                Incident {
+                    kind: IncidentKind::Closure,
+                    at: 0.0,
+                    until: None,
+                },
+                world,
+            ),
+        }
+    }
+
+    // Every lane is asked, those close enough and not blocked already close
+    pub fn close_around(&mut self, position: P2, radius: f32, until: Instant, world: &mut World) {
+        if self.connectivity.on_intersection
+            || self.microtraffic.incident.is_some()
+            || self.construction.path.distance_to(position) > radius
+        {
+            return;
+        }
+
+        self.start_incident(
+            Incident {
+                kind: IncidentKind::Closure,
+                at: 0.0,
+                until: Some(until),
+            },
+            world,
+        );
+    }
+
+    pub fn get_incident_info(&mut self, ui: TransportUIID, world: &mut World) {
+        if let Some(incident) = self.microtraffic.incident {
+            ui.on_lane_incident(
+                self.id.as_raw(),
+                self.incident_position(incident),
+                Some(incident.kind),
+                world,
+            );
+        }
+    }
+}
+
+pub fn clear_expired_incident(lane: &mut Lane, now: Instant, world: &mut World) {
+    let expired = lane
+        .microtraffic
+        .incident
+        .and_then(|incident| incident.until)
+        .map_or(false, |until| now >= until);
+    if expired {
+        lane.clear_incident(world);
+    }
+}
+
+// The more lanes of an intersection a lane crosses or merges into,
+// the likelier cars driving it crash, right where the first conflict starts
+pub fn maybe_accident(lane: &mut Lane, now: Instant, world: &mut World) {
+    if !lane.connectivity.on_intersection || lane.microtraffic.incident.is_some() {
+        return;
+    }
+
+    let conflict_starts: Vec<f32> = lane
+        .connectivity
+        .interactions
+        .iter()
+        .filter_map(|interaction| match *interaction {
+            Interaction::Conflicting { start, .. } => Some(start),
+            _ => None,
+        })
+        .collect();
+    if conflict_starts.is_empty() {
+        return;
+    }
+
+    let chance = ACCIDENT_CHANCE_PER_CONFLICT * conflict_starts.len() as f32;
+    if seed((now.ticks(), lane.id)).gen::<f32>() < chance {
+        let at = conflict_starts.iter().cloned().fold(lane.construction.length, f32::min);
+        lane.start_incident(
+            Incident {
+                kind: IncidentKind::Accident,
+                at,
+                until: Some(now + ACCIDENT_DURATION),
+            },
+            world,
+        );
+
+        // the blocked lane itself can't be routed to, so responders
+        // head for a lane leading into it
+        let maybe_scene = lane
+            .connectivity
+            .interactions
+            .iter()
+            .filter_map(|interaction| match *interaction {
+                Interaction::Previous { previous, .. } => Some(previous),
+                _ => None,
+            })
+            .next();
+        if let Some(scene) = maybe_scene {
+            EmergencyDispatchID::global_first(world).call(
+                scene.into(),
+                lane.id.into(),
+                0,
+                now,
+                world,
+            );
+        }
+    }
+}
+
+impl EmergencyCaller for Lane {
+    fn responders_arrived(
+        &mut self,
+        _tag: u8,
+        _response_time: Duration,
+        instant: Instant,
+        _: &mut World,
+    ) {
+        if let Some(ref mut incident) = self.microtraffic.incident {
+            if incident.kind == IncidentKind::Accident {
+                incident.until = Some(incident.until.map_or(instant + CLEARING_TIME, |until| {
+                    until.min(instant + CLEARING_TIME)
+                }));
+            }
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
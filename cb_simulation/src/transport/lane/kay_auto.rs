@@ -55,18 +55,28 @@ impl TypedID for LaneID {
 }
 
 impl LaneID {
-    pub fn spawn(path: LinePath, on_intersection: bool, timings: CVec < bool >, world: &mut World) -> Self {
+    pub fn spawn(path: LinePath, on_intersection: bool, speed_limit: N, elevation: Elevation, yield_at: Option < N >, world: &mut World) -> Self {
         let id = LaneID::from_raw(world.allocate_instance_id::<Lane>());
         let swarm = world.local_broadcast::<Lane>();
-        world.send(swarm, MSG_Lane_spawn(id, path, on_intersection, timings));
+        world.send(swarm, MSG_Lane_spawn(id, path, on_intersection, speed_limit, elevation, yield_at));
         id
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Lane_spawn(pub LaneID, pub LinePath, pub bool, pub CVec < bool >);
+struct MSG_Lane_spawn(pub LaneID, pub LinePath, pub bool, pub N, pub Elevation, pub Option < N >);
 
+impl Into<SnapshotContributorID> for LaneID {
+    fn into(self) -> SnapshotContributorID {
+        SnapshotContributorID::from_raw(self.as_raw())
+    }
+}
 
+impl Into<ExportableID> for LaneID {
+    fn into(self) -> ExportableID {
+        ExportableID::from_raw(self.as_raw())
+    }
+}
 impl Actor for SwitchLane {
     type ID = SwitchLaneID;
 
@@ -115,32 +125,33 @@ impl TypedID for SwitchLaneID {
 }
 
 impl SwitchLaneID {
-    pub fn spawn(path: LinePath, world: &mut World) -> Self {
+    pub fn spawn(path: LinePath, elevation: Elevation, world: &mut World) -> Self {
         let id = SwitchLaneID::from_raw(world.allocate_instance_id::<SwitchLane>());
         let swarm = world.local_broadcast::<SwitchLane>();
-        world.send(swarm, MSG_SwitchLane_spawn(id, path));
+        world.send(swarm, MSG_SwitchLane_spawn(id, path, elevation));
         id
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_SwitchLane_spawn(pub SwitchLaneID, pub LinePath);
+struct MSG_SwitchLane_spawn(pub SwitchLaneID, pub LinePath, pub Elevation);
 
 
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
-    
+    SnapshotContributorID::register_implementor::<Lane>(system);
+    ExportableID::register_implementor::<Lane>(system);
     system.add_spawner::<Lane, _, _>(
-        |&MSG_Lane_spawn(id, ref path, on_intersection, ref timings), world| {
-            Lane::spawn(id, path, on_intersection, timings, world)
+        |&MSG_Lane_spawn(id, ref path, on_intersection, speed_limit, elevation, yield_at), world| {
+            Lane::spawn(id, path, on_intersection, speed_limit, elevation, yield_at, world)
         }, false
     );
     
     system.add_spawner::<SwitchLane, _, _>(
-        |&MSG_SwitchLane_spawn(id, ref path), world| {
-            SwitchLane::spawn(id, path, world)
+        |&MSG_SwitchLane_spawn(id, ref path, elevation), world| {
+            SwitchLane::spawn(id, path, elevation, world)
         }, false
     );
 }
\ No newline at end of file
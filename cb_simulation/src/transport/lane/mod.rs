@@ -1,12 +1,13 @@
-use compact::{CVec};
 use kay::{ActorSystem, World};
 use descartes::{N, LinePath};
 
 use super::construction::ConstructionInfo;
+use super::transport_planning::Elevation;
 pub mod connectivity;
 use self::connectivity::{ConnectivityInfo, SwitchConnectivityInfo};
 use super::microtraffic::{Microtraffic, TransferringMicrotraffic};
 use super::pathfinding::PathfindingCore;
+use land_use::grid_layers::GridLayersID;
 
 #[derive(Compact, Clone)]
 pub struct Lane {
@@ -15,6 +16,8 @@ pub struct Lane {
     pub connectivity: ConnectivityInfo,
     pub microtraffic: Microtraffic,
     pub pathfinding: PathfindingCore,
+    // in m/s, given by the class of the road the lane belongs to
+    pub speed_limit: N,
 }
 
 impl Lane {
@@ -22,18 +25,32 @@ impl Lane {
         id: LaneID,
         path: &LinePath,
         on_intersection: bool,
-        timings: &CVec<bool>,
+        speed_limit: N,
+        elevation: Elevation,
+        yield_at: Option<N>,
         world: &mut World,
     ) -> Self {
         let lane = Lane {
             id,
-            construction: ConstructionInfo::from_path(path.clone()),
+            construction: ConstructionInfo::from_path(path.clone(), elevation),
             connectivity: ConnectivityInfo::new(on_intersection),
-            microtraffic: Microtraffic::new(timings.clone()),
+            microtraffic: Microtraffic::new(yield_at),
             pathfinding: PathfindingCore::default(),
+            speed_limit,
         };
 
         super::ui::on_build(&lane, world);
+        super::tolls::on_build(&lane, world);
+        ::land_use::districts::on_build(&lane, world);
+        ::land_use::territory::on_build(&lane, world);
+        if !on_intersection {
+            GridLayersID::global_first(world).add_lane(
+                id,
+                lane.construction.path.along(lane.construction.length / 2.0),
+                speed_limit,
+                world,
+            );
+        }
 
         lane
     }
@@ -48,10 +65,15 @@ pub struct SwitchLane {
 }
 
 impl SwitchLane {
-    pub fn spawn(id: SwitchLaneID, path: &LinePath, _: &mut World) -> SwitchLane {
+    pub fn spawn(
+        id: SwitchLaneID,
+        path: &LinePath,
+        elevation: Elevation,
+        _: &mut World,
+    ) -> SwitchLane {
         SwitchLane {
             id,
-            construction: ConstructionInfo::from_path(path.clone()),
+            construction: ConstructionInfo::from_path(path.clone(), elevation),
             connectivity: SwitchConnectivityInfo::default(),
             microtraffic: TransferringMicrotraffic::default(),
         }
@@ -123,6 +145,43 @@ impl SwitchLane {
     }
 }
 
+use timeline::{SnapshotContributor, TimelineID};
+use cb_time::units::Instant;
+
+impl SnapshotContributor for Lane {
+    fn contribute_to_snapshot(
+        &mut self,
+        timeline: TimelineID,
+        instant: Instant,
+        world: &mut World,
+    ) {
+        // intersection lanes would only clutter the simplified map
+        if !self.connectivity.on_intersection {
+            timeline.add_network_segment(
+                instant,
+                self.construction.path.start(),
+                self.construction.path.end(),
+                self.construction.length,
+                world,
+            );
+        }
+    }
+}
+
+use export::{Exportable, ExporterID};
+
+impl Exportable for Lane {
+    fn export(&mut self, exporter: ExporterID, world: &mut World) {
+        exporter.on_exported_lane(
+            self.id,
+            &self.construction.path,
+            self.connectivity.on_intersection,
+            self.microtraffic.cars.len() as u32,
+            world,
+        );
+    }
+}
+
 pub fn setup(system: &mut ActorSystem) {
     system.register::<Lane>();
     system.register::<SwitchLane>();
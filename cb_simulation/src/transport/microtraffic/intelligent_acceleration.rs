@@ -1,16 +1,12 @@
-use super::Obstacle;
+use super::{Obstacle, LaneCar};
 
 pub const COMFORTABLE_BREAKING_DECELERATION: f32 = 0.4;
 
-pub fn intelligent_acceleration(
-    car: &Obstacle,
-    obstacle: &Obstacle,
-    safe_time_headway: f32,
-) -> f32 {
+pub fn intelligent_acceleration(car: &LaneCar, obstacle: &Obstacle, safe_time_headway: f32) -> f32 {
     // http://en.wikipedia.org/wiki/Intelligent_driver_model
 
-    let car_length = 4.0;
-    let acceleration = 0.4;
+    let car_length = car.vehicle.length();
+    let acceleration = car.vehicle.acceleration();
     let max_deceleration: f32 = 5.0;
     let desired_velocity = car.max_velocity;
     let acceleration_exponent = 4.0;
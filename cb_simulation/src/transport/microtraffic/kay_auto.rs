@@ -91,10 +91,22 @@ impl LaneID {
     pub fn on_signal_changed(self, from: LaneID, new_green: bool, world: &mut World) {
         world.send(self.as_raw(), MSG_Lane_on_signal_changed(from, new_green));
     }
+    
+    pub fn forget_cars(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_forget_cars());
+    }
+    
+    pub fn update_waypoints(self, trip: TripID, waypoint: Option < pathfinding :: LinkID >, next_waypoint: Option < pathfinding :: LinkID >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_update_waypoints(trip, waypoint, next_waypoint));
+    }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Lane_on_signal_changed(pub LaneID, pub bool);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_forget_cars();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_update_waypoints(pub TripID, pub Option < pathfinding :: LinkID >, pub Option < pathfinding :: LinkID >);
 
 impl Into<LaneLikeID> for LaneID {
     fn into(self) -> LaneLikeID {
@@ -110,10 +122,13 @@ impl Into<TemporalID> for LaneID {
 
 
 impl SwitchLaneID {
-    
+    pub fn forget_cars(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_SwitchLane_forget_cars());
+    }
 }
 
-
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SwitchLane_forget_cars();
 
 impl Into<LaneLikeID> for SwitchLaneID {
     fn into(self) -> LaneLikeID {
@@ -138,6 +153,23 @@ pub fn auto_setup(system: &mut ActorSystem) {
             instance.on_signal_changed(from, new_green, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_forget_cars(), instance, world| {
+            instance.forget_cars(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_update_waypoints(trip, waypoint, next_waypoint), instance, world| {
+            instance.update_waypoints(trip, waypoint, next_waypoint, world); Fate::Live
+        }, false
+    );
     LaneLikeID::register_implementor::<SwitchLane>(system);
     TemporalID::register_implementor::<SwitchLane>(system);
+    system.add_handler::<SwitchLane, _, _>(
+        |&MSG_SwitchLane_forget_cars(), instance, world| {
+            instance.forget_cars(world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
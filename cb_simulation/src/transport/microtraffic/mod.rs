@@ -7,11 +7,18 @@ use std::ops::{Deref, DerefMut};
 use super::lane::{Lane, LaneID, SwitchLane, SwitchLaneID};
 use super::lane::connectivity::{Interaction};
 use super::pathfinding;
+use super::signals::TrafficSignalID;
+use super::incidents::{self, Incident};
+use super::curbside::{self, Obstruction};
+use super::tolls::{self, Toll};
+use super::emergency;
+use land_use::districts::{self, DistrictPolicies};
 
 mod intelligent_acceleration;
 use self::intelligent_acceleration::intelligent_acceleration;
 
 use cb_util::log::debug;
+use environment::weather::Weather;
 const LOG_T: &str = "Microtraffic";
 
 // TODO: move all iteration, updates, etc into one huge retain loop (see identical TODO below)
@@ -20,21 +27,50 @@ const LOG_T: &str = "Microtraffic";
 pub struct Microtraffic {
     pub obstacles: CVec<(Obstacle, LaneLikeID)>,
     pub cars: CVec<LaneCar>,
-    timings: CVec<bool>,
+    pub signal: Option<TrafficSignalID>,
     pub green: bool,
     pub yellow_to_green: bool,
     pub yellow_to_red: bool,
+    // seconds all cars together waited in front of red since the signal last asked
+    pub waited_at_red: f32,
+    // rolling average of how many seconds cars take for the whole lane
+    pub travel_time: Option<f32>,
+    pub n_timed_cars: u16,
+    // what pathfinding weighs the lane with, see `publish_travel_time`
+    pub published_travel_time: Option<f32>,
+    pub incident: Option<Incident>,
+    pub toll: Option<Toll>,
+    // what the toll costs right now, see `tolls::update_toll_price`
+    pub current_toll: f32,
+    // of the district the lane lies in, see `districts`
+    pub district_policies: Option<DistrictPolicies>,
+    // where the lane enters the ring of a roundabout, cars before it
+    // wait there until no car on the ring is about to pass
+    pub yield_at: Option<f32>,
+    // dwelling buses and parking cars, see `curbside`
+    pub obstructions: CVec<Obstruction>,
 }
 
 impl Microtraffic {
-    pub fn new(timings: CVec<bool>) -> Self {
+    // lanes without a signal are always green
+    pub fn new(yield_at: Option<f32>) -> Self {
         Microtraffic {
             obstacles: CVec::new(),
             cars: CVec::new(),
-            timings,
-            green: false,
-            yellow_to_green: false,
-            yellow_to_red: false,
+            signal: None,
+            green: true,
+            yellow_to_green: true,
+            yellow_to_red: true,
+            waited_at_red: 0.0,
+            travel_time: None,
+            n_timed_cars: 0,
+            published_travel_time: None,
+            incident: None,
+            toll: None,
+            current_toll: 0.0,
+            district_policies: None,
+            yield_at,
+            obstructions: CVec::new(),
         }
     }
 }
@@ -43,6 +79,14 @@ impl Microtraffic {
 // unit values while traffic happening at a slower pace to be visible
 const MICROTRAFFIC_UNREALISTIC_SLOWDOWN: f32 = 6.0;
 
+// cars slower than this in front of red count as waiting for green
+const WAITING_VELOCITY: f32 = 0.5;
+
+// weight of each new measurement in the rolling average of travel times
+const TRAVEL_TIME_SMOOTHING: f32 = 0.2;
+// measured travel times need to differ this much (relatively) to be published again
+const REPUBLISH_THRESHOLD: f32 = 0.1;
+
 #[derive(Compact, Clone, Default)]
 pub struct TransferringMicrotraffic {
     pub left_obstacles: CVec<Obstacle>,
@@ -76,15 +120,74 @@ impl Obstacle {
 use super::pathfinding::trip::{TripID, TripResult, TripFate};
 use super::pathfinding::Link;
 
+// Trucks are longer, keep more distance and get going slower than cars,
+// so every truck on a lane takes away more of its capacity
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum VehicleClass {
+    Car,
+    Truck,
+    Emergency,
+}
+
+impl VehicleClass {
+    pub fn length(self) -> f32 {
+        match self {
+            VehicleClass::Car => 4.0,
+            VehicleClass::Truck => 12.0,
+            VehicleClass::Emergency => 6.0,
+        }
+    }
+
+    pub fn acceleration(self) -> f32 {
+        match self {
+            VehicleClass::Car => 0.4,
+            VehicleClass::Truck => 0.2,
+            VehicleClass::Emergency => 0.6,
+        }
+    }
+
+    // on lanes with a lower speed limit, that limit applies instead
+    pub fn max_velocity(self) -> f32 {
+        match self {
+            VehicleClass::Car => 25.0,
+            VehicleClass::Truck => 18.0,
+            VehicleClass::Emergency => 30.0,
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct LaneCar {
     pub trip: TripID,
+    pub vehicle: VehicleClass,
     pub as_obstacle: Obstacle,
     pub acceleration: f32,
     pub destination: pathfinding::PreciseLocation,
+    // on long trips, the next two landmarks to head for on the way
+    pub waypoint: Option<pathfinding::LinkID>,
+    pub next_waypoint: Option<pathfinding::LinkID>,
     pub next_hop_interaction: Option<u8>,
+    // version of the lane's routes the next hop was chosen with
+    pub routed_with: u16,
+    // when and where the car got onto its current lane, to time it
+    pub entered_at: Instant,
+    pub entered_position: f32,
+    pub leg: Option<Leg>,
+}
+
+// The stretch towards the destination or the next waypoint that a car is on,
+// in the costs that pathfinding weighs lanes with
+#[derive(Copy, Clone)]
+pub struct Leg {
+    pub target: pathfinding::Location,
+    // cost of the whole leg, as expected when the car started on it
+    pub estimate: f32,
+    pub driven: f32,
 }
 
+// long drives ask for other waypoints when their leg takes this much longer than expected
+const REPLAN_SLOWDOWN_FACTOR: f32 = 1.5;
+
 impl LaneCar {
     fn offset_by(&self, delta: f32) -> LaneCar {
         LaneCar {
@@ -131,7 +234,7 @@ impl DerefMut for TransferringLaneCar {
     }
 }
 
-use cb_time::units::Instant;
+use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
 
 pub trait LaneLike {
     fn add_car(
@@ -145,11 +248,13 @@ pub trait LaneLike {
 }
 
 use self::pathfinding::StoredRoutingEntry;
+use self::pathfinding::road_pathfinding::travel_cost;
 
 use cb_time::actors::{Temporal, TemporalID};
 
 const TRAFFIC_LOGIC_THROTTLING: usize = 10;
 const PATHFINDING_THROTTLING: usize = 10;
+const TRAVEL_TIME_PUBLISHING_INTERVAL: usize = 600;
 
 impl LaneLike for Lane {
     fn add_car(
@@ -175,27 +280,38 @@ impl LaneLike for Lane {
             }
         }
 
-        let (maybe_next_hop_interaction, almost_there) =
+        // once near a waypoint, head for the one after it while the trip names the next
+        let reached_waypoint = car.waypoint.is_some()
+            && self.pathfinding.location.map(|location| location.landmark) == car.waypoint;
+        if reached_waypoint {
+            car.trip.waypoint_reached(car.waypoint.unwrap(), self.id, world);
+        }
+        let waypoints_ahead = if reached_waypoint {
+            [car.next_waypoint, None]
+        } else {
+            [car.waypoint, car.next_waypoint]
+        };
+
+        let (maybe_route, almost_there) =
             if Some(car.destination.location) == self.pathfinding.location {
                 (None, true)
             } else {
-                let maybe_hop = self
-                    .pathfinding
-                    .routes
-                    .get(car.destination.location)
-                    .or_else(|| {
-                        self.pathfinding
-                            .routes
-                            .get(car.destination.landmark_destination())
-                    })
-                    .map(|&StoredRoutingEntry { outgoing_idx, .. }| outgoing_idx as usize);
-
-                (maybe_hop, false)
+                (self.route_towards(&car, waypoints_ahead), false)
             };
 
-        if maybe_next_hop_interaction.is_some() || almost_there {
+        if maybe_route.is_some() || almost_there {
             let routed_car = LaneCar {
-                next_hop_interaction: maybe_next_hop_interaction.map(|hop| hop as u8),
+                next_hop_interaction: maybe_route.map(|(_, entry)| entry.outgoing_idx),
+                leg: maybe_route.map(|(target, entry)| {
+                    self.follow_leg(&car, target, entry.distance, instant, world)
+                }),
+                routed_with: self.pathfinding.routes_version,
+                entered_at: instant,
+                entered_position: *car.position,
+                max_velocity: car
+                    .vehicle
+                    .max_velocity()
+                    .min(self.effective_speed_limit() * Weather::on(instant).speed_factor()),
                 ..car
             };
 
@@ -232,6 +348,115 @@ impl LaneLike for Lane {
 }
 
 impl Lane {
+    // the route a car takes from here, and where it leads
+    fn route_towards(
+        &self,
+        car: &LaneCar,
+        waypoints_ahead: [Option<pathfinding::LinkID>; 2],
+    ) -> Option<(pathfinding::Location, StoredRoutingEntry)> {
+        let waypoint_targets = waypoints_ahead
+            .iter()
+            .filter_map(|&waypoint| waypoint)
+            .map(pathfinding::Location::landmark);
+
+        ::std::iter::once(car.destination.location)
+            .chain(::std::iter::once(car.destination.landmark_destination()))
+            .chain(waypoint_targets)
+            .filter_map(|target| {
+                self.pathfinding
+                    .routes
+                    .get(target)
+                    .map(|&entry| (target, entry))
+            })
+            .next()
+    }
+
+    // Compares how long the rest of the car's leg takes now with what was expected.
+    // Long drives whose leg got much slower ask their trip for other waypoints,
+    // after which the slower leg is what is expected, so they don't keep asking
+    fn follow_leg(
+        &self,
+        car: &LaneCar,
+        target: pathfinding::Location,
+        distance_after: f32,
+        instant: Instant,
+        world: &mut World,
+    ) -> Leg {
+        let own_cost = travel_cost(self);
+        let remaining = own_cost + distance_after;
+
+        match car.leg {
+            Some(leg) if leg.target == target => {
+                let much_slower = leg.driven + remaining > leg.estimate * REPLAN_SLOWDOWN_FACTOR;
+                if much_slower && car.waypoint.is_some() {
+                    if let Some(location) = self.pathfinding.location {
+                        car.trip.replan(self.id, location.landmark, instant, world);
+                    }
+                }
+                Leg {
+                    target,
+                    estimate: if much_slower {
+                        leg.driven + remaining
+                    } else {
+                        leg.estimate
+                    },
+                    driven: leg.driven + own_cost,
+                }
+            }
+            _ => Leg {
+                target,
+                estimate: remaining,
+                driven: own_cost,
+            },
+        }
+    }
+
+    // Cars that only drove part of the lane count as if they kept their pace
+    // for the whole lane, unless that part is too short to tell
+    fn record_travel_time(&mut self, car: &LaneCar, now: Instant) {
+        let driven = self.construction.length - car.entered_position;
+        if driven < self.construction.length / 2.0 {
+            return;
+        }
+        let seconds = (now.ticks() - car.entered_at.ticks()) as f32
+            / TICKS_PER_SIM_SECOND as f32
+            / MICROTRAFFIC_UNREALISTIC_SLOWDOWN;
+        let for_whole_lane = seconds * self.construction.length / driven;
+
+        self.microtraffic.travel_time = Some(match self.microtraffic.travel_time {
+            Some(average) => average + (for_whole_lane - average) * TRAVEL_TIME_SMOOTHING,
+            None => for_whole_lane,
+        });
+        self.microtraffic.n_timed_cars = self.microtraffic.n_timed_cars.saturating_add(1);
+    }
+
+    // Measured travel times only become pathfinding weights every so often and
+    // when they changed noticeably, so that routes don't change with every car.
+    // Without cars passing, it slowly returns to driving at the speed limit
+    fn publish_travel_time(&mut self) {
+        let measured = match self.microtraffic.travel_time {
+            Some(travel_time) if self.microtraffic.n_timed_cars == 0 => {
+                let free_flow = self.construction.length / self.effective_speed_limit();
+                travel_time + (free_flow - travel_time) * TRAVEL_TIME_SMOOTHING
+            }
+            Some(travel_time) => travel_time,
+            None => return,
+        };
+        self.microtraffic.travel_time = Some(measured);
+        self.microtraffic.n_timed_cars = 0;
+
+        let changed = self
+            .microtraffic
+            .published_travel_time
+            .map_or(true, |published| {
+                (measured - published).abs() > published * REPUBLISH_THRESHOLD
+            });
+        if changed {
+            self.microtraffic.published_travel_time = Some(measured);
+            self.pathfinding.routes_changed = true;
+        }
+    }
+
     pub fn on_signal_changed(&mut self, from: LaneID, new_green: bool, _: &mut World) {
         for interaction in self.connectivity.interactions.iter_mut() {
             match *interaction {
@@ -243,10 +468,93 @@ impl Lane {
             }
         }
     }
+
+    // cars belong to trips, which are dropped together with households
+    // from savegames that can't be fully loaded
+    pub fn forget_cars(&mut self, _: &mut World) {
+        self.microtraffic.cars.clear();
+        self.microtraffic.obstacles.clear();
+    }
+
+    // if the car already moved on, the next lane near the waypoint asks again
+    pub fn update_waypoints(
+        &mut self,
+        trip: TripID,
+        waypoint: Option<pathfinding::LinkID>,
+        next_waypoint: Option<pathfinding::LinkID>,
+        _: &mut World,
+    ) {
+        for car in self.microtraffic.cars.iter_mut() {
+            if car.trip == trip {
+                car.waypoint = waypoint;
+                car.next_waypoint = next_waypoint;
+            }
+        }
+    }
+}
+
+// Cars that were routed with an older version of the lane's routes get a new
+// next hop, those that can't get to where they are going from here anymore give up
+pub fn reroute_stale_cars(lane: &mut Lane, fate_if_lost: TripFate, world: &mut World) {
+    let routes_version = lane.pathfinding.routes_version;
+    if lane
+        .microtraffic
+        .cars
+        .iter()
+        .all(|car| car.routed_with == routes_version)
+    {
+        return;
+    }
+
+    let new_hops: Vec<Option<Option<u8>>> = lane
+        .microtraffic
+        .cars
+        .iter()
+        .map(|car| {
+            if car.routed_with == routes_version
+                || Some(car.destination.location) == lane.pathfinding.location
+            {
+                Some(car.next_hop_interaction)
+            } else {
+                lane.route_towards(car, [car.waypoint, car.next_waypoint])
+                    .map(|(_, entry)| Some(entry.outgoing_idx))
+            }
+        })
+        .collect();
+
+    let self_as_rough_location = lane.id_as();
+    let mut still_routed = CVec::new();
+    for (car, maybe_new_hop) in lane.microtraffic.cars.iter().zip(new_hops) {
+        if let Some(next_hop_interaction) = maybe_new_hop {
+            still_routed.push(LaneCar {
+                next_hop_interaction,
+                routed_with: routes_version,
+                ..*car
+            });
+        } else {
+            car.trip.finish(
+                TripResult {
+                    location_now: Some(self_as_rough_location),
+                    fate: fate_if_lost,
+                },
+                world,
+            );
+        }
+    }
+    lane.microtraffic.cars = still_routed;
+}
+
+impl SwitchLane {
+    pub fn forget_cars(&mut self, _: &mut World) {
+        self.microtraffic.cars.clear();
+        self.microtraffic.left_obstacles.clear();
+        self.microtraffic.right_obstacles.clear();
+    }
 }
 
 impl Temporal for Lane {
     fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
+        let unslowed_dt = dt;
         let dt = dt / MICROTRAFFIC_UNREALISTIC_SLOWDOWN;
 
         // self.construction.progress += dt * 400.0;
@@ -254,28 +562,8 @@ impl Temporal for Lane {
         let do_traffic = current_instant.ticks() % TRAFFIC_LOGIC_THROTTLING
             == self.id.as_raw().instance_id as usize % TRAFFIC_LOGIC_THROTTLING;
 
-        let old_green = self.microtraffic.green;
-        self.microtraffic.yellow_to_red = if self.microtraffic.timings.is_empty() {
-            true
-        } else {
-            !self.microtraffic.timings
-                [((current_instant.ticks() + 100) / 30) % self.microtraffic.timings.len()]
-        };
-        self.microtraffic.yellow_to_green = if self.microtraffic.timings.is_empty() {
-            true
-        } else {
-            self.microtraffic.timings
-                [((current_instant.ticks() + 100) / 30) % self.microtraffic.timings.len()]
-        };
-        self.microtraffic.green = if self.microtraffic.timings.is_empty() {
-            true
-        } else {
-            self.microtraffic.timings
-                [(current_instant.ticks() / 30) % self.microtraffic.timings.len()]
-        };
-
         // TODO: this is just a hacky way to update new lanes about existing lane's green
-        if old_green != self.microtraffic.green || do_traffic {
+        if do_traffic {
             for interaction in &self.connectivity.interactions {
                 if let Interaction::Previous { previous, .. } = *interaction {
                     previous.on_signal_changed(self.id, self.microtraffic.green, world);
@@ -287,9 +575,34 @@ impl Temporal for Lane {
             == self.id.as_raw().instance_id as usize % PATHFINDING_THROTTLING
         {
             self.pathfinding_tick(world);
+            reroute_stale_cars(self, TripFate::RouteForgotten, world);
+        }
+
+        if current_instant.ticks() % TRAVEL_TIME_PUBLISHING_INTERVAL
+            == self.id.as_raw().instance_id as usize % TRAVEL_TIME_PUBLISHING_INTERVAL
+        {
+            self.publish_travel_time();
+            tolls::update_toll_price(self, current_instant);
         }
 
         if do_traffic {
+            incidents::clear_expired_incident(self, current_instant, world);
+            curbside::clear_expired_obstructions(self, current_instant);
+
+            let blocked_at = self.microtraffic.incident.map(|incident| incident.at);
+            let yield_at = self.microtraffic.yield_at.filter(|&yield_at| {
+                ring_traffic_approaching(
+                    &self.connectivity.interactions,
+                    &self.microtraffic.obstacles,
+                    yield_at,
+                )
+            });
+            let has_emergency = self
+                .microtraffic
+                .cars
+                .iter()
+                .any(|car| car.vehicle == VehicleClass::Emergency);
+
             // TODO: optimize using BinaryHeap?
             self.microtraffic
                 .obstacles
@@ -303,12 +616,23 @@ impl Temporal for Lane {
             let mut maybe_next_obstacle = obstacles.next();
 
             for c in 0..self.microtraffic.cars.len() {
-                let next_obstacle = self
-                    .microtraffic
-                    .cars
-                    .get(c + 1)
-                    .map_or(Obstacle::far_ahead(), |car| car.as_obstacle);
+                let (next_obstacle, yielding) = if has_emergency {
+                    (
+                        emergency::next_car_ahead(&self.microtraffic.cars, c)
+                            .map_or(Obstacle::far_ahead(), |car| car.as_obstacle),
+                        emergency::is_yielding(&self.microtraffic.cars, c),
+                    )
+                } else {
+                    (
+                        self.microtraffic
+                            .cars
+                            .get(c + 1)
+                            .map_or(Obstacle::far_ahead(), |car| car.as_obstacle),
+                        false,
+                    )
+                };
                 let car = &mut self.microtraffic.cars[c];
+                let is_emergency = car.vehicle == VehicleClass::Emergency;
                 let next_car_acceleration = intelligent_acceleration(car, &next_obstacle, 2.0);
 
                 maybe_next_obstacle = maybe_next_obstacle.and_then(|obstacle| {
@@ -329,11 +653,24 @@ impl Temporal for Lane {
 
                 car.acceleration = next_car_acceleration.min(next_obstacle_acceleration);
 
+                if yielding {
+                    car.acceleration = car
+                        .acceleration
+                        .min(emergency::yielding_acceleration(car));
+                }
+
+                // emergency vehicles drive through red, after asking for green
                 if let Some(next_hop_interaction) = car.next_hop_interaction {
-                    if let Interaction::Next { green, .. } =
+                    if let Interaction::Next { next, green } =
                         self.connectivity.interactions[next_hop_interaction as usize]
                     {
-                        if !green {
+                        if is_emergency {
+                            if *car.position
+                                > self.construction.length - emergency::PREEMPTION_DISTANCE
+                            {
+                                next.preempt_signal(current_instant, world);
+                            }
+                        } else if !green {
                             car.acceleration = car.acceleration.min(intelligent_acceleration(
                                 car,
                                 &Obstacle {
@@ -346,9 +683,77 @@ impl Temporal for Lane {
                         }
                     }
                 }
+
+                // emergency vehicles don't wait to enter a roundabout either
+                if let (Some(yield_at), false) = (yield_at, is_emergency) {
+                    if *car.position < yield_at {
+                        car.acceleration = car.acceleration.min(intelligent_acceleration(
+                            car,
+                            &Obstacle {
+                                position: OrderedFloat(yield_at),
+                                velocity: 0.0,
+                                max_velocity: 0.0,
+                            },
+                            2.0,
+                        ))
+                    }
+                }
+
+                if let (Some(obstruction), false) = (
+                    curbside::next_obstruction(&self.microtraffic.obstructions, car),
+                    is_emergency,
+                ) {
+                    car.acceleration = car.acceleration.min(match obstruction.passing_velocity() {
+                        Some(passing_velocity)
+                            if *car.position > obstruction.at - curbside::SQUEEZING_DISTANCE =>
+                        {
+                            curbside::squeezing_acceleration(car, passing_velocity)
+                        }
+                        maybe_passing_velocity => intelligent_acceleration(
+                            car,
+                            &Obstacle {
+                                position: OrderedFloat(obstruction.at),
+                                velocity: maybe_passing_velocity.unwrap_or(0.0),
+                                max_velocity: 0.0,
+                            },
+                            2.0,
+                        ),
+                    })
+                }
+
+                // responders drive right up to the incident
+                if let (Some(blocked_at), false) = (blocked_at, is_emergency) {
+                    if *car.position < blocked_at {
+                        car.acceleration = car.acceleration.min(intelligent_acceleration(
+                            car,
+                            &Obstacle {
+                                position: OrderedFloat(blocked_at),
+                                velocity: 0.0,
+                                max_velocity: 0.0,
+                            },
+                            2.0,
+                        ))
+                    }
+                }
             }
         }
 
+        let n_waiting_at_red = self
+            .microtraffic
+            .cars
+            .iter()
+            .filter(|car| {
+                car.velocity < WAITING_VELOCITY
+                    && car.next_hop_interaction.map_or(false, |hop_interaction| {
+                        match self.connectivity.interactions[hop_interaction as usize] {
+                            Interaction::Next { green, .. } => !green,
+                            _ => false,
+                        }
+                    })
+            })
+            .count();
+        self.microtraffic.waited_at_red += n_waiting_at_red as f32 * unslowed_dt;
+
         for car in &mut self.microtraffic.cars {
             *car.position += dt * car.velocity;
             car.velocity = (car.velocity + dt * car.acceleration)
@@ -360,7 +765,15 @@ impl Temporal for Lane {
             *obstacle.position += dt * obstacle.velocity;
         }
 
-        if self.microtraffic.cars.len() > 1 {
+        // emergency vehicles pass the cars that pulled over for them
+        if self
+            .microtraffic
+            .cars
+            .iter()
+            .any(|car| car.vehicle == VehicleClass::Emergency)
+        {
+            self.microtraffic.cars.sort_by_key(|car| car.position);
+        } else if self.microtraffic.cars.len() > 1 {
             for i in (0..self.microtraffic.cars.len() - 1).rev() {
                 self.microtraffic.cars[i].position = OrderedFloat(
                     (*self.microtraffic.cars[i].position)
@@ -372,10 +785,15 @@ impl Temporal for Lane {
         // TODO: move all iteration, updates, etc into one huge retain loop
 
         if let Some(self_as_location) = self.pathfinding.location {
+            let mut parking_at = Vec::new();
+
             self.microtraffic.cars.retain(|car| {
                 if car.destination.location == self_as_location
                     && *car.position >= car.destination.offset
                 {
+                    if car.vehicle != VehicleClass::Emergency {
+                        parking_at.push(*car.position);
+                    }
                     car.trip.finish(
                         TripResult {
                             location_now: None,
@@ -389,10 +807,14 @@ impl Temporal for Lane {
                     true
                 }
             });
+
+            for at in parking_at {
+                curbside::start_parking(self, at, current_instant);
+            }
         }
 
         loop {
-            let maybe_switch_car: Option<(usize, LaneLikeID, f32)> = self
+            let maybe_switch_car: Option<(usize, LaneLikeID, f32, bool)> = self
                 .microtraffic
                 .cars
                 .iter()
@@ -408,14 +830,14 @@ impl Temporal for Lane {
                             start, end, via, ..
                         }) => {
                             if *car.position > start && *car.position > end - 300.0 {
-                                Some((i, via.into(), start))
+                                Some((i, via.into(), start, false))
                             } else {
                                 None
                             }
                         }
                         Some(Interaction::Next { next, .. }) => {
                             if *car.position > self.construction.length {
-                                Some((i, next.into(), self.construction.length))
+                                Some((i, next.into(), self.construction.length, true))
                             } else {
                                 None
                             }
@@ -428,8 +850,14 @@ impl Temporal for Lane {
                 })
                 .next();
 
-            if let Some((idx_to_remove, next_lane, start)) = maybe_switch_car {
+            if let Some((idx_to_remove, next_lane, start, drove_to_end)) = maybe_switch_car {
                 let car = self.microtraffic.cars.remove(idx_to_remove);
+                if drove_to_end {
+                    self.record_travel_time(&car, current_instant);
+                    tolls::charge_toll(self, &car, world);
+                    districts::fine_heavy_traffic(self, &car, world);
+                    incidents::maybe_accident(self, current_instant, world);
+                }
                 next_lane.add_car(
                     car.offset_by(-start),
                     Some(self.id_as()),
@@ -453,6 +881,7 @@ impl Temporal for Lane {
                     interaction,
                     cars,
                     self.microtraffic.obstacles.iter(),
+                    self.microtraffic.yield_at,
                 );
 
                 if let Some(obstacles) = maybe_obstacles {
@@ -740,10 +1169,38 @@ pub fn setup(system: &mut ActorSystem) {
     auto_setup(system);
 }
 
+// how far before where a lane enters a roundabout's ring
+// cars on the ring keep entering cars waiting
+const RING_ENTRY_GAP: f32 = 15.0;
+
+// lanes that overlap from where they start come from the same road
+// and queue up together before entering a roundabout
+fn shares_approach(overlap_start: f32) -> bool {
+    overlap_start < 1.0
+}
+
+fn ring_traffic_approaching(
+    interactions: &[Interaction],
+    obstacles: &[(Obstacle, LaneLikeID)],
+    yield_at: f32,
+) -> bool {
+    obstacles.iter().any(|&(ref obstacle, from)| {
+        *obstacle.position <= yield_at
+            && *obstacle.position > yield_at - RING_ENTRY_GAP
+            && !interactions.iter().any(|interaction| match *interaction {
+                Interaction::Conflicting {
+                    conflicting, start, ..
+                } => conflicting.as_raw() == from.as_raw() && shares_approach(start),
+                _ => false,
+            })
+    })
+}
+
 fn obstacles_for_interaction(
     interaction: &Interaction,
     mut cars: ::std::slice::Iter<LaneCar>,
     self_obstacles_iter: ::std::slice::Iter<(Obstacle, LaneLikeID)>,
+    yield_at: Option<f32>,
 ) -> Option<CVec<Obstacle>> {
     match *interaction {
         Interaction::Conflicting {
@@ -754,15 +1211,21 @@ fn obstacles_for_interaction(
             ..
         } => {
             if can_weave {
+                // cars still waiting to enter a roundabout don't hold up the ones on the ring
+                let entering_before = yield_at
+                    .filter(|_| !shares_approach(start))
+                    .unwrap_or(::std::f32::MIN);
                 Some(
                     cars.skip_while(|car: &&LaneCar| *car.position + 2.0 * car.velocity < start)
                         .take_while(|car: &&LaneCar| *car.position < end)
+                        .filter(|car: &&LaneCar| *car.position >= entering_before)
                         .map(|car| car.as_obstacle.offset_by(-start + conflicting_start))
                         .collect(),
                 )
             } else {
                 let in_overlap = |car: &LaneCar| {
-                    *car.position + 2.0 * car.velocity > start && *car.position - 2.0 < end
+                    *car.position + 2.0 * car.velocity > start
+                        && *car.position - car.vehicle.length() / 2.0 < end
                 };
                 if cars.any(in_overlap) {
                     Some(
@@ -5,6 +5,17 @@ pub mod ui;
 
 pub mod transport_planning;
 pub mod pathfinding;
+pub mod transit;
+pub mod signals;
+pub mod incidents;
+pub mod curbside;
+pub mod tolls;
+pub mod taxis;
+pub mod emergency;
+pub mod parking;
+pub mod pedestrians;
+pub mod cycling;
+pub mod trip_stats;
 
 use kay::{ActorSystem, World};
 use cb_time::actors::TimeID;
@@ -14,9 +25,28 @@ pub fn setup(system: &mut ActorSystem) {
     self::construction::setup(system);
     self::microtraffic::setup(system);
     self::pathfinding::setup(system);
+    self::transit::setup(system);
+    self::signals::setup(system);
+    self::incidents::setup(system);
+    self::curbside::setup(system);
+    self::tolls::setup(system);
+    self::taxis::setup(system);
+    self::emergency::setup(system);
+    self::parking::setup(system);
+    self::pedestrians::setup(system);
+    self::cycling::setup(system);
+    self::trip_stats::setup(system);
     self::ui::setup(system);
 }
 
 pub fn spawn(world: &mut World, time: TimeID) {
     self::pathfinding::spawn(world, time);
+    self::transit::spawn(world, time);
+    self::parking::spawn(world);
+    self::pedestrians::spawn(world);
+    self::cycling::spawn(world);
+    self::tolls::spawn(world);
+    self::taxis::spawn(world, time);
+    self::emergency::spawn(world);
+    self::trip_stats::spawn(world, time);
 }
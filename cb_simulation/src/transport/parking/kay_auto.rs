@@ -0,0 +1,152 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for ParkingRegistry {
+    type ID = ParkingRegistryID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ParkingRegistryID {
+    _raw_id: RawID
+}
+
+impl Copy for ParkingRegistryID {}
+impl Clone for ParkingRegistryID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ParkingRegistryID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ParkingRegistryID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ParkingRegistryID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ParkingRegistryID {
+    fn eq(&self, other: &ParkingRegistryID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ParkingRegistryID {}
+
+impl TypedID for ParkingRegistryID {
+    type Target = ParkingRegistry;
+
+    fn from_raw(id: RawID) -> Self {
+        ParkingRegistryID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl ParkingRegistryID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = ParkingRegistryID::from_raw(world.allocate_instance_id::<ParkingRegistry>());
+        let swarm = world.local_broadcast::<ParkingRegistry>();
+        world.send(swarm, MSG_ParkingRegistry_spawn(id, ));
+        id
+    }
+    
+    pub fn add_lot(self, building: BuildingID, position: P2, capacity: u16, world: &mut World) {
+        world.send(self.as_raw(), MSG_ParkingRegistry_add_lot(building, position, capacity));
+    }
+    
+    pub fn remove_lot(self, building: BuildingID, world: &mut World) {
+        world.send(self.as_raw(), MSG_ParkingRegistry_remove_lot(building));
+    }
+    
+    pub fn estimate_parking(self, destination: P2, requester: ItineraryQueryID, world: &mut World) {
+        world.send(self.as_raw(), MSG_ParkingRegistry_estimate_parking(destination, requester));
+    }
+    
+    pub fn park(self, destination: P2, trip: TripID, owner: Option < TripListenerID >, world: &mut World) {
+        world.send(self.as_raw(), MSG_ParkingRegistry_park(destination, trip, owner));
+    }
+    
+    pub fn unpark(self, owner: TripListenerID, world: &mut World) {
+        world.send(self.as_raw(), MSG_ParkingRegistry_unpark(owner));
+    }
+    
+    pub fn forget_parked_cars(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_ParkingRegistry_forget_parked_cars());
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ParkingRegistry_spawn(pub ParkingRegistryID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ParkingRegistry_add_lot(pub BuildingID, pub P2, pub u16);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ParkingRegistry_remove_lot(pub BuildingID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ParkingRegistry_estimate_parking(pub P2, pub ItineraryQueryID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ParkingRegistry_park(pub P2, pub TripID, pub Option < TripListenerID >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ParkingRegistry_unpark(pub TripListenerID);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ParkingRegistry_forget_parked_cars();
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    
+    system.add_spawner::<ParkingRegistry, _, _>(
+        |&MSG_ParkingRegistry_spawn(id, ), world| {
+            ParkingRegistry::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<ParkingRegistry, _, _>(
+        |&MSG_ParkingRegistry_add_lot(building, position, capacity), instance, world| {
+            instance.add_lot(building, position, capacity, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ParkingRegistry, _, _>(
+        |&MSG_ParkingRegistry_remove_lot(building), instance, world| {
+            instance.remove_lot(building, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ParkingRegistry, _, _>(
+        |&MSG_ParkingRegistry_estimate_parking(destination, requester), instance, world| {
+            instance.estimate_parking(destination, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ParkingRegistry, _, _>(
+        |&MSG_ParkingRegistry_park(destination, trip, owner), instance, world| {
+            instance.park(destination, trip, owner, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ParkingRegistry, _, _>(
+        |&MSG_ParkingRegistry_unpark(owner), instance, world| {
+            instance.unpark(owner, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ParkingRegistry, _, _>(
+        |&MSG_ParkingRegistry_forget_parked_cars(), instance, world| {
+            instance.forget_parked_cars(world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,182 @@
+// Cars don't vanish at the door of where they're going. Buildings advertise
+// how many cars they have room for, a car trip ends at the best free spot
+// around the destination and the rest of the way is walked. The fuller the
+// lots nearby, the longer it takes to find a spot, and when all of them are
+// full, drivers circle until something frees up. People who know that
+// won't consider driving somewhere they can't park at all
+use kay::{ActorSystem, World};
+use compact::CVec;
+use descartes::P2;
+use cb_time::units::Duration;
+use land_use::buildings::BuildingID;
+use super::pathfinding::itinerary::ItineraryQueryID;
+use super::pathfinding::trip::{TripID, TripListenerID, TravelMode};
+use super::transit::walking_time;
+
+// further away than this, a spot isn't worth it
+const MAX_PARKING_DISTANCE: f32 = 600.0;
+const MIN_SEARCH_TIME: Duration = Duration(30);
+// how much longer finding the last free spot of a lot takes
+const FULL_LOT_SEARCH_TIME: Duration = Duration(5 * 60);
+pub const CIRCLING_TIME: Duration = Duration(3 * 60);
+// after that, drivers leave their car wherever they can, as far as anyone would walk
+pub const MAX_CIRCLING_ROUNDS: u8 = 3;
+
+#[derive(Copy, Clone)]
+pub struct ParkingEstimate {
+    pub search: Duration,
+    pub walk: Duration,
+}
+
+impl ParkingEstimate {
+    pub fn total(&self) -> Duration {
+        self.search + self.walk
+    }
+
+    pub fn fallback() -> ParkingEstimate {
+        ParkingEstimate {
+            search: Duration(0),
+            walk: Duration((MAX_PARKING_DISTANCE / TravelMode::Walking.assumed_avg_speed()) as u32),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct ParkingLot {
+    building: BuildingID,
+    position: P2,
+    capacity: u16,
+    occupied: u16,
+}
+
+impl ParkingLot {
+    fn estimate(&self, destination: P2) -> ParkingEstimate {
+        let occupancy = f32::from(self.occupied) / f32::from(self.capacity);
+        ParkingEstimate {
+            search: MIN_SEARCH_TIME
+                + Duration((occupancy * occupancy * FULL_LOT_SEARCH_TIME.as_seconds()) as u32),
+            walk: Duration(walking_time(self.position, destination) as u32),
+        }
+    }
+}
+
+// whose car stands where, so it frees its spot when they drive off again
+#[derive(Copy, Clone)]
+pub struct ParkedCar {
+    owner: TripListenerID,
+    building: BuildingID,
+}
+
+#[derive(Compact, Clone)]
+pub struct ParkingRegistry {
+    id: ParkingRegistryID,
+    lots: CVec<ParkingLot>,
+    parked: CVec<ParkedCar>,
+}
+
+impl ParkingRegistry {
+    pub fn spawn(id: ParkingRegistryID, _: &mut World) -> ParkingRegistry {
+        ParkingRegistry {
+            id,
+            lots: CVec::new(),
+            parked: CVec::new(),
+        }
+    }
+
+    pub fn add_lot(&mut self, building: BuildingID, position: P2, capacity: u16, _: &mut World) {
+        if capacity > 0 {
+            self.lots.push(ParkingLot {
+                building,
+                position,
+                capacity,
+                occupied: 0,
+            });
+        }
+    }
+
+    pub fn remove_lot(&mut self, building: BuildingID, _: &mut World) {
+        self.lots.retain(|lot| lot.building != building);
+        self.parked.retain(|car| car.building != building);
+    }
+
+    fn best_lot(&self, destination: P2) -> Option<usize> {
+        self.lots
+            .iter()
+            .enumerate()
+            .filter(|&(_, lot)| {
+                lot.occupied < lot.capacity
+                    && (lot.position - destination).norm() <= MAX_PARKING_DISTANCE
+            })
+            .min_by_key(|&(_, lot)| lot.estimate(destination).total())
+            .map(|(idx, _)| idx)
+    }
+
+    pub fn estimate_parking(
+        &mut self,
+        destination: P2,
+        requester: ItineraryQueryID,
+        world: &mut World,
+    ) {
+        let maybe_estimate = self
+            .best_lot(destination)
+            .map(|idx| self.lots[idx].estimate(destination));
+        requester.on_parking_estimate(maybe_estimate, world);
+    }
+
+    pub fn park(
+        &mut self,
+        destination: P2,
+        trip: TripID,
+        owner: Option<TripListenerID>,
+        world: &mut World,
+    ) {
+        if let Some(idx) = self.best_lot(destination) {
+            // the estimate is about finding the spot, so before taking it
+            let estimate = self.lots[idx].estimate(destination);
+            // cars nobody will drive off again don't take up a spot
+            if let Some(owner) = owner {
+                self.lots[idx].occupied += 1;
+                self.parked.push(ParkedCar {
+                    owner,
+                    building: self.lots[idx].building,
+                });
+            }
+            trip.on_parked(Some(estimate), world);
+        } else {
+            trip.on_parked(None, world);
+        }
+    }
+
+    pub fn unpark(&mut self, owner: TripListenerID, _: &mut World) {
+        if let Some(idx) = self.parked.iter().position(|car| car.owner == owner) {
+            let car = self.parked.remove(idx);
+            if let Some(lot) = self
+                .lots
+                .iter_mut()
+                .find(|lot| lot.building == car.building)
+            {
+                lot.occupied = lot.occupied.saturating_sub(1);
+            }
+        }
+    }
+
+    // cars belong to households, which are dropped from savegames that can't be fully loaded
+    pub fn forget_parked_cars(&mut self, _: &mut World) {
+        self.parked.clear();
+        for lot in self.lots.iter_mut() {
+            lot.occupied = 0;
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<ParkingRegistry>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    ParkingRegistryID::spawn(world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
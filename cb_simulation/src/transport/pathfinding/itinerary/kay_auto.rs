@@ -0,0 +1,248 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ItineraryRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for ItineraryRequesterID {}
+impl Clone for ItineraryRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ItineraryRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ItineraryRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ItineraryRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ItineraryRequesterID {
+    fn eq(&self, other: &ItineraryRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ItineraryRequesterID {}
+
+pub struct ItineraryRequesterRepresentative;
+
+impl ActorOrActorTrait for ItineraryRequesterRepresentative {
+    type ID = ItineraryRequesterID;
+}
+
+impl TypedID for ItineraryRequesterID {
+    type Target = ItineraryRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        ItineraryRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + ItineraryRequester> TraitIDFrom<Act> for ItineraryRequesterID {}
+
+impl ItineraryRequesterID {
+    pub fn on_itinerary(self, maybe_itinerary: COption < Itinerary >, world: &mut World) {
+        world.send(self.as_raw(), MSG_ItineraryRequester_on_itinerary(maybe_itinerary));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<ItineraryRequesterRepresentative>();
+        system.register_trait_message::<MSG_ItineraryRequester_on_itinerary>();
+    }
+
+    pub fn register_implementor<Act: Actor + ItineraryRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, ItineraryRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_ItineraryRequester_on_itinerary(ref maybe_itinerary), instance, world| {
+                instance.on_itinerary(maybe_itinerary, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ItineraryRequester_on_itinerary(pub COption < Itinerary >);
+
+impl Actor for ItineraryQuery {
+    type ID = ItineraryQueryID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ItineraryQueryID {
+    _raw_id: RawID
+}
+
+impl Copy for ItineraryQueryID {}
+impl Clone for ItineraryQueryID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ItineraryQueryID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ItineraryQueryID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ItineraryQueryID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ItineraryQueryID {
+    fn eq(&self, other: &ItineraryQueryID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ItineraryQueryID {}
+
+impl TypedID for ItineraryQueryID {
+    type Target = ItineraryQuery;
+
+    fn from_raw(id: RawID) -> Self {
+        ItineraryQueryID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl ItineraryQueryID {
+    pub fn spawn(requester: ItineraryRequesterID, rough_source: RoughLocationID, rough_destination: RoughLocationID, modes: ModeSet, instant: Instant, world: &mut World) -> Self {
+        let id = ItineraryQueryID::from_raw(world.allocate_instance_id::<ItineraryQuery>());
+        let swarm = world.local_broadcast::<ItineraryQuery>();
+        world.send(swarm, MSG_ItineraryQuery_spawn(id, requester, rough_source, rough_destination, modes, instant));
+        id
+    }
+    
+    pub fn on_ride_estimate(self, maybe_estimate: Option < RideEstimate >, world: &mut World) {
+        world.send(self.as_raw(), MSG_ItineraryQuery_on_ride_estimate(maybe_estimate));
+    }
+    
+    pub fn on_parking_estimate(self, maybe_estimate: Option < ParkingEstimate >, world: &mut World) {
+        world.send(self.as_raw(), MSG_ItineraryQuery_on_parking_estimate(maybe_estimate));
+    }
+    
+    pub fn on_walk_estimate(self, estimate: WalkEstimate, world: &mut World) {
+        world.send(self.as_raw(), MSG_ItineraryQuery_on_walk_estimate(estimate));
+    }
+    
+    pub fn on_bike_estimate(self, estimate: BikeEstimate, world: &mut World) {
+        world.send(self.as_raw(), MSG_ItineraryQuery_on_bike_estimate(estimate));
+    }
+    
+    pub fn on_taxi_estimate(self, maybe_pickup: Option < Duration >, world: &mut World) {
+        world.send(self.as_raw(), MSG_ItineraryQuery_on_taxi_estimate(maybe_pickup));
+    }
+    
+    pub fn on_park_and_ride_estimate(self, maybe_estimate: Option < ParkAndRideEstimate >, world: &mut World) {
+        world.send(self.as_raw(), MSG_ItineraryQuery_on_park_and_ride_estimate(maybe_estimate));
+    }
+    
+    pub fn done(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_ItineraryQuery_done());
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ItineraryQuery_spawn(pub ItineraryQueryID, pub ItineraryRequesterID, pub RoughLocationID, pub RoughLocationID, pub ModeSet, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ItineraryQuery_on_ride_estimate(pub Option < RideEstimate >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ItineraryQuery_on_parking_estimate(pub Option < ParkingEstimate >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ItineraryQuery_on_walk_estimate(pub WalkEstimate);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ItineraryQuery_on_bike_estimate(pub BikeEstimate);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ItineraryQuery_on_taxi_estimate(pub Option < Duration >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ItineraryQuery_on_park_and_ride_estimate(pub Option < ParkAndRideEstimate >);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ItineraryQuery_done();
+
+impl Into<LocationRequesterID> for ItineraryQueryID {
+    fn into(self) -> LocationRequesterID {
+        LocationRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<DistanceRequesterID> for ItineraryQueryID {
+    fn into(self) -> DistanceRequesterID {
+        DistanceRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<PositionRequesterID> for ItineraryQueryID {
+    fn into(self) -> PositionRequesterID {
+        PositionRequesterID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    ItineraryRequesterID::register_trait(system);
+    LocationRequesterID::register_implementor::<ItineraryQuery>(system);
+    DistanceRequesterID::register_implementor::<ItineraryQuery>(system);
+    PositionRequesterID::register_implementor::<ItineraryQuery>(system);
+    system.add_spawner::<ItineraryQuery, _, _>(
+        |&MSG_ItineraryQuery_spawn(id, requester, rough_source, rough_destination, modes, instant), world| {
+            ItineraryQuery::spawn(id, requester, rough_source, rough_destination, modes, instant, world)
+        }, false
+    );
+    
+    system.add_handler::<ItineraryQuery, _, _>(
+        |&MSG_ItineraryQuery_on_ride_estimate(maybe_estimate), instance, world| {
+            instance.on_ride_estimate(maybe_estimate, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ItineraryQuery, _, _>(
+        |&MSG_ItineraryQuery_on_parking_estimate(maybe_estimate), instance, world| {
+            instance.on_parking_estimate(maybe_estimate, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ItineraryQuery, _, _>(
+        |&MSG_ItineraryQuery_on_walk_estimate(estimate), instance, world| {
+            instance.on_walk_estimate(estimate, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ItineraryQuery, _, _>(
+        |&MSG_ItineraryQuery_on_bike_estimate(estimate), instance, world| {
+            instance.on_bike_estimate(estimate, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ItineraryQuery, _, _>(
+        |&MSG_ItineraryQuery_on_taxi_estimate(maybe_pickup), instance, world| {
+            instance.on_taxi_estimate(maybe_pickup, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ItineraryQuery, _, _>(
+        |&MSG_ItineraryQuery_on_park_and_ride_estimate(maybe_estimate), instance, world| {
+            instance.on_park_and_ride_estimate(maybe_estimate, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ItineraryQuery, _, _>(
+        |&MSG_ItineraryQuery_done(), instance, world| {
+            instance.done(world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,530 @@
+// Before committing to going somewhere, people compare the ways they could get
+// there. A query resolves both ends, asks the road network how far it is,
+// the transit network whether there's a ride, also from a station with free
+// parking for those with a car, drivers where they could park,
+// pedestrians how walkable the way is, cyclists whether there are bike lanes
+// and the taxi dispatcher how long a taxi
+// would take to come, and answers with the itinerary of the mode that costs
+// the least, as time weighted by how unpleasant it is plus what it costs to pay.
+// Walking and cycling are less pleasant in bad weather
+use kay::{ActorSystem, World, Fate, TypedID, Actor};
+use compact::{CVec, COption};
+use descartes::P2;
+use cb_time::units::{Instant, Duration};
+use super::{PreciseLocation, RoughLocationID, LocationRequester, LocationRequesterID,
+DistanceRequester, DistanceRequesterID, PositionRequester, PositionRequesterID};
+use super::trip::{TravelMode, ALL_TRAVEL_MODES};
+use super::super::transit::{TransitNetworkID, RideEstimate, ParkAndRideEstimate};
+use super::super::parking::{ParkingRegistryID, ParkingEstimate};
+use super::super::pedestrians::{PedestrianNetworkID, WalkEstimate};
+use super::super::cycling::{CyclingNetworkID, BikeEstimate};
+use super::super::taxis::{self, TaxiDispatcherID};
+use super::super::tolls::SECONDS_PER_TOLL_UNIT;
+use environment::weather::Weather;
+
+// how much worse walking without a sidewalk is than walking on one
+const UNSAFE_WALKING_PENALTY: f32 = 1.0;
+// cyclists without a bike lane are right in the traffic
+const UNSAFE_CYCLING_PENALTY: f32 = 1.5;
+
+fn unsafe_penalty(mode: TravelMode) -> f32 {
+    match mode {
+        TravelMode::Bike => UNSAFE_CYCLING_PENALTY,
+        _ => UNSAFE_WALKING_PENALTY,
+    }
+}
+
+// The modes of travel someone has at hand
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ModeSet(u8);
+
+impl ModeSet {
+    pub fn only(mode: TravelMode) -> ModeSet {
+        ModeSet(1 << mode as u8)
+    }
+
+    pub fn with(self, mode: TravelMode) -> ModeSet {
+        ModeSet(self.0 | ModeSet::only(mode).0)
+    }
+
+    pub fn contains(self, mode: TravelMode) -> bool {
+        self.0 & ModeSet::only(mode).0 != 0
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Leg {
+    pub mode: TravelMode,
+    pub duration: Duration,
+    // share of a walk without a sidewalk or of a bike ride without a bike lane
+    pub unsafe_share: f32,
+}
+
+impl Leg {
+    fn new(mode: TravelMode, duration: Duration) -> Leg {
+        Leg {
+            mode,
+            duration,
+            unsafe_share: 0.0,
+        }
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct Itinerary {
+    // the mode a trip following the itinerary is started with
+    pub mode: TravelMode,
+    pub legs: CVec<Leg>,
+    // money paid on the way
+    pub fare: f32,
+}
+
+impl Itinerary {
+    fn single_leg(mode: TravelMode, distance: f32) -> Itinerary {
+        Itinerary {
+            mode,
+            legs: vec![Leg::new(
+                mode,
+                Duration((distance / mode.assumed_avg_speed()) as u32),
+            )]
+            .into(),
+            fare: 0.0,
+        }
+    }
+
+    fn by_car(distance: f32, parking: ParkingEstimate) -> Itinerary {
+        let mut itinerary = Itinerary::single_leg(TravelMode::Car, distance);
+        itinerary
+            .legs
+            .push(Leg::new(TravelMode::Car, parking.search));
+        itinerary
+            .legs
+            .push(Leg::new(TravelMode::Walking, parking.walk));
+        itinerary
+    }
+
+    fn on_foot(estimate: WalkEstimate) -> Itinerary {
+        Itinerary {
+            mode: TravelMode::Walking,
+            legs: vec![Leg {
+                mode: TravelMode::Walking,
+                duration: estimate.duration,
+                unsafe_share: estimate.unsafe_share,
+            }]
+            .into(),
+            fare: 0.0,
+        }
+    }
+
+    fn by_bike(estimate: BikeEstimate) -> Itinerary {
+        Itinerary {
+            mode: TravelMode::Bike,
+            legs: vec![Leg {
+                mode: TravelMode::Bike,
+                duration: estimate.duration,
+                unsafe_share: estimate.unsafe_share,
+            }]
+            .into(),
+            fare: 0.0,
+        }
+    }
+
+    fn by_transit(estimate: RideEstimate) -> Itinerary {
+        Itinerary {
+            mode: TravelMode::Transit,
+            legs: vec![
+                Leg::new(TravelMode::Walking, estimate.walk_to_stop),
+                Leg::new(TravelMode::Transit, estimate.wait_and_ride),
+                Leg::new(TravelMode::Walking, estimate.walk_from_stop),
+            ]
+            .into(),
+            fare: 0.0,
+        }
+    }
+
+    fn by_park_and_ride(estimate: ParkAndRideEstimate) -> Itinerary {
+        Itinerary {
+            mode: TravelMode::ParkAndRide,
+            legs: vec![
+                Leg::new(TravelMode::Car, estimate.drive),
+                Leg::new(TravelMode::Walking, estimate.transfer),
+                Leg::new(TravelMode::Transit, estimate.wait_and_ride),
+                Leg::new(TravelMode::Walking, estimate.walk_from_stop),
+            ]
+            .into(),
+            fare: 0.0,
+        }
+    }
+
+    fn by_taxi(distance: f32, pickup: Duration, fare: f32) -> Itinerary {
+        let ride = Duration((distance / TravelMode::Taxi.assumed_avg_speed()) as u32);
+        Itinerary {
+            mode: TravelMode::Taxi,
+            legs: vec![
+                Leg::new(TravelMode::Taxi, pickup),
+                Leg::new(TravelMode::Taxi, ride),
+            ]
+            .into(),
+            fare,
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration(self.legs.iter().map(|leg| leg.duration.0).sum())
+    }
+
+    pub fn cost(&self, weather: Weather) -> f32 {
+        self.legs
+            .iter()
+            .map(|leg| {
+                leg.duration.as_seconds()
+                    * leg.mode.cost_factor()
+                    * (1.0
+                        + unsafe_penalty(leg.mode) * leg.unsafe_share
+                        + weather.exposure_penalty(leg.mode))
+            })
+            .sum::<f32>()
+            + self.fare * SECONDS_PER_TOLL_UNIT
+    }
+}
+
+pub trait ItineraryRequester {
+    fn on_itinerary(&mut self, maybe_itinerary: &COption<Itinerary>, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct ItineraryQuery {
+    id: ItineraryQueryID,
+    requester: ItineraryRequesterID,
+    modes: ModeSet,
+    rough_source: RoughLocationID,
+    rough_destination: RoughLocationID,
+    source: Option<PreciseLocation>,
+    destination: Option<PreciseLocation>,
+    n_locations_resolved: u8,
+    source_position: Option<P2>,
+    destination_position: Option<P2>,
+    distance: Option<f32>,
+    ride: Option<RideEstimate>,
+    parking: Option<ParkingEstimate>,
+    walk: Option<WalkEstimate>,
+    bike: Option<BikeEstimate>,
+    taxi_pickup: Option<Duration>,
+    park_and_ride: Option<ParkAndRideEstimate>,
+    weather: Weather,
+    n_pending: u8,
+}
+
+impl ItineraryQuery {
+    pub fn spawn(
+        id: ItineraryQueryID,
+        requester: ItineraryRequesterID,
+        rough_source: RoughLocationID,
+        rough_destination: RoughLocationID,
+        modes: ModeSet,
+        instant: Instant,
+        world: &mut World,
+    ) -> ItineraryQuery {
+        // a trip that goes nowhere only needs its one end resolved
+        let both_ends = rough_destination != rough_source;
+        rough_source.resolve_as_location(id.into(), rough_source, instant, world);
+        if both_ends {
+            rough_destination.resolve_as_location(id.into(), rough_destination, instant, world);
+        }
+
+        // the road distance is always needed, a ride only for those who can take one,
+        // a parking spot only for those who can drive, a walk only for those who can walk,
+        // a bike ride only for those with a bike, a taxi only for those who would call one
+        // and a station to park at only for those who would drive there
+        let mut n_pending = 1;
+        let by_transit = modes.contains(TravelMode::Transit);
+        let by_car = modes.contains(TravelMode::Car);
+        let on_foot = modes.contains(TravelMode::Walking);
+        let by_bike = modes.contains(TravelMode::Bike);
+        let by_taxi = modes.contains(TravelMode::Taxi);
+        let by_park_and_ride = modes.contains(TravelMode::ParkAndRide);
+        if by_transit || by_car || on_foot || by_bike || by_taxi || by_park_and_ride {
+            rough_source.resolve_as_position(id.into(), rough_source, world);
+            if both_ends {
+                rough_destination.resolve_as_position(id.into(), rough_destination, world);
+            }
+        }
+        if by_transit {
+            n_pending += 1;
+        }
+        if by_car {
+            n_pending += 1;
+        }
+        if on_foot {
+            n_pending += 1;
+        }
+        if by_bike {
+            n_pending += 1;
+        }
+        if by_taxi {
+            n_pending += 1;
+        }
+        if by_park_and_ride {
+            n_pending += 1;
+        }
+
+        ItineraryQuery {
+            id,
+            requester,
+            modes,
+            rough_source,
+            rough_destination,
+            source: None,
+            destination: None,
+            n_locations_resolved: 0,
+            source_position: None,
+            destination_position: None,
+            distance: None,
+            ride: None,
+            parking: None,
+            walk: None,
+            bike: None,
+            taxi_pickup: None,
+            park_and_ride: None,
+            weather: Weather::on(instant),
+            n_pending,
+        }
+    }
+
+    pub fn on_ride_estimate(&mut self, maybe_estimate: Option<RideEstimate>, world: &mut World) {
+        self.ride = maybe_estimate;
+        self.answered(world);
+    }
+
+    pub fn on_parking_estimate(
+        &mut self,
+        maybe_estimate: Option<ParkingEstimate>,
+        world: &mut World,
+    ) {
+        self.parking = maybe_estimate;
+        self.answered(world);
+    }
+
+    pub fn on_walk_estimate(&mut self, estimate: WalkEstimate, world: &mut World) {
+        self.walk = Some(estimate);
+        self.answered(world);
+    }
+
+    pub fn on_bike_estimate(&mut self, estimate: BikeEstimate, world: &mut World) {
+        self.bike = Some(estimate);
+        self.answered(world);
+    }
+
+    pub fn on_taxi_estimate(&mut self, maybe_pickup: Option<Duration>, world: &mut World) {
+        self.taxi_pickup = maybe_pickup;
+        self.answered(world);
+    }
+
+    pub fn on_park_and_ride_estimate(
+        &mut self,
+        maybe_estimate: Option<ParkAndRideEstimate>,
+        world: &mut World,
+    ) {
+        self.park_and_ride = maybe_estimate;
+        self.answered(world);
+    }
+
+    fn answered(&mut self, world: &mut World) {
+        self.n_pending -= 1;
+        if self.n_pending > 0 {
+            return;
+        }
+
+        let mut candidates = Vec::new();
+        if let Some(distance) = self.distance {
+            for &mode in &ALL_TRAVEL_MODES {
+                let in_range = mode
+                    .max_distance()
+                    .map_or(true, |max_distance| distance <= max_distance);
+                if !self.modes.contains(mode) || !in_range {
+                    continue;
+                }
+                match mode {
+                    TravelMode::Transit => {}
+                    TravelMode::ParkAndRide => {
+                        if let Some(park_and_ride) = self.park_and_ride {
+                            candidates.push(Itinerary::by_park_and_ride(park_and_ride));
+                        }
+                    }
+                    // nobody drives where there's nowhere to park
+                    TravelMode::Car => {
+                        if let Some(parking) = self.parking {
+                            candidates.push(Itinerary::by_car(distance, parking));
+                        }
+                    }
+                    TravelMode::Walking => {
+                        if let Some(walk) = self.walk {
+                            candidates.push(Itinerary::on_foot(walk));
+                        }
+                    }
+                    TravelMode::Bike => {
+                        if let Some(bike) = self.bike {
+                            candidates.push(Itinerary::by_bike(bike));
+                        }
+                    }
+                    TravelMode::Taxi => {
+                        if let (Some(pickup), Some(from), Some(to)) =
+                            (self.taxi_pickup, self.source_position, self.destination_position)
+                        {
+                            candidates.push(Itinerary::by_taxi(
+                                distance,
+                                pickup,
+                                taxis::fare_for(from, to),
+                            ));
+                        }
+                    }
+                    _ => candidates.push(Itinerary::single_leg(mode, distance)),
+                }
+            }
+        }
+        if let Some(ride) = self.ride {
+            candidates.push(Itinerary::by_transit(ride));
+        }
+
+        // walking to and from stops and parking spots is about as safe as walking there
+        if let Some(walk) = self.walk {
+            for candidate in &mut candidates {
+                for leg in candidate.legs.iter_mut() {
+                    if leg.mode == TravelMode::Walking {
+                        leg.unsafe_share = walk.unsafe_share;
+                    }
+                }
+            }
+        }
+
+        let best = candidates
+            .into_iter()
+            .min_by(|a, b| {
+                a.cost(self.weather)
+                    .partial_cmp(&b.cost(self.weather))
+                    .unwrap()
+            });
+        self.requester.on_itinerary(COption(best), world);
+        self.id.done(world);
+    }
+
+    pub fn done(&mut self, _: &mut World) -> Fate {
+        Fate::Die
+    }
+}
+
+impl LocationRequester for ItineraryQuery {
+    fn location_resolved(
+        &mut self,
+        rough_location: RoughLocationID,
+        location: Option<PreciseLocation>,
+        _tick: Instant,
+        world: &mut World,
+    ) {
+        if rough_location == self.rough_source {
+            self.source = location;
+        }
+        if rough_location == self.rough_destination {
+            self.destination = location;
+        }
+        self.n_locations_resolved += 1;
+
+        let n_ends = if self.rough_source == self.rough_destination {
+            1
+        } else {
+            2
+        };
+        if self.n_locations_resolved == n_ends {
+            if let (Some(source), Some(destination)) = (self.source, self.destination) {
+                source
+                    .link
+                    .get_distance_to(destination.location, self.id_as(), world);
+            } else {
+                self.answered(world);
+            }
+        }
+    }
+}
+
+impl DistanceRequester for ItineraryQuery {
+    fn on_distance(&mut self, maybe_distance: Option<f32>, world: &mut World) {
+        self.distance = maybe_distance;
+        self.answered(world);
+    }
+}
+
+impl PositionRequester for ItineraryQuery {
+    fn position_resolved(
+        &mut self,
+        rough_location: RoughLocationID,
+        position: P2,
+        world: &mut World,
+    ) {
+        if rough_location == self.rough_source {
+            self.source_position = Some(position);
+        }
+        if rough_location == self.rough_destination {
+            self.destination_position = Some(position);
+        }
+
+        if let (Some(source_position), Some(destination_position)) =
+            (self.source_position, self.destination_position)
+        {
+            if self.modes.contains(TravelMode::Transit) {
+                TransitNetworkID::global_first(world).estimate_ride(
+                    source_position,
+                    destination_position,
+                    self.id,
+                    world,
+                );
+            }
+            if self.modes.contains(TravelMode::Car) {
+                ParkingRegistryID::global_first(world).estimate_parking(
+                    destination_position,
+                    self.id,
+                    world,
+                );
+            }
+            if self.modes.contains(TravelMode::Walking) {
+                PedestrianNetworkID::global_first(world).estimate_walk(
+                    source_position,
+                    destination_position,
+                    self.id,
+                    world,
+                );
+            }
+            if self.modes.contains(TravelMode::Bike) {
+                CyclingNetworkID::global_first(world).estimate_bike_ride(
+                    source_position,
+                    destination_position,
+                    self.id,
+                    world,
+                );
+            }
+            if self.modes.contains(TravelMode::ParkAndRide) {
+                TransitNetworkID::global_first(world).estimate_park_and_ride(
+                    source_position,
+                    destination_position,
+                    self.id,
+                    world,
+                );
+            }
+            if self.modes.contains(TravelMode::Taxi) {
+                TaxiDispatcherID::global_first(world).estimate_pickup(
+                    self.rough_source,
+                    source_position,
+                    self.id,
+                    world,
+                );
+            }
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<ItineraryQuery>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
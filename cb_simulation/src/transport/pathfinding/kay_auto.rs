@@ -50,10 +50,6 @@ impl TypedID for LinkID {
 impl<Act: Actor + Link> TraitIDFrom<Act> for LinkID {}
 
 impl LinkID {
-    pub fn after_route_forgotten(self, forgotten_route: Location, world: &mut World) {
-        world.send(self.as_raw(), MSG_Link_after_route_forgotten(forgotten_route));
-    }
-    
     pub fn pathfinding_tick(self, world: &mut World) {
         world.send(self.as_raw(), MSG_Link_pathfinding_tick());
     }
@@ -88,7 +84,6 @@ impl LinkID {
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<LinkRepresentative>();
-        system.register_trait_message::<MSG_Link_after_route_forgotten>();
         system.register_trait_message::<MSG_Link_pathfinding_tick>();
         system.register_trait_message::<MSG_Link_query_routes>();
         system.register_trait_message::<MSG_Link_on_routes>();
@@ -101,12 +96,6 @@ impl LinkID {
 
     pub fn register_implementor<Act: Actor + Link>(system: &mut ActorSystem) {
         system.register_implementor::<Act, LinkRepresentative>();
-        system.add_handler::<Act, _, _>(
-            |&MSG_Link_after_route_forgotten(forgotten_route), instance, world| {
-                instance.after_route_forgotten(forgotten_route, world); Fate::Live
-            }, false
-        );
-        
         system.add_handler::<Act, _, _>(
             |&MSG_Link_pathfinding_tick(), instance, world| {
                 instance.pathfinding_tick(world); Fate::Live
@@ -157,8 +146,6 @@ impl LinkID {
     }
 }
 
-#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Link_after_route_forgotten(pub Location);
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Link_pathfinding_tick();
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
@@ -4,11 +4,14 @@ use descartes::{P2};
 use cb_time::units::Instant;
 
 pub mod trip;
+pub mod itinerary;
 pub mod road_pathfinding;
+pub mod overlay;
 
 const LOG_T: &str = "Pathfinding";
 
 use cb_util::log::{debug};
+use self::overlay::{RoutingOverlayID, OverlayEdge};
 
 pub trait Link: Actor {
     fn core(&self) -> &PathfindingCore;
@@ -22,16 +25,93 @@ pub trait Link: Actor {
     fn successors(&self) -> Vec<LinkConnection>;
     fn predecessors(&self) -> Vec<LinkConnection>;
 
-    fn after_route_forgotten(&mut self, forgotten_route: Location, world: &mut World);
-
     fn on_connect(&mut self) {
         self.core_mut().routing_timeout = ROUTING_TIMEOUT_AFTER_CHANGE;
     }
 
+    // Connections were removed, so the remaining routes are pointed at where their
+    // next link is now. Only routes through a link that isn't connected anymore
+    // are forgotten, and so are the routes of predecessors that learned them from here
     fn on_disconnect(&mut self) {
-        self.core_mut().routes = CHashMap::new();
-        self.core_mut().routes_changed = true;
-        self.core_mut().query_routes_next_tick = true;
+        let remapped: Vec<(Location, StoredRoutingEntry, Option<usize>)> = self
+            .core()
+            .routes
+            .pairs()
+            .map(|(&destination, &entry)| {
+                (
+                    destination,
+                    entry,
+                    self.map_connected_link_to_idx(entry.learned_from),
+                )
+            })
+            .collect();
+
+        for (destination, entry, maybe_idx) in remapped {
+            if let Some(idx) = maybe_idx {
+                self.core_mut().routes.insert(
+                    destination,
+                    StoredRoutingEntry {
+                        outgoing_idx: idx as u8,
+                        ..entry
+                    },
+                );
+            } else {
+                self.core_mut().routes.remove(destination);
+                self.core_mut().tell_to_forget_next_tick.push(destination);
+            }
+        }
+
+        let core = self.core_mut();
+        core.routes_version = core.routes_version.wrapping_add(1);
+        core.routes_changed = true;
+        core.query_routes_next_tick = true;
+    }
+
+    // A closed link keeps its routes, so whatever is on it can still leave,
+    // but it stops offering them and predecessors forget all routes through it
+    fn on_closed(&mut self) {
+        if !self.core().is_closed() {
+            self.forget_routes_through();
+        }
+        self.core_mut().closed = true;
+    }
+
+    fn on_reopened(&mut self) {
+        let core = self.core_mut();
+        core.closed = false;
+        if !core.is_closed() {
+            core.routes_changed = true;
+        }
+    }
+
+    // Links outside of the city's territory are closed in the same way,
+    // independently of whether they are closed for any other reason
+    fn set_outside_territory(&mut self, outside_territory: bool) {
+        if outside_territory == self.core().outside_territory {
+            return;
+        }
+        if outside_territory && !self.core().is_closed() {
+            self.forget_routes_through();
+        }
+        let core = self.core_mut();
+        core.outside_territory = outside_territory;
+        if !core.is_closed() {
+            core.routes_changed = true;
+        }
+    }
+
+    fn forget_routes_through(&mut self) {
+        let forget: Vec<Location> = self
+            .core()
+            .routes
+            .keys()
+            .cloned()
+            .chain(self.self_as_route().map(|(location, _)| location))
+            .collect();
+        let core = self.core_mut();
+        for location in forget {
+            core.tell_to_forget_next_tick.push(location);
+        }
     }
 
     fn pathfinding_tick(&mut self, world: &mut World) {
@@ -60,6 +140,9 @@ pub trait Link: Actor {
                 query_routes_next_tick: false,
                 tell_to_forget_next_tick: CVec::new(),
                 routing_timeout: ROUTING_TIMEOUT_AFTER_CHANGE,
+                routes_version: self.core().routes_version,
+                closed: self.core().closed,
+                outside_territory: self.core().outside_territory,
                 attachees: self.core().attachees.clone(),
             }
         }
@@ -91,12 +174,34 @@ pub trait Link: Actor {
                 for predecessor in self.predecessors() {
                     self.query_routes(predecessor.link, predecessor.connection_cost, world);
                 }
+                if let Some(location) = self.core().location.filter(Location::is_landmark) {
+                    let edges = self
+                        .core()
+                        .routes
+                        .pairs()
+                        .filter(|&(destination, _)| {
+                            destination.is_landmark() && destination.landmark != location.landmark
+                        })
+                        .map(|(destination, entry)| OverlayEdge {
+                            to: destination.landmark,
+                            distance: entry.distance,
+                        })
+                        .collect();
+                    RoutingOverlayID::global_first(world).update_landmark(
+                        self.id_as(),
+                        edges,
+                        world,
+                    );
+                }
                 self.core_mut().routes_changed = false;
             }
         }
     }
 
     fn query_routes(&mut self, requester: LinkID, connection_cost: f32, world: &mut World) {
+        if self.core().is_closed() {
+            return;
+        }
         requester.on_routes(
             self.core()
                 .routes
@@ -132,7 +237,7 @@ pub trait Link: Actor {
                 },
             ) in new_routes.pairs()
             {
-                if destination.is_landmark()
+                if (destination.is_landmark() && new_distance_hops <= LOCAL_LANDMARK_HOPS)
                     || new_distance_hops <= IDEAL_LANDMARK_RADIUS
                     || self
                         .core()
@@ -140,11 +245,15 @@ pub trait Link: Actor {
                         .map(|self_dest| self_dest.landmark == destination.landmark)
                         .unwrap_or(false)
                 {
+                    // the link a route was learned from is also believed when it got longer
                     let insert = self
                         .core()
                         .routes
                         .get(destination)
-                        .map(|&StoredRoutingEntry { distance, .. }| new_distance < distance)
+                        .map(|&StoredRoutingEntry { distance, learned_from, .. }| {
+                            new_distance < distance
+                                || (learned_from == from && new_distance != distance)
+                        })
                         .unwrap_or(true);
                     if insert {
                         self.core_mut().routes.insert(
@@ -170,8 +279,10 @@ pub trait Link: Actor {
         }
     }
 
-    fn forget_routes(&mut self, forget: &CVec<Location>, from: LinkID, world: &mut World) {
-        let mut forgotten_routes = CVec::<Location>::new();
+    // Cars routed with the forgotten routes notice the new version and
+    // look for another way, alternatives are asked for from successors
+    fn forget_routes(&mut self, forget: &CVec<Location>, from: LinkID, _: &mut World) {
+        let mut forgot_any = false;
         for &destination_to_forget in forget.iter() {
             let forget = if let Some(routing_info) = self.core().routes.get(destination_to_forget) {
                 routing_info.learned_from == from
@@ -180,11 +291,17 @@ pub trait Link: Actor {
             };
             if forget {
                 self.core_mut().routes.remove(destination_to_forget);
-                self.after_route_forgotten(destination_to_forget, world);
-                forgotten_routes.push(destination_to_forget);
+                self.core_mut()
+                    .tell_to_forget_next_tick
+                    .push(destination_to_forget);
+                forgot_any = true;
             }
         }
-        self.core_mut().tell_to_forget_next_tick = forgotten_routes;
+        if forgot_any {
+            let core = self.core_mut();
+            core.routes_version = core.routes_version.wrapping_add(1);
+            core.query_routes_next_tick = true;
+        }
     }
 
     fn join_landmark(
@@ -226,6 +343,10 @@ pub trait Link: Actor {
                 attachee.location_changed(self.core().location, Some(join_as), world);
             }
 
+            if self.core().location.map_or(false, |location| location.is_landmark()) {
+                RoutingOverlayID::global_first(world).remove_landmark(self.id_as(), world);
+            }
+
             *self.core_mut() = PathfindingCore {
                 location: Some(join_as),
                 learned_landmark_from: Some(from),
@@ -235,6 +356,9 @@ pub trait Link: Actor {
                 query_routes_next_tick: true,
                 tell_to_forget_next_tick,
                 routing_timeout: ROUTING_TIMEOUT_AFTER_CHANGE,
+                routes_version: self.core().routes_version,
+                closed: self.core().closed,
+                outside_territory: self.core().outside_territory,
                 attachees: self.core().attachees.clone(),
             };
         }
@@ -252,7 +376,15 @@ pub trait Link: Actor {
             .get(destination)
             .or_else(|| self.core().routes.get(destination.landmark_destination()))
             .map(|routing_info| routing_info.distance);
-        requester.on_distance(maybe_distance, world);
+        match (maybe_distance, self.core().location) {
+            (None, Some(location)) => RoutingOverlayID::global_first(world).get_distance(
+                location,
+                destination,
+                requester,
+                world,
+            ),
+            _ => requester.on_distance(maybe_distance, world),
+        }
     }
 
     fn add_attachee(&mut self, attachee: AttacheeID, _: &mut World) {
@@ -280,9 +412,22 @@ pub struct PathfindingCore {
     pub tell_to_forget_next_tick: CVec<Location>,
     pub query_routes_next_tick: bool,
     pub routing_timeout: u16,
+    // bumped whenever routes are forgotten or point elsewhere,
+    // so cars routed with an older version know to look again
+    pub routes_version: u16,
+    // see `on_closed`
+    pub closed: bool,
+    // see `set_outside_territory`
+    pub outside_territory: bool,
     attachees: CVec<AttacheeID>,
 }
 
+impl PathfindingCore {
+    pub fn is_closed(&self) -> bool {
+        self.closed || self.outside_territory
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Location {
     pub landmark: LinkID,
@@ -310,7 +455,7 @@ impl ::std::ops::DerefMut for PreciseLocation {
 }
 
 impl Location {
-    fn landmark(landmark: LinkID) -> Self {
+    pub fn landmark(landmark: LinkID) -> Self {
         Location {
             landmark,
             link: landmark,
@@ -348,6 +493,8 @@ pub struct CommunicatedRoutingEntry {
 }
 
 const IDEAL_LANDMARK_RADIUS: u8 = 3;
+// landmarks further away are only reached through the routing overlay
+const LOCAL_LANDMARK_HOPS: u8 = 30;
 const MIN_LANDMARK_INCOMING: usize = 3;
 const ROUTING_TIMEOUT_AFTER_CHANGE: u16 = 15;
 
@@ -420,12 +567,15 @@ use cb_time::actors::TimeID;
 
 pub fn setup(system: &mut ActorSystem) {
     trip::setup(system);
+    itinerary::setup(system);
     road_pathfinding::auto_setup(system);
+    overlay::setup(system);
     auto_setup(system);
 }
 
 pub fn spawn(world: &mut World, time: TimeID) {
     trip::spawn(world, time);
+    overlay::spawn(world, time);
 }
 
 mod kay_auto;
@@ -0,0 +1,133 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for RoutingOverlay {
+    type ID = RoutingOverlayID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct RoutingOverlayID {
+    _raw_id: RawID
+}
+
+impl Copy for RoutingOverlayID {}
+impl Clone for RoutingOverlayID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for RoutingOverlayID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "RoutingOverlayID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for RoutingOverlayID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for RoutingOverlayID {
+    fn eq(&self, other: &RoutingOverlayID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for RoutingOverlayID {}
+
+impl TypedID for RoutingOverlayID {
+    type Target = RoutingOverlay;
+
+    fn from_raw(id: RawID) -> Self {
+        RoutingOverlayID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl RoutingOverlayID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = RoutingOverlayID::from_raw(world.allocate_instance_id::<RoutingOverlay>());
+        let swarm = world.local_broadcast::<RoutingOverlay>();
+        world.send(swarm, MSG_RoutingOverlay_spawn(id, time));
+        id
+    }
+    
+    pub fn update_landmark(self, landmark: LinkID, edges: CVec < OverlayEdge >, world: &mut World) {
+        world.send(self.as_raw(), MSG_RoutingOverlay_update_landmark(landmark, edges));
+    }
+    
+    pub fn remove_landmark(self, landmark: LinkID, world: &mut World) {
+        world.send(self.as_raw(), MSG_RoutingOverlay_remove_landmark(landmark));
+    }
+    
+    pub fn find_waypoints(self, from: LinkID, to: LinkID, trip: TripID, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_RoutingOverlay_find_waypoints(from, to, trip, instant));
+    }
+    
+    pub fn get_distance(self, from: Location, to: Location, requester: DistanceRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_RoutingOverlay_get_distance(from, to, requester));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RoutingOverlay_spawn(pub RoutingOverlayID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RoutingOverlay_update_landmark(pub LinkID, pub CVec < OverlayEdge >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RoutingOverlay_remove_landmark(pub LinkID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RoutingOverlay_find_waypoints(pub LinkID, pub LinkID, pub TripID, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RoutingOverlay_get_distance(pub Location, pub Location, pub DistanceRequesterID);
+
+impl Into<SleeperID> for RoutingOverlayID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    SleeperID::register_implementor::<RoutingOverlay>(system);
+    system.add_spawner::<RoutingOverlay, _, _>(
+        |&MSG_RoutingOverlay_spawn(id, time), world| {
+            RoutingOverlay::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<RoutingOverlay, _, _>(
+        |&MSG_RoutingOverlay_update_landmark(landmark, ref edges), instance, world| {
+            instance.update_landmark(landmark, edges, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<RoutingOverlay, _, _>(
+        |&MSG_RoutingOverlay_remove_landmark(landmark), instance, world| {
+            instance.remove_landmark(landmark, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<RoutingOverlay, _, _>(
+        |&MSG_RoutingOverlay_find_waypoints(from, to, trip, instant), instance, world| {
+            instance.find_waypoints(from, to, trip, instant, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<RoutingOverlay, _, _>(
+        |&MSG_RoutingOverlay_get_distance(from, to, requester), instance, world| {
+            instance.get_distance(from, to, requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
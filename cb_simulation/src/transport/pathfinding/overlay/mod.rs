@@ -0,0 +1,391 @@
+// Lanes only learn routes to landmarks a limited number of hops away, so that
+// their routing tables don't grow with the whole network. Landmarks report the
+// landmarks they know routes to, which makes a much smaller graph of landmarks
+// that long trips are planned on, as a row of waypoints the car drives to one
+// after another. That graph is kept as a contraction hierarchy: landmarks are
+// ranked, and every landmark is bypassed by shortcuts between its higher ranked
+// neighbors, so that a query only ever has to search upwards from both ends.
+// Ranking is only redone when landmarks appear, disappear or get new neighbors,
+// changed distances just recompute the shortcuts with the ranking kept.
+// Trips between landmarks that know of each other don't need the overlay,
+// the lanes find the way on their own
+use kay::{ActorSystem, World};
+use compact::{CVec, CHashMap};
+use ordered_float::OrderedFloat;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, BTreeMap, HashMap};
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Ticks};
+
+use super::{LinkID, Location, DistanceRequesterID};
+use super::trip::TripID;
+
+const REBUILD_INTERVAL: Ticks = Ticks(100);
+
+#[derive(Copy, Clone)]
+pub struct OverlayEdge {
+    pub to: LinkID,
+    pub distance: f32,
+}
+
+// Edges and shortcuts are stored with the lower ranked of their two ends
+#[derive(Copy, Clone)]
+struct Arc {
+    higher: u32,
+    upward: bool,
+    distance: f32,
+    // the landmark a shortcut bypasses
+    middle: Option<u32>,
+}
+
+#[derive(Compact, Clone)]
+pub struct RoutingOverlay {
+    id: RoutingOverlayID,
+    time: TimeID,
+    // what each landmark last reported about the landmarks it knows routes to
+    reported: CHashMap<LinkID, CVec<OverlayEdge>>,
+    topology_changed: bool,
+    distances_changed: bool,
+    // landmarks by rank, lowest first
+    ranked: CVec<LinkID>,
+    rank_of: CHashMap<LinkID, u32>,
+    // arcs of the landmark of rank r are `arcs[first_arc[r]..first_arc[r + 1]]`
+    arcs: CVec<Arc>,
+    first_arc: CVec<u32>,
+}
+
+impl RoutingOverlay {
+    pub fn spawn(id: RoutingOverlayID, time: TimeID, world: &mut World) -> RoutingOverlay {
+        time.wake_up_in(REBUILD_INTERVAL, id.into(), world);
+
+        RoutingOverlay {
+            id,
+            time,
+            reported: CHashMap::new(),
+            topology_changed: false,
+            distances_changed: false,
+            ranked: CVec::new(),
+            rank_of: CHashMap::new(),
+            arcs: CVec::new(),
+            first_arc: CVec::new(),
+        }
+    }
+
+    pub fn update_landmark(
+        &mut self,
+        landmark: LinkID,
+        edges: &CVec<OverlayEdge>,
+        _: &mut World,
+    ) {
+        match self.reported.get(landmark) {
+            Some(previous) => {
+                let same_neighbors = previous.len() == edges.len()
+                    && previous
+                        .iter()
+                        .all(|old| edges.iter().any(|new| new.to == old.to));
+                if !same_neighbors {
+                    self.topology_changed = true;
+                } else if previous
+                    .iter()
+                    .zip(edges.iter())
+                    .any(|(old, new)| old.to != new.to || old.distance != new.distance)
+                {
+                    self.distances_changed = true;
+                }
+            }
+            None => self.topology_changed = true,
+        }
+        self.reported.insert(landmark, edges.clone());
+    }
+
+    pub fn remove_landmark(&mut self, landmark: LinkID, _: &mut World) {
+        if self.reported.remove(landmark).is_some() {
+            self.topology_changed = true;
+        }
+    }
+
+    fn knows_directly(&self, from: LinkID, to: LinkID) -> bool {
+        from == to
+            || self
+                .reported
+                .get(from)
+                .map(|edges| edges.iter().any(|edge| edge.to == to))
+                .unwrap_or(false)
+    }
+
+    // Landmarks to drive to one after another, empty if the lanes
+    // can find the way on their own or there is no known way
+    pub fn find_waypoints(
+        &mut self,
+        from: LinkID,
+        to: LinkID,
+        trip: TripID,
+        instant: Instant,
+        world: &mut World,
+    ) {
+        let waypoints = if self.knows_directly(from, to) {
+            CVec::new()
+        } else {
+            self.route(from, to)
+                .map(|(_, waypoints)| waypoints.into_iter().collect())
+                .unwrap_or_else(CVec::new)
+        };
+        trip.on_waypoints(waypoints, instant, world);
+    }
+
+    pub fn get_distance(
+        &mut self,
+        from: Location,
+        to: Location,
+        requester: DistanceRequesterID,
+        world: &mut World,
+    ) {
+        let maybe_distance = self
+            .route(from.landmark, to.landmark)
+            .map(|(distance, _)| distance);
+        requester.on_distance(maybe_distance, world);
+    }
+
+    fn rebuild(&mut self) {
+        let mut nodes: Vec<LinkID> = Vec::new();
+        let mut index_of: HashMap<LinkID, usize> = HashMap::new();
+        {
+            let mut add_node = |landmark: LinkID| {
+                let next_index = nodes.len();
+                *index_of.entry(landmark).or_insert_with(|| {
+                    nodes.push(landmark);
+                    next_index
+                })
+            };
+            for (&landmark, edges) in self.reported.pairs() {
+                add_node(landmark);
+                for edge in edges.iter() {
+                    add_node(edge.to);
+                }
+            }
+        }
+
+        let mut outgoing: Vec<BTreeMap<usize, (f32, Option<usize>)>> =
+            vec![BTreeMap::new(); nodes.len()];
+        let mut incoming: Vec<BTreeMap<usize, (f32, Option<usize>)>> =
+            vec![BTreeMap::new(); nodes.len()];
+        for (&landmark, edges) in self.reported.pairs() {
+            let from = index_of[&landmark];
+            for edge in edges.iter() {
+                let to = index_of[&edge.to];
+                if from != to {
+                    outgoing[from].insert(to, (edge.distance, None));
+                    incoming[to].insert(from, (edge.distance, None));
+                }
+            }
+        }
+
+        // keep the previous ranking if only distances changed
+        let fixed_order: Option<Vec<usize>> = if self.topology_changed {
+            None
+        } else {
+            self.ranked
+                .iter()
+                .map(|landmark| index_of.get(landmark).cloned())
+                .collect::<Option<Vec<_>>>()
+                .filter(|order| order.len() == nodes.len())
+        };
+
+        let mut contracted = vec![false; nodes.len()];
+        let mut order: Vec<usize> = Vec::with_capacity(nodes.len());
+
+        for step in 0..nodes.len() {
+            let node = match fixed_order {
+                Some(ref fixed_order) => fixed_order[step],
+                None => {
+                    // contracting landmarks with few neighbors first keeps shortcuts few
+                    (0..nodes.len())
+                        .filter(|&node| !contracted[node])
+                        .min_by_key(|&node| {
+                            let n_in = incoming[node].keys().filter(|&&n| !contracted[n]).count();
+                            let n_out = outgoing[node].keys().filter(|&&n| !contracted[n]).count();
+                            n_in * n_out
+                        })
+                        .expect("Should have an uncontracted landmark left")
+                }
+            };
+            contracted[node] = true;
+            order.push(node);
+
+            let ins: Vec<(usize, f32)> = incoming[node]
+                .iter()
+                .filter(|&(&n, _)| !contracted[n])
+                .map(|(&n, &(distance, _))| (n, distance))
+                .collect();
+            let outs: Vec<(usize, f32)> = outgoing[node]
+                .iter()
+                .filter(|&(&n, _)| !contracted[n])
+                .map(|(&n, &(distance, _))| (n, distance))
+                .collect();
+
+            for &(from, in_distance) in &ins {
+                for &(to, out_distance) in &outs {
+                    if from == to {
+                        continue;
+                    }
+                    let distance = in_distance + out_distance;
+                    let shorter = outgoing[from]
+                        .get(&to)
+                        .map(|&(existing, _)| distance < existing)
+                        .unwrap_or(true);
+                    if shorter {
+                        outgoing[from].insert(to, (distance, Some(node)));
+                        incoming[to].insert(from, (distance, Some(node)));
+                    }
+                }
+            }
+        }
+
+        let mut rank = vec![0; nodes.len()];
+        for (r, &node) in order.iter().enumerate() {
+            rank[node] = r;
+        }
+
+        self.ranked = order.iter().map(|&node| nodes[node]).collect();
+        self.rank_of = CHashMap::new();
+        for (r, &landmark) in self.ranked.iter().enumerate() {
+            self.rank_of.insert(landmark, r as u32);
+        }
+
+        self.arcs = CVec::new();
+        self.first_arc = CVec::new();
+        for &node in &order {
+            self.first_arc.push(self.arcs.len() as u32);
+            let to_arc = |(&other, &(distance, middle)): (&usize, &(f32, Option<usize>)),
+                          upward: bool| Arc {
+                higher: rank[other] as u32,
+                upward,
+                distance,
+                middle: middle.map(|middle| rank[middle] as u32),
+            };
+            let arcs_up = outgoing[node]
+                .iter()
+                .filter(|&(&other, _)| rank[other] > rank[node])
+                .map(|pair| to_arc(pair, true));
+            let arcs_down = incoming[node]
+                .iter()
+                .filter(|&(&other, _)| rank[other] > rank[node])
+                .map(|pair| to_arc(pair, false));
+            self.arcs.extend(arcs_up.chain(arcs_down));
+        }
+        self.first_arc.push(self.arcs.len() as u32);
+
+        self.topology_changed = false;
+        self.distances_changed = false;
+    }
+
+    fn arcs_of(&self, rank: u32) -> &[Arc] {
+        let start = self.first_arc[rank as usize] as usize;
+        let end = self.first_arc[rank as usize + 1] as usize;
+        &self.arcs[start..end]
+    }
+
+    // every landmark reachable by only going up in rank, in one direction,
+    // with its distance and the landmark it was reached from
+    fn search_upwards(&self, start: u32, upward: bool) -> BTreeMap<u32, (f32, Option<u32>)> {
+        let mut settled: BTreeMap<u32, (f32, Option<u32>)> = BTreeMap::new();
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((OrderedFloat(0.0), start, None)));
+
+        while let Some(Reverse((OrderedFloat(distance), node, from))) = queue.pop() {
+            if settled.contains_key(&node) {
+                continue;
+            }
+            settled.insert(node, (distance, from));
+            for arc in self.arcs_of(node).iter().filter(|arc| arc.upward == upward) {
+                if !settled.contains_key(&arc.higher) {
+                    queue.push(Reverse((
+                        OrderedFloat(distance + arc.distance),
+                        arc.higher,
+                        Some(node),
+                    )));
+                }
+            }
+        }
+
+        settled
+    }
+
+    fn arc_between(&self, from: u32, to: u32) -> Option<&Arc> {
+        if from < to {
+            self.arcs_of(from)
+                .iter()
+                .find(|arc| arc.upward && arc.higher == to)
+        } else {
+            self.arcs_of(to)
+                .iter()
+                .find(|arc| !arc.upward && arc.higher == from)
+        }
+    }
+
+    // the landmarks passed on the way from one to the other, the first one excluded
+    fn unpack(&self, from: u32, to: u32, into: &mut Vec<LinkID>) {
+        match self.arc_between(from, to).and_then(|arc| arc.middle) {
+            Some(middle) => {
+                self.unpack(from, middle, into);
+                self.unpack(middle, to, into);
+            }
+            None => into.push(self.ranked[to as usize]),
+        }
+    }
+
+    fn route(&self, from: LinkID, to: LinkID) -> Option<(f32, Vec<LinkID>)> {
+        let source = *self.rank_of.get(from)?;
+        let target = *self.rank_of.get(to)?;
+
+        let forward = self.search_upwards(source, true);
+        let backward = self.search_upwards(target, false);
+
+        let (&meeting, distance) = forward
+            .iter()
+            .filter_map(|(node, &(forward_distance, _))| {
+                backward
+                    .get(node)
+                    .map(|&(backward_distance, _)| (node, forward_distance + backward_distance))
+            })
+            .min_by_key(|&(_, distance)| OrderedFloat(distance))?;
+
+        let mut up_from_source = vec![meeting];
+        while let Some(&(_, Some(previous))) = forward.get(up_from_source.last().unwrap()) {
+            up_from_source.push(previous);
+        }
+        up_from_source.reverse();
+        let mut up_from_target = vec![meeting];
+        while let Some(&(_, Some(previous))) = backward.get(up_from_target.last().unwrap()) {
+            up_from_target.push(previous);
+        }
+
+        let mut waypoints = Vec::new();
+        for pair in up_from_source.windows(2).chain(up_from_target.windows(2)) {
+            self.unpack(pair[0], pair[1], &mut waypoints);
+        }
+
+        Some((distance, waypoints))
+    }
+}
+
+impl Sleeper for RoutingOverlay {
+    fn wake(&mut self, _: Instant, world: &mut World) {
+        if self.topology_changed || self.distances_changed {
+            self.rebuild();
+        }
+        self.time.wake_up_in(REBUILD_INTERVAL, self.id.into(), world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<RoutingOverlay>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    RoutingOverlayID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
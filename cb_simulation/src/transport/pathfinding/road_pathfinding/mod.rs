@@ -1,10 +1,28 @@
-use kay::{World, Actor};
+use kay::{World, TypedID};
+use descartes::N;
 use transport::lane::{Lane, LaneID};
 use transport::lane::connectivity::Interaction;
+use transport::transport_planning::RoadClass;
+use transport::tolls::SECONDS_PER_TOLL_UNIT;
 
 use super::{PathfindingCore, Link, LinkID, Location, LinkConnection,
 CommunicatedRoutingEntry, RoughLocation, RoughLocationResolve, PreciseLocation, RoughLocationID};
-use super::trip::{TripResult, TripFate};
+use super::overlay::RoutingOverlayID;
+
+// Lanes are weighed by how long it takes to drive them, expressed as the
+// distance that would take as long at residential speed, so that faster
+// roads attract through traffic even if they are a detour. Until cars were
+// timed on a lane, driving it is assumed to go at the speed limit, which
+// its district might have lowered.
+// Tolls count as the time drivers would rather spend than pay them
+pub fn travel_cost(lane: &Lane) -> N {
+    let travel_time = lane
+        .microtraffic
+        .published_travel_time
+        .unwrap_or(lane.construction.length / lane.effective_speed_limit());
+    let toll_time = lane.microtraffic.current_toll * SECONDS_PER_TOLL_UNIT;
+    (travel_time + toll_time) * RoadClass::Residential.speed_limit()
+}
 
 impl Link for Lane {
     fn core(&self) -> &PathfindingCore {
@@ -60,7 +78,7 @@ impl Link for Lane {
                 }),
                 Interaction::Next { next, .. } => Some(LinkConnection {
                     link: next.into(),
-                    connection_cost: self.construction.length,
+                    connection_cost: travel_cost(self),
                 }),
                 _ => None,
             })
@@ -82,43 +100,21 @@ impl Link for Lane {
                 }),
                 Interaction::Previous { previous, .. } => Some(LinkConnection {
                     link: previous.into(),
-                    connection_cost: self.construction.length,
+                    connection_cost: travel_cost(self),
                 }),
                 _ => None,
             })
             .collect()
     }
-
-    fn after_route_forgotten(&mut self, forgotten_route: Location, world: &mut World) {
-        let self_as_rough_location = self.id_as();
-
-        self.microtraffic.cars.retain(|car| {
-            let car_was_going_there = if forgotten_route.is_landmark() {
-                car.destination.landmark == forgotten_route.landmark
-            } else {
-                car.destination.location == forgotten_route
-            };
-
-            if car_was_going_there {
-                car.trip.finish(
-                    TripResult {
-                        location_now: Some(self_as_rough_location),
-                        fate: TripFate::RouteForgotten,
-                    },
-                    world,
-                );
-                false
-            } else {
-                true
-            }
-        });
-    }
 }
 
 pub fn on_unbuild(lane: &Lane, world: &mut World) {
     for attachee in &lane.pathfinding.attachees {
         attachee.location_changed(lane.pathfinding.location, None, world);
     }
+    if lane.pathfinding.location.map_or(false, |location| location.is_landmark()) {
+        RoutingOverlayID::global_first(world).remove_landmark(lane.id.into(), world);
+    }
 }
 
 impl RoughLocation for Lane {
@@ -57,11 +57,21 @@ impl TripListenerID {
     pub fn trip_result(self, trip: TripID, result: TripResult, rough_source: RoughLocationID, rough_destination: RoughLocationID, world: &mut World) {
         world.send(self.as_raw(), MSG_TripListener_trip_result(trip, result, rough_source, rough_destination));
     }
+    
+    pub fn toll_paid(self, amount: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_TripListener_toll_paid(amount));
+    }
+    
+    pub fn fare_paid(self, amount: f32, operator: TaxiOperatorID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TripListener_fare_paid(amount, operator));
+    }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<TripListenerRepresentative>();
         system.register_trait_message::<MSG_TripListener_trip_created>();
         system.register_trait_message::<MSG_TripListener_trip_result>();
+        system.register_trait_message::<MSG_TripListener_toll_paid>();
+        system.register_trait_message::<MSG_TripListener_fare_paid>();
     }
 
     pub fn register_implementor<Act: Actor + TripListener>(system: &mut ActorSystem) {
@@ -77,6 +87,18 @@ impl TripListenerID {
                 instance.trip_result(trip, result, rough_source, rough_destination, world); Fate::Live
             }, false
         );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TripListener_toll_paid(amount), instance, world| {
+                instance.toll_paid(amount, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TripListener_fare_paid(amount, operator), instance, world| {
+                instance.fare_paid(amount, operator, world); Fate::Live
+            }, false
+        );
     }
 }
 
@@ -84,6 +106,10 @@ impl TripListenerID {
 struct MSG_TripListener_trip_created(pub TripID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_TripListener_trip_result(pub TripID, pub TripResult, pub RoughLocationID, pub RoughLocationID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripListener_toll_paid(pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripListener_fare_paid(pub f32, pub TaxiOperatorID);
 
 impl Actor for Trip {
     type ID = TripID;
@@ -133,20 +159,86 @@ impl TypedID for TripID {
 }
 
 impl TripID {
-    pub fn spawn(rough_source: RoughLocationID, rough_destination: RoughLocationID, listener: Option < TripListenerID >, instant: Instant, world: &mut World) -> Self {
+    pub fn spawn(rough_source: RoughLocationID, rough_destination: RoughLocationID, listener: Option < TripListenerID >, mode: TravelMode, planned: Option < Duration >, instant: Instant, world: &mut World) -> Self {
         let id = TripID::from_raw(world.allocate_instance_id::<Trip>());
         let swarm = world.local_broadcast::<Trip>();
-        world.send(swarm, MSG_Trip_spawn(id, rough_source, rough_destination, listener, instant));
+        world.send(swarm, MSG_Trip_spawn(id, rough_source, rough_destination, listener, mode, planned, instant));
         id
     }
     
+    pub fn ride_planned(self, maybe_plan: Option < RidePlan >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Trip_ride_planned(maybe_plan));
+    }
+    
+    pub fn park_and_ride_planned(self, maybe_plan: Option < RidePlan >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Trip_park_and_ride_planned(maybe_plan));
+    }
+    
+    pub fn ride_finished(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Trip_ride_finished());
+    }
+    
+    pub fn on_parked(self, maybe_estimate: Option < ParkingEstimate >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Trip_on_parked(maybe_estimate));
+    }
+    
+    pub fn on_taxi_dispatched(self, maybe_taxi: Option < ( Duration , TaxiOperatorID ) >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Trip_on_taxi_dispatched(maybe_taxi));
+    }
+    
+    pub fn on_walk_started(self, walking_time: Duration, world: &mut World) {
+        world.send(self.as_raw(), MSG_Trip_on_walk_started(walking_time));
+    }
+    
+    pub fn on_cycling_started(self, cycling_time: Duration, world: &mut World) {
+        world.send(self.as_raw(), MSG_Trip_on_cycling_started(cycling_time));
+    }
+    
+    pub fn on_waypoints(self, waypoints: CVec < LinkID >, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Trip_on_waypoints(waypoints, instant));
+    }
+    
+    pub fn waypoint_reached(self, waypoint: LinkID, lane: LaneID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Trip_waypoint_reached(waypoint, lane));
+    }
+    
+    pub fn replan(self, lane: LaneID, from: LinkID, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Trip_replan(lane, from, instant));
+    }
+    
+    pub fn pay_toll(self, amount: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Trip_pay_toll(amount));
+    }
+    
     pub fn finish(self, result: TripResult, world: &mut World) {
         world.send(self.as_raw(), MSG_Trip_finish(result));
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Trip_spawn(pub TripID, pub RoughLocationID, pub RoughLocationID, pub Option < TripListenerID >, pub Instant);
+struct MSG_Trip_spawn(pub TripID, pub RoughLocationID, pub RoughLocationID, pub Option < TripListenerID >, pub TravelMode, pub Option < Duration >, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_ride_planned(pub Option < RidePlan >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_park_and_ride_planned(pub Option < RidePlan >);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_ride_finished();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_on_parked(pub Option < ParkingEstimate >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_on_taxi_dispatched(pub Option < ( Duration , TaxiOperatorID ) >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_on_walk_started(pub Duration);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_on_cycling_started(pub Duration);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_on_waypoints(pub CVec < LinkID >, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_waypoint_reached(pub LinkID, pub LaneID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_replan(pub LaneID, pub LinkID, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_pay_toll(pub f32);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Trip_finish(pub TripResult);
 
@@ -155,6 +247,24 @@ impl Into<LocationRequesterID> for TripID {
         LocationRequesterID::from_raw(self.as_raw())
     }
 }
+
+impl Into<DistanceRequesterID> for TripID {
+    fn into(self) -> DistanceRequesterID {
+        DistanceRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<PositionRequesterID> for TripID {
+    fn into(self) -> PositionRequesterID {
+        PositionRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for TripID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
 impl Actor for TripCreator {
     type ID = TripCreatorID;
 
@@ -313,9 +423,78 @@ impl Into<PositionRequesterID> for FailedTripDebuggerID {
 pub fn auto_setup(system: &mut ActorSystem) {
     TripListenerID::register_trait(system);
     LocationRequesterID::register_implementor::<Trip>(system);
+    DistanceRequesterID::register_implementor::<Trip>(system);
+    PositionRequesterID::register_implementor::<Trip>(system);
+    SleeperID::register_implementor::<Trip>(system);
     system.add_spawner::<Trip, _, _>(
-        |&MSG_Trip_spawn(id, rough_source, rough_destination, listener, instant), world| {
-            Trip::spawn(id, rough_source, rough_destination, listener, instant, world)
+        |&MSG_Trip_spawn(id, rough_source, rough_destination, listener, mode, planned, instant), world| {
+            Trip::spawn(id, rough_source, rough_destination, listener, mode, planned, instant, world)
+        }, false
+    );
+    
+    system.add_handler::<Trip, _, _>(
+        |&MSG_Trip_ride_planned(maybe_plan), instance, world| {
+            instance.ride_planned(maybe_plan, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Trip, _, _>(
+        |&MSG_Trip_park_and_ride_planned(maybe_plan), instance, world| {
+            instance.park_and_ride_planned(maybe_plan, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Trip, _, _>(
+        |&MSG_Trip_ride_finished(), instance, world| {
+            instance.ride_finished(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Trip, _, _>(
+        |&MSG_Trip_on_parked(maybe_estimate), instance, world| {
+            instance.on_parked(maybe_estimate, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Trip, _, _>(
+        |&MSG_Trip_on_taxi_dispatched(maybe_taxi), instance, world| {
+            instance.on_taxi_dispatched(maybe_taxi, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Trip, _, _>(
+        |&MSG_Trip_on_walk_started(walking_time), instance, world| {
+            instance.on_walk_started(walking_time, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Trip, _, _>(
+        |&MSG_Trip_on_cycling_started(cycling_time), instance, world| {
+            instance.on_cycling_started(cycling_time, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Trip, _, _>(
+        |&MSG_Trip_on_waypoints(ref waypoints, instant), instance, world| {
+            instance.on_waypoints(waypoints, instant, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Trip, _, _>(
+        |&MSG_Trip_waypoint_reached(waypoint, lane), instance, world| {
+            instance.waypoint_reached(waypoint, lane, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Trip, _, _>(
+        |&MSG_Trip_replan(lane, from, instant), instance, world| {
+            instance.replan(lane, from, instant, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Trip, _, _>(
+        |&MSG_Trip_pay_toll(amount), instance, world| {
+            instance.pay_toll(amount, world); Fate::Live
         }, false
     );
     
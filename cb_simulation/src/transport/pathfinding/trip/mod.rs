@@ -4,7 +4,9 @@ use ordered_float::OrderedFloat;
 use cb_time::units::Instant;
 
 use transport::lane::LaneID;
-use super::{PreciseLocation, RoughLocationID, LocationRequester, LocationRequesterID};
+use super::{PreciseLocation, RoughLocationID, LocationRequester, LocationRequesterID,
+DistanceRequester, DistanceRequesterID, LinkID};
+use super::overlay::RoutingOverlayID;
 
 use itertools::Itertools;
 use super::super::lane::Lane;
@@ -12,6 +14,105 @@ use super::super::lane::Lane;
 use cb_util::log::{debug, warn};
 const LOG_T: &str = "Trips";
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum TravelMode {
+    Car,
+    // pedestrians don't interact with cars, they just take as long as
+    // the sidewalks and crosswalks on their way make them take
+    Walking,
+    // cyclists aren't either, they ride on bike lanes where there are some
+    // and slower on the road elsewhere
+    Bike,
+    // walking to the closest stop, riding a bus or train and walking the rest of
+    // the way, or walking all the way if no line gets there faster
+    Transit,
+    // businesses fetching goods bought wholesale drive trucks,
+    // which are loaded at the supplier instead of being parked
+    Freight,
+    // waiting for the closest taxi to come pick them up, being driven
+    // and dropped off at the door, paying by distance
+    Taxi,
+    // ambulances on their way to an emergency, which other cars make way for
+    Emergency,
+    // driving to a rail station with a free parking space, leaving the car
+    // there, riding on and walking the rest of the way
+    ParkAndRide,
+}
+
+pub const ALL_TRAVEL_MODES: [TravelMode; 8] = [
+    TravelMode::Car,
+    TravelMode::Walking,
+    TravelMode::Bike,
+    TravelMode::Transit,
+    TravelMode::Freight,
+    TravelMode::Taxi,
+    TravelMode::Emergency,
+    TravelMode::ParkAndRide,
+];
+
+// how long a truck stands at the loading dock before the goods are handed over
+const LOADING_TIME: Duration = Duration(10 * 60);
+const REPLAN_COOLDOWN: Duration = Duration(10 * 60);
+
+impl TravelMode {
+    // meters per second, averaged over a whole trip
+    pub fn assumed_avg_speed(self) -> f32 {
+        match self {
+            TravelMode::Car | TravelMode::Taxi => 10.0,
+            TravelMode::Walking => 1.4,
+            TravelMode::Bike => 4.5,
+            // waiting for the vehicle and walking to and from stops included
+            TravelMode::Transit => 4.0,
+            // loading included
+            TravelMode::Freight => 7.0,
+            TravelMode::Emergency => 14.0,
+            TravelMode::ParkAndRide => 6.0,
+        }
+    }
+
+    // how much a second spent travelling this way bothers people,
+    // compared to sitting in their own car
+    pub fn cost_factor(self) -> f32 {
+        match self {
+            TravelMode::Car => 1.0,
+            TravelMode::Walking => 1.5,
+            TravelMode::Bike => 1.3,
+            TravelMode::Transit => 1.2,
+            TravelMode::Freight | TravelMode::Emergency | TravelMode::ParkAndRide => 1.0,
+            // being driven is only bothersome because of the fare
+            TravelMode::Taxi => 1.0,
+        }
+    }
+
+    // further than this, nobody considers going this way,
+    // walking is what's left when nothing else goes
+    pub fn max_distance(self) -> Option<f32> {
+        match self {
+            TravelMode::Bike => Some(8000.0),
+            _ => None,
+        }
+    }
+
+    pub fn in_traffic(self) -> bool {
+        match self {
+            TravelMode::Car
+            | TravelMode::Freight
+            | TravelMode::Taxi
+            | TravelMode::Emergency
+            | TravelMode::ParkAndRide => true,
+            _ => false,
+        }
+    }
+
+    pub fn vehicle(self) -> VehicleClass {
+        match self {
+            TravelMode::Freight => VehicleClass::Truck,
+            TravelMode::Emergency => VehicleClass::Emergency,
+            _ => VehicleClass::Car,
+        }
+    }
+}
+
 #[derive(Compact, Clone)]
 pub struct Trip {
     id: TripID,
@@ -20,6 +121,45 @@ pub struct Trip {
     source: Option<PreciseLocation>,
     destination: Option<PreciseLocation>,
     listener: Option<TripListenerID>,
+    mode: TravelMode,
+    departure: Instant,
+    // how long the trip was expected to take, if that was estimated beforehand
+    planned: Option<Duration>,
+    source_position: Option<P2>,
+    destination_position: Option<P2>,
+    transit_leg: TransitLeg,
+    parking: ParkingLeg,
+    taxi_leg: TaxiLeg,
+    // landmarks a long drive goes through, from the routing overlay
+    waypoints: CVec<LinkID>,
+    replanned_at: Option<Instant>,
+    // the lane the car was on when new waypoints were asked for
+    replanning_from: Option<LaneID>,
+}
+
+#[derive(Copy, Clone)]
+enum TransitLeg {
+    Planning,
+    DrivingToStation(RidePlan),
+    ToStop(RidePlan),
+    Riding(RidePlan),
+    FromStop,
+}
+
+#[derive(Copy, Clone)]
+enum ParkingLeg {
+    Driving,
+    Circling(u8),
+    FromSpot,
+    // trucks don't park, they stand at the loading dock
+    Loading,
+}
+
+#[derive(Copy, Clone)]
+enum TaxiLeg {
+    Calling,
+    WaitingFor(TaxiOperatorID),
+    Riding(TaxiOperatorID),
 }
 
 #[derive(Copy, Clone)]
@@ -38,6 +178,7 @@ pub enum TripFate {
     HopDisconnected,
     LaneUnbuilt,
     ForceStopped,
+    NoTaxiAvailable,
 }
 
 const DEBUG_FAILED_TRIPS_VISUALLY: bool = false;
@@ -48,10 +189,37 @@ impl Trip {
         rough_source: RoughLocationID,
         rough_destination: RoughLocationID,
         listener: Option<TripListenerID>,
+        mode: TravelMode,
+        planned: Option<Duration>,
         instant: Instant,
         world: &mut World,
     ) -> Self {
-        rough_source.resolve_as_location(id.into(), rough_source, instant, world);
+        // where exactly the trip starts and ends is what it's counted by once it arrived,
+        // which stops, sidewalks and bike lanes to use depends on it,
+        // where to park, where to send a taxi and what the ride costs as well.
+        // Taxi rides only find their way through traffic once the taxi is there,
+        // park and ride drives once they know which station to go to
+        rough_source.resolve_as_position(id.into(), rough_source, world);
+        if rough_destination != rough_source {
+            rough_destination.resolve_as_position(id.into(), rough_destination, world);
+        }
+
+        let positions_first = mode == TravelMode::Transit
+            || mode == TravelMode::Walking
+            || mode == TravelMode::Bike
+            || mode == TravelMode::Taxi
+            || mode == TravelMode::ParkAndRide;
+        if !positions_first {
+            rough_source.resolve_as_location(id.into(), rough_source, instant, world);
+        }
+
+        // the car is taken from wherever it was left
+        if mode == TravelMode::Car || mode == TravelMode::ParkAndRide {
+            if let Some(listener) = listener {
+                ParkingRegistryID::global_first(world).unpark(listener, world);
+                TransitNetworkID::global_first(world).unpark(listener, world);
+            }
+        }
 
         if let Some(listener) = listener {
             listener.trip_created(id, world);
@@ -62,14 +230,283 @@ impl Trip {
             rough_source,
             rough_destination,
             listener,
+            mode,
+            departure: instant,
+            planned,
             source: None,
             destination: None,
+            source_position: None,
+            destination_position: None,
+            transit_leg: TransitLeg::Planning,
+            parking: ParkingLeg::Driving,
+            taxi_leg: TaxiLeg::Calling,
+            waypoints: CVec::new(),
+            replanned_at: None,
+            replanning_from: None,
+        }
+    }
+
+    pub fn ride_planned(&mut self, maybe_plan: Option<RidePlan>, world: &mut World) {
+        if let (Some(plan), Some(source_position)) = (maybe_plan, self.source_position) {
+            self.transit_leg = TransitLeg::ToStop(plan);
+            self.walk(source_position, plan.board_position, world);
+        } else if let (Some(source_position), Some(destination_position)) =
+            (self.source_position, self.destination_position)
+        {
+            self.mode = TravelMode::Walking;
+            self.walk(source_position, destination_position, world);
+        }
+    }
+
+    // with a space at a station, the car is driven there, otherwise all the way
+    pub fn park_and_ride_planned(&mut self, maybe_plan: Option<RidePlan>, world: &mut World) {
+        if let Some(plan) = maybe_plan {
+            self.transit_leg = TransitLeg::DrivingToStation(plan);
+        } else {
+            self.mode = TravelMode::Car;
+        }
+        self.rough_source.resolve_as_location(
+            self.id_as(),
+            self.rough_source,
+            self.departure,
+            world,
+        );
+    }
+
+    // park and ride drives only go as far as the station
+    fn drive_destination(&self) -> RoughLocationID {
+        match self.transit_leg {
+            TransitLeg::DrivingToStation(plan) => plan.board.into(),
+            _ => self.rough_destination,
+        }
+    }
+
+    pub fn ride_finished(&mut self, world: &mut World) {
+        if let (TransitLeg::Riding(plan), Some(destination_position)) =
+            (self.transit_leg, self.destination_position)
+        {
+            self.transit_leg = TransitLeg::FromStop;
+            self.walk(plan.alight_position, destination_position, world);
+        }
+    }
+
+    fn look_for_parking(&mut self, world: &mut World) {
+        if let Some(destination_position) = self.destination_position {
+            ParkingRegistryID::global_first(world).park(
+                destination_position,
+                self.id,
+                self.listener,
+                world,
+            );
+        }
+    }
+
+    pub fn on_parked(&mut self, maybe_estimate: Option<ParkingEstimate>, world: &mut World) {
+        let rounds = match self.parking {
+            ParkingLeg::Circling(rounds) => rounds,
+            _ => 0,
+        };
+
+        let maybe_estimate = maybe_estimate.or_else(|| {
+            if rounds >= MAX_CIRCLING_ROUNDS {
+                Some(ParkingEstimate::fallback())
+            } else {
+                None
+            }
+        });
+
+        if let Some(estimate) = maybe_estimate {
+            self.parking = ParkingLeg::FromSpot;
+            TimeID::local_first(world).wake_up_in(estimate.total().into(), self.id_as(), world);
+        } else {
+            self.parking = ParkingLeg::Circling(rounds + 1);
+            TimeID::local_first(world).wake_up_in(CIRCLING_TIME.into(), self.id_as(), world);
+        }
+    }
+
+    fn walk(&mut self, from: P2, to: P2, world: &mut World) {
+        PedestrianNetworkID::global_first(world).walk(from, to, self.id, world);
+    }
+
+    pub fn on_taxi_dispatched(
+        &mut self,
+        maybe_taxi: Option<(Duration, TaxiOperatorID)>,
+        world: &mut World,
+    ) {
+        if let Some((pickup, operator)) = maybe_taxi {
+            self.taxi_leg = TaxiLeg::WaitingFor(operator);
+            TimeID::local_first(world).wake_up_in(pickup.into(), self.id_as(), world);
+        } else {
+            self.id.finish(
+                TripResult {
+                    location_now: Some(self.rough_source),
+                    fate: TripFate::NoTaxiAvailable,
+                },
+                world,
+            );
+        }
+    }
+
+    pub fn on_walk_started(&mut self, walking_time: Duration, world: &mut World) {
+        TimeID::local_first(world).wake_up_in(walking_time.into(), self.id_as(), world);
+    }
+
+    pub fn on_cycling_started(&mut self, cycling_time: Duration, world: &mut World) {
+        TimeID::local_first(world).wake_up_in(cycling_time.into(), self.id_as(), world);
+    }
+
+    pub fn on_waypoints(&mut self, waypoints: &CVec<LinkID>, instant: Instant, world: &mut World) {
+        self.waypoints = waypoints.clone();
+
+        if let Some(lane) = self.replanning_from.take() {
+            lane.update_waypoints(
+                self.id,
+                self.waypoints.first().cloned(),
+                self.waypoints.get(1).cloned(),
+                world,
+            );
+        } else if let (Some(source), Some(destination)) = (self.source, self.destination) {
+            // TODO: ugly: untyped RawID shenanigans
+            let source_as_lane: LaneLikeID = LaneLikeID::from_raw(source.link.as_raw());
+            source_as_lane.add_car(
+                LaneCar {
+                    trip: self.id,
+                    vehicle: self.mode.vehicle(),
+                    as_obstacle: Obstacle {
+                        position: OrderedFloat(source.offset),
+                        velocity: 0.0,
+                        max_velocity: self.mode.vehicle().max_velocity(),
+                    },
+                    acceleration: 0.0,
+                    destination,
+                    waypoint: self.waypoints.first().cloned(),
+                    next_waypoint: self.waypoints.get(1).cloned(),
+                    next_hop_interaction: None,
+                    routed_with: 0,
+                    entered_at: instant,
+                    entered_position: source.offset,
+                    leg: None,
+                },
+                None,
+                instant,
+                world,
+            );
+        }
+    }
+
+    // after a replan, the car might still head for a waypoint that isn't one anymore
+    pub fn waypoint_reached(&mut self, waypoint: LinkID, lane: LaneID, world: &mut World) {
+        let next_idx = self
+            .waypoints
+            .iter()
+            .position(|&other| other == waypoint)
+            .map(|idx| idx + 1)
+            .unwrap_or(0);
+        lane.update_waypoints(
+            self.id,
+            self.waypoints.get(next_idx).cloned(),
+            self.waypoints.get(next_idx + 1).cloned(),
+            world,
+        );
+    }
+
+    // Drives whose way got much slower than expected look for other waypoints
+    // from the landmark they are at, but not again right after the last time
+    pub fn replan(&mut self, lane: LaneID, from: LinkID, instant: Instant, world: &mut World) {
+        let cooling_down = self
+            .replanned_at
+            .map_or(false, |replanned_at| instant < replanned_at + REPLAN_COOLDOWN);
+        if let (Some(destination), false) = (self.destination, cooling_down) {
+            self.replanned_at = Some(instant);
+            self.replanning_from = Some(lane);
+            RoutingOverlayID::global_first(world).find_waypoints(
+                from,
+                destination.landmark,
+                self.id,
+                instant,
+                world,
+            );
+        }
+    }
+
+    // whoever the car belongs to pays
+    pub fn pay_toll(&mut self, amount: f32, world: &mut World) {
+        if let Some(listener) = self.listener {
+            listener.toll_paid(amount, world);
+        }
+    }
+
+    // passengers pay once they're dropped off, the taxi is free again either way
+    fn end_taxi_ride(&mut self, operator: TaxiOperatorID, fate: TripFate, world: &mut World) {
+        let drop_off = match fate {
+            TripFate::Success(_) => self.destination_position,
+            _ => None,
+        };
+        if let (Some(listener), Some(from), Some(to)) =
+            (self.listener, self.source_position, drop_off)
+        {
+            listener.fare_paid(taxis::fare_for(from, to), operator, world);
         }
+        TaxiDispatcherID::global_first(world).ride_finished(self.id, drop_off, world);
     }
 
     pub fn finish(&mut self, result: TripResult, world: &mut World) -> Fate {
+        if let (TripFate::Success(_), ParkingLeg::Driving, TravelMode::Freight) =
+            (result.fate, self.parking, self.mode)
+        {
+            self.parking = ParkingLeg::Loading;
+            TimeID::local_first(world).wake_up_in(LOADING_TIME.into(), self.id_as(), world);
+            return Fate::Live;
+        }
+
+        // drivers leave their car at the station and walk to the platform
+        if let (TripFate::Success(_), TransitLeg::DrivingToStation(plan)) =
+            (result.fate, self.transit_leg)
+        {
+            self.transit_leg = TransitLeg::ToStop(plan);
+            TimeID::local_first(world).wake_up_in(
+                PARK_AND_RIDE_TRANSFER.into(),
+                self.id_as(),
+                world,
+            );
+            return Fate::Live;
+        }
+
+        // cars that arrived still need a spot, unless nobody knows where exactly they are
+        if let (TripFate::Success(_), ParkingLeg::Driving, TravelMode::Car, Some(_)) = (
+            result.fate,
+            self.parking,
+            self.mode,
+            self.destination_position,
+        ) {
+            self.look_for_parking(world);
+            return Fate::Live;
+        }
+
+        if let (TripFate::Success(arrival), Some(origin), Some(destination)) = (
+            result.fate,
+            self.source_position,
+            self.destination_position,
+        ) {
+            TripStatsID::global_first(world).record(
+                origin,
+                destination,
+                self.mode,
+                self.departure,
+                arrival,
+                self.planned,
+                world,
+            );
+        }
+
         match result.fate {
             TripFate::Success(_) | TripFate::ForceStopped => {}
+            TripFate::NoTaxiAvailable => debug(
+                LOG_T,
+                format!("No taxi came for trip {:?}", self.id),
+                self.id(),
+                world,
+            ),
             reason => {
                 warn(
                     LOG_T,
@@ -91,6 +528,10 @@ impl Trip {
             }
         }
 
+        if let TaxiLeg::WaitingFor(operator) | TaxiLeg::Riding(operator) = self.taxi_leg {
+            self.end_taxi_ride(operator, result.fate, world);
+        }
+
         if let Some(listener) = self.listener {
             listener.trip_result(
                 self.id,
@@ -113,42 +554,38 @@ impl LocationRequester for Trip {
         instant: Instant,
         world: &mut World,
     ) {
+        let drive_destination = self.drive_destination();
         if let Some(precise) = location {
             if rough_location == self.rough_source {
                 self.source = Some(precise);
 
-                if self.rough_source == self.rough_destination {
+                if self.rough_source == drive_destination {
                     self.destination = Some(precise);
                 } else {
-                    self.rough_destination.resolve_as_location(
+                    drive_destination.resolve_as_location(
                         self.id_as(),
-                        self.rough_destination,
+                        drive_destination,
                         instant,
                         world,
                     );
                 }
-            } else if rough_location == self.rough_destination {
+            } else if rough_location == drive_destination {
                 self.destination = Some(precise);
             } else {
                 unreachable!();
             }
 
-            if let (Some(source), Some(destination)) = (self.source, self.destination) {
-                // TODO: ugly: untyped RawID shenanigans
-                let source_as_lane: LaneLikeID = LaneLikeID::from_raw(source.link.as_raw());
-                source_as_lane.add_car(
-                    LaneCar {
-                        trip: self.id,
-                        as_obstacle: Obstacle {
-                            position: OrderedFloat(source.offset),
-                            velocity: 0.0,
-                            max_velocity: 8.0,
-                        },
-                        acceleration: 0.0,
-                        destination,
-                        next_hop_interaction: None,
-                    },
-                    None,
+            if let (Some(source), Some(destination), false) =
+                (self.source, self.destination, self.mode.in_traffic())
+            {
+                source
+                    .link
+                    .get_distance_to(destination.location, self.id_as(), world);
+            } else if let (Some(source), Some(destination)) = (self.source, self.destination) {
+                RoutingOverlayID::global_first(world).find_waypoints(
+                    source.landmark,
+                    destination.landmark,
+                    self.id,
                     instant,
                     world,
                 );
@@ -175,8 +612,126 @@ impl LocationRequester for Trip {
 }
 
 use cb_time::actors::{TimeID, Sleeper, SleeperID};
-use cb_time::units::Ticks;
-use super::super::microtraffic::{LaneLikeID, LaneCar, Obstacle};
+use cb_time::units::{Ticks, Duration};
+use super::super::microtraffic::{LaneLikeID, LaneCar, Obstacle, VehicleClass};
+use super::super::transit::{TransitNetworkID, RidePlan, Passenger, PARK_AND_RIDE_TRANSFER};
+use super::super::pedestrians::PedestrianNetworkID;
+use super::super::cycling::CyclingNetworkID;
+use super::super::trip_stats::TripStatsID;
+use super::super::taxis::{self, TaxiDispatcherID, TaxiOperatorID};
+use super::super::parking::{ParkingRegistryID, ParkingEstimate, CIRCLING_TIME, MAX_CIRCLING_ROUNDS};
+
+impl DistanceRequester for Trip {
+    fn on_distance(&mut self, maybe_distance: Option<f32>, world: &mut World) {
+        if let Some(distance) = maybe_distance {
+            let walking_time = Duration((distance / self.mode.assumed_avg_speed()) as u32);
+            TimeID::local_first(world).wake_up_in(walking_time.into(), self.id_as(), world);
+        } else {
+            self.id.finish(
+                TripResult {
+                    location_now: Some(self.rough_source),
+                    fate: TripFate::NoRoute,
+                },
+                world,
+            );
+        }
+    }
+}
+
+impl PositionRequester for Trip {
+    fn position_resolved(
+        &mut self,
+        rough_location: RoughLocationID,
+        position: P2,
+        world: &mut World,
+    ) {
+        if rough_location == self.rough_source {
+            self.source_position = Some(position);
+        }
+        if rough_location == self.rough_destination {
+            self.destination_position = Some(position);
+        }
+
+        if let (Some(source_position), Some(destination_position)) =
+            (self.source_position, self.destination_position)
+        {
+            match self.mode {
+                TravelMode::Transit => TransitNetworkID::global_first(world).plan_ride(
+                    source_position,
+                    destination_position,
+                    self.id,
+                    world,
+                ),
+                TravelMode::Walking => self.walk(source_position, destination_position, world),
+                TravelMode::Bike => CyclingNetworkID::global_first(world).cycle(
+                    source_position,
+                    destination_position,
+                    self.id,
+                    world,
+                ),
+                TravelMode::Taxi => {
+                    TaxiDispatcherID::global_first(world).call(self.id, source_position, world)
+                }
+                TravelMode::ParkAndRide => TransitNetworkID::global_first(world)
+                    .plan_park_and_ride(
+                        source_position,
+                        destination_position,
+                        self.id,
+                        self.listener,
+                        world,
+                    ),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Sleeper for Trip {
+    // walkers and cyclists arrive once they covered the whole way,
+    // transit riders once they walked to a stop or parked at a station
+    // wait for their vehicle there,
+    // drivers circling for a free spot look again,
+    // trucks at the loading dock are done loading
+    // and taxi passengers get in once their taxi is there
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        if let TaxiLeg::WaitingFor(operator) = self.taxi_leg {
+            self.taxi_leg = TaxiLeg::Riding(operator);
+            self.rough_source.resolve_as_location(
+                self.id_as(),
+                self.rough_source,
+                current_instant,
+                world,
+            );
+            return;
+        }
+
+        if let ParkingLeg::Circling(_) = self.parking {
+            self.look_for_parking(world);
+            return;
+        }
+
+        if let TransitLeg::ToStop(plan) = self.transit_leg {
+            self.transit_leg = TransitLeg::Riding(plan);
+            plan.board.passenger_arrived(
+                Passenger {
+                    trip: self.id,
+                    line: plan.line,
+                    alight: plan.alight,
+                },
+                world,
+            );
+            return;
+        }
+
+        self.id.finish(
+            TripResult {
+                location_now: None,
+                fate: TripFate::Success(current_instant),
+            },
+            world,
+        );
+    }
+}
 
 pub trait TripListener {
     fn trip_created(&mut self, trip: TripID, world: &mut World);
@@ -188,6 +743,8 @@ pub trait TripListener {
         rough_destination: RoughLocationID,
         world: &mut World,
     );
+    fn toll_paid(&mut self, amount: f32, world: &mut World);
+    fn fare_paid(&mut self, amount: f32, operator: TaxiOperatorID, world: &mut World);
 }
 
 #[derive(Compact, Clone)]
@@ -215,11 +772,11 @@ impl TripCreator {
     }
 }
 
-use rand::Rng;
+use cb_util::random::{seed, Rng};
 
 impl Sleeper for TripCreator {
     fn wake(&mut self, current_instant: Instant, world: &mut World) {
-        ::rand::thread_rng().shuffle(&mut self.lanes);
+        seed((current_instant.ticks(), self.id)).shuffle(&mut self.lanes);
 
         for mut pair in &self.lanes.iter().chunks(2) {
             if let (Some(source), Some(dest)) = (pair.next(), pair.next()) {
@@ -227,6 +784,8 @@ impl Sleeper for TripCreator {
                     (*source).into(),
                     (*dest).into(),
                     None,
+                    TravelMode::Car,
+                    None,
                     current_instant,
                     world,
                 );
@@ -0,0 +1,239 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for Footway {
+    type ID = FootwayID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct FootwayID {
+    _raw_id: RawID
+}
+
+impl Copy for FootwayID {}
+impl Clone for FootwayID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for FootwayID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "FootwayID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for FootwayID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for FootwayID {
+    fn eq(&self, other: &FootwayID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for FootwayID {}
+
+impl TypedID for FootwayID {
+    type Target = Footway;
+
+    fn from_raw(id: RawID) -> Self {
+        FootwayID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl FootwayID {
+    pub fn spawn_and_connect(path: LinePath, crossing: bool, report_to: CBConstructionID, world: &mut World) -> Self {
+        let id = FootwayID::from_raw(world.allocate_instance_id::<Footway>());
+        let swarm = world.local_broadcast::<Footway>();
+        world.send(swarm, MSG_Footway_spawn_and_connect(id, path, crossing, report_to));
+        id
+    }
+    
+    pub fn get_render_info(self, ui: TransportUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Footway_get_render_info(ui));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Footway_spawn_and_connect(pub FootwayID, pub LinePath, pub bool, pub CBConstructionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Footway_get_render_info(pub TransportUIID);
+
+impl Into<ConstructableID<CBPrototypeKind>> for FootwayID {
+    fn into(self) -> ConstructableID<CBPrototypeKind> {
+        ConstructableID::from_raw(self.as_raw())
+    }
+}
+impl Actor for PedestrianNetwork {
+    type ID = PedestrianNetworkID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct PedestrianNetworkID {
+    _raw_id: RawID
+}
+
+impl Copy for PedestrianNetworkID {}
+impl Clone for PedestrianNetworkID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for PedestrianNetworkID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "PedestrianNetworkID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for PedestrianNetworkID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for PedestrianNetworkID {
+    fn eq(&self, other: &PedestrianNetworkID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for PedestrianNetworkID {}
+
+impl TypedID for PedestrianNetworkID {
+    type Target = PedestrianNetwork;
+
+    fn from_raw(id: RawID) -> Self {
+        PedestrianNetworkID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl PedestrianNetworkID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = PedestrianNetworkID::from_raw(world.allocate_instance_id::<PedestrianNetwork>());
+        let swarm = world.local_broadcast::<PedestrianNetwork>();
+        world.send(swarm, MSG_PedestrianNetwork_spawn(id, ));
+        id
+    }
+    
+    pub fn add_footway(self, footway: FootwayID, points: CVec < P2 >, crossing: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_PedestrianNetwork_add_footway(footway, points, crossing));
+    }
+    
+    pub fn remove_footway(self, footway: FootwayID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PedestrianNetwork_remove_footway(footway));
+    }
+    
+    pub fn estimate_walk(self, from: P2, to: P2, requester: ItineraryQueryID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PedestrianNetwork_estimate_walk(from, to, requester));
+    }
+    
+    pub fn walk(self, from: P2, to: P2, trip: TripID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PedestrianNetwork_walk(from, to, trip));
+    }
+    
+    pub fn get_pedestrian_info(self, ui: TransportUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PedestrianNetwork_get_pedestrian_info(ui));
+    }
+    
+    pub fn forget_walkers(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_PedestrianNetwork_forget_walkers());
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PedestrianNetwork_spawn(pub PedestrianNetworkID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PedestrianNetwork_add_footway(pub FootwayID, pub CVec < P2 >, pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PedestrianNetwork_remove_footway(pub FootwayID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PedestrianNetwork_estimate_walk(pub P2, pub P2, pub ItineraryQueryID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PedestrianNetwork_walk(pub P2, pub P2, pub TripID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PedestrianNetwork_get_pedestrian_info(pub TransportUIID);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PedestrianNetwork_forget_walkers();
+
+impl Into<TemporalID> for PedestrianNetworkID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    ConstructableID::<CBPrototypeKind>::register_implementor::<Footway>(system);
+    system.add_spawner::<Footway, _, _>(
+        |&MSG_Footway_spawn_and_connect(id, ref path, crossing, report_to), world| {
+            Footway::spawn_and_connect(id, path, crossing, report_to, world)
+        }, false
+    );
+    
+    system.add_handler::<Footway, _, _>(
+        |&MSG_Footway_get_render_info(ui), instance, world| {
+            instance.get_render_info(ui, world); Fate::Live
+        }, false
+    );
+    TemporalID::register_implementor::<PedestrianNetwork>(system);
+    system.add_spawner::<PedestrianNetwork, _, _>(
+        |&MSG_PedestrianNetwork_spawn(id, ), world| {
+            PedestrianNetwork::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<PedestrianNetwork, _, _>(
+        |&MSG_PedestrianNetwork_add_footway(footway, ref points, crossing), instance, world| {
+            instance.add_footway(footway, points, crossing, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PedestrianNetwork, _, _>(
+        |&MSG_PedestrianNetwork_remove_footway(footway), instance, world| {
+            instance.remove_footway(footway, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PedestrianNetwork, _, _>(
+        |&MSG_PedestrianNetwork_estimate_walk(from, to, requester), instance, world| {
+            instance.estimate_walk(from, to, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PedestrianNetwork, _, _>(
+        |&MSG_PedestrianNetwork_walk(from, to, trip), instance, world| {
+            instance.walk(from, to, trip, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PedestrianNetwork, _, _>(
+        |&MSG_PedestrianNetwork_get_pedestrian_info(ui), instance, world| {
+            instance.get_pedestrian_info(ui, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PedestrianNetwork, _, _>(
+        |&MSG_PedestrianNetwork_forget_walkers(), instance, world| {
+            instance.forget_walkers(world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,300 @@
+// People on foot use the sidewalks along roads and the crosswalks where roads
+// meet intersections. Built footways are recorded in a coarse grid, which is
+// enough to tell how much of a way can be walked on sidewalks: where there are
+// none, people walk slower on the edge of the road and are exposed to traffic,
+// and every crosswalk on the way means waiting to cross. Walkers themselves
+// go in a straight line, just long enough for all of that to add up
+use kay::{ActorSystem, World, Fate, TypedID, Actor};
+use compact::{CVec, CHashMap};
+use descartes::{P2, LinePath};
+use cb_time::actors::{Temporal, TemporalID};
+use cb_time::units::{Instant, Duration};
+
+use cb_planning::Prototype;
+use cb_planning::construction::{Constructable, ConstructableID};
+use planning::{CBConstructionID, CBPrototypeKind};
+use super::pathfinding::trip::TripID;
+use super::pathfinding::itinerary::ItineraryQueryID;
+use super::transit::walking_time;
+use super::ui::{TransportUI, TransportUIID, PedestrianRenderInfo};
+
+// footways this close count as being along the way
+const CELL_SIZE: f32 = 25.0;
+// off sidewalks, people walk this much slower
+const SHOULDER_SPEED_FACTOR: f32 = 0.7;
+const CROSSING_WAIT: Duration = Duration(15);
+
+pub type Cell = (i32, i32);
+
+fn cell_of(position: P2) -> Cell {
+    (
+        (position.x / CELL_SIZE).floor() as i32,
+        (position.y / CELL_SIZE).floor() as i32,
+    )
+}
+
+// cells along a path, each only once
+pub fn cells_along(points: &[P2]) -> Vec<Cell> {
+    let mut cells: Vec<Cell> = Vec::new();
+    for pair in points.windows(2) {
+        let length = (pair[1] - pair[0]).norm();
+        let n_samples = (length / (CELL_SIZE / 2.0)).ceil().max(1.0) as usize;
+        for i in 0..=n_samples {
+            let cell = cell_of(pair[0] + (pair[1] - pair[0]) * (i as f32 / n_samples as f32));
+            if !cells.contains(&cell) {
+                cells.push(cell);
+            }
+        }
+    }
+    cells
+}
+
+#[derive(Copy, Clone)]
+pub struct WalkEstimate {
+    pub duration: Duration,
+    // share of the way without a sidewalk
+    pub unsafe_share: f32,
+}
+
+#[derive(Compact, Clone)]
+pub struct Footway {
+    id: FootwayID,
+    path: LinePath,
+    crossing: bool,
+}
+
+impl Footway {
+    pub fn spawn_and_connect(
+        id: FootwayID,
+        path: &LinePath,
+        crossing: bool,
+        report_to: CBConstructionID,
+        world: &mut World,
+    ) -> Footway {
+        PedestrianNetworkID::global_first(world).add_footway(
+            id,
+            path.points.clone(),
+            crossing,
+            world,
+        );
+        TransportUIID::global_broadcast(world).on_footway_constructed(
+            id.as_raw(),
+            path.clone(),
+            crossing,
+            world,
+        );
+        report_to.action_done(id.into(), world);
+
+        Footway {
+            id,
+            path: path.clone(),
+            crossing,
+        }
+    }
+
+    pub fn get_render_info(&mut self, ui: TransportUIID, world: &mut World) {
+        ui.on_footway_constructed(self.id.as_raw(), self.path.clone(), self.crossing, world);
+    }
+}
+
+impl Constructable<CBPrototypeKind> for Footway {
+    fn morph(
+        &mut self,
+        _new_prototype: &Prototype<CBPrototypeKind>,
+        report_to: CBConstructionID,
+        world: &mut World,
+    ) {
+        report_to.action_done(self.id_as(), world);
+    }
+
+    fn destruct(&mut self, report_to: CBConstructionID, world: &mut World) -> Fate {
+        PedestrianNetworkID::global_first(world).remove_footway(self.id, world);
+        TransportUIID::global_broadcast(world).on_footway_destructed(
+            self.id.as_raw(),
+            self.crossing,
+            world,
+        );
+        report_to.action_done(self.id_as(), world);
+        Fate::Die
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct FootwayCells {
+    footway: FootwayID,
+    cells: CVec<Cell>,
+    crossing: bool,
+}
+
+#[derive(Copy, Clone)]
+pub struct Walker {
+    trip: TripID,
+    from: P2,
+    to: P2,
+    departure: Instant,
+    arrival: Instant,
+}
+
+#[derive(Compact, Clone)]
+pub struct PedestrianNetwork {
+    id: PedestrianNetworkID,
+    footways: CVec<FootwayCells>,
+    // how many sidewalks and crosswalks pass through each cell
+    sidewalk_cells: CHashMap<Cell, u16>,
+    crosswalk_cells: CHashMap<Cell, u16>,
+    walkers: CVec<Walker>,
+    now: Instant,
+}
+
+impl PedestrianNetwork {
+    pub fn spawn(id: PedestrianNetworkID, _: &mut World) -> PedestrianNetwork {
+        PedestrianNetwork {
+            id,
+            footways: CVec::new(),
+            sidewalk_cells: CHashMap::new(),
+            crosswalk_cells: CHashMap::new(),
+            walkers: CVec::new(),
+            now: Instant::new(0),
+        }
+    }
+
+    pub fn add_footway(
+        &mut self,
+        footway: FootwayID,
+        points: &CVec<P2>,
+        crossing: bool,
+        _: &mut World,
+    ) {
+        let cells = cells_along(points);
+        let counts = if crossing {
+            &mut self.crosswalk_cells
+        } else {
+            &mut self.sidewalk_cells
+        };
+        for &cell in &cells {
+            let count = counts.get(cell).cloned().unwrap_or(0);
+            counts.insert(cell, count + 1);
+        }
+        self.footways.push(FootwayCells {
+            footway,
+            cells: cells.into(),
+            crossing,
+        });
+    }
+
+    pub fn remove_footway(&mut self, footway: FootwayID, _: &mut World) {
+        if let Some(idx) = self
+            .footways
+            .iter()
+            .position(|other| other.footway == footway)
+        {
+            let removed = self.footways.remove(idx);
+            let counts = if removed.crossing {
+                &mut self.crosswalk_cells
+            } else {
+                &mut self.sidewalk_cells
+            };
+            for &cell in removed.cells.iter() {
+                match counts.get(cell).cloned() {
+                    Some(count) if count > 1 => {
+                        counts.insert(cell, count - 1);
+                    }
+                    _ => {
+                        counts.remove(cell);
+                    }
+                }
+            }
+        }
+    }
+
+    fn estimate(&self, from: P2, to: P2) -> WalkEstimate {
+        let cells = cells_along(&[from, to]);
+        let n_covered = cells
+            .iter()
+            .filter(|&&cell| self.sidewalk_cells.get(cell).is_some())
+            .count();
+        let n_crossings: u32 = cells
+            .iter()
+            .map(|&cell| u32::from(self.crosswalk_cells.get(cell).cloned().unwrap_or(0)))
+            .sum();
+
+        let unsafe_share = 1.0 - n_covered as f32 / cells.len() as f32;
+        let seconds = walking_time(from, to)
+            * ((1.0 - unsafe_share) + unsafe_share / SHOULDER_SPEED_FACTOR)
+            + (n_crossings * CROSSING_WAIT.0) as f32;
+
+        WalkEstimate {
+            duration: Duration(seconds as u32),
+            unsafe_share,
+        }
+    }
+
+    pub fn estimate_walk(
+        &mut self,
+        from: P2,
+        to: P2,
+        requester: ItineraryQueryID,
+        world: &mut World,
+    ) {
+        requester.on_walk_estimate(self.estimate(from, to), world);
+    }
+
+    pub fn walk(&mut self, from: P2, to: P2, trip: TripID, world: &mut World) {
+        let estimate = self.estimate(from, to);
+        self.walkers.push(Walker {
+            trip,
+            from,
+            to,
+            departure: self.now,
+            arrival: self.now + estimate.duration,
+        });
+        trip.on_walk_started(estimate.duration, world);
+    }
+
+    pub fn get_pedestrian_info(&mut self, ui: TransportUIID, world: &mut World) {
+        let now = self.now;
+        let infos = self
+            .walkers
+            .iter()
+            .filter(|walker| walker.from != walker.to)
+            .map(|walker| {
+                let total = (walker.arrival.ticks() - walker.departure.ticks()).max(1);
+                let progress = (now.ticks() - walker.departure.ticks()) as f32 / total as f32;
+                let position = walker.from + (walker.to - walker.from) * progress.min(1.0);
+                let direction = (walker.to - walker.from).normalize();
+                PedestrianRenderInfo {
+                    position: [position.x, position.y],
+                    direction: [direction.x, direction.y],
+                }
+            })
+            .collect();
+        ui.on_pedestrian_info(infos, world);
+    }
+
+    // walkers belong to trips, which are dropped together with households
+    // from savegames that can't be fully loaded
+    pub fn forget_walkers(&mut self, _: &mut World) {
+        self.walkers.clear();
+    }
+}
+
+impl Temporal for PedestrianNetwork {
+    fn tick(&mut self, _dt: f32, current_instant: Instant, _: &mut World) {
+        self.now = current_instant;
+        self.walkers
+            .retain(|walker| walker.arrival > current_instant);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Footway>();
+    system.register::<PedestrianNetwork>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    PedestrianNetworkID::spawn(world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,210 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for TrafficSignal {
+    type ID = TrafficSignalID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TrafficSignalID {
+    _raw_id: RawID
+}
+
+impl Copy for TrafficSignalID {}
+impl Clone for TrafficSignalID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TrafficSignalID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TrafficSignalID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TrafficSignalID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TrafficSignalID {
+    fn eq(&self, other: &TrafficSignalID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TrafficSignalID {}
+
+impl TypedID for TrafficSignalID {
+    type Target = TrafficSignal;
+
+    fn from_raw(id: RawID) -> Self {
+        TrafficSignalID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TrafficSignalID {
+    pub fn spawn(position: P2, lanes: CVec < LaneID >, phases: CVec < SignalPhase >, world: &mut World) -> Self {
+        let id = TrafficSignalID::from_raw(world.allocate_instance_id::<TrafficSignal>());
+        let swarm = world.local_broadcast::<TrafficSignal>();
+        world.send(swarm, MSG_TrafficSignal_spawn(id, position, lanes, phases));
+        id
+    }
+    
+    pub fn set_phase_duration(self, origin: Origin, phase: u8, duration: Duration, world: &mut World) {
+        world.send(self.as_raw(), MSG_TrafficSignal_set_phase_duration(origin, phase, duration));
+    }
+    
+    pub fn on_approach_delay(self, approach: LaneID, waited: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_TrafficSignal_on_approach_delay(approach, waited));
+    }
+    
+    pub fn preempt(self, lane: LaneID, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_TrafficSignal_preempt(lane, instant));
+    }
+    
+    pub fn lane_unbuilt(self, lane: LaneID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TrafficSignal_lane_unbuilt(lane));
+    }
+    
+    pub fn get_signal_info(self, ui: TransportUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TrafficSignal_get_signal_info(ui));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TrafficSignal_spawn(pub TrafficSignalID, pub P2, pub CVec < LaneID >, pub CVec < SignalPhase >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TrafficSignal_set_phase_duration(pub Origin, pub u8, pub Duration);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TrafficSignal_on_approach_delay(pub LaneID, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TrafficSignal_preempt(pub LaneID, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TrafficSignal_lane_unbuilt(pub LaneID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TrafficSignal_get_signal_info(pub TransportUIID);
+
+impl Into<TemporalID> for TrafficSignalID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+
+impl LaneID {
+    pub fn controlled_by(self, signal: TrafficSignalID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_controlled_by(signal));
+    }
+    
+    pub fn preempt_signal(self, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_preempt_signal(instant));
+    }
+    
+    pub fn set_signal(self, green: bool, green_ahead: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_set_signal(green, green_ahead));
+    }
+    
+    pub fn report_approach_delay(self, signal: TrafficSignalID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_report_approach_delay(signal));
+    }
+    
+    pub fn report_waited_at_red(self, signal: TrafficSignalID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_report_waited_at_red(signal));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_controlled_by(pub TrafficSignalID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_preempt_signal(pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_set_signal(pub bool, pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_report_approach_delay(pub TrafficSignalID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_report_waited_at_red(pub TrafficSignalID);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    TemporalID::register_implementor::<TrafficSignal>(system);
+    system.add_spawner::<TrafficSignal, _, _>(
+        |&MSG_TrafficSignal_spawn(id, position, ref lanes, ref phases), world| {
+            TrafficSignal::spawn(id, position, lanes, phases, world)
+        }, false
+    );
+    
+    system.add_handler::<TrafficSignal, _, _>(
+        |&MSG_TrafficSignal_set_phase_duration(origin, phase, duration), instance, world| {
+            instance.set_phase_duration(origin, phase, duration, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TrafficSignal, _, _>(
+        |&MSG_TrafficSignal_on_approach_delay(approach, waited), instance, world| {
+            instance.on_approach_delay(approach, waited, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TrafficSignal, _, _>(
+        |&MSG_TrafficSignal_preempt(lane, instant), instance, world| {
+            instance.preempt(lane, instant, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TrafficSignal, _, _>(
+        |&MSG_TrafficSignal_lane_unbuilt(lane), instance, world| {
+            instance.lane_unbuilt(lane, world)
+        }, false
+    );
+    
+    system.add_handler::<TrafficSignal, _, _>(
+        |&MSG_TrafficSignal_get_signal_info(ui), instance, world| {
+            instance.get_signal_info(ui, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_controlled_by(signal), instance, world| {
+            instance.controlled_by(signal, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_preempt_signal(instant), instance, world| {
+            instance.preempt_signal(instant, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_set_signal(green, green_ahead), instance, world| {
+            instance.set_signal(green, green_ahead, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_report_approach_delay(signal), instance, world| {
+            instance.report_approach_delay(signal, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_report_waited_at_red(signal), instance, world| {
+            instance.report_waited_at_red(signal, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,293 @@
+// Intersections with conflicting connections get a traffic signal that
+// switches their lanes between green and red following a plan of phases.
+// The plan starts out as fixed-time, with phases as long as the planner laid
+// them out for the connections of the intersection, but they can be retimed.
+// Approach lanes keep track of how long cars waited in front of red,
+// which the signal collects once per cycle for every approach.
+// Approaching emergency vehicles preempt the plan and hold their lane green
+use kay::{ActorSystem, World, TypedID, Fate};
+use compact::CVec;
+use descartes::P2;
+use cb_time::actors::{Temporal, TemporalID};
+use cb_time::units::{Instant, Duration, Ticks, TICKS_PER_SIM_SECOND};
+use cb_util::log::info;
+use cb_util::session::{permits, Permission, Origin};
+use super::lane::{Lane, LaneID};
+use super::lane::connectivity::Interaction;
+use super::ui::{TransportUI, TransportUIID};
+const LOG_T: &str = "Traffic Signals";
+
+// how long each entry of lane timings lasts
+const TIMING_SLOT: Ticks = Ticks(30);
+// how far ahead approaching cars see the signal change
+const YELLOW_LEAD: Ticks = Ticks(100);
+const MIN_PHASE_DURATION: Duration = Duration(5);
+// how long a preempted phase is held after the last request
+const PREEMPTION_HOLD: Duration = Duration(20);
+
+#[derive(Compact, Clone)]
+pub struct SignalPhase {
+    pub duration: Duration,
+    // in the order of the lanes of the signal
+    green: CVec<bool>,
+}
+
+// Turns the timings of the lanes of an intersection into phases,
+// consecutive slots in which the same lanes are green make up one phase.
+// Intersections where all lanes are always green don't need a signal
+pub fn phases_from_timings(lane_timings: &[&CVec<bool>]) -> Option<CVec<SignalPhase>> {
+    let n_slots = lane_timings.iter().map(|timings| timings.len()).max()?;
+    let slot_duration = Duration(TIMING_SLOT.0 / TICKS_PER_SIM_SECOND);
+    let mut phases: CVec<SignalPhase> = CVec::new();
+
+    for slot in 0..n_slots {
+        let green: CVec<bool> = lane_timings
+            .iter()
+            .map(|timings| timings.get(slot).cloned().unwrap_or(true))
+            .collect();
+
+        let same_as_last = phases
+            .last()
+            .map_or(false, |last| last.green[..] == green[..]);
+        if same_as_last {
+            phases.last_mut().unwrap().duration += slot_duration;
+        } else {
+            phases.push(SignalPhase {
+                duration: slot_duration,
+                green,
+            });
+        }
+    }
+
+    if phases.len() > 1 {
+        Some(phases)
+    } else {
+        None
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct ApproachDelay {
+    approach: LaneID,
+    // seconds all cars together waited in front of red
+    waited: f32,
+}
+
+#[derive(Compact, Clone)]
+pub struct TrafficSignal {
+    id: TrafficSignalID,
+    position: P2,
+    lanes: CVec<LaneID>,
+    phases: CVec<SignalPhase>,
+    // the phase that is on and the one approaching cars already see coming
+    shown: Option<(u8, u8)>,
+    approach_delays: CVec<ApproachDelay>,
+    n_cycles: u32,
+    // the phase held green for an emergency vehicle, and until when
+    preempted: Option<(u8, Instant)>,
+}
+
+impl TrafficSignal {
+    pub fn spawn(
+        id: TrafficSignalID,
+        position: P2,
+        lanes: &CVec<LaneID>,
+        phases: &CVec<SignalPhase>,
+        world: &mut World,
+    ) -> TrafficSignal {
+        for lane in lanes.iter() {
+            lane.controlled_by(id, world);
+        }
+
+        TrafficSignal {
+            id,
+            position,
+            lanes: lanes.clone(),
+            phases: phases.clone(),
+            shown: None,
+            approach_delays: CVec::new(),
+            n_cycles: 0,
+            preempted: None,
+        }
+    }
+
+    fn phase_at(&self, ticks: u32) -> u8 {
+        let cycle: u32 = self
+            .phases
+            .iter()
+            .map(|phase| Ticks::from(phase.duration).0)
+            .sum();
+        let mut in_cycle = ticks % cycle.max(1);
+
+        for (idx, phase) in self.phases.iter().enumerate() {
+            let phase_ticks = Ticks::from(phase.duration).0;
+            if in_cycle < phase_ticks {
+                return idx as u8;
+            }
+            in_cycle -= phase_ticks;
+        }
+        0
+    }
+
+    // Meant for an editor of signal plans, which only whoever may plan can use
+    pub fn set_phase_duration(
+        &mut self,
+        origin: Origin,
+        phase: u8,
+        duration: Duration,
+        world: &mut World,
+    ) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
+        if let Some(signal_phase) = self.phases.get_mut(phase as usize) {
+            signal_phase.duration = duration.max(MIN_PHASE_DURATION);
+            info(
+                LOG_T,
+                format!(
+                    "Phase {} now lasts {}s",
+                    phase,
+                    signal_phase.duration.as_seconds()
+                ),
+                self.id,
+                world,
+            );
+        }
+    }
+
+    pub fn on_approach_delay(&mut self, approach: LaneID, waited: f32, _: &mut World) {
+        if let Some(delay) = self
+            .approach_delays
+            .iter_mut()
+            .find(|delay| delay.approach == approach)
+        {
+            delay.waited += waited;
+            return;
+        }
+        self.approach_delays
+            .push(ApproachDelay { approach, waited });
+    }
+
+    // holds the first phase in which the lane is green
+    pub fn preempt(&mut self, lane: LaneID, instant: Instant, _: &mut World) {
+        if let Some(idx) = self.lanes.iter().position(|&other| other == lane) {
+            if let Some(phase) = self.phases.iter().position(|phase| phase.green[idx]) {
+                self.preempted = Some((phase as u8, instant + PREEMPTION_HOLD));
+            }
+        }
+    }
+
+    pub fn lane_unbuilt(&mut self, lane: LaneID, _: &mut World) -> Fate {
+        if let Some(idx) = self.lanes.iter().position(|&other| other == lane) {
+            self.lanes.remove(idx);
+            for phase in self.phases.iter_mut() {
+                phase.green.remove(idx);
+            }
+        }
+
+        if self.lanes.is_empty() {
+            Fate::Die
+        } else {
+            Fate::Live
+        }
+    }
+
+    pub fn get_signal_info(&mut self, ui: TransportUIID, world: &mut World) {
+        let n_cycles = self.n_cycles.max(1) as f32;
+        ui.on_traffic_signal_info(
+            self.id.as_raw(),
+            self.position,
+            self.phases.iter().map(|phase| phase.duration).collect(),
+            self.approach_delays
+                .iter()
+                .map(|delay| delay.waited / n_cycles)
+                .collect(),
+            world,
+        );
+    }
+}
+
+impl Temporal for TrafficSignal {
+    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+        let ticks = current_instant.ticks() as u32;
+        let (now, ahead) = match self.preempted {
+            Some((phase, until)) if current_instant < until => (phase, phase),
+            _ => {
+                self.preempted = None;
+                (
+                    self.phase_at(ticks),
+                    self.phase_at(ticks + YELLOW_LEAD.0),
+                )
+            }
+        };
+
+        if self.shown == Some((now, ahead)) {
+            return;
+        }
+
+        if now == 0 && self.shown.map_or(false, |(before, _)| before != 0) {
+            self.n_cycles += 1;
+            for lane in self.lanes.iter() {
+                lane.report_approach_delay(self.id, world);
+            }
+        }
+
+        let now_phase = &self.phases[now as usize];
+        let ahead_phase = &self.phases[ahead as usize];
+        for (idx, lane) in self.lanes.iter().enumerate() {
+            lane.set_signal(now_phase.green[idx], ahead_phase.green[idx], world);
+        }
+        self.shown = Some((now, ahead));
+    }
+}
+
+impl Lane {
+    pub fn controlled_by(&mut self, signal: TrafficSignalID, _: &mut World) {
+        self.microtraffic.signal = Some(signal);
+    }
+
+    pub fn preempt_signal(&mut self, instant: Instant, world: &mut World) {
+        if let Some(signal) = self.microtraffic.signal {
+            signal.preempt(self.id, instant, world);
+        }
+    }
+
+    pub fn set_signal(&mut self, green: bool, green_ahead: bool, world: &mut World) {
+        self.microtraffic.yellow_to_green = green_ahead;
+        self.microtraffic.yellow_to_red = !green_ahead;
+
+        if green != self.microtraffic.green {
+            self.microtraffic.green = green;
+            for interaction in &self.connectivity.interactions {
+                if let Interaction::Previous { previous, .. } = *interaction {
+                    previous.on_signal_changed(self.id, green, world);
+                }
+            }
+        }
+    }
+
+    // intersection lanes ask the lanes leading into them,
+    // which is where cars wait
+    pub fn report_approach_delay(&mut self, signal: TrafficSignalID, world: &mut World) {
+        for interaction in &self.connectivity.interactions {
+            if let Interaction::Previous { previous, .. } = *interaction {
+                previous.report_waited_at_red(signal, world);
+            }
+        }
+    }
+
+    // several lanes of the intersection may ask, only the first gets the waiting time
+    pub fn report_waited_at_red(&mut self, signal: TrafficSignalID, world: &mut World) {
+        signal.on_approach_delay(self.id, self.microtraffic.waited_at_red, world);
+        self.microtraffic.waited_at_red = 0.0;
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<TrafficSignal>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,296 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TaxiOperatorID {
+    _raw_id: RawID
+}
+
+impl Copy for TaxiOperatorID {}
+impl Clone for TaxiOperatorID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TaxiOperatorID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TaxiOperatorID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TaxiOperatorID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TaxiOperatorID {
+    fn eq(&self, other: &TaxiOperatorID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TaxiOperatorID {}
+
+pub struct TaxiOperatorRepresentative;
+
+impl ActorOrActorTrait for TaxiOperatorRepresentative {
+    type ID = TaxiOperatorID;
+}
+
+impl TypedID for TaxiOperatorID {
+    type Target = TaxiOperatorRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        TaxiOperatorID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + TaxiOperator> TraitIDFrom<Act> for TaxiOperatorID {}
+
+impl TaxiOperatorID {
+    pub fn fare_received(self, amount: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_TaxiOperator_fare_received(amount));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<TaxiOperatorRepresentative>();
+        system.register_trait_message::<MSG_TaxiOperator_fare_received>();
+    }
+
+    pub fn register_implementor<Act: Actor + TaxiOperator>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, TaxiOperatorRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_TaxiOperator_fare_received(amount), instance, world| {
+                instance.fare_received(amount, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TaxiOperator_fare_received(pub f32);
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TaxiDemandRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for TaxiDemandRequesterID {}
+impl Clone for TaxiDemandRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TaxiDemandRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TaxiDemandRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TaxiDemandRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TaxiDemandRequesterID {
+    fn eq(&self, other: &TaxiDemandRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TaxiDemandRequesterID {}
+
+pub struct TaxiDemandRequesterRepresentative;
+
+impl ActorOrActorTrait for TaxiDemandRequesterRepresentative {
+    type ID = TaxiDemandRequesterID;
+}
+
+impl TypedID for TaxiDemandRequesterID {
+    type Target = TaxiDemandRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        TaxiDemandRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + TaxiDemandRequester> TraitIDFrom<Act> for TaxiDemandRequesterID {}
+
+impl TaxiDemandRequesterID {
+    pub fn on_unserved_calls(self, location: RoughLocationID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TaxiDemandRequester_on_unserved_calls(location));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<TaxiDemandRequesterRepresentative>();
+        system.register_trait_message::<MSG_TaxiDemandRequester_on_unserved_calls>();
+    }
+
+    pub fn register_implementor<Act: Actor + TaxiDemandRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, TaxiDemandRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_TaxiDemandRequester_on_unserved_calls(location), instance, world| {
+                instance.on_unserved_calls(location, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TaxiDemandRequester_on_unserved_calls(pub RoughLocationID);
+
+impl Actor for TaxiDispatcher {
+    type ID = TaxiDispatcherID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TaxiDispatcherID {
+    _raw_id: RawID
+}
+
+impl Copy for TaxiDispatcherID {}
+impl Clone for TaxiDispatcherID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TaxiDispatcherID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TaxiDispatcherID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TaxiDispatcherID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TaxiDispatcherID {
+    fn eq(&self, other: &TaxiDispatcherID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TaxiDispatcherID {}
+
+impl TypedID for TaxiDispatcherID {
+    type Target = TaxiDispatcher;
+
+    fn from_raw(id: RawID) -> Self {
+        TaxiDispatcherID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TaxiDispatcherID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = TaxiDispatcherID::from_raw(world.allocate_instance_id::<TaxiDispatcher>());
+        let swarm = world.local_broadcast::<TaxiDispatcher>();
+        world.send(swarm, MSG_TaxiDispatcher_spawn(id, time));
+        id
+    }
+    
+    pub fn add_fleet(self, operator: TaxiOperatorID, depot: RoughLocationID, n_taxis: u8, world: &mut World) {
+        world.send(self.as_raw(), MSG_TaxiDispatcher_add_fleet(operator, depot, n_taxis));
+    }
+    
+    pub fn remove_fleet(self, operator: TaxiOperatorID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TaxiDispatcher_remove_fleet(operator));
+    }
+    
+    pub fn estimate_pickup(self, location: RoughLocationID, position: P2, requester: ItineraryQueryID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TaxiDispatcher_estimate_pickup(location, position, requester));
+    }
+    
+    pub fn call(self, trip: TripID, position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_TaxiDispatcher_call(trip, position));
+    }
+    
+    pub fn ride_finished(self, trip: TripID, drop_off: Option < P2 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TaxiDispatcher_ride_finished(trip, drop_off));
+    }
+    
+    pub fn find_unserved_hotspot(self, min_unserved: u32, requester: TaxiDemandRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TaxiDispatcher_find_unserved_hotspot(min_unserved, requester));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TaxiDispatcher_spawn(pub TaxiDispatcherID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TaxiDispatcher_add_fleet(pub TaxiOperatorID, pub RoughLocationID, pub u8);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TaxiDispatcher_remove_fleet(pub TaxiOperatorID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TaxiDispatcher_estimate_pickup(pub RoughLocationID, pub P2, pub ItineraryQueryID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TaxiDispatcher_call(pub TripID, pub P2);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TaxiDispatcher_ride_finished(pub TripID, pub Option < P2 >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TaxiDispatcher_find_unserved_hotspot(pub u32, pub TaxiDemandRequesterID);
+
+impl Into<PositionRequesterID> for TaxiDispatcherID {
+    fn into(self) -> PositionRequesterID {
+        PositionRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for TaxiDispatcherID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    TaxiOperatorID::register_trait(system);
+    TaxiDemandRequesterID::register_trait(system);
+    PositionRequesterID::register_implementor::<TaxiDispatcher>(system);
+    SleeperID::register_implementor::<TaxiDispatcher>(system);
+    system.add_spawner::<TaxiDispatcher, _, _>(
+        |&MSG_TaxiDispatcher_spawn(id, time), world| {
+            TaxiDispatcher::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<TaxiDispatcher, _, _>(
+        |&MSG_TaxiDispatcher_add_fleet(operator, depot, n_taxis), instance, world| {
+            instance.add_fleet(operator, depot, n_taxis, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TaxiDispatcher, _, _>(
+        |&MSG_TaxiDispatcher_remove_fleet(operator), instance, world| {
+            instance.remove_fleet(operator, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TaxiDispatcher, _, _>(
+        |&MSG_TaxiDispatcher_estimate_pickup(location, position, requester), instance, world| {
+            instance.estimate_pickup(location, position, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TaxiDispatcher, _, _>(
+        |&MSG_TaxiDispatcher_call(trip, position), instance, world| {
+            instance.call(trip, position, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TaxiDispatcher, _, _>(
+        |&MSG_TaxiDispatcher_ride_finished(trip, drop_off), instance, world| {
+            instance.ride_finished(trip, drop_off, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TaxiDispatcher, _, _>(
+        |&MSG_TaxiDispatcher_find_unserved_hotspot(min_unserved, requester), instance, world| {
+            instance.find_unserved_hotspot(min_unserved, requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
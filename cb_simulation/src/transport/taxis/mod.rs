@@ -0,0 +1,238 @@
+// Households without a car of their own can call a taxi. Taxi companies put
+// their fleets at the disposal of a city-wide dispatcher, which sends the
+// closest idle taxi to whoever calls. The ride itself is driven like any other
+// car, passengers pay by distance once they arrive and the fare goes to the
+// company running the taxi. Between rides, taxis wait where they dropped
+// somebody off and drift towards where people recently called for one
+use kay::{ActorSystem, World, TypedID};
+use compact::CVec;
+use descartes::P2;
+use ordered_float::OrderedFloat;
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Duration};
+use cb_util::log::info;
+use super::pathfinding::{RoughLocationID, PositionRequester, PositionRequesterID};
+use super::pathfinding::itinerary::ItineraryQueryID;
+use super::pathfinding::trip::{TripID, TravelMode};
+const LOG_T: &str = "Taxis";
+
+const FARE_BASE: f32 = 3.0;
+const FARE_PER_KM: f32 = 1.5;
+// roads are rarely straight
+const DETOUR_FACTOR: f32 = 1.3;
+// nobody waits for a taxi from further away than this
+const MAX_PICKUP_DISTANCE: f32 = 4000.0;
+const REPOSITION_INTERVAL: Duration = Duration(5 * 60);
+// how many of the latest calls idle taxis orient themselves by
+const MAX_RECENT_CALLS: usize = 30;
+// calls nobody could serve are only kept up to this many until somebody asks
+const MAX_UNSERVED_CALLS: usize = 100;
+
+pub fn fare_for(from: P2, to: P2) -> f32 {
+    FARE_BASE + FARE_PER_KM * (to - from).norm() * DETOUR_FACTOR / 1000.0
+}
+
+fn pickup_time(taxi: P2, passenger: P2) -> Duration {
+    Duration(
+        ((passenger - taxi).norm() * DETOUR_FACTOR / TravelMode::Taxi.assumed_avg_speed()) as u32,
+    )
+}
+
+pub trait TaxiOperator {
+    fn fare_received(&mut self, amount: f32, world: &mut World);
+}
+
+// whoever wants to know where taxis are lacking
+pub trait TaxiDemandRequester {
+    fn on_unserved_calls(&mut self, location: RoughLocationID, world: &mut World);
+}
+
+#[derive(Copy, Clone)]
+pub struct Taxi {
+    operator: TaxiOperatorID,
+    position: P2,
+    serving: Option<TripID>,
+}
+
+#[derive(Compact, Clone)]
+pub struct TaxiDispatcher {
+    id: TaxiDispatcherID,
+    time: TimeID,
+    taxis: CVec<Taxi>,
+    // depots whose position isn't known yet, with how many taxis they field
+    pending_fleets: CVec<(TaxiOperatorID, RoughLocationID, u8)>,
+    recent_calls: CVec<P2>,
+    // where calls came from that no taxi could serve
+    unserved_calls: CVec<RoughLocationID>,
+}
+
+impl TaxiDispatcher {
+    pub fn spawn(id: TaxiDispatcherID, time: TimeID, world: &mut World) -> TaxiDispatcher {
+        time.wake_up_in(REPOSITION_INTERVAL.into(), id.into(), world);
+
+        TaxiDispatcher {
+            id,
+            time,
+            taxis: CVec::new(),
+            pending_fleets: CVec::new(),
+            recent_calls: CVec::new(),
+            unserved_calls: CVec::new(),
+        }
+    }
+
+    pub fn add_fleet(
+        &mut self,
+        operator: TaxiOperatorID,
+        depot: RoughLocationID,
+        n_taxis: u8,
+        world: &mut World,
+    ) {
+        self.pending_fleets.push((operator, depot, n_taxis));
+        depot.resolve_as_position(self.id.into(), depot, world);
+    }
+
+    // taxis still on a ride just don't come back
+    pub fn remove_fleet(&mut self, operator: TaxiOperatorID, _: &mut World) {
+        self.taxis.retain(|taxi| taxi.operator != operator);
+        self.pending_fleets
+            .retain(|&(pending_operator, _, _)| pending_operator != operator);
+    }
+
+    fn closest_idle_taxi(&self, position: P2) -> Option<usize> {
+        self.taxis
+            .iter()
+            .enumerate()
+            .filter(|&(_, taxi)| {
+                taxi.serving.is_none() && (taxi.position - position).norm() <= MAX_PICKUP_DISTANCE
+            })
+            .min_by_key(|&(_, taxi)| OrderedFloat((taxi.position - position).norm()))
+            .map(|(idx, _)| idx)
+    }
+
+    pub fn estimate_pickup(
+        &mut self,
+        location: RoughLocationID,
+        position: P2,
+        requester: ItineraryQueryID,
+        world: &mut World,
+    ) {
+        let maybe_pickup = self
+            .closest_idle_taxi(position)
+            .map(|idx| pickup_time(self.taxis[idx].position, position));
+        if maybe_pickup.is_none() && self.unserved_calls.len() < MAX_UNSERVED_CALLS {
+            self.unserved_calls.push(location);
+        }
+        requester.on_taxi_estimate(maybe_pickup, world);
+    }
+
+    pub fn call(&mut self, trip: TripID, position: P2, world: &mut World) {
+        self.recent_calls.push(position);
+        if self.recent_calls.len() > MAX_RECENT_CALLS {
+            self.recent_calls.remove(0);
+        }
+
+        if let Some(idx) = self.closest_idle_taxi(position) {
+            let pickup = pickup_time(self.taxis[idx].position, position);
+            let taxi = &mut self.taxis[idx];
+            taxi.serving = Some(trip);
+            taxi.position = position;
+            trip.on_taxi_dispatched(Some((pickup, taxi.operator)), world);
+        } else {
+            trip.on_taxi_dispatched(None, world);
+        }
+    }
+
+    // taxis whose passengers didn't get anywhere wait where they picked them up
+    pub fn ride_finished(&mut self, trip: TripID, drop_off: Option<P2>, _: &mut World) {
+        if let Some(taxi) = self
+            .taxis
+            .iter_mut()
+            .find(|taxi| taxi.serving == Some(trip))
+        {
+            taxi.serving = None;
+            if let Some(drop_off) = drop_off {
+                taxi.position = drop_off;
+            }
+        }
+    }
+
+    pub fn find_unserved_hotspot(
+        &mut self,
+        min_unserved: u32,
+        requester: TaxiDemandRequesterID,
+        world: &mut World,
+    ) {
+        if self.unserved_calls.len() >= min_unserved as usize {
+            if let Some(&location) = self.unserved_calls.last() {
+                requester.on_unserved_calls(location, world);
+            }
+        }
+        self.unserved_calls.clear();
+    }
+}
+
+impl PositionRequester for TaxiDispatcher {
+    fn position_resolved(
+        &mut self,
+        rough_location: RoughLocationID,
+        position: P2,
+        world: &mut World,
+    ) {
+        if let Some(idx) = self
+            .pending_fleets
+            .iter()
+            .position(|&(_, depot, _)| depot == rough_location)
+        {
+            let (operator, _, n_taxis) = self.pending_fleets.remove(idx);
+            for _ in 0..n_taxis {
+                self.taxis.push(Taxi {
+                    operator,
+                    position,
+                    serving: None,
+                });
+            }
+            info(
+                LOG_T,
+                format!("{} taxis are ready to be called", n_taxis),
+                self.id,
+                world,
+            );
+        }
+    }
+}
+
+impl Sleeper for TaxiDispatcher {
+    // idle taxis head towards the closest recent call, as far as they get in the meantime
+    fn wake(&mut self, _current_instant: Instant, world: &mut World) {
+        let max_step = TravelMode::Taxi.assumed_avg_speed() * REPOSITION_INTERVAL.as_seconds();
+        let recent_calls = &self.recent_calls;
+        for taxi in self.taxis.iter_mut().filter(|taxi| taxi.serving.is_none()) {
+            let maybe_closest_call = recent_calls
+                .iter()
+                .min_by_key(|&&call| OrderedFloat((call - taxi.position).norm()));
+            if let Some(&call) = maybe_closest_call {
+                let distance = (call - taxi.position).norm();
+                if distance <= max_step {
+                    taxi.position = call;
+                } else {
+                    taxi.position += (call - taxi.position) * (max_step / distance);
+                }
+            }
+        }
+
+        self.time
+            .wake_up_in(REPOSITION_INTERVAL.into(), self.id.into(), world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<TaxiDispatcher>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    TaxiDispatcherID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
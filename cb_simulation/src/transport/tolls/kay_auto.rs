@@ -0,0 +1,182 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for TollRegistry {
+    type ID = TollRegistryID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TollRegistryID {
+    _raw_id: RawID
+}
+
+impl Copy for TollRegistryID {}
+impl Clone for TollRegistryID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TollRegistryID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TollRegistryID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TollRegistryID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TollRegistryID {
+    fn eq(&self, other: &TollRegistryID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TollRegistryID {}
+
+impl TypedID for TollRegistryID {
+    type Target = TollRegistry;
+
+    fn from_raw(id: RawID) -> Self {
+        TollRegistryID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TollRegistryID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = TollRegistryID::from_raw(world.allocate_instance_id::<TollRegistry>());
+        let swarm = world.local_broadcast::<TollRegistry>();
+        world.send(swarm, MSG_TollRegistry_spawn(id, ));
+        id
+    }
+    
+    pub fn designate(self, origin: Origin, kind: TollKind, outline: CVec < P2 >, prices: TollPrices, world: &mut World) {
+        world.send(self.as_raw(), MSG_TollRegistry_designate(origin, kind, outline, prices));
+    }
+    
+    pub fn lift(self, origin: Origin, scheme: u16, world: &mut World) {
+        world.send(self.as_raw(), MSG_TollRegistry_lift(origin, scheme));
+    }
+    
+    pub fn find_toll_for(self, lane: LaneID, path: LinePath, on_intersection: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_TollRegistry_find_toll_for(lane, path, on_intersection));
+    }
+    
+    pub fn on_toll_collected(self, scheme: u16, amount: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_TollRegistry_on_toll_collected(scheme, amount));
+    }
+    
+    pub fn get_toll_info(self, ui: TransportUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TollRegistry_get_toll_info(ui));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TollRegistry_spawn(pub TollRegistryID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TollRegistry_designate(pub Origin, pub TollKind, pub CVec < P2 >, pub TollPrices);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TollRegistry_lift(pub Origin, pub u16);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TollRegistry_find_toll_for(pub LaneID, pub LinePath, pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TollRegistry_on_toll_collected(pub u16, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TollRegistry_get_toll_info(pub TransportUIID);
+
+
+
+
+impl LaneID {
+    pub fn apply_toll(self, toll: Toll, kind: TollKind, outline: CVec < P2 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_apply_toll(toll, kind, outline));
+    }
+    
+    pub fn set_toll(self, toll: Toll, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_set_toll(toll));
+    }
+    
+    pub fn lift_toll(self, scheme: u16, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_lift_toll(scheme));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_apply_toll(pub Toll, pub TollKind, pub CVec < P2 >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_set_toll(pub Toll);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_lift_toll(pub u16);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    
+    system.add_spawner::<TollRegistry, _, _>(
+        |&MSG_TollRegistry_spawn(id, ), world| {
+            TollRegistry::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<TollRegistry, _, _>(
+        |&MSG_TollRegistry_designate(origin, kind, ref outline, prices), instance, world| {
+            instance.designate(origin, kind, outline, prices, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TollRegistry, _, _>(
+        |&MSG_TollRegistry_lift(origin, scheme), instance, world| {
+            instance.lift(origin, scheme, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TollRegistry, _, _>(
+        |&MSG_TollRegistry_find_toll_for(lane, ref path, on_intersection), instance, world| {
+            instance.find_toll_for(lane, path, on_intersection, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TollRegistry, _, _>(
+        |&MSG_TollRegistry_on_toll_collected(scheme, amount), instance, world| {
+            instance.on_toll_collected(scheme, amount, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TollRegistry, _, _>(
+        |&MSG_TollRegistry_get_toll_info(ui), instance, world| {
+            instance.get_toll_info(ui, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_apply_toll(toll, kind, ref outline), instance, world| {
+            instance.apply_toll(toll, kind, outline, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_set_toll(toll), instance, world| {
+            instance.set_toll(toll, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_lift_toll(scheme), instance, world| {
+            instance.lift_toll(scheme, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
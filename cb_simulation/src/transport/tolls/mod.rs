@@ -0,0 +1,296 @@
+// The player can charge for driving a road or for driving into a zone.
+// Lanes of a toll road and lanes crossing into a cordon zone each charge
+// the cars leaving them, at a higher price during peak hours. What drivers
+// pay goes from their household to the city treasury, and pathfinding
+// weighs a toll as if it were extra time spent driving
+use kay::{ActorSystem, World, TypedID};
+use compact::CVec;
+use descartes::{LinePath, P2};
+use cb_time::units::{Instant, TimeOfDayRange};
+use cb_util::log::info;
+use cb_util::session::{permits, Permission, Origin};
+use super::lane::{Lane, LaneID};
+use super::microtraffic::{LaneCar, VehicleClass};
+use super::ui::{TransportUI, TransportUIID};
+const LOG_T: &str = "Tolls";
+
+// how many seconds of driving paying one unit of money feels like
+pub const SECONDS_PER_TOLL_UNIT: f32 = 60.0;
+// lanes this close to a drawn toll road belong to it
+const TOLL_ROAD_TOLERANCE: f32 = 10.0;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum TollKind {
+    Road,
+    Cordon,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct TollPrices {
+    pub base: f32,
+    pub peak: f32,
+    pub peak_hours: TimeOfDayRange,
+}
+
+impl TollPrices {
+    pub fn at(&self, instant: Instant) -> f32 {
+        if self.peak_hours.contains(instant) {
+            self.peak
+        } else {
+            self.base
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Toll {
+    pub scheme: u16,
+    pub prices: TollPrices,
+}
+
+#[derive(Compact, Clone)]
+pub struct TollScheme {
+    id: u16,
+    kind: TollKind,
+    // the drawn road, or the boundary of the zone
+    outline: CVec<P2>,
+    prices: TollPrices,
+    collected: f32,
+}
+
+// Toll roads charge on all their lanes, cordons only on lanes that
+// start outside of the zone and end inside of it
+fn charges_on(
+    kind: TollKind,
+    outline: &CVec<P2>,
+    path: &LinePath,
+    on_intersection: bool,
+) -> bool {
+    match kind {
+        TollKind::Road => {
+            !on_intersection
+                && LinePath::new(outline.to_vec().into()).map_or(false, |road| {
+                    road.distance_to(path.start()) < TOLL_ROAD_TOLERANCE
+                        && road.distance_to(path.end()) < TOLL_ROAD_TOLERANCE
+                })
+        }
+        TollKind::Cordon => !encloses(outline, path.start()) && encloses(outline, path.end()),
+    }
+}
+
+pub fn encloses(outline: &CVec<P2>, point: P2) -> bool {
+    let n = outline.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (outline[i], outline[(i + n - 1) % n]);
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x)
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+#[derive(Compact, Clone)]
+pub struct TollRegistry {
+    id: TollRegistryID,
+    schemes: CVec<TollScheme>,
+    next_scheme: u16,
+}
+
+impl TollRegistry {
+    pub fn spawn(id: TollRegistryID, _: &mut World) -> TollRegistry {
+        TollRegistry {
+            id,
+            schemes: CVec::new(),
+            next_scheme: 0,
+        }
+    }
+
+    pub fn designate(
+        &mut self,
+        origin: Origin,
+        kind: TollKind,
+        outline: &CVec<P2>,
+        prices: TollPrices,
+        world: &mut World,
+    ) {
+        let min_points = if kind == TollKind::Cordon { 3 } else { 2 };
+        if outline.len() < min_points || !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
+        let scheme = TollScheme {
+            id: self.next_scheme,
+            kind,
+            outline: outline.clone(),
+            prices,
+            collected: 0.0,
+        };
+        self.next_scheme = self.next_scheme.wrapping_add(1);
+
+        LaneID::global_broadcast(world).apply_toll(
+            Toll {
+                scheme: scheme.id,
+                prices,
+            },
+            kind,
+            outline.clone(),
+            world,
+        );
+        info(
+            LOG_T,
+            match kind {
+                TollKind::Road => format!("Toll road #{} opened", scheme.id),
+                TollKind::Cordon => format!("Toll zone #{} designated", scheme.id),
+            },
+            self.id,
+            world,
+        );
+        self.send_scheme(&scheme, TransportUIID::global_broadcast(world), world);
+        self.schemes.push(scheme);
+    }
+
+    pub fn lift(&mut self, origin: Origin, scheme: u16, world: &mut World) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
+        if let Some(idx) = self.schemes.iter().position(|other| other.id == scheme) {
+            let lifted = self.schemes.remove(idx);
+            LaneID::global_broadcast(world).lift_toll(scheme, world);
+            info(
+                LOG_T,
+                format!(
+                    "Toll #{} lifted after collecting {:.0}",
+                    scheme, lifted.collected
+                ),
+                self.id,
+                world,
+            );
+            TransportUIID::global_broadcast(world).on_toll_scheme_removed(scheme, world);
+        }
+    }
+
+    // lanes built after a toll was designated ask whether they charge it
+    pub fn find_toll_for(
+        &mut self,
+        lane: LaneID,
+        path: &LinePath,
+        on_intersection: bool,
+        world: &mut World,
+    ) {
+        if let Some(scheme) = self
+            .schemes
+            .iter()
+            .find(|scheme| charges_on(scheme.kind, &scheme.outline, path, on_intersection))
+        {
+            lane.set_toll(
+                Toll {
+                    scheme: scheme.id,
+                    prices: scheme.prices,
+                },
+                world,
+            );
+        }
+    }
+
+    pub fn on_toll_collected(&mut self, scheme: u16, amount: f32, _: &mut World) {
+        if let Some(scheme) = self.schemes.iter_mut().find(|other| other.id == scheme) {
+            scheme.collected += amount;
+        }
+    }
+
+    fn send_scheme(&self, scheme: &TollScheme, ui: TransportUIID, world: &mut World) {
+        ui.on_toll_scheme(
+            scheme.id,
+            scheme.kind,
+            scheme.outline.clone(),
+            scheme.prices,
+            scheme.collected,
+            world,
+        );
+    }
+
+    pub fn get_toll_info(&mut self, ui: TransportUIID, world: &mut World) {
+        for scheme in self.schemes.iter() {
+            self.send_scheme(scheme, ui, world);
+        }
+    }
+}
+
+impl Lane {
+    pub fn apply_toll(
+        &mut self,
+        toll: Toll,
+        kind: TollKind,
+        outline: &CVec<P2>,
+        _: &mut World,
+    ) {
+        if self.microtraffic.toll.is_none()
+            && charges_on(
+                kind,
+                outline,
+                &self.construction.path,
+                self.connectivity.on_intersection,
+            )
+        {
+            self.microtraffic.toll = Some(toll);
+        }
+    }
+
+    pub fn set_toll(&mut self, toll: Toll, _: &mut World) {
+        self.microtraffic.toll = Some(toll);
+    }
+
+    pub fn lift_toll(&mut self, scheme: u16, _: &mut World) {
+        if self.microtraffic.toll.map_or(false, |toll| toll.scheme == scheme) {
+            self.microtraffic.toll = None;
+        }
+    }
+}
+
+// Prices only change with the time of day, routes are updated when they do
+pub fn update_toll_price(lane: &mut Lane, now: Instant) {
+    let price = lane.microtraffic.toll.map_or(0.0, |toll| toll.prices.at(now));
+    if price != lane.microtraffic.current_toll {
+        lane.microtraffic.current_toll = price;
+        lane.pathfinding.routes_changed = true;
+    }
+}
+
+// emergency vehicles ride free
+pub fn charge_toll(lane: &Lane, car: &LaneCar, world: &mut World) {
+    if car.vehicle == VehicleClass::Emergency {
+        return;
+    }
+    if let Some(toll) = lane.microtraffic.toll {
+        let price = lane.microtraffic.current_toll;
+        if price > 0.0 {
+            car.trip.pay_toll(price, world);
+            TollRegistryID::global_first(world).on_toll_collected(toll.scheme, price, world);
+        }
+    }
+}
+
+pub fn on_build(lane: &Lane, world: &mut World) {
+    TollRegistryID::global_first(world).find_toll_for(
+        lane.id,
+        lane.construction.path.clone(),
+        lane.connectivity.on_intersection,
+        world,
+    );
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<TollRegistry>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    TollRegistryID::spawn(world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,563 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for TransitNetwork {
+    type ID = TransitNetworkID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TransitNetworkID {
+    _raw_id: RawID
+}
+
+impl Copy for TransitNetworkID {}
+impl Clone for TransitNetworkID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TransitNetworkID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TransitNetworkID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TransitNetworkID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TransitNetworkID {
+    fn eq(&self, other: &TransitNetworkID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TransitNetworkID {}
+
+impl TypedID for TransitNetworkID {
+    type Target = TransitNetwork;
+
+    fn from_raw(id: RawID) -> Self {
+        TransitNetworkID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TransitNetworkID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = TransitNetworkID::from_raw(world.allocate_instance_id::<TransitNetwork>());
+        let swarm = world.local_broadcast::<TransitNetwork>();
+        world.send(swarm, MSG_TransitNetwork_spawn(id, time));
+        id
+    }
+    
+    pub fn add_line(self, origin: Origin, mode: TransitMode, points: CVec < P2 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitNetwork_add_line(origin, mode, points));
+    }
+    
+    pub fn get_track_render_info(self, ui: TransportUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitNetwork_get_track_render_info(ui));
+    }
+    
+    pub fn remove_line(self, origin: Origin, line: TransitLineID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitNetwork_remove_line(origin, line));
+    }
+    
+    pub fn plan_ride(self, source: P2, destination: P2, trip: TripID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitNetwork_plan_ride(source, destination, trip));
+    }
+    
+    pub fn estimate_ride(self, source: P2, destination: P2, requester: ItineraryQueryID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitNetwork_estimate_ride(source, destination, requester));
+    }
+    
+    pub fn estimate_park_and_ride(self, source: P2, destination: P2, requester: ItineraryQueryID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitNetwork_estimate_park_and_ride(source, destination, requester));
+    }
+    
+    pub fn plan_park_and_ride(self, source: P2, destination: P2, trip: TripID, owner: Option < TripListenerID >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitNetwork_plan_park_and_ride(source, destination, trip, owner));
+    }
+    
+    pub fn unpark(self, owner: TripListenerID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitNetwork_unpark(owner));
+    }
+    
+    pub fn forget_parked_cars(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitNetwork_forget_parked_cars());
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitNetwork_spawn(pub TransitNetworkID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitNetwork_add_line(pub Origin, pub TransitMode, pub CVec < P2 >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitNetwork_get_track_render_info(pub TransportUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitNetwork_remove_line(pub Origin, pub TransitLineID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitNetwork_plan_ride(pub P2, pub P2, pub TripID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitNetwork_estimate_ride(pub P2, pub P2, pub ItineraryQueryID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitNetwork_estimate_park_and_ride(pub P2, pub P2, pub ItineraryQueryID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitNetwork_plan_park_and_ride(pub P2, pub P2, pub TripID, pub Option < TripListenerID >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitNetwork_unpark(pub TripListenerID);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitNetwork_forget_parked_cars();
+
+
+impl Actor for TransitStop {
+    type ID = TransitStopID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TransitStopID {
+    _raw_id: RawID
+}
+
+impl Copy for TransitStopID {}
+impl Clone for TransitStopID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TransitStopID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TransitStopID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TransitStopID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TransitStopID {
+    fn eq(&self, other: &TransitStopID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TransitStopID {}
+
+impl TypedID for TransitStopID {
+    type Target = TransitStop;
+
+    fn from_raw(id: RawID) -> Self {
+        TransitStopID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TransitStopID {
+    pub fn spawn(position: P2, mode: TransitMode, world: &mut World) -> Self {
+        let id = TransitStopID::from_raw(world.allocate_instance_id::<TransitStop>());
+        let swarm = world.local_broadcast::<TransitStop>();
+        world.send(swarm, MSG_TransitStop_spawn(id, position, mode));
+        id
+    }
+    
+    pub fn attach(self, location: PreciseLocation, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitStop_attach(location));
+    }
+    
+    pub fn passenger_arrived(self, passenger: Passenger, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitStop_passenger_arrived(passenger));
+    }
+    
+    pub fn vehicle_arrived(self, vehicle: TransitVehicleID, line: TransitLineID, upcoming: CVec < TransitStopID >, free_seats: u32, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitStop_vehicle_arrived(vehicle, line, upcoming, free_seats, instant));
+    }
+    
+    pub fn line_closed(self, line: TransitLineID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitStop_line_closed(line));
+    }
+    
+    pub fn forget_passengers(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitStop_forget_passengers());
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitStop_spawn(pub TransitStopID, pub P2, pub TransitMode);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitStop_attach(pub PreciseLocation);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitStop_passenger_arrived(pub Passenger);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitStop_vehicle_arrived(pub TransitVehicleID, pub TransitLineID, pub CVec < TransitStopID >, pub u32, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitStop_line_closed(pub TransitLineID);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitStop_forget_passengers();
+
+impl Into<AttacheeID> for TransitStopID {
+    fn into(self) -> AttacheeID {
+        AttacheeID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for TransitStopID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<RoughLocationID> for TransitStopID {
+    fn into(self) -> RoughLocationID {
+        RoughLocationID::from_raw(self.as_raw())
+    }
+}
+
+
+impl LaneID {
+    pub fn try_attach_stop(self, stop: TransitStopID, position: P2, max_distance: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_try_attach_stop(stop, position, max_distance));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_try_attach_stop(pub TransitStopID, pub P2, pub f32);
+
+
+impl Actor for TransitLine {
+    type ID = TransitLineID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TransitLineID {
+    _raw_id: RawID
+}
+
+impl Copy for TransitLineID {}
+impl Clone for TransitLineID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TransitLineID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TransitLineID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TransitLineID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TransitLineID {
+    fn eq(&self, other: &TransitLineID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TransitLineID {}
+
+impl TypedID for TransitLineID {
+    type Target = TransitLine;
+
+    fn from_raw(id: RawID) -> Self {
+        TransitLineID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TransitLineID {
+    pub fn spawn(mode: TransitMode, stops: CVec < StopInfo >, time: TimeID, world: &mut World) -> Self {
+        let id = TransitLineID::from_raw(world.allocate_instance_id::<TransitLine>());
+        let swarm = world.local_broadcast::<TransitLine>();
+        world.send(swarm, MSG_TransitLine_spawn(id, mode, stops, time));
+        id
+    }
+    
+    pub fn close(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitLine_close());
+    }
+    
+    pub fn get_render_info(self, ui: TransportUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitLine_get_render_info(ui));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitLine_spawn(pub TransitLineID, pub TransitMode, pub CVec < StopInfo >, pub TimeID);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitLine_close();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitLine_get_render_info(pub TransportUIID);
+
+impl Into<SleeperID> for TransitLineID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+impl Actor for TransitVehicle {
+    type ID = TransitVehicleID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TransitVehicleID {
+    _raw_id: RawID
+}
+
+impl Copy for TransitVehicleID {}
+impl Clone for TransitVehicleID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TransitVehicleID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TransitVehicleID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TransitVehicleID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TransitVehicleID {
+    fn eq(&self, other: &TransitVehicleID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TransitVehicleID {}
+
+impl TypedID for TransitVehicleID {
+    type Target = TransitVehicle;
+
+    fn from_raw(id: RawID) -> Self {
+        TransitVehicleID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TransitVehicleID {
+    pub fn spawn(line: TransitLineID, mode: TransitMode, stops: CVec < StopInfo >, instant: Instant, world: &mut World) -> Self {
+        let id = TransitVehicleID::from_raw(world.allocate_instance_id::<TransitVehicle>());
+        let swarm = world.local_broadcast::<TransitVehicle>();
+        world.send(swarm, MSG_TransitVehicle_spawn(id, line, mode, stops, instant));
+        id
+    }
+    
+    pub fn board(self, boarding: CVec < Passenger >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitVehicle_board(boarding));
+    }
+    
+    pub fn retire(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitVehicle_retire());
+    }
+    
+    pub fn forget_passengers(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitVehicle_forget_passengers());
+    }
+    
+    pub fn get_vehicle_info(self, ui: TransportUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransitVehicle_get_vehicle_info(ui));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitVehicle_spawn(pub TransitVehicleID, pub TransitLineID, pub TransitMode, pub CVec < StopInfo >, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitVehicle_board(pub CVec < Passenger >);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitVehicle_retire();
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitVehicle_forget_passengers();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransitVehicle_get_vehicle_info(pub TransportUIID);
+
+impl Into<TemporalID> for TransitVehicleID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    
+    system.add_spawner::<TransitNetwork, _, _>(
+        |&MSG_TransitNetwork_spawn(id, time), world| {
+            TransitNetwork::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<TransitNetwork, _, _>(
+        |&MSG_TransitNetwork_add_line(origin, mode, ref points), instance, world| {
+            instance.add_line(origin, mode, points, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TransitNetwork, _, _>(
+        |&MSG_TransitNetwork_get_track_render_info(ui), instance, world| {
+            instance.get_track_render_info(ui, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TransitNetwork, _, _>(
+        |&MSG_TransitNetwork_remove_line(origin, line), instance, world| {
+            instance.remove_line(origin, line, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TransitNetwork, _, _>(
+        |&MSG_TransitNetwork_plan_ride(source, destination, trip), instance, world| {
+            instance.plan_ride(source, destination, trip, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TransitNetwork, _, _>(
+        |&MSG_TransitNetwork_estimate_ride(source, destination, requester), instance, world| {
+            instance.estimate_ride(source, destination, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TransitNetwork, _, _>(
+        |&MSG_TransitNetwork_estimate_park_and_ride(source, destination, requester), instance, world| {
+            instance.estimate_park_and_ride(source, destination, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TransitNetwork, _, _>(
+        |&MSG_TransitNetwork_plan_park_and_ride(source, destination, trip, owner), instance, world| {
+            instance.plan_park_and_ride(source, destination, trip, owner, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TransitNetwork, _, _>(
+        |&MSG_TransitNetwork_unpark(owner), instance, world| {
+            instance.unpark(owner, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TransitNetwork, _, _>(
+        |&MSG_TransitNetwork_forget_parked_cars(), instance, world| {
+            instance.forget_parked_cars(world); Fate::Live
+        }, false
+    );
+    AttacheeID::register_implementor::<TransitStop>(system);
+    SleeperID::register_implementor::<TransitStop>(system);
+    RoughLocationID::register_implementor::<TransitStop>(system);
+    system.add_spawner::<TransitStop, _, _>(
+        |&MSG_TransitStop_spawn(id, position, mode), world| {
+            TransitStop::spawn(id, position, mode, world)
+        }, false
+    );
+    
+    system.add_handler::<TransitStop, _, _>(
+        |&MSG_TransitStop_attach(location), instance, world| {
+            instance.attach(location, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TransitStop, _, _>(
+        |&MSG_TransitStop_passenger_arrived(passenger), instance, world| {
+            instance.passenger_arrived(passenger, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TransitStop, _, _>(
+        |&MSG_TransitStop_vehicle_arrived(vehicle, line, ref upcoming, free_seats, instant), instance, world| {
+            instance.vehicle_arrived(vehicle, line, upcoming, free_seats, instant, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TransitStop, _, _>(
+        |&MSG_TransitStop_line_closed(line), instance, world| {
+            instance.line_closed(line, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TransitStop, _, _>(
+        |&MSG_TransitStop_forget_passengers(), instance, world| {
+            instance.forget_passengers(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_try_attach_stop(stop, position, max_distance), instance, world| {
+            instance.try_attach_stop(stop, position, max_distance, world); Fate::Live
+        }, false
+    );
+    SleeperID::register_implementor::<TransitLine>(system);
+    system.add_spawner::<TransitLine, _, _>(
+        |&MSG_TransitLine_spawn(id, mode, ref stops, time), world| {
+            TransitLine::spawn(id, mode, stops, time, world)
+        }, false
+    );
+    
+    system.add_handler::<TransitLine, _, _>(
+        |&MSG_TransitLine_close(), instance, world| {
+            instance.close(world)
+        }, false
+    );
+    
+    system.add_handler::<TransitLine, _, _>(
+        |&MSG_TransitLine_get_render_info(ui), instance, world| {
+            instance.get_render_info(ui, world); Fate::Live
+        }, false
+    );
+    TemporalID::register_implementor::<TransitVehicle>(system);
+    system.add_spawner::<TransitVehicle, _, _>(
+        |&MSG_TransitVehicle_spawn(id, line, mode, ref stops, instant), world| {
+            TransitVehicle::spawn(id, line, mode, stops, instant, world)
+        }, false
+    );
+    
+    system.add_handler::<TransitVehicle, _, _>(
+        |&MSG_TransitVehicle_board(ref boarding), instance, world| {
+            instance.board(boarding, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TransitVehicle, _, _>(
+        |&MSG_TransitVehicle_retire(), instance, world| {
+            instance.retire(world)
+        }, false
+    );
+    
+    system.add_handler::<TransitVehicle, _, _>(
+        |&MSG_TransitVehicle_forget_passengers(), instance, world| {
+            instance.forget_passengers(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TransitVehicle, _, _>(
+        |&MSG_TransitVehicle_get_vehicle_info(ui), instance, world| {
+            instance.get_vehicle_info(ui, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,940 @@
+// Buses run along lines the player draws over the roads. Every point of a line
+// becomes a stop on the lane closest to it, shared by all lines that stop there.
+// Rail lines run on track of their own between stations instead, which is laid
+// in a straight line from station to station and shared by all rail lines that
+// use it. Stations connect to the closest road a bit further away than stops,
+// that's where passengers come and go.
+// Vehicles aren't simulated in traffic like cars, they take as long as the way
+// between two stops takes at their average speed and run back and forth along
+// their line on a fixed schedule. Only while dwelling at a stop, buses hold up
+// the traffic in its lane. People without a car walk to a stop, wait
+// for the next vehicle of their line, ride it and walk the rest of the way.
+// Rail stations come with a lot where people can leave their car for the day
+// and ride on from there, as long as there is a free space left
+use kay::{ActorSystem, World, TypedID, Actor, Fate};
+use compact::CVec;
+use descartes::{P2, V2};
+use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Duration, Ticks, TimeOfDayRange};
+use cb_util::log::info;
+use cb_util::session::{permits, Permission, Origin};
+use dimensions::LANE_DISTANCE;
+use super::lane::{Lane, LaneID};
+use super::pathfinding::{PreciseLocation, Location, RoughLocation, RoughLocationResolve, Attachee,
+AttacheeID};
+use super::pathfinding::trip::{TripID, TripListenerID, TripResult, TripFate, TravelMode};
+use super::pathfinding::itinerary::ItineraryQueryID;
+use super::ui::{TransportUI, TransportUIID};
+use super::curbside;
+const LOG_T: &str = "Transit";
+
+// points of lines closer than this to an existing stop use that stop
+const STOP_MERGE_DISTANCE: f32 = 30.0;
+// roads are never straight, so the actual way is longer than as the crow flies
+const DETOUR_FACTOR: f32 = 1.3;
+// parking and walking from the lot to the platform
+pub const PARK_AND_RIDE_TRANSFER: Duration = Duration(2 * 60);
+
+// Lines run from early in the morning until late in the evening
+pub fn service_hours() -> TimeOfDayRange {
+    TimeOfDayRange::new(5, 0, 23, 0)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum TransitMode {
+    Bus,
+    Rail,
+}
+
+impl TransitMode {
+    // meters per second, buses are slowed down by traffic and boarding on the way
+    pub fn speed(self) -> f32 {
+        match self {
+            TransitMode::Bus => 7.0,
+            TransitMode::Rail => 18.0,
+        }
+    }
+
+    pub fn capacity(self) -> usize {
+        match self {
+            TransitMode::Bus => 60,
+            TransitMode::Rail => 400,
+        }
+    }
+
+    pub fn dwell_time(self) -> Duration {
+        match self {
+            TransitMode::Bus => Duration(30),
+            TransitMode::Rail => Duration(45),
+        }
+    }
+
+    pub fn headway(self) -> Duration {
+        match self {
+            TransitMode::Bus => Duration(15 * 60),
+            TransitMode::Rail => Duration(10 * 60),
+        }
+    }
+
+    // nobody walks further than this to or from a stop
+    pub fn max_walk(self) -> f32 {
+        match self {
+            TransitMode::Bus => 800.0,
+            TransitMode::Rail => 1200.0,
+        }
+    }
+
+    // how many cars can be left at a stop for the day, bus stops have no lot
+    fn park_and_ride_spaces(self) -> u16 {
+        match self {
+            TransitMode::Bus => 0,
+            TransitMode::Rail => 150,
+        }
+    }
+
+    // how far from a stop the road it connects to may be
+    fn max_transfer_distance(self) -> f32 {
+        match self {
+            TransitMode::Bus => 3.0 * LANE_DISTANCE,
+            TransitMode::Rail => 60.0,
+        }
+    }
+
+    // track goes straight from station to station
+    fn detour_factor(self) -> f32 {
+        match self {
+            TransitMode::Bus => DETOUR_FACTOR,
+            TransitMode::Rail => 1.0,
+        }
+    }
+}
+
+pub fn walking_time(from: P2, to: P2) -> f32 {
+    (to - from).norm() * DETOUR_FACTOR / TravelMode::Walking.assumed_avg_speed()
+}
+
+fn driving_time(from: P2, to: P2) -> f32 {
+    (to - from).norm() * DETOUR_FACTOR / TravelMode::Car.assumed_avg_speed()
+}
+
+fn riding_time(mode: TransitMode, from: P2, to: P2) -> f32 {
+    (to - from).norm() * mode.detour_factor() / mode.speed() + mode.dwell_time().as_seconds()
+}
+
+#[derive(Copy, Clone)]
+pub struct StopInfo {
+    stop: TransitStopID,
+    position: P2,
+    mode: TransitMode,
+}
+
+#[derive(Compact, Clone)]
+pub struct LineInfo {
+    line: TransitLineID,
+    mode: TransitMode,
+    stops: CVec<StopInfo>,
+}
+
+// Track between two stations, laid by the first rail line that runs there
+// and torn down once the last one is closed
+#[derive(Copy, Clone)]
+pub struct TrackSegment {
+    from: StopInfo,
+    to: StopInfo,
+    n_lines: u32,
+}
+
+impl TrackSegment {
+    fn connects(&self, a: TransitStopID, b: TransitStopID) -> bool {
+        (self.from.stop == a && self.to.stop == b) || (self.from.stop == b && self.to.stop == a)
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct RidePlan {
+    pub line: TransitLineID,
+    pub board: TransitStopID,
+    pub board_position: P2,
+    pub alight: TransitStopID,
+    pub alight_position: P2,
+}
+
+#[derive(Copy, Clone)]
+pub struct RideEstimate {
+    pub walk_to_stop: Duration,
+    // half a headway of waiting on average
+    pub wait_and_ride: Duration,
+    pub walk_from_stop: Duration,
+}
+
+impl RideEstimate {
+    pub fn total(&self) -> Duration {
+        self.walk_to_stop + self.wait_and_ride + self.walk_from_stop
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct ParkAndRideEstimate {
+    pub drive: Duration,
+    pub transfer: Duration,
+    pub wait_and_ride: Duration,
+    pub walk_from_stop: Duration,
+}
+
+#[derive(Copy, Clone)]
+pub struct ParkAndRideLot {
+    stop: TransitStopID,
+    capacity: u16,
+    occupied: u16,
+}
+
+// whose car stands at which station, so it frees its space when they drive off again
+#[derive(Copy, Clone)]
+pub struct ParkedAtStation {
+    owner: TripListenerID,
+    stop: TransitStopID,
+}
+
+#[derive(Copy, Clone)]
+pub struct Passenger {
+    pub trip: TripID,
+    pub line: TransitLineID,
+    pub alight: TransitStopID,
+}
+
+#[derive(Compact, Clone)]
+pub struct TransitNetwork {
+    id: TransitNetworkID,
+    time: TimeID,
+    stops: CVec<StopInfo>,
+    lines: CVec<LineInfo>,
+    tracks: CVec<TrackSegment>,
+    lots: CVec<ParkAndRideLot>,
+    parked: CVec<ParkedAtStation>,
+}
+
+impl TransitNetwork {
+    pub fn spawn(id: TransitNetworkID, time: TimeID, _: &mut World) -> TransitNetwork {
+        TransitNetwork {
+            id,
+            time,
+            stops: CVec::new(),
+            lines: CVec::new(),
+            tracks: CVec::new(),
+            lots: CVec::new(),
+            parked: CVec::new(),
+        }
+    }
+
+    pub fn add_line(
+        &mut self,
+        origin: Origin,
+        mode: TransitMode,
+        points: &CVec<P2>,
+        world: &mut World,
+    ) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
+        let mut line_stops: CVec<StopInfo> = CVec::new();
+
+        for &point in points.iter() {
+            let maybe_existing = self
+                .stops
+                .iter()
+                .find(|info| {
+                    info.mode == mode && (info.position - point).norm() < STOP_MERGE_DISTANCE
+                })
+                .cloned();
+            let stop_info = maybe_existing.unwrap_or_else(|| {
+                let new_info = StopInfo {
+                    stop: TransitStopID::spawn(point, mode, world),
+                    position: point,
+                    mode,
+                };
+                self.stops.push(new_info);
+                if mode.park_and_ride_spaces() > 0 {
+                    self.lots.push(ParkAndRideLot {
+                        stop: new_info.stop,
+                        capacity: mode.park_and_ride_spaces(),
+                        occupied: 0,
+                    });
+                }
+                new_info
+            });
+
+            // points drawn close together don't make vehicles stop twice
+            if line_stops
+                .last()
+                .map_or(true, |last| last.stop != stop_info.stop)
+            {
+                line_stops.push(stop_info);
+            }
+        }
+
+        if line_stops.len() < 2 {
+            return;
+        }
+
+        if mode == TransitMode::Rail {
+            let mut laid_track = false;
+            for pair in line_stops.windows(2) {
+                if let Some(segment) = self
+                    .tracks
+                    .iter_mut()
+                    .find(|segment| segment.connects(pair[0].stop, pair[1].stop))
+                {
+                    segment.n_lines += 1;
+                    continue;
+                }
+                self.tracks.push(TrackSegment {
+                    from: pair[0],
+                    to: pair[1],
+                    n_lines: 1,
+                });
+                laid_track = true;
+            }
+            if laid_track {
+                self.send_tracks(TransportUIID::global_broadcast(world), world);
+            }
+        }
+
+        let line = TransitLineID::spawn(mode, line_stops.clone(), self.time, world);
+        TransportUIID::global_broadcast(world).on_transit_line(
+            line.as_raw(),
+            mode,
+            line_stops.iter().map(|info| info.position).collect(),
+            world,
+        );
+        info(
+            LOG_T,
+            format!(
+                "Opened a {} line with {} stops",
+                match mode {
+                    TransitMode::Bus => "bus",
+                    TransitMode::Rail => "rail",
+                },
+                line_stops.len()
+            ),
+            self.id,
+            world,
+        );
+        self.lines.push(LineInfo {
+            line,
+            mode,
+            stops: line_stops,
+        });
+    }
+
+    fn send_tracks(&self, ui: TransportUIID, world: &mut World) {
+        ui.on_rail_tracks(
+            self.tracks
+                .iter()
+                .map(|segment| [segment.from.position, segment.to.position])
+                .collect(),
+            world,
+        );
+    }
+
+    pub fn get_track_render_info(&mut self, ui: TransportUIID, world: &mut World) {
+        self.send_tracks(ui, world);
+    }
+
+    pub fn remove_line(&mut self, origin: Origin, line: TransitLineID, world: &mut World) {
+        if !permits(origin, Permission::Planning, self.id, world) {
+            return;
+        }
+
+        let maybe_line_info = self.lines.iter().find(|info| info.line == line).cloned();
+        if let Some(line_info) = maybe_line_info {
+            self.lines.retain(|info| info.line != line);
+
+            if line_info.mode == TransitMode::Rail {
+                for pair in line_info.stops.windows(2) {
+                    if let Some(segment) = self
+                        .tracks
+                        .iter_mut()
+                        .find(|segment| segment.connects(pair[0].stop, pair[1].stop))
+                    {
+                        segment.n_lines -= 1;
+                    }
+                }
+                let n_segments = self.tracks.len();
+                self.tracks.retain(|segment| segment.n_lines > 0);
+                if self.tracks.len() != n_segments {
+                    self.send_tracks(TransportUIID::global_broadcast(world), world);
+                }
+            }
+
+            line.close(world);
+            TransportUIID::global_broadcast(world).on_transit_line_removed(line.as_raw(), world);
+        }
+    }
+
+    fn in_walking_distance(position: P2, stop: &StopInfo) -> bool {
+        (stop.position - position).norm() <= stop.mode.max_walk()
+    }
+
+    // The ride on any line with stops in walking distance of both ends that gets there the fastest
+    fn best_ride(&self, source: P2, destination: P2) -> Option<(RidePlan, RideEstimate)> {
+        self.best_ride_by(destination, |stop| {
+            if Self::in_walking_distance(source, stop) {
+                Some(Duration(walking_time(source, stop.position) as u32))
+            } else {
+                None
+            }
+        })
+    }
+
+    // The ride from any stop that can be gotten to at all to one in walking distance
+    // of the destination that gets there the fastest, with the way to the stop
+    // in place of walking there
+    fn best_ride_by<F: Fn(&StopInfo) -> Option<Duration>>(
+        &self,
+        destination: P2,
+        way_to_stop: F,
+    ) -> Option<(RidePlan, RideEstimate)> {
+        let mut best: Option<(RidePlan, RideEstimate)> = None;
+
+        for line_info in self.lines.iter() {
+            let stops = &line_info.stops;
+            let mode = line_info.mode;
+
+            for (board_idx, board) in stops.iter().enumerate() {
+                let to_stop = match way_to_stop(board) {
+                    Some(to_stop) => to_stop,
+                    None => continue,
+                };
+
+                for (alight_idx, alight) in stops.iter().enumerate() {
+                    if alight_idx == board_idx || !Self::in_walking_distance(destination, alight) {
+                        continue;
+                    }
+
+                    let (from, to) = if board_idx < alight_idx {
+                        (board_idx, alight_idx)
+                    } else {
+                        (alight_idx, board_idx)
+                    };
+                    let ride: f32 = stops[from..=to]
+                        .windows(2)
+                        .map(|pair| riding_time(mode, pair[0].position, pair[1].position))
+                        .sum();
+                    let estimate = RideEstimate {
+                        walk_to_stop: to_stop,
+                        wait_and_ride: Duration((mode.headway().as_seconds() / 2.0 + ride) as u32),
+                        walk_from_stop: Duration(walking_time(alight.position, destination) as u32),
+                    };
+
+                    if best.map_or(true, |(_, best_estimate)| {
+                        estimate.total() < best_estimate.total()
+                    }) {
+                        best = Some((
+                            RidePlan {
+                                line: line_info.line,
+                                board: board.stop,
+                                board_position: board.position,
+                                alight: alight.stop,
+                                alight_position: alight.position,
+                            },
+                            estimate,
+                        ));
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
+    // Trips only take a ride if it's any faster than walking all the way
+    pub fn plan_ride(&mut self, source: P2, destination: P2, trip: TripID, world: &mut World) {
+        let maybe_plan = self
+            .best_ride(source, destination)
+            .filter(|&(_, estimate)| {
+                estimate.total().as_seconds() < walking_time(source, destination)
+            })
+            .map(|(plan, _)| plan);
+        trip.ride_planned(maybe_plan, world);
+    }
+
+    pub fn estimate_ride(
+        &mut self,
+        source: P2,
+        destination: P2,
+        requester: ItineraryQueryID,
+        world: &mut World,
+    ) {
+        let maybe_estimate = self
+            .best_ride(source, destination)
+            .map(|(_, estimate)| estimate);
+        requester.on_ride_estimate(maybe_estimate, world);
+    }
+
+    fn free_lot_at(&self, stop: TransitStopID) -> Option<usize> {
+        self.lots
+            .iter()
+            .position(|lot| lot.stop == stop && lot.occupied < lot.capacity)
+    }
+
+    // Driving to any station with a free space, leaving the car there and riding on
+    fn best_park_and_ride(
+        &self,
+        source: P2,
+        destination: P2,
+    ) -> Option<(RidePlan, ParkAndRideEstimate)> {
+        self.best_ride_by(destination, |stop| {
+            self.free_lot_at(stop.stop).map(|_| {
+                Duration(driving_time(source, stop.position) as u32) + PARK_AND_RIDE_TRANSFER
+            })
+        })
+        .map(|(plan, estimate)| {
+            (
+                plan,
+                ParkAndRideEstimate {
+                    drive: Duration(driving_time(source, plan.board_position) as u32),
+                    transfer: PARK_AND_RIDE_TRANSFER,
+                    wait_and_ride: estimate.wait_and_ride,
+                    walk_from_stop: estimate.walk_from_stop,
+                },
+            )
+        })
+    }
+
+    pub fn estimate_park_and_ride(
+        &mut self,
+        source: P2,
+        destination: P2,
+        requester: ItineraryQueryID,
+        world: &mut World,
+    ) {
+        let maybe_estimate = self
+            .best_park_and_ride(source, destination)
+            .map(|(_, estimate)| estimate);
+        requester.on_park_and_ride_estimate(maybe_estimate, world);
+    }
+
+    // The space is taken right away, so nobody drives to a station
+    // that fills up while they are on their way
+    pub fn plan_park_and_ride(
+        &mut self,
+        source: P2,
+        destination: P2,
+        trip: TripID,
+        owner: Option<TripListenerID>,
+        world: &mut World,
+    ) {
+        let maybe_plan = self
+            .best_park_and_ride(source, destination)
+            .map(|(plan, _)| plan);
+
+        // cars nobody will drive off again don't take up a space
+        if let (Some(plan), Some(owner)) = (maybe_plan, owner) {
+            if let Some(idx) = self.free_lot_at(plan.board) {
+                self.lots[idx].occupied += 1;
+                self.parked.push(ParkedAtStation {
+                    owner,
+                    stop: plan.board,
+                });
+            }
+        }
+
+        trip.park_and_ride_planned(maybe_plan, world);
+    }
+
+    pub fn unpark(&mut self, owner: TripListenerID, _: &mut World) {
+        if let Some(idx) = self.parked.iter().position(|car| car.owner == owner) {
+            let car = self.parked.remove(idx);
+            if let Some(lot) = self.lots.iter_mut().find(|lot| lot.stop == car.stop) {
+                lot.occupied = lot.occupied.saturating_sub(1);
+            }
+        }
+    }
+
+    // cars belong to households, which are dropped from savegames that can't be fully loaded
+    pub fn forget_parked_cars(&mut self, _: &mut World) {
+        self.parked.clear();
+        for lot in self.lots.iter_mut() {
+            lot.occupied = 0;
+        }
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct TransitStop {
+    id: TransitStopID,
+    position: P2,
+    mode: TransitMode,
+    location: Option<PreciseLocation>,
+    waiting: CVec<Passenger>,
+}
+
+impl TransitStop {
+    pub fn spawn(
+        id: TransitStopID,
+        position: P2,
+        mode: TransitMode,
+        world: &mut World,
+    ) -> TransitStop {
+        LaneID::global_broadcast(world).try_attach_stop(
+            id,
+            position,
+            mode.max_transfer_distance(),
+            world,
+        );
+
+        TransitStop {
+            id,
+            position,
+            mode,
+            location: None,
+            waiting: CVec::new(),
+        }
+    }
+
+    pub fn attach(&mut self, location: PreciseLocation, world: &mut World) {
+        if self.location.is_none() {
+            self.location = Some(location);
+            location.link.add_attachee(self.id_as(), world);
+        }
+    }
+
+    pub fn passenger_arrived(&mut self, passenger: Passenger, _: &mut World) {
+        self.waiting.push(passenger);
+    }
+
+    // Whoever waits for this line and wants to get off at one of the stops
+    // still ahead of the vehicle gets on, as long as there is room
+    pub fn vehicle_arrived(
+        &mut self,
+        vehicle: TransitVehicleID,
+        line: TransitLineID,
+        upcoming: &CVec<TransitStopID>,
+        free_seats: u32,
+        instant: Instant,
+        world: &mut World,
+    ) {
+        let mut boarding = CVec::new();
+        let mut still_waiting = CVec::new();
+
+        for &passenger in self.waiting.iter() {
+            if passenger.line == line
+                && upcoming.contains(&passenger.alight)
+                && boarding.len() < free_seats as usize
+            {
+                boarding.push(passenger);
+            } else {
+                still_waiting.push(passenger);
+            }
+        }
+
+        self.waiting = still_waiting;
+
+        if !boarding.is_empty() {
+            vehicle.board(boarding, world);
+        }
+
+        if let (TransitMode::Bus, Some(location)) = (self.mode, self.location) {
+            curbside::bus_dwelling(location, instant + self.mode.dwell_time(), world);
+        }
+    }
+
+    pub fn line_closed(&mut self, line: TransitLineID, world: &mut World) {
+        for passenger in self
+            .waiting
+            .iter()
+            .filter(|passenger| passenger.line == line)
+        {
+            passenger.trip.finish(
+                TripResult {
+                    location_now: Some(self.id_as()),
+                    fate: TripFate::NoRoute,
+                },
+                world,
+            );
+        }
+        self.waiting.retain(|passenger| passenger.line != line);
+    }
+
+    // passengers are trips, which are dropped together with households
+    pub fn forget_passengers(&mut self, _: &mut World) {
+        self.waiting.clear();
+    }
+}
+
+impl Attachee for TransitStop {
+    fn location_changed(
+        &mut self,
+        _old: Option<Location>,
+        maybe_new: Option<Location>,
+        world: &mut World,
+    ) {
+        if let Some(new) = maybe_new {
+            self.location
+                .as_mut()
+                .expect("Only an existing location can change")
+                .location = new;
+        } else {
+            self.location = None;
+            TimeID::local_first(world).wake_up_in(
+                Ticks::from(Duration::from_minutes(10)),
+                self.id_as(),
+                world,
+            );
+        }
+    }
+}
+
+impl Sleeper for TransitStop {
+    // the lane of the stop was unbuilt, maybe there is another one now
+    fn wake(&mut self, _: Instant, world: &mut World) {
+        if self.location.is_none() {
+            LaneID::global_broadcast(world).try_attach_stop(
+                self.id,
+                self.position,
+                self.mode.max_transfer_distance(),
+                world,
+            );
+            TimeID::local_first(world).wake_up_in(
+                Ticks::from(Duration::from_minutes(10)),
+                self.id_as(),
+                world,
+            );
+        }
+    }
+}
+
+impl RoughLocation for TransitStop {
+    fn resolve(&self) -> RoughLocationResolve {
+        RoughLocationResolve::Done(self.location, self.position)
+    }
+}
+
+impl Lane {
+    pub fn try_attach_stop(
+        &mut self,
+        stop: TransitStopID,
+        position: P2,
+        max_distance: f32,
+        world: &mut World,
+    ) {
+        if let Some(location) = self.pathfinding.location {
+            if !self.connectivity.on_intersection {
+                if let Some((offset, _)) =
+                    self.construction
+                        .path
+                        .project_with_max_distance(position, 0.5, max_distance)
+                {
+                    stop.attach(PreciseLocation { location, offset }, world);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct TransitLine {
+    id: TransitLineID,
+    time: TimeID,
+    mode: TransitMode,
+    stops: CVec<StopInfo>,
+    service_hours: TimeOfDayRange,
+}
+
+impl TransitLine {
+    pub fn spawn(
+        id: TransitLineID,
+        mode: TransitMode,
+        stops: &CVec<StopInfo>,
+        time: TimeID,
+        world: &mut World,
+    ) -> TransitLine {
+        time.wake_up_in(Ticks(0), id.into(), world);
+
+        TransitLine {
+            id,
+            time,
+            mode,
+            stops: stops.clone(),
+            service_hours: service_hours(),
+        }
+    }
+
+    // vehicles already on their way finish their run
+    pub fn close(&mut self, world: &mut World) -> Fate {
+        for info in self.stops.iter() {
+            info.stop.line_closed(self.id, world);
+        }
+        Fate::Die
+    }
+
+    pub fn get_render_info(&mut self, ui: TransportUIID, world: &mut World) {
+        ui.on_transit_line(
+            self.id.as_raw(),
+            self.mode,
+            self.stops.iter().map(|info| info.position).collect(),
+            world,
+        );
+    }
+}
+
+impl Sleeper for TransitLine {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        if self.service_hours.contains(current_instant) {
+            TransitVehicleID::spawn(
+                self.id,
+                self.mode,
+                self.stops.clone(),
+                current_instant,
+                world,
+            );
+        }
+        self.time
+            .wake_up_in(self.mode.headway().into(), self.id_as(), world);
+    }
+}
+
+// A bus or a train, depending on its line
+#[derive(Compact, Clone)]
+pub struct TransitVehicle {
+    id: TransitVehicleID,
+    line: TransitLineID,
+    mode: TransitMode,
+    // all stops of the line there and back again
+    route: CVec<StopInfo>,
+    next_stop_idx: u32,
+    departed: Instant,
+    arrives: Instant,
+    passengers: CVec<Passenger>,
+    position: P2,
+    direction: V2,
+}
+
+impl TransitVehicle {
+    pub fn spawn(
+        id: TransitVehicleID,
+        line: TransitLineID,
+        mode: TransitMode,
+        stops: &CVec<StopInfo>,
+        instant: Instant,
+        _: &mut World,
+    ) -> TransitVehicle {
+        let route: CVec<StopInfo> = stops
+            .iter()
+            .chain(stops.iter().rev().skip(1))
+            .cloned()
+            .collect();
+        let position = route[0].position;
+
+        TransitVehicle {
+            id,
+            line,
+            mode,
+            next_stop_idx: 0,
+            departed: instant,
+            arrives: instant,
+            passengers: CVec::new(),
+            position,
+            direction: (route[1].position - position).normalize(),
+            route,
+        }
+    }
+
+    pub fn board(&mut self, boarding: &CVec<Passenger>, _: &mut World) {
+        for &passenger in boarding.iter() {
+            self.passengers.push(passenger);
+        }
+    }
+
+    pub fn retire(&mut self, _: &mut World) -> Fate {
+        Fate::Die
+    }
+
+    pub fn forget_passengers(&mut self, _: &mut World) {
+        self.passengers.clear();
+    }
+
+    pub fn get_vehicle_info(&mut self, ui: TransportUIID, world: &mut World) {
+        ui.on_transit_vehicle_info(
+            self.id.as_raw(),
+            self.mode,
+            [self.position.x, self.position.y],
+            [self.direction.x, self.direction.y],
+            world,
+        );
+    }
+
+    fn arrive(&mut self, instant: Instant, world: &mut World) {
+        let stop_idx = self.next_stop_idx as usize;
+        let stop = self.route[stop_idx].stop;
+
+        for passenger in self
+            .passengers
+            .iter()
+            .filter(|passenger| passenger.alight == stop)
+        {
+            passenger.trip.ride_finished(world);
+        }
+        self.passengers.retain(|passenger| passenger.alight != stop);
+
+        if stop_idx + 1 >= self.route.len() {
+            // past its last stop, so it doesn't arrive anywhere again until it's gone
+            self.next_stop_idx += 1;
+            self.id.retire(world);
+            return;
+        }
+
+        let upcoming = self.route[stop_idx + 1..]
+            .iter()
+            .map(|info| info.stop)
+            .collect();
+        stop.vehicle_arrived(
+            self.id,
+            self.line,
+            upcoming,
+            self.mode.capacity().saturating_sub(self.passengers.len()) as u32,
+            instant,
+            world,
+        );
+
+        let from = self.route[stop_idx].position;
+        let to = self.route[stop_idx + 1].position;
+        self.next_stop_idx += 1;
+        self.departed = instant;
+        self.arrives = instant + Duration(riding_time(self.mode, from, to) as u32);
+        self.direction = (to - from).normalize();
+    }
+}
+
+impl Temporal for TransitVehicle {
+    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+        if current_instant >= self.arrives && (self.next_stop_idx as usize) < self.route.len() {
+            self.arrive(current_instant, world);
+        }
+
+        let stop_idx = self.next_stop_idx as usize;
+        if stop_idx > 0 && stop_idx < self.route.len() {
+            let from = self.route[stop_idx - 1].position;
+            let to = self.route[stop_idx].position;
+            let progress = (current_instant.ticks() - self.departed.ticks()) as f32
+                / (self.arrives.ticks() - self.departed.ticks()).max(1) as f32;
+            self.position = from + (to - from) * progress.min(1.0);
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<TransitNetwork>();
+    system.register::<TransitStop>();
+    system.register::<TransitLine>();
+    system.register::<TransitVehicle>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    TransitNetworkID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,61 @@
+// Roads planned with bike lanes get one on each side, between the outermost
+// lane and the sidewalk. Like sidewalks, they are cut where the road goes
+// through intersections, cyclists cross those together with the cars
+use descartes::{P2, LinePath};
+use std::collections::HashMap;
+
+use cb_planning::{StepID, PrototypeID, Prototype, GestureID};
+use planning::CBPrototypeKind;
+use super::{RoadIntent, RoadClass, RoadPrototype, BikeLanePrototype, LANE_DISTANCE};
+use super::sidewalks::cut_at_intersections;
+use dimensions::BIKE_LANE_OFFSET;
+
+pub fn bike_lane_prototypes(
+    gesture_intent_smooth_paths: &[(GestureID, StepID, RoadIntent, LinePath)],
+    intersection_prototypes: &[Prototype<CBPrototypeKind>],
+) -> Vec<Prototype<CBPrototypeKind>> {
+    let mut prototypes = Vec::new();
+
+    for &(gesture_id, step_id, road_intent, ref path) in gesture_intent_smooth_paths {
+        // nobody is supposed to cycle along highways
+        if !road_intent.bike_lanes || road_intent.class == RoadClass::Highway {
+            continue;
+        }
+
+        // crossings are the business of sidewalks
+        let mut intersection_edges: HashMap<(PrototypeID, bool), Vec<P2>> = HashMap::new();
+
+        for &(n_lanes, side) in &[
+            (road_intent.n_lanes_forward, 1.0),
+            (road_intent.n_lanes_backward, -1.0),
+        ] {
+            if n_lanes == 0 {
+                continue;
+            }
+            let offset = side * (f32::from(n_lanes) * LANE_DISTANCE + BIKE_LANE_OFFSET);
+            let raw_bike_lane_path = match path.shift_orthogonally(offset) {
+                Some(raw_bike_lane_path) => raw_bike_lane_path,
+                None => continue,
+            };
+            let bike_lane_influence_id =
+                PrototypeID::from_influences(("bike lane", gesture_id, step_id, side > 0.0));
+
+            for (subsection, id) in cut_at_intersections(
+                &raw_bike_lane_path,
+                bike_lane_influence_id,
+                intersection_prototypes,
+                &mut intersection_edges,
+            ) {
+                prototypes.push(Prototype {
+                    representative_position: subsection.points[0],
+                    kind: CBPrototypeKind::Road(RoadPrototype::BikeLane(BikeLanePrototype(
+                        subsection,
+                    ))),
+                    id,
+                });
+            }
+        }
+    }
+
+    prototypes
+}
@@ -1,13 +1,196 @@
-use compact::CVec;
-use descartes::{ArcLinePath, Intersect, WithUniqueOrthogonal,
-RoughEq};
+use compact::{CVec, CHashMap};
+use descartes::{N, P2, V2, ArcLinePath, Intersect, WithUniqueOrthogonal,
+RoughEq, PointContainer};
+use std::f32::consts::{PI, FRAC_PI_2};
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
 
 use super::{IntersectionPrototype, IntersectionConnector, ConnectionRole, LANE_DISTANCE,
-LanePrototype, GestureSideID};
+LanePrototype, GestureSideID, RoadClass, TurnBan, Roundabout, Elevation};
+use cb_planning::{GestureID, StepID};
+
+// how far from an intersection the ends of a turn ban may be drawn
+const TURN_BAN_TOLERANCE: N = 20.0;
+
+// relative to the distance of the closest connector from the center of the intersection
+const RING_RADIUS_RATIO: N = 0.55;
+// how far around the ring lanes merge onto it and leave it again
+const RING_MERGE_ANGLE: N = 0.3;
+// lanes leaving the ring closer than this behind where they entered go all the way around
+const MIN_RING_SWEEP: N = 0.3;
+
+struct Ring {
+    center: P2,
+    radius: N,
+    // 1.0 if traffic goes around counter-clockwise, -1.0 otherwise
+    sense: N,
+}
+
+impl Ring {
+    fn around(intersection: &IntersectionPrototype) -> Option<Ring> {
+        let connectors = intersection
+            .incoming
+            .values()
+            .chain(intersection.outgoing.values())
+            .flat_map(|group| group.iter())
+            .collect::<Vec<_>>();
+        let first_incoming = intersection
+            .incoming
+            .values()
+            .next()
+            .and_then(|group| group.first())?;
+
+        let center = P2::from_coordinates(
+            connectors
+                .iter()
+                .fold(V2::new(0.0, 0.0), |sum, connector| sum + connector.position.coords)
+                / connectors.len() as N,
+        );
+        let radius = *connectors
+            .iter()
+            .map(|connector| OrderedFloat((connector.position - center).norm()))
+            .min()?
+            * RING_RADIUS_RATIO;
+
+        // entering the ring is turning towards the side of outer turns
+        let to_first = first_incoming.position - center;
+        let counter_clockwise = V2::new(-to_first.y, to_first.x);
+        let sense = if first_incoming
+            .direction
+            .orthogonal_right()
+            .dot(&counter_clockwise)
+            > 0.0
+        {
+            1.0
+        } else {
+            -1.0
+        };
+
+        Some(Ring {
+            center,
+            radius,
+            sense,
+        })
+    }
+
+    fn angle_of(&self, position: P2) -> N {
+        let from_center = position - self.center;
+        from_center.y.atan2(from_center.x)
+    }
+
+    fn point_at(&self, angle: N) -> P2 {
+        self.center + self.radius * V2::new(angle.cos(), angle.sin())
+    }
+
+    fn direction_at(&self, angle: N) -> V2 {
+        self.sense * V2::new(-angle.sin(), angle.cos())
+    }
+
+    // merges onto the ring, follows it to the outgoing connector and leaves it again
+    fn connecting_lane(
+        &self,
+        start: &IntersectionConnector,
+        end: &IntersectionConnector,
+        elevation: Elevation,
+    ) -> Option<LanePrototype> {
+        let entry_angle = self.angle_of(start.position) + self.sense * RING_MERGE_ANGLE;
+        let exit_angle = self.angle_of(end.position) - self.sense * RING_MERGE_ANGLE;
+
+        let entry = ArcLinePath::biarc(
+            start.position,
+            start.direction,
+            self.point_at(entry_angle),
+            self.direction_at(entry_angle),
+        )?;
+
+        let mut sweep = (exit_angle - entry_angle) * self.sense;
+        while sweep < MIN_RING_SWEEP {
+            sweep += 2.0 * PI;
+        }
+        while sweep > MIN_RING_SWEEP + 2.0 * PI {
+            sweep -= 2.0 * PI;
+        }
+
+        // arcs can't go around more than half a circle, so the ring is split up
+        let n_arcs = (sweep / FRAC_PI_2).ceil() as usize;
+        let mut path = entry.clone();
+        for a in 0..n_arcs {
+            let from = entry_angle + self.sense * sweep * a as N / n_arcs as N;
+            let to = entry_angle + self.sense * sweep * (a + 1) as N / n_arcs as N;
+            let arc =
+                ArcLinePath::arc(self.point_at(from), self.direction_at(from), self.point_at(to))?;
+            path = path.concat(&arc).ok()?;
+        }
+
+        let exit = ArcLinePath::biarc(
+            self.point_at(exit_angle),
+            self.direction_at(exit_angle),
+            end.position,
+            end.direction,
+        )?;
+        path = path.concat(&exit).ok()?;
+
+        let yield_at = entry.to_line_path_with_max_angle(0.6).length();
+        let class = start.class.min(end.class);
+
+        Some(LanePrototype(
+            path.to_line_path_with_max_angle(0.6),
+            CVec::new(),
+            class,
+            elevation,
+            Some(yield_at),
+        ))
+    }
+}
+
+fn closest_group(
+    groups: &CHashMap<GestureSideID, CVec<IntersectionConnector>>,
+    point: P2,
+) -> Option<GestureSideID> {
+    groups
+        .pairs()
+        .filter_map(|(gesture_side_id, group)| {
+            group
+                .iter()
+                .map(|connector| OrderedFloat((connector.position - point).norm()))
+                .min()
+                .map(|distance| (distance, *gesture_side_id))
+        })
+        .filter(|&(distance, _)| *distance < TURN_BAN_TOLERANCE)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, gesture_side_id)| gesture_side_id)
+}
+
+// Returns the turn bans and the roundabout that apply to this intersection
+pub fn create_connecting_lanes(
+    intersection: &mut IntersectionPrototype,
+    turn_bans: &[TurnBan],
+    roundabouts: &[Roundabout],
+) -> Vec<(GestureID, StepID)> {
+    let banned_turns = turn_bans
+        .iter()
+        .filter_map(|ban| {
+            Some((
+                (
+                    closest_group(&intersection.incoming, ban.from)?,
+                    closest_group(&intersection.outgoing, ban.to)?,
+                ),
+                ban.influence,
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    let roundabout = roundabouts
+        .iter()
+        .find(|roundabout| intersection.area.contains(roundabout.position));
+    let ring = roundabout.and_then(|_| Ring::around(intersection));
+
+    let applied_influences = banned_turns
+        .iter()
+        .map(|&(_, influence)| influence)
+        .chain(roundabout.map(|roundabout| roundabout.influence))
+        .collect::<Vec<_>>();
 
-pub fn create_connecting_lanes(intersection: &mut IntersectionPrototype) {
     // sort intersection connectors from inner to outer lanes
     for incoming_group in intersection.incoming.values_mut() {
         let base_position = incoming_group[0].position;
@@ -164,6 +347,8 @@ pub fn create_connecting_lanes(intersection: &mut IntersectionPrototype) {
             }
         }
 
+        let elevation = Elevation::flat(intersection.level);
+
         let connecting_lane_bundles = intersection
             .incoming
             .pairs()
@@ -196,7 +381,33 @@ pub fn create_connecting_lanes(intersection: &mut IntersectionPrototype) {
                             .collect::<Vec<_>>();
                         let relevant_outgoing_len = relevant_outgoing_connectors.len();
 
-                        let lanes = if relevant_incoming_len > 0 && relevant_outgoing_len > 0 {
+                        let incoming_class = incoming_group[0].class;
+                        let restricted = !incoming_class.connects_to(outgoing_group[0].class)
+                            || (role.u_turn && incoming_class == RoadClass::Highway)
+                            || banned_turns.iter().any(|&(turn, _)| {
+                                turn == (*incoming_gesture_side_id, *outgoing_gesture_side_id)
+                            });
+
+                        let lanes = if restricted
+                            || relevant_incoming_len == 0
+                            || relevant_outgoing_len == 0
+                        {
+                            vec![]
+                        } else if let Some(ref ring) = ring {
+                            // one lane per turn, from and onto the outermost lanes
+                            // for outer turns, else the innermost ones
+                            let (start, end) = if role.outer_turn {
+                                (
+                                    relevant_incoming_connectors[relevant_incoming_len - 1],
+                                    relevant_outgoing_connectors[relevant_outgoing_len - 1],
+                                )
+                            } else {
+                                (relevant_incoming_connectors[0], relevant_outgoing_connectors[0])
+                            };
+                            ring.connecting_lane(start, end, elevation)
+                                .into_iter()
+                                .collect()
+                        } else {
                             (0..relevant_incoming_len.max(relevant_outgoing_len))
                                 .filter_map(|l| {
                                     let start = relevant_incoming_connectors
@@ -211,11 +422,12 @@ pub fn create_connecting_lanes(intersection: &mut IntersectionPrototype) {
                                     )?
                                     .to_line_path_with_max_angle(0.6);
 
-                                    Some(LanePrototype(path, CVec::new()))
+                                    // turning from or onto a slower road means driving at its speed
+                                    let class = start.class.min(end.class);
+
+                                    Some(LanePrototype(path, CVec::new(), class, elevation, None))
                                 })
                                 .collect::<Vec<_>>()
-                        } else {
-                            vec![]
                         };
 
                         (
@@ -239,9 +451,14 @@ pub fn create_connecting_lanes(intersection: &mut IntersectionPrototype) {
             })
             .collect();
 
+        // entering cars yield to the ones on the ring, so there are no signal phases
+        if ring.is_some() {
+            return applied_influences;
+        }
+
         fn compatible(lanes_a: &[LanePrototype], lanes_b: &[LanePrototype]) -> bool {
             lanes_a.iter().cartesian_product(lanes_b).all(
-                |(&LanePrototype(ref path_a, _), &LanePrototype(ref path_b, _))| {
+                |(&LanePrototype(ref path_a, ..), &LanePrototype(ref path_b, ..))| {
                     path_a.start().rough_eq_by(path_b.start(), 0.1)
                         || (!path_a.end().rough_eq_by(path_b.end(), 0.1)
                             && (path_a, path_b).intersect().is_empty())
@@ -364,9 +581,11 @@ pub fn create_connecting_lanes(intersection: &mut IntersectionPrototype) {
                 })
                 .collect();
 
-            for &mut LanePrototype(_, ref mut lane_timings) in lanes.iter_mut() {
+            for &mut LanePrototype(_, ref mut lane_timings, ..) in lanes.iter_mut() {
                 *lane_timings = timings.clone()
             }
         }
     }
+
+    applied_influences
 }
@@ -2,29 +2,134 @@ use compact::{CHashMap, CVec};
 use descartes::{N, P2, V2, Band, LinePath, ClosedLinePath, Area, Intersect, WithUniqueOrthogonal,
 RoughEq, PointContainer, AreaError, ArcOrLineSegment, Segment, AreaEmbedding, AreaFilter};
 use ordered_float::OrderedFloat;
+use std::collections::{BTreeMap, HashMap};
 
 use cb_planning::{VersionedGesture, StepID, PrototypeID, PlanHistory, PlanResult,
 Prototype, GestureID};
 use planning::{CBPrototypeKind, CBGestureIntent};
+use environment::water::{water_areas, touches_water};
 
 mod intersection_connections;
+mod sidewalks;
+mod bike_lanes;
 pub mod smooth_path;
 use dimensions::{LANE_DISTANCE, CENTER_LANE_DISTANCE, MIN_SWITCHING_LANE_LENGTH,
-SWITCHING_LANE_OVERLAP_TOLERANCE};
+SWITCHING_LANE_OVERLAP_TOLERANCE, LEVEL_HEIGHT, RAMP_LENGTH};
+
+// Ordered from the slowest to the fastest kind of road
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum RoadClass {
+    Residential,
+    Avenue,
+    Highway,
+}
+
+impl RoadClass {
+    // in m/s
+    pub fn speed_limit(self) -> N {
+        match self {
+            RoadClass::Residential => 8.0,
+            RoadClass::Avenue => 14.0,
+            RoadClass::Highway => 25.0,
+        }
+    }
+
+    pub fn max_lanes_per_direction(self) -> u8 {
+        match self {
+            RoadClass::Residential => 1,
+            RoadClass::Avenue => 3,
+            RoadClass::Highway => 4,
+        }
+    }
+
+    // the slowest class that allows this many lanes
+    pub fn for_lanes(n_lanes_per_direction: u8) -> RoadClass {
+        [RoadClass::Residential, RoadClass::Avenue]
+            .iter()
+            .cloned()
+            .find(|class| n_lanes_per_direction <= class.max_lanes_per_direction())
+            .unwrap_or(RoadClass::Highway)
+    }
+
+    // highways are only reached via avenues, never straight from a residential street
+    pub fn connects_to(self, other: RoadClass) -> bool {
+        match (self, other) {
+            (RoadClass::Residential, RoadClass::Highway)
+            | (RoadClass::Highway, RoadClass::Residential) => false,
+            _ => true,
+        }
+    }
+}
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 pub struct RoadIntent {
     pub n_lanes_forward: u8,
     pub n_lanes_backward: u8,
+    pub class: RoadClass,
+    // 0 is on the ground, above it are bridges, below it tunnels
+    pub level: i8,
+    pub bike_lanes: bool,
 }
 
 impl RoadIntent {
-    pub fn new(n_lanes_forward: u8, n_lanes_backward: u8) -> Self {
+    pub fn new(n_lanes_forward: u8, n_lanes_backward: u8, class: RoadClass) -> Self {
+        let max_lanes = class.max_lanes_per_direction();
         RoadIntent {
-            n_lanes_forward,
-            n_lanes_backward,
+            n_lanes_forward: n_lanes_forward.min(max_lanes),
+            n_lanes_backward: n_lanes_backward.min(max_lanes),
+            class,
+            level: 0,
+            bike_lanes: false,
         }
     }
+
+    pub fn at_level(self, level: i8) -> Self {
+        RoadIntent { level, ..self }
+    }
+
+    pub fn with_bike_lanes(self, bike_lanes: bool) -> Self {
+        RoadIntent { bike_lanes, ..self }
+    }
+}
+
+// Roads on other levels than the ground cross each other without intersecting.
+// They ramp back down to the ground at their ends, so that's where
+// they connect to other roads
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Elevation {
+    pub level: i8,
+    pub ramp_ends: Option<(P2, P2)>,
+}
+
+impl Elevation {
+    pub fn flat(level: i8) -> Elevation {
+        Elevation {
+            level,
+            ramp_ends: None,
+        }
+    }
+
+    pub fn along_road(level: i8, road_path: &LinePath) -> Elevation {
+        Elevation {
+            level,
+            ramp_ends: Some((road_path.start(), road_path.end())),
+        }
+    }
+
+    pub fn height_at(&self, position: P2) -> N {
+        let full_height = f32::from(self.level) * LEVEL_HEIGHT;
+        match self.ramp_ends {
+            Some((start, end)) => {
+                let to_nearest_end = (position - start).norm().min((position - end).norm());
+                full_height * (to_nearest_end / RAMP_LENGTH).min(1.0)
+            }
+            None => full_height,
+        }
+    }
+
+    pub fn meets_at(&self, other: &Elevation, position: P2) -> bool {
+        (self.height_at(position) - other.height_at(position)).abs() < LEVEL_HEIGHT / 2.0
+    }
 }
 
 #[derive(Compact, Clone, Serialize, Deserialize, Debug)]
@@ -33,6 +138,9 @@ pub enum RoadPrototype {
     SwitchLane(SwitchLanePrototype),
     Intersection(IntersectionPrototype),
     PavedArea(Area),
+    Sidewalk(SidewalkPrototype),
+    Crosswalk(CrosswalkPrototype),
+    BikeLane(BikeLanePrototype),
 }
 
 impl RoadPrototype {
@@ -48,38 +156,91 @@ impl RoadPrototype {
                 &RoadPrototype::Intersection(ref intersection_1),
                 &RoadPrototype::Intersection(ref intersection_2),
             ) => intersection_1.morphable_from(intersection_2),
+            (
+                &RoadPrototype::Sidewalk(ref sidewalk_1),
+                &RoadPrototype::Sidewalk(ref sidewalk_2),
+            ) => sidewalk_1.morphable_from(sidewalk_2),
+            (
+                &RoadPrototype::Crosswalk(ref crosswalk_1),
+                &RoadPrototype::Crosswalk(ref crosswalk_2),
+            ) => crosswalk_1.morphable_from(crosswalk_2),
+            (
+                &RoadPrototype::BikeLane(ref bike_lane_1),
+                &RoadPrototype::BikeLane(ref bike_lane_2),
+            ) => bike_lane_1.morphable_from(bike_lane_2),
             _ => false,
         }
     }
 }
 
 #[derive(Compact, Clone, Serialize, Deserialize, Debug)]
-pub struct LanePrototype(pub LinePath, pub CVec<bool>);
+pub struct LanePrototype(
+    pub LinePath,
+    pub CVec<bool>,
+    pub RoadClass,
+    pub Elevation,
+    // where the lane enters the ring of a roundabout, see `Roundabout`
+    pub Option<N>,
+);
 
 impl LanePrototype {
     pub fn morphable_from(&self, other: &LanePrototype) -> bool {
         match (self, other) {
             (
-                &LanePrototype(ref path_1, ref timings_1),
-                &LanePrototype(ref path_2, ref timings_2),
-            ) => path_1.rough_eq_by(path_2, 0.05) && timings_1[..] == timings_2[..],
+                &LanePrototype(ref path_1, ref timings_1, class_1, elevation_1, yield_at_1),
+                &LanePrototype(ref path_2, ref timings_2, class_2, elevation_2, yield_at_2),
+            ) => {
+                path_1.rough_eq_by(path_2, 0.05)
+                    && timings_1[..] == timings_2[..]
+                    && class_1 == class_2
+                    && elevation_1 == elevation_2
+                    && yield_at_1.is_some() == yield_at_2.is_some()
+            }
         }
     }
 }
 
 #[derive(Compact, Clone, Serialize, Deserialize, Debug)]
-pub struct SwitchLanePrototype(pub LinePath);
+pub struct SwitchLanePrototype(pub LinePath, pub Elevation);
 
 impl SwitchLanePrototype {
     pub fn morphable_from(&self, other: &SwitchLanePrototype) -> bool {
         match (self, other) {
-            (&SwitchLanePrototype(ref path_1), &SwitchLanePrototype(ref path_2)) => {
-                path_1.rough_eq_by(path_2, 0.05)
-            }
+            (
+                &SwitchLanePrototype(ref path_1, elevation_1),
+                &SwitchLanePrototype(ref path_2, elevation_2),
+            ) => path_1.rough_eq_by(path_2, 0.05) && elevation_1 == elevation_2,
         }
     }
 }
 
+#[derive(Compact, Clone, Serialize, Deserialize, Debug)]
+pub struct SidewalkPrototype(pub LinePath);
+
+impl SidewalkPrototype {
+    pub fn morphable_from(&self, other: &SidewalkPrototype) -> bool {
+        self.0.rough_eq_by(&other.0, 0.05)
+    }
+}
+
+#[derive(Compact, Clone, Serialize, Deserialize, Debug)]
+pub struct CrosswalkPrototype(pub LinePath);
+
+impl CrosswalkPrototype {
+    pub fn morphable_from(&self, other: &CrosswalkPrototype) -> bool {
+        self.0.rough_eq_by(&other.0, 0.05)
+    }
+}
+
+#[derive(Compact, Clone, Serialize, Deserialize, Debug)]
+pub struct BikeLanePrototype(pub LinePath);
+
+impl BikeLanePrototype {
+    pub fn morphable_from(&self, other: &BikeLanePrototype) -> bool {
+        self.0.rough_eq_by(&other.0, 0.05)
+    }
+}
+
 #[derive(Copy, Clone, Serialize, Deserialize, Debug)]
 pub struct ConnectionRole {
     straight: bool,
@@ -92,14 +253,16 @@ pub struct ConnectionRole {
 pub struct IntersectionConnector {
     position: P2,
     direction: V2,
+    class: RoadClass,
     role: ConnectionRole,
 }
 
 impl IntersectionConnector {
-    fn new(position: P2, direction: V2) -> Self {
+    fn new(position: P2, direction: V2, class: RoadClass) -> Self {
         IntersectionConnector {
             position,
             direction,
+            class,
             role: ConnectionRole {
                 straight: false,
                 u_turn: false,
@@ -126,6 +289,7 @@ impl GestureSideID {
 #[derive(Compact, Clone, Serialize, Deserialize, Debug)]
 pub struct IntersectionPrototype {
     area: Area,
+    level: i8,
     incoming: CHashMap<GestureSideID, CVec<IntersectionConnector>>,
     outgoing: CHashMap<GestureSideID, CVec<IntersectionConnector>>,
     pub connecting_lanes: CHashMap<(GestureSideID, GestureSideID), CVec<LanePrototype>>,
@@ -134,8 +298,76 @@ pub struct IntersectionPrototype {
 impl IntersectionPrototype {
     pub fn morphable_from(&self, other: &IntersectionPrototype) -> bool {
         // TODO: make this better!!
-        (&self.area).rough_eq_by(&other.area, 0.1)
+        (&self.area).rough_eq_by(&other.area, 0.1) && self.same_connections_as(other)
     }
+
+    // connecting lanes differ when turns are banned or allowed again,
+    // so only then the intersection needs to be rebuilt
+    fn same_connections_as(&self, other: &IntersectionPrototype) -> bool {
+        let all_lanes = |intersection: &IntersectionPrototype| {
+            intersection
+                .connecting_lanes
+                .values()
+                .flat_map(|group| group.iter().cloned())
+                .collect::<Vec<_>>()
+        };
+        let (own_lanes, other_lanes) = (all_lanes(self), all_lanes(other));
+        own_lanes.len() == other_lanes.len()
+            && own_lanes
+                .iter()
+                .all(|lane| other_lanes.iter().any(|other_lane| lane.morphable_from(other_lane)))
+    }
+}
+
+// Drawn as a gesture from a point on the road where the turn would start
+// to a point on the road it would lead onto, next to the intersection
+#[derive(Copy, Clone, Debug)]
+pub struct TurnBan {
+    pub influence: (GestureID, StepID),
+    pub from: P2,
+    pub to: P2,
+}
+
+pub fn turn_bans(history: &PlanHistory<CBGestureIntent>) -> Vec<TurnBan> {
+    history
+        .gestures
+        .pairs()
+        .filter_map(
+            |(gesture_id, VersionedGesture(gesture, step_id))| match gesture.intent {
+                CBGestureIntent::TurnBan if gesture.points.len() >= 2 => Some(TurnBan {
+                    influence: (*gesture_id, *step_id),
+                    from: gesture.points[0],
+                    to: gesture.points[gesture.points.len() - 1],
+                }),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+// Placed as a single point inside an intersection, which then gets built as a ring
+// that all connecting lanes run around instead of crossing it. Cars entering the ring
+// yield to the ones already on it, so there are no traffic signals
+#[derive(Copy, Clone, Debug)]
+pub struct Roundabout {
+    pub influence: (GestureID, StepID),
+    pub position: P2,
+}
+
+pub fn roundabouts(history: &PlanHistory<CBGestureIntent>) -> Vec<Roundabout> {
+    history
+        .gestures
+        .pairs()
+        .filter_map(
+            |(gesture_id, VersionedGesture(gesture, step_id))| match gesture.intent {
+                CBGestureIntent::Roundabout if !gesture.points.is_empty() => Some(Roundabout {
+                    influence: (*gesture_id, *step_id),
+                    position: gesture.points[0],
+                }),
+                _ => None,
+            },
+        )
+        .collect()
 }
 
 pub fn simplify_road_path(points: CVec<P2>) -> CVec<P2> {
@@ -179,10 +411,19 @@ pub fn gesture_intent_smooth_paths(
 #[allow(clippy::cognitive_complexity)]
 pub fn calculate_prototypes(
     history: &PlanHistory<CBGestureIntent>,
-    _current_result: &PlanResult<CBPrototypeKind>,
+    current_result: &PlanResult<CBPrototypeKind>,
 ) -> Result<Vec<Prototype<CBPrototypeKind>>, AreaError> {
     let gesture_intent_smooth_paths = gesture_intent_smooth_paths(history);
 
+    let water_areas = water_areas(current_result);
+    for &(_, _, road_intent, ref path) in &gesture_intent_smooth_paths {
+        if road_intent.level == 0 && touches_water(&water_areas, path) {
+            return Err(AreaError::LeftOver(
+                "Roads can only cross water as bridges or tunnels".to_owned(),
+            ));
+        }
+    }
+
     let gesture_areas_for_intersection = gesture_intent_smooth_paths
         .iter()
         .map(|&(gesture_id, step_id, road_intent, ref path)| {
@@ -205,11 +446,13 @@ pub fn calculate_prototypes(
                 .as_area(),
                 gesture_id,
                 step_id,
+                road_intent.level,
             )
         })
         .collect::<Vec<_>>();
 
-    let mut road_intersection_embedding = AreaEmbedding::new(15.0);
+    // roads on different levels never intersect
+    let mut road_intersection_embeddings = BTreeMap::new();
 
     #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
     enum RoadPart {
@@ -218,11 +461,14 @@ pub fn calculate_prototypes(
         EndCap,
     }
 
-    for (gesture_area, gesture_id, step_id) in &gesture_areas_for_intersection {
-        road_intersection_embedding.insert(
-            gesture_area.clone(),
-            (*gesture_id, *step_id, RoadPart::Road),
-        );
+    for (gesture_area, gesture_id, step_id, level) in &gesture_areas_for_intersection {
+        road_intersection_embeddings
+            .entry(*level)
+            .or_insert_with(|| AreaEmbedding::new(15.0))
+            .insert(
+                gesture_area.clone(),
+                (*gesture_id, *step_id, RoadPart::Road),
+            );
     }
 
     // add intersections at the starts and ends of gestures
@@ -268,37 +514,50 @@ pub fn calculate_prototypes(
         },
     );
 
+    // the ends of roads on all levels are on the ground
+    let ground_embedding = road_intersection_embeddings
+        .entry(0)
+        .or_insert_with(|| AreaEmbedding::new(15.0));
+
     for (road_cap_area, road_cap_label) in road_caps {
-        road_intersection_embedding.insert(road_cap_area, road_cap_label);
+        ground_embedding.insert(road_cap_area, road_cap_label);
     }
 
-    let mut intersection_prototypes: Vec<_> = road_intersection_embedding
-        .view(AreaFilter::Function(Box::new(|labels| labels.len() >= 2)))
-        .get_areas_with_pieces()?
-        .into_iter()
-        .map(|(area, pieces)| {
-            let mut influenced_id = PrototypeID::from_influences(
-                pieces
-                    .iter()
-                    .map(|(_piece, label)| label.own_right_label)
-                    .collect::<Vec<_>>(),
-            );
-            influenced_id = influenced_id.add_influences(vec![
-                pieces[0].0.start().x.to_bits(),
-                pieces[0].0.start().y.to_bits(),
-            ]);
-            Prototype {
-                representative_position: area.primitives[0].boundary.path().points[0],
-                kind: CBPrototypeKind::Road(RoadPrototype::Intersection(IntersectionPrototype {
-                    area,
-                    incoming: CHashMap::new(),
-                    outgoing: CHashMap::new(),
-                    connecting_lanes: CHashMap::new(),
-                })),
-                id: influenced_id,
-            }
-        })
-        .collect();
+    let mut intersection_prototypes = Vec::new();
+
+    for (level, road_intersection_embedding) in road_intersection_embeddings {
+        intersection_prototypes.extend(
+            road_intersection_embedding
+                .view(AreaFilter::Function(Box::new(|labels| labels.len() >= 2)))
+                .get_areas_with_pieces()?
+                .into_iter()
+                .map(|(area, pieces)| {
+                    let mut influenced_id = PrototypeID::from_influences(
+                        pieces
+                            .iter()
+                            .map(|(_piece, label)| label.own_right_label)
+                            .collect::<Vec<_>>(),
+                    );
+                    influenced_id = influenced_id.add_influences(vec![
+                        pieces[0].0.start().x.to_bits(),
+                        pieces[0].0.start().y.to_bits(),
+                    ]);
+                    Prototype {
+                        representative_position: area.primitives[0].boundary.path().points[0],
+                        kind: CBPrototypeKind::Road(RoadPrototype::Intersection(
+                            IntersectionPrototype {
+                                area,
+                                level,
+                                incoming: CHashMap::new(),
+                                outgoing: CHashMap::new(),
+                                connecting_lanes: CHashMap::new(),
+                            },
+                        )),
+                        id: influenced_id,
+                    }
+                }),
+        );
+    }
 
     let intersected_lane_paths = {
         let raw_lane_paths = gesture_intent_smooth_paths
@@ -320,6 +579,7 @@ pub fn calculate_prototypes(
                             )
                         }))
                         .filter_map(|(offset, offset_i)| {
+                            let elevation = Elevation::along_road(road_intent.level, path);
                             path.shift_orthogonally(offset).map(|path| {
                                 (
                                     if offset < 0.0 {
@@ -329,6 +589,8 @@ pub fn calculate_prototypes(
                                     },
                                     PrototypeID::from_influences((gesture_id, step_id, offset_i)),
                                     if offset < 0.0 { path.reverse() } else { path },
+                                    road_intent.class,
+                                    elevation,
                                 )
                             })
                         })
@@ -339,7 +601,7 @@ pub fn calculate_prototypes(
 
         raw_lane_paths
             .into_iter()
-            .flat_map(|(gesture_side_id, lane_influence_id, raw_lane_path)| {
+            .flat_map(|(gesture_side_id, lane_influence_id, raw_lane_path, class, elevation)| {
                 let mut start_trim = 0.0f32;
                 let mut start_influence = lane_influence_id;
                 let mut end_trim = raw_lane_path.length();
@@ -361,7 +623,9 @@ pub fn calculate_prototypes(
                         )
                             .intersect();
 
-                        if points.len() >= 2 {
+                        // lanes only pass through intersections on their own level,
+                        // but always start and end on the ground
+                        if points.len() >= 2 && intersection.level == elevation.level {
                             let entry_distance = points
                                 .iter()
                                 .map(|p| OrderedFloat(p.along_a))
@@ -377,6 +641,7 @@ pub fn calculate_prototypes(
                                 IntersectionConnector::new(
                                     raw_lane_path.along(*entry_distance),
                                     raw_lane_path.direction_along(*entry_distance),
+                                    class,
                                 ),
                             );
                             intersection.outgoing.push_at(
@@ -384,10 +649,11 @@ pub fn calculate_prototypes(
                                 IntersectionConnector::new(
                                     raw_lane_path.along(*exit_distance),
                                     raw_lane_path.direction_along(*exit_distance),
+                                    class,
                                 ),
                             );
                             cuts.push((*entry_distance, *exit_distance, *intersection_id));
-                        } else if points.len() == 1 {
+                        } else if points.len() == 1 && intersection.level == 0 {
                             if intersection.area.contains(raw_lane_path.start()) {
                                 let exit_distance = points[0].along_a;
                                 intersection.outgoing.push_at(
@@ -395,6 +661,7 @@ pub fn calculate_prototypes(
                                     IntersectionConnector::new(
                                         raw_lane_path.along(exit_distance),
                                         raw_lane_path.direction_along(exit_distance),
+                                        class,
                                     ),
                                 );
                                 if exit_distance > start_trim {
@@ -408,6 +675,7 @@ pub fn calculate_prototypes(
                                     IntersectionConnector::new(
                                         raw_lane_path.along(entry_distance),
                                         raw_lane_path.direction_along(entry_distance),
+                                        class,
                                     ),
                                 );
                                 if entry_distance < end_trim {
@@ -436,7 +704,7 @@ pub fn calculate_prototypes(
                             lane_influence_id.add_influences((exit_influence, entry_influence));
                         raw_lane_path
                             .subsection(exit_distance, entry_distance)
-                            .map(|subsection| (subsection, subsection_id))
+                            .map(|subsection| (subsection, subsection_id, class, elevation))
                     })
                     .collect::<Vec<_>>()
             })
@@ -446,48 +714,54 @@ pub fn calculate_prototypes(
     let switch_lane_paths = {
         #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
         enum SwitchLaneLabel {
-            Left(PrototypeID),
-            Right(PrototypeID),
+            Left(PrototypeID, i8),
+            Right(PrototypeID, i8),
         };
 
+        let lane_elevations = intersected_lane_paths
+            .iter()
+            .map(|&(_, id, _, elevation)| (id, elevation))
+            .collect::<HashMap<_, _>>();
+
         let mut switch_lane_embedding = AreaEmbedding::new(30.0);
 
-        let right_lane_bands = intersected_lane_paths.iter().filter_map(|(path, id)| {
+        let right_lane_bands = intersected_lane_paths.iter().filter_map(|(path, id, _, elevation)| {
             path.shift_orthogonally(0.5 * LANE_DISTANCE + 0.5 * SWITCHING_LANE_OVERLAP_TOLERANCE)
                 .map(|right_path| {
                     let band =
                         Band::new(right_path.clone(), SWITCHING_LANE_OVERLAP_TOLERANCE * 2.0);
-                    (band.as_area(), *id)
+                    (band.as_area(), *id, elevation.level)
                 })
         });
 
-        for (band_area, id) in right_lane_bands {
-            switch_lane_embedding.insert(band_area, SwitchLaneLabel::Right(id))
+        for (band_area, id, level) in right_lane_bands {
+            switch_lane_embedding.insert(band_area, SwitchLaneLabel::Right(id, level))
         }
 
-        let left_lane_bands = intersected_lane_paths.iter().filter_map(|(path, id)| {
+        let left_lane_bands = intersected_lane_paths.iter().filter_map(|(path, id, _, elevation)| {
             path.shift_orthogonally(-0.5 * LANE_DISTANCE - 0.5 * SWITCHING_LANE_OVERLAP_TOLERANCE)
                 .map(|left_path| {
                     let band = Band::new(left_path.clone(), SWITCHING_LANE_OVERLAP_TOLERANCE * 2.0);
-                    (band.as_area(), *id)
+                    (band.as_area(), *id, elevation.level)
                 })
         });
 
-        for (band_area, id) in left_lane_bands {
-            switch_lane_embedding.insert(band_area, SwitchLaneLabel::Left(id))
+        for (band_area, id, level) in left_lane_bands {
+            switch_lane_embedding.insert(band_area, SwitchLaneLabel::Left(id, level))
         }
 
+        // lanes on top of each other are not next to each other
         switch_lane_embedding
             .view(AreaFilter::Function(Box::new(|labels| {
                 labels.iter().any(|label| {
-                    if let SwitchLaneLabel::Left(_) = label {
-                        true
-                    } else {
-                        false
-                    }
-                }) && labels.iter().any(|label| {
-                    if let SwitchLaneLabel::Right(_) = label {
-                        true
+                    if let SwitchLaneLabel::Left(_, left_level) = label {
+                        labels.iter().any(|other_label| {
+                            if let SwitchLaneLabel::Right(_, right_level) = other_label {
+                                left_level == right_level
+                            } else {
+                                false
+                            }
+                        })
                     } else {
                         false
                     }
@@ -495,8 +769,8 @@ pub fn calculate_prototypes(
             })))
             .get_unique_pieces()
             .into_iter()
-            .filter_map(|(piece, piece_area_label)| {
-                if let SwitchLaneLabel::Right(own_id) = piece_area_label.own_right_label {
+            .filter_map(move |(piece, piece_area_label)| {
+                if let SwitchLaneLabel::Right(own_id, _) = piece_area_label.own_right_label {
                     if piece.length() > MIN_SWITCHING_LANE_LENGTH {
                         let mut influenced_id = PrototypeID::from_influences(own_id);
                         influenced_id = influenced_id.add_influences(
@@ -507,7 +781,7 @@ pub fn calculate_prototypes(
                         );
                         influenced_id = influenced_id.add_influences(piece.points[0].x.to_bits());
                         influenced_id = influenced_id.add_influences(piece.points[0].y.to_bits());
-                        Some((piece, influenced_id))
+                        Some((piece, influenced_id, lane_elevations[&own_id]))
                     } else {
                         None
                     }
@@ -517,39 +791,65 @@ pub fn calculate_prototypes(
             })
     };
 
+    let turn_bans = turn_bans(history);
+    let roundabouts = roundabouts(history);
+
     for prototype in &mut intersection_prototypes {
         if let CBPrototypeKind::Road(RoadPrototype::Intersection(ref mut intersection)) =
             prototype.kind
         {
-            intersection_connections::create_connecting_lanes(intersection);
+            let applied_influences = intersection_connections::create_connecting_lanes(
+                intersection,
+                &turn_bans,
+                &roundabouts,
+            );
+            // a changed ban or roundabout only gives the affected intersection a new
+            // identity, so only its lanes and the routes through them are rebuilt
+            if !applied_influences.is_empty() {
+                prototype.id = prototype.id.add_influences(applied_influences);
+            }
         } else {
             unreachable!()
         }
     }
 
+    let sidewalk_prototypes =
+        sidewalks::sidewalk_prototypes(&gesture_intent_smooth_paths, &intersection_prototypes);
+    let bike_lane_prototypes =
+        bike_lanes::bike_lane_prototypes(&gesture_intent_smooth_paths, &intersection_prototypes);
+
     Ok(intersection_prototypes
         .into_iter()
         .chain(
             intersected_lane_paths
                 .into_iter()
-                .map(|(path, id)| Prototype {
+                .map(|(path, id, class, elevation)| Prototype {
                     representative_position: path.points[0],
                     kind: CBPrototypeKind::Road(RoadPrototype::Lane(LanePrototype(
                         path,
                         CVec::new(),
+                        class,
+                        elevation,
+                        None,
                     ))),
                     id,
                 }),
         )
-        .chain(switch_lane_paths.map(|(path, id)| Prototype {
+        .chain(switch_lane_paths.map(|(path, id, elevation)| Prototype {
             representative_position: path.points[0],
-            kind: CBPrototypeKind::Road(RoadPrototype::SwitchLane(SwitchLanePrototype(path))),
+            kind: CBPrototypeKind::Road(RoadPrototype::SwitchLane(SwitchLanePrototype(
+                path, elevation,
+            ))),
             id,
         }))
+        .chain(sidewalk_prototypes)
+        .chain(bike_lane_prototypes)
         .chain(
             gesture_areas_for_intersection
                 .into_iter()
-                .map(|(area, gesture_id, step_id)| Prototype {
+                // the ground above tunnels can still be built on
+                .filter(|&(_, _, _, level)| level >= 0)
+                .map(|(area, gesture_id, step_id, _)| Prototype {
                     representative_position: area.primitives[0].boundary.path().points[0],
                     kind: CBPrototypeKind::Road(RoadPrototype::PavedArea(area)),
                     id: PrototypeID::from_influences((gesture_id, step_id)),
@@ -0,0 +1,174 @@
+// Sidewalks run along the outer edges of roads and are cut where roads go through
+// intersections. Where a road meets an intersection, a crosswalk connects
+// the sidewalks on both of its sides. Where a road has bike lanes,
+// its sidewalks are further out, beyond them
+use descartes::{P2, LinePath, Intersect, PointContainer};
+use ordered_float::OrderedFloat;
+use std::collections::HashMap;
+
+use cb_planning::{StepID, PrototypeID, Prototype, GestureID};
+use planning::CBPrototypeKind;
+use super::{RoadIntent, RoadClass, RoadPrototype, SidewalkPrototype, CrosswalkPrototype,
+LANE_DISTANCE};
+use dimensions::{SIDEWALK_OFFSET, BIKE_LANE_WIDTH};
+
+// Cuts a path along a road where it passes through intersections, into
+// the parts in between, each with its own prototype ID. Notes where the path
+// enters (true) and leaves (false) the intersections it passes through
+pub fn cut_at_intersections(
+    raw_path: &LinePath,
+    influence_id: PrototypeID,
+    intersection_prototypes: &[Prototype<CBPrototypeKind>],
+    intersection_edges: &mut HashMap<(PrototypeID, bool), Vec<P2>>,
+) -> Vec<(LinePath, PrototypeID)> {
+    let mut start_trim = 0.0f32;
+    let mut start_influence = influence_id;
+    let mut end_trim = raw_path.length();
+    let mut end_influence = influence_id;
+    let mut cuts = Vec::new();
+
+    for prototype in intersection_prototypes {
+        if let Prototype {
+            id: intersection_id,
+            kind: CBPrototypeKind::Road(RoadPrototype::Intersection(ref intersection)),
+            ..
+        } = *prototype
+        {
+            let points = (&raw_path, intersection.area.primitives[0].boundary.path()).intersect();
+
+            if points.len() >= 2 {
+                let entry_distance = *points
+                    .iter()
+                    .map(|p| OrderedFloat(p.along_a))
+                    .min()
+                    .unwrap();
+                let exit_distance = *points
+                    .iter()
+                    .map(|p| OrderedFloat(p.along_a))
+                    .max()
+                    .unwrap();
+                intersection_edges
+                    .entry((intersection_id, true))
+                    .or_insert_with(Vec::new)
+                    .push(raw_path.along(entry_distance));
+                intersection_edges
+                    .entry((intersection_id, false))
+                    .or_insert_with(Vec::new)
+                    .push(raw_path.along(exit_distance));
+                cuts.push((entry_distance, exit_distance, intersection_id));
+            } else if points.len() == 1 {
+                let distance = points[0].along_a;
+                if intersection.area.contains(raw_path.start()) {
+                    intersection_edges
+                        .entry((intersection_id, false))
+                        .or_insert_with(Vec::new)
+                        .push(raw_path.along(distance));
+                    if distance > start_trim {
+                        start_trim = distance;
+                        start_influence = intersection_id;
+                    }
+                } else if intersection.area.contains(raw_path.end()) {
+                    intersection_edges
+                        .entry((intersection_id, true))
+                        .or_insert_with(Vec::new)
+                        .push(raw_path.along(distance));
+                    if distance < end_trim {
+                        end_trim = distance;
+                        end_influence = intersection_id;
+                    }
+                }
+            }
+        }
+    }
+
+    cuts.sort_by(|a, b| OrderedFloat(a.0).cmp(&OrderedFloat(b.0)));
+    cuts.insert(0, (-1.0, start_trim, start_influence));
+    cuts.push((end_trim, raw_path.length() + 1.0, end_influence));
+
+    cuts.windows(2)
+        .filter_map(|two_cuts| {
+            let ((_, exit_distance, exit_influence), (entry_distance, _, entry_influence)) =
+                (two_cuts[0], two_cuts[1]);
+            raw_path
+                .subsection(exit_distance, entry_distance)
+                .map(|subsection| {
+                    (
+                        subsection,
+                        influence_id.add_influences((exit_influence, entry_influence)),
+                    )
+                })
+        })
+        .collect()
+}
+
+pub fn sidewalk_prototypes(
+    gesture_intent_smooth_paths: &[(GestureID, StepID, RoadIntent, LinePath)],
+    intersection_prototypes: &[Prototype<CBPrototypeKind>],
+) -> Vec<Prototype<CBPrototypeKind>> {
+    let mut prototypes = Vec::new();
+
+    for &(gesture_id, step_id, road_intent, ref path) in gesture_intent_smooth_paths {
+        // nobody is supposed to walk along highways, bridges or through tunnels
+        if road_intent.class == RoadClass::Highway || road_intent.level != 0 {
+            continue;
+        }
+
+        let bike_lane_width = if road_intent.bike_lanes {
+            BIKE_LANE_WIDTH
+        } else {
+            0.0
+        };
+
+        // where the sidewalks of this road enter (true) and leave (false) intersections
+        let mut intersection_edges: HashMap<(PrototypeID, bool), Vec<P2>> = HashMap::new();
+
+        for &(n_lanes, side) in &[
+            (road_intent.n_lanes_forward, 1.0),
+            (road_intent.n_lanes_backward, -1.0),
+        ] {
+            if n_lanes == 0 {
+                continue;
+            }
+            let offset =
+                side * (f32::from(n_lanes) * LANE_DISTANCE + SIDEWALK_OFFSET + bike_lane_width);
+            let raw_sidewalk_path = match path.shift_orthogonally(offset) {
+                Some(raw_sidewalk_path) => raw_sidewalk_path,
+                None => continue,
+            };
+            let sidewalk_influence_id =
+                PrototypeID::from_influences((gesture_id, step_id, side > 0.0));
+
+            for (subsection, id) in cut_at_intersections(
+                &raw_sidewalk_path,
+                sidewalk_influence_id,
+                intersection_prototypes,
+                &mut intersection_edges,
+            ) {
+                prototypes.push(Prototype {
+                    representative_position: subsection.points[0],
+                    kind: CBPrototypeKind::Road(RoadPrototype::Sidewalk(SidewalkPrototype(
+                        subsection,
+                    ))),
+                    id,
+                });
+            }
+        }
+
+        for ((intersection_id, entering), edge_points) in intersection_edges {
+            if let [from, to] = edge_points[..] {
+                if let Some(crosswalk_path) = LinePath::new(vec![from, to].into()) {
+                    prototypes.push(Prototype {
+                        representative_position: from,
+                        kind: CBPrototypeKind::Road(RoadPrototype::Crosswalk(CrosswalkPrototype(
+                            crosswalk_path,
+                        ))),
+                        id: PrototypeID::from_influences((gesture_id, step_id, entering))
+                            .add_influences(intersection_id),
+                    });
+                }
+            }
+        }
+    }
+
+    prototypes
+}
@@ -0,0 +1,174 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TripStatsRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for TripStatsRequesterID {}
+impl Clone for TripStatsRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TripStatsRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TripStatsRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TripStatsRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TripStatsRequesterID {
+    fn eq(&self, other: &TripStatsRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TripStatsRequesterID {}
+
+pub struct TripStatsRequesterRepresentative;
+
+impl ActorOrActorTrait for TripStatsRequesterRepresentative {
+    type ID = TripStatsRequesterID;
+}
+
+impl TypedID for TripStatsRequesterID {
+    type Target = TripStatsRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        TripStatsRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + TripStatsRequester> TraitIDFrom<Act> for TripStatsRequesterID {}
+
+impl TripStatsRequesterID {
+    pub fn on_trip_stats(self, od_matrix: CVec < ODEntry >, travel_times: CVec < ModeTravelTimes >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TripStatsRequester_on_trip_stats(od_matrix, travel_times));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<TripStatsRequesterRepresentative>();
+        system.register_trait_message::<MSG_TripStatsRequester_on_trip_stats>();
+    }
+
+    pub fn register_implementor<Act: Actor + TripStatsRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, TripStatsRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_TripStatsRequester_on_trip_stats(ref od_matrix, ref travel_times), instance, world| {
+                instance.on_trip_stats(od_matrix, travel_times, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripStatsRequester_on_trip_stats(pub CVec < ODEntry >, pub CVec < ModeTravelTimes >);
+
+impl Actor for TripStats {
+    type ID = TripStatsID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TripStatsID {
+    _raw_id: RawID
+}
+
+impl Copy for TripStatsID {}
+impl Clone for TripStatsID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TripStatsID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TripStatsID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TripStatsID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TripStatsID {
+    fn eq(&self, other: &TripStatsID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TripStatsID {}
+
+impl TypedID for TripStatsID {
+    type Target = TripStats;
+
+    fn from_raw(id: RawID) -> Self {
+        TripStatsID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TripStatsID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = TripStatsID::from_raw(world.allocate_instance_id::<TripStats>());
+        let swarm = world.local_broadcast::<TripStats>();
+        world.send(swarm, MSG_TripStats_spawn(id, time));
+        id
+    }
+    
+    pub fn record(self, origin: P2, destination: P2, mode: TravelMode, departure: Instant, arrival: Instant, planned: Option < Duration >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TripStats_record(origin, destination, mode, departure, arrival, planned));
+    }
+    
+    pub fn get_trip_stats(self, requester: TripStatsRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TripStats_get_trip_stats(requester));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripStats_spawn(pub TripStatsID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripStats_record(pub P2, pub P2, pub TravelMode, pub Instant, pub Instant, pub Option < Duration >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripStats_get_trip_stats(pub TripStatsRequesterID);
+
+impl Into<SleeperID> for TripStatsID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    TripStatsRequesterID::register_trait(system);
+    SleeperID::register_implementor::<TripStats>(system);
+    system.add_spawner::<TripStats, _, _>(
+        |&MSG_TripStats_spawn(id, time), world| {
+            TripStats::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<TripStats, _, _>(
+        |&MSG_TripStats_record(origin, destination, mode, departure, arrival, planned), instance, world| {
+            instance.record(origin, destination, mode, departure, arrival, planned, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TripStats, _, _>(
+        |&MSG_TripStats_get_trip_stats(requester), instance, world| {
+            instance.get_trip_stats(requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
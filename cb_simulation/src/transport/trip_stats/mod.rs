@@ -0,0 +1,186 @@
+// Every trip that gets where it was going is counted here, by the zones it
+// went between, the part of the day it left in and the way it went, together
+// with how long it took and, if that was estimated beforehand, how long it was
+// expected to take. Trips are collected for a day at a time and only the last
+// full day is reported, so the numbers don't depend on when they're asked for
+use kay::{ActorSystem, World, TypedID};
+use compact::CVec;
+use descartes::P2;
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Duration, TimeOfDay, TICKS_PER_SIM_MINUTE};
+use super::pathfinding::trip::TravelMode;
+
+// the same grid as the districts of the economy
+const ZONE_SIZE: f32 = 1000.0;
+const SLICE_HOURS: usize = 3;
+pub const N_SLICES: u8 = (24 / SLICE_HOURS) as u8;
+const COLLECTION_INTERVAL: Duration = Duration(24 * 60 * 60);
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Zone(pub i32, pub i32);
+
+impl Zone {
+    pub fn containing(position: P2) -> Zone {
+        Zone(
+            (position.x / ZONE_SIZE).floor() as i32,
+            (position.y / ZONE_SIZE).floor() as i32,
+        )
+    }
+}
+
+// which part of the day a trip left in
+pub fn slice_of(instant: Instant) -> u8 {
+    (TimeOfDay::from(instant).hours_minutes().0 / SLICE_HOURS) as u8
+}
+
+// One cell of the origin-destination matrix of a slice of the day, for one mode
+#[derive(Copy, Clone)]
+pub struct ODEntry {
+    pub origin: Zone,
+    pub destination: Zone,
+    pub slice: u8,
+    pub mode: TravelMode,
+    pub n_trips: u32,
+    pub total_minutes: f32,
+}
+
+// How long trips of a mode that were estimated beforehand actually took
+#[derive(Copy, Clone)]
+pub struct ModeTravelTimes {
+    pub mode: TravelMode,
+    pub n_trips: u32,
+    pub planned_minutes: f32,
+    pub actual_minutes: f32,
+}
+
+impl ModeTravelTimes {
+    // what estimates of the mode have to be multiplied with to be right on average
+    pub fn correction_factor(&self) -> Option<f32> {
+        if self.planned_minutes > 0.0 {
+            Some(self.actual_minutes / self.planned_minutes)
+        } else {
+            None
+        }
+    }
+}
+
+pub trait TripStatsRequester {
+    fn on_trip_stats(
+        &mut self,
+        od_matrix: &CVec<ODEntry>,
+        travel_times: &CVec<ModeTravelTimes>,
+        world: &mut World,
+    );
+}
+
+#[derive(Compact, Clone)]
+pub struct TripStats {
+    id: TripStatsID,
+    time: TimeID,
+    collecting_od_matrix: CVec<ODEntry>,
+    collecting_travel_times: CVec<ModeTravelTimes>,
+    last_day_od_matrix: CVec<ODEntry>,
+    last_day_travel_times: CVec<ModeTravelTimes>,
+}
+
+impl TripStats {
+    pub fn spawn(id: TripStatsID, time: TimeID, world: &mut World) -> TripStats {
+        time.wake_up_in(COLLECTION_INTERVAL.into(), id.into(), world);
+
+        TripStats {
+            id,
+            time,
+            collecting_od_matrix: CVec::new(),
+            collecting_travel_times: CVec::new(),
+            last_day_od_matrix: CVec::new(),
+            last_day_travel_times: CVec::new(),
+        }
+    }
+
+    pub fn record(
+        &mut self,
+        origin: P2,
+        destination: P2,
+        mode: TravelMode,
+        departure: Instant,
+        arrival: Instant,
+        planned: Option<Duration>,
+        _: &mut World,
+    ) {
+        let minutes = arrival.ticks().saturating_sub(departure.ticks()) as f32
+            / TICKS_PER_SIM_MINUTE as f32;
+        let origin = Zone::containing(origin);
+        let destination = Zone::containing(destination);
+        let slice = slice_of(departure);
+
+        if let Some(entry) = self.collecting_od_matrix.iter_mut().find(|entry| {
+            entry.origin == origin
+                && entry.destination == destination
+                && entry.slice == slice
+                && entry.mode == mode
+        }) {
+            entry.n_trips += 1;
+            entry.total_minutes += minutes;
+        } else {
+            self.collecting_od_matrix.push(ODEntry {
+                origin,
+                destination,
+                slice,
+                mode,
+                n_trips: 1,
+                total_minutes: minutes,
+            });
+        }
+
+        if let Some(planned) = planned {
+            if let Some(times) = self
+                .collecting_travel_times
+                .iter_mut()
+                .find(|times| times.mode == mode)
+            {
+                times.n_trips += 1;
+                times.planned_minutes += planned.as_minutes();
+                times.actual_minutes += minutes;
+            } else {
+                self.collecting_travel_times.push(ModeTravelTimes {
+                    mode,
+                    n_trips: 1,
+                    planned_minutes: planned.as_minutes(),
+                    actual_minutes: minutes,
+                });
+            }
+        }
+    }
+
+    pub fn get_trip_stats(&mut self, requester: TripStatsRequesterID, world: &mut World) {
+        requester.on_trip_stats(
+            self.last_day_od_matrix.clone(),
+            self.last_day_travel_times.clone(),
+            world,
+        );
+    }
+}
+
+impl Sleeper for TripStats {
+    fn wake(&mut self, _: Instant, world: &mut World) {
+        self.last_day_od_matrix =
+            ::std::mem::replace(&mut self.collecting_od_matrix, CVec::new());
+        self.last_day_travel_times =
+            ::std::mem::replace(&mut self.collecting_travel_times, CVec::new());
+
+        self.time
+            .wake_up_in(COLLECTION_INTERVAL.into(), self.id_as(), world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<TripStats>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    TripStatsID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -50,8 +50,8 @@ impl TypedID for TransportUIID {
 impl<Act: Actor + TransportUI> TraitIDFrom<Act> for TransportUIID {}
 
 impl TransportUIID {
-    pub fn on_lane_constructed(self, id: RawID, lane_path: LinePath, is_switch: bool, on_intersection: bool, world: &mut World) {
-        world.send(self.as_raw(), MSG_TransportUI_on_lane_constructed(id, lane_path, is_switch, on_intersection));
+    pub fn on_lane_constructed(self, id: RawID, lane_path: LinePath, elevation: Elevation, is_switch: bool, on_intersection: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_lane_constructed(id, lane_path, elevation, is_switch, on_intersection));
     }
     
     pub fn on_lane_destructed(self, id: RawID, is_switch: bool, on_intersection: bool, world: &mut World) {
@@ -61,19 +61,94 @@ impl TransportUIID {
     pub fn on_car_info(self, from_lane: RawID, infos: CVec < CarRenderInfo >, world: &mut World) {
         world.send(self.as_raw(), MSG_TransportUI_on_car_info(from_lane, infos));
     }
+    
+    pub fn on_lane_pathfinding_info(self, id: RawID, label_position: P2, landmark: Option < RawID >, hops_from_landmark: u8, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_lane_pathfinding_info(id, label_position, landmark, hops_from_landmark));
+    }
+    
+    pub fn on_route_hop(self, trip: TripID, hop: u16, hop_path: LinePath, is_last: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_route_hop(trip, hop, hop_path, is_last));
+    }
+    
+    pub fn on_transit_line(self, id: RawID, mode: TransitMode, stop_positions: CVec < P2 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_transit_line(id, mode, stop_positions));
+    }
+    
+    pub fn on_transit_line_removed(self, id: RawID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_transit_line_removed(id));
+    }
+    
+    pub fn on_rail_tracks(self, segments: CVec < [ P2 ; 2 ] >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_rail_tracks(segments));
+    }
+    
+    pub fn on_transit_vehicle_info(self, vehicle: RawID, mode: TransitMode, position: [ f32 ; 2 ], direction: [ f32 ; 2 ], world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_transit_vehicle_info(vehicle, mode, position, direction));
+    }
+    
+    pub fn on_traffic_signal_info(self, signal: RawID, position: P2, phase_durations: CVec < Duration >, approach_delays: CVec < f32 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_traffic_signal_info(signal, position, phase_durations, approach_delays));
+    }
+    
+    pub fn on_lane_incident(self, lane: RawID, position: P2, kind: Option < IncidentKind >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_lane_incident(lane, position, kind));
+    }
+    
+    pub fn on_toll_scheme(self, id: u16, kind: TollKind, outline: CVec < P2 >, prices: TollPrices, collected: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_toll_scheme(id, kind, outline, prices, collected));
+    }
+    
+    pub fn on_toll_scheme_removed(self, id: u16, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_toll_scheme_removed(id));
+    }
+    
+    pub fn on_footway_constructed(self, id: RawID, path: LinePath, crossing: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_footway_constructed(id, path, crossing));
+    }
+    
+    pub fn on_footway_destructed(self, id: RawID, crossing: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_footway_destructed(id, crossing));
+    }
+    
+    pub fn on_pedestrian_info(self, infos: CVec < PedestrianRenderInfo >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_pedestrian_info(infos));
+    }
+    
+    pub fn on_bikeway_constructed(self, id: RawID, path: LinePath, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_bikeway_constructed(id, path));
+    }
+    
+    pub fn on_bikeway_destructed(self, id: RawID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_bikeway_destructed(id));
+    }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<TransportUIRepresentative>();
         system.register_trait_message::<MSG_TransportUI_on_lane_constructed>();
         system.register_trait_message::<MSG_TransportUI_on_lane_destructed>();
         system.register_trait_message::<MSG_TransportUI_on_car_info>();
+        system.register_trait_message::<MSG_TransportUI_on_lane_pathfinding_info>();
+        system.register_trait_message::<MSG_TransportUI_on_route_hop>();
+        system.register_trait_message::<MSG_TransportUI_on_transit_line>();
+        system.register_trait_message::<MSG_TransportUI_on_transit_line_removed>();
+        system.register_trait_message::<MSG_TransportUI_on_rail_tracks>();
+        system.register_trait_message::<MSG_TransportUI_on_transit_vehicle_info>();
+        system.register_trait_message::<MSG_TransportUI_on_traffic_signal_info>();
+        system.register_trait_message::<MSG_TransportUI_on_lane_incident>();
+        system.register_trait_message::<MSG_TransportUI_on_toll_scheme>();
+        system.register_trait_message::<MSG_TransportUI_on_toll_scheme_removed>();
+        system.register_trait_message::<MSG_TransportUI_on_footway_constructed>();
+        system.register_trait_message::<MSG_TransportUI_on_footway_destructed>();
+        system.register_trait_message::<MSG_TransportUI_on_pedestrian_info>();
+        system.register_trait_message::<MSG_TransportUI_on_bikeway_constructed>();
+        system.register_trait_message::<MSG_TransportUI_on_bikeway_destructed>();
     }
 
     pub fn register_implementor<Act: Actor + TransportUI>(system: &mut ActorSystem) {
         system.register_implementor::<Act, TransportUIRepresentative>();
         system.add_handler::<Act, _, _>(
-            |&MSG_TransportUI_on_lane_constructed(id, ref lane_path, is_switch, on_intersection), instance, world| {
-                instance.on_lane_constructed(id, lane_path, is_switch, on_intersection, world); Fate::Live
+            |&MSG_TransportUI_on_lane_constructed(id, ref lane_path, elevation, is_switch, on_intersection), instance, world| {
+                instance.on_lane_constructed(id, lane_path, elevation, is_switch, on_intersection, world); Fate::Live
             }, false
         );
         
@@ -88,15 +163,201 @@ impl TransportUIID {
                 instance.on_car_info(from_lane, infos, world); Fate::Live
             }, false
         );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_lane_pathfinding_info(id, label_position, landmark, hops_from_landmark), instance, world| {
+                instance.on_lane_pathfinding_info(id, label_position, landmark, hops_from_landmark, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_route_hop(trip, hop, ref hop_path, is_last), instance, world| {
+                instance.on_route_hop(trip, hop, hop_path, is_last, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_transit_line(id, mode, ref stop_positions), instance, world| {
+                instance.on_transit_line(id, mode, stop_positions, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_transit_line_removed(id), instance, world| {
+                instance.on_transit_line_removed(id, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_rail_tracks(ref segments), instance, world| {
+                instance.on_rail_tracks(segments, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_transit_vehicle_info(vehicle, mode, position, direction), instance, world| {
+                instance.on_transit_vehicle_info(vehicle, mode, position, direction, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_traffic_signal_info(signal, position, ref phase_durations, ref approach_delays), instance, world| {
+                instance.on_traffic_signal_info(signal, position, phase_durations, approach_delays, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_lane_incident(lane, position, kind), instance, world| {
+                instance.on_lane_incident(lane, position, kind, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_toll_scheme(id, kind, ref outline, prices, collected), instance, world| {
+                instance.on_toll_scheme(id, kind, outline, prices, collected, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_toll_scheme_removed(id), instance, world| {
+                instance.on_toll_scheme_removed(id, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_footway_constructed(id, ref path, crossing), instance, world| {
+                instance.on_footway_constructed(id, path, crossing, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_footway_destructed(id, crossing), instance, world| {
+                instance.on_footway_destructed(id, crossing, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_pedestrian_info(ref infos), instance, world| {
+                instance.on_pedestrian_info(infos, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_bikeway_constructed(id, ref path), instance, world| {
+                instance.on_bikeway_constructed(id, path, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_bikeway_destructed(id), instance, world| {
+                instance.on_bikeway_destructed(id, world); Fate::Live
+            }, false
+        );
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_TransportUI_on_lane_constructed(pub RawID, pub LinePath, pub bool, pub bool);
+struct MSG_TransportUI_on_lane_constructed(pub RawID, pub LinePath, pub Elevation, pub bool, pub bool);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_TransportUI_on_lane_destructed(pub RawID, pub bool, pub bool);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_TransportUI_on_car_info(pub RawID, pub CVec < CarRenderInfo >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_lane_pathfinding_info(pub RawID, pub P2, pub Option < RawID >, pub u8);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_route_hop(pub TripID, pub u16, pub LinePath, pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_transit_line(pub RawID, pub TransitMode, pub CVec < P2 >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_transit_line_removed(pub RawID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_rail_tracks(pub CVec < [ P2 ; 2 ] >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_transit_vehicle_info(pub RawID, pub TransitMode, pub [ f32 ; 2 ], pub [ f32 ; 2 ]);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_traffic_signal_info(pub RawID, pub P2, pub CVec < Duration >, pub CVec < f32 >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_lane_incident(pub RawID, pub P2, pub Option < IncidentKind >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_toll_scheme(pub u16, pub TollKind, pub CVec < P2 >, pub TollPrices, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_toll_scheme_removed(pub u16);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_footway_constructed(pub RawID, pub LinePath, pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_footway_destructed(pub RawID, pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_pedestrian_info(pub CVec < PedestrianRenderInfo >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_bikeway_constructed(pub RawID, pub LinePath);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_bikeway_destructed(pub RawID);
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct RouteTraceableID {
+    _raw_id: RawID
+}
+
+impl Copy for RouteTraceableID {}
+impl Clone for RouteTraceableID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for RouteTraceableID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "RouteTraceableID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for RouteTraceableID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for RouteTraceableID {
+    fn eq(&self, other: &RouteTraceableID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for RouteTraceableID {}
+
+pub struct RouteTraceableRepresentative;
+
+impl ActorOrActorTrait for RouteTraceableRepresentative {
+    type ID = RouteTraceableID;
+}
+
+impl TypedID for RouteTraceableID {
+    type Target = RouteTraceableRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        RouteTraceableID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + RouteTraceable> TraitIDFrom<Act> for RouteTraceableID {}
+
+impl RouteTraceableID {
+    pub fn trace_route(self, trip: TripID, ui: TransportUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_RouteTraceable_trace_route(trip, ui));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<RouteTraceableRepresentative>();
+        system.register_trait_message::<MSG_RouteTraceable_trace_route>();
+    }
+
+    pub fn register_implementor<Act: Actor + RouteTraceable>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, RouteTraceableRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_RouteTraceable_trace_route(trip, ui), instance, world| {
+                instance.trace_route(trip, ui, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RouteTraceable_trace_route(pub TripID, pub TransportUIID);
 
 
 
@@ -108,14 +369,30 @@ impl LaneID {
     pub fn get_render_info(self, ui: TransportUIID, world: &mut World) {
         world.send(self.as_raw(), MSG_Lane_get_render_info(ui));
     }
+    
+    pub fn get_pathfinding_info(self, ui: TransportUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_get_pathfinding_info(ui));
+    }
+    
+    pub fn continue_route_trace(self, trip: TripID, destination: PreciseLocation, hop: u16, ui: TransportUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_continue_route_trace(trip, destination, hop, ui));
+    }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Lane_get_car_info(pub TransportUIID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Lane_get_render_info(pub TransportUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_get_pathfinding_info(pub TransportUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_continue_route_trace(pub TripID, pub PreciseLocation, pub u16, pub TransportUIID);
 
-
+impl Into<RouteTraceableID> for LaneID {
+    fn into(self) -> RouteTraceableID {
+        RouteTraceableID::from_raw(self.as_raw())
+    }
+}
 
 
 impl SwitchLaneID {
@@ -133,12 +410,18 @@ struct MSG_SwitchLane_get_render_info(pub TransportUIID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_SwitchLane_get_car_info(pub TransportUIID);
 
+impl Into<RouteTraceableID> for SwitchLaneID {
+    fn into(self) -> RouteTraceableID {
+        RouteTraceableID::from_raw(self.as_raw())
+    }
+}
 
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     TransportUIID::register_trait(system);
-    
+    RouteTraceableID::register_trait(system);
+    RouteTraceableID::register_implementor::<Lane>(system);
     system.add_handler::<Lane, _, _>(
         |&MSG_Lane_get_car_info(ui), instance, world| {
             instance.get_car_info(ui, world); Fate::Live
@@ -151,6 +434,18 @@ pub fn auto_setup(system: &mut ActorSystem) {
         }, false
     );
     
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_get_pathfinding_info(ui), instance, world| {
+            instance.get_pathfinding_info(ui, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_continue_route_trace(trip, destination, hop, ui), instance, world| {
+            instance.continue_route_trace(trip, destination, hop, ui, world); Fate::Live
+        }, false
+    );
+    RouteTraceableID::register_implementor::<SwitchLane>(system);
     system.add_handler::<SwitchLane, _, _>(
         |&MSG_SwitchLane_get_render_info(ui), instance, world| {
             instance.get_render_info(ui, world); Fate::Live
@@ -1,20 +1,35 @@
-use descartes::{LinePath, Segment, WithUniqueOrthogonal};
+use descartes::{LinePath, Segment, WithUniqueOrthogonal, P2};
 use compact::CVec;
 use kay::{ActorSystem, World, TypedID, RawID};
 use michelangelo::Mesh;
 use super::lane::{Lane, LaneID, SwitchLane, SwitchLaneID};
+use super::lane::connectivity::Interaction;
+use super::microtraffic::VehicleClass;
+use super::incidents::IncidentKind;
+use super::tolls::{TollKind, TollPrices};
+use super::transport_planning::Elevation;
+use transport::pathfinding::{PreciseLocation, StoredRoutingEntry};
 use transport::pathfinding::trip::TripID;
+use transport::transit::TransitMode;
+use cb_time::units::Duration;
 
 use dimensions::{LANE_DISTANCE, LANE_WIDTH, LANE_MARKER_WIDTH, LANE_MARKER_DASH_GAP,
-LANE_MARKER_DASH_LENGTH};
+LANE_MARKER_DASH_LENGTH, SIDEWALK_WIDTH, CROSSWALK_WIDTH, CROSSWALK_STRIPE_WIDTH, BIKE_LANE_WIDTH};
 
 use itertools::Itertools;
 
 #[derive(Compact, Clone)]
 pub struct CarRenderInfo {
-    pub position: [f32; 2],
+    pub position: [f32; 3],
     pub direction: [f32; 2],
     pub trip: TripID,
+    pub vehicle: VehicleClass,
+}
+
+#[derive(Copy, Clone)]
+pub struct PedestrianRenderInfo {
+    pub position: [f32; 2],
+    pub direction: [f32; 2],
 }
 
 pub trait TransportUI {
@@ -22,6 +37,7 @@ pub trait TransportUI {
         &mut self,
         id: RawID,
         lane_path: &LinePath,
+        elevation: Elevation,
         is_switch: bool,
         on_intersection: bool,
         _world: &mut World,
@@ -35,6 +51,73 @@ pub trait TransportUI {
         _world: &mut World,
     );
     fn on_car_info(&mut self, from_lane: RawID, infos: &CVec<CarRenderInfo>, _: &mut World);
+
+    fn on_lane_pathfinding_info(
+        &mut self,
+        id: RawID,
+        label_position: P2,
+        landmark: Option<RawID>,
+        hops_from_landmark: u8,
+        _: &mut World,
+    );
+
+    fn on_route_hop(
+        &mut self,
+        trip: TripID,
+        hop: u16,
+        hop_path: &LinePath,
+        is_last: bool,
+        _: &mut World,
+    );
+
+    fn on_transit_line(
+        &mut self,
+        id: RawID,
+        mode: TransitMode,
+        stop_positions: &CVec<P2>,
+        _: &mut World,
+    );
+    fn on_transit_line_removed(&mut self, id: RawID, _: &mut World);
+    fn on_rail_tracks(&mut self, segments: &CVec<[P2; 2]>, _: &mut World);
+    fn on_transit_vehicle_info(
+        &mut self,
+        vehicle: RawID,
+        mode: TransitMode,
+        position: [f32; 2],
+        direction: [f32; 2],
+        _: &mut World,
+    );
+    fn on_traffic_signal_info(
+        &mut self,
+        signal: RawID,
+        position: P2,
+        phase_durations: &CVec<Duration>,
+        approach_delays: &CVec<f32>,
+        _: &mut World,
+    );
+    // `None` once the lane is open again
+    fn on_lane_incident(
+        &mut self,
+        lane: RawID,
+        position: P2,
+        kind: Option<IncidentKind>,
+        _: &mut World,
+    );
+    fn on_toll_scheme(
+        &mut self,
+        id: u16,
+        kind: TollKind,
+        outline: &CVec<P2>,
+        prices: TollPrices,
+        collected: f32,
+        _: &mut World,
+    );
+    fn on_toll_scheme_removed(&mut self, id: u16, _: &mut World);
+    fn on_footway_constructed(&mut self, id: RawID, path: &LinePath, crossing: bool, _: &mut World);
+    fn on_footway_destructed(&mut self, id: RawID, crossing: bool, _: &mut World);
+    fn on_pedestrian_info(&mut self, infos: &CVec<PedestrianRenderInfo>, _: &mut World);
+    fn on_bikeway_constructed(&mut self, id: RawID, path: &LinePath, _: &mut World);
+    fn on_bikeway_destructed(&mut self, id: RawID, _: &mut World);
 }
 
 impl Lane {
@@ -47,10 +130,12 @@ impl Lane {
             {
                 let position2d = segment.along(*car.position - distance_pair[0]);
                 let direction = segment.direction();
+                let height = self.construction.elevation.height_at(position2d);
                 car_infos.push(CarRenderInfo {
-                    position: [position2d.x, position2d.y],
+                    position: [position2d.x, position2d.y, height],
                     direction: [direction.x, direction.y],
                     trip: car.trip,
+                    vehicle: car.vehicle,
                 })
             }
         }
@@ -63,6 +148,14 @@ impl Lane {
     }
 }
 
+// Meshes are made flat, then lifted or sunk to the height of the road they belong to
+pub fn elevated(mut mesh: Mesh, elevation: &Elevation) -> Mesh {
+    for vertex in mesh.vertices.iter_mut() {
+        vertex.position[2] += elevation.height_at(P2::new(vertex.position[0], vertex.position[1]));
+    }
+    mesh
+}
+
 pub fn lane_mesh(path: &LinePath) -> Mesh {
     Mesh::from_path_as_band(path, LANE_WIDTH, 0.0)
 }
@@ -93,11 +186,30 @@ pub fn switch_marker_gap_mesh(path: &LinePath) -> Mesh {
         .sum()
 }
 
+pub fn sidewalk_mesh(path: &LinePath) -> Mesh {
+    Mesh::from_path_as_band(path, SIDEWALK_WIDTH, 0.0)
+}
+
+// painted on top of the asphalt, like markers
+pub fn bike_lane_mesh(path: &LinePath) -> Mesh {
+    Mesh::from_path_as_band(path, BIKE_LANE_WIDTH, 0.1)
+}
+
+// zebra stripes follow one another along the way across the road
+pub fn crosswalk_mesh(path: &LinePath) -> Mesh {
+    path.dash(CROSSWALK_STRIPE_WIDTH, CROSSWALK_STRIPE_WIDTH)
+        .filter_map(|maybe_dash| {
+            maybe_dash.map(|dash| Mesh::from_path_as_band(&dash, CROSSWALK_WIDTH, 0.1))
+        })
+        .sum()
+}
+
 impl Lane {
     pub fn get_render_info(&mut self, ui: TransportUIID, world: &mut World) {
         ui.on_lane_constructed(
             self.id.as_raw(),
             self.construction.path.clone(),
+            self.construction.elevation,
             false,
             self.connectivity.on_intersection,
             world,
@@ -105,11 +217,112 @@ impl Lane {
     }
 }
 
+impl Lane {
+    // For visually debugging connectivity: which landmark each lane belongs to
+    pub fn get_pathfinding_info(&mut self, ui: TransportUIID, world: &mut World) {
+        let path = &self.construction.path;
+        ui.on_lane_pathfinding_info(
+            self.id.as_raw(),
+            path.along(path.length() / 2.0),
+            self.pathfinding
+                .location
+                .map(|location| location.landmark.as_raw()),
+            self.pathfinding.hops_from_landmark,
+            world,
+        );
+    }
+}
+
+// Cars only know their next hop, so to show a car's remaining route it is
+// traced from lane to lane along the same routing tables the car will use
+const MAX_TRACED_HOPS: u16 = 500;
+
+pub trait RouteTraceable {
+    fn trace_route(&mut self, trip: TripID, ui: TransportUIID, world: &mut World);
+}
+
+impl RouteTraceable for Lane {
+    fn trace_route(&mut self, trip: TripID, ui: TransportUIID, world: &mut World) {
+        if let Some(car) = self.microtraffic.cars.iter().find(|car| car.trip == trip) {
+            self.report_route_hop(trip, car.destination, *car.position, 0, ui, world);
+        }
+    }
+}
+
+impl Lane {
+    pub fn continue_route_trace(
+        &mut self,
+        trip: TripID,
+        destination: PreciseLocation,
+        hop: u16,
+        ui: TransportUIID,
+        world: &mut World,
+    ) {
+        self.report_route_hop(trip, destination, 0.0, hop, ui, world);
+    }
+
+    fn next_lane_towards(&self, destination: PreciseLocation) -> Option<LaneID> {
+        self.pathfinding
+            .routes
+            .get(destination.location)
+            .or_else(|| {
+                self.pathfinding
+                    .routes
+                    .get(destination.landmark_destination())
+            })
+            .and_then(|&StoredRoutingEntry { outgoing_idx, .. }| {
+                match self.connectivity.interactions[outgoing_idx as usize] {
+                    Interaction::Next { next, .. } => Some(next),
+                    Interaction::Switch { to, .. } => Some(to),
+                    _ => None,
+                }
+            })
+    }
+
+    fn report_route_hop(
+        &self,
+        trip: TripID,
+        destination: PreciseLocation,
+        start: f32,
+        hop: u16,
+        ui: TransportUIID,
+        world: &mut World,
+    ) {
+        let arrives = self.pathfinding.location == Some(destination.location);
+        let end = if arrives {
+            destination.offset
+        } else {
+            self.construction.length
+        };
+        let maybe_next_lane = if arrives || hop >= MAX_TRACED_HOPS {
+            None
+        } else {
+            self.next_lane_towards(destination)
+        };
+
+        // the car might already be past the end of a very short remaining piece
+        if let Some(hop_path) = self.construction.path.subsection(start, end) {
+            ui.on_route_hop(trip, hop, hop_path, maybe_next_lane.is_none(), world);
+        }
+
+        if let Some(next_lane) = maybe_next_lane {
+            next_lane.continue_route_trace(trip, destination, hop + 1, ui, world);
+        }
+    }
+}
+
+impl RouteTraceable for SwitchLane {
+    // cars only briefly use switch lanes, the route traced
+    // from the lane they came from is still good enough
+    fn trace_route(&mut self, _: TripID, _: TransportUIID, _: &mut World) {}
+}
+
 impl SwitchLane {
     pub fn get_render_info(&mut self, ui: TransportUIID, world: &mut World) {
         ui.on_lane_constructed(
             self.id.as_raw(),
             self.construction.path.clone(),
+            self.construction.elevation,
             true,
             false,
             world,
@@ -132,10 +345,12 @@ impl SwitchLane {
                 .normalize();
                 let shifted_position2d =
                     position2d + 2.5 * direction.orthogonal_right() * car.switch_position;
+                let height = self.construction.elevation.height_at(shifted_position2d);
                 car_infos.push(CarRenderInfo {
-                    position: [shifted_position2d.x, shifted_position2d.y],
+                    position: [shifted_position2d.x, shifted_position2d.y, height],
                     direction: [rotated_direction.x, rotated_direction.y],
                     trip: car.trip,
+                    vehicle: car.vehicle,
                 })
             }
         }
@@ -152,6 +367,7 @@ pub fn on_build(lane: &Lane, world: &mut World) {
     TransportUIID::global_broadcast(world).on_lane_constructed(
         lane.id.as_raw(),
         lane.construction.path.clone(),
+        lane.construction.elevation,
         false,
         lane.connectivity.on_intersection,
         world,
@@ -162,6 +378,7 @@ pub fn on_build_switch(lane: &SwitchLane, world: &mut World) {
     TransportUIID::global_broadcast(world).on_lane_constructed(
         lane.id.as_raw(),
         lane.construction.path.clone(),
+        lane.construction.elevation,
         true,
         false,
         world,
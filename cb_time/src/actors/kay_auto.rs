@@ -137,6 +137,72 @@ impl SleeperID {
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Sleeper_wake(pub Instant);
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct CalendarListenerID {
+    _raw_id: RawID
+}
+
+impl Copy for CalendarListenerID {}
+impl Clone for CalendarListenerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for CalendarListenerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "CalendarListenerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for CalendarListenerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for CalendarListenerID {
+    fn eq(&self, other: &CalendarListenerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for CalendarListenerID {}
+
+pub struct CalendarListenerRepresentative;
+
+impl ActorOrActorTrait for CalendarListenerRepresentative {
+    type ID = CalendarListenerID;
+}
+
+impl TypedID for CalendarListenerID {
+    type Target = CalendarListenerRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        CalendarListenerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + CalendarListener> TraitIDFrom<Act> for CalendarListenerID {}
+
+impl CalendarListenerID {
+    pub fn on_new_period(self, period: CalendarPeriod, date: Date, world: &mut World) {
+        world.send(self.as_raw(), MSG_CalendarListener_on_new_period(period, date));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<CalendarListenerRepresentative>();
+        system.register_trait_message::<MSG_CalendarListener_on_new_period>();
+    }
+
+    pub fn register_implementor<Act: Actor + CalendarListener>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, CalendarListenerRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_CalendarListener_on_new_period(period, date), instance, world| {
+                instance.on_new_period(period, date, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CalendarListener_on_new_period(pub CalendarPeriod, pub Date);
 
 impl Actor for Time {
     type ID = TimeID;
@@ -197,9 +263,37 @@ impl TimeID {
         world.send(self.as_raw(), MSG_Time_progress());
     }
     
+    pub fn notify_at_start_of(self, period: CalendarPeriod, listener: CalendarListenerID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_notify_at_start_of(period, listener));
+    }
+    
+    pub fn stop_notifying(self, listener: CalendarListenerID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_stop_notifying(listener));
+    }
+    
     pub fn wake_up_in(self, remaining_ticks: Ticks, sleeper_id: SleeperID, world: &mut World) {
         world.send(self.as_raw(), MSG_Time_wake_up_in(remaining_ticks, sleeper_id));
     }
+    
+    pub fn wake_up_at(self, instant: Instant, sleeper_id: SleeperID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_wake_up_at(instant, sleeper_id));
+    }
+    
+    pub fn wake_up_in_turn(self, remaining_ticks: Ticks, window: Ticks, sleeper_id: SleeperID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_wake_up_in_turn(remaining_ticks, window, sleeper_id));
+    }
+    
+    pub fn wake_up_every(self, interval: Duration, sleeper_id: SleeperID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_wake_up_every(interval, sleeper_id));
+    }
+    
+    pub fn stop_waking_up(self, sleeper_id: SleeperID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_stop_waking_up(sleeper_id));
+    }
+    
+    pub fn forget_sleepers(self, actor_types: CVec < u16 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_forget_sleepers(actor_types));
+    }
 }
 
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
@@ -207,7 +301,21 @@ struct MSG_Time_spawn(pub TimeID, );
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Time_progress();
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_notify_at_start_of(pub CalendarPeriod, pub CalendarListenerID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_stop_notifying(pub CalendarListenerID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Time_wake_up_in(pub Ticks, pub SleeperID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_wake_up_at(pub Instant, pub SleeperID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_wake_up_in_turn(pub Ticks, pub Ticks, pub SleeperID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_wake_up_every(pub Duration, pub SleeperID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_stop_waking_up(pub SleeperID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_forget_sleepers(pub CVec < u16 >);
 
 
 #[allow(unused_variables)]
@@ -215,6 +323,7 @@ struct MSG_Time_wake_up_in(pub Ticks, pub SleeperID);
 pub fn auto_setup(system: &mut ActorSystem) {
     TemporalID::register_trait(system);
     SleeperID::register_trait(system);
+    CalendarListenerID::register_trait(system);
     
     system.add_spawner::<Time, _, _>(
         |&MSG_Time_spawn(id, ), world| {
@@ -228,9 +337,51 @@ pub fn auto_setup(system: &mut ActorSystem) {
         }, false
     );
     
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_notify_at_start_of(period, listener), instance, world| {
+            instance.notify_at_start_of(period, listener, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_stop_notifying(listener), instance, world| {
+            instance.stop_notifying(listener, world); Fate::Live
+        }, false
+    );
+    
     system.add_handler::<Time, _, _>(
         |&MSG_Time_wake_up_in(remaining_ticks, sleeper_id), instance, world| {
             instance.wake_up_in(remaining_ticks, sleeper_id, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_wake_up_at(instant, sleeper_id), instance, world| {
+            instance.wake_up_at(instant, sleeper_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_wake_up_in_turn(remaining_ticks, window, sleeper_id), instance, world| {
+            instance.wake_up_in_turn(remaining_ticks, window, sleeper_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_wake_up_every(interval, sleeper_id), instance, world| {
+            instance.wake_up_every(interval, sleeper_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_stop_waking_up(sleeper_id), instance, world| {
+            instance.stop_waking_up(sleeper_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_forget_sleepers(ref actor_types), instance, world| {
+            instance.forget_sleepers(actor_types, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
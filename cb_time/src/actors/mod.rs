@@ -1,5 +1,7 @@
 use kay::{ActorSystem, World, TypedID};
 use compact::CVec;
+use calendar::{self, CalendarPeriod, Date};
+use load_spreading;
 
 pub mod ui;
 
@@ -10,30 +12,64 @@ pub trait Temporal {
     fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World);
 }
 
+// Anything that wants to be woken up at some point in the future, once or
+// regularly, instead of checking on every tick whether it's time yet.
+// Messages of any kind can't be kept around for later, so sleepers that wait
+// for different things remember themselves what they were waiting for
 pub trait Sleeper {
     fn wake(&mut self, current_instant: Instant, world: &mut World);
 }
 
+#[derive(Copy, Clone)]
+struct WakeUp {
+    at: Instant,
+    sleeper: SleeperID,
+    // for sleepers that want to be woken up regularly
+    every: Option<Ticks>,
+}
+
+pub trait CalendarListener {
+    fn on_new_period(&mut self, period: CalendarPeriod, date: Date, world: &mut World);
+}
+
 #[derive(Compact, Clone)]
 pub struct Time {
     id: TimeID,
     current_instant: Instant,
-    sleepers: CVec<(Instant, SleeperID)>,
+    // latest first, so the next ones can be taken off the end
+    sleepers: CVec<WakeUp>,
+    calendar_listeners: CVec<(CalendarPeriod, CalendarListenerID)>,
     speed: u16,
+    // keeps the speed to go on with afterwards
+    paused: bool,
+    // single ticks to do while paused, for debugging
+    requested_steps: u16,
 }
 
+pub const MAX_SPEED: u16 = 32;
+
 impl Time {
     pub fn spawn(id: TimeID, _: &mut World) -> Time {
         Time {
             id,
             current_instant: Instant::new(0),
             sleepers: CVec::new(),
+            calendar_listeners: CVec::new(),
             speed: 1,
+            paused: false,
+            requested_steps: 0,
         }
     }
 
     pub fn progress(&mut self, world: &mut World) {
-        for _ in 0..self.speed {
+        let n_ticks = if self.paused {
+            self.requested_steps
+        } else {
+            self.speed
+        };
+        self.requested_steps = 0;
+
+        for _ in 0..n_ticks {
             TemporalID::global_broadcast(world).tick(
                 1.0 / (TICKS_PER_SIM_SECOND as f32),
                 self.current_instant,
@@ -42,28 +78,128 @@ impl Time {
             while self
                 .sleepers
                 .last()
-                .map(|&(end, _)| end < self.current_instant)
+                .map(|wake_up| wake_up.at < self.current_instant)
                 .unwrap_or(false)
             {
-                let (_, sleeper) = self
+                let wake_up = self
                     .sleepers
                     .pop()
                     .expect("just checked that there are sleepers");
-                sleeper.wake(self.current_instant, world);
+                wake_up.sleeper.wake(self.current_instant, world);
+                if let Some(every) = wake_up.every {
+                    self.schedule(WakeUp {
+                        at: wake_up.at + every,
+                        ..wake_up
+                    });
+                }
             }
             self.current_instant += Ticks(1);
+            self.notify_calendar_listeners(world);
         }
     }
 
-    pub fn wake_up_in(&mut self, remaining_ticks: Ticks, sleeper_id: SleeperID, _: &mut World) {
-        let wake_up_at = self.current_instant + remaining_ticks;
+    fn notify_calendar_listeners(&self, world: &mut World) {
+        let day = calendar::day(self.current_instant);
+        let new_day = calendar::start_of_day(day) == self.current_instant;
+        if !new_day || self.calendar_listeners.is_empty() {
+            return;
+        }
+        let date = Date::from(self.current_instant);
+        for &(period, listener) in self.calendar_listeners.iter() {
+            if period.starts_on(day) {
+                listener.on_new_period(period, date, world);
+            }
+        }
+    }
+
+    pub fn notify_at_start_of(
+        &mut self,
+        period: CalendarPeriod,
+        listener: CalendarListenerID,
+        _: &mut World,
+    ) {
+        if !self.calendar_listeners.contains(&(period, listener)) {
+            self.calendar_listeners.push((period, listener));
+        }
+    }
+
+    pub fn stop_notifying(&mut self, listener: CalendarListenerID, _: &mut World) {
+        self.calendar_listeners
+            .retain(|&(_, other_listener)| other_listener != listener);
+    }
+
+    // Sleepers for the same instant are woken up in the order they asked,
+    // so a new one goes in front of all that are due at the same time
+    fn schedule(&mut self, wake_up: WakeUp) {
         let maybe_idx = self
             .sleepers
-            .binary_search_by_key(&wake_up_at.iticks(), |&(t, _)| -(t.iticks()));
+            .binary_search_by_key(&(-wake_up.at.iticks(), false), |other| {
+                (-other.at.iticks(), true)
+            });
         let insert_idx = match maybe_idx {
             Ok(idx) | Err(idx) => idx,
         };
-        self.sleepers.insert(insert_idx, (wake_up_at, sleeper_id));
+        self.sleepers.insert(insert_idx, wake_up);
+    }
+
+    pub fn wake_up_in(&mut self, remaining_ticks: Ticks, sleeper_id: SleeperID, _: &mut World) {
+        self.schedule(WakeUp {
+            at: self.current_instant + remaining_ticks,
+            sleeper: sleeper_id,
+            every: None,
+        });
+    }
+
+    // Instants that already passed are as good as the next tick
+    pub fn wake_up_at(&mut self, instant: Instant, sleeper_id: SleeperID, _: &mut World) {
+        self.schedule(WakeUp {
+            at: instant,
+            sleeper: sleeper_id,
+            every: None,
+        });
+    }
+
+    // Like `wake_up_in`, but a bit later so that sleepers of a kind that would
+    // all wake up at once are spread over the window, see `load_spreading`
+    pub fn wake_up_in_turn(
+        &mut self,
+        remaining_ticks: Ticks,
+        window: Ticks,
+        sleeper_id: SleeperID,
+        _: &mut World,
+    ) {
+        let earliest = self.current_instant + remaining_ticks;
+        self.schedule(WakeUp {
+            at: load_spreading::next_turn(sleeper_id.as_raw(), earliest, window),
+            sleeper: sleeper_id,
+            every: None,
+        });
+    }
+
+    // Starting one interval from now, until told to stop
+    pub fn wake_up_every(&mut self, interval: Duration, sleeper_id: SleeperID, _: &mut World) {
+        let every = Ticks::from(interval).max(Ticks(1));
+        self.schedule(WakeUp {
+            at: self.current_instant + every,
+            sleeper: sleeper_id,
+            every: Some(every),
+        });
+    }
+
+    // Forgets all wake ups of the sleeper, once or regular
+    pub fn stop_waking_up(&mut self, sleeper_id: SleeperID, _: &mut World) {
+        self.sleepers
+            .retain(|wake_up| wake_up.sleeper != sleeper_id);
+    }
+
+    // For when the actors of whole types are gone, like after loading a partial savegame
+    pub fn forget_sleepers(&mut self, actor_types: &CVec<u16>, _: &mut World) {
+        self.sleepers.retain(|wake_up| {
+            !actor_types.contains(&wake_up.sleeper.as_raw().type_id.as_u16())
+        });
+        self.calendar_listeners.retain(|&(_, listener)| {
+            !actor_types.contains(&listener.as_raw().type_id.as_u16())
+        });
     }
 }
 
@@ -50,8 +50,8 @@ impl TypedID for TimeUIID {
 impl<Act: Actor + TimeUI> TraitIDFrom<Act> for TimeUIID {}
 
 impl TimeUIID {
-    pub fn on_time_info(self, current_instant: :: units :: Instant, speed: u16, world: &mut World) {
-        world.send(self.as_raw(), MSG_TimeUI_on_time_info(current_instant, speed));
+    pub fn on_time_info(self, current_instant: :: units :: Instant, speed: u16, paused: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_TimeUI_on_time_info(current_instant, speed, paused));
     }
 
     pub fn register_trait(system: &mut ActorSystem) {
@@ -62,15 +62,15 @@ impl TimeUIID {
     pub fn register_implementor<Act: Actor + TimeUI>(system: &mut ActorSystem) {
         system.register_implementor::<Act, TimeUIRepresentative>();
         system.add_handler::<Act, _, _>(
-            |&MSG_TimeUI_on_time_info(current_instant, speed), instance, world| {
-                instance.on_time_info(current_instant, speed, world); Fate::Live
+            |&MSG_TimeUI_on_time_info(current_instant, speed, paused), instance, world| {
+                instance.on_time_info(current_instant, speed, paused, world); Fate::Live
             }, false
         );
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_TimeUI_on_time_info(pub :: units :: Instant, pub u16);
+struct MSG_TimeUI_on_time_info(pub :: units :: Instant, pub u16, pub bool);
 
 
 
@@ -79,15 +79,27 @@ impl TimeID {
         world.send(self.as_raw(), MSG_Time_get_info(requester));
     }
     
-    pub fn set_speed(self, speed: u16, world: &mut World) {
-        world.send(self.as_raw(), MSG_Time_set_speed(speed));
+    pub fn set_speed(self, origin: Origin, speed: u16, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_set_speed(origin, speed));
+    }
+    
+    pub fn set_paused(self, origin: Origin, paused: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_set_paused(origin, paused));
+    }
+    
+    pub fn step(self, origin: Origin, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_step(origin));
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Time_get_info(pub TimeUIID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Time_set_speed(pub u16);
+struct MSG_Time_set_speed(pub Origin, pub u16);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_set_paused(pub Origin, pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_step(pub Origin);
 
 
 #[allow(unused_variables)]
@@ -102,8 +114,20 @@ pub fn auto_setup(system: &mut ActorSystem) {
     );
     
     system.add_handler::<Time, _, _>(
-        |&MSG_Time_set_speed(speed), instance, world| {
-            instance.set_speed(speed, world); Fate::Live
+        |&MSG_Time_set_speed(origin, speed), instance, world| {
+            instance.set_speed(origin, speed, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_set_paused(origin, paused), instance, world| {
+            instance.set_paused(origin, paused, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_step(origin), instance, world| {
+            instance.step(origin, world); Fate::Live
         }, false
     );
 }
\ No newline at end of file
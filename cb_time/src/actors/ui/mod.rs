@@ -1,17 +1,57 @@
 use kay::World;
-use super::{Time, TimeID};
+use cb_util::session::{permits, Permission, Origin};
+use cb_util::log::info;
+use super::{Time, TimeID, MAX_SPEED};
+const LOG_T: &str = "Time";
 
 pub trait TimeUI {
-    fn on_time_info(&mut self, current_instant: ::units::Instant, speed: u16, _world: &mut World);
+    fn on_time_info(
+        &mut self,
+        current_instant: ::units::Instant,
+        speed: u16,
+        paused: bool,
+        _world: &mut World,
+    );
 }
 
 impl Time {
     pub fn get_info(&mut self, requester: TimeUIID, world: &mut World) {
-        requester.on_time_info(self.current_instant, self.speed, world);
+        requester.on_time_info(self.current_instant, self.speed, self.paused, world);
     }
 
-    pub fn set_speed(&mut self, speed: u16, _world: &mut World) {
-        self.speed = speed as u16;
+    // A speed of 0 pauses, like it always did, but keeps the previous speed
+    pub fn set_speed(&mut self, origin: Origin, speed: u16, world: &mut World) {
+        if !permits(origin, Permission::Time, self.id, world) {
+            return;
+        }
+
+        if speed == 0 {
+            self.paused = true;
+        } else {
+            self.speed = speed.min(MAX_SPEED);
+        }
+    }
+
+    pub fn set_paused(&mut self, origin: Origin, paused: bool, world: &mut World) {
+        if !permits(origin, Permission::Time, self.id, world) {
+            return;
+        }
+
+        self.paused = paused;
+    }
+
+    // Does a single tick with the next turn of the server. Only while paused,
+    // a running simulation does its ticks anyway
+    pub fn step(&mut self, origin: Origin, world: &mut World) {
+        if !permits(origin, Permission::Time, self.id, world) {
+            return;
+        }
+
+        if self.paused {
+            self.requested_steps = self.requested_steps.saturating_add(1);
+        } else {
+            info(LOG_T, "Can only step while paused, ignored", self.id, world);
+        }
     }
 }
 
@@ -0,0 +1,253 @@
+// The calendar of the city. Years are as compressed as the lives of people,
+// otherwise nobody would ever get to see a generation grow up or a farm bring
+// in a couple of harvests, so each month passes in a single day. Weeks aren't
+// compressed, so that schedules can differ between workdays and weekends.
+// The city is founded on a Monday morning at the beginning of spring, in year 1
+use units::{Instant, TICKS_PER_SIM_MINUTE, BEGINNING_TIME_OF_DAY};
+
+pub const DAYS_PER_YEAR: u32 = 12;
+pub const MONTHS_PER_YEAR: u32 = 12;
+pub const DAYS_PER_MONTH: u32 = DAYS_PER_YEAR / MONTHS_PER_YEAR;
+pub const DAYS_PER_WEEK: u32 = 7;
+pub const TICKS_PER_DAY: usize = 24 * 60 * TICKS_PER_SIM_MINUTE as usize;
+
+// the first month of the city
+const FOUNDING_MONTH: u32 = 2;
+
+// Days since the city was founded, which start at midnight, like `TimeOfDay`
+pub fn day(instant: Instant) -> u32 {
+    ((instant.ticks() + BEGINNING_TIME_OF_DAY * 60 * TICKS_PER_SIM_MINUTE as usize)
+        / TICKS_PER_DAY) as u32
+}
+
+// Whole years since the city was founded
+pub fn years_since_founding(instant: Instant) -> u32 {
+    day(instant) / DAYS_PER_YEAR
+}
+
+pub fn day_of_year(instant: Instant) -> u32 {
+    day(instant) % DAYS_PER_YEAR
+}
+
+// The first instant of the given day since founding. The founding day itself
+// started before the city did, so it's clamped to the founding instant
+pub fn start_of_day(day: u32) -> Instant {
+    let ticks = day as usize * TICKS_PER_DAY;
+    let offset = BEGINNING_TIME_OF_DAY * 60 * TICKS_PER_SIM_MINUTE as usize;
+    Instant::new(ticks.saturating_sub(offset))
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+const WEEKDAYS: [Weekday; DAYS_PER_WEEK as usize] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+];
+
+impl Weekday {
+    pub fn of(instant: Instant) -> Weekday {
+        WEEKDAYS[(day(instant) % DAYS_PER_WEEK) as usize]
+    }
+
+    pub fn is_weekend(self) -> bool {
+        self == Weekday::Saturday || self == Weekday::Sunday
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Weekday::Monday => "Monday",
+            Weekday::Tuesday => "Tuesday",
+            Weekday::Wednesday => "Wednesday",
+            Weekday::Thursday => "Thursday",
+            Weekday::Friday => "Friday",
+            Weekday::Saturday => "Saturday",
+            Weekday::Sunday => "Sunday",
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum Month {
+    January,
+    February,
+    March,
+    April,
+    May,
+    June,
+    July,
+    August,
+    September,
+    October,
+    November,
+    December,
+}
+
+const MONTHS: [Month; MONTHS_PER_YEAR as usize] = [
+    Month::January,
+    Month::February,
+    Month::March,
+    Month::April,
+    Month::May,
+    Month::June,
+    Month::July,
+    Month::August,
+    Month::September,
+    Month::October,
+    Month::November,
+    Month::December,
+];
+
+impl Month {
+    pub fn of(instant: Instant) -> Month {
+        MONTHS[((day_of_year(instant) / DAYS_PER_MONTH + FOUNDING_MONTH) % MONTHS_PER_YEAR)
+            as usize]
+    }
+
+    pub fn season(self) -> Season {
+        match self {
+            Month::March | Month::April | Month::May => Season::Spring,
+            Month::June | Month::July | Month::August => Season::Summer,
+            Month::September | Month::October | Month::November => Season::Autumn,
+            Month::December | Month::January | Month::February => Season::Winter,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Month::January => "January",
+            Month::February => "February",
+            Month::March => "March",
+            Month::April => "April",
+            Month::May => "May",
+            Month::June => "June",
+            Month::July => "July",
+            Month::August => "August",
+            Month::September => "September",
+            Month::October => "October",
+            Month::November => "November",
+            Month::December => "December",
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub fn of(instant: Instant) -> Season {
+        Month::of(instant).season()
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+            Season::Winter => "Winter",
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Date {
+    // counting from 1, like people do
+    pub year: u32,
+    pub month: Month,
+    pub day_of_month: u32,
+    pub weekday: Weekday,
+}
+
+impl Date {
+    pub fn season(self) -> Season {
+        self.month.season()
+    }
+}
+
+impl From<Instant> for Date {
+    fn from(instant: Instant) -> Date {
+        // years start in January, even though the city was founded in spring
+        let months = day(instant) / DAYS_PER_MONTH + FOUNDING_MONTH;
+        Date {
+            year: months / MONTHS_PER_YEAR + 1,
+            month: Month::of(instant),
+            day_of_month: day(instant) % DAYS_PER_MONTH + 1,
+            weekday: Weekday::of(instant),
+        }
+    }
+}
+
+impl ::std::fmt::Display for Date {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        if DAYS_PER_MONTH == 1 {
+            write!(
+                f,
+                "{}, {} of year {}",
+                self.weekday.name(),
+                self.month.name(),
+                self.year
+            )
+        } else {
+            write!(
+                f,
+                "{}, {} {} of year {}",
+                self.weekday.name(),
+                self.day_of_month,
+                self.month.name(),
+                self.year
+            )
+        }
+    }
+}
+
+// What listeners of the calendar can ask to be notified at the start of
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum CalendarPeriod {
+    Day,
+    Week,
+    Month,
+    Season,
+    Year,
+}
+
+impl CalendarPeriod {
+    // Whether one of these starts with the given day since founding
+    pub fn starts_on(self, day: u32) -> bool {
+        let months = day / DAYS_PER_MONTH + FOUNDING_MONTH;
+        let starts_month = day % DAYS_PER_MONTH == 0;
+        match self {
+            CalendarPeriod::Day => true,
+            CalendarPeriod::Week => day % DAYS_PER_WEEK == 0,
+            CalendarPeriod::Month => starts_month,
+            // seasons start in March, June, September and December
+            CalendarPeriod::Season => starts_month && months % 3 == 2,
+            CalendarPeriod::Year => starts_month && months % MONTHS_PER_YEAR == 0,
+        }
+    }
+
+    pub fn start_of_next(self, instant: Instant) -> Instant {
+        let mut next_day = day(instant) + 1;
+        while !self.starts_on(next_day) {
+            next_day += 1;
+        }
+        start_of_day(next_day)
+    }
+}
@@ -2,6 +2,7 @@
 #![feature(custom_inner_attributes)]
 #![allow(clippy::new_without_default)]
 extern crate kay;
+extern crate cb_util;
 extern crate compact;
 #[macro_use]
 extern crate compact_macros;
@@ -9,4 +10,6 @@ extern crate compact_macros;
 extern crate serde_derive;
 
 pub mod units;
+pub mod calendar;
+pub mod load_spreading;
 pub mod actors;
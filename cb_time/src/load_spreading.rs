@@ -0,0 +1,84 @@
+// Periodic work of actors that exist in large numbers, like households letting
+// their needs decay or deciding what to do next, would stall the simulation if
+// all of them did it in the same tick, for example because they were all
+// spawned by loading a city. Instead, each actor gets a phase in the interval
+// of the work, given by its ID so that it stays the same, and only does the
+// work in its own ticks. The work of the whole kind of actor is then spread
+// evenly over the interval, while each actor still does it once per interval
+use kay::RawID;
+use units::{Instant, Ticks};
+
+fn phase(id: RawID, every: usize) -> usize {
+    id.instance_id as usize % every
+}
+
+// Whether it's the actor's tick to do work that is done every so many ticks
+pub fn is_turn(id: RawID, current_instant: Instant, every: Ticks) -> bool {
+    let every = (every.0 as usize).max(1);
+    current_instant.ticks() % every == phase(id, every)
+}
+
+// The first of the actor's ticks at or after the earliest instant
+pub fn next_turn(id: RawID, earliest: Instant, every: Ticks) -> Instant {
+    let every = (every.0 as usize).max(1);
+    let wait = (phase(id, every) + every - earliest.ticks() % every) % every;
+    earliest + Ticks(wait as u32)
+}
+
+// Work that actors start at no particular phase, like households evaluating
+// offers when they decide what to do next, is capped per tick instead:
+// they draw from the tick's budget until it runs out, and whoever finds
+// it empty defers the work to one of its next turns
+pub struct TickBudget {
+    per_tick: u32,
+    tick: Option<usize>,
+    spent: u32,
+}
+
+impl TickBudget {
+    pub fn new(per_tick: u32) -> TickBudget {
+        TickBudget {
+            per_tick,
+            tick: None,
+            spent: 0,
+        }
+    }
+
+    // Whether one more unit of work fits into the current tick, counts it if so
+    pub fn try_spend(&mut self, current_instant: Instant) -> bool {
+        if self.tick != Some(current_instant.ticks()) {
+            self.tick = Some(current_instant.ticks());
+            self.spent = 0;
+        }
+        if self.spent < self.per_tick {
+            self.spent += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TickBudget;
+    use units::Instant;
+
+    #[test]
+    fn budget_runs_out_within_a_tick() {
+        let mut budget = TickBudget::new(2);
+        let now = Instant::new(10);
+        assert!(budget.try_spend(now));
+        assert!(budget.try_spend(now));
+        assert!(!budget.try_spend(now));
+    }
+
+    #[test]
+    fn budget_is_refilled_each_tick() {
+        let mut budget = TickBudget::new(1);
+        assert!(budget.try_spend(Instant::new(10)));
+        assert!(!budget.try_spend(Instant::new(10)));
+        assert!(budget.try_spend(Instant::new(11)));
+        assert!(!budget.try_spend(Instant::new(11)));
+    }
+}
@@ -57,6 +57,21 @@ impl ::std::ops::AddAssign for Duration {
     }
 }
 
+// durations don't go negative, taking away more than there is leaves nothing
+impl ::std::ops::Sub for Duration {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Duration(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl ::std::ops::SubAssign for Duration {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 = self.0.saturating_sub(rhs.0)
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
 pub struct Instant(u32);
 
@@ -91,14 +106,15 @@ impl<D: Into<Ticks>> ::std::ops::AddAssign<D> for Instant {
 impl<D: Into<Ticks>> ::std::ops::Sub<D> for Instant {
     type Output = Self;
 
+    // there is nothing before the start of the simulation
     fn sub(self, rhs: D) -> Self {
-        Instant(self.0 - rhs.into().0)
+        Instant(self.0.saturating_sub(rhs.into().0))
     }
 }
 
 impl<D: Into<Ticks>> ::std::ops::SubAssign<D> for Instant {
     fn sub_assign(&mut self, rhs: D) {
-        self.0 -= rhs.into().0
+        self.0 = self.0.saturating_sub(rhs.into().0)
     }
 }
 
@@ -107,7 +123,7 @@ pub struct TimeOfDay {
     minutes_of_day: u16,
 }
 
-const BEGINNING_TIME_OF_DAY: usize = 7;
+pub(crate) const BEGINNING_TIME_OF_DAY: usize = 7;
 const MINUTES_PER_DAY: usize = 60 * 24;
 
 impl TimeOfDay {
@@ -124,20 +140,34 @@ impl TimeOfDay {
         )
     }
 
-    pub fn earlier_by(self, delta: Duration) -> Self {
+    pub fn minutes_of_day(self) -> usize {
+        self.minutes_of_day as usize
+    }
+
+    fn from_minutes_of_day(minutes: usize) -> Self {
         TimeOfDay {
-            minutes_of_day: ((((self.minutes_of_day as isize - delta.as_minutes() as isize)
-                % MINUTES_PER_DAY as isize)
-                + MINUTES_PER_DAY as isize) as usize
-                % MINUTES_PER_DAY) as u16,
+            minutes_of_day: (minutes % MINUTES_PER_DAY) as u16,
         }
     }
 
+    // Times of day wrap around midnight, so 1:00 earlier by two hours is 23:00
+    pub fn earlier_by(self, delta: Duration) -> Self {
+        let delta_minutes = (delta.0 / 60) as usize % MINUTES_PER_DAY;
+        Self::from_minutes_of_day(self.minutes_of_day() + MINUTES_PER_DAY - delta_minutes)
+    }
+
     pub fn later_by(self, delta: Duration) -> Self {
-        TimeOfDay {
-            minutes_of_day: ((self.minutes_of_day as usize + delta.as_minutes() as usize)
-                % MINUTES_PER_DAY) as u16,
-        }
+        let delta_minutes = (delta.0 / 60) as usize % MINUTES_PER_DAY;
+        Self::from_minutes_of_day(self.minutes_of_day() + delta_minutes)
+    }
+
+    // How long from this time until the given one comes around,
+    // which is the next day if it is earlier than this one, and
+    // right away, not a day later, if it is the same
+    pub fn until(self, later: TimeOfDay) -> Duration {
+        Duration::from_minutes(
+            (later.minutes_of_day() + MINUTES_PER_DAY - self.minutes_of_day()) % MINUTES_PER_DAY,
+        )
     }
 }
 
@@ -168,16 +198,14 @@ impl<D: Into<Duration>> ::std::ops::Add<D> for TimeOfDay {
     type Output = Self;
 
     fn add(self, rhs: D) -> Self {
-        TimeOfDay {
-            minutes_of_day: self.minutes_of_day + (rhs.into().0 / 60) as u16,
-        }
+        self.later_by(rhs.into())
     }
 }
 
 impl<D: Into<Duration>> ::std::ops::AddAssign<D> for TimeOfDay {
     #[allow(clippy::suspicious_op_assign_impl)]
     fn add_assign(&mut self, rhs: D) {
-        self.minutes_of_day += (rhs.into().0 / 60) as u16
+        *self = self.later_by(rhs.into())
     }
 }
 
@@ -185,19 +213,20 @@ impl<D: Into<Duration>> ::std::ops::Sub<D> for TimeOfDay {
     type Output = Self;
 
     fn sub(self, rhs: D) -> Self {
-        TimeOfDay {
-            minutes_of_day: self.minutes_of_day - (rhs.into().0 / 60) as u16,
-        }
+        self.earlier_by(rhs.into())
     }
 }
 
 impl<D: Into<Duration>> ::std::ops::SubAssign<D> for TimeOfDay {
     #[allow(clippy::suspicious_op_assign_impl)]
     fn sub_assign(&mut self, rhs: D) {
-        self.minutes_of_day -= (rhs.into().0 / 60) as u16
+        *self = self.earlier_by(rhs.into())
     }
 }
 
+// From the start up to the end, going on overnight if the end is earlier.
+// A range that ends when it starts is empty, it contains no time at all.
+// Ranges that only touch, one ending when the other starts, don't overlap
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 pub struct TimeOfDayRange {
     pub start: TimeOfDay,
@@ -212,9 +241,16 @@ impl TimeOfDayRange {
         }
     }
 
+    pub fn is_empty(self) -> bool {
+        self.start == self.end
+    }
+
+    // Both the start and the end minute belong to a range
     pub fn contains<T: Into<TimeOfDay>>(self, time: T) -> bool {
         let time = time.into();
-        if self.start <= self.end {
+        if self.is_empty() {
+            false
+        } else if self.start < self.end {
             self.start <= time && time <= self.end
         } else {
             self.start <= time || time <= self.end
@@ -235,11 +271,170 @@ impl TimeOfDayRange {
         }
     }
 
+    // Ranges that end earlier than they start go on overnight, like 22:00 to 2:00
+    pub fn wraps_midnight(self) -> bool {
+        self.start > self.end
+    }
+
+    pub fn duration(self) -> Duration {
+        self.start.until(self.end)
+    }
+
+    // The range as minutes of the day, from the start up to but not including
+    // the end, split in two at midnight if it wraps around it. The second
+    // interval is empty otherwise, both are for an empty range
+    pub fn intervals(self) -> [(usize, usize); 2] {
+        let (start, end) = (self.start.minutes_of_day(), self.end.minutes_of_day());
+        if start <= end {
+            [(start, end), (0, 0)]
+        } else {
+            [(start, MINUTES_PER_DAY), (0, end)]
+        }
+    }
+
+    pub fn overlaps(self, other: TimeOfDayRange) -> bool {
+        self.pieces_in_common(other).next().is_some()
+    }
+
+    // Where each interval of this range meets each interval of the other one,
+    // as minutes of the day. Stretches running into midnight continue after it
+    fn pieces_in_common(
+        self,
+        other: TimeOfDayRange,
+    ) -> impl Iterator<Item = (usize, usize)> + Clone {
+        let (intervals, other_intervals) = (self.intervals(), other.intervals());
+        (0..4).filter_map(move |i| {
+            let (start, end) = intervals[i / 2];
+            let (other_start, other_end) = other_intervals[i % 2];
+            let (piece_start, piece_end) = (start.max(other_start), end.min(other_end));
+            if piece_start < piece_end {
+                Some((piece_start, piece_end))
+            } else {
+                None
+            }
+        })
+    }
+
+    // The first stretch of this range, counting from its start, that also
+    // lies within the other one. Two overnight ranges can have two separate
+    // stretches in common, the later one is left out
+    pub fn intersection(self, other: TimeOfDayRange) -> Option<TimeOfDayRange> {
+        let pieces = self.pieces_in_common(other);
+
+        let self_start = self.start.minutes_of_day();
+        let (start, end) = pieces.clone().min_by_key(|&(start, _)| {
+            (start + MINUTES_PER_DAY - self_start) % MINUTES_PER_DAY
+        })?;
+
+        // a stretch running into midnight continues after it
+        let end = if end == MINUTES_PER_DAY {
+            pieces
+                .filter(|&(next_start, _)| next_start == 0)
+                .map(|(_, next_end)| next_end)
+                .next()
+                .unwrap_or(0)
+        } else {
+            end
+        };
+
+        Some(TimeOfDayRange {
+            start: TimeOfDay::from_minutes_of_day(start),
+            end: TimeOfDay::from_minutes_of_day(end),
+        })
+    }
+
     pub fn end_after_on_same_day(self, time: TimeOfDay) -> bool {
-        if self.end > self.start {
+        if self.is_empty() {
+            false
+        } else if self.end > self.start {
             time < self.end
         } else {
             time > self.start || time < self.end
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Duration, TimeOfDay, TimeOfDayRange};
+
+    fn hours(range: TimeOfDayRange) -> ((usize, usize), (usize, usize)) {
+        (range.start.hours_minutes(), range.end.hours_minutes())
+    }
+
+    #[test]
+    fn ranges_crossing_midnight() {
+        let night = TimeOfDayRange::new(22, 0, 2, 0);
+        assert!(night.wraps_midnight());
+        assert_eq!(night.duration(), Duration::from_hours(4));
+        assert_eq!(night.intervals(), [(22 * 60, 24 * 60), (0, 2 * 60)]);
+        assert!(night.contains(TimeOfDay::new(23, 30)));
+        assert!(night.contains(TimeOfDay::new(1, 0)));
+        assert!(!night.contains(TimeOfDay::new(12, 0)));
+
+        let early = TimeOfDayRange::new(1, 0, 6, 0);
+        assert!(night.overlaps(early));
+        assert_eq!(night.intersection(early).map(hours), Some(((1, 0), (2, 0))));
+
+        // the stretch in common continues past midnight
+        let late = TimeOfDayRange::new(23, 0, 1, 0);
+        assert_eq!(night.intersection(late).map(hours), Some(((23, 0), (1, 0))));
+
+        // two separate stretches in common, the first one counting from 10:00
+        let long_night = TimeOfDayRange::new(10, 0, 5, 0);
+        let evening_to_morning = TimeOfDayRange::new(20, 0, 11, 0);
+        assert_eq!(
+            long_night.intersection(evening_to_morning).map(hours),
+            Some(((10, 0), (11, 0)))
+        );
+    }
+
+    #[test]
+    fn touching_ranges_dont_overlap() {
+        let morning = TimeOfDayRange::new(8, 0, 12, 0);
+        let afternoon = TimeOfDayRange::new(12, 0, 16, 0);
+        assert!(!morning.overlaps(afternoon));
+        assert!(morning.intersection(afternoon).is_none());
+
+        let night = TimeOfDayRange::new(22, 0, 8, 0);
+        assert!(!night.overlaps(morning));
+        assert!(night.intersection(morning).is_none());
+    }
+
+    #[test]
+    fn contained_ranges() {
+        let day = TimeOfDayRange::new(6, 0, 22, 0);
+        let lunch = TimeOfDayRange::new(12, 0, 13, 0);
+        assert!(day.overlaps(lunch) && lunch.overlaps(day));
+        assert_eq!(day.intersection(lunch).map(hours), Some(((12, 0), (13, 0))));
+        assert_eq!(lunch.intersection(day).map(hours), Some(((12, 0), (13, 0))));
+
+        let night = TimeOfDayRange::new(20, 0, 4, 0);
+        let midnight_snack = TimeOfDayRange::new(23, 30, 0, 30);
+        assert_eq!(
+            night.intersection(midnight_snack).map(hours),
+            Some(((23, 30), (0, 30)))
+        );
+    }
+
+    #[test]
+    fn ranges_that_end_when_they_start_are_empty() {
+        let empty = TimeOfDayRange::new(9, 0, 9, 0);
+        assert!(empty.is_empty());
+        assert!(!empty.wraps_midnight());
+        assert_eq!(empty.duration(), Duration(0));
+        assert!(!empty.contains(TimeOfDay::new(9, 0)));
+        assert!(!empty.end_after_on_same_day(TimeOfDay::new(8, 0)));
+
+        let day = TimeOfDayRange::new(6, 0, 22, 0);
+        assert!(!empty.overlaps(day) && !day.overlaps(empty));
+        assert!(empty.intersection(day).is_none());
+        assert!(day.intersection(empty).is_none());
+
+        assert_eq!(TimeOfDay::new(9, 0).until(TimeOfDay::new(9, 0)), Duration(0));
+        assert_eq!(
+            TimeOfDay::new(22, 0).until(TimeOfDay::new(2, 0)),
+            Duration::from_hours(4)
+        );
+    }
+}
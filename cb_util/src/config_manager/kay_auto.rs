@@ -157,8 +157,8 @@ impl<C: Config> ConfigManagerID<C> {
         world.send(self.as_raw(), MSG_ConfigManager_request_current::<C>(requester));
     }
     
-    pub fn update_entry(self, name: Name, maybe_value: COption < C >, world: &mut World) {
-        world.send(self.as_raw(), MSG_ConfigManager_update_entry::<C>(name, maybe_value));
+    pub fn update_entry(self, origin: Origin, name: Name, maybe_value: COption < C >, world: &mut World) {
+        world.send(self.as_raw(), MSG_ConfigManager_update_entry::<C>(origin, name, maybe_value));
     }
 }
 
@@ -167,7 +167,7 @@ struct MSG_ConfigManager_spawn<C: Config>(pub ConfigManagerID<C>, pub CHashMap <
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_ConfigManager_request_current<C: Config>(pub ConfigUserID < C >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_ConfigManager_update_entry<C: Config>(pub Name, pub COption < C >);
+struct MSG_ConfigManager_update_entry<C: Config>(pub Origin, pub Name, pub COption < C >);
 
 
 #[allow(unused_variables)]
@@ -188,8 +188,8 @@ pub fn auto_setup<C: Config>(system: &mut ActorSystem) {
     );
     
     system.add_handler::<ConfigManager<C>, _, _>(
-        |&MSG_ConfigManager_update_entry::<C>(name, ref maybe_value), instance, world| {
-            instance.update_entry(name, maybe_value, world); Fate::Live
+        |&MSG_ConfigManager_update_entry::<C>(origin, name, ref maybe_value), instance, world| {
+            instance.update_entry(origin, name, maybe_value, world); Fate::Live
         }, false
     );
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use kay::{World, Actor, TypedID};
+use session::{permits, Permission, Origin};
 use compact::{CHashMap, Compact, COption};
 use arrayvec::ArrayString;
 
@@ -29,7 +30,17 @@ impl<C: Config> ConfigManager<C> {
         }
     }
 
-    pub fn update_entry(&mut self, name: Name, maybe_value: &COption<C>, world: &mut World) {
+    pub fn update_entry(
+        &mut self,
+        origin: Origin,
+        name: Name,
+        maybe_value: &COption<C>,
+        world: &mut World,
+    ) {
+        if !permits(origin, Permission::Policies, self.id, world) {
+            return;
+        }
+
         if let COption(Some(ref value)) = *maybe_value {
             ConfigUserID::<C>::global_broadcast(world).on_config_change(
                 name,
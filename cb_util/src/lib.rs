@@ -16,3 +16,4 @@ pub mod async_counter;
 pub mod random;
 pub mod config_manager;
 pub mod log;
+pub mod session;
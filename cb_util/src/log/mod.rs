@@ -18,6 +18,22 @@ pub struct Entry {
     level: LogLevel,
 }
 
+impl Entry {
+    pub fn level(&self) -> LogLevel {
+        self.level
+    }
+
+    // `text` as received together with the entry, starting at `text_start`
+    pub fn topic<'a>(&self, text: &'a str, text_start: u32) -> &'a str {
+        &text[(self.topic_start - text_start) as usize..(self.message_start - text_start) as usize]
+    }
+
+    pub fn message<'a>(&self, text: &'a str, text_start: u32) -> &'a str {
+        let message_start = (self.message_start - text_start) as usize;
+        &text[message_start..message_start + self.message_len as usize]
+    }
+}
+
 #[derive(Compact, Clone)]
 pub struct Log {
     id: LogID,
@@ -73,7 +89,8 @@ impl Log {
         recipient: LogRecipientID,
         world: &mut World,
     ) {
-        let effective_last = (last_known as usize).max(self.entries.len() - max_diff as usize);
+        let effective_last =
+            (last_known as usize).max(self.entries.len().saturating_sub(max_diff as usize));
         if effective_last < self.entries.len() {
             let entries = self.entries[effective_last..].to_vec().into();
             let effective_text_start = self.entries[effective_last].topic_start as usize;
@@ -0,0 +1,182 @@
+// Roles of the machines taking part in a networked session and which kinds of
+// changes each of them may make. Kay doesn't tell a handler which connection
+// a message arrived on, so each client machine gets a key of its own from the
+// server, over the connection it loads its page with, and sends it along with
+// every change. Only the server knows all keys, changes that claim to come from
+// a machine without carrying its key are rejected before its role is looked at
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use kay::{World, TypedID};
+use log::warn;
+const LOG_T: &str = "Session";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Role {
+    Mayor,
+    Planner,
+    Observer,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Permission {
+    // drafting, changing and implementing plans
+    Planning,
+    // changing config entries that the simulation follows
+    Policies,
+    // spending from the city treasury
+    Budget,
+    // pausing, stepping and changing the speed of the simulation for everyone
+    Time,
+}
+
+impl Role {
+    pub fn allows(self, permission: Permission) -> bool {
+        match (self, permission) {
+            (Role::Mayor, _) => true,
+            (Role::Planner, Permission::Planning) => true,
+            _ => false,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::Mayor => "mayor",
+            Role::Planner => "planner",
+            Role::Observer => "observer",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Role> {
+        match name.trim().to_lowercase().as_str() {
+            "mayor" => Some(Role::Mayor),
+            "planner" => Some(Role::Planner),
+            "observer" => Some(Role::Observer),
+            _ => None,
+        }
+    }
+}
+
+// the server runs the simulation and can always do everything
+pub const SERVER_MACHINE: u8 = 0;
+
+// Who a change comes from
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Origin {
+    pub machine: MachineID,
+    pub key: u64,
+}
+
+// Unpredictable for anyone who doesn't know the random state of this process
+pub fn new_key() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    hasher.finish()
+}
+
+struct Session {
+    roles: HashMap<u8, Role>,
+    keys: HashMap<u8, u64>,
+}
+
+thread_local! {
+    // only filled in on the server, the only place where roles are checked.
+    // The server's own key never leaves it, it is only used by actors like bots
+    static SESSION: RefCell<Session> = RefCell::new(Session {
+        roles: HashMap::new(),
+        keys: Some((SERVER_MACHINE, new_key())).into_iter().collect(),
+    });
+}
+
+// Roles and keys by client machine number. Has to be called on the thread
+// that processes the actor system's messages
+pub fn set_session(roles: HashMap<u8, Role>, client_keys: HashMap<u8, u64>) {
+    SESSION.with(|session| {
+        let mut session = session.borrow_mut();
+        session.roles = roles;
+        session.keys.retain(|&machine, _| machine == SERVER_MACHINE);
+        session.keys.extend(
+            client_keys
+                .into_iter()
+                .filter(|&(machine, _)| machine != SERVER_MACHINE),
+        );
+    });
+}
+
+// Simulations that have to behave exactly alike, like the two instances of the
+// determinism audit, have to send the same messages and so use the same key
+pub fn set_server_key(key: u64) {
+    SESSION.with(|session| {
+        session.borrow_mut().keys.insert(SERVER_MACHINE, key);
+    });
+}
+
+// For changes made by the server itself
+pub fn server_origin() -> Origin {
+    SESSION.with(|session| Origin {
+        machine: MachineID(SERVER_MACHINE),
+        key: session.borrow().keys[&SERVER_MACHINE],
+    })
+}
+
+pub fn is_authentic(origin: Origin) -> bool {
+    SESSION.with(|session| session.borrow().keys.get(&origin.machine.0) == Some(&origin.key))
+}
+
+// Machines without an assigned role can only watch
+pub fn role_of(machine: MachineID) -> Role {
+    if machine.0 == SERVER_MACHINE {
+        Role::Mayor
+    } else {
+        SESSION.with(|session| {
+            session
+                .borrow()
+                .roles
+                .get(&machine.0)
+                .cloned()
+                .unwrap_or(Role::Observer)
+        })
+    }
+}
+
+// Whether a change coming from `origin` may be made, warning about it if not
+pub fn permits<I: TypedID>(
+    origin: Origin,
+    permission: Permission,
+    checked_by: I,
+    world: &mut World,
+) -> bool {
+    if !is_authentic(origin) {
+        warn(
+            LOG_T,
+            format!(
+                "{:?} changes claiming to come from machine {} without its key, ignored",
+                permission, origin.machine.0
+            ),
+            checked_by,
+            world,
+        );
+        return false;
+    }
+
+    let role = role_of(origin.machine);
+    let permitted = role.allows(permission);
+
+    if !permitted {
+        warn(
+            LOG_T,
+            format!(
+                "Machine {} is {} and may not make {:?} changes, ignored",
+                origin.machine.0,
+                role.name(),
+                permission
+            ),
+            checked_by,
+            world,
+        );
+    }
+
+    permitted
+}